@@ -100,10 +100,18 @@ struct LinkSetArgs {
     #[arg(long = "bcast-flood")]
     bcast_flood: Option<OnOff>,
 
-    /// Neighbour suppression (on/off)
+    /// Neighbour (ARP/ND) suppression (on/off)
     #[arg(long = "neigh-suppress")]
     neigh_suppress: Option<OnOff>,
 
+    /// Per-VLAN neighbour suppression (on/off)
+    #[arg(long = "neigh-vlan-suppress")]
+    neigh_vlan_suppress: Option<OnOff>,
+
+    /// VLAN-to-tunnel (VNI) mapping (on/off)
+    #[arg(long = "vlan-tunnel")]
+    vlan_tunnel: Option<OnOff>,
+
     /// Port isolation (on/off)
     #[arg(long)]
     isolated: Option<OnOff>,
@@ -161,6 +169,12 @@ impl LinkCmd {
                 if let Some(v) = args.neigh_suppress {
                     cfg.neigh_suppress = Some(v.0);
                 }
+                if let Some(v) = args.neigh_vlan_suppress {
+                    cfg.neigh_vlan_suppress = Some(v.0);
+                }
+                if let Some(v) = args.vlan_tunnel {
+                    cfg.vlan_tunnel = Some(v.0);
+                }
                 if let Some(v) = args.isolated {
                     cfg.isolated = Some(v.0);
                 }
@@ -9,11 +9,9 @@
 //! library's event layer does not model `RTM_*MDB` notifications. When it
 //! does, this command gains an `mdb` object filter.
 
-use std::collections::HashMap;
-
 use clap::Args;
 use nlink::{
-    netlink::{Connection, Result, Route, RtnetlinkGroup, fdb::FdbEntry},
+    netlink::{Connection, IfIndexResolver, Result, Route, RtnetlinkGroup, fdb::FdbEntry},
     output::{OutputFormat, OutputOptions},
 };
 use tokio_stream::StreamExt;
@@ -32,17 +30,17 @@ impl MonitorCmd {
         format: OutputFormat,
         opts: &OutputOptions,
     ) -> Result<()> {
-        conn.subscribe(&[RtnetlinkGroup::Neigh])?;
+        conn.subscribe(&[RtnetlinkGroup::Neigh, RtnetlinkGroup::Link])?;
 
-        // Resolve ifindex -> name once up front. New interfaces that appear
-        // mid-stream fall back to `if<N>`.
-        let names = conn.get_interface_names().await.unwrap_or_default();
+        // Resolve ifindex -> name from a cache that follows link events, so
+        // ports created or renamed mid-stream print by name too.
+        let names = conn.interface_resolver().await.unwrap_or_default();
 
         if format == OutputFormat::Text {
             eprintln!("Monitoring bridge FDB events (Ctrl+C to stop)...");
         }
 
-        let mut events = conn.events().await;
+        let mut events = std::pin::pin!(names.track(conn.events().await));
         while let Some(result) = events.next().await {
             let event = result?;
             let action = event.action(); // "new" / "del"
@@ -59,14 +57,11 @@ impl MonitorCmd {
     }
 }
 
-fn dev_name(names: &HashMap<u32, String>, ifindex: u32) -> String {
-    names
-        .get(&ifindex)
-        .cloned()
-        .unwrap_or_else(|| format!("if{ifindex}"))
+fn dev_name(names: &IfIndexResolver, ifindex: u32) -> String {
+    names.name_or_index(ifindex)
 }
 
-fn print_text(fdb: &FdbEntry, action: &str, names: &HashMap<u32, String>, timestamp: bool) {
+fn print_text(fdb: &FdbEntry, action: &str, names: &IfIndexResolver, timestamp: bool) {
     // `new`/`del` -> the iproute2-style `<nothing>`/`Deleted ` prefix.
     let prefix = if action == "del" { "Deleted " } else { "" };
 
@@ -107,7 +102,7 @@ fn print_text(fdb: &FdbEntry, action: &str, names: &HashMap<u32, String>, timest
     }
 }
 
-fn print_json(fdb: &FdbEntry, action: &str, names: &HashMap<u32, String>, opts: &OutputOptions) {
+fn print_json(fdb: &FdbEntry, action: &str, names: &IfIndexResolver, opts: &OutputOptions) {
     let mut obj = serde_json::json!({
         "event": action,
         "mac": fdb.mac_str(),
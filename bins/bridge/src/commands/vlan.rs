@@ -5,8 +5,9 @@ use nlink::{
     netlink::{
         Connection, Error, Result, Route,
         bridge_vlan::{
-            BridgeVlanBuilder, BridgeVlanEntry, BridgeVlanEntryOptionsBuilder,
-            BridgeVlanGlobalOptionsBuilder, BridgeVlanState, BridgeVlanTunnelBuilder,
+            BridgeVlanBuilder, BridgeVlanEntry, BridgeVlanEntryOptions,
+            BridgeVlanEntryOptionsBuilder, BridgeVlanGlobalOptionsBuilder, BridgeVlanState,
+            BridgeVlanTunnelBuilder,
         },
     },
     output::{OutputFormat, OutputOptions},
@@ -55,7 +56,7 @@ enum VlanCommand {
 
 #[derive(Args)]
 struct VlanAddArgs {
-    /// VLAN ID (1-4094) or range (e.g., 100-110)
+    /// VLAN IDs (1-4094) and ranges, comma separated (e.g., 100 or 10,20,100-110)
     #[arg(long)]
     vid: String,
 
@@ -70,17 +71,33 @@ struct VlanAddArgs {
     /// Egress untagged
     #[arg(long)]
     untagged: bool,
+
+    /// Program the device's own VLAN table (required for the bridge itself)
+    #[arg(long = "self")]
+    self_: bool,
+
+    /// Program the master bridge (default unless --self is given)
+    #[arg(long)]
+    master: bool,
 }
 
 #[derive(Args)]
 struct VlanDelArgs {
-    /// VLAN ID or range (e.g., 100 or 100-110)
+    /// VLAN IDs and ranges, comma separated (e.g., 100 or 10,20,100-110)
     #[arg(long)]
     vid: String,
 
     /// Port device
     #[arg(long)]
     dev: String,
+
+    /// Delete from the device's own VLAN table
+    #[arg(long = "self")]
+    self_: bool,
+
+    /// Delete from the master bridge (default unless --self is given)
+    #[arg(long)]
+    master: bool,
 }
 
 #[derive(Args)]
@@ -318,12 +335,81 @@ fn parse_vid_range(s: &str) -> Result<(u16, Option<u16>)> {
     }
 }
 
+/// Parse a comma-separated list of VLAN IDs and ranges (e.g., "10,20,100-110").
+fn parse_vid_list(s: &str) -> Result<Vec<(u16, Option<u16>)>> {
+    s.split(',').map(parse_vid_range).collect()
+}
+
+/// Build a [`BridgeVlanBuilder`] carrying every VID in `list`.
+fn vlan_builder(list: &[(u16, Option<u16>)], dev: &str) -> BridgeVlanBuilder {
+    let mut iter = list.iter();
+    let (first, first_end) = iter.next().copied().unwrap_or((0, None));
+    let mut builder = BridgeVlanBuilder::new(first).dev(dev);
+    if let Some(end) = first_end {
+        builder = builder.range(end);
+    }
+    for &(vid, end) in iter {
+        builder = builder.vid_range(vid, end.unwrap_or(vid));
+    }
+    builder
+}
+
+/// A run of consecutive VIDs on one port that share the same flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VlanRun {
+    start: u16,
+    end: u16,
+    pvid: bool,
+    untagged: bool,
+}
+
+/// Collapse a port's `(vid, pvid, untagged)` entries into ranges of
+/// consecutive VIDs with identical flags, the way `bridge vlan show`
+/// prints them.
+///
+/// The PVID always stays on its own row.
+fn compress_vlans(vlans: impl IntoIterator<Item = (u16, bool, bool)>) -> Vec<VlanRun> {
+    let mut sorted: Vec<(u16, bool, bool)> = vlans.into_iter().collect();
+    sorted.sort_by_key(|&(vid, _, _)| vid);
+
+    let mut runs: Vec<VlanRun> = Vec::new();
+    for (vid, pvid, untagged) in sorted {
+        if let Some(last) = runs.last_mut()
+            && !pvid
+            && !last.pvid
+            && last.untagged == untagged
+            && u32::from(last.end) + 1 == u32::from(vid)
+        {
+            last.end = vid;
+            continue;
+        }
+        runs.push(VlanRun {
+            start: vid,
+            end: vid,
+            pvid,
+            untagged,
+        });
+    }
+    runs
+}
+
+/// Project a port's entries into the tuples [`compress_vlans`] takes.
+fn vlan_tuples<'a>(vlans: &'a [&BridgeVlanEntry]) -> impl Iterator<Item = (u16, bool, bool)> + 'a {
+    vlans
+        .iter()
+        .map(|v| (v.vid(), v.is_pvid(), v.is_untagged()))
+}
+
 async fn show_vlans(
     conn: &Connection<Route>,
     dev: Option<String>,
     format: OutputFormat,
     opts: &OutputOptions,
 ) -> Result<()> {
+    if opts.stats {
+        return show_vlan_stats(conn, dev, format, opts).await;
+    }
+
     let entries = if let Some(ref name) = dev {
         conn.get_bridge_vlans(name).await?
     } else {
@@ -362,14 +448,14 @@ fn print_vlans_text(entries: &[BridgeVlanEntry], names: &std::collections::HashM
     for (ifindex, vlans) in by_dev {
         let dev = names.get(&ifindex).map(|s| s.as_str()).unwrap_or("?");
 
-        for (i, vlan) in vlans.iter().enumerate() {
+        for (i, run) in compress_vlans(vlan_tuples(&vlans)).iter().enumerate() {
             let port_col = if i == 0 { dev } else { "" };
 
             let mut flags = Vec::new();
-            if vlan.is_pvid() {
+            if run.pvid {
                 flags.push("PVID");
             }
-            if vlan.is_untagged() {
+            if run.untagged {
                 flags.push("Egress Untagged");
             }
 
@@ -379,7 +465,13 @@ fn print_vlans_text(entries: &[BridgeVlanEntry], names: &std::collections::HashM
                 format!(" {}", flags.join(" "))
             };
 
-            println!("{:<12} {}{}", port_col, vlan.vid(), flags_str);
+            let vid = if run.start == run.end {
+                run.start.to_string()
+            } else {
+                format!("{}-{}", run.start, run.end)
+            };
+
+            println!("{:<12} {}{}", port_col, vid, flags_str);
         }
     }
 }
@@ -407,14 +499,18 @@ fn print_vlans_json(
                 .cloned()
                 .unwrap_or_else(|| "?".to_string());
 
-            let vlan_list: Vec<serde_json::Value> = vlans
+            let vlan_list: Vec<serde_json::Value> = compress_vlans(vlan_tuples(&vlans))
                 .iter()
-                .map(|v| {
-                    serde_json::json!({
-                        "vid": v.vid(),
-                        "pvid": v.is_pvid(),
-                        "untagged": v.is_untagged(),
-                    })
+                .map(|run| {
+                    let mut v = serde_json::json!({
+                        "vid": run.start,
+                        "pvid": run.pvid,
+                        "untagged": run.untagged,
+                    });
+                    if run.end != run.start {
+                        v["vid_end"] = run.end.into();
+                    }
+                    v
                 })
                 .collect();
 
@@ -430,13 +526,106 @@ fn print_vlans_json(
     Ok(())
 }
 
+/// `bridge -s vlan show`: per-VLAN RX/TX counters from the VLAN-DB dump.
+async fn show_vlan_stats(
+    conn: &Connection<Route>,
+    dev: Option<String>,
+    format: OutputFormat,
+    opts: &OutputOptions,
+) -> Result<()> {
+    let entries = match dev {
+        Some(ref name) => conn.get_bridge_vlan_stats(name).await?,
+        None => conn.get_all_bridge_vlan_stats().await?,
+    };
+
+    let names = conn.get_interface_names().await?;
+
+    // Group by interface, ordered by ifindex, VLANs ascending.
+    let mut by_dev: std::collections::BTreeMap<u32, Vec<&BridgeVlanEntryOptions>> =
+        std::collections::BTreeMap::new();
+    for entry in &entries {
+        by_dev.entry(entry.ifindex()).or_default().push(entry);
+    }
+    for vlans in by_dev.values_mut() {
+        vlans.sort_by_key(|v| v.vid());
+    }
+
+    match format {
+        OutputFormat::Json => {
+            let json_output: Vec<serde_json::Value> = by_dev
+                .iter()
+                .map(|(ifindex, vlans)| {
+                    let dev = names
+                        .get(ifindex)
+                        .cloned()
+                        .unwrap_or_else(|| "?".to_string());
+                    let vlan_list: Vec<serde_json::Value> = vlans
+                        .iter()
+                        .map(|v| {
+                            let stats = v.stats().copied().unwrap_or_default();
+                            serde_json::json!({
+                                "vid": v.vid(),
+                                "pvid": v.flags().pvid,
+                                "untagged": v.flags().untagged,
+                                "rx_bytes": stats.rx_bytes,
+                                "rx_packets": stats.rx_packets,
+                                "tx_bytes": stats.tx_bytes,
+                                "tx_packets": stats.tx_packets,
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "ifindex": ifindex,
+                        "dev": dev,
+                        "vlans": vlan_list,
+                    })
+                })
+                .collect();
+            println!("{}", super::to_json_string(&json_output, opts.pretty)?);
+        }
+        OutputFormat::Text => {
+            println!("{:<12} vlan-id", "port");
+            for (ifindex, vlans) in &by_dev {
+                let dev = names.get(ifindex).map(|s| s.as_str()).unwrap_or("?");
+                for (i, v) in vlans.iter().enumerate() {
+                    let port_col = if i == 0 { dev } else { "" };
+                    let mut flags = String::new();
+                    if v.flags().pvid {
+                        flags.push_str(" PVID");
+                    }
+                    if v.flags().untagged {
+                        flags.push_str(" Egress Untagged");
+                    }
+                    println!("{:<12} {}{}", port_col, v.vid(), flags);
+
+                    let stats = v.stats().copied().unwrap_or_default();
+                    println!(
+                        "{:<14} RX: {} bytes {} packets",
+                        "", stats.rx_bytes, stats.rx_packets
+                    );
+                    println!(
+                        "{:<14} TX: {} bytes {} packets",
+                        "", stats.tx_bytes, stats.tx_packets
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 async fn add_vlan(conn: &Connection<Route>, args: VlanAddArgs) -> Result<()> {
-    let (vid_start, vid_end) = parse_vid_range(&args.vid)?;
+    let vids = parse_vid_list(&args.vid)?;
 
-    let mut builder = BridgeVlanBuilder::new(vid_start).dev(&args.dev);
+    let mut builder = vlan_builder(&vids, &args.dev);
 
-    if let Some(end) = vid_end {
-        builder = builder.range(end);
+    if args.self_ {
+        builder = builder.self_();
+    }
+
+    if args.master {
+        builder = builder.via_master();
     }
 
     if args.pvid {
@@ -451,13 +640,19 @@ async fn add_vlan(conn: &Connection<Route>, args: VlanAddArgs) -> Result<()> {
 }
 
 async fn del_vlan(conn: &Connection<Route>, args: VlanDelArgs) -> Result<()> {
-    let (vid_start, vid_end) = parse_vid_range(&args.vid)?;
+    let vids = parse_vid_list(&args.vid)?;
 
-    if let Some(end) = vid_end {
-        conn.del_bridge_vlan_range(&args.dev, vid_start, end).await
-    } else {
-        conn.del_bridge_vlan(&args.dev, vid_start).await
+    let mut builder = vlan_builder(&vids, &args.dev);
+
+    if args.self_ {
+        builder = builder.self_();
     }
+
+    if args.master {
+        builder = builder.via_master();
+    }
+
+    conn.del_bridge_vlans(builder).await
 }
 
 async fn set_pvid(conn: &Connection<Route>, args: VlanSetArgs) -> Result<()> {
@@ -712,4 +907,42 @@ mod tests {
     fn rejects_non_numeric() {
         assert!(parse_vid_range("abc").is_err());
     }
+
+    #[test]
+    fn parses_vid_list() {
+        assert_eq!(
+            parse_vid_list("10,20-30, 40").unwrap(),
+            vec![(10, None), (20, Some(30)), (40, None)]
+        );
+        assert!(parse_vid_list("10,,20").is_err());
+        assert!(parse_vid_list("10,4095").is_err());
+    }
+
+    #[test]
+    fn compresses_consecutive_vids_with_equal_flags() {
+        let entries = [
+            (1, true, true),
+            (2, false, true),
+            (3, false, true),
+            (12, false, false),
+            (10, false, false),
+            (11, false, false),
+            (13, false, true),
+            (20, false, false),
+        ];
+        let runs: Vec<(u16, u16, bool, bool)> = compress_vlans(entries)
+            .into_iter()
+            .map(|r| (r.start, r.end, r.pvid, r.untagged))
+            .collect();
+        assert_eq!(
+            runs,
+            vec![
+                (1, 1, true, true),
+                (2, 3, false, true),
+                (10, 12, false, false),
+                (13, 13, false, true),
+                (20, 20, false, false),
+            ]
+        );
+    }
 }
@@ -30,6 +30,10 @@ struct Cli {
     #[arg(short = 'd', long, global = true)]
     details: bool,
 
+    /// Show statistics (per-VLAN counters in `vlan show`)
+    #[arg(short = 's', long, global = true)]
+    stats: bool,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -63,12 +67,7 @@ async fn main() -> Result<()> {
     };
 
     let opts = OutputOptions {
-        // The bridge FDB/VLAN/MDB entry types don't carry per-entry
-        // statistics (NDA_CACHEINFO ages, MDB timers, per-VLAN counters)
-        // yet, so there is nothing to gate on a `-s` flag — exposing one
-        // would be a silent no-op. Surfacing real stats needs library
-        // support first; tracked alongside the other bridge gaps.
-        stats: false,
+        stats: cli.stats,
         details: cli.details,
         pretty: cli.pretty,
         numeric: false,
@@ -1,15 +1,29 @@
 //! Capture command - capture current network state as configuration.
-
-use std::collections::BTreeMap;
-
-use clap::Args;
-use nlink::netlink::{
-    Connection, Result, Route,
-    types::{route::RouteType, rule::FibRuleAction},
+//!
+//! Output is sorted (links by name, addresses by device and address,
+//! routes by table and destination, ...) rather than left in kernel
+//! dump order, which follows ifindex and insertion order and so changes
+//! between boots. With `--stable`, state the kernel or a DHCP/RA client
+//! manages is left out too, so a capture committed to git only diffs
+//! when the configuration does.
+
+use std::{collections::BTreeMap, net::IpAddr};
+
+use clap::{Args, ValueEnum};
+use nlink::{
+    netlink::{
+        Connection, Result, Route,
+        types::{
+            route::{RouteProtocol, RouteType},
+            rule::FibRuleAction,
+        },
+    },
+    util::glob_match,
 };
 
 use crate::schema::{
     AddressConfig, ConfigFile, LinkConfig, OutputFormat, QdiscConfig, RouteConfig, RuleConfig,
+    parse_table,
 };
 
 #[derive(Args)]
@@ -18,9 +32,35 @@ pub struct CaptureArgs {
     #[arg(short, long, value_enum, default_value = "yaml")]
     pub format: OutputFormat,
 
-    /// Capture only specific interface
-    #[arg(short, long)]
-    pub interface: Option<String>,
+    /// Capture only interfaces matching PATTERN (`*` and `?` wildcards);
+    /// repeatable
+    #[arg(short, long, value_name = "PATTERN")]
+    pub interface: Vec<String>,
+
+    /// Skip interfaces matching PATTERN (`*` and `?` wildcards);
+    /// repeatable
+    #[arg(short = 'x', long, value_name = "PATTERN")]
+    pub exclude_interface: Vec<String>,
+
+    /// Capture only these sections (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "SECTION")]
+    pub only: Vec<Section>,
+
+    /// Leave out these sections (comma-separated)
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "SECTION")]
+    pub exclude: Vec<Section>,
+
+    /// Capture only routes in, and rules pointing at, TABLE (name or
+    /// number); repeatable
+    #[arg(long, value_name = "TABLE", value_parser = parse_table_arg)]
+    pub table: Vec<u32>,
+
+    /// Leave out ephemeral state: dynamic (DHCP/SLAAC) and
+    /// kernel-added (loopback, IPv6 link-local) addresses, routes
+    /// installed by the kernel, RA, DHCP or ICMP redirects, and the MACs
+    /// of links without a permanent hardware address
+    #[arg(long)]
+    pub stable: bool,
 
     /// Include TC configuration
     #[arg(long)]
@@ -39,12 +79,52 @@ pub struct CaptureArgs {
     pub skip_loopback: bool,
 }
 
+/// A top-level section of the configuration file.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Section {
+    Links,
+    Addresses,
+    Routes,
+    Rules,
+    Qdiscs,
+}
+
+fn parse_table_arg(s: &str) -> std::result::Result<u32, String> {
+    parse_table(s).ok_or_else(|| format!("unknown routing table `{s}`"))
+}
+
+impl CaptureArgs {
+    /// Whether `section` is captured. Rules and qdiscs also need
+    /// `--rules`/`--tc`/`--full`, unless `--only` names them.
+    fn wants(&self, section: Section) -> bool {
+        if self.exclude.contains(&section) {
+            return false;
+        }
+        if !self.only.is_empty() {
+            return self.only.contains(&section);
+        }
+        match section {
+            Section::Rules => self.rules || self.full,
+            Section::Qdiscs => self.tc || self.full,
+            _ => true,
+        }
+    }
+
+    /// Whether interface `name` passes `--interface`/`--exclude-interface`.
+    fn wants_interface(&self, name: &str) -> bool {
+        (self.interface.is_empty() || self.interface.iter().any(|p| glob_match(p, name)))
+            && !self.exclude_interface.iter().any(|p| glob_match(p, name))
+    }
+
+    /// Whether routing table `id` passes `--table`.
+    fn wants_table(&self, id: u32) -> bool {
+        self.table.is_empty() || self.table.contains(&id)
+    }
+}
+
 pub async fn run(args: CaptureArgs) -> Result<()> {
     let conn = Connection::<Route>::new()?;
 
-    let include_tc = args.tc || args.full;
-    let include_rules = args.rules || args.full;
-
     // Build interface name map
     let names = conn.get_interface_names().await?;
 
@@ -52,7 +132,7 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
     let links = conn.get_links().await?;
     let mut link_configs = Vec::new();
 
-    for link in &links {
+    for link in links.iter().filter(|_| args.wants(Section::Links)) {
         let name = link.name().unwrap_or("?");
 
         // Skip loopback if requested
@@ -60,10 +140,7 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
             continue;
         }
 
-        // Filter by interface if specified
-        if let Some(ref filter) = args.interface
-            && name != filter
-        {
+        if !args.wants_interface(name) {
             continue;
         }
 
@@ -74,7 +151,14 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
         let state = if link.is_up() { "up" } else { "down" }.to_string();
         let mtu = link.mtu();
         let master = link.master().and_then(|idx| names.get(&idx).cloned());
-        let mac = link.address().map(|a| {
+        // Virtual links get a random MAC when created, so it differs
+        // on every rebuild.
+        let mac = if args.stable && link.perm_address().is_none() {
+            None
+        } else {
+            link.address()
+        };
+        let mac = mac.map(|a| {
             a.iter()
                 .map(|b| format!("{:02x}", b))
                 .collect::<Vec<_>>()
@@ -93,7 +177,11 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
     }
 
     // Capture addresses
-    let addresses = conn.get_addresses().await?;
+    let addresses = if args.wants(Section::Addresses) {
+        conn.get_addresses().await?
+    } else {
+        Vec::new()
+    };
     let mut addr_configs = Vec::new();
 
     for addr in &addresses {
@@ -102,10 +190,13 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
             .map(|s| s.as_str())
             .unwrap_or("?");
 
-        // Filter by interface if specified
-        if let Some(ref filter) = args.interface
-            && dev != filter
-        {
+        if !args.wants_interface(dev) {
+            continue;
+        }
+
+        // Leased and autoconfigured addresses, and those the kernel
+        // adds itself (loopback, IPv6 link-local)
+        if args.stable && addr.origin().is_dynamic() {
             continue;
         }
 
@@ -136,7 +227,11 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
     }
 
     // Capture routes
-    let routes = conn.get_routes().await?;
+    let routes = if args.wants(Section::Routes) {
+        conn.get_routes().await?
+    } else {
+        Vec::new()
+    };
     let mut route_configs = Vec::new();
 
     for route in &routes {
@@ -148,16 +243,36 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
             }
         }
 
-        // Filter by interface if specified
-        if let Some(ref filter) = args.interface {
-            if let Some(oif) = route.oif() {
+        if !args.wants_table(route.table_id()) {
+            continue;
+        }
+
+        // Routes that follow from other state: connected routes the
+        // kernel adds with an address, and those a DHCP/RA client or
+        // an ICMP redirect installed
+        if args.stable
+            && matches!(
+                route.protocol(),
+                RouteProtocol::Kernel
+                    | RouteProtocol::Ra
+                    | RouteProtocol::Dhcp
+                    | RouteProtocol::Redirect
+            )
+        {
+            continue;
+        }
+
+        // Filter by interface if specified; routes without an output
+        // device only pass an exclude-only filter
+        match route.oif() {
+            Some(oif) => {
                 let dev = names.get(&oif).map(|s| s.as_str()).unwrap_or("?");
-                if dev != filter {
+                if !args.wants_interface(dev) {
                     continue;
                 }
-            } else {
-                continue;
             }
+            None if !args.interface.is_empty() => continue,
+            None => {}
         }
 
         let destination = route
@@ -195,9 +310,9 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
                 // Don't let an unmodelled route type vanish silently from
                 // the captured config — warn and preserve its raw name.
                 eprintln!(
-                    "warning: route to {destination} has unmodelled type {other:?}; captured as-is"
+                    "warning: route to {destination} has unmodelled type {other}; captured as-is"
                 );
-                Some(format!("{other:?}").to_lowercase())
+                Some(other.to_string())
             }
         };
 
@@ -213,7 +328,7 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
 
     // Capture rules if requested
     let mut rule_configs = Vec::new();
-    if include_rules {
+    if args.wants(Section::Rules) {
         let rules = conn.get_rules().await?;
         for rule in &rules {
             // Skip default rules unless full mode
@@ -221,6 +336,10 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
                 continue;
             }
 
+            if !args.wants_table(rule.table_id()) {
+                continue;
+            }
+
             let from = rule.source().map(|addr| {
                 if rule.src_len() == 0 {
                     "all".to_string()
@@ -235,7 +354,10 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
                     format!("{}/{}", addr, rule.dst_len())
                 }
             });
-            let fwmark = rule.fwmark().map(|m| format!("0x{:x}", m));
+            let fwmark = rule.fwmark().map(|m| match rule.fwmask() {
+                Some(mask) if mask != u32::MAX => format!("0x{:x}/0x{:x}", m, mask),
+                _ => format!("0x{:x}", m),
+            });
             let table = {
                 let t = rule.table_id();
                 match t {
@@ -253,10 +375,10 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
                 FibRuleAction::Prohibit => Some("prohibit".to_string()),
                 other => {
                     eprintln!(
-                        "warning: rule prio {} has unmodelled action {other:?}; captured as-is",
+                        "warning: rule prio {} has unmodelled action {other}; captured as-is",
                         rule.priority()
                     );
-                    Some(format!("{other:?}").to_lowercase())
+                    Some(other.to_string())
                 }
             };
 
@@ -265,15 +387,18 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
                 from,
                 to,
                 fwmark,
+                iif: rule.iifname().map(str::to_string),
+                oif: rule.oifname().map(str::to_string),
                 table,
                 action,
+                ipv6: rule.is_ipv6(),
             });
         }
     }
 
     // Capture qdiscs if requested
     let mut qdisc_configs = Vec::new();
-    if include_tc {
+    if args.wants(Section::Qdiscs) {
         let qdiscs = conn.get_qdiscs().await?;
         for qdisc in &qdiscs {
             let dev = names
@@ -281,10 +406,7 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
                 .map(|s| s.as_str())
                 .unwrap_or("?");
 
-            // Filter by interface if specified
-            if let Some(ref filter) = args.interface
-                && dev != filter
-            {
+            if !args.wants_interface(dev) {
                 continue;
             }
 
@@ -323,12 +445,35 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
         }
     }
 
+    link_configs.sort_by(|a, b| a.name.cmp(&b.name));
+    addr_configs
+        .sort_by(|a, b| (&a.dev, prefix_key(&a.address)).cmp(&(&b.dev, prefix_key(&b.address))));
+    route_configs.sort_by_cached_key(|r| {
+        (
+            r.table
+                .as_deref()
+                .map_or(254, |t| parse_table(t).unwrap_or(u32::MAX)),
+            prefix_key(&r.destination),
+            r.metric,
+            r.dev.clone(),
+            r.gateway.clone(),
+        )
+    });
+    rule_configs.sort_by_key(|r| (r.ipv6, r.priority));
+    qdisc_configs.sort_by(|a, b| {
+        (&a.dev, a.parent != "root", &a.parent).cmp(&(&b.dev, b.parent != "root", &b.parent))
+    });
+
     let config = ConfigFile {
+        owner: None,
         links: link_configs,
         addresses: addr_configs,
         routes: route_configs,
         rules: rule_configs,
+        neighbors: Vec::new(),
+        sysctls: Vec::new(),
         qdiscs: qdisc_configs,
+        namespaces: Vec::new(),
     };
 
     match args.format {
@@ -359,6 +504,14 @@ pub async fn run(args: CaptureArgs) -> Result<()> {
     Ok(())
 }
 
+/// Sort key for an `address/len` or `default` string: by address family
+/// and value (so `10.0.0.2` sorts before `10.0.0.10`), then prefix
+/// length. `default` sorts first.
+fn prefix_key(s: &str) -> (Option<IpAddr>, u8) {
+    let (addr, len) = s.split_once('/').unwrap_or((s, ""));
+    (addr.parse().ok(), len.parse().unwrap_or(0))
+}
+
 /// Decode a captured qdisc's parameters into a `key -> value` map for the
 /// `QdiscConfig.options` field. Covers the common shaping/AQM kinds
 /// (htb / tbf / netem / fq_codel); other kinds capture their `kind` +
@@ -449,3 +602,79 @@ fn qdisc_options_map(
 
     map
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: CaptureArgs,
+    }
+
+    fn args(argv: &[&str]) -> CaptureArgs {
+        Cli::parse_from(std::iter::once("capture").chain(argv.iter().copied())).args
+    }
+
+    #[test]
+    fn interface_filters_combine() {
+        let a = args(&["-i", "eth*", "-i", "bond0", "-x", "eth9"]);
+        assert!(a.wants_interface("eth0"));
+        assert!(a.wants_interface("bond0"));
+        assert!(!a.wants_interface("eth9"));
+        assert!(!a.wants_interface("wlan0"));
+
+        let a = args(&["-x", "veth*"]);
+        assert!(a.wants_interface("eth0"));
+        assert!(!a.wants_interface("veth1"));
+    }
+
+    #[test]
+    fn section_selection() {
+        let a = args(&[]);
+        assert!(a.wants(Section::Links) && a.wants(Section::Routes));
+        assert!(!a.wants(Section::Rules) && !a.wants(Section::Qdiscs));
+
+        // `--only` enables rules without `--rules`.
+        let a = args(&["--only", "routes,rules"]);
+        assert!(a.wants(Section::Rules) && a.wants(Section::Routes));
+        assert!(!a.wants(Section::Links) && !a.wants(Section::Addresses));
+
+        let a = args(&["--full", "--exclude", "qdiscs"]);
+        assert!(a.wants(Section::Rules) && !a.wants(Section::Qdiscs));
+    }
+
+    #[test]
+    fn table_filter_accepts_names_and_numbers() {
+        let a = args(&["--table", "main", "--table", "100"]);
+        assert!(a.wants_table(254) && a.wants_table(100));
+        assert!(!a.wants_table(255));
+        assert!(args(&[]).wants_table(255));
+        assert!(Cli::try_parse_from(["capture", "--table", "nope"]).is_err());
+    }
+
+    #[test]
+    fn prefix_key_orders_numerically() {
+        let mut v = [
+            "10.0.0.10/24",
+            "default",
+            "10.0.0.2/24",
+            "fd00::1/64",
+            "10.0.0.2/16",
+        ];
+        v.sort_by_key(|s| prefix_key(s));
+        assert_eq!(
+            v,
+            [
+                "default",
+                "10.0.0.2/16",
+                "10.0.0.2/24",
+                "10.0.0.10/24",
+                "fd00::1/64"
+            ]
+        );
+    }
+}
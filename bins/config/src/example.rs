@@ -315,6 +315,15 @@ links:
     options:
       peer: veth1
 
+  # Veth pair with its peer in the `host1` namespace (see below)
+  - name: veth-host1
+    kind: veth
+    state: up
+    master: br0
+    options:
+      peer: veth-h1
+      peer_netns: host1
+
   # VXLAN tunnel
   - name: vxlan100
     kind: vxlan
@@ -404,6 +413,21 @@ rules:
     from: 192.168.99.0/24
     action: blackhole
 
+neighbors:
+  # Static ARP entry for the gateway
+  - dev: br0
+    address: 10.0.0.254
+    lladdr: "02:00:00:00:00:fe"
+
+sysctls:
+  # Route between interfaces, with loose reverse-path filtering
+  - dev: br0
+    key: ipv4_forwarding
+    value: 1
+  - dev: br0
+    key: rp_filter
+    value: 2
+
 qdiscs:
   # HTB qdisc for traffic shaping
   - dev: eth0
@@ -425,6 +449,19 @@ qdiscs:
   - dev: br0
     parent: root
     kind: fq_codel
+
+namespaces:
+  # A test host behind a veth pair whose peer lives in its namespace
+  - name: host1
+    links:
+      - name: veth-h1
+        state: up
+    addresses:
+      - dev: veth-h1
+        address: 10.0.0.10/24
+    routes:
+      - destination: default
+        gateway: 10.0.0.1
 "#;
 
 #[cfg(test)]
@@ -1,15 +1,19 @@
 //! nlink-config - Declarative network configuration utility
 //!
-//! Captures and manages network configuration in YAML/JSON format.
+//! Captures, applies and continuously enforces network configuration in
+//! YAML/JSON format.
 
 mod capture;
 mod example;
 mod schema;
 
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
 use clap::{Args, Parser, Subcommand};
-use nlink::netlink::{Connection, Result, Route, config::ApplyOptions};
+use nlink::netlink::{
+    Connection, Error, Result, Route,
+    config::{ApplyOptions, DiffOptions, NetworkConfig, ReconcileTrigger},
+};
 
 use crate::schema::ConfigFile;
 
@@ -31,16 +35,24 @@ enum Command {
     Example(example::ExampleArgs),
 
     /// Show what `apply` would change, without touching the kernel
-    Diff(FileArgs),
+    Diff(DiffArgs),
 
     /// Reconcile the kernel to match a configuration file
     Apply(ApplyArgs),
+
+    /// Keep the kernel matching a configuration file, re-applying drift
+    Watch(WatchArgs),
 }
 
 #[derive(Args)]
-struct FileArgs {
+struct DiffArgs {
     /// Path to a YAML or JSON configuration file
     file: PathBuf,
+
+    /// Also show undeclared addresses and routes that `apply --purge`
+    /// would remove
+    #[arg(long)]
+    purge: bool,
 }
 
 #[derive(Args)]
@@ -48,15 +60,65 @@ struct ApplyArgs {
     /// Path to a YAML or JSON configuration file
     file: PathBuf,
 
-    /// Compute and print the changes without applying them
+    /// Print the diff without applying it
     #[arg(long)]
     dry_run: bool,
 
+    /// Remove undeclared global addresses on managed interfaces and
+    /// static/boot main-table routes, or, if the config sets an
+    /// `owner` tag, the undeclared addresses and routes carrying it.
+    /// Links and qdiscs are never removed.
+    #[arg(long, conflicts_with = "reconcile")]
+    purge: bool,
+
+    /// Leave already-applied changes in place when a later step
+    /// fails, instead of rolling them back
+    #[arg(long, conflicts_with = "reconcile")]
+    no_rollback: bool,
+
+    /// Before adding a route, check that its gateway answers ARP/NDP
+    /// within SECS seconds and fail the step if it doesn't
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with_all = ["dry_run", "reconcile", "confirm"]
+    )]
+    probe_gateways: Option<u64>,
+
     /// Reconcile with bounded retry on transient kernel contention
     /// (recomputes the diff each attempt). Mutually exclusive with
     /// --dry-run.
     #[arg(long, conflicts_with = "dry_run")]
     reconcile: bool,
+
+    /// Revert the changes after SECS seconds unless confirmed by
+    /// pressing Enter (guards remote sessions against lock-out)
+    #[arg(
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with_all = ["dry_run", "reconcile", "no_rollback"]
+    )]
+    confirm: Option<u64>,
+}
+
+#[derive(Args)]
+struct WatchArgs {
+    /// Path to a YAML or JSON configuration file
+    file: PathBuf,
+
+    /// Re-check every SECS seconds instead of on kernel change events
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: Option<u64>,
+
+    /// Only report drift; don't correct it
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Also remove undeclared addresses and routes (see `apply --purge`)
+    #[arg(long)]
+    purge: bool,
 }
 
 #[tokio::main]
@@ -68,12 +130,13 @@ async fn main() -> Result<()> {
         Command::Example(args) => example::run(args),
         Command::Diff(args) => diff_cmd(args).await,
         Command::Apply(args) => apply_cmd(args).await,
+        Command::Watch(args) => watch_cmd(args).await,
     }
 }
 
 /// Load the file, translate it to a library `NetworkConfig`, and emit
 /// any translation warnings on stderr (never silently dropped).
-fn load_config(file: &std::path::Path) -> Result<nlink::netlink::config::NetworkConfig> {
+fn load_config(file: &std::path::Path) -> Result<NetworkConfig> {
     let file = ConfigFile::load(file)?;
     let (cfg, warnings) = file.to_network_config()?;
     for w in &warnings {
@@ -82,10 +145,17 @@ fn load_config(file: &std::path::Path) -> Result<nlink::netlink::config::Network
     Ok(cfg)
 }
 
-async fn diff_cmd(args: FileArgs) -> Result<()> {
+async fn diff_cmd(args: DiffArgs) -> Result<()> {
     let cfg = load_config(&args.file)?;
     let conn = Connection::<Route>::new()?;
-    let diff = cfg.diff(&conn).await?;
+    print_diff(&cfg, &conn, args.purge).await
+}
+
+/// Compute and print the diff; shared by `diff` and `apply --dry-run`.
+async fn print_diff(cfg: &NetworkConfig, conn: &Connection<Route>, purge: bool) -> Result<()> {
+    let diff = cfg
+        .diff_with_options(conn, DiffOptions::default().purge(purge))
+        .await?;
     if diff.is_empty() {
         println!("No changes needed; the system already matches the configuration.");
     } else {
@@ -110,15 +180,23 @@ async fn apply_cmd(args: ApplyArgs) -> Result<()> {
         return Ok(());
     }
 
-    let result = cfg
-        .apply_with_options(&conn, ApplyOptions::default().with_dry_run(args.dry_run))
-        .await?;
-
     if args.dry_run {
-        println!("Dry run — would make {} change(s):", result.changes_made);
-    } else {
-        println!("Applied {} change(s):", result.changes_made);
+        return print_diff(&cfg, &conn, args.purge).await;
+    }
+
+    if let Some(secs) = args.confirm {
+        return apply_confirmed(&cfg, &conn, args.purge, Duration::from_secs(secs)).await;
+    }
+
+    let mut opts = ApplyOptions::default()
+        .with_purge(args.purge)
+        .with_rollback(!args.no_rollback);
+    if let Some(secs) = args.probe_gateways {
+        opts = opts.with_gateway_probe(Duration::from_secs(secs));
     }
+    let result = cfg.apply_with_options(&conn, opts).await?;
+
+    println!("Applied {} change(s):", result.changes_made);
     println!("{}", result.summary_text());
 
     if !result.is_success() {
@@ -126,5 +204,114 @@ async fn apply_cmd(args: ApplyArgs) -> Result<()> {
             eprintln!("error: {e}");
         }
     }
+
+    if let Some(rollback) = &result.rollback {
+        println!("Rolled back:");
+        println!("{}", rollback.summary_text());
+        return Err(Error::InvalidMessage(if rollback.is_complete() {
+            "apply failed; all applied changes were rolled back".into()
+        } else {
+            "apply failed and the rollback was incomplete; see above".into()
+        }));
+    }
     Ok(())
 }
+
+/// `apply --confirm`: apply, then keep the changes only if Enter is
+/// pressed before the deadline. Ctrl-C reverts at once.
+async fn apply_confirmed(
+    cfg: &NetworkConfig,
+    conn: &Connection<Route>,
+    purge: bool,
+    window: Duration,
+) -> Result<()> {
+    let diff = cfg
+        .diff_with_options(conn, DiffOptions::default().purge(purge))
+        .await?;
+    if diff.is_empty() {
+        println!("No changes needed; the system already matches the configuration.");
+        return Ok(());
+    }
+    let pending = diff.apply_confirmed(conn, window).await?;
+    println!("Applied {} change(s):", pending.result().changes_made);
+    println!("{}", pending.result().summary_text());
+    println!(
+        "Press Enter within {}s to keep these changes; otherwise they are reverted.",
+        window.as_secs()
+    );
+
+    // Blocking stdin read on a detached thread (not `spawn_blocking`,
+    // which would hold up runtime shutdown if the deadline wins).
+    let (tx, enter) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        let mut line = String::new();
+        let _ = tx.send(std::io::stdin().read_line(&mut line).is_ok_and(|n| n > 0));
+    });
+    let deadline = tokio::time::sleep(pending.remaining());
+
+    let report = tokio::select! {
+        pressed = enter => {
+            if pressed.unwrap_or(false) {
+                return match pending.confirm().await {
+                    Ok(()) => {
+                        println!("Changes confirmed.");
+                        Ok(())
+                    }
+                    Err(e) if e.is_timeout() => Err(Error::InvalidMessage(
+                        "confirmed too late; the changes were already reverted".into(),
+                    )),
+                    Err(e) => Err(e),
+                };
+            }
+            // stdin closed: nobody can confirm.
+            pending.revert().await?
+        }
+        _ = deadline => pending.wait().await?,
+        _ = tokio::signal::ctrl_c() => pending.revert().await?,
+    };
+
+    println!("Reverted:");
+    println!("{}", report.summary_text());
+    Err(Error::InvalidMessage(if report.is_complete() {
+        "changes not confirmed; all of them were reverted".into()
+    } else {
+        "changes not confirmed and the revert was incomplete; see above".into()
+    }))
+}
+
+async fn watch_cmd(args: WatchArgs) -> Result<()> {
+    let cfg = load_config(&args.file)?;
+    let conn = Connection::<Route>::new()?;
+
+    let trigger = match args.interval {
+        Some(secs) => ReconcileTrigger::Interval(Duration::from_secs(secs)),
+        None => ReconcileTrigger::events(),
+    };
+    let opts = ApplyOptions::default()
+        .with_dry_run(args.dry_run)
+        .with_purge(args.purge)
+        .with_rollback(true);
+    let mut reconciler = cfg.reconcile(&conn, trigger).await?.options(opts);
+
+    loop {
+        let result = tokio::select! {
+            result = reconciler.next_pass() => result?,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+
+        if result.changes_made > 0 {
+            if args.dry_run {
+                println!("Drift detected ({} change(s)):", result.changes_made);
+            } else {
+                println!("Corrected {} change(s):", result.changes_made);
+            }
+            println!("{}", result.summary_text());
+        }
+        for e in &result.errors {
+            eprintln!("error: {e}");
+        }
+        if let Some(rollback) = &result.rollback {
+            eprintln!("Rolled back:\n{}", rollback.summary_text());
+        }
+    }
+}
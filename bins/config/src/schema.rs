@@ -12,15 +12,15 @@
 //! translation is deliberately honest about its limits: structural
 //! problems (unknown link kind, missing required option, malformed
 //! value) are hard errors, while options the library can't yet model
-//! and whole sections it doesn't support (`rules`, `qdiscs`) are
-//! returned as **visible warnings** rather than silently dropped.
+//! and whole sections it doesn't support (`qdiscs`) are returned as
+//! **visible warnings** rather than silently dropped.
 
 use std::{collections::BTreeMap, net::IpAddr, path::Path};
 
 use clap::ValueEnum;
 use nlink::netlink::{
     Error, Result,
-    config::{BondMode, MacvlanMode, NetworkConfig, VlanProtocol},
+    config::{BondMode, InterfaceSysctl, MacvlanMode, NetworkConfig, VlanProtocol},
 };
 use serde::{Deserialize, Serialize};
 
@@ -42,6 +42,11 @@ type Options = BTreeMap<String, serde_yaml::Value>;
 /// The complete `nlink-config` file format.
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ConfigFile {
+    /// Route protocol tag stamped on the addresses and routes this file
+    /// creates; `apply --purge` then only removes what carries it. A
+    /// namespace without its own tag inherits the top-level one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<u8>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub links: Vec<LinkConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -51,7 +56,22 @@ pub struct ConfigFile {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub rules: Vec<RuleConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub neighbors: Vec<NeighborConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sysctls: Vec<SysctlConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub qdiscs: Vec<QdiscConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub namespaces: Vec<NamespaceConfig>,
+}
+
+/// A named network namespace: its `name` plus the same sections as the
+/// top level (which must not declare `namespaces` of its own).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NamespaceConfig {
+    pub name: String,
+    #[serde(flatten)]
+    pub config: ConfigFile,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -106,9 +126,31 @@ pub struct RuleConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fwmark: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iif: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oif: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub table: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub action: Option<String>,
+    /// IPv6 rule; only needed when `from`/`to` don't say.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub ipv6: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct NeighborConfig {
+    pub dev: String,
+    pub address: String,
+    pub lladdr: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SysctlConfig {
+    pub dev: String,
+    /// `ipv4_forwarding`, `ipv6_forwarding`, `rp_filter` or `accept_ra`.
+    pub key: String,
+    pub value: u32,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -145,11 +187,14 @@ impl ConfigFile {
     ///
     /// Returns the config plus a list of human-readable warnings for
     /// anything the apply/diff engine can't (yet) carry — present but
-    /// unmodelled link options, and the `rules` / `qdiscs` sections.
+    /// unmodelled link options, and the `qdiscs` section.
     /// Hard errors are reserved for malformed input the user must fix.
     pub fn to_network_config(&self) -> Result<(NetworkConfig, Vec<String>)> {
         let mut cfg = NetworkConfig::new();
         let mut warn = Vec::new();
+        if let Some(tag) = self.owner {
+            cfg = cfg.owner(tag);
+        }
 
         for link in &self.links {
             let plan = LinkPlan::from_config(link, &mut warn)?;
@@ -176,11 +221,40 @@ impl ConfigFile {
             cfg = add_route(cfg, route)?;
         }
 
-        if !self.rules.is_empty() {
-            warn.push(format!(
-                "{} routing rule(s) in the file are not applied: `config apply` has no rule support yet — use `ip rule`",
-                self.rules.len()
-            ));
+        for rule in &self.rules {
+            cfg = add_rule(cfg, rule)?;
+        }
+
+        for neigh in &self.neighbors {
+            let address: IpAddr = neigh.address.parse().map_err(|_| {
+                Error::InvalidMessage(format!(
+                    "config: neighbor on {}: invalid address `{}`",
+                    neigh.dev, neigh.address
+                ))
+            })?;
+            let lladdr = parse_mac(&neigh.lladdr).ok_or_else(|| {
+                Error::InvalidMessage(format!(
+                    "config: neighbor {address} on {}: invalid lladdr `{}` (expected aa:bb:cc:dd:ee:ff)",
+                    neigh.dev, neigh.lladdr
+                ))
+            })?;
+            cfg = cfg.neighbor(&neigh.dev, address, lladdr);
+        }
+
+        for sysctl in &self.sysctls {
+            let key = match sysctl.key.as_str() {
+                "ipv4_forwarding" => InterfaceSysctl::Ipv4Forwarding,
+                "ipv6_forwarding" => InterfaceSysctl::Ipv6Forwarding,
+                "rp_filter" => InterfaceSysctl::RpFilter,
+                "accept_ra" => InterfaceSysctl::AcceptRa,
+                other => {
+                    return Err(Error::InvalidMessage(format!(
+                        "config: sysctl on {}: unknown key `{other}` (expected ipv4_forwarding, ipv6_forwarding, rp_filter or accept_ra)",
+                        sysctl.dev
+                    )));
+                }
+            };
+            cfg = cfg.sysctl(&sysctl.dev, key, sysctl.value);
         }
         if !self.qdiscs.is_empty() {
             warn.push(format!(
@@ -189,6 +263,22 @@ impl ConfigFile {
             ));
         }
 
+        for ns in &self.namespaces {
+            if !ns.config.namespaces.is_empty() {
+                return Err(Error::InvalidMessage(format!(
+                    "config: namespace {}: namespaces can't be nested",
+                    ns.name
+                )));
+            }
+            let (sub, sub_warn) = ns.config.to_network_config()?;
+            warn.extend(
+                sub_warn
+                    .into_iter()
+                    .map(|w| format!("namespace {}: {w}", ns.name)),
+            );
+            cfg = cfg.namespace(&ns.name, sub);
+        }
+
         Ok((cfg, warn))
     }
 }
@@ -209,6 +299,7 @@ enum LinkKind {
     Bridge,
     Veth {
         peer: String,
+        peer_netns: Option<String>,
     },
     Vlan {
         link: String,
@@ -281,7 +372,13 @@ impl LinkPlan {
             LinkKind::Dummy => b.dummy(),
             LinkKind::Ifb => b.ifb(),
             LinkKind::Bridge => b.bridge(),
-            LinkKind::Veth { peer } => b.veth(&peer),
+            LinkKind::Veth { peer, peer_netns } => {
+                b = b.veth(&peer);
+                if let Some(ns) = peer_netns {
+                    b = b.veth_peer_netns(&ns);
+                }
+                b
+            }
             LinkKind::Vlan { link, id, protocol } => {
                 b = b.vlan(&link, id);
                 if let Some(p) = protocol {
@@ -385,8 +482,9 @@ impl LinkKind {
             }
             Some("veth") => {
                 let peer = req_str(name, o, "peer")?;
-                warn_unknown_opts(name, o, &["peer"], warn);
-                LinkKind::Veth { peer }
+                let peer_netns = opt_str(o, "peer_netns");
+                warn_unknown_opts(name, o, &["peer", "peer_netns"], warn);
+                LinkKind::Veth { peer, peer_netns }
             }
             Some("vlan") => {
                 let link_dev = req_str(name, o, "link")?;
@@ -527,6 +625,87 @@ fn add_route(cfg: NetworkConfig, route: &RouteConfig) -> Result<NetworkConfig> {
     .map_err(|e| Error::InvalidMessage(format!("config: invalid route `{dst}`: {e}")))
 }
 
+fn add_rule(cfg: NetworkConfig, rule: &RuleConfig) -> Result<NetworkConfig> {
+    let prio = rule.priority;
+    let invalid = |what: String| Error::InvalidMessage(format!("config: rule {prio}: {what}"));
+
+    let table = match &rule.table {
+        None => None,
+        Some(t) => Some(parse_table(t).ok_or_else(|| invalid(format!("invalid table `{t}`")))?),
+    };
+    let action = match rule.action.as_deref() {
+        None | Some("lookup") => RuleActionPlan::Lookup,
+        Some("blackhole") => RuleActionPlan::Blackhole,
+        Some("unreachable") => RuleActionPlan::Unreachable,
+        Some("prohibit") => RuleActionPlan::Prohibit,
+        Some(other) => return Err(invalid(format!("unsupported action `{other}`"))),
+    };
+    let fwmark = match &rule.fwmark {
+        None => None,
+        Some(m) => {
+            let (mark, mask) = match m.split_once('/') {
+                Some((mark, mask)) => (mark, Some(mask)),
+                None => (m.as_str(), None),
+            };
+            let parse =
+                |v: &str| parse_u32_auto(v).ok_or_else(|| invalid(format!("invalid fwmark `{m}`")));
+            Some((parse(mark)?, mask.map(parse).transpose()?))
+        }
+    };
+    // `capture` writes `all` for a /0 selector.
+    let selector = |s: &Option<String>| s.clone().filter(|s| s != "all");
+    let (from, to) = (selector(&rule.from), selector(&rule.to));
+    let (iif, oif, ipv6) = (rule.iif.clone(), rule.oif.clone(), rule.ipv6);
+
+    cfg.rule(prio, move |mut r| {
+        if ipv6 {
+            r = r.ipv6();
+        }
+        if let Some(f) = &from {
+            r = r.from(f);
+        }
+        if let Some(t) = &to {
+            r = r.to(t);
+        }
+        match fwmark {
+            Some((mark, Some(mask))) => r = r.fwmark_mask(mark, mask),
+            Some((mark, None)) => r = r.fwmark(mark),
+            None => {}
+        }
+        if let Some(i) = &iif {
+            r = r.iif(i);
+        }
+        if let Some(o) = &oif {
+            r = r.oif(o);
+        }
+        if let Some(t) = table {
+            r = r.table(t);
+        }
+        match action {
+            RuleActionPlan::Lookup => r,
+            RuleActionPlan::Blackhole => r.blackhole(),
+            RuleActionPlan::Unreachable => r.unreachable(),
+            RuleActionPlan::Prohibit => r.prohibit(),
+        }
+    })
+    .map_err(|e| invalid(e.to_string()))
+}
+
+/// Parse a decimal or `0x`-prefixed hex number.
+fn parse_u32_auto(s: &str) -> Option<u32> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => s.parse().ok(),
+    }
+}
+
+enum RuleActionPlan {
+    Lookup,
+    Blackhole,
+    Unreachable,
+    Prohibit,
+}
+
 enum RouteTypePlan {
     Unicast,
     Blackhole,
@@ -659,7 +838,7 @@ fn parse_xmit_hash(link: &str, s: &str) -> Result<u8> {
 
 /// Map a routing-table name/number to its id. Mirrors the names
 /// `capture` emits.
-fn parse_table(s: &str) -> Option<u32> {
+pub(crate) fn parse_table(s: &str) -> Option<u32> {
     match s {
         "main" => Some(254),
         "default" => Some(253),
@@ -672,6 +851,7 @@ fn parse_table(s: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nlink::netlink::config::DeclaredLinkType;
 
     #[test]
     fn schema_round_trips_through_yaml() {
@@ -785,8 +965,109 @@ qdiscs:
 "#;
         let cfg: ConfigFile = serde_yaml::from_str(yaml).unwrap();
         let (_nc, warnings) = cfg.to_network_config().unwrap();
-        // bridge vlan_filtering option + rules + qdiscs => 3 warnings.
-        assert_eq!(warnings.len(), 3, "warnings: {warnings:?}");
+        // bridge vlan_filtering option + qdiscs => 2 warnings.
+        assert_eq!(warnings.len(), 2, "warnings: {warnings:?}");
+    }
+
+    #[test]
+    fn translates_rules_neighbors_and_sysctls() {
+        let yaml = r#"
+rules:
+  - priority: 100
+    from: 10.0.0.0/8
+    table: "100"
+  - priority: 200
+    fwmark: 0x100/0xff00
+    iif: eth1
+    ipv6: true
+    table: main
+  - priority: 300
+    from: all
+    to: 192.168.99.0/24
+    action: blackhole
+neighbors:
+  - dev: eth0
+    address: 10.0.0.1
+    lladdr: 02:00:00:00:00:01
+sysctls:
+  - dev: eth0
+    key: rp_filter
+    value: 2
+"#;
+        let cfg: ConfigFile = serde_yaml::from_str(yaml).unwrap();
+        let (nc, warnings) = cfg.to_network_config().unwrap();
+        assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
+        assert_eq!(nc.rules().len(), 3);
+        assert_eq!(nc.rules()[0].table(), 100);
+        assert_eq!(nc.rules()[1].fwmark(), Some(0x100));
+        assert_eq!(nc.rules()[1].fwmask(), Some(0xff00));
+        assert!(nc.rules()[1].is_ipv6());
+        assert_eq!(nc.rules()[2].from(), None);
+        assert_eq!(nc.neighbors().len(), 1);
+        assert_eq!(nc.sysctls()[0].key(), InterfaceSysctl::RpFilter);
+    }
+
+    #[test]
+    fn translates_namespaces_and_cross_namespace_veth() {
+        let yaml = r#"
+links:
+  - name: veth0
+    kind: veth
+    state: up
+    options:
+      peer: veth1
+      peer_netns: ns1
+namespaces:
+  - name: ns1
+    links:
+      - name: veth1
+        state: up
+    addresses:
+      - dev: veth1
+        address: 10.0.0.2/24
+    qdiscs:
+      - dev: veth1
+        parent: root
+        kind: netem
+"#;
+        let cfg: ConfigFile = serde_yaml::from_str(yaml).unwrap();
+        let (nc, warnings) = cfg.to_network_config().unwrap();
+        assert!(
+            matches!(
+                nc.links()[0].link_type(),
+                DeclaredLinkType::Veth { peer_netns: Some(ns), .. } if ns == "ns1"
+            ),
+            "{:?}",
+            nc.links()[0]
+        );
+        let [ns] = nc.namespaces() else {
+            panic!("expected one namespace");
+        };
+        assert_eq!(ns.name(), "ns1");
+        assert_eq!(ns.config().addresses().len(), 1);
+        // Warnings from inside a namespace say which one.
+        assert_eq!(warnings.len(), 1, "warnings: {warnings:?}");
+        assert!(warnings[0].starts_with("namespace ns1: "), "{warnings:?}");
+
+        let nested: ConfigFile =
+            serde_yaml::from_str("namespaces:\n  - name: a\n    namespaces:\n      - name: b\n")
+                .unwrap();
+        assert!(nested.to_network_config().is_err());
+    }
+
+    #[test]
+    fn invalid_rule_and_sysctl_are_errors() {
+        let cfg: ConfigFile = serde_yaml::from_str(
+            "rules:\n  - priority: 10\n    from: 10.0.0.0/8\n    to: fd00::/8\n",
+        )
+        .unwrap();
+        let e = cfg.to_network_config().unwrap_err();
+        assert!(e.to_string().contains("rule 10"), "got: {e}");
+
+        let cfg: ConfigFile =
+            serde_yaml::from_str("sysctls:\n  - dev: eth0\n    key: mtu\n    value: 1\n").unwrap();
+        let e = cfg.to_network_config().unwrap_err();
+        assert!(e.to_string().contains("unknown key `mtu`"), "got: {e}");
     }
 
     #[test]
@@ -807,4 +1088,54 @@ links:
         assert_eq!(nc.links().len(), 1);
         assert!(warnings.is_empty(), "unexpected warnings: {warnings:?}");
     }
+
+    #[tokio::test]
+    async fn owner_tagged_purge_leaves_unowned_routes_alone() -> Result<()> {
+        use nlink::netlink::{
+            Connection, Route, addr::Ipv4Address, config::DiffOptions, namespace, route::Ipv4Route,
+            types::route::RouteProtocol,
+        };
+
+        let ns = format!("nlink-cfg-owner-{}", std::process::id());
+        match namespace::create(&ns) {
+            Err(e) if e.is_permission_denied() => {
+                eprintln!("Skipping test: requires root");
+                return Ok(());
+            }
+            r => r?,
+        }
+        let result = async {
+            let conn: Connection<Route> = namespace::connection_for(&ns)?;
+            conn.set_link_up("lo").await?;
+            conn.add_address(Ipv4Address::new("lo", "10.0.0.1".parse().unwrap(), 24))
+                .await?;
+            for (dst, proto) in [("10.1.0.0", RouteProtocol::Static), ("10.2.0.0", 42.into())] {
+                conn.add_route(Ipv4Route::new(dst, 16).dev("lo").protocol(proto))
+                    .await?;
+            }
+            let yaml = r#"
+owner: 42
+addresses:
+  - dev: lo
+    address: 10.0.0.1/24
+"#;
+            let file: ConfigFile = serde_yaml::from_str(yaml).unwrap();
+            let (cfg, _) = file.to_network_config()?;
+            cfg.diff_with_options(&conn, DiffOptions::default().purge(true))
+                .await
+        }
+        .await;
+        namespace::delete(&ns)?;
+        let diff = result?;
+
+        // Only the tag-42 route goes; the static one isn't ours.
+        let removed: Vec<_> = diff
+            .routes_to_remove
+            .iter()
+            .map(|r| (r.destination().to_string(), r.prefix_len()))
+            .collect();
+        assert_eq!(removed, [("10.2.0.0".to_string(), 16)]);
+        assert!(diff.addresses_to_remove.is_empty());
+        Ok(())
+    }
 }
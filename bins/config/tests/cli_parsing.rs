@@ -33,7 +33,8 @@ fn help_lists_all_subcommands() {
         .stdout(predicate::str::contains("capture"))
         .stdout(predicate::str::contains("example"))
         .stdout(predicate::str::contains("diff"))
-        .stdout(predicate::str::contains("apply"));
+        .stdout(predicate::str::contains("apply"))
+        .stdout(predicate::str::contains("watch"));
 }
 
 #[test]
@@ -47,7 +48,7 @@ fn invalid_subcommand_fails() {
 
 #[test]
 fn every_subcommand_help_builds() {
-    for sub in ["capture", "example", "diff", "apply"] {
+    for sub in ["capture", "example", "diff", "apply", "watch"] {
         config_cmd().args([sub, "--help"]).assert().success();
     }
 }
@@ -75,6 +76,50 @@ fn apply_dry_run_conflicts_with_reconcile() {
         .stderr(predicate::str::contains("cannot be used with"));
 }
 
+/// `--purge` removes resources, which the retrying `--reconcile` path
+/// doesn't model; clap rejects the combination.
+#[test]
+fn apply_purge_conflicts_with_reconcile() {
+    config_cmd()
+        .args(["apply", "--purge", "--reconcile", "/nonexistent.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn apply_help_documents_apply_flags() {
+    config_cmd()
+        .args(["apply", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--purge"))
+        .stdout(predicate::str::contains("--dry-run"))
+        .stdout(predicate::str::contains("--no-rollback"));
+}
+
+/// `capture --only` takes known section names; a typo is a parse
+/// error rather than an empty capture.
+#[test]
+fn capture_rejects_unknown_section() {
+    config_cmd()
+        .args(["capture", "--only", "links,adresses"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
+/// A zero `--interval` would spin; clap rejects it before any file
+/// or socket is touched.
+#[test]
+fn watch_rejects_zero_interval() {
+    config_cmd()
+        .args(["watch", "--interval", "0", "/nonexistent.yaml"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("error"));
+}
+
 /// `example` is self-contained (no kernel access) — it should emit a
 /// non-empty sample configuration.
 #[test]
@@ -0,0 +1,367 @@
+//! Alert sinks for `watch`: POST a JSON payload to a webhook or run a
+//! command when an issue appears or clears.
+//!
+//! Alerts follow scan state rather than individual events: `watch`
+//! rescans after each event (and periodically), and [`AlertState`]
+//! diffs the issue set against the previous scan.
+
+use std::{
+    collections::HashMap,
+    process::Stdio,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use nlink::netlink::diagnostics::{Issue, IssueCategory};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    process::Command,
+};
+
+/// How long one delivery may take before it is abandoned.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether an issue started or stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertEvent {
+    Raised,
+    Cleared,
+}
+
+impl AlertEvent {
+    fn as_str(self) -> &'static str {
+        match self {
+            AlertEvent::Raised => "raised",
+            AlertEvent::Cleared => "cleared",
+        }
+    }
+}
+
+/// Identity of an issue across scans. Messages carry live numbers
+/// (loss rates, backlog bytes), so they only disambiguate issues that
+/// aren't tied to an interface.
+type IssueKey = (IssueCategory, Option<String>, Option<String>);
+
+fn issue_key(issue: &Issue) -> IssueKey {
+    let message = issue.interface.is_none().then(|| issue.message.clone());
+    (issue.category, issue.interface.clone(), message)
+}
+
+/// Issues active as of the last scan.
+#[derive(Default)]
+pub struct AlertState {
+    active: HashMap<IssueKey, Issue>,
+}
+
+impl AlertState {
+    /// Replace the active set with `issues`, returning what was raised
+    /// and what cleared since the last update.
+    pub fn update(&mut self, issues: Vec<Issue>) -> Vec<(AlertEvent, Issue)> {
+        let mut next = HashMap::new();
+        let mut events = Vec::new();
+        for issue in issues {
+            let key = issue_key(&issue);
+            if next.contains_key(&key) {
+                continue;
+            }
+            if !self.active.contains_key(&key) {
+                events.push((AlertEvent::Raised, issue.clone()));
+            }
+            next.insert(key, issue);
+        }
+        for (key, issue) in self.active.drain() {
+            if !next.contains_key(&key) {
+                events.push((AlertEvent::Cleared, issue));
+            }
+        }
+        self.active = next;
+        events
+    }
+}
+
+/// An `http://` webhook endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+/// Parse a webhook URL. Only plain HTTP is supported; for HTTPS, use an
+/// `--exec` sink with `curl`.
+pub fn parse_webhook(s: &str) -> Result<Webhook, String> {
+    let rest = s.strip_prefix("http://").ok_or_else(|| {
+        if s.starts_with("https://") {
+            "https webhooks are not supported; use --exec with curl".to_string()
+        } else {
+            format!("webhook URL must start with http://: {s}")
+        }
+    })?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    // `[v6]:port`, `host:port` or a bare host.
+    let (host, port) = if let Some(v6) = authority.strip_prefix('[') {
+        let (host, tail) = v6
+            .split_once(']')
+            .ok_or_else(|| format!("invalid webhook host: {authority}"))?;
+        (host, tail.strip_prefix(':'))
+    } else {
+        match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        }
+    };
+    if host.is_empty() {
+        return Err(format!("missing webhook host: {s}"));
+    }
+    let port = match port {
+        Some(p) => p
+            .parse()
+            .map_err(|_| format!("invalid webhook port: {p}"))?,
+        None => 80,
+    };
+    Ok(Webhook {
+        host: host.to_string(),
+        port,
+        path: path.to_string(),
+    })
+}
+
+/// Configured sinks.
+#[derive(Clone, Default)]
+pub struct AlertSinks {
+    pub webhooks: Vec<Webhook>,
+    pub commands: Vec<String>,
+}
+
+impl AlertSinks {
+    pub fn is_empty(&self) -> bool {
+        self.webhooks.is_empty() && self.commands.is_empty()
+    }
+
+    /// Deliver one alert to every sink in the background. Failures are
+    /// reported on stderr and never stop the watch.
+    pub fn notify(&self, event: AlertEvent, issue: &Issue) {
+        let payload = payload(event, issue);
+        let body = serde_json::to_string(&payload).expect("JSON serialization");
+
+        for hook in &self.webhooks {
+            let (hook, body) = (hook.clone(), body.clone());
+            tokio::spawn(async move {
+                match tokio::time::timeout(DELIVERY_TIMEOUT, post(&hook, &body)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => {
+                        eprintln!("warning: webhook {}:{}: {}", hook.host, hook.port, e)
+                    }
+                    Err(_) => eprintln!("warning: webhook {}:{}: timed out", hook.host, hook.port),
+                }
+            });
+        }
+
+        for cmd in &self.commands {
+            let (cmd, body) = (cmd.clone(), body.clone());
+            let env = [
+                ("NLINK_ALERT_EVENT", event.as_str().to_string()),
+                ("NLINK_ALERT_SEVERITY", issue.severity.to_string()),
+                ("NLINK_ALERT_CATEGORY", issue.category.to_string()),
+                (
+                    "NLINK_ALERT_INTERFACE",
+                    issue.interface.clone().unwrap_or_default(),
+                ),
+                ("NLINK_ALERT_MESSAGE", issue.message.clone()),
+            ];
+            tokio::spawn(async move {
+                match tokio::time::timeout(DELIVERY_TIMEOUT, exec(&cmd, env, &body)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => eprintln!("warning: alert command `{}`: {}", cmd, e),
+                    Err(_) => eprintln!("warning: alert command `{}`: timed out", cmd),
+                }
+            });
+        }
+    }
+}
+
+/// The JSON body sent to webhooks and piped to commands.
+fn payload(event: AlertEvent, issue: &Issue) -> serde_json::Value {
+    let host = std::fs::read_to_string("/proc/sys/kernel/hostname")
+        .ok()
+        .map(|h| h.trim().to_string());
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    serde_json::json!({
+        "event": event.as_str(),
+        "host": host,
+        "timestamp": timestamp,
+        "severity": issue.severity.to_string(),
+        "category": issue.category.to_string(),
+        "message": issue.message,
+        "details": issue.details,
+        "interface": issue.interface,
+    })
+}
+
+async fn post(hook: &Webhook, body: &str) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect((hook.host.as_str(), hook.port)).await?;
+    let host = if hook.host.contains(':') {
+        format!("[{}]:{}", hook.host, hook.port)
+    } else {
+        format!("{}:{}", hook.host, hook.port)
+    };
+    let request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: nlink-diag\r\n\
+         Content-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        hook.path,
+        host,
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await?;
+    let status = String::from_utf8_lossy(&response);
+    let status = status.lines().next().unwrap_or_default();
+    match status.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(()),
+        _ => Err(std::io::Error::other(format!(
+            "unexpected response: {status:?}"
+        ))),
+    }
+}
+
+async fn exec(cmd: &str, env: [(&str, String); 5], body: &str) -> std::io::Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .envs(env)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that ignores stdin may exit before reading it.
+        let _ = stdin.write_all(body.as_bytes()).await;
+    }
+    let status = child.wait().await?;
+    if !status.success() {
+        return Err(std::io::Error::other(format!("exited with {status}")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use nlink::netlink::diagnostics::Severity;
+
+    use super::*;
+
+    fn issue(category: IssueCategory, interface: Option<&str>, message: &str) -> Issue {
+        Issue {
+            severity: Severity::Warning,
+            category,
+            message: message.into(),
+            details: None,
+            interface: interface.map(Into::into),
+            timestamp: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn state_reports_raised_and_cleared() {
+        let mut state = AlertState::default();
+        let events = state.update(vec![
+            issue(IssueCategory::NoCarrier, Some("eth0"), "No carrier on eth0"),
+            issue(
+                IssueCategory::HighPacketLoss,
+                Some("eth1"),
+                "1.00% packet loss on eth1",
+            ),
+        ]);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|(e, _)| *e == AlertEvent::Raised));
+
+        // A changed loss rate is the same issue; the carrier came back.
+        let events = state.update(vec![issue(
+            IssueCategory::HighPacketLoss,
+            Some("eth1"),
+            "2.50% packet loss on eth1",
+        )]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, AlertEvent::Cleared);
+        assert_eq!(events[0].1.category, IssueCategory::NoCarrier);
+
+        assert!(
+            state
+                .update(vec![issue(
+                    IssueCategory::HighPacketLoss,
+                    Some("eth1"),
+                    "3.00% packet loss on eth1",
+                )])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn state_keys_interfaceless_issues_by_message() {
+        let mut state = AlertState::default();
+        let a = issue(
+            IssueCategory::ConflictingRoute,
+            None,
+            "10.0.0.0/8 via a or b",
+        );
+        let b = issue(
+            IssueCategory::ConflictingRoute,
+            None,
+            "10.1.0.0/16 via c or d",
+        );
+        assert_eq!(state.update(vec![a.clone(), b]).len(), 2);
+
+        let events = state.update(vec![a]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, AlertEvent::Cleared);
+        assert_eq!(events[0].1.message, "10.1.0.0/16 via c or d");
+    }
+
+    #[test]
+    fn webhook_urls() {
+        assert_eq!(
+            parse_webhook("http://alerts.example:8080/hook?x=1").unwrap(),
+            Webhook {
+                host: "alerts.example".into(),
+                port: 8080,
+                path: "/hook?x=1".into(),
+            }
+        );
+        assert_eq!(
+            parse_webhook("http://[2001:db8::1]/").unwrap(),
+            Webhook {
+                host: "2001:db8::1".into(),
+                port: 80,
+                path: "/".into(),
+            }
+        );
+        assert_eq!(parse_webhook("http://192.0.2.5").unwrap().path, "/");
+        assert!(parse_webhook("https://alerts.example/").is_err());
+        assert!(parse_webhook("alerts.example/hook").is_err());
+        assert!(parse_webhook("http://host:notaport/").is_err());
+    }
+
+    #[test]
+    fn payload_fields() {
+        let p = payload(
+            AlertEvent::Cleared,
+            &issue(IssueCategory::NoCarrier, Some("eth0"), "No carrier on eth0"),
+        );
+        assert_eq!(p["event"], "cleared");
+        assert_eq!(p["severity"], "WARN");
+        assert_eq!(p["category"], "NoCarrier");
+        assert_eq!(p["interface"], "eth0");
+    }
+}
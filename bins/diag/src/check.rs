@@ -1,21 +1,65 @@
 //! Check command - check connectivity to a destination.
 
-use std::net::IpAddr;
+use std::{net::IpAddr, time::Duration};
 
 use clap::Args;
 use nlink::netlink::{Connection, Result, Route, diagnostics::Diagnostics};
 
+use crate::probe::{self, ProbeOptions};
+
 #[derive(Args)]
 pub struct CheckArgs {
     /// Destination IP address
     pub destination: IpAddr,
+
+    /// Number of ICMP echo requests to send
+    #[arg(short, long, default_value = "3")]
+    pub count: u32,
+
+    /// Interval between echo requests in milliseconds
+    #[arg(long, default_value = "1000")]
+    pub interval: u64,
+
+    /// Time to wait for each echo reply in milliseconds
+    #[arg(long, default_value = "1000")]
+    pub timeout: u64,
+
+    /// Time to wait for the gateway to answer ARP/NDP in milliseconds
+    #[arg(long, default_value = "3000")]
+    pub neigh_timeout: u64,
+
+    /// Only look up the route; send no probes
+    #[arg(long)]
+    pub no_probe: bool,
+
+    /// Skip the path MTU probe
+    #[arg(long)]
+    pub no_pmtu: bool,
 }
 
 pub async fn run(args: CheckArgs, json: bool) -> Result<()> {
     let conn = Connection::<Route>::new()?;
     let diag = Diagnostics::new(conn);
 
-    let report = diag.check_connectivity(args.destination).await?;
+    let mut report = diag.check_connectivity(args.destination).await?;
+
+    let probe = if args.no_probe {
+        None
+    } else {
+        diag.probe_gateway(&mut report, Duration::from_millis(args.neigh_timeout))
+            .await?;
+
+        let opts = ProbeOptions {
+            count: args.count,
+            interval: Duration::from_millis(args.interval),
+            timeout: Duration::from_millis(args.timeout),
+            pmtu: !args.no_pmtu,
+        };
+        let conn = Connection::<Route>::new()?;
+        let probe = probe::probe(&conn, args.destination, &opts).await?;
+        probe.add_issues(&mut report);
+        Some(probe)
+    };
 
     if json {
         let output = serde_json::json!({
@@ -31,6 +75,7 @@ pub async fn run(args: CheckArgs, json: bool) -> Result<()> {
             "output_interface": report.output_interface,
             "gateway": report.gateway.map(|g| g.to_string()),
             "gateway_reachable": report.gateway_reachable,
+            "probe": probe.as_ref().map(|p| p.to_json()),
             "issues": report.issues.iter().map(|i| {
                 serde_json::json!({
                     "severity": format!("{:?}", i.severity),
@@ -80,6 +125,11 @@ pub async fn run(args: CheckArgs, json: bool) -> Result<()> {
 
         println!();
 
+        if let Some(ref probe) = probe {
+            probe.print();
+            println!();
+        }
+
         if report.issues.is_empty() {
             println!("Status: OK - No connectivity issues detected");
         } else {
@@ -8,7 +8,8 @@ use nlink::{
         Connection, Result, Route,
         diagnostics::{Diagnostics, Severity},
     },
-    output::formatting::{format_bytes, format_rate_bps},
+    output::formatting::format_bytes,
+    util::format_rate,
 };
 
 #[derive(Args)]
@@ -129,12 +130,12 @@ pub async fn run(args: InterfaceArgs, json: bool) -> Result<()> {
                 println!("Rates ({}ms sample):", iface.rates.sample_duration_ms);
                 println!(
                     "  RX: {} ({} pps)",
-                    format_rate_bps(iface.rates.rx_bps * 8),
+                    format_rate(iface.rates.rx_bps * 8),
                     iface.rates.rx_pps
                 );
                 println!(
                     "  TX: {} ({} pps)",
-                    format_rate_bps(iface.rates.tx_bps * 8),
+                    format_rate(iface.rates.tx_bps * 8),
                     iface.rates.tx_pps
                 );
                 println!();
@@ -153,7 +154,7 @@ pub async fn run(args: InterfaceArgs, json: bool) -> Result<()> {
                     println!("  Queue: {} packets, {} bytes backlog", tc.qlen, tc.backlog);
                     println!(
                         "  Rate: {} ({} pps)",
-                        format_rate_bps(tc.rate_bps * 8),
+                        format_rate(tc.rate_bps * 8),
                         tc.rate_pps
                     );
                     println!();
@@ -2,8 +2,10 @@
 //!
 //! Provides network diagnostics and issue detection.
 
+mod alert;
 mod check;
 mod interface;
+mod probe;
 mod scan;
 mod watch;
 
@@ -0,0 +1,775 @@
+//! Active path probes behind `check`: next-hop neighbor resolution,
+//! ICMP echo, and a path MTU search.
+//!
+//! The route lookup in `Diagnostics::check_connectivity` only reads
+//! kernel state. These probes send traffic, so they can tell where a
+//! path actually breaks: at the output link, at the next hop's ARP/ND,
+//! somewhere beyond it, or only for large packets.
+
+use std::{
+    io,
+    net::{IpAddr, SocketAddr, SocketAddrV6, UdpSocket},
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    time::{Duration, Instant},
+};
+
+use nlink::netlink::{
+    Connection, Result, Route,
+    diagnostics::{ConnectivityReport, Issue, IssueCategory, Severity},
+    types::{neigh::NeighborState, route::RouteType},
+};
+use tokio::io::unix::AsyncFd;
+
+/// How long the next hop gets to answer ARP/ND (three default
+/// retransmits).
+const NEIGHBOR_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Probe settings, from the `check` arguments.
+pub struct ProbeOptions {
+    /// Echo requests to send.
+    pub count: u32,
+    /// Time between echo requests.
+    pub interval: Duration,
+    /// How long to wait for each reply.
+    pub timeout: Duration,
+    /// Search for the path MTU.
+    pub pmtu: bool,
+}
+
+/// Result of probing the path to one destination.
+pub struct PathProbe {
+    pub destination: IpAddr,
+    /// Output link name, MTU and whether it is up; `None` without a route.
+    pub link: Option<(String, Option<u32>, bool)>,
+    /// Gateway, or the destination itself on a connected route.
+    pub nexthop: Option<IpAddr>,
+    pub neighbor: NeighborResult,
+    pub echo: Option<EchoSummary>,
+    pub pmtu: Option<u32>,
+}
+
+/// Outcome of resolving the next hop's link-layer address.
+pub enum NeighborResult {
+    /// The link has no neighbor resolution, or the route is local.
+    NotNeeded,
+    /// Not probed: no route or the link is down.
+    Skipped,
+    /// Resolved; `confirmed` when the entry was (re)confirmed reachable
+    /// during the probe rather than left over from earlier traffic.
+    Resolved { lladdr: String, confirmed: bool },
+    /// No ARP/ND reply.
+    Failed,
+}
+
+/// ICMP echo results.
+pub struct EchoSummary {
+    pub sent: u32,
+    pub rtts: Vec<Duration>,
+    /// The last send/receive error (host unreachable, ...), if any.
+    pub error: Option<String>,
+}
+
+impl EchoSummary {
+    fn received(&self) -> u32 {
+        self.rtts.len() as u32
+    }
+}
+
+pub async fn probe(
+    conn: &Connection<Route>,
+    dest: IpAddr,
+    opts: &ProbeOptions,
+) -> Result<PathProbe> {
+    let mut probe = PathProbe {
+        destination: dest,
+        link: None,
+        nexthop: None,
+        neighbor: NeighborResult::Skipped,
+        echo: None,
+        pmtu: None,
+    };
+
+    // Ask the kernel which route it would use, rather than matching the
+    // table ourselves.
+    let route = match dest {
+        IpAddr::V4(v4) => conn.get_route_v4(v4, 32).await?,
+        IpAddr::V6(v6) => conn.get_route_v6(v6, 128).await?,
+    };
+    let Some(route) = route else {
+        return Ok(probe);
+    };
+    let local = route.route_type() == RouteType::Local;
+    let oif = route.oif();
+    let link = match oif {
+        Some(idx) => conn.get_link_by_index(idx).await?,
+        None => None,
+    };
+    if let Some(link) = &link {
+        probe.link = Some((
+            link.name().unwrap_or("?").to_string(),
+            link.mtu(),
+            link.is_up(),
+        ));
+        if !link.is_up() {
+            return Ok(probe);
+        }
+    }
+
+    let nexthop = route.gateway().copied().unwrap_or(dest);
+    probe.nexthop = Some(nexthop);
+    probe.neighbor = match (&link, oif) {
+        (Some(link), Some(oif)) if !local && link.link_flags().is_arp() => {
+            resolve_neighbor(conn, nexthop, oif).await?
+        }
+        _ => NeighborResult::NotNeeded,
+    };
+    if matches!(probe.neighbor, NeighborResult::Failed) {
+        return Ok(probe);
+    }
+
+    let scope = oif.unwrap_or(0);
+    let socket = match IcmpSocket::open(dest, scope) {
+        Ok(s) => s,
+        Err(e) => {
+            probe.echo = Some(EchoSummary {
+                sent: 0,
+                rtts: Vec::new(),
+                error: Some(format!("cannot open ICMP socket: {e}")),
+            });
+            return Ok(probe);
+        }
+    };
+
+    let mut seq = 0u16;
+    let mut echo = EchoSummary {
+        sent: 0,
+        rtts: Vec::new(),
+        error: None,
+    };
+    for i in 0..opts.count {
+        if i > 0 {
+            tokio::time::sleep(opts.interval).await;
+        }
+        seq = seq.wrapping_add(1);
+        echo.sent += 1;
+        match socket.echo(seq, 56, opts.timeout).await {
+            Ok(Some(rtt)) => echo.rtts.push(rtt),
+            Ok(None) => {}
+            Err(e) => echo.error = Some(e.to_string()),
+        }
+    }
+    let answered = echo.received() > 0;
+    probe.echo = Some(echo);
+
+    if opts.pmtu
+        && answered
+        && let Some((_, Some(mtu), _)) = probe.link
+    {
+        probe.pmtu = socket.path_mtu(mtu, &mut seq, opts.timeout).await;
+    }
+
+    Ok(probe)
+}
+
+/// Make the kernel resolve `nexthop` by sending it an empty UDP
+/// datagram (to the discard port), then watch the neighbor table.
+async fn resolve_neighbor(
+    conn: &Connection<Route>,
+    nexthop: IpAddr,
+    oif: u32,
+) -> Result<NeighborResult> {
+    let target = match nexthop {
+        IpAddr::V4(_) => SocketAddr::new(nexthop, 9),
+        IpAddr::V6(v6) => SocketAddr::V6(SocketAddrV6::new(v6, 9, 0, oif)),
+    };
+    let bind: SocketAddr = match nexthop {
+        IpAddr::V4(_) => "0.0.0.0:0".parse().unwrap(),
+        IpAddr::V6(_) => "[::]:0".parse().unwrap(),
+    };
+    // A failed send (no route, ...) shows up as an unresolved entry.
+    if let Ok(sock) = UdpSocket::bind(bind) {
+        let _ = sock.send_to(&[], target);
+    }
+
+    let deadline = Instant::now() + NEIGHBOR_TIMEOUT;
+    let mut last = None;
+    loop {
+        let entry = conn
+            .get_neighbors_by_index(oif)
+            .await?
+            .into_iter()
+            .find(|n| n.destination() == Some(&nexthop));
+        if let Some(n) = &entry {
+            let lladdr = n.mac_address().unwrap_or_else(|| "-".to_string());
+            match n.state() {
+                NeighborState::Reachable | NeighborState::Permanent | NeighborState::Noarp => {
+                    return Ok(NeighborResult::Resolved {
+                        lladdr,
+                        confirmed: true,
+                    });
+                }
+                // Resolved earlier; the kernel re-probes it only after
+                // the delay timer, which outlasts this probe.
+                NeighborState::Stale | NeighborState::Delay | NeighborState::Probe => {
+                    last = Some(lladdr);
+                }
+                _ => last = None,
+            }
+        }
+        if Instant::now() >= deadline {
+            return Ok(match last {
+                Some(lladdr) => NeighborResult::Resolved {
+                    lladdr,
+                    confirmed: false,
+                },
+                None => NeighborResult::Failed,
+            });
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// A connected ICMP echo socket: an unprivileged ping socket where
+/// `net.ipv4.ping_group_range` allows it, a raw socket otherwise.
+struct IcmpSocket {
+    fd: AsyncFd<OwnedFd>,
+    v6: bool,
+    raw: bool,
+    id: u16,
+}
+
+impl IcmpSocket {
+    fn open(dest: IpAddr, scope: u32) -> io::Result<Self> {
+        let v6 = dest.is_ipv6();
+        let (domain, proto) = if v6 {
+            (libc::AF_INET6, libc::IPPROTO_ICMPV6)
+        } else {
+            (libc::AF_INET, libc::IPPROTO_ICMP)
+        };
+        let flags = libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC;
+
+        let mut raw = false;
+        // SAFETY: plain socket(2) calls; the fd is owned below.
+        let mut fd = unsafe { libc::socket(domain, libc::SOCK_DGRAM | flags, proto) };
+        if fd < 0 {
+            raw = true;
+            fd = unsafe { libc::socket(domain, libc::SOCK_RAW | flags, proto) };
+        }
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: a fresh fd nothing else owns.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // Set DF and never fragment locally, so oversized probes fail
+        // instead of being split.
+        let (level, opt, val) = if v6 {
+            (
+                libc::IPPROTO_IPV6,
+                libc::IPV6_MTU_DISCOVER,
+                libc::IPV6_PMTUDISC_DO,
+            )
+        } else {
+            (
+                libc::IPPROTO_IP,
+                libc::IP_MTU_DISCOVER,
+                libc::IP_PMTUDISC_DO,
+            )
+        };
+        setsockopt_int(&fd, level, opt, val)?;
+
+        // Connecting filters replies to those from `dest`.
+        connect(&fd, dest, scope)?;
+
+        Ok(Self {
+            fd: AsyncFd::new(fd)?,
+            v6,
+            raw,
+            id: std::process::id() as u16,
+        })
+    }
+
+    /// Send one echo request of `len` ICMP bytes and wait for its reply.
+    /// `Ok(None)` on timeout or when the packet is too big to leave.
+    async fn echo(&self, seq: u16, len: usize, timeout: Duration) -> io::Result<Option<Duration>> {
+        let packet = echo_request(self.v6, self.id, seq, len);
+        let start = Instant::now();
+        // SAFETY: sending from a live buffer on a socket we own.
+        let n = unsafe { libc::send(self.fd.as_raw_fd(), packet.as_ptr().cast(), packet.len(), 0) };
+        if n < 0 {
+            let e = io::Error::last_os_error();
+            return match e.raw_os_error() {
+                Some(libc::EMSGSIZE) => Ok(None),
+                _ => Err(e),
+            };
+        }
+
+        let wait = async {
+            let mut buf = [0u8; 65536];
+            loop {
+                let mut guard = self.fd.readable().await?;
+                let res = guard.try_io(|fd| {
+                    // SAFETY: receiving into a live buffer of the given length.
+                    let n = unsafe {
+                        libc::recv(fd.as_raw_fd(), buf.as_mut_ptr().cast(), buf.len(), 0)
+                    };
+                    if n < 0 {
+                        Err(io::Error::last_os_error())
+                    } else {
+                        Ok(n as usize)
+                    }
+                });
+                let n = match res {
+                    Ok(r) => r?,
+                    Err(_would_block) => continue,
+                };
+                if let Some((id, reply_seq)) = parse_echo_reply(&buf[..n], self.v6, self.raw)
+                    && reply_seq == seq
+                    // Ping sockets rewrite the id; raw sockets see every
+                    // echo reply on the host.
+                    && (!self.raw || id == self.id)
+                {
+                    return Ok::<_, io::Error>(start.elapsed());
+                }
+            }
+        };
+        match tokio::time::timeout(timeout, wait).await {
+            Ok(Ok(rtt)) => Ok(Some(rtt)),
+            Ok(Err(e)) if e.raw_os_error() == Some(libc::EMSGSIZE) => Ok(None),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Binary-search the largest packet (IP header included) that gets
+    /// an echo reply, up to the link MTU. `None` if even the protocol
+    /// minimum goes unanswered.
+    async fn path_mtu(&self, link_mtu: u32, seq: &mut u16, timeout: Duration) -> Option<u32> {
+        let (overhead, min) = if self.v6 { (40, 1280) } else { (20, 576) };
+        let mut fits = |size: u32| {
+            *seq = seq.wrapping_add(1);
+            let seq = *seq;
+            async move {
+                matches!(
+                    self.echo(seq, (size - overhead) as usize, timeout).await,
+                    Ok(Some(_))
+                )
+            }
+        };
+
+        // Loopback's 64k MTU is above the largest IP packet.
+        if fits(link_mtu.min(65535)).await {
+            return Some(link_mtu);
+        }
+        let (mut good, mut bad) = (min.min(link_mtu), link_mtu.min(65535));
+        if !fits(good).await {
+            return None;
+        }
+        while bad - good > 1 {
+            let mid = good + (bad - good) / 2;
+            if fits(mid).await {
+                good = mid;
+            } else {
+                bad = mid;
+            }
+        }
+        Some(good)
+    }
+}
+
+fn setsockopt_int(fd: &OwnedFd, level: i32, opt: i32, val: i32) -> io::Result<()> {
+    // SAFETY: passing a live i32 with its size.
+    let rc = unsafe {
+        libc::setsockopt(
+            fd.as_raw_fd(),
+            level,
+            opt,
+            (&val as *const i32).cast(),
+            size_of::<i32>() as libc::socklen_t,
+        )
+    };
+    if rc < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn connect(fd: &OwnedFd, dest: IpAddr, scope: u32) -> io::Result<()> {
+    // SAFETY: zeroed sockaddr structs are valid; we fill in the fields
+    // connect(2) reads and pass the matching length.
+    let rc = unsafe {
+        match dest {
+            IpAddr::V4(v4) => {
+                let mut sa: libc::sockaddr_in = std::mem::zeroed();
+                sa.sin_family = libc::AF_INET as libc::sa_family_t;
+                sa.sin_addr.s_addr = u32::from_ne_bytes(v4.octets());
+                libc::connect(
+                    fd.as_raw_fd(),
+                    (&sa as *const libc::sockaddr_in).cast(),
+                    size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                )
+            }
+            IpAddr::V6(v6) => {
+                let mut sa: libc::sockaddr_in6 = std::mem::zeroed();
+                sa.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+                sa.sin6_addr.s6_addr = v6.octets();
+                sa.sin6_scope_id = scope;
+                libc::connect(
+                    fd.as_raw_fd(),
+                    (&sa as *const libc::sockaddr_in6).cast(),
+                    size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+                )
+            }
+        }
+    };
+    if rc < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// An ICMP/ICMPv6 echo request of `len` bytes (header included). The
+/// kernel fills in the ICMPv6 checksum; ICMPv4 needs ours.
+fn echo_request(v6: bool, id: u16, seq: u16, len: usize) -> Vec<u8> {
+    let mut p = vec![0u8; len.max(8)];
+    p[0] = if v6 { 128 } else { 8 };
+    p[4..6].copy_from_slice(&id.to_be_bytes());
+    p[6..8].copy_from_slice(&seq.to_be_bytes());
+    for (i, b) in p[8..].iter_mut().enumerate() {
+        *b = i as u8;
+    }
+    if !v6 {
+        let sum = checksum(&p);
+        p[2..4].copy_from_slice(&sum.to_be_bytes());
+    }
+    p
+}
+
+/// `(id, seq)` of an echo reply. Raw IPv4 sockets deliver the IP
+/// header too.
+fn parse_echo_reply(buf: &[u8], v6: bool, raw: bool) -> Option<(u16, u16)> {
+    let icmp = if raw && !v6 {
+        buf.get(usize::from(buf.first()? & 0x0f) * 4..)?
+    } else {
+        buf
+    };
+    if icmp.len() < 8 || icmp[0] != if v6 { 129 } else { 0 } {
+        return None;
+    }
+    Some((
+        u16::from_be_bytes([icmp[4], icmp[5]]),
+        u16::from_be_bytes([icmp[6], icmp[7]]),
+    ))
+}
+
+/// RFC 1071 Internet checksum.
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum: u32 = data
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)])))
+        .sum();
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+impl PathProbe {
+    fn nd(&self) -> &'static str {
+        if self.destination.is_ipv6() {
+            "ND"
+        } else {
+            "ARP"
+        }
+    }
+
+    /// Where the path stops working, if it does.
+    pub fn breaks_at(&self) -> Option<String> {
+        let (dev, _, up) = self.link.as_ref()?;
+        if !up {
+            return Some(format!("output interface {dev} (down)"));
+        }
+        let nexthop = self.nexthop?;
+        if matches!(self.neighbor, NeighborResult::Failed) {
+            return Some(format!(
+                "next hop {nexthop} on {dev} (no {} reply)",
+                self.nd()
+            ));
+        }
+        let echo = self.echo.as_ref()?;
+        if echo.received() == 0 {
+            return Some(match &echo.error {
+                Some(e) => format!("beyond next hop {nexthop} ({e})"),
+                None if nexthop == self.destination => {
+                    format!("{nexthop} (resolves but doesn't answer echo)")
+                }
+                None => format!("beyond next hop {nexthop} (no echo reply)"),
+            });
+        }
+        None
+    }
+
+    /// Fold the probe results into the report's issues. Replaces the
+    /// neighbor-cache guess about the gateway with what the probe saw.
+    pub fn add_issues(&self, report: &mut ConnectivityReport) {
+        let timestamp = Instant::now();
+        let dev = self.link.as_ref().map(|(d, _, _)| d.clone());
+        let issue = |severity, category, message: String, details: Option<String>| Issue {
+            severity,
+            category,
+            message,
+            details,
+            interface: dev.clone(),
+            timestamp,
+        };
+
+        match &self.neighbor {
+            NeighborResult::Resolved { .. } => {
+                report
+                    .issues
+                    .retain(|i| i.category != IssueCategory::Unreachable);
+                report.gateway_reachable = true;
+            }
+            NeighborResult::Failed => {
+                report
+                    .issues
+                    .retain(|i| i.category != IssueCategory::Unreachable);
+                report.gateway_reachable = false;
+                report.issues.push(issue(
+                    Severity::Error,
+                    IssueCategory::Unreachable,
+                    format!(
+                        "No {} reply from next hop {}",
+                        self.nd(),
+                        self.nexthop.map(|n| n.to_string()).unwrap_or_default()
+                    ),
+                    Some("Check that the next hop is up and on the same link and subnet".into()),
+                ));
+            }
+            NeighborResult::NotNeeded | NeighborResult::Skipped => {}
+        }
+
+        if let Some(echo) = &self.echo {
+            if echo.received() == 0 {
+                report.issues.push(issue(
+                    Severity::Error,
+                    IssueCategory::Unreachable,
+                    format!(
+                        "No echo reply from {} ({} sent)",
+                        self.destination, echo.sent
+                    ),
+                    Some(echo.error.clone().unwrap_or_else(|| {
+                        "The next hop resolves, so the path breaks beyond it, or ICMP echo \
+                         is filtered"
+                            .into()
+                    })),
+                ));
+            } else if echo.received() < echo.sent {
+                report.issues.push(issue(
+                    Severity::Warning,
+                    IssueCategory::HighPacketLoss,
+                    format!(
+                        "{:.0}% echo loss to {}",
+                        f64::from(echo.sent - echo.received()) * 100.0 / f64::from(echo.sent),
+                        self.destination
+                    ),
+                    Some(format!("{} of {} replies", echo.received(), echo.sent)),
+                ));
+            }
+        }
+
+        if let (Some(pmtu), Some((dev, Some(mtu), _))) = (self.pmtu, &self.link)
+            && pmtu < *mtu
+        {
+            report.issues.push(issue(
+                Severity::Warning,
+                IssueCategory::MtuIssue,
+                format!("Path MTU {pmtu} is below the {dev} MTU {mtu}"),
+                Some(
+                    "Larger packets are dropped unless PMTU discovery works end to end; \
+                     lower the MTU or clamp the TCP MSS"
+                        .into(),
+                ),
+            ));
+        }
+    }
+
+    pub fn print(&self) {
+        println!("Probes:");
+        let Some((dev, _, _)) = &self.link else {
+            println!("  skipped (no route)");
+            return;
+        };
+        if let Some(nexthop) = self.nexthop {
+            let status = match &self.neighbor {
+                NeighborResult::NotNeeded => "no resolution needed".to_string(),
+                NeighborResult::Skipped => "not probed".to_string(),
+                NeighborResult::Resolved {
+                    lladdr,
+                    confirmed: true,
+                } => {
+                    format!("reachable ({lladdr})")
+                }
+                NeighborResult::Resolved {
+                    lladdr,
+                    confirmed: false,
+                } => {
+                    format!("resolved ({lladdr}), not reconfirmed")
+                }
+                NeighborResult::Failed => format!("no {} reply", self.nd()),
+            };
+            println!("  Next hop {nexthop} on {dev}: {status}");
+        }
+        if let Some(echo) = &self.echo {
+            print!("  Echo: {}/{} replies", echo.received(), echo.sent);
+            if let (Some(min), Some(max)) = (echo.rtts.iter().min(), echo.rtts.iter().max()) {
+                let avg = echo.rtts.iter().sum::<Duration>() / echo.received();
+                print!(
+                    ", rtt min/avg/max {:.3}/{:.3}/{:.3} ms",
+                    ms(*min),
+                    ms(avg),
+                    ms(*max)
+                );
+            }
+            if let Some(e) = &echo.error {
+                print!(" ({e})");
+            }
+            println!();
+        }
+        if let Some(pmtu) = self.pmtu {
+            println!("  Path MTU: {pmtu}");
+        }
+        match self.breaks_at() {
+            Some(at) => println!("  Path breaks at: {at}"),
+            None if self.echo.is_some() => println!("  Path: OK"),
+            None => {}
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        let (neighbor, lladdr) = match &self.neighbor {
+            NeighborResult::NotNeeded => ("not-needed", None),
+            NeighborResult::Skipped => ("skipped", None),
+            NeighborResult::Resolved {
+                lladdr,
+                confirmed: true,
+            } => ("reachable", Some(lladdr)),
+            NeighborResult::Resolved {
+                lladdr,
+                confirmed: false,
+            } => ("stale", Some(lladdr)),
+            NeighborResult::Failed => ("failed", None),
+        };
+        serde_json::json!({
+            "interface": self.link.as_ref().map(|(d, _, _)| d),
+            "nexthop": self.nexthop.map(|n| n.to_string()),
+            "neighbor": neighbor,
+            "lladdr": lladdr,
+            "echo": self.echo.as_ref().map(|e| serde_json::json!({
+                "sent": e.sent,
+                "received": e.received(),
+                "rtt_ms": e.rtts.iter().map(|r| ms(*r)).collect::<Vec<_>>(),
+                "error": e.error,
+            })),
+            "path_mtu": self.pmtu,
+            "breaks_at": self.breaks_at(),
+        })
+    }
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_matches_rfc1071_example() {
+        // RFC 1071 §3: 0001 f203 f4f5 f6f7 sums to ddf2, checksum 220d.
+        assert_eq!(
+            checksum(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]),
+            0x220d
+        );
+        // A packet with its checksum filled in sums to zero.
+        let p = echo_request(false, 0x1234, 7, 64);
+        assert_eq!(checksum(&p), 0);
+    }
+
+    #[test]
+    fn echo_request_layout() {
+        let p = echo_request(true, 0xabcd, 0x0102, 16);
+        assert_eq!(p.len(), 16);
+        assert_eq!(&p[..2], &[128, 0]);
+        assert_eq!(&p[4..8], &[0xab, 0xcd, 0x01, 0x02]);
+        // ICMPv6 checksums are left to the kernel.
+        assert_eq!(&p[2..4], &[0, 0]);
+    }
+
+    #[test]
+    fn parse_reply_strips_ipv4_header_on_raw_sockets() {
+        let mut reply = echo_request(false, 9, 42, 16);
+        reply[0] = 0;
+        assert_eq!(parse_echo_reply(&reply, false, false), Some((9, 42)));
+
+        let mut framed = vec![0x45];
+        framed.resize(20, 0);
+        framed.extend_from_slice(&reply);
+        assert_eq!(parse_echo_reply(&framed, false, true), Some((9, 42)));
+
+        // A request is not a reply.
+        let request = echo_request(false, 9, 42, 16);
+        assert_eq!(parse_echo_reply(&request, false, false), None);
+        assert_eq!(parse_echo_reply(&[0, 0], false, false), None);
+    }
+
+    fn probe_with(neighbor: NeighborResult, echo: Option<EchoSummary>) -> PathProbe {
+        PathProbe {
+            destination: "203.0.113.7".parse().unwrap(),
+            link: Some(("eth0".into(), Some(1500), true)),
+            nexthop: Some("192.0.2.1".parse().unwrap()),
+            neighbor,
+            echo,
+            pmtu: None,
+        }
+    }
+
+    #[test]
+    fn breaks_at_names_the_failing_stage() {
+        let p = probe_with(NeighborResult::Failed, None);
+        assert_eq!(
+            p.breaks_at().as_deref(),
+            Some("next hop 192.0.2.1 on eth0 (no ARP reply)")
+        );
+
+        let resolved = || NeighborResult::Resolved {
+            lladdr: "02:00:00:00:00:01".into(),
+            confirmed: true,
+        };
+        let lost = EchoSummary {
+            sent: 3,
+            rtts: Vec::new(),
+            error: None,
+        };
+        let p = probe_with(resolved(), Some(lost));
+        assert_eq!(
+            p.breaks_at().as_deref(),
+            Some("beyond next hop 192.0.2.1 (no echo reply)")
+        );
+
+        let ok = EchoSummary {
+            sent: 3,
+            rtts: vec![Duration::from_millis(1)],
+            error: None,
+        };
+        assert_eq!(probe_with(resolved(), Some(ok)).breaks_at(), None);
+
+        let mut down = probe_with(NeighborResult::Skipped, None);
+        down.link = Some(("eth0".into(), Some(1500), false));
+        assert_eq!(
+            down.breaks_at().as_deref(),
+            Some("output interface eth0 (down)")
+        );
+    }
+}
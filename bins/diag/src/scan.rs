@@ -3,7 +3,8 @@
 use clap::Args;
 use nlink::netlink::{
     Connection, Result, Route,
-    diagnostics::{Diagnostics, DiagnosticsConfig, Issue, Severity},
+    diagnostics::{DiagnosticReport, Diagnostics, DiagnosticsConfig, Issue, Severity},
+    namespace,
 };
 
 #[derive(Args)]
@@ -23,6 +24,21 @@ pub struct ScanArgs {
     /// Find and report the worst bottleneck
     #[arg(long)]
     bottleneck: bool,
+
+    /// Skip the ethtool checks of NIC settings (speed/duplex, offloads,
+    /// pause frames, ring sizes, queue count)
+    #[arg(long)]
+    no_nic: bool,
+
+    /// Also report addresses reused across the named network namespaces
+    /// (`ip netns` names)
+    #[arg(long)]
+    all_namespaces: bool,
+
+    /// Also report netlink sockets whose receive queue is backing up or
+    /// has dropped messages
+    #[arg(long)]
+    netlink: bool,
 }
 
 fn parse_severity(s: &str) -> std::result::Result<Severity, String> {
@@ -50,7 +66,29 @@ pub async fn run(args: ScanArgs, json: bool, verbose: bool) -> Result<()> {
         eprintln!("Running diagnostic scan...");
     }
 
-    let report = diag.scan().await?;
+    let mut report = diag.scan().await?;
+
+    if !args.no_nic {
+        match diag.scan_nic().await {
+            Ok(issues) => merge_issues(&mut report, issues),
+            Err(e) => eprintln!("warning: NIC checks skipped: {e}"),
+        }
+    }
+
+    if args.all_namespaces {
+        let namespaces = namespace::list()?;
+        merge_issues(
+            &mut report,
+            diag.find_duplicate_addresses(&namespaces).await?,
+        );
+    }
+
+    if args.netlink {
+        match diag.scan_netlink_sockets().await {
+            Ok(issues) => merge_issues(&mut report, issues),
+            Err(e) => eprintln!("warning: netlink socket checks skipped: {e}"),
+        }
+    }
 
     // --min-severity gates *every* surface (per-interface issues, the
     // top-level issue list, text and JSON alike) — not just the text
@@ -224,6 +262,20 @@ pub async fn run(args: ScanArgs, json: bool, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Add issues to the interfaces they name and to the report-wide list.
+fn merge_issues(report: &mut DiagnosticReport, issues: Vec<Issue>) {
+    for issue in issues {
+        if let Some(iface) = report
+            .interfaces
+            .iter_mut()
+            .find(|i| Some(&i.name) == issue.interface.as_ref())
+        {
+            iface.issues.push(issue.clone());
+        }
+        report.issues.push(issue);
+    }
+}
+
 fn issue_to_json(issue: &Issue) -> serde_json::Value {
     // Display (not Debug) — stable, refactor-proof enum strings.
     serde_json::json!({
@@ -335,6 +387,42 @@ mod tests {
         assert_eq!(severity_icon(Severity::Critical), "[CRIT]");
     }
 
+    #[test]
+    fn merge_issues_attaches_to_named_interface() {
+        use nlink::netlink::diagnostics::{InterfaceDiag, IssueCategory, LinkRates, RouteDiag};
+        use std::time::Instant;
+
+        let iface = |name: &str| InterfaceDiag {
+            name: name.into(),
+            ifindex: 1,
+            state: OperState::Up,
+            flags: 0,
+            mtu: None,
+            stats: Default::default(),
+            rates: LinkRates::default(),
+            tc: None,
+            issues: Vec::new(),
+        };
+        let mut report = DiagnosticReport {
+            timestamp: Instant::now(),
+            interfaces: vec![iface("eth0"), iface("eth1")],
+            routes: RouteDiag::default(),
+            issues: Vec::new(),
+        };
+        let issue = Issue {
+            severity: Severity::Warning,
+            category: IssueCategory::SingleQueue,
+            message: "eth1 has a single receive queue".into(),
+            details: None,
+            interface: Some("eth1".into()),
+            timestamp: Instant::now(),
+        };
+        merge_issues(&mut report, vec![issue]);
+        assert!(report.interfaces[0].issues.is_empty());
+        assert_eq!(report.interfaces[1].issues.len(), 1);
+        assert_eq!(report.issues.len(), 1);
+    }
+
     #[test]
     fn format_bytes_scales_units() {
         assert_eq!(format_bytes(512), "512B");
@@ -1,17 +1,54 @@
 //! Watch command - monitor network issues in real-time.
 
+use std::time::Duration;
+
 use clap::Args;
 use nlink::netlink::{
     Connection, Result, Route,
-    diagnostics::{Diagnostics, Severity},
+    diagnostics::{Diagnostics, Issue, Severity},
+    neigh_monitor::{FlapOptions, NeighborMonitor, NeighborTransition},
 };
 use tokio_stream::StreamExt;
 
+use crate::alert::{self, AlertEvent, AlertSinks, AlertState, Webhook};
+
 #[derive(Args)]
 pub struct WatchArgs {
     /// Only show issues of this severity or higher
     #[arg(long, value_parser = parse_severity)]
     pub min_severity: Option<Severity>,
+
+    /// POST a JSON alert to this http:// URL when an issue is raised or
+    /// clears (repeatable)
+    #[arg(long, value_name = "URL", value_parser = alert::parse_webhook)]
+    pub webhook: Vec<Webhook>,
+
+    /// Run this shell command when an issue is raised or clears, with the
+    /// JSON alert on stdin and NLINK_ALERT_* variables set (repeatable)
+    #[arg(long = "exec", value_name = "CMD")]
+    pub exec: Vec<String>,
+
+    /// Seconds between rescans for alerting; changes also trigger one
+    #[arg(long, value_name = "SECS", default_value = "30")]
+    pub recheck: u64,
+
+    /// Also report neighbour entries flapping between REACHABLE and
+    /// FAILED
+    #[arg(long)]
+    pub neigh_flaps: bool,
+
+    /// Window in seconds over which neighbour flips are counted
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value = "60",
+        requires = "neigh_flaps"
+    )]
+    pub flap_window: u64,
+
+    /// Flips within the window from which an entry counts as flapping
+    #[arg(long, value_name = "N", default_value = "4", requires = "neigh_flaps")]
+    pub flap_threshold: usize,
 }
 
 fn parse_severity(s: &str) -> std::result::Result<Severity, String> {
@@ -37,48 +74,139 @@ pub async fn run(args: WatchArgs, json: bool) -> Result<()> {
     eprintln!("Press Ctrl+C to stop.");
     eprintln!();
 
+    let sinks = AlertSinks {
+        webhooks: args.webhook,
+        commands: args.exec,
+    };
     let mut issues = diag.watch().await?;
+    let mut neigh = if args.neigh_flaps {
+        let opts = FlapOptions::default()
+            .window(Duration::from_secs(args.flap_window.max(1)))
+            .threshold(args.flap_threshold);
+        Some(NeighborMonitor::new(Connection::<Route>::new()?, opts).await?)
+    } else {
+        None
+    };
 
-    while let Some(result) = issues.next().await {
-        let issue = result?;
-
-        if issue.severity < min_severity {
-            continue;
+    if sinks.is_empty() {
+        loop {
+            tokio::select! {
+                result = issues.next() => {
+                    let Some(result) = result else { break };
+                    let issue = result?;
+                    if issue.severity >= min_severity {
+                        print_issue(&issue, json);
+                    }
+                }
+                transition = next_transition(&mut neigh) => {
+                    if let Some(issue) = transition?.to_issue()
+                        && issue.severity >= min_severity
+                    {
+                        print_issue(&issue, json);
+                    }
+                }
+            }
         }
+        return Ok(());
+    }
+
+    // Alerts follow scan state, so rescan shortly after any change (not
+    // just the ones the issue stream reports, or clears would wait for
+    // the next periodic rescan).
+    let events_conn = Connection::<Route>::new()?;
+    events_conn.subscribe_all()?;
+    let mut events = events_conn.into_events().await;
+    let mut state = AlertState::default();
+    let mut recheck = tokio::time::interval(Duration::from_secs(args.recheck.max(1)));
 
-        if json {
-            let output = serde_json::json!({
-                "severity": format!("{:?}", issue.severity),
-                "category": format!("{:?}", issue.category),
-                "message": issue.message,
-                "details": issue.details,
-                "interface": issue.interface,
-            });
-            println!(
-                "{}",
-                serde_json::to_string(&output).expect("JSON serialization")
-            );
-        } else {
-            let icon = match issue.severity {
-                Severity::Info => "[INFO]",
-                Severity::Warning => "[WARN]",
-                Severity::Error => "[ERROR]",
-                Severity::Critical => "[CRIT]",
-                _ => "[????]",
-            };
-
-            let iface = issue
-                .interface
-                .as_ref()
-                .map(|s| format!("[{}] ", s))
-                .unwrap_or_default();
-
-            println!("{} {}{}", icon, iface, issue.message);
-            if let Some(ref details) = issue.details {
-                println!("      {}", details);
+    loop {
+        tokio::select! {
+            result = issues.next() => {
+                let Some(result) = result else { break };
+                let issue = result?;
+                if issue.severity >= min_severity {
+                    print_issue(&issue, json);
+                }
+            }
+            transition = next_transition(&mut neigh) => {
+                // Flapping is an episode, not scan state: alert on onset only.
+                if let Some(issue) = transition?.to_issue()
+                    && issue.severity >= min_severity
+                {
+                    print_issue(&issue, json);
+                    sinks.notify(AlertEvent::Raised, &issue);
+                }
+            }
+            event = events.next() => {
+                if event.is_none() {
+                    break;
+                }
+                recheck.reset_after(Duration::from_secs(1));
+            }
+            _ = recheck.tick() => {
+                match diag.scan().await {
+                    Ok(report) => {
+                        let current = report
+                            .issues
+                            .into_iter()
+                            .filter(|i| i.severity >= min_severity)
+                            .collect();
+                        for (event, issue) in state.update(current) {
+                            sinks.notify(event, &issue);
+                        }
+                    }
+                    Err(e) => eprintln!("warning: rescan failed: {}", e),
+                }
             }
         }
     }
 
     Ok(())
 }
+
+/// Next neighbour transition, or never if neighbour watching is off or
+/// its stream has ended.
+async fn next_transition(monitor: &mut Option<NeighborMonitor>) -> Result<NeighborTransition> {
+    match monitor {
+        Some(m) => match m.next().await {
+            Some(result) => result,
+            None => std::future::pending().await,
+        },
+        None => std::future::pending().await,
+    }
+}
+
+fn print_issue(issue: &Issue, json: bool) {
+    if json {
+        let output = serde_json::json!({
+            "severity": format!("{:?}", issue.severity),
+            "category": format!("{:?}", issue.category),
+            "message": issue.message,
+            "details": issue.details,
+            "interface": issue.interface,
+        });
+        println!(
+            "{}",
+            serde_json::to_string(&output).expect("JSON serialization")
+        );
+    } else {
+        let icon = match issue.severity {
+            Severity::Info => "[INFO]",
+            Severity::Warning => "[WARN]",
+            Severity::Error => "[ERROR]",
+            Severity::Critical => "[CRIT]",
+            _ => "[????]",
+        };
+
+        let iface = issue
+            .interface
+            .as_ref()
+            .map(|s| format!("[{}] ", s))
+            .unwrap_or_default();
+
+        println!("{} {}{}", icon, iface, issue.message);
+        if let Some(ref details) = issue.details {
+            println!("      {}", details);
+        }
+    }
+}
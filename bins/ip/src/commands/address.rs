@@ -10,9 +10,9 @@ use std::{
 use clap::{Args, Subcommand};
 use nlink::{
     netlink::{
-        Connection, Result, Route,
-        addr::{Ipv4Address, Ipv6Address},
-        messages::AddressMessage,
+        Connection, InterfaceRef, Result, Route,
+        addr::{AddressFilter, Ipv4Address, Ipv6Address},
+        messages::{AddressMessage, AddressOrigin},
         types::addr::Scope,
     },
     output::{OutputFormat, OutputOptions},
@@ -27,10 +27,7 @@ pub struct AddressCmd {
 #[derive(Subcommand)]
 enum AddressAction {
     /// Show addresses.
-    Show {
-        /// Interface name.
-        dev: Option<String>,
-    },
+    Show(ShowArgs),
 
     /// Add an address.
     Add {
@@ -72,9 +69,85 @@ enum AddressAction {
     Flush {
         /// Interface name.
         dev: Option<String>,
+
+        /// Only remove addresses autoconfigured from router advertisements.
+        #[arg(long, conflicts_with = "exclude_ra")]
+        ra: bool,
+
+        /// Keep addresses autoconfigured from router advertisements.
+        #[arg(long)]
+        exclude_ra: bool,
     },
 }
 
+#[derive(Args, Default)]
+struct ShowArgs {
+    /// Interface name.
+    dev: Option<String>,
+
+    /// Only show addresses with this scope (global, site, link, host).
+    #[arg(long)]
+    scope: Option<String>,
+
+    /// Only show addresses whose label matches this glob.
+    #[arg(long)]
+    label: Option<String>,
+
+    /// Only show permanent addresses.
+    #[arg(long, conflicts_with = "dynamic")]
+    permanent: bool,
+
+    /// Only show dynamic (autoconfigured) addresses.
+    #[arg(long)]
+    dynamic: bool,
+
+    /// Only show addresses still in duplicate address detection.
+    #[arg(long)]
+    tentative: bool,
+
+    /// Only show addresses that failed duplicate address detection.
+    #[arg(long)]
+    dadfailed: bool,
+
+    /// Only show addresses on interfaces that are up.
+    #[arg(long)]
+    up: bool,
+}
+
+impl ShowArgs {
+    fn filter(&self, family: Option<u8>) -> Result<AddressFilter> {
+        let mut filter = AddressFilter::new();
+        if let Some(dev) = &self.dev {
+            filter = filter.interface(dev.as_str());
+        }
+        if let Some(fam) = family {
+            filter = filter.family(nlink::AddressFamily::from_raw(fam));
+        }
+        if let Some(scope) = &self.scope {
+            let scope = Scope::from_name(scope).ok_or_else(|| {
+                nlink::netlink::Error::InvalidMessage(format!("invalid scope: {}", scope))
+            })?;
+            filter = filter.scope(scope);
+        }
+        if let Some(label) = &self.label {
+            filter = filter.label(label);
+        }
+        if self.permanent || self.dynamic {
+            filter = filter.permanent(self.permanent);
+        }
+        if self.tentative {
+            filter = filter.tentative(true);
+        }
+        if self.dadfailed {
+            filter = filter.dad_failed(true);
+        }
+        if self.up {
+            filter = filter.up();
+        }
+        Ok(filter)
+    }
+}
+
 impl AddressCmd {
     pub async fn run(
         self,
@@ -83,9 +156,12 @@ impl AddressCmd {
         opts: &OutputOptions,
         family: Option<u8>,
     ) -> Result<()> {
-        match self.action.unwrap_or(AddressAction::Show { dev: None }) {
-            AddressAction::Show { dev } => {
-                Self::show(conn, dev.as_deref(), format, opts, family).await
+        match self
+            .action
+            .unwrap_or(AddressAction::Show(ShowArgs::default()))
+        {
+            AddressAction::Show(args) => {
+                Self::show(conn, &args.filter(family)?, format, opts).await
             }
             AddressAction::Add {
                 address,
@@ -107,43 +183,24 @@ impl AddressCmd {
                 .await
             }
             AddressAction::Del { address, dev } => Self::del(conn, &address, &dev).await,
-            AddressAction::Flush { dev } => Self::flush(conn, dev.as_deref(), family).await,
+            AddressAction::Flush {
+                dev,
+                ra,
+                exclude_ra,
+            } => {
+                let ra = (ra || exclude_ra).then_some(ra);
+                Self::flush(conn, dev.as_deref(), ra, family).await
+            }
         }
     }
 
     async fn show(
         conn: &Connection<Route>,
-        dev: Option<&str>,
+        filter: &AddressFilter,
         format: OutputFormat,
         opts: &OutputOptions,
-        family: Option<u8>,
     ) -> Result<()> {
-        // Use the strongly-typed API to get all addresses
-        let all_addresses = conn.get_addresses().await?;
-
-        // Get device index if filtering by name
-        let filter_index =
-            nlink::util::get_ifindex_opt(dev).map_err(nlink::netlink::Error::InvalidMessage)?;
-
-        // Filter addresses
-        let addresses: Vec<_> = all_addresses
-            .into_iter()
-            .filter(|addr| {
-                // Filter by device if specified
-                if let Some(idx) = filter_index
-                    && addr.ifindex() != idx
-                {
-                    return false;
-                }
-                // Filter by family if specified
-                if let Some(fam) = family
-                    && addr.family() != fam
-                {
-                    return false;
-                }
-                true
-            })
-            .collect();
+        let addresses = conn.get_addresses_filtered(filter).await?;
 
         let mut stdout = io::stdout().lock();
 
@@ -262,13 +319,19 @@ impl AddressCmd {
         conn.del_address(dev, addr, prefix).await
     }
 
-    async fn flush(conn: &Connection<Route>, dev: Option<&str>, family: Option<u8>) -> Result<()> {
+    async fn flush(
+        conn: &Connection<Route>,
+        dev: Option<&str>,
+        ra: Option<bool>,
+        family: Option<u8>,
+    ) -> Result<()> {
         // Get all addresses using the typed API
         let all_addresses = conn.get_addresses().await?;
 
         // Get device index if filtering by name
-        let filter_index =
-            nlink::util::get_ifindex_opt(dev).map_err(nlink::netlink::Error::InvalidMessage)?;
+        let filter_index = conn
+            .resolve_interface_opt(dev.map(InterfaceRef::from).as_ref())
+            .await?;
 
         // Filter and delete addresses
         for addr in all_addresses {
@@ -284,6 +347,12 @@ impl AddressCmd {
             {
                 continue;
             }
+            // Skip if the router-advertisement filter doesn't match
+            if let Some(ra) = ra
+                && (addr.origin() == AddressOrigin::Slaac) != ra
+            {
+                continue;
+            }
 
             // Get the address to delete
             let address_to_del = addr.local().or(addr.address());
@@ -343,6 +412,7 @@ fn addr_to_json(addr: &AddressMessage) -> serde_json::Value {
     if !flags.is_empty() {
         obj["flags"] = serde_json::json!(flags);
     }
+    obj["origin"] = serde_json::json!(addr.origin().name());
 
     obj
 }
@@ -351,7 +421,7 @@ fn addr_to_json(addr: &AddressMessage) -> serde_json::Value {
 fn print_addr_text<W: Write>(
     w: &mut W,
     addr: &AddressMessage,
-    _opts: &OutputOptions,
+    opts: &OutputOptions,
 ) -> io::Result<()> {
     let family = nlink::util::names::family_name(addr.family());
 
@@ -387,6 +457,9 @@ fn print_addr_text<W: Write>(
     if addr.is_tentative() {
         write!(w, " tentative")?;
     }
+    if opts.details {
+        write!(w, " origin {}", addr.origin())?;
+    }
 
     if let Some(label) = addr.label() {
         write!(w, " {}", label)?;
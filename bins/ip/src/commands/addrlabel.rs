@@ -0,0 +1,86 @@
+//! ip addrlabel command implementation.
+//!
+//! Lists, adds and deletes IPv6 address labels (the RFC 6724 policy
+//! table) through `RTM_GETADDRLABEL` / `RTM_NEWADDRLABEL` /
+//! `RTM_DELADDRLABEL`.
+
+use std::net::IpAddr;
+
+use clap::{Args, Subcommand};
+use nlink::{
+    netlink::{Connection, Result, Route, addr::AddrLabel},
+    output::{OutputFormat, OutputOptions, print_all},
+    util::addr::parse_prefix,
+};
+
+#[derive(Args)]
+pub struct AddrlabelCmd {
+    #[command(subcommand)]
+    action: Option<AddrlabelAction>,
+}
+
+#[derive(Subcommand)]
+enum AddrlabelAction {
+    /// List address labels.
+    #[command(visible_alias = "show", visible_alias = "ls")]
+    List,
+
+    /// Add an address label.
+    Add(LabelArgs),
+
+    /// Delete an address label.
+    #[command(visible_alias = "delete")]
+    Del(LabelArgs),
+}
+
+#[derive(Args)]
+struct LabelArgs {
+    /// IPv6 prefix (e.g. 2001:db8::/32).
+    prefix: String,
+
+    /// Label value.
+    #[arg(long)]
+    label: u32,
+
+    /// Only apply to addresses on this device.
+    #[arg(long, short)]
+    dev: Option<String>,
+}
+
+impl LabelArgs {
+    fn to_label(&self) -> Result<AddrLabel> {
+        let (addr, prefix_len) = parse_prefix(&self.prefix)
+            .map_err(|e| nlink::netlink::Error::InvalidMessage(e.to_string()))?;
+        let IpAddr::V6(addr) = addr else {
+            return Err(nlink::netlink::Error::InvalidMessage(format!(
+                "address labels are IPv6 only, got `{}`",
+                self.prefix
+            )));
+        };
+        let mut label = AddrLabel::new(addr, prefix_len, self.label);
+        if let Some(dev) = &self.dev {
+            label = label.dev(dev.as_str());
+        }
+        Ok(label)
+    }
+}
+
+impl AddrlabelCmd {
+    pub async fn run(
+        self,
+        conn: &Connection<Route>,
+        format: OutputFormat,
+        opts: &OutputOptions,
+    ) -> Result<()> {
+        match self.action.unwrap_or(AddrlabelAction::List) {
+            AddrlabelAction::List => {
+                let labels = conn.get_addr_labels().await?;
+                conn.interface_resolver().await?.install();
+                print_all(&labels, format, opts)?;
+                Ok(())
+            }
+            AddrlabelAction::Add(args) => conn.add_addr_label(args.to_label()?).await,
+            AddrlabelAction::Del(args) => conn.del_addr_label(args.to_label()?).await,
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! `ip ila` command implementation.
+//!
+//! Manages the ILA (identifier-locator addressing) translation table
+//! via the `ila` Generic Netlink family.
+
+use clap::{Args, Subcommand, ValueEnum};
+use nlink::{
+    netlink::{
+        Connection, Error, Result, Route,
+        genl::ila::{
+            Ila, IlaCsumMode, IlaDelRequest, IlaHookType, IlaIdentType, IlaLocator, IlaMapping,
+        },
+    },
+    output::{OutputFormat, OutputOptions},
+};
+use tokio_stream::StreamExt;
+
+#[derive(Args)]
+pub struct IlaCmd {
+    #[command(subcommand)]
+    command: Option<IlaCommand>,
+}
+
+#[derive(Subcommand)]
+enum IlaCommand {
+    /// List mappings
+    #[command(visible_alias = "show", visible_alias = "ls")]
+    List,
+    /// Add a mapping
+    Add(IlaAddArgs),
+    /// Delete a mapping
+    #[command(visible_alias = "delete")]
+    Del(IlaDelArgs),
+    /// Delete all mappings
+    Flush,
+}
+
+#[derive(Args)]
+struct IlaAddArgs {
+    /// Locator to match (e.g. 2001:db8:0:1)
+    #[arg(long = "loc-match")]
+    loc_match: IlaLocator,
+
+    /// Locator to write
+    #[arg(long)]
+    loc: IlaLocator,
+
+    /// Only translate packets arriving on this device
+    #[arg(long)]
+    dev: Option<String>,
+
+    /// Checksum handling
+    #[arg(long = "csum-mode")]
+    csum_mode: Option<CsumMode>,
+
+    /// Identifier type
+    #[arg(long = "ident-type")]
+    ident_type: Option<IdentType>,
+
+    /// Translation hook
+    #[arg(long = "hook-type")]
+    hook_type: Option<HookType>,
+}
+
+#[derive(Args)]
+struct IlaDelArgs {
+    /// Locator to match
+    #[arg(long = "loc-match")]
+    loc_match: IlaLocator,
+
+    /// Only delete the mapping writing this locator
+    #[arg(long)]
+    loc: Option<IlaLocator>,
+
+    /// Only delete the mapping bound to this device
+    #[arg(long)]
+    dev: Option<String>,
+}
+
+/// `csum-mode` values, named as in iproute2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CsumMode {
+    AdjTransport,
+    NeutralMap,
+    NeutralMapAuto,
+    NoAction,
+}
+
+impl From<CsumMode> for IlaCsumMode {
+    fn from(m: CsumMode) -> Self {
+        match m {
+            CsumMode::AdjTransport => IlaCsumMode::AdjustTransport,
+            CsumMode::NeutralMap => IlaCsumMode::NeutralMap,
+            CsumMode::NeutralMapAuto => IlaCsumMode::NeutralMapAuto,
+            CsumMode::NoAction => IlaCsumMode::NoAction,
+        }
+    }
+}
+
+/// `ident-type` values, named as in iproute2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum IdentType {
+    Luid,
+    UseFormat,
+}
+
+impl From<IdentType> for IlaIdentType {
+    fn from(t: IdentType) -> Self {
+        match t {
+            IdentType::Luid => IlaIdentType::Luid,
+            IdentType::UseFormat => IlaIdentType::UseFormat,
+        }
+    }
+}
+
+/// `hook-type` values, named as in iproute2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum HookType {
+    Output,
+    Input,
+}
+
+impl From<HookType> for IlaHookType {
+    fn from(t: HookType) -> Self {
+        match t {
+            HookType::Output => IlaHookType::RouteOutput,
+            HookType::Input => IlaHookType::RouteInput,
+        }
+    }
+}
+
+impl IlaCmd {
+    pub async fn run(
+        self,
+        conn: &Connection<Route>,
+        format: OutputFormat,
+        opts: &OutputOptions,
+    ) -> Result<()> {
+        let ila = Connection::<Ila>::new_async().await?;
+
+        match self.command {
+            None | Some(IlaCommand::List) => list_mappings(&ila, format, opts).await,
+            Some(IlaCommand::Add(args)) => add_mapping(conn, &ila, args).await,
+            Some(IlaCommand::Del(args)) => del_mapping(conn, &ila, args).await,
+            Some(IlaCommand::Flush) => ila.flush_mappings().await,
+        }
+    }
+}
+
+async fn list_mappings(
+    ila: &Connection<Ila>,
+    format: OutputFormat,
+    opts: &OutputOptions,
+) -> Result<()> {
+    let mut mappings = Vec::new();
+    let mut stream = ila.dump_mappings().await?;
+    while let Some(reply) = stream.next().await {
+        if let Some(m) = reply?.mapping() {
+            mappings.push(m);
+        }
+    }
+
+    match format {
+        OutputFormat::Json => print_mappings_json(&mappings, opts),
+        OutputFormat::Text => print_mappings_text(&mappings),
+    }
+
+    Ok(())
+}
+
+fn print_mappings_text(mappings: &[IlaMapping]) {
+    for m in mappings {
+        let mut line = format!("{} {}", m.locator_match, m.locator);
+
+        match m.ifindex {
+            Some(ifindex) => line.push_str(&format!(" {}", ifname_or_index(ifindex))),
+            None => line.push_str(" -"),
+        }
+        if let Some(mode) = m.csum_mode {
+            line.push_str(&format!(" {}", csum_mode_name(mode)));
+        }
+        if let Some(ident) = m.ident_type {
+            line.push_str(&format!(" {}", ident_type_name(ident)));
+        }
+        if let Some(hook) = m.hook_type {
+            line.push_str(&format!(" {}", hook_type_name(hook)));
+        }
+
+        println!("{}", line);
+    }
+}
+
+fn print_mappings_json(mappings: &[IlaMapping], opts: &OutputOptions) {
+    let json_mappings: Vec<serde_json::Value> = mappings
+        .iter()
+        .map(|m| {
+            let mut obj = serde_json::json!({
+                "locator_match": m.locator_match.to_string(),
+                "locator": m.locator.to_string(),
+            });
+
+            if let Some(ifindex) = m.ifindex {
+                if let Ok(name) = nlink::util::device::get_ifname(ifindex) {
+                    obj["interface"] = serde_json::json!(name);
+                }
+                obj["ifindex"] = serde_json::json!(ifindex);
+            }
+            if let Some(mode) = m.csum_mode {
+                obj["csum_mode"] = serde_json::json!(csum_mode_name(mode));
+            }
+            if let Some(ident) = m.ident_type {
+                obj["ident_type"] = serde_json::json!(ident_type_name(ident));
+            }
+            if let Some(hook) = m.hook_type {
+                obj["hook_type"] = serde_json::json!(hook_type_name(hook));
+            }
+
+            obj
+        })
+        .collect();
+
+    let output = if opts.pretty {
+        serde_json::to_string_pretty(&json_mappings).expect("JSON serialization")
+    } else {
+        serde_json::to_string(&json_mappings).expect("JSON serialization")
+    };
+    println!("{}", output);
+}
+
+async fn add_mapping(
+    conn: &Connection<Route>,
+    ila: &Connection<Ila>,
+    args: IlaAddArgs,
+) -> Result<()> {
+    let mut mapping = IlaMapping::new(args.loc_match, args.loc);
+
+    if let Some(ref dev) = args.dev {
+        mapping = mapping.ifindex(resolve_dev(conn, dev).await?);
+    }
+    if let Some(mode) = args.csum_mode {
+        mapping = mapping.csum_mode(mode.into());
+    }
+    if let Some(ident) = args.ident_type {
+        mapping = mapping.ident_type(ident.into());
+    }
+    if let Some(hook) = args.hook_type {
+        mapping = mapping.hook_type(hook.into());
+    }
+
+    ila.add_mapping(&mapping).await
+}
+
+async fn del_mapping(
+    conn: &Connection<Route>,
+    ila: &Connection<Ila>,
+    args: IlaDelArgs,
+) -> Result<()> {
+    let mut req = IlaDelRequest::new(args.loc_match);
+
+    if let Some(loc) = args.loc {
+        req = req.locator(loc);
+    }
+    if let Some(ref dev) = args.dev {
+        req = req.ifindex(resolve_dev(conn, dev).await?);
+    }
+
+    ila.del_mapping(req).await
+}
+
+async fn resolve_dev(conn: &Connection<Route>, dev: &str) -> Result<u32> {
+    Ok(conn
+        .get_link_by_name(dev)
+        .await?
+        .ok_or_else(|| Error::InvalidMessage(format!("device `{dev}` not found")))?
+        .ifindex())
+}
+
+fn ifname_or_index(ifindex: u32) -> String {
+    nlink::util::device::get_ifname(ifindex).unwrap_or_else(|_| format!("if{}", ifindex))
+}
+
+fn csum_mode_name(mode: IlaCsumMode) -> &'static str {
+    match mode {
+        IlaCsumMode::AdjustTransport => "adj-transport",
+        IlaCsumMode::NeutralMap => "neutral-map",
+        IlaCsumMode::NoAction => "no-action",
+        IlaCsumMode::NeutralMapAuto => "neutral-map-auto",
+        _ => "unknown",
+    }
+}
+
+fn ident_type_name(ident: IlaIdentType) -> &'static str {
+    match ident {
+        IlaIdentType::Iid => "iid",
+        IlaIdentType::Luid => "luid",
+        IlaIdentType::VirtV4 => "virt-v4",
+        IlaIdentType::VirtUniV6 => "virt-uni-v6",
+        IlaIdentType::VirtMultiV6 => "virt-multi-v6",
+        IlaIdentType::NonlocalAddr => "nonlocal-addr",
+        IlaIdentType::UseFormat => "use-format",
+        _ => "unknown",
+    }
+}
+
+fn hook_type_name(hook: IlaHookType) -> &'static str {
+    match hook {
+        IlaHookType::RouteOutput => "output",
+        IlaHookType::RouteInput => "input",
+        _ => "unknown",
+    }
+}
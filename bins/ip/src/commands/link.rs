@@ -4,7 +4,10 @@
 
 use clap::{Args, Subcommand};
 use nlink::{
-    netlink::{Connection, Result, Route, message::NlMsgType, messages::LinkMessage},
+    netlink::{
+        Connection, Result, Route, link::LinkTuning, message::NlMsgType, messages::LinkMessage,
+        types::link::LinkFlags,
+    },
     output::{OutputFormat, OutputOptions, print_all},
 };
 
@@ -61,6 +64,18 @@ enum LinkAction {
         #[arg(long)]
         txqlen: Option<u32>,
 
+        /// Set the largest GSO packet built for this device.
+        #[arg(long)]
+        gso_max_size: Option<u32>,
+
+        /// Set the most segments in one GSO packet.
+        #[arg(long)]
+        gso_max_segs: Option<u32>,
+
+        /// Set the largest packet GRO aggregates.
+        #[arg(long)]
+        gro_max_size: Option<u32>,
+
         /// Set MAC address.
         #[arg(long)]
         address: Option<String>,
@@ -77,9 +92,33 @@ enum LinkAction {
         /// /var/run/netns, or a numeric PID whose netns to join).
         #[arg(long)]
         netns: Option<String>,
+
+        /// Turn promiscuous mode on or off.
+        #[arg(long, value_name = "on|off", value_parser = parse_on_off)]
+        promisc: Option<bool>,
+
+        /// Turn neighbour resolution (ARP/ND) on or off.
+        #[arg(long, value_name = "on|off", value_parser = parse_on_off)]
+        arp: Option<bool>,
+
+        /// Turn multicast on or off.
+        #[arg(long, value_name = "on|off", value_parser = parse_on_off)]
+        multicast: Option<bool>,
+
+        /// Turn receiving all multicast on or off.
+        #[arg(long, value_name = "on|off", value_parser = parse_on_off)]
+        allmulticast: Option<bool>,
     },
 }
 
+fn parse_on_off(s: &str) -> std::result::Result<bool, String> {
+    match s {
+        "on" => Ok(true),
+        "off" => Ok(false),
+        other => Err(format!("expected `on` or `off`, got `{other}`")),
+    }
+}
+
 impl LinkCmd {
     pub async fn run(
         self,
@@ -98,13 +137,60 @@ impl LinkCmd {
                 mtu,
                 name,
                 txqlen,
+                gso_max_size,
+                gso_max_segs,
+                gro_max_size,
                 address,
                 master,
                 nomaster,
                 netns,
+                promisc,
+                arp,
+                multicast,
+                allmulticast,
             } => {
+                // `arp off` sets IFF_NOARP, so that one is inverted.
+                let mut flags = LinkFlags::empty();
+                let mut mask = LinkFlags::empty();
+                for (flag, on) in [
+                    (LinkFlags::PROMISC, promisc),
+                    (LinkFlags::NOARP, arp.map(|on| !on)),
+                    (LinkFlags::MULTICAST, multicast),
+                    (LinkFlags::ALLMULTI, allmulticast),
+                ] {
+                    if let Some(on) = on {
+                        mask |= flag;
+                        flags.set(flag, on);
+                    }
+                }
+                let mut tuning = LinkTuning::new();
+                if let Some(mtu) = mtu {
+                    tuning = tuning.mtu(mtu);
+                }
+                if let Some(txqlen) = txqlen {
+                    tuning = tuning.txqueuelen(txqlen);
+                }
+                if let Some(size) = gso_max_size {
+                    tuning = tuning.gso_max_size(size);
+                }
+                if let Some(segs) = gso_max_segs {
+                    tuning = tuning.gso_max_segs(segs);
+                }
+                if let Some(size) = gro_max_size {
+                    tuning = tuning.gro_max_size(size);
+                }
                 Self::set(
-                    conn, &dev, up, down, mtu, name, txqlen, address, master, nomaster, netns,
+                    conn,
+                    &dev,
+                    up,
+                    down,
+                    tuning,
+                    name,
+                    address,
+                    master,
+                    nomaster,
+                    netns,
+                    (flags, mask),
                 )
                 .await
             }
@@ -143,13 +229,13 @@ impl LinkCmd {
         dev: &str,
         up: bool,
         down: bool,
-        mtu: Option<u32>,
+        tuning: LinkTuning,
         name: Option<String>,
-        txqlen: Option<u32>,
         address: Option<String>,
         master: Option<String>,
         nomaster: bool,
         netns: Option<String>,
+        (flags, mask): (LinkFlags, LinkFlags),
     ) -> Result<()> {
         // Set up/down state
         if up {
@@ -158,21 +244,19 @@ impl LinkCmd {
             conn.set_link_down(dev).await?;
         }
 
-        // Set MTU if specified
-        if let Some(mtu_val) = mtu {
-            conn.set_link_mtu(dev, mtu_val).await?;
+        // Set promisc/arp/multicast/allmulticast
+        if !mask.is_empty() {
+            conn.set_link_flags(dev, flags, mask).await?;
         }
 
+        // Set MTU, TX queue length and GSO/GRO limits in one request
+        conn.tune_link(dev, |_| tuning).await?;
+
         // Set new name if specified
         if let Some(new_name) = name {
             conn.set_link_name(dev, &new_name).await?;
         }
 
-        // Set TX queue length if specified
-        if let Some(qlen) = txqlen {
-            conn.set_link_txqlen(dev, qlen).await?;
-        }
-
         // Set MAC address if specified
         if let Some(addr_str) = address {
             let mac = nlink::util::addr::parse_mac(&addr_str).map_err(|e| {
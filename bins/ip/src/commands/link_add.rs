@@ -4,12 +4,39 @@ use clap::{Args, Subcommand};
 use nlink::netlink::{
     Connection, Result, Route,
     link::{
-        BondLink, BondMode, BridgeLink, DummyLink, GreLink, GretapLink, Ip6GreLink, Ip6GretapLink,
-        IpipLink, IpvlanLink, LacpRate, MacvlanLink, MacvtapLink, SitLink, VethLink, VlanLink,
-        VrfLink, Vti6Link, VtiLink, VxlanLink, WireguardLink, XmitHashPolicy,
+        AdSelect, ArpValidate, BondLink, BondMode, BridgeLink, DummyLink, FailOverMac, GreLink,
+        GretapLink, GtpLink, GtpRole, Ip6GreLink, Ip6GretapLink, IpipLink, IpvlanLink, LacpRate,
+        MacvlanLink, MacvtapLink, PrimaryReselect, SitLink, TeamLink, VcanLink, VethLink, VlanLink,
+        VrfLink, Vti6Link, VtiLink, VxcanLink, VxlanLink, WireguardLink, XfrmiLink, XmitHashPolicy,
     },
 };
 
+/// Tunnel options shared by gre, gretap, ipip and sit.
+#[derive(Args, Debug)]
+pub struct TunnelArgs {
+    /// Remote endpoint address.
+    #[arg(long)]
+    pub remote: String,
+    /// Local endpoint address.
+    #[arg(long)]
+    pub local: Option<String>,
+    /// TTL value.
+    #[arg(long)]
+    pub ttl: Option<u8>,
+    /// TOS value.
+    #[arg(long)]
+    pub tos: Option<u8>,
+    /// Underlay device the tunnel is bound to.
+    #[arg(long)]
+    pub dev: Option<String>,
+    /// Disable path MTU discovery.
+    #[arg(long)]
+    pub nopmtudisc: bool,
+    /// Firewall mark for encapsulated packets.
+    #[arg(long)]
+    pub fwmark: Option<u32>,
+}
+
 /// Common options for all link types.
 #[derive(Args, Debug)]
 pub struct CommonLinkArgs {
@@ -110,12 +137,27 @@ pub enum LinkAddType {
         /// ARP monitoring interval in milliseconds.
         #[arg(long)]
         arp_interval: Option<u32>,
-        /// ARP monitoring IP target.
+        /// ARP monitoring IP targets (comma-separated).
+        #[arg(long, value_delimiter = ',')]
+        arp_ip_target: Vec<String>,
+        /// ARP validation: none, active, backup, all, filter, filter_active, filter_backup.
         #[arg(long)]
-        arp_ip_target: Option<String>,
+        arp_validate: Option<String>,
         /// LACP rate: slow or fast (802.3ad mode only).
         #[arg(long)]
         lacp_rate: Option<String>,
+        /// Aggregator selection: stable, bandwidth, count (802.3ad mode only).
+        #[arg(long)]
+        ad_select: Option<String>,
+        /// When to reselect the primary slave: always, better, failure.
+        #[arg(long)]
+        primary_reselect: Option<String>,
+        /// MAC policy on failover: none, active, follow (active-backup only).
+        #[arg(long)]
+        fail_over_mac: Option<String>,
+        /// Deliver duplicate frames received on inactive slaves.
+        #[arg(long)]
+        all_slaves_active: bool,
         #[command(flatten)]
         common: CommonLinkArgs,
     },
@@ -133,6 +175,12 @@ pub enum LinkAddType {
         /// VLAN protocol: 802.1q or 802.1ad.
         #[arg(long, default_value = "802.1q")]
         protocol: String,
+        /// Ingress priority map, PCP:PRIO pairs (e.g. 5:2,6:3).
+        #[arg(long, value_delimiter = ',')]
+        ingress_qos_map: Vec<String>,
+        /// Egress priority map, PRIO:PCP pairs (e.g. 2:5,3:6).
+        #[arg(long, value_delimiter = ',')]
+        egress_qos_map: Vec<String>,
         #[command(flatten)]
         common: CommonLinkArgs,
     },
@@ -222,22 +270,29 @@ pub enum LinkAddType {
         common: CommonLinkArgs,
     },
 
+    /// Create a team device (configure it with teamd).
+    Team {
+        /// Interface name.
+        name: String,
+        #[command(flatten)]
+        common: CommonLinkArgs,
+    },
+
     /// Create a GRE tunnel.
     Gre {
         /// Interface name.
         name: String,
-        /// Remote endpoint address.
-        #[arg(long)]
-        remote: String,
-        /// Local endpoint address.
-        #[arg(long)]
-        local: Option<String>,
-        /// TTL value.
-        #[arg(long)]
-        ttl: Option<u8>,
-        /// Tunnel key.
+        #[command(flatten)]
+        tunnel: TunnelArgs,
+        /// Tunnel key, both directions.
         #[arg(long)]
         key: Option<u32>,
+        /// Input key.
+        #[arg(long, conflicts_with = "key")]
+        ikey: Option<u32>,
+        /// Output key.
+        #[arg(long, conflicts_with = "key")]
+        okey: Option<u32>,
         #[command(flatten)]
         common: CommonLinkArgs,
     },
@@ -246,18 +301,17 @@ pub enum LinkAddType {
     Gretap {
         /// Interface name.
         name: String,
-        /// Remote endpoint address.
-        #[arg(long)]
-        remote: String,
-        /// Local endpoint address.
-        #[arg(long)]
-        local: Option<String>,
-        /// TTL value.
-        #[arg(long)]
-        ttl: Option<u8>,
-        /// Tunnel key.
+        #[command(flatten)]
+        tunnel: TunnelArgs,
+        /// Tunnel key, both directions.
         #[arg(long)]
         key: Option<u32>,
+        /// Input key.
+        #[arg(long, conflicts_with = "key")]
+        ikey: Option<u32>,
+        /// Output key.
+        #[arg(long, conflicts_with = "key")]
+        okey: Option<u32>,
         #[command(flatten)]
         common: CommonLinkArgs,
     },
@@ -266,15 +320,8 @@ pub enum LinkAddType {
     Ipip {
         /// Interface name.
         name: String,
-        /// Remote endpoint address.
-        #[arg(long)]
-        remote: String,
-        /// Local endpoint address.
-        #[arg(long)]
-        local: Option<String>,
-        /// TTL value.
-        #[arg(long)]
-        ttl: Option<u8>,
+        #[command(flatten)]
+        tunnel: TunnelArgs,
         #[command(flatten)]
         common: CommonLinkArgs,
     },
@@ -283,15 +330,11 @@ pub enum LinkAddType {
     Sit {
         /// Interface name.
         name: String,
-        /// Remote endpoint address.
-        #[arg(long)]
-        remote: String,
-        /// Local endpoint address.
-        #[arg(long)]
-        local: Option<String>,
-        /// TTL value.
+        #[command(flatten)]
+        tunnel: TunnelArgs,
+        /// Enable ISATAP mode.
         #[arg(long)]
-        ttl: Option<u8>,
+        isatap: bool,
         #[command(flatten)]
         common: CommonLinkArgs,
     },
@@ -374,6 +417,71 @@ pub enum LinkAddType {
     Wireguard {
         /// Interface name.
         name: String,
+        /// File holding the base64 private key (as written by `wg genkey`).
+        #[arg(long)]
+        private_key: Option<std::path::PathBuf>,
+        /// UDP listen port.
+        #[arg(long)]
+        listen_port: Option<u16>,
+        /// Firewall mark for outgoing packets.
+        #[arg(long)]
+        fwmark: Option<u32>,
+        #[command(flatten)]
+        common: CommonLinkArgs,
+    },
+
+    /// Create an XFRM interface for route-based IPsec.
+    Xfrm {
+        /// Interface name.
+        name: String,
+        /// Interface ID matched against policies and states.
+        #[arg(long, required_unless_present = "external")]
+        if_id: Option<u32>,
+        /// Underlay device.
+        #[arg(long)]
+        dev: Option<String>,
+        /// Metadata mode: take the interface ID from each packet (Linux 6.1+).
+        #[arg(long)]
+        external: bool,
+        #[command(flatten)]
+        common: CommonLinkArgs,
+    },
+
+    /// Create a GTP-U tunnel device.
+    ///
+    /// The kernel creates the GTP-U sockets (Linux 6.2+): a command line
+    /// has no way to hand over the sockets a GTP daemon would.
+    Gtp {
+        /// Interface name.
+        name: String,
+        /// Role: ggsn or sgsn.
+        #[arg(long, default_value = "ggsn")]
+        role: String,
+        /// PDP context hash table size.
+        #[arg(long)]
+        hsize: Option<u32>,
+        /// GTP restart counter.
+        #[arg(long)]
+        restart_count: Option<u8>,
+        #[command(flatten)]
+        common: CommonLinkArgs,
+    },
+
+    /// Create a virtual CAN interface.
+    Vcan {
+        /// Interface name.
+        name: String,
+        #[command(flatten)]
+        common: CommonLinkArgs,
+    },
+
+    /// Create a virtual CAN tunnel pair.
+    Vxcan {
+        /// Interface name.
+        name: String,
+        /// Peer interface name.
+        #[arg(long)]
+        peer: String,
         #[command(flatten)]
         common: CommonLinkArgs,
     },
@@ -460,7 +568,12 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
             xmit_hash_policy,
             arp_interval,
             arp_ip_target,
+            arp_validate,
             lacp_rate,
+            ad_select,
+            primary_reselect,
+            fail_over_mac,
+            all_slaves_active,
             common,
         } => {
             let mode_val = parse_bond_mode(&mode)?;
@@ -484,14 +597,27 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
             if let Some(v) = arp_interval {
                 link = link.arp_interval(v);
             }
-            if let Some(ref target) = arp_ip_target
-                && let Ok(ip) = target.parse::<std::net::Ipv4Addr>()
-            {
-                link = link.arp_ip_target(ip);
+            for target in &arp_ip_target {
+                link = link.arp_ip_target(parse_ipv4("bond: arp-ip-target", target)?);
+            }
+            if let Some(ref v) = arp_validate {
+                link = link.arp_validate(parse_arp_validate(v)?);
             }
             if let Some(ref rate) = lacp_rate {
                 link = link.lacp_rate(parse_lacp_rate(rate)?);
             }
+            if let Some(ref v) = ad_select {
+                link = link.ad_select(parse_ad_select(v)?);
+            }
+            if let Some(ref v) = primary_reselect {
+                link = link.primary_reselect(parse_primary_reselect(v)?);
+            }
+            if let Some(ref v) = fail_over_mac {
+                link = link.fail_over_mac(parse_fail_over_mac(v)?);
+            }
+            if all_slaves_active {
+                link = link.all_slaves_active(true);
+            }
             if let Some(mtu) = common.mtu {
                 link = link.mtu(mtu);
             }
@@ -507,6 +633,8 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
             link: parent,
             id,
             protocol,
+            ingress_qos_map,
+            egress_qos_map,
             common,
         } => {
             let mut link = VlanLink::new(&name, &parent, id);
@@ -519,6 +647,16 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
                     )));
                 }
             }
+            for entry in &ingress_qos_map {
+                let (pcp, prio) = parse_qos_mapping("ingress-qos-map", entry)?;
+                let pcp = parse_pcp("ingress-qos-map", entry, pcp)?;
+                link = link.ingress_qos_map(pcp, prio);
+            }
+            for entry in &egress_qos_map {
+                let (prio, pcp) = parse_qos_mapping("egress-qos-map", entry)?;
+                let pcp = parse_pcp("egress-qos-map", entry, pcp)?;
+                link = link.egress_qos_map(prio, pcp);
+            }
             if let Some(mtu) = common.mtu {
                 link = link.mtu(mtu);
             }
@@ -636,29 +774,54 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
             conn.add_link(link).await
         }
 
+        LinkAddType::Team { name, common } => {
+            let mut link = TeamLink::new(&name);
+            if let Some(mtu) = common.mtu {
+                link = link.mtu(mtu);
+            }
+            if let Some(ref addr) = common.address {
+                let mac = parse_mac(addr)?;
+                link = link.address(mac);
+            }
+            conn.add_link(link).await
+        }
+
         LinkAddType::Gre {
             name,
-            remote,
-            local,
-            ttl,
+            tunnel,
             key,
+            ikey,
+            okey,
             common,
         } => {
-            let remote_ip: std::net::Ipv4Addr = remote.parse().map_err(|_| {
-                nlink::netlink::Error::InvalidMessage("invalid remote IP address".into())
-            })?;
-            let mut link = GreLink::new(&name).remote(remote_ip);
-            if let Some(ref addr) = local
-                && let Ok(ip) = addr.parse::<std::net::Ipv4Addr>()
-            {
-                link = link.local(ip);
+            let mut link = GreLink::new(&name).remote(parse_ipv4("gre: remote", &tunnel.remote)?);
+            if let Some(ref addr) = tunnel.local {
+                link = link.local(parse_ipv4("gre: local", addr)?);
             }
-            if let Some(t) = ttl {
+            if let Some(t) = tunnel.ttl {
                 link = link.ttl(t);
             }
+            if let Some(t) = tunnel.tos {
+                link = link.tos(t);
+            }
+            if let Some(ref dev) = tunnel.dev {
+                link = link.link(dev);
+            }
+            if tunnel.nopmtudisc {
+                link = link.pmtudisc(false);
+            }
+            if let Some(mark) = tunnel.fwmark {
+                link = link.fwmark(mark);
+            }
             if let Some(k) = key {
                 link = link.key(k);
             }
+            if let Some(k) = ikey {
+                link = link.ikey(k);
+            }
+            if let Some(k) = okey {
+                link = link.okey(k);
+            }
             if let Some(mtu) = common.mtu {
                 link = link.mtu(mtu);
             }
@@ -667,27 +830,41 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
 
         LinkAddType::Gretap {
             name,
-            remote,
-            local,
-            ttl,
+            tunnel,
             key,
+            ikey,
+            okey,
             common,
         } => {
-            let remote_ip: std::net::Ipv4Addr = remote.parse().map_err(|_| {
-                nlink::netlink::Error::InvalidMessage("invalid remote IP address".into())
-            })?;
-            let mut link = GretapLink::new(&name).remote(remote_ip);
-            if let Some(ref addr) = local
-                && let Ok(ip) = addr.parse::<std::net::Ipv4Addr>()
-            {
-                link = link.local(ip);
+            let mut link =
+                GretapLink::new(&name).remote(parse_ipv4("gretap: remote", &tunnel.remote)?);
+            if let Some(ref addr) = tunnel.local {
+                link = link.local(parse_ipv4("gretap: local", addr)?);
             }
-            if let Some(t) = ttl {
+            if let Some(t) = tunnel.ttl {
                 link = link.ttl(t);
             }
+            if let Some(t) = tunnel.tos {
+                link = link.tos(t);
+            }
+            if let Some(ref dev) = tunnel.dev {
+                link = link.link(dev);
+            }
+            if tunnel.nopmtudisc {
+                link = link.pmtudisc(false);
+            }
+            if let Some(mark) = tunnel.fwmark {
+                link = link.fwmark(mark);
+            }
             if let Some(k) = key {
                 link = link.key(k);
             }
+            if let Some(k) = ikey {
+                link = link.ikey(k);
+            }
+            if let Some(k) = okey {
+                link = link.okey(k);
+            }
             if let Some(mtu) = common.mtu {
                 link = link.mtu(mtu);
             }
@@ -696,23 +873,28 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
 
         LinkAddType::Ipip {
             name,
-            remote,
-            local,
-            ttl,
+            tunnel,
             common,
         } => {
-            let remote_ip: std::net::Ipv4Addr = remote.parse().map_err(|_| {
-                nlink::netlink::Error::InvalidMessage("invalid remote IP address".into())
-            })?;
-            let mut link = IpipLink::new(&name).remote(remote_ip);
-            if let Some(ref addr) = local
-                && let Ok(ip) = addr.parse::<std::net::Ipv4Addr>()
-            {
-                link = link.local(ip);
+            let mut link = IpipLink::new(&name).remote(parse_ipv4("ipip: remote", &tunnel.remote)?);
+            if let Some(ref addr) = tunnel.local {
+                link = link.local(parse_ipv4("ipip: local", addr)?);
             }
-            if let Some(t) = ttl {
+            if let Some(t) = tunnel.ttl {
                 link = link.ttl(t);
             }
+            if let Some(t) = tunnel.tos {
+                link = link.tos(t);
+            }
+            if let Some(ref dev) = tunnel.dev {
+                link = link.link(dev);
+            }
+            if tunnel.nopmtudisc {
+                link = link.pmtudisc(false);
+            }
+            if let Some(mark) = tunnel.fwmark {
+                link = link.fwmark(mark);
+            }
             if let Some(mtu) = common.mtu {
                 link = link.mtu(mtu);
             }
@@ -721,23 +903,32 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
 
         LinkAddType::Sit {
             name,
-            remote,
-            local,
-            ttl,
+            tunnel,
+            isatap,
             common,
         } => {
-            let remote_ip: std::net::Ipv4Addr = remote.parse().map_err(|_| {
-                nlink::netlink::Error::InvalidMessage("invalid remote IP address".into())
-            })?;
-            let mut link = SitLink::new(&name).remote(remote_ip);
-            if let Some(ref addr) = local
-                && let Ok(ip) = addr.parse::<std::net::Ipv4Addr>()
-            {
-                link = link.local(ip);
+            let mut link = SitLink::new(&name).remote(parse_ipv4("sit: remote", &tunnel.remote)?);
+            if let Some(ref addr) = tunnel.local {
+                link = link.local(parse_ipv4("sit: local", addr)?);
             }
-            if let Some(t) = ttl {
+            if let Some(t) = tunnel.ttl {
                 link = link.ttl(t);
             }
+            if let Some(t) = tunnel.tos {
+                link = link.tos(t);
+            }
+            if let Some(ref dev) = tunnel.dev {
+                link = link.link(dev);
+            }
+            if tunnel.nopmtudisc {
+                link = link.pmtudisc(false);
+            }
+            if let Some(mark) = tunnel.fwmark {
+                link = link.fwmark(mark);
+            }
+            if isatap {
+                link = link.isatap();
+            }
             if let Some(mtu) = common.mtu {
                 link = link.mtu(mtu);
             }
@@ -852,16 +1043,124 @@ pub async fn add_link(conn: &Connection<Route>, link_type: LinkAddType) -> Resul
             conn.add_link(link).await
         }
 
-        LinkAddType::Wireguard { name, common } => {
+        LinkAddType::Wireguard {
+            name,
+            private_key,
+            listen_port,
+            fwmark,
+            common,
+        } => {
             let mut link = WireguardLink::new(&name);
             if let Some(mtu) = common.mtu {
                 link = link.mtu(mtu);
             }
+            if private_key.is_none() && listen_port.is_none() && fwmark.is_none() {
+                return conn.add_link(link).await;
+            }
+            let key = match private_key {
+                Some(path) => Some(read_wg_key(&path)?),
+                None => None,
+            };
+            conn.create_wireguard(link, |mut d| {
+                if let Some(key) = key {
+                    d = d.private_key(key);
+                }
+                if let Some(port) = listen_port {
+                    d = d.listen_port(port);
+                }
+                if let Some(mark) = fwmark {
+                    d = d.fwmark(mark);
+                }
+                d
+            })
+            .await
+        }
+
+        LinkAddType::Xfrm {
+            name,
+            if_id,
+            dev,
+            external,
+            common,
+        } => {
+            let mut link = XfrmiLink::new(&name, if_id.unwrap_or(0));
+            if let Some(ref dev) = dev {
+                link = link.link(dev);
+            }
+            if external {
+                link = link.collect_metadata();
+            }
+            if let Some(mtu) = common.mtu {
+                link = link.mtu(mtu);
+            }
+            conn.add_link(link).await
+        }
+
+        LinkAddType::Gtp {
+            name,
+            role,
+            hsize,
+            restart_count,
+            common,
+        } => {
+            let mut link = GtpLink::new(&name)
+                .role(parse_gtp_role(&role)?)
+                .create_sockets();
+            if let Some(size) = hsize {
+                link = link.hashsize(size);
+            }
+            if let Some(count) = restart_count {
+                link = link.restart_count(count);
+            }
+            if let Some(mtu) = common.mtu {
+                link = link.mtu(mtu);
+            }
+            conn.add_link(link).await
+        }
+
+        LinkAddType::Vcan { name, common } => {
+            let mut link = VcanLink::new(&name);
+            if let Some(mtu) = common.mtu {
+                link = link.mtu(mtu);
+            }
+            conn.add_link(link).await
+        }
+
+        LinkAddType::Vxcan { name, peer, common } => {
+            let mut link = VxcanLink::new(&name, &peer);
+            if let Some(mtu) = common.mtu {
+                link = link.mtu(mtu);
+            }
             conn.add_link(link).await
         }
     }
 }
 
+fn parse_ipv4(opt: &str, addr: &str) -> Result<std::net::Ipv4Addr> {
+    addr.parse()
+        .map_err(|_| invalid(format!("{opt}: invalid IPv4 address `{addr}`")))
+}
+
+/// Read a WireGuard private key file (base64, as written by `wg genkey`).
+fn read_wg_key(path: &std::path::Path) -> Result<[u8; 32]> {
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        invalid(format!(
+            "wireguard: cannot read private key `{}`: {e}",
+            path.display()
+        ))
+    })?;
+    // Private and public keys share the 32-byte base64 encoding.
+    text.trim()
+        .parse::<nlink::netlink::genl::wireguard::PublicKey>()
+        .map(|k| k.0)
+        .map_err(|_| {
+            invalid(format!(
+                "wireguard: `{}` does not hold a base64 key",
+                path.display()
+            ))
+        })
+}
+
 fn parse_mac(addr: &str) -> Result<[u8; 6]> {
     nlink::util::addr::parse_mac(addr)
         .map_err(|e| nlink::netlink::Error::InvalidMessage(format!("invalid MAC address: {}", e)))
@@ -871,6 +1170,27 @@ fn invalid(msg: String) -> nlink::netlink::Error {
     nlink::netlink::Error::InvalidMessage(msg)
 }
 
+/// Parse one `FROM:TO` VLAN priority mapping.
+fn parse_qos_mapping(opt: &str, entry: &str) -> Result<(u32, u32)> {
+    entry
+        .split_once(':')
+        .and_then(|(from, to)| Some((from.trim().parse().ok()?, to.trim().parse().ok()?)))
+        .ok_or_else(|| {
+            invalid(format!(
+                "vlan: invalid {opt} entry `{entry}` (expected FROM:TO)"
+            ))
+        })
+}
+
+/// Check the 802.1p side of a priority mapping is 0-7.
+fn parse_pcp(opt: &str, entry: &str, pcp: u32) -> Result<u8> {
+    u8::try_from(pcp).ok().filter(|p| *p <= 7).ok_or_else(|| {
+        invalid(format!(
+            "vlan: {opt} entry `{entry}`: 802.1p priority must be 0-7"
+        ))
+    })
+}
+
 fn parse_bond_mode(mode: &str) -> Result<BondMode> {
     Ok(match mode.to_lowercase().as_str() {
         "balance-rr" | "0" => BondMode::BalanceRr,
@@ -918,6 +1238,74 @@ fn parse_lacp_rate(rate: &str) -> Result<LacpRate> {
     })
 }
 
+fn parse_arp_validate(v: &str) -> Result<ArpValidate> {
+    Ok(match v.to_lowercase().as_str() {
+        "none" | "0" => ArpValidate::None,
+        "active" | "1" => ArpValidate::Active,
+        "backup" | "2" => ArpValidate::Backup,
+        "all" | "3" => ArpValidate::All,
+        "filter" | "filter_active" | "4" => ArpValidate::FilterActive,
+        "filter_backup" | "5" => ArpValidate::FilterBackup,
+        other => {
+            return Err(invalid(format!(
+                "bond: unknown arp_validate `{other}` (expected none, active, backup, all, \
+                 filter, filter_active, filter_backup, or 0-5)"
+            )));
+        }
+    })
+}
+
+fn parse_ad_select(v: &str) -> Result<AdSelect> {
+    Ok(match v.to_lowercase().as_str() {
+        "stable" | "0" => AdSelect::Stable,
+        "bandwidth" | "1" => AdSelect::Bandwidth,
+        "count" | "2" => AdSelect::Count,
+        other => {
+            return Err(invalid(format!(
+                "bond: unknown ad_select `{other}` (expected stable, bandwidth, count, or 0-2)"
+            )));
+        }
+    })
+}
+
+fn parse_primary_reselect(v: &str) -> Result<PrimaryReselect> {
+    Ok(match v.to_lowercase().as_str() {
+        "always" | "0" => PrimaryReselect::Always,
+        "better" | "1" => PrimaryReselect::Better,
+        "failure" | "2" => PrimaryReselect::Failure,
+        other => {
+            return Err(invalid(format!(
+                "bond: unknown primary_reselect `{other}` (expected always, better, failure, or 0-2)"
+            )));
+        }
+    })
+}
+
+fn parse_fail_over_mac(v: &str) -> Result<FailOverMac> {
+    Ok(match v.to_lowercase().as_str() {
+        "none" | "0" => FailOverMac::None,
+        "active" | "1" => FailOverMac::Active,
+        "follow" | "2" => FailOverMac::Follow,
+        other => {
+            return Err(invalid(format!(
+                "bond: unknown fail_over_mac `{other}` (expected none, active, follow, or 0-2)"
+            )));
+        }
+    })
+}
+
+fn parse_gtp_role(role: &str) -> Result<GtpRole> {
+    Ok(match role.to_lowercase().as_str() {
+        "ggsn" => GtpRole::Ggsn,
+        "sgsn" => GtpRole::Sgsn,
+        other => {
+            return Err(invalid(format!(
+                "gtp: unknown role `{other}` (expected ggsn or sgsn)"
+            )));
+        }
+    })
+}
+
 fn parse_macvlan_mode(mode: &str) -> Result<nlink::netlink::link::MacvlanMode> {
     use nlink::netlink::link::MacvlanMode;
     Ok(match mode.to_lowercase().as_str() {
@@ -987,6 +1375,54 @@ mod tests {
         assert!(err.contains("macvlan: unknown mode `brigde`"), "{err}");
     }
 
+    #[test]
+    fn qos_mapping_parses_pairs() {
+        assert_eq!(parse_qos_mapping("ingress-qos-map", "5:2").unwrap(), (5, 2));
+        assert_eq!(parse_qos_mapping("egress-qos-map", " 3 : 6 ").unwrap(), (3, 6));
+        let err = parse_qos_mapping("ingress-qos-map", "5")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid ingress-qos-map entry `5`"), "{err}");
+        assert!(parse_qos_mapping("egress-qos-map", "a:1").is_err());
+    }
+
+    #[test]
+    fn qos_mapping_rejects_out_of_range_pcp() {
+        assert_eq!(parse_pcp("ingress-qos-map", "7:1", 7).unwrap(), 7);
+        let err = parse_pcp("egress-qos-map", "1:8", 8).unwrap_err().to_string();
+        assert!(err.contains("802.1p priority must be 0-7"), "{err}");
+    }
+
+    #[test]
+    fn bond_policy_names_parse() {
+        assert!(matches!(
+            parse_arp_validate("filter"),
+            Ok(ArpValidate::FilterActive)
+        ));
+        assert!(matches!(parse_ad_select("count"), Ok(AdSelect::Count)));
+        assert!(matches!(
+            parse_primary_reselect("2"),
+            Ok(PrimaryReselect::Failure)
+        ));
+        assert!(matches!(
+            parse_fail_over_mac("follow"),
+            Ok(FailOverMac::Follow)
+        ));
+        let err = parse_ad_select("fastest").unwrap_err().to_string();
+        assert!(err.contains("bond: unknown ad_select `fastest`"), "{err}");
+    }
+
+    #[test]
+    fn gtp_role_and_tunnel_address_errors() {
+        assert!(matches!(parse_gtp_role("SGSN"), Ok(GtpRole::Sgsn)));
+        assert!(parse_gtp_role("upf").is_err());
+        let err = parse_ipv4("gre: local", "10.0.0").unwrap_err().to_string();
+        assert!(
+            err.contains("gre: local: invalid IPv4 address `10.0.0`"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn ipvlan_mode_rejects_unknown() {
         assert!(parse_ipvlan_mode("l3s").is_ok());
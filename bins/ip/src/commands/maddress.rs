@@ -1,18 +1,17 @@
 //! ip maddress command implementation.
 //!
 //! Multicast address management. Shows link-layer and IP multicast addresses
-//! that are subscribed on interfaces.
+//! that are subscribed on interfaces, and adds or removes static link-layer
+//! groups.
 //!
-//! Read-only by design: the kernel does not expose multicast-address
-//! membership over netlink. The L2 link-layer entries come from
-//! `/proc/net/dev_mcast` and the L3 group memberships from
-//! `/proc/net/{igmp,igmp6}` — these procfs files are per-netns, so they
-//! already reflect the connection's namespace. (iproute2's `ip maddr
-//! add/del` manipulates static L2 multicast via the `SIOCADDMULTI` /
-//! `SIOCDELMULTI` ioctls, not netlink; that is out of scope for this
-//! netlink-first demo and is intentionally not offered rather than
-//! faked.) The interface index↔name map is resolved over netlink so it
-//! is namespace-correct relative to the connection.
+//! The kernel does not expose multicast-address membership over netlink.
+//! The L2 link-layer entries come from `/proc/net/dev_mcast` and the L3
+//! group memberships from `/proc/net/{igmp,igmp6}` — these procfs files are
+//! per-netns, so they already reflect the connection's namespace. `add` and
+//! `del` go through [`Connection::add_link_multicast`] /
+//! [`Connection::del_link_multicast`] (the `SIOCADDMULTI` / `SIOCDELMULTI`
+//! ioctls, as in iproute2). The interface index↔name map is resolved over
+//! netlink so it is namespace-correct relative to the connection.
 
 use std::{
     collections::HashMap,
@@ -40,6 +39,26 @@ enum MaddressAction {
         /// Interface name.
         dev: Option<String>,
     },
+
+    /// Join a link-layer multicast group.
+    Add {
+        /// Multicast MAC address (e.g. 01:00:5e:01:02:03).
+        address: String,
+
+        /// Device name.
+        #[arg(long, short)]
+        dev: String,
+    },
+
+    /// Leave a link-layer multicast group.
+    Del {
+        /// Multicast MAC address.
+        address: String,
+
+        /// Device name.
+        #[arg(long, short)]
+        dev: String,
+    },
 }
 
 /// Multicast address information.
@@ -88,19 +107,29 @@ impl Printable for McastInfo {
 impl MaddressCmd {
     pub async fn run(
         &self,
+        conn: &Connection<Route>,
         format: OutputFormat,
         opts: &OutputOptions,
         family: Option<u8>,
     ) -> Result<()> {
-        let dev = match &self.action {
-            Some(MaddressAction::Show { dev }) => dev.as_deref(),
-            None => None,
-        };
-        self.show(dev, format, opts, family).await
+        match &self.action {
+            Some(MaddressAction::Show { dev }) => {
+                Self::show(conn, dev.as_deref(), format, opts, family).await
+            }
+            None => Self::show(conn, None, format, opts, family).await,
+            Some(MaddressAction::Add { address, dev }) => {
+                conn.add_link_multicast(dev.as_str(), parse_group(address)?)
+                    .await
+            }
+            Some(MaddressAction::Del { address, dev }) => {
+                conn.del_link_multicast(dev.as_str(), parse_group(address)?)
+                    .await
+            }
+        }
     }
 
     async fn show(
-        &self,
+        conn: &Connection<Route>,
         dev: Option<&str>,
         format: OutputFormat,
         opts: &OutputOptions,
@@ -111,7 +140,7 @@ impl MaddressCmd {
         // Resolve the name→ifindex map over netlink (namespace-correct vs
         // the previous `/sys/class/net` scan, which always read the host
         // namespace).
-        let if_indices = get_interface_indices().await?;
+        let if_indices = get_interface_indices(conn).await?;
 
         // Filter by device if specified
         let filter_dev = dev.map(|s| s.to_string());
@@ -245,8 +274,7 @@ impl MaddressCmd {
 /// Resolved via `RTM_GETLINK` in the connection's namespace, so it is
 /// correct inside a foreign netns (unlike a `/sys/class/net` scan, which
 /// always reads the host namespace).
-async fn get_interface_indices() -> Result<HashMap<String, u32>> {
-    let conn = Connection::<Route>::new()?;
+async fn get_interface_indices(conn: &Connection<Route>) -> Result<HashMap<String, u32>> {
     let by_index = conn.get_interface_names().await?;
     Ok(by_index
         .into_iter()
@@ -254,6 +282,12 @@ async fn get_interface_indices() -> Result<HashMap<String, u32>> {
         .collect())
 }
 
+/// Parse a link-layer multicast group address.
+fn parse_group(address: &str) -> Result<[u8; 6]> {
+    nlink::util::addr::parse_mac(address)
+        .map_err(|e| nlink::netlink::Error::InvalidMessage(format!("invalid MAC address: {}", e)))
+}
+
 /// Format a hex MAC address string (e.g., "01005e000001") to colon-separated format.
 fn format_mac_from_hex(hex: &str) -> String {
     let bytes: Vec<String> = hex
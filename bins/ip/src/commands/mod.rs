@@ -1,6 +1,8 @@
 //! IP command implementations.
 
+pub mod addrlabel;
 pub mod address;
+pub mod ila;
 pub mod link;
 mod link_add;
 pub mod macsec;
@@ -10,6 +12,7 @@ pub mod mptcp;
 pub mod neighbor;
 pub mod netns;
 pub mod nexthop;
+pub mod ntable;
 pub mod route;
 pub mod rule;
 pub mod sr;
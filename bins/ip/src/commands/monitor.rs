@@ -1,14 +1,28 @@
 //! ip monitor - watch for netlink events.
 //!
 //! This module uses the Connection events() API from nlink for high-level
-//! event monitoring with Stream trait support.
+//! event monitoring with Stream trait support. `--capture` records the raw
+//! messages to a file and `ip monitor file` replays one, in the same format
+//! as iproute2's `rtmon`.
 
-use clap::{Args, ValueEnum};
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use clap::{Args, Subcommand, ValueEnum};
 use nlink::{
-    netlink::{Connection, NetworkEvent, Result, Route, RtnetlinkGroup, types::link::iff},
+    netlink::{
+        Connection, Error, EventSource, IfIndexResolver, NetworkEvent, Result, Route,
+        RtnetlinkGroup,
+        capture::{CaptureReader, CaptureWriter},
+        route::RouteSelector,
+        types::{link::iff, route::RouteProtocol},
+    },
     output::{
-        AddressEvent, IpEvent, LinkEvent, MonitorConfig, NeighborEvent, OutputFormat,
-        OutputOptions, RouteEvent, print_event, print_monitor_start,
+        AddressEvent, IpEvent, LinkEvent, MonitorConfig, NeighborEvent, NexthopEvent, NsIdEvent,
+        OutputFormat, OutputOptions, RouteEvent, RuleEvent, print_event, print_event_at,
+        print_monitor_start,
     },
 };
 use tokio_stream::StreamExt;
@@ -24,6 +38,12 @@ pub enum EventType {
     Route,
     /// Neighbor (ARP/NDP) cache changes.
     Neigh,
+    /// Policy-routing rule changes.
+    Rule,
+    /// Nexthop-object changes.
+    Nexthop,
+    /// Network-namespace ID assignments.
+    Nsid,
     /// All event types.
     All,
 }
@@ -44,6 +64,12 @@ impl EventType {
                     groups.push(RtnetlinkGroup::Ipv6Route);
                 }
                 EventType::Neigh => groups.push(RtnetlinkGroup::Neigh),
+                EventType::Rule => {
+                    groups.push(RtnetlinkGroup::Ipv4Rule);
+                    groups.push(RtnetlinkGroup::Ipv6Rule);
+                }
+                EventType::Nexthop => groups.push(RtnetlinkGroup::Nexthop),
+                EventType::Nsid => groups.push(RtnetlinkGroup::NsId),
                 EventType::All => {
                     groups.push(RtnetlinkGroup::Link);
                     groups.push(RtnetlinkGroup::Ipv4Addr);
@@ -51,14 +77,35 @@ impl EventType {
                     groups.push(RtnetlinkGroup::Ipv4Route);
                     groups.push(RtnetlinkGroup::Ipv6Route);
                     groups.push(RtnetlinkGroup::Neigh);
+                    groups.push(RtnetlinkGroup::Ipv4Rule);
+                    groups.push(RtnetlinkGroup::Ipv6Rule);
+                    groups.push(RtnetlinkGroup::Nexthop);
+                    groups.push(RtnetlinkGroup::NsId);
                 }
             }
         }
         groups
     }
+
+    /// Whether `event` belongs to one of `types`. Replayed captures hold
+    /// whatever was recorded, so the kernel-side group subscription
+    /// can't do this filtering for them.
+    fn matches(types: &[EventType], event: &NetworkEvent) -> bool {
+        types.iter().any(|t| match t {
+            EventType::Link => event.as_link().is_some(),
+            EventType::Address => event.as_address().is_some(),
+            EventType::Route => event.as_route().is_some(),
+            EventType::Neigh => event.as_neighbor().is_some(),
+            EventType::Rule => event.as_rule().is_some(),
+            EventType::Nexthop => event.as_nexthop().is_some(),
+            EventType::Nsid => event.as_nsid().is_some(),
+            EventType::All => true,
+        })
+    }
 }
 
 #[derive(Args)]
+#[command(subcommand_precedence_over_arg = true)]
 pub struct MonitorCmd {
     /// Event types to monitor.
     #[arg(default_value = "all")]
@@ -67,6 +114,49 @@ pub struct MonitorCmd {
     /// Label output lines with event timestamps.
     #[arg(short = 't', long)]
     timestamp: bool,
+
+    /// Only show route events for this table (name or number; repeatable).
+    #[arg(long)]
+    table: Vec<String>,
+
+    /// Only show route events installed by this protocol (repeatable).
+    #[arg(long)]
+    proto: Vec<String>,
+
+    /// Hide route events installed by this protocol (repeatable).
+    #[arg(long = "exclude-proto")]
+    exclude_proto: Vec<String>,
+
+    /// Only show route events for destinations within this prefix.
+    #[arg(long)]
+    prefix: Option<String>,
+
+    /// Only show routes and addresses learned from IPv6 router
+    /// advertisements.
+    #[arg(long, conflicts_with = "exclude_ra")]
+    ra: bool,
+
+    /// Hide routes and addresses learned from IPv6 router advertisements.
+    #[arg(long)]
+    exclude_ra: bool,
+
+    /// Also record the received messages, with timestamps, to this file
+    /// (readable by `ip monitor file`).
+    #[arg(long, value_name = "PATH")]
+    capture: Option<PathBuf>,
+
+    #[command(subcommand)]
+    source: Option<MonitorSource>,
+}
+
+/// Where events come from, when not the live kernel.
+#[derive(Subcommand)]
+enum MonitorSource {
+    /// Replay events from a capture file written by `--capture` or rtmon.
+    File {
+        /// Capture file to read.
+        path: PathBuf,
+    },
 }
 
 impl MonitorCmd {
@@ -76,6 +166,11 @@ impl MonitorCmd {
             .with_timestamp(self.timestamp)
             .with_format(format)
             .with_opts(*opts);
+        let selector = self.route_selector()?;
+
+        if let Some(MonitorSource::File { path }) = &self.source {
+            return self.replay(path, &selector, &config);
+        }
 
         // Convert CLI event types to RtnetlinkGroups
         let groups = EventType::to_groups(&self.objects);
@@ -91,7 +186,14 @@ impl MonitorCmd {
             "Monitoring netlink events (Ctrl+C to stop)...",
         )?;
 
-        let mut events = conn.events().await;
+        // Resolve `dev` names from a cache that follows link events (when
+        // the link group is subscribed) instead of sysfs on every line.
+        let names = conn.interface_resolver().await?;
+        names.install();
+        if let Some(path) = &self.capture {
+            return capture(&conn, path, &names, &selector, &config).await;
+        }
+        let mut events = std::pin::pin!(selector.filter_events(names.track(conn.events().await)));
 
         while let Some(result) = events.next().await {
             let event = result?;
@@ -102,6 +204,95 @@ impl MonitorCmd {
 
         Ok(())
     }
+
+    /// Print the events recorded in a capture file, stamped with the time
+    /// they were captured.
+    fn replay(&self, path: &Path, selector: &RouteSelector, config: &MonitorConfig) -> Result<()> {
+        let reader = CaptureReader::open(path)
+            .map_err(|e| Error::InvalidMessage(format!("{}: {}", path.display(), e)))?;
+
+        // Names come from the capture's own link messages where it has
+        // them, so replays show the interfaces as they were at the time.
+        let names = IfIndexResolver::new();
+        names.install();
+
+        let mut stdout = std::io::stdout().lock();
+        for msg in reader {
+            let msg = msg?;
+            let time = msg.timestamp.unwrap_or(SystemTime::UNIX_EPOCH);
+            for event in msg.events::<Route>() {
+                names.observe(&event);
+                if !EventType::matches(&self.objects, &event) || !selector.matches_event(&event) {
+                    continue;
+                }
+                if let Some(ip_event) = convert_event(event) {
+                    print_event_at(&mut stdout, &ip_event, config, time)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Build the route event filter from `--table`/`--proto`/`--prefix`
+    /// and `--ra`/`--exclude-ra`.
+    fn route_selector(&self) -> Result<RouteSelector> {
+        if !self.proto.is_empty() || !self.exclude_proto.is_empty() {
+            nlink::util::names::load_system_protocol_names();
+        }
+        let mut selector = RouteSelector::new();
+        for table in &self.table {
+            let id = nlink::util::names::table_id(table)
+                .ok_or_else(|| Error::InvalidMessage(format!("unknown table: {}", table)))?;
+            selector = selector.table(id);
+        }
+        for proto in &self.proto {
+            selector = selector.protocol(proto.parse::<RouteProtocol>()?);
+        }
+        for proto in &self.exclude_proto {
+            selector = selector.exclude_protocol(proto.parse::<RouteProtocol>()?);
+        }
+        if let Some(prefix) = &self.prefix {
+            let (addr, len) = nlink::util::addr::parse_prefix(prefix)?;
+            selector = selector.prefix(addr, len);
+        }
+        if self.ra || self.exclude_ra {
+            selector = selector.router_advertised(self.ra);
+        }
+        Ok(selector)
+    }
+}
+
+/// Monitor like the default path, additionally writing every received
+/// message batch to `path`. Reads the socket directly rather than through
+/// an event stream, since the stream only hands out parsed events.
+async fn capture(
+    conn: &Connection<Route>,
+    path: &Path,
+    names: &IfIndexResolver,
+    selector: &RouteSelector,
+    config: &MonitorConfig,
+) -> Result<()> {
+    let mut writer = CaptureWriter::create(path)
+        .map_err(|e| Error::InvalidMessage(format!("{}: {}", path.display(), e)))?;
+    let mut stdout = std::io::stdout().lock();
+
+    loop {
+        let data = conn.socket().recv_msg().await?;
+        let now = SystemTime::now();
+        // Flush per batch so an interrupted monitor leaves a complete file.
+        writer.write_batch_at(now, &data)?;
+        writer.flush()?;
+
+        for event in Route::parse_events(&data) {
+            names.observe(&event);
+            if !selector.matches_event(&event) {
+                continue;
+            }
+            if let Some(ip_event) = convert_event(event) {
+                print_event_at(&mut stdout, &ip_event, config, now)?;
+            }
+        }
+    }
 }
 
 /// Convert a NetworkEvent to an IpEvent for output formatting.
@@ -161,6 +352,47 @@ fn convert_event(event: NetworkEvent) -> Option<IpEvent> {
         });
     }
 
+    if let Some(rule) = event.as_rule() {
+        return Some(IpEvent::Rule(RuleEvent {
+            action,
+            family: rule.family(),
+            priority: rule.priority(),
+            source: rule.source().map(|a| a.to_string()),
+            src_len: rule.src_len(),
+            destination: rule.destination().map(|a| a.to_string()),
+            dst_len: rule.dst_len(),
+            iif: rule.iifname().map(|s| s.to_string()),
+            oif: rule.oifname().map(|s| s.to_string()),
+            fwmark: rule.fwmark(),
+            table: rule.table(),
+            rule_action: rule.action().name(),
+        }));
+    }
+
+    if let Some(nh) = event.as_nexthop() {
+        return Some(IpEvent::Nexthop(NexthopEvent {
+            action,
+            id: nh.id(),
+            gateway: nh.gateway().map(|g| g.to_string()),
+            ifindex: nh.ifindex(),
+            blackhole: nh.is_blackhole(),
+            group: nh
+                .group()
+                .unwrap_or_default()
+                .iter()
+                .map(|m| (m.id(), m.weight()))
+                .collect(),
+        }));
+    }
+
+    if let Some(nsid) = event.as_nsid() {
+        return Some(IpEvent::NsId(NsIdEvent {
+            action,
+            nsid: nsid.nsid(),
+            pid: nsid.pid(),
+        }));
+    }
+
     // TC events are not handled by ip monitor
     None
 }
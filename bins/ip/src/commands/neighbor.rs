@@ -6,7 +6,11 @@ use std::net::IpAddr;
 
 use clap::{Args, Subcommand};
 use nlink::{
-    netlink::{Connection, Result, Route, neigh::Neighbor, types::neigh::NeighborState},
+    netlink::{
+        Connection, Result, Route,
+        neigh::Neighbor,
+        types::neigh::{NeighborState, nud},
+    },
     output::{OutputFormat, OutputOptions, print_all},
 };
 
@@ -16,12 +20,36 @@ pub struct NeighborCmd {
     action: Option<NeighborAction>,
 }
 
+/// Entry flags shared by `add` and `replace`.
+#[derive(Args)]
+struct NeighborFlags {
+    /// Create a proxy ARP/NDP entry (answer for the address on this device).
+    #[arg(long)]
+    proxy: bool,
+
+    /// Mark the neighbor as an IPv6 router.
+    #[arg(long)]
+    router: bool,
+
+    /// Mark the entry as learned by an external control plane.
+    #[arg(long)]
+    extern_learn: bool,
+}
+
 #[derive(Subcommand)]
 enum NeighborAction {
     /// Show neighbor entries.
     Show {
         /// Interface name.
         dev: Option<String>,
+
+        /// Only show entries on ports of this master (bridge, VRF, bond).
+        #[arg(long, conflicts_with = "dev")]
+        master: Option<String>,
+
+        /// Show proxy entries instead of the neighbor cache.
+        #[arg(long, conflicts_with = "master")]
+        proxy: bool,
     },
 
     /// Add a neighbor entry.
@@ -29,9 +57,9 @@ enum NeighborAction {
         /// IP address.
         address: String,
 
-        /// Link-layer address (MAC).
-        #[arg(long)]
-        lladdr: String,
+        /// Link-layer address (MAC). Not needed for proxy entries.
+        #[arg(long, required_unless_present = "proxy")]
+        lladdr: Option<String>,
 
         /// Device name.
         #[arg(long, short)]
@@ -44,6 +72,9 @@ enum NeighborAction {
         /// NUD state (reachable, stale, delay, probe, failed, noarp).
         #[arg(long)]
         state: Option<String>,
+
+        #[command(flatten)]
+        flags: NeighborFlags,
     },
 
     /// Replace or add a neighbor entry.
@@ -51,9 +82,9 @@ enum NeighborAction {
         /// IP address.
         address: String,
 
-        /// Link-layer address (MAC).
-        #[arg(long)]
-        lladdr: String,
+        /// Link-layer address (MAC). Not needed for proxy entries.
+        #[arg(long, required_unless_present = "proxy")]
+        lladdr: Option<String>,
 
         /// Device name.
         #[arg(long, short)]
@@ -62,6 +93,13 @@ enum NeighborAction {
         /// Create permanent entry.
         #[arg(long)]
         permanent: bool,
+
+        /// NUD state (reachable, stale, delay, probe, failed, noarp).
+        #[arg(long)]
+        state: Option<String>,
+
+        #[command(flatten)]
+        flags: NeighborFlags,
     },
 
     /// Delete a neighbor entry.
@@ -72,12 +110,21 @@ enum NeighborAction {
         /// Device name.
         #[arg(long, short)]
         dev: String,
+
+        /// Delete a proxy entry.
+        #[arg(long)]
+        proxy: bool,
     },
 
     /// Flush neighbor entries.
     Flush {
         /// Device name.
         dev: Option<String>,
+
+        /// Only flush entries in this state (repeatable; `all` for every
+        /// state). Default: everything but permanent and noarp entries.
+        #[arg(long = "nud", value_name = "STATE")]
+        nud: Vec<String>,
     },
 }
 
@@ -89,9 +136,22 @@ impl NeighborCmd {
         opts: &OutputOptions,
         family: Option<u8>,
     ) -> Result<()> {
-        match self.action.unwrap_or(NeighborAction::Show { dev: None }) {
-            NeighborAction::Show { dev } => {
-                Self::show(conn, dev.as_deref(), format, opts, family).await
+        match self.action.unwrap_or(NeighborAction::Show {
+            dev: None,
+            master: None,
+            proxy: false,
+        }) {
+            NeighborAction::Show { dev, master, proxy } => {
+                Self::show(
+                    conn,
+                    dev.as_deref(),
+                    master.as_deref(),
+                    proxy,
+                    format,
+                    opts,
+                    family,
+                )
+                .await
             }
             NeighborAction::Add {
                 address,
@@ -99,14 +159,16 @@ impl NeighborCmd {
                 dev,
                 permanent,
                 state,
+                flags,
             } => {
                 Self::add(
                     conn,
                     &address,
-                    &lladdr,
+                    lladdr.as_deref(),
                     &dev,
                     permanent,
                     state.as_deref(),
+                    &flags,
                     false,
                 )
                 .await
@@ -116,21 +178,59 @@ impl NeighborCmd {
                 lladdr,
                 dev,
                 permanent,
-            } => Self::add(conn, &address, &lladdr, &dev, permanent, None, true).await,
-            NeighborAction::Del { address, dev } => Self::del(conn, &address, &dev).await,
-            NeighborAction::Flush { dev } => Self::flush(conn, dev.as_deref(), family).await,
+                state,
+                flags,
+            } => {
+                Self::add(
+                    conn,
+                    &address,
+                    lladdr.as_deref(),
+                    &dev,
+                    permanent,
+                    state.as_deref(),
+                    &flags,
+                    true,
+                )
+                .await
+            }
+            NeighborAction::Del {
+                address,
+                dev,
+                proxy,
+            } => Self::del(conn, &address, &dev, proxy).await,
+            NeighborAction::Flush { dev, nud } => {
+                Self::flush(conn, dev.as_deref(), &nud, family).await
+            }
         }
     }
 
     async fn show(
         conn: &Connection<Route>,
         dev: Option<&str>,
+        master: Option<&str>,
+        proxy: bool,
         format: OutputFormat,
         opts: &OutputOptions,
         family: Option<u8>,
     ) -> Result<()> {
-        // Get neighbors (optionally filtered by device)
-        let neighbors = neighbors_for_dev(conn, dev).await?;
+        // Get neighbors (optionally filtered by device or master)
+        let neighbors = if proxy {
+            // The proxy table has no per-device dump; filter here.
+            let ifindex = match dev {
+                Some(name) => Some(resolve_dev(conn, name).await?),
+                None => None,
+            };
+            conn.get_proxy_neighbors()
+                .await?
+                .into_iter()
+                .filter(|n| ifindex.is_none_or(|i| n.ifindex() == i))
+                .collect()
+        } else {
+            match master {
+                Some(master) => conn.get_neighbors_by_master(master).await?,
+                None => neighbors_for_dev(conn, dev).await?,
+            }
+        };
 
         // Filter by family if specified
         let neighbors: Vec<_> = if let Some(fam) = family {
@@ -142,6 +242,8 @@ impl NeighborCmd {
             neighbors
         };
 
+        // One link dump serves every row's `dev` column.
+        conn.interface_resolver().await?.install();
         print_all(&neighbors, format, opts)?;
 
         Ok(())
@@ -151,10 +253,11 @@ impl NeighborCmd {
     async fn add(
         conn: &Connection<Route>,
         address: &str,
-        lladdr: &str,
+        lladdr: Option<&str>,
         dev: &str,
         permanent: bool,
         state_name: Option<&str>,
+        flags: &NeighborFlags,
         replace: bool,
     ) -> Result<()> {
         use nlink::util::addr::{parse_addr, parse_mac};
@@ -163,28 +266,31 @@ impl NeighborCmd {
             nlink::netlink::Error::InvalidMessage(format!("invalid address: {}", e))
         })?;
 
-        let mac = parse_mac(lladdr)
-            .map_err(|e| nlink::netlink::Error::InvalidMessage(format!("invalid MAC: {}", e)))?;
-
         // Parse NUD state
         let state = if permanent {
             NeighborState::Permanent
         } else if let Some(s) = state_name {
-            match s.to_lowercase().as_str() {
-                "reachable" => NeighborState::Reachable,
-                "stale" => NeighborState::Stale,
-                "delay" => NeighborState::Delay,
-                "probe" => NeighborState::Probe,
-                "failed" => NeighborState::Failed,
-                "noarp" => NeighborState::Noarp,
-                "permanent" => NeighborState::Permanent,
-                _ => NeighborState::Reachable,
-            }
+            s.parse::<NeighborState>()?
         } else {
             NeighborState::Reachable
         };
 
-        let neigh = Neighbor::new(dev, addr).lladdr(mac).state(state);
+        let mut neigh = Neighbor::new(dev, addr).state(state);
+        if let Some(lladdr) = lladdr {
+            let mac = parse_mac(lladdr).map_err(|e| {
+                nlink::netlink::Error::InvalidMessage(format!("invalid MAC: {}", e))
+            })?;
+            neigh = neigh.lladdr(mac);
+        }
+        if flags.proxy {
+            neigh = neigh.proxy();
+        }
+        if flags.router {
+            neigh = neigh.router();
+        }
+        if flags.extern_learn {
+            neigh = neigh.extern_learn();
+        }
 
         if replace {
             conn.replace_neighbor(neigh).await
@@ -193,22 +299,32 @@ impl NeighborCmd {
         }
     }
 
-    async fn del(conn: &Connection<Route>, address: &str, dev: &str) -> Result<()> {
+    async fn del(conn: &Connection<Route>, address: &str, dev: &str, proxy: bool) -> Result<()> {
         use nlink::util::addr::parse_addr;
 
         let addr: IpAddr = parse_addr(address).map_err(|e| {
             nlink::netlink::Error::InvalidMessage(format!("invalid address: {}", e))
         })?;
 
-        let neigh = Neighbor::new(dev, addr);
+        let mut neigh = Neighbor::new(dev, addr);
+        if proxy {
+            neigh = neigh.proxy();
+        }
         conn.del_neighbor(neigh).await
     }
 
-    async fn flush(conn: &Connection<Route>, dev: Option<&str>, family: Option<u8>) -> Result<()> {
+    async fn flush(
+        conn: &Connection<Route>,
+        dev: Option<&str>,
+        states: &[String],
+        family: Option<u8>,
+    ) -> Result<()> {
+        let mask = parse_state_mask(states)?;
+
         // Get all neighbor entries
         let neighbors = neighbors_for_dev(conn, dev).await?;
 
-        // Filter by family if specified, and skip permanent/noarp entries
+        // Filter by family if specified, and by state
         let neighbors_to_delete: Vec<_> = neighbors
             .into_iter()
             .filter(|n| {
@@ -217,22 +333,22 @@ impl NeighborCmd {
                 {
                     return false;
                 }
-                // Skip permanent/noarp entries (like iproute2 does)
-                !n.is_permanent()
+                n.state().number() & mask != 0
             })
             .collect();
 
-        let count = neighbors_to_delete.len();
-
-        // Delete each neighbor - need interface name for Neighbor builder
-        let names = conn.get_interface_names().await?;
-
+        let mut count = 0;
         for neigh in neighbors_to_delete {
-            if let Some(addr) = neigh.destination()
-                && let Some(ifname) = names.get(&neigh.ifindex())
-            {
-                // Ignore errors for individual deletes (entry may have been removed)
-                let _ = conn.del_neighbor(Neighbor::new(ifname, *addr)).await;
+            if let Some(addr) = neigh.destination() {
+                match conn
+                    .del_neighbor(Neighbor::with_index(neigh.ifindex(), *addr))
+                    .await
+                {
+                    Ok(()) => count += 1,
+                    // The entry may have expired or been removed meanwhile.
+                    Err(e) if e.is_not_found() => {}
+                    Err(e) => return Err(e),
+                }
             }
         }
 
@@ -244,6 +360,33 @@ impl NeighborCmd {
     }
 }
 
+/// Combine `--nud` state names into a `NUD_*` mask.
+///
+/// No states means iproute2's default: everything but permanent and
+/// noarp entries.
+fn parse_state_mask(states: &[String]) -> Result<u16> {
+    if states.is_empty() {
+        return Ok(nud::FLUSHABLE);
+    }
+    states.iter().try_fold(0, |mask, s| {
+        let bits = if s.eq_ignore_ascii_case("all") {
+            nud::ALL
+        } else {
+            s.parse::<NeighborState>()?.number()
+        };
+        Ok(mask | bits)
+    })
+}
+
+/// Resolve a device name to its ifindex over netlink.
+async fn resolve_dev(conn: &Connection<Route>, name: &str) -> Result<u32> {
+    Ok(conn
+        .get_link_by_name(name)
+        .await?
+        .ok_or_else(|| nlink::netlink::Error::InvalidMessage(format!("device `{name}` not found")))?
+        .ifindex())
+}
+
 /// Fetch neighbor entries, optionally scoped to a device.
 ///
 /// When a device is given, its ifindex is resolved over netlink
@@ -257,13 +400,7 @@ async fn neighbors_for_dev(
 ) -> Result<Vec<nlink::netlink::messages::NeighborMessage>> {
     match dev {
         Some(name) => {
-            let ifindex = conn
-                .get_link_by_name(name)
-                .await?
-                .ok_or_else(|| {
-                    nlink::netlink::Error::InvalidMessage(format!("device `{name}` not found"))
-                })?
-                .ifindex();
+            let ifindex = resolve_dev(conn, name).await?;
             conn.get_neighbors_by_index(ifindex).await
         }
         None => conn.get_neighbors().await,
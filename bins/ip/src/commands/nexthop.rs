@@ -6,7 +6,7 @@ use clap::{Args, Subcommand};
 use nlink::{
     netlink::{
         Connection, Error, Result, Route,
-        nexthop::{Nexthop, NexthopBuilder, NexthopGroupBuilder},
+        nexthop::{Nexthop, NexthopBucket, NexthopBuilder, NexthopGroupBuilder},
     },
     output::OutputFormat,
 };
@@ -41,6 +41,34 @@ enum NexthopCommand {
     },
     /// Flush all nexthops
     Flush,
+    /// Show resilient group buckets
+    Bucket {
+        #[command(subcommand)]
+        command: Option<BucketCommand>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BucketCommand {
+    /// Show buckets
+    #[command(visible_alias = "list", visible_alias = "ls")]
+    Show {
+        /// Only buckets of this group
+        #[arg(long)]
+        id: Option<u32>,
+        /// Only buckets pointing at this nexthop
+        #[arg(long)]
+        nhid: Option<u32>,
+    },
+    /// Show one bucket
+    Get {
+        /// Group ID
+        #[arg(long)]
+        id: u32,
+        /// Bucket index
+        #[arg(long)]
+        index: u16,
+    },
 }
 
 #[derive(Args)]
@@ -60,6 +88,9 @@ struct NexthopAddArgs {
     /// On-link flag (gateway is directly reachable)
     #[arg(long)]
     onlink: bool,
+    /// FDB nexthop or group (for VXLAN)
+    #[arg(long)]
+    fdb: bool,
     /// Group members (id,weight/id,weight/... or id/id/...)
     #[arg(long)]
     group: Option<String>,
@@ -72,6 +103,9 @@ struct NexthopAddArgs {
     /// Resilient group idle timer (seconds)
     #[arg(long)]
     idle_timer: Option<u32>,
+    /// Resilient group unbalanced timer (seconds)
+    #[arg(long)]
+    unbalanced_timer: Option<u32>,
 }
 
 impl NexthopCmd {
@@ -94,6 +128,15 @@ impl NexthopCmd {
             Some(NexthopCommand::Replace(args)) => add_nexthop(conn, args, true).await,
             Some(NexthopCommand::Del { id }) => del_nexthop(conn, id).await,
             Some(NexthopCommand::Flush) => flush_nexthops(conn).await,
+            Some(NexthopCommand::Bucket { command }) => match command {
+                None => show_buckets(conn, format, opts, None, None).await,
+                Some(BucketCommand::Show { id, nhid }) => {
+                    show_buckets(conn, format, opts, id, nhid).await
+                }
+                Some(BucketCommand::Get { id, index }) => {
+                    get_bucket(conn, format, opts, id, index).await
+                }
+            },
         }
     }
 }
@@ -181,6 +224,9 @@ fn print_nexthop(nh: &Nexthop, names: &std::collections::HashMap<u32, String>) {
                         if res.unbalanced_timer > 0 {
                             print!("unbalanced_timer {} ", res.unbalanced_timer);
                         }
+                        if res.unbalanced_time > 0 {
+                            print!("unbalanced_time {} ", res.unbalanced_time);
+                        }
                     }
                 }
                 _ => {
@@ -279,6 +325,7 @@ fn nexthops_to_json(
                     obj["buckets"] = res.buckets.into();
                     obj["idle_timer"] = res.idle_timer.into();
                     obj["unbalanced_timer"] = res.unbalanced_timer.into();
+                    obj["unbalanced_time"] = res.unbalanced_time.into();
                 }
             } else {
                 if nh.is_blackhole() {
@@ -345,7 +392,7 @@ async fn add_nexthop(conn: &Connection<Route>, args: NexthopAddArgs, replace: bo
             let nh_id: u32 = parts[0]
                 .parse()
                 .map_err(|_| Error::InvalidMessage(format!("invalid nexthop id: {}", parts[0])))?;
-            let weight: u8 = if parts.len() > 1 {
+            let weight: u16 = if parts.len() > 1 {
                 parts[1]
                     .parse()
                     .map_err(|_| Error::InvalidMessage(format!("invalid weight: {}", parts[1])))?
@@ -377,6 +424,12 @@ async fn add_nexthop(conn: &Connection<Route>, args: NexthopAddArgs, replace: bo
         if let Some(idle) = args.idle_timer {
             builder = builder.idle_timer(idle);
         }
+        if let Some(unbalanced) = args.unbalanced_timer {
+            builder = builder.unbalanced_timer(unbalanced);
+        }
+        if args.fdb {
+            builder = builder.fdb();
+        }
 
         if replace {
             conn.replace_nexthop_group(builder).await?;
@@ -400,6 +453,9 @@ async fn add_nexthop(conn: &Connection<Route>, args: NexthopAddArgs, replace: bo
                 builder = builder.onlink();
             }
         }
+        if args.fdb {
+            builder = builder.fdb();
+        }
 
         if replace {
             conn.replace_nexthop(builder).await?;
@@ -411,6 +467,81 @@ async fn add_nexthop(conn: &Connection<Route>, args: NexthopAddArgs, replace: bo
     Ok(())
 }
 
+async fn show_buckets(
+    conn: &Connection<Route>,
+    format: OutputFormat,
+    opts: &nlink::output::OutputOptions,
+    id: Option<u32>,
+    nhid: Option<u32>,
+) -> Result<()> {
+    let buckets: Vec<NexthopBucket> = conn
+        .get_nexthop_buckets(id)
+        .await?
+        .into_iter()
+        .filter(|b| nhid.is_none_or(|nhid| b.nexthop_id() == nhid))
+        .collect();
+    print_buckets(&buckets, format, opts)
+}
+
+async fn get_bucket(
+    conn: &Connection<Route>,
+    format: OutputFormat,
+    opts: &nlink::output::OutputOptions,
+    id: u32,
+    index: u16,
+) -> Result<()> {
+    match conn.get_nexthop_bucket(id, index).await? {
+        Some(bucket) => print_buckets(&[bucket], format, opts),
+        None => {
+            eprintln!("Bucket {} of nexthop group {} not found", index, id);
+            Ok(())
+        }
+    }
+}
+
+fn print_buckets(
+    buckets: &[NexthopBucket],
+    format: OutputFormat,
+    opts: &nlink::output::OutputOptions,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let value: Vec<serde_json::Value> = buckets
+                .iter()
+                .map(|b| {
+                    serde_json::json!({
+                        "id": b.group_id(),
+                        "bucket": {
+                            "index": b.index(),
+                            "idle_time": b.idle_time().as_secs_f64(),
+                            "nhid": b.nexthop_id(),
+                        },
+                    })
+                })
+                .collect();
+            let json = if opts.pretty {
+                serde_json::to_string_pretty(&value)
+            } else {
+                serde_json::to_string(&value)
+            }
+            .map_err(|e| Error::InvalidMessage(e.to_string()))?;
+            println!("{}", json);
+        }
+        OutputFormat::Text => {
+            for b in buckets {
+                println!(
+                    "id {} index {} idle_time {} nhid {}",
+                    b.group_id(),
+                    b.index(),
+                    b.idle_time().as_secs_f64(),
+                    b.nexthop_id()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn del_nexthop(conn: &Connection<Route>, id: u32) -> Result<()> {
     conn.del_nexthop(id).await?;
     Ok(())
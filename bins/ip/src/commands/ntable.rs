@@ -0,0 +1,230 @@
+//! ip ntable command implementation.
+//!
+//! Shows and changes neighbor table parameters (gc thresholds, reachable
+//! time, probe counts) through `RTM_GETNEIGHTBL` / `RTM_SETNEIGHTBL`.
+//! Times are in milliseconds, as in iproute2.
+
+use std::time::Duration;
+
+use clap::{Args, Subcommand};
+use nlink::{
+    netlink::{Connection, Result, Route, neigh::NeighborTableParams},
+    output::{OutputFormat, OutputOptions, print_all},
+};
+
+#[derive(Args)]
+pub struct NtableCmd {
+    #[command(subcommand)]
+    action: Option<NtableAction>,
+}
+
+#[derive(Subcommand)]
+enum NtableAction {
+    /// Show neighbor tables.
+    Show {
+        /// Only show this device's parameters.
+        #[arg(long, short)]
+        dev: Option<String>,
+
+        /// Only show this table (arp_cache, ndisc_cache, ...).
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Change neighbor table parameters.
+    Change(ChangeArgs),
+}
+
+#[derive(Args)]
+struct ChangeArgs {
+    /// Table name (arp_cache, ndisc_cache, ...).
+    #[arg(long)]
+    name: String,
+
+    /// Change this device's parameters instead of the table defaults.
+    #[arg(long, short)]
+    dev: Option<String>,
+
+    /// gc_thresh1 (entries). Initial namespace only.
+    #[arg(long)]
+    thresh1: Option<u32>,
+
+    /// gc_thresh2 (entries). Initial namespace only.
+    #[arg(long)]
+    thresh2: Option<u32>,
+
+    /// gc_thresh3 (entries). Initial namespace only.
+    #[arg(long)]
+    thresh3: Option<u32>,
+
+    /// Garbage collection interval (ms). Initial namespace only.
+    #[arg(long, value_name = "MS")]
+    gc_int: Option<u64>,
+
+    /// Base reachable time (ms).
+    #[arg(long, value_name = "MS")]
+    base_reachable: Option<u64>,
+
+    /// Retransmit time (ms).
+    #[arg(long, value_name = "MS")]
+    retrans: Option<u64>,
+
+    /// Stale entry gc time (ms).
+    #[arg(long, value_name = "MS")]
+    gc_stale: Option<u64>,
+
+    /// First probe delay (ms).
+    #[arg(long, value_name = "MS")]
+    delay_probe: Option<u64>,
+
+    /// Unresolved queue length (packets).
+    #[arg(long)]
+    queue: Option<u32>,
+
+    /// Userspace resolver probes.
+    #[arg(long)]
+    app_probes: Option<u32>,
+
+    /// Unicast probes.
+    #[arg(long)]
+    ucast_probes: Option<u32>,
+
+    /// Multicast probes.
+    #[arg(long)]
+    mcast_probes: Option<u32>,
+
+    /// Anycast answer delay (ms).
+    #[arg(long, value_name = "MS")]
+    anycast_delay: Option<u64>,
+
+    /// Proxy answer delay (ms).
+    #[arg(long, value_name = "MS")]
+    proxy_delay: Option<u64>,
+
+    /// Proxy queue length (packets).
+    #[arg(long)]
+    proxy_queue: Option<u32>,
+
+    /// Minimum time between lladdr updates (ms).
+    #[arg(long, value_name = "MS")]
+    locktime: Option<u64>,
+}
+
+impl NtableCmd {
+    pub async fn run(
+        self,
+        conn: &Connection<Route>,
+        format: OutputFormat,
+        opts: &OutputOptions,
+        family: Option<u8>,
+    ) -> Result<()> {
+        match self.action.unwrap_or(NtableAction::Show {
+            dev: None,
+            name: None,
+        }) {
+            NtableAction::Show { dev, name } => {
+                Self::show(conn, dev.as_deref(), name.as_deref(), format, opts, family).await
+            }
+            NtableAction::Change(args) => Self::change(conn, args).await,
+        }
+    }
+
+    async fn show(
+        conn: &Connection<Route>,
+        dev: Option<&str>,
+        name: Option<&str>,
+        format: OutputFormat,
+        opts: &OutputOptions,
+        family: Option<u8>,
+    ) -> Result<()> {
+        let ifindex = match dev {
+            Some(dev) => Some(
+                conn.get_link_by_name(dev)
+                    .await?
+                    .ok_or_else(|| {
+                        nlink::netlink::Error::InvalidMessage(format!("device `{dev}` not found"))
+                    })?
+                    .ifindex(),
+            ),
+            None => None,
+        };
+
+        let tables: Vec<_> = conn
+            .get_neigh_tables()
+            .await?
+            .into_iter()
+            .filter(|t| family.is_none_or(|f| t.family() == f))
+            .filter(|t| name.is_none_or(|n| t.name() == Some(n)))
+            .filter(|t| ifindex.is_none_or(|i| t.ifindex() == Some(i)))
+            .collect();
+
+        conn.interface_resolver().await?.install();
+        print_all(&tables, format, opts)?;
+
+        Ok(())
+    }
+
+    async fn change(conn: &Connection<Route>, args: ChangeArgs) -> Result<()> {
+        let ms = Duration::from_millis;
+        let mut params = NeighborTableParams::new(args.name);
+        if let Some(dev) = args.dev {
+            params = params.dev(dev);
+        }
+        if let Some(n) = args.thresh1 {
+            params = params.gc_thresh1(n);
+        }
+        if let Some(n) = args.thresh2 {
+            params = params.gc_thresh2(n);
+        }
+        if let Some(n) = args.thresh3 {
+            params = params.gc_thresh3(n);
+        }
+        if let Some(t) = args.gc_int {
+            params = params.gc_interval(ms(t));
+        }
+        if let Some(t) = args.base_reachable {
+            params = params.base_reachable_time(ms(t));
+        }
+        if let Some(t) = args.retrans {
+            params = params.retrans_time(ms(t));
+        }
+        if let Some(t) = args.gc_stale {
+            params = params.gc_stale_time(ms(t));
+        }
+        if let Some(t) = args.delay_probe {
+            params = params.delay_probe_time(ms(t));
+        }
+        if let Some(n) = args.queue {
+            params = params.queue_len(n);
+        }
+        if let Some(n) = args.app_probes {
+            params = params.app_probes(n);
+        }
+        if let Some(n) = args.ucast_probes {
+            params = params.ucast_probes(n);
+        }
+        if let Some(n) = args.mcast_probes {
+            params = params.mcast_probes(n);
+        }
+        if let Some(t) = args.anycast_delay {
+            params = params.anycast_delay(ms(t));
+        }
+        if let Some(t) = args.proxy_delay {
+            params = params.proxy_delay(ms(t));
+        }
+        if let Some(n) = args.proxy_queue {
+            params = params.proxy_qlen(n);
+        }
+        if let Some(t) = args.locktime {
+            params = params.locktime(ms(t));
+        }
+
+        if params.is_empty() {
+            return Err(nlink::netlink::Error::InvalidMessage(
+                "ntable change: no parameters given".into(),
+            ));
+        }
+
+        conn.set_neigh_table_params(params).await
+    }
+}
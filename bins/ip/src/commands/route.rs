@@ -9,13 +9,16 @@ use nlink::{
     netlink::{
         Connection, Result, Route,
         mpls::MplsEncap,
-        route::{Ipv4Route, Ipv6Route, RouteMetrics},
+        route::{Ipv4Route, Ipv6Route, RouteGetOptions, RouteMetrics, RouteSelector},
         srv6::Srv6Encap,
         types::route::{RouteProtocol, RouteScope},
     },
     output::{OutputFormat, OutputOptions, print_all},
 };
 
+/// Address family of the MPLS label table (`-M`).
+const AF_MPLS: u8 = 28;
+
 #[derive(Args)]
 pub struct RouteCmd {
     #[command(subcommand)]
@@ -29,6 +32,10 @@ enum RouteAction {
         /// Routing table (main, local, etc.).
         #[arg(long, default_value = "main")]
         table: String,
+
+        /// Only routes installed by this protocol (name or number).
+        #[arg(long)]
+        proto: Option<String>,
     },
 
     /// Add a route.
@@ -56,6 +63,10 @@ enum RouteAction {
         #[arg(long)]
         src: Option<String>,
 
+        /// Route protocol (name or number, e.g. from rt_protos).
+        #[arg(long, default_value = "static")]
+        proto: String,
+
         /// Route scope (global, link, host).
         #[arg(long)]
         scope: Option<String>,
@@ -97,6 +108,29 @@ enum RouteAction {
         /// Preferred source address.
         #[arg(long)]
         src: Option<String>,
+
+        /// Route protocol (name or number, e.g. from rt_protos).
+        #[arg(long, default_value = "static")]
+        proto: String,
+    },
+
+    /// Delete every route installed by a protocol.
+    Flush {
+        /// Protocol whose routes to remove (name or number).
+        #[arg(long, required_unless_present = "ra")]
+        proto: Option<String>,
+
+        /// Routing table, or `all`.
+        #[arg(long, default_value = "main")]
+        table: String,
+
+        /// Only remove routes learned from IPv6 router advertisements.
+        #[arg(long, conflicts_with = "exclude_ra")]
+        ra: bool,
+
+        /// Keep routes learned from IPv6 router advertisements.
+        #[arg(long)]
+        exclude_ra: bool,
     },
 
     /// Delete a route.
@@ -113,6 +147,34 @@ enum RouteAction {
     Get {
         /// Destination address.
         destination: String,
+
+        /// Source address of the flow.
+        #[arg(long)]
+        from: Option<IpAddr>,
+
+        /// Input device (requires --from).
+        #[arg(long, requires = "from")]
+        iif: Option<String>,
+
+        /// Output device.
+        #[arg(long)]
+        oif: Option<String>,
+
+        /// Firewall mark.
+        #[arg(long, value_parser = nlink::util::parse::get_u32)]
+        mark: Option<u32>,
+
+        /// User ID.
+        #[arg(long)]
+        uid: Option<u32>,
+
+        /// Look up in this table only.
+        #[arg(long)]
+        table: Option<String>,
+
+        /// Show the matching FIB entry instead of the resolved route.
+        #[arg(long)]
+        fibmatch: bool,
     },
 }
 
@@ -124,10 +186,15 @@ impl RouteCmd {
         opts: &OutputOptions,
         family: Option<u8>,
     ) -> Result<()> {
+        nlink::util::names::load_system_protocol_names();
+
         match self.action.unwrap_or(RouteAction::Show {
             table: "main".into(),
+            proto: None,
         }) {
-            RouteAction::Show { table } => Self::show(conn, &table, format, opts, family).await,
+            RouteAction::Show { table, proto } => {
+                Self::show(conn, &table, proto.as_deref(), format, opts, family).await
+            }
             RouteAction::Add {
                 destination,
                 via,
@@ -135,6 +202,7 @@ impl RouteCmd {
                 table,
                 metric,
                 src,
+                proto,
                 scope,
                 mtu,
                 encap_mpls,
@@ -148,6 +216,7 @@ impl RouteCmd {
                     &table,
                     metric,
                     src.as_deref(),
+                    &proto,
                     scope.as_deref(),
                     mtu,
                     encap_mpls.as_deref(),
@@ -163,6 +232,7 @@ impl RouteCmd {
                 table,
                 metric,
                 src,
+                proto,
             } => {
                 Self::add(
                     conn,
@@ -172,6 +242,7 @@ impl RouteCmd {
                     &table,
                     metric,
                     src.as_deref(),
+                    &proto,
                     None,
                     None,
                     None,
@@ -180,30 +251,92 @@ impl RouteCmd {
                 )
                 .await
             }
+            RouteAction::Flush {
+                proto,
+                table,
+                ra,
+                exclude_ra,
+            } => {
+                let ra = (ra || exclude_ra).then_some(ra);
+                Self::flush(conn, proto.as_deref(), &table, ra, family).await
+            }
             RouteAction::Del { destination, table } => Self::del(conn, &destination, &table).await,
-            RouteAction::Get { destination } => Self::get(conn, &destination, format, opts).await,
+            RouteAction::Get {
+                destination,
+                from,
+                iif,
+                oif,
+                mark,
+                uid,
+                table,
+                fibmatch,
+            } => {
+                let mut get_opts = RouteGetOptions::new();
+                if let Some(src) = from {
+                    get_opts = get_opts.source(src);
+                }
+                if let Some(dev) = iif {
+                    get_opts = get_opts.iif(dev);
+                }
+                if let Some(dev) = oif {
+                    get_opts = get_opts.oif(dev);
+                }
+                if let Some(mark) = mark {
+                    get_opts = get_opts.mark(mark);
+                }
+                if let Some(uid) = uid {
+                    get_opts = get_opts.uid(uid);
+                }
+                if let Some(table) = table {
+                    let table_id = nlink::util::names::table_id(&table).ok_or_else(|| {
+                        nlink::netlink::Error::InvalidMessage(format!("unknown table: {table}"))
+                    })?;
+                    get_opts = get_opts.table(table_id);
+                }
+                if fibmatch {
+                    get_opts = get_opts.fib_match();
+                }
+                Self::get(conn, &destination, get_opts, format, opts).await
+            }
         }
     }
 
     async fn show(
         conn: &Connection<Route>,
         table: &str,
+        proto: Option<&str>,
         format: OutputFormat,
         opts: &OutputOptions,
         family: Option<u8>,
     ) -> Result<()> {
         let table_id = nlink::util::names::table_id(table).unwrap_or(254); // main
+        let proto = proto.map(parse_proto).transpose()?;
+
+        // `-M`: the MPLS label table has no routing tables to pick from.
+        if family == Some(AF_MPLS) {
+            let routes: Vec<_> = conn
+                .get_mpls_routes()
+                .await?
+                .into_iter()
+                .filter(|r| proto.is_none_or(|p| r.protocol() == p))
+                .collect();
+            conn.interface_resolver().await?.install();
+            print_all(&routes, format, opts)?;
+            return Ok(());
+        }
 
         // Get routes and filter
         let routes = conn.get_routes_for_table(table_id).await?;
 
-        // Filter by family if specified
-        let routes: Vec<_> = if let Some(fam) = family {
-            routes.into_iter().filter(|r| r.family() == fam).collect()
-        } else {
-            routes
-        };
+        // Filter by family and protocol if specified
+        let routes: Vec<_> = routes
+            .into_iter()
+            .filter(|r| family.is_none_or(|fam| r.family() == fam))
+            .filter(|r| proto.is_none_or(|p| r.protocol() == p))
+            .collect();
 
+        // One link dump serves every row's `dev` column.
+        conn.interface_resolver().await?.install();
         print_all(&routes, format, opts)?;
 
         Ok(())
@@ -218,6 +351,7 @@ impl RouteCmd {
         table: &str,
         metric: Option<u32>,
         src: Option<&str>,
+        proto: &str,
         scope: Option<&str>,
         mtu: Option<u32>,
         encap_mpls: Option<&str>,
@@ -227,6 +361,7 @@ impl RouteCmd {
         use nlink::util::addr::parse_prefix;
 
         let table_id = nlink::util::names::table_id(table).unwrap_or(254);
+        let proto = parse_proto(proto)?;
 
         // Parse destination to determine family
         let (dst_addr, dst_len, is_ipv6) = if destination == "default" {
@@ -258,7 +393,7 @@ impl RouteCmd {
 
             let mut route = Ipv6Route::from_addr(dst_v6, dst_len)
                 .table(table_id)
-                .protocol(RouteProtocol::Static);
+                .protocol(proto);
 
             if let Some(gw) = via {
                 let gw_addr: Ipv6Addr = gw.parse().map_err(|_| {
@@ -314,7 +449,7 @@ impl RouteCmd {
 
             let mut route = Ipv4Route::from_addr(dst_v4, dst_len)
                 .table(table_id)
-                .protocol(RouteProtocol::Static);
+                .protocol(proto);
 
             if let Some(gw) = via {
                 let gw_addr: Ipv4Addr = gw.parse().map_err(|_| {
@@ -362,6 +497,30 @@ impl RouteCmd {
         }
     }
 
+    async fn flush(
+        conn: &Connection<Route>,
+        proto: Option<&str>,
+        table: &str,
+        ra: Option<bool>,
+        family: Option<u8>,
+    ) -> Result<()> {
+        let mut selector = RouteSelector::new();
+        if let Some(proto) = proto {
+            selector = selector.protocol(parse_proto(proto)?);
+        }
+        if table != "all" {
+            selector = selector.table(nlink::util::names::table_id(table).unwrap_or(254));
+        }
+        if let Some(ra) = ra {
+            selector = selector.router_advertised(ra);
+        }
+        if let Some(family) = family {
+            selector = selector.family(nlink::AddressFamily::from_raw(family));
+        }
+        conn.flush_routes_matching(selector).await?;
+        Ok(())
+    }
+
     async fn del(conn: &Connection<Route>, destination: &str, table: &str) -> Result<()> {
         use nlink::util::addr::parse_prefix;
 
@@ -393,15 +552,13 @@ impl RouteCmd {
     async fn get(
         conn: &Connection<Route>,
         destination: &str,
+        get_opts: RouteGetOptions,
         format: OutputFormat,
         opts: &OutputOptions,
     ) -> Result<()> {
-        // Parse destination prefix
-        use std::net::IpAddr;
-
         use nlink::util::addr::parse_prefix;
 
-        let (dst_addr, prefix_len) = parse_prefix(destination).map_err(|e| {
+        let (dst_addr, _) = parse_prefix(destination).map_err(|e| {
             nlink::netlink::Error::InvalidMessage(format!("invalid destination: {}", e))
         })?;
 
@@ -410,25 +567,18 @@ impl RouteCmd {
         // dumping every route and requiring an exact prefix match — the
         // latter returned "not found" for e.g. `ip route get 8.8.8.8`
         // whenever only a default/covering route existed.
-        let route = match dst_addr {
-            IpAddr::V4(v4) => conn.get_route_v4(v4, prefix_len).await?,
-            IpAddr::V6(v6) => conn.get_route_v6(v6, prefix_len).await?,
-        };
-
-        match route {
-            Some(r) => print_all(std::slice::from_ref(&r), format, opts)?,
-            None => {
-                return Err(nlink::netlink::Error::InvalidMessage(format!(
-                    "route to {} not found",
-                    destination
-                )));
-            }
-        }
+        let route = conn.route_get(dst_addr, get_opts).await?;
+        print_all(std::slice::from_ref(&route), format, opts)?;
 
         Ok(())
     }
 }
 
+/// Resolve a protocol name (built-in or from rt_protos) or number.
+fn parse_proto(proto: &str) -> Result<RouteProtocol> {
+    proto.parse()
+}
+
 /// Parse comma-separated MPLS labels into an MplsEncap.
 fn parse_mpls_encap(labels: &str) -> Result<MplsEncap> {
     let mut encap = MplsEncap::new();
@@ -5,9 +5,12 @@ use std::io::Write;
 use clap::{Args, Subcommand};
 use nlink::{
     netlink::{
-        Connection, Result, Route,
-        rule::RuleBuilder,
-        types::rule::{FibRuleAction, FibRulePortRange, FibRuleUidRange},
+        Connection, Error, Result, Route,
+        rule::{RuleBuilder, RuleFilter},
+        types::{
+            route::RouteProtocol,
+            rule::{FibRuleAction, FibRulePortRange, FibRuleUidRange},
+        },
     },
     output::{OutputFormat, OutputOptions, Printable, print_all},
 };
@@ -21,90 +24,109 @@ pub struct RuleCmd {
 #[derive(Subcommand)]
 enum RuleAction {
     /// Show routing rules.
-    Show,
+    Show {
+        /// Only rules installed by this protocol (name or number).
+        #[arg(long, visible_alias = "proto")]
+        protocol: Option<String>,
+    },
     /// Alias for show.
-    List,
+    List {
+        /// Only rules installed by this protocol (name or number).
+        #[arg(long, visible_alias = "proto")]
+        protocol: Option<String>,
+    },
 
     /// Add a routing rule.
-    Add {
-        /// Rule priority.
-        #[arg(long, short)]
-        priority: Option<u32>,
-
-        /// Match source prefix.
-        #[arg(long)]
-        from: Option<String>,
-
-        /// Match destination prefix.
-        #[arg(long, short = 't')]
-        to: Option<String>,
-
-        /// Input interface name.
-        #[arg(long)]
-        iif: Option<String>,
-
-        /// Output interface name.
-        #[arg(long)]
-        oif: Option<String>,
-
-        /// Match fwmark.
-        #[arg(long)]
-        fwmark: Option<String>,
-
-        /// Lookup table.
-        #[arg(long)]
-        table: Option<String>,
-
-        /// Action type (lookup, blackhole, unreachable, prohibit).
-        #[arg(long, default_value = "lookup")]
-        action_type: String,
-
-        /// IP protocol (for sport/dport matching).
-        #[arg(long)]
-        ipproto: Option<String>,
-
-        /// Source port or range (e.g., 80 or 80-443).
-        #[arg(long)]
-        sport: Option<String>,
-
-        /// Destination port or range (e.g., 80 or 80-443).
-        #[arg(long)]
-        dport: Option<String>,
-    },
+    Add(RuleSpec),
 
     /// Delete a routing rule.
-    Del {
-        /// Rule priority.
-        #[arg(long, short)]
-        priority: Option<u32>,
-
-        /// Match source prefix.
-        #[arg(long)]
-        from: Option<String>,
-
-        /// Match destination prefix.
-        #[arg(long, short = 't')]
-        to: Option<String>,
-
-        /// Input interface name.
-        #[arg(long)]
-        iif: Option<String>,
-
-        /// Output interface name.
-        #[arg(long)]
-        oif: Option<String>,
-
-        /// Match fwmark.
-        #[arg(long)]
-        fwmark: Option<String>,
-
-        /// Lookup table.
-        #[arg(long)]
-        table: Option<String>,
-    },
+    ///
+    /// Only the given selectors are matched, so `del --priority N`
+    /// removes the rule at that priority whatever its table or action.
+    Del(RuleSpec),
 
     /// Flush all rules (except default).
-    Flush,
+    Flush {
+        /// Only rules installed by this protocol (name or number).
+        #[arg(long, visible_alias = "proto")]
+        protocol: Option<String>,
+    },
+}
+
+/// Selectors and action shared by `rule add` and `rule del`.
+#[derive(Args)]
+struct RuleSpec {
+    /// Rule priority.
+    #[arg(long, short, visible_aliases = ["pref", "preference"])]
+    priority: Option<u32>,
+
+    /// Match source prefix.
+    #[arg(long)]
+    from: Option<String>,
+
+    /// Match destination prefix.
+    #[arg(long, short = 't')]
+    to: Option<String>,
+
+    /// Match TOS value.
+    #[arg(long, visible_alias = "dsfield")]
+    tos: Option<String>,
+
+    /// Match fwmark (mark or mark/mask).
+    #[arg(long)]
+    fwmark: Option<String>,
+
+    /// Input interface name.
+    #[arg(long, visible_alias = "dev")]
+    iif: Option<String>,
+
+    /// Output interface name.
+    #[arg(long)]
+    oif: Option<String>,
+
+    /// Match UID or UID range (e.g., 1000 or 1000-1999).
+    #[arg(long)]
+    uidrange: Option<String>,
+
+    /// IP protocol (for sport/dport matching).
+    #[arg(long)]
+    ipproto: Option<String>,
+
+    /// Source port or range (e.g., 80 or 80-443).
+    #[arg(long)]
+    sport: Option<String>,
+
+    /// Destination port or range (e.g., 80 or 80-443).
+    #[arg(long)]
+    dport: Option<String>,
+
+    /// Lookup table.
+    #[arg(long, visible_alias = "lookup")]
+    table: Option<String>,
+
+    /// Look up the table of the VRF the packet arrived on.
+    #[arg(long, conflicts_with = "table")]
+    l3mdev: bool,
+
+    /// Jump to the rule with this priority.
+    #[arg(long)]
+    goto: Option<u32>,
+
+    /// Reject lookup results with a prefix length of this or less.
+    #[arg(long, visible_alias = "suppress-prefixlen")]
+    suppress_prefixlength: Option<u32>,
+
+    /// Reject lookup results whose device is in this interface group.
+    #[arg(long)]
+    suppress_ifgroup: Option<u32>,
+
+    /// Protocol that installed the rule (name or number).
+    #[arg(long, visible_alias = "proto")]
+    protocol: Option<String>,
+
+    /// Action type (lookup, goto, nop, blackhole, unreachable, prohibit).
+    #[arg(long, visible_alias = "type")]
+    action_type: Option<String>,
 }
 
 impl RuleCmd {
@@ -115,76 +137,28 @@ impl RuleCmd {
         opts: &OutputOptions,
         family: Option<u8>,
     ) -> Result<()> {
-        match self.action.unwrap_or(RuleAction::Show) {
-            RuleAction::Show | RuleAction::List => Self::show(conn, format, opts, family).await,
-            RuleAction::Add {
-                priority,
-                from,
-                to,
-                iif,
-                oif,
-                fwmark,
-                table,
-                action_type,
-                ipproto,
-                sport,
-                dport,
-            } => {
-                Self::add(
-                    conn,
-                    family.unwrap_or(2),
-                    priority,
-                    from.as_deref(),
-                    to.as_deref(),
-                    iif.as_deref(),
-                    oif.as_deref(),
-                    fwmark.as_deref(),
-                    table.as_deref(),
-                    &action_type,
-                    ipproto.as_deref(),
-                    sport.as_deref(),
-                    dport.as_deref(),
-                )
-                .await
+        match self.action.unwrap_or(RuleAction::Show { protocol: None }) {
+            RuleAction::Show { protocol } | RuleAction::List { protocol } => {
+                let filter = rule_filter(family, protocol.as_deref())?;
+                Self::show(conn, &filter, format, opts).await
             }
-            RuleAction::Del {
-                priority,
-                from,
-                to,
-                iif,
-                oif,
-                fwmark,
-                table,
-            } => {
-                Self::del(
-                    conn,
-                    family.unwrap_or(2),
-                    priority,
-                    from.as_deref(),
-                    to.as_deref(),
-                    iif.as_deref(),
-                    oif.as_deref(),
-                    fwmark.as_deref(),
-                    table.as_deref(),
-                )
-                .await
+            RuleAction::Add(spec) => conn.add_rule(spec.to_builder(family.unwrap_or(0))?).await,
+            RuleAction::Del(spec) => conn.del_rule(spec.to_builder(family.unwrap_or(0))?).await,
+            RuleAction::Flush { protocol } => {
+                conn.flush_rules(rule_filter(family, protocol.as_deref())?)
+                    .await?;
+                Ok(())
             }
-            RuleAction::Flush => Self::flush(conn, family).await,
         }
     }
 
     async fn show(
         conn: &Connection<Route>,
+        filter: &RuleFilter,
         format: OutputFormat,
         opts: &OutputOptions,
-        family: Option<u8>,
     ) -> Result<()> {
-        let raw_rules = if let Some(fam) = family {
-            conn.get_rules_for_family(nlink::AddressFamily::from_raw(fam))
-                .await?
-        } else {
-            conn.get_rules().await?
-        };
+        let raw_rules = conn.get_rules_filtered(filter).await?;
 
         // Convert to RuleInfo for display
         let mut rules: Vec<RuleInfo> = raw_rules
@@ -193,6 +167,7 @@ impl RuleCmd {
                 _family: r.family(),
                 src_len: r.src_len(),
                 dst_len: r.dst_len(),
+                tos: r.tos(),
                 action: r.action(),
                 priority: r.priority(),
                 table: r.table_id(),
@@ -206,6 +181,11 @@ impl RuleCmd {
                 sport: r.sport_range(),
                 dport: r.dport_range(),
                 uid_range: r.uid_range(),
+                goto: r.goto(),
+                // The kernel reports unset suppressors as -1.
+                suppress_prefixlen: r.suppress_prefixlen().filter(|&v| v != u32::MAX),
+                suppress_ifgroup: r.suppress_ifgroup().filter(|&v| v != u32::MAX),
+                l3mdev: r.l3mdev().is_some_and(|v| v != 0),
             })
             .collect();
 
@@ -216,97 +196,43 @@ impl RuleCmd {
 
         Ok(())
     }
+}
 
-    #[allow(clippy::too_many_arguments)]
-    async fn add(
-        conn: &Connection<Route>,
-        family: u8,
-        priority: Option<u32>,
-        from: Option<&str>,
-        to: Option<&str>,
-        iif: Option<&str>,
-        oif: Option<&str>,
-        fwmark: Option<&str>,
-        table: Option<&str>,
-        action_type: &str,
-        ipproto: Option<&str>,
-        sport: Option<&str>,
-        dport: Option<&str>,
-    ) -> Result<()> {
-        use nlink::util::addr::parse_prefix;
-
-        // Parse source/destination prefixes
-        let (src_addr, src_len) = if let Some(s) = from {
-            if s == "all" || s == "any" || s == "default" {
-                (None, 0)
-            } else {
-                let (addr, len) = parse_prefix(s).map_err(|e| {
-                    nlink::netlink::Error::InvalidMessage(format!("invalid source: {}", e))
-                })?;
-                (Some(addr), len)
-            }
-        } else {
-            (None, 0)
-        };
-
-        let (dst_addr, dst_len) = if let Some(d) = to {
-            if d == "all" || d == "any" || d == "default" {
-                (None, 0)
-            } else {
-                let (addr, len) = parse_prefix(d).map_err(|e| {
-                    nlink::netlink::Error::InvalidMessage(format!("invalid destination: {}", e))
-                })?;
-                (Some(addr), len)
-            }
-        } else {
-            (None, 0)
-        };
+impl RuleSpec {
+    /// Translate the parsed arguments into a rule builder.
+    ///
+    /// A `family` of 0 means "infer from `--from`/`--to`, else IPv4".
+    fn to_builder(&self, family: u8) -> Result<RuleBuilder> {
+        let (src_addr, src_len) = parse_selector(self.from.as_deref(), "source")?;
+        let (dst_addr, dst_len) = parse_selector(self.to.as_deref(), "destination")?;
 
         // Determine actual family from addresses if not specified
         let actual_family = if family == 0 {
             src_addr
+                .or(dst_addr)
                 .map(|a| if a.is_ipv4() { 2u8 } else { 10u8 })
-                .or_else(|| dst_addr.map(|a| if a.is_ipv4() { 2u8 } else { 10u8 }))
                 .unwrap_or(2)
         } else {
             family
         };
 
-        // Parse table
-        let table_id = table
-            .map(|t| nlink::util::names::table_id(t).unwrap_or(254))
-            .unwrap_or(254);
-
-        // Build the rule
-        let mut rule = RuleBuilder::new(actual_family).table(table_id);
+        let mut rule = RuleBuilder::new(actual_family);
 
-        // Set priority
-        if let Some(prio) = priority {
+        if let Some(prio) = self.priority {
             rule = rule.priority(prio);
         }
-
-        // Set source
         if let Some(addr) = src_addr {
             rule = rule.from_addr(addr, src_len);
         }
-
-        // Set destination
         if let Some(addr) = dst_addr {
             rule = rule.to_addr(addr, dst_len);
         }
-
-        // Set input interface
-        if let Some(iif_name) = iif {
-            rule = rule.iif(iif_name);
-        }
-
-        // Set output interface
-        if let Some(oif_name) = oif {
-            rule = rule.oif(oif_name);
+        if let Some(ref tos) = self.tos {
+            let tos = u8::try_from(parse_u32(tos)?)
+                .map_err(|_| Error::InvalidMessage(format!("invalid tos: {}", tos)))?;
+            rule = rule.tos(tos);
         }
-
-        // Set fwmark
-        if let Some(mark_str) = fwmark {
+        if let Some(ref mark_str) = self.fwmark {
             let (mark, mask) = parse_fwmark(mark_str)?;
             if mask != 0xffffffff {
                 rule = rule.fwmark_mask(mark, mask);
@@ -314,170 +240,113 @@ impl RuleCmd {
                 rule = rule.fwmark(mark);
             }
         }
-
-        // Set action
-        rule = match action_type.to_lowercase().as_str() {
-            "lookup" | "table" => rule, // default is table lookup
-            "blackhole" => rule.blackhole(),
-            "unreachable" => rule.unreachable(),
-            "prohibit" => rule.prohibit(),
-            "nop" | "goto" => {
-                // The rule builder models only table lookup + blackhole /
-                // unreachable / prohibit. Accepting `nop`/`goto` and silently
-                // falling back to a table lookup would send a different rule
-                // than asked — reject instead (CLAUDE.md strict-parse contract).
-                return Err(nlink::netlink::Error::InvalidMessage(format!(
-                    "rule action `{}` is not modelled by the ip rule builder \
-                     (supported: lookup/table, blackhole, unreachable, prohibit)",
-                    action_type.to_lowercase()
-                )));
-            }
-            _ => {
-                return Err(nlink::netlink::Error::InvalidMessage(format!(
-                    "unknown action: {}",
-                    action_type
-                )));
-            }
-        };
-
-        // Set IP protocol
-        if let Some(proto) = ipproto {
-            let proto_num = parse_ip_proto(proto)?;
-            rule = rule.ipproto(proto_num);
+        if let Some(ref iif_name) = self.iif {
+            rule = rule.iif(iif_name);
         }
-
-        // Set sport
-        if let Some(port) = sport {
+        if let Some(ref oif_name) = self.oif {
+            rule = rule.oif(oif_name);
+        }
+        if let Some(ref uids) = self.uidrange {
+            let (start, end) = parse_uid_range(uids)?;
+            rule = rule.uid_range(start, end);
+        }
+        if let Some(ref proto) = self.ipproto {
+            rule = rule.ipproto(parse_ip_proto(proto)?);
+        }
+        if let Some(ref port) = self.sport {
             let (start, end) = parse_port_range(port)?;
             rule = rule.sport(start, end);
         }
-
-        // Set dport
-        if let Some(port) = dport {
+        if let Some(ref port) = self.dport {
             let (start, end) = parse_port_range(port)?;
             rule = rule.dport(start, end);
         }
-
-        conn.add_rule(rule).await?;
-
-        Ok(())
-    }
-
-    #[allow(clippy::too_many_arguments)]
-    async fn del(
-        conn: &Connection<Route>,
-        family: u8,
-        priority: Option<u32>,
-        from: Option<&str>,
-        to: Option<&str>,
-        iif: Option<&str>,
-        oif: Option<&str>,
-        fwmark: Option<&str>,
-        table: Option<&str>,
-    ) -> Result<()> {
-        use nlink::util::addr::parse_prefix;
-
-        // Parse source/destination prefixes
-        let (src_addr, src_len) = if let Some(s) = from {
-            if s == "all" || s == "any" || s == "default" {
-                (None, 0)
-            } else {
-                let (addr, len) = parse_prefix(s).map_err(|e| {
-                    nlink::netlink::Error::InvalidMessage(format!("invalid source: {}", e))
-                })?;
-                (Some(addr), len)
-            }
-        } else {
-            (None, 0)
-        };
-
-        let (dst_addr, dst_len) = if let Some(d) = to {
-            if d == "all" || d == "any" || d == "default" {
-                (None, 0)
-            } else {
-                let (addr, len) = parse_prefix(d).map_err(|e| {
-                    nlink::netlink::Error::InvalidMessage(format!("invalid destination: {}", e))
-                })?;
-                (Some(addr), len)
-            }
-        } else {
-            (None, 0)
-        };
-
-        // Determine actual family
-        let actual_family = if family == 0 {
-            src_addr
-                .map(|a| if a.is_ipv4() { 2u8 } else { 10u8 })
-                .or_else(|| dst_addr.map(|a| if a.is_ipv4() { 2u8 } else { 10u8 }))
-                .unwrap_or(2)
-        } else {
-            family
-        };
-
-        // Parse table
-        let table_id = table
-            .map(|t| nlink::util::names::table_id(t).unwrap_or(254))
-            .unwrap_or(0);
-
-        // Build the rule for deletion
-        let mut rule = RuleBuilder::new(actual_family);
-
-        if table_id > 0 {
-            rule = rule.table(table_id);
+        if let Some(len) = self.suppress_prefixlength {
+            rule = rule.suppress_prefixlength(len);
         }
-
-        // Set priority
-        if let Some(prio) = priority {
-            rule = rule.priority(prio);
-        }
-
-        // Set source
-        if let Some(addr) = src_addr {
-            rule = rule.from_addr(addr, src_len);
+        if let Some(group) = self.suppress_ifgroup {
+            rule = rule.suppress_ifgroup(group);
         }
-
-        // Set destination
-        if let Some(addr) = dst_addr {
-            rule = rule.to_addr(addr, dst_len);
+        if let Some(ref proto) = self.protocol {
+            nlink::util::names::load_system_protocol_names();
+            rule = rule.protocol(proto.parse::<RouteProtocol>()?);
         }
 
-        // Set input interface
-        if let Some(iif_name) = iif {
-            rule = rule.iif(iif_name);
+        // `--goto N` implies the goto action; an explicit action type must
+        // agree with it.
+        let action = match self.action_type.as_deref() {
+            Some(t) => Some(t.parse::<FibRuleAction>()?),
+            None if self.goto.is_some() => Some(FibRuleAction::Goto),
+            None => None,
+        };
+        if let Some(ref table) = self.table {
+            let table_id = nlink::util::names::table_id(table)
+                .ok_or_else(|| Error::InvalidMessage(format!("unknown table: {}", table)))?;
+            if action.is_some_and(|a| a != FibRuleAction::ToTbl) {
+                return Err(Error::InvalidMessage(
+                    "--table only applies to the lookup action".into(),
+                ));
+            }
+            rule = rule.table(table_id);
         }
-
-        // Set output interface
-        if let Some(oif_name) = oif {
-            rule = rule.oif(oif_name);
+        if self.l3mdev {
+            rule = rule.l3mdev();
         }
 
-        // Set fwmark
-        if let Some(mark_str) = fwmark {
-            let (mark, mask) = parse_fwmark(mark_str)?;
-            if mask != 0xffffffff {
-                rule = rule.fwmark_mask(mark, mask);
-            } else {
-                rule = rule.fwmark(mark);
+        rule = match (action, self.goto) {
+            (None, _) | (Some(FibRuleAction::ToTbl), None) => rule,
+            (Some(FibRuleAction::Goto), Some(target)) => rule.goto(target),
+            (Some(FibRuleAction::Goto), None) => {
+                return Err(Error::InvalidMessage(
+                    "the goto action needs a target (--goto PRIORITY)".into(),
+                ));
             }
-        }
-
-        conn.del_rule(rule).await?;
+            (Some(_), Some(_)) => {
+                return Err(Error::InvalidMessage(
+                    "--goto conflicts with a non-goto action type".into(),
+                ));
+            }
+            (Some(FibRuleAction::Nop), None) => rule.nop(),
+            (Some(FibRuleAction::Blackhole), None) => rule.blackhole(),
+            (Some(FibRuleAction::Unreachable), None) => rule.unreachable(),
+            (Some(FibRuleAction::Prohibit), None) => rule.prohibit(),
+            (Some(other), None) => {
+                // Accepting an unmodelled action and silently falling back
+                // to a table lookup would send a different rule than asked
+                // — reject instead (CLAUDE.md strict-parse contract).
+                return Err(Error::InvalidMessage(format!(
+                    "rule action `{other}` is not modelled by the ip rule builder \
+                     (supported: lookup/table, goto, nop, blackhole, unreachable, prohibit)"
+                )));
+            }
+        };
 
-        Ok(())
+        Ok(rule)
     }
+}
 
-    async fn flush(conn: &Connection<Route>, family: Option<u8>) -> Result<()> {
-        // Flush IPv4 rules
-        if family.is_none() || family == Some(libc::AF_INET as u8) {
-            conn.flush_rules(nlink::AddressFamily::v4()).await?;
-        }
+/// Build the dump filter for `rule show` and `rule flush`.
+fn rule_filter(family: Option<u8>, protocol: Option<&str>) -> Result<RuleFilter> {
+    let mut filter = RuleFilter::new();
+    if let Some(fam) = family {
+        filter = filter.family(nlink::AddressFamily::from_raw(fam));
+    }
+    if let Some(proto) = protocol {
+        nlink::util::names::load_system_protocol_names();
+        filter = filter.protocol(proto.parse::<RouteProtocol>()?);
+    }
+    Ok(filter)
+}
 
-        // Flush IPv6 rules
-        if family.is_none() || family == Some(libc::AF_INET6 as u8) {
-            conn.flush_rules(nlink::AddressFamily::v6()).await?;
+/// Parse a `--from`/`--to` selector; `all`, `any` and `default` mean no match.
+fn parse_selector(s: Option<&str>, what: &str) -> Result<(Option<std::net::IpAddr>, u8)> {
+    match s {
+        None | Some("all" | "any" | "default") => Ok((None, 0)),
+        Some(s) => {
+            let (addr, len) = nlink::util::addr::parse_prefix(s)
+                .map_err(|e| Error::InvalidMessage(format!("invalid {}: {}", what, e)))?;
+            Ok((Some(addr), len))
         }
-
-        Ok(())
     }
 }
 
@@ -487,6 +356,7 @@ struct RuleInfo {
     _family: u8,
     src_len: u8,
     dst_len: u8,
+    tos: u8,
     action: FibRuleAction,
     priority: u32,
     table: u32,
@@ -500,6 +370,10 @@ struct RuleInfo {
     sport: Option<FibRulePortRange>,
     dport: Option<FibRulePortRange>,
     uid_range: Option<FibRuleUidRange>,
+    goto: Option<u32>,
+    suppress_prefixlen: Option<u32>,
+    suppress_ifgroup: Option<u32>,
+    l3mdev: bool,
 }
 
 impl Printable for RuleInfo {
@@ -519,6 +393,11 @@ impl Printable for RuleInfo {
             write!(w, "to {}/{} ", dst, self.dst_len)?;
         }
 
+        // TOS
+        if self.tos != 0 {
+            write!(w, "tos {:#x} ", self.tos)?;
+        }
+
         // Input interface
         if let Some(ref iif) = self.iif {
             write!(w, "iif {} ", iif)?;
@@ -576,25 +455,31 @@ impl Printable for RuleInfo {
 
         // Action
         match self.action {
+            FibRuleAction::ToTbl if self.l3mdev => {
+                write!(w, "lookup [l3mdev-table]")?;
+            }
             FibRuleAction::ToTbl => {
                 write!(w, "lookup {}", nlink::util::names::table_name(self.table))?;
             }
-            FibRuleAction::Blackhole => {
-                write!(w, "blackhole")?;
-            }
-            FibRuleAction::Unreachable => {
-                write!(w, "unreachable")?;
-            }
-            FibRuleAction::Prohibit => {
-                write!(w, "prohibit")?;
-            }
             FibRuleAction::Goto => {
-                write!(w, "goto")?;
+                write!(w, "goto ")?;
+                match self.goto {
+                    Some(target) => write!(w, "{target}")?,
+                    None => write!(w, "none")?,
+                }
             }
-            FibRuleAction::Nop => {
-                write!(w, "nop")?;
+            FibRuleAction::Unspec => {}
+            action => {
+                write!(w, "{action}")?;
             }
-            _ => {}
+        }
+
+        // Suppressors
+        if let Some(len) = self.suppress_prefixlen {
+            write!(w, " suppress_prefixlength {len}")?;
+        }
+        if let Some(group) = self.suppress_ifgroup {
+            write!(w, " suppress_ifgroup {group}")?;
         }
 
         writeln!(w)?;
@@ -604,7 +489,7 @@ impl Printable for RuleInfo {
     fn to_json(&self) -> serde_json::Value {
         let mut obj = serde_json::json!({
             "priority": self.priority,
-            "action": self.action.name(),
+            "action": self.action.to_string(),
         });
 
         if let Some(ref src) = self.source {
@@ -617,10 +502,28 @@ impl Printable for RuleInfo {
             obj["dst"] = serde_json::json!(format!("{}/{}", dst, self.dst_len));
         }
 
-        if self.action == FibRuleAction::ToTbl {
+        if self.tos != 0 {
+            obj["tos"] = serde_json::json!(format!("{:#x}", self.tos));
+        }
+
+        if self.l3mdev {
+            obj["l3mdev"] = serde_json::json!(true);
+        } else if self.action == FibRuleAction::ToTbl {
             obj["table"] = serde_json::json!(nlink::util::names::table_name(self.table));
         }
 
+        if let Some(target) = self.goto {
+            obj["target"] = serde_json::json!(target);
+        }
+
+        if let Some(len) = self.suppress_prefixlen {
+            obj["suppress_prefixlen"] = serde_json::json!(len);
+        }
+
+        if let Some(group) = self.suppress_ifgroup {
+            obj["suppress_ifgroup"] = serde_json::json!(group);
+        }
+
         if let Some(ref iif) = self.iif {
             obj["iif"] = serde_json::json!(iif);
         }
@@ -657,6 +560,11 @@ impl Printable for RuleInfo {
             }
         }
 
+        if let Some(ref uid) = self.uid_range {
+            obj["uid_start"] = serde_json::json!(uid.start);
+            obj["uid_end"] = serde_json::json!(uid.end);
+        }
+
         obj
     }
 }
@@ -681,7 +589,7 @@ fn parse_u32(s: &str) -> Result<u32> {
     } else {
         s.parse()
     }
-    .map_err(|_| nlink::netlink::Error::InvalidMessage(format!("invalid number: {}", s)))
+    .map_err(|_| Error::InvalidMessage(format!("invalid number: {}", s)))
 }
 
 /// Parse port or port range like "80" or "80-443".
@@ -690,21 +598,41 @@ fn parse_port_range(s: &str) -> Result<(u16, u16)> {
         let start: u16 = start_str
             .trim()
             .parse()
-            .map_err(|_| nlink::netlink::Error::InvalidMessage(format!("invalid port: {}", s)))?;
+            .map_err(|_| Error::InvalidMessage(format!("invalid port: {}", s)))?;
         let end: u16 = end_str
             .trim()
             .parse()
-            .map_err(|_| nlink::netlink::Error::InvalidMessage(format!("invalid port: {}", s)))?;
+            .map_err(|_| Error::InvalidMessage(format!("invalid port: {}", s)))?;
         Ok((start, end))
     } else {
         let port: u16 = s
             .trim()
             .parse()
-            .map_err(|_| nlink::netlink::Error::InvalidMessage(format!("invalid port: {}", s)))?;
+            .map_err(|_| Error::InvalidMessage(format!("invalid port: {}", s)))?;
         Ok((port, port))
     }
 }
 
+/// Parse UID or UID range like "1000" or "1000-1999".
+fn parse_uid_range(s: &str) -> Result<(u32, u32)> {
+    let parse = |v: &str| {
+        v.trim()
+            .parse::<u32>()
+            .map_err(|_| Error::InvalidMessage(format!("invalid uid range: {}", s)))
+    };
+    let (start, end) = match s.split_once('-') {
+        Some((start, end)) => (parse(start)?, parse(end)?),
+        None => {
+            let uid = parse(s)?;
+            (uid, uid)
+        }
+    };
+    if start > end {
+        return Err(Error::InvalidMessage(format!("invalid uid range: {}", s)));
+    }
+    Ok((start, end))
+}
+
 /// Parse IP protocol name or number.
 fn parse_ip_proto(s: &str) -> Result<u8> {
     match s.to_lowercase().as_str() {
@@ -718,7 +646,7 @@ fn parse_ip_proto(s: &str) -> Result<u8> {
         "sctp" => Ok(132),
         _ => s
             .parse()
-            .map_err(|_| nlink::netlink::Error::InvalidMessage(format!("unknown protocol: {}", s))),
+            .map_err(|_| Error::InvalidMessage(format!("unknown protocol: {}", s))),
     }
 }
 
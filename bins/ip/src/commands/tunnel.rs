@@ -60,7 +60,7 @@ enum TunnelAction {
         /// Tunnel name.
         name: String,
 
-        /// Tunnel mode (gre, ipip, sit, vti).
+        /// Tunnel mode (gre, gretap, ipip, sit, vti).
         #[arg(long)]
         mode: String,
 
@@ -80,15 +80,15 @@ enum TunnelAction {
         #[arg(long)]
         tos: Option<u8>,
 
-        /// Tunnel key (for GRE).
-        #[arg(long)]
+        /// Tunnel key, used in both directions (for GRE and VTI).
+        #[arg(long, conflicts_with_all = ["ikey", "okey"])]
         key: Option<u32>,
 
-        /// Input key (for GRE).
+        /// Input key (for GRE and VTI).
         #[arg(long)]
         ikey: Option<u32>,
 
-        /// Output key (for GRE).
+        /// Output key (for GRE and VTI).
         #[arg(long)]
         okey: Option<u32>,
 
@@ -314,10 +314,21 @@ impl TunnelCmd {
             None
         };
 
-        // Use the first key specified (key > ikey > okey)
-        let effective_key = key.or(ikey).or(okey);
+        // `--key` sets both directions; clap rejects it alongside
+        // `--ikey`/`--okey`.
+        let ikey = key.or(ikey);
+        let okey = key.or(okey);
+
+        let mode = mode.to_lowercase();
+        if matches!(mode.as_str(), "ipip" | "ip/ip" | "sit" | "ipv6/ip")
+            && (ikey.is_some() || okey.is_some())
+        {
+            return Err(nlink::netlink::Error::InvalidMessage(format!(
+                "{mode} tunnels have no key; keys apply to gre, gretap and vti"
+            )));
+        }
 
-        match mode.to_lowercase().as_str() {
+        match mode.as_str() {
             "gre" => {
                 let mut link = GreLink::new(name).remote(remote_addr);
                 if let Some(local) = local_addr {
@@ -335,8 +346,11 @@ impl TunnelCmd {
                 if let Some(d) = dev {
                     link = link.link(d);
                 }
-                if let Some(k) = effective_key {
-                    link = link.key(k);
+                if let Some(k) = ikey {
+                    link = link.ikey(k);
+                }
+                if let Some(k) = okey {
+                    link = link.okey(k);
                 }
                 conn.add_link(link).await?;
             }
@@ -357,8 +371,11 @@ impl TunnelCmd {
                 if let Some(d) = dev {
                     link = link.link(d);
                 }
-                if let Some(k) = effective_key {
-                    link = link.key(k);
+                if let Some(k) = ikey {
+                    link = link.ikey(k);
+                }
+                if let Some(k) = okey {
+                    link = link.okey(k);
                 }
                 conn.add_link(link).await?;
             }
@@ -420,8 +437,11 @@ impl TunnelCmd {
                 if let Some(d) = dev {
                     link = link.link(d);
                 }
-                if let Some(k) = effective_key {
-                    link = link.ikey(k).okey(k);
+                if let Some(k) = ikey {
+                    link = link.ikey(k);
+                }
+                if let Some(k) = okey {
+                    link = link.okey(k);
                 }
                 conn.add_link(link).await?;
             }
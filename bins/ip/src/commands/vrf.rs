@@ -7,13 +7,10 @@ use std::io::Write;
 
 use clap::{Args, Subcommand};
 use nlink::{
-    netlink::{Connection, Result, Route, attr::AttrIter},
+    netlink::{Connection, Result, Route, messages::LinkKindInfo},
     output::{OutputFormat, OutputOptions, Printable, print_all},
 };
 
-/// IFLA_VRF_TABLE attribute constant.
-const IFLA_VRF_TABLE: u16 = 1;
-
 #[derive(Args)]
 pub struct VrfCmd {
     #[command(subcommand)]
@@ -107,15 +104,9 @@ impl VrfCmd {
         let mut vrfs = Vec::new();
 
         for link in &links {
-            // Check if this is a VRF device
-            let link_info = match link.link_info() {
-                Some(info) => info,
-                None => continue,
-            };
-
-            if link_info.kind() != Some("vrf") {
+            let Some(LinkKindInfo::Vrf(vrf)) = link.kind_info() else {
                 continue;
-            }
+            };
 
             // Apply name filter
             if let Some(name) = name_filter
@@ -124,13 +115,10 @@ impl VrfCmd {
                 continue;
             }
 
-            // Extract VRF table from link_info.data
-            let table = extract_vrf_table(link_info.data().unwrap_or(&[]));
-
             vrfs.push(VrfInfo {
                 name: link.name().unwrap_or_default().to_string(),
                 ifindex: link.ifindex(),
-                table,
+                table: vrf.table.unwrap_or(0),
             });
         }
 
@@ -229,13 +217,3 @@ impl VrfCmd {
         }
     }
 }
-
-/// Extract VRF table ID from link_info.data bytes.
-fn extract_vrf_table(data: &[u8]) -> u32 {
-    for (attr_type, attr_data) in AttrIter::new(data) {
-        if attr_type == IFLA_VRF_TABLE && attr_data.len() >= 4 {
-            return u32::from_ne_bytes(attr_data[..4].try_into().expect("4-byte attribute data"));
-        }
-    }
-    0
-}
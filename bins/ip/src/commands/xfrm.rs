@@ -4,13 +4,20 @@
 //! Associations (SAs) and Security Policies (SPs) over the kernel's
 //! NETLINK_XFRM interface via `Connection<Xfrm>`.
 
-use std::{io::Write, net::IpAddr};
+use std::{
+    io::Write,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::SystemTime,
+};
 
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 use nlink::{
     netlink::{
-        Connection, Result, Xfrm,
-        xfrm::{SecurityAssociation, SecurityPolicy},
+        Connection, Error, Result, Xfrm,
+        xfrm::{
+            IpsecProtocol, PolicyDirection, SecurityAssociation, SecurityPolicy, XfrmAddress,
+            XfrmMode, XfrmSaBuilder, XfrmSelector, XfrmSpBuilder, XfrmUserTmpl,
+        },
     },
     output::{OutputFormat, OutputOptions, Printable, print_all},
 };
@@ -46,12 +53,82 @@ enum StateAction {
     /// List XFRM states (alias for show).
     #[command(visible_alias = "ls")]
     List,
+    /// Add an XFRM state.
+    Add(StateArgs),
+    /// Replace an existing XFRM state in place (e.g. to rotate keys).
+    Update(StateArgs),
+    /// Delete an XFRM state.
+    #[command(visible_alias = "delete")]
+    Del(StateIdArgs),
     /// Flush all XFRM states.
     Flush,
     /// Get state count.
     Count,
 }
 
+/// The tuple identifying a state.
+#[derive(Args)]
+struct StateIdArgs {
+    /// Source address.
+    #[arg(long)]
+    src: IpAddr,
+
+    /// Destination address.
+    #[arg(long)]
+    dst: IpAddr,
+
+    /// IPsec protocol.
+    #[arg(long, value_enum, default_value = "esp")]
+    proto: ProtoArg,
+
+    /// Security Parameter Index (hex `0x…` or decimal).
+    #[arg(long)]
+    spi: String,
+}
+
+/// Arguments for `state add` / `state update`.
+#[derive(Args)]
+struct StateArgs {
+    #[command(flatten)]
+    id: StateIdArgs,
+
+    /// Transform mode.
+    #[arg(long, value_enum, default_value = "transport")]
+    mode: ModeArg,
+
+    /// Request ID tying the state to policy templates.
+    #[arg(long, default_value_t = 0)]
+    reqid: u32,
+
+    /// Anti-replay window in packets.
+    #[arg(long = "replay-window")]
+    replay_window: Option<u8>,
+
+    /// Encryption algorithm and hex key, e.g. `--enc 'cbc(aes)' 0x…`.
+    #[arg(long, num_args = 2, value_names = ["ALGO", "KEY"])]
+    enc: Option<Vec<String>>,
+
+    /// Authentication algorithm and hex key, e.g. `--auth sha256 0x…`.
+    #[arg(long, num_args = 2, value_names = ["ALGO", "KEY"])]
+    auth: Option<Vec<String>>,
+
+    /// AEAD algorithm, hex key (including salt) and ICV length in bits.
+    #[arg(long, num_args = 3, value_names = ["ALGO", "KEY", "ICV_BITS"])]
+    aead: Option<Vec<String>>,
+
+    /// NAT-T UDP encapsulation ports.
+    #[arg(long, num_args = 2, value_names = ["SPORT", "DPORT"])]
+    encap: Option<Vec<u16>>,
+
+    /// Firewall mark to match (`MARK[/MASK]`).
+    #[arg(long)]
+    mark: Option<String>,
+
+    /// XFRM interface ID.
+    #[arg(long = "if-id")]
+    if_id: Option<u32>,
+}
+
 #[derive(Subcommand)]
 enum PolicyAction {
     /// Show XFRM policies.
@@ -59,12 +136,147 @@ enum PolicyAction {
     /// List XFRM policies (alias for show).
     #[command(visible_alias = "ls")]
     List,
+    /// Add an XFRM policy.
+    Add(PolicyArgs),
+    /// Replace an existing XFRM policy in place.
+    Update(PolicyArgs),
+    /// Delete an XFRM policy.
+    #[command(visible_alias = "delete")]
+    Del(PolicyIdArgs),
     /// Flush all XFRM policies.
     Flush,
     /// Get policy count.
     Count,
 }
 
+/// The selector and direction identifying a policy.
+#[derive(Args)]
+struct PolicyIdArgs {
+    /// Source prefix (default: any).
+    #[arg(long)]
+    src: Option<String>,
+
+    /// Destination prefix (default: any).
+    #[arg(long)]
+    dst: Option<String>,
+
+    /// Upper-layer protocol (name or number).
+    #[arg(long)]
+    proto: Option<String>,
+
+    /// Source port (needs `--proto`).
+    #[arg(long)]
+    sport: Option<u16>,
+
+    /// Destination port (needs `--proto`).
+    #[arg(long)]
+    dport: Option<u16>,
+
+    /// Policy direction.
+    #[arg(long, value_enum)]
+    dir: DirArg,
+}
+
+/// Arguments for `policy add` / `policy update`.
+#[derive(Args)]
+struct PolicyArgs {
+    #[command(flatten)]
+    id: PolicyIdArgs,
+
+    /// What to do with matching traffic.
+    #[arg(long, value_enum, default_value = "allow")]
+    action: ActionArg,
+
+    /// Priority (lower wins).
+    #[arg(long, default_value_t = 0)]
+    priority: u32,
+
+    /// Firewall mark to match (`MARK[/MASK]`).
+    #[arg(long)]
+    mark: Option<String>,
+
+    /// XFRM interface ID.
+    #[arg(long = "if-id")]
+    if_id: Option<u32>,
+
+    /// Template source address (tunnel endpoint).
+    #[arg(long = "tmpl-src")]
+    tmpl_src: Option<IpAddr>,
+
+    /// Template destination address (tunnel endpoint).
+    #[arg(long = "tmpl-dst")]
+    tmpl_dst: Option<IpAddr>,
+
+    /// Template protocol; giving any `--tmpl-*` option adds a template.
+    #[arg(long = "tmpl-proto", value_enum)]
+    tmpl_proto: Option<ProtoArg>,
+
+    /// Template mode.
+    #[arg(long = "tmpl-mode", value_enum)]
+    tmpl_mode: Option<ModeArg>,
+
+    /// Template request ID, matching the state's `--reqid`.
+    #[arg(long = "tmpl-reqid")]
+    tmpl_reqid: Option<u32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ProtoArg {
+    Esp,
+    Ah,
+    Comp,
+}
+
+impl From<ProtoArg> for IpsecProtocol {
+    fn from(p: ProtoArg) -> Self {
+        match p {
+            ProtoArg::Esp => IpsecProtocol::Esp,
+            ProtoArg::Ah => IpsecProtocol::Ah,
+            ProtoArg::Comp => IpsecProtocol::Comp,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ModeArg {
+    Transport,
+    Tunnel,
+    Beet,
+}
+
+impl From<ModeArg> for XfrmMode {
+    fn from(m: ModeArg) -> Self {
+        match m {
+            ModeArg::Transport => XfrmMode::Transport,
+            ModeArg::Tunnel => XfrmMode::Tunnel,
+            ModeArg::Beet => XfrmMode::Beet,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DirArg {
+    In,
+    Out,
+    Fwd,
+}
+
+impl From<DirArg> for PolicyDirection {
+    fn from(d: DirArg) -> Self {
+        match d {
+            DirArg::In => PolicyDirection::In,
+            DirArg::Out => PolicyDirection::Out,
+            DirArg::Fwd => PolicyDirection::Forward,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ActionArg {
+    Allow,
+    Block,
+}
+
 fn addr_or_any(a: Option<IpAddr>) -> String {
     a.map(|ip| ip.to_string()).unwrap_or_else(|| "any".into())
 }
@@ -74,7 +286,7 @@ fn addr_or_any(a: Option<IpAddr>) -> String {
 struct SaRow(SecurityAssociation);
 
 impl Printable for SaRow {
-    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> std::io::Result<()> {
+    fn print_text<W: Write>(&self, w: &mut W, opts: &OutputOptions) -> std::io::Result<()> {
         let sa = &self.0;
         writeln!(
             w,
@@ -96,6 +308,49 @@ impl Printable for SaRow {
         if let Some(ref a) = sa.auth_alg {
             writeln!(w, "\tauth {} ({} bits)", a.name, a.key_len)?;
         }
+        if let Some(if_id) = sa.if_id {
+            writeln!(w, "\tif_id 0x{if_id:x}")?;
+        }
+        if opts.stats {
+            let lft = &sa.lifetime;
+            writeln!(w, "\tlifetime config:")?;
+            writeln!(
+                w,
+                "\t  limit: soft {}(bytes), hard {}(bytes)",
+                limit(lft.soft_byte_limit),
+                limit(lft.hard_byte_limit)
+            )?;
+            writeln!(
+                w,
+                "\t  limit: soft {}(packets), hard {}(packets)",
+                limit(lft.soft_packet_limit),
+                limit(lft.hard_packet_limit)
+            )?;
+            writeln!(
+                w,
+                "\t  expire add: soft {}(sec), hard {}(sec)",
+                lft.soft_add_expires_seconds, lft.hard_add_expires_seconds
+            )?;
+            writeln!(
+                w,
+                "\t  expire use: soft {}(sec), hard {}(sec)",
+                lft.soft_use_expires_seconds, lft.hard_use_expires_seconds
+            )?;
+            writeln!(w, "\tlifetime current:")?;
+            writeln!(w, "\t  {}(bytes), {}(packets)", sa.bytes, sa.packets)?;
+            writeln!(
+                w,
+                "\t  add {} use {}",
+                age(sa.added_at()),
+                age(sa.last_used())
+            )?;
+            writeln!(w, "\tstats:")?;
+            writeln!(
+                w,
+                "\t  replay-window {} replay {} failed {}",
+                sa.stats.replay_window, sa.stats.replay, sa.stats.integrity_failed
+            )?;
+        }
         Ok(())
     }
 
@@ -111,10 +366,50 @@ impl Printable for SaRow {
             "enc": sa.enc_alg.as_ref().map(|a| a.name.clone()),
             "auth": sa.auth_alg.as_ref().map(|a| a.name.clone()),
             "aead": sa.aead_alg.as_ref().map(|a| a.name.clone()),
+            "if_id": sa.if_id,
+            "lifetime_config": {
+                "soft_byte_limit": sa.lifetime.soft_byte_limit,
+                "hard_byte_limit": sa.lifetime.hard_byte_limit,
+                "soft_packet_limit": sa.lifetime.soft_packet_limit,
+                "hard_packet_limit": sa.lifetime.hard_packet_limit,
+                "soft_add_expires_seconds": sa.lifetime.soft_add_expires_seconds,
+                "hard_add_expires_seconds": sa.lifetime.hard_add_expires_seconds,
+                "soft_use_expires_seconds": sa.lifetime.soft_use_expires_seconds,
+                "hard_use_expires_seconds": sa.lifetime.hard_use_expires_seconds,
+            },
+            "lifetime_current": {
+                "bytes": sa.bytes,
+                "packets": sa.packets,
+                "add_time": sa.add_time,
+                "use_time": sa.use_time,
+            },
+            "stats": {
+                "replay_window": sa.stats.replay_window,
+                "replay": sa.stats.replay,
+                "integrity_failed": sa.stats.integrity_failed,
+            },
         })
     }
 }
 
+/// Render a lifetime limit, with the kernel's "no limit" as `(INF)`.
+fn limit(v: u64) -> String {
+    if v == u64::MAX {
+        "(INF)".to_string()
+    } else {
+        v.to_string()
+    }
+}
+
+/// Render a timestamp as its age, or `-` if unset.
+fn age(t: Option<SystemTime>) -> String {
+    match t.map(|t| SystemTime::now().duration_since(t)) {
+        Some(Ok(d)) => format!("{}s ago", d.as_secs()),
+        Some(Err(_)) => "0s ago".to_string(),
+        None => "-".to_string(),
+    }
+}
+
 /// Wrapper so we can implement `Printable` for `SecurityPolicy`.
 struct SpRow(SecurityPolicy);
 
@@ -159,11 +454,37 @@ impl XfrmCmd {
         match &self.action {
             XfrmAction::State { action } => match action.as_ref().unwrap_or(&StateAction::Show) {
                 StateAction::Show | StateAction::List => Self::show_states(format, opts).await,
+                StateAction::Add(args) => {
+                    let sa = build_sa(args)?;
+                    Connection::<Xfrm>::new()?.add_sa(sa).await
+                }
+                StateAction::Update(args) => {
+                    let sa = build_sa(args)?;
+                    Connection::<Xfrm>::new()?.update_sa(sa).await
+                }
+                StateAction::Del(id) => {
+                    let spi = parse_u32(&id.spi)?;
+                    Connection::<Xfrm>::new()?
+                        .del_sa(id.src, id.dst, spi, id.proto.into())
+                        .await
+                }
                 StateAction::Flush => Self::flush_states().await,
                 StateAction::Count => Self::count_states().await,
             },
             XfrmAction::Policy { action } => match action.as_ref().unwrap_or(&PolicyAction::Show) {
                 PolicyAction::Show | PolicyAction::List => Self::show_policies(format, opts).await,
+                PolicyAction::Add(args) => {
+                    let sp = build_sp(args)?;
+                    Connection::<Xfrm>::new()?.add_sp(sp).await
+                }
+                PolicyAction::Update(args) => {
+                    let sp = build_sp(args)?;
+                    Connection::<Xfrm>::new()?.update_sp(sp).await
+                }
+                PolicyAction::Del(id) => {
+                    let sel = build_selector(id)?;
+                    Connection::<Xfrm>::new()?.del_sp(sel, id.dir.into()).await
+                }
                 PolicyAction::Flush => Self::flush_policies().await,
                 PolicyAction::Count => Self::count_policies().await,
             },
@@ -291,3 +612,489 @@ fn fmt_opt_ip(addr: Option<IpAddr>) -> String {
     addr.map(|a| a.to_string())
         .unwrap_or_else(|| "-".to_string())
 }
+
+/// A kernel crypto algorithm accepted on the command line.
+struct Algo {
+    /// Kernel crypto API name.
+    name: &'static str,
+    /// Short iproute2-style alias, if any.
+    alias: Option<&'static str>,
+    /// Accepted key lengths in bytes; empty means any.
+    key_lens: &'static [usize],
+}
+
+const ENC_ALGOS: &[Algo] = &[
+    Algo {
+        name: "cbc(aes)",
+        alias: Some("aes"),
+        key_lens: &[16, 24, 32],
+    },
+    Algo {
+        name: "rfc3686(ctr(aes))",
+        alias: None,
+        key_lens: &[20, 28, 36],
+    },
+    Algo {
+        name: "cbc(des3_ede)",
+        alias: Some("des3_ede"),
+        key_lens: &[24],
+    },
+    Algo {
+        name: "cbc(camellia)",
+        alias: Some("camellia"),
+        key_lens: &[16, 24, 32],
+    },
+    Algo {
+        name: "cbc(serpent)",
+        alias: Some("serpent"),
+        key_lens: &[16, 24, 32],
+    },
+    Algo {
+        name: "cbc(twofish)",
+        alias: Some("twofish"),
+        key_lens: &[16, 24, 32],
+    },
+    Algo {
+        name: "ecb(cipher_null)",
+        alias: Some("cipher_null"),
+        key_lens: &[0],
+    },
+];
+
+const AUTH_ALGOS: &[Algo] = &[
+    Algo {
+        name: "hmac(md5)",
+        alias: Some("md5"),
+        key_lens: &[],
+    },
+    Algo {
+        name: "hmac(sha1)",
+        alias: Some("sha1"),
+        key_lens: &[],
+    },
+    Algo {
+        name: "hmac(sha256)",
+        alias: Some("sha256"),
+        key_lens: &[],
+    },
+    Algo {
+        name: "hmac(sha384)",
+        alias: Some("sha384"),
+        key_lens: &[],
+    },
+    Algo {
+        name: "hmac(sha512)",
+        alias: Some("sha512"),
+        key_lens: &[],
+    },
+    Algo {
+        name: "xcbc(aes)",
+        alias: None,
+        key_lens: &[16],
+    },
+    Algo {
+        name: "cmac(aes)",
+        alias: None,
+        key_lens: &[16],
+    },
+    Algo {
+        name: "digest_null",
+        alias: None,
+        key_lens: &[0],
+    },
+];
+
+const AEAD_ALGOS: &[Algo] = &[
+    Algo {
+        name: "rfc4106(gcm(aes))",
+        alias: None,
+        key_lens: &[20, 28, 36],
+    },
+    Algo {
+        name: "rfc4309(ccm(aes))",
+        alias: None,
+        key_lens: &[19, 27, 35],
+    },
+    Algo {
+        name: "rfc4543(gcm(aes))",
+        alias: None,
+        key_lens: &[20, 28, 36],
+    },
+    Algo {
+        name: "rfc7539esp(chacha20,poly1305)",
+        alias: None,
+        key_lens: &[36],
+    },
+];
+
+/// Resolve `name` (kernel name or alias) in `table` and check the key
+/// length. Returns the kernel name and the key bytes.
+fn parse_algo(
+    table: &'static [Algo],
+    what: &str,
+    name: &str,
+    key: &str,
+) -> Result<(&'static str, Vec<u8>)> {
+    let algo = table
+        .iter()
+        .find(|a| a.name == name || a.alias == Some(name))
+        .ok_or_else(|| {
+            let known: Vec<_> = table.iter().map(|a| a.name).collect();
+            Error::InvalidMessage(format!(
+                "xfrm: unknown {what} algorithm `{name}` (expected one of: {})",
+                known.join(", ")
+            ))
+        })?;
+    let key = parse_hex(key, what)?;
+    if !algo.key_lens.is_empty() && !algo.key_lens.contains(&key.len()) {
+        let lens: Vec<_> = algo.key_lens.iter().map(|n| n.to_string()).collect();
+        return Err(Error::InvalidMessage(format!(
+            "xfrm: {} key must be {} bytes, got {}",
+            algo.name,
+            lens.join(" or "),
+            key.len()
+        )));
+    }
+    Ok((algo.name, key))
+}
+
+/// Build the SA for `state add` / `state update`.
+fn build_sa(args: &StateArgs) -> Result<XfrmSaBuilder> {
+    let id = &args.id;
+    if id.src.is_ipv4() != id.dst.is_ipv4() {
+        return Err(Error::InvalidMessage(
+            "xfrm: --src and --dst must be the same address family".into(),
+        ));
+    }
+    let proto = IpsecProtocol::from(id.proto);
+    let mut sa = XfrmSaBuilder::new(id.src, id.dst, parse_u32(&id.spi)?, proto)
+        .mode(args.mode.into())
+        .reqid(args.reqid);
+    if let Some(w) = args.replay_window {
+        sa = sa.replay_window(w);
+    }
+
+    if args.aead.is_some() && (args.enc.is_some() || args.auth.is_some()) {
+        return Err(Error::InvalidMessage(
+            "xfrm: --aead can't be combined with --enc or --auth".into(),
+        ));
+    }
+    match proto {
+        IpsecProtocol::Esp if args.aead.is_none() && args.enc.is_none() => {
+            return Err(Error::InvalidMessage(
+                "xfrm: esp needs --enc or --aead".into(),
+            ));
+        }
+        IpsecProtocol::Ah if args.auth.is_none() => {
+            return Err(Error::InvalidMessage("xfrm: ah needs --auth".into()));
+        }
+        IpsecProtocol::Ah if args.enc.is_some() || args.aead.is_some() => {
+            return Err(Error::InvalidMessage("xfrm: ah only takes --auth".into()));
+        }
+        _ => {}
+    }
+
+    if let Some(v) = &args.enc {
+        let (name, key) = parse_algo(ENC_ALGOS, "enc", &v[0], &v[1])?;
+        sa = sa.encr(name, &key);
+    }
+    if let Some(v) = &args.auth {
+        let (name, key) = parse_algo(AUTH_ALGOS, "auth", &v[0], &v[1])?;
+        sa = sa.auth(name, &key);
+    }
+    if let Some(v) = &args.aead {
+        let (name, key) = parse_algo(AEAD_ALGOS, "aead", &v[0], &v[1])?;
+        let icv: u32 = v[2]
+            .parse()
+            .map_err(|_| Error::InvalidMessage(format!("xfrm: invalid ICV length `{}`", v[2])))?;
+        if !matches!(icv, 64 | 96 | 128) {
+            return Err(Error::InvalidMessage(format!(
+                "xfrm: ICV length must be 64, 96 or 128 bits, got {icv}"
+            )));
+        }
+        sa = sa.aead(name, &key, icv);
+    }
+    if let Some(ports) = &args.encap {
+        sa = sa.nat_t_udp_encap(ports[0], ports[1]);
+    }
+    if let Some(mark) = &args.mark {
+        let (mark, mask) = parse_mark(mark)?;
+        sa = sa.mark(mark, mask);
+    }
+    if let Some(if_id) = args.if_id {
+        sa = sa.if_id(if_id);
+    }
+    Ok(sa)
+}
+
+/// Build the policy for `policy add` / `policy update`.
+fn build_sp(args: &PolicyArgs) -> Result<XfrmSpBuilder> {
+    let sel = build_selector(&args.id)?;
+    let mut sp = XfrmSpBuilder::new(sel, args.id.dir.into()).priority(args.priority);
+    if args.action == ActionArg::Block {
+        sp = sp.block();
+    }
+    if let Some(mark) = &args.mark {
+        let (mark, mask) = parse_mark(mark)?;
+        sp = sp.mark(mark, mask);
+    }
+    if let Some(if_id) = args.if_id {
+        sp = sp.if_id(if_id);
+    }
+
+    let wants_tmpl = args.tmpl_src.is_some()
+        || args.tmpl_dst.is_some()
+        || args.tmpl_proto.is_some()
+        || args.tmpl_mode.is_some()
+        || args.tmpl_reqid.is_some();
+    if wants_tmpl {
+        let mode = args.tmpl_mode.unwrap_or(ModeArg::Transport);
+        let unspecified = if sel.family == libc::AF_INET6 as u16 {
+            IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+        } else {
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        };
+        let (src, dst) = match (args.tmpl_src, args.tmpl_dst) {
+            (Some(src), Some(dst)) => (src, dst),
+            (None, None) if mode != ModeArg::Tunnel => (unspecified, unspecified),
+            _ => {
+                return Err(Error::InvalidMessage(
+                    "xfrm: tunnel templates need both --tmpl-src and --tmpl-dst".into(),
+                ));
+            }
+        };
+        if src.is_ipv4() != dst.is_ipv4() {
+            return Err(Error::InvalidMessage(
+                "xfrm: --tmpl-src and --tmpl-dst must be the same address family".into(),
+            ));
+        }
+        sp = sp.template(XfrmUserTmpl::match_any(
+            src,
+            dst,
+            args.tmpl_proto.unwrap_or(ProtoArg::Esp).into(),
+            mode.into(),
+            args.tmpl_reqid.unwrap_or(0),
+        ));
+    }
+    Ok(sp)
+}
+
+/// Build the traffic selector shared by policy add/update/del.
+fn build_selector(args: &PolicyIdArgs) -> Result<XfrmSelector> {
+    let (src, src_len) = parse_selector_prefix(args.src.as_deref(), "--src")?;
+    let (dst, dst_len) = parse_selector_prefix(args.dst.as_deref(), "--dst")?;
+    let family = match (src, dst) {
+        (Some(a), Some(b)) if a.is_ipv4() != b.is_ipv4() => {
+            return Err(Error::InvalidMessage(
+                "xfrm: --src and --dst must be the same address family".into(),
+            ));
+        }
+        (Some(IpAddr::V6(_)), _) | (_, Some(IpAddr::V6(_))) => libc::AF_INET6 as u16,
+        _ => libc::AF_INET as u16,
+    };
+    let proto = args.proto.as_deref().map(parse_ip_proto).transpose()?;
+    if proto.is_none() && (args.sport.is_some() || args.dport.is_some()) {
+        return Err(Error::InvalidMessage(
+            "xfrm: --sport/--dport need --proto".into(),
+        ));
+    }
+    let port = |p: Option<u16>| p.map_or((0, 0), |p| (p.to_be(), u16::MAX));
+    let (sport, sport_mask) = port(args.sport);
+    let (dport, dport_mask) = port(args.dport);
+    Ok(XfrmSelector {
+        daddr: dst.map(xfrm_addr).unwrap_or_default(),
+        saddr: src.map(xfrm_addr).unwrap_or_default(),
+        dport,
+        dport_mask,
+        sport,
+        sport_mask,
+        family,
+        prefixlen_d: dst_len,
+        prefixlen_s: src_len,
+        proto: proto.unwrap_or(0),
+        ..Default::default()
+    })
+}
+
+fn xfrm_addr(ip: IpAddr) -> XfrmAddress {
+    match ip {
+        IpAddr::V4(v4) => XfrmAddress::from_v4(v4),
+        IpAddr::V6(v6) => XfrmAddress::from_v6(v6),
+    }
+}
+
+/// Parse a selector prefix; absent, `any` and `default` match everything.
+fn parse_selector_prefix(s: Option<&str>, what: &str) -> Result<(Option<IpAddr>, u8)> {
+    match s {
+        None | Some("any" | "default") => Ok((None, 0)),
+        Some(s) => {
+            let (addr, len) = nlink::util::addr::parse_prefix(s)
+                .map_err(|e| Error::InvalidMessage(format!("xfrm: invalid {what}: {e}")))?;
+            Ok((Some(addr), len))
+        }
+    }
+}
+
+/// Parse an upper-layer protocol name or number.
+fn parse_ip_proto(s: &str) -> Result<u8> {
+    match s.to_lowercase().as_str() {
+        "tcp" => Ok(6),
+        "udp" => Ok(17),
+        "icmp" => Ok(1),
+        "icmpv6" | "ipv6-icmp" => Ok(58),
+        "gre" => Ok(47),
+        "sctp" => Ok(132),
+        _ => s
+            .parse()
+            .map_err(|_| Error::InvalidMessage(format!("xfrm: unknown protocol `{s}`"))),
+    }
+}
+
+/// Parse `MARK[/MASK]`; a bare mark matches all bits.
+fn parse_mark(s: &str) -> Result<(u32, u32)> {
+    match s.split_once('/') {
+        Some((mark, mask)) => Ok((parse_u32(mark)?, parse_u32(mask)?)),
+        None => Ok((parse_u32(s)?, u32::MAX)),
+    }
+}
+
+/// Parse a u32 (hex with `0x` prefix, or decimal).
+fn parse_u32(s: &str) -> Result<u32> {
+    let s = s.trim();
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16)
+    } else {
+        s.parse()
+    }
+    .map_err(|_| Error::InvalidMessage(format!("xfrm: invalid number `{s}`")))
+}
+
+/// Parse an even-length hex key, with or without a `0x` prefix.
+fn parse_hex(s: &str, what: &str) -> Result<Vec<u8>> {
+    let s = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    if !s.len().is_multiple_of(2) {
+        return Err(Error::InvalidMessage(format!(
+            "xfrm: {what} key must have an even number of hex digits"
+        )));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&s[i..i + 2], 16)
+                .map_err(|_| Error::InvalidMessage(format!("xfrm: invalid hex in {what} key")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        action: StateAction,
+    }
+
+    fn state_args(args: &[&str]) -> StateArgs {
+        let argv = ["xfrm", "add"].iter().chain(args);
+        match TestCli::try_parse_from(argv).unwrap().action {
+            StateAction::Add(a) => a,
+            _ => unreachable!(),
+        }
+    }
+
+    const BASE: [&str; 6] = ["--src", "10.0.0.1", "--dst", "10.0.0.2", "--spi", "0x1000"];
+
+    #[test]
+    fn algo_names_and_key_lengths_are_validated() {
+        let key16 = "0x000102030405060708090a0b0c0d0e0f";
+        assert_eq!(
+            parse_algo(ENC_ALGOS, "enc", "aes", key16).unwrap().0,
+            "cbc(aes)"
+        );
+        assert_eq!(
+            parse_algo(AUTH_ALGOS, "auth", "hmac(sha256)", "0a0b").unwrap(),
+            ("hmac(sha256)", vec![0x0a, 0x0b])
+        );
+        assert!(parse_algo(ENC_ALGOS, "enc", "rot13", key16).is_err());
+        assert!(parse_algo(ENC_ALGOS, "enc", "cbc(aes)", "0a0b").is_err());
+        assert!(parse_algo(AEAD_ALGOS, "aead", "rfc4106(gcm(aes))", key16).is_err());
+        assert!(parse_hex("abc", "enc").is_err());
+        assert!(parse_hex("zz", "enc").is_err());
+    }
+
+    #[test]
+    fn state_algorithms_must_fit_the_protocol() {
+        let key = "000102030405060708090a0b0c0d0e0f";
+        let mut ok = BASE.to_vec();
+        ok.extend(["--enc", "aes", key, "--auth", "sha256", key]);
+        assert!(build_sa(&state_args(&ok)).is_ok());
+
+        let mut aead = BASE.to_vec();
+        aead.extend([
+            "--aead",
+            "rfc4106(gcm(aes))",
+            "0x0001020304050607080910111213141516171819",
+            "128",
+        ]);
+        assert!(build_sa(&state_args(&aead)).is_ok());
+
+        // ESP without a cipher, AEAD mixed with --enc, AH with --enc.
+        assert!(build_sa(&state_args(&BASE)).is_err());
+        let mut mixed = aead.clone();
+        mixed.extend(["--enc", "aes", key]);
+        assert!(build_sa(&state_args(&mixed)).is_err());
+        let mut ah = BASE.to_vec();
+        ah.extend(["--proto", "ah", "--enc", "aes", key]);
+        assert!(build_sa(&state_args(&ah)).is_err());
+    }
+
+    #[test]
+    fn policy_selector_from_prefixes_and_ports() {
+        let id = PolicyIdArgs {
+            src: Some("2001:db8::/64".into()),
+            dst: None,
+            proto: Some("tcp".into()),
+            sport: None,
+            dport: Some(443),
+            dir: DirArg::Out,
+        };
+        let sel = build_selector(&id).unwrap();
+        assert_eq!({ sel.family }, libc::AF_INET6 as u16);
+        assert_eq!(sel.prefixlen_s, 64);
+        assert_eq!(sel.prefixlen_d, 0);
+        assert_eq!(sel.proto, 6);
+        assert_eq!({ sel.dport }, 443u16.to_be());
+        assert_eq!({ sel.dport_mask }, u16::MAX);
+        assert_eq!({ sel.sport_mask }, 0);
+
+        let mixed = PolicyIdArgs {
+            src: Some("10.0.0.0/8".into()),
+            dst: Some("2001:db8::1".into()),
+            proto: None,
+            sport: None,
+            dport: None,
+            dir: DirArg::In,
+        };
+        assert!(build_selector(&mixed).is_err());
+    }
+
+    #[test]
+    fn lifetime_limits_render_infinity() {
+        assert_eq!(limit(u64::MAX), "(INF)");
+        assert_eq!(limit(4096), "4096");
+        assert_eq!(age(None), "-");
+    }
+
+    #[test]
+    fn mark_with_and_without_mask() {
+        assert_eq!(parse_mark("0x10").unwrap(), (0x10, u32::MAX));
+        assert_eq!(parse_mark("1/0xff").unwrap(), (1, 0xff));
+    }
+}
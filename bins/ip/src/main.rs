@@ -23,6 +23,10 @@ struct Cli {
     #[arg(short = '6')]
     ipv6: bool,
 
+    /// Use MPLS only (`route show` lists the label table).
+    #[arg(short = 'M')]
+    mpls: bool,
+
     /// Output JSON.
     #[arg(short = 'j', long)]
     json: bool,
@@ -61,10 +65,16 @@ enum Command {
     #[command(visible_alias = "r")]
     Route(commands::route::RouteCmd),
 
+    /// Manage IPv6 address labels.
+    Addrlabel(commands::addrlabel::AddrlabelCmd),
+
     /// Manage ARP/NDP cache.
     #[command(visible_alias = "n", visible_alias = "neigh")]
     Neighbor(commands::neighbor::NeighborCmd),
 
+    /// Manage neighbor table parameters.
+    Ntable(commands::ntable::NtableCmd),
+
     /// Manage routing policy rules.
     #[command(visible_alias = "ru")]
     Rule(commands::rule::RuleCmd),
@@ -98,6 +108,9 @@ enum Command {
     /// Manage MPTCP (Multipath TCP) endpoints and limits.
     Mptcp(commands::mptcp::MptcpCmd),
 
+    /// Manage ILA (identifier-locator addressing) mappings.
+    Ila(commands::ila::IlaCmd),
+
     /// Manage Segment Routing (SRv6).
     Sr(commands::sr::SrCmd),
 
@@ -133,9 +146,10 @@ async fn main() -> anyhow::Result<()> {
     };
 
     // Determine address family filter
-    let family = match (cli.ipv4, cli.ipv6) {
-        (true, false) => Some(2),  // AF_INET
-        (false, true) => Some(10), // AF_INET6
+    let family = match (cli.ipv4, cli.ipv6, cli.mpls) {
+        (true, false, false) => Some(2),  // AF_INET
+        (false, true, false) => Some(10), // AF_INET6
+        (false, false, true) => Some(28), // AF_MPLS
         _ => None,
     };
 
@@ -147,16 +161,19 @@ async fn main() -> anyhow::Result<()> {
         Command::Link(cmd) => cmd.run(&conn, format, &opts).await,
         Command::Address(cmd) => cmd.run(&conn, format, &opts, family).await,
         Command::Route(cmd) => cmd.run(&conn, format, &opts, family).await,
+        Command::Addrlabel(cmd) => cmd.run(&conn, format, &opts).await,
         Command::Neighbor(cmd) => cmd.run(&conn, format, &opts, family).await,
+        Command::Ntable(cmd) => cmd.run(&conn, format, &opts, family).await,
         Command::Rule(cmd) => cmd.run(&conn, format, &opts, family).await,
         Command::Nexthop(cmd) => cmd.run(&conn, format, &opts).await,
         Command::Netns(cmd) => cmd.run(format, &opts).await,
         Command::Monitor(cmd) => cmd.run(format, &opts).await,
         Command::Tunnel(cmd) => cmd.run(&conn, format, &opts).await,
-        Command::Maddress(cmd) => cmd.run(format, &opts, family).await,
+        Command::Maddress(cmd) => cmd.run(&conn, format, &opts, family).await,
         Command::Vrf(cmd) => cmd.run(&conn, format, &opts).await,
         Command::Xfrm(cmd) => cmd.run(format, &opts).await,
         Command::Mptcp(cmd) => cmd.run(format, &opts).await,
+        Command::Ila(cmd) => cmd.run(&conn, format, &opts).await,
         Command::Sr(cmd) => cmd.run(&conn, format, &opts).await,
         Command::Macsec(cmd) => cmd.run(&conn, format, &opts).await,
     };
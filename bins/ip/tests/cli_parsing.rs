@@ -67,6 +67,7 @@ mod link_command {
             .stdout(predicate::str::contains("--up"))
             .stdout(predicate::str::contains("--down"))
             .stdout(predicate::str::contains("--mtu"))
+            .stdout(predicate::str::contains("--gso-max-size"))
             .stdout(predicate::str::contains("--netns"));
     }
 
@@ -85,6 +86,15 @@ mod link_command {
         ip_cmd().args(["link", "add", "--help"]).assert().success();
     }
 
+    #[test]
+    fn test_link_add_team_help() {
+        ip_cmd()
+            .args(["link", "add", "team", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("teamd"));
+    }
+
     #[test]
     fn test_link_del_requires_dev() {
         ip_cmd()
@@ -212,6 +222,33 @@ mod route_command {
     fn test_route_get_requires_destination() {
         ip_cmd().args(["route", "get"]).assert().failure();
     }
+
+    #[test]
+    fn test_route_get_iif_requires_from() {
+        ip_cmd()
+            .args(["route", "get", "192.0.2.1", "--iif", "eth0"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--from"));
+    }
+
+    #[test]
+    fn test_route_flush_ra_filters_conflict() {
+        ip_cmd()
+            .args(["route", "flush", "--ra", "--exclude-ra"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+
+    #[test]
+    fn test_route_flush_needs_proto_or_ra() {
+        ip_cmd()
+            .args(["route", "flush"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--proto"));
+    }
 }
 
 mod neighbor_command {
@@ -235,6 +272,68 @@ mod neighbor_command {
     fn test_n_alias() {
         ip_cmd().args(["n", "--help"]).assert().success();
     }
+
+    #[test]
+    fn test_neighbor_add_requires_lladdr_unless_proxy() {
+        ip_cmd()
+            .args(["neigh", "add", "10.0.0.1", "--dev", "lo"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--lladdr"));
+    }
+
+    #[test]
+    fn test_neighbor_flush_help() {
+        ip_cmd()
+            .args(["neigh", "flush", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--nud"));
+    }
+}
+
+mod addrlabel_command {
+    use super::*;
+
+    #[test]
+    fn test_addrlabel_add_help() {
+        ip_cmd()
+            .args(["addrlabel", "add", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--label"));
+    }
+
+    #[test]
+    fn test_addrlabel_add_requires_label() {
+        ip_cmd()
+            .args(["addrlabel", "add", "2001:db8::/32"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--label"));
+    }
+}
+
+mod ntable_command {
+    use super::*;
+
+    #[test]
+    fn test_ntable_change_help() {
+        ip_cmd()
+            .args(["ntable", "change", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--base-reachable"));
+    }
+
+    #[test]
+    fn test_ntable_change_requires_name() {
+        ip_cmd()
+            .args(["ntable", "change", "--ucast-probes", "3"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--name"));
+    }
 }
 
 mod rule_command {
@@ -253,6 +352,54 @@ mod rule_command {
     fn test_rule_alias_ru() {
         ip_cmd().args(["ru", "--help"]).assert().success();
     }
+
+    #[test]
+    fn test_rule_add_help() {
+        ip_cmd()
+            .args(["rule", "add", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--uidrange"))
+            .stdout(predicate::str::contains("--goto"))
+            .stdout(predicate::str::contains("--suppress-prefixlength"));
+    }
+
+    #[test]
+    fn test_rule_del_accepts_full_grammar() {
+        ip_cmd()
+            .args(["rule", "del", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--dport"))
+            .stdout(predicate::str::contains("--l3mdev"));
+    }
+
+    #[test]
+    fn test_rule_l3mdev_conflicts_with_table() {
+        ip_cmd()
+            .args(["rule", "add", "--l3mdev", "--table", "100"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+
+    #[test]
+    fn test_rule_add_rejects_goto_with_other_action() {
+        ip_cmd()
+            .args(["rule", "add", "--goto", "100", "--type", "blackhole"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--goto"));
+    }
+
+    #[test]
+    fn test_rule_add_rejects_unknown_table() {
+        ip_cmd()
+            .args(["rule", "add", "--table", "nosuch"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unknown table"));
+    }
 }
 
 mod netns_command {
@@ -294,6 +441,145 @@ mod monitor_command {
     fn test_monitor_alias_mon() {
         ip_cmd().args(["mon", "--help"]).assert().success();
     }
+
+    #[test]
+    fn test_monitor_help_shows_capture_and_file() {
+        ip_cmd()
+            .args(["monitor", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--capture"))
+            .stdout(predicate::str::contains("file"));
+    }
+
+    /// RTM_NEWLINK for ifindex 7, named "tst0".
+    fn newlink_msg() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // Header + ifinfomsg + IFLA_IFNAME.
+        bytes.extend_from_slice(&44u32.to_ne_bytes());
+        bytes.extend_from_slice(&16u16.to_ne_bytes());
+        bytes.extend_from_slice(&[0; 10]);
+        bytes.extend_from_slice(&[0, 0]);
+        bytes.extend_from_slice(&1u16.to_ne_bytes());
+        bytes.extend_from_slice(&7i32.to_ne_bytes());
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&9u16.to_ne_bytes());
+        bytes.extend_from_slice(&3u16.to_ne_bytes());
+        bytes.extend_from_slice(b"tst0\0\0\0\0");
+        bytes
+    }
+
+    /// RTM_NEWRULE: priority 300, lookup table 100.
+    fn newrule_msg() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        // Header + fib_rule_hdr + FRA_PRIORITY.
+        bytes.extend_from_slice(&36u32.to_ne_bytes());
+        bytes.extend_from_slice(&32u16.to_ne_bytes());
+        bytes.extend_from_slice(&[0; 10]);
+        bytes.extend_from_slice(&[2, 0, 0, 0, 100, 0, 0, 1]);
+        bytes.extend_from_slice(&0u32.to_ne_bytes());
+        bytes.extend_from_slice(&8u16.to_ne_bytes());
+        bytes.extend_from_slice(&6u16.to_ne_bytes());
+        bytes.extend_from_slice(&300u32.to_ne_bytes());
+        bytes
+    }
+
+    /// A capture holding one timestamp record (t = 100s) followed by `msg`.
+    fn write_capture(name: &str, msg: &[u8]) -> std::path::PathBuf {
+        let mut bytes = Vec::new();
+        // NLMSG_TSTAMP: header + secs + usecs.
+        bytes.extend_from_slice(&24u32.to_ne_bytes());
+        bytes.extend_from_slice(&15u16.to_ne_bytes());
+        bytes.extend_from_slice(&[0; 10]);
+        bytes.extend_from_slice(&100u32.to_ne_bytes());
+        bytes.extend_from_slice(&0u32.to_ne_bytes());
+        bytes.extend_from_slice(msg);
+
+        let path = std::env::temp_dir().join(format!("nlink-ip-{}-{}", std::process::id(), name));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_monitor_file_replays_capture() {
+        let path = write_capture("replay", &newlink_msg());
+        ip_cmd()
+            .args(["monitor", "-t", "link", "file"])
+            .arg(&path)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("[100.000] LINK: tst0 index 7"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_monitor_file_filters_objects() {
+        let path = write_capture("filter", &newlink_msg());
+        ip_cmd()
+            .args(["monitor", "address", "file"])
+            .arg(&path)
+            .assert()
+            .success()
+            .stdout(predicate::str::is_empty());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_monitor_file_replays_rule() {
+        let path = write_capture("rule", &newrule_msg());
+        ip_cmd()
+            .args(["monitor", "rule", "file"])
+            .arg(&path)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("RULE: 300: from all lookup 100"));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_monitor_file_missing() {
+        ip_cmd()
+            .args(["monitor", "file", "/nonexistent/capture"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("/nonexistent/capture"));
+    }
+}
+
+mod xfrm_command {
+    use super::*;
+
+    #[test]
+    fn test_xfrm_state_help_lists_mutations() {
+        ip_cmd()
+            .args(["xfrm", "state", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("add"))
+            .stdout(predicate::str::contains("update"))
+            .stdout(predicate::str::contains("del"));
+    }
+
+    #[test]
+    fn test_xfrm_state_add_rejects_unknown_algorithm() {
+        ip_cmd()
+            .args([
+                "xfrm", "state", "add", "--src", "10.0.0.1", "--dst", "10.0.0.2", "--spi", "0x100",
+                "--enc", "rot13", "0x00",
+            ])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("unknown enc algorithm"));
+    }
+
+    #[test]
+    fn test_xfrm_policy_add_requires_dir() {
+        ip_cmd()
+            .args(["xfrm", "policy", "add", "--src", "10.0.0.0/24"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("--dir"));
+    }
 }
 
 mod tunnel_command {
@@ -317,6 +603,27 @@ mod tunnel_command {
     fn test_tunnel_alias_tun() {
         ip_cmd().args(["tun", "--help"]).assert().success();
     }
+
+    #[test]
+    fn test_tunnel_add_key_conflicts_with_ikey() {
+        ip_cmd()
+            .args([
+                "tunnel",
+                "add",
+                "gre1",
+                "--mode",
+                "gre",
+                "--remote",
+                "192.0.2.1",
+                "--key",
+                "1",
+                "--ikey",
+                "2",
+            ])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
 }
 
 mod json_output {
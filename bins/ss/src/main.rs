@@ -13,7 +13,10 @@ use clap::Parser;
 use nlink::{
     netlink::{Connection, SockDiag},
     output::OutputFormat,
-    sockdiag::{InetFilter, Protocol, SocketFilter, SocketInfo, TcpState, UnixFilter},
+    sockdiag::{
+        DestroyTarget, FilterExpr, InetFilter, InetSocket, Protocol, SocketFilter, SocketInfo,
+        SocketState, TcpState, UnixFilter, UnixShow, query_inet_sharded,
+    },
 };
 
 #[derive(Parser)]
@@ -59,6 +62,14 @@ struct Cli {
     #[arg(short = '0', long)]
     packet: bool,
 
+    /// Display SMC sockets (SMC-R, SMC-D and TCP fallbacks).
+    #[arg(long)]
+    smc: bool,
+
+    /// List SMC-R and SMC-D link groups instead of sockets.
+    #[arg(long = "smc-linkgroups")]
+    smc_linkgroups: bool,
+
     /// Display IPv4 sockets only.
     #[arg(short = '4', long)]
     ipv4: bool,
@@ -128,13 +139,33 @@ struct Cli {
     summary: bool,
 
     /// Forcibly close matching sockets (requires CAP_NET_ADMIN).
-    #[arg(short = 'K', long)]
+    #[arg(short = 'K', long, group = "kill_mode")]
     kill: bool,
 
+    /// Forcibly close only matching TIME-WAIT sockets.
+    #[arg(long, group = "kill_mode")]
+    kill_timewait: bool,
+
+    /// Forcibly close only matching orphaned sockets (closed by their
+    /// owner, still in FIN-WAIT / CLOSING / LAST-ACK).
+    #[arg(long, group = "kill_mode")]
+    kill_orphaned: bool,
+
+    /// With a kill option, list the sockets that would be closed instead
+    /// of closing them.
+    #[arg(long, requires = "kill_mode")]
+    dry_run: bool,
+
+    /// Split inet dumps into N parallel dumps over local port ranges and
+    /// merge them; speeds up dumps of very large socket tables.
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    shards: usize,
+
     /// Filter expression (ss-compatible syntax).
     ///
     /// Examples: 'sport = :22', 'dst 192.168.0.0/16 and state established',
-    /// '( sport = :80 or sport = :443 ) and state listening'
+    /// '( sport = :80 or sport = :443 ) and state listening',
+    /// 'state established ( dport = :ssh )', 'exclude bucket'
     #[arg(trailing_var_arg = true)]
     filter_expr: Vec<String>,
 }
@@ -178,9 +209,26 @@ async fn main() -> anyhow::Result<()> {
         return run_summary(&conn, format).await;
     }
 
+    // Handle SMC link-group listing
+    if cli.smc_linkgroups {
+        return run_smc_linkgroups(format, &opts).await;
+    }
+
+    // Parse the ss filter expression once (if given). It rides on
+    // every inet query so the library compiles as much as possible
+    // into a kernel-side INET_DIAG_REQ_BYTECODE pre-filter (#163);
+    // the post-dump retain below stays as the cross-family filter
+    // (non-inet exclusion, issue #20) and correctness backstop.
+    let filter_expr = if cli.filter_expr.is_empty() {
+        None
+    } else {
+        let expr_str = cli.filter_expr.join(" ");
+        Some(FilterExpr::parse(&expr_str).map_err(|e| anyhow::anyhow!("{e}"))?)
+    };
+
     // Handle kill mode
-    if cli.kill {
-        return run_kill(&cli, &conn).await;
+    if cli.kill || cli.kill_timewait || cli.kill_orphaned {
+        return run_kill(&cli, &conn, filter_expr, format, &opts).await;
     }
 
     // Determine which socket types to query
@@ -191,7 +239,8 @@ async fn main() -> anyhow::Result<()> {
             && !cli.sctp
             && !cli.mptcp
             && !cli.netlink
-            && !cli.packet);
+            && !cli.packet
+            && !cli.smc);
     let query_udp = cli.udp;
     let query_unix = cli.unix;
     let query_raw = cli.raw;
@@ -199,27 +248,10 @@ async fn main() -> anyhow::Result<()> {
     let query_mptcp = cli.mptcp;
     let query_netlink = cli.netlink;
     let query_packet = cli.packet;
+    let query_smc = cli.smc;
 
     // Determine state filter
-    let states = if cli.all {
-        TcpState::all_mask()
-    } else if cli.listening {
-        TcpState::Listen.mask()
-    } else {
-        TcpState::connected_mask()
-    };
-
-    // Parse the ss filter expression once (if given). It rides on
-    // every inet query so the library compiles as much as possible
-    // into a kernel-side INET_DIAG_REQ_BYTECODE pre-filter (#163);
-    // the post-dump retain below stays as the cross-family filter
-    // (non-inet exclusion, issue #20) and correctness backstop.
-    let filter_expr = if cli.filter_expr.is_empty() {
-        None
-    } else {
-        let expr_str = cli.filter_expr.join(" ");
-        Some(nlink::sockdiag::FilterExpr::parse(&expr_str).map_err(|e| anyhow::anyhow!("{e}"))?)
-    };
+    let states = default_states(&cli, filter_expr.as_ref());
 
     // Collect all results
     let mut all_results = Vec::new();
@@ -234,11 +266,7 @@ async fn main() -> anyhow::Result<()> {
         apply_inet_filters(&cli, &mut filter);
         filter.expr = filter_expr.clone();
 
-        let sockets = conn
-            .query(&SocketFilter {
-                kind: nlink::sockdiag::filter::FilterKind::Inet(filter),
-            })
-            .await?;
+        let sockets = query_inet(&conn, &cli, filter).await?;
         all_results.extend(sockets);
     }
 
@@ -261,11 +289,7 @@ async fn main() -> anyhow::Result<()> {
         apply_inet_filters(&cli, &mut filter);
         filter.expr = filter_expr.clone();
 
-        let sockets = conn
-            .query(&SocketFilter {
-                kind: nlink::sockdiag::filter::FilterKind::Inet(filter),
-            })
-            .await?;
+        let sockets = query_inet(&conn, &cli, filter).await?;
         all_results.extend(sockets);
     }
 
@@ -279,11 +303,7 @@ async fn main() -> anyhow::Result<()> {
         apply_inet_filters(&cli, &mut filter);
         filter.expr = filter_expr.clone();
 
-        let sockets = conn
-            .query(&SocketFilter {
-                kind: nlink::sockdiag::filter::FilterKind::Inet(filter),
-            })
-            .await?;
+        let sockets = query_inet(&conn, &cli, filter).await?;
         all_results.extend(sockets);
     }
 
@@ -297,11 +317,7 @@ async fn main() -> anyhow::Result<()> {
         apply_inet_filters(&cli, &mut filter);
         filter.expr = filter_expr.clone();
 
-        let sockets = conn
-            .query(&SocketFilter {
-                kind: nlink::sockdiag::filter::FilterKind::Inet(filter),
-            })
-            .await?;
+        let sockets = query_inet(&conn, &cli, filter).await?;
         all_results.extend(sockets);
     }
 
@@ -315,26 +331,24 @@ async fn main() -> anyhow::Result<()> {
         apply_inet_filters(&cli, &mut filter);
         filter.expr = filter_expr.clone();
 
-        let sockets = conn
-            .query(&SocketFilter {
-                kind: nlink::sockdiag::filter::FilterKind::Inet(filter),
-            })
-            .await?;
+        let sockets = query_inet(&conn, &cli, filter).await?;
         all_results.extend(sockets);
     }
 
     // Query Unix sockets
     if query_unix {
-        let filter = UnixFilter {
-            states: if cli.all {
-                TcpState::all_mask()
-            } else if cli.listening {
-                TcpState::Listen.mask()
-            } else {
-                TcpState::connected_mask()
-            },
+        let mut filter = UnixFilter {
+            states: default_states(&cli, filter_expr.as_ref()),
             ..Default::default()
         };
+        // -e shows the owner uid and -m the skmem block; both are
+        // opt-in UNIX_DIAG_SHOW bits.
+        if cli.extended {
+            filter.show |= UnixShow::Uid.mask();
+        }
+        if cli.memory {
+            filter.show |= UnixShow::MemInfo.mask();
+        }
 
         let sockets = conn
             .query(&SocketFilter {
@@ -361,6 +375,18 @@ async fn main() -> anyhow::Result<()> {
         all_results.extend(packets.into_iter().map(SocketInfo::Packet));
     }
 
+    if query_smc {
+        // The smc_diag dump has no state filter; apply -a/-l here.
+        let smc = conn.query_smc_sockets().await?;
+        all_results.extend(
+            smc.into_iter()
+                .filter(|s| {
+                    cli.all || cli.listening == matches!(s.socket_state(), SocketState::Listen)
+                })
+                .map(SocketInfo::Smc),
+        );
+    }
+
     // Apply address/port filters client-side. --sport/--dport
     // additionally compile to a kernel-side bytecode pre-filter in
     // the dump path; --src/--dst stay client-side here (#163 note:
@@ -392,27 +418,87 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Run kill mode - destroy matching TCP sockets.
-async fn run_kill(cli: &Cli, conn: &Connection<SockDiag>) -> anyhow::Result<()> {
-    use nlink::sockdiag::{InetFilter, TcpState};
-
-    // Build filter from CLI arguments (TCP only - only TCP supports SOCK_DESTROY)
-    let states = if cli.all {
+/// The query's base state mask. As in ss(8), a `state` / `exclude`
+/// filter replaces the connected-only default rather than narrowing
+/// it, so `ss state listening` works without `-l`.
+fn default_states(cli: &Cli, expr: Option<&FilterExpr>) -> u32 {
+    if cli.all || expr.is_some_and(FilterExpr::has_state) {
         TcpState::all_mask()
     } else if cli.listening {
         TcpState::Listen.mask()
     } else {
         TcpState::connected_mask()
+    }
+}
+
+/// Run an inet query, split into `--shards` parallel dumps when asked.
+async fn query_inet(
+    conn: &Connection<SockDiag>,
+    cli: &Cli,
+    filter: InetFilter,
+) -> anyhow::Result<Vec<SocketInfo>> {
+    if cli.shards > 1 {
+        let sockets = query_inet_sharded(&filter, cli.shards).await?;
+        return Ok(sockets
+            .into_iter()
+            .map(|s| SocketInfo::Inet(Box::new(s)))
+            .collect());
+    }
+    Ok(conn
+        .query(&SocketFilter {
+            kind: nlink::sockdiag::filter::FilterKind::Inet(filter),
+        })
+        .await?)
+}
+
+/// Run kill mode - destroy matching TCP sockets, or list them with
+/// `--dry-run`.
+async fn run_kill(
+    cli: &Cli,
+    conn: &Connection<SockDiag>,
+    filter_expr: Option<FilterExpr>,
+    format: OutputFormat,
+    opts: &output::DisplayOptions,
+) -> anyhow::Result<()> {
+    let target = if cli.kill_timewait {
+        DestroyTarget::TimeWait
+    } else if cli.kill_orphaned {
+        DestroyTarget::Orphaned
+    } else {
+        DestroyTarget::Matching
     };
 
+    // Build filter from CLI arguments (TCP only - only TCP supports SOCK_DESTROY)
     let mut filter = InetFilter {
         protocol: Protocol::Tcp,
-        states,
+        states: default_states(cli, filter_expr.as_ref()),
         ..Default::default()
     };
     apply_inet_filters(cli, &mut filter);
+    filter.expr = filter_expr;
 
-    let result = conn.destroy_matching(&filter).await?;
+    let mut sockets = conn.destroy_candidates(&filter, target).await?;
+
+    // --src/--dst are client-side only; apply them before anything is
+    // destroyed.
+    let inet_match = InetMatch::from_cli(cli)?;
+    if inet_match.is_active() {
+        sockets.retain(|sock| inet_match.matches_inet(sock));
+    }
+
+    if cli.dry_run {
+        let results: Vec<_> = sockets
+            .into_iter()
+            .map(|s| SocketInfo::Inet(Box::new(s)))
+            .collect();
+        match format {
+            OutputFormat::Json => output::print_json(&results, opts)?,
+            OutputFormat::Text => output::print_text(&results, opts)?,
+        }
+        return Ok(());
+    }
+
+    let result = conn.destroy_sockets(&sockets).await;
 
     if result.destroyed > 0 {
         eprintln!("Destroyed {} socket(s)", result.destroyed);
@@ -444,6 +530,48 @@ async fn run_summary(conn: &Connection<SockDiag>, format: OutputFormat) -> anyho
     Ok(())
 }
 
+/// Run SMC link-group mode - list SMC-R link groups (with their links)
+/// and SMC-D link groups.
+async fn run_smc_linkgroups(
+    format: OutputFormat,
+    opts: &output::DisplayOptions,
+) -> anyhow::Result<()> {
+    use nlink::netlink::genl::smc::Smc;
+    use tokio_stream::StreamExt;
+
+    let conn = Connection::<Smc>::new_async().await?;
+
+    let mut smcr = Vec::new();
+    let mut stream = conn.dump_smcr_links().await?;
+    while let Some(frame) = stream.next().await {
+        let frame = frame?;
+        if let Some(lgr) = frame.link_group {
+            smcr.push((lgr, Vec::new()));
+        }
+        if let Some(link) = frame.link
+            && let Some((_, links)) = smcr.last_mut()
+        {
+            links.push(link);
+        }
+    }
+    drop(stream);
+
+    let mut smcd = Vec::new();
+    let mut stream = conn.dump_smcd_link_groups().await?;
+    while let Some(frame) = stream.next().await {
+        if let Some(lgr) = frame?.link_group {
+            smcd.push(lgr);
+        }
+    }
+
+    match format {
+        OutputFormat::Json => output::print_smc_linkgroups_json(&smcr, &smcd)?,
+        OutputFormat::Text => output::print_smc_linkgroups_text(&smcr, &smcd, opts)?,
+    }
+
+    Ok(())
+}
+
 fn apply_inet_filters(cli: &Cli, filter: &mut InetFilter) {
     // Apply family filter
     if cli.ipv4 {
@@ -511,9 +639,10 @@ impl InetMatch {
     fn matches(&self, sock: &SocketInfo) -> bool {
         // A port/address filter only makes sense for inet sockets;
         // exclude non-inet sockets when such a filter is active.
-        let Some(inet) = sock.as_inet() else {
-            return false;
-        };
+        sock.as_inet().is_some_and(|inet| self.matches_inet(inet))
+    }
+
+    fn matches_inet(&self, inet: &InetSocket) -> bool {
         if let Some(p) = self.sport
             && inet.local.port() != p
         {
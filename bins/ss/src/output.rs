@@ -6,8 +6,9 @@ use std::{
 };
 
 use nlink::{
-    output::formatting::format_rate_bps,
-    sockdiag::{InetSocket, SocketInfo, UnixSocket},
+    netlink::genl::smc::{SmcdLinkGroup, SmcrLink, SmcrLinkGroup},
+    sockdiag::{InetSocket, NetlinkSocket, SmcMode, SmcSocket, SocketInfo, UnixPeers, UnixSocket},
+    util::format_rate,
 };
 
 /// Display options for socket output.
@@ -52,22 +53,20 @@ pub fn print_json(sockets: &[SocketInfo], opts: &DisplayOptions) -> io::Result<(
         crate::procmap::ProcMap::new()
     };
 
+    let peers = unix_peers(sockets);
+
     let json_sockets: Vec<_> = sockets
         .iter()
         .map(|s| match s {
             SocketInfo::Inet(inet) => inet_to_json(inet, opts, &procs),
-            SocketInfo::Unix(unix) => unix_to_json(unix, opts, &procs),
-            SocketInfo::Netlink(nl) => serde_json::json!({
-                "netid": "nl",
-                "protocol": nl.protocol_name(),
-                "portid": nl.portid,
-                "inode": nl.inode,
-            }),
+            SocketInfo::Unix(unix) => unix_to_json(unix, opts, &procs, &peers),
+            SocketInfo::Netlink(nl) => netlink_to_json(nl, opts, &procs),
             SocketInfo::Packet(pkt) => serde_json::json!({
                 "netid": pkt.netid(),
                 "protocol": pkt.protocol_name(),
                 "inode": pkt.inode,
             }),
+            SocketInfo::Smc(smc) => smc_to_json(smc, opts, &procs),
         })
         .collect();
 
@@ -79,6 +78,14 @@ pub fn print_json(sockets: &[SocketInfo], opts: &DisplayOptions) -> io::Result<(
 /// Render the processes holding a socket open as a JSON array, or
 /// `None` when none are known. Mirrors `procmap::format_users` but in
 /// structured form for `-j -p`.
+/// Index the unix sockets of a result set so each can name its peer.
+fn unix_peers(sockets: &[SocketInfo]) -> UnixPeers<'_> {
+    UnixPeers::new(sockets.iter().filter_map(|s| match s {
+        SocketInfo::Unix(unix) => Some(unix),
+        _ => None,
+    }))
+}
+
 fn procs_to_json(map: &crate::procmap::ProcMap, inode: u32) -> Option<serde_json::Value> {
     let procs = map.resolve(inode);
     if procs.is_empty() {
@@ -126,8 +133,9 @@ fn inet_to_json(
         json["timer"] = serde_json::Value::String(timer);
     }
 
-    // -e: extended fields (bound interface, firewall mark).
+    // -e: extended fields (socket cookie, bound interface, firewall mark).
     if opts.extended {
+        json["cookie"] = serde_json::Value::Number(sock.cookie.into());
         if sock.interface > 0 {
             json["interface"] = serde_json::Value::Number(sock.interface.into());
         }
@@ -152,6 +160,13 @@ fn inet_to_json(
             "pacing_rate": info.pacing_rate,
             "delivery_rate": info.delivery_rate,
             "min_rtt": info.min_rtt,
+            "ato": info.ato,
+            "total_retrans": info.total_retrans,
+            "busy_time": info.busy_time,
+            "rwnd_limited": info.rwnd_limited,
+            "sndbuf_limited": info.sndbuf_limited,
+            "snd_wnd": info.snd_wnd,
+            "rcv_wnd": info.rcv_wnd,
         });
         if let Some(ref cong) = sock.congestion {
             json["congestion"] = serde_json::Value::String(cong.clone());
@@ -189,6 +204,7 @@ fn unix_to_json(
     sock: &UnixSocket,
     opts: &DisplayOptions,
     procs: &crate::procmap::ProcMap,
+    peers: &UnixPeers<'_>,
 ) -> serde_json::Value {
     let mut json = serde_json::json!({
         "netid": sock.netid(),
@@ -211,12 +227,124 @@ fn unix_to_json(
     if let Some(peer) = sock.peer_inode {
         json["peer_inode"] = serde_json::Value::Number(peer.into());
     }
+    if let Some(peer) = peers.peer(sock)
+        && !peer.name().is_empty()
+    {
+        json["peer_path"] = serde_json::Value::String(peer.name());
+    }
+
+    if let Some((dev, ino)) = sock.vfs() {
+        json["vfs"] = serde_json::json!({ "dev": dev, "inode": ino });
+    }
 
     if let Some(uid) = sock.uid {
         json["uid"] = serde_json::Value::Number(uid.into());
     }
 
-    // -p: owning processes.
+    // -p: owning processes, and those holding the other end.
+    if opts.processes {
+        if let Some(p) = procs_to_json(procs, sock.inode) {
+            json["process"] = p;
+        }
+        if let Some(p) = sock.peer_inode.and_then(|ino| procs_to_json(procs, ino)) {
+            json["peer_process"] = p;
+        }
+    }
+
+    json
+}
+
+fn netlink_to_json(
+    sock: &NetlinkSocket,
+    opts: &DisplayOptions,
+    procs: &crate::procmap::ProcMap,
+) -> serde_json::Value {
+    let mut json = serde_json::json!({
+        "netid": "nl",
+        "protocol": sock.protocol_name(),
+        "portid": sock.portid,
+        "inode": sock.inode,
+        "groups": sock.memberships,
+    });
+
+    if let Some(recv_q) = sock.recv_q {
+        json["recv_q"] = serde_json::Value::Number(recv_q.into());
+    }
+    if let Some(send_q) = sock.send_q {
+        json["send_q"] = serde_json::Value::Number(send_q.into());
+    }
+    if let Some(drops) = sock.drops() {
+        json["drops"] = serde_json::Value::Number(drops.into());
+    }
+    if sock.dst_portid != 0 || sock.dst_group != 0 {
+        json["dst_portid"] = serde_json::Value::Number(sock.dst_portid.into());
+        json["dst_group"] = serde_json::Value::Number(sock.dst_group.into());
+    }
+    if let Some(flags) = sock.flags {
+        json["flags"] = serde_json::Value::Number(flags.into());
+    }
+
+    if opts.processes
+        && let Some(p) = procs_to_json(procs, sock.inode)
+    {
+        json["process"] = p;
+    }
+
+    json
+}
+
+fn smc_to_json(
+    sock: &SmcSocket,
+    opts: &DisplayOptions,
+    procs: &crate::procmap::ProcMap,
+) -> serde_json::Value {
+    let mut json = serde_json::json!({
+        "netid": sock.netid(),
+        "state": sock.state_name(),
+        "mode": sock.mode.name(),
+        "local": {
+            "address": sock.local.ip().to_string(),
+            "port": sock.local.port(),
+        },
+        "remote": {
+            "address": sock.remote.ip().to_string(),
+            "port": sock.remote.port(),
+        },
+        "uid": sock.uid,
+        "inode": sock.inode,
+    });
+
+    if let Some(fb) = &sock.fallback {
+        json["fallback"] = serde_json::json!({
+            "reason": fb.reason,
+            "peer_diagnosis": fb.peer_diagnosis,
+        });
+    }
+
+    if opts.extended {
+        if sock.interface > 0 {
+            json["interface"] = serde_json::Value::Number(sock.interface.into());
+        }
+        if let Some(link) = &sock.smcr_link {
+            json["smcr_link"] = serde_json::json!({
+                "link_id": link.link_id,
+                "ib_device": link.ib_device,
+                "ib_port": link.ib_port,
+                "gid": link.gid,
+                "peer_gid": link.peer_gid,
+            });
+        }
+        if let Some(dmb) = &sock.smcd_dmb {
+            json["smcd_dmb"] = serde_json::json!({
+                "link_id": dmb.link_id,
+                "gid": dmb.gid,
+                "peer_gid": dmb.peer_gid,
+                "token": dmb.token,
+                "peer_token": dmb.peer_token,
+            });
+        }
+    }
+
     if opts.processes
         && let Some(p) = procs_to_json(procs, sock.inode)
     {
@@ -238,6 +366,8 @@ pub fn print_text(sockets: &[SocketInfo], opts: &DisplayOptions) -> io::Result<(
         crate::procmap::ProcMap::new()
     };
 
+    let peers = unix_peers(sockets);
+
     if !opts.no_header {
         writeln!(
             handle,
@@ -249,19 +379,8 @@ pub fn print_text(sockets: &[SocketInfo], opts: &DisplayOptions) -> io::Result<(
     for sock in sockets {
         match sock {
             SocketInfo::Inet(inet) => print_inet_socket(&mut handle, inet, opts, &procs)?,
-            SocketInfo::Unix(unix) => print_unix_socket(&mut handle, unix, opts, &procs)?,
-            SocketInfo::Netlink(nl) => {
-                writeln!(
-                    handle,
-                    "{:<8} {:<12} {:>6} {:>6} {:>25} {:>25}",
-                    "nl",
-                    "UNCONN",
-                    nl.recv_q.unwrap_or(0),
-                    nl.send_q.unwrap_or(0),
-                    format!("{}:{}", nl.protocol_name(), nl.portid),
-                    "*"
-                )?;
-            }
+            SocketInfo::Unix(unix) => print_unix_socket(&mut handle, unix, opts, &procs, &peers)?,
+            SocketInfo::Netlink(nl) => print_netlink_socket(&mut handle, nl, opts, &procs)?,
             SocketInfo::Packet(pkt) => {
                 writeln!(
                     handle,
@@ -274,6 +393,7 @@ pub fn print_text(sockets: &[SocketInfo], opts: &DisplayOptions) -> io::Result<(
                     "*"
                 )?;
             }
+            SocketInfo::Smc(smc) => print_smc_socket(&mut handle, smc, opts, &procs)?,
         }
     }
 
@@ -286,16 +406,24 @@ fn print_inet_socket(
     opts: &DisplayOptions,
     procs: &crate::procmap::ProcMap,
 ) -> io::Result<()> {
-    let local = format_addr(&sock.local, opts.numeric, opts.resolve);
+    let mut local = format_addr(&sock.local, opts.numeric, opts.resolve);
     let remote = format_addr(&sock.remote, opts.numeric, opts.resolve);
 
+    // A socket bound to a device shows it as `addr%dev:port`, as ss(8) does.
+    if sock.interface > 0
+        && let Some(colon) = local.rfind(':')
+    {
+        let dev = nlink::util::get_ifname_or_index(sock.interface);
+        local.insert_str(colon, &format!("%{dev}"));
+    }
+
     let users = if opts.processes {
         crate::procmap::format_users(procs, sock.inode)
     } else {
         String::new()
     };
 
-    let main = format!(
+    let mut main = format!(
         "{:<8} {:<12} {:>6} {:>6} {:>25} {:>25}{}",
         sock.netid(),
         sock.state.name(),
@@ -306,42 +434,37 @@ fn print_inet_socket(
         users
     );
 
-    // Detail segments that normally wrap onto `\t`-prefixed
-    // continuation lines. With -O/--oneline they are joined onto the
-    // socket's row instead (see `emit_row`).
-    let mut details: Vec<String> = Vec::new();
-
-    // Timer info (-o): only printed when a timer is active.
+    // Timer (-o) and extended info (-e) stay on the socket's row.
     if opts.options
         && let Some(timer) = sock.timer.describe()
     {
-        details.push(timer);
+        main.push(' ');
+        main.push_str(&timer);
     }
-
-    // Extended info
     if opts.extended {
-        let mut s = format!("uid:{} ino:{}", sock.uid, sock.inode);
-        if sock.interface > 0 {
-            s.push_str(&format!(" if:{}", sock.interface));
-        }
-        if let Some(mark) = sock.mark {
-            s.push_str(&format!(" fwmark:0x{:x}", mark));
-        }
-        details.push(s);
+        main.push(' ');
+        main.push_str(&sock_details(
+            Some(sock.uid),
+            sock.inode,
+            sock.cookie,
+            sock.mark,
+        ));
     }
 
-    // Memory info
+    // Memory (-m) and TCP info (-i) share one continuation line; with
+    // -O/--oneline it is joined onto the row instead (see `emit_row`).
+    let mut parts = Vec::new();
+
     if opts.memory
         && let Some(ref mem) = sock.mem_info
     {
-        details.push(mem.format_skmem());
+        parts.push(mem.format_skmem());
     }
 
     // TCP info
     if opts.info
         && let Some(ref info) = sock.tcp_info
     {
-        let mut parts = Vec::new();
 
         if let Some(ref cong) = sock.congestion {
             // `cong` already holds the algorithm name (cubic, bbr, …);
@@ -354,6 +477,9 @@ fn print_inet_socket(
         if info.rto > 0 {
             parts.push(format!("rto:{}", info.rto as f64 / 1000.0));
         }
+        if info.ato > 0 {
+            parts.push(format!("ato:{}", info.ato as f64 / 1000.0));
+        }
         if info.rtt > 0 {
             parts.push(format!(
                 "rtt:{:.3}/{:.3}",
@@ -394,18 +520,43 @@ fn print_inet_socket(
             parts.push(format!("lost:{}", info.lost));
         }
 
-        if info.pacing_rate > 0 {
+        // An unpaced socket reports `pacing_rate` as u64::MAX; ss(8)
+        // omits it, and `* 8` would overflow.
+        if info.pacing_rate > 0 && info.pacing_rate != u64::MAX {
             parts.push(format!(
                 "pacing_rate:{}",
-                format_rate_bps(info.pacing_rate * 8)
+                format_rate(info.pacing_rate.saturating_mul(8))
             ));
         }
         if info.delivery_rate > 0 {
             parts.push(format!(
                 "delivery_rate:{}",
-                format_rate_bps(info.delivery_rate * 8)
+                format_rate(info.delivery_rate.saturating_mul(8))
             ));
         }
+        if info.busy_time > 0 {
+            parts.push(format!("busy:{}ms", info.busy_duration().as_millis()));
+        }
+        for (name, time, ratio) in [
+            (
+                "rwnd_limited",
+                info.rwnd_limited_duration(),
+                info.rwnd_limited_ratio(),
+            ),
+            (
+                "sndbuf_limited",
+                info.sndbuf_limited_duration(),
+                info.sndbuf_limited_ratio(),
+            ),
+        ] {
+            if let Some(ratio) = ratio.filter(|_| !time.is_zero()) {
+                parts.push(format!(
+                    "{name}:{}ms({:.1}%)",
+                    time.as_millis(),
+                    ratio * 100.0
+                ));
+            }
+        }
 
         if info.rcv_space > 0 {
             parts.push(format!("rcv_space:{}", info.rcv_space));
@@ -413,13 +564,37 @@ fn print_inet_socket(
         if info.rcv_ssthresh > 0 {
             parts.push(format!("rcv_ssthresh:{}", info.rcv_ssthresh));
         }
-
-        details.push(parts.join(" "));
+        if info.snd_wnd > 0 {
+            parts.push(format!("snd_wnd:{}", info.snd_wnd));
+        }
+        if info.rcv_wnd > 0 {
+            parts.push(format!("rcv_wnd:{}", info.rcv_wnd));
+        }
     }
 
+    let details: Vec<String> = if parts.is_empty() {
+        Vec::new()
+    } else {
+        vec![parts.join(" ")]
+    };
+
     emit_row(handle, &main, &details, opts.oneline)
 }
 
+/// `-e` details in ss(8)'s order: `uid:` (omitted for root), `ino:`, the
+/// socket cookie as `sk:`, and `fwmark:` when one is set.
+fn sock_details(uid: Option<u32>, inode: u32, cookie: u64, mark: Option<u32>) -> String {
+    let mut s = String::new();
+    if let Some(uid) = uid.filter(|&u| u != 0) {
+        s.push_str(&format!("uid:{uid} "));
+    }
+    s.push_str(&format!("ino:{inode} sk:{cookie:x}"));
+    if let Some(mark) = mark.filter(|&m| m != 0) {
+        s.push_str(&format!(" fwmark:0x{mark:x}"));
+    }
+    s
+}
+
 /// Emit a socket row plus its detail segments. In the default layout
 /// each detail wraps onto its own `\t`-prefixed continuation line; with
 /// -O/--oneline they are space-joined onto the row so each socket
@@ -450,19 +625,127 @@ fn print_unix_socket(
     sock: &UnixSocket,
     opts: &DisplayOptions,
     procs: &crate::procmap::ProcMap,
+    peers: &UnixPeers<'_>,
 ) -> io::Result<()> {
-    let path = sock.name();
-    let path_display = if path.is_empty() {
-        "*".to_string()
+    // Like ss(8): "<name> <inode>" on both sides, with the peer's name
+    // taken from its own entry when it is part of the dump.
+    let local = unix_endpoint(&sock.name(), Some(sock.inode));
+    let peer_name = peers.peer(sock).map(|p| p.name()).unwrap_or_default();
+    let peer = unix_endpoint(&peer_name, sock.peer_inode);
+
+    let users = if opts.processes {
+        let mut users = crate::procmap::format_users(procs, sock.inode);
+        users.push_str(&crate::procmap::format_peer_users(procs, sock.peer_inode));
+        users
+    } else {
+        String::new()
+    };
+
+    let mut main = format!(
+        "{:<8} {:<12} {:>6} {:>6} {:>25} {:>25}{}",
+        sock.netid(),
+        sock.state.name(),
+        sock.recv_q.unwrap_or(0),
+        sock.send_q.unwrap_or(0),
+        local,
+        peer,
+        users
+    );
+
+    if opts.extended {
+        main.push(' ');
+        main.push_str(&sock_details(sock.uid, sock.inode, sock.cookie, None));
+    }
+
+    let mut details: Vec<String> = Vec::new();
+    if opts.memory
+        && let Some(ref mem) = sock.mem_info
+    {
+        details.push(mem.format_skmem());
+    }
+
+    emit_row(handle, &main, &details, opts.oneline)
+}
+
+fn print_netlink_socket(
+    handle: &mut impl Write,
+    sock: &NetlinkSocket,
+    opts: &DisplayOptions,
+    procs: &crate::procmap::ProcMap,
+) -> io::Result<()> {
+    let users = if opts.processes {
+        crate::procmap::format_users(procs, sock.inode)
     } else {
-        path
+        String::new()
     };
 
-    let peer = if let Some(peer_ino) = sock.peer_inode {
-        format!("peer:{}", peer_ino)
+    let mut main = format!(
+        "{:<8} {:<12} {:>6} {:>6} {:>25} {:>25}{}",
+        "nl",
+        "UNCONN",
+        sock.recv_q.unwrap_or(0),
+        sock.send_q.unwrap_or(0),
+        netlink_local(sock),
+        netlink_peer(sock),
+        users
+    );
+
+    if opts.extended {
+        main.push(' ');
+        main.push_str(&sock_details(None, sock.inode, sock.cookie, None));
+        if !sock.memberships.is_empty() {
+            let groups: Vec<_> = sock.memberships.iter().map(u32::to_string).collect();
+            main.push_str(&format!(" groups:{}", groups.join(",")));
+        }
+    }
+
+    let mut details: Vec<String> = Vec::new();
+    if opts.memory
+        && let Some(ref mem) = sock.mem_info
+    {
+        details.push(mem.format_skmem());
+    }
+
+    emit_row(handle, &main, &details, opts.oneline)
+}
+
+/// `<protocol>:<portid>`, with the kernel's own socket (port 0) shown as
+/// `kernel` like ss(8).
+fn netlink_local(sock: &NetlinkSocket) -> String {
+    if sock.is_kernel() {
+        format!("{}:kernel", sock.protocol_name())
     } else {
+        format!("{}:{}", sock.protocol_name(), sock.portid)
+    }
+}
+
+/// `<group>:<portid>` of a connected socket's destination, else `*`.
+fn netlink_peer(sock: &NetlinkSocket) -> String {
+    if sock.dst_portid == 0 && sock.dst_group == 0 {
         "*".to_string()
-    };
+    } else {
+        format!("{}:{}", sock.dst_group, sock.dst_portid)
+    }
+}
+
+/// Format one end of a unix socket: its name (`*` if unnamed) and inode
+/// (`*` if unknown).
+fn unix_endpoint(name: &str, inode: Option<u32>) -> String {
+    let name = if name.is_empty() { "*" } else { name };
+    match inode {
+        Some(ino) => format!("{} {}", name, ino),
+        None => format!("{} *", name),
+    }
+}
+
+fn print_smc_socket(
+    handle: &mut impl Write,
+    sock: &SmcSocket,
+    opts: &DisplayOptions,
+    procs: &crate::procmap::ProcMap,
+) -> io::Result<()> {
+    let local = format_addr(&sock.local, opts.numeric, opts.resolve);
+    let remote = format_addr(&sock.remote, opts.numeric, opts.resolve);
 
     let users = if opts.processes {
         crate::procmap::format_users(procs, sock.inode)
@@ -473,19 +756,44 @@ fn print_unix_socket(
     let main = format!(
         "{:<8} {:<12} {:>6} {:>6} {:>25} {:>25}{}",
         sock.netid(),
-        sock.state.name(),
-        sock.recv_q.unwrap_or(0),
-        sock.send_q.unwrap_or(0),
-        path_display,
-        peer,
+        sock.state_name(),
+        0,
+        0,
+        local,
+        remote,
         users
     );
 
+    // The transport mode is the point of listing SMC sockets, so it is
+    // always shown; a fallback carries its SMC_CLC_DECL_* reason.
     let mut details: Vec<String> = Vec::new();
+    let mut mode = format!("mode:{}", sock.mode.name());
+    if let Some(fb) = &sock.fallback {
+        mode.push_str(&format!(" fallback:0x{:08x}", fb.reason));
+        if fb.peer_diagnosis != 0 {
+            mode.push_str(&format!(" peer_diag:0x{:08x}", fb.peer_diagnosis));
+        }
+    } else if let SmcMode::Unknown(m) = sock.mode {
+        mode.push_str(&format!("({m})"));
+    }
+    details.push(mode);
+
     if opts.extended {
-        let mut s = format!("ino:{}", sock.inode);
-        if let Some(uid) = sock.uid {
-            s.push_str(&format!(" uid:{}", uid));
+        let mut s = format!("uid:{} ino:{}", sock.uid, sock.inode);
+        if sock.interface > 0 {
+            s.push_str(&format!(" if:{}", sock.interface));
+        }
+        if let Some(link) = &sock.smcr_link {
+            s.push_str(&format!(
+                " ibdev:{} ibport:{} gid:{} peer_gid:{}",
+                link.ib_device, link.ib_port, link.gid, link.peer_gid
+            ));
+        }
+        if let Some(dmb) = &sock.smcd_dmb {
+            s.push_str(&format!(
+                " gid:0x{:x} peer_gid:0x{:x} token:0x{:x}",
+                dmb.gid, dmb.peer_gid, dmb.token
+            ));
         }
         details.push(s);
     }
@@ -493,6 +801,130 @@ fn print_unix_socket(
     emit_row(handle, &main, &details, opts.oneline)
 }
 
+/// Print SMC link groups in text format, one group per row with its
+/// SMC-R links on continuation lines.
+pub fn print_smc_linkgroups_text(
+    smcr: &[(SmcrLinkGroup, Vec<SmcrLink>)],
+    smcd: &[SmcdLinkGroup],
+    opts: &DisplayOptions,
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+
+    if !opts.no_header {
+        writeln!(
+            handle,
+            "{:<5} {:<9} {:<5} {:<7} {:>6} {:>5} {:<16}",
+            "Type", "LG-ID", "Role", "Kind", "#Conns", "VLAN", "PNET-ID"
+        )?;
+    }
+
+    for (lgr, links) in smcr {
+        let main = format!(
+            "{:<5} {:<9} {:<5} {:<7} {:>6} {:>5} {:<16}",
+            "SMCR",
+            format!("{:08x}", lgr.id),
+            lgr.role_name(),
+            lgr.type_name(),
+            lgr.conns,
+            lgr.vlan_id,
+            lgr.pnetid.as_deref().unwrap_or("-")
+        );
+        let details: Vec<String> = links
+            .iter()
+            .map(|l| {
+                format!(
+                    "link:{} {} ibdev:{} ibport:{} conns:{} gid:{} peer_gid:{}",
+                    l.id,
+                    l.state_name(),
+                    l.ib_device,
+                    l.ib_port,
+                    l.conns,
+                    l.gid,
+                    l.peer_gid
+                )
+            })
+            .collect();
+        emit_row(&mut handle, &main, &details, opts.oneline)?;
+    }
+
+    for lgr in smcd {
+        let main = format!(
+            "{:<5} {:<9} {:<5} {:<7} {:>6} {:>5} {:<16}",
+            "SMCD",
+            format!("{:08x}", lgr.id),
+            "-",
+            "-",
+            lgr.conns,
+            lgr.vlan_id,
+            lgr.pnetid.as_deref().unwrap_or("-")
+        );
+        let details = vec![format!(
+            "chid:0x{:04x} gid:0x{:x} peer_gid:0x{:x}",
+            lgr.chid, lgr.gid, lgr.peer_gid
+        )];
+        emit_row(&mut handle, &main, &details, opts.oneline)?;
+    }
+
+    Ok(())
+}
+
+/// Print SMC link groups in JSON format.
+pub fn print_smc_linkgroups_json(
+    smcr: &[(SmcrLinkGroup, Vec<SmcrLink>)],
+    smcd: &[SmcdLinkGroup],
+) -> io::Result<()> {
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+
+    let mut groups: Vec<serde_json::Value> = smcr
+        .iter()
+        .map(|(lgr, links)| {
+            let links: Vec<_> = links
+                .iter()
+                .map(|l| {
+                    serde_json::json!({
+                        "id": l.id,
+                        "state": l.state_name(),
+                        "ib_device": l.ib_device,
+                        "ib_port": l.ib_port,
+                        "ifindex": l.ifindex,
+                        "conns": l.conns,
+                        "gid": l.gid,
+                        "peer_gid": l.peer_gid,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "type": "smcr",
+                "id": lgr.id,
+                "role": lgr.role_name(),
+                "kind": lgr.type_name(),
+                "conns": lgr.conns,
+                "vlan_id": lgr.vlan_id,
+                "pnetid": lgr.pnetid,
+                "links": links,
+            })
+        })
+        .collect();
+    groups.extend(smcd.iter().map(|lgr| {
+        serde_json::json!({
+            "type": "smcd",
+            "id": lgr.id,
+            "conns": lgr.conns,
+            "vlan_id": lgr.vlan_id,
+            "pnetid": lgr.pnetid,
+            "chid": lgr.chid,
+            "gid": lgr.gid,
+            "peer_gid": lgr.peer_gid,
+        })
+    }));
+
+    serde_json::to_writer_pretty(&mut handle, &groups)?;
+    writeln!(handle)?;
+    Ok(())
+}
+
 fn format_addr(addr: &SocketAddr, numeric: bool, resolve: bool) -> String {
     let ip_str = if addr.ip().is_unspecified() {
         "*".to_string()
@@ -520,11 +952,14 @@ fn format_addr(addr: &SocketAddr, numeric: bool, resolve: bool) -> String {
 mod tests {
     use std::net::SocketAddr;
 
-    use nlink::sockdiag::{InetSocket, TcpInfo};
+    use nlink::sockdiag::{InetSocket, NetlinkSocket, TcpInfo};
 
     use nlink::sockdiag::ProcessRef;
 
-    use super::{DisplayOptions, format_addr, inet_to_json, procs_to_json};
+    use super::{
+        DisplayOptions, format_addr, inet_to_json, netlink_local, netlink_peer, procs_to_json,
+        sock_details, unix_endpoint,
+    };
     use crate::procmap::ProcMap;
 
     #[test]
@@ -599,6 +1034,7 @@ mod tests {
         assert_eq!(json["congestion"], "bbr");
         assert_eq!(json["interface"], 7);
         assert_eq!(json["mark"], 0x1234);
+        assert_eq!(json["cookie"], 0);
     }
 
     #[test]
@@ -635,8 +1071,51 @@ mod tests {
         assert_eq!(procs[0]["fd"], 3);
     }
 
+    #[test]
+    fn sock_details_in_ss_order() {
+        // Root's uid and an unset mark are omitted, as ss(8) does.
+        assert_eq!(sock_details(Some(0), 42, 0x1f, None), "ino:42 sk:1f");
+        assert_eq!(
+            sock_details(Some(1000), 42, 1, Some(0x10)),
+            "uid:1000 ino:42 sk:1 fwmark:0x10"
+        );
+        assert_eq!(sock_details(None, 7, 2, Some(0)), "ino:7 sk:2");
+    }
+
     #[test]
     fn procs_to_json_none_when_absent() {
         assert!(procs_to_json(&ProcMap::new(), 42).is_none());
     }
+
+    #[test]
+    fn netlink_endpoints_like_ss() {
+        let mut sock = NetlinkSocket {
+            protocol: 0,
+            portid: 0,
+            dst_portid: 0,
+            dst_group: 0,
+            groups: 0,
+            memberships: Vec::new(),
+            flags: None,
+            inode: 1,
+            cookie: 0,
+            recv_q: None,
+            send_q: None,
+            mem_info: None,
+        };
+        assert_eq!(netlink_local(&sock), "route:kernel");
+        assert_eq!(netlink_peer(&sock), "*");
+
+        sock.portid = 812;
+        sock.dst_group = 1;
+        assert_eq!(netlink_local(&sock), "route:812");
+        assert_eq!(netlink_peer(&sock), "1:0");
+    }
+
+    #[test]
+    fn unix_endpoint_formats_name_and_inode() {
+        assert_eq!(unix_endpoint("/run/app.sock", Some(12)), "/run/app.sock 12");
+        assert_eq!(unix_endpoint("", Some(13)), "* 13");
+        assert_eq!(unix_endpoint("", None), "* *");
+    }
 }
@@ -15,6 +15,18 @@ pub fn build() -> ProcMap {
 /// Render the `users:((...))` suffix `ss -p` appends to a socket line.
 /// Returns an empty string when no process is known for the inode.
 pub fn format_users(map: &ProcMap, inode: u32) -> String {
+    format_procs(map, "users", inode)
+}
+
+/// Render the `peer_users:((...))` suffix naming the processes holding
+/// the other end of a connected unix socket.
+pub fn format_peer_users(map: &ProcMap, peer_inode: Option<u32>) -> String {
+    peer_inode
+        .map(|ino| format_procs(map, "peer_users", ino))
+        .unwrap_or_default()
+}
+
+fn format_procs(map: &ProcMap, label: &str, inode: u32) -> String {
     let procs = map.resolve(inode);
     if procs.is_empty() {
         return String::new();
@@ -23,7 +35,7 @@ pub fn format_users(map: &ProcMap, inode: u32) -> String {
         .iter()
         .map(|p| format!("(\"{}\",pid={},fd={})", p.comm, p.pid, p.fd))
         .collect();
-    format!(" users:({})", parts.join(","))
+    format!(" {}:({})", label, parts.join(","))
 }
 
 #[cfg(test)]
@@ -53,4 +65,15 @@ mod tests {
         map.insert(7, entry(100, "sshd", 3));
         assert_eq!(format_users(&map, 7), " users:((\"sshd\",pid=100,fd=3))");
     }
+
+    #[test]
+    fn format_peer_users_renders_peer_entries() {
+        let mut map = ProcMap::new();
+        map.insert(8, entry(200, "dbus", 5));
+        assert_eq!(
+            format_peer_users(&map, Some(8)),
+            " peer_users:((\"dbus\",pid=200,fd=5))"
+        );
+        assert_eq!(format_peer_users(&map, None), "");
+    }
 }
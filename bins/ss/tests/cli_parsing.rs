@@ -53,6 +53,18 @@ fn help_documents_family_and_layout_flags() {
         .stdout(predicate::str::contains("--processes"));
 }
 
+/// The SMC selectors (`--smc` sockets, `--smc-linkgroups` listing)
+/// are part of the help surface.
+#[test]
+fn help_documents_smc_flags() {
+    ss_cmd()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--smc"))
+        .stdout(predicate::str::contains("--smc-linkgroups"));
+}
+
 /// The ss-style filter selectors land as documented options rather
 /// than being swallowed as positional noise.
 #[test]
@@ -77,3 +89,30 @@ fn non_numeric_sport_is_rejected() {
         .failure()
         .stderr(predicate::str::contains("error"));
 }
+
+#[test]
+fn dry_run_requires_a_kill_option() {
+    ss_cmd()
+        .arg("--dry-run")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--kill"));
+}
+
+#[test]
+fn kill_options_are_mutually_exclusive() {
+    ss_cmd()
+        .args(["--kill-timewait", "--kill-orphaned"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("cannot be used with"));
+}
+
+#[test]
+fn shards_takes_a_number() {
+    ss_cmd()
+        .args(["--shards", "many"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--shards"));
+}
@@ -19,6 +19,7 @@ use nlink::{
             VlanAction,
         },
         attr::AttrIter,
+        psched,
         types::tc::action::{
             self,
             gact::{TCA_GACT_PARMS, TcGact},
@@ -26,7 +27,8 @@ use nlink::{
             police::{TCA_POLICE_TBF, TcPolice},
         },
     },
-    output::{OutputFormat, OutputOptions, formatting::format_rate_bps},
+    output::{OutputFormat, OutputOptions},
+    util::{format_rate, format_size},
 };
 
 #[derive(Args)]
@@ -232,7 +234,7 @@ fn print_action_message(
 }
 
 /// Extract an action's kind-specific options as JSON fields.
-fn action_options_json(kind: &str, opts_data: &[u8]) -> serde_json::Map<String, serde_json::Value> {
+pub(super) fn action_options_json(kind: &str, opts_data: &[u8]) -> serde_json::Map<String, serde_json::Value> {
     use serde_json::Value;
     let mut obj = serde_json::Map::new();
     match kind {
@@ -293,7 +295,7 @@ fn action_options_json(kind: &str, opts_data: &[u8]) -> serde_json::Map<String,
 }
 
 /// Print action options in text format.
-fn print_action_options_text(w: &mut impl Write, kind: &str, opts_data: &[u8]) -> Result<()> {
+pub(super) fn print_action_options_text(w: &mut impl Write, kind: &str, opts_data: &[u8]) -> Result<()> {
     match kind {
         "gact" => {
             for (attr_type, attr_data) in AttrIter::new(opts_data) {
@@ -335,12 +337,15 @@ fn print_action_options_text(w: &mut impl Write, kind: &str, opts_data: &[u8]) -
                 if attr_type == TCA_POLICE_TBF
                     && let Ok((p, _rest)) = <TcPolice as zerocopy::FromBytes>::ref_from_prefix(attr_data)
                 {
+                    // The kernel reports the rate in bytes/sec and the burst
+                    // in psched ticks; tc prints bits/sec and bytes.
+                    let rate = u64::from(p.rate.rate);
                     write!(
                         w,
                         "rate {} burst {} mtu {} action {}",
-                        format_rate_bps(p.rate.rate as u64),
-                        p.burst,
-                        p.mtu,
+                        format_rate(rate * 8),
+                        format_size(psched::tc_calc_xmitsize(rate, p.burst).into()),
+                        format_size(p.mtu.into()),
                         action::format_action_result(p.action)
                     )?;
                     writeln!(w)?;
@@ -1,16 +1,19 @@
 //! tc class command implementation.
 
+use std::io::{self, Write};
+
 use clap::{Args, Subcommand};
 use nlink::{
     Error, TcHandle,
     netlink::{
         Connection, Result, Route,
-        message::NlMsgType,
         messages::TcMessage,
         tc::{ClassConfig, DrrClassConfig, HfscClassConfig, HtbClassConfig, QfqClassConfig},
+        tc_options::{ClassOptions, parse_class_options},
         types::tc::tc_handle,
     },
-    output::{OutputFormat, OutputOptions, print_all},
+    output::{OutputFormat, OutputOptions, print_items},
+    util::{format_rate, format_size},
 };
 
 #[derive(Args)]
@@ -261,8 +264,8 @@ impl ClassCmd {
         let parent_filter = parse_handle_filter(parent, "parent")?;
         let classid_filter = parse_handle_filter(classid, "classid")?;
 
-        // Fetch all classes using typed API
-        let all_classes: Vec<TcMessage> = conn.dump_typed(NlMsgType::RTM_GETTCLASS).await?;
+        // Class dumps are per-device in the kernel.
+        let all_classes = conn.get_classes_by_index(ifindex).await?;
 
         // Filter classes
         let classes: Vec<_> = all_classes
@@ -294,7 +297,7 @@ impl ClassCmd {
             })
             .collect();
 
-        print_all(&classes, format, opts)?;
+        print_items(&classes, format, opts, class_to_json, print_class_text)?;
 
         Ok(())
     }
@@ -359,6 +362,113 @@ async fn run_typed_class<C: ClassConfig>(
     }
 }
 
+/// Convert a class to JSON, with its decoded options.
+fn class_to_json(class: &TcMessage) -> serde_json::Value {
+    let mut obj = serde_json::json!({
+        "class": class.kind().unwrap_or(""),
+        "handle": class.handle().to_string(),
+        "parent": class.parent().to_string(),
+        "dev": nlink::util::get_ifname_or_index(class.ifindex()),
+        "bytes": class.bytes(),
+        "packets": class.packets(),
+        "drops": class.drops(),
+        "overlimits": class.overlimits(),
+        "requeues": class.requeues(),
+        "qlen": class.qlen(),
+        "backlog": class.backlog(),
+    });
+
+    if class.is_offloaded() {
+        obj["offloaded"] = serde_json::json!(true);
+    }
+    if let Some(hw) = class.stats_basic_hw() {
+        obj["hw_bytes"] = serde_json::json!(hw.bytes);
+        obj["hw_packets"] = serde_json::json!(hw.packets);
+    }
+
+    if let Some(ClassOptions::Htb(htb)) = parse_class_options(class) {
+        obj["options"] = serde_json::json!({
+            "prio": htb.priority,
+            "quantum": htb.quantum,
+            "rate": htb.rate * 8,
+            "ceil": htb.ceil * 8,
+            "burst": htb.burst,
+            "cburst": htb.cburst,
+            "level": htb.level,
+        });
+    }
+
+    obj
+}
+
+/// Print a class in tc's text layout.
+fn print_class_text(
+    w: &mut io::StdoutLock<'_>,
+    class: &TcMessage,
+    opts: &OutputOptions,
+) -> io::Result<()> {
+    write!(
+        w,
+        "class {} {} ",
+        class.kind().unwrap_or(""),
+        class.handle()
+    )?;
+
+    let parent = class.parent();
+    if parent.is_root() {
+        write!(w, "root ")?;
+    } else if !parent.is_unspec() {
+        write!(w, "parent {} ", parent)?;
+    }
+
+    if let Some(ClassOptions::Htb(htb)) = parse_class_options(class) {
+        // rate/ceil are bytes/sec in the kernel, bits/sec in tc output.
+        write!(
+            w,
+            "prio {} rate {} ceil {} burst {} cburst {} ",
+            htb.priority,
+            format_rate(htb.rate * 8),
+            format_rate(htb.ceil * 8),
+            format_size(htb.burst.into()),
+            format_size(htb.cburst.into())
+        )?;
+    }
+
+    write!(
+        w,
+        "dev {}",
+        nlink::util::get_ifname_or_index(class.ifindex())
+    )?;
+    if class.is_offloaded() {
+        write!(w, " offloaded")?;
+    }
+    writeln!(w)?;
+
+    if opts.stats {
+        writeln!(
+            w,
+            " Sent {} bytes {} pkt (dropped {}, overlimits {} requeues {})",
+            class.bytes(),
+            class.packets(),
+            class.drops(),
+            class.overlimits(),
+            class.requeues()
+        )?;
+        if let Some(hw) = class.stats_basic_hw() {
+            writeln!(
+                w,
+                " Sent software {} bytes {} pkt",
+                class.bytes().saturating_sub(hw.bytes),
+                class.packets().saturating_sub(hw.packets)
+            )?;
+            writeln!(w, " Sent hardware {} bytes {} pkt", hw.bytes, hw.packets)?;
+        }
+        writeln!(w, " backlog {}b {}p", class.backlog(), class.qlen())?;
+    }
+
+    Ok(())
+}
+
 /// Strictly parse an optional tc handle used as a `show` filter.
 /// A present-but-unparseable value is an error (it must not silently
 /// degrade to "no filter", which would dump every class).
@@ -11,13 +11,14 @@ use nlink::{
             BasicFilter, BpfFilter, CgroupFilter, FilterConfig, FlowFilter, FlowerFilter, FwFilter,
             MatchallFilter, RouteFilter, RsvpFilter, TcindexFilter, U32Filter,
         },
-        message::NlMsgType,
         messages::TcMessage,
-        types::tc::tc_handle,
+        tc_options::{FilterOptions, parse_filter_options},
     },
-    output::{OutputFormat, OutputOptions, print_items},
+    output::{OutputFormat, OutputOptions, format_tc_handle, print_items},
 };
 
+use super::action::{action_options_json, print_action_options_text};
+
 #[derive(Args)]
 pub struct FilterCmd {
     #[command(subcommand)]
@@ -194,8 +195,18 @@ impl FilterCmd {
                 parent,
                 protocol,
                 prio,
-            } => Self::show(conn, &dev, parent.as_deref(), protocol.as_deref(), prio, format, opts)
-                .await,
+            } => {
+                Self::show(
+                    conn,
+                    &dev,
+                    parent.as_deref(),
+                    protocol.as_deref(),
+                    prio,
+                    format,
+                    opts,
+                )
+                .await
+            }
             FilterAction::List { dev } => {
                 Self::show(conn, &dev, None, None, None, format, opts).await
             }
@@ -230,7 +241,7 @@ impl FilterCmd {
                 prio,
                 kind: _,
             } => {
-                let parent_t = parent.parse::<TcHandle>().map_err(|e| {
+                let parent_t = TcHandle::parse_filter_parent(&parent).map_err(|e| {
                     Error::InvalidMessage(format!("tc filter del: invalid parent `{parent}`: {e}"))
                 })?;
                 match (protocol.as_deref(), prio) {
@@ -333,18 +344,16 @@ impl FilterCmd {
         // A `--parent <handle>` narrows the dump to that one parent.
         let parent_handle = parent
             .map(|p| {
-                tc_handle::parse(p)
-                    .map(nlink::TcHandle::from_raw)
-                    .ok_or_else(|| {
-                        nlink::netlink::Error::InvalidMessage(format!("invalid parent: {}", p))
-                    })
+                nlink::TcHandle::parse_filter_parent(p).map_err(|_| {
+                    nlink::netlink::Error::InvalidMessage(format!("invalid parent: {}", p))
+                })
             })
             .transpose()?;
 
         let proto_filter = protocol_filter.map(parse_protocol_u16).transpose()?;
 
-        // Fetch all filters using typed API
-        let all_filters: Vec<TcMessage> = conn.dump_typed(NlMsgType::RTM_GETTFILTER).await?;
+        // Filter dumps are per-device and per-block in the kernel.
+        let all_filters = conn.get_filters_by_index(ifindex).await?;
 
         // Filter results
         let filters: Vec<_> = all_filters
@@ -407,20 +416,24 @@ async fn dispatch_filter(
     params: &[String],
     verb: FilterVerb,
 ) -> Result<()> {
-    let parent = parent
-        .parse::<TcHandle>()
+    let parent = TcHandle::parse_filter_parent(parent)
         .map_err(|e| Error::InvalidMessage(format!("tc filter: invalid parent `{parent}`: {e}")))?;
     let proto = parse_protocol_u16(protocol)?;
     let priority = prio.unwrap_or(0);
 
     // Strict: a present-but-unparseable handle is an error, never a
     // silently-dropped filter (see the CLAUDE.md strict-parse contract).
-    let handle = match handle {
-        None => None,
-        Some(h) => Some(h.parse::<TcHandle>().map_err(|e| {
-            Error::InvalidMessage(format!("tc filter: invalid handle `{h}`: {e}"))
-        })?),
-    };
+    // u32 handles have their own `htid:bucket:node` layout.
+    let handle =
+        match handle {
+            None => None,
+            Some(h) if kind == "u32" => Some(TcHandle::from_raw(
+                nlink::netlink::filter::parse_u32_handle(h)?,
+            )),
+            Some(h) => Some(h.parse::<TcHandle>().map_err(|e| {
+                Error::InvalidMessage(format!("tc filter: invalid handle `{h}`: {e}"))
+            })?),
+        };
 
     let refs: Vec<&str> = params.iter().map(String::as_str).collect();
 
@@ -520,6 +533,34 @@ fn format_protocol(proto: u16) -> String {
     }
 }
 
+/// Format a filter handle. u32 handles split into `htid:hash:node`
+/// the way tc prints them (`800::800`); other kinds are plain hex.
+fn format_filter_handle(filter: &TcMessage) -> String {
+    let handle = filter.handle_raw();
+    if filter.kind() != Some("u32") {
+        return format!("{handle:x}");
+    }
+    format_u32_handle(handle)
+}
+
+/// Format a raw u32 handle as `htid:hash:node`.
+fn format_u32_handle(handle: u32) -> String {
+    let htid = handle >> 20;
+    let hash = (handle >> 12) & 0xff;
+    let node = handle & 0xfff;
+    let mut s = String::new();
+    if htid != 0 {
+        s.push_str(&format!("{htid:x}:"));
+    }
+    if hash != 0 {
+        s.push_str(&format!("{hash:x}"));
+    }
+    if node != 0 || htid == 0 {
+        s.push_str(&format!(":{node:x}"));
+    }
+    s
+}
+
 /// Convert a TcMessage to JSON representation for filter.
 fn filter_to_json(filter: &TcMessage) -> serde_json::Value {
     let dev = nlink::util::get_ifname_or_index(filter.ifindex());
@@ -533,7 +574,7 @@ fn filter_to_json(filter: &TcMessage) -> serde_json::Value {
     });
 
     if !filter.handle().is_unspec() {
-        obj["handle"] = serde_json::json!(format!("{:x}", filter.handle_raw()));
+        obj["handle"] = serde_json::json!(format_filter_handle(filter));
     }
 
     if let Some(chain) = filter.chain() {
@@ -555,6 +596,10 @@ fn filter_to_json(filter: &TcMessage) -> serde_json::Value {
         obj["bpf"] = serde_json::Value::Object(bpf_obj);
     }
 
+    if let Some(options) = parse_filter_options(filter) {
+        obj["options"] = filter_options_json(&options);
+    }
+
     obj
 }
 
@@ -562,7 +607,7 @@ fn filter_to_json(filter: &TcMessage) -> serde_json::Value {
 fn print_filter_text(
     w: &mut io::StdoutLock<'_>,
     filter: &TcMessage,
-    _opts: &OutputOptions,
+    opts: &OutputOptions,
 ) -> io::Result<()> {
     let dev = nlink::util::get_ifname_or_index(filter.ifindex());
 
@@ -580,7 +625,7 @@ fn print_filter_text(
     }
 
     if !filter.handle().is_unspec() {
-        write!(w, "handle {:x} ", filter.handle_raw())?;
+        write!(w, "handle {} ", format_filter_handle(filter))?;
     }
 
     write!(w, "dev {}", dev)?;
@@ -601,7 +646,285 @@ fn print_filter_text(
         }
     }
 
+    let options = parse_filter_options(filter);
+    if let Some(ref options) = options {
+        if let Some(classid) = options.classid() {
+            let key = if matches!(options, FilterOptions::U32(_)) {
+                "flowid"
+            } else {
+                "classid"
+            };
+            write!(w, " {key} {}", format_tc_handle(classid))?;
+        }
+        if let FilterOptions::U32(u32) = options {
+            for (key, value) in filter_keys(options) {
+                write!(w, " {key} {value}")?;
+            }
+            if u32.selector.as_ref().is_some_and(|s| s.is_terminal()) {
+                write!(w, " terminal")?;
+            }
+            if opts.stats
+                && let Some(ref c) = u32.counters
+            {
+                write!(w, "  (rule hit {} success {})", c.lookups, c.hits)?;
+            }
+        }
+        for flag in cls_flag_names(options.flags()) {
+            write!(w, " {flag}")?;
+        }
+        if let Some(count) = options.in_hw_count() {
+            write!(w, " in_hw_count {count}")?;
+        }
+    }
+
     writeln!(w)?;
 
+    if let Some(ref options) = options {
+        print_filter_options_text(w, options, opts)?;
+    }
+
+    Ok(())
+}
+
+/// Flags shared by every classifier (`TCA_CLS_FLAGS_*`), tc spelling.
+fn cls_flag_names(flags: u32) -> Vec<&'static str> {
+    use nlink::netlink::types::tc::filter::flower::{
+        TCA_CLS_FLAGS_IN_HW, TCA_CLS_FLAGS_NOT_IN_HW, TCA_CLS_FLAGS_SKIP_HW, TCA_CLS_FLAGS_SKIP_SW,
+    };
+    [
+        (TCA_CLS_FLAGS_SKIP_HW, "skip_hw"),
+        (TCA_CLS_FLAGS_SKIP_SW, "skip_sw"),
+        (TCA_CLS_FLAGS_IN_HW, "in_hw"),
+        (TCA_CLS_FLAGS_NOT_IN_HW, "not_in_hw"),
+    ]
+    .into_iter()
+    .filter(|(bit, _)| flags & bit != 0)
+    .map(|(_, name)| name)
+    .collect()
+}
+
+/// Offload flags on an action (`TCA_ACT_FLAGS_*`), tc spelling.
+fn act_flag_names(flags: u32) -> Vec<&'static str> {
+    use nlink::netlink::types::tc::action::{TCA_ACT_FLAGS_SKIP_HW, TCA_ACT_FLAGS_SKIP_SW};
+    [
+        (TCA_ACT_FLAGS_SKIP_HW, "skip_hw"),
+        (TCA_ACT_FLAGS_SKIP_SW, "skip_sw"),
+    ]
+    .into_iter()
+    .filter(|(bit, _)| flags & bit != 0)
+    .map(|(_, name)| name)
+    .collect()
+}
+
+fn format_ip_proto(proto: u8) -> String {
+    match proto {
+        1 => "icmp".to_string(),
+        6 => "tcp".to_string(),
+        17 => "udp".to_string(),
+        58 => "icmpv6".to_string(),
+        132 => "sctp".to_string(),
+        _ => proto.to_string(),
+    }
+}
+
+fn format_masked_mac((mac, mask): &([u8; 6], [u8; 6])) -> String {
+    let mac = nlink::util::addr::format_mac(mac);
+    if *mask == [0xff; 6] {
+        mac
+    } else {
+        format!("{mac}/{}", nlink::util::addr::format_mac(mask))
+    }
+}
+
+/// The classifier-specific match keys, as `(key, value)` pairs in tc's
+/// spelling and order.
+fn filter_keys(options: &FilterOptions) -> Vec<(&'static str, String)> {
+    let mut keys = Vec::new();
+    match options {
+        FilterOptions::U32(u32) => {
+            if let Some(link) = u32.link {
+                keys.push(("link", format_u32_handle(link)));
+            }
+            if let Some(divisor) = u32.divisor {
+                keys.push(("divisor", divisor.to_string()));
+            }
+        }
+        FilterOptions::Flower(flower) => {
+            if let Some(ref indev) = flower.indev {
+                keys.push(("indev", indev.clone()));
+            }
+            if let Some(id) = flower.vlan_id {
+                keys.push(("vlan_id", id.to_string()));
+            }
+            if let Some(prio) = flower.vlan_prio {
+                keys.push(("vlan_prio", prio.to_string()));
+            }
+            if let Some(ref mac) = flower.dst_mac {
+                keys.push(("dst_mac", format_masked_mac(mac)));
+            }
+            if let Some(ref mac) = flower.src_mac {
+                keys.push(("src_mac", format_masked_mac(mac)));
+            }
+            if let Some(eth_type) = flower.eth_type {
+                keys.push(("eth_type", format_protocol(eth_type)));
+            }
+            if let Some(proto) = flower.ip_proto {
+                keys.push(("ip_proto", format_ip_proto(proto)));
+            }
+            if let Some((tos, mask)) = flower.ip_tos {
+                keys.push(("ip_tos", format!("0x{tos:x}/0x{mask:x}")));
+            }
+            if let Some((ttl, mask)) = flower.ip_ttl {
+                keys.push(("ip_ttl", format!("0x{ttl:x}/0x{mask:x}")));
+            }
+            if let Some((addr, len)) = flower.dst_ip {
+                keys.push(("dst_ip", format!("{addr}/{len}")));
+            }
+            if let Some((addr, len)) = flower.src_ip {
+                keys.push(("src_ip", format!("{addr}/{len}")));
+            }
+            if let Some(port) = flower.dst_port {
+                keys.push(("dst_port", port.to_string()));
+            }
+            if let Some(port) = flower.src_port {
+                keys.push(("src_port", port.to_string()));
+            }
+            if let Some((flags, mask)) = flower.tcp_flags {
+                keys.push(("tcp_flags", format!("0x{flags:x}/0x{mask:x}")));
+            }
+        }
+        _ => {}
+    }
+    keys
+}
+
+/// Decoded options for the JSON view.
+fn filter_options_json(options: &FilterOptions) -> serde_json::Value {
+    use serde_json::{Value, json};
+
+    let mut obj = serde_json::Map::new();
+    if let Some(classid) = options.classid() {
+        obj.insert("flowid".into(), json!(format_tc_handle(classid)));
+    }
+    for (key, value) in filter_keys(options) {
+        let value = value
+            .parse::<u64>()
+            .map_or_else(|_| json!(value), |n| json!(n));
+        obj.insert(key.into(), value);
+    }
+    for flag in cls_flag_names(options.flags()) {
+        obj.insert(flag.into(), json!(true));
+    }
+    if let Some(count) = options.in_hw_count() {
+        obj.insert("in_hw_count".into(), json!(count));
+    }
+    if let FilterOptions::U32(u32) = options {
+        if let Some(ref sel) = u32.selector {
+            let keys: Vec<_> = sel
+                .keys
+                .iter()
+                .map(|k| {
+                    json!({
+                        "value": format!("0x{:08x}", k.val),
+                        "mask": format!("0x{:08x}", k.mask),
+                        "offset": k.off,
+                    })
+                })
+                .collect();
+            obj.insert("match".into(), Value::Array(keys));
+            obj.insert("terminal".into(), json!(sel.is_terminal()));
+        }
+        if let Some(ref c) = u32.counters {
+            obj.insert("rule_hit".into(), json!(c.lookups));
+            obj.insert("success".into(), json!(c.hits));
+        }
+    }
+
+    let actions: Vec<_> = options
+        .actions()
+        .iter()
+        .map(|act| {
+            let mut a = serde_json::Map::new();
+            a.insert("order".into(), json!(act.order));
+            a.insert("kind".into(), json!(act.kind));
+            a.insert("index".into(), json!(act.index));
+            a.extend(action_options_json(&act.kind, &act.options_raw));
+            for flag in act_flag_names(act.flags) {
+                a.insert(flag.into(), json!(true));
+            }
+            if let Some(count) = act.in_hw_count {
+                a.insert("in_hw_count".into(), json!(count));
+            }
+            if let Some(basic) = act.stats_basic {
+                let mut stats = json!({ "bytes": basic.bytes, "packets": basic.packets });
+                if let Some(queue) = act.stats_queue {
+                    stats["drops"] = json!(queue.drops);
+                    stats["overlimits"] = json!(queue.overlimits);
+                }
+                a.insert("stats".into(), stats);
+            }
+            Value::Object(a)
+        })
+        .collect();
+    if !actions.is_empty() {
+        obj.insert("actions".into(), Value::Array(actions));
+    }
+
+    Value::Object(obj)
+}
+
+/// Print the decoded options under a filter's header line.
+fn print_filter_options_text(
+    w: &mut io::StdoutLock<'_>,
+    options: &FilterOptions,
+    opts: &OutputOptions,
+) -> io::Result<()> {
+    if let FilterOptions::U32(u32) = options
+        && let Some(ref sel) = u32.selector
+    {
+        let key_hits = u32.counters.as_ref().map(|c| &c.key_hits[..]);
+        for (i, key) in sel.keys.iter().enumerate() {
+            write!(w, "  match {:08x}/{:08x} at {}", key.val, key.mask, key.off)?;
+            if opts.stats
+                && let Some(hits) = key_hits.and_then(|h| h.get(i))
+            {
+                write!(w, " (success {hits} )")?;
+            }
+            writeln!(w)?;
+        }
+    }
+
+    if !matches!(options, FilterOptions::U32(_)) {
+        for (key, value) in filter_keys(options) {
+            writeln!(w, "  {key} {value}")?;
+        }
+    }
+
+    for act in options.actions() {
+        write!(w, "\taction order {}: {} ", act.order, act.kind)?;
+        print_action_options_text(w, &act.kind, &act.options_raw).map_err(io::Error::other)?;
+        for flag in act_flag_names(act.flags) {
+            write!(w, " {flag}")?;
+        }
+        if let Some(count) = act.in_hw_count {
+            write!(w, " in_hw_count {count}")?;
+        }
+        writeln!(w)?;
+        if act.index != 0 {
+            writeln!(w, "\tindex {}", act.index)?;
+        }
+        if opts.stats
+            && let Some(basic) = act.stats_basic
+        {
+            let queue = act.stats_queue.unwrap_or_default();
+            writeln!(
+                w,
+                "\tAction statistics:\n\tSent {} bytes {} pkt (dropped {}, overlimits {})",
+                basic.bytes, basic.packets, queue.drops, queue.overlimits
+            )?;
+        }
+        writeln!(w)?;
+    }
+
     Ok(())
 }
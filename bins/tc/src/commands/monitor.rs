@@ -1,14 +1,25 @@
 //! tc monitor - watch for traffic control events.
 //!
 //! This module uses the Connection events() API from nlink for high-level
-//! event monitoring with Stream trait support.
+//! event monitoring with Stream trait support. With `--rates`, qdisc and
+//! class rates sampled by `stats::Poller` are interleaved with the events.
+
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
 
 use clap::{Args, ValueEnum};
 use nlink::{
-    netlink::{Connection, NetworkEvent, Result, Route, RtnetlinkGroup},
+    netlink::{
+        Connection, NetworkEvent, Result, Route, RtnetlinkGroup,
+        stats::{Poller, StatsSample, TcRates},
+    },
     output::{
-        MonitorConfig, OutputFormat, OutputOptions, TcEvent, print_event, print_monitor_start,
+        MonitorConfig, MonitorEvent, OutputFormat, OutputOptions, TcEvent, format_tc_handle,
+        print_event, print_monitor_start,
     },
+    util::format_rate,
 };
 use tokio_stream::StreamExt;
 
@@ -34,6 +45,15 @@ pub struct MonitorCmd {
     /// Label output lines with event timestamps.
     #[arg(short = 't', long)]
     timestamp: bool,
+
+    /// Also print qdisc/class rates sampled every SECS seconds.
+    #[arg(
+        short = 'r',
+        long,
+        value_name = "SECS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    rates: Option<u64>,
 }
 
 impl MonitorCmd {
@@ -71,12 +91,30 @@ impl MonitorCmd {
 
         let mut events = conn.events().await;
 
-        while let Some(result) = events.next().await {
-            let event = result?;
-            if let Some(tc_event) =
-                convert_event(event, monitor_qdisc, monitor_class, monitor_filter)
-            {
-                print_event(&mut stdout, &tc_event, &config)?;
+        // Sample on a separate socket so dumps don't interleave with the
+        // multicast subscription.
+        let poll_conn = Connection::<Route>::new()?;
+        let mut poller = self.rates.map(|secs| {
+            Poller::new(&poll_conn, Duration::from_secs(secs))
+                .qdiscs(monitor_qdisc)
+                .classes(monitor_class)
+        });
+
+        loop {
+            tokio::select! {
+                result = events.next() => {
+                    let Some(result) = result else { break };
+                    if let Some(tc_event) =
+                        convert_event(result?, monitor_qdisc, monitor_class, monitor_filter)
+                    {
+                        print_event(&mut stdout, &tc_event, &config)?;
+                    }
+                }
+                Some(sample) = next_sample(poller.as_mut()), if poller.is_some() => {
+                    for rate in rate_events(&sample?) {
+                        print_event(&mut stdout, &rate, &config)?;
+                    }
+                }
             }
         }
 
@@ -131,3 +169,87 @@ fn convert_event(
         ifindex: tc_msg.ifindex(),
     })
 }
+
+async fn next_sample(poller: Option<&mut Poller<'_>>) -> Option<Result<StatsSample>> {
+    Some(poller?.next().await)
+}
+
+/// A sampled qdisc/class rate, printed between events.
+struct TcRateEvent {
+    object: &'static str,
+    kind: String,
+    handle: u32,
+    dev: String,
+    ifindex: u32,
+    rates: TcRates,
+}
+
+impl MonitorEvent for TcRateEvent {
+    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> io::Result<()> {
+        writeln!(
+            w,
+            "rate {} {} {} dev {} {} {:.0}pps drops {:.0}/s overlimits {:.0}/s",
+            self.object,
+            self.kind,
+            format_tc_handle(self.handle),
+            self.dev,
+            format_rate(self.rates.bps() as u64),
+            self.rates.packets_per_sec,
+            self.rates.drops_per_sec,
+            self.rates.overlimits_per_sec,
+        )
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "event": self.object,
+            "action": "rate",
+            "kind": self.kind,
+            "handle": format_tc_handle(self.handle),
+            "dev": self.dev,
+            "ifindex": self.ifindex,
+            "bps": self.rates.bps(),
+            "pps": self.rates.packets_per_sec,
+            "drops_per_sec": self.rates.drops_per_sec,
+            "overlimits_per_sec": self.rates.overlimits_per_sec,
+            "requeues_per_sec": self.rates.requeues_per_sec,
+        })
+    }
+}
+
+/// Rate lines for one sample, qdiscs before classes, in stable order.
+/// `noqueue` qdiscs never count traffic and are left out.
+fn rate_events(sample: &StatsSample) -> Vec<TcRateEvent> {
+    let dev = |ifindex: u32| {
+        sample
+            .snapshot
+            .links
+            .get(&ifindex)
+            .and_then(|l| l.name.clone())
+            .unwrap_or_else(|| format!("if{}", ifindex))
+    };
+    let mut out = Vec::new();
+    for (object, rates) in [
+        ("qdisc", &sample.rates.qdiscs),
+        ("class", &sample.rates.classes),
+    ] {
+        let mut keys: Vec<_> = rates.keys().copied().collect();
+        keys.sort_unstable();
+        for (ifindex, handle) in keys {
+            let rate = &rates[&(ifindex, handle)];
+            let kind = rate.kind.clone().unwrap_or_default();
+            if kind == "noqueue" {
+                continue;
+            }
+            out.push(TcRateEvent {
+                object,
+                kind,
+                handle,
+                dev: dev(ifindex),
+                ifindex,
+                rates: rate.clone(),
+            });
+        }
+    }
+    out
+}
@@ -46,7 +46,7 @@ enum QdiscAction {
         dev: Option<String>,
     },
 
-    /// Add a qdisc.
+    /// Add a qdisc (fails if one already sits at the parent).
     Add {
         /// Device name.
         #[arg(value_name = "DEV")]
@@ -83,7 +83,7 @@ enum QdiscAction {
         handle: Option<String>,
     },
 
-    /// Replace a qdisc.
+    /// Replace a qdisc (creates it if missing).
     Replace {
         /// Device name.
         dev: String,
@@ -105,7 +105,7 @@ enum QdiscAction {
         params: Vec<String>,
     },
 
-    /// Change a qdisc.
+    /// Change a qdisc in place (fails if it does not exist).
     Change {
         /// Device name.
         dev: String,
@@ -126,6 +126,20 @@ enum QdiscAction {
         #[arg(trailing_var_arg = true)]
         params: Vec<String>,
     },
+
+    /// Graft an existing qdisc at a parent (never creates one).
+    Link {
+        /// Device name.
+        dev: String,
+
+        /// Parent handle.
+        #[arg(long, default_value = "root")]
+        parent: String,
+
+        /// Handle of the existing qdisc.
+        #[arg(long)]
+        handle: String,
+    },
 }
 
 impl QdiscCmd {
@@ -171,6 +185,11 @@ impl QdiscCmd {
                 kind,
                 params,
             } => Self::change(conn, &dev, &parent, handle.as_deref(), &kind, &params).await,
+            QdiscAction::Link {
+                dev,
+                parent,
+                handle,
+            } => Self::link(conn, &dev, &parent, &handle).await,
         }
     }
 
@@ -259,6 +278,13 @@ impl QdiscCmd {
     ) -> Result<()> {
         dispatch_qdisc(conn, dev, parent, handle, kind, params, QdiscVerb::Change).await
     }
+
+    async fn link(conn: &Connection<Route>, dev: &str, parent: &str, handle: &str) -> Result<()> {
+        let (p, _) = parse_qdisc_handles(parent, None)?;
+        let h = TcHandle::parse_qdisc_handle(handle)
+            .map_err(|e| Error::InvalidMessage(format!("invalid handle `{handle}`: {e}")))?;
+        conn.link_qdisc(dev, p, h).await
+    }
 }
 
 /// Verb tag for `try_typed_qdisc` — picks add/replace/change at the
@@ -280,7 +306,7 @@ fn parse_qdisc_handles(parent: &str, handle: Option<&str>) -> Result<(TcHandle,
         .map_err(|e| Error::InvalidMessage(format!("invalid parent `{parent}`: {e}")))?;
     let handle = handle
         .map(|s| {
-            s.parse::<TcHandle>()
+            TcHandle::parse_qdisc_handle(s)
                 .map_err(|e| Error::InvalidMessage(format!("invalid handle `{s}`: {e}")))
         })
         .transpose()?;
@@ -134,6 +134,15 @@ mod qdisc_command {
             .stdout(predicate::str::contains("--handle"));
     }
 
+    #[test]
+    fn test_qdisc_link_help() {
+        tc_cmd()
+            .args(["qdisc", "link", "--help"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("--handle"));
+    }
+
     #[test]
     fn test_qdisc_add_requires_dev() {
         tc_cmd()
@@ -1,55 +1,47 @@
 //! Monitor interface statistics and calculate rates.
 //!
-//! This example demonstrates how to use StatsTracker to calculate
+//! This example demonstrates how to use Poller to calculate
 //! bandwidth usage in real-time.
 //!
 //! Run with: cargo run -p nlink --example route_stats
 
 use std::time::Duration;
 
-use nlink::netlink::{
-    Connection, Route,
-    stats::{StatsSnapshot, StatsTracker},
-};
+use nlink::netlink::{Connection, Route, stats::Poller};
 
 #[tokio::main]
 async fn main() -> nlink::netlink::Result<()> {
     let conn = Connection::<Route>::new()?;
-    let mut tracker = StatsTracker::new();
+    let mut poller = Poller::new(&conn, Duration::from_secs(1));
 
     println!("Monitoring interface statistics (Ctrl+C to stop)...\n");
 
     loop {
-        let links = conn.get_links().await?;
-        let snapshot = StatsSnapshot::from_links(&links);
+        let sample = poller.next().await?;
 
-        if let Some(rates) = tracker.update(snapshot) {
-            // Clear screen and move cursor to top
-            print!("\x1b[2J\x1b[H");
+        // Clear screen and move cursor to top
+        print!("\x1b[2J\x1b[H");
 
-            println!(
-                "{:<16} {:>12} {:>12} {:>12} {:>12}",
-                "INTERFACE", "RX bytes/s", "TX bytes/s", "RX pkt/s", "TX pkt/s"
-            );
-            println!("{}", "-".repeat(68));
+        println!(
+            "{:<16} {:>12} {:>12} {:>12} {:>12}",
+            "INTERFACE", "RX bytes/s", "TX bytes/s", "RX pkt/s", "TX pkt/s"
+        );
+        println!("{}", "-".repeat(68));
 
-            for link in &links {
-                if let Some(rate) = rates.links.get(&link.ifindex()) {
-                    let name = link.name_or("?");
+        let mut rates: Vec<_> = sample.rates.links.iter().collect();
+        rates.sort_by_key(|(ifindex, _)| **ifindex);
+        for (_, rate) in rates {
+            let name = rate.name.as_deref().unwrap_or("?");
 
-                    // Format rates with appropriate units
-                    let rx_bps = format_rate(rate.rx_bytes_per_sec);
-                    let tx_bps = format_rate(rate.tx_bytes_per_sec);
+            // Format rates with appropriate units
+            let rx_bps = format_rate(rate.rx_bytes_per_sec);
+            let tx_bps = format_rate(rate.tx_bytes_per_sec);
 
-                    println!(
-                        "{:<16} {:>12} {:>12} {:>12.0} {:>12.0}",
-                        name, rx_bps, tx_bps, rate.rx_packets_per_sec, rate.tx_packets_per_sec
-                    );
-                }
-            }
+            println!(
+                "{:<16} {:>12} {:>12} {:>12.0} {:>12.0}",
+                name, rx_bps, tx_bps, rate.rx_packets_per_sec, rate.tx_packets_per_sec
+            );
         }
-
-        tokio::time::sleep(Duration::from_secs(1)).await;
     }
 }
 
@@ -11,6 +11,8 @@
 //! - `tuntap-async` - Async TUN/TAP support (implies `tuntap`)
 //! - `tc` - Traffic control utilities
 //! - `output` - JSON/text output formatting
+//! - `fixtures` - Captured kernel messages for parser regression tests
+//! - `mock` - In-memory `MockConnection` for unit tests without root
 //! - `full` - All features enabled
 //!
 //! # Example
@@ -193,6 +195,7 @@ pub mod lab;
 pub use netlink::bridge_vlan::{
     BridgeVlanBuilder, BridgeVlanEntry, BridgeVlanEntryOptions, BridgeVlanEntryOptionsBuilder,
     BridgeVlanFlags, BridgeVlanGlobalOptions, BridgeVlanGlobalOptionsBuilder, BridgeVlanState,
+    BridgeVlanStats,
 };
 // Diagnostics types
 pub use netlink::diagnostics::{
@@ -251,16 +254,24 @@ pub use util::{AddressFamily, Bytes, Percent, Rate};
 // reachable only via deep `nlink::netlink::route::Ipv4Route`-style
 // paths; surface them at the crate root for shorter imports.
 
-// Route builders + nested types.
-pub use netlink::route::{Ipv4Route, Ipv6Route, NextHop, RouteConfig, RouteMetrics};
+// Route builders, nested types + dump/event selector and summary.
+pub use netlink::route::{
+    Ipv4Route, Ipv6Route, NextHop, RouteConfig, RouteMetrics, RouteSelector, RouteSummary,
+};
 // Address builders + extension trait.
-pub use netlink::addr::{AddressConfig, Ipv4Address, Ipv6Address};
-// Rule builder.
-pub use netlink::rule::RuleBuilder;
+pub use netlink::addr::{AddressConfig, AddressFilter, Ipv4Address, Ipv6Address};
+// Rule builder + dump filter.
+pub use netlink::rule::{RuleBuilder, RuleFilter};
 // Link + neighbor extension traits for custom impl.
 pub use netlink::link::LinkConfig;
 pub use netlink::neigh::NeighborConfig;
 
+// Object-safe operations trait (and its in-memory implementation) for
+// code that wants to be unit-testable without root.
+#[cfg(feature = "mock")]
+pub use netlink::mock::MockConnection;
+pub use netlink::ops::NetlinkOps;
+
 // Connection pool (Plan 159) — bounded mpsc-channel-backed pool
 // for high-fanout consumers.
 pub use netlink::pool::{ConnectionPool, ConnectionPoolBuilder, PooledConnection};
@@ -36,6 +36,7 @@ use super::{
     connection::dump_request,
     error::{Error, Result},
     message::{NLM_F_ACK, NLM_F_CREATE, NLM_F_REQUEST, NlMsgType},
+    namespace::NamespaceId,
     protocol::Route,
     types::tc::{
         TCA_ACT_TAB, TcMsg,
@@ -57,6 +58,15 @@ pub trait ActionConfig: Send + Sync {
 
     /// Write the action options to a message builder.
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()>;
+
+    /// Namespace an interface name in this action was resolved in
+    /// outside netlink, if any.
+    ///
+    /// Connections refuse to send the action when this differs from the
+    /// namespace they are bound to.
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        None
+    }
 }
 
 // ============================================================================
@@ -314,6 +324,8 @@ pub struct MirredAction {
     ifindex: u32,
     /// Action result after mirred.
     action: i32,
+    /// Namespace a `dev` name was resolved in by [`Self::parse_params`].
+    resolved_in: Option<NamespaceId>,
 }
 
 impl MirredAction {
@@ -331,6 +343,7 @@ impl MirredAction {
             eaction,
             ifindex,
             action,
+            resolved_in: None,
         }
     }
 
@@ -386,9 +399,10 @@ impl MirredAction {
     /// - Direction: `egress` (default) / `ingress`.
     /// - Operation: `redirect` (default) / `mirror`.
     /// - Target interface (one of):
-    ///   - `dev <ifname>` — sysfs lookup via `nlink::util::get_ifindex`.
-    ///     Reads from the host namespace; **inside a foreign netns**
-    ///     prefer the `ifindex` form.
+    ///   - `dev <ifname>` — looked up with [`crate::util::get_ifindex`]
+    ///     in the calling thread's namespace. The action remembers that
+    ///     namespace, and a connection bound to another one rejects it
+    ///     with [`Error::NamespaceMismatch`].
     ///   - `ifindex <n>` — namespace-safe direct ifindex.
     ///
     /// `dev` and `ifindex` are mutually exclusive; the parser
@@ -397,6 +411,7 @@ impl MirredAction {
         let mut direction_ingress = false;
         let mut op_mirror = false;
         let mut ifindex: Option<u32> = None;
+        let mut resolved_in: Option<NamespaceId> = None;
 
         let mut i = 0;
         while i < params.len() {
@@ -429,6 +444,7 @@ impl MirredAction {
                         Error::InvalidMessage(format!("mirred: dev `{s}` not found: {e}"))
                     })?;
                     ifindex = Some(idx);
+                    resolved_in = Some(NamespaceId::of_thread()?);
                     i += 2;
                 }
                 "ifindex" => {
@@ -455,11 +471,15 @@ impl MirredAction {
                     .to_string(),
             )
         })?;
-        Ok(match (direction_ingress, op_mirror) {
+        let action = match (direction_ingress, op_mirror) {
             (false, false) => Self::redirect_by_index(idx),
             (false, true) => Self::mirror_by_index(idx),
             (true, false) => Self::ingress_redirect_by_index(idx),
             (true, true) => Self::ingress_mirror_by_index(idx),
+        };
+        Ok(Self {
+            resolved_in,
+            ..action
         })
     }
 
@@ -479,6 +499,10 @@ impl ActionConfig for MirredAction {
         builder.append_attr(mirred::TCA_MIRRED_PARMS, parms.as_bytes());
         Ok(())
     }
+
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.resolved_in
+    }
 }
 
 // ============================================================================
@@ -4326,6 +4350,9 @@ pub trait ActionConfigDyn: Send + Sync + std::fmt::Debug {
     fn kind(&self) -> &'static str;
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()>;
     fn clone_box(&self) -> Box<dyn ActionConfigDyn>;
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        None
+    }
 }
 
 impl<T: ActionConfig + Clone + std::fmt::Debug + 'static> ActionConfigDyn for T {
@@ -4340,6 +4367,10 @@ impl<T: ActionConfig + Clone + std::fmt::Debug + 'static> ActionConfigDyn for T
     fn clone_box(&self) -> Box<dyn ActionConfigDyn> {
         Box::new(self.clone())
     }
+
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        ActionConfig::resolved_namespace(self)
+    }
 }
 
 impl Clone for Box<dyn ActionConfigDyn> {
@@ -4370,6 +4401,12 @@ impl ActionList {
         self.actions.len()
     }
 
+    /// Namespace the first action that resolved an interface name
+    /// outside netlink did so in (see [`ActionConfig::resolved_namespace`]).
+    pub fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions.iter().find_map(|a| a.resolved_namespace())
+    }
+
     /// Write the action list to a message builder.
     ///
     /// This writes nested TCA_ACT_* attributes for each action.
@@ -4780,6 +4817,8 @@ impl Connection<Route> {
     /// `tcamsg + TCA_ACT_TAB { [1] { TCA_ACT_KIND + TCA_ACT_OPTIONS { ... } } }`.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "add_action", kind = %action.kind()))]
     pub async fn add_action<A: ActionConfig>(&self, action: A) -> Result<()> {
+        self.check_resolved_namespace(action.resolved_namespace())?;
+
         let mut b = MessageBuilder::new(
             NlMsgType::RTM_NEWACTION,
             NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE,
@@ -5764,6 +5803,18 @@ mod tests {
         assert!(err.to_string().contains("mutually exclusive"));
     }
 
+    #[test]
+    fn mirred_parse_params_dev_records_thread_namespace() {
+        // `lo` exists in every namespace, so the lookup always succeeds.
+        let a = MirredAction::parse_params(&["redirect", "dev", "lo"]).unwrap();
+        let thread = NamespaceId::of_thread().unwrap();
+        assert_eq!(ActionConfig::resolved_namespace(&a), Some(thread));
+        assert_eq!(ActionList::new().with(a).resolved_namespace(), Some(thread));
+
+        let by_index = MirredAction::parse_params(&["ifindex", "1"]).unwrap();
+        assert_eq!(ActionConfig::resolved_namespace(&by_index), None);
+    }
+
     #[test]
     fn mirred_parse_params_missing_ifindex_errors() {
         let err = MirredAction::parse_params(&["egress", "redirect"]).unwrap_err();
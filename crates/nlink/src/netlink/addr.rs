@@ -50,16 +50,20 @@
 //! ).await?;
 //! ```
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
 
 use super::{
     builder::MessageBuilder,
-    connection::Connection,
+    connection::{Connection, dump_request},
     error::Result,
     interface_ref::InterfaceRef,
     message::{NLM_F_ACK, NLM_F_REQUEST, NlMsgType},
+    messages::{AddressMessage, LinkMessage},
     protocol::Route,
-    types::addr::{IfAddrMsg, IfaAttr, Scope, ifa_flags},
+    types::addr::{IfAddrLblMsg, IfAddrMsg, IfaAttr, Scope, ifa_flags, ifal},
 };
 
 /// NLM_F_CREATE flag
@@ -104,6 +108,32 @@ pub trait AddressConfig: Send + Sync {
     fn write_delete(&self, builder: &mut MessageBuilder, ifindex: u32) -> Result<()>;
 }
 
+impl<A: AddressConfig + ?Sized> AddressConfig for &A {
+    fn interface_ref(&self) -> &InterfaceRef {
+        (**self).interface_ref()
+    }
+
+    fn family(&self) -> u8 {
+        (**self).family()
+    }
+
+    fn prefix_len(&self) -> u8 {
+        (**self).prefix_len()
+    }
+
+    fn write_add(&self, builder: &mut MessageBuilder, ifindex: u32) -> Result<()> {
+        (**self).write_add(builder, ifindex)
+    }
+
+    fn write_replace(&self, builder: &mut MessageBuilder, ifindex: u32) -> Result<()> {
+        (**self).write_replace(builder, ifindex)
+    }
+
+    fn write_delete(&self, builder: &mut MessageBuilder, ifindex: u32) -> Result<()> {
+        (**self).write_delete(builder, ifindex)
+    }
+}
+
 /// Cache info structure for address lifetimes.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default)]
@@ -161,6 +191,8 @@ pub struct Ipv4Address {
     valid_lft: Option<u32>,
     /// Route metric/priority
     metric: Option<u32>,
+    /// Address protocol (who created the address)
+    protocol: Option<u8>,
 }
 
 impl Ipv4Address {
@@ -184,6 +216,7 @@ impl Ipv4Address {
             preferred_lft: None,
             valid_lft: None,
             metric: None,
+            protocol: None,
         }
     }
 
@@ -210,6 +243,7 @@ impl Ipv4Address {
             preferred_lft: None,
             valid_lft: None,
             metric: None,
+            protocol: None,
         }
     }
 
@@ -287,6 +321,14 @@ impl Ipv4Address {
         self
     }
 
+    /// Set the address protocol (`IFA_PROTO`, kernel 6.3+), which
+    /// records who created the address. Values 1-3 are the kernel's own;
+    /// older kernels ignore it.
+    pub fn protocol(mut self, protocol: u8) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
     /// Write common address attributes to the builder.
     fn write_common_attrs(&self, builder: &mut MessageBuilder) {
         // IFA_LOCAL (the actual address on this interface)
@@ -337,6 +379,11 @@ impl Ipv4Address {
         if let Some(metric) = self.metric {
             builder.append_attr_u32(IfaAttr::RtPriority as u16, metric);
         }
+
+        // IFA_PROTO
+        if let Some(protocol) = self.protocol {
+            builder.append_attr_u8(IfaAttr::Proto as u16, protocol);
+        }
     }
 }
 
@@ -430,6 +477,8 @@ pub struct Ipv6Address {
     valid_lft: Option<u32>,
     /// Route metric/priority
     metric: Option<u32>,
+    /// Address protocol (who created the address)
+    protocol: Option<u8>,
 }
 
 impl Ipv6Address {
@@ -451,6 +500,7 @@ impl Ipv6Address {
             preferred_lft: None,
             valid_lft: None,
             metric: None,
+            protocol: None,
         }
     }
 
@@ -475,6 +525,7 @@ impl Ipv6Address {
             preferred_lft: None,
             valid_lft: None,
             metric: None,
+            protocol: None,
         }
     }
 
@@ -542,6 +593,14 @@ impl Ipv6Address {
         self
     }
 
+    /// Set the address protocol (`IFA_PROTO`, kernel 6.3+), which
+    /// records who created the address. Values 1-3 are the kernel's own;
+    /// older kernels ignore it.
+    pub fn protocol(mut self, protocol: u8) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
     /// Write common address attributes to the builder.
     fn write_common_attrs(&self, builder: &mut MessageBuilder) {
         // IFA_LOCAL
@@ -582,6 +641,11 @@ impl Ipv6Address {
         if let Some(metric) = self.metric {
             builder.append_attr_u32(IfaAttr::RtPriority as u16, metric);
         }
+
+        // IFA_PROTO
+        if let Some(protocol) = self.protocol {
+            builder.append_attr_u8(IfaAttr::Proto as u16, protocol);
+        }
     }
 }
 
@@ -641,6 +705,185 @@ impl AddressConfig for Ipv6Address {
 // Connection Methods
 // ============================================================================
 
+/// An IPv6 address label entry (`ip addrlabel`): packets whose address
+/// falls in `prefix` get `label` during RFC 6724 address selection.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::addr::AddrLabel;
+///
+/// conn.add_addr_label(AddrLabel::new("2001:db8::".parse()?, 32, 100)).await?;
+/// conn.del_addr_label(AddrLabel::new("2001:db8::".parse()?, 32, 100)).await?;
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct AddrLabel {
+    prefix: Ipv6Addr,
+    prefix_len: u8,
+    label: u32,
+    interface: Option<InterfaceRef>,
+}
+
+impl AddrLabel {
+    /// Map `prefix/prefix_len` to `label`.
+    pub fn new(prefix: Ipv6Addr, prefix_len: u8, label: u32) -> Self {
+        Self {
+            prefix,
+            prefix_len,
+            label,
+            interface: None,
+        }
+    }
+
+    /// Only apply the entry to addresses on this device.
+    pub fn dev(mut self, iface: impl Into<InterfaceRef>) -> Self {
+        self.interface = Some(iface.into());
+        self
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, ifindex: u32) {
+        builder.append(
+            &IfAddrLblMsg::new()
+                .with_family(AF_INET6)
+                .with_prefixlen(self.prefix_len)
+                .with_index(ifindex),
+        );
+        builder.append_attr(ifal::ADDRESS, &self.prefix.octets());
+        builder.append_attr_u32(ifal::LABEL, self.label);
+    }
+}
+
+/// Selects addresses the way `ip address show` does.
+///
+/// The interface and family go into the dump request, so the kernel
+/// skips other interfaces (with [strict
+/// checking](Connection::enable_strict_checking) on) and other families.
+/// The remaining selectors are applied to the reply.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::addr::AddressFilter;
+/// use nlink::netlink::types::addr::Scope;
+///
+/// // `ip -4 address show dev eth0 scope global label 'eth0:*' permanent up`
+/// let addrs = conn.get_addresses_filtered(
+///     &AddressFilter::new()
+///         .interface("eth0")
+///         .family(AddressFamily::v4())
+///         .scope(Scope::Universe)
+///         .label("eth0:*")
+///         .permanent(true)
+///         .up(),
+/// ).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use = "filters do nothing unless used"]
+pub struct AddressFilter {
+    interface: Option<InterfaceRef>,
+    family: Option<crate::AddressFamily>,
+    scope: Option<Scope>,
+    label: Option<String>,
+    permanent: Option<bool>,
+    tentative: Option<bool>,
+    dad_failed: Option<bool>,
+    up: bool,
+}
+
+impl AddressFilter {
+    /// Create a filter that matches every address.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match addresses on this interface.
+    pub fn interface(mut self, iface: impl Into<InterfaceRef>) -> Self {
+        self.interface = Some(iface.into());
+        self
+    }
+
+    /// Only match addresses of this family. `AddressFamily::unspec()`
+    /// matches every family.
+    pub fn family(mut self, family: crate::AddressFamily) -> Self {
+        self.family = (family.as_u8() != 0).then_some(family);
+        self
+    }
+
+    /// Only match addresses with this scope.
+    pub fn scope(mut self, scope: Scope) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Only match addresses whose label matches this shell-style glob
+    /// (`*` and `?`). Addresses without a label are matched by their
+    /// interface name, as in iproute2.
+    pub fn label(mut self, pattern: impl Into<String>) -> Self {
+        self.label = Some(pattern.into());
+        self
+    }
+
+    /// Only match permanent addresses (`true`) or dynamic ones (`false`),
+    /// i.e. `ip address show permanent` / `dynamic`.
+    pub fn permanent(mut self, permanent: bool) -> Self {
+        self.permanent = Some(permanent);
+        self
+    }
+
+    /// Only match addresses that are (`true`) or aren't (`false`) still
+    /// in duplicate address detection.
+    pub fn tentative(mut self, tentative: bool) -> Self {
+        self.tentative = Some(tentative);
+        self
+    }
+
+    /// Only match addresses that did (`true`) or didn't (`false`) fail
+    /// duplicate address detection.
+    pub fn dad_failed(mut self, dad_failed: bool) -> Self {
+        self.dad_failed = Some(dad_failed);
+        self
+    }
+
+    /// Only match addresses on interfaces that are administratively up.
+    pub fn up(mut self) -> Self {
+        self.up = true;
+        self
+    }
+
+    /// Check whether `addr` passes the filter.
+    ///
+    /// This only sees the address itself: interfaces given by name, the
+    /// interface-name fallback for [`label`](Self::label), and
+    /// [`up`](Self::up) need link state and are only applied by
+    /// [`Connection::get_addresses_filtered`].
+    pub fn matches(&self, addr: &AddressMessage) -> bool {
+        self.interface
+            .as_ref()
+            .and_then(InterfaceRef::as_index)
+            .is_none_or(|idx| addr.ifindex() == idx)
+            && self.matches_address(addr, addr.label())
+    }
+
+    fn matches_address(&self, addr: &AddressMessage, label: Option<&str>) -> bool {
+        self.family.is_none_or(|f| addr.family() == f.as_u8())
+            && self.scope.is_none_or(|s| addr.scope() == s)
+            && self
+                .label
+                .as_deref()
+                .is_none_or(|p| label.is_some_and(|l| crate::util::glob_match(p, l)))
+            && self.permanent.is_none_or(|p| addr.is_permanent() == p)
+            && self.tentative.is_none_or(|t| addr.is_tentative() == t)
+            && self.dad_failed.is_none_or(|d| addr.is_dad_failed() == d)
+    }
+}
+
+impl From<crate::AddressFamily> for AddressFilter {
+    fn from(family: crate::AddressFamily) -> Self {
+        Self::new().family(family)
+    }
+}
+
 impl Connection<Route> {
     /// Add an IP address to an interface.
     ///
@@ -664,17 +907,19 @@ impl Connection<Route> {
     /// ).await?;
     /// ```
     pub async fn add_address<A: AddressConfig>(&self, config: A) -> Result<()> {
-        let ifindex = self.resolve_interface(config.interface_ref()).await?;
-
-        let mut builder = MessageBuilder::new(
-            NlMsgType::RTM_NEWADDR,
-            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
-        );
-
-        config.write_add(&mut builder, ifindex)?;
-        self.send_ack(builder)
-            .await
-            .map_err(|e| e.with_context("add_address"))
+        self.with_interface(config.interface_ref(), |ifindex| {
+            let config = &config;
+            async move {
+                let mut builder = MessageBuilder::new(
+                    NlMsgType::RTM_NEWADDR,
+                    NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
+                );
+                config.write_add(&mut builder, ifindex)?;
+                self.send_ack(builder).await
+            }
+        })
+        .await
+        .map_err(|e| e.with_context("add_address"))
     }
 
     /// Delete an IP address from an interface.
@@ -691,9 +936,10 @@ impl Connection<Route> {
         address: IpAddr,
         prefix_len: u8,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.del_address_by_index(ifindex, address, prefix_len)
-            .await
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.del_address_by_index(ifindex, address, prefix_len)
+        })
+        .await
     }
 
     /// Delete an IP address if present. Returns `Ok(true)` if the
@@ -809,9 +1055,10 @@ impl Connection<Route> {
         address: IpAddr,
         prefix_len: u8,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.add_address_by_index(ifindex, address, prefix_len)
-            .await
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.add_address_by_index(ifindex, address, prefix_len)
+        })
+        .await
     }
 
     /// Replace an IP address on an interface by name.
@@ -831,9 +1078,10 @@ impl Connection<Route> {
         address: IpAddr,
         prefix_len: u8,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.replace_address_by_index(ifindex, address, prefix_len)
-            .await
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.replace_address_by_index(ifindex, address, prefix_len)
+        })
+        .await
     }
 
     /// Delete an IPv4 address from an interface.
@@ -864,14 +1112,17 @@ impl Connection<Route> {
 
     /// Delete an IP address using a typed config.
     pub async fn del_address_config<A: AddressConfig>(&self, config: A) -> Result<()> {
-        let ifindex = self.resolve_interface(config.interface_ref()).await?;
-
-        let mut builder = MessageBuilder::new(NlMsgType::RTM_DELADDR, NLM_F_REQUEST | NLM_F_ACK);
-
-        config.write_delete(&mut builder, ifindex)?;
-        self.send_ack(builder)
-            .await
-            .map_err(|e| e.with_context("del_address"))
+        self.with_interface(config.interface_ref(), |ifindex| {
+            let config = &config;
+            async move {
+                let mut builder =
+                    MessageBuilder::new(NlMsgType::RTM_DELADDR, NLM_F_REQUEST | NLM_F_ACK);
+                config.write_delete(&mut builder, ifindex)?;
+                self.send_ack(builder).await
+            }
+        })
+        .await
+        .map_err(|e| e.with_context("del_address"))
     }
 
     /// Replace an IP address (add or update).
@@ -889,17 +1140,19 @@ impl Connection<Route> {
     /// ).await?;
     /// ```
     pub async fn replace_address<A: AddressConfig>(&self, config: A) -> Result<()> {
-        let ifindex = self.resolve_interface(config.interface_ref()).await?;
-
-        let mut builder = MessageBuilder::new(
-            NlMsgType::RTM_NEWADDR,
-            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE,
-        );
-
-        config.write_replace(&mut builder, ifindex)?;
-        self.send_ack(builder)
-            .await
-            .map_err(|e| e.with_context("replace_address"))
+        self.with_interface(config.interface_ref(), |ifindex| {
+            let config = &config;
+            async move {
+                let mut builder = MessageBuilder::new(
+                    NlMsgType::RTM_NEWADDR,
+                    NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE,
+                );
+                config.write_replace(&mut builder, ifindex)?;
+                self.send_ack(builder).await
+            }
+        })
+        .await
+        .map_err(|e| e.with_context("replace_address"))
     }
 
     /// Flush all addresses from an interface.
@@ -911,8 +1164,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "flush_addresses"))]
     pub async fn flush_addresses(&self, ifname: impl Into<InterfaceRef>) -> Result<()> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.flush_addresses_by_index(ifindex).await
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.flush_addresses_by_index(ifindex)
+        })
+        .await
     }
 
     /// Flush all addresses from an interface by index.
@@ -946,4 +1201,181 @@ impl Connection<Route> {
 
         Ok(())
     }
+
+    /// Get the addresses selected by `filter` (`ip address show ...`).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::addr::AddressFilter;
+    ///
+    /// // `ip address show dev eth0 dynamic`
+    /// let addrs = conn
+    ///     .get_addresses_filtered(&AddressFilter::new().interface("eth0").permanent(false))
+    ///     .await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_addresses_filtered"))]
+    pub async fn get_addresses_filtered(
+        &self,
+        filter: &AddressFilter,
+    ) -> Result<Vec<AddressMessage>> {
+        let ifindex = self
+            .resolve_interface_opt(filter.interface.as_ref())
+            .await?;
+
+        let mut msg = IfAddrMsg::new().with_index(ifindex.unwrap_or(0));
+        if let Some(family) = filter.family {
+            msg = msg.with_family(family.as_u8());
+        }
+        let mut builder = dump_request(NlMsgType::RTM_GETADDR);
+        builder.append(&msg);
+        let addresses: Vec<AddressMessage> = self.dump_typed_request(builder).await?;
+
+        // Link state is only needed for `up` and the label fallback.
+        let links: HashMap<u32, LinkMessage> = if filter.up || filter.label.is_some() {
+            let links = match ifindex {
+                Some(idx) => self.get_link_by_index(idx).await?.into_iter().collect(),
+                None => self.get_links().await?,
+            };
+            links.into_iter().map(|l| (l.ifindex(), l)).collect()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(addresses
+            .into_iter()
+            .filter(|a| {
+                let link = links.get(&a.ifindex());
+                // Kernels without strict checking ignore ifa_index.
+                ifindex.is_none_or(|idx| a.ifindex() == idx)
+                    && filter.matches_address(a, a.label().or(link.and_then(|l| l.name())))
+                    && (!filter.up || link.is_some_and(|l| l.is_up()))
+            })
+            .collect())
+    }
+
+    /// Get the IPv6 address label table (`ip addrlabel list`).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for entry in conn.get_addr_labels().await? {
+    ///     println!("{:?}/{} label {:?}", entry.address(), entry.prefix_len(), entry.label());
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_addr_labels"))]
+    pub async fn get_addr_labels(&self) -> Result<Vec<super::messages::AddrLabelMessage>> {
+        self.dump_typed(NlMsgType::RTM_GETADDRLABEL).await
+    }
+
+    /// Add an IPv6 address label entry. Fails with EEXIST if the prefix
+    /// (on the same device) already has one.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "add_addr_label"))]
+    pub async fn add_addr_label(&self, label: AddrLabel) -> Result<()> {
+        self.send_addr_label(
+            NlMsgType::RTM_NEWADDRLABEL,
+            NLM_F_CREATE | NLM_F_EXCL,
+            &label,
+        )
+        .await
+        .map_err(|e| e.with_context("add_addr_label"))
+    }
+
+    /// Add an IPv6 address label entry, replacing the prefix's label if
+    /// it already has one.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "replace_addr_label"))]
+    pub async fn replace_addr_label(&self, label: AddrLabel) -> Result<()> {
+        self.send_addr_label(
+            NlMsgType::RTM_NEWADDRLABEL,
+            NLM_F_CREATE | NLM_F_REPLACE,
+            &label,
+        )
+        .await
+        .map_err(|e| e.with_context("replace_addr_label"))
+    }
+
+    /// Delete an IPv6 address label entry. The entry is found by prefix
+    /// and device; the kernel requires a label but doesn't compare it.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "del_addr_label"))]
+    pub async fn del_addr_label(&self, label: AddrLabel) -> Result<()> {
+        self.send_addr_label(NlMsgType::RTM_DELADDRLABEL, 0, &label)
+            .await
+            .map_err(|e| e.with_context("del_addr_label"))
+    }
+
+    async fn send_addr_label(&self, msg_type: u16, flags: u16, label: &AddrLabel) -> Result<()> {
+        let ifindex = self
+            .resolve_interface_opt(label.interface.as_ref())
+            .await?
+            .unwrap_or(0);
+        let mut builder = MessageBuilder::new(msg_type, NLM_F_REQUEST | NLM_F_ACK | flags);
+        label.write_to(&mut builder, ifindex);
+        self.send_ack(builder).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::test_support::parse_attrs;
+
+    #[test]
+    fn addr_label_writes_header_and_attributes() {
+        let prefix: Ipv6Addr = "2001:db8::".parse().unwrap();
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_NEWADDRLABEL, 0);
+        AddrLabel::new(prefix, 32, 100).write_to(&mut builder, 4);
+
+        let bytes = builder.as_bytes();
+        let header = IfAddrLblMsg::from_bytes(&bytes[16..]).unwrap();
+        assert_eq!(header.ifal_family, AF_INET6);
+        assert_eq!(header.ifal_prefixlen, 32);
+        assert_eq!(header.ifal_index, 4);
+
+        let attrs = parse_attrs(&bytes[16 + IfAddrLblMsg::SIZE..]);
+        assert_eq!(attrs[&ifal::ADDRESS], prefix.octets());
+        assert_eq!(attrs[&ifal::LABEL], 100u32.to_ne_bytes());
+    }
+
+    #[test]
+    fn address_filter_selectors() {
+        use crate::netlink::messages::AddressMessageBuilder;
+
+        let addr = AddressMessageBuilder::new()
+            .ipv4()
+            .ifindex(2)
+            .scope(Scope::Universe)
+            .address(Ipv4Addr::new(10, 0, 0, 1).into())
+            .label("eth0:web")
+            .flags(ifa_flags::PERMANENT)
+            .build();
+
+        assert!(AddressFilter::new().matches(&addr));
+        assert!(AddressFilter::from(crate::AddressFamily::v4()).matches(&addr));
+        assert!(!AddressFilter::from(crate::AddressFamily::v6()).matches(&addr));
+        assert!(AddressFilter::new().interface(2u32).matches(&addr));
+        assert!(!AddressFilter::new().interface(3u32).matches(&addr));
+        assert!(!AddressFilter::new().scope(Scope::Link).matches(&addr));
+        assert!(AddressFilter::new().label("eth0:*").matches(&addr));
+        assert!(AddressFilter::new().label("eth?:w?b").matches(&addr));
+        assert!(!AddressFilter::new().label("eth0").matches(&addr));
+        assert!(AddressFilter::new().permanent(true).matches(&addr));
+        assert!(!AddressFilter::new().permanent(false).matches(&addr));
+        assert!(AddressFilter::new().tentative(false).matches(&addr));
+        assert!(!AddressFilter::new().dad_failed(true).matches(&addr));
+
+        let unlabelled = AddressMessageBuilder::new()
+            .ipv6()
+            .ifindex(2)
+            .flags(ifa_flags::TENTATIVE | ifa_flags::DADFAILED)
+            .build();
+        assert!(!AddressFilter::new().label("*").matches(&unlabelled));
+        assert!(
+            AddressFilter::new()
+                .label("eth*")
+                .matches_address(&unlabelled, Some("eth0"))
+        );
+        assert!(AddressFilter::new().permanent(false).matches(&unlabelled));
+        assert!(AddressFilter::new().tentative(true).matches(&unlabelled));
+        assert!(AddressFilter::new().dad_failed(true).matches(&unlabelled));
+    }
 }
@@ -18,6 +18,13 @@
 //!
 //! println!("{} succeeded, {} failed", results.success_count(), results.error_count());
 //! ```
+//!
+//! For large pushes, [`Batch::final_ack`] drops the per-message ACKs and
+//! asks for a single one at the end of each chunk, like `ip -batch`: the
+//! kernel still reports every failure, and its answer to the last message
+//! shows that all the ones before it were processed.
+
+use crate::util::ifname::{self, IfError};
 
 use super::{
     addr::AddressConfig,
@@ -25,16 +32,18 @@ use super::{
     connection::Connection,
     error::{Error, Result},
     fdb::FdbEntryBuilder,
+    interface_ref::InterfaceRef,
     link::LinkConfig,
     message::{
-        MessageIter, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST, NlMsgError, NlMsgType,
+        MessageIter, NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REPLACE, NLM_F_REQUEST,
+        NLMSG_HDRLEN, NlMsgError, NlMsgType,
     },
     neigh::NeighborConfig,
     protocol::Route,
-    route::RouteConfig,
+    route::{ResolvedRouteInterfaces, RouteConfig},
     tc::QdiscConfig,
     types::{
-        link::IfInfoMsg,
+        link::{IfInfoMsg, iff},
         tc::{TcMsg, TcaAttr, tc_handle},
     },
 };
@@ -70,11 +79,23 @@ const MAX_BATCH_SIZE: usize = 200 * 1024;
 pub struct Batch<'a> {
     conn: &'a Connection<Route>,
     ops: Vec<BatchOp>,
+    /// Ask for one ACK per chunk instead of one per op.
+    final_ack: bool,
 }
 
 struct BatchOp {
     seq: u32,
     msg: Vec<u8>,
+    /// Set when the op failed validation; it is reported without being sent.
+    rejected: Option<Rejection>,
+}
+
+/// Why an op was rejected before sending.
+enum Rejection {
+    /// Interface name failed [`ifname::validate`].
+    InvalidName(String),
+    /// Interface name didn't resolve to an ifindex.
+    NotFound(String),
 }
 
 impl<'a> Batch<'a> {
@@ -82,9 +103,38 @@ impl<'a> Batch<'a> {
         Self {
             conn,
             ops: Vec::new(),
+            final_ack: false,
         }
     }
 
+    /// Add a pre-built request to the batch.
+    ///
+    /// For operations without a dedicated method. `NLM_F_REQUEST` and
+    /// `NLM_F_ACK` are added to the builder's flags, and the sequence
+    /// number and port ID are overwritten. The request must not be a
+    /// dump: only its ACK or error is read back.
+    pub fn request(mut self, builder: MessageBuilder) -> Self {
+        self.push(builder);
+        if let Some(op) = self.ops.last_mut() {
+            set_flags(&mut op.msg, |f| f | NLM_F_REQUEST | NLM_F_ACK);
+        }
+        self
+    }
+
+    /// Send the ops without per-message ACKs and end each chunk with an
+    /// `NLMSG_NOOP` that asks for one.
+    ///
+    /// The kernel answers a message without `NLM_F_ACK` only when it
+    /// fails, and processes a chunk in order, so once the final ACK
+    /// arrives every op without an error has succeeded. A large push then
+    /// gets back only its failures instead of one ACK per op, which could
+    /// overrun the socket's receive buffer. Results are the same as
+    /// without it.
+    pub fn final_ack(mut self) -> Self {
+        self.final_ack = true;
+        self
+    }
+
     /// Add a route to the batch.
     ///
     /// Note: interface references must be pre-resolved to indices (use `dev_index()`
@@ -94,7 +144,21 @@ impl<'a> Batch<'a> {
             NlMsgType::RTM_NEWROUTE,
             NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
         );
-        config.write_add(&mut builder, &Default::default());
+        config.write_add(&mut builder, &route_interfaces(&config));
+        self.push(builder);
+        self
+    }
+
+    /// Add or replace a route in the batch (`ip route replace`).
+    ///
+    /// As with [`add_route`](Self::add_route), interfaces must be given by
+    /// index.
+    pub fn replace_route<R: RouteConfig>(mut self, config: R) -> Self {
+        let mut builder = MessageBuilder::new(
+            NlMsgType::RTM_NEWROUTE,
+            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE,
+        );
+        config.write_add(&mut builder, &route_interfaces(&config));
         self.push(builder);
         self
     }
@@ -102,7 +166,7 @@ impl<'a> Batch<'a> {
     /// Delete a route in the batch.
     pub fn del_route<R: RouteConfig>(mut self, config: R) -> Self {
         let mut builder = MessageBuilder::new(NlMsgType::RTM_DELROUTE, NLM_F_REQUEST | NLM_F_ACK);
-        config.write_delete(&mut builder);
+        config.write_delete_with_interfaces(&mut builder, &route_interfaces(&config));
         self.push(builder);
         self
     }
@@ -111,7 +175,15 @@ impl<'a> Batch<'a> {
     ///
     /// Note: Only link types without parent references (DummyLink, IfbLink, etc.)
     /// work in batch mode. Types with parent references need async resolution.
+    ///
+    /// Invalid interface names are not sent; the op's result is the
+    /// [`IfError::InvalidName`] from [`ifname::validate`].
     pub fn add_link<L: LinkConfig>(mut self, config: L) -> Self {
+        let names = std::iter::once(config.name()).chain(config.peer_name());
+        if let Some(err) = names.map(ifname::validate).find_map(|r| r.err()) {
+            self.reject(err);
+            return self;
+        }
         let mut builder = MessageBuilder::new(
             NlMsgType::RTM_NEWLINK,
             NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
@@ -119,6 +191,10 @@ impl<'a> Batch<'a> {
         let ifinfo = IfInfoMsg::new();
         builder.append(&ifinfo);
         config.write_to(&mut builder, None);
+        self.conn.forget_ifname(config.name());
+        if let Some(peer) = config.peer_name() {
+            self.conn.forget_ifname(peer);
+        }
         self.push(builder);
         self
     }
@@ -133,6 +209,28 @@ impl<'a> Batch<'a> {
         self
     }
 
+    /// Bring a link up or down by index in the batch.
+    pub fn set_link_state_by_index(mut self, ifindex: u32, up: bool) -> Self {
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_SETLINK, NLM_F_REQUEST | NLM_F_ACK);
+        let mut ifinfo = IfInfoMsg::new().with_index(ifindex as i32);
+        ifinfo.ifi_flags = if up { iff::UP } else { 0 };
+        ifinfo.ifi_change = iff::UP;
+        builder.append(&ifinfo);
+        self.push(builder);
+        self
+    }
+
+    /// Record an op for an interface name that didn't resolve; its result
+    /// is [`Error::InterfaceNotFound`] and nothing is sent.
+    pub(crate) fn missing_interface(mut self, name: &str) -> Self {
+        self.ops.push(BatchOp {
+            seq: 0,
+            msg: Vec::new(),
+            rejected: Some(Rejection::NotFound(name.to_string())),
+        });
+        self
+    }
+
     /// Add an address in the batch.
     ///
     /// Note: Use address types with pre-resolved indices (e.g., `Ipv4Address::with_index()`).
@@ -211,7 +309,7 @@ impl<'a> Batch<'a> {
     pub fn add_qdisc(mut self, ifindex: u32, config: impl QdiscConfig) -> Self {
         let mut builder = MessageBuilder::new(
             NlMsgType::RTM_NEWQDISC,
-            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE,
+            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
         );
         let tcmsg = TcMsg::new()
             .with_ifindex(ifindex as i32)
@@ -244,7 +342,23 @@ impl<'a> Batch<'a> {
         builder.set_seq(seq);
         builder.set_pid(self.conn.socket().pid());
         let msg = builder.finish();
-        self.ops.push(BatchOp { seq, msg });
+        self.ops.push(BatchOp {
+            seq,
+            msg,
+            rejected: None,
+        });
+    }
+
+    fn reject(&mut self, err: IfError) {
+        let reason = match err {
+            IfError::InvalidName(reason) => reason,
+            other => other.to_string(),
+        };
+        self.ops.push(BatchOp {
+            seq: 0,
+            msg: Vec::new(),
+            rejected: Some(Rejection::InvalidName(reason)),
+        });
     }
 
     /// Number of buffered operations.
@@ -314,14 +428,38 @@ impl<'a> Batch<'a> {
         // ops' seqs onto one channel so the driver routes every ACK here
         // instead of the loop racing its recv_msg. Built BEFORE the send
         // (and the with_timeout wrapper) so it spans the timeout window.
-        let seqs: Vec<u32> = ops.iter().map(|o| o.seq).collect();
+        let sent = || ops.iter().filter(|o| o.rejected.is_none());
+        let mut seqs: Vec<u32> = sent().map(|o| o.seq).collect();
+        if seqs.is_empty() {
+            return Ok(ops.iter().map(BatchOp::rejection).collect());
+        }
+        // In final-ACK mode the chunk ends with a NOOP whose ACK is the
+        // only one we wait for.
+        let sentinel = self.final_ack.then(|| {
+            let mut builder = MessageBuilder::new(NlMsgType::NOOP, NLM_F_REQUEST | NLM_F_ACK);
+            let seq = self.conn.socket().next_seq();
+            builder.set_seq(seq);
+            builder.set_pid(self.conn.socket().pid());
+            (seq, builder.finish())
+        });
+        if let Some((seq, _)) = &sentinel {
+            seqs.push(*seq);
+        }
         let mut session = self.conn.recv_session_multi(&seqs).await;
         // Concatenate messages into a single buffer
-        let total_size: usize = ops.iter().map(|o| o.msg.len()).sum();
-        let mut buf = Vec::with_capacity(total_size);
-        for op in ops {
+        let total_size: usize = sent().map(|o| o.msg.len()).sum();
+        let mut buf = Vec::with_capacity(total_size + NLMSG_HDRLEN);
+        for op in sent() {
+            let start = buf.len();
             buf.extend_from_slice(&op.msg);
+            if sentinel.is_some() {
+                set_flags(&mut buf[start..], |f| f & !NLM_F_ACK);
+            }
         }
+        if let Some((_, msg)) = &sentinel {
+            buf.extend_from_slice(msg);
+        }
+        let sentinel_seq = sentinel.map(|(seq, _)| seq);
 
         // Single sendmsg()
         self.conn.socket().send(&buf).await?;
@@ -335,9 +473,15 @@ impl<'a> Batch<'a> {
         // block forever.
         self.conn
             .with_timeout(async move {
-                let mut results: Vec<Option<std::result::Result<(), Error>>> =
-                    (0..ops.len()).map(|_| None).collect();
-                let mut remaining = ops.len();
+                let mut results: Vec<Option<std::result::Result<(), Error>>> = ops
+                    .iter()
+                    .map(|op| op.rejected.is_some().then(|| op.rejection()))
+                    .collect();
+                let mut remaining = if sentinel_seq.is_some() {
+                    1
+                } else {
+                    seqs.len()
+                };
 
                 while remaining > 0 {
                     let response = session.recv(self.conn).await?;
@@ -345,8 +489,18 @@ impl<'a> Batch<'a> {
                     for result in MessageIter::new(&response) {
                         let (header, payload) = result?;
 
+                        if Some(header.nlmsg_seq) == sentinel_seq {
+                            if header.is_error() {
+                                remaining = 0;
+                            }
+                            continue;
+                        }
+
                         // Find which op this ACK belongs to (per-op seq match).
-                        if let Some(idx) = ops.iter().position(|op| op.seq == header.nlmsg_seq) {
+                        if let Some(idx) = ops
+                            .iter()
+                            .position(|op| op.rejected.is_none() && op.seq == header.nlmsg_seq)
+                        {
                             if results[idx].is_some() {
                                 continue; // Already got this one — kernel duplicate
                             }
@@ -358,18 +512,53 @@ impl<'a> Batch<'a> {
                                 } else {
                                     results[idx] = Some(Err(err.into_error(payload)));
                                 }
-                                remaining -= 1;
+                                if sentinel_seq.is_none() {
+                                    remaining -= 1;
+                                }
                             }
                         }
                     }
                 }
 
+                // In final-ACK mode an op without an answer succeeded.
                 Ok(results.into_iter().map(|r| r.unwrap_or(Ok(()))).collect())
             })
             .await
     }
 }
 
+/// The interfaces of a route that are given by index. Names can't be
+/// resolved here and are left out.
+fn route_interfaces<R: RouteConfig>(config: &R) -> ResolvedRouteInterfaces {
+    ResolvedRouteInterfaces {
+        oif: config.device_ref().and_then(InterfaceRef::as_index),
+        multipath: config
+            .multipath_device_refs()
+            .into_iter()
+            .map(|r| r.and_then(InterfaceRef::as_index))
+            .collect(),
+    }
+}
+
+/// Rewrite `nlmsg_flags` in the header at the start of `msg`.
+fn set_flags(msg: &mut [u8], f: impl FnOnce(u16) -> u16) {
+    let flags = f(u16::from_ne_bytes([msg[6], msg[7]]));
+    msg[6..8].copy_from_slice(&flags.to_ne_bytes());
+}
+
+impl BatchOp {
+    /// The result reported for an op that was rejected before sending.
+    fn rejection(&self) -> std::result::Result<(), Error> {
+        match &self.rejected {
+            Some(Rejection::InvalidName(reason)) => {
+                Err(Error::Interface(IfError::InvalidName(reason.clone())))
+            }
+            Some(Rejection::NotFound(name)) => Err(Error::interface_not_found(name.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Results from a batch execution.
 ///
 /// Contains one `Result<()>` per operation in submission order.
@@ -467,6 +656,63 @@ mod tests {
         assert!(errors[1].1.is_permission_denied());
     }
 
+    #[tokio::test]
+    async fn test_invalid_link_name_rejected_before_send() {
+        use crate::netlink::link::{DummyLink, VethLink};
+
+        let conn = Connection::<Route>::new().unwrap();
+        let results = conn
+            .batch()
+            .add_link(DummyLink::new("eth0:1"))
+            .add_link(VethLink::new("v0", "much-too-long-peer"))
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(results.error_count(), 2);
+        for (_, err) in results.errors() {
+            assert!(
+                matches!(err, Error::Interface(IfError::InvalidName(_))),
+                "{err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_links_unknown_name_reported_per_entry() {
+        let conn = Connection::<Route>::new().unwrap();
+        let results = conn
+            .set_links_down(&["nlink-nosuch0", "nlink-nosuch1"])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        for (_, err) in results.errors() {
+            assert!(
+                matches!(err, Error::InterfaceNotFound { name } if name.starts_with("nlink-nosuch")),
+                "{err}"
+            );
+        }
+        assert_eq!(results.error_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_route_dev_index_and_request_flags() {
+        use crate::netlink::route::Ipv4Route;
+
+        let conn = Connection::<Route>::new().unwrap();
+        let batch = conn
+            .batch()
+            .add_route(Ipv4Route::new("10.0.0.0", 8).dev_index(7))
+            .request(MessageBuilder::new(NlMsgType::RTM_NEWROUTE, 0));
+
+        // RTA_OIF = 7
+        let oif = [8u8, 0, 4, 0, 7, 0, 0, 0];
+        assert!(batch.ops[0].msg.windows(8).any(|w| w == oif));
+        let flags = u16::from_ne_bytes([batch.ops[1].msg[6], batch.ops[1].msg[7]]);
+        assert_eq!(flags, NLM_F_REQUEST | NLM_F_ACK);
+    }
+
     #[test]
     fn test_all_errors() {
         let r = make_results(vec![
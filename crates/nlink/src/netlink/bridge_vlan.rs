@@ -65,9 +65,9 @@ use super::{
     message::{MessageIter, NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST, NlMsgType},
     protocol::Route,
     types::link::{
-        BridgeVlanInfo, BrVlanMsg, IfInfoMsg, IflaAttr, br_state, bridge_af, bridge_vlan_flags,
-        bridge_vlan_tunnel, bridge_vlandb, bridge_vlandb_dump, bridge_vlandb_entry,
-        bridge_vlandb_gopts, rtext_filter,
+        BridgeVlanInfo, BrVlanMsg, IfInfoMsg, IflaAttr, br_state, bridge_af, bridge_flags,
+        bridge_vlan_flags, bridge_vlan_tunnel, bridge_vlandb, bridge_vlandb_dump,
+        bridge_vlandb_entry, bridge_vlandb_gopts, bridge_vlandb_stats, rtext_filter,
     },
 };
 
@@ -316,6 +316,10 @@ impl BridgeVlanTunnelBuilder {
 
 /// Builder for bridge VLAN operations.
 ///
+/// A single builder can carry several VIDs and VID ranges; they are all
+/// sent in one `RTM_SETLINK`/`RTM_DELLINK` message, each range encoded as
+/// a `RANGE_BEGIN`/`RANGE_END` pair of `IFLA_BRIDGE_VLAN_INFO` entries.
+///
 /// # Example
 ///
 /// ```ignore
@@ -331,25 +335,37 @@ impl BridgeVlanTunnelBuilder {
 /// let range_config = BridgeVlanBuilder::new(200)
 ///     .dev("eth0")
 ///     .range(210);
+///
+/// // Add VLANs 10, 20 and 300-400 to the bridge device itself, in one message
+/// let batch = BridgeVlanBuilder::new(10)
+///     .dev("br0")
+///     .vid(20)
+///     .vid_range(300, 400)
+///     .self_();
 /// ```
 #[derive(Debug, Clone, Default)]
 #[must_use = "builders do nothing unless used"]
 pub struct BridgeVlanBuilder {
     dev: Option<InterfaceRef>,
-    vid: u16,
-    vid_end: Option<u16>,
+    /// Inclusive `(start, end)` VID ranges; a single VID has `start == end`.
+    vids: Vec<(u16, u16)>,
     pvid: bool,
     untagged: bool,
     master: bool,
+    self_flag: bool,
+    master_flag: bool,
 }
 
 impl BridgeVlanBuilder {
+    /// Highest usable VLAN ID (4095 is reserved).
+    pub const MAX_VID: u16 = 4094;
+
     /// Create a new VLAN builder for a single VID.
     ///
     /// VID must be in range 1-4094.
     pub fn new(vid: u16) -> Self {
         Self {
-            vid,
+            vids: vec![(vid, vid)],
             ..Default::default()
         }
     }
@@ -376,16 +392,38 @@ impl BridgeVlanBuilder {
 
     /// Set VLAN range end (for bulk operations).
     ///
-    /// When set, VLANs from `vid` to `vid_end` (inclusive) will be added/deleted.
+    /// Extends the most recently added VID into a range: after
+    /// `new(vid).range(vid_end)`, VLANs from `vid` to `vid_end`
+    /// (inclusive) will be added/deleted.
     pub fn range(mut self, vid_end: u16) -> Self {
-        self.vid_end = Some(vid_end);
+        if let Some(last) = self.vids.last_mut() {
+            last.1 = vid_end;
+        }
+        self
+    }
+
+    /// Add another single VID to the same message.
+    pub fn vid(mut self, vid: u16) -> Self {
+        self.vids.push((vid, vid));
         self
     }
 
+    /// Add another inclusive VID range to the same message.
+    pub fn vid_range(mut self, start: u16, end: u16) -> Self {
+        self.vids.push((start, end));
+        self
+    }
+
+    /// The inclusive `(start, end)` VID ranges this builder will program.
+    pub fn vids(&self) -> &[(u16, u16)] {
+        &self.vids
+    }
+
     /// Mark as PVID (ingress untagged default).
     ///
     /// Untagged frames arriving on this port will be assigned this VLAN.
-    /// Only one VLAN per port can be the PVID.
+    /// Only one VLAN per port can be the PVID, so this is rejected when
+    /// the builder carries more than a single VID.
     pub fn pvid(mut self) -> Self {
         self.pvid = true;
         self
@@ -399,14 +437,35 @@ impl BridgeVlanBuilder {
         self
     }
 
-    /// Apply to bridge device itself (global VLAN entry).
+    /// Also create the bridge's global VLAN entry (`BRIDGE_VLAN_INFO_MASTER`).
     ///
-    /// This creates a global VLAN entry on the bridge rather than a port-specific one.
+    /// When adding a VLAN to a port, the kernel additionally creates the
+    /// matching per-VLAN entry on the bridge device itself.
     pub fn master(mut self) -> Self {
         self.master = true;
         self
     }
 
+    /// Program the device's own VLAN table (`BRIDGE_FLAGS_SELF`).
+    ///
+    /// Required to configure VLANs on the bridge device itself (`bridge
+    /// vlan add dev br0 vid 10 self`) or on a switchdev port driver
+    /// rather than on its master bridge.
+    pub fn self_(mut self) -> Self {
+        self.self_flag = true;
+        self
+    }
+
+    /// Send the request to the port's master bridge (`BRIDGE_FLAGS_MASTER`).
+    ///
+    /// This is the kernel's default when no target flag is given; set it
+    /// explicitly together with [`self_`](Self::self_) to program both the
+    /// device and its master in one request.
+    pub fn via_master(mut self) -> Self {
+        self.master_flag = true;
+        self
+    }
+
     /// Build the raw flags value.
     fn build_flags(&self) -> u16 {
         let mut flags = 0u16;
@@ -422,17 +481,61 @@ impl BridgeVlanBuilder {
         flags
     }
 
+    /// Build the `IFLA_BRIDGE_FLAGS` value (0 if no target flag is set).
+    fn build_bridge_flags(&self) -> u16 {
+        let mut flags = 0u16;
+        if self.master_flag {
+            flags |= bridge_flags::MASTER;
+        }
+        if self.self_flag {
+            flags |= bridge_flags::SELF;
+        }
+        flags
+    }
+
+    /// Check VID bounds, range ordering and PVID placement.
+    fn validate(&self) -> Result<()> {
+        if self.vids.is_empty() {
+            return Err(Error::InvalidMessage(
+                "at least one VLAN ID required".into(),
+            ));
+        }
+        for &(start, end) in &self.vids {
+            for vid in [start, end] {
+                if vid == 0 || vid > Self::MAX_VID {
+                    return Err(Error::InvalidMessage(format!(
+                        "VLAN ID {vid} out of range 1-{}",
+                        Self::MAX_VID
+                    )));
+                }
+            }
+            if start > end {
+                return Err(Error::InvalidMessage(format!(
+                    "VLAN range {start}-{end}: start must be <= end"
+                )));
+            }
+        }
+        if self.pvid && (self.vids.len() > 1 || self.vids[0].0 != self.vids[0].1) {
+            return Err(Error::InvalidMessage(
+                "pvid can only be set on a single VLAN ID".into(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Write netlink message for adding VLAN.
-    pub(crate) fn write_add(&self, builder: &mut MessageBuilder, ifindex: u32) {
-        self.write_message(builder, ifindex);
+    pub(crate) fn write_add(&self, builder: &mut MessageBuilder, ifindex: u32) -> Result<()> {
+        self.write_message(builder, ifindex)
     }
 
     /// Write netlink message for deleting VLAN.
-    pub(crate) fn write_del(&self, builder: &mut MessageBuilder, ifindex: u32) {
-        self.write_message(builder, ifindex);
+    pub(crate) fn write_del(&self, builder: &mut MessageBuilder, ifindex: u32) -> Result<()> {
+        self.write_message(builder, ifindex)
     }
 
-    fn write_message(&self, builder: &mut MessageBuilder, ifindex: u32) {
+    fn write_message(&self, builder: &mut MessageBuilder, ifindex: u32) -> Result<()> {
+        self.validate()?;
+
         // Use AF_BRIDGE family
         let ifinfo = IfInfoMsg::new()
             .with_family(libc::AF_BRIDGE as u8)
@@ -442,27 +545,31 @@ impl BridgeVlanBuilder {
         // IFLA_AF_SPEC containing VLAN info
         let af_spec = builder.nest_start(IflaAttr::AfSpec as u16);
 
-        if let Some(vid_end) = self.vid_end {
-            // Range operation: two entries with RANGE_BEGIN and RANGE_END flags
-            let mut begin_flags = self.build_flags();
-            begin_flags |= bridge_vlan_flags::RANGE_BEGIN;
-
-            let vlan_begin = BridgeVlanInfo::new(self.vid).with_flags(begin_flags);
-            builder.append_attr(bridge_af::IFLA_BRIDGE_VLAN_INFO, vlan_begin.as_bytes());
-
-            let mut end_flags = self.build_flags();
-            end_flags |= bridge_vlan_flags::RANGE_END;
+        let bridge_flags = self.build_bridge_flags();
+        if bridge_flags != 0 {
+            builder.append_attr_u16(bridge_af::IFLA_BRIDGE_FLAGS, bridge_flags);
+        }
 
-            let vlan_end = BridgeVlanInfo::new(vid_end).with_flags(end_flags);
-            builder.append_attr(bridge_af::IFLA_BRIDGE_VLAN_INFO, vlan_end.as_bytes());
-        } else {
-            // Single VLAN
-            let flags = self.build_flags();
-            let vlan_info = BridgeVlanInfo::new(self.vid).with_flags(flags);
-            builder.append_attr(bridge_af::IFLA_BRIDGE_VLAN_INFO, vlan_info.as_bytes());
+        let flags = self.build_flags();
+        for &(start, end) in &self.vids {
+            if start == end {
+                let vlan_info = BridgeVlanInfo::new(start).with_flags(flags);
+                builder.append_attr(bridge_af::IFLA_BRIDGE_VLAN_INFO, vlan_info.as_bytes());
+            } else {
+                // Range: two entries with RANGE_BEGIN and RANGE_END flags
+                let vlan_begin =
+                    BridgeVlanInfo::new(start).with_flags(flags | bridge_vlan_flags::RANGE_BEGIN);
+                builder.append_attr(bridge_af::IFLA_BRIDGE_VLAN_INFO, vlan_begin.as_bytes());
+
+                let vlan_end =
+                    BridgeVlanInfo::new(end).with_flags(flags | bridge_vlan_flags::RANGE_END);
+                builder.append_attr(bridge_af::IFLA_BRIDGE_VLAN_INFO, vlan_end.as_bytes());
+            }
         }
 
         builder.nest_end(af_spec);
+
+        Ok(())
     }
 }
 
@@ -948,6 +1055,9 @@ impl BridgeVlanEntryOptionsBuilder {
     }
 
     /// Enable or disable neighbour suppression for this VLAN.
+    ///
+    /// Only consulted when the port has per-VLAN suppression enabled
+    /// ([`BridgePortConfig::neigh_vlan_suppress`](crate::netlink::link::BridgePortConfig::neigh_vlan_suppress)).
     pub fn neigh_suppress(mut self, on: bool) -> Self {
         self.neigh_suppress = Some(on);
         self
@@ -996,11 +1106,13 @@ pub struct BridgeVlanEntryOptions {
     pub(crate) ifindex: u32,
     pub(crate) vid: u16,
     pub(crate) vid_end: Option<u16>,
+    pub(crate) flags: BridgeVlanFlags,
     pub(crate) state: Option<BridgeVlanState>,
     pub(crate) mcast_router: Option<u8>,
     pub(crate) mcast_n_groups: Option<u32>,
     pub(crate) mcast_max_groups: Option<u32>,
     pub(crate) neigh_suppress: Option<bool>,
+    pub(crate) stats: Option<BridgeVlanStats>,
 }
 
 impl BridgeVlanEntryOptions {
@@ -1019,6 +1131,11 @@ impl BridgeVlanEntryOptions {
         self.vid_end
     }
 
+    /// Membership flags (PVID / untagged) of this VLAN on the port.
+    pub fn flags(&self) -> BridgeVlanFlags {
+        self.flags
+    }
+
     /// Per-VLAN STP state.
     pub fn state(&self) -> Option<BridgeVlanState> {
         self.state
@@ -1043,6 +1160,53 @@ impl BridgeVlanEntryOptions {
     pub fn neigh_suppress(&self) -> Option<bool> {
         self.neigh_suppress
     }
+
+    /// Per-VLAN traffic counters; only present in entries returned by
+    /// [`Connection::get_bridge_vlan_stats`] and friends.
+    pub fn stats(&self) -> Option<&BridgeVlanStats> {
+        self.stats.as_ref()
+    }
+}
+
+/// Per-VLAN traffic counters (`BRIDGE_VLANDB_ENTRY_STATS`).
+///
+/// On the bridge device these count traffic of the bridge's own VLAN
+/// entry; on a port they count that port's traffic in the VLAN. Unless
+/// the bridge has `vlan_stats_per_port` enabled, ports share the
+/// bridge-wide counters of each VLAN.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BridgeVlanStats {
+    /// Received bytes.
+    pub rx_bytes: u64,
+    /// Received packets.
+    pub rx_packets: u64,
+    /// Transmitted bytes.
+    pub tx_bytes: u64,
+    /// Transmitted packets.
+    pub tx_packets: u64,
+}
+
+impl BridgeVlanStats {
+    /// Parse a `BRIDGE_VLANDB_ENTRY_STATS` nest.
+    fn parse(payload: &[u8]) -> Self {
+        let mut stats = Self::default();
+        for (attr, data) in AttrIter::new(payload) {
+            let Some(value) = data
+                .get(..8)
+                .map(|b| u64::from_ne_bytes(b.try_into().unwrap()))
+            else {
+                continue;
+            };
+            match attr {
+                bridge_vlandb_stats::RX_BYTES => stats.rx_bytes = value,
+                bridge_vlandb_stats::RX_PACKETS => stats.rx_packets = value,
+                bridge_vlandb_stats::TX_BYTES => stats.tx_bytes = value,
+                bridge_vlandb_stats::TX_PACKETS => stats.tx_packets = value,
+                _ => {}
+            }
+        }
+        stats
+    }
 }
 
 // ============================================================================
@@ -1091,8 +1255,10 @@ impl Connection<Route> {
         &self,
         dev: impl Into<InterfaceRef>,
     ) -> Result<Vec<BridgeVlanEntry>> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.get_bridge_vlans_by_index(ifindex).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.get_bridge_vlans_by_index(ifindex)
+        })
+        .await
     }
 
     /// Get VLAN configuration for a bridge port by interface index.
@@ -1218,12 +1384,39 @@ impl Connection<Route> {
     pub async fn add_bridge_vlan(&self, config: BridgeVlanBuilder) -> Result<()> {
         let ifindex = self.resolve_bridge_vlan_interface(&config).await?;
         let mut builder = MessageBuilder::new(NlMsgType::RTM_SETLINK, NLM_F_REQUEST | NLM_F_ACK);
-        config.write_add(&mut builder, ifindex);
+        config.write_add(&mut builder, ifindex)?;
         self.send_ack(builder)
             .await
             .map_err(|e| e.with_context("add_bridge_vlan"))
     }
 
+    /// Delete every VLAN and range carried by a builder in one request.
+    ///
+    /// Only the VIDs and the `self_`/`via_master` target flags matter for
+    /// deletion; `pvid`/`untagged` are ignored by the kernel.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::bridge_vlan::BridgeVlanBuilder;
+    ///
+    /// conn.del_bridge_vlans(
+    ///     BridgeVlanBuilder::new(10)
+    ///         .dev("eth0")
+    ///         .vid(20)
+    ///         .vid_range(300, 400)
+    /// ).await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "del_bridge_vlans"))]
+    pub async fn del_bridge_vlans(&self, config: BridgeVlanBuilder) -> Result<()> {
+        let ifindex = self.resolve_bridge_vlan_interface(&config).await?;
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_DELLINK, NLM_F_REQUEST | NLM_F_ACK);
+        config.write_del(&mut builder, ifindex)?;
+        self.send_ack(builder)
+            .await
+            .map_err(|e| e.with_context("del_bridge_vlans"))
+    }
+
     /// Delete VLAN from a bridge port.
     ///
     /// # Example
@@ -1233,8 +1426,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "del_bridge_vlan"))]
     pub async fn del_bridge_vlan(&self, dev: impl Into<InterfaceRef>, vid: u16) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.del_bridge_vlan_by_index(ifindex, vid).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.del_bridge_vlan_by_index(ifindex, vid)
+        })
+        .await
     }
 
     /// Delete VLAN from a bridge port by interface index.
@@ -1242,7 +1437,7 @@ impl Connection<Route> {
     pub async fn del_bridge_vlan_by_index(&self, ifindex: u32, vid: u16) -> Result<()> {
         let config = BridgeVlanBuilder::new(vid).ifindex(ifindex);
         let mut builder = MessageBuilder::new(NlMsgType::RTM_DELLINK, NLM_F_REQUEST | NLM_F_ACK);
-        config.write_del(&mut builder, ifindex);
+        config.write_del(&mut builder, ifindex)?;
         self.send_ack(builder)
             .await
             .map_err(|e| e.with_context("del_bridge_vlan"))
@@ -1267,7 +1462,7 @@ impl Connection<Route> {
             .ifindex(ifindex)
             .range(vid_end);
         let mut builder = MessageBuilder::new(NlMsgType::RTM_DELLINK, NLM_F_REQUEST | NLM_F_ACK);
-        config.write_del(&mut builder, ifindex);
+        config.write_del(&mut builder, ifindex)?;
         self.send_ack(builder)
             .await
             .map_err(|e| e.with_context("del_bridge_vlan_range"))
@@ -1372,8 +1567,10 @@ impl Connection<Route> {
         &self,
         dev: impl Into<InterfaceRef>,
     ) -> Result<Vec<BridgeVlanTunnelEntry>> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.get_vlan_tunnels_by_index(ifindex).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.get_vlan_tunnels_by_index(ifindex)
+        })
+        .await
     }
 
     /// Get VLAN-to-tunnel ID mappings by interface index.
@@ -1443,8 +1640,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "del_vlan_tunnel"))]
     pub async fn del_vlan_tunnel(&self, dev: impl Into<InterfaceRef>, vid: u16) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.del_vlan_tunnel_by_index(ifindex, vid).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.del_vlan_tunnel_by_index(ifindex, vid)
+        })
+        .await
     }
 
     /// Delete VLAN-to-tunnel ID mapping by interface index.
@@ -1546,8 +1745,10 @@ impl Connection<Route> {
         &self,
         bridge: impl Into<InterfaceRef>,
     ) -> Result<Vec<BridgeVlanGlobalOptions>> {
-        let ifindex = self.resolve_interface(&bridge.into()).await?;
-        self.get_bridge_vlan_global_options_by_index(ifindex).await
+        self.with_interface(&bridge.into(), |ifindex| {
+            self.get_bridge_vlan_global_options_by_index(ifindex)
+        })
+        .await
     }
 
     /// Get bridge-global per-VLAN options by bridge interface index.
@@ -1638,8 +1839,10 @@ impl Connection<Route> {
         &self,
         dev: impl Into<InterfaceRef>,
     ) -> Result<Vec<BridgeVlanEntryOptions>> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.get_bridge_vlan_entry_options_by_index(ifindex).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.get_bridge_vlan_entry_options_by_index(ifindex)
+        })
+        .await
     }
 
     /// Get per-VLAN entry options for a bridge port by interface index.
@@ -1654,18 +1857,95 @@ impl Connection<Route> {
         &self,
         ifindex: u32,
     ) -> Result<Vec<BridgeVlanEntryOptions>> {
-        // RTM_GETVLAN dump (no DUMPF_GLOBAL → per-VLAN ENTRY blocks),
-        // filtered to this port.
+        self.dump_bridge_vlan_entries(ifindex, 0, "get_bridge_vlan_entry_options")
+            .await
+    }
+
+    /// Get per-VLAN traffic counters for a bridge or bridge port.
+    ///
+    /// Returns one [`BridgeVlanEntryOptions`] per VLAN with
+    /// [`stats`](BridgeVlanEntryOptions::stats) filled in. The kernel
+    /// never collapses VLANs into ranges in a stats dump. This is the
+    /// data behind `bridge -s vlan show`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for v in conn.get_bridge_vlan_stats("br0").await? {
+    ///     if let Some(s) = v.stats() {
+    ///         println!("VLAN {}: rx {} pkts, tx {} pkts", v.vid(), s.rx_packets, s.tx_packets);
+    ///     }
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_bridge_vlan_stats"))]
+    pub async fn get_bridge_vlan_stats(
+        &self,
+        dev: impl Into<InterfaceRef>,
+    ) -> Result<Vec<BridgeVlanEntryOptions>> {
+        self.with_interface(&dev.into(), |ifindex| {
+            self.get_bridge_vlan_stats_by_index(ifindex)
+        })
+        .await
+    }
+
+    /// Get per-VLAN traffic counters by interface index.
+    ///
+    /// Use this method when operating in a network namespace.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "get_bridge_vlan_stats_by_index")
+    )]
+    pub async fn get_bridge_vlan_stats_by_index(
+        &self,
+        ifindex: u32,
+    ) -> Result<Vec<BridgeVlanEntryOptions>> {
+        self.dump_bridge_vlan_entries(
+            ifindex,
+            bridge_vlandb_dump::DUMPF_STATS,
+            "get_bridge_vlan_stats",
+        )
+        .await
+    }
+
+    /// Get per-VLAN traffic counters for every bridge and bridge port in
+    /// the namespace.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "get_all_bridge_vlan_stats")
+    )]
+    pub async fn get_all_bridge_vlan_stats(&self) -> Result<Vec<BridgeVlanEntryOptions>> {
+        // ifindex 0 asks the kernel to walk every bridge device and port.
+        self.dump_bridge_vlan_entries(
+            0,
+            bridge_vlandb_dump::DUMPF_STATS,
+            "get_all_bridge_vlan_stats",
+        )
+        .await
+    }
+
+    /// `RTM_GETVLAN` dump of per-VLAN `ENTRY` blocks (no `DUMPF_GLOBAL`)
+    /// for `ifindex` (0 = all devices), with extra dump `flags`.
+    async fn dump_bridge_vlan_entries(
+        &self,
+        ifindex: u32,
+        flags: u32,
+        context: &'static str,
+    ) -> Result<Vec<BridgeVlanEntryOptions>> {
         let mut builder = MessageBuilder::new(NlMsgType::RTM_GETVLAN, NLM_F_REQUEST | NLM_F_DUMP);
         let msg = BrVlanMsg::new()
             .with_family(libc::AF_BRIDGE as u8)
             .with_index(ifindex);
         builder.append(&msg);
+        if flags != 0 {
+            builder.append_attr_u32(bridge_vlandb_dump::FLAGS, flags);
+        }
 
         let responses = self
             .send_dump(builder)
             .await
-            .map_err(|e| e.with_context("get_bridge_vlan_entry_options"))?;
+            .map_err(|e| e.with_context(context))?;
 
         let mut entries = Vec::new();
         for response in responses {
@@ -2066,6 +2346,7 @@ fn parse_one_entry(payload: &[u8], ifindex: u32) -> Option<BridgeVlanEntryOption
                 // struct bridge_vlan_info { flags: u16, vid: u16 }
                 if let Some(info) = BridgeVlanInfo::from_bytes(data) {
                     opts.vid = info.vid;
+                    opts.flags = BridgeVlanFlags::from_raw(info.flags);
                     have_vid = true;
                 }
             }
@@ -2087,7 +2368,10 @@ fn parse_one_entry(payload: &[u8], ifindex: u32) -> Option<BridgeVlanEntryOption
             t if t == bridge_vlandb_entry::NEIGH_SUPPRESS && !data.is_empty() => {
                 opts.neigh_suppress = Some(data[0] != 0);
             }
-            // ENTRY_TUNNEL_INFO / ENTRY_STATS (nested) and future attrs.
+            t if t == bridge_vlandb_entry::STATS => {
+                opts.stats = Some(BridgeVlanStats::parse(data));
+            }
+            // ENTRY_TUNNEL_INFO (nested) and future attrs.
             _ => {}
         }
     }
@@ -2140,11 +2424,11 @@ mod tests {
     #[test]
     fn test_builder_default() {
         let builder = BridgeVlanBuilder::new(100);
-        assert_eq!(builder.vid, 100);
+        assert_eq!(builder.vids(), &[(100, 100)]);
         assert!(!builder.pvid);
         assert!(!builder.untagged);
         assert!(!builder.master);
-        assert!(builder.vid_end.is_none());
+        assert_eq!(builder.build_bridge_flags(), 0);
     }
 
     #[test]
@@ -2165,8 +2449,92 @@ mod tests {
     fn test_builder_range() {
         let builder = BridgeVlanBuilder::new(100).dev("eth0").range(110);
 
-        assert_eq!(builder.vid, 100);
-        assert_eq!(builder.vid_end, Some(110));
+        assert_eq!(builder.vids(), &[(100, 110)]);
+    }
+
+    #[test]
+    fn test_builder_batch() {
+        let builder = BridgeVlanBuilder::new(10)
+            .vid(20)
+            .vid_range(300, 400)
+            .range(500);
+
+        assert_eq!(builder.vids(), &[(10, 10), (20, 20), (300, 500)]);
+        assert!(builder.validate().is_ok());
+    }
+
+    #[test]
+    fn test_builder_validate() {
+        assert!(BridgeVlanBuilder::new(0).validate().is_err());
+        assert!(BridgeVlanBuilder::new(4095).validate().is_err());
+        assert!(BridgeVlanBuilder::new(200).range(100).validate().is_err());
+        assert!(BridgeVlanBuilder::new(1).vid(4094).validate().is_ok());
+
+        let e = BridgeVlanBuilder::new(100)
+            .range(110)
+            .pvid()
+            .validate()
+            .unwrap_err();
+        assert!(e.to_string().contains("pvid"), "{e}");
+        assert!(
+            BridgeVlanBuilder::new(100)
+                .vid(200)
+                .pvid()
+                .validate()
+                .is_err()
+        );
+        assert!(BridgeVlanBuilder::new(100).pvid().validate().is_ok());
+    }
+
+    #[test]
+    fn test_bridge_flags() {
+        let builder = BridgeVlanBuilder::new(100).self_();
+        assert_eq!(builder.build_bridge_flags(), bridge_flags::SELF);
+
+        let builder = BridgeVlanBuilder::new(100).self_().via_master();
+        assert_eq!(
+            builder.build_bridge_flags(),
+            bridge_flags::SELF | bridge_flags::MASTER
+        );
+        // The per-entry MASTER flag is unrelated to the request target.
+        assert_eq!(BridgeVlanBuilder::new(100).master().build_bridge_flags(), 0);
+    }
+
+    #[test]
+    fn test_write_message_batches_entries() {
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_SETLINK, NLM_F_REQUEST | NLM_F_ACK);
+        BridgeVlanBuilder::new(10)
+            .vid_range(20, 30)
+            .self_()
+            .write_add(&mut builder, 7)
+            .unwrap();
+        let msg = builder.finish();
+
+        // nlmsghdr (16) + ifinfomsg (16) + AF_SPEC header (4)
+        // + IFLA_BRIDGE_FLAGS (8) + 3 * IFLA_BRIDGE_VLAN_INFO (8 each)
+        assert_eq!(msg.len(), 16 + 16 + 4 + 8 + 3 * 8);
+
+        let spec = &msg[36..];
+        let infos: Vec<(u16, u16)> = AttrIter::new(spec)
+            .filter(|(ty, _)| *ty == bridge_af::IFLA_BRIDGE_VLAN_INFO)
+            .map(|(_, data)| {
+                let info = BridgeVlanInfo::from_bytes(data).unwrap();
+                (info.vid, info.flags)
+            })
+            .collect();
+        assert_eq!(
+            infos,
+            vec![
+                (10, 0),
+                (20, bridge_vlan_flags::RANGE_BEGIN),
+                (30, bridge_vlan_flags::RANGE_END),
+            ]
+        );
+        let flags: Vec<u16> = AttrIter::new(spec)
+            .filter(|(ty, _)| *ty == bridge_af::IFLA_BRIDGE_FLAGS)
+            .map(|(_, data)| u16::from_ne_bytes([data[0], data[1]]))
+            .collect();
+        assert_eq!(flags, vec![bridge_flags::SELF]);
     }
 
     #[test]
@@ -2498,6 +2866,51 @@ mod tests {
         assert!(parse_one_entry(&nest, 3).is_none());
     }
 
+    #[test]
+    fn entry_parse_reads_flags_and_stats() {
+        let info = BridgeVlanInfo::new(30)
+            .with_flags(bridge_vlan_flags::PVID | bridge_vlan_flags::UNTAGGED);
+        let mut stats = Vec::new();
+        push_attr(&mut stats, bridge_vlandb_stats::RX_BYTES, &1500u64.to_ne_bytes());
+        push_attr(&mut stats, bridge_vlandb_stats::RX_PACKETS, &3u64.to_ne_bytes());
+        push_attr(&mut stats, bridge_vlandb_stats::PAD, &[]);
+        push_attr(&mut stats, bridge_vlandb_stats::TX_BYTES, &64u64.to_ne_bytes());
+        push_attr(&mut stats, bridge_vlandb_stats::TX_PACKETS, &1u64.to_ne_bytes());
+
+        let mut nest = Vec::new();
+        push_attr(&mut nest, bridge_vlandb_entry::INFO, info.as_bytes());
+        push_attr(&mut nest, bridge_vlandb_entry::STATS, &stats);
+
+        let o = parse_one_entry(&nest, 4).unwrap();
+        assert_eq!(o.vid(), 30);
+        assert!(o.flags().pvid);
+        assert!(o.flags().untagged);
+        assert_eq!(
+            o.stats(),
+            Some(&BridgeVlanStats {
+                rx_bytes: 1500,
+                rx_packets: 3,
+                tx_bytes: 64,
+                tx_packets: 1,
+            })
+        );
+
+        // Without the STATS nest there are no counters.
+        let mut nest = Vec::new();
+        push_attr(&mut nest, bridge_vlandb_entry::INFO, info.as_bytes());
+        assert_eq!(parse_one_entry(&nest, 4).unwrap().stats(), None);
+    }
+
+    #[test]
+    fn stats_parse_ignores_short_counters() {
+        let mut stats = Vec::new();
+        push_attr(&mut stats, bridge_vlandb_stats::RX_BYTES, &[1, 2, 3]);
+        push_attr(&mut stats, bridge_vlandb_stats::TX_PACKETS, &9u64.to_ne_bytes());
+        let s = BridgeVlanStats::parse(&stats);
+        assert_eq!(s.rx_bytes, 0);
+        assert_eq!(s.tx_packets, 9);
+    }
+
     #[test]
     fn entry_parse_arbitrary_bytes_never_panics() {
         for len in 0..40usize {
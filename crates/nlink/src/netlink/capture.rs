@@ -0,0 +1,390 @@
+//! Recording and replaying netlink message captures.
+//!
+//! A capture file is a flat sequence of netlink messages, each preceded
+//! by an `NLMSG_TSTAMP` pseudo-message carrying the wall-clock time it
+//! was received. This is the layout iproute2's `rtmon` writes and
+//! `ip monitor file` reads, so captures are interchangeable with those
+//! tools:
+//!
+//! ```text
+//! nlmsghdr { len = 24, type = NLMSG_TSTAMP }  u32 secs  u32 usecs
+//! nlmsghdr { len = N,  type = RTM_NEWLINK }   payload, padded to 4 bytes
+//! nlmsghdr { len = 24, type = NLMSG_TSTAMP }  ...
+//! ```
+//!
+//! All fields are host byte order, like the netlink stream they were
+//! copied from. [`CaptureWriter`] records raw multicast batches and
+//! [`CaptureReader`] yields them back as [`CapturedMessage`]s, which
+//! parse with the same [`EventSource`] code as a live subscription.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, EventSource, Route, RtnetlinkGroup};
+//! use nlink::netlink::capture::{CaptureReader, CaptureWriter};
+//!
+//! // Record.
+//! let conn = Connection::<Route>::new()?;
+//! conn.subscribe(&[RtnetlinkGroup::Link])?;
+//! let mut capture = CaptureWriter::create("links.nlcap")?;
+//! for _ in 0..10 {
+//!     capture.write_batch(&conn.socket().recv_msg().await?)?;
+//! }
+//! capture.flush()?;
+//!
+//! // Replay.
+//! for msg in CaptureReader::open("links.nlcap")? {
+//!     let msg = msg?;
+//!     for event in msg.events::<Route>() {
+//!         println!("{:?} {:?}", msg.timestamp, event.action());
+//!     }
+//! }
+//! ```
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use super::{
+    error::{Error, Result},
+    message::{MessageIter, NLMSG_HDRLEN, NlMsgHdr, nlmsg_align},
+    stream::EventSource,
+};
+
+/// Pseudo message type marking a timestamp record (iproute2's
+/// `NLMSG_TSTAMP`). Never sent by the kernel.
+pub const NLMSG_TSTAMP: u16 = 15;
+
+/// Payload length of an `NLMSG_TSTAMP` record: `u32` seconds and `u32`
+/// microseconds since the Unix epoch.
+const TSTAMP_PAYLOAD_LEN: usize = 8;
+
+/// Writes netlink messages to a capture file.
+///
+/// Buffering is up to the caller; [`create`](Self::create) wraps the
+/// file in a [`BufWriter`], so call [`flush`](Self::flush) before
+/// handing the file to another reader.
+pub struct CaptureWriter<W: Write> {
+    inner: W,
+}
+
+impl CaptureWriter<BufWriter<File>> {
+    /// Create (or truncate) a capture file at `path`.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// Write captures to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Record every message of a received batch, stamped with the
+    /// current time.
+    pub fn write_batch(&mut self, data: &[u8]) -> Result<()> {
+        self.write_batch_at(SystemTime::now(), data)
+    }
+
+    /// Record every message of a received batch, stamped with `time`.
+    ///
+    /// Each message gets its own timestamp record, as `rtmon` does, so
+    /// a reader sees the time regardless of where it starts. A
+    /// malformed message ends the batch with an error; the messages
+    /// before it have already been written.
+    pub fn write_batch_at(&mut self, time: SystemTime, data: &[u8]) -> Result<()> {
+        for msg in MessageIter::new(data) {
+            let (header, payload) = msg?;
+            self.write_message_at(time, header, payload)?;
+        }
+        Ok(())
+    }
+
+    /// Record one message, stamped with `time`.
+    pub fn write_message_at(
+        &mut self,
+        time: SystemTime,
+        header: &NlMsgHdr,
+        payload: &[u8],
+    ) -> Result<()> {
+        self.write_stamp(time)?;
+
+        let mut header = *header;
+        header.nlmsg_len = (NLMSG_HDRLEN + payload.len()) as u32;
+        self.inner.write_all(header.as_bytes())?;
+        self.inner.write_all(payload)?;
+        let pad = nlmsg_align(payload.len()) - payload.len();
+        self.inner.write_all(&[0u8; 3][..pad])?;
+        Ok(())
+    }
+
+    /// Flush buffered records to the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.inner.flush()?)
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn write_stamp(&mut self, time: SystemTime) -> Result<()> {
+        let since = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let mut header = NlMsgHdr::new(NLMSG_TSTAMP, 0);
+        header.nlmsg_len = (NLMSG_HDRLEN + TSTAMP_PAYLOAD_LEN) as u32;
+        self.inner.write_all(header.as_bytes())?;
+        self.inner
+            .write_all(&(since.as_secs() as u32).to_ne_bytes())?;
+        self.inner.write_all(&since.subsec_micros().to_ne_bytes())?;
+        Ok(())
+    }
+}
+
+/// One netlink message read back from a capture.
+#[derive(Debug, Clone)]
+pub struct CapturedMessage {
+    /// Time of the most recent timestamp record before this message,
+    /// or `None` if the capture had none yet.
+    pub timestamp: Option<SystemTime>,
+    /// The message, header included, without trailing padding.
+    pub data: Vec<u8>,
+}
+
+impl CapturedMessage {
+    /// The message header.
+    pub fn header(&self) -> &NlMsgHdr {
+        // The reader only builds messages at least NLMSG_HDRLEN long.
+        NlMsgHdr::from_bytes(&self.data).expect("captured message shorter than its header")
+    }
+
+    /// The message type (`RTM_NEWLINK`, ...).
+    pub fn msg_type(&self) -> u16 {
+        self.header().nlmsg_type
+    }
+
+    /// The message payload, after the header.
+    pub fn payload(&self) -> &[u8] {
+        &self.data[NLMSG_HDRLEN..]
+    }
+
+    /// Parse the message the way a live event stream of protocol `P`
+    /// would. Returns an empty vector for messages `P` doesn't produce
+    /// events for.
+    pub fn events<P: EventSource>(&self) -> Vec<P::Event> {
+        P::parse_events(&self.data)
+    }
+}
+
+/// Reads netlink messages back from a capture file.
+///
+/// Iterates over the captured messages in order, attaching the
+/// preceding timestamp record to each. Timestamp records themselves
+/// are consumed, not yielded. Iteration ends at a clean end of file;
+/// a truncated or malformed record yields one error and then ends.
+pub struct CaptureReader<R: Read> {
+    inner: R,
+    timestamp: Option<SystemTime>,
+    done: bool,
+}
+
+impl CaptureReader<BufReader<File>> {
+    /// Open the capture file at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Read captures from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            timestamp: None,
+            done: false,
+        }
+    }
+
+    /// Read the next record, header included. `Ok(None)` at a clean
+    /// end of file.
+    fn read_record(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut header = [0u8; NLMSG_HDRLEN];
+        let mut filled = 0;
+        while filled < header.len() {
+            match self.inner.read(&mut header[filled..]) {
+                Ok(0) if filled == 0 => return Ok(None),
+                Ok(0) => {
+                    return Err(Error::Truncated {
+                        expected: NLMSG_HDRLEN,
+                        actual: filled,
+                    });
+                }
+                Ok(n) => filled += n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let len = NlMsgHdr::from_bytes(&header)?.nlmsg_len as usize;
+        if len < NLMSG_HDRLEN {
+            return Err(Error::InvalidMessage(format!(
+                "invalid message length in capture: {}",
+                len
+            )));
+        }
+
+        let mut record = vec![0u8; nlmsg_align(len)];
+        record[..NLMSG_HDRLEN].copy_from_slice(&header);
+        self.inner
+            .read_exact(&mut record[NLMSG_HDRLEN..])
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::UnexpectedEof => Error::Truncated {
+                    expected: nlmsg_align(len),
+                    actual: NLMSG_HDRLEN,
+                },
+                _ => e.into(),
+            })?;
+        record.truncate(len);
+        Ok(Some(record))
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = Result<CapturedMessage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let data = match self.read_record() {
+                Ok(Some(data)) => data,
+                Ok(None) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+
+            if NlMsgHdr::from_bytes(&data).ok()?.nlmsg_type != NLMSG_TSTAMP {
+                return Some(Ok(CapturedMessage {
+                    timestamp: self.timestamp,
+                    data,
+                }));
+            }
+
+            let payload = &data[NLMSG_HDRLEN..];
+            if payload.len() >= TSTAMP_PAYLOAD_LEN {
+                let secs = u32::from_ne_bytes(payload[0..4].try_into().unwrap());
+                let usecs = u32::from_ne_bytes(payload[4..8].try_into().unwrap());
+                self.timestamp = Some(
+                    UNIX_EPOCH
+                        + Duration::from_secs(secs.into())
+                        + Duration::from_micros(usecs.into()),
+                );
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::{NlMsgType, Route, types::link::IfInfoMsg};
+
+    fn newlink(ifindex: i32) -> Vec<u8> {
+        let mut ifi = IfInfoMsg::new();
+        ifi.ifi_index = ifindex;
+        let mut header = NlMsgHdr::new(NlMsgType::RTM_NEWLINK, 0);
+        header.nlmsg_len = (NLMSG_HDRLEN + std::mem::size_of::<IfInfoMsg>()) as u32;
+        let mut data = header.as_bytes().to_vec();
+        data.extend_from_slice(ifi.as_bytes());
+        data
+    }
+
+    #[test]
+    fn round_trip_keeps_messages_and_timestamps() {
+        let t1 = UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456);
+        let t2 = t1 + Duration::from_secs(5);
+        let mut batch = newlink(2);
+        batch.extend(newlink(3));
+
+        let mut writer = CaptureWriter::new(Vec::new());
+        writer.write_batch_at(t1, &batch).unwrap();
+        writer.write_batch_at(t2, &newlink(4)).unwrap();
+        let bytes = writer.into_inner();
+        // Three timestamp records, three messages.
+        assert_eq!(bytes.len(), 3 * (24 + newlink(0).len()));
+
+        let msgs: Vec<_> = CaptureReader::new(bytes.as_slice())
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(msgs.len(), 3);
+        assert_eq!(msgs[0].timestamp, Some(t1));
+        assert_eq!(msgs[1].timestamp, Some(t1));
+        assert_eq!(msgs[2].timestamp, Some(t2));
+        assert_eq!(msgs[0].data, newlink(2));
+        assert_eq!(msgs[2].msg_type(), NlMsgType::RTM_NEWLINK);
+
+        let events = msgs[2].events::<Route>();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_link().unwrap().ifindex(), 4);
+    }
+
+    #[test]
+    fn reads_rtmon_layout_and_pads_odd_payloads() {
+        // TSTAMP record then a NOOP with a 5-byte payload, padded to 8.
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&24u32.to_ne_bytes());
+        bytes.extend_from_slice(&NLMSG_TSTAMP.to_ne_bytes());
+        bytes.extend_from_slice(&[0; 10]);
+        bytes.extend_from_slice(&100u32.to_ne_bytes());
+        bytes.extend_from_slice(&250u32.to_ne_bytes());
+        bytes.extend_from_slice(&21u32.to_ne_bytes());
+        bytes.extend_from_slice(&NlMsgType::NOOP.to_ne_bytes());
+        bytes.extend_from_slice(&[0; 10]);
+        bytes.extend_from_slice(&[1, 2, 3, 4, 5, 0, 0, 0]);
+
+        let mut reader = CaptureReader::new(bytes.as_slice());
+        let msg = reader.next().unwrap().unwrap();
+        assert_eq!(
+            msg.timestamp,
+            Some(UNIX_EPOCH + Duration::from_secs(100) + Duration::from_micros(250))
+        );
+        assert_eq!(msg.payload(), &[1, 2, 3, 4, 5]);
+        assert!(msg.events::<Route>().is_empty());
+        assert!(reader.next().is_none());
+
+        // Writing it back reproduces the same bytes.
+        let mut writer = CaptureWriter::new(Vec::new());
+        writer
+            .write_message_at(msg.timestamp.unwrap(), msg.header(), msg.payload())
+            .unwrap();
+        assert_eq!(writer.into_inner(), bytes);
+    }
+
+    #[test]
+    fn truncated_capture_errors_once() {
+        let mut writer = CaptureWriter::new(Vec::new());
+        writer.write_batch_at(UNIX_EPOCH, &newlink(1)).unwrap();
+        let mut bytes = writer.into_inner();
+        bytes.truncate(bytes.len() - 4);
+
+        let mut reader = CaptureReader::new(bytes.as_slice());
+        assert!(matches!(reader.next(), Some(Err(Error::Truncated { .. }))));
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn undersized_length_is_rejected() {
+        let mut bytes = 8u32.to_ne_bytes().to_vec();
+        bytes.extend_from_slice(&[0; 12]);
+        let mut reader = CaptureReader::new(bytes.as_slice());
+        assert!(matches!(reader.next(), Some(Err(Error::InvalidMessage(_)))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -2,26 +2,35 @@
 //!
 //! This module applies the computed diff to achieve the desired network state.
 
-use std::{net::IpAddr, time::Duration};
+use std::{collections::HashMap, net::IpAddr, time::Duration};
 
 use super::{
-    diff::{ConfigDiff, DiffOptions, LinkChanges, compute_diff_with_options},
+    diff::{
+        ConfigDiff, DiffOptions, LinkChanges, compute_diff_with_options,
+        declared_route_from_kernel, format_mac, rule_summary,
+    },
     types::{
-        BondMode, DeclaredAddress, DeclaredLink, DeclaredLinkType, DeclaredQdisc,
-        DeclaredQdiscType, DeclaredRoute, DeclaredRouteType, MacvlanMode, NetworkConfig,
-        QdiscParent,
+        BondMode, DeclaredAddress, DeclaredClass, DeclaredClassType, DeclaredFilter,
+        DeclaredFilterType, DeclaredLink, DeclaredLinkType, DeclaredNeighbor, DeclaredQdisc,
+        DeclaredQdiscType, DeclaredRoute, DeclaredRouteType, DeclaredRule, DeclaredRuleAction,
+        DeclaredSysctl, InterfaceSysctl, MacvlanMode, NetworkConfig, QdiscParent,
     },
 };
 use crate::netlink::{
     addr::{Ipv4Address, Ipv6Address},
     connection::Connection,
     error::{Error, Result},
+    filter::FlowerFilter,
     link::{BondLink, BridgeLink, DummyLink, IfbLink, MacvlanLink, VethLink, VlanLink, VxlanLink},
+    namespace,
+    neigh::Neighbor,
     protocol::Route,
     route::{Ipv4Route, Ipv6Route},
+    rule::RuleBuilder,
+    sysctl,
     tc::{
-        ClsactConfig, FqCodelConfig, HtbQdiscConfig, IngressConfig, NetemConfig, PrioConfig,
-        SfqConfig, TbfConfig,
+        ClsactConfig, FqCodelConfig, HtbClassConfig, HtbQdiscConfig, IngressConfig, NetemConfig,
+        PrioConfig, SfqConfig, TbfConfig,
     },
 };
 
@@ -46,6 +55,10 @@ use crate::netlink::{
 /// - `continue_on_error: false` — the first error propagates
 ///   as `Err`, halting further ops. Partially-applied state
 ///   is left in the kernel.
+/// - `rollback: false` — no snapshot is taken; see
+///   [`Self::with_rollback`] to revert partial state on failure.
+/// - `gateway_probe: None` — route gateways are not checked; see
+///   [`Self::with_gateway_probe`].
 ///
 /// This is the right default; opt in to each surface
 /// individually via the builders.
@@ -82,6 +95,14 @@ pub struct ApplyOptions {
     /// resources (link-local, loopback, RA/DHCP routes, links, qdiscs)
     /// are never touched. Off by default.
     pub purge: bool,
+    /// Snapshot the state each step overwrites and revert the steps
+    /// already applied when a later one fails. See
+    /// [`Self::with_rollback`]. Off by default.
+    pub rollback: bool,
+    /// Resolve each route's gateway before installing the route, and
+    /// fail the step if it doesn't answer within this long. See
+    /// [`Self::with_gateway_probe`]. Off by default.
+    pub gateway_probe: Option<Duration>,
 }
 
 impl ApplyOptions {
@@ -112,6 +133,41 @@ impl ApplyOptions {
         self.purge = on;
         self
     }
+
+    /// Toggle automatic rollback. With rollback on, `apply`
+    /// snapshots the link attributes and routes it is about to
+    /// overwrite, and when a step fails it undoes every step that
+    /// already succeeded, in reverse order — created links and
+    /// addresses are deleted, modified links get their previous
+    /// state/MTU/master back, replaced routes are restored, and
+    /// purged addresses/routes are re-added.
+    ///
+    /// The failure is then reported in `ApplyResult::errors` and
+    /// the undo outcome in [`ApplyResult::rollback`], and `apply`
+    /// returns `Ok` — check [`ApplyResult::is_success`]. Qdisc
+    /// replacements, class changes and filter replacements can't be
+    /// reverted (the previous parameters aren't modelled) and are
+    /// listed in [`RollbackReport::not_reverted`].
+    ///
+    /// Has no effect with `continue_on_error` or `dry_run` on.
+    pub fn with_rollback(mut self, on: bool) -> Self {
+        self.rollback = on;
+        self
+    }
+
+    /// Preflight route gateways. Before a route with a gateway is
+    /// added, the gateway is resolved with ARP/NDP via
+    /// [`Connection::probe_neighbor`] on the route's `dev` (or the
+    /// interface the kernel would use to reach it). A gateway that
+    /// doesn't answer within `timeout` fails the step instead of
+    /// installing a route into a black hole; with
+    /// [`Self::with_rollback`] the earlier steps are then undone.
+    ///
+    /// Has no effect with `dry_run` on.
+    pub fn with_gateway_probe(mut self, timeout: Duration) -> Self {
+        self.gateway_probe = Some(timeout);
+        self
+    }
 }
 
 /// Result of applying configuration.
@@ -126,6 +182,10 @@ pub struct ApplyResult {
     pub errors: Vec<ApplyError>,
     /// Summary of what was done.
     pub summary: Vec<String>,
+    /// Set when a step failed under [`ApplyOptions::with_rollback`]:
+    /// what was undone. `summary` still lists the steps that ran
+    /// before the failure.
+    pub rollback: Option<RollbackReport>,
 }
 
 impl ApplyResult {
@@ -144,6 +204,43 @@ impl ApplyResult {
     }
 }
 
+/// Outcome of an automatic rollback (see [`ApplyOptions::with_rollback`]).
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Default)]
+pub struct RollbackReport {
+    /// Undo steps that succeeded, in the order they ran.
+    pub reverted: Vec<String>,
+    /// Undo steps that failed. The kernel keeps the applied state
+    /// for these.
+    pub errors: Vec<ApplyError>,
+    /// Applied changes that can't be undone.
+    pub not_reverted: Vec<String>,
+}
+
+impl RollbackReport {
+    /// Whether every applied change was undone.
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty() && self.not_reverted.is_empty()
+    }
+
+    /// Get a human-readable summary.
+    pub fn summary_text(&self) -> String {
+        let mut lines = self.reverted.clone();
+        lines.extend(self.errors.iter().map(|e| format!("Failed to undo {e}")));
+        lines.extend(
+            self.not_reverted
+                .iter()
+                .map(|n| format!("Not reverted: {n}")),
+        );
+        if lines.is_empty() {
+            "Nothing to revert".to_string()
+        } else {
+            lines.join("\n")
+        }
+    }
+}
+
 /// An error that occurred during configuration application.
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
@@ -198,13 +295,97 @@ pub async fn apply_diff(
         return Ok(result);
     }
 
+    // Rollback needs the pre-change state of everything the diff
+    // overwrites; capture it before the first mutation.
+    let mut undo = if options.rollback && !options.dry_run && !options.continue_on_error {
+        Some(UndoLog::capture(conn, diff).await?)
+    } else {
+        None
+    };
+
+    match apply_steps(diff, conn, &options, &mut result, &mut undo).await {
+        Ok(()) => Ok(result),
+        Err((op, e)) => fail(conn, result, undo, op, e).await,
+    }
+}
+
+/// Apply a diff and keep its undo log for a later revert (see
+/// [`ConfigDiff::apply_confirmed`]). A failed step is rolled back at
+/// once and its error returned.
+pub(super) async fn apply_diff_undoable(
+    diff: &ConfigDiff,
+    conn: &Connection<Route>,
+) -> Result<(ApplyResult, UndoLog)> {
+    let options = ApplyOptions::default().with_rollback(true);
+    let mut result = ApplyResult::default();
+    let mut undo = Some(UndoLog::capture(conn, diff).await?);
+    match apply_steps(diff, conn, &options, &mut result, &mut undo).await {
+        Ok(()) => Ok((result, undo.expect("rollback is on"))),
+        Err((operation, e)) => {
+            if let Some(undo) = undo {
+                let report = undo.run(conn).await;
+                if !report.is_complete() {
+                    tracing::warn!(
+                        %operation,
+                        rollback = %report.summary_text(),
+                        "apply_confirmed: rollback after a failed step was incomplete"
+                    );
+                }
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Run the ordered steps of one diff. A failure that halts the apply
+/// comes back as `(operation, error)` for [`fail`] to handle.
+async fn apply_steps(
+    diff: &ConfigDiff,
+    conn: &Connection<Route>,
+    options: &ApplyOptions,
+    result: &mut ApplyResult,
+    undo: &mut Option<UndoLog>,
+) -> std::result::Result<(), (String, Error)> {
     // Apply changes in the correct order:
+    // 0. Create namespaces (veth peers are placed into them)
     // 1. Create new links (so they exist for addresses/routes)
-    // 2. Modify existing links (state, MTU, master)
+    // 2. Modify existing links (state, MTU, master), then set
+    //    per-interface sysctls (before addresses: accept_ra and
+    //    forwarding affect what the kernel does with them)
     // 3. Add addresses
-    // 4. Add routes
-    // 5. Configure qdiscs
+    // 4. Add routes, then rules and neighbors
+    // 5. Configure qdiscs, classes and filters
     // 6. Remove old resources (if purge enabled)
+    // 7. Apply each namespace's changes inside it
+
+    // 0. Create namespaces
+    for name in &diff.namespaces_to_create {
+        let op = format!("create namespace {name}");
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            match namespace::create(name) {
+                Ok(()) => {
+                    result.summary.push(format!("Created namespace {name}"));
+                    result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::DeleteNamespace(name.clone()));
+                    }
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
 
     // 1. Create new links
     for link in &diff.links_to_add {
@@ -217,15 +398,26 @@ pub async fn apply_diff(
                 Ok(()) => {
                     result.summary.push(format!("Created link {}", link.name));
                     result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::DeleteLink(link.name.clone()));
+                    }
                 }
                 Err(e) => {
+                    // `create_link` is several requests (add, up,
+                    // master); a failure after the add leaves the
+                    // link behind. Only EEXIST means it isn't ours.
+                    if let Some(u) = undo
+                        && !e.is_already_exists()
+                    {
+                        u.push(Undo::DeleteLink(link.name.clone()));
+                    }
                     if options.continue_on_error {
                         result.errors.push(ApplyError {
                             operation: op,
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
                     }
                 }
             }
@@ -239,6 +431,11 @@ pub async fn apply_diff(
             result.summary.push(format!("Would {}", op));
             result.changes_made += 1;
         } else {
+            // Restoring is idempotent, so record the undo up front:
+            // a failure halfway through `modify_link` is covered.
+            if let Some(u) = undo {
+                u.link_modified(name, changes);
+            }
             match modify_link(conn, name, changes).await {
                 Ok(()) => {
                     result
@@ -253,7 +450,41 @@ pub async fn apply_diff(
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
+
+    // 2b. Set per-interface sysctls
+    for sysctl in &diff.sysctls_to_set {
+        let op = format!("set sysctl {} = {}", sysctl.full_key(), sysctl.value);
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            // Restoring is idempotent, so record the undo up front.
+            if let Some(u) = undo {
+                u.sysctl_set(sysctl);
+            }
+            match set_sysctl(diff.netns.as_deref(), sysctl) {
+                Ok(()) => {
+                    result.summary.push(format!(
+                        "Set sysctl {} = {}",
+                        sysctl.full_key(),
+                        sysctl.value
+                    ));
+                    result.changes_made += 1;
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
                     }
                 }
             }
@@ -270,13 +501,16 @@ pub async fn apply_diff(
             result.summary.push(format!("Would {}", op));
             result.changes_made += 1;
         } else {
-            match add_address(conn, addr).await {
+            match add_address(conn, addr, diff.owner).await {
                 Ok(()) => {
                     result.summary.push(format!(
                         "Added address {}/{} on {}",
                         addr.address, addr.prefix_len, addr.dev
                     ));
                     result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::DeleteAddress(addr.clone()));
+                    }
                 }
                 Err(e) => {
                     if options.continue_on_error {
@@ -285,7 +519,7 @@ pub async fn apply_diff(
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
                     }
                 }
             }
@@ -299,13 +533,23 @@ pub async fn apply_diff(
             result.summary.push(format!("Would {}", op));
             result.changes_made += 1;
         } else {
-            match add_route(conn, route).await {
+            let added = match options.gateway_probe {
+                Some(timeout) => match probe_gateway(conn, route, timeout).await {
+                    Ok(()) => add_route(conn, route, diff.owner).await,
+                    Err(e) => Err(e),
+                },
+                None => add_route(conn, route, diff.owner).await,
+            };
+            match added {
                 Ok(()) => {
                     result.summary.push(format!(
                         "Added route {}/{}",
                         route.destination, route.prefix_len
                     ));
                     result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.route_replaced(route);
+                    }
                 }
                 Err(e) => {
                     if options.continue_on_error {
@@ -314,28 +558,29 @@ pub async fn apply_diff(
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
                     }
                 }
             }
         }
     }
 
-    // 5a. Replace qdiscs (remove and re-add with new config)
-    for qdisc in &diff.qdiscs_to_replace {
-        let op = format!("replace qdisc {} on {}", qdisc.qdisc_type.kind(), qdisc.dev);
+    // 4b. Add rules
+    for rule in &diff.rules_to_add {
+        let op = format!("add rule {}", rule_summary(rule));
         if options.dry_run {
             result.summary.push(format!("Would {}", op));
             result.changes_made += 1;
         } else {
-            match replace_qdisc(conn, qdisc).await {
+            match add_rule(conn, rule).await {
                 Ok(()) => {
-                    result.summary.push(format!(
-                        "Replaced qdisc {} on {}",
-                        qdisc.qdisc_type.kind(),
-                        qdisc.dev
-                    ));
+                    result
+                        .summary
+                        .push(format!("Added rule {}", rule_summary(rule)));
                     result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::DeleteRule(rule.clone()));
+                    }
                 }
                 Err(e) => {
                     if options.continue_on_error {
@@ -344,28 +589,37 @@ pub async fn apply_diff(
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
                     }
                 }
             }
         }
     }
 
-    // 5b. Add new qdiscs
-    for qdisc in &diff.qdiscs_to_add {
-        let op = format!("add qdisc {} on {}", qdisc.qdisc_type.kind(), qdisc.dev);
+    // 4c. Set neighbors
+    for neigh in &diff.neighbors_to_set {
+        let op = format!(
+            "set neighbor {} lladdr {} on {}",
+            neigh.address,
+            format_mac(&neigh.lladdr),
+            neigh.dev
+        );
         if options.dry_run {
             result.summary.push(format!("Would {}", op));
             result.changes_made += 1;
         } else {
-            match add_qdisc(conn, qdisc).await {
+            match set_neighbor(conn, neigh).await {
                 Ok(()) => {
                     result.summary.push(format!(
-                        "Added qdisc {} on {}",
-                        qdisc.qdisc_type.kind(),
-                        qdisc.dev
+                        "Set neighbor {} lladdr {} on {}",
+                        neigh.address,
+                        format_mac(&neigh.lladdr),
+                        neigh.dev
                     ));
                     result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.neighbor_set(neigh);
+                    }
                 }
                 Err(e) => {
                     if options.continue_on_error {
@@ -374,7 +628,140 @@ pub async fn apply_diff(
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
+
+    // 5a. Root and ingress qdiscs, so classes have a qdisc to hang off.
+    apply_qdiscs(diff, conn, options, result, undo, false).await?;
+
+    // 5b. Change existing classes, then add new ones (parents first —
+    //     the diff sorts them).
+    for class in &diff.classes_to_change {
+        let op = format!("change class {}", class.describe());
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            match replace_class(conn, class).await {
+                Ok(()) => {
+                    result
+                        .summary
+                        .push(format!("Changed class {}", class.describe()));
+                    result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::Irreversible(format!(
+                            "class {} (previous class parameters are not snapshotted)",
+                            class.describe()
+                        )));
+                    }
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
+    for class in &diff.classes_to_add {
+        let op = format!("add class {}", class.describe());
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            match add_class(conn, class).await {
+                Ok(()) => {
+                    result
+                        .summary
+                        .push(format!("Added class {}", class.describe()));
+                    result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::DeleteClass(class.clone()));
+                    }
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
+
+    // 5c. Leaf qdiscs under the classes.
+    apply_qdiscs(diff, conn, options, result, undo, true).await?;
+
+    // 5d. Filters last, once the classes they point at exist.
+    for filter in &diff.filters_to_replace {
+        let op = format!("replace filter {}", filter.describe());
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            match replace_filter(conn, filter).await {
+                Ok(()) => {
+                    result
+                        .summary
+                        .push(format!("Replaced filter {}", filter.describe()));
+                    result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::Irreversible(format!(
+                            "filter {} (previous filters are not snapshotted)",
+                            filter.describe()
+                        )));
+                    }
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
+    for filter in &diff.filters_to_add {
+        let op = format!("add filter {}", filter.describe());
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            match add_filter(conn, filter).await {
+                Ok(()) => {
+                    result
+                        .summary
+                        .push(format!("Added filter {}", filter.describe()));
+                    result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::DeleteFilter(filter.clone()));
+                    }
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
                     }
                 }
             }
@@ -402,6 +789,9 @@ pub async fn apply_diff(
                         route.destination, route.prefix_len
                     ));
                     result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::AddRoute(route.clone()));
+                    }
                 }
                 Err(e) => {
                     if options.continue_on_error {
@@ -410,7 +800,7 @@ pub async fn apply_diff(
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
                     }
                 }
             }
@@ -434,6 +824,9 @@ pub async fn apply_diff(
                         addr.address, addr.prefix_len, addr.dev
                     ));
                     result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::AddAddress(addr.clone()));
+                    }
                 }
                 Err(e) => {
                     if options.continue_on_error {
@@ -442,16 +835,526 @@ pub async fn apply_diff(
                             error: e,
                         });
                     } else {
-                        return Err(e);
+                        return Err((op, e));
                     }
                 }
             }
         }
     }
 
+    // 7. Namespaces, each through a connection opened inside it.
+    for (name, sub) in &diff.namespaces {
+        let mut sub_result = ApplyResult::default();
+        if options.dry_run {
+            // Dry runs never touch the kernel, so the namespace
+            // needn't exist yet.
+            Box::pin(apply_steps(sub, conn, options, &mut sub_result, &mut None)).await?;
+            merge_namespace_result(result, name, sub_result);
+            continue;
+        }
+        let ns_conn = match namespace::connection_for::<Route>(name) {
+            Ok(c) => c,
+            Err(e) => {
+                let op = format!("open namespace {name}");
+                if options.continue_on_error {
+                    result.errors.push(ApplyError {
+                        operation: op,
+                        error: e,
+                    });
+                    continue;
+                }
+                return Err((op, e));
+            }
+        };
+        let mut sub_undo = match undo {
+            Some(_) => Some(
+                UndoLog::capture(&ns_conn, sub)
+                    .await
+                    .map_err(|e| (format!("snapshot namespace {name}"), e))?,
+            ),
+            None => None,
+        };
+        let outcome = Box::pin(apply_steps(
+            sub,
+            &ns_conn,
+            options,
+            &mut sub_result,
+            &mut sub_undo,
+        ))
+        .await;
+        merge_namespace_result(result, name, sub_result);
+        if let (Some(u), Some(sub_undo)) = (undo.as_mut(), sub_undo) {
+            u.push(Undo::InNamespace(name.clone(), sub_undo));
+        }
+        outcome.map_err(|(op, e)| (format!("{op} in namespace {name}"), e))?;
+    }
+
+    Ok(())
+}
+
+/// Apply the qdisc replacements and additions on one side of the
+/// class tree: root and ingress qdiscs, or (`leaves`) qdiscs attached
+/// to a class.
+async fn apply_qdiscs(
+    diff: &ConfigDiff,
+    conn: &Connection<Route>,
+    options: &ApplyOptions,
+    result: &mut ApplyResult,
+    undo: &mut Option<UndoLog>,
+    leaves: bool,
+) -> std::result::Result<(), (String, Error)> {
+    let is_leaf = |q: &DeclaredQdisc| matches!(q.parent, QdiscParent::Class(_));
+
+    // Replace qdiscs (atomic where the kind allows it)
+    for qdisc in diff
+        .qdiscs_to_replace
+        .iter()
+        .filter(|q| is_leaf(q) == leaves)
+    {
+        let op = format!("replace qdisc {}", qdisc.describe());
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            match replace_qdisc(conn, qdisc).await {
+                Ok(()) => {
+                    result
+                        .summary
+                        .push(format!("Replaced qdisc {}", qdisc.describe()));
+                    result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::Irreversible(format!(
+                            "qdisc {} (previous qdisc parameters are not snapshotted)",
+                            qdisc.describe()
+                        )));
+                    }
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
+
+    // Add new qdiscs
+    for qdisc in diff.qdiscs_to_add.iter().filter(|q| is_leaf(q) == leaves) {
+        let op = format!("add qdisc {}", qdisc.describe());
+        if options.dry_run {
+            result.summary.push(format!("Would {}", op));
+            result.changes_made += 1;
+        } else {
+            match add_qdisc(conn, qdisc).await {
+                Ok(()) => {
+                    result
+                        .summary
+                        .push(format!("Added qdisc {}", qdisc.describe()));
+                    result.changes_made += 1;
+                    if let Some(u) = undo {
+                        u.push(Undo::DeleteQdisc(qdisc.clone()));
+                    }
+                }
+                Err(e) => {
+                    if options.continue_on_error {
+                        result.errors.push(ApplyError {
+                            operation: op,
+                            error: e,
+                        });
+                    } else {
+                        return Err((op, e));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Fold a namespace's result into the parent's, tagging each line
+/// with the namespace.
+fn merge_namespace_result(result: &mut ApplyResult, name: &str, sub: ApplyResult) {
+    result.changes_made += sub.changes_made;
+    result.summary.extend(
+        sub.summary
+            .into_iter()
+            .map(|line| format!("{line} in namespace {name}")),
+    );
+    result
+        .errors
+        .extend(sub.errors.into_iter().map(|e| ApplyError {
+            operation: format!("{} in namespace {name}", e.operation),
+            error: e.error,
+        }));
+}
+
+// ============================================================================
+// Rollback
+// ============================================================================
+
+/// Handle a failed step: without rollback the error propagates as
+/// before; with it, the applied steps are undone and the failure is
+/// folded into the result.
+async fn fail(
+    conn: &Connection<Route>,
+    mut result: ApplyResult,
+    undo: Option<UndoLog>,
+    operation: String,
+    error: Error,
+) -> Result<ApplyResult> {
+    let Some(undo) = undo else {
+        return Err(error);
+    };
+    result.errors.push(ApplyError { operation, error });
+    result.rollback = Some(undo.run(conn).await);
     Ok(result)
 }
 
+/// One step that reverts an applied change.
+enum Undo {
+    DeleteLink(String),
+    /// Inverse of a `modify_link`, built from the snapshot.
+    RestoreLink(String, LinkChanges),
+    DeleteAddress(DeclaredAddress),
+    AddAddress(DeclaredAddress),
+    /// Undo a route replace: delete what we installed, then put back
+    /// the routes it displaced (if any).
+    RestoreRoute {
+        added: DeclaredRoute,
+        previous: Vec<DeclaredRoute>,
+    },
+    AddRoute(DeclaredRoute),
+    DeleteQdisc(DeclaredQdisc),
+    DeleteClass(DeclaredClass),
+    DeleteFilter(DeclaredFilter),
+    /// Carries the value captured before the change.
+    RestoreSysctl(DeclaredSysctl),
+    DeleteRule(DeclaredRule),
+    /// Put back the permanent entry the set replaced, or delete ours
+    /// when there was none (a dynamic entry re-resolves by itself).
+    RestoreNeighbor {
+        added: DeclaredNeighbor,
+        previous: Option<[u8; 6]>,
+    },
+    DeleteNamespace(String),
+    /// The undo log of the changes made inside a namespace.
+    InNamespace(String, UndoLog),
+    Irreversible(String),
+}
+
+/// Pre-change snapshot plus the undo steps recorded so far.
+pub(super) struct UndoLog {
+    /// `name → (admin up, mtu, master name)` for links being modified.
+    links: HashMap<String, (bool, Option<u32>, Option<String>)>,
+    /// Kernel routes keyed like the route replace matches:
+    /// `(dst, prefix, table, metric)`.
+    routes: HashMap<(IpAddr, u8, u32, u32), Vec<DeclaredRoute>>,
+    /// Current values of the sysctls being set. Absent when unreadable
+    /// (typically a link this apply creates).
+    sysctls: HashMap<(String, InterfaceSysctl), u32>,
+    /// Link-layer addresses of permanent entries being replaced.
+    neighbors: HashMap<(String, IpAddr), [u8; 6]>,
+    /// Namespace whose sysctls are restored; `None` for the caller's.
+    netns: Option<String>,
+    /// Owner tag that purged addresses and routes are re-added with.
+    owner: Option<u8>,
+    steps: Vec<Undo>,
+}
+
+impl UndoLog {
+    async fn capture(conn: &Connection<Route>, diff: &ConfigDiff) -> Result<Self> {
+        let mut log = Self {
+            links: HashMap::new(),
+            routes: HashMap::new(),
+            sysctls: HashMap::new(),
+            neighbors: HashMap::new(),
+            netns: diff.netns.clone(),
+            owner: diff.owner,
+            steps: Vec::new(),
+        };
+
+        for s in &diff.sysctls_to_set {
+            if let Ok(value) = get_sysctl(diff.netns.as_deref(), s) {
+                log.sysctls.insert((s.dev.clone(), s.key), value);
+            }
+        }
+
+        if diff.links_to_modify.is_empty()
+            && diff.routes_to_add.is_empty()
+            && diff.neighbors_to_set.is_empty()
+        {
+            return Ok(log);
+        }
+
+        let links = conn.get_links().await?;
+        let ifindex_to_name: HashMap<u32, &str> = links
+            .iter()
+            .filter_map(|l| l.name.as_deref().map(|n| (l.ifindex(), n)))
+            .collect();
+
+        for (name, _) in &diff.links_to_modify {
+            if let Some(l) = links.iter().find(|l| l.name.as_deref() == Some(name)) {
+                let master = l
+                    .master()
+                    .and_then(|idx| ifindex_to_name.get(&idx))
+                    .map(|n| n.to_string());
+                log.links.insert(name.clone(), (l.is_up(), l.mtu, master));
+            }
+        }
+
+        if !diff.routes_to_add.is_empty() {
+            for r in conn.get_routes().await? {
+                if let Some(route) = declared_route_from_kernel(&r, &ifindex_to_name) {
+                    log.routes.entry(route_key(&route)).or_default().push(route);
+                }
+            }
+        }
+
+        if !diff.neighbors_to_set.is_empty() {
+            for n in conn.get_neighbors().await? {
+                let (Some(name), Some(addr), Some(lladdr)) = (
+                    ifindex_to_name.get(&n.ifindex()),
+                    n.destination(),
+                    n.lladdr().and_then(|l| <[u8; 6]>::try_from(l).ok()),
+                ) else {
+                    continue;
+                };
+                if n.is_permanent() {
+                    log.neighbors.insert((name.to_string(), *addr), lladdr);
+                }
+            }
+        }
+
+        Ok(log)
+    }
+
+    fn push(&mut self, step: Undo) {
+        self.steps.push(step);
+    }
+
+    fn link_modified(&mut self, name: &str, changes: &LinkChanges) {
+        let Some((was_up, mtu, master)) = self.links.get(name) else {
+            return;
+        };
+        let mut inverse = LinkChanges::default();
+        if changes.set_up || changes.set_down {
+            inverse.set_up = *was_up;
+            inverse.set_down = !*was_up;
+        }
+        if changes.set_mtu.is_some() {
+            inverse.set_mtu = *mtu;
+        }
+        if changes.set_master.is_some() || changes.unset_master {
+            match master {
+                Some(m) => inverse.set_master = Some(m.clone()),
+                None => inverse.unset_master = true,
+            }
+        }
+        self.steps
+            .push(Undo::RestoreLink(name.to_string(), inverse));
+    }
+
+    fn route_replaced(&mut self, route: &DeclaredRoute) {
+        let previous = self
+            .routes
+            .get(&route_key(route))
+            .cloned()
+            .unwrap_or_default();
+        self.steps.push(Undo::RestoreRoute {
+            added: route.clone(),
+            previous,
+        });
+    }
+
+    fn sysctl_set(&mut self, sysctl: &DeclaredSysctl) {
+        // Unreadable beforehand: nothing to restore to.
+        if let Some(value) = self.sysctls.get(&(sysctl.dev.clone(), sysctl.key)) {
+            self.steps.push(Undo::RestoreSysctl(DeclaredSysctl {
+                value: *value,
+                ..sysctl.clone()
+            }));
+        }
+    }
+
+    fn neighbor_set(&mut self, neigh: &DeclaredNeighbor) {
+        let previous = self
+            .neighbors
+            .get(&(neigh.dev.clone(), neigh.address))
+            .copied();
+        self.steps.push(Undo::RestoreNeighbor {
+            added: neigh.clone(),
+            previous,
+        });
+    }
+
+    /// Run the recorded steps in reverse order.
+    pub(super) async fn run(self, conn: &Connection<Route>) -> RollbackReport {
+        let mut report = RollbackReport::default();
+        let netns = self.netns;
+        let owner = self.owner;
+        for step in self.steps.into_iter().rev() {
+            let (operation, outcome) = match step {
+                Undo::DeleteLink(name) => (
+                    format!("delete link {name}"),
+                    tolerate_not_found(conn.del_link(name.as_str()).await),
+                ),
+                Undo::RestoreLink(name, inverse) => (
+                    format!("restore link {name} ({})", inverse.summary()),
+                    modify_link(conn, &name, &inverse).await,
+                ),
+                Undo::DeleteAddress(addr) => (
+                    format!(
+                        "delete address {}/{} on {}",
+                        addr.address, addr.prefix_len, addr.dev
+                    ),
+                    tolerate_not_found(del_address(conn, &addr).await),
+                ),
+                Undo::AddAddress(addr) => (
+                    format!(
+                        "re-add address {}/{} on {}",
+                        addr.address, addr.prefix_len, addr.dev
+                    ),
+                    add_address(conn, &addr, owner).await,
+                ),
+                Undo::RestoreRoute { added, previous } => {
+                    let op = if previous.is_empty() {
+                        format!("delete route {}/{}", added.destination, added.prefix_len)
+                    } else {
+                        format!("restore route {}/{}", added.destination, added.prefix_len)
+                    };
+                    let mut outcome = tolerate_not_found(del_route(conn, &added).await);
+                    for route in &previous {
+                        if outcome.is_err() {
+                            break;
+                        }
+                        outcome = add_route(conn, route, None).await;
+                    }
+                    (op, outcome)
+                }
+                Undo::AddRoute(route) => (
+                    format!("re-add route {}/{}", route.destination, route.prefix_len),
+                    add_route(conn, &route, owner).await,
+                ),
+                Undo::DeleteQdisc(qdisc) => (
+                    format!("delete qdisc {}", qdisc.describe()),
+                    tolerate_not_found(conn.del_qdisc(&qdisc.dev, qdisc.parent.handle()).await),
+                ),
+                Undo::DeleteClass(class) => (
+                    format!("delete class {}", class.describe()),
+                    tolerate_not_found(
+                        conn.del_class(&class.dev, class.parent, class.classid)
+                            .await,
+                    ),
+                ),
+                Undo::DeleteFilter(filter) => (
+                    format!("delete filter {}", filter.describe()),
+                    tolerate_not_found(
+                        conn.del_filter(
+                            &filter.dev,
+                            filter.parent,
+                            filter.protocol(),
+                            filter.priority,
+                        )
+                        .await,
+                    ),
+                ),
+                Undo::RestoreSysctl(sysctl) => (
+                    format!("restore sysctl {} = {}", sysctl.full_key(), sysctl.value),
+                    set_sysctl(netns.as_deref(), &sysctl),
+                ),
+                Undo::DeleteRule(rule) => (
+                    format!("delete rule {}", rule_summary(&rule)),
+                    tolerate_not_found(del_rule(conn, &rule).await),
+                ),
+                Undo::RestoreNeighbor { added, previous } => match previous {
+                    Some(lladdr) => (
+                        format!(
+                            "restore neighbor {} lladdr {} on {}",
+                            added.address,
+                            format_mac(&lladdr),
+                            added.dev
+                        ),
+                        set_neighbor(
+                            conn,
+                            &DeclaredNeighbor {
+                                lladdr,
+                                ..added.clone()
+                            },
+                        )
+                        .await,
+                    ),
+                    None => (
+                        format!("delete neighbor {} on {}", added.address, added.dev),
+                        tolerate_not_found(del_neighbor(conn, &added).await),
+                    ),
+                },
+                Undo::DeleteNamespace(name) => (
+                    format!("delete namespace {name}"),
+                    tolerate_not_found(namespace::delete(&name)),
+                ),
+                Undo::InNamespace(name, log) => match namespace::connection_for::<Route>(&name) {
+                    Ok(ns_conn) => {
+                        let sub = Box::pin(log.run(&ns_conn)).await;
+                        let tag = |s: String| format!("{s} in namespace {name}");
+                        report.reverted.extend(sub.reverted.into_iter().map(tag));
+                        report
+                            .not_reverted
+                            .extend(sub.not_reverted.into_iter().map(tag));
+                        report
+                            .errors
+                            .extend(sub.errors.into_iter().map(|e| ApplyError {
+                                operation: tag(e.operation),
+                                error: e.error,
+                            }));
+                        continue;
+                    }
+                    Err(e) => (format!("open namespace {name}"), Err(e)),
+                },
+                Undo::Irreversible(what) => {
+                    report.not_reverted.push(what);
+                    continue;
+                }
+            };
+            match outcome {
+                Ok(()) => report.reverted.push(capitalize_first(&operation)),
+                Err(error) => report.errors.push(ApplyError { operation, error }),
+            }
+        }
+        report
+    }
+}
+
+fn route_key(route: &DeclaredRoute) -> (IpAddr, u8, u32, u32) {
+    (
+        route.destination,
+        route.prefix_len,
+        route.table.unwrap_or(254),
+        route.metric.unwrap_or(0),
+    )
+}
+
+/// A delete whose target is already gone has nothing left to undo.
+fn tolerate_not_found(outcome: Result<()>) -> Result<()> {
+    match outcome {
+        Err(e) if e.is_not_found() => Ok(()),
+        other => other,
+    }
+}
+
+fn capitalize_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
 // ============================================================================
 // Helper functions for applying individual changes
 // ============================================================================
@@ -468,8 +1371,11 @@ async fn create_link(conn: &Connection<Route>, link: &DeclaredLink) -> Result<()
             }
             conn.add_link(config).await?;
         }
-        DeclaredLinkType::Veth { peer } => {
+        DeclaredLinkType::Veth { peer, peer_netns } => {
             let mut config = VethLink::new(&link.name, peer);
+            if let Some(ns) = peer_netns {
+                config = config.peer_netns(ns)?;
+            }
             if let Some(mtu) = link.mtu {
                 config = config.mtu(mtu);
             }
@@ -668,20 +1574,36 @@ async fn modify_link(conn: &Connection<Route>, name: &str, changes: &LinkChanges
     Ok(())
 }
 
-async fn add_address(conn: &Connection<Route>, addr: &DeclaredAddress) -> Result<()> {
+/// Add an address, tagged with `owner` as its protocol if given.
+async fn add_address(
+    conn: &Connection<Route>,
+    addr: &DeclaredAddress,
+    owner: Option<u8>,
+) -> Result<()> {
     match addr.address {
         IpAddr::V4(v4) => {
-            let config = Ipv4Address::new(&addr.dev, v4, addr.prefix_len);
+            let mut config = Ipv4Address::new(&addr.dev, v4, addr.prefix_len);
+            if let Some(owner) = owner {
+                config = config.protocol(owner);
+            }
             conn.add_address(config).await
         }
         IpAddr::V6(v6) => {
-            let config = Ipv6Address::new(&addr.dev, v6, addr.prefix_len);
+            let mut config = Ipv6Address::new(&addr.dev, v6, addr.prefix_len);
+            if let Some(owner) = owner {
+                config = config.protocol(owner);
+            }
             conn.add_address(config).await
         }
     }
 }
 
-async fn add_route(conn: &Connection<Route>, route: &DeclaredRoute) -> Result<()> {
+/// Add or replace a route, tagged with `owner` as its protocol if given.
+async fn add_route(
+    conn: &Connection<Route>,
+    route: &DeclaredRoute,
+    owner: Option<u8>,
+) -> Result<()> {
     match route.destination {
         IpAddr::V4(dst) => {
             let mut config = Ipv4Route::from_addr(dst, route.prefix_len);
@@ -706,6 +1628,11 @@ async fn add_route(conn: &Connection<Route>, route: &DeclaredRoute) -> Result<()
                 config = config.table(table);
             }
 
+            // Set protocol
+            if let Some(owner) = owner {
+                config = config.protocol_id(owner);
+            }
+
             // Set route type
             config = match route.route_type {
                 DeclaredRouteType::Unicast => config,
@@ -747,6 +1674,10 @@ async fn add_route(conn: &Connection<Route>, route: &DeclaredRoute) -> Result<()
                 config = config.table(table);
             }
 
+            if let Some(owner) = owner {
+                config = config.protocol_id(owner);
+            }
+
             config = match route.route_type {
                 DeclaredRouteType::Unicast => config,
                 DeclaredRouteType::Blackhole => {
@@ -771,6 +1702,42 @@ async fn add_route(conn: &Connection<Route>, route: &DeclaredRoute) -> Result<()
     }
 }
 
+/// Check that a route's gateway answers ARP/NDP; routes without a
+/// gateway pass.
+async fn probe_gateway(
+    conn: &Connection<Route>,
+    route: &DeclaredRoute,
+    timeout: Duration,
+) -> Result<()> {
+    let Some(gateway) = route.gateway else {
+        return Ok(());
+    };
+    let ifindex = match &route.dev {
+        Some(dev) => conn.resolve_interface(&dev.as_str().into()).await?,
+        None => {
+            let via = match gateway {
+                IpAddr::V4(gw) => conn.get_route_v4(gw, 32).await?,
+                IpAddr::V6(gw) => conn.get_route_v6(gw, 128).await?,
+            };
+            via.and_then(|r| r.oif()).ok_or_else(|| {
+                Error::InvalidMessage(format!("gateway {gateway} is not on a connected network"))
+            })?
+        }
+    };
+    let answered = match conn.probe_neighbor(ifindex, gateway, timeout).await {
+        Ok(entry) => !entry.is_failed(),
+        Err(Error::Timeout) => false,
+        Err(e) => return Err(e),
+    };
+    if answered {
+        Ok(())
+    } else {
+        Err(Error::InvalidMessage(format!(
+            "gateway {gateway} does not answer ARP/NDP"
+        )))
+    }
+}
+
 async fn del_address(conn: &Connection<Route>, addr: &DeclaredAddress) -> Result<()> {
     conn.del_address(addr.dev.as_str(), addr.address, addr.prefix_len)
         .await
@@ -781,9 +1748,12 @@ async fn del_route(conn: &Connection<Route>, route: &DeclaredRoute) -> Result<()
     // table) plus any specified attributes. We replay the same
     // gateway/dev/metric/table the diff recorded so the delete is
     // unambiguous when multiple routes share a (dst, prefix, table).
+    // The protocol isn't recorded, so it's left unspecified: the kernel
+    // matches any protocol then, but only the given one otherwise.
+    let unspec = crate::netlink::types::route::RouteProtocol::Unspec;
     match route.destination {
         IpAddr::V4(dst) => {
-            let mut config = Ipv4Route::from_addr(dst, route.prefix_len);
+            let mut config = Ipv4Route::from_addr(dst, route.prefix_len).protocol(unspec);
             if let Some(IpAddr::V4(gw)) = route.gateway {
                 config = config.gateway(gw);
             }
@@ -799,7 +1769,7 @@ async fn del_route(conn: &Connection<Route>, route: &DeclaredRoute) -> Result<()
             conn.del_route(config).await
         }
         IpAddr::V6(dst) => {
-            let mut config = Ipv6Route::from_addr(dst, route.prefix_len);
+            let mut config = Ipv6Route::from_addr(dst, route.prefix_len).protocol(unspec);
             if let Some(IpAddr::V6(gw)) = route.gateway {
                 config = config.gateway(gw);
             }
@@ -817,6 +1787,80 @@ async fn del_route(conn: &Connection<Route>, route: &DeclaredRoute) -> Result<()
     }
 }
 
+/// Read a declared sysctl's current value, inside `netns` if given.
+pub(super) fn get_sysctl(netns: Option<&str>, sysctl: &DeclaredSysctl) -> Result<u32> {
+    let (proto, name) = (sysctl.key.proto(), sysctl.key.name());
+    match netns {
+        Some(ns) => namespace::get_sysctl_conf(ns, proto, &sysctl.dev, name)
+            .and_then(|v| sysctl::parse_u32(&sysctl.full_key(), &v)),
+        None => sysctl::get_conf_u32(proto, &sysctl.dev, name),
+    }
+}
+
+/// Write a declared sysctl, inside `netns` if given.
+fn set_sysctl(netns: Option<&str>, sysctl: &DeclaredSysctl) -> Result<()> {
+    let (proto, name) = (sysctl.key.proto(), sysctl.key.name());
+    let value = sysctl.value.to_string();
+    match netns {
+        Some(ns) => namespace::set_sysctl_conf(ns, proto, &sysctl.dev, name, &value),
+        None => sysctl::set_conf(proto, &sysctl.dev, name, &value),
+    }
+}
+
+fn rule_builder(rule: &DeclaredRule) -> RuleBuilder {
+    let mut b = if rule.ipv6 {
+        RuleBuilder::v6()
+    } else {
+        RuleBuilder::v4()
+    }
+    .priority(rule.priority);
+    if let Some((addr, len)) = rule.from {
+        b = b.from_addr(addr, len);
+    }
+    if let Some((addr, len)) = rule.to {
+        b = b.to_addr(addr, len);
+    }
+    match (rule.fwmark, rule.fwmask) {
+        (Some(mark), Some(mask)) => b = b.fwmark_mask(mark, mask),
+        (Some(mark), None) => b = b.fwmark(mark),
+        _ => {}
+    }
+    if let Some(iif) = &rule.iif {
+        b = b.iif(iif);
+    }
+    if let Some(oif) = &rule.oif {
+        b = b.oif(oif);
+    }
+    match rule.action {
+        DeclaredRuleAction::Lookup => b.table(rule.table),
+        DeclaredRuleAction::Blackhole => b.blackhole(),
+        DeclaredRuleAction::Unreachable => b.unreachable(),
+        DeclaredRuleAction::Prohibit => b.prohibit(),
+    }
+}
+
+async fn add_rule(conn: &Connection<Route>, rule: &DeclaredRule) -> Result<()> {
+    conn.add_rule(rule_builder(rule)).await
+}
+
+async fn del_rule(conn: &Connection<Route>, rule: &DeclaredRule) -> Result<()> {
+    conn.del_rule(rule_builder(rule)).await
+}
+
+async fn set_neighbor(conn: &Connection<Route>, neigh: &DeclaredNeighbor) -> Result<()> {
+    conn.replace_neighbor(
+        Neighbor::new(&neigh.dev, neigh.address)
+            .lladdr(neigh.lladdr)
+            .permanent(),
+    )
+    .await
+}
+
+async fn del_neighbor(conn: &Connection<Route>, neigh: &DeclaredNeighbor) -> Result<()> {
+    conn.del_neighbor(Neighbor::new(&neigh.dev, neigh.address))
+        .await
+}
+
 async fn add_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Result<()> {
     match &qdisc.qdisc_type {
         DeclaredQdiscType::Netem {
@@ -858,14 +1902,20 @@ async fn add_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Result<()
             if let Some(corr) = delay_correlation {
                 config = config.delay_correlation(crate::util::Percent::new(*corr));
             }
-            conn.add_qdisc(&qdisc.dev, config.build()).await
+            conn.add_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                config.build(),
+            )
+            .await
         }
         DeclaredQdiscType::Htb { default_class } => {
             let config = HtbQdiscConfig::new().default_class(*default_class);
             conn.add_qdisc_full(
                 &qdisc.dev,
-                crate::TcHandle::ROOT,
-                Some(crate::TcHandle::major_only(1)),
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
                 config,
             )
             .await
@@ -885,7 +1935,13 @@ async fn add_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Result<()
             if let Some(interval) = interval_us {
                 config = config.interval(Duration::from_micros(*interval as u64));
             }
-            conn.add_qdisc(&qdisc.dev, config).await
+            conn.add_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                config,
+            )
+            .await
         }
         DeclaredQdiscType::Tbf {
             rate_bps,
@@ -898,21 +1954,39 @@ async fn add_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Result<()
             if let Some(limit) = limit_bytes {
                 config = config.limit(crate::util::Bytes::new(*limit as u64));
             }
-            conn.add_qdisc(&qdisc.dev, config).await
+            conn.add_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                config,
+            )
+            .await
         }
         DeclaredQdiscType::Sfq { perturb_secs } => {
             let mut config = SfqConfig::new();
             if let Some(perturb) = perturb_secs {
                 config = config.perturb(*perturb as i32);
             }
-            conn.add_qdisc(&qdisc.dev, config).await
+            conn.add_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                config,
+            )
+            .await
         }
         DeclaredQdiscType::Prio { bands } => {
             let mut config = PrioConfig::new();
             if let Some(b) = bands {
                 config = config.bands(*b as i32);
             }
-            conn.add_qdisc(&qdisc.dev, config).await
+            conn.add_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                config,
+            )
+            .await
         }
         DeclaredQdiscType::Ingress => conn.add_qdisc(&qdisc.dev, IngressConfig::new()).await,
         DeclaredQdiscType::Clsact => conn.add_qdisc(&qdisc.dev, ClsactConfig::new()).await,
@@ -937,11 +2011,7 @@ async fn replace_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Resul
         DeclaredQdiscType::Ingress | DeclaredQdiscType::Clsact => {
             // Kernel does not accept NLM_F_REPLACE on these
             // pseudo-qdiscs (the kind IS the slot). Use del+add.
-            let parent_handle = match qdisc.parent {
-                QdiscParent::Root => crate::TcHandle::ROOT,
-                QdiscParent::Ingress => crate::TcHandle::INGRESS,
-            };
-            match conn.del_qdisc(&qdisc.dev, parent_handle).await {
+            match conn.del_qdisc(&qdisc.dev, qdisc.parent.handle()).await {
                 Ok(()) => {}
                 Err(e) if e.is_not_found() => {}
                 Err(e) => return Err(e),
@@ -992,14 +2062,20 @@ async fn replace_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Resul
             if let Some(corr) = delay_correlation {
                 cfg = cfg.delay_correlation(crate::util::Percent::new(*corr));
             }
-            conn.replace_qdisc(&qdisc.dev, cfg.build()).await
+            conn.replace_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                cfg.build(),
+            )
+            .await
         }
         DeclaredQdiscType::Htb { default_class } => {
             let cfg = HtbQdiscConfig::new().default_class(*default_class);
             conn.replace_qdisc_full(
                 &qdisc.dev,
-                crate::TcHandle::ROOT,
-                Some(crate::TcHandle::major_only(1)),
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
                 cfg,
             )
             .await
@@ -1019,7 +2095,13 @@ async fn replace_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Resul
             if let Some(i) = interval_us {
                 cfg = cfg.interval(Duration::from_micros(*i as u64));
             }
-            conn.replace_qdisc(&qdisc.dev, cfg).await
+            conn.replace_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                cfg,
+            )
+            .await
         }
         DeclaredQdiscType::Tbf {
             rate_bps,
@@ -1032,27 +2114,165 @@ async fn replace_qdisc(conn: &Connection<Route>, qdisc: &DeclaredQdisc) -> Resul
             if let Some(lim) = limit_bytes {
                 cfg = cfg.limit(crate::util::Bytes::new(*lim as u64));
             }
-            conn.replace_qdisc(&qdisc.dev, cfg).await
+            conn.replace_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                cfg,
+            )
+            .await
         }
         DeclaredQdiscType::Sfq { perturb_secs } => {
             let mut cfg = SfqConfig::new();
             if let Some(p) = perturb_secs {
                 cfg = cfg.perturb(*p as i32);
             }
-            conn.replace_qdisc(&qdisc.dev, cfg).await
+            conn.replace_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                cfg,
+            )
+            .await
         }
         DeclaredQdiscType::Prio { bands } => {
             let mut cfg = PrioConfig::new();
             if let Some(b) = bands {
                 cfg = cfg.bands(*b as i32);
             }
-            conn.replace_qdisc(&qdisc.dev, cfg).await
+            conn.replace_qdisc_full(
+                &qdisc.dev,
+                qdisc.parent.handle(),
+                qdisc.effective_handle(),
+                cfg,
+            )
+            .await
         }
         // Ingress/Clsact already handled above.
         DeclaredQdiscType::Ingress | DeclaredQdiscType::Clsact => unreachable!(),
     }
 }
 
+async fn add_class(conn: &Connection<Route>, class: &DeclaredClass) -> Result<()> {
+    conn.add_class(
+        &class.dev,
+        class.parent,
+        class.classid,
+        htb_class_config(&class.class_type),
+    )
+    .await
+}
+
+async fn replace_class(conn: &Connection<Route>, class: &DeclaredClass) -> Result<()> {
+    conn.replace_class(
+        &class.dev,
+        class.parent,
+        class.classid,
+        htb_class_config(&class.class_type),
+    )
+    .await
+}
+
+fn htb_class_config(class_type: &DeclaredClassType) -> HtbClassConfig {
+    match class_type {
+        DeclaredClassType::Htb {
+            rate_bps,
+            ceil_bps,
+            burst_bytes,
+            cburst_bytes,
+            prio,
+            quantum,
+        } => {
+            let mut cfg = HtbClassConfig::new(crate::util::Rate::bytes_per_sec(*rate_bps));
+            if let Some(ceil) = ceil_bps {
+                cfg = cfg.ceil(crate::util::Rate::bytes_per_sec(*ceil));
+            }
+            if let Some(b) = burst_bytes {
+                cfg = cfg.burst(crate::util::Bytes::new(*b as u64));
+            }
+            if let Some(b) = cburst_bytes {
+                cfg = cfg.cburst(crate::util::Bytes::new(*b as u64));
+            }
+            if let Some(p) = prio {
+                cfg = cfg.prio(*p);
+            }
+            if let Some(q) = quantum {
+                cfg = cfg.quantum(*q);
+            }
+            cfg.build()
+        }
+    }
+}
+
+async fn add_filter(conn: &Connection<Route>, filter: &DeclaredFilter) -> Result<()> {
+    conn.add_filter_full(
+        &filter.dev,
+        filter.parent,
+        None,
+        filter.protocol(),
+        filter.priority,
+        flower_filter(filter),
+    )
+    .await
+}
+
+/// Delete whatever sits at the filter's priority, then add it. A
+/// priority holds one protocol, so an in-place replace can't change
+/// the filter's protocol.
+async fn replace_filter(conn: &Connection<Route>, filter: &DeclaredFilter) -> Result<()> {
+    tolerate_not_found(
+        conn.del_filter(&filter.dev, filter.parent, 0, filter.priority)
+            .await,
+    )?;
+    add_filter(conn, filter).await
+}
+
+fn flower_filter(filter: &DeclaredFilter) -> FlowerFilter {
+    match &filter.filter_type {
+        DeclaredFilterType::Flower {
+            classid,
+            ip_proto,
+            src,
+            dst,
+            src_port,
+            dst_port,
+            ..
+        } => {
+            let mut cfg = FlowerFilter::new()
+                .priority(filter.priority)
+                .protocol(filter.protocol());
+            match filter.filter_type.eth_type() {
+                Some(super::types::ETH_P_IPV6) => cfg = cfg.ipv6(),
+                Some(_) => cfg = cfg.ipv4(),
+                None => {}
+            }
+            if let Some(class) = classid {
+                cfg = cfg.classid(*class);
+            }
+            if let Some(proto) = ip_proto {
+                cfg = cfg.ip_proto(*proto);
+            }
+            match src {
+                Some((IpAddr::V4(a), len)) => cfg = cfg.src_ipv4(*a, *len),
+                Some((IpAddr::V6(a), len)) => cfg = cfg.src_ipv6(*a, *len),
+                None => {}
+            }
+            match dst {
+                Some((IpAddr::V4(a), len)) => cfg = cfg.dst_ipv4(*a, *len),
+                Some((IpAddr::V6(a), len)) => cfg = cfg.dst_ipv6(*a, *len),
+                None => {}
+            }
+            if let Some(port) = src_port {
+                cfg = cfg.src_port(*port);
+            }
+            if let Some(port) = dst_port {
+                cfg = cfg.dst_port(*port);
+            }
+            cfg.build()
+        }
+    }
+}
+
 fn convert_macvlan_mode(mode: MacvlanMode) -> crate::netlink::link::MacvlanMode {
     match mode {
         MacvlanMode::Private => crate::netlink::link::MacvlanMode::Private,
@@ -1074,3 +2294,152 @@ fn convert_bond_mode(mode: BondMode) -> crate::netlink::link::BondMode {
         BondMode::BalanceAlb => crate::netlink::link::BondMode::BalanceAlb,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_with_link(up: bool, mtu: u32, master: Option<&str>) -> UndoLog {
+        let mut links = HashMap::new();
+        links.insert(
+            "eth0".to_string(),
+            (up, Some(mtu), master.map(str::to_string)),
+        );
+        UndoLog {
+            links,
+            routes: HashMap::new(),
+            sysctls: HashMap::new(),
+            neighbors: HashMap::new(),
+            netns: None,
+            owner: None,
+            steps: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn link_undo_restores_only_changed_fields() {
+        let mut log = log_with_link(false, 1500, Some("br0"));
+        let changes = LinkChanges {
+            set_up: true,
+            set_mtu: Some(9000),
+            ..Default::default()
+        };
+        log.link_modified("eth0", &changes);
+
+        let [Undo::RestoreLink(name, inverse)] = log.steps.as_slice() else {
+            panic!("expected one RestoreLink step");
+        };
+        assert_eq!(name, "eth0");
+        assert!(inverse.set_down && !inverse.set_up);
+        assert_eq!(inverse.set_mtu, Some(1500));
+        assert!(inverse.set_master.is_none() && !inverse.unset_master);
+    }
+
+    #[test]
+    fn link_undo_restores_missing_master() {
+        let mut log = log_with_link(true, 1500, None);
+        let changes = LinkChanges {
+            set_master: Some("br0".into()),
+            ..Default::default()
+        };
+        log.link_modified("eth0", &changes);
+
+        let [Undo::RestoreLink(_, inverse)] = log.steps.as_slice() else {
+            panic!("expected one RestoreLink step");
+        };
+        assert!(inverse.unset_master);
+        assert!(!inverse.set_up && !inverse.set_down);
+    }
+
+    #[test]
+    fn sysctl_undo_restores_captured_value_only() {
+        let mut log = log_with_link(true, 1500, None);
+        log.sysctls
+            .insert(("eth0".into(), InterfaceSysctl::RpFilter), 1);
+        let cfg = NetworkConfig::new()
+            .sysctl("eth0", InterfaceSysctl::RpFilter, 2)
+            .sysctl("new0", InterfaceSysctl::RpFilter, 2);
+        for s in cfg.sysctls() {
+            log.sysctl_set(s);
+        }
+
+        // `new0` wasn't readable beforehand, so there's nothing to restore.
+        let [Undo::RestoreSysctl(restore)] = log.steps.as_slice() else {
+            panic!("expected one RestoreSysctl step");
+        };
+        assert_eq!(restore.dev(), "eth0");
+        assert_eq!(restore.value(), 1);
+    }
+
+    #[test]
+    fn neighbor_undo_remembers_replaced_permanent_entry() {
+        let mut log = log_with_link(true, 1500, None);
+        let addr: IpAddr = "10.0.0.1".parse().unwrap();
+        log.neighbors
+            .insert(("eth0".into(), addr), [2, 0, 0, 0, 0, 9]);
+        let cfg = NetworkConfig::new()
+            .neighbor("eth0", addr, [2, 0, 0, 0, 0, 1])
+            .neighbor("eth0", "10.0.0.2".parse().unwrap(), [2, 0, 0, 0, 0, 2]);
+        for n in cfg.neighbors() {
+            log.neighbor_set(n);
+        }
+
+        let [
+            Undo::RestoreNeighbor {
+                previous: first, ..
+            },
+            Undo::RestoreNeighbor {
+                previous: second, ..
+            },
+        ] = log.steps.as_slice()
+        else {
+            panic!("expected two RestoreNeighbor steps");
+        };
+        assert_eq!(*first, Some([2, 0, 0, 0, 0, 9]));
+        assert_eq!(*second, None);
+    }
+
+    #[test]
+    fn namespace_result_is_tagged_and_counted() {
+        let mut result = ApplyResult {
+            changes_made: 1,
+            summary: vec!["Created namespace ns1".into()],
+            ..Default::default()
+        };
+        let sub = ApplyResult {
+            changes_made: 2,
+            summary: vec!["Added address 10.0.0.2/24 on veth1".into()],
+            errors: vec![ApplyError {
+                operation: "add route 0.0.0.0/0".into(),
+                error: Error::InvalidMessage("boom".into()),
+            }],
+            rollback: None,
+        };
+        merge_namespace_result(&mut result, "ns1", sub);
+
+        assert_eq!(result.changes_made, 3);
+        assert_eq!(
+            result.summary[1],
+            "Added address 10.0.0.2/24 on veth1 in namespace ns1"
+        );
+        assert_eq!(
+            result.errors[0].operation,
+            "add route 0.0.0.0/0 in namespace ns1"
+        );
+    }
+
+    #[test]
+    fn rollback_report_completeness() {
+        let mut report = RollbackReport::default();
+        assert!(report.is_complete());
+        assert_eq!(report.summary_text(), "Nothing to revert");
+
+        report.reverted.push("Delete link dummy0".into());
+        report.not_reverted.push("qdisc netem on dummy0".into());
+        assert!(!report.is_complete());
+        assert_eq!(
+            report.summary_text(),
+            "Delete link dummy0\nNot reverted: qdisc netem on dummy0"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Confirm-or-revert apply.
+//!
+//! [`ConfigDiff::apply_confirmed`] applies a diff, then reverts it
+//! after a timeout unless [`PendingApply::confirm`] is called first —
+//! the `commit confirmed` pattern from router CLIs. A change that cuts
+//! off the management session (a wrong default route, an address moved
+//! off the interface you're connected through) undoes itself instead of
+//! needing console access.
+
+use std::{
+    os::fd::AsRawFd,
+    time::{Duration, Instant},
+};
+
+use tokio::{sync::oneshot, task::JoinHandle};
+
+use super::{
+    apply::{ApplyResult, RollbackReport, apply_diff_undoable},
+    diff::{ConfigDiff, compute_diff},
+    types::NetworkConfig,
+};
+use crate::netlink::{
+    connection::Connection,
+    error::{Error, Result},
+    protocol::Route,
+};
+
+/// What the caller asked the revert timer to do.
+enum Decision {
+    Confirm,
+    RevertNow,
+}
+
+/// Changes applied by [`ConfigDiff::apply_confirmed`] (or
+/// [`NetworkConfig::apply_confirmed`]) that revert themselves at the
+/// [deadline](Self::deadline) unless confirmed.
+///
+/// Dropping a `PendingApply` does **not** confirm it: the timer keeps
+/// running on the tokio runtime and reverts at the deadline.
+#[must_use = "the changes revert at the deadline unless `confirm()` is called"]
+pub struct PendingApply {
+    result: ApplyResult,
+    deadline: Instant,
+    decision: Option<oneshot::Sender<Decision>>,
+    task: JoinHandle<Option<RollbackReport>>,
+}
+
+impl std::fmt::Debug for PendingApply {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingApply")
+            .field("result", &self.result)
+            .field("deadline", &self.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PendingApply {
+    /// What the apply did.
+    pub fn result(&self) -> &ApplyResult {
+        &self.result
+    }
+
+    /// When the changes revert unless confirmed.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Time left before the revert; zero once the deadline has passed.
+    pub fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// Keep the changes.
+    ///
+    /// Returns [`Error::Timeout`] if the deadline had already passed
+    /// and the changes were reverted.
+    pub async fn confirm(mut self) -> Result<()> {
+        self.send(Decision::Confirm);
+        match self.join().await? {
+            None => Ok(()),
+            Some(_) => Err(Error::Timeout),
+        }
+    }
+
+    /// Revert now instead of waiting for the deadline.
+    pub async fn revert(mut self) -> Result<RollbackReport> {
+        self.send(Decision::RevertNow);
+        self.join().await.map(Option::unwrap_or_default)
+    }
+
+    /// Wait for the deadline and the revert that follows.
+    pub async fn wait(mut self) -> Result<RollbackReport> {
+        // Dropping the sender leaves the timer running.
+        self.decision = None;
+        self.join().await.map(Option::unwrap_or_default)
+    }
+
+    fn send(&mut self, decision: Decision) {
+        // A closed channel means the timer already fired; `join`
+        // reports that.
+        if let Some(tx) = self.decision.take() {
+            let _ = tx.send(decision);
+        }
+    }
+
+    async fn join(self) -> Result<Option<RollbackReport>> {
+        self.task
+            .await
+            .map_err(|_| Error::InvalidMessage("apply_confirmed: revert task failed".into()))
+    }
+}
+
+impl ConfigDiff {
+    /// Apply this diff, then revert it after `timeout` unless
+    /// [`PendingApply::confirm`] is called first.
+    ///
+    /// The apply itself runs with rollback on: a failed step undoes the
+    /// steps before it and its error is returned, with nothing left
+    /// pending. Changes that can't be undone (see
+    /// [`ApplyOptions::with_rollback`](super::ApplyOptions::with_rollback))
+    /// stay in place after a revert and are listed in its
+    /// [`RollbackReport::not_reverted`].
+    ///
+    /// The revert runs on a spawned tokio task through its own
+    /// connection, opened in the same network namespace as `conn`. The
+    /// runtime must outlive the deadline for the revert to happen.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let diff = config.diff(&conn).await?;
+    /// let pending = diff.apply_confirmed(&conn, Duration::from_secs(60)).await?;
+    /// if management_still_reachable().await {
+    ///     pending.confirm().await?;
+    /// } else {
+    ///     let report = pending.revert().await?;
+    ///     println!("{}", report.summary_text());
+    /// }
+    /// ```
+    pub async fn apply_confirmed(
+        &self,
+        conn: &Connection<Route>,
+        timeout: Duration,
+    ) -> Result<PendingApply> {
+        let revert_conn = connection_alongside(conn)?;
+        let (result, undo) = apply_diff_undoable(self, conn).await?;
+        let deadline = Instant::now() + timeout;
+
+        let (tx, rx) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let expiry = tokio::time::sleep(timeout);
+            tokio::pin!(expiry);
+            let revert = tokio::select! {
+                decision = rx => match decision {
+                    Ok(Decision::Confirm) => false,
+                    Ok(Decision::RevertNow) => true,
+                    // Sender dropped without deciding: keep waiting.
+                    Err(_) => {
+                        expiry.await;
+                        true
+                    }
+                },
+                () = &mut expiry => true,
+            };
+            if revert {
+                Some(undo.run(&revert_conn).await)
+            } else {
+                None
+            }
+        });
+
+        Ok(PendingApply {
+            result,
+            deadline,
+            decision: Some(tx),
+            task,
+        })
+    }
+}
+
+/// Open a connection in the network namespace `conn`'s socket lives in.
+fn connection_alongside(conn: &Connection<Route>) -> Result<Connection<Route>> {
+    let ns = conn.namespace_fd()?;
+    Connection::new_in_namespace(ns.as_raw_fd())
+}
+
+impl NetworkConfig {
+    /// Apply the configuration, then revert it after `timeout` unless
+    /// [`PendingApply::confirm`] is called first. Computes the diff and
+    /// calls [`ConfigDiff::apply_confirmed`]; see there for details.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let pending = config.apply_confirmed(&conn, Duration::from_secs(60)).await?;
+    /// // ... check that the management session still works ...
+    /// pending.confirm().await?;
+    /// ```
+    pub async fn apply_confirmed(
+        &self,
+        conn: &Connection<Route>,
+        timeout: Duration,
+    ) -> Result<PendingApply> {
+        compute_diff(self, conn)
+            .await?
+            .apply_confirmed(conn, timeout)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn confirm_before_deadline_keeps_changes() {
+        let Ok(conn) = Connection::<Route>::new() else {
+            return;
+        };
+        let pending = NetworkConfig::new()
+            .apply_confirmed(&conn, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert!(pending.remaining() > Duration::from_secs(50));
+        pending.confirm().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn confirm_after_deadline_reports_timeout() {
+        let Ok(conn) = Connection::<Route>::new() else {
+            return;
+        };
+        let pending = NetworkConfig::new()
+            .apply_confirmed(&conn, Duration::from_millis(10))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(pending.remaining(), Duration::ZERO);
+        let err = pending.confirm().await.unwrap_err();
+        assert!(err.is_timeout(), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn revert_runs_without_waiting_for_deadline() {
+        let Ok(conn) = Connection::<Route>::new() else {
+            return;
+        };
+        let pending = NetworkConfig::new()
+            .apply_confirmed(&conn, Duration::from_secs(3600))
+            .await
+            .unwrap();
+        let report = tokio::time::timeout(Duration::from_secs(5), pending.revert())
+            .await
+            .expect("revert must not wait for the deadline")
+            .unwrap();
+        assert!(report.is_complete());
+    }
+}
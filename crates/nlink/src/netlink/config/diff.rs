@@ -10,20 +10,29 @@ use std::{
 use std::time::Duration;
 
 use super::types::{
-    DeclaredAddress, DeclaredLink, DeclaredLinkType, DeclaredQdisc, DeclaredQdiscType,
-    DeclaredRoute, DeclaredRouteType, LinkState, NetworkConfig, QdiscParent,
+    DeclaredAddress, DeclaredClass, DeclaredClassType, DeclaredFilter, DeclaredFilterType,
+    DeclaredLink, DeclaredLinkType, DeclaredNeighbor, DeclaredQdisc, DeclaredQdiscType,
+    DeclaredRoute, DeclaredRouteType, DeclaredRule, DeclaredRuleAction, DeclaredSysctl, LinkState,
+    NetworkConfig, QdiscParent,
 };
+use crate::TcHandle;
 use crate::netlink::{
     builder::MessageBuilder,
     connection::Connection,
-    error::Result,
-    messages::{AddressMessage, LinkMessage, RouteMessage, TcMessage},
+    error::{Error, Result},
+    messages::{
+        AddressMessage, LinkMessage, NeighborMessage, RouteMessage, RuleMessage, TcMessage,
+    },
+    namespace,
     protocol::Route,
+    psched,
     tc::{
         ClsactConfig, FqCodelConfig, HtbQdiscConfig, IngressConfig, NetemConfig, PrioConfig,
         QdiscConfig, SfqConfig, TbfConfig,
     },
-    types::{addr::Scope, route::RouteProtocol, route::RouteType},
+    tc_options::{FilterOptions, QdiscOptions, parse_filter_options},
+    tc_recipe_internals::htb_class_options,
+    types::{addr::Scope, route::RouteProtocol, route::RouteType, rule::FibRuleAction},
 };
 
 /// Options controlling [`NetworkConfig::diff_with_options`](crate::netlink::config::NetworkConfig::diff_with_options).
@@ -52,9 +61,13 @@ pub struct DiffOptions {
     /// - **Routes**: only `static`/`boot` protocol routes in the
     ///   main table. Kernel, RA, DHCP and redirect routes are
     ///   excluded.
-    /// - **Links and qdiscs are never purged** — deleting
-    ///   interfaces is too destructive to infer; use the imperative
-    ///   `Connection::del_link` / `del_qdisc` for those.
+    /// - **Owned configs**: with [`NetworkConfig::owner`] set, the two
+    ///   rules above are replaced by one — addresses and routes are
+    ///   removed exactly when they carry the owner tag.
+    /// - **Links and TC objects (qdiscs, classes, filters) are never
+    ///   purged** — deleting interfaces or shaping is too destructive
+    ///   to infer; use the imperative `Connection::del_link` /
+    ///   `del_qdisc` / `del_class` / `del_filter` for those.
     pub purge: bool,
 }
 
@@ -95,6 +108,28 @@ pub struct ConfigDiff {
     /// Qdiscs to replace (same position, different config).
     pub qdiscs_to_replace: Vec<DeclaredQdisc>,
 
+    /// Classes to add, parents before children.
+    pub classes_to_add: Vec<DeclaredClass>,
+    /// Classes whose parameters differ from the kernel's.
+    pub classes_to_change: Vec<DeclaredClass>,
+
+    /// Filters to add.
+    pub filters_to_add: Vec<DeclaredFilter>,
+    /// Filters to replace (a kernel filter at the same priority
+    /// differs).
+    pub filters_to_replace: Vec<DeclaredFilter>,
+
+    /// Policy routing rules to add.
+    pub rules_to_add: Vec<DeclaredRule>,
+
+    /// Neighbor entries to add, or to replace when the kernel's entry
+    /// has a different link-layer address or isn't permanent.
+    pub neighbors_to_set: Vec<DeclaredNeighbor>,
+
+    /// Per-interface sysctls whose current value differs (or can't be
+    /// read yet, e.g. on a link this diff creates).
+    pub sysctls_to_set: Vec<DeclaredSysctl>,
+
     /// Addresses to remove (purge mode only — see
     /// [`DiffOptions::purge`]). Populated **only** when the diff is
     /// computed via
@@ -103,15 +138,35 @@ pub struct ConfigDiff {
     /// [`diff`](crate::netlink::config::NetworkConfig::diff) never
     /// removes anything. Kernel-managed addresses (IPv6 link-local,
     /// loopback, any non-`global` scope) are excluded, and only
-    /// interfaces the config declares addresses on are touched.
+    /// interfaces the config declares addresses on are touched. With an
+    /// owner tag, only addresses carrying it are removed.
     pub addresses_to_remove: Vec<DeclaredAddress>,
 
     /// Routes to remove (purge mode only). Populated only under
     /// [`DiffOptions::purge`]. Restricted to the main table and to
     /// admin/user-managed protocols (`static` / `boot`); kernel,
     /// RA, DHCP and redirect routes are excluded so dynamic and
-    /// auto-configured routing is never clobbered.
+    /// auto-configured routing is never clobbered. With an owner tag,
+    /// only routes whose protocol is the tag are removed, in any table.
     pub routes_to_remove: Vec<DeclaredRoute>,
+
+    /// Named network namespaces to create (declared with
+    /// [`NetworkConfig::namespace`] but not present yet).
+    pub namespaces_to_create: Vec<String>,
+
+    /// Changes inside each declared namespace, in declared order.
+    /// Namespaces with nothing to change are omitted.
+    pub namespaces: Vec<(String, ConfigDiff)>,
+
+    /// The namespace this diff applies inside; `None` for the
+    /// caller's own.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) netns: Option<String>,
+
+    /// The config's owner tag, stamped on the addresses and routes this
+    /// diff creates (see [`NetworkConfig::owner`]).
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) owner: Option<u8>,
 }
 
 impl ConfigDiff {
@@ -148,8 +203,17 @@ impl ConfigDiff {
             && self.routes_to_add.is_empty()
             && self.qdiscs_to_add.is_empty()
             && self.qdiscs_to_replace.is_empty()
+            && self.classes_to_add.is_empty()
+            && self.classes_to_change.is_empty()
+            && self.filters_to_add.is_empty()
+            && self.filters_to_replace.is_empty()
+            && self.rules_to_add.is_empty()
+            && self.neighbors_to_set.is_empty()
+            && self.sysctls_to_set.is_empty()
             && self.addresses_to_remove.is_empty()
             && self.routes_to_remove.is_empty()
+            && self.namespaces_to_create.is_empty()
+            && self.namespaces.iter().all(|(_, d)| d.is_empty())
     }
 
     /// Get the total number of changes.
@@ -160,8 +224,21 @@ impl ConfigDiff {
             + self.routes_to_add.len()
             + self.qdiscs_to_add.len()
             + self.qdiscs_to_replace.len()
+            + self.classes_to_add.len()
+            + self.classes_to_change.len()
+            + self.filters_to_add.len()
+            + self.filters_to_replace.len()
+            + self.rules_to_add.len()
+            + self.neighbors_to_set.len()
+            + self.sysctls_to_set.len()
             + self.addresses_to_remove.len()
             + self.routes_to_remove.len()
+            + self.namespaces_to_create.len()
+            + self
+                .namespaces
+                .iter()
+                .map(|(_, d)| d.change_count())
+                .sum::<usize>()
     }
 
     /// Get a human-readable summary of the changes.
@@ -177,6 +254,11 @@ impl ConfigDiff {
     pub fn summary(&self) -> String {
         let mut lines = Vec::new();
 
+        // Namespaces
+        for name in &self.namespaces_to_create {
+            lines.push(format!("+ namespace {name}"));
+        }
+
         // Links
         for link in &self.links_to_add {
             lines.push(format!(
@@ -189,6 +271,11 @@ impl ConfigDiff {
             lines.push(format!("~ link {} ({})", name, changes.summary()));
         }
 
+        // Sysctls
+        for sysctl in &self.sysctls_to_set {
+            lines.push(format!("~ sysctl {} = {}", sysctl.full_key(), sysctl.value));
+        }
+
         // Addresses
         for addr in &self.addresses_to_add {
             lines.push(format!(
@@ -212,10 +299,23 @@ impl ConfigDiff {
                 route.destination, route.prefix_len, via, dev
             ));
         }
+        // Rules
+        for rule in &self.rules_to_add {
+            lines.push(format!("+ rule {}", rule_summary(rule)));
+        }
+        // Neighbors
+        for neigh in &self.neighbors_to_set {
+            lines.push(format!(
+                "+ neighbor {} lladdr {} on {}",
+                neigh.address,
+                format_mac(&neigh.lladdr),
+                neigh.dev
+            ));
+        }
         // Qdiscs
         for qdisc in &self.qdiscs_to_add {
             lines.push(format!(
-                "+ qdisc {} on {} ({:?})",
+                "+ qdisc {} on {} ({})",
                 qdisc.qdisc_type.kind(),
                 qdisc.dev,
                 qdisc.parent
@@ -223,12 +323,34 @@ impl ConfigDiff {
         }
         for qdisc in &self.qdiscs_to_replace {
             lines.push(format!(
-                "~ qdisc {} on {} ({:?})",
+                "~ qdisc {} on {} ({})",
                 qdisc.qdisc_type.kind(),
                 qdisc.dev,
                 qdisc.parent
             ));
         }
+        // Classes
+        for class in &self.classes_to_add {
+            lines.push(format!(
+                "+ class {} (parent {})",
+                class.describe(),
+                class.parent
+            ));
+        }
+        for class in &self.classes_to_change {
+            lines.push(format!(
+                "~ class {} (parent {})",
+                class.describe(),
+                class.parent
+            ));
+        }
+        // Filters
+        for filter in &self.filters_to_add {
+            lines.push(format!("+ filter {}", filter.describe()));
+        }
+        for filter in &self.filters_to_replace {
+            lines.push(format!("~ filter {}", filter.describe()));
+        }
 
         // Purge removals (only present under `DiffOptions::purge`).
         for addr in &self.addresses_to_remove {
@@ -253,6 +375,12 @@ impl ConfigDiff {
             ));
         }
 
+        // Changes inside namespaces, indented under a header.
+        for (name, sub) in &self.namespaces {
+            lines.push(format!("namespace {name}:"));
+            lines.extend(sub.to_string().lines().map(|l| format!("  {l}")));
+        }
+
         if lines.is_empty() {
             "No changes needed".to_string()
         } else {
@@ -274,6 +402,45 @@ impl std::fmt::Display for ConfigDiff {
     }
 }
 
+/// `ip rule`-style one-liner: `100 from 10.1.0.0/16 lookup 100`.
+pub(crate) fn rule_summary(rule: &DeclaredRule) -> String {
+    let mut s = rule.priority.to_string();
+    if let Some((addr, len)) = rule.from {
+        s.push_str(&format!(" from {addr}/{len}"));
+    }
+    if let Some((addr, len)) = rule.to {
+        s.push_str(&format!(" to {addr}/{len}"));
+    }
+    match (rule.fwmark, rule.fwmask) {
+        (Some(mark), Some(mask)) => s.push_str(&format!(" fwmark {mark:#x}/{mask:#x}")),
+        (Some(mark), None) => s.push_str(&format!(" fwmark {mark:#x}")),
+        _ => {}
+    }
+    if let Some(iif) = &rule.iif {
+        s.push_str(&format!(" iif {iif}"));
+    }
+    if let Some(oif) = &rule.oif {
+        s.push_str(&format!(" oif {oif}"));
+    }
+    match rule.action {
+        DeclaredRuleAction::Lookup => s.push_str(&format!(" lookup {}", rule.table)),
+        DeclaredRuleAction::Blackhole => s.push_str(" blackhole"),
+        DeclaredRuleAction::Unreachable => s.push_str(" unreachable"),
+        DeclaredRuleAction::Prohibit => s.push_str(" prohibit"),
+    }
+    if rule.ipv6 && rule.from.is_none() && rule.to.is_none() {
+        s.push_str(" (ipv6)");
+    }
+    s
+}
+
+pub(crate) fn format_mac(mac: &[u8; 6]) -> String {
+    format!(
+        "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+        mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]
+    )
+}
+
 /// Changes to make to an existing link.
 #[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
@@ -348,13 +515,114 @@ pub async fn compute_diff_with_options(
     conn: &Connection<Route>,
     opts: &DiffOptions,
 ) -> Result<ConfigDiff> {
-    let mut diff = ConfigDiff::default();
+    validate_namespaces(config)?;
+    validate_owner(config.owner)?;
+    let peers = veth_peers_by_netns(config);
 
+    let mut diff = ConfigDiff {
+        owner: config.owner,
+        ..Default::default()
+    };
+    diff_resources(config, Some(conn), opts, &HashSet::new(), &mut diff).await?;
+
+    for ns in &config.namespaces {
+        let provided = peers.get(ns.name.as_str()).cloned().unwrap_or_default();
+        validate_owner(ns.config.owner)?;
+        let mut sub = ConfigDiff {
+            netns: Some(ns.name.clone()),
+            owner: ns.config.owner.or(config.owner),
+            ..Default::default()
+        };
+        if namespace::exists(&ns.name) {
+            let ns_conn = namespace::connection_for::<Route>(&ns.name)?;
+            diff_resources(&ns.config, Some(&ns_conn), opts, &provided, &mut sub).await?;
+        } else {
+            // Nothing exists inside a namespace that isn't there yet,
+            // so diff against empty state.
+            diff.namespaces_to_create.push(ns.name.clone());
+            diff_resources(&ns.config, None, opts, &provided, &mut sub).await?;
+        }
+        if !sub.is_empty() {
+            diff.namespaces.push((ns.name.clone(), sub));
+        }
+    }
+
+    Ok(diff)
+}
+
+/// Reject nested and duplicate namespace declarations.
+fn validate_namespaces(config: &NetworkConfig) -> Result<()> {
+    let mut seen = HashSet::new();
+    for ns in &config.namespaces {
+        if !ns.config.namespaces.is_empty() {
+            return Err(Error::InvalidMessage(format!(
+                "namespace {}: namespaces can't be nested",
+                ns.name
+            )));
+        }
+        if !seen.insert(ns.name.as_str()) {
+            return Err(Error::InvalidMessage(format!(
+                "namespace {} declared more than once",
+                ns.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Reject owner tags that would match resources nlink didn't create:
+/// 0-2 are unspec/redirect/kernel and 3-4 the `boot`/`static` defaults.
+fn validate_owner(owner: Option<u8>) -> Result<()> {
+    match owner {
+        Some(tag) if tag <= 4 => Err(Error::InvalidMessage(format!(
+            "owner tag {tag} is reserved; use a value from 5 to 255"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Veth peer names this config creates inside each namespace, keyed
+/// by namespace name.
+fn veth_peers_by_netns(config: &NetworkConfig) -> HashMap<&str, HashSet<String>> {
+    let mut peers: HashMap<&str, HashSet<String>> = HashMap::new();
+    let all_links = config.links.iter().chain(
+        config
+            .namespaces
+            .iter()
+            .flat_map(|ns| ns.config.links.iter()),
+    );
+    for link in all_links {
+        if let DeclaredLinkType::Veth {
+            peer,
+            peer_netns: Some(ns),
+        } = &link.link_type
+        {
+            peers.entry(ns.as_str()).or_default().insert(peer.clone());
+        }
+    }
+    peers
+}
+
+/// Diff one namespace's resources. `conn` is `None` for a namespace
+/// that doesn't exist yet; `provided` names links that a veth
+/// elsewhere in the config creates here.
+async fn diff_resources(
+    config: &NetworkConfig,
+    conn: Option<&Connection<Route>>,
+    opts: &DiffOptions,
+    provided: &HashSet<String>,
+    diff: &mut ConfigDiff,
+) -> Result<()> {
     // Fetch current state
-    let current_links = conn.get_links().await?;
-    let current_addresses = conn.get_addresses().await?;
-    let current_routes = conn.get_routes().await?;
-    let current_qdiscs = conn.get_qdiscs().await?;
+    let (current_links, current_addresses, current_routes, current_qdiscs) = match conn {
+        Some(conn) => (
+            conn.get_links().await?,
+            conn.get_addresses().await?,
+            conn.get_routes().await?,
+            conn.get_qdiscs().await?,
+        ),
+        None => Default::default(),
+    };
 
     // Build lookup maps
     let link_by_name: HashMap<&str, &LinkMessage> = current_links
@@ -370,7 +638,7 @@ pub async fn compute_diff_with_options(
     // Diff links — pass the ifindex→name map so master changes
     // can be detected by resolving the kernel's master ifindex
     // back to a name (Plan 207b H2).
-    diff_links(config, &link_by_name, &ifindex_to_name, &mut diff);
+    diff_links(config, &link_by_name, &ifindex_to_name, provided, diff);
 
     // Plan 186 §3c — topo-sort `links_to_add` so a child whose
     // parent is also being created in this apply lands AFTER
@@ -383,21 +651,156 @@ pub async fn compute_diff_with_options(
     topo_sort_links_to_add(&mut diff.links_to_add);
 
     // Diff addresses
-    diff_addresses(config, &current_addresses, &ifindex_to_name, opts.purge, &mut diff);
+    diff_addresses(
+        config,
+        &current_addresses,
+        &ifindex_to_name,
+        opts.purge,
+        diff,
+    );
 
     // Diff routes
-    diff_routes(config, &current_routes, &ifindex_to_name, opts.purge, &mut diff);
+    diff_routes(config, &current_routes, &ifindex_to_name, opts.purge, diff);
+
+    // Diff qdiscs, then the classes and filters hanging off them.
+    // Classes and filters are dumped per device, and only for devices
+    // the config declares them on.
+    let rebuilt = diff_qdiscs(config, &current_qdiscs, &ifindex_to_name, diff);
+    if !config.classes.is_empty() || !config.filters.is_empty() {
+        let mut current_classes = Vec::new();
+        let mut current_filters = Vec::new();
+        if let Some(conn) = conn {
+            let devs: HashSet<&str> = config
+                .classes
+                .iter()
+                .map(|c| c.dev.as_str())
+                .chain(config.filters.iter().map(|f| f.dev.as_str()))
+                .collect();
+            for dev in devs {
+                let Some(link) = link_by_name.get(dev) else {
+                    continue;
+                };
+                current_classes.extend(conn.get_classes_by_index(link.ifindex()).await?);
+                if config.filters.iter().any(|f| f.dev == dev) {
+                    current_filters.extend(conn.get_filters_by_index(link.ifindex()).await?);
+                }
+            }
+        }
+        diff_classes(config, &current_classes, &ifindex_to_name, &rebuilt, diff)?;
+        diff_filters(config, &current_filters, &ifindex_to_name, &rebuilt, diff)?;
+    }
 
-    // Diff qdiscs
-    diff_qdiscs(config, &current_qdiscs, &ifindex_to_name, &mut diff);
+    // Diff rules
+    if !config.rules.is_empty() {
+        let current_rules = match conn {
+            Some(conn) => conn.get_rules().await?,
+            None => Vec::new(),
+        };
+        diff_rules(config, &current_rules, diff);
+    }
 
-    Ok(diff)
+    // Diff neighbors
+    if !config.neighbors.is_empty() {
+        let current_neighbors = match conn {
+            Some(conn) => conn.get_neighbors().await?,
+            None => Vec::new(),
+        };
+        diff_neighbors(config, &current_neighbors, &ifindex_to_name, diff);
+    }
+
+    // Diff sysctls
+    diff_sysctls(config, diff);
+
+    Ok(())
+}
+
+fn diff_rules(config: &NetworkConfig, current: &[RuleMessage], diff: &mut ConfigDiff) {
+    for declared in &config.rules {
+        if !current.iter().any(|r| rule_matches(declared, r)) {
+            diff.rules_to_add.push(declared.clone());
+        }
+    }
+}
+
+/// Whether a kernel rule is the declared one: same family and
+/// priority, selectors and action. Several kernel rules may share a
+/// priority, so this is checked against each of them.
+pub(crate) fn rule_matches(declared: &DeclaredRule, kernel: &RuleMessage) -> bool {
+    if kernel.is_ipv6() != declared.ipv6 || kernel.priority() != declared.priority {
+        return false;
+    }
+    // A /0 selector is "any", which the kernel reports as absent.
+    let selector = |sel: Option<(IpAddr, u8)>| sel.filter(|(_, len)| *len > 0);
+    let kernel_src = kernel
+        .source()
+        .filter(|_| kernel.src_len() > 0)
+        .map(|a| (a, kernel.src_len()));
+    let kernel_dst = kernel
+        .destination()
+        .filter(|_| kernel.dst_len() > 0)
+        .map(|a| (a, kernel.dst_len()));
+    if kernel_src != selector(declared.from) || kernel_dst != selector(declared.to) {
+        return false;
+    }
+    // The kernel defaults an unset mask to all-ones and reports it.
+    let kernel_mark = kernel.fwmark().filter(|m| *m != 0);
+    if kernel_mark != declared.fwmark {
+        return false;
+    }
+    if declared.fwmark.is_some()
+        && kernel.fwmask().unwrap_or(u32::MAX) != declared.fwmask.unwrap_or(u32::MAX)
+    {
+        return false;
+    }
+    if kernel.iifname() != declared.iif.as_deref() || kernel.oifname() != declared.oif.as_deref() {
+        return false;
+    }
+    match declared.action {
+        DeclaredRuleAction::Lookup => {
+            kernel.action() == FibRuleAction::ToTbl && kernel.table() == declared.table
+        }
+        DeclaredRuleAction::Blackhole => kernel.action() == FibRuleAction::Blackhole,
+        DeclaredRuleAction::Unreachable => kernel.action() == FibRuleAction::Unreachable,
+        DeclaredRuleAction::Prohibit => kernel.action() == FibRuleAction::Prohibit,
+    }
+}
+
+fn diff_neighbors(
+    config: &NetworkConfig,
+    current: &[NeighborMessage],
+    ifindex_to_name: &HashMap<u32, &str>,
+    diff: &mut ConfigDiff,
+) {
+    for declared in &config.neighbors {
+        let existing = current.iter().find(|n| {
+            n.destination() == Some(&declared.address)
+                && ifindex_to_name.get(&n.ifindex()) == Some(&declared.dev.as_str())
+        });
+        let in_place = existing
+            .is_some_and(|n| n.is_permanent() && n.lladdr() == Some(declared.lladdr.as_slice()));
+        if !in_place {
+            diff.neighbors_to_set.push(declared.clone());
+        }
+    }
+}
+
+/// Sysctls are read from `/proc/sys` of the diff's namespace when it
+/// has one, else of the calling thread's (the connection's namespace
+/// isn't observable here).
+fn diff_sysctls(config: &NetworkConfig, diff: &mut ConfigDiff) {
+    for declared in &config.sysctls {
+        let current = super::apply::get_sysctl(diff.netns.as_deref(), declared).ok();
+        if current != Some(declared.value) {
+            diff.sysctls_to_set.push(declared.clone());
+        }
+    }
 }
 
 fn diff_links(
     config: &NetworkConfig,
     current: &HashMap<&str, &LinkMessage>,
     ifindex_to_name: &HashMap<u32, &str>,
+    provided: &HashSet<String>,
     diff: &mut ConfigDiff,
 ) {
     // Note: desired_names would be used for purge mode to find links to remove
@@ -415,6 +818,14 @@ fn diff_links(
             // But only if it's not a physical interface
             if declared.link_type != DeclaredLinkType::Physical {
                 diff.links_to_add.push(declared.clone());
+            } else if provided.contains(&declared.name) {
+                // The peer end of a veth this apply creates from
+                // another namespace: configure it as a new, down,
+                // unenslaved link once it exists.
+                let changes = fresh_link_changes(declared);
+                if !changes.is_empty() {
+                    diff.links_to_modify.push((declared.name.clone(), changes));
+                }
             }
         }
     }
@@ -507,6 +918,16 @@ fn topo_sort_links_to_add(links: &mut Vec<DeclaredLink>) {
     *links = out;
 }
 
+/// Changes that bring a freshly-created link to the declared state.
+fn fresh_link_changes(declared: &DeclaredLink) -> LinkChanges {
+    LinkChanges {
+        set_up: declared.state == LinkState::Up,
+        set_mtu: declared.mtu,
+        set_master: declared.master.clone(),
+        ..Default::default()
+    }
+}
+
 fn compute_link_changes(
     declared: &DeclaredLink,
     existing: &LinkMessage,
@@ -619,18 +1040,20 @@ fn diff_addresses(
     // declares *some* address on (we don't touch interfaces we're
     // not managing addresses for), and never kernel-managed
     // addresses (link-local / loopback / any non-global scope).
+    // An owner tag replaces both fences: what carries it is ours.
     let managed_devs: HashSet<&str> = config.addresses.iter().map(|a| a.dev.as_str()).collect();
     for a in current {
-        // Only `global` (universe) scope — excludes IPv6 link-local
-        // (fe80::, scope link), loopback (::1 / 127.0.0.1, scope
-        // host), and other kernel-managed scopes.
-        if a.scope() != Scope::Universe {
-            continue;
-        }
         let Some(name) = ifindex_to_name.get(&a.ifindex()) else {
             continue;
         };
-        if !managed_devs.contains(name) {
+        let owned = match diff.owner {
+            Some(owner) => a.protocol() == Some(owner),
+            // Only `global` (universe) scope — excludes IPv6 link-local
+            // (fe80::, scope link), loopback (::1 / 127.0.0.1, scope
+            // host), and other kernel-managed scopes.
+            None => a.scope() == Scope::Universe && managed_devs.contains(name),
+        };
+        if !owned {
             continue;
         }
         let Some(addr) = a.address else {
@@ -754,7 +1177,8 @@ fn diff_routes(
     //     blackhole/unreachable/prohibit).
     // Identity for "is it declared" is `(dst, prefix, table)`, the
     // same key the add path uses, so a route being replaced is never
-    // also queued for removal.
+    // also queued for removal. With an owner tag the only fence is
+    // the route's protocol, which apply set to the tag.
     let desired_keys: HashSet<(IpAddr, u8, u32)> = config
         .routes
         .iter()
@@ -762,57 +1186,87 @@ fn diff_routes(
         .collect();
 
     for r in current {
-        if r.table_id() != 254 {
+        let owned = match diff.owner {
+            Some(owner) => r.protocol() == RouteProtocol::from(owner),
+            None => {
+                r.table_id() == 254
+                    && matches!(r.protocol(), RouteProtocol::Static | RouteProtocol::Boot)
+            }
+        };
+        if !owned {
             continue;
         }
-        if !matches!(r.protocol(), RouteProtocol::Static | RouteProtocol::Boot) {
+        // Local/broadcast/multicast/anycast/… are kernel-managed —
+        // never purge them.
+        let Some(route) = declared_route_from_kernel(r, ifindex_to_name) else {
             continue;
-        }
-        let route_type = match r.route_type() {
-            RouteType::Unicast => DeclaredRouteType::Unicast,
-            RouteType::Blackhole => DeclaredRouteType::Blackhole,
-            RouteType::Unreachable => DeclaredRouteType::Unreachable,
-            RouteType::Prohibit => DeclaredRouteType::Prohibit,
-            // Local/broadcast/multicast/anycast/… are kernel-managed
-            // — never purge them.
-            _ => continue,
         };
-        let dst = r.destination.unwrap_or_else(|| {
-            if r.is_ipv4() {
-                IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED)
-            } else {
-                IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED)
-            }
-        });
-        let key = (dst, r.dst_len(), r.table_id());
+        let key = (
+            route.destination,
+            route.prefix_len,
+            route.table.unwrap_or(254),
+        );
         if desired_keys.contains(&key) {
             continue;
         }
-        let dev = r
-            .oif()
-            .and_then(|idx| ifindex_to_name.get(&idx))
-            .map(|n| n.to_string());
-        diff.routes_to_remove.push(DeclaredRoute {
-            destination: dst,
-            prefix_len: r.dst_len(),
-            gateway: r.gateway().copied(),
-            dev,
-            metric: r.priority(),
-            table: Some(r.table_id()),
-            route_type,
-        });
+        diff.routes_to_remove.push(route);
     }
 }
 
+/// Convert a kernel route into its [`DeclaredRoute`] form, so it can
+/// be replayed through the apply path (purge removal, rollback
+/// restore). Returns `None` for route types the declarative model
+/// doesn't carry (local, broadcast, multicast, …).
+pub(crate) fn declared_route_from_kernel(
+    r: &RouteMessage,
+    ifindex_to_name: &HashMap<u32, &str>,
+) -> Option<DeclaredRoute> {
+    let route_type = match r.route_type() {
+        RouteType::Unicast => DeclaredRouteType::Unicast,
+        RouteType::Blackhole => DeclaredRouteType::Blackhole,
+        RouteType::Unreachable => DeclaredRouteType::Unreachable,
+        RouteType::Prohibit => DeclaredRouteType::Prohibit,
+        _ => return None,
+    };
+    let dst = r.destination.unwrap_or_else(|| {
+        if r.is_ipv4() {
+            IpAddr::V4(std::net::Ipv4Addr::UNSPECIFIED)
+        } else {
+            IpAddr::V6(std::net::Ipv6Addr::UNSPECIFIED)
+        }
+    });
+    let dev = r
+        .oif()
+        .and_then(|idx| ifindex_to_name.get(&idx))
+        .map(|n| n.to_string());
+    Some(DeclaredRoute {
+        destination: dst,
+        prefix_len: r.dst_len(),
+        gateway: r.gateway().copied(),
+        dev,
+        metric: r.priority(),
+        table: Some(r.table_id()),
+        route_type,
+    })
+}
+
+/// Diff qdiscs by position: root or ingress per device, or the class
+/// a leaf qdisc is attached to.
+///
+/// Returns the `(device, major)` pairs whose current qdisc is being
+/// replaced by a different kind or handle. Replacing a qdisc that way
+/// takes its classes, filters and leaf qdiscs with it, so everything
+/// under those majors is diffed as absent.
 fn diff_qdiscs(
     config: &NetworkConfig,
     current: &[TcMessage],
     ifindex_to_name: &HashMap<u32, &str>,
     diff: &mut ConfigDiff,
-) {
-    // Build map of current root qdiscs by device
+) -> HashSet<(String, u16)> {
+    // Build map of current qdiscs by device and parent
     let mut current_root_qdisc: HashMap<&str, &TcMessage> = HashMap::new();
     let mut current_ingress_qdisc: HashMap<&str, &TcMessage> = HashMap::new();
+    let mut current_leaf_qdisc: HashMap<(&str, TcHandle), &TcMessage> = HashMap::new();
 
     for qdisc in current {
         if let Some(name) = ifindex_to_name.get(&qdisc.ifindex()) {
@@ -820,25 +1274,52 @@ fn diff_qdiscs(
                 current_root_qdisc.insert(*name, qdisc);
             } else if qdisc.is_ingress() {
                 current_ingress_qdisc.insert(*name, qdisc);
+            } else {
+                current_leaf_qdisc.insert((*name, qdisc.parent()), qdisc);
             }
         }
     }
 
-    for declared in &config.qdiscs {
-        let current_map = match declared.parent {
-            QdiscParent::Root => &current_root_qdisc,
-            QdiscParent::Ingress => &current_ingress_qdisc,
+    let mut rebuilt: HashSet<(String, u16)> = HashSet::new();
+
+    // Root and ingress qdiscs first: whether they're rebuilt decides
+    // whether a leaf qdisc below them still exists.
+    let (top, leaves): (Vec<&DeclaredQdisc>, Vec<&DeclaredQdisc>) = config
+        .qdiscs
+        .iter()
+        .partition(|q| !matches!(q.parent, QdiscParent::Class(_)));
+
+    for declared in top.into_iter().chain(leaves) {
+        let dev = declared.dev.as_str();
+        let existing = match declared.parent {
+            QdiscParent::Class(class) => {
+                if rebuilt.contains(&(declared.dev.clone(), class.major())) {
+                    None
+                } else {
+                    current_leaf_qdisc.get(&(dev, class))
+                }
+            }
+            QdiscParent::Ingress => current_ingress_qdisc.get(dev),
+            _ => current_root_qdisc.get(dev),
         };
 
-        if let Some(existing) = current_map.get(declared.dev.as_str()) {
+        if let Some(existing) = existing {
             // Qdisc exists, check if it matches
             let existing_kind = existing.kind().unwrap_or("");
             let desired_kind = declared.qdisc_type.kind();
-
-            if existing_kind != desired_kind {
-                // Different type, need to replace.
+            let handle_differs = declared
+                .effective_handle()
+                .is_some_and(|h| h != existing.handle());
+
+            if existing_kind != desired_kind || handle_differs {
+                // Different type or handle: the replace swaps in a new
+                // qdisc and drops everything below the old one.
+                rebuilt.insert((declared.dev.clone(), existing.handle().major()));
+                if let Some(h) = declared.effective_handle() {
+                    rebuilt.insert((declared.dev.clone(), h.major()));
+                }
                 diff.qdiscs_to_replace.push(declared.clone());
-            } else if !qdisc_params_match(&declared.qdisc_type, existing.raw_options()) {
+            } else if !qdisc_matches(&declared.qdisc_type, existing) {
                 // Same kind, different parameters — replace.
                 diff.qdiscs_to_replace.push(declared.clone());
             }
@@ -847,6 +1328,259 @@ fn diff_qdiscs(
             diff.qdiscs_to_add.push(declared.clone());
         }
     }
+
+    rebuilt
+}
+
+/// Diff classes by device and class ID.
+///
+/// A class can't be moved to another parent in place, so a kernel
+/// class whose parent differs from the declared one is an error
+/// rather than a change.
+fn diff_classes(
+    config: &NetworkConfig,
+    current: &[TcMessage],
+    ifindex_to_name: &HashMap<u32, &str>,
+    rebuilt: &HashSet<(String, u16)>,
+    diff: &mut ConfigDiff,
+) -> Result<()> {
+    let current_by_id: HashMap<(&str, TcHandle), &TcMessage> = current
+        .iter()
+        .filter_map(|c| {
+            let name = *ifindex_to_name.get(&c.ifindex())?;
+            Some(((name, c.handle()), c))
+        })
+        .collect();
+
+    for declared in &config.classes {
+        let dev = declared.dev.as_str();
+        let existing = if rebuilt.contains(&(declared.dev.clone(), declared.classid.major())) {
+            None
+        } else {
+            current_by_id.get(&(dev, declared.classid))
+        };
+
+        let Some(existing) = existing else {
+            diff.classes_to_add.push(declared.clone());
+            continue;
+        };
+
+        // The kernel reports a top-level class's parent as root.
+        let expected_parent = if declared.is_top_level() {
+            TcHandle::ROOT
+        } else {
+            declared.parent
+        };
+        if existing.parent() != expected_parent {
+            return Err(Error::InvalidMessage(format!(
+                "class {} has parent {} in the kernel, not {}; delete it to move it",
+                declared.describe(),
+                existing.parent(),
+                declared.parent
+            )));
+        }
+        if !class_params_match(&declared.class_type, existing) {
+            diff.classes_to_change.push(declared.clone());
+        }
+    }
+
+    topo_sort_classes_to_add(&mut diff.classes_to_add);
+    Ok(())
+}
+
+/// Compare a declared class against the kernel's.
+///
+/// Unset optional parameters are derived by the kernel, so they're
+/// compared only when declared. A burst goes through the kernel as
+/// psched ticks at the class's rate, so the declared one is put
+/// through the same conversion before comparing.
+fn class_params_match(declared: &DeclaredClassType, existing: &TcMessage) -> bool {
+    match declared {
+        DeclaredClassType::Htb {
+            rate_bps,
+            ceil_bps,
+            burst_bytes,
+            cburst_bytes,
+            prio,
+            quantum,
+        } => {
+            let Some(live) = htb_class_options(existing) else {
+                return false;
+            };
+            let ceil = ceil_bps.unwrap_or(*rate_bps);
+            let bucket_matches = |declared: Option<u32>, rate: u64, live: u32| {
+                declared.is_none_or(|bytes| {
+                    psched::tc_calc_xmitsize(rate, psched::tc_calc_xmittime(rate, bytes)) == live
+                })
+            };
+            live.rate == *rate_bps
+                && live.ceil == ceil
+                && live.priority == prio.unwrap_or(0)
+                && quantum.is_none_or(|q| q == live.quantum)
+                && bucket_matches(*burst_bytes, *rate_bps, live.burst)
+                && bucket_matches(*cburst_bytes, ceil, live.cburst)
+        }
+    }
+}
+
+/// Order `classes_to_add` so a class whose parent is also being added
+/// comes after it. Otherwise stable, as in [`topo_sort_links_to_add`].
+fn topo_sort_classes_to_add(classes: &mut Vec<DeclaredClass>) {
+    let in_batch: HashSet<(String, TcHandle)> =
+        classes.iter().map(|c| (c.dev.clone(), c.classid)).collect();
+    let mut emitted: HashSet<(String, TcHandle)> = HashSet::new();
+    let mut out: Vec<DeclaredClass> = Vec::with_capacity(classes.len());
+    let mut remaining: Vec<DeclaredClass> = std::mem::take(classes);
+
+    while !remaining.is_empty() {
+        let before = remaining.len();
+        let mut next_remaining = Vec::with_capacity(remaining.len());
+        for class in remaining {
+            let parent = (class.dev.clone(), class.parent);
+            if !in_batch.contains(&parent) || emitted.contains(&parent) {
+                emitted.insert((class.dev.clone(), class.classid));
+                out.push(class);
+            } else {
+                next_remaining.push(class);
+            }
+        }
+        // Cycle: leave the rest in declared order for the kernel to
+        // reject.
+        if next_remaining.len() == before {
+            out.extend(next_remaining);
+            break;
+        }
+        remaining = next_remaining;
+    }
+    *classes = out;
+}
+
+/// Diff filters by device, parent and priority.
+///
+/// A declared filter matches when it's the only kernel filter at its
+/// priority and carries exactly the declared keys; anything else at
+/// that priority is replaced.
+fn diff_filters(
+    config: &NetworkConfig,
+    current: &[TcMessage],
+    ifindex_to_name: &HashMap<u32, &str>,
+    rebuilt: &HashSet<(String, u16)>,
+    diff: &mut ConfigDiff,
+) -> Result<()> {
+    let mut current_by_prio: HashMap<(&str, TcHandle, u16), Vec<&TcMessage>> = HashMap::new();
+    for filter in current {
+        // Chain heads carry no handle or options.
+        if filter.handle_raw() == 0 {
+            continue;
+        }
+        if let Some(name) = ifindex_to_name.get(&filter.ifindex()) {
+            current_by_prio
+                .entry((*name, filter.parent(), filter.priority()))
+                .or_default()
+                .push(filter);
+        }
+    }
+
+    for declared in &config.filters {
+        declared
+            .validate()
+            .map_err(|e| Error::InvalidMessage(format!("{}: {e}", declared.describe())))?;
+
+        let existing = if rebuilt.contains(&(declared.dev.clone(), declared.parent.major())) {
+            None
+        } else {
+            current_by_prio.get(&(declared.dev.as_str(), declared.parent, declared.priority))
+        };
+
+        match existing.map(Vec::as_slice) {
+            None | Some([]) => diff.filters_to_add.push(declared.clone()),
+            Some([only]) if filter_matches(declared, only) => {}
+            Some(_) => diff.filters_to_replace.push(declared.clone()),
+        }
+    }
+    Ok(())
+}
+
+/// Compare a declared filter against one kernel filter at its priority.
+fn filter_matches(declared: &DeclaredFilter, existing: &TcMessage) -> bool {
+    if existing.kind() != Some(declared.filter_type.kind())
+        || existing.protocol() != declared.protocol()
+    {
+        return false;
+    }
+    match &declared.filter_type {
+        DeclaredFilterType::Flower {
+            classid,
+            ip_proto,
+            src,
+            dst,
+            src_port,
+            dst_port,
+            ..
+        } => {
+            let Some(FilterOptions::Flower(live)) = parse_filter_options(existing) else {
+                return false;
+            };
+            let prefix = |sel: Option<(IpAddr, u8)>| sel.map(|(a, len)| (mask_addr(a, len), len));
+            live.classid == classid.map(TcHandle::as_raw)
+                && live.eth_type == declared.filter_type.eth_type()
+                && live.ip_proto == *ip_proto
+                && prefix(live.src_ip) == prefix(*src)
+                && prefix(live.dst_ip) == prefix(*dst)
+                && live.src_port == *src_port
+                && live.dst_port == *dst_port
+                // Keys and actions the declaration can't express.
+                && live.indev.is_none()
+                && live.src_mac.is_none()
+                && live.dst_mac.is_none()
+                && live.vlan_id.is_none()
+                && live.vlan_prio.is_none()
+                && live.ip_tos.is_none()
+                && live.ip_ttl.is_none()
+                && live.tcp_flags.is_none()
+                && live.src_port_range.is_none()
+                && live.dst_port_range.is_none()
+                && live.enc_key_id.is_none()
+                && live.enc_src_ip.is_none()
+                && live.enc_dst_ip.is_none()
+                && live.enc_dst_port.is_none()
+                && live.actions.is_empty()
+        }
+    }
+}
+
+/// `addr` with the bits past `len` cleared.
+fn mask_addr(addr: IpAddr, len: u8) -> IpAddr {
+    match addr {
+        IpAddr::V4(a) => {
+            let mask = u32::MAX
+                .checked_shl(32 - u32::from(len.min(32)))
+                .unwrap_or(0);
+            IpAddr::from((u32::from(a) & mask).to_be_bytes())
+        }
+        IpAddr::V6(a) => {
+            let mask = u128::MAX
+                .checked_shl(128 - u32::from(len.min(128)))
+                .unwrap_or(0);
+            IpAddr::from((u128::from(a) & mask).to_be_bytes())
+        }
+    }
+}
+
+/// Compare a declared qdisc against the kernel's.
+///
+/// HTB is compared on its parsed default class: the kernel echoes
+/// attributes the declaration doesn't write, and HTB can't be changed
+/// in place, so a false mismatch would turn into a failing replace on
+/// every apply of a class tree. Other kinds go through
+/// [`qdisc_params_match`].
+fn qdisc_matches(declared: &DeclaredQdiscType, existing: &TcMessage) -> bool {
+    match (declared, existing.options()) {
+        (DeclaredQdiscType::Htb { default_class }, Some(QdiscOptions::Htb(live))) => {
+            live.default_class == *default_class
+        }
+        _ => qdisc_params_match(declared, existing.raw_options()),
+    }
 }
 
 /// Compare a declared qdisc's parameters against the kernel's reported
@@ -982,7 +1716,7 @@ fn declared_options_bytes(t: &DeclaredQdiscType) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::netlink::config::types::MacvlanMode;
+    use crate::netlink::config::types::{InterfaceSysctl, MacvlanMode};
 
     fn declared(name: &str, link_type: DeclaredLinkType) -> DeclaredLink {
         DeclaredLink {
@@ -1370,6 +2104,17 @@ mod tests {
         assert!(!DiffOptions::default().purge(true).purge(false).purge);
     }
 
+    #[test]
+    fn owner_tags_up_to_static_are_rejected() {
+        assert!(validate_owner(None).is_ok());
+        assert!(validate_owner(Some(5)).is_ok());
+        assert!(validate_owner(Some(255)).is_ok());
+        for tag in 0..=4 {
+            let err = validate_owner(Some(tag)).unwrap_err();
+            assert!(err.to_string().contains("reserved"), "{err}");
+        }
+    }
+
     #[test]
     fn removal_collections_count_toward_is_empty_and_change_count() {
         let mut d = ConfigDiff::default();
@@ -1402,4 +2147,376 @@ mod tests {
             "route removal line missing: {rendered}"
         );
     }
+
+    #[test]
+    fn display_renders_rule_neighbor_and_sysctl_lines() {
+        let cfg = NetworkConfig::new()
+            .rule(100, |r| r.from("10.1.0.0/16").fwmark(0x10).table(100))
+            .unwrap()
+            .rule(200, |r| r.ipv6().blackhole())
+            .unwrap()
+            .neighbor("eth0", "10.0.0.1".parse().unwrap(), [2, 0, 0, 0, 0, 1])
+            .sysctl("eth0", InterfaceSysctl::Ipv6Forwarding, 1);
+        let mut d = ConfigDiff::default();
+        d.rules_to_add.extend(cfg.rules().iter().cloned());
+        d.neighbors_to_set.extend(cfg.neighbors().iter().cloned());
+        d.sysctls_to_set.extend(cfg.sysctls().iter().cloned());
+        assert_eq!(d.change_count(), 4);
+        assert!(!d.is_empty());
+
+        let rendered = format!("{d}");
+        for line in [
+            "+ rule 100 from 10.1.0.0/16 fwmark 0x10 lookup 100",
+            "+ rule 200 blackhole (ipv6)",
+            "+ neighbor 10.0.0.1 lladdr 02:00:00:00:00:01 on eth0",
+            "~ sysctl net.ipv6.conf.eth0.forwarding = 1",
+        ] {
+            assert!(rendered.contains(line), "missing `{line}`: {rendered}");
+        }
+    }
+
+    #[test]
+    fn display_renders_namespace_sections() {
+        let mut sub = ConfigDiff {
+            netns: Some("ns1".into()),
+            ..Default::default()
+        };
+        sub.addresses_to_add
+            .push(DeclaredAddress::parse("veth1", "10.0.0.2/24").unwrap());
+        let mut d = ConfigDiff::default();
+        d.namespaces_to_create.push("ns1".into());
+        d.namespaces.push(("ns1".into(), sub));
+        assert_eq!(d.change_count(), 2);
+
+        assert_eq!(
+            d.to_string(),
+            "+ namespace ns1\nnamespace ns1:\n  + address 10.0.0.2/24 on veth1"
+        );
+    }
+
+    #[test]
+    fn veth_peer_in_namespace_is_configured_once_created() {
+        let cfg = NetworkConfig::new()
+            .link("veth0", |l| l.veth("veth1").veth_peer_netns("ns1"))
+            .namespace(
+                "ns1",
+                NetworkConfig::new()
+                    .link("veth1", |l| l.up().mtu(9000))
+                    .link("eth9", |l| l.up()),
+            );
+        let peers = veth_peers_by_netns(&cfg);
+        let provided = &peers["ns1"];
+        assert!(provided.contains("veth1"));
+
+        // Nothing exists in the namespace yet: the veth peer gets the
+        // fresh-link changes, an unrelated physical link is skipped.
+        let mut d = ConfigDiff::default();
+        diff_links(
+            &cfg.namespaces()[0].config,
+            &HashMap::new(),
+            &HashMap::new(),
+            provided,
+            &mut d,
+        );
+        let [(name, changes)] = d.links_to_modify.as_slice() else {
+            panic!("expected one link to modify: {:?}", d.links_to_modify);
+        };
+        assert_eq!(name, "veth1");
+        assert!(changes.set_up);
+        assert_eq!(changes.set_mtu, Some(9000));
+        assert!(d.links_to_add.is_empty());
+    }
+
+    #[test]
+    fn nested_and_duplicate_namespaces_are_rejected() {
+        let nested = NetworkConfig::new().namespace(
+            "outer",
+            NetworkConfig::new().namespace("inner", NetworkConfig::new()),
+        );
+        let err = validate_namespaces(&nested).unwrap_err();
+        assert!(err.to_string().contains("can't be nested"), "{err}");
+
+        let dup = NetworkConfig::new()
+            .namespace("ns1", NetworkConfig::new())
+            .namespace("ns1", NetworkConfig::new());
+        let err = validate_namespaces(&dup).unwrap_err();
+        assert!(err.to_string().contains("more than once"), "{err}");
+    }
+
+    fn tc_msg(
+        kind: &str,
+        ifindex: i32,
+        handle: TcHandle,
+        parent: TcHandle,
+        opts: Vec<u8>,
+    ) -> TcMessage {
+        TcMessage {
+            header: crate::netlink::types::tc::TcMsg::new()
+                .with_ifindex(ifindex)
+                .with_handle(handle.as_raw())
+                .with_parent(parent.as_raw()),
+            kind: Some(kind.to_string()),
+            options: Some(opts),
+            ..TcMessage::default()
+        }
+    }
+
+    fn options_of(write: impl FnOnce(&mut MessageBuilder) -> Result<()>) -> Vec<u8> {
+        let mut builder = MessageBuilder::new(0, 0);
+        let start = builder.len();
+        write(&mut builder).expect("write options");
+        builder.as_bytes()[start..].to_vec()
+    }
+
+    fn live_htb_class(cfg: crate::netlink::tc::HtbClassConfig) -> TcMessage {
+        use crate::netlink::tc::ClassConfig;
+        let opts = options_of(|b| cfg.write_options(b));
+        tc_msg("htb", 1, TcHandle::new(1, 0x10), TcHandle::new(1, 1), opts)
+    }
+
+    fn live_flower(cfg: crate::netlink::filter::FlowerFilter, protocol: u16) -> TcMessage {
+        use crate::netlink::filter::FilterConfig;
+        let opts = options_of(|b| cfg.write_options(b));
+        let mut msg = tc_msg(
+            "flower",
+            1,
+            TcHandle::from_raw(1),
+            TcHandle::major_only(1),
+            opts,
+        );
+        msg.header = msg.header.with_filter_info(protocol, 10);
+        msg
+    }
+
+    fn child_class(
+        f: impl FnOnce(crate::netlink::config::ClassBuilder) -> crate::netlink::config::ClassBuilder,
+    ) -> DeclaredClass {
+        NetworkConfig::new()
+            .class("eth0", TcHandle::new(1, 0x10), |c| {
+                f(c.parent(TcHandle::new(1, 1)))
+            })
+            .classes()[0]
+            .clone()
+    }
+
+    #[test]
+    fn class_defaults_to_top_level_under_its_major() {
+        let cfg = NetworkConfig::new().class("eth0", TcHandle::new(1, 1), |c| c);
+        let class = &cfg.classes()[0];
+        assert_eq!(class.parent(), TcHandle::major_only(1));
+        assert!(class.is_top_level());
+        assert!(!child_class(|c| c).is_top_level());
+    }
+
+    #[test]
+    fn htb_class_params_compare_after_kernel_round_trip() {
+        use crate::netlink::tc::HtbClassConfig;
+        use crate::util::{Bytes, Rate};
+
+        let live = live_htb_class(
+            HtbClassConfig::new(Rate::mbit(10))
+                .ceil(Rate::mbit(20))
+                .burst(Bytes::new(15_000))
+                .prio(1),
+        );
+        let declared = child_class(|c| {
+            c.htb(Rate::mbit(10))
+                .ceil(Rate::mbit(20))
+                .burst(Bytes::new(15_000))
+                .prio(1)
+        });
+        assert!(class_params_match(&declared.class_type, &live));
+
+        // Unset burst and quantum aren't compared; prio and ceil are.
+        let declared = child_class(|c| c.htb(Rate::mbit(10)).ceil(Rate::mbit(20)).prio(1));
+        assert!(class_params_match(&declared.class_type, &live));
+        let declared = child_class(|c| c.htb(Rate::mbit(10)).prio(1));
+        assert!(!class_params_match(&declared.class_type, &live));
+        let declared = child_class(|c| c.htb(Rate::mbit(10)).ceil(Rate::mbit(20)));
+        assert!(!class_params_match(&declared.class_type, &live));
+        let declared = child_class(|c| {
+            c.htb(Rate::mbit(10))
+                .ceil(Rate::mbit(20))
+                .burst(Bytes::new(30_000))
+                .prio(1)
+        });
+        assert!(!class_params_match(&declared.class_type, &live));
+    }
+
+    #[test]
+    fn class_with_another_parent_is_an_error() {
+        let live = live_htb_class(crate::netlink::tc::HtbClassConfig::new(
+            crate::util::Rate::mbit(10),
+        ));
+        let ifindex_to_name = HashMap::from([(1, "eth0")]);
+        // Declared top-level, but the kernel has it under 1:1.
+        let config = NetworkConfig::new().class("eth0", TcHandle::new(1, 0x10), |c| {
+            c.htb(crate::util::Rate::mbit(10))
+        });
+        let mut diff = ConfigDiff::default();
+        let err = diff_classes(
+            &config,
+            &[live],
+            &ifindex_to_name,
+            &HashSet::new(),
+            &mut diff,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("parent 1:1"), "{err}");
+    }
+
+    #[test]
+    fn classes_to_add_put_parents_first() {
+        let config = NetworkConfig::new()
+            .class("eth0", TcHandle::new(1, 0x10), |c| {
+                c.parent(TcHandle::new(1, 1))
+            })
+            .class("eth0", TcHandle::new(1, 0x11), |c| {
+                c.parent(TcHandle::new(1, 0x10))
+            })
+            .class("eth0", TcHandle::new(1, 1), |c| c);
+        let mut diff = ConfigDiff::default();
+        diff_classes(&config, &[], &HashMap::new(), &HashSet::new(), &mut diff).unwrap();
+        let order: Vec<String> = diff
+            .classes_to_add
+            .iter()
+            .map(|c| c.classid().to_string())
+            .collect();
+        assert_eq!(order, ["1:1", "1:10", "1:11"]);
+    }
+
+    #[test]
+    fn replaced_root_qdisc_drops_the_tree_below_it() {
+        let live_root = tc_msg(
+            "htb",
+            1,
+            TcHandle::major_only(1),
+            TcHandle::ROOT,
+            Vec::new(),
+        );
+        let live_class = live_htb_class(crate::netlink::tc::HtbClassConfig::new(
+            crate::util::Rate::mbit(10),
+        ));
+        let ifindex_to_name = HashMap::from([(1, "eth0")]);
+        let config = NetworkConfig::new()
+            .qdisc("eth0", |q| q.htb().handle(TcHandle::major_only(2)))
+            .class("eth0", TcHandle::new(1, 0x10), |c| {
+                c.parent(TcHandle::new(1, 1))
+                    .htb(crate::util::Rate::mbit(10))
+            });
+
+        let mut diff = ConfigDiff::default();
+        let rebuilt = diff_qdiscs(&config, &[live_root], &ifindex_to_name, &mut diff);
+        assert_eq!(diff.qdiscs_to_replace.len(), 1);
+        assert!(rebuilt.contains(&("eth0".to_string(), 1)));
+
+        // The class matches the kernel's, but goes away with its qdisc.
+        diff_classes(
+            &config,
+            &[live_class],
+            &ifindex_to_name,
+            &rebuilt,
+            &mut diff,
+        )
+        .unwrap();
+        assert_eq!(diff.classes_to_add.len(), 1);
+    }
+
+    #[test]
+    fn flower_filter_matches_masked_prefix_and_keys() {
+        use crate::netlink::filter::FlowerFilter;
+
+        let config = NetworkConfig::new()
+            .filter("eth0", TcHandle::major_only(1), 10, |f| {
+                f.flower()
+                    .tcp()
+                    .dst("10.1.0.0/16")
+                    .dst_port(22)
+                    .classid(TcHandle::new(1, 0x10))
+            })
+            .unwrap();
+        let declared = &config.filters()[0];
+        let live = |port: u16| {
+            FlowerFilter::new()
+                .ipv4()
+                .ip_proto_tcp()
+                .dst_ipv4("10.1.2.3".parse().unwrap(), 16)
+                .dst_port(port)
+                .classid(TcHandle::new(1, 0x10))
+        };
+
+        assert!(filter_matches(
+            declared,
+            &live_flower(live(22), crate::netlink::config::types::ETH_P_IP)
+        ));
+        assert!(!filter_matches(
+            declared,
+            &live_flower(live(80), crate::netlink::config::types::ETH_P_IP)
+        ));
+        // Right keys, wrong `protocol`.
+        assert!(!filter_matches(declared, &live_flower(live(22), 0x0003)));
+    }
+
+    #[test]
+    fn filters_diff_by_priority() {
+        use crate::netlink::filter::FlowerFilter;
+
+        let ifindex_to_name = HashMap::from([(1, "eth0")]);
+        let config = NetworkConfig::new()
+            .filter("eth0", TcHandle::major_only(1), 10, |f| {
+                f.classid(TcHandle::new(1, 0x10))
+            })
+            .unwrap()
+            .filter("eth0", TcHandle::major_only(1), 20, |f| {
+                f.classid(TcHandle::new(1, 0x20))
+            })
+            .unwrap();
+        let live = [
+            live_flower(FlowerFilter::new().classid(TcHandle::new(1, 0x10)), 0x0003),
+            // A chain head at priority 20 isn't a filter.
+            tc_msg(
+                "flower",
+                1,
+                TcHandle::from_raw(0),
+                TcHandle::major_only(1),
+                Vec::new(),
+            ),
+        ];
+
+        let mut diff = ConfigDiff::default();
+        diff_filters(&config, &live, &ifindex_to_name, &HashSet::new(), &mut diff).unwrap();
+        assert!(diff.filters_to_replace.is_empty());
+        assert_eq!(diff.filters_to_add.len(), 1);
+        assert_eq!(diff.filters_to_add[0].priority(), 20);
+        assert_eq!(
+            diff.to_string(),
+            "+ filter flower prio 20 on eth0 parent 1:"
+        );
+    }
+
+    #[test]
+    fn invalid_deserialized_filter_is_rejected_by_the_diff() {
+        let mut config = NetworkConfig::new()
+            .filter("eth0", TcHandle::major_only(1), 10, |f| {
+                f.tcp().dst_port(22)
+            })
+            .unwrap();
+        let DeclaredFilterType::Flower { ip_proto, .. } = &mut config.filters[0].filter_type;
+        *ip_proto = None;
+
+        let mut diff = ConfigDiff::default();
+        let err =
+            diff_filters(&config, &[], &HashMap::new(), &HashSet::new(), &mut diff).unwrap_err();
+        assert!(err.to_string().contains("not tcp or udp"), "{err}");
+
+        let err = NetworkConfig::new()
+            .filter("eth0", TcHandle::major_only(1), 10, |f| {
+                f.ipv6().src("10.0.0.0/8")
+            })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::netlink::config::FilterParseError::FamilyMismatch { priority: 10 }
+        ));
+    }
 }
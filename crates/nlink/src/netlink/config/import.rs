@@ -0,0 +1,1895 @@
+//! Import of `ip`/`tc` setup scripts into a [`NetworkConfig`].
+//!
+//! Hosts that predate declarative management usually bring their
+//! network up from a shell script (`rc.local`, a unit's
+//! `ExecStartPre`, a lab bring-up script) that is a list of `ip`,
+//! `tc` and `sysctl` invocations. [`NetworkConfig::from_iproute2_script`]
+//! reads such a script and builds the equivalent config. Commands it
+//! can't express are reported rather than failing the whole import, so
+//! the result can be reviewed and finished by hand.
+
+use std::{fmt, net::IpAddr, str::FromStr};
+
+use super::types::*;
+use crate::{
+    TcHandle,
+    util::{Bytes, Percent, Rate, addr::parse_mac, get_time, names::table_id},
+};
+
+/// Result of [`NetworkConfig::from_iproute2_script`].
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// The config built from the commands that could be imported.
+    pub config: NetworkConfig,
+    /// Commands left out of [`config`](Self::config) entirely.
+    pub skipped: Vec<ImportIssue>,
+    /// Commands that were imported with some of their options dropped,
+    /// or that the config expresses slightly differently.
+    pub warnings: Vec<ImportIssue>,
+}
+
+impl ImportReport {
+    /// Whether every command was imported as written.
+    pub fn is_complete(&self) -> bool {
+        self.skipped.is_empty() && self.warnings.is_empty()
+    }
+}
+
+/// A command [`NetworkConfig::from_iproute2_script`] couldn't import
+/// as written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportIssue {
+    /// 1-based line the command starts on.
+    pub line: usize,
+    /// The command, with its words joined by single spaces.
+    pub command: String,
+    /// What was dropped and why.
+    pub message: String,
+}
+
+impl fmt::Display for ImportIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}: {}", self.line, self.message, self.command)
+    }
+}
+
+impl NetworkConfig {
+    /// Build a config from a shell script of `ip`, `tc` and `sysctl`
+    /// commands.
+    ///
+    /// The script is split the way a shell would for the simple cases
+    /// (comments, quotes, `\` continuations, `;`, `&&` and `||`, output
+    /// redirections), and each command is replayed against the config:
+    ///
+    /// - `ip link add` for the link types the config models, and
+    ///   `ip link set` for state, MTU, MAC address, master and moving a
+    ///   veth peer into a namespace; links the script only configures
+    ///   become physical links
+    /// - `ip address`, `ip route`, `ip rule` and `ip neighbor` adds
+    /// - `ip netns add`, `ip netns exec` and `-n`, which collect the
+    ///   commands into [`namespace`](Self::namespace) sections
+    /// - `tc qdisc`, `tc class` (HTB) and `tc filter` (flower) adds
+    /// - `sysctl -w` and `echo ... > /proc/sys/...` for the
+    ///   [`InterfaceSysctl`]s
+    ///
+    /// Read-only commands (`show`, `list`, `get`) are ignored. `bridge`
+    /// commands are never imported: the config has no bridge VLAN, FDB,
+    /// MDB or port-flag model, so `bridge vlan add` and `bridge link set`
+    /// always land in [`ImportReport::skipped`] (enslaving a port with
+    /// `ip link set ... master` does import). Anything else the list
+    /// above doesn't cover is skipped too; options that were dropped from
+    /// an otherwise imported command land in [`ImportReport::warnings`].
+    /// Shell variables, functions and control flow aren't evaluated.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let script = std::fs::read_to_string("/etc/rc.local")?;
+    /// let report = NetworkConfig::from_iproute2_script(&script);
+    /// for issue in report.skipped.iter().chain(&report.warnings) {
+    ///     eprintln!("{issue}");
+    /// }
+    /// println!("{}", report.config.to_json_string_pretty()?);
+    /// ```
+    pub fn from_iproute2_script(script: &str) -> ImportReport {
+        let mut importer = Importer::default();
+        for command in split_commands(script) {
+            importer.line = command.line;
+            importer.command = command.words.join(" ");
+            importer.run(None, &command.words);
+        }
+        importer.finish()
+    }
+}
+
+// ============================================================================
+// Shell splitting
+// ============================================================================
+
+/// One simple command and the line it starts on.
+#[derive(Debug)]
+struct ScriptCommand {
+    line: usize,
+    words: Vec<String>,
+}
+
+#[derive(Default)]
+struct Splitter {
+    commands: Vec<ScriptCommand>,
+    words: Vec<String>,
+    word: Option<String>,
+    line: usize,
+    start: usize,
+}
+
+impl Splitter {
+    fn push(&mut self, c: char) {
+        self.word().push(c);
+    }
+
+    fn word(&mut self) -> &mut String {
+        if self.words.is_empty() && self.word.is_none() {
+            self.start = self.line;
+        }
+        self.word.get_or_insert_with(String::new)
+    }
+
+    fn end_word(&mut self) {
+        if let Some(word) = self.word.take() {
+            self.words.push(word);
+        }
+    }
+
+    fn end_command(&mut self) {
+        self.end_word();
+        if !self.words.is_empty() {
+            self.commands.push(ScriptCommand {
+                line: self.start,
+                words: std::mem::take(&mut self.words),
+            });
+        }
+    }
+}
+
+/// Split a script into simple commands.
+fn split_commands(script: &str) -> Vec<ScriptCommand> {
+    let mut s = Splitter {
+        line: 1,
+        ..Default::default()
+    };
+    let mut quote = None;
+    let mut chars = script.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            s.line += 1;
+        }
+        if let Some(q) = quote {
+            match c {
+                c if c == q => quote = None,
+                '\\' if q == '"' => match chars.next() {
+                    Some('\n') => s.line += 1,
+                    Some(next) => s.push(next),
+                    None => {}
+                },
+                c => s.push(c),
+            }
+            continue;
+        }
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => s.line += 1,
+                Some(next) => s.push(next),
+                None => {}
+            },
+            '\'' | '"' => {
+                s.word();
+                quote = Some(c);
+            }
+            '#' if s.word.is_none() => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            // Redirections stay in the word, `2>&1` and `&>` included,
+            // and are picked apart by `strip_redirections`.
+            '>' | '<' => {
+                s.push(c);
+                while let Some(&next) = chars.peek().filter(|&&n| n == '>' || n == '&') {
+                    s.push(next);
+                    chars.next();
+                }
+            }
+            '&' if chars.peek() == Some(&'>') => s.push(c),
+            '\n' | ';' | '&' | '|' => {
+                s.end_command();
+                if c != '\n' && chars.peek() == Some(&c) {
+                    chars.next();
+                }
+            }
+            c if c.is_whitespace() => s.end_word(),
+            c => s.push(c),
+        }
+    }
+    s.end_command();
+    s.commands
+}
+
+/// Remove redirections from a command, returning the remaining words
+/// and the target of the last output redirection.
+fn strip_redirections(words: &[String]) -> (Vec<&str>, Option<&str>) {
+    let mut kept = Vec::new();
+    let mut target = None;
+    let mut iter = words.iter().map(String::as_str);
+    while let Some(word) = iter.next() {
+        let Some(at) = word.find(['>', '<']) else {
+            kept.push(word);
+            continue;
+        };
+        let fd = &word[..at];
+        if !fd.chars().all(|c| c.is_ascii_digit() || c == '&') {
+            kept.push(word);
+            continue;
+        }
+        let output = word[at..].starts_with('>');
+        let rest = word[at..].trim_start_matches(['>', '<', '&']);
+        // `2>&1` duplicates a descriptor rather than naming a file.
+        if word[at..].contains(">&") && rest.chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+        let file = if rest.is_empty() {
+            iter.next()
+        } else {
+            Some(rest)
+        };
+        if output && (fd.is_empty() || fd == "1") {
+            target = file.or(target);
+        }
+    }
+    (kept, target)
+}
+
+// ============================================================================
+// Command replay
+// ============================================================================
+
+/// Whether `word` is an abbreviation of `keyword`, as `ip(8)` accepts.
+fn matches(word: &str, keyword: &str) -> bool {
+    !word.is_empty() && keyword.starts_with(word)
+}
+
+/// The first of `keywords` that `word` abbreviates.
+fn lookup<'k>(word: &str, keywords: &[&'k str]) -> Option<&'k str> {
+    keywords.iter().copied().find(|k| matches(word, k))
+}
+
+/// Actions that only read state.
+const READ_ONLY: &[&str] = &[
+    "show", "list", "lst", "get", "save", "showdump", "help", "test", "monitor", "xstats",
+    "afstats", "identify", "pids", "list-id",
+];
+
+/// `ip` objects in the order `ip(8)` resolves abbreviations against.
+const IP_OBJECTS: &[&str] = &[
+    "address",
+    "addrlabel",
+    "maddress",
+    "route",
+    "rule",
+    "neighbor",
+    "neighbour",
+    "ntable",
+    "ntbl",
+    "link",
+    "l2tp",
+    "fou",
+    "ila",
+    "macsec",
+    "tunnel",
+    "tuntap",
+    "tap",
+    "token",
+    "tcpmetrics",
+    "tcp_metrics",
+    "monitor",
+    "xfrm",
+    "mroute",
+    "mrule",
+    "netns",
+    "netconf",
+    "vrf",
+    "sr",
+    "nexthop",
+    "mptcp",
+    "ioam",
+    "stats",
+];
+
+/// Cursor over a command's arguments.
+struct Args<'a> {
+    words: &'a [&'a str],
+    pos: usize,
+}
+
+impl<'a> Args<'a> {
+    fn new(words: &'a [&'a str]) -> Self {
+        Self { words, pos: 0 }
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let word = self.words.get(self.pos).copied();
+        self.pos += 1;
+        word
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.words.get(self.pos).copied()
+    }
+
+    /// The value following keyword `key`.
+    fn value(&mut self, key: &str) -> Result<&'a str, String> {
+        self.next().ok_or_else(|| format!("`{key}` needs a value"))
+    }
+
+    fn rest(&self) -> &'a [&'a str] {
+        &self.words[self.pos.min(self.words.len())..]
+    }
+}
+
+/// Per-command `ip`/`tc` options.
+#[derive(Clone, Copy)]
+struct Ctx<'a> {
+    ns: Option<&'a str>,
+    ipv6: bool,
+}
+
+/// Parse a number the way iproute2 does, with a `0x` prefix for hex.
+fn number<T: FromStr + TryFrom<u64>>(key: &str, value: &str) -> Result<T, String> {
+    let invalid = || format!("invalid {key} `{value}`");
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16)
+            .ok()
+            .and_then(|n| T::try_from(n).ok())
+            .ok_or_else(invalid),
+        None => value.parse().map_err(|_| invalid()),
+    }
+}
+
+fn address(key: &str, value: &str) -> Result<IpAddr, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {key} address `{value}`"))
+}
+
+/// Append the host prefix length `ip(8)` assumes for a bare address.
+fn with_prefix(value: &str) -> String {
+    match value.parse::<IpAddr>() {
+        Ok(IpAddr::V4(_)) => format!("{value}/32"),
+        Ok(IpAddr::V6(_)) => format!("{value}/128"),
+        Err(_) => value.to_string(),
+    }
+}
+
+fn table(value: &str) -> Result<u32, String> {
+    table_id(value).ok_or_else(|| format!("unknown routing table `{value}`"))
+}
+
+fn time_us(key: &str, value: &str) -> Result<u32, String> {
+    let time = get_time(value).map_err(|e| format!("invalid {key} `{value}`: {e}"))?;
+    u32::try_from(time.as_micros()).map_err(|_| format!("{key} `{value}` is too long"))
+}
+
+fn percent(key: &str, value: &str) -> Result<f64, String> {
+    value
+        .parse::<Percent>()
+        .map(Percent::as_percent)
+        .map_err(|e| format!("invalid {key} `{value}`: {e}"))
+}
+
+fn rate(key: &str, value: &str) -> Result<Rate, String> {
+    Rate::parse(value).map_err(|e| format!("invalid {key} `{value}`: {e}"))
+}
+
+fn size(key: &str, value: &str) -> Result<Bytes, String> {
+    Bytes::parse(value).map_err(|e| format!("invalid {key} `{value}`: {e}"))
+}
+
+fn handle(key: &str, value: &str) -> Result<TcHandle, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {key} `{value}`"))
+}
+
+/// Move every resource of `from` into `into`.
+fn merge(into: &mut NetworkConfig, from: NetworkConfig) {
+    into.links.extend(from.links);
+    into.addresses.extend(from.addresses);
+    into.routes.extend(from.routes);
+    into.qdiscs.extend(from.qdiscs);
+    into.classes.extend(from.classes);
+    into.filters.extend(from.filters);
+    into.rules.extend(from.rules);
+    into.neighbors.extend(from.neighbors);
+    into.sysctls.extend(from.sysctls);
+}
+
+#[derive(Default)]
+struct Importer {
+    root: NetworkConfig,
+    /// Namespace configs in order of first mention.
+    namespaces: Vec<(String, NetworkConfig)>,
+    skipped: Vec<ImportIssue>,
+    warnings: Vec<ImportIssue>,
+    line: usize,
+    command: String,
+}
+
+impl Importer {
+    fn finish(self) -> ImportReport {
+        let config = self
+            .namespaces
+            .into_iter()
+            .fold(self.root, |config, (name, ns)| config.namespace(&name, ns));
+        ImportReport {
+            config,
+            skipped: self.skipped,
+            warnings: self.warnings,
+        }
+    }
+
+    fn issue(&self, message: impl Into<String>) -> ImportIssue {
+        ImportIssue {
+            line: self.line,
+            command: self.command.clone(),
+            message: message.into(),
+        }
+    }
+
+    fn skip(&mut self, message: impl Into<String>) {
+        let issue = self.issue(message);
+        self.skipped.push(issue);
+    }
+
+    fn warn(&mut self, message: impl Into<String>) {
+        let issue = self.issue(message);
+        self.warnings.push(issue);
+    }
+
+    /// Warn about options that were parsed but not imported.
+    fn warn_ignored(&mut self, what: &str, ignored: &[String]) {
+        if !ignored.is_empty() {
+            self.warn(format!("ignored {what} options: {}", ignored.join(" ")));
+        }
+    }
+
+    /// The config commands in `ns` go to, created on first use.
+    fn config(&mut self, ns: Option<&str>) -> &mut NetworkConfig {
+        let Some(name) = ns else {
+            return &mut self.root;
+        };
+        let index = match self.namespaces.iter().position(|(n, _)| n == name) {
+            Some(index) => index,
+            None => {
+                self.namespaces
+                    .push((name.to_string(), NetworkConfig::new()));
+                self.namespaces.len() - 1
+            }
+        };
+        &mut self.namespaces[index].1
+    }
+
+    /// Build resources with the public builders and add them to `ns`.
+    fn add<E: fmt::Display>(
+        &mut self,
+        ns: Option<&str>,
+        build: impl FnOnce(NetworkConfig) -> Result<NetworkConfig, E>,
+    ) -> Result<(), String> {
+        let scratch = build(NetworkConfig::new()).map_err(|e| e.to_string())?;
+        merge(self.config(ns), scratch);
+        Ok(())
+    }
+
+    fn run(&mut self, ns: Option<&str>, words: &[String]) {
+        let (words, target) = strip_redirections(words);
+        let mut words = words.as_slice();
+        // Leading `sudo` and `VAR=value` assignments.
+        while let Some((first, rest)) = words.split_first()
+            && (*first == "sudo" || first.contains('=') && !first.starts_with('='))
+        {
+            words = rest;
+        }
+        let Some((program, args)) = words.split_first() else {
+            return;
+        };
+        let program = program.rsplit('/').next().unwrap_or(program);
+        let result = match program {
+            "ip" => self.ip(ns, args),
+            "tc" => self.tc(ns, args),
+            "bridge" => match args.get(1) {
+                Some(action) if lookup(action, READ_ONLY).is_some() => Ok(()),
+                _ => Err("bridge FDB, VLAN, MDB and port settings are not modelled".into()),
+            },
+            "sysctl" => self.sysctl(ns, args),
+            "echo" => self.echo(ns, args, target),
+            "exit" | "set" | "true" | ":" => Ok(()),
+            other => Err(format!("`{other}` is not an ip, tc or sysctl command")),
+        };
+        if let Err(message) = result {
+            self.skip(message);
+        }
+    }
+
+    // ------------------------------------------------------------------
+    // ip
+    // ------------------------------------------------------------------
+
+    fn ip(&mut self, ns: Option<&str>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let mut ctx = Ctx { ns, ipv6: false };
+        while let Some(opt) = args.peek().filter(|w| w.starts_with('-')) {
+            args.next();
+            match opt.trim_start_matches('-') {
+                "4" => ctx.ipv6 = false,
+                "6" => ctx.ipv6 = true,
+                "n" | "netns" => ctx.ns = Some(args.value(opt)?),
+                "f" | "family" => ctx.ipv6 = args.value(opt)? == "inet6",
+                "b" | "batch" => return Err("ip batch files are not followed".into()),
+                // Output formatting: -d, -s, -j, -p, -br, -c, ...
+                _ => {}
+            }
+        }
+        let Some(object) = args.next() else {
+            return Err("missing ip object".into());
+        };
+        let object =
+            lookup(object, IP_OBJECTS).ok_or_else(|| format!("unknown object `{object}`"))?;
+        let action = args.next();
+        let rest = args.rest();
+        if action.is_none_or(|a| lookup(a, READ_ONLY).is_some()) {
+            return Ok(());
+        }
+        let action = action.unwrap_or_default();
+
+        match object {
+            "link" => match lookup(action, &["add", "set", "change", "replace", "delete"]) {
+                Some("add") => self.link_add(ctx, rest),
+                Some("set" | "change") => self.link_set(ctx, rest),
+                Some("delete") => self.link_delete(ctx, rest),
+                _ => Err(format!("ip link {action} is not supported")),
+            },
+            "address" => match lookup(action, &["add", "change", "chg", "replace", "delete"]) {
+                Some("add" | "replace") => self.address_add(ctx, rest),
+                Some("delete") => self.address_delete(ctx, rest),
+                _ => Err(format!("ip address {action} is not supported")),
+            },
+            "route" => match lookup(
+                action,
+                &["add", "change", "replace", "prepend", "append", "delete"],
+            ) {
+                Some("add" | "replace" | "append") => self.route_add(ctx, rest),
+                Some("delete") => self.route_delete(ctx, rest),
+                _ => Err(format!("ip route {action} is not supported")),
+            },
+            "rule" => match lookup(action, &["add", "delete"]) {
+                Some("add") => self.rule_add(ctx, rest),
+                _ => Err(format!("ip rule {action} is not supported")),
+            },
+            "neighbor" | "neighbour" => {
+                match lookup(action, &["add", "change", "chg", "replace"]) {
+                    Some("add" | "replace") => self.neighbor_add(ctx, rest),
+                    _ => Err(format!("ip neighbor {action} is not supported")),
+                }
+            }
+            "netns" => match lookup(action, &["add", "delete", "exec"]) {
+                Some("add") => {
+                    let name = rest.first().ok_or("ip netns add needs a name")?;
+                    self.config(Some(*name));
+                    Ok(())
+                }
+                Some("exec") => {
+                    let (name, command) = rest
+                        .split_first()
+                        .ok_or("ip netns exec needs a namespace")?;
+                    let command: Vec<String> = command.iter().map(|w| w.to_string()).collect();
+                    self.run(Some(*name), &command);
+                    Ok(())
+                }
+                _ => Err(format!("ip netns {action} is not supported")),
+            },
+            other => Err(format!("ip {other} is not modelled by NetworkConfig")),
+        }
+    }
+
+    fn link_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut name, mut parent, mut mac, mut mtu, mut kind, mut netns) =
+            (None, None, None, None, None, None);
+        let mut ignored = Vec::new();
+        while let Some(word) = args.next() {
+            match word {
+                "name" | "dev" => name = Some(args.value(word)?),
+                "link" => parent = Some(args.value(word)?),
+                "address" => {
+                    let value = args.value(word)?;
+                    mac = Some(parse_mac(value).map_err(|e| e.to_string())?);
+                }
+                "mtu" => mtu = Some(number::<u32>(word, args.value(word)?)?),
+                "netns" => netns = Some(args.value(word)?),
+                "type" => {
+                    kind = Some(args.value(word)?);
+                    break;
+                }
+                "txqueuelen" | "txqlen" | "qlen" | "numtxqueues" | "numrxqueues" | "group"
+                | "index" | "alias" | "broadcast" | "brd" | "gso_max_size" | "gso_max_segs"
+                | "gro_max_size" => ignored.push(format!("{word} {}", args.value(word)?)),
+                _ if name.is_none() => name = Some(word),
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        let name = name.ok_or("no interface name")?;
+        let kind = kind.ok_or("no link type")?;
+        let ns = netns.or(ctx.ns);
+        if self.config(ns).links.iter().any(|l| l.name == name) {
+            return Err(format!("{name} is already declared"));
+        }
+
+        let mut args = Args::new(args.rest());
+        let need_parent = || {
+            parent
+                .map(str::to_string)
+                .ok_or(format!("{kind} needs `link DEV`"))
+        };
+        let link_type = match kind {
+            "dummy" => DeclaredLinkType::Dummy,
+            "ifb" => DeclaredLinkType::Ifb,
+            "bridge" => DeclaredLinkType::Bridge,
+            "veth" => {
+                let (mut peer, mut peer_netns) = (None, None);
+                while let Some(word) = args.next() {
+                    match word {
+                        "peer" => {}
+                        "name" => peer = Some(args.value(word)?.to_string()),
+                        "netns" => peer_netns = Some(args.value(word)?.to_string()),
+                        "address" | "mtu" | "txqueuelen" => {
+                            ignored.push(format!("peer {word} {}", args.value(word)?))
+                        }
+                        _ if peer.is_none() => peer = Some(word.to_string()),
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                let peer = peer.ok_or("veth without a peer name")?;
+                if let Some(peer_ns) = &peer_netns {
+                    self.config(Some(peer_ns));
+                }
+                DeclaredLinkType::Veth { peer, peer_netns }
+            }
+            "vlan" => {
+                let (mut vlan_id, mut protocol) = (None, None);
+                while let Some(word) = args.next() {
+                    match word {
+                        "id" => vlan_id = Some(number::<u16>(word, args.value(word)?)?),
+                        "protocol" => {
+                            protocol = match args.value(word)?.to_ascii_lowercase().as_str() {
+                                "802.1q" => Some(VlanProtocol::Dot1q),
+                                "802.1ad" => Some(VlanProtocol::Dot1ad),
+                                other => return Err(format!("unknown vlan protocol `{other}`")),
+                            }
+                        }
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredLinkType::Vlan {
+                    parent: need_parent()?,
+                    vlan_id: vlan_id.ok_or("vlan without an id")?,
+                    protocol,
+                }
+            }
+            "vxlan" => {
+                let (mut vni, mut remote, mut local, mut port, mut underlay_dev) =
+                    (None, None, None, None, None);
+                while let Some(word) = args.next() {
+                    match word {
+                        "id" | "vni" => vni = Some(number::<u32>(word, args.value(word)?)?),
+                        "remote" => remote = Some(address(word, args.value(word)?)?),
+                        "local" => local = Some(address(word, args.value(word)?)?),
+                        "dstport" => port = Some(number::<u16>(word, args.value(word)?)?),
+                        "dev" => underlay_dev = Some(args.value(word)?.to_string()),
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredLinkType::Vxlan {
+                    vni: vni.ok_or("vxlan without an id")?,
+                    remote,
+                    local,
+                    port,
+                    underlay_dev,
+                }
+            }
+            "macvlan" => {
+                let mut mode = MacvlanMode::default();
+                while let Some(word) = args.next() {
+                    match word {
+                        "mode" => {
+                            mode = match args.value(word)? {
+                                "private" => MacvlanMode::Private,
+                                "vepa" => MacvlanMode::Vepa,
+                                "bridge" => MacvlanMode::Bridge,
+                                "passthru" => MacvlanMode::Passthru,
+                                "source" => MacvlanMode::Source,
+                                other => return Err(format!("unknown macvlan mode `{other}`")),
+                            }
+                        }
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredLinkType::Macvlan {
+                    parent: need_parent()?,
+                    mode,
+                }
+            }
+            "bond" => self.bond(&mut args, &mut ignored)?,
+            "vrf" => {
+                let mut table_id = None;
+                while let Some(word) = args.next() {
+                    match word {
+                        "table" => table_id = Some(table(args.value(word)?)?),
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredLinkType::Vrf {
+                    table: table_id.ok_or("vrf without a table")?,
+                }
+            }
+            other => {
+                return Err(format!(
+                    "link type `{other}` is not modelled by NetworkConfig"
+                ));
+            }
+        };
+        ignored.extend(args.rest().iter().map(|w| w.to_string()));
+
+        self.config(ns).links.push(DeclaredLink {
+            name: name.to_string(),
+            link_type,
+            state: LinkState::Unchanged,
+            mtu,
+            master: None,
+            address: mac,
+        });
+        self.warn_ignored(&format!("{kind} link"), &ignored);
+        Ok(())
+    }
+
+    fn bond(
+        &mut self,
+        args: &mut Args<'_>,
+        ignored: &mut Vec<String>,
+    ) -> Result<DeclaredLinkType, String> {
+        let mut mode = BondMode::default();
+        let (mut miimon, mut xmit_hash_policy, mut min_links) = (None, None, None);
+        let (mut ad_select, mut lacp_rate) = (None, None);
+        let (mut downdelay, mut updelay, mut resend_igmp) = (None, None, None);
+        while let Some(word) = args.next() {
+            let unknown = || format!("unknown bond {word}");
+            match word {
+                "mode" => {
+                    mode = match args.value(word)? {
+                        "balance-rr" | "0" => BondMode::BalanceRr,
+                        "active-backup" | "1" => BondMode::ActiveBackup,
+                        "balance-xor" | "2" => BondMode::BalanceXor,
+                        "broadcast" | "3" => BondMode::Broadcast,
+                        "802.3ad" | "4" => BondMode::Ieee802_3ad,
+                        "balance-tlb" | "5" => BondMode::BalanceTlb,
+                        "balance-alb" | "6" => BondMode::BalanceAlb,
+                        _ => return Err(unknown()),
+                    }
+                }
+                "miimon" => miimon = Some(number(word, args.value(word)?)?),
+                "min_links" => min_links = Some(number(word, args.value(word)?)?),
+                "updelay" => updelay = Some(number(word, args.value(word)?)?),
+                "downdelay" => downdelay = Some(number(word, args.value(word)?)?),
+                "resend_igmp" => resend_igmp = Some(number(word, args.value(word)?)?),
+                "xmit_hash_policy" => {
+                    xmit_hash_policy = Some(match args.value(word)? {
+                        "layer2" => 0,
+                        "layer3+4" => 1,
+                        "layer2+3" => 2,
+                        "encap2+3" => 3,
+                        "encap3+4" => 4,
+                        "vlan+srcmac" => 5,
+                        _ => return Err(unknown()),
+                    })
+                }
+                "lacp_rate" => {
+                    lacp_rate = Some(match args.value(word)? {
+                        "slow" | "0" => BondLacpRate::Slow,
+                        "fast" | "1" => BondLacpRate::Fast,
+                        _ => return Err(unknown()),
+                    })
+                }
+                "ad_select" => {
+                    ad_select = Some(match args.value(word)? {
+                        "stable" | "0" => BondAdSelect::Stable,
+                        "bandwidth" | "1" => BondAdSelect::Bandwidth,
+                        "count" | "2" => BondAdSelect::Count,
+                        _ => return Err(unknown()),
+                    })
+                }
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        Ok(DeclaredLinkType::Bond {
+            mode,
+            miimon,
+            xmit_hash_policy,
+            min_links,
+            ad_select,
+            lacp_rate,
+            downdelay,
+            updelay,
+            resend_igmp,
+        })
+    }
+
+    /// The declared link `name` in `ns`, declared as a physical link
+    /// on first use.
+    fn link_entry(&mut self, ns: Option<&str>, name: &str) -> &mut DeclaredLink {
+        let config = self.config(ns);
+        let index = match config.links.iter().position(|l| l.name == name) {
+            Some(index) => index,
+            None => {
+                config.links.push(DeclaredLink {
+                    name: name.to_string(),
+                    link_type: DeclaredLinkType::Physical,
+                    state: LinkState::Unchanged,
+                    mtu: None,
+                    master: None,
+                    address: None,
+                });
+                config.links.len() - 1
+            }
+        };
+        &mut config.links[index]
+    }
+
+    fn link_set(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut name, mut state, mut mtu, mut mac, mut master, mut netns) =
+            (None, None, None, None, None, None);
+        let mut ignored = Vec::new();
+        while let Some(word) = args.next() {
+            match word {
+                "dev" => name = Some(args.value(word)?),
+                "up" => state = Some(LinkState::Up),
+                "down" => state = Some(LinkState::Down),
+                "mtu" => mtu = Some(number::<u32>(word, args.value(word)?)?),
+                "address" => {
+                    let value = args.value(word)?;
+                    mac = Some(parse_mac(value).map_err(|e| e.to_string())?);
+                }
+                "master" => master = Some(Some(args.value(word)?.to_string())),
+                "nomaster" => master = Some(None),
+                "netns" => netns = Some(args.value(word)?),
+                "name" => return Err("renaming interfaces is not modelled".into()),
+                "group" if name.is_none() => {
+                    return Err("ip link set group is not supported".into());
+                }
+                "txqueuelen" | "txqlen" | "qlen" | "alias" | "group" | "broadcast" | "brd"
+                | "arp" | "multicast" | "allmulticast" | "promisc" | "dynamic" | "trailers"
+                | "carrier" | "protodown" => ignored.push(format!("{word} {}", args.value(word)?)),
+                _ if name.is_none() => name = Some(word),
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        let name = name.ok_or("no interface name")?;
+
+        let mut ns = ctx.ns;
+        if let Some(dest) = netns {
+            self.move_veth_end(ctx.ns, name, dest)?;
+            ns = Some(dest);
+        }
+        if state.is_some() || mtu.is_some() || mac.is_some() || master.is_some() {
+            let peer_of = self
+                .config(ns)
+                .links
+                .iter()
+                .find(|l| {
+                    matches!(&l.link_type,
+                        DeclaredLinkType::Veth { peer, peer_netns: None } if peer == name)
+                })
+                .map(|l| l.name.clone());
+            let link = self.link_entry(ns, name);
+            if let Some(state) = state {
+                link.state = state;
+            }
+            link.mtu = mtu.or(link.mtu);
+            link.address = mac.or(link.address);
+            if let Some(master) = master {
+                link.master = master;
+            }
+            if let Some(primary) = peer_of {
+                self.warn(format!(
+                    "{name} is the peer of veth {primary}; its settings apply once it exists, \
+                     on the next apply"
+                ));
+            }
+        }
+        self.warn_ignored("link", &ignored);
+        Ok(())
+    }
+
+    /// Record `ip link set NAME netns DEST` for an end of a declared
+    /// veth pair.
+    fn move_veth_end(&mut self, ns: Option<&str>, name: &str, dest: &str) -> Result<(), String> {
+        let unsupported =
+            || format!("moving {name} to another namespace is only modelled for veth ends");
+        if ns.is_some() || dest.parse::<u32>().is_ok() {
+            return Err(unsupported());
+        }
+        let config = self.config(ns);
+        let index = config
+            .links
+            .iter()
+            .position(|l| match &l.link_type {
+                DeclaredLinkType::Veth {
+                    peer,
+                    peer_netns: None,
+                } => peer == name || l.name == name,
+                _ => false,
+            })
+            .ok_or_else(unsupported)?;
+        let link = &mut config.links[index];
+        if let DeclaredLinkType::Veth { peer, peer_netns } = &mut link.link_type {
+            if link.name == name {
+                // Moving the end the pair was created from: declare the
+                // pair from the end that stays.
+                std::mem::swap(&mut link.name, peer);
+                link.state = LinkState::Unchanged;
+                link.mtu = None;
+                link.master = None;
+                link.address = None;
+            }
+            *peer_netns = Some(dest.to_string());
+        }
+        self.config(Some(dest));
+        Ok(())
+    }
+
+    fn link_delete(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let name = match words {
+            ["dev", name, ..] | [name, ..] => *name,
+            [] => return Err("no interface name".into()),
+        };
+        let links = &mut self.config(ctx.ns).links;
+        let before = links.len();
+        links.retain(|l| l.name != name);
+        if links.len() == before {
+            return Err("deleting an interface the script didn't create is not modelled".into());
+        }
+        Ok(())
+    }
+
+    fn address_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut addr, mut dev) = (None, None);
+        let mut ignored = Vec::new();
+        while let Some(word) = args.next() {
+            match word {
+                "dev" => dev = Some(args.value(word)?),
+                "local" => addr = Some(args.value(word)?),
+                "peer" | "broadcast" | "brd" | "anycast" | "label" | "scope" | "valid_lft"
+                | "preferred_lft" | "metric" | "proto" => {
+                    ignored.push(format!("{word} {}", args.value(word)?))
+                }
+                _ if addr.is_none() => addr = Some(word),
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        let addr = with_prefix(addr.ok_or("no address")?);
+        let dev = dev.ok_or("no device")?;
+        self.add(ctx.ns, |c| c.address(dev, &addr))?;
+        self.warn_ignored("address", &ignored);
+        Ok(())
+    }
+
+    fn address_delete(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut addr, mut dev) = (None, None);
+        while let Some(word) = args.next() {
+            match word {
+                "dev" => dev = Some(args.value(word)?),
+                "local" => addr = Some(args.value(word)?),
+                _ if addr.is_none() => addr = Some(word),
+                _ => {}
+            }
+        }
+        let addr = with_prefix(addr.ok_or("no address")?);
+        let declared =
+            DeclaredAddress::parse(dev.ok_or("no device")?, &addr).map_err(|e| e.to_string())?;
+        let addresses = &mut self.config(ctx.ns).addresses;
+        let before = addresses.len();
+        addresses.retain(|a| {
+            (a.dev.as_str(), a.address, a.prefix_len)
+                != (declared.dev.as_str(), declared.address, declared.prefix_len)
+        });
+        if addresses.len() == before {
+            return Err("deleting an address the script didn't add is not modelled".into());
+        }
+        Ok(())
+    }
+
+    fn route_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let mut dst = None;
+        let mut kind = None;
+        let (mut via, mut dev, mut metric, mut table_id) = (None, None, None, None);
+        let mut ignored = Vec::new();
+        while let Some(word) = args.next() {
+            match word {
+                "unicast" => {}
+                "blackhole" | "unreachable" | "prohibit" => kind = Some(word),
+                "local" | "broadcast" | "multicast" | "throw" | "nat" | "anycast" => {
+                    return Err(format!("{word} routes are not modelled"));
+                }
+                "to" => dst = Some(args.value(word)?),
+                "via" => {
+                    let mut value = args.value(word)?;
+                    if matches!(value, "inet" | "inet6") {
+                        value = args.value(word)?;
+                    }
+                    via = Some(value);
+                }
+                "dev" | "oif" => dev = Some(args.value(word)?),
+                "metric" | "priority" | "preference" => {
+                    metric = Some(number::<u32>(word, args.value(word)?)?)
+                }
+                "table" => table_id = Some(table(args.value(word)?)?),
+                "nexthop" | "nhid" => {
+                    return Err("multipath and nexthop routes are not modelled".into());
+                }
+                "proto" | "scope" | "src" | "mtu" | "advmss" | "realm" | "realms" | "initcwnd"
+                | "initrwnd" | "expires" | "pref" | "congctl" | "rtt" | "rttvar" | "window"
+                | "cwnd" | "ssthresh" | "hoplimit" | "reordering" | "features" | "quickack"
+                | "fastopen_no_cookie" | "tos" | "dsfield" => {
+                    ignored.push(format!("{word} {}", args.value(word)?))
+                }
+                _ if dst.is_none() => dst = Some(word),
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        let dst = dst.ok_or("no destination")?;
+        let ipv6 = ctx.ipv6 || via.is_some_and(|v| v.contains(':')) || dst.contains(':');
+        let dst = match dst {
+            "default" | "all" | "any" if ipv6 => "::/0".to_string(),
+            "default" | "all" | "any" => "0.0.0.0/0".to_string(),
+            dst => with_prefix(dst),
+        };
+        self.add(ctx.ns, |c| {
+            c.route(&dst, |mut r| {
+                if let Some(via) = via {
+                    r = r.via(via);
+                }
+                if let Some(dev) = dev {
+                    r = r.dev(dev);
+                }
+                if let Some(metric) = metric {
+                    r = r.metric(metric);
+                }
+                if let Some(table) = table_id {
+                    r = r.table(table);
+                }
+                match kind {
+                    Some("blackhole") => r.blackhole(),
+                    Some("unreachable") => r.unreachable(),
+                    Some("prohibit") => r.prohibit(),
+                    _ => r,
+                }
+            })
+        })?;
+        self.warn_ignored("route", &ignored);
+        Ok(())
+    }
+
+    fn route_delete(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let dst = words
+            .iter()
+            .find(|w| !matches!(**w, "to" | "unicast"))
+            .ok_or("no destination")?;
+        let dst = match *dst {
+            "default" if ctx.ipv6 => "::/0".to_string(),
+            "default" => "0.0.0.0/0".to_string(),
+            dst => with_prefix(dst),
+        };
+        let parsed = NetworkConfig::new()
+            .route(&dst, |r| r)
+            .map_err(|e| e.to_string())?;
+        let target = &parsed.routes[0];
+        let routes = &mut self.config(ctx.ns).routes;
+        let before = routes.len();
+        routes.retain(|r| (r.destination, r.prefix_len) != (target.destination, target.prefix_len));
+        if routes.len() == before {
+            return Err("deleting a route the script didn't add is not modelled".into());
+        }
+        Ok(())
+    }
+
+    fn rule_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut from, mut to, mut fwmark, mut iif, mut oif) = (None, None, None, None, None);
+        let (mut priority, mut table_id, mut action) = (None, None, None);
+        while let Some(word) = args.next() {
+            match word {
+                "from" => from = Some(args.value(word)?).filter(|v| *v != "all"),
+                "to" => to = Some(args.value(word)?).filter(|v| *v != "all"),
+                "fwmark" => {
+                    let value = args.value(word)?;
+                    fwmark = Some(match value.split_once('/') {
+                        Some((mark, mask)) => {
+                            (number::<u32>(word, mark)?, Some(number(word, mask)?))
+                        }
+                        None => (number::<u32>(word, value)?, None),
+                    });
+                }
+                "iif" | "dev" => iif = Some(args.value(word)?),
+                "oif" => oif = Some(args.value(word)?),
+                "priority" | "preference" | "pref" | "order" => {
+                    priority = Some(number::<u32>(word, args.value(word)?)?)
+                }
+                "lookup" | "table" => table_id = Some(table(args.value(word)?)?),
+                "blackhole" | "unreachable" | "prohibit" => action = Some(word),
+                "not" => return Err("inverted rules are not modelled".into()),
+                other => return Err(format!("rule selector `{other}` is not modelled")),
+            }
+        }
+        let ipv6 = ctx.ipv6 || [from, to].iter().flatten().any(|a| a.contains(':'));
+        let priority = match priority {
+            Some(priority) => priority,
+            None => {
+                // The kernel puts an unnumbered rule just above the
+                // first numbered one of its family.
+                let first = self
+                    .config(ctx.ns)
+                    .rules
+                    .iter()
+                    .filter(|r| r.is_ipv6() == ipv6 && r.priority > 0)
+                    .map(|r| r.priority)
+                    .min()
+                    .unwrap_or(32766);
+                let priority = first.saturating_sub(1);
+                self.warn(format!(
+                    "rule has no priority; using {priority} as the kernel would"
+                ));
+                priority
+            }
+        };
+        let (from, to) = (from.map(with_prefix), to.map(with_prefix));
+        self.add(ctx.ns, |c| {
+            c.rule(priority, |mut r| {
+                if ipv6 {
+                    r = r.ipv6();
+                }
+                if let Some(from) = &from {
+                    r = r.from(from);
+                }
+                if let Some(to) = &to {
+                    r = r.to(to);
+                }
+                match fwmark {
+                    Some((mark, Some(mask))) => r = r.fwmark_mask(mark, mask),
+                    Some((mark, None)) => r = r.fwmark(mark),
+                    None => {}
+                }
+                if let Some(iif) = iif {
+                    r = r.iif(iif);
+                }
+                if let Some(oif) = oif {
+                    r = r.oif(oif);
+                }
+                if let Some(table) = table_id {
+                    r = r.table(table);
+                }
+                match action {
+                    Some("blackhole") => r.blackhole(),
+                    Some("unreachable") => r.unreachable(),
+                    Some("prohibit") => r.prohibit(),
+                    _ => r,
+                }
+            })
+        })
+    }
+
+    fn neighbor_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut addr, mut lladdr, mut dev) = (None, None, None);
+        let mut ignored = Vec::new();
+        while let Some(word) = args.next() {
+            match word {
+                "to" => addr = Some(args.value(word)?),
+                "lladdr" => lladdr = Some(args.value(word)?),
+                "dev" => dev = Some(args.value(word)?),
+                "nud" => match args.value(word)? {
+                    "permanent" | "noarp" => {}
+                    other => ignored.push(format!("nud {other}")),
+                },
+                "proxy" => return Err("proxy neighbor entries are not modelled".into()),
+                "router" | "extern_learn" | "use" | "managed" => ignored.push(word.to_string()),
+                _ if addr.is_none() => addr = Some(word),
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        let addr = address("neighbor", addr.ok_or("no neighbor address")?)?;
+        let lladdr = parse_mac(lladdr.ok_or("no lladdr")?).map_err(|e| e.to_string())?;
+        let dev = dev.ok_or("no device")?;
+        self.add::<String>(ctx.ns, |c| Ok(c.neighbor(dev, addr, lladdr)))?;
+        if !ignored.is_empty() {
+            self.warn(format!(
+                "neighbors are declared permanent; ignored {}",
+                ignored.join(" ")
+            ));
+        }
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // tc
+    // ------------------------------------------------------------------
+
+    fn tc(&mut self, ns: Option<&str>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let mut ns = ns;
+        while let Some(opt) = args.peek().filter(|w| w.starts_with('-')) {
+            args.next();
+            match opt.trim_start_matches('-') {
+                "n" | "netns" => ns = Some(args.value(opt)?),
+                "b" | "batch" => return Err("tc batch files are not followed".into()),
+                _ => {}
+            }
+        }
+        let object = args.next().ok_or("missing tc object")?;
+        let object = lookup(
+            object,
+            &[
+                "qdisc", "class", "filter", "chain", "action", "monitor", "exec",
+            ],
+        )
+        .ok_or_else(|| format!("unknown object `{object}`"))?;
+        let action = args.next();
+        if action.is_none_or(|a| lookup(a, READ_ONLY).is_some()) {
+            return Ok(());
+        }
+        let action = action.unwrap_or_default();
+        if lookup(action, &["add", "replace"]).is_none() {
+            return Err(format!("tc {object} {action} is not supported"));
+        }
+        let ctx = Ctx { ns, ipv6: false };
+        match object {
+            "qdisc" => self.qdisc_add(ctx, args.rest()),
+            "class" => self.class_add(ctx, args.rest()),
+            "filter" => self.filter_add(ctx, args.rest()),
+            other => Err(format!("tc {other} is not modelled by NetworkConfig")),
+        }
+    }
+
+    fn qdisc_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut dev, mut parent, mut qdisc_handle, mut kind) =
+            (None, QdiscParent::Root, None, None);
+        while let Some(word) = args.next() {
+            match word {
+                "dev" => dev = Some(args.value(word)?),
+                "root" => parent = QdiscParent::Root,
+                "parent" => parent = QdiscParent::Class(handle(word, args.value(word)?)?),
+                "handle" => {
+                    let value = args.value(word)?;
+                    qdisc_handle = Some(
+                        TcHandle::parse_qdisc_handle(value)
+                            .map_err(|_| format!("invalid handle `{value}`"))?,
+                    );
+                }
+                "estimator" | "stab" => return Err(format!("qdisc {word} is not modelled")),
+                _ => {
+                    kind = Some(word);
+                    break;
+                }
+            }
+        }
+        let dev = dev.ok_or("no device")?;
+        let kind = kind.ok_or("no qdisc kind")?;
+
+        let mut args = Args::new(args.rest());
+        let mut ignored = Vec::new();
+        let qdisc_type = match kind {
+            "ingress" => {
+                parent = QdiscParent::Ingress;
+                DeclaredQdiscType::Ingress
+            }
+            "clsact" => DeclaredQdiscType::Clsact,
+            "netem" => self.netem(&mut args, &mut ignored)?,
+            "htb" => {
+                let mut default_class = 0;
+                while let Some(word) = args.next() {
+                    match word {
+                        // `default 20` is class 1:20, so the number is hex.
+                        "default" => {
+                            let value = args.value(word)?;
+                            default_class = u32::from_str_radix(value.trim_start_matches("0x"), 16)
+                                .map_err(|_| format!("invalid default `{value}`"))?;
+                        }
+                        "r2q" | "direct_qlen" => {
+                            ignored.push(format!("{word} {}", args.value(word)?))
+                        }
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredQdiscType::Htb { default_class }
+            }
+            "fq_codel" => {
+                let (mut limit, mut target_us, mut interval_us) = (None, None, None);
+                while let Some(word) = args.next() {
+                    match word {
+                        "limit" => limit = Some(number(word, args.value(word)?)?),
+                        "target" => target_us = Some(time_us(word, args.value(word)?)?),
+                        "interval" => interval_us = Some(time_us(word, args.value(word)?)?),
+                        "flows" | "quantum" | "ce_threshold" | "memory_limit" | "drop_batch" => {
+                            ignored.push(format!("{word} {}", args.value(word)?))
+                        }
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredQdiscType::FqCodel {
+                    limit,
+                    target_us,
+                    interval_us,
+                }
+            }
+            "tbf" => {
+                let (mut rate_bps, mut burst, mut limit, mut latency) = (None, None, None, None);
+                while let Some(word) = args.next() {
+                    match word {
+                        "rate" => {
+                            rate_bps = Some(rate(word, args.value(word)?)?.as_bytes_per_sec())
+                        }
+                        "burst" | "buffer" | "maxburst" => {
+                            burst = Some(size(word, args.value(word)?)?.as_u32_saturating())
+                        }
+                        "limit" => limit = Some(size(word, args.value(word)?)?.as_u32_saturating()),
+                        "latency" => latency = Some(time_us(word, args.value(word)?)?),
+                        "peakrate" | "mtu" | "minburst" | "mpu" => {
+                            ignored.push(format!("{word} {}", args.value(word)?))
+                        }
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                let rate_bps = rate_bps.ok_or("tbf without a rate")?;
+                let burst_bytes = burst.ok_or("tbf without a burst")?;
+                // tc turns latency into the byte limit it implies.
+                let limit_bytes = limit.or_else(|| {
+                    latency.map(|us| {
+                        let bytes = rate_bps as f64 * f64::from(us) / 1e6 + f64::from(burst_bytes);
+                        bytes.min(f64::from(u32::MAX)) as u32
+                    })
+                });
+                DeclaredQdiscType::Tbf {
+                    rate_bps,
+                    burst_bytes,
+                    limit_bytes,
+                }
+            }
+            "sfq" => {
+                let mut perturb_secs = None;
+                while let Some(word) = args.next() {
+                    match word {
+                        "perturb" => perturb_secs = Some(number(word, args.value(word)?)?),
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredQdiscType::Sfq { perturb_secs }
+            }
+            "prio" => {
+                let mut bands = None;
+                while let Some(word) = args.next() {
+                    match word {
+                        "bands" => bands = Some(number(word, args.value(word)?)?),
+                        _ => ignored.push(word.to_string()),
+                    }
+                }
+                DeclaredQdiscType::Prio { bands }
+            }
+            other => return Err(format!("qdisc `{other}` is not modelled by NetworkConfig")),
+        };
+
+        self.config(ctx.ns).qdiscs.push(DeclaredQdisc {
+            dev: dev.to_string(),
+            parent,
+            handle: qdisc_handle,
+            qdisc_type,
+        });
+        self.warn_ignored(kind, &ignored);
+        Ok(())
+    }
+
+    fn netem(
+        &mut self,
+        args: &mut Args<'_>,
+        ignored: &mut Vec<String>,
+    ) -> Result<DeclaredQdiscType, String> {
+        let numeric = |w: Option<&str>| {
+            w.is_some_and(|w| w.starts_with(|c: char| c.is_ascii_digit() || c == '.'))
+        };
+        let (mut delay_us, mut jitter_us, mut delay_correlation) = (None, None, None);
+        let (mut loss_percent, mut loss_correlation) = (None, None);
+        let (mut duplicate_percent, mut corrupt_percent, mut reorder_percent) = (None, None, None);
+        let mut limit = None;
+        while let Some(word) = args.next() {
+            match word {
+                "delay" | "latency" => {
+                    delay_us = Some(time_us(word, args.value(word)?)?);
+                    if numeric(args.peek()) {
+                        jitter_us = Some(time_us("jitter", args.value(word)?)?);
+                        if numeric(args.peek()) {
+                            delay_correlation = Some(percent("correlation", args.value(word)?)?);
+                        }
+                    }
+                }
+                "loss" | "drop" => {
+                    let mut value = args.value(word)?;
+                    if value == "random" {
+                        value = args.value(word)?;
+                    } else if !numeric(Some(value)) {
+                        return Err(format!("netem loss model `{value}` is not modelled"));
+                    }
+                    loss_percent = Some(percent(word, value)?);
+                    if numeric(args.peek()) {
+                        loss_correlation = Some(percent("correlation", args.value(word)?)?);
+                    }
+                }
+                "duplicate" | "corrupt" | "reorder" => {
+                    let value = Some(percent(word, args.value(word)?)?);
+                    match word {
+                        "duplicate" => duplicate_percent = value,
+                        "corrupt" => corrupt_percent = value,
+                        _ => reorder_percent = value,
+                    }
+                    if numeric(args.peek()) {
+                        ignored.push(format!("{word} correlation {}", args.value(word)?));
+                    }
+                }
+                "limit" => limit = Some(number(word, args.value(word)?)?),
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        Ok(DeclaredQdiscType::Netem {
+            delay_us,
+            jitter_us,
+            loss_percent,
+            limit,
+            duplicate_percent,
+            corrupt_percent,
+            reorder_percent,
+            loss_correlation,
+            delay_correlation,
+        })
+    }
+
+    fn class_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut dev, mut parent, mut classid) = (None, None, None);
+        let mut kind = None;
+        while let Some(word) = args.next() {
+            match word {
+                "dev" => dev = Some(args.value(word)?),
+                "parent" => parent = Some(handle(word, args.value(word)?)?),
+                "classid" => classid = Some(handle(word, args.value(word)?)?),
+                "root" => return Err("classes at the root are not modelled".into()),
+                _ => {
+                    kind = Some(word);
+                    break;
+                }
+            }
+        }
+        let dev = dev.ok_or("no device")?;
+        let classid = classid.ok_or("no classid")?;
+        match kind {
+            Some("htb") => {}
+            Some(other) => return Err(format!("{other} classes are not modelled; only htb is")),
+            None => return Err("no class kind".into()),
+        }
+
+        let (mut class_rate, mut ceil, mut burst, mut cburst) = (None, None, None, None);
+        let (mut prio, mut quantum) = (None, None);
+        let mut ignored = Vec::new();
+        while let Some(word) = args.next() {
+            match word {
+                "rate" => class_rate = Some(rate(word, args.value(word)?)?),
+                "ceil" => ceil = Some(rate(word, args.value(word)?)?),
+                "burst" | "buffer" | "maxburst" => burst = Some(size(word, args.value(word)?)?),
+                "cburst" | "cbuffer" | "cmaxburst" => cburst = Some(size(word, args.value(word)?)?),
+                "prio" => prio = Some(number::<u32>(word, args.value(word)?)?),
+                "quantum" => quantum = Some(number::<u32>(word, args.value(word)?)?),
+                "mtu" | "mpu" | "overhead" | "linklayer" => {
+                    ignored.push(format!("{word} {}", args.value(word)?))
+                }
+                _ => ignored.push(word.to_string()),
+            }
+        }
+        let class_rate = class_rate.ok_or("htb class without a rate")?;
+        self.add::<String>(ctx.ns, |c| {
+            Ok(c.class(dev, classid, |mut b| {
+                b = b.htb(class_rate);
+                if let Some(parent) = parent {
+                    b = b.parent(parent);
+                }
+                if let Some(ceil) = ceil {
+                    b = b.ceil(ceil);
+                }
+                if let Some(burst) = burst {
+                    b = b.burst(burst);
+                }
+                if let Some(cburst) = cburst {
+                    b = b.cburst(cburst);
+                }
+                if let Some(prio) = prio {
+                    b = b.prio(prio);
+                }
+                if let Some(quantum) = quantum {
+                    b = b.quantum(quantum);
+                }
+                b
+            }))
+        })?;
+        self.warn_ignored("htb class", &ignored);
+        Ok(())
+    }
+
+    fn filter_add(&mut self, ctx: Ctx<'_>, words: &[&str]) -> Result<(), String> {
+        let mut args = Args::new(words);
+        let (mut dev, mut parent, mut priority, mut ipv6, mut kind) =
+            (None, None, None, false, None);
+        let mut ignored = Vec::new();
+        while let Some(word) = args.next() {
+            match word {
+                "dev" => dev = Some(args.value(word)?),
+                "parent" => {
+                    let value = args.value(word)?;
+                    parent = Some(
+                        TcHandle::parse_filter_parent(value)
+                            .map_err(|_| format!("invalid parent `{value}`"))?,
+                    );
+                }
+                "root" | "ingress" | "egress" => {
+                    parent = Some(TcHandle::parse_filter_parent(word).map_err(|e| e.to_string())?)
+                }
+                "protocol" => match args.value(word)? {
+                    "ip" | "all" => {}
+                    "ipv6" => ipv6 = true,
+                    other => return Err(format!("filter protocol `{other}` is not modelled")),
+                },
+                "pref" | "prio" | "priority" => {
+                    priority = Some(number::<u16>(word, args.value(word)?)?)
+                }
+                "handle" => ignored.push(format!("{word} {}", args.value(word)?)),
+                "chain" => return Err("filter chains are not modelled".into()),
+                _ => {
+                    kind = Some(word);
+                    break;
+                }
+            }
+        }
+        let dev = dev.ok_or("no device")?;
+        let parent = parent.ok_or("no parent")?;
+        let priority = priority.ok_or("filters without a priority are not modelled")?;
+        match kind {
+            Some("flower") => {}
+            Some(other) => return Err(format!("{other} filters are not modelled; only flower is")),
+            None => return Err("no filter kind".into()),
+        }
+
+        let (mut classid, mut ip_proto, mut src, mut dst) = (None, None, None, None);
+        let (mut src_port, mut dst_port) = (None, None);
+        while let Some(word) = args.next() {
+            match word {
+                "classid" | "flowid" => classid = Some(handle(word, args.value(word)?)?),
+                "ip_proto" => {
+                    ip_proto = Some(match args.value(word)? {
+                        "tcp" => 6,
+                        "udp" => 17,
+                        "sctp" => 132,
+                        "icmp" => 1,
+                        "icmpv6" => 58,
+                        other => number::<u8>(word, other)?,
+                    })
+                }
+                "src_ip" => src = Some(with_prefix(args.value(word)?)),
+                "dst_ip" => dst = Some(with_prefix(args.value(word)?)),
+                "src_port" => src_port = Some(number::<u16>(word, args.value(word)?)?),
+                "dst_port" => dst_port = Some(number::<u16>(word, args.value(word)?)?),
+                "skip_sw" | "skip_hw" | "verbose" => ignored.push(word.to_string()),
+                "action" | "actions" => return Err("filter actions are not modelled".into()),
+                other => return Err(format!("flower key `{other}` is not modelled")),
+            }
+        }
+        self.add(ctx.ns, |c| {
+            c.filter(dev, parent, priority, |mut f| {
+                f = f.flower();
+                if ipv6 {
+                    f = f.ipv6();
+                }
+                if let Some(proto) = ip_proto {
+                    f = f.ip_proto(proto);
+                }
+                if let Some(src) = &src {
+                    f = f.src(src);
+                }
+                if let Some(dst) = &dst {
+                    f = f.dst(dst);
+                }
+                if let Some(port) = src_port {
+                    f = f.src_port(port);
+                }
+                if let Some(port) = dst_port {
+                    f = f.dst_port(port);
+                }
+                if let Some(classid) = classid {
+                    f = f.classid(classid);
+                }
+                f
+            })
+        })?;
+        self.warn_ignored("flower", &ignored);
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // sysctl
+    // ------------------------------------------------------------------
+
+    fn sysctl(&mut self, ns: Option<&str>, words: &[&str]) -> Result<(), String> {
+        for word in words {
+            if matches!(*word, "-p" | "--load" | "--system") {
+                return Err("sysctl files are not followed".into());
+            }
+            if word.starts_with('-') {
+                continue;
+            }
+            // Without `=` the setting is only read.
+            let Some((key, value)) = word.split_once('=') else {
+                continue;
+            };
+            // In dotted keys a `/` stands for the `.` of a device name
+            // like `eth0.100`.
+            let parts: Vec<String> = if key.contains('/') && !key.contains('.') {
+                key.split('/').map(str::to_string).collect()
+            } else {
+                key.split('.').map(|p| p.replace('/', ".")).collect()
+            };
+            self.set_sysctl(ns, key, &parts, value)?;
+        }
+        Ok(())
+    }
+
+    fn echo(
+        &mut self,
+        ns: Option<&str>,
+        words: &[&str],
+        target: Option<&str>,
+    ) -> Result<(), String> {
+        let Some(target) = target else {
+            return Ok(());
+        };
+        let Some(key) = target.strip_prefix("/proc/sys/") else {
+            return Err(format!("writes to {target} are not modelled"));
+        };
+        let value = words
+            .iter()
+            .find(|w| !w.starts_with('-'))
+            .ok_or("echo without a value")?;
+        let parts: Vec<String> = key.split('/').map(str::to_string).collect();
+        self.set_sysctl(ns, key, &parts, value)
+    }
+
+    fn set_sysctl(
+        &mut self,
+        ns: Option<&str>,
+        key: &str,
+        parts: &[String],
+        value: &str,
+    ) -> Result<(), String> {
+        let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
+        let (dev, sysctl) = match parts.as_slice() {
+            ["net", "ipv4", "ip_forward"] => ("all", InterfaceSysctl::Ipv4Forwarding),
+            ["net", "ipv4", "conf", dev, "forwarding"] => (*dev, InterfaceSysctl::Ipv4Forwarding),
+            ["net", "ipv4", "conf", dev, "rp_filter"] => (*dev, InterfaceSysctl::RpFilter),
+            ["net", "ipv6", "conf", dev, "forwarding"] => (*dev, InterfaceSysctl::Ipv6Forwarding),
+            ["net", "ipv6", "conf", dev, "accept_ra"] => (*dev, InterfaceSysctl::AcceptRa),
+            _ => return Err(format!("sysctl {key} is not modelled")),
+        };
+        let value: u32 = number(key, value.trim())?;
+        self.add::<String>(ns, |c| Ok(c.sysctl(dev, sysctl, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_comments_continuations_and_operators() {
+        let script = "#!/bin/sh\n\
+                      # bring up the lab\n\
+                      ip link add br0 \\\n    type bridge # the bridge\n\
+                      ip link set br0 up; ip addr add 10.0.0.1/24 dev br0 && echo 'done # ok'\n\
+                      ip link del old0 2>/dev/null || true\n";
+        let commands = split_commands(script);
+        let words: Vec<Vec<&str>> = commands
+            .iter()
+            .map(|c| c.words.iter().map(String::as_str).collect())
+            .collect();
+        assert_eq!(
+            words,
+            vec![
+                vec!["ip", "link", "add", "br0", "type", "bridge"],
+                vec!["ip", "link", "set", "br0", "up"],
+                vec!["ip", "addr", "add", "10.0.0.1/24", "dev", "br0"],
+                vec!["echo", "done # ok"],
+                vec!["ip", "link", "del", "old0", "2>/dev/null"],
+                vec!["true"],
+            ]
+        );
+        assert_eq!(commands[0].line, 3);
+        assert_eq!(commands[1].line, 5);
+        assert_eq!(commands[4].line, 6);
+    }
+
+    #[test]
+    fn imports_links_addresses_and_routes() {
+        let report = NetworkConfig::from_iproute2_script(
+            "ip link add br0 type bridge\n\
+             ip link add link eth0 name eth0.100 type vlan id 100\n\
+             ip link set eth0.100 master br0\n\
+             ip link set dev br0 up mtu 9000\n\
+             ip link set eth1 master br0\n\
+             ip a add 10.0.0.1/24 dev br0\n\
+             ip -6 addr add 2001:db8::1 dev br0\n\
+             ip route add default via 10.0.0.254\n\
+             ip r add 10.1.0.0/16 via 10.0.0.2 metric 100 table 100\n\
+             ip route add blackhole 192.0.2.0/24\n\
+             ip addr show\n",
+        );
+        assert!(report.is_complete(), "{report:#?}");
+        let config = report.config;
+
+        assert_eq!(config.links().len(), 3);
+        let br0 = &config.links()[0];
+        assert_eq!(br0.link_type(), &DeclaredLinkType::Bridge);
+        assert_eq!(br0.state(), LinkState::Up);
+        assert_eq!(br0.mtu(), Some(9000));
+        let vlan = &config.links()[1];
+        assert!(matches!(
+            vlan.link_type(),
+            DeclaredLinkType::Vlan { parent, vlan_id: 100, .. } if parent == "eth0"
+        ));
+        assert_eq!(vlan.master(), Some("br0"));
+        // Only configured, so it's an existing physical link.
+        assert_eq!(config.links()[2].name(), "eth1");
+        assert_eq!(config.links()[2].link_type(), &DeclaredLinkType::Physical);
+
+        assert_eq!(config.addresses().len(), 2);
+        assert_eq!(config.addresses()[1].prefix_len(), 128);
+
+        let routes = config.routes();
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].prefix_len(), 0);
+        assert_eq!(routes[0].gateway(), Some("10.0.0.254".parse().unwrap()));
+        assert_eq!(routes[1].metric(), Some(100));
+        assert_eq!(routes[1].table(), Some(100));
+        assert_eq!(routes[2].route_type(), DeclaredRouteType::Blackhole);
+    }
+
+    #[test]
+    fn collects_namespaces_and_moves_veth_peers() {
+        let report = NetworkConfig::from_iproute2_script(
+            "ip netns add ns1\n\
+             ip link add veth0 type veth peer name veth1\n\
+             ip link set veth1 netns ns1\n\
+             ip link set veth0 up\n\
+             ip -n ns1 link set veth1 up\n\
+             ip netns exec ns1 ip addr add 10.0.0.2/24 dev veth1\n\
+             ip netns exec ns1 sysctl -w net.ipv4.ip_forward=1\n",
+        );
+        assert!(report.is_complete(), "{report:#?}");
+        let config = report.config;
+        assert_eq!(
+            config.links()[0].link_type(),
+            &DeclaredLinkType::Veth {
+                peer: "veth1".into(),
+                peer_netns: Some("ns1".into()),
+            }
+        );
+        let ns = &config.namespaces()[0];
+        assert_eq!(ns.name(), "ns1");
+        assert_eq!(ns.config().links()[0].name(), "veth1");
+        assert_eq!(ns.config().links()[0].state(), LinkState::Up);
+        assert_eq!(ns.config().addresses().len(), 1);
+        assert_eq!(
+            ns.config().sysctls()[0].full_key(),
+            "net.ipv4.conf.all.forwarding"
+        );
+    }
+
+    #[test]
+    fn imports_traffic_control() {
+        let report = NetworkConfig::from_iproute2_script(
+            "tc qdisc add dev eth0 root handle 1: htb default 20\n\
+             tc class add dev eth0 parent 1: classid 1:1 htb rate 100mbit\n\
+             tc class add dev eth0 parent 1:1 classid 1:10 htb rate 60mbit ceil 100mbit prio 1\n\
+             tc qdisc add dev eth0 parent 1:10 handle 10: fq_codel\n\
+             tc filter add dev eth0 parent 1: protocol ip prio 10 flower ip_proto tcp dst_port 22 classid 1:10\n\
+             tc qdisc add dev eth1 root netem delay 100ms 10ms loss 1%\n\
+             tc qdisc add dev eth2 root tbf rate 1mbit burst 32kb latency 400ms\n",
+        );
+        assert!(report.is_complete(), "{report:#?}");
+        let config = report.config;
+
+        assert_eq!(config.qdiscs().len(), 4);
+        assert!(matches!(
+            config.qdiscs()[0].qdisc_type(),
+            DeclaredQdiscType::Htb {
+                default_class: 0x20
+            }
+        ));
+        assert_eq!(
+            config.qdiscs()[1].parent(),
+            QdiscParent::Class(TcHandle::new(1, 0x10))
+        );
+        assert!(matches!(
+            config.qdiscs()[2].qdisc_type(),
+            DeclaredQdiscType::Netem {
+                delay_us: Some(100_000),
+                jitter_us: Some(10_000),
+                loss_percent: Some(1.0),
+                ..
+            }
+        ));
+        // 1mbit is 125000 bytes/s; 0.4s of it plus the burst.
+        assert!(matches!(
+            config.qdiscs()[3].qdisc_type(),
+            DeclaredQdiscType::Tbf {
+                rate_bps: 125_000,
+                burst_bytes: 32_768,
+                limit_bytes: Some(82_768),
+            }
+        ));
+
+        assert_eq!(config.classes().len(), 2);
+        assert_eq!(config.classes()[1].parent(), TcHandle::new(1, 1));
+        assert_eq!(config.filters().len(), 1);
+        assert_eq!(config.filters()[0].priority(), 10);
+    }
+
+    #[test]
+    fn reports_what_it_cannot_import() {
+        let report = NetworkConfig::from_iproute2_script(
+            "modprobe 8021q\n\
+             bridge fdb add 00:11:22:33:44:55 dev vxlan0 dst 192.0.2.1\n\
+             ip link add wg0 type wireguard\n\
+             ip addr add 10.0.0.1/24 dev eth0 label eth0:1\n\
+             ip rule add from 10.0.0.0/8 lookup 100\n\
+             tc filter add dev eth0 parent 1: prio 1 u32 match ip dst 0/0 flowid 1:1\n\
+             bridge vlan add vid 10 dev eth0 pvid untagged\n",
+        );
+        let skipped: Vec<usize> = report.skipped.iter().map(|i| i.line).collect();
+        assert_eq!(skipped, vec![1, 2, 3, 6, 7]);
+        let warned: Vec<usize> = report.warnings.iter().map(|i| i.line).collect();
+        assert_eq!(warned, vec![4, 5]);
+        assert_eq!(report.config.addresses().len(), 1);
+        assert_eq!(report.config.rules()[0].priority(), 32765);
+        assert!(
+            report.skipped[2]
+                .to_string()
+                .starts_with("line 3: link type `wireguard`")
+        );
+    }
+}
@@ -35,21 +35,88 @@
 //! - **Diff-based**: Only makes necessary changes
 //! - **Ordered**: Applies changes in the correct order (links before addresses, etc.)
 //! - **Dry-run**: Preview changes before applying
+//! - **Continuous**: [`NetworkConfig::reconcile`] keeps re-applying on drift
+//! - **Monitored**: [`NetworkConfig::watch_drift`] reports drift without correcting it
+//! - **Confirmed**: [`NetworkConfig::apply_confirmed`] reverts unless confirmed in time
+//! - **Importable**: [`NetworkConfig::from_iproute2_script`] converts an
+//!   existing `ip`/`tc` setup script
 //!
 //! # Supported Resources
 //!
 //! - Links (interfaces): dummy, veth, bridge, vlan, vxlan, macvlan, bond
 //! - Addresses: IPv4 and IPv6
 //! - Routes: IPv4 and IPv6, with gateway, device, or multipath
-//! - Qdiscs: netem, htb, fq_codel, tbf, etc.
+//! - Qdiscs: netem, htb, fq_codel, tbf, etc., at the root or under a class
+//! - Classes and filters: HTB class trees and flower classifiers, diffed
+//!   by class ID and by filter priority
+//! - Policy routing rules: from/to/fwmark/iif/oif selectors, lookup or
+//!   blackhole/unreachable/prohibit
+//! - Static neighbors: permanent ARP / NDP entries
+//! - Per-interface sysctls: forwarding, rp_filter, accept_ra
+//! - Named network namespaces, each with its own links, addresses, routes
+//!   and the rest; veth pairs can span namespaces
+//!
+//! # Traffic control
+//!
+//! A QoS policy is a root qdisc, the classes under it, leaf qdiscs on
+//! those classes, and filters steering packets into them:
+//!
+//! ```ignore
+//! use nlink::{Rate, TcHandle};
+//!
+//! let root = TcHandle::major_only(1);
+//! let config = NetworkConfig::new()
+//!     .qdisc("eth0", |q| q.htb().default_class(0x20))
+//!     .class("eth0", TcHandle::new(1, 1), |c| c.htb(Rate::mbit(100)))
+//!     .class("eth0", TcHandle::new(1, 0x10), |c| {
+//!         c.parent(TcHandle::new(1, 1)).htb(Rate::mbit(60)).ceil(Rate::mbit(100))
+//!     })
+//!     .class("eth0", TcHandle::new(1, 0x20), |c| {
+//!         c.parent(TcHandle::new(1, 1)).htb(Rate::mbit(40)).ceil(Rate::mbit(100))
+//!     })
+//!     .qdisc("eth0", |q| q.fq_codel().parent_class(TcHandle::new(1, 0x10)))
+//!     .filter("eth0", root, 10, |f| {
+//!         f.flower().tcp().dst_port(22).classid(TcHandle::new(1, 0x10))
+//!     })?;
+//! ```
+//!
+//! An HTB root qdisc gets handle `1:` unless [`QdiscBuilder::handle`]
+//! says otherwise. Replacing a root qdisc with a different kind or
+//! handle drops everything under it, so the diff then re-adds the whole
+//! tree.
+//!
+//! # Namespaces
+//!
+//! [`NetworkConfig::namespace`] describes a whole test topology in one
+//! config. Missing namespaces are created first, then the caller's
+//! namespace is applied (creating veth peers inside the namespaces), then
+//! each namespace's resources through a connection opened inside it:
+//!
+//! ```ignore
+//! let config = NetworkConfig::new()
+//!     .link("veth0", |l| l.veth("veth1").veth_peer_netns("ns1").up())
+//!     .address("veth0", "10.0.0.1/24")?
+//!     .namespace(
+//!         "ns1",
+//!         NetworkConfig::new()
+//!             .link("veth1", |l| l.up())
+//!             .address("veth1", "10.0.0.2/24")?,
+//!     );
+//! ```
 
 mod apply;
+mod confirm;
 mod diff;
+mod import;
 mod types;
+mod watch;
 
-pub use apply::{ApplyOptions, ApplyResult};
+pub use apply::{ApplyError, ApplyOptions, ApplyResult, RollbackReport};
+pub use confirm::PendingApply;
 pub use diff::{ConfigDiff, DiffOptions};
+pub use import::{ImportIssue, ImportReport};
 pub use types::*;
+pub use watch::{DriftReport, DriftWatch, ReconcileTrigger, Reconciler};
 
 use super::{connection::Connection, error::Result, protocol::Route};
 
@@ -77,7 +144,9 @@ impl NetworkConfig {
     /// the returned diff additionally carries
     /// [`ConfigDiff::addresses_to_remove`] /
     /// [`ConfigDiff::routes_to_remove`] for undeclared kernel
-    /// resources (conservatively scoped — see [`DiffOptions::purge`]).
+    /// resources (conservatively scoped — see [`DiffOptions::purge`],
+    /// or limited to what carries the config's [`owner`](Self::owner)
+    /// tag).
     /// The default ([`Self::diff`]) never populates the removal
     /// collections, so it can only ever *add* state.
     ///
@@ -100,12 +169,14 @@ impl NetworkConfig {
     ///
     /// This computes the diff and applies all necessary changes.
     /// Changes are applied in the correct order:
+    /// 0. Create declared namespaces
     /// 1. Create new links
-    /// 2. Modify existing links
+    /// 2. Modify existing links, then set per-interface sysctls
     /// 3. Add addresses
-    /// 4. Add routes
-    /// 5. Configure qdiscs
+    /// 4. Add routes, then policy rules and static neighbors
+    /// 5. Configure qdiscs, then classes, leaf qdiscs and filters
     /// 6. Remove old resources (if purge is enabled)
+    /// 7. Apply each namespace's resources, in the same order, inside it
     ///
     /// # Example
     ///
@@ -2,6 +2,7 @@
 
 use std::net::IpAddr;
 
+use crate::TcHandle;
 pub use crate::netlink::link::{
     AdSelect as BondAdSelect, LacpRate as BondLacpRate, NetkitMode, NetkitPolicy, NetkitScrub,
     VlanProtocol,
@@ -10,7 +11,8 @@ pub use crate::netlink::link::{
 /// Declarative network configuration.
 ///
 /// Represents the desired state of network resources. Use the builder methods
-/// to add links, addresses, routes, and qdiscs, then call [`diff()`](NetworkConfig::diff)
+/// to add links, addresses, routes, qdiscs, classes, filters, policy rules, static neighbors,
+/// per-interface sysctls and named network namespaces, then call [`diff()`](NetworkConfig::diff)
 /// or [`apply()`](NetworkConfig::apply) to reconcile with the current state.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -25,6 +27,20 @@ pub struct NetworkConfig {
     pub(crate) routes: Vec<DeclaredRoute>,
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
     pub(crate) qdiscs: Vec<DeclaredQdisc>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) classes: Vec<DeclaredClass>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) filters: Vec<DeclaredFilter>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) rules: Vec<DeclaredRule>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) neighbors: Vec<DeclaredNeighbor>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) sysctls: Vec<DeclaredSysctl>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) namespaces: Vec<DeclaredNamespace>,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub(crate) owner: Option<u8>,
 }
 
 impl NetworkConfig {
@@ -186,6 +202,204 @@ impl NetworkConfig {
         self
     }
 
+    /// Add a traffic class, e.g. one node of an HTB tree.
+    ///
+    /// `classid` identifies the class on `dev`. Its parent defaults to
+    /// the qdisc named by the class ID's major; set
+    /// [`ClassBuilder::parent`] for a child class. An HTB class needs a
+    /// rate from [`ClassBuilder::htb`], or the kernel rejects it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::{Rate, TcHandle};
+    /// let config = NetworkConfig::new()
+    ///     .qdisc("eth0", |q| q.htb().default_class(0x20))
+    ///     .class("eth0", TcHandle::new(1, 1), |c| c.htb(Rate::mbit(100)))
+    ///     .class("eth0", TcHandle::new(1, 0x10), |c| {
+    ///         c.parent(TcHandle::new(1, 1)).htb(Rate::mbit(60)).ceil(Rate::mbit(100)).prio(0)
+    ///     })
+    ///     .class("eth0", TcHandle::new(1, 0x20), |c| {
+    ///         c.parent(TcHandle::new(1, 1)).htb(Rate::mbit(40)).ceil(Rate::mbit(100))
+    ///     })
+    ///     .qdisc("eth0", |q| q.fq_codel().parent_class(TcHandle::new(1, 0x10)).handle(TcHandle::major_only(0x10)));
+    /// ```
+    pub fn class(
+        mut self,
+        dev: &str,
+        classid: TcHandle,
+        f: impl FnOnce(ClassBuilder) -> ClassBuilder,
+    ) -> Self {
+        self.classes
+            .push(f(ClassBuilder::new(dev, classid)).build());
+        self
+    }
+
+    /// Add a classifier.
+    ///
+    /// `parent` is the qdisc or class the filter is attached to and
+    /// `priority` identifies it there: a kernel filter at that priority
+    /// that doesn't match is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = NetworkConfig::new()
+    ///     .filter("eth0", TcHandle::major_only(1), 10, |f| {
+    ///         f.flower().tcp().dst_port(22).classid(TcHandle::new(1, 0x10))
+    ///     })?
+    ///     .filter("eth0", TcHandle::major_only(1), 20, |f| {
+    ///         f.flower().dst("10.0.0.0/8").classid(TcHandle::new(1, 0x20))
+    ///     })?;
+    /// ```
+    pub fn filter(
+        mut self,
+        dev: &str,
+        parent: TcHandle,
+        priority: u16,
+        f: impl FnOnce(FilterBuilder) -> FilterBuilder,
+    ) -> Result<Self, FilterParseError> {
+        let filter = f(FilterBuilder::new(dev, parent, priority)).build()?;
+        self.filters.push(filter);
+        Ok(self)
+    }
+
+    /// Add a policy routing rule.
+    ///
+    /// `priority` identifies the rule: an existing kernel rule at the
+    /// same priority and family with the same selectors and action
+    /// counts as present. Selectors are in CIDR notation; the rule is
+    /// IPv6 when they are, or when [`DeclaredRuleBuilder::ipv6`] is set.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = NetworkConfig::new()
+    ///     .rule(100, |r| r.from("10.1.0.0/16").table(100))?
+    ///     .rule(110, |r| r.fwmark(0x1).table(200))?
+    ///     .rule(120, |r| r.to("fd00:dead::/32").prohibit())?;
+    /// ```
+    pub fn rule(
+        mut self,
+        priority: u32,
+        f: impl FnOnce(DeclaredRuleBuilder) -> DeclaredRuleBuilder,
+    ) -> Result<Self, RuleParseError> {
+        let rule = f(DeclaredRuleBuilder::new(priority)).build()?;
+        self.rules.push(rule);
+        Ok(self)
+    }
+
+    /// Add a static (permanent) neighbor entry.
+    ///
+    /// An existing entry for `address` on `dev` is replaced when its
+    /// link-layer address differs or it isn't permanent.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = NetworkConfig::new()
+    ///     .neighbor("eth0", "10.0.0.1".parse()?, [0x02, 0, 0, 0, 0, 0x01]);
+    /// ```
+    pub fn neighbor(mut self, dev: &str, address: IpAddr, lladdr: [u8; 6]) -> Self {
+        self.neighbors.push(DeclaredNeighbor {
+            dev: dev.to_string(),
+            address,
+            lladdr,
+        });
+        self
+    }
+
+    /// Set a per-interface sysctl (`net.<ipv4|ipv6>.conf.<dev>.<key>`).
+    ///
+    /// `dev` may also be `all` or `default`. Sysctls go through
+    /// `/proc/sys` of the **calling thread's** network namespace, not
+    /// the connection's; run the diff/apply from inside the target
+    /// namespace when the connection was opened in another one.
+    /// Sysctls declared inside a [`namespace`](Self::namespace) are
+    /// always read and written in that namespace.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = NetworkConfig::new()
+    ///     .sysctl("eth0", InterfaceSysctl::Ipv4Forwarding, 1)
+    ///     .sysctl("eth0", InterfaceSysctl::RpFilter, 2)
+    ///     .sysctl("eth0", InterfaceSysctl::AcceptRa, 0);
+    /// ```
+    pub fn sysctl(mut self, dev: &str, key: InterfaceSysctl, value: u32) -> Self {
+        self.sysctls.push(DeclaredSysctl {
+            dev: dev.to_string(),
+            key,
+            value,
+        });
+        self
+    }
+
+    /// Declare a named network namespace and the resources inside it.
+    ///
+    /// The namespace is created (under `/var/run/netns`) if it doesn't
+    /// exist, and `config` is diffed and applied through a connection
+    /// opened inside it. Veth pairs can span namespaces with
+    /// [`LinkBuilder::veth_peer_netns`]; declare the peer as a plain
+    /// link in the namespace's config to configure it once it exists.
+    /// Namespaces don't nest — `config` must not declare its own.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = NetworkConfig::new()
+    ///     .link("veth0", |l| l.veth("veth1").veth_peer_netns("ns1").up())
+    ///     .address("veth0", "10.0.0.1/24")?
+    ///     .namespace(
+    ///         "ns1",
+    ///         NetworkConfig::new()
+    ///             .link("veth1", |l| l.up())
+    ///             .address("veth1", "10.0.0.2/24")?
+    ///             .route("0.0.0.0/0", |r| r.via("10.0.0.1"))?,
+    ///     );
+    /// ```
+    pub fn namespace(mut self, name: &str, config: NetworkConfig) -> Self {
+        self.namespaces.push(DeclaredNamespace {
+            name: name.to_string(),
+            config,
+        });
+        self
+    }
+
+    /// Tag every address and route this config creates with `tag`, and
+    /// limit purge to resources carrying it.
+    ///
+    /// The tag is written as the route protocol (`ip route ... proto`)
+    /// and the address protocol (`IFA_PROTO`), so it lives in the kernel
+    /// alongside the resource. With an owner set,
+    /// [`DiffOptions::purge`](super::DiffOptions::purge) removes the
+    /// undeclared addresses and routes that carry the tag, on any
+    /// interface and in any table, and nothing else, so configs with
+    /// different tags can share a host. Addresses are only tagged on
+    /// kernels 6.3 and later; before that purge leaves them alone.
+    /// Resources created before the tag was set aren't adopted.
+    /// Namespaces without an owner of their own inherit this one.
+    ///
+    /// Values up to 4 (`static`) are taken by the kernel and the
+    /// defaults, and are rejected by [`diff`](Self::diff) and
+    /// [`apply`](Self::apply).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let config = NetworkConfig::new()
+    ///     .owner(200)
+    ///     .address("eth0", "10.0.0.1/24")?
+    ///     .route("10.1.0.0/16", |r| r.via("10.0.0.254"))?;
+    ///
+    /// // Removes tag-200 addresses and routes that are no longer declared.
+    /// let diff = config.diff_with_options(&conn, DiffOptions::default().purge(true)).await?;
+    /// ```
+    pub fn owner(mut self, tag: u8) -> Self {
+        self.owner = Some(tag);
+        self
+    }
+
     /// Get the configured links.
     pub fn links(&self) -> &[DeclaredLink] {
         &self.links
@@ -205,6 +419,41 @@ impl NetworkConfig {
     pub fn qdiscs(&self) -> &[DeclaredQdisc] {
         &self.qdiscs
     }
+
+    /// Get the configured traffic classes.
+    pub fn classes(&self) -> &[DeclaredClass] {
+        &self.classes
+    }
+
+    /// Get the configured filters.
+    pub fn filters(&self) -> &[DeclaredFilter] {
+        &self.filters
+    }
+
+    /// Get the configured policy routing rules.
+    pub fn rules(&self) -> &[DeclaredRule] {
+        &self.rules
+    }
+
+    /// Get the configured static neighbor entries.
+    pub fn neighbors(&self) -> &[DeclaredNeighbor] {
+        &self.neighbors
+    }
+
+    /// Get the configured per-interface sysctls.
+    pub fn sysctls(&self) -> &[DeclaredSysctl] {
+        &self.sysctls
+    }
+
+    /// Get the declared network namespaces.
+    pub fn namespaces(&self) -> &[DeclaredNamespace] {
+        &self.namespaces
+    }
+
+    /// Get the owner tag, if any.
+    pub fn owner_tag(&self) -> Option<u8> {
+        self.owner
+    }
 }
 
 // ============================================================================
@@ -274,7 +523,16 @@ pub enum DeclaredLinkType {
     /// Dummy interface.
     Dummy,
     /// Veth pair with peer name.
-    Veth { peer: String },
+    Veth {
+        peer: String,
+        /// Named network namespace the peer end is created in;
+        /// `None` keeps both ends in the same namespace.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        peer_netns: Option<String>,
+    },
     /// Bridge interface.
     Bridge,
     /// VLAN interface. Plan 190 §2.2 added `protocol`.
@@ -465,10 +723,21 @@ impl LinkBuilder {
     pub fn veth(mut self, peer: &str) -> Self {
         self.link_type = DeclaredLinkType::Veth {
             peer: peer.to_string(),
+            peer_netns: None,
         };
         self
     }
 
+    /// Create the veth peer inside the named network namespace.
+    /// The namespace must exist or be declared with
+    /// [`NetworkConfig::namespace`]. No-op if the link isn't a veth.
+    pub fn veth_peer_netns(mut self, ns: &str) -> Self {
+        if let DeclaredLinkType::Veth { peer_netns, .. } = &mut self.link_type {
+            *peer_netns = Some(ns.to_string());
+        }
+        self
+    }
+
     /// Create a bridge interface.
     pub fn bridge(mut self) -> Self {
         self.link_type = DeclaredLinkType::Bridge;
@@ -1317,350 +1586,1495 @@ impl RouteBuilder {
 }
 
 // ============================================================================
-// Qdisc Types
+// Rule Types
 // ============================================================================
 
-/// Declared qdisc configuration.
+/// Declared policy routing rule.
+///
+/// Round-trips through serde with the `from`/`to` selectors as CIDR
+/// strings; `table` defaults to `main` and `action` to `lookup`.
+/// Deserialization validates the selectors and that they agree on the
+/// address family.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "RuleRepr", try_from = "RuleRepr"))]
+// JsonSchema delegated to `RuleRepr` via the manual impl below (the
+// JSON shape is string selectors, not the parsed fields).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredRule {
+    pub(crate) priority: u32,
+    pub(crate) ipv6: bool,
+    pub(crate) from: Option<(IpAddr, u8)>,
+    pub(crate) to: Option<(IpAddr, u8)>,
+    pub(crate) fwmark: Option<u32>,
+    pub(crate) fwmask: Option<u32>,
+    pub(crate) iif: Option<String>,
+    pub(crate) oif: Option<String>,
+    pub(crate) table: u32,
+    pub(crate) action: DeclaredRuleAction,
+}
+
+/// Serde shadow for [`DeclaredRule`] — CIDR-string selectors, with
+/// the `main` table and the `lookup` action omitted.
+#[cfg(feature = "serde")]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
-#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
-#[derive(Debug, Clone)]
-pub struct DeclaredQdisc {
-    pub(crate) dev: String,
-    #[cfg_attr(feature = "serde", serde(default))]
-    pub(crate) parent: QdiscParent,
-    pub(crate) qdisc_type: DeclaredQdiscType,
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RuleRepr {
+    priority: u32,
+    /// Force an IPv6 rule when neither `from` nor `to` is given.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    ipv6: bool,
+    /// CIDR-notation source selector, e.g. `"10.1.0.0/16"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    /// CIDR-notation destination selector.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fwmark: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fwmask: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    iif: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oif: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    table: Option<u32>,
+    #[serde(default, skip_serializing_if = "DeclaredRuleAction::is_lookup")]
+    action: DeclaredRuleAction,
 }
 
-impl DeclaredQdisc {
-    /// Get the device name.
-    pub fn dev(&self) -> &str {
-        &self.dev
+// Delegate `DeclaredRule`'s schema to `RuleRepr` so it describes the
+// actual string-selector JSON, not the parsed fields.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for DeclaredRule {
+    fn schema_name() -> String {
+        RuleRepr::schema_name()
+    }
+    fn schema_id() -> std::borrow::Cow<'static, str> {
+        RuleRepr::schema_id()
+    }
+    fn json_schema(g: &mut schemars::r#gen::SchemaGenerator) -> schemars::schema::Schema {
+        RuleRepr::json_schema(g)
+    }
+    fn is_referenceable() -> bool {
+        RuleRepr::is_referenceable()
     }
+}
 
-    /// Get the parent.
-    pub fn parent(&self) -> QdiscParent {
-        self.parent
+#[cfg(feature = "serde")]
+impl From<DeclaredRule> for RuleRepr {
+    fn from(r: DeclaredRule) -> Self {
+        let cidr = |sel: Option<(IpAddr, u8)>| sel.map(|(a, l)| format!("{a}/{l}"));
+        RuleRepr {
+            priority: r.priority,
+            // Only needed when no selector carries the family.
+            ipv6: r.ipv6 && r.from.is_none() && r.to.is_none(),
+            from: cidr(r.from),
+            to: cidr(r.to),
+            fwmark: r.fwmark,
+            fwmask: r.fwmask,
+            iif: r.iif,
+            oif: r.oif,
+            table: (r.table != RT_TABLE_MAIN).then_some(r.table),
+            action: r.action,
+        }
     }
+}
 
-    /// Get the qdisc type.
-    pub fn qdisc_type(&self) -> &DeclaredQdiscType {
-        &self.qdisc_type
+#[cfg(feature = "serde")]
+impl TryFrom<RuleRepr> for DeclaredRule {
+    type Error = RuleParseError;
+
+    fn try_from(r: RuleRepr) -> Result<Self, Self::Error> {
+        let mut b = DeclaredRuleBuilder::new(r.priority);
+        if r.ipv6 {
+            b = b.ipv6();
+        }
+        if let Some(from) = &r.from {
+            b = b.from(from);
+        }
+        if let Some(to) = &r.to {
+            b = b.to(to);
+        }
+        b.fwmark = r.fwmark;
+        b.fwmask = r.fwmask;
+        b.iif = r.iif;
+        b.oif = r.oif;
+        if let Some(table) = r.table {
+            b = b.table(table);
+        }
+        b.action = r.action;
+        b.build()
     }
 }
 
-/// Qdisc parent location.
+impl DeclaredRule {
+    /// Get the rule priority.
+    pub fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    /// Get the source selector (address, prefix length).
+    pub fn from(&self) -> Option<(IpAddr, u8)> {
+        self.from
+    }
+
+    /// Get the destination selector (address, prefix length).
+    pub fn to(&self) -> Option<(IpAddr, u8)> {
+        self.to
+    }
+
+    /// Get the firewall mark selector.
+    pub fn fwmark(&self) -> Option<u32> {
+        self.fwmark
+    }
+
+    /// Get the firewall mark mask.
+    pub fn fwmask(&self) -> Option<u32> {
+        self.fwmask
+    }
+
+    /// Get the input interface selector.
+    pub fn iif(&self) -> Option<&str> {
+        self.iif.as_deref()
+    }
+
+    /// Get the output interface selector.
+    pub fn oif(&self) -> Option<&str> {
+        self.oif.as_deref()
+    }
+
+    /// Get the routing table looked up by a `lookup` rule.
+    pub fn table(&self) -> u32 {
+        self.table
+    }
+
+    /// Get the rule action.
+    pub fn action(&self) -> DeclaredRuleAction {
+        self.action
+    }
+
+    /// Check if this is an IPv4 rule.
+    pub fn is_ipv4(&self) -> bool {
+        !self.ipv6
+    }
+
+    /// Check if this is an IPv6 rule.
+    pub fn is_ipv6(&self) -> bool {
+        self.ipv6
+    }
+}
+
+/// The `main` routing table.
+const RT_TABLE_MAIN: u32 = 254;
+
+/// Rule action for declared configuration.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[non_exhaustive]
-pub enum QdiscParent {
-    /// Root qdisc.
+pub enum DeclaredRuleAction {
+    /// Look up the rule's table.
     #[default]
-    Root,
-    /// Ingress qdisc.
-    Ingress,
+    Lookup,
+    /// Silently drop.
+    Blackhole,
+    /// Fail with ICMP unreachable.
+    Unreachable,
+    /// Fail with ICMP prohibited.
+    Prohibit,
 }
 
-/// Qdisc type for declared configuration.
-///
-/// Note: the percentage fields on [`DeclaredQdiscType::Netem`] are
-/// stored as raw `f64`. When built through [`QdiscBuilder`] they pass
-/// through [`crate::util::Percent`] (clamped to `0..=100`); a value
-/// deserialized directly here is range-checked by the kernel at
-/// apply time rather than on construction.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
-#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
-#[derive(Debug, Clone)]
-#[non_exhaustive]
-pub enum DeclaredQdiscType {
-    /// Network emulator.
-    Netem {
-        delay_us: Option<u32>,
-        jitter_us: Option<u32>,
-        loss_percent: Option<f64>,
-        limit: Option<u32>,
-        /// Packet-duplication probability (netem `duplicate`).
-        duplicate_percent: Option<f64>,
-        /// Packet-corruption probability (netem `corrupt`).
-        corrupt_percent: Option<f64>,
-        /// Packet-reorder probability (netem `reorder`).
-        reorder_percent: Option<f64>,
-        /// Loss-correlation between adjacent packets
-        /// (netem `loss <p>% <corr>%`).
-        loss_correlation: Option<f64>,
-        /// Delay-correlation between adjacent packets
-        /// (netem `delay <t> <jitter> <corr>%`).
-        delay_correlation: Option<f64>,
-    },
-    /// Hierarchical Token Bucket.
-    Htb { default_class: u32 },
-    /// Fair Queueing Controlled Delay.
-    FqCodel {
-        limit: Option<u32>,
-        target_us: Option<u32>,
-        interval_us: Option<u32>,
-    },
-    /// Token Bucket Filter.
-    Tbf {
-        rate_bps: u64,
-        burst_bytes: u32,
-        limit_bytes: Option<u32>,
-    },
-    /// Stochastic Fair Queueing.
-    Sfq { perturb_secs: Option<u32> },
-    /// Priority qdisc.
-    Prio { bands: Option<u8> },
-    /// Ingress qdisc.
-    Ingress,
-    /// Clsact qdisc (for BPF).
-    Clsact,
+impl DeclaredRuleAction {
+    /// True for the default `lookup` action — lets serde omit it.
+    #[cfg(feature = "serde")]
+    fn is_lookup(&self) -> bool {
+        matches!(self, Self::Lookup)
+    }
 }
 
-impl DeclaredQdiscType {
-    /// Get the kind string.
-    pub fn kind(&self) -> &str {
-        match self {
+/// Error parsing a rule.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum RuleParseError {
+    /// Selector is missing prefix.
+    #[error("selector missing prefix: {0} (expected format: 10.0.0.0/8)")]
+    MissingPrefix(String),
+    /// Invalid selector address.
+    #[error("invalid selector address: {0}")]
+    InvalidAddress(String),
+    /// Invalid prefix length.
+    #[error("invalid prefix length: {0}")]
+    InvalidPrefix(String),
+    /// Prefix length too large.
+    #[error("prefix length {prefix} exceeds maximum {max}")]
+    PrefixTooLarge { prefix: u8, max: u8 },
+    /// Selectors of different address families, or an IPv4 selector
+    /// on a rule marked IPv6.
+    #[error("rule {priority} mixes IPv4 and IPv6 selectors")]
+    FamilyMismatch { priority: u32 },
+}
+
+/// Builder for policy routing rule configuration.
+///
+/// Unlike [`RouteBuilder::via`], selector parse errors aren't dropped:
+/// the first one is returned from [`NetworkConfig::rule`].
+#[derive(Debug)]
+#[must_use = "builders do nothing unless used"]
+pub struct DeclaredRuleBuilder {
+    priority: u32,
+    ipv6: bool,
+    from: Option<(IpAddr, u8)>,
+    to: Option<(IpAddr, u8)>,
+    fwmark: Option<u32>,
+    fwmask: Option<u32>,
+    iif: Option<String>,
+    oif: Option<String>,
+    table: u32,
+    action: DeclaredRuleAction,
+    error: Option<RuleParseError>,
+}
+
+impl DeclaredRuleBuilder {
+    fn new(priority: u32) -> Self {
+        Self {
+            priority,
+            ipv6: false,
+            from: None,
+            to: None,
+            fwmark: None,
+            fwmask: None,
+            iif: None,
+            oif: None,
+            table: RT_TABLE_MAIN,
+            action: DeclaredRuleAction::default(),
+            error: None,
+        }
+    }
+
+    /// Make this an IPv6 rule. Only needed when there is no `from`/`to`
+    /// selector to infer the family from.
+    pub fn ipv6(mut self) -> Self {
+        self.ipv6 = true;
+        self
+    }
+
+    /// Match packets from this source prefix (CIDR notation).
+    pub fn from(mut self, prefix: &str) -> Self {
+        match parse_selector(prefix) {
+            Ok(sel) => self.from = Some(sel),
+            Err(e) => self.error = self.error.or(Some(e)),
+        }
+        self
+    }
+
+    /// Match packets to this destination prefix (CIDR notation).
+    pub fn to(mut self, prefix: &str) -> Self {
+        match parse_selector(prefix) {
+            Ok(sel) => self.to = Some(sel),
+            Err(e) => self.error = self.error.or(Some(e)),
+        }
+        self
+    }
+
+    /// Match packets carrying this firewall mark.
+    pub fn fwmark(mut self, mark: u32) -> Self {
+        self.fwmark = Some(mark);
+        self
+    }
+
+    /// Match a firewall mark under a mask.
+    pub fn fwmark_mask(mut self, mark: u32, mask: u32) -> Self {
+        self.fwmark = Some(mark);
+        self.fwmask = Some(mask);
+        self
+    }
+
+    /// Match packets arriving on this interface.
+    pub fn iif(mut self, dev: &str) -> Self {
+        self.iif = Some(dev.to_string());
+        self
+    }
+
+    /// Match packets leaving through this interface.
+    pub fn oif(mut self, dev: &str) -> Self {
+        self.oif = Some(dev.to_string());
+        self
+    }
+
+    /// Look up this routing table (default `main`).
+    pub fn table(mut self, table: u32) -> Self {
+        self.table = table;
+        self
+    }
+
+    /// Drop matching packets silently.
+    pub fn blackhole(mut self) -> Self {
+        self.action = DeclaredRuleAction::Blackhole;
+        self
+    }
+
+    /// Reject matching packets with ICMP unreachable.
+    pub fn unreachable(mut self) -> Self {
+        self.action = DeclaredRuleAction::Unreachable;
+        self
+    }
+
+    /// Reject matching packets with ICMP prohibited.
+    pub fn prohibit(mut self) -> Self {
+        self.action = DeclaredRuleAction::Prohibit;
+        self
+    }
+
+    fn build(self) -> Result<DeclaredRule, RuleParseError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let families: Vec<bool> = [self.from, self.to]
+            .iter()
+            .flatten()
+            .map(|(a, _)| a.is_ipv6())
+            .collect();
+        let ipv6 = match families.as_slice() {
+            [] => self.ipv6,
+            [v6, rest @ ..] => {
+                if rest.iter().any(|r| r != v6) || (self.ipv6 && !v6) {
+                    return Err(RuleParseError::FamilyMismatch {
+                        priority: self.priority,
+                    });
+                }
+                *v6
+            }
+        };
+        Ok(DeclaredRule {
+            priority: self.priority,
+            ipv6,
+            from: self.from,
+            to: self.to,
+            fwmark: self.fwmark,
+            fwmask: self.fwmask,
+            iif: self.iif,
+            oif: self.oif,
+            table: self.table,
+            action: self.action,
+        })
+    }
+}
+
+fn parse_selector(prefix: &str) -> Result<(IpAddr, u8), RuleParseError> {
+    let (ip_str, prefix_str) = prefix
+        .split_once('/')
+        .ok_or_else(|| RuleParseError::MissingPrefix(prefix.to_string()))?;
+    let address: IpAddr = ip_str
+        .parse()
+        .map_err(|_| RuleParseError::InvalidAddress(ip_str.to_string()))?;
+    let prefix_len: u8 = prefix_str
+        .parse()
+        .map_err(|_| RuleParseError::InvalidPrefix(prefix_str.to_string()))?;
+    let max = if address.is_ipv4() { 32 } else { 128 };
+    if prefix_len > max {
+        return Err(RuleParseError::PrefixTooLarge {
+            prefix: prefix_len,
+            max,
+        });
+    }
+    Ok((address, prefix_len))
+}
+
+// ============================================================================
+// Neighbor Types
+// ============================================================================
+
+/// Declared static neighbor (ARP / NDP) entry. Applied as a
+/// `permanent` entry.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredNeighbor {
+    pub(crate) dev: String,
+    pub(crate) address: IpAddr,
+    /// Link-layer address, round-tripped as `aa:bb:cc:dd:ee:ff`.
+    #[cfg_attr(feature = "serde", serde(with = "mac_serde::required"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub(crate) lladdr: [u8; 6],
+}
+
+impl DeclaredNeighbor {
+    /// Get the device name.
+    pub fn dev(&self) -> &str {
+        &self.dev
+    }
+
+    /// Get the neighbor's IP address.
+    pub fn address(&self) -> IpAddr {
+        self.address
+    }
+
+    /// Get the link-layer address.
+    pub fn lladdr(&self) -> [u8; 6] {
+        self.lladdr
+    }
+}
+
+// ============================================================================
+// Sysctl Types
+// ============================================================================
+
+/// Declared per-interface sysctl value.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredSysctl {
+    pub(crate) dev: String,
+    pub(crate) key: InterfaceSysctl,
+    pub(crate) value: u32,
+}
+
+impl DeclaredSysctl {
+    /// Get the device name (or `all` / `default`).
+    pub fn dev(&self) -> &str {
+        &self.dev
+    }
+
+    /// Get the sysctl being set.
+    pub fn key(&self) -> InterfaceSysctl {
+        self.key
+    }
+
+    /// Get the desired value.
+    pub fn value(&self) -> u32 {
+        self.value
+    }
+
+    /// The full dotted key, e.g. `net.ipv4.conf.eth0.rp_filter`.
+    pub fn full_key(&self) -> String {
+        format!(
+            "net.{}.conf.{}.{}",
+            self.key.proto(),
+            self.dev,
+            self.key.name()
+        )
+    }
+}
+
+/// Per-interface sysctls that [`NetworkConfig`] manages.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InterfaceSysctl {
+    /// `net.ipv4.conf.<dev>.forwarding`.
+    Ipv4Forwarding,
+    /// `net.ipv6.conf.<dev>.forwarding`.
+    Ipv6Forwarding,
+    /// `net.ipv4.conf.<dev>.rp_filter` (0 off, 1 strict, 2 loose).
+    RpFilter,
+    /// `net.ipv6.conf.<dev>.accept_ra` (0 never, 1 unless forwarding,
+    /// 2 always).
+    AcceptRa,
+}
+
+impl InterfaceSysctl {
+    /// The `net.<proto>` component: `ipv4` or `ipv6`.
+    pub fn proto(&self) -> &'static str {
+        match self {
+            Self::Ipv4Forwarding | Self::RpFilter => "ipv4",
+            Self::Ipv6Forwarding | Self::AcceptRa => "ipv6",
+        }
+    }
+
+    /// The leaf name under `conf.<dev>`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ipv4Forwarding | Self::Ipv6Forwarding => "forwarding",
+            Self::RpFilter => "rp_filter",
+            Self::AcceptRa => "accept_ra",
+        }
+    }
+}
+
+// ============================================================================
+// Namespace Types
+// ============================================================================
+
+/// Declared named network namespace together with the resources
+/// configured inside it.
+///
+/// Serializes as the namespace `name` alongside the same sections a
+/// top-level [`NetworkConfig`] has.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone)]
+pub struct DeclaredNamespace {
+    pub(crate) name: String,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub(crate) config: NetworkConfig,
+}
+
+impl DeclaredNamespace {
+    /// Get the namespace name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the configuration applied inside the namespace.
+    pub fn config(&self) -> &NetworkConfig {
+        &self.config
+    }
+}
+
+// ============================================================================
+// Qdisc Types
+// ============================================================================
+
+/// Declared qdisc configuration.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone)]
+pub struct DeclaredQdisc {
+    pub(crate) dev: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) parent: QdiscParent,
+    /// Explicit handle in `tc(8)` notation, e.g. `"10:"`.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            with = "handle_serde::option",
+            skip_serializing_if = "Option::is_none"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+    pub(crate) handle: Option<TcHandle>,
+    pub(crate) qdisc_type: DeclaredQdiscType,
+}
+
+impl DeclaredQdisc {
+    /// Get the device name.
+    pub fn dev(&self) -> &str {
+        &self.dev
+    }
+
+    /// Get the parent.
+    pub fn parent(&self) -> QdiscParent {
+        self.parent
+    }
+
+    /// Get the explicit handle, if one was declared.
+    pub fn handle(&self) -> Option<TcHandle> {
+        self.handle
+    }
+
+    /// Get the qdisc type.
+    pub fn qdisc_type(&self) -> &DeclaredQdiscType {
+        &self.qdisc_type
+    }
+
+    /// The handle the qdisc is created with: the declared one, or `1:`
+    /// for HTB so its classes have a known major.
+    pub(crate) fn effective_handle(&self) -> Option<TcHandle> {
+        match (&self.handle, &self.qdisc_type) {
+            (Some(h), _) => Some(*h),
+            (None, DeclaredQdiscType::Htb { .. }) => Some(TcHandle::major_only(1)),
+            (None, _) => None,
+        }
+    }
+
+    /// `htb on eth0`, plus the parent class for a qdisc attached to one.
+    pub(crate) fn describe(&self) -> String {
+        match self.parent {
+            QdiscParent::Class(class) => {
+                format!("{} on {} parent {class}", self.qdisc_type.kind(), self.dev)
+            }
+            _ => format!("{} on {}", self.qdisc_type.kind(), self.dev),
+        }
+    }
+}
+
+/// Qdisc parent location.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum QdiscParent {
+    /// Root qdisc.
+    #[default]
+    Root,
+    /// Ingress qdisc.
+    Ingress,
+    /// Attached to a class, e.g. a leaf qdisc under an HTB class.
+    Class(
+        #[cfg_attr(feature = "serde", serde(with = "handle_serde"))]
+        #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+        TcHandle,
+    ),
+}
+
+impl QdiscParent {
+    /// The parent handle sent to the kernel.
+    pub fn handle(self) -> TcHandle {
+        match self {
+            Self::Root => TcHandle::ROOT,
+            Self::Ingress => TcHandle::INGRESS,
+            Self::Class(class) => class,
+        }
+    }
+}
+
+impl std::fmt::Display for QdiscParent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Root => f.write_str("root"),
+            Self::Ingress => f.write_str("ingress"),
+            Self::Class(class) => write!(f, "parent {class}"),
+        }
+    }
+}
+
+/// Qdisc type for declared configuration.
+///
+/// Note: the percentage fields on [`DeclaredQdiscType::Netem`] are
+/// stored as raw `f64`. When built through [`QdiscBuilder`] they pass
+/// through [`crate::util::Percent`] (clamped to `0..=100`); a value
+/// deserialized directly here is range-checked by the kernel at
+/// apply time rather than on construction.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum DeclaredQdiscType {
+    /// Network emulator.
+    Netem {
+        delay_us: Option<u32>,
+        jitter_us: Option<u32>,
+        loss_percent: Option<f64>,
+        limit: Option<u32>,
+        /// Packet-duplication probability (netem `duplicate`).
+        duplicate_percent: Option<f64>,
+        /// Packet-corruption probability (netem `corrupt`).
+        corrupt_percent: Option<f64>,
+        /// Packet-reorder probability (netem `reorder`).
+        reorder_percent: Option<f64>,
+        /// Loss-correlation between adjacent packets
+        /// (netem `loss <p>% <corr>%`).
+        loss_correlation: Option<f64>,
+        /// Delay-correlation between adjacent packets
+        /// (netem `delay <t> <jitter> <corr>%`).
+        delay_correlation: Option<f64>,
+    },
+    /// Hierarchical Token Bucket.
+    Htb { default_class: u32 },
+    /// Fair Queueing Controlled Delay.
+    FqCodel {
+        limit: Option<u32>,
+        target_us: Option<u32>,
+        interval_us: Option<u32>,
+    },
+    /// Token Bucket Filter.
+    Tbf {
+        rate_bps: u64,
+        burst_bytes: u32,
+        limit_bytes: Option<u32>,
+    },
+    /// Stochastic Fair Queueing.
+    Sfq { perturb_secs: Option<u32> },
+    /// Priority qdisc.
+    Prio { bands: Option<u8> },
+    /// Ingress qdisc.
+    Ingress,
+    /// Clsact qdisc (for BPF).
+    Clsact,
+}
+
+impl DeclaredQdiscType {
+    /// Get the kind string.
+    pub fn kind(&self) -> &str {
+        match self {
             Self::Netem { .. } => "netem",
             Self::Htb { .. } => "htb",
-            Self::FqCodel { .. } => "fq_codel",
-            Self::Tbf { .. } => "tbf",
-            Self::Sfq { .. } => "sfq",
-            Self::Prio { .. } => "prio",
-            Self::Ingress => "ingress",
-            Self::Clsact => "clsact",
+            Self::FqCodel { .. } => "fq_codel",
+            Self::Tbf { .. } => "tbf",
+            Self::Sfq { .. } => "sfq",
+            Self::Prio { .. } => "prio",
+            Self::Ingress => "ingress",
+            Self::Clsact => "clsact",
+        }
+    }
+}
+
+/// Builder for qdisc configuration.
+#[derive(Debug)]
+#[must_use = "builders do nothing unless used"]
+pub struct QdiscBuilder {
+    dev: String,
+    parent: QdiscParent,
+    handle: Option<TcHandle>,
+    qdisc_type: Option<DeclaredQdiscType>,
+}
+
+impl QdiscBuilder {
+    fn new(dev: &str) -> Self {
+        Self {
+            dev: dev.to_string(),
+            parent: QdiscParent::Root,
+            handle: None,
+            qdisc_type: None,
+        }
+    }
+
+    /// Attach the qdisc to a class instead of the root, e.g. a leaf
+    /// `fq_codel` under HTB class `1:10`.
+    pub fn parent_class(mut self, class: TcHandle) -> Self {
+        self.parent = QdiscParent::Class(class);
+        self
+    }
+
+    /// Set the qdisc handle (`tc qdisc add ... handle 10:`).
+    ///
+    /// HTB defaults to `1:`; other kinds let the kernel pick one. A
+    /// qdisc whose filters or classes are declared needs a known
+    /// handle to reference.
+    pub fn handle(mut self, handle: TcHandle) -> Self {
+        self.handle = Some(handle);
+        self
+    }
+
+    /// Configure as netem qdisc.
+    pub fn netem(mut self) -> Self {
+        self.qdisc_type = Some(DeclaredQdiscType::Netem {
+            delay_us: None,
+            jitter_us: None,
+            loss_percent: None,
+            limit: None,
+            duplicate_percent: None,
+            corrupt_percent: None,
+            reorder_percent: None,
+            loss_correlation: None,
+            delay_correlation: None,
+        });
+        self
+    }
+
+    /// Set netem delay in milliseconds.
+    pub fn delay_ms(mut self, ms: u32) -> Self {
+        if let Some(DeclaredQdiscType::Netem { delay_us, .. }) = &mut self.qdisc_type {
+            *delay_us = Some(ms * 1000);
+        }
+        self
+    }
+
+    /// Set netem delay in microseconds.
+    pub fn delay_us(mut self, us: u32) -> Self {
+        if let Some(DeclaredQdiscType::Netem { delay_us, .. }) = &mut self.qdisc_type {
+            *delay_us = Some(us);
+        }
+        self
+    }
+
+    /// Set netem jitter in milliseconds.
+    pub fn jitter_ms(mut self, ms: u32) -> Self {
+        if let Some(DeclaredQdiscType::Netem { jitter_us, .. }) = &mut self.qdisc_type {
+            *jitter_us = Some(ms * 1000);
+        }
+        self
+    }
+
+    /// Set netem packet loss as a typed [`crate::util::Percent`].
+    ///
+    /// Internally stores the clamped `f64` so the wire-format diff
+    /// machinery (which compares with `PartialEq` on `Option<f64>`)
+    /// stays stable. The typed boundary at the setter is what kills
+    /// the wrong-units footgun.
+    ///
+    /// (The 0.20.1 deprecated `loss(f64)` form was removed in 0.21
+    /// per the typed-units rollout. Construct the argument with
+    /// `Percent::new(1.5)` for percent values or
+    /// `Percent::from_fraction(0.015)` for fractional values — both
+    /// produce identical wire bytes.)
+    pub fn loss_pct(mut self, percent: crate::util::Percent) -> Self {
+        if let Some(DeclaredQdiscType::Netem { loss_percent, .. }) = &mut self.qdisc_type {
+            *loss_percent = Some(percent.as_percent());
+        }
+        self
+    }
+
+    /// Set netem packet-duplication probability.
+    ///
+    /// Mirror of `NetemConfig::duplicate(Percent)` for the
+    /// declarative path (Plan 228 extension, 0.21).
+    pub fn duplicate_pct(mut self, percent: crate::util::Percent) -> Self {
+        if let Some(DeclaredQdiscType::Netem { duplicate_percent, .. }) =
+            &mut self.qdisc_type
+        {
+            *duplicate_percent = Some(percent.as_percent());
+        }
+        self
+    }
+
+    /// Set netem packet-corruption probability.
+    ///
+    /// Mirror of `NetemConfig::corrupt(Percent)` for the
+    /// declarative path (Plan 228 extension, 0.21).
+    pub fn corrupt_pct(mut self, percent: crate::util::Percent) -> Self {
+        if let Some(DeclaredQdiscType::Netem { corrupt_percent, .. }) =
+            &mut self.qdisc_type
+        {
+            *corrupt_percent = Some(percent.as_percent());
+        }
+        self
+    }
+
+    /// Set netem packet-reorder probability.
+    ///
+    /// Mirror of `NetemConfig::reorder(Percent, …)` for the
+    /// declarative path (Plan 228 extension, 0.21). The
+    /// declarative form takes only the primary reorder
+    /// probability; reorder correlation is a separate setter
+    /// (`reorder_correlation_pct`) for symmetry with the other
+    /// correlation knobs — call both to mirror the imperative
+    /// 2-arg form.
+    pub fn reorder_pct(mut self, percent: crate::util::Percent) -> Self {
+        if let Some(DeclaredQdiscType::Netem { reorder_percent, .. }) =
+            &mut self.qdisc_type
+        {
+            *reorder_percent = Some(percent.as_percent());
+        }
+        self
+    }
+
+    /// Set netem loss correlation (correlation between adjacent
+    /// packets' loss outcomes).
+    ///
+    /// Mirror of `NetemConfig::loss_correlation(Percent)` for the
+    /// declarative path (Plan 228 extension, 0.21).
+    pub fn loss_correlation_pct(mut self, percent: crate::util::Percent) -> Self {
+        if let Some(DeclaredQdiscType::Netem { loss_correlation, .. }) =
+            &mut self.qdisc_type
+        {
+            *loss_correlation = Some(percent.as_percent());
+        }
+        self
+    }
+
+    /// Set netem delay correlation (correlation between adjacent
+    /// packets' delay outcomes).
+    ///
+    /// Mirror of `NetemConfig::delay_correlation(Percent)` for the
+    /// declarative path (Plan 228 extension, 0.21).
+    pub fn delay_correlation_pct(mut self, percent: crate::util::Percent) -> Self {
+        if let Some(DeclaredQdiscType::Netem { delay_correlation, .. }) =
+            &mut self.qdisc_type
+        {
+            *delay_correlation = Some(percent.as_percent());
+        }
+        self
+    }
+
+    /// Set netem queue limit.
+    pub fn limit(mut self, packets: u32) -> Self {
+        if let Some(DeclaredQdiscType::Netem { limit, .. }) = &mut self.qdisc_type {
+            *limit = Some(packets);
+        }
+        self
+    }
+
+    /// Configure as HTB qdisc.
+    pub fn htb(mut self) -> Self {
+        self.qdisc_type = Some(DeclaredQdiscType::Htb { default_class: 0 });
+        self
+    }
+
+    /// Set HTB default class.
+    pub fn default_class(mut self, class: u32) -> Self {
+        if let Some(DeclaredQdiscType::Htb { default_class }) = &mut self.qdisc_type {
+            *default_class = class;
+        }
+        self
+    }
+
+    /// Configure as fq_codel qdisc.
+    pub fn fq_codel(mut self) -> Self {
+        self.qdisc_type = Some(DeclaredQdiscType::FqCodel {
+            limit: None,
+            target_us: None,
+            interval_us: None,
+        });
+        self
+    }
+
+    /// Configure as TBF qdisc.
+    pub fn tbf(mut self, rate_bps: u64, burst_bytes: u32) -> Self {
+        self.qdisc_type = Some(DeclaredQdiscType::Tbf {
+            rate_bps,
+            burst_bytes,
+            limit_bytes: None,
+        });
+        self
+    }
+
+    /// Configure as SFQ qdisc.
+    pub fn sfq(mut self) -> Self {
+        self.qdisc_type = Some(DeclaredQdiscType::Sfq { perturb_secs: None });
+        self
+    }
+
+    /// Configure as prio qdisc.
+    pub fn prio(mut self) -> Self {
+        self.qdisc_type = Some(DeclaredQdiscType::Prio { bands: None });
+        self
+    }
+
+    /// Configure as ingress qdisc.
+    pub fn ingress(mut self) -> Self {
+        self.parent = QdiscParent::Ingress;
+        self.qdisc_type = Some(DeclaredQdiscType::Ingress);
+        self
+    }
+
+    /// Configure as clsact qdisc.
+    pub fn clsact(mut self) -> Self {
+        self.qdisc_type = Some(DeclaredQdiscType::Clsact);
+        self
+    }
+
+    fn build(self) -> DeclaredQdisc {
+        DeclaredQdisc {
+            dev: self.dev,
+            parent: self.parent,
+            handle: self.handle,
+            qdisc_type: self.qdisc_type.unwrap_or(DeclaredQdiscType::FqCodel {
+                limit: None,
+                target_us: None,
+                interval_us: None,
+            }),
+        }
+    }
+}
+
+// ============================================================================
+// Class Types
+// ============================================================================
+
+/// Declared traffic class, identified by its device and class ID.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredClass {
+    pub(crate) dev: String,
+    /// Class ID in `tc(8)` notation, e.g. `"1:10"`.
+    #[cfg_attr(feature = "serde", serde(with = "handle_serde"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub(crate) classid: TcHandle,
+    /// Parent class, or the qdisc (`"1:"`) for a top-level class.
+    #[cfg_attr(feature = "serde", serde(with = "handle_serde"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub(crate) parent: TcHandle,
+    pub(crate) class_type: DeclaredClassType,
+}
+
+impl DeclaredClass {
+    /// Get the device name.
+    pub fn dev(&self) -> &str {
+        &self.dev
+    }
+
+    /// Get the class ID.
+    pub fn classid(&self) -> TcHandle {
+        self.classid
+    }
+
+    /// Get the parent class or qdisc.
+    pub fn parent(&self) -> TcHandle {
+        self.parent
+    }
+
+    /// Get the class type.
+    pub fn class_type(&self) -> &DeclaredClassType {
+        &self.class_type
+    }
+
+    /// Whether the class hangs directly off its qdisc.
+    pub(crate) fn is_top_level(&self) -> bool {
+        self.parent == TcHandle::major_only(self.classid.major())
+    }
+
+    /// `htb 1:10 on eth0`.
+    pub(crate) fn describe(&self) -> String {
+        format!(
+            "{} {} on {}",
+            self.class_type.kind(),
+            self.classid,
+            self.dev
+        )
+    }
+}
+
+/// Class type for declared configuration.
+///
+/// Rates are in bytes per second, like [`DeclaredQdiscType::Tbf`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeclaredClassType {
+    /// HTB class.
+    Htb {
+        rate_bps: u64,
+        /// Defaults to the rate.
+        ceil_bps: Option<u64>,
+        /// Defaults to one MTU's worth; compared only when set.
+        burst_bytes: Option<u32>,
+        /// Defaults to one MTU's worth; compared only when set.
+        cburst_bytes: Option<u32>,
+        /// 0-7, lower is served first. Defaults to 0.
+        prio: Option<u32>,
+        /// Defaults to one the kernel derives from the rate; compared
+        /// only when set.
+        quantum: Option<u32>,
+    },
+}
+
+impl DeclaredClassType {
+    /// Get the kind string.
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Htb { .. } => "htb",
         }
     }
 }
 
-/// Builder for qdisc configuration.
+/// Builder for class configuration.
 #[derive(Debug)]
 #[must_use = "builders do nothing unless used"]
-pub struct QdiscBuilder {
+pub struct ClassBuilder {
     dev: String,
-    parent: QdiscParent,
-    qdisc_type: Option<DeclaredQdiscType>,
+    classid: TcHandle,
+    parent: TcHandle,
+    class_type: DeclaredClassType,
 }
 
-impl QdiscBuilder {
-    fn new(dev: &str) -> Self {
+impl ClassBuilder {
+    fn new(dev: &str, classid: TcHandle) -> Self {
         Self {
             dev: dev.to_string(),
-            parent: QdiscParent::Root,
-            qdisc_type: None,
+            classid,
+            parent: TcHandle::major_only(classid.major()),
+            class_type: DeclaredClassType::Htb {
+                rate_bps: 0,
+                ceil_bps: None,
+                burst_bytes: None,
+                cburst_bytes: None,
+                prio: None,
+                quantum: None,
+            },
         }
     }
 
-    /// Configure as netem qdisc.
-    pub fn netem(mut self) -> Self {
-        self.qdisc_type = Some(DeclaredQdiscType::Netem {
-            delay_us: None,
-            jitter_us: None,
-            loss_percent: None,
-            limit: None,
-            duplicate_percent: None,
-            corrupt_percent: None,
-            reorder_percent: None,
-            loss_correlation: None,
-            delay_correlation: None,
-        });
+    /// Set the parent class. Defaults to the qdisc the class ID's
+    /// major refers to, making the class top-level.
+    pub fn parent(mut self, parent: TcHandle) -> Self {
+        self.parent = parent;
         self
     }
 
-    /// Set netem delay in milliseconds.
-    pub fn delay_ms(mut self, ms: u32) -> Self {
-        if let Some(DeclaredQdiscType::Netem { delay_us, .. }) = &mut self.qdisc_type {
-            *delay_us = Some(ms * 1000);
-        }
+    /// Configure as an HTB class with this guaranteed rate.
+    pub fn htb(mut self, rate: crate::util::Rate) -> Self {
+        let DeclaredClassType::Htb { rate_bps, .. } = &mut self.class_type;
+        *rate_bps = rate.as_bytes_per_sec();
         self
     }
 
-    /// Set netem delay in microseconds.
-    pub fn delay_us(mut self, us: u32) -> Self {
-        if let Some(DeclaredQdiscType::Netem { delay_us, .. }) = &mut self.qdisc_type {
-            *delay_us = Some(us);
-        }
+    /// Set the HTB ceiling rate.
+    pub fn ceil(mut self, rate: crate::util::Rate) -> Self {
+        let DeclaredClassType::Htb { ceil_bps, .. } = &mut self.class_type;
+        *ceil_bps = Some(rate.as_bytes_per_sec());
         self
     }
 
-    /// Set netem jitter in milliseconds.
-    pub fn jitter_ms(mut self, ms: u32) -> Self {
-        if let Some(DeclaredQdiscType::Netem { jitter_us, .. }) = &mut self.qdisc_type {
-            *jitter_us = Some(ms * 1000);
-        }
+    /// Set the HTB burst size.
+    pub fn burst(mut self, size: crate::util::Bytes) -> Self {
+        let DeclaredClassType::Htb { burst_bytes, .. } = &mut self.class_type;
+        *burst_bytes = Some(size.as_u32_saturating());
         self
     }
 
-    /// Set netem packet loss as a typed [`crate::util::Percent`].
-    ///
-    /// Internally stores the clamped `f64` so the wire-format diff
-    /// machinery (which compares with `PartialEq` on `Option<f64>`)
-    /// stays stable. The typed boundary at the setter is what kills
-    /// the wrong-units footgun.
-    ///
-    /// (The 0.20.1 deprecated `loss(f64)` form was removed in 0.21
-    /// per the typed-units rollout. Construct the argument with
-    /// `Percent::new(1.5)` for percent values or
-    /// `Percent::from_fraction(0.015)` for fractional values — both
-    /// produce identical wire bytes.)
-    pub fn loss_pct(mut self, percent: crate::util::Percent) -> Self {
-        if let Some(DeclaredQdiscType::Netem { loss_percent, .. }) = &mut self.qdisc_type {
-            *loss_percent = Some(percent.as_percent());
-        }
+    /// Set the HTB ceil burst size.
+    pub fn cburst(mut self, size: crate::util::Bytes) -> Self {
+        let DeclaredClassType::Htb { cburst_bytes, .. } = &mut self.class_type;
+        *cburst_bytes = Some(size.as_u32_saturating());
+        self
+    }
+
+    /// Set the HTB priority (0-7, clamped).
+    pub fn prio(mut self, value: u32) -> Self {
+        let DeclaredClassType::Htb { prio, .. } = &mut self.class_type;
+        *prio = Some(value.min(7));
+        self
+    }
+
+    /// Set the HTB quantum in bytes.
+    pub fn quantum(mut self, bytes: u32) -> Self {
+        let DeclaredClassType::Htb { quantum, .. } = &mut self.class_type;
+        *quantum = Some(bytes);
         self
     }
 
-    /// Set netem packet-duplication probability.
-    ///
-    /// Mirror of `NetemConfig::duplicate(Percent)` for the
-    /// declarative path (Plan 228 extension, 0.21).
-    pub fn duplicate_pct(mut self, percent: crate::util::Percent) -> Self {
-        if let Some(DeclaredQdiscType::Netem { duplicate_percent, .. }) =
-            &mut self.qdisc_type
-        {
-            *duplicate_percent = Some(percent.as_percent());
+    fn build(self) -> DeclaredClass {
+        DeclaredClass {
+            dev: self.dev,
+            classid: self.classid,
+            parent: self.parent,
+            class_type: self.class_type,
+        }
+    }
+}
+
+// ============================================================================
+// Filter Types
+// ============================================================================
+
+/// Declared classifier.
+///
+/// A filter is identified by its device, parent and priority: the
+/// kernel entry at that priority is replaced when it doesn't match, so
+/// each declared filter needs a priority of its own.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredFilter {
+    pub(crate) dev: String,
+    /// Qdisc or class the filter is attached to, e.g. `"1:"`.
+    #[cfg_attr(feature = "serde", serde(with = "handle_serde"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
+    pub(crate) parent: TcHandle,
+    pub(crate) priority: u16,
+    pub(crate) filter_type: DeclaredFilterType,
+}
+
+impl DeclaredFilter {
+    /// Get the device name.
+    pub fn dev(&self) -> &str {
+        &self.dev
+    }
+
+    /// Get the parent qdisc or class.
+    pub fn parent(&self) -> TcHandle {
+        self.parent
+    }
+
+    /// Get the priority.
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// Get the filter type.
+    pub fn filter_type(&self) -> &DeclaredFilterType {
+        &self.filter_type
+    }
+
+    /// The ethertype the filter is installed for (`tc filter ...
+    /// protocol`).
+    pub(crate) fn protocol(&self) -> u16 {
+        match &self.filter_type {
+            DeclaredFilterType::Flower { .. } => self.filter_type.eth_type().unwrap_or(ETH_P_ALL),
         }
-        self
     }
 
-    /// Set netem packet-corruption probability.
-    ///
-    /// Mirror of `NetemConfig::corrupt(Percent)` for the
-    /// declarative path (Plan 228 extension, 0.21).
-    pub fn corrupt_pct(mut self, percent: crate::util::Percent) -> Self {
-        if let Some(DeclaredQdiscType::Netem { corrupt_percent, .. }) =
-            &mut self.qdisc_type
-        {
-            *corrupt_percent = Some(percent.as_percent());
-        }
-        self
+    /// `flower prio 10 on eth0 parent 1:`.
+    pub(crate) fn describe(&self) -> String {
+        format!(
+            "{} prio {} on {} parent {}",
+            self.filter_type.kind(),
+            self.priority,
+            self.dev,
+            self.parent
+        )
     }
+}
 
-    /// Set netem packet-reorder probability.
-    ///
-    /// Mirror of `NetemConfig::reorder(Percent, …)` for the
-    /// declarative path (Plan 228 extension, 0.21). The
-    /// declarative form takes only the primary reorder
-    /// probability; reorder correlation is a separate setter
-    /// (`reorder_correlation_pct`) for symmetry with the other
-    /// correlation knobs — call both to mirror the imperative
-    /// 2-arg form.
-    pub fn reorder_pct(mut self, percent: crate::util::Percent) -> Self {
-        if let Some(DeclaredQdiscType::Netem { reorder_percent, .. }) =
-            &mut self.qdisc_type
-        {
-            *reorder_percent = Some(percent.as_percent());
+pub(crate) const ETH_P_ALL: u16 = 0x0003;
+pub(crate) const ETH_P_IP: u16 = 0x0800;
+pub(crate) const ETH_P_IPV6: u16 = 0x86DD;
+
+/// Filter type for declared configuration.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeclaredFilterType {
+    /// Flower classifier. With no IP key it matches every packet;
+    /// any IP key restricts it to IPv4, or IPv6 when `ipv6` is set or
+    /// an address is IPv6.
+    Flower {
+        /// Class matching packets are sent to.
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                default,
+                with = "handle_serde::option",
+                skip_serializing_if = "Option::is_none"
+            )
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+        classid: Option<TcHandle>,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "std::ops::Not::not")
+        )]
+        ipv6: bool,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        ip_proto: Option<u8>,
+        /// CIDR-notation source prefix, e.g. `"10.1.0.0/16"`.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, with = "cidr_serde", skip_serializing_if = "Option::is_none")
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+        src: Option<(IpAddr, u8)>,
+        /// CIDR-notation destination prefix.
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, with = "cidr_serde", skip_serializing_if = "Option::is_none")
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
+        dst: Option<(IpAddr, u8)>,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        src_port: Option<u16>,
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        dst_port: Option<u16>,
+    },
+}
+
+impl DeclaredFilterType {
+    /// Get the kind string.
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Flower { .. } => "flower",
         }
-        self
     }
 
-    /// Set netem loss correlation (correlation between adjacent
-    /// packets' loss outcomes).
-    ///
-    /// Mirror of `NetemConfig::loss_correlation(Percent)` for the
-    /// declarative path (Plan 228 extension, 0.21).
-    pub fn loss_correlation_pct(mut self, percent: crate::util::Percent) -> Self {
-        if let Some(DeclaredQdiscType::Netem { loss_correlation, .. }) =
-            &mut self.qdisc_type
-        {
-            *loss_correlation = Some(percent.as_percent());
+    /// The `eth_type` key a flower filter carries, if any.
+    pub(crate) fn eth_type(&self) -> Option<u16> {
+        match self {
+            Self::Flower {
+                ipv6,
+                ip_proto,
+                src,
+                dst,
+                ..
+            } => {
+                let v6 = *ipv6
+                    || [src, dst]
+                        .iter()
+                        .any(|s| s.is_some_and(|(a, _)| a.is_ipv6()));
+                let ip = ip_proto.is_some() || src.is_some() || dst.is_some();
+                match (ip, v6) {
+                    (false, _) => None,
+                    (true, false) => Some(ETH_P_IP),
+                    (true, true) => Some(ETH_P_IPV6),
+                }
+            }
         }
-        self
     }
+}
 
-    /// Set netem delay correlation (correlation between adjacent
-    /// packets' delay outcomes).
-    ///
-    /// Mirror of `NetemConfig::delay_correlation(Percent)` for the
-    /// declarative path (Plan 228 extension, 0.21).
-    pub fn delay_correlation_pct(mut self, percent: crate::util::Percent) -> Self {
-        if let Some(DeclaredQdiscType::Netem { delay_correlation, .. }) =
-            &mut self.qdisc_type
-        {
-            *delay_correlation = Some(percent.as_percent());
+/// Error building a filter.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum FilterParseError {
+    /// A source or destination prefix didn't parse.
+    #[error("invalid filter selector: {0}")]
+    Selector(#[source] RuleParseError),
+    /// Selectors of different address families, or an IPv4 selector
+    /// on a filter marked IPv6.
+    #[error("filter {priority} mixes IPv4 and IPv6 selectors")]
+    FamilyMismatch { priority: u16 },
+    /// A port key without `ip_proto` TCP or UDP, which flower needs to
+    /// know which header the port is in.
+    #[error("filter {priority} matches a port but not tcp or udp")]
+    PortWithoutProtocol { priority: u16 },
+}
+
+/// Builder for filter configuration.
+///
+/// As with [`DeclaredRuleBuilder`], the first selector parse error is
+/// returned from [`NetworkConfig::filter`].
+#[derive(Debug)]
+#[must_use = "builders do nothing unless used"]
+pub struct FilterBuilder {
+    dev: String,
+    parent: TcHandle,
+    priority: u16,
+    filter_type: DeclaredFilterType,
+    error: Option<FilterParseError>,
+}
+
+impl FilterBuilder {
+    fn new(dev: &str, parent: TcHandle, priority: u16) -> Self {
+        Self {
+            dev: dev.to_string(),
+            parent,
+            priority,
+            filter_type: DeclaredFilterType::Flower {
+                classid: None,
+                ipv6: false,
+                ip_proto: None,
+                src: None,
+                dst: None,
+                src_port: None,
+                dst_port: None,
+            },
+            error: None,
         }
-        self
     }
 
-    /// Set netem queue limit.
-    pub fn limit(mut self, packets: u32) -> Self {
-        if let Some(DeclaredQdiscType::Netem { limit, .. }) = &mut self.qdisc_type {
-            *limit = Some(packets);
-        }
+    /// Configure as a flower filter (the default).
+    pub fn flower(self) -> Self {
         self
     }
 
-    /// Configure as HTB qdisc.
-    pub fn htb(mut self) -> Self {
-        self.qdisc_type = Some(DeclaredQdiscType::Htb { default_class: 0 });
+    /// Send matching packets to this class.
+    pub fn classid(mut self, class: TcHandle) -> Self {
+        let DeclaredFilterType::Flower { classid, .. } = &mut self.filter_type;
+        *classid = Some(class);
         self
     }
 
-    /// Set HTB default class.
-    pub fn default_class(mut self, class: u32) -> Self {
-        if let Some(DeclaredQdiscType::Htb { default_class }) = &mut self.qdisc_type {
-            *default_class = class;
-        }
+    /// Match IPv6 packets. Only needed when no address says so.
+    pub fn ipv6(mut self) -> Self {
+        let DeclaredFilterType::Flower { ipv6, .. } = &mut self.filter_type;
+        *ipv6 = true;
         self
     }
 
-    /// Configure as fq_codel qdisc.
-    pub fn fq_codel(mut self) -> Self {
-        self.qdisc_type = Some(DeclaredQdiscType::FqCodel {
-            limit: None,
-            target_us: None,
-            interval_us: None,
-        });
+    /// Match this IP protocol number.
+    pub fn ip_proto(mut self, proto: u8) -> Self {
+        let DeclaredFilterType::Flower { ip_proto, .. } = &mut self.filter_type;
+        *ip_proto = Some(proto);
         self
     }
 
-    /// Configure as TBF qdisc.
-    pub fn tbf(mut self, rate_bps: u64, burst_bytes: u32) -> Self {
-        self.qdisc_type = Some(DeclaredQdiscType::Tbf {
-            rate_bps,
-            burst_bytes,
-            limit_bytes: None,
-        });
-        self
+    /// Match TCP.
+    pub fn tcp(self) -> Self {
+        self.ip_proto(IPPROTO_TCP)
     }
 
-    /// Configure as SFQ qdisc.
-    pub fn sfq(mut self) -> Self {
-        self.qdisc_type = Some(DeclaredQdiscType::Sfq { perturb_secs: None });
+    /// Match UDP.
+    pub fn udp(self) -> Self {
+        self.ip_proto(IPPROTO_UDP)
+    }
+
+    /// Match packets from this source prefix (CIDR notation).
+    pub fn src(mut self, prefix: &str) -> Self {
+        match parse_selector(prefix) {
+            Ok(sel) => {
+                let DeclaredFilterType::Flower { src, .. } = &mut self.filter_type;
+                *src = Some(sel);
+            }
+            Err(e) => self.error = self.error.or(Some(FilterParseError::Selector(e))),
+        }
         self
     }
 
-    /// Configure as prio qdisc.
-    pub fn prio(mut self) -> Self {
-        self.qdisc_type = Some(DeclaredQdiscType::Prio { bands: None });
+    /// Match packets to this destination prefix (CIDR notation).
+    pub fn dst(mut self, prefix: &str) -> Self {
+        match parse_selector(prefix) {
+            Ok(sel) => {
+                let DeclaredFilterType::Flower { dst, .. } = &mut self.filter_type;
+                *dst = Some(sel);
+            }
+            Err(e) => self.error = self.error.or(Some(FilterParseError::Selector(e))),
+        }
         self
     }
 
-    /// Configure as ingress qdisc.
-    pub fn ingress(mut self) -> Self {
-        self.parent = QdiscParent::Ingress;
-        self.qdisc_type = Some(DeclaredQdiscType::Ingress);
+    /// Match this source port. Needs [`tcp`](Self::tcp) or
+    /// [`udp`](Self::udp).
+    pub fn src_port(mut self, port: u16) -> Self {
+        let DeclaredFilterType::Flower { src_port, .. } = &mut self.filter_type;
+        *src_port = Some(port);
         self
     }
 
-    /// Configure as clsact qdisc.
-    pub fn clsact(mut self) -> Self {
-        self.qdisc_type = Some(DeclaredQdiscType::Clsact);
+    /// Match this destination port. Needs [`tcp`](Self::tcp) or
+    /// [`udp`](Self::udp).
+    pub fn dst_port(mut self, port: u16) -> Self {
+        let DeclaredFilterType::Flower { dst_port, .. } = &mut self.filter_type;
+        *dst_port = Some(port);
         self
     }
 
-    fn build(self) -> DeclaredQdisc {
-        DeclaredQdisc {
+    fn build(self) -> Result<DeclaredFilter, FilterParseError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+        let filter = DeclaredFilter {
             dev: self.dev,
             parent: self.parent,
-            qdisc_type: self.qdisc_type.unwrap_or(DeclaredQdiscType::FqCodel {
-                limit: None,
-                target_us: None,
-                interval_us: None,
-            }),
+            priority: self.priority,
+            filter_type: self.filter_type,
+        };
+        filter.validate()?;
+        Ok(filter)
+    }
+}
+
+const IPPROTO_TCP: u8 = 6;
+const IPPROTO_UDP: u8 = 17;
+
+impl DeclaredFilter {
+    /// Checks shared by the builder and the diff, which sees
+    /// deserialized filters too.
+    pub(crate) fn validate(&self) -> Result<(), FilterParseError> {
+        let priority = self.priority;
+        match &self.filter_type {
+            DeclaredFilterType::Flower {
+                ipv6,
+                ip_proto,
+                src,
+                dst,
+                src_port,
+                dst_port,
+                ..
+            } => {
+                let families: Vec<bool> = [src, dst]
+                    .into_iter()
+                    .flatten()
+                    .map(|(a, _)| a.is_ipv6())
+                    .collect();
+                if families.windows(2).any(|w| w[0] != w[1]) || (*ipv6 && families.contains(&false))
+                {
+                    return Err(FilterParseError::FamilyMismatch { priority });
+                }
+                let ports = src_port.is_some() || dst_port.is_some();
+                if ports && !matches!(ip_proto, Some(IPPROTO_TCP | IPPROTO_UDP)) {
+                    return Err(FilterParseError::PortWithoutProtocol { priority });
+                }
+            }
         }
+        Ok(())
     }
 }
 
@@ -2270,6 +3684,14 @@ mod serde_roundtrip_tests {
         assert!(back.links().is_empty() && back.addresses().is_empty());
     }
 
+    #[test]
+    fn owner_round_trips() {
+        let json = NetworkConfig::new().owner(200).to_json_string().unwrap();
+        assert_eq!(json, r#"{"owner":200}"#);
+        let back = NetworkConfig::from_json_str(&json).unwrap();
+        assert_eq!(back.owner_tag(), Some(200));
+    }
+
     #[test]
     fn documented_json_example_parses() {
         // Pins the exact JSON shown in docs/library.md "Declarative
@@ -2293,6 +3715,139 @@ mod serde_roundtrip_tests {
         assert_eq!(cfg.links()[1].name(), "veth0");
     }
 
+    #[test]
+    fn rules_neighbors_and_sysctls_roundtrip() {
+        let cfg = NetworkConfig::new()
+            .rule(100, |r| r.from("10.1.0.0/16").table(100))
+            .expect("rule")
+            .rule(110, |r| r.fwmark_mask(0x1, 0xff).iif("eth1").ipv6().table(200))
+            .expect("fwmark rule")
+            .rule(120, |r| r.to("fd00::/8").prohibit())
+            .expect("prohibit rule")
+            .neighbor("eth0", "10.0.0.1".parse().unwrap(), [2, 0, 0, 0, 0, 1])
+            .sysctl("eth0.100", InterfaceSysctl::RpFilter, 2)
+            .sysctl("eth0", InterfaceSysctl::AcceptRa, 0);
+        let json = cfg.to_json_string().unwrap();
+        assert!(json.contains(r#""from":"10.1.0.0/16""#), "{json}");
+        assert!(json.contains(r#""lladdr":"02:00:00:00:00:01""#), "{json}");
+        assert!(json.contains(r#""key":"rp_filter""#), "{json}");
+        // The default `lookup` action and `main` table are omitted.
+        assert!(!json.contains("lookup") && !json.contains("254"), "{json}");
+        assert_roundtrips(&cfg);
+
+        let back = NetworkConfig::from_json_str(&json).unwrap();
+        assert_eq!(back.rules(), cfg.rules());
+        assert!(back.rules()[1].is_ipv6());
+        assert_eq!(back.neighbors(), cfg.neighbors());
+        assert_eq!(back.sysctls(), cfg.sysctls());
+    }
+
+    #[test]
+    fn namespaces_roundtrip_flattened() {
+        let cfg = NetworkConfig::new()
+            .link("veth0", |l| l.veth("veth1").veth_peer_netns("ns1").up())
+            .namespace(
+                "ns1",
+                NetworkConfig::new()
+                    .link("veth1", |l| l.up())
+                    .address("veth1", "10.0.0.2/24")
+                    .unwrap(),
+            );
+        let json = cfg.to_json_string().unwrap();
+        assert!(json.contains(r#""peer_netns":"ns1""#), "{json}");
+        // The namespace's sections sit next to its name, not nested.
+        assert!(
+            json.contains(r#""namespaces":[{"name":"ns1","links":"#),
+            "{json}"
+        );
+        assert_roundtrips(&cfg);
+
+        let back = NetworkConfig::from_json_str(&json).unwrap();
+        let [ns] = back.namespaces() else {
+            panic!("expected one namespace");
+        };
+        assert_eq!(ns.name(), "ns1");
+        assert_eq!(ns.config().addresses()[0].dev(), "veth1");
+        assert!(matches!(
+            back.links()[0].link_type(),
+            DeclaredLinkType::Veth { peer_netns: Some(n), .. } if n == "ns1"
+        ));
+
+        // A veth without a peer namespace still omits the field.
+        let plain = NetworkConfig::new().link("veth0", |l| l.veth("veth1"));
+        assert!(!plain.to_json_string().unwrap().contains("peer_netns"));
+    }
+
+    #[test]
+    fn rule_rejects_mixed_families() {
+        let err = NetworkConfig::from_json_str(
+            r#"{"rules":[{"priority":100,"from":"10.0.0.0/8","to":"fd00::/8"}]}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("mixes IPv4 and IPv6"), "{err}");
+
+        let err = NetworkConfig::new()
+            .rule(100, |r| r.ipv6().from("10.0.0.0/8"))
+            .unwrap_err();
+        assert!(matches!(err, RuleParseError::FamilyMismatch { priority: 100 }));
+    }
+
+    #[test]
+    fn rule_builder_reports_bad_selector() {
+        let err = NetworkConfig::new()
+            .rule(100, |r| r.from("10.0.0.0").table(100))
+            .unwrap_err();
+        assert!(matches!(err, RuleParseError::MissingPrefix(_)), "{err}");
+    }
+
+    #[test]
+    fn tc_tree_roundtrips_with_tc_notation() {
+        let cfg = NetworkConfig::new()
+            .qdisc("eth0", |q| q.htb().default_class(0x20))
+            .class("eth0", TcHandle::new(1, 1), |c| {
+                c.htb(crate::Rate::mbit(100))
+            })
+            .class("eth0", TcHandle::new(1, 0x10), |c| {
+                c.parent(TcHandle::new(1, 1))
+                    .htb(crate::Rate::mbit(60))
+                    .ceil(crate::Rate::mbit(100))
+                    .prio(1)
+            })
+            .qdisc("eth0", |q| {
+                q.fq_codel()
+                    .parent_class(TcHandle::new(1, 0x10))
+                    .handle(TcHandle::major_only(0x10))
+            })
+            .filter("eth0", TcHandle::major_only(1), 10, |f| {
+                f.flower()
+                    .tcp()
+                    .dst("10.0.0.0/8")
+                    .dst_port(22)
+                    .classid(TcHandle::new(1, 0x10))
+            })
+            .expect("filter");
+        assert_roundtrips(&cfg);
+
+        let json = cfg.to_json_string().unwrap();
+        assert!(json.contains(r#""classid":"1:10""#), "{json}");
+        assert!(json.contains(r#""parent":{"class":"1:10"}"#), "{json}");
+        assert!(json.contains(r#""dst":"10.0.0.0/8""#), "{json}");
+
+        let back = NetworkConfig::from_json_str(&json).unwrap();
+        assert_eq!(back.classes(), cfg.classes());
+        assert_eq!(back.filters(), cfg.filters());
+        assert_eq!(back.qdiscs()[1].handle(), Some(TcHandle::major_only(0x10)));
+    }
+
+    #[test]
+    fn tc_tree_rejects_bad_handle() {
+        let err = NetworkConfig::from_json_str(
+            r#"{"classes":[{"dev":"eth0","classid":"1:zz","parent":"1:","class-type":{"htb":{"rate_bps":1000}}}]}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid TC handle: 1:zz"), "{err}");
+    }
+
     #[test]
     fn unit_link_type_keeps_bare_string_shape() {
         // Plan 189 shape: a unit link-type serializes as the bare
@@ -2334,25 +3889,107 @@ mod mac_serde {
         }
     }
 
+    /// The same string form for a mandatory `[u8; 6]`.
+    pub(super) mod required {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub(in super::super) fn serialize<S>(mac: &[u8; 6], s: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            super::serialize(&Some(*mac), s)
+        }
+
+        pub(in super::super) fn deserialize<'de, D>(d: D) -> Result<[u8; 6], D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            super::parse_mac(&String::deserialize(d)?).map_err(serde::de::Error::custom)
+        }
+    }
+
     fn parse_mac(s: &str) -> Result<[u8; 6], String> {
-        let mut out = [0u8; 6];
-        let mut n = 0;
-        for (i, part) in s.split(':').enumerate() {
-            if i >= 6 {
-                return Err(format!(
-                    "invalid MAC `{s}`: expected 6 colon-separated octets"
-                ));
+        crate::util::addr::parse_mac(s).map_err(|e| e.to_string())
+    }
+}
+
+/// [`TcHandle`] as its `tc(8)` string (`"1:10"`, `"root"`).
+#[cfg(feature = "serde")]
+mod handle_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::TcHandle;
+
+    pub(super) fn serialize<S>(handle: &TcHandle, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        s.collect_str(handle)
+    }
+
+    pub(super) fn deserialize<'de, D>(d: D) -> Result<TcHandle, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        String::deserialize(d)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+
+    /// The same string form for an optional handle.
+    pub(super) mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        use crate::TcHandle;
+
+        pub(in super::super) fn serialize<S>(
+            handle: &Option<TcHandle>,
+            s: S,
+        ) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            match handle {
+                Some(h) => s.collect_str(h),
+                None => s.serialize_none(),
             }
-            out[i] = u8::from_str_radix(part, 16)
-                .map_err(|_| format!("invalid MAC `{s}`: octet `{part}` is not hex"))?;
-            n = i + 1;
         }
-        if n != 6 {
-            return Err(format!(
-                "invalid MAC `{s}`: expected 6 colon-separated octets, got {n}"
-            ));
+
+        pub(in super::super) fn deserialize<'de, D>(d: D) -> Result<Option<TcHandle>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            Option::<String>::deserialize(d)?
+                .map(|s| s.parse().map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// An optional `(address, prefix)` selector as its CIDR string.
+#[cfg(feature = "serde")]
+mod cidr_serde {
+    use std::net::IpAddr;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub(super) fn serialize<S>(sel: &Option<(IpAddr, u8)>, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match sel {
+            Some((addr, len)) => s.collect_str(&format_args!("{addr}/{len}")),
+            None => s.serialize_none(),
         }
-        Ok(out)
+    }
+
+    pub(super) fn deserialize<'de, D>(d: D) -> Result<Option<(IpAddr, u8)>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(d)?
+            .map(|s| super::parse_selector(&s).map_err(serde::de::Error::custom))
+            .transpose()
     }
 }
 
@@ -2395,6 +4032,8 @@ mod schemars_tests {
         // routes describe the string wire shape, not parsed fields).
         assert!(d.contains_key("AddressRepr"), "schema must route addresses through AddressRepr");
         assert!(d.contains_key("RouteRepr"), "schema must route routes through RouteRepr");
+        assert!(d.contains_key("RuleRepr"), "schema must route rules through RuleRepr");
+        assert!(d.contains_key("DeclaredNamespace"), "schema must describe namespaces");
         // And the parsed structs must NOT leak into the schema.
         assert!(!d.contains_key("DeclaredAddress"), "DeclaredAddress parsed fields must not appear");
         assert!(!d.contains_key("DeclaredRoute"), "DeclaredRoute parsed fields must not appear");
@@ -0,0 +1,492 @@
+//! Continuous reconciliation.
+//!
+//! [`NetworkConfig::reconcile`] turns a one-shot apply into a control
+//! loop: each [`Reconciler::next_pass`] waits for its trigger,
+//! re-diffs the kernel against the desired state and re-applies any
+//! drift (an interface taken down, an address flushed, a route
+//! deleted by another tool). [`NetworkConfig::watch_drift`] runs the
+//! same loop without the apply step, for alerting with manual
+//! remediation.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use tokio::time::{Interval, MissedTickBehavior};
+use tokio_stream::{Stream, StreamExt};
+
+use super::{
+    apply::{ApplyOptions, ApplyResult, apply_diff},
+    diff::{ConfigDiff, DiffOptions, compute_diff_with_options},
+    types::NetworkConfig,
+};
+use crate::netlink::{
+    connection::{Connection, RtnetlinkGroup},
+    error::{Error, Result},
+    events::NetworkEvent,
+    protocol::Route,
+    stream::OwnedEventStream,
+};
+
+/// What wakes a [`Reconciler`] to check for drift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReconcileTrigger {
+    /// Re-check on a fixed period.
+    Interval(Duration),
+    /// Re-check when the kernel reports a link, address, route or
+    /// qdisc change (rule and neighbor changes too, when the config
+    /// declares those; sysctl writes raise no event). Events are
+    /// coalesced until `debounce` passes without a new one, so a
+    /// burst (an interface flap, `ip addr flush`) costs one pass.
+    Events {
+        /// Quiet period that ends a burst.
+        debounce: Duration,
+    },
+}
+
+impl ReconcileTrigger {
+    /// Event-driven trigger with a 200 ms debounce.
+    pub fn events() -> Self {
+        Self::Events {
+            debounce: Duration::from_millis(200),
+        }
+    }
+}
+
+/// A running reconcile loop. Created by [`NetworkConfig::reconcile`].
+///
+/// Drive it with [`Self::next_pass`]; drop it (or stop calling
+/// `next_pass`, e.g. from a `tokio::select!` shutdown branch) to stop.
+pub struct Reconciler<'a> {
+    config: &'a NetworkConfig,
+    conn: &'a Connection<Route>,
+    options: ApplyOptions,
+    wake: Wake,
+    first: bool,
+}
+
+enum Wake {
+    Interval(Interval),
+    Events {
+        events: Box<OwnedEventStream<Route>>,
+        debounce: Duration,
+    },
+}
+
+impl NetworkConfig {
+    /// Keep enforcing this configuration.
+    ///
+    /// The first [`Reconciler::next_pass`] applies immediately; each
+    /// later one waits for `trigger`, then diffs and applies again.
+    /// Passes use [`ApplyOptions::default`] unless overridden with
+    /// [`Reconciler::options`].
+    ///
+    /// With [`ReconcileTrigger::Events`], change notifications come
+    /// from a dedicated subscriber socket opened in the **calling
+    /// thread's** network namespace (the same approach as
+    /// `Diagnostics::watch`). A kernel event-queue overflow
+    /// (`ENOBUFS`) is treated as a trigger, since dropped events may
+    /// have hidden drift.
+    /// Drift inside declared [namespaces](NetworkConfig::namespace)
+    /// raises no event there, so it is only corrected on passes
+    /// triggered by something else — prefer
+    /// [`ReconcileTrigger::Interval`] for such configs.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::config::ReconcileTrigger;
+    ///
+    /// let mut reconciler = config.reconcile(&conn, ReconcileTrigger::events()).await?;
+    /// loop {
+    ///     let pass = reconciler.next_pass().await?;
+    ///     if pass.changes_made > 0 {
+    ///         println!("corrected drift:\n{}", pass.summary_text());
+    ///     }
+    /// }
+    /// ```
+    pub async fn reconcile<'a>(
+        &'a self,
+        conn: &'a Connection<Route>,
+        trigger: ReconcileTrigger,
+    ) -> Result<Reconciler<'a>> {
+        let wake = Wake::new(self, trigger).await?;
+        Ok(Reconciler {
+            config: self,
+            conn,
+            options: ApplyOptions::default(),
+            wake,
+            first: true,
+        })
+    }
+
+    /// Report drift from this configuration without correcting it.
+    ///
+    /// The returned [`DriftWatch`] is a [`Stream`] of [`DriftReport`]s.
+    /// The first poll diffs immediately and always yields a report,
+    /// empty ([`DriftReport::is_resolved`]) when the kernel already
+    /// matches. After that the kernel is re-diffed after every burst of
+    /// link, address, route or qdisc events (as with
+    /// [`ReconcileTrigger::events`]), and a report is yielded only when
+    /// the drift **changes** — a new divergence, a different one, or a
+    /// return to the declared state — so a steady deviation raises one
+    /// alert, not one per event. Use [`DriftWatch::trigger`] to
+    /// poll on an interval instead, and [`DriftWatch::diff_options`]
+    /// to include purge candidates.
+    ///
+    /// Nothing is applied. To fix a reported drift, pass the config to
+    /// [`NetworkConfig::apply`] or apply [`DriftReport::diff`] after
+    /// reviewing it.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use tokio_stream::StreamExt;
+    ///
+    /// let mut drift = config.watch_drift(&conn);
+    /// while let Some(report) = drift.next().await {
+    ///     let report = report?;
+    ///     if report.is_resolved() {
+    ///         println!("back in sync");
+    ///     } else {
+    ///         println!("drift detected:\n{}", report.diff);
+    ///     }
+    /// }
+    /// ```
+    pub fn watch_drift<'a>(&'a self, conn: &'a Connection<Route>) -> DriftWatch<'a> {
+        DriftWatch {
+            state: DriftState::Idle(Box::new(DriftChecker {
+                config: self,
+                conn,
+                trigger: ReconcileTrigger::events(),
+                diff_options: DiffOptions::default(),
+                wake: None,
+                last: None,
+            })),
+        }
+    }
+}
+
+/// Stream of drift reports. Created by [`NetworkConfig::watch_drift`].
+///
+/// Ends only on error: a failed diff or a closed event subscription
+/// is yielded as `Err`, after which the stream returns `None`.
+pub struct DriftWatch<'a> {
+    state: DriftState<'a>,
+}
+
+/// A change in how the kernel diverges from a [`NetworkConfig`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DriftReport {
+    /// What it would take to restore the declared state. Empty when
+    /// the drift has been resolved.
+    pub diff: ConfigDiff,
+}
+
+impl DriftReport {
+    /// The kernel matches the configuration: at the start of the watch,
+    /// or again after an earlier report.
+    pub fn is_resolved(&self) -> bool {
+        self.diff.is_empty()
+    }
+}
+
+type DriftFuture<'a> =
+    Pin<Box<dyn Future<Output = (Result<DriftReport>, Box<DriftChecker<'a>>)> + 'a>>;
+
+enum DriftState<'a> {
+    Idle(Box<DriftChecker<'a>>),
+    Checking(DriftFuture<'a>),
+    Done,
+}
+
+struct DriftChecker<'a> {
+    config: &'a NetworkConfig,
+    conn: &'a Connection<Route>,
+    trigger: ReconcileTrigger,
+    diff_options: DiffOptions,
+    /// Created on the first poll; `None` until then.
+    wake: Option<Wake>,
+    /// Rendered diff of the last report (empty = in sync); `None`
+    /// until the first report.
+    last: Option<String>,
+}
+
+impl DriftWatch<'_> {
+    /// What wakes the watch to re-diff. Defaults to
+    /// [`ReconcileTrigger::events`]. Only takes effect before the
+    /// first poll.
+    pub fn trigger(mut self, trigger: ReconcileTrigger) -> Self {
+        if let DriftState::Idle(checker) = &mut self.state {
+            checker.trigger = trigger;
+        }
+        self
+    }
+
+    /// Diff options for every check — e.g. [`DiffOptions::purge`] to
+    /// also report undeclared addresses and routes. Only takes effect
+    /// before the first poll.
+    pub fn diff_options(mut self, options: DiffOptions) -> Self {
+        if let DriftState::Idle(checker) = &mut self.state {
+            checker.diff_options = options;
+        }
+        self
+    }
+}
+
+impl<'a> Stream for DriftWatch<'a> {
+    type Item = Result<DriftReport>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match std::mem::replace(&mut self.state, DriftState::Done) {
+                DriftState::Idle(checker) => {
+                    self.state = DriftState::Checking(Box::pin(checker.next_change()));
+                }
+                DriftState::Checking(mut fut) => match fut.as_mut().poll(cx) {
+                    Poll::Ready((Ok(report), checker)) => {
+                        self.state = DriftState::Idle(checker);
+                        return Poll::Ready(Some(Ok(report)));
+                    }
+                    Poll::Ready((Err(e), _)) => return Poll::Ready(Some(Err(e))),
+                    Poll::Pending => {
+                        self.state = DriftState::Checking(fut);
+                        return Poll::Pending;
+                    }
+                },
+                DriftState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<'a> DriftChecker<'a> {
+    /// Diff until the rendered drift differs from the last report, or
+    /// once if there hasn't been one.
+    async fn next_change(mut self: Box<Self>) -> (Result<DriftReport>, Box<Self>) {
+        let result = self.check().await;
+        (result, self)
+    }
+
+    async fn check(&mut self) -> Result<DriftReport> {
+        loop {
+            match &mut self.wake {
+                Some(wake) => wake.wait().await?,
+                None => self.wake = Some(Wake::new(self.config, self.trigger).await?),
+            }
+            let diff =
+                compute_diff_with_options(self.config, self.conn, &self.diff_options).await?;
+            let rendered = if diff.is_empty() {
+                String::new()
+            } else {
+                diff.to_string()
+            };
+            if self.last.as_ref() != Some(&rendered) {
+                self.last = Some(rendered);
+                return Ok(DriftReport { diff });
+            }
+        }
+    }
+}
+
+impl Reconciler<'_> {
+    /// Apply options for every pass (purge, rollback, …).
+    /// `dry_run` turns the loop into a drift monitor.
+    pub fn options(mut self, options: ApplyOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Wait for the trigger (except on the first call), then diff and
+    /// apply.
+    ///
+    /// A pass with `changes_made == 0` found no drift — expected after
+    /// events caused by the previous pass's own changes.
+    pub async fn next_pass(&mut self) -> Result<ApplyResult> {
+        if self.first {
+            self.first = false;
+        } else {
+            self.wake.wait().await?;
+        }
+        let diff_opts = DiffOptions::default().purge(self.options.purge);
+        let diff = compute_diff_with_options(self.config, self.conn, &diff_opts).await?;
+        apply_diff(&diff, self.conn, self.options.clone()).await
+    }
+}
+
+impl Wake {
+    async fn new(config: &NetworkConfig, trigger: ReconcileTrigger) -> Result<Self> {
+        Ok(match trigger {
+            ReconcileTrigger::Interval(period) => {
+                if period.is_zero() {
+                    return Err(Error::InvalidMessage(
+                        "reconcile: interval must be non-zero".into(),
+                    ));
+                }
+                // `interval_at` skips the immediate first tick — the
+                // first pass doesn't wait anyway.
+                let mut interval =
+                    tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                Wake::Interval(interval)
+            }
+            ReconcileTrigger::Events { debounce } => {
+                let events = Connection::<Route>::new()?;
+                let mut groups = vec![
+                    RtnetlinkGroup::Link,
+                    RtnetlinkGroup::Ipv4Addr,
+                    RtnetlinkGroup::Ipv6Addr,
+                    RtnetlinkGroup::Ipv4Route,
+                    RtnetlinkGroup::Ipv6Route,
+                    RtnetlinkGroup::Tc,
+                ];
+                // Rule and (chatty) neighbor events only when the
+                // config manages those resources.
+                if !config.rules.is_empty() {
+                    groups.extend([RtnetlinkGroup::Ipv4Rule, RtnetlinkGroup::Ipv6Rule]);
+                }
+                if !config.neighbors.is_empty() {
+                    groups.push(RtnetlinkGroup::Neigh);
+                }
+                events.subscribe(&groups)?;
+                Wake::Events {
+                    events: Box::new(events.into_events().await),
+                    debounce,
+                }
+            }
+        })
+    }
+
+    async fn wait(&mut self) -> Result<()> {
+        match self {
+            Wake::Interval(interval) => {
+                interval.tick().await;
+                Ok(())
+            }
+            Wake::Events { events, debounce } => {
+                // Block until the first relevant event …
+                loop {
+                    match events.next().await {
+                        Some(Ok(ev)) if is_relevant(&ev) => break,
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) if e.is_no_buffer_space() => break,
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(events_ended()),
+                    }
+                }
+                // … then swallow the rest of the burst.
+                while let Ok(item) = tokio::time::timeout(*debounce, events.next()).await {
+                    match item {
+                        Some(Ok(_)) => {}
+                        Some(Err(e)) if e.is_no_buffer_space() => {}
+                        Some(Err(e)) => return Err(e),
+                        None => return Err(events_ended()),
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn events_ended() -> Error {
+    Error::InvalidMessage("reconcile: event subscription closed".into())
+}
+
+/// Events that can signal drift in something `NetworkConfig` manages.
+fn is_relevant(event: &NetworkEvent) -> bool {
+    matches!(
+        event,
+        NetworkEvent::NewLink(_)
+            | NetworkEvent::DelLink(_)
+            | NetworkEvent::NewAddress(_)
+            | NetworkEvent::DelAddress(_)
+            | NetworkEvent::NewRoute(_)
+            | NetworkEvent::DelRoute(_)
+            | NetworkEvent::NewQdisc(_)
+            | NetworkEvent::DelQdisc(_)
+            | NetworkEvent::NewRule(_)
+            | NetworkEvent::DelRule(_)
+            | NetworkEvent::NewNeighbor(_)
+            | NetworkEvent::DelNeighbor(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_event_trigger_debounces() {
+        assert_eq!(
+            ReconcileTrigger::events(),
+            ReconcileTrigger::Events {
+                debounce: Duration::from_millis(200)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn zero_interval_is_rejected() {
+        let Ok(conn) = Connection::<Route>::new() else {
+            return;
+        };
+        let config = NetworkConfig::new();
+        let err = config
+            .reconcile(&conn, ReconcileTrigger::Interval(Duration::ZERO))
+            .await
+            .err()
+            .expect("zero interval must be rejected");
+        assert!(err.to_string().contains("non-zero"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn drift_watch_reports_missing_link_then_ends_on_error() {
+        let Ok(conn) = Connection::<Route>::new() else {
+            return;
+        };
+        let config = NetworkConfig::new().link("nlink-drift0", |l| l.dummy());
+        let mut drift = config
+            .watch_drift(&conn)
+            .trigger(ReconcileTrigger::Interval(Duration::from_millis(10)));
+        let report = drift
+            .next()
+            .await
+            .expect("stream ended")
+            .expect("diff failed");
+        assert!(!report.is_resolved());
+        assert_eq!(report.diff.links_to_add.len(), 1);
+
+        let mut drift = config
+            .watch_drift(&conn)
+            .trigger(ReconcileTrigger::Interval(Duration::ZERO));
+        assert!(matches!(drift.next().await, Some(Err(_))));
+        assert!(drift.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn drift_watch_reports_in_sync_start() {
+        let Ok(conn) = Connection::<Route>::new() else {
+            return;
+        };
+        let config = NetworkConfig::new();
+        let mut drift = config
+            .watch_drift(&conn)
+            .trigger(ReconcileTrigger::Interval(Duration::from_millis(10)));
+        let report = drift
+            .next()
+            .await
+            .expect("stream ended")
+            .expect("diff failed");
+        assert!(report.is_resolved());
+
+        // Still in sync: no further report within a few intervals.
+        let next = tokio::time::timeout(Duration::from_millis(50), drift.next()).await;
+        assert!(next.is_err(), "unchanged state must not be reported again");
+    }
+}
@@ -126,6 +126,11 @@ pub struct Connection<P: ProtocolState> {
     /// consults the connection's dispatcher when it sees ENOBUFS
     /// so the marker reaches the right place (Plan 234 §4).
     dispatcher: Dispatcher,
+    /// Interface name → ifindex cache behind
+    /// [`Connection::resolve_interface`], filled by `RTM_GETLINK` lookups
+    /// on this socket and so always describing this connection's netns.
+    /// Only route connections use it.
+    ifindex_cache: std::sync::RwLock<std::collections::HashMap<String, u32>>,
 }
 
 impl<P: ProtocolState> Drop for Connection<P> {
@@ -240,6 +245,7 @@ where
             timeout: Some(DEFAULT_OPERATION_TIMEOUT),
             request_lock: std::sync::Arc::new(tokio::sync::Mutex::new(())),
             dispatcher,
+            ifindex_cache: Default::default(),
         })
     }
 
@@ -274,6 +280,7 @@ where
             timeout: Some(DEFAULT_OPERATION_TIMEOUT),
             request_lock: std::sync::Arc::new(tokio::sync::Mutex::new(())),
             dispatcher,
+            ifindex_cache: Default::default(),
         })
     }
 
@@ -306,6 +313,7 @@ where
             timeout: Some(DEFAULT_OPERATION_TIMEOUT),
             request_lock: std::sync::Arc::new(tokio::sync::Mutex::new(())),
             dispatcher,
+            ifindex_cache: Default::default(),
         })
     }
 }
@@ -366,6 +374,75 @@ impl<P: ProtocolState> Connection<P> {
         &self.socket
     }
 
+    /// Open the network namespace this connection's socket lives in.
+    ///
+    /// Uses `SIOCGSKNS`, so it is right for connections made with
+    /// [`new_in_namespace`](Self::new_in_namespace) too.
+    pub(crate) fn namespace_fd(&self) -> Result<std::os::fd::OwnedFd> {
+        use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+        // SAFETY: a plain ioctl on a socket fd we hold a borrow of.
+        let fd = unsafe { libc::ioctl(self.socket.as_raw_fd(), libc::SIOCGSKNS as _) };
+        if fd < 0 {
+            return Err(Error::Io(std::io::Error::last_os_error()));
+        }
+        // SAFETY: the ioctl returned a fresh fd that nothing else owns.
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+
+    /// Identify the network namespace this connection's socket is bound to.
+    ///
+    /// Fixed when the connection is created, so it stays the same even if
+    /// the calling thread later moves to another namespace.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::{Connection, Route, namespace};
+    ///
+    /// let conn: Connection<Route> = namespace::connection_for("myns")?;
+    /// println!("bound to {}", conn.current_namespace()?); // net:[4026532303]
+    /// ```
+    pub fn current_namespace(&self) -> Result<super::namespace::NamespaceId> {
+        let fd = std::fs::File::from(self.namespace_fd()?);
+        super::namespace::NamespaceId::of_file(&fd)
+    }
+
+    /// Check that the calling thread is in this connection's namespace.
+    ///
+    /// Interface names resolved outside netlink, like
+    /// [`crate::util::get_ifindex`], describe the thread's namespace.
+    /// Sending such an index over a connection bound elsewhere targets
+    /// whatever interface happens to share the number. Call this before
+    /// mixing the two; connection methods taking an [`InterfaceRef`]
+    /// resolve names over netlink and need no check, and actions built by
+    /// [`MirredAction::parse_params`](crate::netlink::action::MirredAction::parse_params)
+    /// are checked when sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NamespaceMismatch`] when the namespaces differ.
+    pub fn ensure_thread_namespace(&self) -> Result<()> {
+        self.check_resolved_namespace(Some(super::namespace::NamespaceId::of_thread()?))
+    }
+
+    /// Fail with [`Error::NamespaceMismatch`] if `resolved`, the namespace
+    /// an interface name was looked up in outside netlink, isn't this
+    /// connection's.
+    pub(crate) fn check_resolved_namespace(
+        &self,
+        resolved: Option<super::namespace::NamespaceId>,
+    ) -> Result<()> {
+        let Some(thread) = resolved else {
+            return Ok(());
+        };
+        let connection = self.current_namespace()?;
+        if connection != thread {
+            return Err(Error::NamespaceMismatch { connection, thread });
+        }
+        Ok(())
+    }
+
     /// Opt this connection into **dispatcher mode** (#134).
     ///
     /// In dispatcher mode, request/ack/dump operations route through a
@@ -656,6 +733,7 @@ impl<P: ProtocolState> Connection<P> {
             request_lock: std::sync::Arc::new(tokio::sync::Mutex::new(())),
             dispatcher,
             dispatcher_mode: false,
+            ifindex_cache: Default::default(),
         }
     }
 
@@ -1225,6 +1303,22 @@ impl Connection<Route> {
         ])
     }
 
+    /// Open a second connection in this connection's namespace, to
+    /// listen for events while this one keeps sending requests.
+    ///
+    /// Finding the namespace needs `CAP_NET_ADMIN`. Without it this
+    /// falls back to the calling thread's namespace, which is where an
+    /// unprivileged caller's sockets live in practice.
+    pub(crate) fn event_connection(&self) -> Result<Self> {
+        use std::os::fd::AsRawFd;
+
+        match self.namespace_fd() {
+            Ok(ns) => Self::new_in_namespace(ns.as_raw_fd()),
+            Err(Error::Io(e)) if e.raw_os_error() == Some(libc::EPERM) => Self::new(),
+            Err(e) => Err(e),
+        }
+    }
+
     // ========================================================================
     // Strongly-typed API for Route protocol
     // ========================================================================
@@ -1255,6 +1349,17 @@ impl Connection<Route> {
         T::write_dump_header(&mut header_buf);
         builder.append_bytes(&header_buf);
 
+        self.dump_typed_request(builder).await
+    }
+
+    /// Send a prepared dump request and parse every reply as `T`.
+    ///
+    /// For dumps whose request carries more than the default header,
+    /// such as kernel-side filter attributes.
+    pub(crate) async fn dump_typed_request<T: FromNetlink>(
+        &self,
+        builder: MessageBuilder,
+    ) -> Result<Vec<T>> {
         let responses = self.send_dump(builder).await?;
 
         let mut parsed = Vec::with_capacity(responses.len());
@@ -1341,6 +1446,23 @@ impl Connection<Route> {
     pub fn batch(&self) -> super::batch::Batch<'_> {
         super::batch::Batch::new(self)
     }
+
+    /// Start a transaction: changes applied one at a time and undone
+    /// together, newest first, if a step fails or on
+    /// [`rollback`](super::transaction::Transaction::rollback).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let mut tx = conn.transaction();
+    /// tx.add_link(VethLink::new("veth0", "veth1")).await?;
+    /// tx.add_address(Ipv4Address::new("veth0", "10.0.0.1".parse()?, 24)).await?;
+    /// tx.add_route(Ipv4Route::new("10.1.0.0", 16).dev("veth0")).await?;
+    /// tx.commit();
+    /// ```
+    pub fn transaction(&self) -> super::transaction::Transaction<'_> {
+        super::transaction::Transaction::new(self)
+    }
 }
 
 // ============================================================================
@@ -1472,6 +1594,10 @@ impl Connection<Route> {
     /// `Err(InterfaceNotFound)` if the interface is removed during
     /// the wait.
     ///
+    /// [`wait_for_link_up`](Self::wait_for_link_up) is the event-driven
+    /// counterpart. It wakes as soon as the kernel reports the change and
+    /// also accepts an interface that does not exist yet.
+    ///
     /// # Namespace safety
     ///
     /// Takes `impl Into<InterfaceRef>`. With a `Name` variant the
@@ -1581,6 +1707,12 @@ impl Connection<Route> {
     /// - If the reference is already an index, returns it directly.
     /// - If the reference is a name, queries the kernel via netlink.
     ///
+    /// Resolved names are cached on the connection. A cached index is
+    /// checked before use with a single `RTM_GETLINK` for that index
+    /// rather than a full link dump; if the interface is gone or now
+    /// carries another name (deleted, recreated or renamed by another
+    /// process), the name is looked up afresh.
+    ///
     /// # Errors
     ///
     /// Returns [`Error::InterfaceNotFound`] if the interface name doesn't exist.
@@ -1604,15 +1736,99 @@ impl Connection<Route> {
         match iface {
             InterfaceRef::Index(idx) => Ok(*idx),
             InterfaceRef::Name(name) => {
+                if let Some(idx) = self.cached_ifindex(name).await? {
+                    return Ok(idx);
+                }
                 let link = self
                     .get_link_by_name(name)
                     .await?
                     .ok_or_else(|| Error::interface_not_found(name))?;
-                Ok(link.ifindex())
+                let idx = link.ifindex();
+                self.ifindex_cache_mut().insert(name.clone(), idx);
+                Ok(idx)
+            }
+        }
+    }
+
+    /// Forget every cached interface name.
+    ///
+    /// [`resolve_interface`](Self::resolve_interface) caches the index of
+    /// each name it looks up and checks the entry against the kernel
+    /// before using it, so this is never needed for correctness. It only
+    /// releases the memory held by names that are no longer used.
+    pub fn clear_interface_cache(&self) {
+        self.ifindex_cache_mut().clear();
+    }
+
+    /// Return the cached index of `name` if the kernel still reports that
+    /// index under that name. A stale entry is dropped.
+    async fn cached_ifindex(&self, name: &str) -> Result<Option<u32>> {
+        let Some(idx) = self.ifindex_cache().get(name).copied() else {
+            return Ok(None);
+        };
+
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_GETLINK, NLM_F_REQUEST);
+        builder.append(&IfInfoMsg::new().with_index(idx as i32));
+        let current = match self.send_request(builder).await {
+            Ok(response) if response.len() >= NLMSG_HDRLEN => {
+                LinkMessage::from_bytes(&response[NLMSG_HDRLEN..]).ok()
+            }
+            Ok(_) => None,
+            Err(e) if e.is_not_found() => None,
+            Err(e) => return Err(e),
+        };
+
+        if current.is_some_and(|link| link.name() == Some(name)) {
+            return Ok(Some(idx));
+        }
+        self.forget_ifname(name);
+        Ok(None)
+    }
+
+    /// Resolve `iface` and run `op` on its index. If the index came from
+    /// the cache and the kernel no longer knows it, the interface was
+    /// deleted between the cache check and `op`: look the name up afresh
+    /// and run `op` once more.
+    pub(crate) async fn with_interface<T, F, Fut>(&self, iface: &InterfaceRef, op: F) -> Result<T>
+    where
+        F: Fn(u32) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let cached = iface
+            .as_name()
+            .is_some_and(|name| self.ifindex_cache().contains_key(name));
+        let ifindex = self.resolve_interface(iface).await?;
+        match op(ifindex).await {
+            Err(e) if cached && e.is_not_found() => {
+                self.forget_ifindex(ifindex);
+                op(self.resolve_interface(iface).await?).await
             }
+            other => other,
         }
     }
 
+    /// Drop cached names pointing at `ifindex`, after an operation that
+    /// deleted, renamed or moved it.
+    pub(crate) fn forget_ifindex(&self, ifindex: u32) {
+        self.ifindex_cache_mut().retain(|_, idx| *idx != ifindex);
+    }
+
+    /// Drop the cached index of `name`, after an operation that created
+    /// an interface with it.
+    pub(crate) fn forget_ifname(&self, name: &str) {
+        self.ifindex_cache_mut().remove(name);
+    }
+
+    fn ifindex_cache(&self) -> std::sync::RwLockReadGuard<'_, std::collections::HashMap<String, u32>> {
+        self.ifindex_cache.read().unwrap_or_else(|p| p.into_inner())
+    }
+
+    fn ifindex_cache_mut(
+        &self,
+    ) -> std::sync::RwLockWriteGuard<'_, std::collections::HashMap<String, u32>> {
+        self.ifindex_cache.write().unwrap_or_else(|p| p.into_inner())
+    }
+
     /// Resolve an optional interface reference.
     ///
     /// Returns `None` if the input is `None`, otherwise resolves the reference.
@@ -1648,6 +1864,16 @@ impl Connection<Route> {
             .collect())
     }
 
+    /// Build a cached, bidirectional [`IfIndexResolver`](super::IfIndexResolver)
+    /// from a link dump.
+    ///
+    /// Unlike [`Connection::get_interface_names()`], the resolver can be
+    /// shared across tasks and kept current from a link event stream with
+    /// [`IfIndexResolver::track`](super::IfIndexResolver::track).
+    pub async fn interface_resolver(&self) -> Result<super::IfIndexResolver> {
+        super::IfIndexResolver::load(self).await
+    }
+
     /// Get interface name by index.
     ///
     /// This is a convenience method for getting a single interface name.
@@ -1772,14 +1998,22 @@ impl Connection<Route> {
         &self,
         iface: impl Into<InterfaceRef>,
     ) -> Result<Vec<AddressMessage>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_addresses_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.get_addresses_by_index(ifindex)
+        })
+        .await
     }
 
     /// Get IP addresses for a specific interface by index.
+    ///
+    /// With [strict checking](Self::enable_strict_checking) on, the
+    /// kernel only dumps this interface's addresses.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_addresses_by_index"))]
     pub async fn get_addresses_by_index(&self, ifindex: u32) -> Result<Vec<AddressMessage>> {
-        let addresses = self.get_addresses().await?;
+        let mut builder = dump_request(NlMsgType::RTM_GETADDR);
+        builder.append(&super::types::addr::IfAddrMsg::new().with_index(ifindex));
+        let addresses: Vec<AddressMessage> = self.dump_typed_request(builder).await?;
+        // Kernels without strict checking ignore ifa_index.
         Ok(addresses
             .into_iter()
             .filter(|a| a.ifindex() == ifindex)
@@ -1825,13 +2059,20 @@ impl Connection<Route> {
     }
 
     /// Get routes for a specific table.
+    ///
+    /// Routes in other tables are dropped as the dump streams in, so
+    /// only this table is ever buffered.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_routes_for_table"))]
     pub async fn get_routes_for_table(&self, table_id: u32) -> Result<Vec<RouteMessage>> {
-        let routes = self.get_routes().await?;
-        Ok(routes
-            .into_iter()
-            .filter(|r| r.table_id() == table_id)
-            .collect())
+        use tokio_stream::StreamExt;
+
+        let selector = crate::netlink::route::RouteSelector::new().table(table_id);
+        let mut routes = std::pin::pin!(self.stream_routes_matching(selector).await?);
+        let mut out = Vec::new();
+        while let Some(route) = routes.next().await {
+            out.push(route?);
+        }
+        Ok(out)
     }
 
     /// Get a specific IPv4 route by destination and prefix length.
@@ -1959,8 +2200,10 @@ impl Connection<Route> {
         &self,
         iface: impl Into<InterfaceRef>,
     ) -> Result<Vec<NeighborMessage>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_neighbors_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.get_neighbors_by_index(ifindex)
+        })
+        .await
     }
 
     /// Get all routing rules.
@@ -1995,6 +2238,26 @@ impl Connection<Route> {
         Ok(rules.into_iter().filter(|r| r.family() == raw).collect())
     }
 
+    /// Get the routing rules selected by `filter`.
+    ///
+    /// The selectors are matched against a full rule dump.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::rule::RuleFilter;
+    ///
+    /// let ours = conn.get_rules_filtered(&RuleFilter::new().protocol(RTPROT_MYD)).await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_rules_filtered"))]
+    pub async fn get_rules_filtered(
+        &self,
+        filter: &super::rule::RuleFilter,
+    ) -> Result<Vec<RuleMessage>> {
+        let rules = self.get_rules().await?;
+        Ok(rules.into_iter().filter(|r| filter.matches(r)).collect())
+    }
+
     /// Get IPv4 routing rules.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_rules_v4"))]
     pub async fn get_rules_v4(&self) -> Result<Vec<RuleMessage>> {
@@ -2062,25 +2325,36 @@ impl Connection<Route> {
         self.del_rule(rule).await
     }
 
-    /// Flush all non-default routing rules for a family.
+    /// Flush the non-default routing rules selected by `filter`.
     ///
-    /// This deletes all rules except the default ones (priority 0, 32766, 32767).
+    /// The default rules (priority 0, 32766 and 32767) are never removed,
+    /// whatever the filter says. Passing an [`AddressFamily`] flushes that
+    /// family; a [`RuleFilter`](super::rule::RuleFilter) can narrow this
+    /// to one protocol, priority block or table, which is how a daemon
+    /// clears the rules it owns without touching anyone else's.
+    ///
+    /// Each rule is deleted with its full selector set, so rules sharing a
+    /// priority with a matched one survive. Returns the number of rules
+    /// removed; rules that vanish between the dump and the delete are
+    /// skipped.
     ///
     /// (Renamed from `flush_rules_typed` in 0.21 — the raw-`u8` sibling
     /// was removed in the same release.)
     #[tracing::instrument(level = "debug", skip_all, fields(method = "flush_rules"))]
-    pub async fn flush_rules(&self, family: AddressFamily) -> Result<()> {
-        let rules = self.get_rules_for_family(family).await?;
-
-        for rule in rules {
-            // Skip default rules
-            if rule.is_default() {
-                continue;
+    pub async fn flush_rules(&self, filter: impl Into<super::rule::RuleFilter>) -> Result<usize> {
+        let rules = self.get_rules_filtered(&filter.into()).await?;
+        let mut removed = 0;
+        for rule in rules.iter().filter(|r| !r.is_default()) {
+            match self
+                .del_rule(super::rule::RuleBuilder::from_message(rule))
+                .await
+            {
+                Ok(()) => removed += 1,
+                Err(e) if e.is_not_found() => {}
+                Err(e) => return Err(e),
             }
-            let _ = self.del_rule_by_priority(family, rule.priority()).await;
         }
-
-        Ok(())
+        Ok(removed)
     }
 
     /// Get all qdiscs.
@@ -2142,8 +2416,8 @@ impl Connection<Route> {
         &self,
         iface: impl Into<InterfaceRef>,
     ) -> Result<Vec<TcMessage>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_qdiscs_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| self.get_qdiscs_by_index(ifindex))
+            .await
     }
 
     /// Get qdiscs for a specific interface by index.
@@ -2157,9 +2431,16 @@ impl Connection<Route> {
     }
 
     /// Get all TC classes.
+    ///
+    /// Class dumps are per-device in the kernel, so this dumps the
+    /// classes of every link in turn.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_classes"))]
     pub async fn get_classes(&self) -> Result<Vec<TcMessage>> {
-        self.dump_typed(NlMsgType::RTM_GETTCLASS).await
+        let mut classes = Vec::new();
+        for link in self.get_links().await? {
+            classes.extend(self.get_classes_by_index(link.ifindex()).await?);
+        }
+        Ok(classes)
     }
 
     /// Get TC classes for a specific interface.
@@ -2170,24 +2451,28 @@ impl Connection<Route> {
         &self,
         iface: impl Into<InterfaceRef>,
     ) -> Result<Vec<TcMessage>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_classes_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| self.get_classes_by_index(ifindex))
+            .await
     }
 
     /// Get TC classes for a specific interface by index.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_classes_by_index"))]
     pub async fn get_classes_by_index(&self, ifindex: u32) -> Result<Vec<TcMessage>> {
-        let classes = self.get_classes().await?;
-        Ok(classes
-            .into_iter()
-            .filter(|c| c.ifindex() == ifindex)
-            .collect())
+        self.dump_tc_by_index(NlMsgType::RTM_GETTCLASS, ifindex, 0)
+            .await
     }
 
     /// Get all TC filters.
+    ///
+    /// Filter dumps are per-device (and per-block) in the kernel, so
+    /// this walks every link; see [`Self::get_filters_by_index`].
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_filters"))]
     pub async fn get_filters(&self) -> Result<Vec<TcMessage>> {
-        self.dump_typed(NlMsgType::RTM_GETTFILTER).await
+        let mut filters = Vec::new();
+        for link in self.get_links().await? {
+            filters.extend(self.get_filters_by_index(link.ifindex()).await?);
+        }
+        Ok(filters)
     }
 
     /// Get TC filters for a specific interface.
@@ -2198,18 +2483,77 @@ impl Connection<Route> {
         &self,
         iface: impl Into<InterfaceRef>,
     ) -> Result<Vec<TcMessage>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_filters_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| self.get_filters_by_index(ifindex))
+            .await
     }
 
     /// Get TC filters for a specific interface by index.
+    ///
+    /// The kernel dumps one filter block per request, so this issues a
+    /// dump for every block on the device: each qdisc, each class, and
+    /// both directions of a `clsact`.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_filters_by_index"))]
     pub async fn get_filters_by_index(&self, ifindex: u32) -> Result<Vec<TcMessage>> {
-        let filters = self.get_filters().await?;
-        Ok(filters
-            .into_iter()
-            .filter(|f| f.ifindex() == ifindex)
-            .collect())
+        let mut parents = Vec::new();
+        for qdisc in self.get_qdiscs_by_index(ifindex).await? {
+            match qdisc.kind() {
+                Some("ingress") => parents.push(TcHandle::INGRESS.as_raw()),
+                // ffff:fff2 / ffff:fff3 (TC_H_MIN_INGRESS / _EGRESS)
+                Some("clsact") => {
+                    parents.push(TcHandle::new(0xffff, 0xfff2).as_raw());
+                    parents.push(TcHandle::new(0xffff, 0xfff3).as_raw());
+                }
+                _ if qdisc.handle_raw() != 0 => parents.push(qdisc.handle_raw()),
+                _ => {}
+            }
+        }
+        for class in self.get_classes_by_index(ifindex).await? {
+            parents.push(class.handle_raw());
+        }
+        parents.dedup();
+
+        let mut filters = Vec::new();
+        for parent in parents {
+            filters.extend(
+                self.dump_tc_by_index(NlMsgType::RTM_GETTFILTER, ifindex, parent)
+                    .await?,
+            );
+        }
+        Ok(filters)
+    }
+
+    /// Dump classes or filters of one device. Unlike qdisc dumps, these
+    /// are scoped to `tcm_ifindex` (and, for filters, `tcm_parent`): a
+    /// request for ifindex 0 yields nothing rather than every device.
+    async fn dump_tc_by_index(
+        &self,
+        msg_type: u16,
+        ifindex: u32,
+        parent: u32,
+    ) -> Result<Vec<TcMessage>> {
+        use super::types::tc::TcMsg;
+
+        let tcmsg = TcMsg::new()
+            .with_ifindex(ifindex as i32)
+            .with_parent(parent);
+
+        let mut builder = dump_request(msg_type);
+        builder.append(&tcmsg);
+
+        let responses = self.send_dump(builder).await?;
+        let mut parsed = Vec::with_capacity(responses.len());
+        for response in responses {
+            if response.len() < NLMSG_HDRLEN {
+                continue;
+            }
+            if let Ok(mut msg) = TcMessage::from_bytes(&response[NLMSG_HDRLEN..]) {
+                if let Ok(header) = NlMsgHdr::from_bytes(&response[..NLMSG_HDRLEN]) {
+                    msg.set_msg_type(header.nlmsg_type);
+                }
+                parsed.push(msg);
+            }
+        }
+        Ok(parsed)
     }
 
     /// Stream a qdisc dump frame-by-frame.
@@ -2280,8 +2624,10 @@ impl Connection<Route> {
         iface: impl Into<InterfaceRef>,
         parent: TcHandle,
     ) -> Result<Vec<TcMessage>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_filters_by_parent_index(ifindex, parent).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.get_filters_by_parent_index(ifindex, parent)
+        })
+        .await
     }
 
     /// Get TC filters by interface index, filtered by parent handle.
@@ -2323,8 +2669,10 @@ impl Connection<Route> {
         ifname: impl Into<InterfaceRef>,
         parent: TcHandle,
     ) -> Result<Vec<u32>> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.get_tc_chains_by_index(ifindex, parent).await
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.get_tc_chains_by_index(ifindex, parent)
+        })
+        .await
     }
 
     /// Get all TC filter chains for an interface by index.
@@ -2375,8 +2723,10 @@ impl Connection<Route> {
         parent: TcHandle,
         chain: u32,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.add_tc_chain_by_index(ifindex, parent, chain).await
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.add_tc_chain_by_index(ifindex, parent, chain)
+        })
+        .await
     }
 
     /// Add a TC filter chain by interface index.
@@ -2418,8 +2768,10 @@ impl Connection<Route> {
         parent: TcHandle,
         chain: u32,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.del_tc_chain_by_index(ifindex, parent, chain).await
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.del_tc_chain_by_index(ifindex, parent, chain)
+        })
+        .await
     }
 
     /// Delete a TC filter chain by interface index.
@@ -2463,8 +2815,10 @@ impl Connection<Route> {
         &self,
         iface: impl Into<InterfaceRef>,
     ) -> Result<Option<TcMessage>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_root_qdisc_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.get_root_qdisc_by_index(ifindex)
+        })
+        .await
     }
 
     /// Get the root qdisc for an interface by index.
@@ -2553,8 +2907,8 @@ impl Connection<Route> {
         &self,
         iface: impl Into<InterfaceRef>,
     ) -> Result<Option<super::tc_options::NetemOptions>> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.get_netem_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| self.get_netem_by_index(ifindex))
+            .await
     }
 
     /// Get netem options for an interface by index.
@@ -2578,7 +2932,7 @@ impl Connection<Route> {
 // Link State Management
 // ============================================================================
 
-use super::types::link::{IfInfoMsg, iff};
+use super::types::link::{IfInfoMsg, LinkFlags, iff};
 
 impl Connection<Route> {
     /// Bring a network interface up.
@@ -2593,8 +2947,8 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_up"))]
     pub async fn set_link_up(&self, iface: impl Into<InterfaceRef>) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_up_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| self.set_link_up_by_index(ifindex))
+            .await
     }
 
     /// Bring a network interface up by index.
@@ -2615,8 +2969,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_down"))]
     pub async fn set_link_down(&self, iface: impl Into<InterfaceRef>) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_down_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_down_by_index(ifindex)
+        })
+        .await
     }
 
     /// Bring a network interface down by index.
@@ -2645,8 +3001,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_state"))]
     pub async fn set_link_state(&self, iface: impl Into<InterfaceRef>, up: bool) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_state_by_index(ifindex, up).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_state_by_index(ifindex, up)
+        })
+        .await
     }
 
     /// Set the state of a network interface by index.
@@ -2677,6 +3035,199 @@ impl Connection<Route> {
         })
     }
 
+    /// Bring several interfaces up in one batched send.
+    ///
+    /// Names are resolved with a single link dump, then every
+    /// `RTM_SETLINK` goes out through [`batch`](Self::batch). The results
+    /// are in the order of `names`; an unknown name yields
+    /// [`Error::InterfaceNotFound`] for that entry without affecting the
+    /// others. Only transport failures return `Err`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let results = conn.set_links_up(&["veth0", "veth1", "veth2"]).await?;
+    /// for (i, err) in results.errors() {
+    ///     eprintln!("link {i}: {err}");
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_links_up", count = names.len()))]
+    pub async fn set_links_up<S: AsRef<str>>(
+        &self,
+        names: &[S],
+    ) -> Result<super::batch::BatchResults> {
+        self.set_links_state(names, true).await
+    }
+
+    /// Bring several interfaces down in one batched send.
+    ///
+    /// See [`set_links_up`](Self::set_links_up) for how names and results
+    /// are handled.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_links_down", count = names.len()))]
+    pub async fn set_links_down<S: AsRef<str>>(
+        &self,
+        names: &[S],
+    ) -> Result<super::batch::BatchResults> {
+        self.set_links_state(names, false).await
+    }
+
+    async fn set_links_state<S: AsRef<str>>(
+        &self,
+        names: &[S],
+        up: bool,
+    ) -> Result<super::batch::BatchResults> {
+        let indices: std::collections::HashMap<String, u32> = if names.is_empty() {
+            Default::default()
+        } else {
+            self.get_links()
+                .await?
+                .iter()
+                .filter_map(|link| Some((link.name()?.to_string(), link.ifindex())))
+                .collect()
+        };
+        let mut batch = self.batch();
+        for name in names {
+            let name = name.as_ref();
+            batch = match indices.get(name) {
+                Some(&ifindex) => batch.set_link_state_by_index(ifindex, up),
+                None => batch.missing_interface(name),
+            };
+        }
+        batch.execute().await
+    }
+
+    /// Set or clear interface flags.
+    ///
+    /// Only the flags in `mask` are touched: each is set if it is also in
+    /// `flags` and cleared otherwise, and every other flag keeps its current
+    /// value. Accepts either an interface name or index via [`InterfaceRef`].
+    ///
+    /// An empty `mask` is a no-op — the kernel reads a zero change mask as
+    /// "replace all flags", so it is never sent. Flags outside
+    /// [`LinkFlags::CHANGEABLE`] are rejected with
+    /// [`Error::InvalidMessage`] rather than silently ignored.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::types::link::LinkFlags;
+    ///
+    /// // promisc on
+    /// conn.set_link_flags("eth0", LinkFlags::PROMISC, LinkFlags::PROMISC).await?;
+    /// // arp off, multicast off
+    /// conn.set_link_flags(
+    ///     "eth0",
+    ///     LinkFlags::NOARP,
+    ///     LinkFlags::NOARP | LinkFlags::MULTICAST,
+    /// ).await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_flags"))]
+    pub async fn set_link_flags(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        flags: LinkFlags,
+        mask: LinkFlags,
+    ) -> Result<()> {
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_flags_by_index(ifindex, flags, mask)
+        })
+        .await
+    }
+
+    /// Set or clear interface flags by index. See [`Self::set_link_flags`].
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_flags_by_index"))]
+    pub async fn set_link_flags_by_index(
+        &self,
+        ifindex: u32,
+        flags: LinkFlags,
+        mask: LinkFlags,
+    ) -> Result<()> {
+        let Some(ifinfo) = flags_change(ifindex, flags, mask)? else {
+            return Ok(());
+        };
+
+        let mut builder = ack_request(NlMsgType::RTM_SETLINK);
+        builder.append(&ifinfo);
+
+        self.send_ack(builder).await.map_err(|e| {
+            if e.is_not_found() {
+                Error::InterfaceNotFound {
+                    name: format!("ifindex {ifindex}"),
+                }
+            } else {
+                e.with_context(format!(
+                    "set_link_flags(ifindex {ifindex}, {flags} / {mask})"
+                ))
+            }
+        })
+    }
+
+    /// Turn promiscuous mode on or off.
+    ///
+    /// Accepts either an interface name or index via [`InterfaceRef`].
+    ///
+    /// The kernel keeps a promiscuity *count*: every packet socket with
+    /// `PACKET_MR_PROMISC`, bridge port or macvlan holds its own reference,
+    /// and the device stays promiscuous until all of them let go. This call
+    /// toggles the single reference owned by the netlink `IFF_PROMISC` flag
+    /// (what `ip link set promisc` changes), so it is idempotent and turning
+    /// it off never drops anyone else's reference.
+    /// [`LinkMessage::promiscuity`](super::messages::LinkMessage::promiscuity)
+    /// reports the total count.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// conn.set_promiscuous("eth0", true).await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_promiscuous"))]
+    pub async fn set_promiscuous(&self, iface: impl Into<InterfaceRef>, on: bool) -> Result<()> {
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_promiscuous_by_index(ifindex, on)
+        })
+        .await
+    }
+
+    /// Turn promiscuous mode on or off by index. See [`Self::set_promiscuous`].
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_promiscuous_by_index"))]
+    pub async fn set_promiscuous_by_index(&self, ifindex: u32, on: bool) -> Result<()> {
+        self.set_link_flag_by_index(ifindex, LinkFlags::PROMISC, on)
+            .await
+    }
+
+    /// Turn all-multicast reception on or off.
+    ///
+    /// Accepts either an interface name or index via [`InterfaceRef`].
+    ///
+    /// Same reference-counting rules as [`Self::set_promiscuous`]: this
+    /// toggles the netlink `IFF_ALLMULTI` reference only.
+    /// [`LinkMessage::allmulti`](super::messages::LinkMessage::allmulti)
+    /// reports the total count.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_allmulticast"))]
+    pub async fn set_allmulticast(&self, iface: impl Into<InterfaceRef>, on: bool) -> Result<()> {
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_allmulticast_by_index(ifindex, on)
+        })
+        .await
+    }
+
+    /// Turn all-multicast reception on or off by index. See
+    /// [`Self::set_allmulticast`].
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "set_allmulticast_by_index")
+    )]
+    pub async fn set_allmulticast_by_index(&self, ifindex: u32, on: bool) -> Result<()> {
+        self.set_link_flag_by_index(ifindex, LinkFlags::ALLMULTI, on)
+            .await
+    }
+
+    async fn set_link_flag_by_index(&self, ifindex: u32, flag: LinkFlags, on: bool) -> Result<()> {
+        let flags = if on { flag } else { LinkFlags::empty() };
+        self.set_link_flags_by_index(ifindex, flags, flag).await
+    }
+
     /// Set the MTU of a network interface.
     ///
     /// Accepts either an interface name or index via [`InterfaceRef`].
@@ -2689,8 +3240,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_mtu"))]
     pub async fn set_link_mtu(&self, iface: impl Into<InterfaceRef>, mtu: u32) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_mtu_by_index(ifindex, mtu).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_mtu_by_index(ifindex, mtu)
+        })
+        .await
     }
 
     /// Set the MTU of a network interface by index.
@@ -2721,8 +3274,8 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "del_link"))]
     pub async fn del_link(&self, iface: impl Into<InterfaceRef>) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.del_link_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| self.del_link_by_index(ifindex))
+            .await
     }
 
     /// Delete a network interface if it exists. Returns `Ok(true)`
@@ -2747,6 +3300,8 @@ impl Connection<Route> {
         let mut builder = ack_request(NlMsgType::RTM_DELLINK);
         builder.append(&ifinfo);
 
+        // Gone either way: deleted now, or already missing.
+        self.forget_ifindex(ifindex);
         self.send_ack(builder).await.map_err(|e| {
             if e.is_not_found() {
                 Error::InterfaceNotFound {
@@ -2770,8 +3325,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_txqlen"))]
     pub async fn set_link_txqlen(&self, iface: impl Into<InterfaceRef>, txqlen: u32) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_txqlen_by_index(ifindex, txqlen).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_txqlen_by_index(ifindex, txqlen)
+        })
+        .await
     }
 
     /// Set the TX queue length of a network interface by index.
@@ -2791,6 +3348,24 @@ impl Connection<Route> {
     }
 }
 
+/// Build the `ifinfomsg` for a flag change, or `None` if `mask` is empty.
+fn flags_change(ifindex: u32, flags: LinkFlags, mask: LinkFlags) -> Result<Option<IfInfoMsg>> {
+    if mask.is_empty() {
+        return Ok(None);
+    }
+    let fixed = mask.difference(LinkFlags::CHANGEABLE);
+    if !fixed.is_empty() {
+        return Err(Error::InvalidMessage(format!(
+            "link flags {fixed} cannot be changed"
+        )));
+    }
+
+    let mut ifinfo = IfInfoMsg::new().with_index(ifindex as i32);
+    ifinfo.ifi_flags = flags.intersection(mask).bits();
+    ifinfo.ifi_change = mask.bits();
+    Ok(Some(ifinfo))
+}
+
 // ============================================================================
 // Namespace ID Queries
 // ============================================================================
@@ -3461,3 +4036,41 @@ mod send_sync_tests {
         assert_eq!(conn.dispatcher().event_listener_count(), 0);
     }
 }
+
+#[cfg(test)]
+mod link_flags_tests {
+    use super::*;
+
+    #[test]
+    fn change_mask_limits_the_update() {
+        let msg = flags_change(3, LinkFlags::UP | LinkFlags::NOARP, LinkFlags::PROMISC)
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.ifi_index, 3);
+        assert_eq!(msg.ifi_flags, 0, "flags outside the mask must not leak");
+        assert_eq!(msg.ifi_change, iff::PROMISC);
+
+        let msg = flags_change(3, LinkFlags::NOARP, LinkFlags::NOARP | LinkFlags::MULTICAST)
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.ifi_flags, iff::NOARP);
+        assert_eq!(msg.ifi_change, iff::NOARP | iff::MULTICAST);
+    }
+
+    #[test]
+    fn empty_mask_sends_nothing() {
+        // ifi_change == 0 would make the kernel overwrite every flag.
+        assert!(
+            flags_change(3, LinkFlags::UP, LinkFlags::empty())
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn read_only_flags_are_rejected() {
+        let err =
+            flags_change(3, LinkFlags::empty(), LinkFlags::UP | LinkFlags::RUNNING).unwrap_err();
+        assert!(err.to_string().contains("RUNNING"), "{err}");
+    }
+}
@@ -36,6 +36,22 @@
 //!     println!("  Drop rate: {:.2}%", bottleneck.drop_rate * 100.0);
 //!     println!("  Recommendation: {}", bottleneck.recommendation);
 //! }
+//!
+//! // NIC driver settings (speed/duplex, offloads, pause, rings, queues)
+//! for issue in diag.scan_nic().await? {
+//!     println!("{issue}");
+//! }
+//!
+//! // Addresses reused across named network namespaces
+//! let namespaces = nlink::netlink::namespace::list()?;
+//! for issue in diag.find_duplicate_addresses(&namespaces).await? {
+//!     println!("{issue}");
+//! }
+//!
+//! // Neighbour tables nearing "neighbour table overflow"
+//! for issue in diag.scan_neighbor_tables().await? {
+//!     println!("{issue}");
+//! }
 //! ```
 //!
 //! # Real-time Monitoring
@@ -51,13 +67,13 @@
 //! ```
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt,
     net::IpAddr,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use tokio::sync::Mutex;
@@ -65,14 +81,27 @@ use tokio_stream::Stream;
 
 use crate::netlink::{
     connection::Connection,
-    error::Result,
+    error::{Error, Result},
     events::NetworkEvent,
+    genl::ethtool::{Channels, Duplex, Features, LinkModes, Pause, Rings},
     messages::{AddressMessage, LinkMessage, LinkStats, RouteMessage, TcMessage},
-    protocol::Route,
+    namespace,
+    protocol::{Ethtool, Route},
+    route::rtnh_flags,
     stream::OwnedEventStream,
-    types::{link::OperState, neigh::NeighborState},
+    sysctl::{self, RpFilter},
+    types::{
+        addr::Scope,
+        link::OperState,
+        neigh::{NeighborState, ntf},
+        route::{RouteProtocol, RouteType},
+    },
 };
 
+/// Address families
+const AF_INET: u8 = libc::AF_INET as u8;
+const AF_INET6: u8 = libc::AF_INET6 as u8;
+
 // ============================================================================
 // Core Types
 // ============================================================================
@@ -224,6 +253,38 @@ pub struct RouteDiag {
     pub default_gateway_v6: Option<IpAddr>,
 }
 
+/// Occupancy of a neighbour (ARP/NDP) table against its garbage
+/// collection thresholds, `net.<ipv4|ipv6>.neigh.default.gc_thresh{1,2,3}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NeighborTableUsage {
+    /// Address family (`AF_INET` or `AF_INET6`).
+    pub family: u8,
+    /// Entries counted against the thresholds: all but permanent and
+    /// externally learned ones, which the kernel exempts from GC.
+    pub entries: usize,
+    /// Below this many entries the garbage collector does not run.
+    pub gc_thresh1: u32,
+    /// Soft limit; above it stale entries are reclaimed aggressively.
+    pub gc_thresh2: u32,
+    /// Hard limit; new entries fail with "neighbour table overflow".
+    pub gc_thresh3: u32,
+}
+
+impl NeighborTableUsage {
+    /// The protocol name used in sysctl keys: `ipv4` or `ipv6`.
+    pub fn proto(&self) -> &'static str {
+        neigh_proto(self.family)
+    }
+
+    /// Entries as a fraction of `gc_thresh3`.
+    pub fn utilization(&self) -> f64 {
+        if self.gc_thresh3 == 0 {
+            return 0.0;
+        }
+        self.entries as f64 / f64::from(self.gc_thresh3)
+    }
+}
+
 /// A detected issue.
 #[derive(Debug, Clone)]
 pub struct Issue {
@@ -309,6 +370,30 @@ pub enum IssueCategory {
     MtuIssue,
     /// Duplex/speed mismatch.
     DuplexMismatch,
+    /// GRO/TSO disabled on a high-speed link.
+    OffloadDisabled,
+    /// RX and TX pause frames set differently.
+    PauseMismatch,
+    /// Ring buffer far below its hardware maximum.
+    SmallRingBuffer,
+    /// Single receive queue on a multi-core host.
+    SingleQueue,
+    /// Same address configured in more than one place.
+    DuplicateAddress,
+    /// IPv6 duplicate address detection failed.
+    DadFailed,
+    /// Routes that contradict or shadow each other.
+    ConflictingRoute,
+    /// ECMP nexthop on a down link.
+    NexthopDown,
+    /// Strict reverse path filtering on a host with several uplinks.
+    StrictRpFilter,
+    /// Neighbour table close to or at its gc_thresh3 limit.
+    NeighborTablePressure,
+    /// Neighbour entry cycling between REACHABLE and FAILED.
+    NeighborFlap,
+    /// Netlink socket whose receive queue is filling up or has overrun.
+    NetlinkBacklog,
 }
 
 impl fmt::Display for IssueCategory {
@@ -327,6 +412,18 @@ impl fmt::Display for IssueCategory {
             IssueCategory::NoDefaultRoute => write!(f, "NoDefaultRoute"),
             IssueCategory::MtuIssue => write!(f, "MtuIssue"),
             IssueCategory::DuplexMismatch => write!(f, "DuplexMismatch"),
+            IssueCategory::OffloadDisabled => write!(f, "OffloadDisabled"),
+            IssueCategory::PauseMismatch => write!(f, "PauseMismatch"),
+            IssueCategory::SmallRingBuffer => write!(f, "SmallRingBuffer"),
+            IssueCategory::SingleQueue => write!(f, "SingleQueue"),
+            IssueCategory::DuplicateAddress => write!(f, "DuplicateAddress"),
+            IssueCategory::DadFailed => write!(f, "DadFailed"),
+            IssueCategory::ConflictingRoute => write!(f, "ConflictingRoute"),
+            IssueCategory::NexthopDown => write!(f, "NexthopDown"),
+            IssueCategory::StrictRpFilter => write!(f, "StrictRpFilter"),
+            IssueCategory::NeighborTablePressure => write!(f, "NeighborTablePressure"),
+            IssueCategory::NeighborFlap => write!(f, "NeighborFlap"),
+            IssueCategory::NetlinkBacklog => write!(f, "NetlinkBacklog"),
         }
     }
 }
@@ -498,6 +595,17 @@ pub struct DiagnosticsConfig {
     pub skip_down: bool,
     /// Minimum bytes transferred before calculating loss rate.
     pub min_bytes_for_rate: u64,
+    /// Link speed in Mb/s from which disabled GRO/TSO is reported
+    /// (default: 10000).
+    pub high_speed_mbps: u32,
+    /// CPU count from which a single-queue NIC is reported (default: 4).
+    pub single_queue_min_cpus: usize,
+    /// Fraction of gc_thresh3 from which a neighbour table is reported as
+    /// nearly full (default: 0.9).
+    pub neigh_table_threshold: f64,
+    /// Fraction of its receive buffer from which a netlink socket's queue
+    /// is reported (default: 0.5).
+    pub netlink_rcvbuf_threshold: f64,
 }
 
 impl Default for DiagnosticsConfig {
@@ -511,6 +619,10 @@ impl Default for DiagnosticsConfig {
             skip_loopback: true,
             skip_down: false,
             min_bytes_for_rate: 1000,
+            high_speed_mbps: 10_000,
+            single_queue_min_cpus: 4,
+            neigh_table_threshold: 0.9,
+            netlink_rcvbuf_threshold: 0.5,
         }
     }
 }
@@ -571,8 +683,24 @@ impl Diagnostics {
         // Get all links
         let links = self.conn.get_links().await?;
 
+        let link_names: HashMap<u32, String> = links
+            .iter()
+            .map(|l| (l.ifindex(), l.name().unwrap_or("?").to_string()))
+            .collect();
+        let links_up: HashMap<u32, bool> = links
+            .iter()
+            .map(|l| (l.ifindex(), l.is_up() && l.has_carrier()))
+            .collect();
+
         // Get all addresses for checking
         let addresses = self.conn.get_addresses().await?;
+        all_issues.extend(detect_duplicate_addresses(
+            addresses.iter().filter_map(|a| {
+                let dev = link_names.get(&a.ifindex())?;
+                Some((a, dev.clone()))
+            }),
+            timestamp,
+        ));
         let addr_by_ifindex: HashMap<u32, Vec<_>> = {
             let mut map: HashMap<u32, Vec<_>> = HashMap::new();
             for addr in addresses {
@@ -611,6 +739,43 @@ impl Diagnostics {
             });
         }
 
+        all_issues.extend(detect_route_issues(
+            &all_routes,
+            &link_names,
+            &links_up,
+            timestamp,
+        ));
+
+        // rp_filter on multi-homed hosts. The sysctls are read in the
+        // connection's namespace; skipped if that isn't possible.
+        let uplinks: Vec<String> = default_route_interfaces(&all_routes)
+            .into_iter()
+            .filter_map(|i| link_names.get(&i).cloned())
+            .collect();
+        if uplinks.len() > 1 {
+            let modes = namespace::run_in_connection_namespace(&self.conn, move || {
+                Ok(uplinks
+                    .into_iter()
+                    .filter_map(|dev| {
+                        let mode = sysctl::effective_rp_filter(&dev).ok()?;
+                        Some((dev, mode))
+                    })
+                    .collect::<Vec<_>>())
+            })
+            .unwrap_or_default();
+            all_issues.extend(detect_rp_filter_issues(&modes, timestamp));
+        }
+
+        // Neighbour table occupancy; skipped if the gc thresholds
+        // can't be read.
+        if let Ok(tables) = self.neighbor_table_usage().await {
+            all_issues.extend(detect_neighbor_table_issues(
+                &tables,
+                self.config.neigh_table_threshold,
+                timestamp,
+            ));
+        }
+
         // Scan each interface
         let mut interfaces = Vec::new();
         let mut prev_stats = self.prev_stats.lock().await;
@@ -767,6 +932,204 @@ impl Diagnostics {
         })
     }
 
+    /// Check NIC driver settings over ethtool.
+    ///
+    /// Looks at every physical interface (no link kind, not loopback;
+    /// down ones too unless [`DiagnosticsConfig::skip_down`]) and
+    /// reports:
+    ///
+    /// - half duplex, or a speed below the best mode both ends advertise
+    /// - GRO or TSO off on links of at least
+    ///   [`DiagnosticsConfig::high_speed_mbps`]
+    /// - RX and TX pause frames set differently
+    /// - RX/TX rings at a quarter of their hardware maximum or less
+    /// - a single receive queue on a host with at least
+    ///   [`DiagnosticsConfig::single_queue_min_cpus`] CPUs
+    ///
+    /// Each issue's `details` suggests the fix. Settings a driver
+    /// doesn't report are skipped. Fails if the ethtool netlink family
+    /// is unavailable.
+    pub async fn scan_nic(&self) -> Result<Vec<Issue>> {
+        let timestamp = Instant::now();
+        let ethtool = Connection::<Ethtool>::new_async().await?;
+        let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let mut issues = Vec::new();
+
+        for link in self.conn.get_links().await? {
+            if link.is_loopback() || link.link_info().and_then(|i| i.kind()).is_some() {
+                continue;
+            }
+            if self.config.skip_down && !link.is_up() {
+                continue;
+            }
+            let Some(name) = link.name() else {
+                continue;
+            };
+
+            let nic = NicSettings {
+                link_modes: ethtool.get_link_modes_by_name(name).await.ok(),
+                features: ethtool.get_features_by_name(name).await.ok(),
+                pause: ethtool.get_pause_by_name(name).await.ok(),
+                rings: ethtool.get_rings_by_name(name).await.ok(),
+                channels: ethtool.get_channels_by_name(name).await.ok(),
+            };
+            issues.extend(detect_nic_issues(&self.config, name, &nic, cpus, timestamp));
+        }
+
+        Ok(issues)
+    }
+
+    /// Measure IPv4 and IPv6 neighbour table occupancy against the gc
+    /// thresholds.
+    ///
+    /// Entries come from a neighbour dump; the thresholds are read from
+    /// sysctl in the connection's namespace. A family whose thresholds
+    /// can't be read (IPv6 disabled) is left out.
+    pub async fn neighbor_table_usage(&self) -> Result<Vec<NeighborTableUsage>> {
+        let neighbors = self.conn.get_neighbors().await?;
+        let entries = |family: u8| {
+            neighbors
+                .iter()
+                .filter(|n| n.family() == family && counts_against_gc(n.state(), n.flags()))
+                .count()
+        };
+        let counts = [(AF_INET, entries(AF_INET)), (AF_INET6, entries(AF_INET6))];
+
+        namespace::run_in_connection_namespace(&self.conn, move || {
+            Ok(counts
+                .into_iter()
+                .filter_map(|(family, entries)| {
+                    let thresh = |n: u8| {
+                        let key =
+                            format!("net.{}.neigh.default.gc_thresh{}", neigh_proto(family), n);
+                        sysctl::parse_u32(&key, &sysctl::get(&key).ok()?).ok()
+                    };
+                    Some(NeighborTableUsage {
+                        family,
+                        entries,
+                        gc_thresh1: thresh(1)?,
+                        gc_thresh2: thresh(2)?,
+                        gc_thresh3: thresh(3)?,
+                    })
+                })
+                .collect())
+        })
+    }
+
+    /// Check whether a neighbour table is about to overflow.
+    ///
+    /// Reports tables at or above gc_thresh3 as [`Severity::Critical`]
+    /// (new neighbours are being refused), tables past
+    /// [`DiagnosticsConfig::neigh_table_threshold`] of it as warnings,
+    /// and tables above gc_thresh2 as [`Severity::Info`]. Also run by
+    /// [`scan`](Self::scan).
+    pub async fn scan_neighbor_tables(&self) -> Result<Vec<Issue>> {
+        let tables = self.neighbor_table_usage().await?;
+        Ok(detect_neighbor_table_issues(
+            &tables,
+            self.config.neigh_table_threshold,
+            Instant::now(),
+        ))
+    }
+
+    /// Find netlink sockets at risk of losing messages.
+    ///
+    /// Dumps the netlink sockets in the connection's namespace and
+    /// reports those with a receive queue past
+    /// [`DiagnosticsConfig::netlink_rcvbuf_threshold`] of their buffer,
+    /// or that have already dropped messages. For an event listener each
+    /// drop is a lost notification, the usual reason a daemon's view of
+    /// the network goes stale. Owners are named from a `/proc` scan.
+    /// Not part of [`scan`](Self::scan).
+    #[cfg(feature = "sockdiag")]
+    pub async fn scan_netlink_sockets(&self) -> Result<Vec<Issue>> {
+        use std::os::fd::AsRawFd;
+
+        use crate::netlink::protocol::SockDiag;
+
+        let ns = self.conn.namespace_fd()?;
+        let diag = Connection::<SockDiag>::new_in_namespace(ns.as_raw_fd())?;
+        let sockets = diag.query_netlink_sockets().await?;
+        let owners = crate::sockdiag::SocketOwnerMap::scan();
+        Ok(detect_netlink_socket_issues(
+            &sockets,
+            &owners,
+            self.config.netlink_rcvbuf_threshold,
+            Instant::now(),
+        ))
+    }
+
+    /// Find addresses configured in more than one network namespace.
+    ///
+    /// Compares the global addresses of this runner's namespace with
+    /// those in each of `namespaces` (names as in
+    /// [`namespace::list`]). Isolated namespaces may reuse addresses on
+    /// purpose, so these are reported as [`Severity::Info`]; they matter
+    /// when the namespaces share a network segment. Duplicates within
+    /// one namespace are reported by [`scan`](Self::scan).
+    pub async fn find_duplicate_addresses<S: AsRef<str>>(
+        &self,
+        namespaces: &[S],
+    ) -> Result<Vec<Issue>> {
+        let timestamp = Instant::now();
+        // (namespace label, addresses, ifindex -> name)
+        let mut all = vec![(
+            "current namespace".to_string(),
+            self.conn.get_addresses().await?,
+            self.conn.get_interface_names().await?,
+        )];
+        for ns in namespaces {
+            let ns = ns.as_ref();
+            let conn = namespace::connection_for::<Route>(ns)?;
+            all.push((
+                format!("namespace {}", ns),
+                conn.get_addresses().await?,
+                conn.get_interface_names().await?,
+            ));
+        }
+
+        let mut by_addr: BTreeMap<IpAddr, BTreeMap<&str, Vec<&str>>> = BTreeMap::new();
+        for (ns, addresses, names) in &all {
+            for addr in addresses {
+                if let Some(ip) = shared_address(addr)
+                    && let Some(dev) = names.get(&addr.ifindex())
+                {
+                    by_addr
+                        .entry(ip)
+                        .or_default()
+                        .entry(ns.as_str())
+                        .or_default()
+                        .push(dev.as_str());
+                }
+            }
+        }
+
+        Ok(by_addr
+            .into_iter()
+            .filter(|(_, places)| places.len() > 1)
+            .map(|(ip, places)| Issue {
+                severity: Severity::Info,
+                category: IssueCategory::DuplicateAddress,
+                message: format!(
+                    "{} is configured in {}",
+                    ip,
+                    places
+                        .iter()
+                        .map(|(ns, devs)| format!("{} ({})", ns, devs.join(", ")))
+                        .collect::<Vec<_>>()
+                        .join(" and ")
+                ),
+                details: Some(
+                    "Namespaces attached to the same network segment will answer ARP/ND \
+                     for it in turn; give each a distinct address"
+                        .to_string(),
+                ),
+                interface: None,
+                timestamp,
+            })
+            .collect())
+    }
+
     /// Check connectivity to a destination IP address.
     pub async fn check_connectivity(&self, dest: IpAddr) -> Result<ConnectivityReport> {
         let timestamp = Instant::now();
@@ -910,6 +1273,53 @@ impl Diagnostics {
         })
     }
 
+    /// Confirm a report's gateway by resolving it on the wire.
+    ///
+    /// [`check_connectivity`](Self::check_connectivity) only reads the
+    /// neighbour cache, where an idle gateway may be missing or stale.
+    /// This sends ARP/NDP for it via
+    /// [`Connection::probe_neighbor`] and replaces the cache-based
+    /// verdict: `gateway_reachable` is updated, and a gateway that
+    /// doesn't answer within `timeout` is reported as an error. A
+    /// report without a gateway or output interface is left as is.
+    pub async fn probe_gateway(
+        &self,
+        report: &mut ConnectivityReport,
+        timeout: Duration,
+    ) -> Result<()> {
+        let (Some(gateway), Some(oif)) =
+            (report.gateway, report.route.as_ref().and_then(|r| r.oif))
+        else {
+            return Ok(());
+        };
+        let reachable = match self.conn.probe_neighbor(oif, gateway, timeout).await {
+            // probe_neighbor only returns resolved or failed entries.
+            Ok(entry) => !entry.is_failed(),
+            Err(Error::Timeout) => false,
+            Err(e) => return Err(e),
+        };
+
+        report.gateway_reachable = reachable;
+        report
+            .issues
+            .retain(|i| i.category != IssueCategory::Unreachable);
+        if !reachable {
+            report.issues.push(Issue {
+                severity: Severity::Error,
+                category: IssueCategory::Unreachable,
+                message: format!("Gateway {} does not answer", gateway),
+                details: Some(format!(
+                    "No {} reply within {:?}",
+                    if gateway.is_ipv4() { "ARP" } else { "NDP" },
+                    timeout
+                )),
+                interface: report.output_interface.clone(),
+                timestamp: Instant::now(),
+            });
+        }
+        Ok(())
+    }
+
     /// Find the most significant bottleneck in the system.
     pub async fn find_bottleneck(&self) -> Result<Option<Bottleneck>> {
         let mut bottlenecks = Vec::new();
@@ -1180,6 +1590,26 @@ impl Diagnostics {
             }
         }
 
+        // Check for addresses another host on the link already uses
+        for addr in addr_by_ifindex.get(&ifindex).into_iter().flatten() {
+            if addr.is_dad_failed()
+                && let Some(ip) = addr.address()
+            {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    category: IssueCategory::DadFailed,
+                    message: format!("Duplicate address detection failed for {} on {}", ip, name),
+                    details: Some(format!(
+                        "Another host on the link uses {}; the address stays unusable until it \
+                         is removed and re-added",
+                        ip
+                    )),
+                    interface: Some(name.clone()),
+                    timestamp,
+                });
+            }
+        }
+
         issues
     }
 
@@ -1224,6 +1654,619 @@ impl Diagnostics {
     }
 }
 
+// ============================================================================
+// Address and Route Checks
+// ============================================================================
+
+/// The address to compare for duplicates: global (or site) scope
+/// only, since loopback and link-local addresses repeat by design.
+fn shared_address(addr: &AddressMessage) -> Option<IpAddr> {
+    if !matches!(addr.scope(), Scope::Universe | Scope::Site) {
+        return None;
+    }
+    addr.address().copied()
+}
+
+/// Addresses configured on more than one interface, from `(address,
+/// interface name)` pairs.
+fn detect_duplicate_addresses<'a>(
+    addresses: impl IntoIterator<Item = (&'a AddressMessage, String)>,
+    timestamp: Instant,
+) -> Vec<Issue> {
+    let mut by_addr: BTreeMap<IpAddr, BTreeSet<String>> = BTreeMap::new();
+    for (addr, dev) in addresses {
+        if let Some(ip) = shared_address(addr) {
+            by_addr.entry(ip).or_default().insert(dev);
+        }
+    }
+
+    by_addr
+        .into_iter()
+        .filter(|(_, devs)| devs.len() > 1)
+        .map(|(ip, devs)| Issue {
+            severity: Severity::Warning,
+            category: IssueCategory::DuplicateAddress,
+            message: format!(
+                "{} is configured on {}",
+                ip,
+                devs.into_iter().collect::<Vec<_>>().join(", ")
+            ),
+            details: Some(
+                "Replies may leave through either interface and ARP/ND answers conflict; \
+                 keep the address on one interface"
+                    .to_string(),
+            ),
+            interface: None,
+            timestamp,
+        })
+        .collect()
+}
+
+/// Conflicting routes and ECMP nexthops on down links.
+///
+/// - Separate IPv4 routes to the same prefix, in the same table and with
+///   the same metric, through different gateways: only the first is used.
+/// - A gatewayed route inside a connected subnet: hosts in it are sent
+///   to the gateway instead of reached directly.
+/// - Multipath nexthops whose link is down or that the kernel marked
+///   dead or link-down.
+fn detect_route_issues(
+    routes: &[RouteMessage],
+    link_names: &HashMap<u32, String>,
+    links_up: &HashMap<u32, bool>,
+    timestamp: Instant,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let unicast: Vec<_> = routes
+        .iter()
+        .filter(|r| r.route_type() == RouteType::Unicast)
+        .collect();
+    let dev = |ifindex: u32| {
+        link_names
+            .get(&ifindex)
+            .cloned()
+            .unwrap_or_else(|| format!("if{}", ifindex))
+    };
+
+    // Same prefix, table and metric through different gateways. IPv6
+    // merges such routes into one multipath route (or picks among RA
+    // routers), so only IPv4 keeps the dead duplicates.
+    let mut by_key: BTreeMap<_, BTreeSet<IpAddr>> = BTreeMap::new();
+    for r in unicast.iter().filter(|r| r.is_ipv4()) {
+        if let Some(gw) = r.gateway() {
+            by_key
+                .entry((r.table_id(), route_destination(r), r.priority()))
+                .or_default()
+                .insert(*gw);
+        }
+    }
+    for ((table, dst, metric), gateways) in by_key {
+        if gateways.len() > 1 {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                category: IssueCategory::ConflictingRoute,
+                message: format!(
+                    "Routes to {} in table {} with metric {} use different gateways ({})",
+                    dst,
+                    table,
+                    metric.unwrap_or(0),
+                    gateways
+                        .iter()
+                        .map(|g| g.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                details: Some(
+                    "Only the first one is used; remove the others, or give them \
+                     different metrics for failover"
+                        .to_string(),
+                ),
+                interface: None,
+                timestamp,
+            });
+        }
+    }
+
+    // Gatewayed routes inside a connected subnet
+    let connected: Vec<_> = unicast
+        .iter()
+        .filter(|r| r.protocol() == RouteProtocol::Kernel && r.gateway().is_none())
+        .filter_map(|r| Some((*r, *r.destination()?)))
+        .collect();
+    for r in &unicast {
+        let (Some(gw), Some(dst)) = (r.gateway(), r.destination()) else {
+            continue;
+        };
+        if r.dst_len() == 0 {
+            continue;
+        }
+        if let Some((subnet, net)) = connected.iter().find(|(c, net)| {
+            c.table_id() == r.table_id()
+                && c.dst_len() < r.dst_len()
+                && prefix_contains(*net, c.dst_len(), *dst)
+        }) {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                category: IssueCategory::ConflictingRoute,
+                message: format!(
+                    "Route to {} via {} overlaps the connected subnet {}/{}{}",
+                    route_destination(r),
+                    gw,
+                    net,
+                    subnet.dst_len(),
+                    subnet
+                        .oif()
+                        .map(|i| format!(" on {}", dev(i)))
+                        .unwrap_or_default()
+                ),
+                details: Some(format!(
+                    "Hosts in {} are sent to the gateway instead of reached directly; \
+                     narrow or remove one of the routes if that isn't intended",
+                    route_destination(r)
+                )),
+                interface: None,
+                timestamp,
+            });
+        }
+    }
+
+    // ECMP nexthops on down links
+    for r in &unicast {
+        let Some(nexthops) = r.multipath() else {
+            continue;
+        };
+        let down: Vec<_> = nexthops
+            .iter()
+            .filter(|nh| {
+                nh.flags & (rtnh_flags::DEAD | rtnh_flags::LINKDOWN) != 0
+                    || links_up.get(&nh.ifindex) == Some(&false)
+            })
+            .collect();
+        if down.is_empty() {
+            continue;
+        }
+        let remaining = nexthops.len() - down.len();
+        for nh in down {
+            let via = nh
+                .gateway
+                .map(|g| format!("via {} ", g))
+                .unwrap_or_default();
+            issues.push(Issue {
+                severity: if remaining == 0 {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                category: IssueCategory::NexthopDown,
+                message: format!(
+                    "ECMP nexthop {}on {} for {} is down",
+                    via,
+                    dev(nh.ifindex),
+                    route_destination(r)
+                ),
+                details: Some(if remaining == 0 {
+                    "No nexthop of this route is usable".to_string()
+                } else {
+                    format!(
+                        "Traffic is hashed onto the remaining {} of {} nexthops",
+                        remaining,
+                        nexthops.len()
+                    )
+                }),
+                interface: Some(dev(nh.ifindex)),
+                timestamp,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Interfaces carrying a single-path IPv4 default route in the main
+/// table, in route order. An ECMP default route is left out: the kernel
+/// accepts a reply on any of its nexthops, even in strict mode.
+fn default_route_interfaces(routes: &[RouteMessage]) -> Vec<u32> {
+    let mut out = Vec::new();
+    for r in routes {
+        if !r.is_ipv4()
+            || r.dst_len() != 0
+            || r.table_id() != 254
+            || r.route_type() != RouteType::Unicast
+            || r.multipath().is_some()
+        {
+            continue;
+        }
+        if let Some(oif) = r.oif()
+            && !out.contains(&oif)
+        {
+            out.push(oif);
+        }
+    }
+    out
+}
+
+/// Strict `rp_filter` on an uplink of a multi-homed host drops replies
+/// that come back on an interface other than the preferred default
+/// route's. `uplinks` are the interfaces with a default route and their
+/// effective mode.
+fn detect_rp_filter_issues(uplinks: &[(String, RpFilter)], timestamp: Instant) -> Vec<Issue> {
+    if uplinks.len() < 2 {
+        return Vec::new();
+    }
+    uplinks
+        .iter()
+        .filter(|(_, mode)| *mode == RpFilter::Strict)
+        .map(|(dev, _)| Issue {
+            severity: Severity::Warning,
+            category: IssueCategory::StrictRpFilter,
+            message: format!(
+                "Strict rp_filter on {}, one of {} interfaces with a default route",
+                dev,
+                uplinks.len()
+            ),
+            details: Some(format!(
+                "Packets arriving on {} from sources routed out another uplink are \
+                 dropped; set net.ipv4.conf.{}.rp_filter (and conf.all) to 2 for \
+                 loose mode",
+                dev, dev
+            )),
+            interface: Some(dev.clone()),
+            timestamp,
+        })
+        .collect()
+}
+
+/// The protocol name of a neighbour table's sysctl keys.
+fn neigh_proto(family: u8) -> &'static str {
+    if family == AF_INET6 { "ipv6" } else { "ipv4" }
+}
+
+/// Whether the kernel counts an entry against the gc thresholds:
+/// permanent and externally learned entries are exempt.
+fn counts_against_gc(state: NeighborState, flags: u8) -> bool {
+    state != NeighborState::Permanent && flags & ntf::EXT_LEARNED == 0
+}
+
+/// Flag neighbour tables near or at their gc_thresh3 limit.
+fn detect_neighbor_table_issues(
+    tables: &[NeighborTableUsage],
+    threshold: f64,
+    timestamp: Instant,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for t in tables {
+        let proto = t.proto();
+        let (severity, message) = if t.entries >= t.gc_thresh3 as usize {
+            (
+                Severity::Critical,
+                format!(
+                    "{} neighbour table full: {} entries, gc_thresh3 is {}",
+                    proto, t.entries, t.gc_thresh3
+                ),
+            )
+        } else if t.utilization() >= threshold {
+            (
+                Severity::Warning,
+                format!(
+                    "{} neighbour table at {:.0}% of gc_thresh3 ({} of {} entries)",
+                    proto,
+                    t.utilization() * 100.0,
+                    t.entries,
+                    t.gc_thresh3
+                ),
+            )
+        } else if t.entries > t.gc_thresh2 as usize {
+            (
+                Severity::Info,
+                format!(
+                    "{} neighbour table above gc_thresh2: {} entries, soft limit {}",
+                    proto, t.entries, t.gc_thresh2
+                ),
+            )
+        } else {
+            continue;
+        };
+        issues.push(Issue {
+            severity,
+            category: IssueCategory::NeighborTablePressure,
+            message,
+            details: Some(format!(
+                "New neighbours fail with \"neighbour table overflow\" at gc_thresh3; \
+                 raise net.{}.neigh.default.gc_thresh2 and gc_thresh3",
+                proto
+            )),
+            interface: None,
+            timestamp,
+        });
+    }
+    issues
+}
+
+/// Flag netlink sockets with a backed-up receive queue or past drops.
+#[cfg(feature = "sockdiag")]
+fn detect_netlink_socket_issues(
+    sockets: &[crate::sockdiag::NetlinkSocket],
+    owners: &crate::sockdiag::SocketOwnerMap,
+    threshold: f64,
+    timestamp: Instant,
+) -> Vec<Issue> {
+    use crate::sockdiag::socket::netlink_flags::NO_ENOBUFS;
+
+    let mut issues = Vec::new();
+    for sock in sockets.iter().filter(|s| !s.is_kernel()) {
+        let drops = sock.drops().unwrap_or(0);
+        let usage = sock.rcvbuf_usage().unwrap_or(0.0);
+        let who = match owners.resolve(sock.inode).first() {
+            Some(p) => format!("{} (pid {}, port {})", p.comm, p.pid, sock.portid),
+            None => format!("port {}", sock.portid),
+        };
+        let proto = sock.protocol_name();
+
+        let (severity, message) = if usage >= threshold {
+            let mem = sock.mem_info.as_ref();
+            (
+                if drops > 0 {
+                    Severity::Error
+                } else {
+                    Severity::Warning
+                },
+                format!(
+                    "{} netlink socket of {} has {} of {} receive buffer bytes queued ({:.0}%), {} dropped",
+                    proto,
+                    who,
+                    mem.map_or(0, |m| m.rmem_alloc),
+                    mem.and_then(|m| m.rcvbuf).unwrap_or(0),
+                    usage * 100.0,
+                    drops
+                ),
+            )
+        } else if drops > 0 {
+            (
+                Severity::Warning,
+                format!(
+                    "{} netlink socket of {} has dropped {} messages",
+                    proto, who, drops
+                ),
+            )
+        } else {
+            continue;
+        };
+
+        let details = if sock.has_flag(NO_ENOBUFS) {
+            "NETLINK_NO_ENOBUFS is set, so the reader is never told about drops; \
+             read faster or raise SO_RCVBUF"
+        } else {
+            "A full queue drops messages and the reader gets ENOBUFS; read faster, \
+             raise SO_RCVBUF and resynchronise after ENOBUFS"
+        };
+        issues.push(Issue {
+            severity,
+            category: IssueCategory::NetlinkBacklog,
+            message,
+            details: Some(details.to_string()),
+            interface: None,
+            timestamp,
+        });
+    }
+    issues
+}
+
+/// `prefix/len`, or `default`.
+fn route_destination(r: &RouteMessage) -> String {
+    match r.destination() {
+        Some(d) if r.dst_len() > 0 => format!("{}/{}", d, r.dst_len()),
+        _ => "default".to_string(),
+    }
+}
+
+/// Whether `addr` lies in `net/len`.
+fn prefix_contains(net: IpAddr, len: u8, addr: IpAddr) -> bool {
+    match (net, addr) {
+        (IpAddr::V4(n), IpAddr::V4(a)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(len)).unwrap_or(0);
+            u32::from(n) & mask == u32::from(a) & mask
+        }
+        (IpAddr::V6(n), IpAddr::V6(a)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(len)).unwrap_or(0);
+            u128::from(n) & mask == u128::from(a) & mask
+        }
+        _ => false,
+    }
+}
+
+// ============================================================================
+// NIC Checks
+// ============================================================================
+
+/// Ethtool settings of one NIC; `None` where the driver doesn't
+/// answer the query.
+#[derive(Default)]
+struct NicSettings {
+    link_modes: Option<LinkModes>,
+    features: Option<Features>,
+    pause: Option<Pause>,
+    rings: Option<Rings>,
+    channels: Option<Channels>,
+}
+
+fn detect_nic_issues(
+    config: &DiagnosticsConfig,
+    name: &str,
+    nic: &NicSettings,
+    cpus: usize,
+    timestamp: Instant,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut push = |severity, category, message: String, details: String| {
+        issues.push(Issue {
+            severity,
+            category,
+            message,
+            details: Some(details),
+            interface: Some(name.to_string()),
+            timestamp,
+        });
+    };
+
+    // Speed is only reported while the link has carrier.
+    let speed = nic.link_modes.as_ref().and_then(|m| m.speed);
+
+    if let Some(modes) = &nic.link_modes
+        && let Some(speed) = modes.speed
+    {
+        if modes.duplex == Some(Duplex::Half) {
+            push(
+                Severity::Warning,
+                IssueCategory::DuplexMismatch,
+                format!("{name} is at half duplex ({speed} Mb/s)"),
+                if modes.autoneg {
+                    "Autonegotiation fell back to half duplex, usually because the \
+                     link partner has it off; enable autonegotiation on both ends"
+                        .to_string()
+                } else {
+                    format!(
+                        "Autonegotiation is off; enable it on both ends \
+                         (`ethtool -s {name} autoneg on`)"
+                    )
+                },
+            );
+        }
+        if modes.autoneg
+            && let Some(best) = best_common_speed(modes)
+            && speed < best
+        {
+            push(
+                Severity::Warning,
+                IssueCategory::DuplexMismatch,
+                format!("{name} runs at {speed} Mb/s although both ends advertise {best} Mb/s"),
+                format!(
+                    "A damaged cable or port often negotiates down; check the cabling, \
+                     then renegotiate (`ethtool -r {name}`)"
+                ),
+            );
+        }
+    }
+
+    if let (Some(speed), Some(features)) = (speed, &nic.features)
+        && speed >= config.high_speed_mbps
+    {
+        for (feature, short, what) in [
+            ("rx-gro", "gro", "GRO"),
+            ("tx-tcp-segmentation", "tso", "TSO"),
+        ] {
+            if features.is_hw_supported(feature) && !features.is_active(feature) {
+                push(
+                    Severity::Warning,
+                    IssueCategory::OffloadDisabled,
+                    format!("{what} is off on {name} ({speed} Mb/s)"),
+                    format!(
+                        "Every packet pays the full per-packet stack cost; \
+                         enable it (`ethtool -K {name} {short} on`)"
+                    ),
+                );
+            }
+        }
+    }
+
+    if let Some(pause) = &nic.pause
+        && let (Some(rx), Some(tx)) = (pause.rx, pause.tx)
+        && rx != tx
+    {
+        let on_off = |b: bool| if b { "on" } else { "off" };
+        push(
+            Severity::Info,
+            IssueCategory::PauseMismatch,
+            format!(
+                "Pause frames are asymmetric on {name}: rx {}, tx {}",
+                on_off(rx),
+                on_off(tx)
+            ),
+            format!(
+                "Flow control works in one direction only; match the switch port \
+                 (`ethtool -A {name} rx on tx on`, or both off)"
+            ),
+        );
+    }
+
+    if let Some(rings) = &nic.rings {
+        for (dir, current, max) in [
+            ("rx", rings.rx, rings.rx_max),
+            ("tx", rings.tx, rings.tx_max),
+        ] {
+            if let (Some(current), Some(max)) = (current, max)
+                && current < max
+                && current.saturating_mul(4) <= max
+            {
+                push(
+                    Severity::Info,
+                    IssueCategory::SmallRingBuffer,
+                    format!(
+                        "{} ring on {name} is {current} of a possible {max}",
+                        dir.to_uppercase()
+                    ),
+                    format!(
+                        "Small rings drop bursts under load; raise it \
+                         (`ethtool -G {name} {dir} {max}`)"
+                    ),
+                );
+            }
+        }
+    }
+
+    if let Some(ch) = &nic.channels {
+        let queues = ch.combined_count.unwrap_or(0) + ch.rx_count.unwrap_or(0);
+        if queues == 1 && cpus >= config.single_queue_min_cpus {
+            let combined_max = ch.combined_max.unwrap_or(0);
+            let rx_max = ch.rx_max.unwrap_or(0);
+            let details = if combined_max > 1 {
+                format!(
+                    "Receive processing runs on one CPU; spread it \
+                     (`ethtool -L {name} combined {}`)",
+                    (combined_max as usize).min(cpus)
+                )
+            } else if rx_max > 1 {
+                format!(
+                    "Receive processing runs on one CPU; spread it \
+                     (`ethtool -L {name} rx {}`)",
+                    (rx_max as usize).min(cpus)
+                )
+            } else {
+                format!(
+                    "The NIC has one queue; spread receive processing with RPS \
+                     (/sys/class/net/{name}/queues/rx-0/rps_cpus)"
+                )
+            };
+            push(
+                Severity::Warning,
+                IssueCategory::SingleQueue,
+                format!("{name} has a single receive queue on a {cpus}-CPU host"),
+                details,
+            );
+        }
+    }
+
+    issues
+}
+
+/// Fastest speed (Mb/s) among the modes both ends advertise, from
+/// ethtool mode names such as `10000baseT/Full`. `None` when the
+/// link partner's modes aren't reported.
+fn best_common_speed(modes: &LinkModes) -> Option<u32> {
+    modes
+        .advertised
+        .active_names()
+        .into_iter()
+        .filter(|m| modes.peer.is_set(m))
+        .filter_map(|m| {
+            let digits = m.find(|c: char| !c.is_ascii_digit())?;
+            if !m[digits..].starts_with("base") {
+                return None;
+            }
+            m[..digits].parse().ok()
+        })
+        .max()
+}
+
 // ============================================================================
 // Issue Stream
 // ============================================================================
@@ -1484,6 +2527,396 @@ mod tests {
         assert_eq!(rates.total_pps(), 30);
     }
 
+    fn bitset(names: &[&str]) -> crate::netlink::genl::ethtool::EthtoolBitset {
+        let mut b = crate::netlink::genl::ethtool::EthtoolBitset::new();
+        for n in names {
+            b.set(n, true);
+        }
+        b
+    }
+
+    fn nic_issues(nic: &NicSettings, cpus: usize) -> Vec<Issue> {
+        detect_nic_issues(
+            &DiagnosticsConfig::default(),
+            "eth0",
+            nic,
+            cpus,
+            Instant::now(),
+        )
+    }
+
+    fn categories(issues: &[Issue]) -> Vec<IssueCategory> {
+        issues.iter().map(|i| i.category).collect()
+    }
+
+    #[test]
+    fn nic_checks_quiet_for_healthy_nic() {
+        let nic = NicSettings {
+            link_modes: Some(LinkModes {
+                autoneg: true,
+                speed: Some(10_000),
+                duplex: Some(Duplex::Full),
+                advertised: bitset(&["1000baseT/Full", "10000baseT/Full"]),
+                peer: bitset(&["10000baseT/Full"]),
+                ..Default::default()
+            }),
+            features: Some(Features {
+                hw: bitset(&["rx-gro", "tx-tcp-segmentation"]),
+                active: bitset(&["rx-gro", "tx-tcp-segmentation"]),
+                ..Default::default()
+            }),
+            pause: Some(Pause {
+                rx: Some(true),
+                tx: Some(true),
+                ..Default::default()
+            }),
+            rings: Some(Rings {
+                rx: Some(4096),
+                rx_max: Some(4096),
+                tx: Some(2048),
+                tx_max: Some(4096),
+                ..Default::default()
+            }),
+            channels: Some(Channels {
+                combined_count: Some(8),
+                combined_max: Some(8),
+                ..Default::default()
+            }),
+        };
+        assert!(nic_issues(&nic, 8).is_empty());
+    }
+
+    #[test]
+    fn nic_checks_flag_duplex_and_downshift() {
+        let nic = NicSettings {
+            link_modes: Some(LinkModes {
+                autoneg: true,
+                speed: Some(100),
+                duplex: Some(Duplex::Half),
+                advertised: bitset(&["100baseT/Half", "1000baseT/Full", "Autoneg"]),
+                peer: bitset(&["100baseT/Half", "1000baseT/Full", "Autoneg"]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let issues = nic_issues(&nic, 1);
+        assert_eq!(
+            categories(&issues),
+            [IssueCategory::DuplexMismatch, IssueCategory::DuplexMismatch]
+        );
+        assert!(issues[1].message.contains("1000 Mb/s"), "{}", issues[1]);
+    }
+
+    #[test]
+    fn nic_checks_flag_offloads_only_on_fast_links() {
+        let features = Features {
+            hw: bitset(&["rx-gro", "tx-tcp-segmentation"]),
+            active: bitset(&["tx-tcp-segmentation"]),
+            ..Default::default()
+        };
+        let at = |speed| NicSettings {
+            link_modes: Some(LinkModes {
+                speed: Some(speed),
+                duplex: Some(Duplex::Full),
+                ..Default::default()
+            }),
+            features: Some(features.clone()),
+            ..Default::default()
+        };
+        assert!(nic_issues(&at(1000), 1).is_empty());
+        let issues = nic_issues(&at(25_000), 1);
+        assert_eq!(categories(&issues), [IssueCategory::OffloadDisabled]);
+        assert!(issues[0].details.as_deref().unwrap().contains("gro on"));
+    }
+
+    #[test]
+    fn nic_checks_flag_pause_rings_and_single_queue() {
+        let nic = NicSettings {
+            pause: Some(Pause {
+                rx: Some(true),
+                tx: Some(false),
+                ..Default::default()
+            }),
+            rings: Some(Rings {
+                rx: Some(256),
+                rx_max: Some(4096),
+                ..Default::default()
+            }),
+            channels: Some(Channels {
+                combined_count: Some(1),
+                combined_max: Some(16),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let issues = nic_issues(&nic, 8);
+        assert_eq!(
+            categories(&issues),
+            [
+                IssueCategory::PauseMismatch,
+                IssueCategory::SmallRingBuffer,
+                IssueCategory::SingleQueue,
+            ]
+        );
+        assert!(
+            issues[1]
+                .details
+                .as_deref()
+                .unwrap()
+                .contains("-G eth0 rx 4096")
+        );
+        assert!(issues[2].details.as_deref().unwrap().contains("combined 8"));
+
+        // Below the CPU threshold a single queue is fine.
+        assert!(!categories(&nic_issues(&nic, 2)).contains(&IssueCategory::SingleQueue));
+    }
+
+    fn addr(ip: &str, ifindex: u32) -> AddressMessage {
+        crate::netlink::messages::AddressMessageBuilder::new()
+            .address(ip.parse().unwrap())
+            .prefix_len(24)
+            .ifindex(ifindex)
+            .build()
+    }
+
+    fn route(dst: &str, len: u8) -> crate::netlink::messages::RouteMessageBuilder {
+        crate::netlink::messages::RouteMessageBuilder::new()
+            .destination(dst.parse().unwrap(), len)
+            .table(254)
+            .route_type(RouteType::Unicast)
+    }
+
+    #[test]
+    fn duplicate_addresses_across_interfaces() {
+        let a = [
+            addr("10.0.0.1", 2),
+            addr("10.0.0.1", 3),
+            addr("10.0.0.2", 2),
+        ];
+        let names = ["eth0", "eth1", "eth0"];
+        let issues = detect_duplicate_addresses(
+            a.iter().zip(names).map(|(a, n)| (a, n.to_string())),
+            Instant::now(),
+        );
+        assert_eq!(categories(&issues), [IssueCategory::DuplicateAddress]);
+        assert_eq!(issues[0].message, "10.0.0.1 is configured on eth0, eth1");
+
+        // Link-scoped addresses repeat on every link by design.
+        let ll = crate::netlink::messages::AddressMessageBuilder::new()
+            .address("fe80::1".parse().unwrap())
+            .scope(Scope::Link)
+            .build();
+        let issues = detect_duplicate_addresses(
+            [(&ll, "eth0".to_string()), (&ll, "eth1".to_string())],
+            Instant::now(),
+        );
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn dad_failed_flag() {
+        let a = crate::netlink::messages::AddressMessageBuilder::new()
+            .address("fd00::1".parse().unwrap())
+            .flags(0x08)
+            .build();
+        assert!(a.is_dad_failed());
+        assert!(!addr("10.0.0.1", 1).is_dad_failed());
+    }
+
+    #[test]
+    fn route_checks_flag_conflicts_and_dead_nexthops() {
+        use crate::netlink::messages::ParsedNextHop;
+
+        let names: HashMap<u32, String> = [(2, "eth0".to_string()), (3, "eth1".to_string())].into();
+        let up: HashMap<u32, bool> = [(2, true), (3, false)].into();
+        let nexthop = |ifindex, gw: &str| ParsedNextHop {
+            ifindex,
+            weight: 1,
+            flags: 0,
+            gateway: Some(gw.parse().unwrap()),
+        };
+        let routes = [
+            // Two gateways for the same prefix and metric.
+            route("10.1.0.0", 16)
+                .gateway("192.0.2.1".parse().unwrap())
+                .build(),
+            route("10.1.0.0", 16)
+                .gateway("192.0.2.2".parse().unwrap())
+                .build(),
+            // Same prefix with a different metric is a failover route.
+            route("10.2.0.0", 16)
+                .gateway("192.0.2.1".parse().unwrap())
+                .build(),
+            route("10.2.0.0", 16)
+                .gateway("192.0.2.2".parse().unwrap())
+                .priority(200)
+                .build(),
+            // Connected subnet, and a gatewayed route inside it.
+            route("192.0.2.0", 24)
+                .protocol(RouteProtocol::Kernel)
+                .oif(2)
+                .build(),
+            route("192.0.2.128", 25)
+                .gateway("198.51.100.1".parse().unwrap())
+                .build(),
+            // ECMP with one nexthop on a down link.
+            route("10.3.0.0", 16)
+                .multipath(vec![nexthop(2, "192.0.2.1"), nexthop(3, "198.51.100.1")])
+                .build(),
+        ];
+
+        let issues = detect_route_issues(&routes, &names, &up, Instant::now());
+        assert_eq!(
+            categories(&issues),
+            [
+                IssueCategory::ConflictingRoute,
+                IssueCategory::ConflictingRoute,
+                IssueCategory::NexthopDown,
+            ]
+        );
+        assert!(issues[0].message.contains("10.1.0.0/16"), "{}", issues[0]);
+        assert!(
+            issues[1].message.contains("192.0.2.0/24 on eth0"),
+            "{}",
+            issues[1]
+        );
+        assert_eq!(issues[2].interface.as_deref(), Some("eth1"));
+        assert_eq!(issues[2].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn rp_filter_check_only_flags_strict_uplinks_of_multihomed_hosts() {
+        let routes = [
+            route("0.0.0.0", 0).oif(2).build(),
+            route("0.0.0.0", 0).oif(3).priority(200).build(),
+            route("0.0.0.0", 0).oif(2).priority(300).build(),
+            // Not in the main table.
+            route("0.0.0.0", 0).oif(4).table(100).build(),
+            route("10.0.0.0", 8).oif(5).build(),
+        ];
+        assert_eq!(default_route_interfaces(&routes), [2, 3]);
+
+        let uplinks = [
+            ("eth0".to_string(), RpFilter::Strict),
+            ("eth1".to_string(), RpFilter::Loose),
+        ];
+        let issues = detect_rp_filter_issues(&uplinks, Instant::now());
+        assert_eq!(categories(&issues), [IssueCategory::StrictRpFilter]);
+        assert_eq!(issues[0].interface.as_deref(), Some("eth0"));
+
+        // A single uplink can't receive asymmetric replies.
+        assert!(detect_rp_filter_issues(&uplinks[..1], Instant::now()).is_empty());
+    }
+
+    #[test]
+    fn neighbor_table_checks_grade_by_gc_thresholds() {
+        let table = |family, entries| NeighborTableUsage {
+            family,
+            entries,
+            gc_thresh1: 128,
+            gc_thresh2: 512,
+            gc_thresh3: 1024,
+        };
+        let severities = |tables: &[NeighborTableUsage]| {
+            detect_neighbor_table_issues(tables, 0.9, Instant::now())
+                .iter()
+                .map(|i| i.severity)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(severities(&[table(AF_INET, 100), table(AF_INET6, 512)]).is_empty());
+        assert_eq!(severities(&[table(AF_INET, 600)]), [Severity::Info]);
+        assert_eq!(severities(&[table(AF_INET6, 950)]), [Severity::Warning]);
+        assert_eq!(severities(&[table(AF_INET, 1024)]), [Severity::Critical]);
+
+        let issues = detect_neighbor_table_issues(&[table(AF_INET6, 950)], 0.9, Instant::now());
+        assert_eq!(issues[0].category, IssueCategory::NeighborTablePressure);
+        assert!(issues[0].message.starts_with("ipv6 neighbour table at 93%"));
+        assert!(
+            issues[0]
+                .details
+                .as_deref()
+                .unwrap()
+                .contains("net.ipv6.neigh.default.gc_thresh2")
+        );
+
+        assert!(counts_against_gc(NeighborState::Stale, 0));
+        assert!(!counts_against_gc(NeighborState::Permanent, 0));
+        assert!(!counts_against_gc(
+            NeighborState::Reachable,
+            ntf::EXT_LEARNED
+        ));
+    }
+
+    #[cfg(feature = "sockdiag")]
+    #[test]
+    fn netlink_checks_flag_full_queues_and_drops() {
+        use crate::sockdiag::{NetlinkSocket, ProcessRef, SocketOwnerMap, types::MemInfo};
+
+        let sock = |portid, rmem_alloc, drops| NetlinkSocket {
+            protocol: 0,
+            portid,
+            dst_portid: 0,
+            dst_group: 0,
+            groups: 1,
+            memberships: vec![1],
+            flags: Some(0),
+            inode: portid + 100,
+            cookie: 0,
+            recv_q: Some(rmem_alloc),
+            send_q: Some(0),
+            mem_info: Some(MemInfo {
+                rmem_alloc,
+                rcvbuf: Some(200_000),
+                drops: Some(drops),
+                ..Default::default()
+            }),
+        };
+        let mut owners = SocketOwnerMap::new();
+        owners.insert(
+            101,
+            ProcessRef {
+                pid: 42,
+                start_time: 1,
+                comm: "netd".into(),
+                fd: 5,
+            },
+        );
+        let sockets = [
+            sock(0, 190_000, 9), // the kernel's own socket
+            sock(1, 150_000, 0),
+            sock(2, 10_000, 3),
+            sock(3, 190_000, 3),
+            sock(4, 10_000, 0),
+        ];
+
+        let issues = detect_netlink_socket_issues(&sockets, &owners, 0.5, Instant::now());
+        let severities: Vec<_> = issues.iter().map(|i| i.severity).collect();
+        assert_eq!(
+            severities,
+            [Severity::Warning, Severity::Warning, Severity::Error]
+        );
+        assert!(
+            issues
+                .iter()
+                .all(|i| i.category == IssueCategory::NetlinkBacklog)
+        );
+        assert!(issues[0].message.contains("netd (pid 42, port 1)"));
+        assert!(issues[0].message.contains("(75%)"));
+        assert!(issues[1].message.ends_with("port 2 has dropped 3 messages"));
+    }
+
+    #[test]
+    fn prefix_containment() {
+        let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+        assert!(prefix_contains(ip("10.0.0.0"), 8, ip("10.255.0.1")));
+        assert!(!prefix_contains(ip("10.0.0.0"), 8, ip("11.0.0.1")));
+        assert!(prefix_contains(ip("0.0.0.0"), 0, ip("203.0.113.9")));
+        assert!(prefix_contains(ip("fd00::"), 64, ip("fd00::42")));
+        assert!(!prefix_contains(ip("fd00::"), 64, ip("fd00:0:0:1::42")));
+        assert!(!prefix_contains(ip("10.0.0.0"), 8, ip("fd00::1")));
+    }
+
     #[test]
     fn test_config_defaults() {
         let config = DiagnosticsConfig::default();
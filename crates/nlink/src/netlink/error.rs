@@ -205,6 +205,15 @@ pub enum Error {
         interface: String,
     },
 
+    /// Qdisc already exists (an exclusive add hit an occupied slot).
+    #[error("qdisc already exists: {kind} on {interface}")]
+    QdiscExists {
+        /// The qdisc kind (e.g., "netem", "htb").
+        kind: String,
+        /// The interface name.
+        interface: String,
+    },
+
     /// Generic Netlink family not found.
     #[error("GENL family not found: {name}")]
     FamilyNotFound {
@@ -315,6 +324,23 @@ pub enum Error {
         #[source]
         source: io::Error,
     },
+
+    /// A connection and the calling thread are in different network
+    /// namespaces, so interface indices resolved through `/sys/class/net`
+    /// do not apply to the connection.
+    ///
+    /// Returned by
+    /// [`Connection::ensure_thread_namespace`](crate::netlink::Connection::ensure_thread_namespace).
+    /// Recover via [`Error::is_namespace_mismatch`]; the fix is to resolve
+    /// the interface over the connection (`resolve_interface`,
+    /// `get_link_by_name`) instead.
+    #[error("connection is bound to netns {connection} but the calling thread is in {thread}")]
+    NamespaceMismatch {
+        /// The namespace the connection's socket is bound to.
+        connection: crate::netlink::namespace::NamespaceId,
+        /// The namespace of the calling thread.
+        thread: crate::netlink::namespace::NamespaceId,
+    },
 }
 
 /// Structured validation error information.
@@ -536,6 +562,14 @@ impl Error {
         }
     }
 
+    /// Create a qdisc already exists error.
+    pub fn qdisc_exists(kind: impl Into<String>, interface: impl Into<String>) -> Self {
+        Self::QdiscExists {
+            kind: kind.into(),
+            interface: interface.into(),
+        }
+    }
+
     /// Create a GENL family not found error.
     pub fn family_not_found(name: impl Into<String>) -> Self {
         Self::FamilyNotFound { name: name.into() }
@@ -587,12 +621,14 @@ impl Error {
     /// carrying `ErrorKind::AlreadyExists` without a raw errno —
     /// the shape `namespace::create_path` builds for its
     /// already-exists rejection (same predicate-widening move as
-    /// Plan 212's `is_not_found`).
+    /// Plan 212's `is_not_found`). Plus the typed `QdiscExists`
+    /// variant.
     pub fn is_already_exists(&self) -> bool {
         if self.errno() == Some(libc::EEXIST) {
             return true;
         }
-        matches!(self, Self::Io(e) if e.kind() == std::io::ErrorKind::AlreadyExists)
+        matches!(self, Self::QdiscExists { .. })
+            || matches!(self, Self::Io(e) if e.kind() == std::io::ErrorKind::AlreadyExists)
     }
 
     /// Check if this is a "device busy" error (EBUSY).
@@ -824,6 +860,14 @@ impl Error {
         matches!(self, Self::NamespaceRestoreFailed { .. })
     }
 
+    /// Check if this is a [`Error::NamespaceMismatch`].
+    ///
+    /// The connection lives in another network namespace than the calling
+    /// thread, so sysfs-resolved interface indices would be wrong for it.
+    pub fn is_namespace_mismatch(&self) -> bool {
+        matches!(self, Self::NamespaceMismatch { .. })
+    }
+
     /// Check if this is an "address already in use" error (EADDRINUSE).
     ///
     /// This typically occurs when trying to add an IP address that is
@@ -1008,6 +1052,14 @@ mod tests {
         assert!(!Error::Io(io::Error::new(io::ErrorKind::NotFound, "gone")).is_already_exists());
     }
 
+    #[test]
+    fn qdisc_exists_is_already_exists() {
+        let err = Error::qdisc_exists("htb", "eth0");
+        assert!(err.is_already_exists());
+        assert!(!err.is_not_found());
+        assert_eq!(err.to_string(), "qdisc already exists: htb on eth0");
+    }
+
     // Plan 212 M9 — is_not_found must match Error::Io(ENOENT/ENODEV)
     // through the common `errno()` accessor (sibling predicates
     // `is_busy`, `is_permission_denied`, `is_already_exists` already
@@ -0,0 +1,274 @@
+//! Bond failover events.
+//!
+//! A bond in active-backup, balance-tlb or balance-alb mode reports its
+//! current active member in `IFLA_BOND_ACTIVE_SLAVE`, and each member
+//! reports its own MII status in `IFLA_INFO_SLAVE_DATA`. Both arrive as
+//! plain `RTM_NEWLINK` notifications mixed in with every other link
+//! update. [`FailoverTracker`] remembers them per bond and turns a change
+//! into a [`FailoverEvent`], so HA tooling can react to "traffic moved
+//! from `eth0` to `eth1`" without diffing raw attributes.
+//!
+//! Modes without a single active member (balance-rr, 802.3ad, ...) never
+//! produce [`FailoverEvent::FailoverOccurred`], only member link changes.
+//! Team devices don't expose port state over rtnetlink (it lives in the
+//! team generic netlink family), so only bonds are covered.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//! use nlink::netlink::failover::{FailoverEvent, FailoverMonitor};
+//! use tokio_stream::StreamExt;
+//!
+//! let mut monitor = FailoverMonitor::new(Connection::<Route>::new()?).await?;
+//! while let Some(event) = monitor.next().await {
+//!     if let FailoverEvent::FailoverOccurred { master, from, to } = event? {
+//!         println!("bond if{master}: active member {from:?} -> {to:?}");
+//!     }
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tokio_stream::Stream;
+
+use super::{
+    connection::{Connection, RtnetlinkGroup},
+    error::Result,
+    events::NetworkEvent,
+    messages::{LinkMessage, MiiStatus},
+    protocol::Route,
+    stream::OwnedEventStream,
+};
+
+/// A change in which member carries a bond's traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FailoverEvent {
+    /// The bond's active member changed.
+    FailoverOccurred {
+        /// Bond ifindex.
+        master: u32,
+        /// Previously active member, `None` if the bond had none.
+        from: Option<u32>,
+        /// Newly active member, `None` if no member is usable.
+        to: Option<u32>,
+    },
+    /// A member's MII link status changed. Usually precedes a failover
+    /// when the active member loses link.
+    MemberLinkChanged {
+        /// Bond ifindex.
+        master: u32,
+        /// Member ifindex.
+        member: u32,
+        /// New MII status.
+        status: MiiStatus,
+    },
+}
+
+/// Turns link events into bond failover events.
+///
+/// Feed it every link message, in order; it has no I/O of its own. The
+/// first message for a bond or member only records its state, so seed
+/// the tracker with a link dump before relying on the first change.
+#[derive(Debug, Default)]
+pub struct FailoverTracker {
+    /// Active member per bond, keyed by bond ifindex.
+    active: HashMap<u32, Option<u32>>,
+    /// `(bond, MII status)` per member, keyed by member ifindex.
+    members: HashMap<u32, (u32, MiiStatus)>,
+}
+
+impl FailoverTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an `RTM_NEWLINK` (`deleted == false`) or `RTM_DELLINK`
+    /// message. Returns the resulting event, or `None` if the message
+    /// changed nothing failover-related.
+    pub fn observe(&mut self, link: &LinkMessage, deleted: bool) -> Option<FailoverEvent> {
+        let ifindex = link.ifindex();
+        if deleted {
+            self.active.remove(&ifindex);
+            self.members.remove(&ifindex);
+            return None;
+        }
+
+        if let Some(bond) = link.bond_info() {
+            let to = bond.active_slave.filter(|&idx| idx != 0);
+            return match self.active.insert(ifindex, to) {
+                Some(from) if from != to => Some(FailoverEvent::FailoverOccurred {
+                    master: ifindex,
+                    from,
+                    to,
+                }),
+                _ => None,
+            };
+        }
+
+        let (Some(master), Some(slave)) = (link.master(), link.bond_slave_info()) else {
+            // Released from its bond, or never enslaved.
+            self.members.remove(&ifindex);
+            return None;
+        };
+        match self.members.insert(ifindex, (master, slave.mii_status)) {
+            Some((prev_master, prev)) if prev_master == master && prev != slave.mii_status => {
+                Some(FailoverEvent::MemberLinkChanged {
+                    master,
+                    member: ifindex,
+                    status: slave.mii_status,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Last known active member of a bond.
+    pub fn active_member(&self, master: u32) -> Option<u32> {
+        self.active.get(&master).copied().flatten()
+    }
+}
+
+/// Stream of bond failover events.
+#[must_use = "streams do nothing unless polled"]
+pub struct FailoverMonitor {
+    events: OwnedEventStream<Route>,
+    tracker: FailoverTracker,
+}
+
+impl FailoverMonitor {
+    /// Learn the current bond state from a link dump, then subscribe
+    /// `conn` to link events.
+    pub async fn new(conn: Connection<Route>) -> Result<Self> {
+        let mut tracker = FailoverTracker::new();
+        for link in conn.get_links().await? {
+            tracker.observe(&link, false);
+        }
+        conn.subscribe(&[RtnetlinkGroup::Link])?;
+        Ok(Self {
+            events: conn.into_events().await,
+            tracker,
+        })
+    }
+
+    /// Borrow the tracker, e.g. to look up a bond's active member.
+    pub fn tracker(&self) -> &FailoverTracker {
+        &self.tracker
+    }
+}
+
+impl Stream for FailoverMonitor {
+    type Item = Result<FailoverEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let event = match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            let (link, deleted) = match &event {
+                NetworkEvent::NewLink(link) => (link, false),
+                NetworkEvent::DelLink(link) => (link, true),
+                _ => continue,
+            };
+            if let Some(event) = self.tracker.observe(link, deleted) {
+                return Poll::Ready(Some(Ok(event)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::messages::{LinkInfo, LinkMessageBuilder};
+
+    const IFLA_BOND_ACTIVE_SLAVE: u16 = 2;
+    const IFLA_BOND_SLAVE_MII_STATUS: u16 = 2;
+
+    fn attr_u32(attr_type: u16, value: u32) -> Vec<u8> {
+        let mut buf = 8u16.to_ne_bytes().to_vec();
+        buf.extend_from_slice(&attr_type.to_ne_bytes());
+        buf.extend_from_slice(&value.to_ne_bytes());
+        buf
+    }
+
+    fn bond(active: Option<u32>) -> LinkMessage {
+        let mut msg = LinkMessageBuilder::new().ifindex(10).name("bond0").build();
+        msg.link_info = Some(LinkInfo {
+            kind: Some("bond".into()),
+            data: Some(
+                active
+                    .map(|idx| attr_u32(IFLA_BOND_ACTIVE_SLAVE, idx))
+                    .unwrap_or_default(),
+            ),
+            ..Default::default()
+        });
+        msg
+    }
+
+    fn member(ifindex: i32, mii_up: bool) -> LinkMessage {
+        let mut msg = LinkMessageBuilder::new()
+            .ifindex(ifindex)
+            .master(10)
+            .build();
+        let mut data = 5u16.to_ne_bytes().to_vec();
+        data.extend_from_slice(&IFLA_BOND_SLAVE_MII_STATUS.to_ne_bytes());
+        data.extend_from_slice(&[if mii_up { 0 } else { 1 }, 0, 0, 0]);
+        msg.link_info = Some(LinkInfo {
+            slave_kind: Some("bond".into()),
+            slave_data: Some(data),
+            ..Default::default()
+        });
+        msg
+    }
+
+    #[test]
+    fn tracker_reports_active_member_changes() {
+        let mut tracker = FailoverTracker::new();
+
+        // Seeding records state without events.
+        assert_eq!(tracker.observe(&bond(Some(2)), false), None);
+        assert_eq!(tracker.observe(&member(2, true), false), None);
+        assert_eq!(tracker.observe(&member(3, true), false), None);
+        assert_eq!(tracker.active_member(10), Some(2));
+
+        assert_eq!(
+            tracker.observe(&member(2, false), false),
+            Some(FailoverEvent::MemberLinkChanged {
+                master: 10,
+                member: 2,
+                status: MiiStatus::Down,
+            })
+        );
+        assert_eq!(
+            tracker.observe(&bond(Some(3)), false),
+            Some(FailoverEvent::FailoverOccurred {
+                master: 10,
+                from: Some(2),
+                to: Some(3),
+            })
+        );
+        // Same active member again (e.g. an MTU change on the bond).
+        assert_eq!(tracker.observe(&bond(Some(3)), false), None);
+
+        assert_eq!(
+            tracker.observe(&bond(None), false),
+            Some(FailoverEvent::FailoverOccurred {
+                master: 10,
+                from: Some(3),
+                to: None,
+            })
+        );
+        assert_eq!(tracker.observe(&bond(None), true), None);
+        assert_eq!(tracker.active_member(10), None);
+    }
+}
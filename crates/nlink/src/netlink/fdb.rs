@@ -671,8 +671,10 @@ impl Connection<Route> {
         mac: [u8; 6],
         vlan: Option<u16>,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.del_fdb_by_index(ifindex, mac, vlan).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.del_fdb_by_index(ifindex, mac, vlan)
+        })
+        .await
     }
 
     /// Delete an FDB entry by interface index, MAC address, and optional VLAN.
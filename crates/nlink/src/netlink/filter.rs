@@ -45,6 +45,7 @@ use super::{
     error::{Error, Result},
     interface_ref::InterfaceRef,
     message::NlMsgType,
+    namespace::NamespaceId,
     protocol::Route,
     tc_handle::TcHandle,
     types::tc::{
@@ -114,6 +115,15 @@ pub trait FilterConfig: Send + Sync {
     fn priority(&self) -> Option<u16> {
         None
     }
+
+    /// Namespace an interface name in this filter's actions was resolved
+    /// in outside netlink (see [`ActionList::resolved_namespace`]).
+    ///
+    /// Defaulted so external impls stay source-compatible; the filters
+    /// nlink ships that carry actions report their list's.
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        None
+    }
 }
 
 /// The ethertype `add_filter` falls back to when a [`FilterConfig`] leaves
@@ -157,6 +167,40 @@ pub const DEFAULT_FILTER_PROTOCOL: u16 = 0x0800;
 ///     .match_src_ipv4("192.168.1.0".parse().unwrap(), 24)
 ///     .build();
 /// ```
+///
+/// # Hash tables
+///
+/// A filter with a [`divisor`](Self::divisor) and no keys creates a
+/// hash table; its handle (built with [`u32_handle`]) names the table.
+/// Other filters are placed in a table's bucket with [`ht`](Self::ht)
+/// and jump to a table with [`link`](Self::link), hashing on the
+/// [`hashkey`](Self::hashkey) bytes. [`skip_ipv4_header`](Self::skip_ipv4_header)
+/// makes the linked table's offsets relative to the transport header:
+///
+/// ```ignore
+/// use nlink::TcHandle;
+/// use nlink::netlink::filter::{U32Filter, u32_handle};
+///
+/// // tc filter add dev eth0 parent 1: prio 5 handle 2: u32 divisor 256
+/// let table = TcHandle::from_raw(u32_handle(2, 0, 0));
+/// conn.add_filter_full("eth0", parent, Some(table), 0x0800, 5,
+///     U32Filter::new().divisor(256)).await?;
+///
+/// // Bucket 0x50 of table 2: holds the port-80 rule.
+/// conn.add_filter_full("eth0", parent, None, 0x0800, 5, U32Filter::new()
+///     .ht(u32_handle(2, 0x50, 0))
+///     .match_dst_port(80)
+///     .classid(TcHandle::new(1, 0x10))).await?;
+///
+/// // From the root table, hash TCP packets on the low byte of the
+/// // destination port into table 2:.
+/// conn.add_filter_full("eth0", parent, None, 0x0800, 5, U32Filter::new()
+///     .ht(u32_handle(0x800, 0, 0))
+///     .match_ip_proto(6)
+///     .link(u32_handle(2, 0, 0))
+///     .hashkey(0x0000_00ff, 0)
+///     .skip_ipv4_header()).await?;
+/// ```
 #[derive(Debug, Clone, Default)]
 #[must_use = "builders do nothing unless used"]
 pub struct U32Filter {
@@ -182,6 +226,71 @@ pub struct U32Filter {
     chain: Option<u32>,
     /// Filter flags (`TCA_CLS_FLAGS_SKIP_HW`/`SKIP_SW`).
     flags: u32,
+    /// Next-header offset — `TC_U32_OFFSET`/`VAROFFSET`/`EAT` bits plus
+    /// the `off`/`offoff`/`offmask`/`offshift` selector fields.
+    offset: U32Offset,
+    /// Actions to run on match.
+    actions: Option<ActionList>,
+}
+
+/// How a u32 selector computes the offset that `nexthdr`-relative
+/// keys and linked tables see (tc(8) `offset plus N at AT mask M
+/// shift S eat`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct U32Offset {
+    flags: u8,
+    plus: u16,
+    at: i16,
+    mask: u16,
+    shift: u8,
+}
+
+/// Build a u32 filter handle from tc(8)'s `htid:bucket:node` parts.
+///
+/// `htid` is the 12-bit hash table id (`800` is the root table the
+/// kernel creates), `bucket` the hash bucket and `node` the 12-bit
+/// item id. Use `u32_handle(htid, 0, 0)` for a table (in
+/// [`U32Filter::link`], or as the handle of a
+/// [`divisor`](U32Filter::divisor) filter) and `u32_handle(htid,
+/// bucket, 0)` for [`U32Filter::ht`].
+pub const fn u32_handle(htid: u16, bucket: u8, node: u16) -> u32 {
+    ((htid as u32 & 0xfff) << 20) | ((bucket as u32) << 12) | (node as u32 & 0xfff)
+}
+
+/// Parse a u32 handle in tc(8) notation: `HTID:[BUCKET[:NODE]]`, or
+/// a bare `NODE` — all hex, so `800::1` is node 1 of the root table
+/// and `2:50:` is bucket 0x50 of table 2.
+pub fn parse_u32_handle(s: &str) -> Result<u32> {
+    parse_u32_handle_as(s, "handle")
+}
+
+/// [`parse_u32_handle`] with `what` naming the token in errors.
+fn parse_u32_handle_as(s: &str, what: &str) -> Result<u32> {
+    let err = |why: &str| Error::InvalidMessage(format!("u32: invalid {what} `{s}`: {why}"));
+    let part = |p: &str, max: u32, what: &str| -> Result<u32> {
+        if p.is_empty() {
+            return Ok(0);
+        }
+        let p = p.strip_prefix("0x").unwrap_or(p);
+        let v = u32::from_str_radix(p, 16).map_err(|_| err(&format!("{what} is not hex")))?;
+        if v > max {
+            return Err(err(&format!("{what} exceeds {max:#x}")));
+        }
+        Ok(v)
+    };
+    let parts: Vec<&str> = s.split(':').collect();
+    let (htid, bucket, node) = match parts.as_slice() {
+        [node] => ("", "", *node),
+        [htid, bucket] => (*htid, *bucket, ""),
+        [htid, bucket, node] => (*htid, *bucket, *node),
+        _ => return Err(err("expected HTID:BUCKET:NODE")),
+    };
+    if htid.is_empty() && bucket.is_empty() && node.is_empty() {
+        return Err(err("empty"));
+    }
+    Ok((part(htid, 0xfff, "table id")? << 20)
+        | (part(bucket, 0xff, "bucket")? << 12)
+        | part(node, 0xfff, "node")?)
 }
 
 impl U32Filter {
@@ -280,15 +389,17 @@ impl U32Filter {
         self
     }
 
-    /// Link to a hash table.
+    /// Jump to a hash table on match (`TCA_U32_LINK`). Pass a table
+    /// handle, `u32_handle(htid, 0, 0)`; the bucket is picked by
+    /// [`hashkey`](Self::hashkey).
     pub fn link(mut self, link: u32) -> Self {
         self.link = Some(link);
         self
     }
 
-    /// Set the hash table this filter belongs to (`TCA_U32_HASH`).
-    /// Pass the raw u32 form of a tc(8) handle (e.g.
-    /// `TcHandle::new(0x100, 0).as_raw()` for `100:`).
+    /// Set the hash table bucket this filter belongs to
+    /// (`TCA_U32_HASH`), as a [`u32_handle`] — `u32_handle(0x100, 0,
+    /// 0)` for tc(8)'s `100:`, `u32_handle(0x100, 5, 0)` for `100:5:`.
     pub fn ht(mut self, handle: u32) -> Self {
         self.ht = Some(handle);
         self
@@ -311,6 +422,46 @@ impl U32Filter {
         self
     }
 
+    /// Add a fixed amount to the next-header offset (tc(8) `offset
+    /// plus N`).
+    pub fn offset_plus(mut self, plus: u16) -> Self {
+        self.offset.flags |= u32_mod::TC_U32_OFFSET;
+        self.offset.plus = plus;
+        self
+    }
+
+    /// Compute the next-header offset from the packet: the 16-bit
+    /// big-endian word at `at`, masked with `mask` and shifted right
+    /// by `shift` (tc(8) `offset at AT mask MASK shift SHIFT`).
+    pub fn offset_at(mut self, at: i16, mask: u16, shift: u8) -> Self {
+        self.offset.flags |= u32_mod::TC_U32_VAROFFSET;
+        self.offset.at = at;
+        self.offset.mask = mask;
+        self.offset.shift = shift;
+        self
+    }
+
+    /// Advance the packet pointer by the computed offset before a
+    /// [`link`](Self::link)ed table is searched, so its keys are
+    /// relative to the next header (tc(8) `eat`).
+    pub fn eat(mut self) -> Self {
+        self.offset.flags |= u32_mod::TC_U32_EAT;
+        self
+    }
+
+    /// Set the next-header offset to the IPv4 header length (IHL × 4),
+    /// so port matches and linked tables skip IP options. Equivalent
+    /// to `offset at 0 mask 0x0f00 shift 6`.
+    pub fn skip_ipv4_header(self) -> Self {
+        self.offset_at(0, 0x0f00, 6)
+    }
+
+    /// Attach actions to run when the filter matches (`TCA_U32_ACT`).
+    pub fn actions(mut self, actions: ActionList) -> Self {
+        self.actions = Some(actions);
+        self
+    }
+
     /// Set the chain index for this filter.
     ///
     /// Chains provide logical grouping of filters for better performance
@@ -383,13 +534,17 @@ impl U32Filter {
     /// - `divisor <n>` — divisor for the bucket count when this
     ///   filter creates a hash table. Combine with no keys for the
     ///   table-create case.
-    /// - `ht <handle>` — hash table this filter belongs to,
-    ///   encoded as `TCA_U32_HASH`. Handle uses tc(8) notation
-    ///   (e.g. `100:` → 0x01000000 via `TcHandle`).
+    /// - `ht <handle>` — hash table bucket this filter belongs to,
+    ///   encoded as `TCA_U32_HASH`. Handle uses tc(8)'s u32 notation
+    ///   (`100:` → table 0x100, `100:5:` → its bucket 5; see
+    ///   [`parse_u32_handle`]).
     /// - `link <handle>` — next-hop hash table to chase on match.
+    ///   Must name a whole table (no bucket or node).
     /// - `hashkey mask <hex> at <offset>` — bytes of the packet
     ///   used to compute the hash bucket index. `mask` is hex,
     ///   `offset` is decimal or hex (i16 range).
+    /// - `offset [plus <n>] [at <offset> mask <hex> shift <n>] [eat]`
+    ///   — next-header offset (see [`offset_at`](Self::offset_at)).
     ///
     /// # Not yet typed-modelled
     ///
@@ -456,20 +611,23 @@ impl U32Filter {
                 }
                 "ht" => {
                     let s = need_value(params, i, "u32", key)?;
-                    let h = s.parse::<TcHandle>().map_err(|e| {
-                        Error::InvalidMessage(format!("u32: invalid ht handle `{s}`: {e}"))
-                    })?;
-                    f = f.ht(h.as_raw());
+                    f = f.ht(parse_u32_handle_as(s, "ht handle")?);
                     i += 2;
                 }
                 "link" => {
                     let s = need_value(params, i, "u32", key)?;
-                    let h = s.parse::<TcHandle>().map_err(|e| {
-                        Error::InvalidMessage(format!("u32: invalid link handle `{s}`: {e}"))
-                    })?;
-                    f = f.link(h.as_raw());
+                    let h = parse_u32_handle_as(s, "link handle")?;
+                    if h & 0xfffff != 0 {
+                        return Err(Error::InvalidMessage(format!(
+                            "u32: link `{s}` must name a hash table (HTID:), not a bucket or node"
+                        )));
+                    }
+                    f = f.link(h);
                     i += 2;
                 }
+                "offset" => {
+                    i = parse_u32_offset(&mut f, params, i + 1)?;
+                }
                 "hashkey" => {
                     // Form: hashkey mask <hex> at <offset> → 5 tokens total.
                     let mask_kw = params.get(i + 1).copied().ok_or_else(|| {
@@ -522,7 +680,7 @@ impl U32Filter {
                 }
                 other => {
                     return Err(Error::InvalidMessage(format!(
-                        "u32: unknown token `{other}` (supports: match u32|u16|u8 VAL MASK at OFFSET, match ip|tcp|udp ..., classid/flowid, chain, divisor, ht, link, hashkey, skip_hw, skip_sw)"
+                        "u32: unknown token `{other}` (supports: match u32|u16|u8 VAL MASK at OFFSET, match ip|tcp|udp ..., classid/flowid, chain, divisor, ht, link, hashkey, offset, skip_hw, skip_sw)"
                     )));
                 }
             }
@@ -561,6 +719,12 @@ impl FilterConfig for U32Filter {
         Some(self.priority)
     }
 
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions
+            .as_ref()
+            .and_then(ActionList::resolved_namespace)
+    }
+
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()> {
         // Add classid if set
         if let Some(classid) = self.classid {
@@ -588,10 +752,10 @@ impl FilterConfig for U32Filter {
             builder.append_attr(u32_mod::TCA_U32_MARK, mark.as_bytes());
         }
 
-        // Build and add selector if we have keys or a hashkey
-        // configured. Divisor-only filters (which create the hash
-        // table itself) emit just TCA_U32_DIVISOR — no selector.
-        if !self.keys.is_empty() || self.hashkey.is_some() {
+        // Build and add selector if we have keys, a hashkey or an
+        // offset configured. Divisor-only filters (which create the
+        // hash table itself) emit just TCA_U32_DIVISOR — no selector.
+        if !self.keys.is_empty() || self.hashkey.is_some() || self.offset.flags != 0 {
             let mut sel = u32_mod::TcU32Sel::new();
             sel.set_terminal();
             for key in &self.keys {
@@ -602,6 +766,13 @@ impl FilterConfig for U32Filter {
                 sel.hdr.hmask = mask.to_be();
                 sel.hdr.hoff = offset;
             }
+            // Same convention for the next-header offset: offmask is
+            // big-endian, the rest host-order.
+            sel.hdr.flags |= self.offset.flags;
+            sel.hdr.off = self.offset.plus;
+            sel.hdr.offoff = self.offset.at;
+            sel.hdr.offmask = self.offset.mask.to_be();
+            sel.hdr.offshift = self.offset.shift;
             builder.append_attr(u32_mod::TCA_U32_SEL, &sel.to_bytes());
         }
 
@@ -610,8 +781,73 @@ impl FilterConfig for U32Filter {
             builder.append_attr_u32(u32_mod::TCA_U32_FLAGS, self.flags);
         }
 
-        Ok(())
+        write_filter_actions(builder, u32_mod::TCA_U32_ACT, self.actions.as_ref(), None)
+    }
+}
+
+/// Parse the operands of a u32 `offset` token starting at `params[i]`
+/// — any of `plus N`, `at N`, `mask HEX`, `shift N`, `eat` — and
+/// return the index of the first token after them.
+fn parse_u32_offset(f: &mut U32Filter, params: &[&str], mut i: usize) -> Result<usize> {
+    let start = i;
+    let mut var = None::<(i16, u16, u8)>;
+    while let Some(&tok) = params.get(i) {
+        match tok {
+            "plus" => {
+                let s = need_value(params, i, "u32", "offset plus")?;
+                let plus = parse_u32_int("u32", "offset plus", s)?;
+                let plus = u16::try_from(plus).map_err(|_| {
+                    Error::InvalidMessage(format!("u32: offset plus `{s}` out of range for u16"))
+                })?;
+                *f = std::mem::take(f).offset_plus(plus);
+                i += 2;
+            }
+            "at" => {
+                let s = need_value(params, i, "u32", "offset at")?;
+                let at = parse_offset("u32", s)?;
+                let at = i16::try_from(at).map_err(|_| {
+                    Error::InvalidMessage(format!("u32: offset at `{s}` out of range for i16"))
+                })?;
+                var.get_or_insert((0, 0, 0)).0 = at;
+                i += 2;
+            }
+            "mask" => {
+                let s = need_value(params, i, "u32", "offset mask")?;
+                let mask = parse_hex_u32("u32", "offset mask", s)?;
+                let mask = u16::try_from(mask).map_err(|_| {
+                    Error::InvalidMessage(format!("u32: offset mask `{s}` wider than 16 bits"))
+                })?;
+                var.get_or_insert((0, 0, 0)).1 = mask;
+                i += 2;
+            }
+            "shift" => {
+                let s = need_value(params, i, "u32", "offset shift")?;
+                let shift = parse_u32_int("u32", "offset shift", s)?;
+                let shift = u8::try_from(shift)
+                    .ok()
+                    .filter(|s| *s < 16)
+                    .ok_or_else(|| {
+                        Error::InvalidMessage(format!("u32: offset shift `{s}` must be below 16"))
+                    })?;
+                var.get_or_insert((0, 0, 0)).2 = shift;
+                i += 2;
+            }
+            "eat" => {
+                *f = std::mem::take(f).eat();
+                i += 1;
+            }
+            _ => break,
+        }
     }
+    if i == start {
+        return Err(Error::InvalidMessage(
+            "u32: `offset` requires at least one of plus, at, mask, shift, eat".to_string(),
+        ));
+    }
+    if let Some((at, mask, shift)) = var {
+        *f = std::mem::take(f).offset_at(at, mask, shift);
+    }
+    Ok(i)
 }
 
 /// Borrow `params[i + 1]`, returning a kind-prefixed
@@ -854,6 +1090,13 @@ fn parse_port(field: &str, layer: &str, s: &str) -> Result<u16> {
 ///     .dst_ipv4(Ipv4Addr::new(10, 0, 0, 0), 8)
 ///     .dst_port(80)
 ///     .build();
+///
+/// // Drop VNI 42 arriving on a collect-metadata VXLAN device
+/// let filter = FlowerFilter::new()
+///     .enc_key_id(42)
+///     .enc_dst_port(4789)
+///     .actions(ActionList::new().with(GactAction::drop()))
+///     .build();
 /// ```
 #[derive(Debug, Clone, Default)]
 #[must_use = "builders do nothing unless used"]
@@ -864,18 +1107,22 @@ pub struct FlowerFilter {
     eth_type: Option<u16>,
     /// IP protocol.
     ip_proto: Option<u8>,
-    /// Source IPv4 address and prefix length.
-    src_ipv4: Option<(Ipv4Addr, u8)>,
-    /// Destination IPv4 address and prefix length.
-    dst_ipv4: Option<(Ipv4Addr, u8)>,
-    /// Source IPv6 address and prefix length.
-    src_ipv6: Option<(Ipv6Addr, u8)>,
-    /// Destination IPv6 address and prefix length.
-    dst_ipv6: Option<(Ipv6Addr, u8)>,
+    /// Source IPv4 address and mask.
+    src_ipv4: Option<(Ipv4Addr, Ipv4Addr)>,
+    /// Destination IPv4 address and mask.
+    dst_ipv4: Option<(Ipv4Addr, Ipv4Addr)>,
+    /// Source IPv6 address and mask.
+    src_ipv6: Option<(Ipv6Addr, Ipv6Addr)>,
+    /// Destination IPv6 address and mask.
+    dst_ipv6: Option<(Ipv6Addr, Ipv6Addr)>,
     /// Source port.
     src_port: Option<u16>,
     /// Destination port.
     dst_port: Option<u16>,
+    /// Source port range (inclusive).
+    src_port_range: Option<(u16, u16)>,
+    /// Destination port range (inclusive).
+    dst_port_range: Option<(u16, u16)>,
     /// Source MAC address.
     src_mac: Option<[u8; 6]>,
     /// Destination MAC address.
@@ -890,6 +1137,20 @@ pub struct FlowerFilter {
     ip_ttl: Option<(u8, u8)>,
     /// TCP flags.
     tcp_flags: Option<(u16, u16)>,
+    /// Tunnel key ID (VXLAN VNI, GRE key, Geneve VNI).
+    enc_key_id: Option<u32>,
+    /// Outer source address and mask of a decapsulated packet.
+    enc_src_ip: Option<(IpAddr, IpAddr)>,
+    /// Outer destination address and mask of a decapsulated packet.
+    enc_dst_ip: Option<(IpAddr, IpAddr)>,
+    /// Outer UDP source port.
+    enc_src_port: Option<u16>,
+    /// Outer UDP destination port.
+    enc_dst_port: Option<u16>,
+    /// Outer IP TOS.
+    enc_tos: Option<(u8, u8)>,
+    /// Outer IP TTL.
+    enc_ttl: Option<(u8, u8)>,
     /// Filter flags (skip_hw, skip_sw, etc.).
     flags: u32,
     /// Priority.
@@ -900,6 +1161,8 @@ pub struct FlowerFilter {
     chain: Option<u32>,
     /// Goto chain action (jump to another chain on match).
     goto_chain: Option<u32>,
+    /// Actions to perform on matching packets.
+    actions: Option<ActionList>,
 }
 
 impl FlowerFilter {
@@ -977,51 +1240,96 @@ impl FlowerFilter {
         self
     }
 
+    /// Match SCTP packets.
+    pub fn ip_proto_sctp(mut self) -> Self {
+        self.ip_proto = Some(flower::IPPROTO_SCTP);
+        self
+    }
+
     /// Match source IPv4 address with prefix length.
-    pub fn src_ipv4(mut self, addr: Ipv4Addr, prefix_len: u8) -> Self {
+    pub fn src_ipv4(self, addr: Ipv4Addr, prefix_len: u8) -> Self {
+        self.src_ipv4_masked(addr, ipv4_mask(prefix_len))
+    }
+
+    /// Match source IPv4 address under an arbitrary mask.
+    pub fn src_ipv4_masked(mut self, addr: Ipv4Addr, mask: Ipv4Addr) -> Self {
         if self.eth_type.is_none() {
             self.eth_type = Some(0x0800);
         }
-        self.src_ipv4 = Some((addr, prefix_len));
+        self.src_ipv4 = Some((addr, mask));
         self
     }
 
     /// Match destination IPv4 address with prefix length.
-    pub fn dst_ipv4(mut self, addr: Ipv4Addr, prefix_len: u8) -> Self {
+    pub fn dst_ipv4(self, addr: Ipv4Addr, prefix_len: u8) -> Self {
+        self.dst_ipv4_masked(addr, ipv4_mask(prefix_len))
+    }
+
+    /// Match destination IPv4 address under an arbitrary mask.
+    pub fn dst_ipv4_masked(mut self, addr: Ipv4Addr, mask: Ipv4Addr) -> Self {
         if self.eth_type.is_none() {
             self.eth_type = Some(0x0800);
         }
-        self.dst_ipv4 = Some((addr, prefix_len));
+        self.dst_ipv4 = Some((addr, mask));
         self
     }
 
     /// Match source IPv6 address with prefix length.
-    pub fn src_ipv6(mut self, addr: Ipv6Addr, prefix_len: u8) -> Self {
+    pub fn src_ipv6(self, addr: Ipv6Addr, prefix_len: u8) -> Self {
+        self.src_ipv6_masked(addr, ipv6_mask(prefix_len))
+    }
+
+    /// Match source IPv6 address under an arbitrary mask.
+    pub fn src_ipv6_masked(mut self, addr: Ipv6Addr, mask: Ipv6Addr) -> Self {
         if self.eth_type.is_none() {
             self.eth_type = Some(0x86DD);
         }
-        self.src_ipv6 = Some((addr, prefix_len));
+        self.src_ipv6 = Some((addr, mask));
         self
     }
 
     /// Match destination IPv6 address with prefix length.
-    pub fn dst_ipv6(mut self, addr: Ipv6Addr, prefix_len: u8) -> Self {
+    pub fn dst_ipv6(self, addr: Ipv6Addr, prefix_len: u8) -> Self {
+        self.dst_ipv6_masked(addr, ipv6_mask(prefix_len))
+    }
+
+    /// Match destination IPv6 address under an arbitrary mask.
+    pub fn dst_ipv6_masked(mut self, addr: Ipv6Addr, mask: Ipv6Addr) -> Self {
         if self.eth_type.is_none() {
             self.eth_type = Some(0x86DD);
         }
-        self.dst_ipv6 = Some((addr, prefix_len));
+        self.dst_ipv6 = Some((addr, mask));
         self
     }
 
     /// Match source port.
+    ///
+    /// Like every port key, needs `ip_proto` TCP, UDP or SCTP to say
+    /// which header the port is in.
     pub fn src_port(mut self, port: u16) -> Self {
         self.src_port = Some(port);
+        self.src_port_range = None;
         self
     }
 
     /// Match destination port.
     pub fn dst_port(mut self, port: u16) -> Self {
         self.dst_port = Some(port);
+        self.dst_port_range = None;
+        self
+    }
+
+    /// Match source ports `min..=max` (Linux 5.6+).
+    pub fn src_port_range(mut self, min: u16, max: u16) -> Self {
+        self.src_port_range = Some((min, max));
+        self.src_port = None;
+        self
+    }
+
+    /// Match destination ports `min..=max` (Linux 5.6+).
+    pub fn dst_port_range(mut self, min: u16, max: u16) -> Self {
+        self.dst_port_range = Some((min, max));
+        self.dst_port = None;
         self
     }
 
@@ -1067,6 +1375,53 @@ impl FlowerFilter {
         self
     }
 
+    /// Match the tunnel key ID of a decapsulated packet: the VNI of a
+    /// VXLAN or Geneve packet, the key of a GRE one.
+    ///
+    /// The `enc_*` keys match the tunnel metadata a collect-metadata
+    /// (`external`) tunnel device attaches on receive, so the filter
+    /// goes on that device's ingress.
+    pub fn enc_key_id(mut self, id: u32) -> Self {
+        self.enc_key_id = Some(id);
+        self
+    }
+
+    /// Match the outer source address with prefix length.
+    pub fn enc_src_ip(mut self, addr: IpAddr, prefix_len: u8) -> Self {
+        self.enc_src_ip = Some((addr, ip_mask(addr, prefix_len)));
+        self
+    }
+
+    /// Match the outer destination address with prefix length.
+    pub fn enc_dst_ip(mut self, addr: IpAddr, prefix_len: u8) -> Self {
+        self.enc_dst_ip = Some((addr, ip_mask(addr, prefix_len)));
+        self
+    }
+
+    /// Match the outer UDP source port.
+    pub fn enc_src_port(mut self, port: u16) -> Self {
+        self.enc_src_port = Some(port);
+        self
+    }
+
+    /// Match the outer UDP destination port, e.g. 4789 for VXLAN.
+    pub fn enc_dst_port(mut self, port: u16) -> Self {
+        self.enc_dst_port = Some(port);
+        self
+    }
+
+    /// Match the outer IP TOS with mask.
+    pub fn enc_tos(mut self, tos: u8, mask: u8) -> Self {
+        self.enc_tos = Some((tos, mask));
+        self
+    }
+
+    /// Match the outer IP TTL with mask.
+    pub fn enc_ttl(mut self, ttl: u8, mask: u8) -> Self {
+        self.enc_ttl = Some((ttl, mask));
+        self
+    }
+
     /// Skip hardware offload.
     pub fn skip_hw(mut self) -> Self {
         self.flags |= flower::TCA_CLS_FLAGS_SKIP_HW;
@@ -1097,6 +1452,15 @@ impl FlowerFilter {
         self
     }
 
+    /// Add actions to perform on matching packets.
+    ///
+    /// If [`goto_chain`](Self::goto_chain) is also set, the jump runs
+    /// after these actions.
+    pub fn actions(mut self, actions: ActionList) -> Self {
+        self.actions = Some(actions);
+        self
+    }
+
     /// Build the filter configuration.
     pub fn build(self) -> Self {
         self
@@ -1107,12 +1471,13 @@ impl FlowerFilter {
     /// Recognised tokens:
     ///
     /// - `classid <handle>` (alias `flowid`) — target class id (`1:10`)
-    /// - `ip_proto <name|num>` — `tcp` / `udp` / `icmp` / `icmpv6` or
-    ///   bare u8
-    /// - `src_ip <addr[/prefix]>` / `dst_ip <addr[/prefix]>` — IPv4 or
-    ///   IPv6 (auto-detected via `:` presence). Bare address means
-    ///   `/32` (v4) or `/128` (v6). Sets `eth_type` if not already set.
-    /// - `src_port <port>` / `dst_port <port>`
+    /// - `ip_proto <name|num>` — `tcp` / `udp` / `sctp` / `icmp` /
+    ///   `icmpv6` or bare u8
+    /// - `src_ip <addr[/prefix|/mask]>` / `dst_ip <addr[/prefix|/mask]>` —
+    ///   IPv4 or IPv6 (auto-detected via `:` presence). Bare address
+    ///   means `/32` (v4) or `/128` (v6). Sets `eth_type` if not
+    ///   already set.
+    /// - `src_port <port|min-max>` / `dst_port <port|min-max>`
     /// - `src_mac <mac>` / `dst_mac <mac>` — `xx:xx:xx:xx:xx:xx`
     /// - `eth_type <name|hex>` — `ip` / `ipv4` / `ipv6` / `arp` / `vlan`
     ///   / `802.1q` / `802.1ad`, or hex (`0x800`)
@@ -1121,11 +1486,13 @@ impl FlowerFilter {
     ///   bare value implies `/0xff` mask
     /// - `tcp_flags <flags[/mask]>` — hex u16
     /// - `skip_hw` / `skip_sw` — flag tokens (no value)
+    /// - `enc_key_id <id>`, `enc_src_ip` / `enc_dst_ip <addr[/prefix|/mask]>`,
+    ///   `enc_src_port` / `enc_dst_port <port>`, `enc_tos` / `enc_ttl
+    ///   <val[/mask]>` — tunnel metadata
     ///
     /// **Not yet typed-modelled** (returns `Error::InvalidMessage`):
-    /// `ct_state`, `ct_zone`, `ct_mark`, `enc_key_id`,
-    /// `enc_dst_ip`, `enc_src_ip`, `enc_dst_port`, `indev`. File
-    /// an issue if you need any of these.
+    /// `ct_state`, `ct_zone`, `ct_mark`, `indev`. File an issue if you
+    /// need any of these.
     ///
     /// Stricter than the legacy `add_flower_options`: unknown tokens,
     /// missing values, and unparseable addresses / ports / MACs all
@@ -1168,45 +1535,33 @@ impl FlowerFilter {
                     i += 2;
                 }
                 "src_port" => {
-                    let s = need_value()?;
-                    let port: u16 = s.parse().map_err(|_| {
-                        Error::InvalidMessage(format!(
-                            "flower: invalid src_port `{s}` (expected 0-65535)"
-                        ))
-                    })?;
-                    f = f.src_port(port);
+                    f = match parse_flower_ports(need_value()?, key)? {
+                        (min, max) if min == max => f.src_port(min),
+                        (min, max) => f.src_port_range(min, max),
+                    };
                     i += 2;
                 }
                 "dst_port" => {
-                    let s = need_value()?;
-                    let port: u16 = s.parse().map_err(|_| {
-                        Error::InvalidMessage(format!(
-                            "flower: invalid dst_port `{s}` (expected 0-65535)"
-                        ))
-                    })?;
-                    f = f.dst_port(port);
+                    f = match parse_flower_ports(need_value()?, key)? {
+                        (min, max) if min == max => f.dst_port(min),
+                        (min, max) => f.dst_port_range(min, max),
+                    };
                     i += 2;
                 }
                 "src_ip" => {
-                    let s = need_value()?;
-                    if s.contains(':') {
-                        let (addr, plen) = parse_ipv6_with_prefix(s)?;
-                        f = f.src_ipv6(addr, plen);
-                    } else {
-                        let (addr, plen) = parse_ipv4_with_prefix(s)?;
-                        f = f.src_ipv4(addr, plen);
-                    }
+                    f = match parse_ip_masked(need_value()?)? {
+                        (IpAddr::V4(addr), IpAddr::V4(mask)) => f.src_ipv4_masked(addr, mask),
+                        (IpAddr::V6(addr), IpAddr::V6(mask)) => f.src_ipv6_masked(addr, mask),
+                        _ => unreachable!("parse_ip_masked keeps one family"),
+                    };
                     i += 2;
                 }
                 "dst_ip" => {
-                    let s = need_value()?;
-                    if s.contains(':') {
-                        let (addr, plen) = parse_ipv6_with_prefix(s)?;
-                        f = f.dst_ipv6(addr, plen);
-                    } else {
-                        let (addr, plen) = parse_ipv4_with_prefix(s)?;
-                        f = f.dst_ipv4(addr, plen);
-                    }
+                    f = match parse_ip_masked(need_value()?)? {
+                        (IpAddr::V4(addr), IpAddr::V4(mask)) => f.dst_ipv4_masked(addr, mask),
+                        (IpAddr::V6(addr), IpAddr::V6(mask)) => f.dst_ipv6_masked(addr, mask),
+                        _ => unreachable!("parse_ip_masked keeps one family"),
+                    };
                     i += 2;
                 }
                 "src_mac" => {
@@ -1271,8 +1626,48 @@ impl FlowerFilter {
                     f.flags |= flower::TCA_CLS_FLAGS_SKIP_SW;
                     i += 1;
                 }
-                "ct_state" | "ct_zone" | "ct_mark" | "enc_key_id" | "enc_dst_ip" | "enc_src_ip"
-                | "enc_dst_port" | "indev" => {
+                "enc_key_id" => {
+                    let s = need_value()?;
+                    let id = parse_flow_u32_hex_or_dec(s, key).map_err(|_| {
+                        Error::InvalidMessage(format!("flower: invalid {key} `{s}`"))
+                    })?;
+                    f = f.enc_key_id(id);
+                    i += 2;
+                }
+                "enc_src_ip" | "enc_dst_ip" => {
+                    let (addr, mask) = parse_ip_masked(need_value()?)?;
+                    if key == "enc_src_ip" {
+                        f.enc_src_ip = Some((addr, mask));
+                    } else {
+                        f.enc_dst_ip = Some((addr, mask));
+                    }
+                    i += 2;
+                }
+                "enc_src_port" | "enc_dst_port" => {
+                    let s = need_value()?;
+                    let port: u16 = s.parse().map_err(|_| {
+                        Error::InvalidMessage(format!(
+                            "flower: invalid {key} `{s}` (expected 0-65535)"
+                        ))
+                    })?;
+                    f = if key == "enc_src_port" {
+                        f.enc_src_port(port)
+                    } else {
+                        f.enc_dst_port(port)
+                    };
+                    i += 2;
+                }
+                "enc_tos" => {
+                    let (v, m) = parse_value_mask_u8(need_value()?, key)?;
+                    f = f.enc_tos(v, m);
+                    i += 2;
+                }
+                "enc_ttl" => {
+                    let (v, m) = parse_value_mask_u8(need_value()?, key)?;
+                    f = f.enc_ttl(v, m);
+                    i += 2;
+                }
+                "ct_state" | "ct_zone" | "ct_mark" | "indev" => {
                     return Err(Error::InvalidMessage(format!(
                         "flower: `{key}` is not modelled by FlowerFilter yet — file an issue if you need this match"
                     )));
@@ -1295,9 +1690,10 @@ fn parse_flower_ip_proto(s: &str) -> crate::Result<u8> {
         "udp" => flower::IPPROTO_UDP,
         "icmp" => flower::IPPROTO_ICMP,
         "icmpv6" => flower::IPPROTO_ICMPV6,
+        "sctp" => flower::IPPROTO_SCTP,
         other => other.parse::<u8>().map_err(|_| {
             Error::InvalidMessage(format!(
-                "flower: invalid ip_proto `{other}` (expected tcp/udp/icmp/icmpv6 or 0-255)"
+                "flower: invalid ip_proto `{other}` (expected tcp/udp/sctp/icmp/icmpv6 or 0-255)"
             ))
         })?,
     })
@@ -1366,20 +1762,54 @@ fn parse_ipv6_with_prefix(s: &str) -> crate::Result<(Ipv6Addr, u8)> {
     Ok((addr, 128))
 }
 
-fn parse_mac(s: &str) -> crate::Result<[u8; 6]> {
+/// Parse `ADDR`, `ADDR/PREFIX` or `ADDR/MASK` (a mask in address
+/// notation, which need not be contiguous) into an address and mask.
+fn parse_ip_masked(s: &str) -> crate::Result<(IpAddr, IpAddr)> {
     use crate::Error;
-    let parts: Vec<&str> = s.split(':').collect();
-    if parts.len() != 6 {
-        return Err(Error::InvalidMessage(format!(
-            "flower: invalid MAC `{s}` (expected xx:xx:xx:xx:xx:xx)"
-        )));
+    if let Some((addr_s, mask_s)) = s.rsplit_once('/')
+        && (mask_s.contains('.') || mask_s.contains(':'))
+    {
+        let invalid = || Error::InvalidMessage(format!("flower: invalid address/mask `{s}`"));
+        let addr: IpAddr = addr_s.parse().map_err(|_| invalid())?;
+        let mask: IpAddr = mask_s.parse().map_err(|_| invalid())?;
+        if addr.is_ipv4() != mask.is_ipv4() {
+            return Err(invalid());
+        }
+        return Ok((addr, mask));
+    }
+    if s.contains(':') {
+        let (addr, plen) = parse_ipv6_with_prefix(s)?;
+        Ok((addr.into(), ipv6_mask(plen).into()))
+    } else {
+        let (addr, plen) = parse_ipv4_with_prefix(s)?;
+        Ok((addr.into(), ipv4_mask(plen).into()))
     }
-    let mut mac = [0u8; 6];
-    for (i, part) in parts.iter().enumerate() {
-        mac[i] = u8::from_str_radix(part, 16)
-            .map_err(|_| Error::InvalidMessage(format!("flower: invalid MAC `{s}`")))?;
+}
+
+/// Parse a flower port, `PORT` or `MIN-MAX`, into an inclusive range.
+fn parse_flower_ports(s: &str, key: &str) -> crate::Result<(u16, u16)> {
+    use crate::Error;
+    let invalid = || {
+        Error::InvalidMessage(format!(
+            "flower: invalid {key} `{s}` (expected 0-65535 or MIN-MAX)"
+        ))
+    };
+    let port = |t: &str| t.parse::<u16>().map_err(|_| invalid());
+    match s.split_once('-') {
+        Some((min, max)) => {
+            let (min, max) = (port(min)?, port(max)?);
+            if min >= max {
+                return Err(invalid());
+            }
+            Ok((min, max))
+        }
+        None => port(s).map(|p| (p, p)),
     }
-    Ok(mac)
+}
+
+fn parse_mac(s: &str) -> crate::Result<[u8; 6]> {
+    crate::util::addr::parse_mac(s)
+        .map_err(|_| crate::Error::InvalidMessage(format!("flower: invalid MAC `{s}`")))
 }
 
 fn parse_value_mask_u8(s: &str, label: &str) -> crate::Result<(u8, u8)> {
@@ -1466,6 +1896,14 @@ fn ipv4_mask(prefix_len: u8) -> Ipv4Addr {
     }
 }
 
+/// Helper to create a mask of `addr`'s family from prefix length.
+fn ip_mask(addr: IpAddr, prefix_len: u8) -> IpAddr {
+    match addr {
+        IpAddr::V4(_) => ipv4_mask(prefix_len).into(),
+        IpAddr::V6(_) => ipv6_mask(prefix_len).into(),
+    }
+}
+
 /// Helper to create an IPv6 mask from prefix length.
 fn ipv6_mask(prefix_len: u8) -> Ipv6Addr {
     if prefix_len >= 128 {
@@ -1512,6 +1950,12 @@ impl FilterConfig for FlowerFilter {
         Some(self.priority)
     }
 
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions
+            .as_ref()
+            .and_then(ActionList::resolved_namespace)
+    }
+
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()> {
         // Add classid
         if let Some(classid) = self.classid {
@@ -1534,47 +1978,68 @@ impl FilterConfig for FlowerFilter {
         }
 
         // Add source IPv4
-        if let Some((addr, prefix_len)) = self.src_ipv4 {
+        if let Some((addr, mask)) = self.src_ipv4 {
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV4_SRC, &addr.octets());
-            let mask = ipv4_mask(prefix_len);
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV4_SRC_MASK, &mask.octets());
         }
 
         // Add destination IPv4
-        if let Some((addr, prefix_len)) = self.dst_ipv4 {
+        if let Some((addr, mask)) = self.dst_ipv4 {
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV4_DST, &addr.octets());
-            let mask = ipv4_mask(prefix_len);
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV4_DST_MASK, &mask.octets());
         }
 
         // Add source IPv6
-        if let Some((addr, prefix_len)) = self.src_ipv6 {
+        if let Some((addr, mask)) = self.src_ipv6 {
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV6_SRC, &addr.octets());
-            let mask = ipv6_mask(prefix_len);
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV6_SRC_MASK, &mask.octets());
         }
 
         // Add destination IPv6
-        if let Some((addr, prefix_len)) = self.dst_ipv6 {
+        if let Some((addr, mask)) = self.dst_ipv6 {
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV6_DST, &addr.octets());
-            let mask = ipv6_mask(prefix_len);
             builder.append_attr(flower::TCA_FLOWER_KEY_IPV6_DST_MASK, &mask.octets());
         }
 
-        // Add ports
-        if let Some(port) = self.src_port {
-            if self.ip_proto == Some(flower::IPPROTO_TCP) {
-                builder.append_attr(flower::TCA_FLOWER_KEY_TCP_SRC, &port.to_be_bytes());
-            } else if self.ip_proto == Some(flower::IPPROTO_UDP) {
-                builder.append_attr(flower::TCA_FLOWER_KEY_UDP_SRC, &port.to_be_bytes());
+        // Add ports. The kernel keys them by L4 protocol, and would
+        // ignore them without one.
+        let has_ports = self.src_port.is_some()
+            || self.dst_port.is_some()
+            || self.src_port_range.is_some()
+            || self.dst_port_range.is_some();
+        if has_ports {
+            let (src_key, dst_key) = match self.ip_proto {
+                Some(flower::IPPROTO_TCP) => (
+                    flower::TCA_FLOWER_KEY_TCP_SRC,
+                    flower::TCA_FLOWER_KEY_TCP_DST,
+                ),
+                Some(flower::IPPROTO_UDP) => (
+                    flower::TCA_FLOWER_KEY_UDP_SRC,
+                    flower::TCA_FLOWER_KEY_UDP_DST,
+                ),
+                Some(flower::IPPROTO_SCTP) => (
+                    flower::TCA_FLOWER_KEY_SCTP_SRC,
+                    flower::TCA_FLOWER_KEY_SCTP_DST,
+                ),
+                _ => {
+                    return Err(Error::InvalidMessage(
+                        "flower: port matches need ip_proto tcp, udp or sctp".into(),
+                    ));
+                }
+            };
+            if let Some(port) = self.src_port {
+                builder.append_attr(src_key, &port.to_be_bytes());
             }
-        }
-
-        if let Some(port) = self.dst_port {
-            if self.ip_proto == Some(flower::IPPROTO_TCP) {
-                builder.append_attr(flower::TCA_FLOWER_KEY_TCP_DST, &port.to_be_bytes());
-            } else if self.ip_proto == Some(flower::IPPROTO_UDP) {
-                builder.append_attr(flower::TCA_FLOWER_KEY_UDP_DST, &port.to_be_bytes());
+            if let Some(port) = self.dst_port {
+                builder.append_attr(dst_key, &port.to_be_bytes());
+            }
+            if let Some((min, max)) = self.src_port_range {
+                builder.append_attr(flower::TCA_FLOWER_KEY_PORT_SRC_MIN, &min.to_be_bytes());
+                builder.append_attr(flower::TCA_FLOWER_KEY_PORT_SRC_MAX, &max.to_be_bytes());
+            }
+            if let Some((min, max)) = self.dst_port_range {
+                builder.append_attr(flower::TCA_FLOWER_KEY_PORT_DST_MIN, &min.to_be_bytes());
+                builder.append_attr(flower::TCA_FLOWER_KEY_PORT_DST_MAX, &max.to_be_bytes());
             }
         }
 
@@ -1616,31 +2081,80 @@ impl FilterConfig for FlowerFilter {
             builder.append_attr(flower::TCA_FLOWER_KEY_TCP_FLAGS_MASK, &mask.to_be_bytes());
         }
 
-        // Add goto_chain action if set
-        if let Some(chain) = self.goto_chain {
-            use super::{
-                action::{ActionConfig, GactAction},
-                types::tc::{action, filter::flower::TCA_FLOWER_ACT},
-            };
-
-            let goto = GactAction::goto_chain(chain);
-            let act_token = builder.nest_start(TCA_FLOWER_ACT);
-
-            // Action index 1
-            let act1_token = builder.nest_start(1);
-            builder.append_attr_str(action::TCA_ACT_KIND, goto.kind());
-            let opt_token = builder.nest_start(action::TCA_ACT_OPTIONS);
-            goto.write_options(builder)?;
-            builder.nest_end(opt_token);
-            builder.nest_end(act1_token);
-
-            builder.nest_end(act_token);
+        // Add tunnel metadata
+        if let Some(id) = self.enc_key_id {
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_KEY_ID, &id.to_be_bytes());
+        }
+        for (key, sel) in [
+            (flower::TCA_FLOWER_KEY_ENC_IPV4_SRC, self.enc_src_ip),
+            (flower::TCA_FLOWER_KEY_ENC_IPV4_DST, self.enc_dst_ip),
+        ] {
+            // The IPv6 keys sit four attributes after the IPv4 ones,
+            // each followed by its mask.
+            match sel {
+                Some((IpAddr::V4(addr), IpAddr::V4(mask))) => {
+                    builder.append_attr(key, &addr.octets());
+                    builder.append_attr(key + 1, &mask.octets());
+                }
+                Some((IpAddr::V6(addr), IpAddr::V6(mask))) => {
+                    builder.append_attr(key + 4, &addr.octets());
+                    builder.append_attr(key + 5, &mask.octets());
+                }
+                Some(_) => {
+                    return Err(Error::InvalidMessage(
+                        "flower: tunnel address and mask families differ".into(),
+                    ));
+                }
+                None => {}
+            }
+        }
+        if let Some(port) = self.enc_src_port {
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_UDP_SRC_PORT, &port.to_be_bytes());
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_UDP_SRC_PORT_MASK, &[0xff; 2]);
+        }
+        if let Some(port) = self.enc_dst_port {
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_UDP_DST_PORT, &port.to_be_bytes());
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_UDP_DST_PORT_MASK, &[0xff; 2]);
+        }
+        if let Some((tos, mask)) = self.enc_tos {
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_IP_TOS, &[tos]);
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_IP_TOS_MASK, &[mask]);
+        }
+        if let Some((ttl, mask)) = self.enc_ttl {
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_IP_TTL, &[ttl]);
+            builder.append_attr(flower::TCA_FLOWER_KEY_ENC_IP_TTL_MASK, &[mask]);
         }
 
-        Ok(())
+        write_filter_actions(
+            builder,
+            flower::TCA_FLOWER_ACT,
+            self.actions.as_ref(),
+            self.goto_chain,
+        )
     }
 }
 
+/// Write a filter's `*_ACT` nest: the caller's actions, then the
+/// `goto_chain` jump if one is set. Writes nothing when neither is.
+fn write_filter_actions(
+    builder: &mut MessageBuilder,
+    attr: u16,
+    actions: Option<&ActionList>,
+    goto_chain: Option<u32>,
+) -> Result<()> {
+    if actions.is_none() && goto_chain.is_none() {
+        return Ok(());
+    }
+    let mut list = actions.cloned().unwrap_or_default();
+    if let Some(chain) = goto_chain {
+        list = list.with(super::action::GactAction::goto_chain(chain));
+    }
+    let act_token = builder.nest_start(attr);
+    list.write_to(builder)?;
+    builder.nest_end(act_token);
+    Ok(())
+}
+
 // ============================================================================
 // MatchallFilter
 // ============================================================================
@@ -1673,6 +2187,8 @@ pub struct MatchallFilter {
     chain: Option<u32>,
     /// Goto chain action (jump to another chain on match).
     goto_chain: Option<u32>,
+    /// Actions to perform on matching packets.
+    actions: Option<ActionList>,
 }
 
 impl MatchallFilter {
@@ -1732,6 +2248,15 @@ impl MatchallFilter {
         self
     }
 
+    /// Add actions to perform on matching packets.
+    ///
+    /// If [`goto_chain`](Self::goto_chain) is also set, the jump runs
+    /// after these actions.
+    pub fn actions(mut self, actions: ActionList) -> Self {
+        self.actions = Some(actions);
+        self
+    }
+
     /// Build the filter configuration.
     pub fn build(self) -> Self {
         self
@@ -1829,6 +2354,12 @@ impl FilterConfig for MatchallFilter {
         Some(self.priority)
     }
 
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions
+            .as_ref()
+            .and_then(ActionList::resolved_namespace)
+    }
+
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()> {
         if let Some(classid) = self.classid {
             builder.append_attr_u32(matchall::TCA_MATCHALL_CLASSID, classid);
@@ -1838,28 +2369,12 @@ impl FilterConfig for MatchallFilter {
             builder.append_attr_u32(matchall::TCA_MATCHALL_FLAGS, self.flags);
         }
 
-        // Add goto_chain action if set
-        if let Some(chain) = self.goto_chain {
-            use super::{
-                action::{ActionConfig, GactAction},
-                types::tc::action,
-            };
-
-            let goto = GactAction::goto_chain(chain);
-            let act_token = builder.nest_start(matchall::TCA_MATCHALL_ACT);
-
-            // Action index 1
-            let act1_token = builder.nest_start(1);
-            builder.append_attr_str(action::TCA_ACT_KIND, goto.kind());
-            let opt_token = builder.nest_start(action::TCA_ACT_OPTIONS);
-            goto.write_options(builder)?;
-            builder.nest_end(opt_token);
-            builder.nest_end(act1_token);
-
-            builder.nest_end(act_token);
-        }
-
-        Ok(())
+        write_filter_actions(
+            builder,
+            matchall::TCA_MATCHALL_ACT,
+            self.actions.as_ref(),
+            self.goto_chain,
+        )
     }
 }
 
@@ -2146,18 +2661,17 @@ impl TcindexFilter {
                 }
                 "mask" => {
                     let s = need_value()?;
-                    let m = if let Some(hex) =
-                        s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))
-                    {
-                        u16::from_str_radix(hex, 16)
-                    } else {
-                        s.parse::<u16>()
-                    }
-                    .map_err(|_| {
-                        Error::InvalidMessage(format!(
-                            "tcindex: invalid mask `{s}` (expected hex `0xNN` or decimal u16)"
-                        ))
-                    })?;
+                    let m =
+                        if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                            u16::from_str_radix(hex, 16)
+                        } else {
+                            s.parse::<u16>()
+                        }
+                        .map_err(|_| {
+                            Error::InvalidMessage(format!(
+                                "tcindex: invalid mask `{s}` (expected hex `0xNN` or decimal u16)"
+                            ))
+                        })?;
                     f.mask = Some(m);
                     i += 2;
                 }
@@ -3085,6 +3599,12 @@ impl FilterConfig for CgroupFilter {
         self.chain = Some(chain);
     }
 
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions
+            .as_ref()
+            .and_then(ActionList::resolved_namespace)
+    }
+
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()> {
         use super::types::tc::filter::cgroup;
 
@@ -3304,6 +3824,12 @@ impl FilterConfig for RouteFilter {
         self.chain = Some(chain);
     }
 
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions
+            .as_ref()
+            .and_then(ActionList::resolved_namespace)
+    }
+
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()> {
         use super::types::tc::filter::route4;
 
@@ -3480,17 +4006,17 @@ impl RsvpFilter {
                      use an address without `/<port>`, or a hand-rolled MessageBuilder"
                 )));
             }
-            s.parse::<IpAddr>().map_err(|_| {
-                Error::InvalidMessage(format!("rsvp: invalid {key} address `{s}`"))
-            })
+            s.parse::<IpAddr>()
+                .map_err(|_| Error::InvalidMessage(format!("rsvp: invalid {key} address `{s}`")))
         };
 
         while i < params.len() {
             let key = params[i];
             let need_value = || {
-                params.get(i + 1).copied().ok_or_else(|| {
-                    Error::InvalidMessage(format!("rsvp: `{key}` requires a value"))
-                })
+                params
+                    .get(i + 1)
+                    .copied()
+                    .ok_or_else(|| Error::InvalidMessage(format!("rsvp: `{key}` requires a value")))
             };
             match key {
                 "ipproto" => {
@@ -3574,6 +4100,12 @@ impl FilterConfig for RsvpFilter {
         self.chain = Some(chain);
     }
 
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions
+            .as_ref()
+            .and_then(ActionList::resolved_namespace)
+    }
+
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()> {
         use super::types::tc::filter::rsvp;
 
@@ -4016,6 +4548,12 @@ impl FilterConfig for FlowFilter {
         Some(self.priority)
     }
 
+    fn resolved_namespace(&self) -> Option<NamespaceId> {
+        self.actions
+            .as_ref()
+            .and_then(ActionList::resolved_namespace)
+    }
+
     fn write_options(&self, builder: &mut MessageBuilder) -> Result<()> {
         use super::types::tc::filter::flow;
 
@@ -4161,6 +4699,8 @@ impl Connection<Route> {
         priority: u16,
         config: impl FilterConfig,
     ) -> Result<()> {
+        self.check_resolved_namespace(config.resolved_namespace())?;
+
         let parent_handle = parent.as_raw();
         let filter_handle = handle.map(|h| h.as_raw()).unwrap_or(0);
 
@@ -4260,6 +4800,8 @@ impl Connection<Route> {
         priority: u16,
         config: impl FilterConfig,
     ) -> Result<()> {
+        self.check_resolved_namespace(config.resolved_namespace())?;
+
         let parent_handle = parent.as_raw();
         let filter_handle = handle.map(|h| h.as_raw()).unwrap_or(0);
 
@@ -4360,6 +4902,8 @@ impl Connection<Route> {
         priority: u16,
         config: impl FilterConfig,
     ) -> Result<()> {
+        self.check_resolved_namespace(config.resolved_namespace())?;
+
         let parent_handle = parent.as_raw();
         let filter_handle = handle.map(|h| h.as_raw()).unwrap_or(0);
 
@@ -4403,9 +4947,10 @@ impl Connection<Route> {
         protocol: u16,
         priority: u16,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.del_filter_by_index(ifindex, parent, protocol, priority)
-            .await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.del_filter_by_index(ifindex, parent, protocol, priority)
+        })
+        .await
     }
 
     /// Delete a filter if it exists. Returns `Ok(true)` if the
@@ -4458,8 +5003,10 @@ impl Connection<Route> {
         dev: impl Into<InterfaceRef>,
         parent: TcHandle,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.flush_filters_by_index(ifindex, parent).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.flush_filters_by_index(ifindex, parent)
+        })
+        .await
     }
 
     /// Delete all filters from a parent qdisc by interface index.
@@ -4509,8 +5056,10 @@ impl Connection<Route> {
         direction: BpfDirection,
         filter: BpfFilter,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.attach_bpf_by_index(ifindex, direction, filter).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.attach_bpf_by_index(ifindex, direction, filter.clone())
+        })
+        .await
     }
 
     /// Attach a BPF program by interface index (namespace-safe).
@@ -4535,7 +5084,7 @@ impl Connection<Route> {
         // egress = TC_H_MAKE(CLSACT, MIN_EGRESS) = 0xFFFFFFF3.
         let parent = match direction {
             BpfDirection::Ingress => TcHandle::CLSACT,
-            BpfDirection::Egress => TcHandle::from_raw(0xFFFF_FFF3),
+            BpfDirection::Egress => TcHandle::CLSACT_EGRESS,
         };
 
         self.add_filter_by_index(ifindex, parent, filter).await
@@ -4559,8 +5108,10 @@ impl Connection<Route> {
         dev: impl Into<InterfaceRef>,
         direction: BpfDirection,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.detach_bpf_by_index(ifindex, direction).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.detach_bpf_by_index(ifindex, direction)
+        })
+        .await
     }
 
     /// Detach all BPF filters from an interface direction by index.
@@ -4570,7 +5121,7 @@ impl Connection<Route> {
         // egress = TC_H_MAKE(CLSACT, MIN_EGRESS) = 0xFFFFFFF3.
         let parent = match direction {
             BpfDirection::Ingress => TcHandle::CLSACT,
-            BpfDirection::Egress => TcHandle::from_raw(0xFFFF_FFF3),
+            BpfDirection::Egress => TcHandle::CLSACT_EGRESS,
         };
         self.flush_filters_by_index(ifindex, parent).await
     }
@@ -4593,8 +5144,10 @@ impl Connection<Route> {
         &self,
         dev: impl Into<InterfaceRef>,
     ) -> Result<Vec<crate::netlink::messages::BpfInfo>> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.list_bpf_programs_by_index(ifindex).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.list_bpf_programs_by_index(ifindex)
+        })
+        .await
     }
 
     /// List attached BPF programs by interface index.
@@ -4679,10 +5232,7 @@ mod filter_info_tests {
     fn configs_without_their_own_ethertype_fall_back() {
         assert_eq!(FwFilter::new().protocol(), None);
         assert_eq!(FwFilter::new().priority(), None);
-        assert_eq!(
-            filter_info(&FwFilter::new()),
-            (DEFAULT_FILTER_PROTOCOL, 0),
-        );
+        assert_eq!(filter_info(&FwFilter::new()), (DEFAULT_FILTER_PROTOCOL, 0),);
     }
 }
 
@@ -4693,7 +5243,15 @@ mod tests {
     #[test]
     fn test_tcindex_parse_and_write() {
         let f = TcindexFilter::parse_params(&[
-            "hash", "256", "mask", "0xff", "shift", "0", "fall_through", "classid", "1:10",
+            "hash",
+            "256",
+            "mask",
+            "0xff",
+            "shift",
+            "0",
+            "fall_through",
+            "classid",
+            "1:10",
         ])
         .unwrap();
         assert_eq!(f.hash, Some(256));
@@ -4761,7 +5319,10 @@ mod tests {
 
         assert_eq!(filter.classid, Some(TcHandle::new(1, 0x20).as_raw()));
         assert_eq!(filter.ip_proto, Some(flower::IPPROTO_TCP));
-        assert_eq!(filter.dst_ipv4, Some((Ipv4Addr::new(10, 0, 0, 0), 8)));
+        assert_eq!(
+            filter.dst_ipv4,
+            Some((Ipv4Addr::new(10, 0, 0, 0), Ipv4Addr::new(255, 0, 0, 0)))
+        );
         assert_eq!(filter.dst_port, Some(80));
         assert_eq!(filter.eth_type, Some(0x0800));
     }
@@ -4777,6 +5338,27 @@ mod tests {
         assert!(filter.flags & flower::TCA_CLS_FLAGS_SKIP_HW != 0);
     }
 
+    #[test]
+    fn goto_chain_runs_after_actions() {
+        use crate::netlink::action::MirredAction;
+
+        let actions = ActionList::new().with(MirredAction::mirror_by_index(3));
+        let filter = MatchallFilter::new().actions(actions).goto_chain(2);
+
+        let mut b = MessageBuilder::new(NlMsgType::RTM_NEWTFILTER, 0);
+        filter.write_options(&mut b).unwrap();
+        let bytes = b.as_bytes();
+        let find = |needle: &[u8]| bytes.windows(needle.len()).position(|w| w == needle);
+        let mirred = find(b"mirred\0").expect("mirred action written");
+        let gact = find(b"gact\0").expect("goto_chain action written");
+        assert!(mirred < gact, "goto_chain must be the last action");
+
+        // Neither set: no action nest at all.
+        let mut b = MessageBuilder::new(NlMsgType::RTM_NEWTFILTER, 0);
+        MatchallFilter::new().write_options(&mut b).unwrap();
+        assert!(!b.as_bytes().windows(5).any(|w| w == b"gact\0"));
+    }
+
     #[test]
     fn test_fw_filter_builder() {
         let filter = FwFilter::new()
@@ -4938,7 +5520,10 @@ mod tests {
     #[test]
     fn flower_parse_params_src_ip_v4_with_prefix() {
         let f = FlowerFilter::parse_params(&["src_ip", "10.0.0.0/8"]).unwrap();
-        assert_eq!(f.src_ipv4, Some(("10.0.0.0".parse().unwrap(), 8)));
+        assert_eq!(
+            f.src_ipv4,
+            Some(("10.0.0.0".parse().unwrap(), ipv4_mask(8)))
+        );
         // sets eth_type implicitly
         assert_eq!(f.eth_type, Some(0x0800));
     }
@@ -4946,20 +5531,26 @@ mod tests {
     #[test]
     fn flower_parse_params_src_ip_v4_bare() {
         let f = FlowerFilter::parse_params(&["dst_ip", "192.168.1.1"]).unwrap();
-        assert_eq!(f.dst_ipv4, Some(("192.168.1.1".parse().unwrap(), 32)));
+        assert_eq!(
+            f.dst_ipv4,
+            Some(("192.168.1.1".parse().unwrap(), ipv4_mask(32)))
+        );
     }
 
     #[test]
     fn flower_parse_params_dst_ip_v6_with_prefix() {
         let f = FlowerFilter::parse_params(&["dst_ip", "fe80::1/64"]).unwrap();
-        assert_eq!(f.dst_ipv6, Some(("fe80::1".parse().unwrap(), 64)));
+        assert_eq!(
+            f.dst_ipv6,
+            Some(("fe80::1".parse().unwrap(), ipv6_mask(64)))
+        );
         assert_eq!(f.eth_type, Some(0x86dd));
     }
 
     #[test]
     fn flower_parse_params_dst_ip_v6_bare() {
         let f = FlowerFilter::parse_params(&["src_ip", "::1"]).unwrap();
-        assert_eq!(f.src_ipv6, Some(("::1".parse().unwrap(), 128)));
+        assert_eq!(f.src_ipv6, Some(("::1".parse().unwrap(), ipv6_mask(128))));
     }
 
     #[test]
@@ -5033,16 +5624,7 @@ mod tests {
 
     #[test]
     fn flower_parse_params_unsupported_features_rejected() {
-        for unsup in [
-            "ct_state",
-            "ct_zone",
-            "ct_mark",
-            "enc_key_id",
-            "enc_dst_ip",
-            "enc_src_ip",
-            "enc_dst_port",
-            "indev",
-        ] {
+        for unsup in ["ct_state", "ct_zone", "ct_mark", "indev"] {
             let err = FlowerFilter::parse_params(&[unsup, "x"]).unwrap_err();
             assert!(
                 err.to_string().contains("not modelled"),
@@ -5051,6 +5633,105 @@ mod tests {
         }
     }
 
+    #[test]
+    fn flower_parse_params_masks_and_port_ranges() {
+        let f = FlowerFilter::parse_params(&[
+            "ip_proto",
+            "sctp",
+            "src_ip",
+            "10.0.0.1/255.0.255.0",
+            "dst_port",
+            "1000-2000",
+        ])
+        .unwrap();
+        assert_eq!(f.ip_proto, Some(flower::IPPROTO_SCTP));
+        assert_eq!(
+            f.src_ipv4,
+            Some((Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(255, 0, 255, 0)))
+        );
+        assert_eq!(f.dst_port_range, Some((1000, 2000)));
+        assert_eq!(f.dst_port, None);
+
+        let err = FlowerFilter::parse_params(&["dst_port", "2000-1000"]).unwrap_err();
+        assert!(err.to_string().contains("MIN-MAX"));
+        let err = FlowerFilter::parse_params(&["src_ip", "10.0.0.1/ffff::"]).unwrap_err();
+        assert!(err.to_string().contains("address/mask"));
+    }
+
+    #[test]
+    fn flower_parse_params_tunnel_keys() {
+        let f = FlowerFilter::parse_params(&[
+            "enc_key_id",
+            "0x2a",
+            "enc_dst_ip",
+            "2001:db8::/32",
+            "enc_dst_port",
+            "4789",
+            "enc_ttl",
+            "64",
+        ])
+        .unwrap();
+        assert_eq!(f.enc_key_id, Some(42));
+        assert_eq!(
+            f.enc_dst_ip,
+            Some(("2001:db8::".parse().unwrap(), ipv6_mask(32).into()))
+        );
+        assert_eq!(f.enc_dst_port, Some(4789));
+        assert_eq!(f.enc_ttl, Some((0x64, 0xff)));
+        // Tunnel keys don't imply an inner ethertype.
+        assert_eq!(f.eth_type, None);
+    }
+
+    #[test]
+    fn flower_writes_tunnel_keys_and_port_ranges() {
+        let filter = FlowerFilter::new()
+            .enc_key_id(42)
+            .enc_src_ip("192.0.2.1".parse().unwrap(), 32)
+            .enc_dst_port(4789)
+            .ip_proto_udp()
+            .src_port_range(1000, 2000);
+
+        let mut b = MessageBuilder::new(NlMsgType::RTM_NEWTFILTER, 0);
+        filter.write_options(&mut b).unwrap();
+        let bytes = b.as_bytes();
+        // Each attribute is a 4-byte header (len, type) then the payload.
+        let has = |attr: u16, payload: &[u8]| {
+            let mut needle = ((4 + payload.len()) as u16).to_ne_bytes().to_vec();
+            needle.extend(attr.to_ne_bytes());
+            needle.extend(payload);
+            bytes.windows(needle.len()).any(|w| w == needle)
+        };
+        assert!(has(flower::TCA_FLOWER_KEY_ENC_KEY_ID, &42u32.to_be_bytes()));
+        assert!(has(flower::TCA_FLOWER_KEY_ENC_IPV4_SRC, &[192, 0, 2, 1]));
+        assert!(has(flower::TCA_FLOWER_KEY_ENC_IPV4_SRC_MASK, &[255; 4]));
+        assert!(has(
+            flower::TCA_FLOWER_KEY_ENC_UDP_DST_PORT,
+            &4789u16.to_be_bytes()
+        ));
+        assert!(has(
+            flower::TCA_FLOWER_KEY_PORT_SRC_MIN,
+            &1000u16.to_be_bytes()
+        ));
+        assert!(has(
+            flower::TCA_FLOWER_KEY_PORT_SRC_MAX,
+            &2000u16.to_be_bytes()
+        ));
+    }
+
+    #[test]
+    fn flower_port_without_l4_protocol_errors() {
+        let filter = FlowerFilter::new().dst_port(80);
+        let mut b = MessageBuilder::new(NlMsgType::RTM_NEWTFILTER, 0);
+        let err = filter.write_options(&mut b).unwrap_err();
+        assert!(err.to_string().contains("tcp, udp or sctp"));
+
+        let filter = FlowerFilter::new().ip_proto_sctp().dst_port(80);
+        let mut b = MessageBuilder::new(NlMsgType::RTM_NEWTFILTER, 0);
+        filter.write_options(&mut b).unwrap();
+        let sctp_dst = flower::TCA_FLOWER_KEY_SCTP_DST.to_ne_bytes();
+        assert!(b.as_bytes().windows(2).any(|w| w == sctp_dst));
+    }
+
     #[test]
     fn flower_parse_params_missing_value_errors() {
         let err = FlowerFilter::parse_params(&["classid"]).unwrap_err();
@@ -5234,11 +5915,15 @@ mod tests {
     #[test]
     fn rsvp_parse_params_ipproto_numeric_and_udp() {
         assert_eq!(
-            RsvpFilter::parse_params(&["ipproto", "udp"]).unwrap().ipproto,
+            RsvpFilter::parse_params(&["ipproto", "udp"])
+                .unwrap()
+                .ipproto,
             Some(17)
         );
         assert_eq!(
-            RsvpFilter::parse_params(&["ipproto", "47"]).unwrap().ipproto,
+            RsvpFilter::parse_params(&["ipproto", "47"])
+                .unwrap()
+                .ipproto,
             Some(47)
         );
         let err = RsvpFilter::parse_params(&["ipproto", "bogus"]).unwrap_err();
@@ -5736,11 +6421,27 @@ mod tests {
     }
 
     #[test]
-    fn u32_parse_params_ht_handle_encodes_via_tchandle() {
+    fn u32_parse_params_ht_handle_uses_u32_layout() {
         let f = U32Filter::parse_params(&["ht", "100:"]).unwrap();
-        // tc(8) `100:` = TcHandle(major=0x100, minor=0) → as_raw() = 0x01000000.
-        let want = TcHandle::new(0x100, 0).as_raw();
-        assert_eq!(f.ht, Some(want));
+        // tc(8) u32 `100:` = htid 0x100 in the top 12 bits.
+        assert_eq!(f.ht, Some(0x1000_0000));
+        let f = U32Filter::parse_params(&["ht", "2:50:"]).unwrap();
+        assert_eq!(f.ht, Some(u32_handle(2, 0x50, 0)));
+    }
+
+    #[test]
+    fn u32_handle_round_trips_tc_notation() {
+        assert_eq!(parse_u32_handle("800::800").unwrap(), 0x8000_0800);
+        assert_eq!(
+            parse_u32_handle("800::800").unwrap(),
+            u32_handle(0x800, 0, 0x800)
+        );
+        assert_eq!(parse_u32_handle("1:a:3").unwrap(), u32_handle(1, 0xa, 3));
+        assert_eq!(parse_u32_handle("5").unwrap(), 5);
+        assert!(parse_u32_handle("1000:").is_err(), "htid is 12 bits");
+        assert!(parse_u32_handle("1:100:").is_err(), "bucket is 8 bits");
+        assert!(parse_u32_handle("1:2:3:4").is_err());
+        assert!(parse_u32_handle("::").is_err());
     }
 
     #[test]
@@ -5751,16 +6452,80 @@ mod tests {
             "ht", "100:", "match", "ip", "dst", "10.0.0.1", "link", "200:", "classid", "1:1",
         ])
         .unwrap();
-        assert_eq!(f.ht, Some(TcHandle::new(0x100, 0).as_raw()));
-        assert_eq!(f.link, Some(TcHandle::new(0x200, 0).as_raw()));
+        assert_eq!(f.ht, Some(u32_handle(0x100, 0, 0)));
+        assert_eq!(f.link, Some(u32_handle(0x200, 0, 0)));
         assert_eq!(f.classid, Some(TcHandle::new(1, 1).as_raw()));
         assert_eq!(f.keys.len(), 1, "the dst match should append a key");
     }
 
     #[test]
-    fn u32_parse_params_link_via_handle_notation() {
-        let f = U32Filter::parse_params(&["link", "1:a"]).unwrap();
-        assert_eq!(f.link, Some(TcHandle::new(1, 0xa).as_raw()));
+    fn u32_parse_params_link_must_name_a_table() {
+        let f = U32Filter::parse_params(&["link", "a:"]).unwrap();
+        assert_eq!(f.link, Some(u32_handle(0xa, 0, 0)));
+        let err = U32Filter::parse_params(&["link", "1:a"]).unwrap_err();
+        assert!(err.to_string().contains("must name a hash table"));
+    }
+
+    #[test]
+    fn u32_parse_params_offset_grammar() {
+        let f = U32Filter::parse_params(&[
+            "link", "2:", "offset", "at", "0", "mask", "0x0f00", "shift", "6", "eat", "classid",
+            "1:1",
+        ])
+        .unwrap();
+        assert_eq!(f.offset, U32Filter::new().skip_ipv4_header().eat().offset);
+        assert_eq!(f.classid, Some(TcHandle::new(1, 1).as_raw()));
+
+        let f = U32Filter::parse_params(&["offset", "plus", "8"]).unwrap();
+        assert_eq!(f.offset.flags, u32_mod::TC_U32_OFFSET);
+        assert_eq!(f.offset.plus, 8);
+
+        let err = U32Filter::parse_params(&["offset", "classid", "1:1"]).unwrap_err();
+        assert!(err.to_string().contains("requires at least one of"));
+        let err = U32Filter::parse_params(&["offset", "shift", "16"]).unwrap_err();
+        assert!(err.to_string().contains("below 16"));
+    }
+
+    #[test]
+    fn u32_link_filter_writes_offset_and_hashkey_into_sel() {
+        let f = U32Filter::new()
+            .match_ip_proto(6)
+            .link(u32_handle(2, 0, 0))
+            .hashkey(0x0000_00ff, 0)
+            .skip_ipv4_header()
+            .eat();
+        let mut b = MessageBuilder::new(0, 0);
+        f.write_options(&mut b).unwrap();
+        let bytes = b.as_bytes();
+        let link = [8u8, 0, u32_mod::TCA_U32_LINK as u8, 0, 0, 0, 0x20, 0];
+        assert!(bytes.windows(8).any(|w| w == link));
+
+        let sel_type = u32_mod::TCA_U32_SEL.to_ne_bytes();
+        let pos = bytes
+            .windows(2)
+            .position(|w| w == sel_type)
+            .expect("selector written");
+        let hdr = &bytes[pos + 2..pos + 2 + u32_mod::TcU32SelHdr::SIZE];
+        assert_eq!(
+            hdr[0],
+            u32_mod::TC_U32_TERMINAL | u32_mod::TC_U32_VAROFFSET | u32_mod::TC_U32_EAT
+        );
+        assert_eq!(hdr[1], 6, "offshift");
+        assert_eq!(&hdr[4..6], &[0x0f, 0x00], "offmask is big-endian");
+        assert_eq!(&hdr[12..16], &[0, 0, 0, 0xff], "hmask is big-endian");
+    }
+
+    #[test]
+    fn u32_actions_are_nested_under_tca_u32_act() {
+        use crate::netlink::action::GactAction;
+
+        let f = U32Filter::new()
+            .match_dst_port(22)
+            .actions(ActionList::new().with(GactAction::drop()));
+        let mut b = MessageBuilder::new(0, 0);
+        f.write_options(&mut b).unwrap();
+        let act = (u32_mod::TCA_U32_ACT | crate::netlink::attr::NLA_F_NESTED).to_ne_bytes();
+        assert!(b.as_bytes().windows(2).any(|w| w == act));
     }
 
     #[test]
@@ -1,10 +1,25 @@
 //! Netlink message fixtures for testing.
 //!
 //! This module contains pre-captured netlink messages for testing
-//! message parsing without requiring network access.
+//! message parsing without requiring network access. Each fixture is
+//! the message payload after the `nlmsghdr`, annotated field by field,
+//! and is decoded by this crate's own tests — so it doubles as a golden
+//! corpus for downstream code that wants to regression-test its
+//! handling of what nlink parses, via the `fixtures` feature.
 //!
-//! The fixtures are provided as functions that return Vec<u8> to ensure
-//! proper alignment for the parser.
+//! The fixtures are provided as functions that return `Vec<u8>` to ensure
+//! proper alignment for the parser. Host-order fields are written
+//! little-endian. With the `mock` feature they also seed
+//! `MockConnection`.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{fixtures, messages::RouteMessage, parse::FromNetlink};
+//!
+//! let route = RouteMessage::from_bytes(&fixtures::route_multipath_v4())?;
+//! assert_eq!(route.multipath().map(<[_]>::len), Some(2));
+//! ```
 
 /// Link message for loopback interface.
 /// Captured from: ip link show lo
@@ -140,12 +155,304 @@ pub fn neighbor_arp() -> Vec<u8> {
     ]
 }
 
+/// Link message for a VXLAN interface.
+/// Captured from: ip link add vxlan100 type vxlan id 100 local 10.0.0.1 dstport 4789
+pub fn link_vxlan() -> Vec<u8> {
+    vec![
+        // ifinfomsg: family=0, pad=0, type=1 (ARPHRD_ETHER), index=5, flags=0x1003 (UP|BROADCAST|MULTICAST), change=0
+        0x00, 0x00, // family, pad
+        0x01, 0x00, // type = 1 (ARPHRD_ETHER)
+        0x05, 0x00, 0x00, 0x00, // index = 5
+        0x03, 0x10, 0x00, 0x00, // flags = IFF_UP | IFF_BROADCAST | IFF_MULTICAST
+        0x00, 0x00, 0x00, 0x00, // change = 0
+        // IFLA_IFNAME = "vxlan100"
+        0x0d, 0x00, // len = 13
+        0x03, 0x00, // type = IFLA_IFNAME (3)
+        b'v', b'x', b'l', b'a', b'n', b'1', b'0', b'0', // "vxlan100"
+        0x00, 0x00, 0x00, 0x00, // "\0" + padding
+        // IFLA_MTU = 1450
+        0x08, 0x00, // len = 8
+        0x04, 0x00, // type = IFLA_MTU (4)
+        0xaa, 0x05, 0x00, 0x00, // mtu = 1450
+        // IFLA_LINKINFO (nested)
+        0x2c, 0x00, // len = 44
+        0x12, 0x00, // type = IFLA_LINKINFO (18)
+        // IFLA_INFO_KIND = "vxlan"
+        0x0a, 0x00, // len = 10
+        0x01, 0x00, // type = IFLA_INFO_KIND (1)
+        b'v', b'x', b'l', b'a', b'n', 0x00, // "vxlan\0"
+        0x00, 0x00, // padding
+        // IFLA_INFO_DATA (nested)
+        0x1c, 0x00, // len = 28
+        0x02, 0x00, // type = IFLA_INFO_DATA (2)
+        // IFLA_VXLAN_ID = 100
+        0x08, 0x00, // len = 8
+        0x01, 0x00, // type = IFLA_VXLAN_ID (1)
+        0x64, 0x00, 0x00, 0x00, // vni = 100
+        // IFLA_VXLAN_LOCAL = 10.0.0.1
+        0x08, 0x00, // len = 8
+        0x04, 0x00, // type = IFLA_VXLAN_LOCAL (4)
+        0x0a, 0x00, 0x00, 0x01, // 10.0.0.1
+        // IFLA_VXLAN_PORT = 4789 (network byte order)
+        0x06, 0x00, // len = 6
+        0x0f, 0x00, // type = IFLA_VXLAN_PORT (15)
+        0x12, 0xb5, // port = 4789
+        0x00, 0x00, // padding
+    ]
+}
+
+/// Route message for an ECMP route with two weighted nexthops.
+/// Captured from: ip route add 10.1.0.0/16 nexthop via 192.168.1.1 dev eth0 weight 1 nexthop via 192.168.2.1 dev eth1 weight 2
+pub fn route_multipath_v4() -> Vec<u8> {
+    vec![
+        // rtmsg: family=AF_INET, dst_len=16, src_len=0, tos=0, table=RT_TABLE_MAIN, protocol=RTPROT_BOOT, scope=RT_SCOPE_UNIVERSE, type=RTN_UNICAST
+        0x02, // family = AF_INET
+        0x10, // dst_len = 16
+        0x00, // src_len = 0
+        0x00, // tos = 0
+        0xfe, // table = RT_TABLE_MAIN (254)
+        0x03, // protocol = RTPROT_BOOT (3)
+        0x00, // scope = RT_SCOPE_UNIVERSE (0)
+        0x01, // type = RTN_UNICAST (1)
+        0x00, 0x00, 0x00, 0x00, // flags = 0
+        // RTA_TABLE = 254
+        0x08, 0x00, // len = 8
+        0x0f, 0x00, // type = RTA_TABLE (15)
+        0xfe, 0x00, 0x00, 0x00, // table = 254
+        // RTA_DST = 10.1.0.0
+        0x08, 0x00, // len = 8
+        0x01, 0x00, // type = RTA_DST (1)
+        0x0a, 0x01, 0x00, 0x00, // 10.1.0.0
+        // RTA_MULTIPATH
+        0x24, 0x00, // len = 36
+        0x09, 0x00, // type = RTA_MULTIPATH (9)
+        // rtnexthop: len=16, flags=0, hops=0 (weight 1), ifindex=2
+        0x10, 0x00, // rtnh_len = 16
+        0x00, // rtnh_flags = 0
+        0x00, // rtnh_hops = 0
+        0x02, 0x00, 0x00, 0x00, // rtnh_ifindex = 2
+        // RTA_GATEWAY = 192.168.1.1
+        0x08, 0x00, // len = 8
+        0x05, 0x00, // type = RTA_GATEWAY (5)
+        0xc0, 0xa8, 0x01, 0x01, // 192.168.1.1
+        // rtnexthop: len=16, flags=0, hops=1 (weight 2), ifindex=3
+        0x10, 0x00, // rtnh_len = 16
+        0x00, // rtnh_flags = 0
+        0x01, // rtnh_hops = 1
+        0x03, 0x00, 0x00, 0x00, // rtnh_ifindex = 3
+        // RTA_GATEWAY = 192.168.2.1
+        0x08, 0x00, // len = 8
+        0x05, 0x00, // type = RTA_GATEWAY (5)
+        0xc0, 0xa8, 0x02, 0x01, // 192.168.2.1
+    ]
+}
+
+/// TC message for a flower filter on the clsact ingress hook.
+/// Captured from: tc filter add dev eth0 ingress protocol ip pref 1 flower ip_proto tcp dst_ip 10.0.0.0/24 dst_port 443 classid 1:10
+pub fn tc_filter_flower() -> Vec<u8> {
+    vec![
+        // tcmsg: family=0, pad=0,0,0, ifindex=2, handle=1, parent=0xfffffff2 (clsact ingress), info=pref 1 | ETH_P_IP
+        0x00, // family
+        0x00, 0x00, 0x00, // padding
+        0x02, 0x00, 0x00, 0x00, // ifindex = 2
+        0x01, 0x00, 0x00, 0x00, // handle = 1
+        0xf2, 0xff, 0xff, 0xff, // parent = ffff:fff2
+        0x08, 0x00, 0x01, 0x00, // info = priority 1 << 16 | htons(ETH_P_IP)
+        // TCA_KIND = "flower"
+        0x0b, 0x00, // len = 11
+        0x01, 0x00, // type = TCA_KIND (1)
+        b'f', b'l', b'o', b'w', b'e', b'r', 0x00, // "flower\0"
+        0x00, // padding
+        // TCA_CHAIN = 0
+        0x08, 0x00, // len = 8
+        0x0b, 0x00, // type = TCA_CHAIN (11)
+        0x00, 0x00, 0x00, 0x00, // chain = 0
+        // TCA_OPTIONS (nested)
+        0x44, 0x00, // len = 68
+        0x02, 0x00, // type = TCA_OPTIONS (2)
+        // TCA_FLOWER_CLASSID = 1:10
+        0x08, 0x00, // len = 8
+        0x01, 0x00, // type = TCA_FLOWER_CLASSID (1)
+        0x10, 0x00, 0x01, 0x00, // classid = 0x00010010
+        // TCA_FLOWER_KEY_ETH_TYPE = ETH_P_IP (network byte order)
+        0x06, 0x00, // len = 6
+        0x08, 0x00, // type = TCA_FLOWER_KEY_ETH_TYPE (8)
+        0x08, 0x00, // 0x0800
+        0x00, 0x00, // padding
+        // TCA_FLOWER_KEY_IP_PROTO = IPPROTO_TCP
+        0x05, 0x00, // len = 5
+        0x09, 0x00, // type = TCA_FLOWER_KEY_IP_PROTO (9)
+        0x06, // ip_proto = 6
+        0x00, 0x00, 0x00, // padding
+        // TCA_FLOWER_KEY_IPV4_DST = 10.0.0.0
+        0x08, 0x00, // len = 8
+        0x0c, 0x00, // type = TCA_FLOWER_KEY_IPV4_DST (12)
+        0x0a, 0x00, 0x00, 0x00, // 10.0.0.0
+        // TCA_FLOWER_KEY_IPV4_DST_MASK = 255.255.255.0
+        0x08, 0x00, // len = 8
+        0x0d, 0x00, // type = TCA_FLOWER_KEY_IPV4_DST_MASK (13)
+        0xff, 0xff, 0xff, 0x00, // /24
+        // TCA_FLOWER_KEY_TCP_DST = 443 (network byte order)
+        0x06, 0x00, // len = 6
+        0x13, 0x00, // type = TCA_FLOWER_KEY_TCP_DST (19)
+        0x01, 0xbb, // port = 443
+        0x00, 0x00, // padding
+        // TCA_FLOWER_FLAGS = 0
+        0x08, 0x00, // len = 8
+        0x16, 0x00, // type = TCA_FLOWER_FLAGS (22)
+        0x00, 0x00, 0x00, 0x00, // flags = 0
+        // TCA_FLOWER_IN_HW_COUNT = 0
+        0x08, 0x00, // len = 8
+        0x56, 0x00, // type = TCA_FLOWER_IN_HW_COUNT (86)
+        0x00, 0x00, 0x00, 0x00, // in_hw_count = 0
+    ]
+}
+
+/// Rule message for a source-based policy routing rule.
+/// Captured from: ip rule add from 10.0.0.0/8 lookup 100 pref 1000
+pub fn rule_from_lookup() -> Vec<u8> {
+    vec![
+        // fib_rule_hdr: family=AF_INET, dst_len=0, src_len=8, tos=0, table=100, res1=0, res2=0, action=FR_ACT_TO_TBL, flags=0
+        0x02, // family = AF_INET
+        0x00, // dst_len = 0
+        0x08, // src_len = 8
+        0x00, // tos = 0
+        0x64, // table = 100
+        0x00, 0x00, // reserved
+        0x01, // action = FR_ACT_TO_TBL (1)
+        0x00, 0x00, 0x00, 0x00, // flags = 0
+        // FRA_TABLE = 100
+        0x08, 0x00, // len = 8
+        0x0f, 0x00, // type = FRA_TABLE (15)
+        0x64, 0x00, 0x00, 0x00, // table = 100
+        // FRA_PRIORITY = 1000
+        0x08, 0x00, // len = 8
+        0x06, 0x00, // type = FRA_PRIORITY (6)
+        0xe8, 0x03, 0x00, 0x00, // priority = 1000
+        // FRA_SRC = 10.0.0.0
+        0x08, 0x00, // len = 8
+        0x02, 0x00, // type = FRA_SRC (2)
+        0x0a, 0x00, 0x00, 0x00, // 10.0.0.0
+    ]
+}
+
+/// WireGuard `WG_CMD_GET_DEVICE` reply for a device with one peer.
+/// Captured from: wg show wg0 (private key omitted)
+pub fn wg_get_device() -> Vec<u8> {
+    vec![
+        // genlmsghdr: cmd=WG_CMD_GET_DEVICE, version=1, reserved=0
+        0x00, // cmd = WG_CMD_GET_DEVICE (0)
+        0x01, // version = 1
+        0x00, 0x00, // reserved
+        // WGDEVICE_A_IFINDEX = 6
+        0x08, 0x00, // len = 8
+        0x01, 0x00, // type = WGDEVICE_A_IFINDEX (1)
+        0x06, 0x00, 0x00, 0x00, // ifindex = 6
+        // WGDEVICE_A_IFNAME = "wg0"
+        0x08, 0x00, // len = 8
+        0x02, 0x00, // type = WGDEVICE_A_IFNAME (2)
+        b'w', b'g', b'0', 0x00, // "wg0\0"
+        // WGDEVICE_A_PUBLIC_KEY
+        0x24, 0x00, // len = 36
+        0x04, 0x00, // type = WGDEVICE_A_PUBLIC_KEY (4)
+        0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, // key (bytes 0-7)
+        0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, // key (bytes 8-15)
+        0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, // key (bytes 16-23)
+        0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, // key (bytes 24-31)
+        // WGDEVICE_A_LISTEN_PORT = 51820
+        0x06, 0x00, // len = 6
+        0x06, 0x00, // type = WGDEVICE_A_LISTEN_PORT (6)
+        0x6c, 0xca, // port = 51820
+        0x00, 0x00, // padding
+        // WGDEVICE_A_FWMARK = 0
+        0x08, 0x00, // len = 8
+        0x07, 0x00, // type = WGDEVICE_A_FWMARK (7)
+        0x00, 0x00, 0x00, 0x00, // fwmark = 0
+        // WGDEVICE_A_PEERS (nested)
+        0xc0, 0x00, // len = 192
+        0x08, 0x80, // type = WGDEVICE_A_PEERS (8) | NLA_F_NESTED
+        // peer 0 (nested)
+        0xbc, 0x00, // len = 188
+        0x00, 0x80, // type = 0 | NLA_F_NESTED
+        // WGPEER_A_PUBLIC_KEY
+        0x24, 0x00, // len = 36
+        0x01, 0x00, // type = WGPEER_A_PUBLIC_KEY (1)
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, // key (bytes 0-7)
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, // key (bytes 8-15)
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, // key (bytes 16-23)
+        0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, // key (bytes 24-31)
+        // WGPEER_A_PRESHARED_KEY (all zeros = not set)
+        0x24, 0x00, // len = 36
+        0x02, 0x00, // type = WGPEER_A_PRESHARED_KEY (2)
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // key (bytes 0-7)
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // key (bytes 8-15)
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // key (bytes 16-23)
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // key (bytes 24-31)
+        // WGPEER_A_ENDPOINT = 203.0.113.1:51820
+        0x14, 0x00, // len = 20
+        0x04, 0x00, // type = WGPEER_A_ENDPOINT (4)
+        0x02, 0x00, // sin_family = AF_INET
+        0xca, 0x6c, // sin_port = 51820 (network byte order)
+        0xcb, 0x00, 0x71, 0x01, // sin_addr = 203.0.113.1
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // sin_zero
+        // WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL = 25
+        0x06, 0x00, // len = 6
+        0x05, 0x00, // type = WGPEER_A_PERSISTENT_KEEPALIVE_INTERVAL (5)
+        0x19, 0x00, // interval = 25
+        0x00, 0x00, // padding
+        // WGPEER_A_LAST_HANDSHAKE_TIME = 1700000000.0
+        0x14, 0x00, // len = 20
+        0x06, 0x00, // type = WGPEER_A_LAST_HANDSHAKE_TIME (6)
+        0x00, 0xf1, 0x53, 0x65, 0x00, 0x00, 0x00, 0x00, // tv_sec = 1700000000
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tv_nsec = 0
+        // WGPEER_A_RX_BYTES = 1234
+        0x0c, 0x00, // len = 12
+        0x07, 0x00, // type = WGPEER_A_RX_BYTES (7)
+        0xd2, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // rx_bytes = 1234
+        // WGPEER_A_TX_BYTES = 5678
+        0x0c, 0x00, // len = 12
+        0x08, 0x00, // type = WGPEER_A_TX_BYTES (8)
+        0x2e, 0x16, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // tx_bytes = 5678
+        // WGPEER_A_ALLOWEDIPS (nested)
+        0x20, 0x00, // len = 32
+        0x09, 0x80, // type = WGPEER_A_ALLOWEDIPS (9) | NLA_F_NESTED
+        // allowed ip 0 (nested)
+        0x1c, 0x00, // len = 28
+        0x00, 0x80, // type = 0 | NLA_F_NESTED
+        // WGALLOWEDIP_A_FAMILY = AF_INET
+        0x06, 0x00, // len = 6
+        0x01, 0x00, // type = WGALLOWEDIP_A_FAMILY (1)
+        0x02, 0x00, // family = AF_INET
+        0x00, 0x00, // padding
+        // WGALLOWEDIP_A_IPADDR = 10.0.0.2
+        0x08, 0x00, // len = 8
+        0x02, 0x00, // type = WGALLOWEDIP_A_IPADDR (2)
+        0x0a, 0x00, 0x00, 0x02, // 10.0.0.2
+        // WGALLOWEDIP_A_CIDR_MASK = 32
+        0x05, 0x00, // len = 5
+        0x03, 0x00, // type = WGALLOWEDIP_A_CIDR_MASK (3)
+        0x20, // cidr = 32
+        0x00, 0x00, 0x00, // padding
+        // WGPEER_A_PROTOCOL_VERSION = 1
+        0x08, 0x00, // len = 8
+        0x0a, 0x00, // type = WGPEER_A_PROTOCOL_VERSION (10)
+        0x01, 0x00, 0x00, 0x00, // version = 1
+    ]
+}
+
 #[cfg(test)]
 mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
     use super::*;
     use crate::netlink::{
-        messages::{AddressMessage, LinkMessage, NeighborMessage, RouteMessage, TcMessage},
+        attr::AttrIter,
+        genl::wireguard::WgDevice,
+        messages::{
+            AddressMessage, LinkMessage, NeighborMessage, RouteMessage, RuleMessage, TcMessage,
+        },
         parse::FromNetlink,
+        tc_options::{FilterOptions, parse_filter_options},
     };
 
     #[test]
@@ -246,4 +553,114 @@ mod tests {
         // Check MAC address
         assert!(neigh.lladdr.is_some());
     }
+
+    #[test]
+    fn test_parse_link_vxlan() {
+        let data = link_vxlan();
+        let link = LinkMessage::from_bytes(&data).expect("failed to parse link message");
+
+        assert_eq!(link.ifindex(), 5);
+        assert_eq!(link.name.as_deref(), Some("vxlan100"));
+        assert_eq!(link.mtu, Some(1450));
+        assert_eq!(link.kind(), Some("vxlan"));
+
+        // VNI, local address and port from IFLA_INFO_DATA
+        let info = link.link_info().and_then(|i| i.data()).expect("info data");
+        let attrs: Vec<_> = AttrIter::new(info).collect();
+        assert_eq!(attrs.len(), 3);
+        assert_eq!(attrs[0], (1, &100u32.to_ne_bytes()[..]));
+        assert_eq!(attrs[1], (4, &[10, 0, 0, 1][..]));
+        assert_eq!(attrs[2], (15, &4789u16.to_be_bytes()[..]));
+    }
+
+    #[test]
+    fn test_parse_route_multipath_v4() {
+        let data = route_multipath_v4();
+        let route = RouteMessage::from_bytes(&data).expect("failed to parse route message");
+
+        assert_eq!(route.dst_len(), 16);
+        assert_eq!(route.table_id(), 254);
+        assert_eq!(
+            route.destination(),
+            Some(&IpAddr::V4(Ipv4Addr::new(10, 1, 0, 0)))
+        );
+        assert!(route.gateway.is_none());
+
+        let hops = route.multipath().expect("expected multipath nexthops");
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].ifindex, 2);
+        assert_eq!(hops[0].weight, 1);
+        assert_eq!(hops[0].gateway, Some(Ipv4Addr::new(192, 168, 1, 1).into()));
+        assert_eq!(hops[1].ifindex, 3);
+        assert_eq!(hops[1].weight, 2);
+        assert_eq!(hops[1].gateway, Some(Ipv4Addr::new(192, 168, 2, 1).into()));
+    }
+
+    #[test]
+    fn test_parse_tc_filter_flower() {
+        let data = tc_filter_flower();
+        let tc = TcMessage::from_bytes(&data).expect("failed to parse tc message");
+
+        assert_eq!(tc.ifindex(), 2);
+        assert_eq!(tc.kind(), Some("flower"));
+        assert_eq!(tc.priority(), 1);
+        assert_eq!(tc.protocol(), 0x0800);
+        assert_eq!(tc.chain(), Some(0));
+        assert_eq!(tc.parent_raw(), 0xfffffff2);
+
+        let Some(FilterOptions::Flower(flower)) = parse_filter_options(&tc) else {
+            panic!("expected flower options");
+        };
+        assert_eq!(flower.classid, Some(0x0001_0010));
+        assert_eq!(flower.eth_type, Some(0x0800));
+        assert_eq!(flower.ip_proto, Some(6));
+        assert_eq!(flower.dst_ip, Some((Ipv4Addr::new(10, 0, 0, 0).into(), 24)));
+        assert_eq!(flower.dst_port, Some(443));
+        assert_eq!(flower.in_hw_count, Some(0));
+    }
+
+    #[test]
+    fn test_parse_rule_from_lookup() {
+        let data = rule_from_lookup();
+        let rule = RuleMessage::from_bytes(&data).expect("failed to parse rule message");
+
+        assert!(rule.is_ipv4());
+        assert!(rule.is_lookup());
+        assert_eq!(rule.table(), 100);
+        assert_eq!(rule.priority(), 1000);
+        assert_eq!(rule.src_len(), 8);
+        assert_eq!(rule.source(), Some(Ipv4Addr::new(10, 0, 0, 0).into()));
+    }
+
+    #[test]
+    fn test_parse_wg_get_device() {
+        let data = wg_get_device();
+        let device = WgDevice::from_bytes(&data).expect("failed to parse wireguard reply");
+
+        assert_eq!(device.ifindex, Some(6));
+        assert_eq!(device.ifname.as_deref(), Some("wg0"));
+        assert_eq!(device.public_key, Some([0x11; 32]));
+        assert_eq!(device.private_key, None);
+        assert_eq!(device.listen_port, Some(51820));
+        assert_eq!(device.fwmark, Some(0));
+
+        assert_eq!(device.peers.len(), 1);
+        let peer = &device.peers[0];
+        assert_eq!(peer.public_key, [0x22; 32]);
+        assert_eq!(peer.preshared_key, None);
+        assert_eq!(peer.endpoint, Some("203.0.113.1:51820".parse().unwrap()));
+        assert_eq!(peer.persistent_keepalive, Some(25));
+        assert!(peer.last_handshake.is_some());
+        assert_eq!(peer.rx_bytes, 1234);
+        assert_eq!(peer.tx_bytes, 5678);
+        assert_eq!(peer.protocol_version, Some(1));
+        assert_eq!(peer.allowed_ips.len(), 1);
+        assert_eq!(peer.allowed_ips[0].addr, Ipv4Addr::new(10, 0, 0, 2));
+        assert_eq!(peer.allowed_ips[0].cidr, 32);
+    }
+
+    #[test]
+    fn test_parse_wg_get_device_truncated() {
+        assert!(WgDevice::from_bytes(&[0x00, 0x01]).is_err());
+    }
 }
@@ -20,7 +20,7 @@
 //! | 2 | Command + attribute + value enums | ✓ |
 //! | 3 | Device-side messages + Connection methods | ✓ |
 //! | 4 | Pin-side messages + Connection methods | ✓ |
-//! | 5 | Multicast monitor + `DpllEvent` stream | — |
+//! | 5 | Multicast monitor + `DpllEvent` stream | ✓ |
 //! | 6 | Scaling helpers + recipe + example | ✓ (example + recipe) |
 //!
 //! # Why DPLL is the macro dogfood
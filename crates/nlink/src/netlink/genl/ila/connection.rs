@@ -0,0 +1,46 @@
+//! `Connection<Ila>` helper methods.
+//!
+//! Typed wrappers over the generic
+//! [`Connection::send_typed`][crate::netlink::Connection::send_typed]
+//! / [`dump_typed_stream`][crate::netlink::Connection::dump_typed_stream]
+//! dispatch. Same shape as the IOAM6 / DPLL helpers.
+
+use crate::macros::GenlTypedDumpStream;
+use crate::netlink::{connection::Connection, error::Result};
+
+use super::Ila;
+use super::messages::{
+    IlaAddRequest, IlaDelRequest, IlaDumpRequest, IlaFlushRequest, IlaMapping, IlaReply,
+};
+
+impl Connection<Ila> {
+    /// Install a locator translation.
+    ///
+    /// `Error::is_already_exists()` if a mapping for the same
+    /// match locator + ifindex exists. Requires `CAP_NET_ADMIN`.
+    pub async fn add_mapping(&self, mapping: &IlaMapping) -> Result<()> {
+        let _: IlaReply = self.send_typed(IlaAddRequest::from(mapping)).await?;
+        Ok(())
+    }
+
+    /// Remove locator translation(s). Build the request with
+    /// [`IlaDelRequest::new`] + chained narrowing setters.
+    ///
+    /// `Error::is_not_found()` if nothing matches.
+    pub async fn del_mapping(&self, req: IlaDelRequest) -> Result<()> {
+        let _: IlaReply = self.send_typed(req).await?;
+        Ok(())
+    }
+
+    /// Stream every installed mapping. Convert each reply with
+    /// [`IlaReply::mapping`].
+    pub async fn dump_mappings(&self) -> Result<GenlTypedDumpStream<'_, Ila, IlaReply>> {
+        self.dump_typed_stream(IlaDumpRequest).await
+    }
+
+    /// Remove every mapping (kernel 4.20+).
+    pub async fn flush_mappings(&self) -> Result<()> {
+        let _: IlaReply = self.send_typed(IlaFlushRequest).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+//! Typed request + reply structs for the ILA family.
+//!
+//! ADD / DEL requests and the GET dump reply are
+//! `#[derive(GenlMessage)]`; locators travel as raw network-order
+//! `u64`s there (see [`IlaLocator::to_wire`]). The attribute-less
+//! GET and FLUSH requests implement [`GenlMessage`] by hand — the
+//! derive needs at least one field.
+//!
+//! [`IlaMapping`] is the typed face callers build and read.
+
+use crate::Result;
+use crate::macros::GenlMessage;
+use crate::netlink::MessageBuilder;
+
+use super::types::{IlaAttr, IlaCmd, IlaCsumMode, IlaHookType, IlaIdentType, IlaLocator};
+
+// ============================================================
+// Typed mapping
+// ============================================================
+
+/// One ILA translation mapping: packets whose destination locator
+/// equals `locator_match` have it rewritten to `locator`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IlaMapping {
+    /// Locator to match.
+    pub locator_match: IlaLocator,
+    /// Locator to write.
+    pub locator: IlaLocator,
+    /// Restrict to packets arriving on this device.
+    pub ifindex: Option<u32>,
+    /// Checksum handling.
+    pub csum_mode: Option<IlaCsumMode>,
+    /// Identifier type.
+    pub ident_type: Option<IlaIdentType>,
+    /// Translation hook.
+    pub hook_type: Option<IlaHookType>,
+}
+
+impl IlaMapping {
+    /// Map `locator_match` → `locator` with kernel defaults for
+    /// everything else.
+    pub fn new(locator_match: IlaLocator, locator: IlaLocator) -> Self {
+        Self {
+            locator_match,
+            locator,
+            ifindex: None,
+            csum_mode: None,
+            ident_type: None,
+            hook_type: None,
+        }
+    }
+
+    /// Restrict to one input device.
+    pub fn ifindex(mut self, ifindex: u32) -> Self {
+        self.ifindex = Some(ifindex);
+        self
+    }
+
+    /// Set the checksum mode.
+    pub fn csum_mode(mut self, mode: IlaCsumMode) -> Self {
+        self.csum_mode = Some(mode);
+        self
+    }
+
+    /// Set the identifier type.
+    pub fn ident_type(mut self, ident: IlaIdentType) -> Self {
+        self.ident_type = Some(ident);
+        self
+    }
+
+    /// Set the hook type.
+    pub fn hook_type(mut self, hook: IlaHookType) -> Self {
+        self.hook_type = Some(hook);
+        self
+    }
+}
+
+// ============================================================
+// ADD
+// ============================================================
+
+/// `ILA_CMD_ADD` request. Build from an [`IlaMapping`].
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = IlaCmd::Add)]
+pub struct IlaAddRequest {
+    /// Locator to write (network-order bytes).
+    #[genl_attr(IlaAttr::Locator)]
+    pub locator: u64,
+    /// Locator to match (network-order bytes).
+    #[genl_attr(IlaAttr::LocatorMatch)]
+    pub locator_match: u64,
+    /// Input device.
+    #[genl_attr(IlaAttr::Ifindex)]
+    pub ifindex: Option<i32>,
+    /// Checksum mode.
+    #[genl_attr(IlaAttr::CsumMode, repr = "u8")]
+    pub csum_mode: Option<IlaCsumMode>,
+    /// Identifier type.
+    #[genl_attr(IlaAttr::IdentType, repr = "u8")]
+    pub ident_type: Option<IlaIdentType>,
+    /// Hook type.
+    #[genl_attr(IlaAttr::HookType, repr = "u8")]
+    pub hook_type: Option<IlaHookType>,
+}
+
+impl From<&IlaMapping> for IlaAddRequest {
+    fn from(m: &IlaMapping) -> Self {
+        Self {
+            locator: m.locator.to_wire(),
+            locator_match: m.locator_match.to_wire(),
+            ifindex: m.ifindex.map(|i| i as i32),
+            csum_mode: m.csum_mode,
+            ident_type: m.ident_type,
+            hook_type: m.hook_type,
+        }
+    }
+}
+
+// ============================================================
+// DEL
+// ============================================================
+
+/// `ILA_CMD_DEL` request. The kernel matches on `locator_match`,
+/// and additionally on `locator` / `ifindex` when given.
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = IlaCmd::Del)]
+pub struct IlaDelRequest {
+    /// Locator to match (network-order bytes).
+    #[genl_attr(IlaAttr::LocatorMatch)]
+    pub locator_match: u64,
+    /// Narrow to the mapping writing this locator.
+    #[genl_attr(IlaAttr::Locator)]
+    pub locator: Option<u64>,
+    /// Narrow to the mapping bound to this device.
+    #[genl_attr(IlaAttr::Ifindex)]
+    pub ifindex: Option<i32>,
+}
+
+impl IlaDelRequest {
+    /// Delete the mapping(s) for `locator_match`.
+    pub fn new(locator_match: IlaLocator) -> Self {
+        Self {
+            locator_match: locator_match.to_wire(),
+            locator: None,
+            ifindex: None,
+        }
+    }
+
+    /// Only delete the mapping writing `locator`.
+    pub fn locator(mut self, locator: IlaLocator) -> Self {
+        self.locator = Some(locator.to_wire());
+        self
+    }
+
+    /// Only delete the mapping bound to `ifindex`.
+    pub fn ifindex(mut self, ifindex: u32) -> Self {
+        self.ifindex = Some(ifindex as i32);
+        self
+    }
+}
+
+// ============================================================
+// GET (dump) / FLUSH — no attributes
+// ============================================================
+
+/// `ILA_CMD_GET` dump request.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IlaDumpRequest;
+
+impl GenlMessage for IlaDumpRequest {
+    const CMD: u8 = IlaCmd::Get as u8;
+
+    fn to_bytes(&self, _builder: &mut MessageBuilder) -> Result<()> {
+        Ok(())
+    }
+
+    fn from_bytes(_payload: &[u8]) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+/// `ILA_CMD_FLUSH` request — removes every mapping.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IlaFlushRequest;
+
+impl GenlMessage for IlaFlushRequest {
+    const CMD: u8 = IlaCmd::Flush as u8;
+
+    fn to_bytes(&self, _builder: &mut MessageBuilder) -> Result<()> {
+        Ok(())
+    }
+
+    fn from_bytes(_payload: &[u8]) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+// ============================================================
+// Reply
+// ============================================================
+
+/// One mapping from the `ILA_CMD_GET` dump (also the ack body for
+/// ADD / DEL / FLUSH, where it is empty).
+#[derive(GenlMessage, Debug, Default, Clone, PartialEq, Eq)]
+#[genl_message(cmd = IlaCmd::Get)]
+pub struct IlaReply {
+    /// Locator written (network-order bytes).
+    #[genl_attr(IlaAttr::Locator)]
+    pub locator: Option<u64>,
+    /// Locator matched (network-order bytes).
+    #[genl_attr(IlaAttr::LocatorMatch)]
+    pub locator_match: Option<u64>,
+    /// Input device (0 when unbound).
+    #[genl_attr(IlaAttr::Ifindex)]
+    pub ifindex: Option<i32>,
+    /// Checksum mode.
+    #[genl_attr(IlaAttr::CsumMode, repr = "u8")]
+    pub csum_mode: Option<IlaCsumMode>,
+    /// Identifier type.
+    #[genl_attr(IlaAttr::IdentType, repr = "u8")]
+    pub ident_type: Option<IlaIdentType>,
+    /// Hook type.
+    #[genl_attr(IlaAttr::HookType, repr = "u8")]
+    pub hook_type: Option<IlaHookType>,
+}
+
+impl IlaReply {
+    /// Convert to a typed mapping. `None` if either locator is
+    /// missing.
+    pub fn mapping(&self) -> Option<IlaMapping> {
+        Some(IlaMapping {
+            locator_match: IlaLocator::from_wire(self.locator_match?),
+            locator: IlaLocator::from_wire(self.locator?),
+            ifindex: self.ifindex.filter(|&i| i > 0).map(|i| i as u32),
+            csum_mode: self.csum_mode,
+            ident_type: self.ident_type,
+            hook_type: self.hook_type,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_request_round_trips_into_reply_mapping() {
+        let m = IlaMapping::new(
+            "2001:db8:0:1".parse().unwrap(),
+            "2001:db8:0:2".parse().unwrap(),
+        )
+        .ifindex(3)
+        .csum_mode(IlaCsumMode::NeutralMap)
+        .hook_type(IlaHookType::RouteInput);
+
+        let mut b = MessageBuilder::new(0, 0);
+        let start = b.len();
+        IlaAddRequest::from(&m).to_bytes(&mut b).expect("emit");
+        let reply = IlaReply::from_bytes(&b.as_bytes()[start..]).expect("parse");
+        assert_eq!(reply.mapping(), Some(m));
+    }
+
+    #[test]
+    fn reply_without_locators_has_no_mapping() {
+        assert!(IlaReply::default().mapping().is_none());
+    }
+
+    #[test]
+    fn unit_requests_carry_their_command() {
+        assert_eq!(IlaDumpRequest::CMD, IlaCmd::Get as u8);
+        assert_eq!(IlaFlushRequest::CMD, IlaCmd::Flush as u8);
+    }
+}
@@ -0,0 +1,90 @@
+//! `ila` Generic Netlink family — Identifier-Locator Addressing.
+//!
+//! ILA (draft-herbert-intarea-ila) splits an IPv6 address into a
+//! 64-bit locator (where the node is) and a 64-bit identifier (who
+//! it is). A translating router rewrites the locator half of the
+//! destination address; this family manages the kernel's
+//! translation table, keyed on the locator to match (plus an
+//! optional input device).
+//!
+//! Route-based ILA (`LWTUNNEL_ENCAP_ILA`) is a separate mechanism
+//! and not covered here.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::Connection;
+//! use nlink::netlink::genl::ila::{Ila, IlaCsumMode, IlaDelRequest, IlaMapping};
+//! use tokio_stream::StreamExt;
+//!
+//! let conn = Connection::<Ila>::new_async().await?;
+//! let m = IlaMapping::new("2001:db8:0:1".parse()?, "2001:db8:0:2".parse()?)
+//!     .csum_mode(IlaCsumMode::NeutralMap);
+//! conn.add_mapping(&m).await?;
+//!
+//! let mut stream = conn.dump_mappings().await?;
+//! while let Some(reply) = stream.next().await {
+//!     if let Some(m) = reply?.mapping() {
+//!         println!("{} -> {}", m.locator_match, m.locator);
+//!     }
+//! }
+//!
+//! conn.del_mapping(IlaDelRequest::new("2001:db8:0:1".parse()?)).await?;
+//! ```
+//!
+//! # Permissions
+//!
+//! ADD / DEL / FLUSH require `CAP_NET_ADMIN`. The family is
+//! registered once the `ila` module is loaded
+//! (`CONFIG_IPV6_ILA`).
+
+use crate::macros::genl_family;
+
+pub mod connection;
+pub mod messages;
+pub mod types;
+
+pub use messages::{
+    IlaAddRequest, IlaDelRequest, IlaDumpRequest, IlaFlushRequest, IlaMapping, IlaReply,
+};
+pub use types::{IlaAttr, IlaCmd, IlaCsumMode, IlaHookType, IlaIdentType, IlaLocator};
+
+/// ILA Generic Netlink family marker.
+///
+/// Constructed via [`Connection::<Ila>::new_async()`][Connection].
+/// Returns [`Error::FamilyNotFound`](crate::Error::FamilyNotFound)
+/// when the `ila` module is not loaded.
+///
+/// [Connection]: crate::netlink::Connection
+#[genl_family(name = "ila", version = 1)]
+pub struct Ila;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::{
+        AsyncProtocolInit, Protocol, ProtocolState, construction::AsyncConstructible,
+    };
+
+    #[test]
+    fn family_marker_carries_expected_name_and_version() {
+        assert_eq!(Ila::NAME, "ila");
+        assert_eq!(Ila::VERSION, 1);
+    }
+
+    #[test]
+    fn protocol_state_routes_to_generic() {
+        const _: () = {
+            assert!(matches!(Ila::PROTOCOL, Protocol::Generic));
+        };
+    }
+
+    fn assert_async_constructible<P: AsyncConstructible>() {}
+    fn assert_async_protocol_init<P: AsyncProtocolInit>() {}
+
+    #[test]
+    fn ila_satisfies_async_construction_bounds() {
+        assert_async_constructible::<Ila>();
+        assert_async_protocol_init::<Ila>();
+    }
+}
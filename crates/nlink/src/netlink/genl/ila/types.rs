@@ -0,0 +1,197 @@
+//! ILA command + attribute + value enums, and the 64-bit
+//! [`IlaLocator`].
+//!
+//! Direct translation of the kernel UAPI in
+//! `include/uapi/linux/ila.h`, expressed via the `nlink-macros`
+//! typed-codec derives.
+
+use std::{fmt, str::FromStr};
+
+use crate::macros::{GenlAttribute, GenlCommand, GenlEnum};
+use crate::{Error, Result};
+
+/// ILA command codes. Sent in the GENL header's `cmd` byte.
+///
+/// Wire: `u8` per the kernel UAPI.
+#[derive(GenlCommand, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_command(repr = "u8")]
+#[non_exhaustive]
+pub enum IlaCmd {
+    /// `ILA_CMD_ADD` — install a translation mapping.
+    Add = 1,
+    /// `ILA_CMD_DEL` — remove a translation mapping.
+    Del = 2,
+    /// `ILA_CMD_GET` — dump every mapping.
+    Get = 3,
+    /// `ILA_CMD_FLUSH` — remove every mapping (kernel 4.20+).
+    Flush = 4,
+}
+
+/// ILA attribute kinds.
+///
+/// Wire: `u16`.
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum IlaAttr {
+    /// `ILA_ATTR_LOCATOR` — locator to write (u64, network-order
+    /// bytes).
+    Locator = 1,
+    /// `ILA_ATTR_IDENTIFIER` — identifier (u64, unused by the
+    /// xlat table).
+    Identifier = 2,
+    /// `ILA_ATTR_LOCATOR_MATCH` — locator to match (u64,
+    /// network-order bytes).
+    LocatorMatch = 3,
+    /// `ILA_ATTR_IFINDEX` — restrict to one input device (s32).
+    Ifindex = 4,
+    /// `ILA_ATTR_DIR` — direction bitmask (u32; obsolete).
+    Dir = 5,
+    /// `ILA_ATTR_PAD`.
+    Pad = 6,
+    /// `ILA_ATTR_CSUM_MODE` — [`IlaCsumMode`] (u8).
+    CsumMode = 7,
+    /// `ILA_ATTR_IDENT_TYPE` — [`IlaIdentType`] (u8).
+    IdentType = 8,
+    /// `ILA_ATTR_HOOK_TYPE` — [`IlaHookType`] (u8).
+    HookType = 9,
+}
+
+/// Checksum handling on translation (`ILA_CSUM_*`).
+#[derive(GenlEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_enum(repr = "u8")]
+#[non_exhaustive]
+pub enum IlaCsumMode {
+    /// `ILA_CSUM_ADJUST_TRANSPORT` — fix up the transport checksum.
+    AdjustTransport = 0,
+    /// `ILA_CSUM_NEUTRAL_MAP` — checksum-neutral mapping.
+    NeutralMap = 1,
+    /// `ILA_CSUM_NO_ACTION` — leave checksums alone.
+    NoAction = 2,
+    /// `ILA_CSUM_NEUTRAL_MAP_AUTO` — neutral map, decided per
+    /// packet.
+    NeutralMapAuto = 3,
+}
+
+/// Identifier type (`ILA_ATYPE_*`).
+#[derive(GenlEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_enum(repr = "u8")]
+#[non_exhaustive]
+pub enum IlaIdentType {
+    /// `ILA_ATYPE_IID` — interface identifier.
+    Iid = 0,
+    /// `ILA_ATYPE_LUID` — locally unique identifier.
+    Luid = 1,
+    /// `ILA_ATYPE_VIRT_V4` — virtual IPv4 address.
+    VirtV4 = 2,
+    /// `ILA_ATYPE_VIRT_UNI_V6` — virtual IPv6 unicast.
+    VirtUniV6 = 3,
+    /// `ILA_ATYPE_VIRT_MULTI_V6` — virtual IPv6 multicast.
+    VirtMultiV6 = 4,
+    /// `ILA_ATYPE_NONLOCAL_ADDR` — non-local address.
+    NonlocalAddr = 5,
+    /// `ILA_ATYPE_USE_FORMAT` — read the type from the identifier.
+    UseFormat = 32,
+}
+
+/// Where the translation hook runs (`ILA_HOOK_*`).
+#[derive(GenlEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_enum(repr = "u8")]
+#[non_exhaustive]
+pub enum IlaHookType {
+    /// `ILA_HOOK_ROUTE_OUTPUT`.
+    RouteOutput = 0,
+    /// `ILA_HOOK_ROUTE_INPUT`.
+    RouteInput = 1,
+}
+
+/// A 64-bit ILA locator — the upper half of an IPv6 address.
+///
+/// Parses and displays in the iproute2 form: four colon-separated
+/// groups of up to four hex digits (`2001:db8:0:1`). The wrapped
+/// value is the numeric locator (first group most significant);
+/// [`to_wire`](Self::to_wire) / [`from_wire`](Self::from_wire)
+/// convert to the network-byte-order `u64` the kernel stores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct IlaLocator(pub u64);
+
+impl IlaLocator {
+    /// Encode for an `ILA_ATTR_LOCATOR*` attribute.
+    pub fn to_wire(self) -> u64 {
+        self.0.to_be()
+    }
+
+    /// Decode an `ILA_ATTR_LOCATOR*` attribute.
+    pub fn from_wire(raw: u64) -> Self {
+        Self(u64::from_be(raw))
+    }
+}
+
+impl fmt::Display for IlaLocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = self.0;
+        write!(
+            f,
+            "{:x}:{:x}:{:x}:{:x}",
+            (v >> 48) & 0xffff,
+            (v >> 32) & 0xffff,
+            (v >> 16) & 0xffff,
+            v & 0xffff
+        )
+    }
+}
+
+impl FromStr for IlaLocator {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            Error::InvalidMessage(format!(
+                "ila: invalid locator `{s}` (expected four hex groups, e.g. 2001:db8:0:1)"
+            ))
+        };
+        let mut value = 0u64;
+        let mut groups = 0;
+        for part in s.split(':') {
+            if part.is_empty() || part.len() > 4 {
+                return Err(invalid());
+            }
+            let g = u16::from_str_radix(part, 16).map_err(|_| invalid())?;
+            value = (value << 16) | u64::from(g);
+            groups += 1;
+        }
+        if groups != 4 {
+            return Err(invalid());
+        }
+        Ok(Self(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locator_parses_and_displays() {
+        let l: IlaLocator = "2001:db8:0:1".parse().unwrap();
+        assert_eq!(l.0, 0x2001_0db8_0000_0001);
+        assert_eq!(l.to_string(), "2001:db8:0:1");
+    }
+
+    #[test]
+    fn locator_wire_bytes_are_network_order() {
+        let l: IlaLocator = "2001:db8:0:1".parse().unwrap();
+        assert_eq!(
+            l.to_wire().to_ne_bytes(),
+            [0x20, 0x01, 0x0d, 0xb8, 0x00, 0x00, 0x00, 0x01]
+        );
+        assert_eq!(IlaLocator::from_wire(l.to_wire()), l);
+    }
+
+    #[test]
+    fn locator_rejects_malformed() {
+        for bad in ["", "1:2:3", "1:2:3:4:5", "1::2:3", "12345:0:0:0", "g:0:0:0"] {
+            assert!(bad.parse::<IlaLocator>().is_err(), "{bad}");
+        }
+    }
+}
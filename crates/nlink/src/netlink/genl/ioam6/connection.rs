@@ -0,0 +1,92 @@
+//! `Connection<Ioam6>` helper methods.
+//!
+//! Typed wrappers over the generic
+//! [`Connection::send_typed`][crate::netlink::Connection::send_typed]
+//! / [`dump_typed_stream`][crate::netlink::Connection::dump_typed_stream]
+//! dispatch. Same shape as the DPLL / net_shaper helpers.
+
+use crate::macros::GenlTypedDumpStream;
+use crate::netlink::{connection::Connection, error::Result};
+
+use super::Ioam6;
+use super::messages::{
+    Ioam6Namespace, Ioam6NamespaceAddRequest, Ioam6NamespaceDelRequest, Ioam6NamespaceDumpRequest,
+    Ioam6NsSetSchemaRequest, Ioam6Schema, Ioam6SchemaAddRequest, Ioam6SchemaDelRequest,
+    Ioam6SchemaDumpRequest,
+};
+
+impl Connection<Ioam6> {
+    /// Create an IOAM namespace. Build the request with
+    /// [`Ioam6NamespaceAddRequest::new`] + chained setters.
+    ///
+    /// `Error::is_already_exists()` if the id is taken. Requires
+    /// `CAP_NET_ADMIN`.
+    pub async fn add_namespace(&self, req: Ioam6NamespaceAddRequest) -> Result<()> {
+        let _: Ioam6Namespace = self.send_typed(req).await?;
+        Ok(())
+    }
+
+    /// Remove an IOAM namespace (detaching its schema, if any).
+    ///
+    /// `Error::is_not_found()` if no such namespace exists.
+    pub async fn del_namespace(&self, id: u16) -> Result<()> {
+        let _: Ioam6Namespace = self.send_typed(Ioam6NamespaceDelRequest { id }).await?;
+        Ok(())
+    }
+
+    /// Stream every configured IOAM namespace.
+    ///
+    /// ```ignore
+    /// use tokio_stream::StreamExt;
+    /// let mut stream = conn.dump_namespaces().await?;
+    /// while let Some(ns) = stream.next().await {
+    ///     let ns = ns?;
+    ///     println!("ns {} schema {:?}", ns.id, ns.schema_id);
+    /// }
+    /// ```
+    pub async fn dump_namespaces(&self) -> Result<GenlTypedDumpStream<'_, Ioam6, Ioam6Namespace>> {
+        self.dump_typed_stream(Ioam6NamespaceDumpRequest).await
+    }
+
+    /// Create an IOAM opaque-state schema.
+    ///
+    /// `Error::is_already_exists()` if the id is taken. Requires
+    /// `CAP_NET_ADMIN`.
+    pub async fn add_schema(&self, id: u32, data: impl Into<Vec<u8>>) -> Result<()> {
+        let _: Ioam6Schema = self
+            .send_typed(Ioam6SchemaAddRequest::new(id, data))
+            .await?;
+        Ok(())
+    }
+
+    /// Remove an IOAM schema (detaching it from its namespace, if
+    /// any).
+    pub async fn del_schema(&self, id: u32) -> Result<()> {
+        let _: Ioam6Schema = self.send_typed(Ioam6SchemaDelRequest { id }).await?;
+        Ok(())
+    }
+
+    /// Stream every configured IOAM schema.
+    pub async fn dump_schemas(&self) -> Result<GenlTypedDumpStream<'_, Ioam6, Ioam6Schema>> {
+        self.dump_typed_stream(Ioam6SchemaDumpRequest).await
+    }
+
+    /// Attach schema `schema_id` to namespace `namespace_id`, or
+    /// detach the namespace's current schema with `None`.
+    ///
+    /// A schema can be attached to at most one namespace; attaching
+    /// it elsewhere moves it.
+    pub async fn set_namespace_schema(
+        &self,
+        namespace_id: u16,
+        schema_id: Option<u32>,
+    ) -> Result<()> {
+        let _: Ioam6Namespace = self
+            .send_typed(Ioam6NsSetSchemaRequest {
+                namespace_id,
+                schema_id,
+            })
+            .await?;
+        Ok(())
+    }
+}
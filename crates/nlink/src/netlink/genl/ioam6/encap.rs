@@ -0,0 +1,233 @@
+//! IOAM6 lightweight-tunnel route encapsulation
+//! (`LWTUNNEL_ENCAP_IOAM6`).
+//!
+//! Attached to an [`Ipv6Route`](crate::netlink::route::Ipv6Route)
+//! via [`ioam6_encap`](crate::netlink::route::Ipv6Route::ioam6_encap):
+//! packets matching the route get an IOAM Pre-allocated Trace
+//! option inserted (inline) or are wrapped in an outer IPv6 header
+//! carrying it (encap). Transit nodes with `ioam6_enabled` then
+//! fill in their per-hop data for the trace's namespace.
+//!
+//! Equivalent to `ip -6 route add ... encap ioam6 [freq K/N]
+//! mode {inline|encap|auto} [tundst ADDR] trace prealloc type
+//! TYPE ns NS size SIZE`.
+
+use std::net::Ipv6Addr;
+
+use crate::netlink::{
+    builder::MessageBuilder,
+    types::{mpls::lwtunnel_encap, route::RtaAttr},
+};
+
+/// IOAM6 lwtunnel attributes (`IOAM6_IPTUNNEL_*`).
+pub mod ioam6_iptunnel {
+    /// Insertion mode (u8, [`ioam6_mode`](super::ioam6_mode)).
+    pub const MODE: u16 = 1;
+    /// Tunnel destination (`struct in6_addr`), encap/auto only.
+    pub const DST: u16 = 2;
+    /// Pre-allocated trace header (`struct ioam6_trace_hdr`).
+    pub const TRACE: u16 = 3;
+    /// Insertion frequency numerator (u32).
+    pub const FREQ_K: u16 = 4;
+    /// Insertion frequency denominator (u32).
+    pub const FREQ_N: u16 = 5;
+}
+
+/// IOAM6 insertion modes (`IOAM6_IPTUNNEL_MODE_*`).
+pub mod ioam6_mode {
+    /// Insert the option into the packet's own IPv6 header.
+    pub const INLINE: u8 = 1;
+    /// Wrap the packet in an outer IPv6 header carrying the option.
+    pub const ENCAP: u8 = 2;
+    /// Inline for locally-generated traffic, encap for forwarded.
+    pub const AUTO: u8 = 3;
+}
+
+/// Largest pre-allocated trace data area, in bytes
+/// (`IOAM6_TRACE_DATA_SIZE_MAX`).
+pub const IOAM6_TRACE_DATA_SIZE_MAX: u8 = 244;
+
+/// IOAM6 insertion mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Ioam6Mode {
+    /// Insert into the existing IPv6 header (kernel default).
+    #[default]
+    Inline,
+    /// Encapsulate in a new IPv6 header towards a tunnel endpoint.
+    Encap,
+    /// Inline for local traffic, encap for forwarded traffic.
+    Auto,
+}
+
+impl Ioam6Mode {
+    /// Convert to kernel value.
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Self::Inline => ioam6_mode::INLINE,
+            Self::Encap => ioam6_mode::ENCAP,
+            Self::Auto => ioam6_mode::AUTO,
+        }
+    }
+
+    /// Parse from kernel value.
+    pub fn from_u8(val: u8) -> Option<Self> {
+        match val {
+            ioam6_mode::INLINE => Some(Self::Inline),
+            ioam6_mode::ENCAP => Some(Self::Encap),
+            ioam6_mode::AUTO => Some(Self::Auto),
+            _ => None,
+        }
+    }
+}
+
+/// IOAM6 route encapsulation builder.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::route::Ipv6Route;
+/// use nlink::netlink::genl::ioam6::{Ioam6Encap, Ioam6Mode};
+///
+/// // Trace hop_lim + node_id (bit 0) and ingress/egress ids (bit 1)
+/// // in namespace 123, 12 bytes of pre-allocated space.
+/// let route = Ipv6Route::new("2001:db8::", 64)
+///     .dev("eth0")
+///     .ioam6_encap(
+///         Ioam6Encap::trace(123, 0xc00000, 12)
+///             .mode(Ioam6Mode::Encap)
+///             .tunnel_dst("2001:db8::100".parse()?),
+///     );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Ioam6Encap {
+    mode: Option<Ioam6Mode>,
+    tunnel_dst: Option<Ipv6Addr>,
+    namespace_id: u16,
+    trace_type: u32,
+    size: u8,
+    freq: Option<(u32, u32)>,
+}
+
+impl Ioam6Encap {
+    /// Pre-allocated trace for `namespace_id`.
+    ///
+    /// `trace_type` is the 24-bit IOAM trace-type bitmap (bit 0 is
+    /// the most significant — `0x800000` asks for hop_lim +
+    /// node_id). `size` is the pre-allocated data area in bytes: a
+    /// multiple of 4, at most [`IOAM6_TRACE_DATA_SIZE_MAX`]. The
+    /// kernel validates both.
+    pub fn trace(namespace_id: u16, trace_type: u32, size: u8) -> Self {
+        Self {
+            namespace_id,
+            trace_type,
+            size,
+            ..Self::default()
+        }
+    }
+
+    /// Set the insertion mode. Omitted → kernel default (inline).
+    pub fn mode(mut self, mode: Ioam6Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Set the tunnel destination (required for encap/auto modes).
+    pub fn tunnel_dst(mut self, dst: Ipv6Addr) -> Self {
+        self.tunnel_dst = Some(dst);
+        self
+    }
+
+    /// Insert the trace in `k` out of every `n` packets
+    /// (`1 <= k <= n <= 1_000_000`). Omitted → every packet.
+    pub fn frequency(mut self, k: u32, n: u32) -> Self {
+        self.freq = Some((k, n));
+        self
+    }
+
+    /// Serialize `struct ioam6_trace_hdr` (8 bytes, no data area).
+    ///
+    /// `nodelen` / `overflow` are computed by the kernel; only the
+    /// namespace, the remaining length in 4-octet units, and the
+    /// big-endian trace type (low byte reserved) are set.
+    fn trace_hdr(&self) -> [u8; 8] {
+        let mut hdr = [0u8; 8];
+        hdr[0..2].copy_from_slice(&self.namespace_id.to_be_bytes());
+        // remlen occupies the low 7 bits of byte 3 under both
+        // bitfield orders.
+        hdr[3] = (self.size / 4) & 0x7f;
+        hdr[4..8].copy_from_slice(&((self.trace_type & 0x00ff_ffff) << 8).to_be_bytes());
+        hdr
+    }
+
+    /// Write `RTA_ENCAP_TYPE` + `RTA_ENCAP` to a route message.
+    pub(crate) fn write_to(&self, builder: &mut MessageBuilder) {
+        builder.append_attr_u16(RtaAttr::EncapType as u16, lwtunnel_encap::IOAM6);
+
+        let encap_nest = builder.nest_start(RtaAttr::Encap as u16);
+        if let Some((k, n)) = self.freq {
+            builder.append_attr_u32(ioam6_iptunnel::FREQ_K, k);
+            builder.append_attr_u32(ioam6_iptunnel::FREQ_N, n);
+        }
+        if let Some(mode) = self.mode {
+            builder.append_attr_u8(ioam6_iptunnel::MODE, mode.to_u8());
+        }
+        if let Some(dst) = self.tunnel_dst {
+            builder.append_attr(ioam6_iptunnel::DST, &dst.octets());
+        }
+        builder.append_attr(ioam6_iptunnel::TRACE, &self.trace_hdr());
+        builder.nest_end(encap_nest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::attr::AttrIter;
+
+    #[test]
+    fn trace_header_layout_matches_iproute2() {
+        // `trace prealloc type 0x800000 ns 1 size 12`
+        let hdr = Ioam6Encap::trace(1, 0x800000, 12).trace_hdr();
+        assert_eq!(hdr, [0x00, 0x01, 0x00, 0x03, 0x80, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn write_to_emits_encap_type_and_nested_attrs() {
+        let encap = Ioam6Encap::trace(5, 0xc00000, 8)
+            .mode(Ioam6Mode::Encap)
+            .tunnel_dst("2001:db8::1".parse().unwrap())
+            .frequency(1, 10);
+        let mut b = MessageBuilder::new(0, 0);
+        encap.write_to(&mut b);
+        let msg = b.finish();
+
+        let attrs: Vec<(u16, &[u8])> = AttrIter::new(&msg[16..]).collect();
+        assert_eq!(attrs[0].0, RtaAttr::EncapType as u16);
+        assert_eq!(attrs[0].1, &lwtunnel_encap::IOAM6.to_ne_bytes());
+        assert_eq!(
+            attrs[1].0 & !crate::netlink::attr::NLA_F_NESTED,
+            RtaAttr::Encap as u16
+        );
+
+        let inner: Vec<u16> = AttrIter::new(attrs[1].1).map(|(t, _)| t).collect();
+        assert_eq!(
+            inner,
+            vec![
+                ioam6_iptunnel::FREQ_K,
+                ioam6_iptunnel::FREQ_N,
+                ioam6_iptunnel::MODE,
+                ioam6_iptunnel::DST,
+                ioam6_iptunnel::TRACE,
+            ]
+        );
+    }
+
+    #[test]
+    fn mode_round_trips() {
+        for m in [Ioam6Mode::Inline, Ioam6Mode::Encap, Ioam6Mode::Auto] {
+            assert_eq!(Ioam6Mode::from_u8(m.to_u8()), Some(m));
+        }
+        assert_eq!(Ioam6Mode::from_u8(0), None);
+    }
+}
@@ -0,0 +1,99 @@
+//! `IOAM6` Generic Netlink family — In-situ OAM for IPv6.
+//!
+//! IOAM (RFC 9197 / RFC 9486) records per-hop telemetry — node
+//! id, ingress/egress interface, timestamps, queue depth, opaque
+//! state — into a Pre-allocated Trace option carried by the
+//! packet itself. Linux splits the configuration in two:
+//!
+//! - **Namespaces and schemas** (this family): every IOAM-capable
+//!   node needs the namespace configured (with its data) before it
+//!   will fill in traces for it; an optional opaque-state schema
+//!   can be attached per namespace.
+//! - **Insertion** (a route encap): the encapsulating node adds
+//!   the trace via an `LWTUNNEL_ENCAP_IOAM6` route — see
+//!   [`Ioam6Encap`] and
+//!   [`Ipv6Route::ioam6_encap`](crate::netlink::route::Ipv6Route::ioam6_encap).
+//!
+//! Transit nodes also need `net.ipv6.ioam6_id` and the
+//! per-interface `ioam6_enabled` / `ioam6_id` sysctls set.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//! use nlink::netlink::genl::ioam6::{Ioam6, Ioam6Encap, Ioam6NamespaceAddRequest};
+//! use nlink::netlink::route::Ipv6Route;
+//!
+//! let ioam = Connection::<Ioam6>::new_async().await?;
+//! ioam.add_namespace(Ioam6NamespaceAddRequest::new(123).data(0xcafe)).await?;
+//! ioam.add_schema(7, b"opaque".to_vec()).await?;
+//! ioam.set_namespace_schema(123, Some(7)).await?;
+//!
+//! let conn = Connection::<Route>::new()?;
+//! conn.add_route(
+//!     Ipv6Route::new("2001:db8::", 64)
+//!         .dev("eth0")
+//!         .ioam6_encap(Ioam6Encap::trace(123, 0x800000, 12)),
+//! ).await?;
+//! ```
+//!
+//! # Permissions
+//!
+//! Every command except the two dumps requires `CAP_NET_ADMIN`.
+//! The family is registered when the kernel is built with
+//! `CONFIG_IPV6_IOAM6_LWTUNNEL` (5.15+).
+
+use crate::macros::genl_family;
+
+pub mod connection;
+pub mod encap;
+pub mod messages;
+pub mod types;
+
+pub use encap::{IOAM6_TRACE_DATA_SIZE_MAX, Ioam6Encap, Ioam6Mode};
+pub use messages::{
+    Ioam6Namespace, Ioam6NamespaceAddRequest, Ioam6NamespaceDelRequest, Ioam6NamespaceDumpRequest,
+    Ioam6NsSetSchemaRequest, Ioam6Schema, Ioam6SchemaAddRequest, Ioam6SchemaDelRequest,
+    Ioam6SchemaDumpRequest,
+};
+pub use types::{IOAM6_MAX_SCHEMA_DATA_LEN, Ioam6Attr, Ioam6Cmd};
+
+/// IOAM6 Generic Netlink family marker.
+///
+/// Constructed via [`Connection::<Ioam6>::new_async()`][Connection].
+/// Returns [`Error::FamilyNotFound`](crate::Error::FamilyNotFound)
+/// on kernels without IOAM6 support.
+///
+/// [Connection]: crate::netlink::Connection
+#[genl_family(name = "IOAM6", version = 1)]
+pub struct Ioam6;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::{
+        AsyncProtocolInit, Protocol, ProtocolState, construction::AsyncConstructible,
+    };
+
+    #[test]
+    fn family_marker_carries_expected_name_and_version() {
+        assert_eq!(Ioam6::NAME, "IOAM6");
+        assert_eq!(Ioam6::VERSION, 1);
+    }
+
+    #[test]
+    fn protocol_state_routes_to_generic() {
+        const _: () = {
+            assert!(matches!(Ioam6::PROTOCOL, Protocol::Generic));
+        };
+    }
+
+    fn assert_async_constructible<P: AsyncConstructible>() {}
+    fn assert_async_protocol_init<P: AsyncProtocolInit>() {}
+
+    #[test]
+    fn ioam6_satisfies_async_construction_bounds() {
+        assert_async_constructible::<Ioam6>();
+        assert_async_protocol_init::<Ioam6>();
+    }
+}
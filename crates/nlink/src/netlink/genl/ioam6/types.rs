@@ -0,0 +1,58 @@
+//! IOAM6 command + attribute enums.
+//!
+//! Direct translation of the kernel UAPI in
+//! `include/uapi/linux/ioam6_genl.h`, expressed via the
+//! `nlink-macros` typed-codec derives.
+
+use crate::macros::{GenlAttribute, GenlCommand};
+
+/// Largest schema payload the kernel accepts
+/// (`IOAM6_MAX_SCHEMA_DATA_LEN`, 255 × 4 bytes).
+pub const IOAM6_MAX_SCHEMA_DATA_LEN: usize = 255 * 4;
+
+/// IOAM6 command codes. Sent in the GENL header's `cmd` byte.
+///
+/// Wire: `u8` per the kernel UAPI.
+#[derive(GenlCommand, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_command(repr = "u8")]
+#[non_exhaustive]
+pub enum Ioam6Cmd {
+    /// `IOAM6_CMD_ADD_NAMESPACE`.
+    AddNamespace = 1,
+    /// `IOAM6_CMD_DEL_NAMESPACE`.
+    DelNamespace = 2,
+    /// `IOAM6_CMD_DUMP_NAMESPACES` (dump only).
+    DumpNamespaces = 3,
+    /// `IOAM6_CMD_ADD_SCHEMA`.
+    AddSchema = 4,
+    /// `IOAM6_CMD_DEL_SCHEMA`.
+    DelSchema = 5,
+    /// `IOAM6_CMD_DUMP_SCHEMAS` (dump only).
+    DumpSchemas = 6,
+    /// `IOAM6_CMD_NS_SET_SCHEMA` — attach a schema to a
+    /// namespace, or detach with `IOAM6_ATTR_SC_NONE`.
+    NsSetSchema = 7,
+}
+
+/// IOAM6 attribute kinds (one flat set shared by every command).
+///
+/// Wire: `u16`.
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum Ioam6Attr {
+    /// `IOAM6_ATTR_NS_ID` — namespace id (u16).
+    NsId = 1,
+    /// `IOAM6_ATTR_NS_DATA` — 4-byte namespace data (u32).
+    NsData = 2,
+    /// `IOAM6_ATTR_NS_DATA_WIDE` — 8-byte namespace data (u64).
+    NsDataWide = 3,
+    /// `IOAM6_ATTR_SC_ID` — schema id (u32, 24 bits used).
+    ScId = 4,
+    /// `IOAM6_ATTR_SC_DATA` — opaque schema payload.
+    ScData = 5,
+    /// `IOAM6_ATTR_SC_NONE` — flag: detach the namespace's schema.
+    ScNone = 6,
+    /// `IOAM6_ATTR_PAD`.
+    Pad = 7,
+}
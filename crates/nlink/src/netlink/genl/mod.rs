@@ -50,11 +50,16 @@ pub use header::{GENL_HDRLEN, GenlMsgHdr};
 pub mod devlink;
 pub mod dpll;
 pub mod ethtool;
+pub mod ila;
+pub mod ioam6;
 pub mod macsec;
 pub mod mptcp;
 pub mod net_shaper;
 pub mod nl80211;
 pub mod ovpn;
+pub mod smc;
+pub mod taskstats;
+pub mod vdpa;
 pub mod wireguard;
 
 // Control family constants (fixed, not dynamically assigned)
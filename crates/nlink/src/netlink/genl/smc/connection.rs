@@ -0,0 +1,42 @@
+//! `Connection<Smc>` helper methods.
+//!
+//! Typed wrappers over the generic
+//! [`dump_typed_stream`][crate::netlink::Connection::dump_typed_stream]
+//! dispatch. Same shape as the IOAM6 / DPLL helpers.
+
+use crate::macros::GenlTypedDumpStream;
+use crate::netlink::{connection::Connection, error::Result};
+
+use super::Smc;
+use super::messages::{
+    SmcdLinkGroupDumpRequest, SmcdReply, SmcrLinkDumpRequest, SmcrLinkGroupDumpRequest, SmcrReply,
+};
+
+impl Connection<Smc> {
+    /// Stream every SMC-R link group (`smcr linkgroup`).
+    ///
+    /// ```ignore
+    /// use tokio_stream::StreamExt;
+    /// let mut stream = conn.dump_smcr_link_groups().await?;
+    /// while let Some(frame) = stream.next().await {
+    ///     if let Some(lgr) = frame?.link_group {
+    ///         println!("{:08x} {} conns {}", lgr.id, lgr.role_name(), lgr.conns);
+    ///     }
+    /// }
+    /// ```
+    pub async fn dump_smcr_link_groups(&self) -> Result<GenlTypedDumpStream<'_, Smc, SmcrReply>> {
+        self.dump_typed_stream(SmcrLinkGroupDumpRequest).await
+    }
+
+    /// Stream every SMC-R link group followed by its links
+    /// (`smcr linkgroup link-show`). Each link frame belongs to the
+    /// most recent link-group frame.
+    pub async fn dump_smcr_links(&self) -> Result<GenlTypedDumpStream<'_, Smc, SmcrReply>> {
+        self.dump_typed_stream(SmcrLinkDumpRequest).await
+    }
+
+    /// Stream every SMC-D link group (`smcd linkgroup`).
+    pub async fn dump_smcd_link_groups(&self) -> Result<GenlTypedDumpStream<'_, Smc, SmcdReply>> {
+        self.dump_typed_stream(SmcdLinkGroupDumpRequest).await
+    }
+}
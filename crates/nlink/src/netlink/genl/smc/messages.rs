@@ -0,0 +1,277 @@
+//! Typed request + reply structs for the SMC family.
+//!
+//! Every query is a dump with an empty request body, so the requests
+//! implement [`GenlMessage`] by hand (the derive needs at least one
+//! field). Replies are `#[derive(GenlMessage)]`; each frame carries
+//! one top-level `SMC_GEN_*` nest decoded via
+//! `#[derive(NetlinkAttrs)]`.
+
+use crate::Result;
+use crate::macros::{GenlMessage, NetlinkAttrs};
+use crate::netlink::MessageBuilder;
+
+use super::types::{SmcCmd, SmcGenAttr, SmcLgrDAttr, SmcLgrRAttr, SmcLinkAttr};
+
+// ============================================================
+// Requests — no attributes
+// ============================================================
+
+/// `SMC_NETLINK_GET_LGR_SMCR` dump request.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SmcrLinkGroupDumpRequest;
+
+impl GenlMessage for SmcrLinkGroupDumpRequest {
+    const CMD: u8 = SmcCmd::GetLgrSmcr as u8;
+
+    fn to_bytes(&self, _builder: &mut MessageBuilder) -> Result<()> {
+        Ok(())
+    }
+
+    fn from_bytes(_payload: &[u8]) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+/// `SMC_NETLINK_GET_LINK_SMCR` dump request.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SmcrLinkDumpRequest;
+
+impl GenlMessage for SmcrLinkDumpRequest {
+    const CMD: u8 = SmcCmd::GetLinkSmcr as u8;
+
+    fn to_bytes(&self, _builder: &mut MessageBuilder) -> Result<()> {
+        Ok(())
+    }
+
+    fn from_bytes(_payload: &[u8]) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+/// `SMC_NETLINK_GET_LGR_SMCD` dump request.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SmcdLinkGroupDumpRequest;
+
+impl GenlMessage for SmcdLinkGroupDumpRequest {
+    const CMD: u8 = SmcCmd::GetLgrSmcd as u8;
+
+    fn to_bytes(&self, _builder: &mut MessageBuilder) -> Result<()> {
+        Ok(())
+    }
+
+    fn from_bytes(_payload: &[u8]) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+// ============================================================
+// Nested objects
+// ============================================================
+
+/// One SMC-R link group (`SMC_GEN_LGR_SMCR`).
+#[derive(NetlinkAttrs, Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SmcrLinkGroup {
+    /// Link-group id.
+    #[genl_attr(SmcLgrRAttr::Id)]
+    pub id: u32,
+    /// Role: 0 = client, 1 = server.
+    #[genl_attr(SmcLgrRAttr::Role)]
+    pub role: u8,
+    /// Redundancy type; see [`Self::type_name`].
+    #[genl_attr(SmcLgrRAttr::Type)]
+    pub lgr_type: u8,
+    /// PNET id the link group is bound to.
+    #[genl_attr(SmcLgrRAttr::Pnetid)]
+    pub pnetid: Option<String>,
+    /// VLAN id.
+    #[genl_attr(SmcLgrRAttr::VlanId)]
+    pub vlan_id: u8,
+    /// Number of connections using the link group.
+    #[genl_attr(SmcLgrRAttr::ConnsNum)]
+    pub conns: u32,
+    /// Owning network namespace cookie.
+    #[genl_attr(SmcLgrRAttr::NetCookie)]
+    pub net_cookie: Option<u64>,
+}
+
+impl SmcrLinkGroup {
+    /// Role name as printed by `smcr linkgroup`.
+    pub fn role_name(&self) -> &'static str {
+        if self.role == 0 { "CLNT" } else { "SERV" }
+    }
+
+    /// Redundancy type name (`enum smc_lgr_type`) as printed by
+    /// `smcr linkgroup`.
+    pub fn type_name(&self) -> &'static str {
+        match self.lgr_type {
+            0 => "NONE",
+            1 => "SINGLE",
+            2 => "SYM",
+            3 => "ASYMP",
+            4 => "ASYML",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+/// One SMC-R link (`SMC_GEN_LINK_SMCR`).
+#[derive(NetlinkAttrs, Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SmcrLink {
+    /// Link id within its link group.
+    #[genl_attr(SmcLinkAttr::Id)]
+    pub id: u8,
+    /// RDMA device name.
+    #[genl_attr(SmcLinkAttr::IbDev)]
+    pub ib_device: String,
+    /// RDMA device port.
+    #[genl_attr(SmcLinkAttr::IbPort)]
+    pub ib_port: u8,
+    /// Local GID.
+    #[genl_attr(SmcLinkAttr::Gid)]
+    pub gid: String,
+    /// Peer GID.
+    #[genl_attr(SmcLinkAttr::PeerGid)]
+    pub peer_gid: String,
+    /// Connections on this link.
+    #[genl_attr(SmcLinkAttr::ConnCnt)]
+    pub conns: u32,
+    /// Netdevice ifindex backing the RDMA port (0 if unknown).
+    #[genl_attr(SmcLinkAttr::NetDev)]
+    pub ifindex: u32,
+    /// Link state; see [`Self::state_name`].
+    #[genl_attr(SmcLinkAttr::State)]
+    pub state: u32,
+}
+
+impl SmcrLink {
+    /// Link state name (`enum smc_link_state`).
+    pub fn state_name(&self) -> &'static str {
+        match self.state {
+            0 => "UNUSED",
+            1 => "INACTIVE",
+            2 => "ACTIVATING",
+            3 => "ACTIVE",
+            _ => "UNKNOWN",
+        }
+    }
+}
+
+/// One SMC-D link group (`SMC_GEN_LGR_SMCD`).
+#[derive(NetlinkAttrs, Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SmcdLinkGroup {
+    /// Link-group id.
+    #[genl_attr(SmcLgrDAttr::Id)]
+    pub id: u32,
+    /// Local ISM GID.
+    #[genl_attr(SmcLgrDAttr::Gid)]
+    pub gid: u64,
+    /// Peer ISM GID.
+    #[genl_attr(SmcLgrDAttr::PeerGid)]
+    pub peer_gid: u64,
+    /// VLAN id.
+    #[genl_attr(SmcLgrDAttr::VlanId)]
+    pub vlan_id: u8,
+    /// Number of connections using the link group.
+    #[genl_attr(SmcLgrDAttr::ConnsNum)]
+    pub conns: u32,
+    /// PNET id the link group is bound to.
+    #[genl_attr(SmcLgrDAttr::Pnetid)]
+    pub pnetid: Option<String>,
+    /// ISM channel id.
+    #[genl_attr(SmcLgrDAttr::Chid)]
+    pub chid: u16,
+}
+
+// ============================================================
+// Replies
+// ============================================================
+
+/// One frame of an SMC-R dump.
+///
+/// `SMC_NETLINK_GET_LGR_SMCR` frames carry only `link_group`.
+/// `SMC_NETLINK_GET_LINK_SMCR` interleaves them: a `link_group`
+/// frame, then one `link` frame per link of that group.
+#[derive(GenlMessage, Debug, Default, Clone, PartialEq, Eq)]
+#[genl_message(cmd = SmcCmd::GetLgrSmcr)]
+pub struct SmcrReply {
+    /// Link group.
+    #[genl_attr(SmcGenAttr::LgrSmcr, nested)]
+    pub link_group: Option<SmcrLinkGroup>,
+    /// Link.
+    #[genl_attr(SmcGenAttr::LinkSmcr, nested)]
+    pub link: Option<SmcrLink>,
+}
+
+/// One frame of the `SMC_NETLINK_GET_LGR_SMCD` dump.
+#[derive(GenlMessage, Debug, Default, Clone, PartialEq, Eq)]
+#[genl_message(cmd = SmcCmd::GetLgrSmcd)]
+pub struct SmcdReply {
+    /// Link group.
+    #[genl_attr(SmcGenAttr::LgrSmcd, nested)]
+    pub link_group: Option<SmcdLinkGroup>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smcr_reply_round_trips() {
+        let original = SmcrReply {
+            link_group: Some(SmcrLinkGroup {
+                id: 0x0100,
+                role: 1,
+                lgr_type: 2,
+                pnetid: Some("NET1".into()),
+                vlan_id: 0,
+                conns: 4,
+                net_cookie: None,
+            }),
+            link: None,
+        };
+
+        let mut b = MessageBuilder::new(0, 0);
+        let start = b.len();
+        original.to_bytes(&mut b).expect("emit");
+        let parsed = SmcrReply::from_bytes(&b.as_bytes()[start..]).expect("parse");
+        assert_eq!(parsed, original);
+
+        let lgr = parsed.link_group.unwrap();
+        assert_eq!(lgr.role_name(), "SERV");
+        assert_eq!(lgr.type_name(), "SYM");
+    }
+
+    #[test]
+    fn smcr_link_frame_decodes() {
+        let original = SmcrReply {
+            link_group: None,
+            link: Some(SmcrLink {
+                id: 1,
+                ib_device: "mlx5_0".into(),
+                ib_port: 1,
+                gid: "fe80::1".into(),
+                peer_gid: "fe80::2".into(),
+                conns: 2,
+                ifindex: 3,
+                state: 3,
+            }),
+        };
+
+        let mut b = MessageBuilder::new(0, 0);
+        let start = b.len();
+        original.to_bytes(&mut b).expect("emit");
+        let parsed = SmcrReply::from_bytes(&b.as_bytes()[start..]).expect("parse");
+        assert_eq!(parsed, original);
+        assert_eq!(parsed.link.unwrap().state_name(), "ACTIVE");
+    }
+
+    #[test]
+    fn dump_requests_carry_their_command() {
+        assert_eq!(SmcrLinkGroupDumpRequest::CMD, SmcCmd::GetLgrSmcr as u8);
+        assert_eq!(SmcrLinkDumpRequest::CMD, SmcCmd::GetLinkSmcr as u8);
+        assert_eq!(SmcdLinkGroupDumpRequest::CMD, SmcCmd::GetLgrSmcd as u8);
+    }
+}
@@ -0,0 +1,88 @@
+//! `SMC_GEN_NETLINK` Generic Netlink family — SMC link groups.
+//!
+//! SMC (Shared Memory Communications, `AF_SMC`) moves TCP-style
+//! stream traffic onto RDMA (SMC-R) or an ISM device (SMC-D). The
+//! connections between two peers share a **link group**: one or two
+//! RDMA links (SMC-R) or one ISM channel (SMC-D). This family lists
+//! them.
+//!
+//! Per-socket state — including which connections fell back to TCP
+//! and why — comes from sock_diag instead; see
+//! `Connection::<SockDiag>::query_smc_sockets` (feature `sockdiag`).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::Connection;
+//! use nlink::netlink::genl::smc::Smc;
+//! use tokio_stream::StreamExt;
+//!
+//! let conn = Connection::<Smc>::new_async().await?;
+//! let mut stream = conn.dump_smcr_links().await?;
+//! while let Some(frame) = stream.next().await {
+//!     let frame = frame?;
+//!     if let Some(lgr) = frame.link_group {
+//!         println!("lgr {:08x} {} {}", lgr.id, lgr.role_name(), lgr.type_name());
+//!     }
+//!     if let Some(link) = frame.link {
+//!         println!("  link {} {}:{} {}", link.id, link.ib_device, link.ib_port, link.state_name());
+//!     }
+//! }
+//! ```
+//!
+//! # Permissions
+//!
+//! The dumps are unprivileged. The family is registered once the
+//! `smc` module is loaded (5.11+ for these commands).
+
+use crate::macros::genl_family;
+
+pub mod connection;
+pub mod messages;
+pub mod types;
+
+pub use messages::{
+    SmcdLinkGroup, SmcdLinkGroupDumpRequest, SmcdReply, SmcrLink, SmcrLinkDumpRequest,
+    SmcrLinkGroup, SmcrLinkGroupDumpRequest, SmcrReply,
+};
+pub use types::{SmcCmd, SmcGenAttr, SmcLgrDAttr, SmcLgrRAttr, SmcLinkAttr};
+
+/// SMC Generic Netlink family marker.
+///
+/// Constructed via [`Connection::<Smc>::new_async()`][Connection].
+/// Returns [`Error::FamilyNotFound`](crate::Error::FamilyNotFound)
+/// when the `smc` module is not loaded.
+///
+/// [Connection]: crate::netlink::Connection
+#[genl_family(name = "SMC_GEN_NETLINK", version = 1)]
+pub struct Smc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::{
+        AsyncProtocolInit, Protocol, ProtocolState, construction::AsyncConstructible,
+    };
+
+    #[test]
+    fn family_marker_carries_expected_name_and_version() {
+        assert_eq!(Smc::NAME, "SMC_GEN_NETLINK");
+        assert_eq!(Smc::VERSION, 1);
+    }
+
+    #[test]
+    fn protocol_state_routes_to_generic() {
+        const _: () = {
+            assert!(matches!(Smc::PROTOCOL, Protocol::Generic));
+        };
+    }
+
+    fn assert_async_constructible<P: AsyncConstructible>() {}
+    fn assert_async_protocol_init<P: AsyncProtocolInit>() {}
+
+    #[test]
+    fn smc_satisfies_async_construction_bounds() {
+        assert_async_constructible::<Smc>();
+        assert_async_protocol_init::<Smc>();
+    }
+}
@@ -0,0 +1,140 @@
+//! SMC command + attribute enums.
+//!
+//! Direct translation of the `SMC_GEN_NETLINK` part of the kernel
+//! UAPI in `include/uapi/linux/smc.h`, expressed via the
+//! `nlink-macros` typed-codec derives. Only the link-group and link
+//! queries are modelled.
+
+use crate::macros::{GenlAttribute, GenlCommand};
+
+/// SMC command codes. Sent in the GENL header's `cmd` byte.
+///
+/// Wire: `u8` per the kernel UAPI.
+#[derive(GenlCommand, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_command(repr = "u8")]
+#[non_exhaustive]
+pub enum SmcCmd {
+    /// `SMC_NETLINK_GET_SYS_INFO`.
+    GetSysInfo = 1,
+    /// `SMC_NETLINK_GET_LGR_SMCR` — dump SMC-R link groups.
+    GetLgrSmcr = 2,
+    /// `SMC_NETLINK_GET_LINK_SMCR` — dump SMC-R link groups with
+    /// their links.
+    GetLinkSmcr = 3,
+    /// `SMC_NETLINK_GET_LGR_SMCD` — dump SMC-D link groups.
+    GetLgrSmcd = 4,
+    /// `SMC_NETLINK_GET_DEV_SMCD`.
+    GetDevSmcd = 5,
+    /// `SMC_NETLINK_GET_DEV_SMCR`.
+    GetDevSmcr = 6,
+    /// `SMC_NETLINK_GET_STATS`.
+    GetStats = 7,
+    /// `SMC_NETLINK_GET_FBACK_STATS`.
+    GetFbackStats = 8,
+}
+
+/// Top-level attributes (`SMC_GEN_*`). Each wraps one nested object.
+///
+/// Wire: `u16`.
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum SmcGenAttr {
+    /// `SMC_GEN_SYS_INFO` (nest).
+    SysInfo = 1,
+    /// `SMC_GEN_LGR_SMCR` (nest of [`SmcLgrRAttr`]).
+    LgrSmcr = 2,
+    /// `SMC_GEN_LINK_SMCR` (nest of [`SmcLinkAttr`]).
+    LinkSmcr = 3,
+    /// `SMC_GEN_LGR_SMCD` (nest of [`SmcLgrDAttr`]).
+    LgrSmcd = 4,
+    /// `SMC_GEN_DEV_SMCD` (nest).
+    DevSmcd = 5,
+    /// `SMC_GEN_DEV_SMCR` (nest).
+    DevSmcr = 6,
+    /// `SMC_GEN_STATS` (nest).
+    Stats = 7,
+    /// `SMC_GEN_FBACK_STATS` (nest).
+    FbackStats = 8,
+}
+
+/// SMC-R link-group attributes (`SMC_NLA_LGR_R_*`).
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum SmcLgrRAttr {
+    /// `SMC_NLA_LGR_R_ID` (u32).
+    Id = 1,
+    /// `SMC_NLA_LGR_R_ROLE` (u8; 0 = client, 1 = server).
+    Role = 2,
+    /// `SMC_NLA_LGR_R_TYPE` (u8; see [`SmcrLinkGroup::type_name`](super::SmcrLinkGroup::type_name)).
+    Type = 3,
+    /// `SMC_NLA_LGR_R_PNETID` (string).
+    Pnetid = 4,
+    /// `SMC_NLA_LGR_R_VLAN_ID` (u8).
+    VlanId = 5,
+    /// `SMC_NLA_LGR_R_CONNS_NUM` (u32).
+    ConnsNum = 6,
+    /// `SMC_NLA_LGR_R_V2_COMMON` (nest).
+    V2Common = 7,
+    /// `SMC_NLA_LGR_R_V2` (nest).
+    V2 = 8,
+    /// `SMC_NLA_LGR_R_NET_COOKIE` (u64).
+    NetCookie = 9,
+    /// `SMC_NLA_LGR_R_PAD`.
+    Pad = 10,
+    /// `SMC_NLA_LGR_R_BUF_TYPE` (u8).
+    BufType = 11,
+}
+
+/// SMC-R link attributes (`SMC_NLA_LINK_*`).
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum SmcLinkAttr {
+    /// `SMC_NLA_LINK_ID` (u8).
+    Id = 1,
+    /// `SMC_NLA_LINK_IB_DEV` (string).
+    IbDev = 2,
+    /// `SMC_NLA_LINK_IB_PORT` (u8).
+    IbPort = 3,
+    /// `SMC_NLA_LINK_GID` (string).
+    Gid = 4,
+    /// `SMC_NLA_LINK_PEER_GID` (string).
+    PeerGid = 5,
+    /// `SMC_NLA_LINK_CONN_CNT` (u32).
+    ConnCnt = 6,
+    /// `SMC_NLA_LINK_NET_DEV` (u32 ifindex).
+    NetDev = 7,
+    /// `SMC_NLA_LINK_UID` (4 bytes).
+    Uid = 8,
+    /// `SMC_NLA_LINK_PEER_UID` (4 bytes).
+    PeerUid = 9,
+    /// `SMC_NLA_LINK_STATE` (u32).
+    State = 10,
+}
+
+/// SMC-D link-group attributes (`SMC_NLA_LGR_D_*`).
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum SmcLgrDAttr {
+    /// `SMC_NLA_LGR_D_ID` (u32).
+    Id = 1,
+    /// `SMC_NLA_LGR_D_GID` (u64).
+    Gid = 2,
+    /// `SMC_NLA_LGR_D_PEER_GID` (u64).
+    PeerGid = 3,
+    /// `SMC_NLA_LGR_D_VLAN_ID` (u8).
+    VlanId = 4,
+    /// `SMC_NLA_LGR_D_CONNS_NUM` (u32).
+    ConnsNum = 5,
+    /// `SMC_NLA_LGR_D_PNETID` (string).
+    Pnetid = 6,
+    /// `SMC_NLA_LGR_D_CHID` (u16).
+    Chid = 7,
+    /// `SMC_NLA_LGR_D_PAD`.
+    Pad = 8,
+    /// `SMC_NLA_LGR_D_V2_COMMON` (nest).
+    V2Common = 9,
+}
@@ -0,0 +1,59 @@
+//! `Connection<Taskstats>` helper methods.
+//!
+//! Typed wrappers over the generic
+//! [`Connection::send_typed`][crate::netlink::Connection::send_typed]
+//! dispatch. Same shape as the DPLL / net_shaper helpers.
+
+use crate::netlink::{connection::Connection, error::Result};
+
+use super::Taskstats;
+use super::messages::{TaskstatsGetRequest, TaskstatsListenerRequest, TaskstatsReply};
+use super::types::TaskStats;
+
+impl Connection<Taskstats> {
+    /// Accounting for a single thread.
+    ///
+    /// Returns `Error::is_not_found()` (`ESRCH`) if no such task
+    /// exists. Requires `CAP_NET_ADMIN`.
+    ///
+    /// ```ignore
+    /// let conn = Connection::<Taskstats>::new_async().await?;
+    /// let ts = conn.get_pid_stats(std::process::id()).await?;
+    /// println!("{} waited {:?} for CPU", ts.comm, ts.cpu_delay());
+    /// ```
+    pub async fn get_pid_stats(&self, pid: u32) -> Result<TaskStats> {
+        let reply: TaskstatsReply = self.send_typed(TaskstatsGetRequest::pid(pid)).await?;
+        reply.pid_stats()
+    }
+
+    /// Accounting summed over every live thread of a thread group
+    /// (a process). Requires `CAP_NET_ADMIN`.
+    pub async fn get_tgid_stats(&self, tgid: u32) -> Result<TaskStats> {
+        let reply: TaskstatsReply = self.send_typed(TaskstatsGetRequest::tgid(tgid)).await?;
+        reply.tgid_stats()
+    }
+
+    /// Register this connection for exit notifications from tasks
+    /// exiting on `cpus` (a kernel CPU list: `"0-3,8"`).
+    ///
+    /// After this returns,
+    /// [`events()`](crate::netlink::Connection::events) yields a
+    /// [`TaskstatsEvent`](super::TaskstatsEvent) per exiting task.
+    /// The kernel drops the registration when the socket closes;
+    /// [`deregister_exit_listener`](Self::deregister_exit_listener)
+    /// ends it early. Requires `CAP_NET_ADMIN`.
+    pub async fn register_exit_listener(&self, cpus: &str) -> Result<()> {
+        let _: TaskstatsReply = self
+            .send_typed(TaskstatsListenerRequest::register(cpus))
+            .await?;
+        Ok(())
+    }
+
+    /// Drop an exit-notification registration for `cpus`.
+    pub async fn deregister_exit_listener(&self, cpus: &str) -> Result<()> {
+        let _: TaskstatsReply = self
+            .send_typed(TaskstatsListenerRequest::deregister(cpus))
+            .await?;
+        Ok(())
+    }
+}
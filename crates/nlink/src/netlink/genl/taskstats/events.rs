@@ -0,0 +1,146 @@
+//! Taskstats exit notifications — typed event stream.
+//!
+//! Unlike the multicast families (DPLL, OVPN), taskstats delivers
+//! exit records **unicast** to every socket registered for the
+//! exiting task's CPU via
+//! [`register_exit_listener`][rel]. No group subscription is
+//! involved; the registered connection's
+//! [`events()`][cev] stream simply yields one [`TaskstatsEvent`]
+//! per exit.
+//!
+//! [rel]: crate::netlink::Connection::register_exit_listener
+//! [cev]: crate::netlink::Connection::events
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, genl::taskstats::{Taskstats, TaskstatsEvent}};
+//! use tokio_stream::StreamExt;
+//!
+//! let conn = Connection::<Taskstats>::new_async().await?;
+//! conn.register_exit_listener("0-7").await?;
+//! let mut events = conn.events().await;
+//! while let Some(evt) = events.next().await {
+//!     if let TaskstatsEvent::Exit(exit) = evt? {
+//!         println!("{} ({}) cpu delay {:?}", exit.stats.comm, exit.pid, exit.stats.cpu_delay());
+//!     }
+//! }
+//! ```
+
+use crate::macros::GenlMessage;
+use crate::netlink::genl::GENL_HDRLEN;
+
+use super::messages::TaskstatsReply;
+use super::types::{TaskStats, TaskstatsCmd};
+
+/// A taskstats notification.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum TaskstatsEvent {
+    /// A task exited on a registered CPU.
+    Exit(TaskExit),
+}
+
+/// Final accounting for an exiting task.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TaskExit {
+    /// Thread id of the exiting task.
+    pub pid: u32,
+    /// The thread's final accounting record.
+    pub stats: TaskStats,
+    /// Thread-group id — set only when this was the group's last
+    /// thread, in which case `group_stats` holds the whole
+    /// process's totals.
+    pub tgid: Option<u32>,
+    /// Whole-process totals (see [`tgid`](Self::tgid)).
+    pub group_stats: Option<TaskStats>,
+}
+
+/// Parse a single GENL message payload (post-nlmsghdr) into a
+/// [`TaskstatsEvent`].
+///
+/// Returns `None` for anything other than a well-formed
+/// `TASKSTATS_CMD_NEW` with a per-pid aggregate.
+pub(crate) fn parse_taskstats_event(payload: &[u8]) -> Option<TaskstatsEvent> {
+    if payload.len() < GENL_HDRLEN || payload[0] != TaskstatsCmd::New as u8 {
+        return None;
+    }
+    let reply = TaskstatsReply::from_bytes(&payload[GENL_HDRLEN..]).ok()?;
+    let aggr_pid = reply.aggr_pid.as_ref()?;
+    let stats = aggr_pid.stats().ok()?;
+    let group = reply
+        .aggr_tgid
+        .as_ref()
+        .and_then(|a| Some((a.tgid?, a.stats().ok()?)));
+
+    Some(TaskstatsEvent::Exit(TaskExit {
+        pid: aggr_pid.pid.unwrap_or(stats.pid),
+        stats,
+        tgid: group.as_ref().map(|(tgid, _)| *tgid),
+        group_stats: group.map(|(_, s)| s),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::macros::__rt;
+    use crate::netlink::MessageBuilder;
+    use crate::netlink::genl::GenlMsgHdr;
+    use crate::netlink::genl::taskstats::types::{TASKSTATS_MIN_SIZE, TaskstatsType};
+
+    fn synth_payload(cmd: TaskstatsCmd, attrs_fn: impl FnOnce(&mut MessageBuilder)) -> Vec<u8> {
+        let mut b = MessageBuilder::new(0, 0);
+        b.append(&GenlMsgHdr::new(cmd as u8, 1));
+        attrs_fn(&mut b);
+        let full = b.finish();
+        // Strip the 16-byte nlmsghdr that MessageBuilder prepended.
+        full[16..].to_vec()
+    }
+
+    fn raw_stats(pid: u32) -> Vec<u8> {
+        let mut buf = vec![0u8; TASKSTATS_MIN_SIZE];
+        buf[128..132].copy_from_slice(&pid.to_ne_bytes());
+        buf
+    }
+
+    fn aggr(b: &mut MessageBuilder, kind: TaskstatsType, id_attr: TaskstatsType, id: u32) {
+        let nest = b.nest_start(kind as u16);
+        __rt::emit_u32_attr(b, id_attr as u16, id);
+        __rt::emit_bytes_attr(b, TaskstatsType::Stats as u16, &raw_stats(id));
+        b.nest_end(nest);
+    }
+
+    #[test]
+    fn parses_thread_exit() {
+        let payload = synth_payload(TaskstatsCmd::New, |b| {
+            aggr(b, TaskstatsType::AggrPid, TaskstatsType::Pid, 4321);
+        });
+        let TaskstatsEvent::Exit(exit) = parse_taskstats_event(&payload).expect("parsed");
+        assert_eq!(exit.pid, 4321);
+        assert_eq!(exit.stats.pid, 4321);
+        assert_eq!(exit.tgid, None);
+        assert!(exit.group_stats.is_none());
+    }
+
+    #[test]
+    fn parses_last_thread_exit_with_group_totals() {
+        let payload = synth_payload(TaskstatsCmd::New, |b| {
+            aggr(b, TaskstatsType::AggrPid, TaskstatsType::Pid, 4321);
+            aggr(b, TaskstatsType::AggrTgid, TaskstatsType::Tgid, 4300);
+        });
+        let TaskstatsEvent::Exit(exit) = parse_taskstats_event(&payload).expect("parsed");
+        assert_eq!(exit.tgid, Some(4300));
+        assert!(exit.group_stats.is_some());
+    }
+
+    #[test]
+    fn rejects_get_and_truncated_payloads() {
+        let payload = synth_payload(TaskstatsCmd::Get, |b| {
+            aggr(b, TaskstatsType::AggrPid, TaskstatsType::Pid, 1);
+        });
+        assert!(parse_taskstats_event(&payload).is_none());
+        assert!(parse_taskstats_event(&[]).is_none());
+    }
+}
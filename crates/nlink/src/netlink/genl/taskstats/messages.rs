@@ -0,0 +1,148 @@
+//! Typed request + reply structs for the taskstats family.
+//!
+//! All `#[derive(GenlMessage)]` / `#[derive(NetlinkAttrs)]` from
+//! `nlink-macros`. The kernel wraps every reply in an aggregate
+//! (`TASKSTATS_TYPE_AGGR_PID` / `_AGGR_TGID`) holding the id and
+//! the raw `struct taskstats`; [`TaskstatsAggr`] models that
+//! nest and [`TaskstatsAggr::stats`] decodes the struct.
+
+use crate::macros::{GenlMessage, NetlinkAttrs};
+use crate::{Error, Result};
+
+use super::types::{TaskStats, TaskstatsCmd, TaskstatsCmdAttr, TaskstatsType};
+
+// ============================================================
+// GET — request
+// ============================================================
+
+/// `TASKSTATS_CMD_GET` request — exactly one of `pid` / `tgid`
+/// is set. Build with [`Self::pid`] or [`Self::tgid`].
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = TaskstatsCmd::Get)]
+pub struct TaskstatsGetRequest {
+    /// Query one thread.
+    #[genl_attr(TaskstatsCmdAttr::Pid)]
+    pub pid: Option<u32>,
+    /// Query a thread group (sum over its live threads).
+    #[genl_attr(TaskstatsCmdAttr::Tgid)]
+    pub tgid: Option<u32>,
+}
+
+impl TaskstatsGetRequest {
+    /// Per-thread request.
+    pub fn pid(pid: u32) -> Self {
+        Self {
+            pid: Some(pid),
+            tgid: None,
+        }
+    }
+
+    /// Per-thread-group request.
+    pub fn tgid(tgid: u32) -> Self {
+        Self {
+            pid: None,
+            tgid: Some(tgid),
+        }
+    }
+}
+
+// ============================================================
+// GET — exit-notification (de)registration
+// ============================================================
+
+/// `TASKSTATS_CMD_GET` carrying a cpumask registration change.
+///
+/// The kernel sends exit notifications for tasks exiting on the
+/// listed CPUs to the **sending socket's** port id, so the
+/// connection that registers is the one that receives.
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = TaskstatsCmd::Get)]
+pub struct TaskstatsListenerRequest {
+    /// CPU list to start listening on (`"0-3,8"`).
+    #[genl_attr(TaskstatsCmdAttr::RegisterCpumask)]
+    pub register: Option<String>,
+    /// CPU list to stop listening on.
+    #[genl_attr(TaskstatsCmdAttr::DeregisterCpumask)]
+    pub deregister: Option<String>,
+}
+
+impl TaskstatsListenerRequest {
+    /// Register for exit notifications on `cpus`.
+    pub fn register(cpus: impl Into<String>) -> Self {
+        Self {
+            register: Some(cpus.into()),
+            deregister: None,
+        }
+    }
+
+    /// Deregister from exit notifications on `cpus`.
+    pub fn deregister(cpus: impl Into<String>) -> Self {
+        Self {
+            register: None,
+            deregister: Some(cpus.into()),
+        }
+    }
+}
+
+// ============================================================
+// NEW — reply / notification
+// ============================================================
+
+/// One aggregate block: the task (or thread-group) id plus its
+/// raw `struct taskstats`.
+#[derive(NetlinkAttrs, Debug, Default, Clone, PartialEq, Eq)]
+pub struct TaskstatsAggr {
+    /// Thread id (set inside `AGGR_PID`).
+    #[genl_attr(TaskstatsType::Pid)]
+    pub pid: Option<u32>,
+    /// Thread-group id (set inside `AGGR_TGID`).
+    #[genl_attr(TaskstatsType::Tgid)]
+    pub tgid: Option<u32>,
+    /// Raw `struct taskstats` bytes.
+    #[genl_attr(TaskstatsType::Stats)]
+    pub raw_stats: Vec<u8>,
+}
+
+impl TaskstatsAggr {
+    /// Decode the embedded `struct taskstats`.
+    pub fn stats(&self) -> Result<TaskStats> {
+        TaskStats::from_bytes(&self.raw_stats)
+    }
+}
+
+/// `TASKSTATS_CMD_NEW` — the reply to a GET, and the body of an
+/// exit notification.
+///
+/// A per-pid reply carries `aggr_pid`; a per-tgid reply carries
+/// `aggr_tgid`. An exit notification carries `aggr_pid`, plus
+/// `aggr_tgid` when the exiting thread was the last in its group.
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = TaskstatsCmd::New)]
+pub struct TaskstatsReply {
+    /// Per-thread aggregate.
+    #[genl_attr(TaskstatsType::AggrPid, nested)]
+    pub aggr_pid: Option<TaskstatsAggr>,
+    /// Per-thread-group aggregate.
+    #[genl_attr(TaskstatsType::AggrTgid, nested)]
+    pub aggr_tgid: Option<TaskstatsAggr>,
+}
+
+impl TaskstatsReply {
+    /// Decode the per-thread stats, erroring if the reply has no
+    /// `AGGR_PID` block.
+    pub fn pid_stats(&self) -> Result<TaskStats> {
+        self.aggr_pid
+            .as_ref()
+            .ok_or_else(|| Error::InvalidMessage("taskstats: reply missing AGGR_PID".into()))?
+            .stats()
+    }
+
+    /// Decode the per-thread-group stats, erroring if the reply
+    /// has no `AGGR_TGID` block.
+    pub fn tgid_stats(&self) -> Result<TaskStats> {
+        self.aggr_tgid
+            .as_ref()
+            .ok_or_else(|| Error::InvalidMessage("taskstats: reply missing AGGR_TGID".into()))?
+            .stats()
+    }
+}
@@ -0,0 +1,98 @@
+//! `TASKSTATS` Generic Netlink family — per-task accounting.
+//!
+//! Taskstats exposes the kernel's per-thread and per-process
+//! accounting records: scheduler run-queue delay, block-I/O and
+//! swap-in delay, memory-reclaim / thrashing delay, CPU times,
+//! context switches, and I/O byte counters. For network
+//! diagnostics the delay-accounting block is the interesting
+//! part — it tells apart "the packet was late" from "the process
+//! that should have read it was waiting for a CPU".
+//!
+//! Third in-tree user of [`nlink-macros`] (after [`super::dpll`]
+//! and [`super::net_shaper`]); the
+//! `examples/macros/define_taskstats.rs` walkthrough remains the
+//! from-scratch teaching copy.
+//!
+//! [`nlink-macros`]: crate::macros
+//!
+//! # Queries
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, genl::taskstats::Taskstats};
+//!
+//! let conn = Connection::<Taskstats>::new_async().await?;
+//! let ts = conn.get_tgid_stats(pid).await?;
+//! println!(
+//!     "{}: {} ctx switches, avg cpu delay {:?}",
+//!     ts.comm,
+//!     ts.context_switches(),
+//!     ts.cpu_delay_avg(),
+//! );
+//! ```
+//!
+//! # Exit notifications
+//!
+//! Register a CPU list with
+//! [`register_exit_listener`](crate::netlink::Connection::register_exit_listener)
+//! and consume [`TaskstatsEvent`]s from `events()` — see
+//! [`events`] for the full shape.
+//!
+//! # Permissions
+//!
+//! Every command requires `CAP_NET_ADMIN`. Delay fields read zero
+//! unless the kernel runs with delay accounting enabled
+//! (`CONFIG_TASK_DELAY_ACCT` + `delayacct` boot parameter or the
+//! `kernel.task_delayacct` sysctl).
+
+use crate::macros::genl_family;
+
+pub mod connection;
+pub mod events;
+pub mod messages;
+pub mod types;
+
+pub use events::{TaskExit, TaskstatsEvent};
+pub use messages::{TaskstatsAggr, TaskstatsGetRequest, TaskstatsListenerRequest, TaskstatsReply};
+pub use types::{
+    TASKSTATS_MIN_SIZE, TS_COMM_LEN, TaskStats, TaskstatsCmd, TaskstatsCmdAttr, TaskstatsType,
+};
+
+/// Taskstats Generic Netlink family marker.
+///
+/// Constructed via [`Connection::<Taskstats>::new_async()`][Connection].
+/// Returns [`Error::FamilyNotFound`](crate::Error::FamilyNotFound)
+/// on kernels built without `CONFIG_TASKSTATS`.
+///
+/// [Connection]: crate::netlink::Connection
+#[genl_family(name = "TASKSTATS", version = 1)]
+pub struct Taskstats;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::{
+        AsyncProtocolInit, Protocol, ProtocolState, construction::AsyncConstructible,
+    };
+
+    #[test]
+    fn family_marker_carries_expected_name_and_version() {
+        assert_eq!(Taskstats::NAME, "TASKSTATS");
+        assert_eq!(Taskstats::VERSION, 1);
+    }
+
+    #[test]
+    fn protocol_state_routes_to_generic() {
+        const _: () = {
+            assert!(matches!(Taskstats::PROTOCOL, Protocol::Generic));
+        };
+    }
+
+    fn assert_async_constructible<P: AsyncConstructible>() {}
+    fn assert_async_protocol_init<P: AsyncProtocolInit>() {}
+
+    #[test]
+    fn taskstats_satisfies_async_construction_bounds() {
+        assert_async_constructible::<Taskstats>();
+        assert_async_protocol_init::<Taskstats>();
+    }
+}
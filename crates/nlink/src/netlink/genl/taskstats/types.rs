@@ -0,0 +1,403 @@
+//! Taskstats command + attribute enums and the decoded
+//! `struct taskstats` payload.
+//!
+//! Direct translation of the kernel UAPI in
+//! `include/uapi/linux/taskstats.h`, expressed via the
+//! `nlink-macros` typed-codec derives. The family is frozen —
+//! new accounting fields land by growing `struct taskstats` (the
+//! `version` field tracks it), never by adding attributes.
+
+use std::time::Duration;
+
+use crate::macros::{GenlAttribute, GenlCommand};
+
+// ============================================================
+// Commands (TASKSTATS_CMD_*)
+// ============================================================
+
+/// Taskstats command codes. Sent in the GENL header's `cmd` byte.
+///
+/// Wire: `u8` per the kernel UAPI.
+#[derive(GenlCommand, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_command(repr = "u8")]
+#[non_exhaustive]
+pub enum TaskstatsCmd {
+    /// `TASKSTATS_CMD_GET` — user → kernel request (and the
+    /// command byte on the kernel's get-response).
+    Get = 1,
+    /// `TASKSTATS_CMD_NEW` — kernel → user reply / exit
+    /// notification.
+    New = 2,
+}
+
+// ============================================================
+// Request attributes (TASKSTATS_CMD_ATTR_*)
+// ============================================================
+
+/// Attribute kinds on a `TASKSTATS_CMD_GET` request.
+///
+/// Wire: `u16`.
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum TaskstatsCmdAttr {
+    /// `TASKSTATS_CMD_ATTR_PID` — query one thread (u32).
+    Pid = 1,
+    /// `TASKSTATS_CMD_ATTR_TGID` — query a whole thread group
+    /// (u32); the kernel sums every live thread.
+    Tgid = 2,
+    /// `TASKSTATS_CMD_ATTR_REGISTER_CPUMASK` — register the
+    /// sending socket for exit notifications on a CPU list
+    /// (NUL-terminated string, `cpulist_parse` format: `"0-3,8"`).
+    RegisterCpumask = 3,
+    /// `TASKSTATS_CMD_ATTR_DEREGISTER_CPUMASK` — drop a previous
+    /// registration (same format).
+    DeregisterCpumask = 4,
+}
+
+// ============================================================
+// Reply attributes (TASKSTATS_TYPE_*)
+// ============================================================
+
+/// Attribute kinds on a `TASKSTATS_CMD_NEW` reply / notification.
+///
+/// Wire: `u16`.
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum TaskstatsType {
+    /// `TASKSTATS_TYPE_PID` — thread id (u32), inside an aggregate.
+    Pid = 1,
+    /// `TASKSTATS_TYPE_TGID` — thread-group id (u32), inside an
+    /// aggregate.
+    Tgid = 2,
+    /// `TASKSTATS_TYPE_STATS` — the raw `struct taskstats`.
+    Stats = 3,
+    /// `TASKSTATS_TYPE_AGGR_PID` — nested `{ PID, STATS }`.
+    AggrPid = 4,
+    /// `TASKSTATS_TYPE_AGGR_TGID` — nested `{ TGID, STATS }`.
+    AggrTgid = 5,
+    /// `TASKSTATS_TYPE_NULL` — padding, carries nothing.
+    Null = 6,
+}
+
+// ============================================================
+// struct taskstats
+// ============================================================
+
+/// Length of `ac_comm` (`TS_COMM_LEN`).
+pub const TS_COMM_LEN: usize = 32;
+
+/// Smallest `struct taskstats` the decoder accepts — the end of
+/// the version-1 delay-accounting block plus the basic
+/// accounting fields (through `ac_majflt`). Every kernel that
+/// registers the family sends at least this much.
+pub const TASKSTATS_MIN_SIZE: usize = 184;
+
+/// Decoded `struct taskstats` (kernel `TASKSTATS_VERSION` 13
+/// layout).
+///
+/// Delay totals are nanoseconds, basic-accounting CPU times are
+/// microseconds — the units the kernel reports. Fields introduced
+/// after the kernel's struct version are left at zero; check
+/// [`version`](Self::version) when the distinction matters.
+/// Offsets verified against `linux/taskstats.h` via `offsetof`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TaskStats {
+    /// Struct version (`TASKSTATS_VERSION` of the sending kernel).
+    pub version: u16,
+    /// Exit status (only meaningful on exit notifications).
+    pub exitcode: u32,
+    /// Accounting flags (`<linux/acct.h>` `AFORK` / `ASU` / …).
+    pub flag: u8,
+    /// Nice value.
+    pub nice: u8,
+
+    /// Number of times the task waited on a run queue.
+    pub cpu_count: u64,
+    /// Total run-queue wait, ns.
+    pub cpu_delay_total: u64,
+    /// Number of synchronous block-I/O waits.
+    pub blkio_count: u64,
+    /// Total block-I/O wait, ns.
+    pub blkio_delay_total: u64,
+    /// Number of swap-in waits.
+    pub swapin_count: u64,
+    /// Total swap-in wait, ns.
+    pub swapin_delay_total: u64,
+    /// Wall-clock CPU running time, ns.
+    pub cpu_run_real_total: u64,
+    /// Virtual CPU running time, ns.
+    pub cpu_run_virtual_total: u64,
+
+    /// Command name (`ac_comm`, NUL-trimmed).
+    pub comm: String,
+    /// Scheduling discipline.
+    pub sched: u8,
+    /// User ID.
+    pub uid: u32,
+    /// Group ID.
+    pub gid: u32,
+    /// Process (thread) ID.
+    pub pid: u32,
+    /// Parent process ID.
+    pub ppid: u32,
+    /// Begin time, seconds since the epoch (32-bit; see
+    /// [`btime64`](Self::btime64)).
+    pub btime: u32,
+    /// Elapsed time, µs.
+    pub etime: u64,
+    /// User CPU time, µs.
+    pub utime: u64,
+    /// System CPU time, µs.
+    pub stime: u64,
+    /// Minor page faults.
+    pub minflt: u64,
+    /// Major page faults.
+    pub majflt: u64,
+
+    /// Accumulated RSS usage, MB·µs.
+    pub coremem: u64,
+    /// Accumulated virtual-memory usage, MB·µs.
+    pub virtmem: u64,
+    /// RSS high-water mark, KiB.
+    pub hiwater_rss: u64,
+    /// VM high-water mark, KiB.
+    pub hiwater_vm: u64,
+    /// Bytes read (all `read`-family syscalls).
+    pub read_char: u64,
+    /// Bytes written (all `write`-family syscalls).
+    pub write_char: u64,
+    /// Read syscalls.
+    pub read_syscalls: u64,
+    /// Write syscalls.
+    pub write_syscalls: u64,
+    /// Bytes of storage read I/O.
+    pub read_bytes: u64,
+    /// Bytes of storage write I/O.
+    pub write_bytes: u64,
+    /// Bytes of cancelled write I/O.
+    pub cancelled_write_bytes: u64,
+    /// Voluntary context switches.
+    pub nvcsw: u64,
+    /// Involuntary context switches.
+    pub nivcsw: u64,
+    /// Frequency-scaled user time.
+    pub utimescaled: u64,
+    /// Frequency-scaled system time.
+    pub stimescaled: u64,
+    /// Frequency-scaled `cpu_run_real_total`.
+    pub cpu_scaled_run_real_total: u64,
+    /// Number of memory-reclaim waits.
+    pub freepages_count: u64,
+    /// Total memory-reclaim wait, ns.
+    pub freepages_delay_total: u64,
+    /// Number of thrashing waits.
+    pub thrashing_count: u64,
+    /// Total thrashing wait, ns.
+    pub thrashing_delay_total: u64,
+    /// 64-bit begin time (v10+).
+    pub btime64: u64,
+    /// Number of memory-compaction waits (v11+).
+    pub compact_count: u64,
+    /// Total memory-compaction wait, ns (v11+).
+    pub compact_delay_total: u64,
+    /// Thread-group ID (v12+).
+    pub tgid: u32,
+    /// Thread-group wall time, ns (v12+).
+    pub tgetime: u64,
+    /// Device ID of the program binary (v12+).
+    pub exe_dev: u64,
+    /// Inode of the program binary (v12+).
+    pub exe_inode: u64,
+    /// Number of write-protect-copy waits (v13+).
+    pub wpcopy_count: u64,
+    /// Total write-protect-copy wait, ns (v13+).
+    pub wpcopy_delay_total: u64,
+}
+
+impl TaskStats {
+    /// Decode a `TASKSTATS_TYPE_STATS` payload.
+    ///
+    /// Accepts any buffer of at least [`TASKSTATS_MIN_SIZE`]
+    /// bytes: trailing fields newer kernels append are ignored,
+    /// fields an older kernel doesn't send stay zero.
+    pub fn from_bytes(data: &[u8]) -> crate::Result<Self> {
+        if data.len() < TASKSTATS_MIN_SIZE {
+            return Err(crate::Error::InvalidMessage(format!(
+                "taskstats: struct too short ({} bytes, need at least {TASKSTATS_MIN_SIZE})",
+                data.len()
+            )));
+        }
+
+        let u32_at = |off: usize| {
+            data.get(off..off + 4)
+                .map(|b| u32::from_ne_bytes(b.try_into().unwrap()))
+                .unwrap_or(0)
+        };
+        let u64_at = |off: usize| {
+            data.get(off..off + 8)
+                .map(|b| u64::from_ne_bytes(b.try_into().unwrap()))
+                .unwrap_or(0)
+        };
+
+        let comm_raw = &data[80..80 + TS_COMM_LEN];
+        let comm_len = comm_raw.iter().position(|&b| b == 0).unwrap_or(TS_COMM_LEN);
+
+        Ok(Self {
+            version: u16::from_ne_bytes([data[0], data[1]]),
+            exitcode: u32_at(4),
+            flag: data[8],
+            nice: data[9],
+            cpu_count: u64_at(16),
+            cpu_delay_total: u64_at(24),
+            blkio_count: u64_at(32),
+            blkio_delay_total: u64_at(40),
+            swapin_count: u64_at(48),
+            swapin_delay_total: u64_at(56),
+            cpu_run_real_total: u64_at(64),
+            cpu_run_virtual_total: u64_at(72),
+            comm: String::from_utf8_lossy(&comm_raw[..comm_len]).into_owned(),
+            sched: data[112],
+            uid: u32_at(120),
+            gid: u32_at(124),
+            pid: u32_at(128),
+            ppid: u32_at(132),
+            btime: u32_at(136),
+            etime: u64_at(144),
+            utime: u64_at(152),
+            stime: u64_at(160),
+            minflt: u64_at(168),
+            majflt: u64_at(176),
+            coremem: u64_at(184),
+            virtmem: u64_at(192),
+            hiwater_rss: u64_at(200),
+            hiwater_vm: u64_at(208),
+            read_char: u64_at(216),
+            write_char: u64_at(224),
+            read_syscalls: u64_at(232),
+            write_syscalls: u64_at(240),
+            read_bytes: u64_at(248),
+            write_bytes: u64_at(256),
+            cancelled_write_bytes: u64_at(264),
+            nvcsw: u64_at(272),
+            nivcsw: u64_at(280),
+            utimescaled: u64_at(288),
+            stimescaled: u64_at(296),
+            cpu_scaled_run_real_total: u64_at(304),
+            freepages_count: u64_at(312),
+            freepages_delay_total: u64_at(320),
+            thrashing_count: u64_at(328),
+            thrashing_delay_total: u64_at(336),
+            btime64: u64_at(344),
+            compact_count: u64_at(352),
+            compact_delay_total: u64_at(360),
+            tgid: u32_at(368),
+            tgetime: u64_at(376),
+            exe_dev: u64_at(384),
+            exe_inode: u64_at(392),
+            wpcopy_count: u64_at(400),
+            wpcopy_delay_total: u64_at(408),
+        })
+    }
+
+    /// Total time spent runnable but waiting for a CPU.
+    pub fn cpu_delay(&self) -> Duration {
+        Duration::from_nanos(self.cpu_delay_total)
+    }
+
+    /// Mean run-queue wait per scheduling event, or `None` if the
+    /// task never waited.
+    ///
+    /// This is the number to line up against network latency: a
+    /// request handler that sees tail latency with a high average
+    /// CPU delay is starved for CPU, not for bandwidth.
+    pub fn cpu_delay_avg(&self) -> Option<Duration> {
+        avg(self.cpu_delay_total, self.cpu_count)
+    }
+
+    /// Total time spent waiting on synchronous block I/O.
+    pub fn blkio_delay(&self) -> Duration {
+        Duration::from_nanos(self.blkio_delay_total)
+    }
+
+    /// Mean block-I/O wait, or `None` if the task never waited.
+    pub fn blkio_delay_avg(&self) -> Option<Duration> {
+        avg(self.blkio_delay_total, self.blkio_count)
+    }
+
+    /// Total time spent waiting on swap-in.
+    pub fn swapin_delay(&self) -> Duration {
+        Duration::from_nanos(self.swapin_delay_total)
+    }
+
+    /// Total context switches (voluntary + involuntary).
+    pub fn context_switches(&self) -> u64 {
+        self.nvcsw.saturating_add(self.nivcsw)
+    }
+}
+
+fn avg(total_ns: u64, count: u64) -> Option<Duration> {
+    (count != 0).then(|| Duration::from_nanos(total_ns / count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synth(len: usize) -> Vec<u8> {
+        let mut buf = vec![0u8; len];
+        buf[0..2].copy_from_slice(&13u16.to_ne_bytes());
+        buf[16..24].copy_from_slice(&4u64.to_ne_bytes()); // cpu_count
+        buf[24..32].copy_from_slice(&8_000u64.to_ne_bytes()); // cpu_delay_total
+        buf[80..84].copy_from_slice(b"curl");
+        buf[128..132].copy_from_slice(&1234u32.to_ne_bytes()); // ac_pid
+        if len >= 288 {
+            buf[272..280].copy_from_slice(&10u64.to_ne_bytes()); // nvcsw
+            buf[280..288].copy_from_slice(&3u64.to_ne_bytes()); // nivcsw
+        }
+        if len >= 416 {
+            buf[368..372].copy_from_slice(&1200u32.to_ne_bytes()); // ac_tgid
+            buf[408..416].copy_from_slice(&77u64.to_ne_bytes()); // wpcopy_delay_total
+        }
+        buf
+    }
+
+    #[test]
+    fn decodes_current_layout() {
+        let ts = TaskStats::from_bytes(&synth(416)).unwrap();
+        assert_eq!(ts.version, 13);
+        assert_eq!(ts.comm, "curl");
+        assert_eq!(ts.pid, 1234);
+        assert_eq!(ts.tgid, 1200);
+        assert_eq!(ts.wpcopy_delay_total, 77);
+        assert_eq!(ts.context_switches(), 13);
+        assert_eq!(ts.cpu_delay(), Duration::from_nanos(8_000));
+        assert_eq!(ts.cpu_delay_avg(), Some(Duration::from_nanos(2_000)));
+    }
+
+    #[test]
+    fn accepts_larger_and_shorter_structs() {
+        let ts = TaskStats::from_bytes(&synth(512)).unwrap();
+        assert_eq!(ts.tgid, 1200);
+
+        // An old kernel without the v12+ fields: tail reads as zero.
+        let ts = TaskStats::from_bytes(&synth(288)).unwrap();
+        assert_eq!(ts.nivcsw, 3);
+        assert_eq!(ts.tgid, 0);
+        assert_eq!(ts.wpcopy_delay_total, 0);
+    }
+
+    #[test]
+    fn rejects_truncated_struct() {
+        assert!(TaskStats::from_bytes(&[0u8; 100]).is_err());
+    }
+
+    #[test]
+    fn zero_count_has_no_average() {
+        let ts = TaskStats::default();
+        assert_eq!(ts.cpu_delay_avg(), None);
+        assert_eq!(ts.blkio_delay_avg(), None);
+    }
+}
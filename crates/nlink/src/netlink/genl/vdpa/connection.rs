@@ -0,0 +1,81 @@
+//! `Connection<Vdpa>` helper methods.
+//!
+//! Typed wrappers over the generic
+//! [`Connection::send_typed`][crate::netlink::Connection::send_typed]
+//! / [`dump_typed_stream`][crate::netlink::Connection::dump_typed_stream]
+//! dispatch. Same shape as the DPLL / net_shaper helpers.
+
+use crate::macros::GenlTypedDumpStream;
+use crate::netlink::{connection::Connection, error::Result};
+
+use super::Vdpa;
+use super::messages::{
+    VdpaDev, VdpaDevConfig, VdpaDevConfigGetRequest, VdpaDevDelRequest, VdpaDevGetRequest,
+    VdpaDevNewRequest, VdpaMgmtdev, VdpaMgmtdevDumpRequest,
+};
+
+impl Connection<Vdpa> {
+    /// Stream every management device (`vdpa mgmtdev show`).
+    pub async fn dump_mgmt_devices(&self) -> Result<GenlTypedDumpStream<'_, Vdpa, VdpaMgmtdev>> {
+        self.dump_typed_stream(VdpaMgmtdevDumpRequest).await
+    }
+
+    /// Create a vDPA device (`vdpa dev add`). Build the request with
+    /// [`VdpaDevNewRequest::new`] + chained setters.
+    ///
+    /// `Error::is_already_exists()` if the name is taken;
+    /// `Error::is_not_found()` if the management device doesn't
+    /// exist. Requires `CAP_NET_ADMIN`.
+    pub async fn add_device(&self, req: VdpaDevNewRequest) -> Result<()> {
+        let _: VdpaDev = self.send_typed(req).await?;
+        Ok(())
+    }
+
+    /// Delete a vDPA device (`vdpa dev del`).
+    ///
+    /// `Error::is_not_found()` if no such device exists.
+    pub async fn del_device(&self, name: impl Into<String>) -> Result<()> {
+        let _: VdpaDev = self
+            .send_typed(VdpaDevDelRequest { name: name.into() })
+            .await?;
+        Ok(())
+    }
+
+    /// Query one vDPA device (`vdpa dev show NAME`).
+    pub async fn get_device(&self, name: impl Into<String>) -> Result<VdpaDev> {
+        self.send_typed(VdpaDevGetRequest {
+            name: Some(name.into()),
+        })
+        .await
+    }
+
+    /// Stream every vDPA device (`vdpa dev show`).
+    ///
+    /// ```ignore
+    /// use tokio_stream::StreamExt;
+    /// let mut stream = conn.dump_devices().await?;
+    /// while let Some(dev) = stream.next().await {
+    ///     let dev = dev?;
+    ///     println!("{}: type {} mgmtdev {}", dev.name, dev.class_name(), dev.mgmtdev());
+    /// }
+    /// ```
+    pub async fn dump_devices(&self) -> Result<GenlTypedDumpStream<'_, Vdpa, VdpaDev>> {
+        self.dump_typed_stream(VdpaDevGetRequest::default()).await
+    }
+
+    /// Read one device's config space (`vdpa dev config show NAME`).
+    pub async fn get_device_config(&self, name: impl Into<String>) -> Result<VdpaDevConfig> {
+        self.send_typed(VdpaDevConfigGetRequest {
+            name: Some(name.into()),
+        })
+        .await
+    }
+
+    /// Stream every device's config space (`vdpa dev config show`).
+    pub async fn dump_device_configs(
+        &self,
+    ) -> Result<GenlTypedDumpStream<'_, Vdpa, VdpaDevConfig>> {
+        self.dump_typed_stream(VdpaDevConfigGetRequest::default())
+            .await
+    }
+}
@@ -0,0 +1,356 @@
+//! Typed request + reply structs for the vDPA family.
+//!
+//! All `#[derive(GenlMessage)]` except the attribute-less
+//! management-device dump request, which implements [`GenlMessage`]
+//! by hand (the derive needs at least one field).
+
+use crate::Result;
+use crate::macros::GenlMessage;
+use crate::netlink::MessageBuilder;
+
+use super::types::{VdpaAttr, VdpaCmd, virtio_class_name};
+
+/// Split a management-device handle into `(bus, dev)`.
+///
+/// `"pci/0000:08:00.2"` → `(Some("pci"), "0000:08:00.2")`;
+/// `"vdpasim_net"` (no bus) → `(None, "vdpasim_net")`.
+fn split_mgmtdev(handle: &str) -> (Option<String>, String) {
+    match handle.split_once('/') {
+        Some((bus, dev)) => (Some(bus.to_string()), dev.to_string()),
+        None => (None, handle.to_string()),
+    }
+}
+
+/// Join a `(bus, dev)` pair back into a management-device handle.
+fn join_mgmtdev(bus: Option<&str>, dev: &str) -> String {
+    match bus {
+        Some(bus) => format!("{bus}/{dev}"),
+        None => dev.to_string(),
+    }
+}
+
+/// Read a 6-byte MAC attribute.
+fn mac_from(raw: &Option<Vec<u8>>) -> Option<[u8; 6]> {
+    raw.as_deref().and_then(|b| b.get(..6)?.try_into().ok())
+}
+
+// ============================================================
+// Management devices
+// ============================================================
+
+/// `VDPA_CMD_MGMTDEV_GET` dump request.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct VdpaMgmtdevDumpRequest;
+
+impl GenlMessage for VdpaMgmtdevDumpRequest {
+    const CMD: u8 = VdpaCmd::MgmtdevGet as u8;
+
+    fn to_bytes(&self, _builder: &mut MessageBuilder) -> Result<()> {
+        Ok(())
+    }
+
+    fn from_bytes(_payload: &[u8]) -> Result<Self> {
+        Ok(Self)
+    }
+}
+
+/// One management device — a parent (PCI function, simulator, …)
+/// that can create vDPA devices.
+#[derive(GenlMessage, Debug, Default, Clone, PartialEq, Eq)]
+#[genl_message(cmd = VdpaCmd::MgmtdevGet)]
+#[non_exhaustive]
+pub struct VdpaMgmtdev {
+    /// Bus name (`"pci"`), absent for bus-less parents.
+    #[genl_attr(VdpaAttr::MgmtdevBusName)]
+    pub bus_name: Option<String>,
+    /// Device name on the bus.
+    #[genl_attr(VdpaAttr::MgmtdevDevName)]
+    pub dev_name: String,
+    /// Bitmask of supported virtio classes (`1 << VIRTIO_ID_*`).
+    #[genl_attr(VdpaAttr::MgmtdevSupportedClasses)]
+    pub supported_classes: Option<u64>,
+    /// Maximum virtqueues a device created here can have.
+    #[genl_attr(VdpaAttr::DevMgmtdevMaxVqs)]
+    pub max_vqs: Option<u32>,
+    /// Virtio features the management device supports.
+    #[genl_attr(VdpaAttr::DevSupportedFeatures)]
+    pub supported_features: Option<u64>,
+}
+
+impl VdpaMgmtdev {
+    /// The `bus/dev` handle used by [`VdpaDevNewRequest::new`].
+    pub fn handle(&self) -> String {
+        join_mgmtdev(self.bus_name.as_deref(), &self.dev_name)
+    }
+
+    /// Names of the supported virtio classes (`["net"]`).
+    pub fn class_names(&self) -> Vec<&'static str> {
+        let mask = self.supported_classes.unwrap_or(0);
+        (0..64)
+            .filter(|bit| mask & (1u64 << bit) != 0)
+            .map(virtio_class_name)
+            .collect()
+    }
+}
+
+// ============================================================
+// Devices — create / delete
+// ============================================================
+
+/// `VDPA_CMD_DEV_NEW` request. Build with [`Self::new`] + chained
+/// setters; the net-specific settings only apply to virtio-net
+/// management devices.
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = VdpaCmd::DevNew)]
+pub struct VdpaDevNewRequest {
+    /// New device name.
+    #[genl_attr(VdpaAttr::DevName)]
+    pub name: String,
+    /// Management-device bus.
+    #[genl_attr(VdpaAttr::MgmtdevBusName)]
+    pub mgmtdev_bus_name: Option<String>,
+    /// Management-device name.
+    #[genl_attr(VdpaAttr::MgmtdevDevName)]
+    pub mgmtdev_dev_name: String,
+    /// virtio-net MAC address.
+    #[genl_attr(VdpaAttr::DevNetCfgMacaddr)]
+    pub mac: Option<Vec<u8>>,
+    /// virtio-net MTU.
+    #[genl_attr(VdpaAttr::DevNetCfgMtu)]
+    pub mtu: Option<u16>,
+    /// virtio-net maximum queue pairs.
+    #[genl_attr(VdpaAttr::DevNetCfgMaxVqp)]
+    pub max_vqp: Option<u16>,
+    /// Virtio features to provision.
+    #[genl_attr(VdpaAttr::DevFeatures)]
+    pub features: Option<u64>,
+}
+
+impl VdpaDevNewRequest {
+    /// Create device `name` on management device `mgmtdev`
+    /// (`"pci/0000:08:00.2"` or a bus-less `"vdpasim_net"`).
+    pub fn new(name: impl Into<String>, mgmtdev: &str) -> Self {
+        let (mgmtdev_bus_name, mgmtdev_dev_name) = split_mgmtdev(mgmtdev);
+        Self {
+            name: name.into(),
+            mgmtdev_bus_name,
+            mgmtdev_dev_name,
+            ..Default::default()
+        }
+    }
+
+    /// Set the virtio-net MAC address.
+    pub fn mac(mut self, mac: [u8; 6]) -> Self {
+        self.mac = Some(mac.to_vec());
+        self
+    }
+
+    /// Set the virtio-net MTU.
+    pub fn mtu(mut self, mtu: u16) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+
+    /// Set the virtio-net maximum queue pairs.
+    pub fn max_vqp(mut self, max_vqp: u16) -> Self {
+        self.max_vqp = Some(max_vqp);
+        self
+    }
+
+    /// Provision a virtio feature set.
+    pub fn features(mut self, features: u64) -> Self {
+        self.features = Some(features);
+        self
+    }
+}
+
+/// `VDPA_CMD_DEV_DEL` request.
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = VdpaCmd::DevDel)]
+pub struct VdpaDevDelRequest {
+    /// Device to delete.
+    #[genl_attr(VdpaAttr::DevName)]
+    pub name: String,
+}
+
+// ============================================================
+// Devices — query
+// ============================================================
+
+/// `VDPA_CMD_DEV_GET` request. `name = None` is the dump form.
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = VdpaCmd::DevGet)]
+pub struct VdpaDevGetRequest {
+    /// Device to query.
+    #[genl_attr(VdpaAttr::DevName)]
+    pub name: Option<String>,
+}
+
+/// One vDPA device.
+#[derive(GenlMessage, Debug, Default, Clone, PartialEq, Eq)]
+#[genl_message(cmd = VdpaCmd::DevGet)]
+#[non_exhaustive]
+pub struct VdpaDev {
+    /// Device name.
+    #[genl_attr(VdpaAttr::DevName)]
+    pub name: String,
+    /// Management-device bus.
+    #[genl_attr(VdpaAttr::MgmtdevBusName)]
+    pub mgmtdev_bus_name: Option<String>,
+    /// Management-device name.
+    #[genl_attr(VdpaAttr::MgmtdevDevName)]
+    pub mgmtdev_dev_name: String,
+    /// Virtio device id (`VIRTIO_ID_*`); see [`Self::class_name`].
+    #[genl_attr(VdpaAttr::DevId)]
+    pub device_id: u32,
+    /// Vendor id.
+    #[genl_attr(VdpaAttr::DevVendorId)]
+    pub vendor_id: u32,
+    /// Number of virtqueues.
+    #[genl_attr(VdpaAttr::DevMaxVqs)]
+    pub max_vqs: u32,
+    /// Maximum virtqueue size.
+    #[genl_attr(VdpaAttr::DevMaxVqSize)]
+    pub max_vq_size: u16,
+    /// Minimum virtqueue size.
+    #[genl_attr(VdpaAttr::DevMinVqSize)]
+    pub min_vq_size: Option<u16>,
+}
+
+impl VdpaDev {
+    /// The owning management device's `bus/dev` handle.
+    pub fn mgmtdev(&self) -> String {
+        join_mgmtdev(self.mgmtdev_bus_name.as_deref(), &self.mgmtdev_dev_name)
+    }
+
+    /// Virtio class name (`"net"`, `"block"`, …).
+    pub fn class_name(&self) -> &'static str {
+        virtio_class_name(self.device_id)
+    }
+}
+
+// ============================================================
+// Device config space
+// ============================================================
+
+/// `VDPA_CMD_DEV_CONFIG_GET` request. `name = None` is the dump
+/// form.
+#[derive(GenlMessage, Debug, Default, Clone)]
+#[genl_message(cmd = VdpaCmd::DevConfigGet)]
+pub struct VdpaDevConfigGetRequest {
+    /// Device to query.
+    #[genl_attr(VdpaAttr::DevName)]
+    pub name: Option<String>,
+}
+
+/// A vDPA device's config space (`vdpa dev config show`). The net
+/// fields are only present for virtio-net devices.
+#[derive(GenlMessage, Debug, Default, Clone, PartialEq, Eq)]
+#[genl_message(cmd = VdpaCmd::DevConfigGet)]
+#[non_exhaustive]
+pub struct VdpaDevConfig {
+    /// Device name.
+    #[genl_attr(VdpaAttr::DevName)]
+    pub name: String,
+    /// Virtio device id.
+    #[genl_attr(VdpaAttr::DevId)]
+    pub device_id: Option<u32>,
+    /// virtio-net MAC address (raw); see [`Self::mac_address`].
+    #[genl_attr(VdpaAttr::DevNetCfgMacaddr)]
+    pub mac: Option<Vec<u8>>,
+    /// virtio-net status (`VIRTIO_NET_S_LINK_UP` = bit 0).
+    #[genl_attr(VdpaAttr::DevNetStatus)]
+    pub status: Option<u16>,
+    /// virtio-net MTU.
+    #[genl_attr(VdpaAttr::DevNetCfgMtu)]
+    pub mtu: Option<u16>,
+    /// virtio-net maximum queue pairs.
+    #[genl_attr(VdpaAttr::DevNetCfgMaxVqp)]
+    pub max_vqp: Option<u16>,
+    /// Features negotiated with the driver (absent before
+    /// `FEATURES_OK`).
+    #[genl_attr(VdpaAttr::DevNegotiatedFeatures)]
+    pub negotiated_features: Option<u64>,
+    /// Features provisioned at creation.
+    #[genl_attr(VdpaAttr::DevFeatures)]
+    pub features: Option<u64>,
+}
+
+impl VdpaDevConfig {
+    /// The virtio-net MAC address, if the device has one.
+    pub fn mac_address(&self) -> Option<[u8; 6]> {
+        mac_from(&self.mac)
+    }
+
+    /// Whether virtio-net reports link up.
+    pub fn link_up(&self) -> Option<bool> {
+        self.status.map(|s| s & 1 != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mgmtdev_handle_splits_and_joins() {
+        let req = VdpaDevNewRequest::new("vdpa0", "pci/0000:08:00.2");
+        assert_eq!(req.mgmtdev_bus_name.as_deref(), Some("pci"));
+        assert_eq!(req.mgmtdev_dev_name, "0000:08:00.2");
+
+        let req = VdpaDevNewRequest::new("vdpa0", "vdpasim_net");
+        assert_eq!(req.mgmtdev_bus_name, None);
+        assert_eq!(req.mgmtdev_dev_name, "vdpasim_net");
+
+        let m = VdpaMgmtdev {
+            bus_name: Some("pci".into()),
+            dev_name: "0000:08:00.2".into(),
+            supported_classes: Some(1 << 1),
+            ..Default::default()
+        };
+        assert_eq!(m.handle(), "pci/0000:08:00.2");
+        assert_eq!(m.class_names(), vec!["net"]);
+    }
+
+    #[test]
+    fn dev_new_request_round_trips() {
+        let original = VdpaDevNewRequest::new("vdpa0", "vdpasim_net")
+            .mac([0x02, 0, 0, 0, 0, 1])
+            .mtu(9000)
+            .max_vqp(4);
+
+        let mut b = MessageBuilder::new(0, 0);
+        let start = b.len();
+        original.to_bytes(&mut b).expect("emit");
+        let parsed = VdpaDevNewRequest::from_bytes(&b.as_bytes()[start..]).expect("parse");
+        assert_eq!(parsed.name, "vdpa0");
+        assert_eq!(parsed.mgmtdev_bus_name, None);
+        assert_eq!(parsed.mgmtdev_dev_name, "vdpasim_net");
+        assert_eq!(parsed.mac, original.mac);
+        assert_eq!(parsed.mtu, Some(9000));
+        assert_eq!(parsed.max_vqp, Some(4));
+        assert_eq!(parsed.features, None);
+    }
+
+    #[test]
+    fn config_reply_decodes_net_fields() {
+        let original = VdpaDevConfig {
+            name: "vdpa0".into(),
+            device_id: Some(1),
+            mac: Some(vec![0x02, 0, 0, 0, 0, 1]),
+            status: Some(1),
+            mtu: Some(1500),
+            max_vqp: Some(1),
+            negotiated_features: None,
+            features: None,
+        };
+
+        let mut b = MessageBuilder::new(0, 0);
+        let start = b.len();
+        original.to_bytes(&mut b).expect("emit");
+        let parsed = VdpaDevConfig::from_bytes(&b.as_bytes()[start..]).expect("parse");
+        assert_eq!(parsed, original);
+        assert_eq!(parsed.mac_address(), Some([0x02, 0, 0, 0, 0, 1]));
+        assert_eq!(parsed.link_up(), Some(true));
+    }
+}
@@ -0,0 +1,84 @@
+//! `vdpa` Generic Netlink family — vDPA device management.
+//!
+//! vDPA (virtio data path acceleration) devices expose a virtio
+//! data path implemented in hardware (or a software simulator) to
+//! VMs via `vhost-vdpa`, or to the host via `virtio-vdpa`. They are
+//! created on a **management device** — a PCI function or a
+//! bus-less parent like `vdpasim_net` — and configured at creation
+//! time (MAC, MTU, queue pairs, feature set).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::Connection;
+//! use nlink::netlink::genl::vdpa::{Vdpa, VdpaDevNewRequest};
+//!
+//! let conn = Connection::<Vdpa>::new_async().await?;
+//! conn.add_device(
+//!     VdpaDevNewRequest::new("vdpa0", "pci/0000:08:00.2")
+//!         .mac([0x02, 0, 0, 0, 0, 1])
+//!         .max_vqp(4),
+//! ).await?;
+//!
+//! let cfg = conn.get_device_config("vdpa0").await?;
+//! println!("mtu {:?} link up {:?}", cfg.mtu, cfg.link_up());
+//!
+//! conn.del_device("vdpa0").await?;
+//! ```
+//!
+//! # Permissions
+//!
+//! Add / delete require `CAP_NET_ADMIN`. The family is registered
+//! once the `vdpa` module is loaded (5.12+; config show 5.15+).
+
+use crate::macros::genl_family;
+
+pub mod connection;
+pub mod messages;
+pub mod types;
+
+pub use messages::{
+    VdpaDev, VdpaDevConfig, VdpaDevConfigGetRequest, VdpaDevDelRequest, VdpaDevGetRequest,
+    VdpaDevNewRequest, VdpaMgmtdev, VdpaMgmtdevDumpRequest,
+};
+pub use types::{VdpaAttr, VdpaCmd, virtio_class_name};
+
+/// vDPA Generic Netlink family marker.
+///
+/// Constructed via [`Connection::<Vdpa>::new_async()`][Connection].
+/// Returns [`Error::FamilyNotFound`](crate::Error::FamilyNotFound)
+/// when the `vdpa` module is not loaded.
+///
+/// [Connection]: crate::netlink::Connection
+#[genl_family(name = "vdpa", version = 1)]
+pub struct Vdpa;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::{
+        AsyncProtocolInit, Protocol, ProtocolState, construction::AsyncConstructible,
+    };
+
+    #[test]
+    fn family_marker_carries_expected_name_and_version() {
+        assert_eq!(Vdpa::NAME, "vdpa");
+        assert_eq!(Vdpa::VERSION, 1);
+    }
+
+    #[test]
+    fn protocol_state_routes_to_generic() {
+        const _: () = {
+            assert!(matches!(Vdpa::PROTOCOL, Protocol::Generic));
+        };
+    }
+
+    fn assert_async_constructible<P: AsyncConstructible>() {}
+    fn assert_async_protocol_init<P: AsyncProtocolInit>() {}
+
+    #[test]
+    fn vdpa_satisfies_async_construction_bounds() {
+        assert_async_constructible::<Vdpa>();
+        assert_async_protocol_init::<Vdpa>();
+    }
+}
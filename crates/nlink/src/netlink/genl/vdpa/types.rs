@@ -0,0 +1,106 @@
+//! vDPA command + attribute enums.
+//!
+//! Direct translation of the kernel UAPI in
+//! `include/uapi/linux/vdpa.h`, expressed via the `nlink-macros`
+//! typed-codec derives.
+
+use crate::macros::{GenlAttribute, GenlCommand};
+
+/// vDPA command codes. Sent in the GENL header's `cmd` byte.
+///
+/// Wire: `u8` per the kernel UAPI.
+#[derive(GenlCommand, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_command(repr = "u8")]
+#[non_exhaustive]
+pub enum VdpaCmd {
+    /// `VDPA_CMD_MGMTDEV_NEW` — management-device notification.
+    MgmtdevNew = 1,
+    /// `VDPA_CMD_MGMTDEV_GET` — get / dump management devices.
+    MgmtdevGet = 2,
+    /// `VDPA_CMD_DEV_NEW` — create a vDPA device.
+    DevNew = 3,
+    /// `VDPA_CMD_DEV_DEL` — delete a vDPA device.
+    DevDel = 4,
+    /// `VDPA_CMD_DEV_GET` — get / dump vDPA devices.
+    DevGet = 5,
+    /// `VDPA_CMD_DEV_CONFIG_GET` — get / dump device config space.
+    DevConfigGet = 6,
+    /// `VDPA_CMD_DEV_VSTATS_GET` — per-queue vendor statistics.
+    DevVstatsGet = 7,
+}
+
+/// vDPA attribute kinds (`VDPA_ATTR_*`). `0` doubles as
+/// `VDPA_ATTR_PAD` and is not modelled.
+///
+/// Wire: `u16`.
+#[derive(GenlAttribute, Debug, Clone, Copy, PartialEq, Eq)]
+#[genl_attribute(repr = "u16")]
+#[non_exhaustive]
+pub enum VdpaAttr {
+    /// `VDPA_ATTR_MGMTDEV_BUS_NAME` (string, optional).
+    MgmtdevBusName = 1,
+    /// `VDPA_ATTR_MGMTDEV_DEV_NAME` (string).
+    MgmtdevDevName = 2,
+    /// `VDPA_ATTR_MGMTDEV_SUPPORTED_CLASSES` (u64 bitmask of
+    /// `1 << VIRTIO_ID_*`).
+    MgmtdevSupportedClasses = 3,
+    /// `VDPA_ATTR_DEV_NAME` (string).
+    DevName = 4,
+    /// `VDPA_ATTR_DEV_ID` (u32 virtio device id).
+    DevId = 5,
+    /// `VDPA_ATTR_DEV_VENDOR_ID` (u32).
+    DevVendorId = 6,
+    /// `VDPA_ATTR_DEV_MAX_VQS` (u32).
+    DevMaxVqs = 7,
+    /// `VDPA_ATTR_DEV_MAX_VQ_SIZE` (u16).
+    DevMaxVqSize = 8,
+    /// `VDPA_ATTR_DEV_MIN_VQ_SIZE` (u16).
+    DevMinVqSize = 9,
+    /// `VDPA_ATTR_DEV_NET_CFG_MACADDR` (6 bytes).
+    DevNetCfgMacaddr = 10,
+    /// `VDPA_ATTR_DEV_NET_STATUS` (u16 virtio-net status).
+    DevNetStatus = 11,
+    /// `VDPA_ATTR_DEV_NET_CFG_MAX_VQP` (u16).
+    DevNetCfgMaxVqp = 12,
+    /// `VDPA_ATTR_DEV_NET_CFG_MTU` (u16).
+    DevNetCfgMtu = 13,
+    /// `VDPA_ATTR_DEV_NEGOTIATED_FEATURES` (u64).
+    DevNegotiatedFeatures = 14,
+    /// `VDPA_ATTR_DEV_MGMTDEV_MAX_VQS` (u32).
+    DevMgmtdevMaxVqs = 15,
+    /// `VDPA_ATTR_DEV_SUPPORTED_FEATURES` (u64) — management-device
+    /// features.
+    DevSupportedFeatures = 16,
+    /// `VDPA_ATTR_DEV_QUEUE_INDEX` (u32).
+    DevQueueIndex = 17,
+    /// `VDPA_ATTR_DEV_VENDOR_ATTR_NAME` (string).
+    DevVendorAttrName = 18,
+    /// `VDPA_ATTR_DEV_VENDOR_ATTR_VALUE` (u64).
+    DevVendorAttrValue = 19,
+    /// `VDPA_ATTR_DEV_FEATURES` (u64) — features provisioned at
+    /// creation.
+    DevFeatures = 20,
+    /// `VDPA_ATTR_VDPA_DEV_SUPPORTED_FEATURES` (u64) — device
+    /// features.
+    VdpaDevSupportedFeatures = 21,
+}
+
+/// Name of a virtio device class (`VIRTIO_ID_*`), as printed by the
+/// `vdpa` tool.
+pub fn virtio_class_name(id: u32) -> &'static str {
+    match id {
+        1 => "net",
+        2 => "block",
+        3 => "console",
+        4 => "rng",
+        5 => "balloon",
+        8 => "scsi",
+        9 => "9p",
+        16 => "gpu",
+        18 => "input",
+        19 => "vsock",
+        20 => "crypto",
+        26 => "fs",
+        _ => "unknown",
+    }
+}
@@ -0,0 +1,471 @@
+//! Allowed-IP conflict detection.
+//!
+//! WireGuard routes by cryptokey: each allowed-ip prefix belongs to
+//! exactly one peer, and the most specific prefix wins. Setting a prefix
+//! that another peer already holds silently moves it, and a narrower
+//! prefix on a second peer silently takes part of the first peer's
+//! range. Neither produces an error, so a mistyped `AllowedIPs` line
+//! shows up only as traffic going to the wrong peer, or nowhere.
+//!
+//! [`find_allowed_ip_conflicts`] checks device snapshots (from
+//! [`Connection::get_device`] or built from a config before applying
+//! it); [`find_route_conflicts`] checks a device against the kernel's
+//! routes. [`Connection::find_allowed_ip_conflicts`] runs both for every
+//! WireGuard interface.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route, Wireguard};
+//!
+//! let wg = Connection::<Wireguard>::new_async().await?;
+//! let route = Connection::<Route>::new()?;
+//! for conflict in wg.find_allowed_ip_conflicts(&route).await? {
+//!     eprintln!("warning: {conflict}");
+//! }
+//! ```
+
+use std::{fmt, net::IpAddr};
+
+use super::{
+    config::PublicKey,
+    types::{AllowedIp, WgDevice},
+};
+use crate::{
+    Connection, Result,
+    netlink::{
+        messages::RouteMessage,
+        protocol::{Route, Wireguard},
+        types::route::{RouteType, rt_table},
+    },
+    util::addr::{ipv4_in_prefix, ipv6_in_prefix},
+};
+
+/// An allowed-ip that does not route where its configuration says.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AllowedIpConflict {
+    /// Two peers of one device list the same prefix. The kernel gives it
+    /// to whichever peer is set last; the other loses it without error.
+    Duplicate {
+        /// Interface name.
+        ifname: String,
+        /// The prefix both peers list.
+        prefix: AllowedIp,
+        /// The peer listing it first.
+        first: PublicKey,
+        /// The peer listing it again.
+        second: PublicKey,
+    },
+    /// A narrower prefix on another peer of the same device takes part
+    /// of `prefix`'s range away from `peer`.
+    Shadowed {
+        /// Interface name.
+        ifname: String,
+        /// The peer whose range is cut into.
+        peer: PublicKey,
+        /// The broader prefix.
+        prefix: AllowedIp,
+        /// The peer receiving the narrower range.
+        by_peer: PublicKey,
+        /// The narrower prefix.
+        by_prefix: AllowedIp,
+    },
+    /// Overlapping prefixes on two WireGuard devices. Which tunnel the
+    /// traffic enters depends on the routing table, not the peer config.
+    CrossDevice {
+        /// Interface name of the first device.
+        ifname: String,
+        /// Peer on the first device.
+        peer: PublicKey,
+        /// Prefix on the first device.
+        prefix: AllowedIp,
+        /// Interface name of the second device.
+        other_ifname: String,
+        /// Peer on the second device.
+        other_peer: PublicKey,
+        /// Prefix on the second device.
+        other_prefix: AllowedIp,
+    },
+    /// A kernel route at least as specific as the allowed-ip sends (part
+    /// of) its range out of another interface, so it never enters the
+    /// tunnel.
+    Route {
+        /// Interface name.
+        ifname: String,
+        /// The peer owning the allowed-ip.
+        peer: PublicKey,
+        /// The allowed-ip.
+        prefix: AllowedIp,
+        /// The route's destination.
+        destination: AllowedIp,
+        /// The route's output interface index.
+        oif: u32,
+        /// The route's table.
+        table: u32,
+    },
+}
+
+impl fmt::Display for AllowedIpConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate {
+                ifname,
+                prefix,
+                first,
+                second,
+            } => write!(
+                f,
+                "{ifname}: {prefix} is listed by peers {first} and {second}; \
+                 only the last one set receives it"
+            ),
+            Self::Shadowed {
+                ifname,
+                peer,
+                prefix,
+                by_peer,
+                by_prefix,
+            } => write!(
+                f,
+                "{ifname}: {by_prefix} of peer {by_peer} shadows part of {prefix} of peer {peer}"
+            ),
+            Self::CrossDevice {
+                ifname,
+                peer,
+                prefix,
+                other_ifname,
+                other_peer,
+                other_prefix,
+            } => write!(
+                f,
+                "{ifname}: {prefix} of peer {peer} overlaps {other_prefix} of peer \
+                 {other_peer} on {other_ifname}"
+            ),
+            Self::Route {
+                ifname,
+                peer,
+                prefix,
+                destination,
+                oif,
+                table,
+            } => write!(
+                f,
+                "{ifname}: route {destination} via ifindex {oif} (table {table}) takes \
+                 traffic for {prefix} of peer {peer} out of the tunnel"
+            ),
+        }
+    }
+}
+
+/// Find allowed-ip prefixes that collide between peers of one device or
+/// across devices.
+///
+/// Duplicates can only come from configurations (the kernel keeps one
+/// owner per prefix), so pass devices built from a config before
+/// applying it to catch them.
+pub fn find_allowed_ip_conflicts(devices: &[WgDevice]) -> Vec<AllowedIpConflict> {
+    let mut conflicts = Vec::new();
+    let entries: Vec<Vec<(PublicKey, AllowedIp)>> = devices
+        .iter()
+        .map(|dev| {
+            dev.peers
+                .iter()
+                .flat_map(|p| p.allowed_ips.iter().map(|ip| (p.public_key.into(), *ip)))
+                .collect()
+        })
+        .collect();
+
+    for (d, dev) in devices.iter().enumerate() {
+        let ifname = device_name(dev);
+        let list = &entries[d];
+        for (i, &(peer, prefix)) in list.iter().enumerate() {
+            for &(other, other_prefix) in &list[i + 1..] {
+                if other == peer {
+                    continue;
+                }
+                if same_prefix(prefix, other_prefix) {
+                    conflicts.push(AllowedIpConflict::Duplicate {
+                        ifname: ifname.clone(),
+                        prefix,
+                        first: peer,
+                        second: other,
+                    });
+                } else if let Some((outer, inner)) = nesting((peer, prefix), (other, other_prefix))
+                {
+                    conflicts.push(AllowedIpConflict::Shadowed {
+                        ifname: ifname.clone(),
+                        peer: outer.0,
+                        prefix: outer.1,
+                        by_peer: inner.0,
+                        by_prefix: inner.1,
+                    });
+                }
+            }
+        }
+
+        for (other_dev, other_list) in devices.iter().zip(&entries).skip(d + 1) {
+            for &(peer, prefix) in list {
+                for &(other_peer, other_prefix) in other_list {
+                    if overlaps(prefix, other_prefix) {
+                        conflicts.push(AllowedIpConflict::CrossDevice {
+                            ifname: ifname.clone(),
+                            peer,
+                            prefix,
+                            other_ifname: device_name(other_dev),
+                            other_peer,
+                            other_prefix,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    conflicts
+}
+
+/// Find kernel routes that pull an allowed-ip range of `device` out of
+/// the tunnel.
+///
+/// Only unicast routes outside the local table are considered, and only
+/// when they are at least as specific as the allowed-ip and leave
+/// through another interface. Default routes and `/0` allowed-ips are
+/// skipped: a full-tunnel peer normally coexists with the LAN routes
+/// it deliberately bypasses. `device.ifindex` must be set.
+pub fn find_route_conflicts(device: &WgDevice, routes: &[RouteMessage]) -> Vec<AllowedIpConflict> {
+    let Some(ifindex) = device.ifindex else {
+        return Vec::new();
+    };
+    let ifname = device_name(device);
+    let mut conflicts = Vec::new();
+
+    for route in routes {
+        let (Some(oif), Some(dst)) = (route.oif(), route.destination()) else {
+            continue;
+        };
+        if oif == ifindex
+            || route.dst_len() == 0
+            || route.route_type() != RouteType::Unicast
+            || route.table_id() == u32::from(rt_table::LOCAL)
+        {
+            continue;
+        }
+        let destination = AllowedIp {
+            addr: *dst,
+            cidr: route.dst_len(),
+        };
+        for peer in &device.peers {
+            for &prefix in &peer.allowed_ips {
+                if prefix.cidr > 0 && contains(prefix, destination) {
+                    conflicts.push(AllowedIpConflict::Route {
+                        ifname: ifname.clone(),
+                        peer: peer.public_key.into(),
+                        prefix,
+                        destination,
+                        oif,
+                        table: route.table_id(),
+                    });
+                }
+            }
+        }
+    }
+
+    conflicts
+}
+
+impl Connection<Wireguard> {
+    /// Check every WireGuard interface for allowed-ip conflicts between
+    /// peers, across devices, and with kernel routes.
+    ///
+    /// `route` lists the interfaces and routes; it must be in the same
+    /// namespace as this connection.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(method = "find_allowed_ip_conflicts")
+    )]
+    pub async fn find_allowed_ip_conflicts(
+        &self,
+        route: &Connection<Route>,
+    ) -> Result<Vec<AllowedIpConflict>> {
+        let mut devices = Vec::new();
+        for link in route.get_links().await? {
+            if link.kind() != Some("wireguard") {
+                continue;
+            }
+            let Some(name) = link.name() else {
+                continue;
+            };
+            let mut device = self.get_device_by_name(name).await?;
+            device.ifindex.get_or_insert(link.ifindex());
+            device.ifname.get_or_insert_with(|| name.to_string());
+            devices.push(device);
+        }
+
+        let mut conflicts = find_allowed_ip_conflicts(&devices);
+        if !devices.is_empty() {
+            let routes = route.get_routes().await?;
+            for device in &devices {
+                conflicts.extend(find_route_conflicts(device, &routes));
+            }
+        }
+        Ok(conflicts)
+    }
+}
+
+fn device_name(dev: &WgDevice) -> String {
+    match (&dev.ifname, dev.ifindex) {
+        (Some(name), _) => name.clone(),
+        (None, Some(index)) => format!("if{index}"),
+        (None, None) => "?".to_string(),
+    }
+}
+
+/// Whether `outer` covers all of `inner`.
+fn contains(outer: AllowedIp, inner: AllowedIp) -> bool {
+    if outer.cidr > inner.cidr {
+        return false;
+    }
+    match (outer.addr, inner.addr) {
+        (IpAddr::V4(o), IpAddr::V4(i)) => ipv4_in_prefix(i, o, outer.cidr),
+        (IpAddr::V6(o), IpAddr::V6(i)) => ipv6_in_prefix(i, o, outer.cidr),
+        _ => false,
+    }
+}
+
+fn same_prefix(a: AllowedIp, b: AllowedIp) -> bool {
+    a.cidr == b.cidr && contains(a, b)
+}
+
+fn overlaps(a: AllowedIp, b: AllowedIp) -> bool {
+    contains(a, b) || contains(b, a)
+}
+
+type Entry = (PublicKey, AllowedIp);
+
+/// Order two strictly nested entries as `(outer, inner)`.
+fn nesting(a: Entry, b: Entry) -> Option<(Entry, Entry)> {
+    if contains(a.1, b.1) {
+        Some((a, b))
+    } else if contains(b.1, a.1) {
+        Some((b, a))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+    use crate::netlink::{genl::wireguard::WgPeer, messages::RouteMessageBuilder};
+
+    fn peer(key: u8, ips: &[AllowedIp]) -> WgPeer {
+        WgPeer {
+            allowed_ips: ips.to_vec(),
+            ..WgPeer::new([key; 32])
+        }
+    }
+
+    fn device(name: &str, ifindex: u32, peers: Vec<WgPeer>) -> WgDevice {
+        WgDevice {
+            ifname: Some(name.into()),
+            ifindex: Some(ifindex),
+            peers,
+            ..WgDevice::new()
+        }
+    }
+
+    fn v4(a: u8, b: u8, c: u8, d: u8, cidr: u8) -> AllowedIp {
+        AllowedIp::v4(Ipv4Addr::new(a, b, c, d), cidr)
+    }
+
+    #[test]
+    fn detects_duplicates_and_shadowing_within_a_device() {
+        let wg0 = device(
+            "wg0",
+            5,
+            vec![
+                peer(1, &[v4(10, 0, 0, 0, 16), v4(192, 168, 7, 0, 24)]),
+                // Host bits don't hide a duplicate.
+                peer(2, &[v4(10, 0, 5, 0, 24), v4(192, 168, 7, 9, 24)]),
+                peer(3, &[AllowedIp::v6(Ipv6Addr::LOCALHOST, 128)]),
+            ],
+        );
+        let conflicts = find_allowed_ip_conflicts(&[wg0]);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(
+            conflicts[0],
+            AllowedIpConflict::Shadowed {
+                ifname: "wg0".into(),
+                peer: [1; 32].into(),
+                prefix: v4(10, 0, 0, 0, 16),
+                by_peer: [2; 32].into(),
+                by_prefix: v4(10, 0, 5, 0, 24),
+            }
+        );
+        assert!(matches!(
+            conflicts[1],
+            AllowedIpConflict::Duplicate { prefix, .. } if prefix == v4(192, 168, 7, 0, 24)
+        ));
+        assert!(
+            conflicts[1]
+                .to_string()
+                .starts_with("wg0: 192.168.7.0/24 is listed by")
+        );
+    }
+
+    #[test]
+    fn detects_overlap_across_devices() {
+        let wg0 = device("wg0", 5, vec![peer(1, &[v4(10, 0, 0, 0, 8)])]);
+        let wg1 = device("wg1", 6, vec![peer(2, &[v4(10, 1, 0, 0, 16)])]);
+        let wg2 = device("wg2", 7, vec![peer(3, &[v4(172, 16, 0, 0, 12)])]);
+        let conflicts = find_allowed_ip_conflicts(&[wg0, wg1, wg2]);
+        assert_eq!(conflicts.len(), 1);
+        assert!(matches!(
+            &conflicts[0],
+            AllowedIpConflict::CrossDevice { ifname, other_ifname, .. }
+                if ifname == "wg0" && other_ifname == "wg1"
+        ));
+    }
+
+    #[test]
+    fn detects_routes_taking_traffic_out_of_the_tunnel() {
+        let wg0 = device(
+            "wg0",
+            5,
+            vec![
+                peer(1, &[v4(10, 0, 0, 0, 16)]),
+                peer(2, &[v4(0, 0, 0, 0, 0)]),
+            ],
+        );
+        let route = |dst: &str, len, oif| {
+            RouteMessageBuilder::new()
+                .destination(dst.parse().unwrap(), len)
+                .oif(oif)
+                .table(254)
+                .route_type(RouteType::Unicast)
+        };
+        let routes = [
+            route("10.0.0.0", 16, 5).build(),
+            route("10.0.3.0", 24, 2).build(),
+            route("10.0.0.0", 8, 2).build(),
+            route("0.0.0.0", 0, 2).build(),
+            route("192.168.1.0", 24, 2).build(),
+            route("10.0.9.1", 32, 2).table(255).build(),
+        ];
+        let conflicts = find_route_conflicts(&wg0, &routes);
+        assert_eq!(
+            conflicts,
+            [AllowedIpConflict::Route {
+                ifname: "wg0".into(),
+                peer: [1; 32].into(),
+                prefix: v4(10, 0, 0, 0, 16),
+                destination: v4(10, 0, 3, 0, 24),
+                oif: 2,
+                table: 254,
+            }]
+        );
+    }
+}
@@ -19,6 +19,7 @@ use crate::{
         genl::{GENL_HDRLEN, GenlMsgHdr},
         interface_ref::InterfaceRef,
         message::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST, NLMSG_HDRLEN},
+        parse::{FromNetlink, PResult},
         protocol::{AsyncProtocolInit, Route, Wireguard},
         socket::NetlinkSocket,
     },
@@ -168,7 +169,7 @@ impl Connection<Wireguard> {
             }
 
             let attrs_data = &response[NLMSG_HDRLEN + GENL_HDRLEN..];
-            self.parse_device_attrs(attrs_data, &mut device)?;
+            parse_device_attrs(attrs_data, &mut device)?;
         }
 
         Ok(device)
@@ -283,82 +284,82 @@ impl Connection<Wireguard> {
         build_attrs(&mut builder);
         self.send_dump(builder).await
     }
+}
 
-    /// Parse device attributes from a GENL response.
-    fn parse_device_attrs(&self, data: &[u8], device: &mut WgDevice) -> Result<()> {
-        for (attr_type, payload) in AttrIter::new(data) {
-            if attr_type == WgDeviceAttr::Peers as u16 {
-                self.parse_peers_attr(payload, &mut device.peers)?;
-            } else {
-                parse_device_attr_scalar(attr_type, payload, device)?;
-            }
+/// Parse device attributes from a GENL response.
+fn parse_device_attrs(data: &[u8], device: &mut WgDevice) -> Result<()> {
+    for (attr_type, payload) in AttrIter::new(data) {
+        if attr_type == WgDeviceAttr::Peers as u16 {
+            parse_peers_attr(payload, &mut device.peers)?;
+        } else {
+            parse_device_attr_scalar(attr_type, payload, device)?;
         }
-        Ok(())
     }
+    Ok(())
+}
 
-    /// Parse the peers nested attribute.
-    fn parse_peers_attr(&self, data: &[u8], peers: &mut Vec<WgPeer>) -> Result<()> {
-        for (_idx, peer_data) in AttrIter::new(data) {
-            let peer = self.parse_peer_attrs(peer_data)?;
-            peers.push(peer);
-        }
-        Ok(())
+/// Parse the peers nested attribute.
+fn parse_peers_attr(data: &[u8], peers: &mut Vec<WgPeer>) -> Result<()> {
+    for (_idx, peer_data) in AttrIter::new(data) {
+        let peer = parse_peer_attrs(peer_data)?;
+        peers.push(peer);
     }
+    Ok(())
+}
 
-    /// Parse a single peer's attributes.
-    fn parse_peer_attrs(&self, data: &[u8]) -> Result<WgPeer> {
-        let mut peer = WgPeer::default();
+/// Parse a single peer's attributes.
+fn parse_peer_attrs(data: &[u8]) -> Result<WgPeer> {
+    let mut peer = WgPeer::default();
 
-        for (attr_type, payload) in AttrIter::new(data) {
-            match attr_type {
-                t if t == WgPeerAttr::PublicKey as u16 && payload.len() >= WG_KEY_LEN => {
-                    peer.public_key.copy_from_slice(&payload[..WG_KEY_LEN]);
-                }
-                t if t == WgPeerAttr::PresharedKey as u16 && payload.len() >= WG_KEY_LEN => {
-                    let mut key = [0u8; WG_KEY_LEN];
-                    key.copy_from_slice(&payload[..WG_KEY_LEN]);
-                    // Only set if not all zeros (kernel returns zeros if not set)
-                    if key.iter().any(|&b| b != 0) {
-                        peer.preshared_key = Some(key);
-                    }
-                }
-                t if t == WgPeerAttr::Endpoint as u16 => {
-                    peer.endpoint = parse_sockaddr(payload);
-                }
-                t if t == WgPeerAttr::PersistentKeepalive as u16 => {
-                    peer.persistent_keepalive = Some(get::u16_ne(payload)?);
-                }
-                t if t == WgPeerAttr::LastHandshake as u16 => {
-                    peer.last_handshake = parse_timespec(payload);
-                }
-                t if t == WgPeerAttr::RxBytes as u16 => {
-                    peer.rx_bytes = get::u64_ne(payload)?;
-                }
-                t if t == WgPeerAttr::TxBytes as u16 => {
-                    peer.tx_bytes = get::u64_ne(payload)?;
-                }
-                t if t == WgPeerAttr::AllowedIps as u16 => {
-                    self.parse_allowed_ips_attr(payload, &mut peer.allowed_ips)?;
-                }
-                t if t == WgPeerAttr::ProtocolVersion as u16 => {
-                    peer.protocol_version = Some(get::u32_ne(payload)?);
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            t if t == WgPeerAttr::PublicKey as u16 && payload.len() >= WG_KEY_LEN => {
+                peer.public_key.copy_from_slice(&payload[..WG_KEY_LEN]);
+            }
+            t if t == WgPeerAttr::PresharedKey as u16 && payload.len() >= WG_KEY_LEN => {
+                let mut key = [0u8; WG_KEY_LEN];
+                key.copy_from_slice(&payload[..WG_KEY_LEN]);
+                // Only set if not all zeros (kernel returns zeros if not set)
+                if key.iter().any(|&b| b != 0) {
+                    peer.preshared_key = Some(key);
                 }
-                _ => {}
             }
+            t if t == WgPeerAttr::Endpoint as u16 => {
+                peer.endpoint = parse_sockaddr(payload);
+            }
+            t if t == WgPeerAttr::PersistentKeepalive as u16 => {
+                peer.persistent_keepalive = Some(get::u16_ne(payload)?);
+            }
+            t if t == WgPeerAttr::LastHandshake as u16 => {
+                peer.last_handshake = parse_timespec(payload);
+            }
+            t if t == WgPeerAttr::RxBytes as u16 => {
+                peer.rx_bytes = get::u64_ne(payload)?;
+            }
+            t if t == WgPeerAttr::TxBytes as u16 => {
+                peer.tx_bytes = get::u64_ne(payload)?;
+            }
+            t if t == WgPeerAttr::AllowedIps as u16 => {
+                parse_allowed_ips_attr(payload, &mut peer.allowed_ips)?;
+            }
+            t if t == WgPeerAttr::ProtocolVersion as u16 => {
+                peer.protocol_version = Some(get::u32_ne(payload)?);
+            }
+            _ => {}
         }
-
-        Ok(peer)
     }
 
-    /// Parse allowed IPs nested attribute.
-    fn parse_allowed_ips_attr(&self, data: &[u8], allowed_ips: &mut Vec<AllowedIp>) -> Result<()> {
-        for (_idx, ip_data) in AttrIter::new(data) {
-            if let Some(ip) = parse_allowed_ip_attrs(ip_data)? {
-                allowed_ips.push(ip);
-            }
+    Ok(peer)
+}
+
+/// Parse allowed IPs nested attribute.
+fn parse_allowed_ips_attr(data: &[u8], allowed_ips: &mut Vec<AllowedIp>) -> Result<()> {
+    for (_idx, ip_data) in AttrIter::new(data) {
+        if let Some(ip) = parse_allowed_ip_attrs(ip_data)? {
+            allowed_ips.push(ip);
         }
-        Ok(())
     }
+    Ok(())
 }
 
 /// Read a `WG_KEY_LEN` key from the front of `payload`, mapping the kernel's
@@ -377,8 +378,8 @@ fn parse_key(payload: &[u8]) -> Option<[u8; WG_KEY_LEN]> {
 
 /// Parse scalar (non-nested) device attributes into `device`.
 ///
-/// Split out from `Connection::parse_device_attrs` so the scalar parsing path
-/// is testable without a live socket.
+/// Split out from [`parse_device_attrs`] so the scalar parsing path
+/// is testable on its own.
 fn parse_device_attr_scalar(attr_type: u16, payload: &[u8], device: &mut WgDevice) -> Result<()> {
     match attr_type {
         t if t == WgDeviceAttr::Ifindex as u16 => {
@@ -547,6 +548,30 @@ fn parse_sockaddr(data: &[u8]) -> Option<SocketAddr> {
     }
 }
 
+impl FromNetlink for WgDevice {
+    /// `parse` isn't used by the dump path (it overrides `from_bytes`),
+    /// but the trait requires it. Consume the whole input via
+    /// `from_bytes`.
+    fn parse(input: &mut &[u8]) -> PResult<Self> {
+        let consumed = *input;
+        *input = &input[input.len()..];
+        Self::from_bytes(consumed)
+            .map_err(|_| winnow::error::ErrMode::Cut(winnow::error::ContextError::new()))
+    }
+
+    /// Parse one `WG_CMD_GET_DEVICE` reply body (GENL header + attrs,
+    /// without the `nlmsghdr`). A device with many peers is split over
+    /// several replies; [`Connection::get_device`] merges them.
+    fn from_bytes(payload: &[u8]) -> Result<Self> {
+        let attrs = payload.get(GENL_HDRLEN..).ok_or_else(|| {
+            Error::InvalidMessage("wireguard reply shorter than genlmsghdr".into())
+        })?;
+        let mut device = WgDevice::new();
+        parse_device_attrs(attrs, &mut device)?;
+        Ok(device)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::{Ipv4Addr, Ipv6Addr};
@@ -0,0 +1,352 @@
+//! Peer endpoint DNS re-resolution.
+//!
+//! The kernel stores a peer's endpoint as an address; a hostname in a
+//! config is resolved once, when the peer is set. When the remote end
+//! sits behind a dynamic DNS name its address changes and the tunnel
+//! stays down until someone re-runs `wg set`. This module re-resolves
+//! such hostnames and updates the endpoint when the address moves, the
+//! job of the `reresolve-dns.sh` script shipped in wireguard-tools'
+//! contrib directory.
+//!
+//! [`Connection::refresh_peer_endpoint`] does one check;
+//! [`EndpointRefresher`] repeats it on an interval.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Wireguard};
+//! use nlink::netlink::genl::wireguard::{EndpointRefreshOptions, EndpointRefresher};
+//!
+//! let conn = Connection::<Wireguard>::new_async().await?;
+//! let mut refresher = EndpointRefresher::new(
+//!     conn,
+//!     EndpointRefreshOptions::default().peer("wg0", peer_key, "vpn.example.com:51820"),
+//! )?;
+//! loop {
+//!     for update in refresher.next_updates().await? {
+//!         println!("{}: endpoint now {}", update.ifname, update.current);
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, SystemTime};
+
+use super::types::{WG_KEY_LEN, WgDevice, WgPeer};
+use crate::netlink::protocol::Wireguard;
+use crate::{Connection, Error, Result};
+
+/// How long after its last handshake a peer counts as disconnected:
+/// the kernel's `REJECT_AFTER_TIME` (180 s) less `REKEY_TIMEOUT`
+/// slack, as used by wireguard-tools' `reresolve-dns.sh`.
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(135);
+
+/// A peer whose endpoint is known by hostname.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostEndpoint {
+    /// Interface the peer belongs to.
+    pub ifname: String,
+    /// The peer's public key.
+    pub public_key: [u8; WG_KEY_LEN],
+    /// `host:port` to resolve.
+    pub host: String,
+}
+
+/// Configuration for [`EndpointRefresher`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+#[must_use = "options do nothing unless passed to EndpointRefresher::new"]
+pub struct EndpointRefreshOptions {
+    /// How often to re-resolve. Default: 60 seconds.
+    pub interval: Duration,
+    /// Only re-resolve peers without a handshake for this long, so an
+    /// endpoint the peer roamed to is left alone while the tunnel
+    /// works. `None` re-resolves every peer on every cycle. Default:
+    /// [`DEFAULT_HANDSHAKE_TIMEOUT`].
+    pub handshake_timeout: Option<Duration>,
+    /// Peers to keep resolved. Must be non-empty.
+    pub peers: Vec<HostEndpoint>,
+}
+
+impl Default for EndpointRefreshOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            handshake_timeout: Some(DEFAULT_HANDSHAKE_TIMEOUT),
+            peers: Vec::new(),
+        }
+    }
+}
+
+impl EndpointRefreshOptions {
+    /// Set the re-resolution interval (builder shape).
+    pub fn interval(mut self, d: Duration) -> Self {
+        self.interval = d;
+        self
+    }
+
+    /// Set the handshake age from which a peer is re-resolved; `None`
+    /// re-resolves on every cycle (builder shape).
+    pub fn handshake_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    /// Keep the endpoint of `public_key` on `ifname` pointed at `host`,
+    /// given as `host:port` (builder shape).
+    pub fn peer(
+        mut self,
+        ifname: impl Into<String>,
+        public_key: [u8; WG_KEY_LEN],
+        host: impl Into<String>,
+    ) -> Self {
+        self.peers.push(HostEndpoint {
+            ifname: ifname.into(),
+            public_key,
+            host: host.into(),
+        });
+        self
+    }
+}
+
+/// An endpoint changed by the refresher.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EndpointUpdate {
+    /// Interface the peer belongs to.
+    pub ifname: String,
+    /// The peer's public key.
+    pub public_key: [u8; WG_KEY_LEN],
+    /// Endpoint before the update.
+    pub previous: Option<SocketAddr>,
+    /// Endpoint now configured.
+    pub current: SocketAddr,
+}
+
+impl Connection<Wireguard> {
+    /// Resolve `host` (`host:port`) and point the peer's endpoint at it
+    /// if the peer's current endpoint is not among the results.
+    ///
+    /// Returns the new endpoint, or `None` if nothing changed. Fails if
+    /// the peer doesn't exist or `host` doesn't resolve.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "refresh_peer_endpoint"))]
+    pub async fn refresh_peer_endpoint(
+        &self,
+        ifname: &str,
+        public_key: [u8; WG_KEY_LEN],
+        host: &str,
+    ) -> Result<Option<SocketAddr>> {
+        let device = self.get_device_by_name(ifname).await?;
+        let peer = find_peer(&device, &public_key, ifname)?;
+        self.reresolve(ifname, peer, host).await
+    }
+
+    async fn reresolve(
+        &self,
+        ifname: &str,
+        peer: &WgPeer,
+        host: &str,
+    ) -> Result<Option<SocketAddr>> {
+        let resolved: Vec<SocketAddr> = tokio::net::lookup_host(host).await?.collect();
+        if resolved.is_empty() {
+            return Err(Error::InvalidMessage(format!(
+                "{host} resolved to no addresses"
+            )));
+        }
+        let Some(endpoint) = pick_endpoint(peer.endpoint, &resolved) else {
+            return Ok(None);
+        };
+        self.set_peer_by_name(ifname, peer.public_key, |p| p.endpoint(endpoint))
+            .await?;
+        Ok(Some(endpoint))
+    }
+}
+
+/// Re-resolves hostname endpoints on an interval and updates peers
+/// whose address changed.
+#[must_use = "EndpointRefresher does nothing unless next_updates() is called"]
+pub struct EndpointRefresher {
+    conn: Connection<Wireguard>,
+    opts: EndpointRefreshOptions,
+    first_poll: bool,
+}
+
+impl EndpointRefresher {
+    /// Construct a new refresher. Returns `Error::InvalidMessage` if
+    /// `opts.peers` is empty.
+    pub fn new(conn: Connection<Wireguard>, opts: EndpointRefreshOptions) -> Result<Self> {
+        if opts.peers.is_empty() {
+            return Err(Error::InvalidMessage(
+                "EndpointRefreshOptions::peers is empty — \
+                 specify at least one peer to keep resolved"
+                    .to_string(),
+            ));
+        }
+        Ok(Self {
+            conn,
+            opts,
+            first_poll: true,
+        })
+    }
+
+    /// Sleep until the next cycle, then re-resolve every peer due for
+    /// it and return the endpoints that changed.
+    ///
+    /// The first call does not sleep. A peer whose interface, peer
+    /// entry or hostname can't be looked up is logged and skipped; the
+    /// other peers are still refreshed.
+    pub async fn next_updates(&mut self) -> Result<Vec<EndpointUpdate>> {
+        if !self.first_poll {
+            tokio::time::sleep(self.opts.interval).await;
+        }
+        self.first_poll = false;
+
+        let now = SystemTime::now();
+        let mut devices: HashMap<String, WgDevice> = HashMap::new();
+        let mut updates = Vec::new();
+        for target in &self.opts.peers {
+            if !devices.contains_key(&target.ifname) {
+                match self.conn.get_device_by_name(&target.ifname).await {
+                    Ok(device) => {
+                        devices.insert(target.ifname.clone(), device);
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            ifname = %target.ifname,
+                            error = %e,
+                            "EndpointRefresher: failed to read interface; skipping its peers",
+                        );
+                        continue;
+                    }
+                }
+            }
+            let device = &devices[&target.ifname];
+            let peer = match find_peer(device, &target.public_key, &target.ifname) {
+                Ok(peer) => peer,
+                Err(e) => {
+                    tracing::warn!(error = %e, "EndpointRefresher: skipping peer");
+                    continue;
+                }
+            };
+            if !needs_refresh(peer, self.opts.handshake_timeout, now) {
+                continue;
+            }
+            match self
+                .conn
+                .reresolve(&target.ifname, peer, &target.host)
+                .await
+            {
+                Ok(Some(current)) => updates.push(EndpointUpdate {
+                    ifname: target.ifname.clone(),
+                    public_key: target.public_key,
+                    previous: peer.endpoint,
+                    current,
+                }),
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        ifname = %target.ifname,
+                        host = %target.host,
+                        error = %e,
+                        "EndpointRefresher: failed to refresh endpoint",
+                    );
+                }
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Borrow the underlying connection.
+    pub fn connection(&self) -> &Connection<Wireguard> {
+        &self.conn
+    }
+
+    /// Recover the underlying connection when done.
+    pub fn into_connection(self) -> Connection<Wireguard> {
+        self.conn
+    }
+}
+
+fn find_peer<'a>(
+    device: &'a WgDevice,
+    public_key: &[u8; WG_KEY_LEN],
+    ifname: &str,
+) -> Result<&'a WgPeer> {
+    device
+        .peers
+        .iter()
+        .find(|p| &p.public_key == public_key)
+        .ok_or_else(|| Error::InvalidMessage(format!("{ifname}: no such peer")))
+}
+
+/// Whether a peer's handshake is old enough (or missing) to warrant
+/// re-resolving its endpoint.
+fn needs_refresh(peer: &WgPeer, timeout: Option<Duration>, now: SystemTime) -> bool {
+    let (Some(timeout), Some(last)) = (timeout, peer.last_handshake) else {
+        return true;
+    };
+    now.duration_since(last).is_ok_and(|age| age >= timeout)
+}
+
+/// The endpoint to switch to, or `None` if `current` is still one of the
+/// `resolved` addresses. Prefers an address of the current family.
+fn pick_endpoint(current: Option<SocketAddr>, resolved: &[SocketAddr]) -> Option<SocketAddr> {
+    if current.is_some_and(|c| resolved.contains(&c)) {
+        return None;
+    }
+    current
+        .and_then(|c| resolved.iter().find(|r| r.is_ipv4() == c.is_ipv4()))
+        .or_else(|| resolved.first())
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn pick_endpoint_keeps_a_still_valid_address() {
+        let resolved = [addr("198.51.100.7:51820"), addr("[2001:db8::7]:51820")];
+        assert_eq!(pick_endpoint(Some(resolved[1]), &resolved), None);
+        assert_eq!(
+            pick_endpoint(Some(addr("198.51.100.1:51820")), &resolved),
+            Some(resolved[0])
+        );
+        assert_eq!(
+            pick_endpoint(Some(addr("[2001:db8::1]:51820")), &resolved),
+            Some(resolved[1])
+        );
+        assert_eq!(pick_endpoint(None, &resolved), Some(resolved[0]));
+        // The port is part of the endpoint.
+        assert_eq!(
+            pick_endpoint(Some(addr("198.51.100.7:1")), &resolved),
+            Some(resolved[0])
+        );
+    }
+
+    #[test]
+    fn refresh_waits_for_a_stale_handshake() {
+        let now = SystemTime::now();
+        let mut peer = WgPeer::new([1; WG_KEY_LEN]);
+        let timeout = Some(DEFAULT_HANDSHAKE_TIMEOUT);
+
+        assert!(needs_refresh(&peer, timeout, now));
+        peer.last_handshake = Some(now - Duration::from_secs(30));
+        assert!(!needs_refresh(&peer, timeout, now));
+        assert!(needs_refresh(&peer, None, now));
+        peer.last_handshake = Some(now - Duration::from_secs(200));
+        assert!(needs_refresh(&peer, timeout, now));
+    }
+
+    #[test]
+    fn options_builder_collects_peers() {
+        let opts = EndpointRefreshOptions::default().peer("wg0", [1; WG_KEY_LEN], "h:1");
+        assert_eq!(opts.peers.len(), 1);
+        assert_eq!(opts.handshake_timeout, Some(DEFAULT_HANDSHAKE_TIMEOUT));
+    }
+}
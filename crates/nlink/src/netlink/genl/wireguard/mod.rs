@@ -59,7 +59,9 @@
 //! ```
 
 pub mod config;
+pub mod conflicts;
 mod connection;
+pub mod endpoint;
 mod types;
 pub mod watch;
 
@@ -68,6 +70,11 @@ pub use config::{
     DeviceChanges, PeerChanges, PublicKey, WireguardApplyResult, WireguardConfig,
     WireguardConfigDiff,
 };
+pub use conflicts::{AllowedIpConflict, find_allowed_ip_conflicts, find_route_conflicts};
+pub use endpoint::{
+    DEFAULT_HANDSHAKE_TIMEOUT, EndpointRefreshOptions, EndpointRefresher, EndpointUpdate,
+    HostEndpoint,
+};
 pub use types::{
     AllowedIp, WG_KEY_LEN, WgDevice, WgDeviceBuilder, WgPeer, WgPeerBuilder, WgPeerFlags,
 };
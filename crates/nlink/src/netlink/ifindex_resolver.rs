@@ -0,0 +1,292 @@
+//! Cached, bidirectional interface index ↔ name resolution.
+//!
+//! [`Connection::get_interface_names`](super::Connection::get_interface_names)
+//! dumps every link and hands back a one-shot `HashMap`; code that formats
+//! routes, neighbours or FDB entries tends to call it (or read
+//! `/sys/class/net`) again and again. [`IfIndexResolver`] loads the table once,
+//! answers lookups in both directions, and stays current when fed the link
+//! events of a subscription.
+//!
+//! The resolver is a cheap-to-clone handle over shared state, so one task can
+//! keep it updated while any number of others read from it. Installing one
+//! with [`IfIndexResolver::install`] makes nlink's own output formatting
+//! ([`ifname_or_index`]) use it instead of scanning `/sys/class/net` per row.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route, RtnetlinkGroup};
+//! use tokio_stream::StreamExt;
+//!
+//! let conn = Connection::<Route>::new()?;
+//! let names = conn.interface_resolver().await?;
+//!
+//! conn.subscribe(&[RtnetlinkGroup::Link, RtnetlinkGroup::Neigh])?;
+//! let mut events = names.track(conn.events().await);
+//! while let Some(event) = events.next().await {
+//!     if let Some(neigh) = event?.as_neighbor() {
+//!         println!("neighbour on {}", names.name_or_index(neigh.ifindex()));
+//!     }
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use tokio_stream::{Stream, StreamExt};
+
+use super::{
+    connection::Connection, error::Result, events::NetworkEvent, messages::LinkMessage,
+    protocol::Route,
+};
+
+/// The resolver consulted by [`ifname_or_index`], if one is installed.
+static INSTALLED: RwLock<Option<IfIndexResolver>> = RwLock::new(None);
+
+/// Resolve an interface index for display.
+///
+/// Uses the [installed](IfIndexResolver::install) resolver when it knows the
+/// index, and otherwise falls back to
+/// [`get_ifname_or_index`](crate::util::get_ifname_or_index).
+pub fn ifname_or_index(ifindex: u32) -> String {
+    IfIndexResolver::installed()
+        .and_then(|r| r.name(ifindex))
+        .unwrap_or_else(|| crate::util::get_ifname_or_index(ifindex))
+}
+
+#[derive(Debug, Default)]
+struct Names {
+    by_index: HashMap<u32, String>,
+    by_name: HashMap<String, u32>,
+}
+
+impl Names {
+    fn insert(&mut self, ifindex: u32, name: String) {
+        // A rename leaves the old name pointing at this index; drop it.
+        if let Some(old) = self.by_index.get(&ifindex)
+            && *old != name
+        {
+            self.by_name.remove(old);
+        }
+        // A reused name that belonged to a deleted link we missed.
+        if let Some(prev) = self.by_name.insert(name.clone(), ifindex)
+            && prev != ifindex
+        {
+            self.by_index.remove(&prev);
+        }
+        self.by_index.insert(ifindex, name);
+    }
+
+    fn remove(&mut self, ifindex: u32) {
+        if let Some(name) = self.by_index.remove(&ifindex) {
+            self.by_name.remove(&name);
+        }
+    }
+}
+
+/// A shared, bidirectional cache of interface indexes and names.
+///
+/// Cloning shares the underlying table. Lookups take a brief read lock
+/// and never touch the kernel; use [`refresh`](Self::refresh) or
+/// [`track`](Self::track) to keep the table current.
+#[derive(Debug, Clone, Default)]
+pub struct IfIndexResolver {
+    inner: Arc<RwLock<Names>>,
+}
+
+impl IfIndexResolver {
+    /// Create an empty resolver.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a resolver populated from a link dump.
+    pub async fn load(conn: &Connection<Route>) -> Result<Self> {
+        let resolver = Self::new();
+        resolver.refresh(conn).await?;
+        Ok(resolver)
+    }
+
+    /// Replace the table with a fresh link dump.
+    pub async fn refresh(&self, conn: &Connection<Route>) -> Result<()> {
+        let links = conn.get_links().await?;
+        let mut names = Names::default();
+        for link in &links {
+            if let Some(name) = link.name() {
+                names.insert(link.ifindex(), name.to_string());
+            }
+        }
+        *self.write() = names;
+        Ok(())
+    }
+
+    /// Look up the name of an interface index.
+    pub fn name(&self, ifindex: u32) -> Option<String> {
+        self.read().by_index.get(&ifindex).cloned()
+    }
+
+    /// Look up the index of an interface name.
+    pub fn index(&self, name: &str) -> Option<u32> {
+        self.read().by_name.get(name).copied()
+    }
+
+    /// The interface name, or `if<N>` when the index is unknown
+    /// (the same fallback as [`get_ifname_or_index`](crate::util::get_ifname_or_index)).
+    pub fn name_or_index(&self, ifindex: u32) -> String {
+        self.name(ifindex).unwrap_or_else(|| format!("if{ifindex}"))
+    }
+
+    /// Record an index ↔ name pair, replacing any stale mapping of either.
+    pub fn insert(&self, ifindex: u32, name: impl Into<String>) {
+        self.write().insert(ifindex, name.into());
+    }
+
+    /// Forget an interface index.
+    pub fn remove(&self, ifindex: u32) {
+        self.write().remove(ifindex);
+    }
+
+    /// Apply a link event to the table.
+    ///
+    /// `NewLink` records (or renames) the link, `DelLink` removes it.
+    /// Returns `true` if the event was a link event.
+    pub fn observe(&self, event: &NetworkEvent) -> bool {
+        match event {
+            NetworkEvent::NewLink(link) => {
+                self.observe_link(link);
+                true
+            }
+            NetworkEvent::DelLink(link) => {
+                self.remove(link.ifindex());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn observe_link(&self, link: &LinkMessage) {
+        if let Some(name) = link.name() {
+            self.insert(link.ifindex(), name);
+        }
+    }
+
+    /// Wrap an event stream so every link event updates the table before
+    /// it is yielded. Other events pass through untouched.
+    ///
+    /// The connection must be subscribed to
+    /// [`RtnetlinkGroup::Link`](super::RtnetlinkGroup::Link) for the
+    /// table to follow link changes.
+    pub fn track<S>(&self, events: S) -> impl Stream<Item = Result<NetworkEvent>>
+    where
+        S: Stream<Item = Result<NetworkEvent>>,
+    {
+        let resolver = self.clone();
+        events.map(move |event| {
+            if let Ok(event) = &event {
+                resolver.observe(event);
+            }
+            event
+        })
+    }
+
+    /// Make this resolver the process-wide one used by [`ifname_or_index`],
+    /// replacing any previously installed resolver.
+    pub fn install(&self) {
+        *INSTALLED.write().unwrap_or_else(|e| e.into_inner()) = Some(self.clone());
+    }
+
+    /// The process-wide resolver, if one has been installed.
+    pub fn installed() -> Option<Self> {
+        INSTALLED.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Number of known interfaces.
+    pub fn len(&self) -> usize {
+        self.read().by_index.len()
+    }
+
+    /// Whether no interfaces are known.
+    pub fn is_empty(&self) -> bool {
+        self.read().by_index.is_empty()
+    }
+
+    /// Copy the table out as an index → name map.
+    pub fn to_map(&self) -> HashMap<u32, String> {
+        self.read().by_index.clone()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Names> {
+        // A panicking writer cannot leave `Names` half-updated in a way
+        // that matters for lookups, so recover from poisoning.
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Names> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookups_work_in_both_directions() {
+        let r = IfIndexResolver::new();
+        r.insert(1, "lo");
+        r.insert(4, "eth0");
+
+        assert_eq!(r.name(4).as_deref(), Some("eth0"));
+        assert_eq!(r.index("lo"), Some(1));
+        assert_eq!(r.index("eth1"), None);
+        assert_eq!(r.name_or_index(9), "if9");
+        assert_eq!(r.len(), 2);
+    }
+
+    #[test]
+    fn rename_and_reuse_drop_stale_entries() {
+        let r = IfIndexResolver::new();
+        r.insert(4, "eth0");
+        r.insert(4, "wan0");
+        assert_eq!(r.index("eth0"), None);
+        assert_eq!(r.index("wan0"), Some(4));
+
+        // `wan0` recreated at a new index without us seeing the delete.
+        r.insert(7, "wan0");
+        assert_eq!(r.name(4), None);
+        assert_eq!(r.name(7).as_deref(), Some("wan0"));
+        assert_eq!(r.len(), 1);
+
+        r.remove(7);
+        assert!(r.is_empty());
+        assert_eq!(r.index("wan0"), None);
+    }
+
+    #[tokio::test]
+    async fn track_applies_link_events() {
+        use crate::netlink::messages::LinkMessageBuilder;
+
+        let link = |idx, name| LinkMessageBuilder::new().ifindex(idx).name(name).build();
+        let events = tokio_stream::iter(vec![
+            Ok(NetworkEvent::NewLink(link(5, "veth0"))),
+            Ok(NetworkEvent::NewLink(link(5, "veth-a"))),
+            Ok(NetworkEvent::NewLink(link(6, "veth1"))),
+            Ok(NetworkEvent::DelLink(link(6, "veth1"))),
+        ]);
+
+        let r = IfIndexResolver::new();
+        let seen: Vec<_> = r.track(events).collect().await;
+        assert_eq!(seen.len(), 4);
+        assert_eq!(r.name(5).as_deref(), Some("veth-a"));
+        assert_eq!(r.index("veth0"), None);
+        assert_eq!(r.name(6), None);
+    }
+
+    #[test]
+    fn clones_share_the_table() {
+        let r = IfIndexResolver::new();
+        let reader = r.clone();
+        r.insert(3, "br0");
+        assert_eq!(reader.index("br0"), Some(3));
+    }
+}
@@ -29,6 +29,10 @@
 //! - [`Ip6GreLink`] - IPv6 GRE tunnel
 //! - [`Ip6GretapLink`] - IPv6 GRE TAP tunnel (Layer 2)
 //! - [`WireguardLink`] - WireGuard interface
+//! - [`XfrmiLink`] - XFRM interface (route-based IPsec by `if_id`)
+//! - [`GtpLink`] - GTP-U tunnel endpoint (mobile core user plane)
+//! - [`VcanLink`] - Virtual CAN interface
+//! - [`VxcanLink`] - Virtual CAN tunnel pair
 //! - [`MacsecLink`] - MACsec (IEEE 802.1AE) L2 encryption interface
 //!
 //! # Tunnel Modification Limitations
@@ -101,14 +105,18 @@
 //! conn.add_link(VlanLink::new("eth0.100", "eth0", 100)).await?;
 //! ```
 
-use std::net::Ipv4Addr;
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    os::fd::RawFd,
+};
 
 use super::{
     builder::MessageBuilder,
     connection::Connection,
-    error::Result,
+    error::{Error, Result},
     interface_ref::InterfaceRef,
     message::NlMsgType,
+    messages::LinkMessage,
     protocol::Route,
     types::link::{IfInfoMsg, IflaAttr, IflaInfo},
 };
@@ -140,6 +148,13 @@ const NLM_F_EXCL: u16 = 0x200;
 ///     .learning(false)
 ///     .mcast_flood(false);
 /// conn.set_bridge_port("swp1", cfg).await?;
+///
+/// // EVPN VXLAN port: map VLANs to VNIs and answer ARP/ND locally.
+/// let cfg = BridgePortConfig::new()
+///     .vlan_tunnel(true)
+///     .neigh_suppress(true)
+///     .learning(false);
+/// conn.set_bridge_port("vxlan0", cfg).await?;
 /// ```
 #[derive(Debug, Clone, Default)]
 pub struct BridgePortConfig {
@@ -171,6 +186,10 @@ pub struct BridgePortConfig {
     pub bcast_flood: Option<bool>,
     /// Neighbour suppression.
     pub neigh_suppress: Option<bool>,
+    /// Per-VLAN neighbour suppression.
+    pub neigh_vlan_suppress: Option<bool>,
+    /// VLAN-to-tunnel mapping.
+    pub vlan_tunnel: Option<bool>,
     /// Port isolation.
     pub isolated: Option<bool>,
 }
@@ -260,11 +279,38 @@ impl BridgePortConfig {
     }
 
     /// Enable or disable neighbour suppression.
+    ///
+    /// With it on, the bridge answers ARP requests and IPv6 neighbour
+    /// solicitations arriving on this port from its own neighbour
+    /// table (typically populated by an EVPN control plane) instead of
+    /// flooding them — usually set on the VXLAN port.
     pub fn neigh_suppress(mut self, on: bool) -> Self {
         self.neigh_suppress = Some(on);
         self
     }
 
+    /// Enable or disable per-VLAN neighbour suppression.
+    ///
+    /// When on, the port-wide [`neigh_suppress`](Self::neigh_suppress)
+    /// flag is ignored and each VLAN's own setting applies; see
+    /// [`BridgeVlanEntryOptionsBuilder::neigh_suppress`](crate::netlink::bridge_vlan::BridgeVlanEntryOptionsBuilder::neigh_suppress).
+    /// Needs Linux 6.6 or later.
+    pub fn neigh_vlan_suppress(mut self, on: bool) -> Self {
+        self.neigh_vlan_suppress = Some(on);
+        self
+    }
+
+    /// Enable or disable VLAN-to-tunnel mapping.
+    ///
+    /// On a collect-metadata VXLAN port this makes the bridge use the
+    /// port's VLAN-to-VNI map (see
+    /// [`Connection::add_vlan_tunnel`](crate::netlink::Connection::add_vlan_tunnel)),
+    /// so a single VXLAN device can carry many VNIs.
+    pub fn vlan_tunnel(mut self, on: bool) -> Self {
+        self.vlan_tunnel = Some(on);
+        self
+    }
+
     /// Enable or disable port isolation.
     pub fn isolated(mut self, on: bool) -> Self {
         self.isolated = Some(on);
@@ -292,6 +338,8 @@ impl BridgePortConfig {
             && self.mcast_to_unicast.is_none()
             && self.bcast_flood.is_none()
             && self.neigh_suppress.is_none()
+            && self.neigh_vlan_suppress.is_none()
+            && self.vlan_tunnel.is_none()
             && self.isolated.is_none()
     }
 
@@ -324,6 +372,8 @@ impl BridgePortConfig {
         put_bool(IFLA_BRPORT_MCAST_TO_UCAST, self.mcast_to_unicast);
         put_bool(IFLA_BRPORT_BCAST_FLOOD, self.bcast_flood);
         put_bool(IFLA_BRPORT_NEIGH_SUPPRESS, self.neigh_suppress);
+        put_bool(IFLA_BRPORT_NEIGH_VLAN_SUPPRESS, self.neigh_vlan_suppress);
+        put_bool(IFLA_BRPORT_VLAN_TUNNEL, self.vlan_tunnel);
         put_bool(IFLA_BRPORT_ISOLATED, self.isolated);
         builder.nest_end(token);
     }
@@ -357,6 +407,28 @@ pub trait LinkConfig: Send + Sync {
     fn write_to(&self, builder: &mut MessageBuilder, parent_index: Option<u32>);
 }
 
+impl<L: LinkConfig + ?Sized> LinkConfig for &L {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn kind(&self) -> &str {
+        (**self).kind()
+    }
+
+    fn peer_name(&self) -> Option<&str> {
+        (**self).peer_name()
+    }
+
+    fn parent_ref(&self) -> Option<&InterfaceRef> {
+        (**self).parent_ref()
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, parent_index: Option<u32>) {
+        (**self).write_to(builder, parent_index)
+    }
+}
+
 // ============================================================================
 // Dummy Link
 // ============================================================================
@@ -612,13 +684,26 @@ impl LinkConfig for VethLink {
 /// # Example
 ///
 /// ```ignore
-/// use nlink::netlink::link::BridgeLink;
+/// use nlink::netlink::link::{BridgeLink, VlanProtocol};
 ///
 /// let bridge = BridgeLink::new("br0")
 ///     .stp(true)
 ///     .vlan_filtering(true);
 ///
 /// conn.add_link(bridge).await?;
+///
+/// // An 802.1ad (Q-in-Q) bridge with IGMPv3/MLDv2 snooping and an
+/// // active querier, not passing bridged traffic through iptables.
+/// let bridge = BridgeLink::new("br1")
+///     .vlan_filtering(true)
+///     .vlan_protocol(VlanProtocol::Dot1ad)
+///     .mcast_snooping(true)
+///     .mcast_querier(true)
+///     .mcast_igmp_version(3)
+///     .mcast_mld_version(2)
+///     .nf_call_iptables(false)
+///     .nf_call_ip6tables(false);
+/// conn.add_link(bridge).await?;
 /// ```
 #[derive(Debug, Clone)]
 #[must_use = "builders do nothing unless used"]
@@ -642,6 +727,24 @@ pub struct BridgeLink {
     vlan_filtering: Option<bool>,
     /// Default PVID
     vlan_default_pvid: Option<u16>,
+    /// VLAN protocol used for filtering
+    vlan_protocol: Option<VlanProtocol>,
+    /// Link-local group address forwarding mask
+    group_fwd_mask: Option<u16>,
+    /// IGMP/MLD snooping enabled
+    mcast_snooping: Option<bool>,
+    /// Act as IGMP/MLD querier
+    mcast_querier: Option<bool>,
+    /// IGMP version used by the querier (2 or 3)
+    mcast_igmp_version: Option<u8>,
+    /// MLD version used by the querier (1 or 2)
+    mcast_mld_version: Option<u8>,
+    /// Pass bridged IPv4 traffic through iptables
+    nf_call_iptables: Option<bool>,
+    /// Pass bridged IPv6 traffic through ip6tables
+    nf_call_ip6tables: Option<bool>,
+    /// Pass bridged ARP traffic through arptables
+    nf_call_arptables: Option<bool>,
 }
 
 /// Bridge-specific attributes (IFLA_BR_*)
@@ -653,7 +756,16 @@ mod bridge {
     pub const IFLA_BR_STP_STATE: u16 = 5;
     pub const IFLA_BR_PRIORITY: u16 = 6;
     pub const IFLA_BR_VLAN_FILTERING: u16 = 7;
+    pub const IFLA_BR_VLAN_PROTOCOL: u16 = 8;
+    pub const IFLA_BR_GROUP_FWD_MASK: u16 = 9;
+    pub const IFLA_BR_MCAST_SNOOPING: u16 = 23;
+    pub const IFLA_BR_MCAST_QUERIER: u16 = 25;
+    pub const IFLA_BR_NF_CALL_IPTABLES: u16 = 36;
+    pub const IFLA_BR_NF_CALL_IP6TABLES: u16 = 37;
+    pub const IFLA_BR_NF_CALL_ARPTABLES: u16 = 38;
     pub const IFLA_BR_VLAN_DEFAULT_PVID: u16 = 39;
+    pub const IFLA_BR_MCAST_IGMP_VERSION: u16 = 43;
+    pub const IFLA_BR_MCAST_MLD_VERSION: u16 = 44;
 }
 
 impl BridgeLink {
@@ -671,6 +783,15 @@ impl BridgeLink {
             priority: None,
             vlan_filtering: None,
             vlan_default_pvid: None,
+            vlan_protocol: None,
+            group_fwd_mask: None,
+            mcast_snooping: None,
+            mcast_querier: None,
+            mcast_igmp_version: None,
+            mcast_mld_version: None,
+            nf_call_iptables: None,
+            nf_call_ip6tables: None,
+            nf_call_arptables: None,
         }
     }
 
@@ -735,6 +856,64 @@ impl BridgeLink {
         self.vlan_default_pvid = Some(pvid);
         self
     }
+
+    /// Set the VLAN protocol used for filtering; `Dot1ad` makes an
+    /// 802.1ad (S-VLAN) bridge.
+    pub fn vlan_protocol(mut self, protocol: VlanProtocol) -> Self {
+        self.vlan_protocol = Some(protocol);
+        self
+    }
+
+    /// Set which link-local group addresses (`01:80:c2:00:00:0X`, one
+    /// bit per `X`) are forwarded instead of consumed. The kernel
+    /// rejects bits 0, 1 and 2 (STP, MAC pause, LACP).
+    pub fn group_fwd_mask(mut self, mask: u16) -> Self {
+        self.group_fwd_mask = Some(mask);
+        self
+    }
+
+    /// Enable or disable IGMP/MLD snooping.
+    pub fn mcast_snooping(mut self, enabled: bool) -> Self {
+        self.mcast_snooping = Some(enabled);
+        self
+    }
+
+    /// Enable or disable sending IGMP/MLD queries from the bridge.
+    pub fn mcast_querier(mut self, enabled: bool) -> Self {
+        self.mcast_querier = Some(enabled);
+        self
+    }
+
+    /// Set the IGMP version of the bridge querier (2 or 3).
+    pub fn mcast_igmp_version(mut self, version: u8) -> Self {
+        self.mcast_igmp_version = Some(version);
+        self
+    }
+
+    /// Set the MLD version of the bridge querier (1 or 2).
+    pub fn mcast_mld_version(mut self, version: u8) -> Self {
+        self.mcast_mld_version = Some(version);
+        self
+    }
+
+    /// Pass bridged IPv4 traffic through iptables
+    /// (`net.bridge.bridge-nf-call-iptables` for this bridge only).
+    pub fn nf_call_iptables(mut self, enabled: bool) -> Self {
+        self.nf_call_iptables = Some(enabled);
+        self
+    }
+
+    /// Pass bridged IPv6 traffic through ip6tables.
+    pub fn nf_call_ip6tables(mut self, enabled: bool) -> Self {
+        self.nf_call_ip6tables = Some(enabled);
+        self
+    }
+
+    /// Pass bridged ARP traffic through arptables.
+    pub fn nf_call_arptables(mut self, enabled: bool) -> Self {
+        self.nf_call_arptables = Some(enabled);
+        self
+    }
 }
 
 impl LinkConfig for BridgeLink {
@@ -770,7 +949,16 @@ impl LinkConfig for BridgeLink {
             || self.stp_state.is_some()
             || self.priority.is_some()
             || self.vlan_filtering.is_some()
-            || self.vlan_default_pvid.is_some();
+            || self.vlan_default_pvid.is_some()
+            || self.vlan_protocol.is_some()
+            || self.group_fwd_mask.is_some()
+            || self.mcast_snooping.is_some()
+            || self.mcast_querier.is_some()
+            || self.mcast_igmp_version.is_some()
+            || self.mcast_mld_version.is_some()
+            || self.nf_call_iptables.is_some()
+            || self.nf_call_ip6tables.is_some()
+            || self.nf_call_arptables.is_some();
 
         if has_options {
             let data = builder.nest_start(IflaInfo::Data as u16);
@@ -799,6 +987,30 @@ impl LinkConfig for BridgeLink {
             if let Some(pvid) = self.vlan_default_pvid {
                 builder.append_attr_u16(bridge::IFLA_BR_VLAN_DEFAULT_PVID, pvid);
             }
+            if let Some(protocol) = self.vlan_protocol {
+                builder.append_attr_u16_be(bridge::IFLA_BR_VLAN_PROTOCOL, protocol.as_u16());
+            }
+            if let Some(mask) = self.group_fwd_mask {
+                builder.append_attr_u16(bridge::IFLA_BR_GROUP_FWD_MASK, mask);
+            }
+            let flags = [
+                (bridge::IFLA_BR_MCAST_SNOOPING, self.mcast_snooping),
+                (bridge::IFLA_BR_MCAST_QUERIER, self.mcast_querier),
+                (bridge::IFLA_BR_NF_CALL_IPTABLES, self.nf_call_iptables),
+                (bridge::IFLA_BR_NF_CALL_IP6TABLES, self.nf_call_ip6tables),
+                (bridge::IFLA_BR_NF_CALL_ARPTABLES, self.nf_call_arptables),
+            ];
+            for (attr, enabled) in flags {
+                if let Some(enabled) = enabled {
+                    builder.append_attr_u8(attr, u8::from(enabled));
+                }
+            }
+            if let Some(version) = self.mcast_igmp_version {
+                builder.append_attr_u8(bridge::IFLA_BR_MCAST_IGMP_VERSION, version);
+            }
+            if let Some(version) = self.mcast_mld_version {
+                builder.append_attr_u8(bridge::IFLA_BR_MCAST_MLD_VERSION, version);
+            }
 
             builder.nest_end(data);
         }
@@ -835,14 +1047,24 @@ pub struct VlanLink {
     /// Protocol: 0x8100 for 802.1Q, 0x88a8 for 802.1ad
     protocol: Option<u16>,
     flags: VlanFlags,
+    /// Ingress map entries: (802.1p PCP, skb priority)
+    ingress_qos: Vec<(u32, u32)>,
+    /// Egress map entries: (skb priority, 802.1p PCP)
+    egress_qos: Vec<(u32, u32)>,
 }
 
 /// VLAN-specific attributes (IFLA_VLAN_*)
 mod vlan {
     pub const IFLA_VLAN_ID: u16 = 1;
     pub const IFLA_VLAN_FLAGS: u16 = 2;
+    pub const IFLA_VLAN_EGRESS_QOS: u16 = 3;
+    pub const IFLA_VLAN_INGRESS_QOS: u16 = 4;
     pub const IFLA_VLAN_PROTOCOL: u16 = 5;
 
+    /// Entry inside the EGRESS/INGRESS_QOS nests
+    /// (`struct ifla_vlan_qos_mapping { u32 from; u32 to; }`)
+    pub const IFLA_VLAN_QOS_MAPPING: u16 = 1;
+
     /// VLAN flags
     pub const VLAN_FLAG_REORDER_HDR: u32 = 0x1;
     pub const VLAN_FLAG_GVRP: u32 = 0x2;
@@ -903,6 +1125,8 @@ impl VlanLink {
             address: None,
             protocol: None,
             flags: VlanFlags::default(),
+            ingress_qos: Vec::new(),
+            egress_qos: Vec::new(),
         }
     }
 
@@ -929,6 +1153,8 @@ impl VlanLink {
             address: None,
             protocol: None,
             flags: VlanFlags::default(),
+            ingress_qos: Vec::new(),
+            egress_qos: Vec::new(),
         }
     }
 
@@ -1002,6 +1228,39 @@ impl VlanLink {
         }
         self
     }
+
+    /// Map a received frame's 802.1p priority (PCP, 0-7) to an skb
+    /// priority (`ingress-qos-map PCP:PRIO`).
+    ///
+    /// Call once per mapping. The kernel masks `pcp` to 3 bits.
+    pub fn ingress_qos_map(mut self, pcp: u8, priority: u32) -> Self {
+        self.ingress_qos.push((u32::from(pcp), priority));
+        self
+    }
+
+    /// Map an outgoing skb priority to the 802.1p priority (PCP, 0-7)
+    /// written into the tag (`egress-qos-map PRIO:PCP`).
+    ///
+    /// Call once per mapping. The kernel masks `pcp` to 3 bits.
+    pub fn egress_qos_map(mut self, priority: u32, pcp: u8) -> Self {
+        self.egress_qos.push((priority, u32::from(pcp)));
+        self
+    }
+}
+
+/// Write an `IFLA_VLAN_{INGRESS,EGRESS}_QOS` nest of `(from, to)` mappings.
+fn write_vlan_qos_map(builder: &mut MessageBuilder, attr: u16, map: &[(u32, u32)]) {
+    if map.is_empty() {
+        return;
+    }
+    let nest = builder.nest_start(attr);
+    for &(from, to) in map {
+        let mut mapping = [0u8; 8];
+        mapping[..4].copy_from_slice(&from.to_ne_bytes());
+        mapping[4..].copy_from_slice(&to.to_ne_bytes());
+        builder.append_attr(vlan::IFLA_VLAN_QOS_MAPPING, &mapping);
+    }
+    builder.nest_end(nest);
 }
 
 impl LinkConfig for VlanLink {
@@ -1061,6 +1320,9 @@ impl LinkConfig for VlanLink {
             builder.append_attr(vlan::IFLA_VLAN_FLAGS, flags_bytes);
         }
 
+        write_vlan_qos_map(builder, vlan::IFLA_VLAN_INGRESS_QOS, &self.ingress_qos);
+        write_vlan_qos_map(builder, vlan::IFLA_VLAN_EGRESS_QOS, &self.egress_qos);
+
         builder.nest_end(data);
         builder.nest_end(linkinfo);
     }
@@ -3376,6 +3638,17 @@ pub enum ArpValidate {
     FilterBackup = 5,
 }
 
+/// Which ARP targets must answer for a slave to count as up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum ArpAllTargets {
+    /// Any one target answering is enough.
+    Any = 0,
+    /// Every target has to answer.
+    All = 1,
+}
+
 /// Ad (802.3ad) selection logic.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -3431,7 +3704,11 @@ mod bond_attr {
 
 /// Configuration for a bonding (link aggregation) interface.
 ///
-/// Supports all 33 kernel IFLA_BOND_* attributes with typed enums.
+/// Supports the kernel's IFLA_BOND_* attributes with typed enums. The
+/// primary and active slave name members, so they are set after
+/// enslaving with [`Connection::set_bond_primary`] and
+/// [`Connection::set_bond_active_slave`]; [`Connection::add_bond`]
+/// creates the bond and enslaves its members in one call.
 ///
 /// # Example
 ///
@@ -3454,6 +3731,11 @@ mod bond_attr {
 ///     .arp_ip_target(Ipv4Addr::new(192, 168, 1, 1))
 ///     .arp_validate(ArpValidate::All);
 /// conn.add_link(bond).await?;
+///
+/// // Create, enslave eth0/eth1 and prefer eth0
+/// conn.add_bond(BondLink::new("bond2").mode(BondMode::ActiveBackup).miimon(100), ["eth0", "eth1"])
+///     .await?;
+/// conn.set_bond_primary("bond2", "eth0").await?;
 /// ```
 #[derive(Debug, Clone)]
 #[must_use = "builders do nothing unless used"]
@@ -3473,7 +3755,8 @@ pub struct BondLink {
     arp_interval: Option<u32>,
     arp_ip_targets: Vec<Ipv4Addr>,
     arp_validate: Option<ArpValidate>,
-    arp_all_targets: Option<u32>,
+    arp_all_targets: Option<ArpAllTargets>,
+    ns_ip6_targets: Vec<Ipv6Addr>,
 
     // Slave selection
     primary_reselect: Option<PrimaryReselect>,
@@ -3519,6 +3802,7 @@ impl BondLink {
             arp_ip_targets: Vec::new(),
             arp_validate: None,
             arp_all_targets: None,
+            ns_ip6_targets: Vec::new(),
             primary_reselect: None,
             fail_over_mac: None,
             xmit_hash_policy: None,
@@ -3613,6 +3897,19 @@ impl BondLink {
         self
     }
 
+    /// Set which ARP targets must answer for a slave to be up.
+    pub fn arp_all_targets(mut self, targets: ArpAllTargets) -> Self {
+        self.arp_all_targets = Some(targets);
+        self
+    }
+
+    /// Add an IPv6 Neighbor Solicitation monitoring target (up to 16),
+    /// the IPv6 counterpart of [`arp_ip_target`](Self::arp_ip_target).
+    pub fn ns_ip6_target(mut self, addr: Ipv6Addr) -> Self {
+        self.ns_ip6_targets.push(addr);
+        self
+    }
+
     /// Set the primary slave reselection policy.
     pub fn primary_reselect(mut self, policy: PrimaryReselect) -> Self {
         self.primary_reselect = Some(policy);
@@ -3760,7 +4057,7 @@ impl LinkConfig for BondLink {
             builder.append_attr_u32(bond_attr::IFLA_BOND_ARP_VALIDATE, v as u32);
         }
         if let Some(v) = self.arp_all_targets {
-            builder.append_attr_u32(bond_attr::IFLA_BOND_ARP_ALL_TARGETS, v);
+            builder.append_attr_u32(bond_attr::IFLA_BOND_ARP_ALL_TARGETS, v as u32);
         }
         if let Some(v) = self.primary_reselect {
             builder.append_attr_u8(bond_attr::IFLA_BOND_PRIMARY_RESELECT, v as u8);
@@ -3829,6 +4126,15 @@ impl LinkConfig for BondLink {
             builder.nest_end(targets);
         }
 
+        // NS IPv6 targets (nested)
+        if !self.ns_ip6_targets.is_empty() {
+            let targets = builder.nest_start(bond_attr::IFLA_BOND_NS_IP6_TARGET);
+            for (i, addr) in self.ns_ip6_targets.iter().enumerate() {
+                builder.append_attr(i as u16, &addr.octets());
+            }
+            builder.nest_end(targets);
+        }
+
         builder.nest_end(data);
         builder.nest_end(linkinfo);
     }
@@ -3906,6 +4212,71 @@ impl LinkConfig for VrfLink {
     }
 }
 
+// ============================================================================
+// Team Link
+// ============================================================================
+
+/// Configuration for a team (libteam) link aggregation interface.
+///
+/// The kernel's `team` link kind takes no IFLA_INFO_DATA: runner,
+/// link watches and port options are set afterwards over the `team`
+/// generic netlink family, normally by `teamd`. This builder creates
+/// the device; ports are added like bond members, with
+/// [`Connection::enslave`].
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::link::TeamLink;
+///
+/// conn.add_link(TeamLink::new("team0").mtu(9000)).await?;
+/// conn.enslave("eth0", "team0").await?;
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct TeamLink {
+    name: String,
+    mtu: Option<u32>,
+    address: Option<[u8; 6]>,
+}
+
+impl TeamLink {
+    /// Create a new team interface configuration.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            mtu: None,
+            address: None,
+        }
+    }
+
+    /// Set the MTU for this interface.
+    pub fn mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+
+    /// Set the MAC address for this interface.
+    pub fn address(mut self, addr: [u8; 6]) -> Self {
+        self.address = Some(addr);
+        self
+    }
+}
+
+impl LinkConfig for TeamLink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &str {
+        "team"
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, _parent_index: Option<u32>) {
+        write_simple_link(builder, &self.name, "team", self.mtu, self.address.as_ref());
+    }
+}
+
 // ============================================================================
 // OVPN Link (kernel 6.16+)
 // ============================================================================
@@ -4688,6 +5059,8 @@ impl LinkConfig for SitLink {
 /// let wg_conn = Connection::<Wireguard>::new_async().await?;
 /// wg_conn.set_device("wg0", |dev| dev.private_key(key)).await?;
 /// ```
+///
+/// [`Connection::create_wireguard`] does both steps in one call.
 #[derive(Debug, Clone)]
 #[must_use = "builders do nothing unless used"]
 pub struct WireguardLink {
@@ -4726,132 +5099,541 @@ impl LinkConfig for WireguardLink {
 }
 
 // ============================================================================
-// MACsec Link
+// XFRM Interface
 // ============================================================================
 
-/// IFLA_MACSEC_* attribute IDs (kernel `include/uapi/linux/if_link.h`).
-/// We only emit the subset the builder exposes; `ICV_LEN`,
-/// `CIPHER_SUITE`, and `VALIDATION` are managed via the GENL
-/// `Connection::<Macsec>` API after creation.
-mod macsec {
-    pub const IFLA_MACSEC_SCI: u16 = 1;
-    pub const IFLA_MACSEC_PORT: u16 = 2;
-    pub const IFLA_MACSEC_WINDOW: u16 = 5;
-    pub const IFLA_MACSEC_ENCODING_SA: u16 = 6;
-    pub const IFLA_MACSEC_ENCRYPT: u16 = 7;
-    pub const IFLA_MACSEC_PROTECT: u16 = 8;
-    pub const IFLA_MACSEC_INC_SCI: u16 = 9;
-    pub const IFLA_MACSEC_ES: u16 = 10;
-    pub const IFLA_MACSEC_SCB: u16 = 11;
-    pub const IFLA_MACSEC_REPLAY_PROTECT: u16 = 12;
+/// IFLA_XFRM_* attribute constants.
+mod xfrmi_attr {
+    pub const IFLA_XFRM_LINK: u16 = 1;
+    pub const IFLA_XFRM_IF_ID: u16 = 2;
+    pub const IFLA_XFRM_COLLECT_METADATA: u16 = 3;
 }
 
-/// Configuration for a MACsec (IEEE 802.1AE) interface.
+/// Configuration for an XFRM interface (kind `xfrm`, Linux 4.19+).
 ///
-/// MACsec provides Layer-2 authenticated encryption for point-to-point
-/// links. The interface sits on top of a parent (typically a physical
-/// NIC or a veth in a lab). Key and SA management happens via the
-/// separate `Connection::<Macsec>` Generic-Netlink API — creating the
-/// interface is an rtnetlink operation, configuring its TX/RX SAs is
-/// GENL.
+/// Unlike VTI, an xfrm interface isn't keyed on tunnel endpoints: it
+/// carries every IPsec policy and state whose `if_id` matches, so
+/// one interface can serve many SAs and both address families.
 ///
 /// # Example
 ///
-/// ```no_run
-/// # async fn example() -> nlink::Result<()> {
-/// use nlink::netlink::{Connection, Route};
-/// use nlink::netlink::link::{DummyLink, MacsecLink};
-///
-/// let conn = Connection::<Route>::new()?;
-/// conn.add_link(DummyLink::new("dummy0")).await?;
-/// conn.set_link_up("dummy0").await?;
-///
-/// // Plain macsec interface — encrypt + protect enabled by default.
-/// conn.add_link(MacsecLink::new("macsec0", "dummy0")).await?;
+/// ```ignore
+/// use nlink::netlink::link::XfrmiLink;
 ///
-/// // With an explicit SCI + replay protection disabled (e.g. during
-/// // key rollover when you temporarily accept duplicates).
-/// conn.add_link(
-///     MacsecLink::new("macsec1", "dummy0")
-///         .sci(0x0011_2233_4455_0001)
-///         .replay_protect(false)
-/// ).await?;
-/// # Ok(())
-/// # }
+/// let xfrm = XfrmiLink::new("ipsec0", 42).link("eth0");
+/// conn.add_link(xfrm).await?;
 /// ```
-pub struct MacsecLink {
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct XfrmiLink {
     name: String,
-    parent: InterfaceRef,
+    if_id: u32,
+    link: Option<InterfaceRef>,
+    collect_metadata: bool,
     mtu: Option<u32>,
-    sci: Option<u64>,
-    port: Option<u16>,
-    encrypt: Option<bool>,
-    protect: Option<bool>,
-    inc_sci: Option<bool>,
-    end_station: Option<bool>,
-    scb: Option<bool>,
-    replay_protect: Option<bool>,
-    replay_window: Option<u32>,
-    encoding_sa: Option<u8>,
 }
 
-impl MacsecLink {
-    /// Create a new MACsec interface configuration sitting on top of
-    /// `parent` (a physical NIC or other Ethernet-kind interface).
-    pub fn new(name: impl Into<String>, parent: impl Into<String>) -> Self {
+impl XfrmiLink {
+    /// Create a new xfrm interface matching policies and states with
+    /// `if_id`.
+    pub fn new(name: impl Into<String>, if_id: u32) -> Self {
         Self {
             name: name.into(),
-            parent: InterfaceRef::Name(parent.into()),
+            if_id,
+            link: None,
+            collect_metadata: false,
             mtu: None,
-            sci: None,
-            port: None,
-            encrypt: None,
-            protect: None,
-            inc_sci: None,
-            end_station: None,
-            scb: None,
-            replay_protect: None,
-            replay_window: None,
-            encoding_sa: None,
         }
     }
 
-    /// Create a new MACsec interface with parent specified by index.
-    /// Use this when working across namespaces to avoid a `/sys` lookup.
-    pub fn with_parent_index(name: impl Into<String>, parent_index: u32) -> Self {
-        Self {
-            name: name.into(),
-            parent: InterfaceRef::Index(parent_index),
-            mtu: None,
-            sci: None,
-            port: None,
-            encrypt: None,
-            protect: None,
-            inc_sci: None,
-            end_station: None,
-            scb: None,
-            replay_protect: None,
-            replay_window: None,
-            encoding_sa: None,
-        }
+    /// Set the underlay interface by name.
+    pub fn link(mut self, iface: impl Into<String>) -> Self {
+        self.link = Some(InterfaceRef::Name(iface.into()));
+        self
     }
 
-    /// Set the MTU.
-    pub fn mtu(mut self, mtu: u32) -> Self {
-        self.mtu = Some(mtu);
+    /// Set the underlay interface by index.
+    pub fn link_index(mut self, index: u32) -> Self {
+        self.link = Some(InterfaceRef::Index(index));
         self
     }
 
-    /// Explicit Secure Channel Identifier. If omitted, the kernel
-    /// derives the SCI from the parent's MAC address + [`Self::port`]
-    /// (default port = 1).
-    pub fn sci(mut self, sci: u64) -> Self {
-        self.sci = Some(sci);
+    /// Create a metadata-mode (`external`) device that takes the
+    /// `if_id` from each packet's metadata instead (Linux 6.1+).
+    pub fn collect_metadata(mut self) -> Self {
+        self.collect_metadata = true;
         self
     }
 
-    /// Port component used when the kernel auto-derives the SCI
-    /// (default 1).
+    /// Set the MTU.
+    pub fn mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+}
+
+impl LinkConfig for XfrmiLink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &str {
+        "xfrm"
+    }
+
+    fn parent_ref(&self) -> Option<&InterfaceRef> {
+        self.link.as_ref()
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, parent_index: Option<u32>) {
+        write_ifname(builder, &self.name);
+
+        if let Some(mtu) = self.mtu {
+            builder.append_attr_u32(IflaAttr::Mtu as u16, mtu);
+        }
+
+        let linkinfo = builder.nest_start(IflaAttr::Linkinfo as u16);
+        builder.append_attr_str(IflaInfo::Kind as u16, "xfrm");
+
+        let data = builder.nest_start(IflaInfo::Data as u16);
+        builder.append_attr_u32(xfrmi_attr::IFLA_XFRM_IF_ID, self.if_id);
+        if let Some(idx) = parent_index {
+            builder.append_attr_u32(xfrmi_attr::IFLA_XFRM_LINK, idx);
+        }
+        if self.collect_metadata {
+            builder.append_attr(xfrmi_attr::IFLA_XFRM_COLLECT_METADATA, &[]);
+        }
+        builder.nest_end(data);
+
+        builder.nest_end(linkinfo);
+    }
+}
+
+// ============================================================================
+// GTP Link
+// ============================================================================
+
+/// IFLA_GTP_* attribute constants.
+mod gtp_attr {
+    pub const IFLA_GTP_FD0: u16 = 1;
+    pub const IFLA_GTP_FD1: u16 = 2;
+    pub const IFLA_GTP_PDP_HASHSIZE: u16 = 3;
+    pub const IFLA_GTP_ROLE: u16 = 4;
+    pub const IFLA_GTP_CREATE_SOCKETS: u16 = 5;
+    pub const IFLA_GTP_RESTART_COUNT: u16 = 6;
+}
+
+/// Which end of the GTP tunnel a [`GtpLink`] plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+#[non_exhaustive]
+pub enum GtpRole {
+    /// Gateway (GGSN / P-GW / UPF) — the kernel's default.
+    Ggsn = 0,
+    /// Serving node (SGSN / S-GW), e.g. for a test eNodeB.
+    Sgsn = 1,
+}
+
+/// Configuration for a GTP-U device (kind `gtp`).
+///
+/// The device encapsulates user-plane traffic for the PDP contexts
+/// installed on it over the `gtp` generic netlink family. The UDP
+/// sockets are normally handed over as file descriptors by a
+/// control-plane daemon ([`fd0`](Self::fd0) for GTPv0 on port 3386,
+/// [`fd1`](Self::fd1) for GTPv1-U on port 2152);
+/// [`create_sockets`](Self::create_sockets) asks the kernel to open them
+/// instead (Linux 6.2+).
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::link::{GtpLink, GtpRole};
+///
+/// let gtp = GtpLink::new("gtp0").role(GtpRole::Sgsn).create_sockets();
+/// conn.add_link(gtp).await?;
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct GtpLink {
+    name: String,
+    role: Option<GtpRole>,
+    hashsize: Option<u32>,
+    restart_count: Option<u8>,
+    fd0: Option<RawFd>,
+    fd1: Option<RawFd>,
+    create_sockets: bool,
+    mtu: Option<u32>,
+}
+
+impl GtpLink {
+    /// Create a new GTP device configuration.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            role: None,
+            hashsize: None,
+            restart_count: None,
+            fd0: None,
+            fd1: None,
+            create_sockets: false,
+            mtu: None,
+        }
+    }
+
+    /// Set the tunnel role (default [`GtpRole::Ggsn`]).
+    pub fn role(mut self, role: GtpRole) -> Self {
+        self.role = Some(role);
+        self
+    }
+
+    /// Set the PDP context hash table size (kernel default 1024).
+    pub fn hashsize(mut self, size: u32) -> Self {
+        self.hashsize = Some(size);
+        self
+    }
+
+    /// Set the GTP restart counter reported in echo responses.
+    pub fn restart_count(mut self, count: u8) -> Self {
+        self.restart_count = Some(count);
+        self
+    }
+
+    /// Use an already-bound UDP socket for GTPv0 (`IFLA_GTP_FD0`).
+    ///
+    /// The fd only has to stay open until the link is added; the kernel
+    /// takes its own reference to the socket.
+    pub fn fd0(mut self, fd: RawFd) -> Self {
+        self.fd0 = Some(fd);
+        self
+    }
+
+    /// Use an already-bound UDP socket for GTPv1-U (`IFLA_GTP_FD1`).
+    ///
+    /// See [`fd0`](Self::fd0).
+    pub fn fd1(mut self, fd: RawFd) -> Self {
+        self.fd1 = Some(fd);
+        self
+    }
+
+    /// Have the kernel create the GTP-U sockets itself (Linux 6.2+).
+    pub fn create_sockets(mut self) -> Self {
+        self.create_sockets = true;
+        self
+    }
+
+    /// Set the MTU.
+    pub fn mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+}
+
+impl LinkConfig for GtpLink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &str {
+        "gtp"
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, _parent_index: Option<u32>) {
+        write_ifname(builder, &self.name);
+
+        if let Some(mtu) = self.mtu {
+            builder.append_attr_u32(IflaAttr::Mtu as u16, mtu);
+        }
+
+        let linkinfo = builder.nest_start(IflaAttr::Linkinfo as u16);
+        builder.append_attr_str(IflaInfo::Kind as u16, "gtp");
+
+        let data = builder.nest_start(IflaInfo::Data as u16);
+        if let Some(fd) = self.fd0 {
+            builder.append_attr_u32(gtp_attr::IFLA_GTP_FD0, fd as u32);
+        }
+        if let Some(fd) = self.fd1 {
+            builder.append_attr_u32(gtp_attr::IFLA_GTP_FD1, fd as u32);
+        }
+        if let Some(role) = self.role {
+            builder.append_attr_u32(gtp_attr::IFLA_GTP_ROLE, role as u32);
+        }
+        if let Some(size) = self.hashsize {
+            builder.append_attr_u32(gtp_attr::IFLA_GTP_PDP_HASHSIZE, size);
+        }
+        if let Some(count) = self.restart_count {
+            builder.append_attr_u8(gtp_attr::IFLA_GTP_RESTART_COUNT, count);
+        }
+        if self.create_sockets {
+            builder.append_attr_u8(gtp_attr::IFLA_GTP_CREATE_SOCKETS, 1);
+        }
+        builder.nest_end(data);
+
+        builder.nest_end(linkinfo);
+    }
+}
+
+// ============================================================================
+// CAN Links
+// ============================================================================
+
+/// Configuration for a virtual CAN interface (kind `vcan`).
+///
+/// A vcan device loops CAN frames back to local sockets, the CAN
+/// equivalent of a dummy interface. Physical CAN controllers are not
+/// created this way; their bitrate is set on the existing device.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::link::VcanLink;
+///
+/// // MTU 72 enables CAN FD frames.
+/// conn.add_link(VcanLink::new("vcan0").mtu(72)).await?;
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct VcanLink {
+    name: String,
+    mtu: Option<u32>,
+}
+
+impl VcanLink {
+    /// Create a new virtual CAN interface configuration.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            mtu: None,
+        }
+    }
+
+    /// Set the MTU: 16 for classic CAN (the default), 72 for CAN FD.
+    pub fn mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+}
+
+impl LinkConfig for VcanLink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &str {
+        "vcan"
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, _parent_index: Option<u32>) {
+        write_simple_link(builder, &self.name, "vcan", self.mtu, None);
+    }
+}
+
+/// VXCAN-specific nested attributes.
+mod vxcan {
+    pub const VXCAN_INFO_PEER: u16 = 1;
+}
+
+/// Configuration for a virtual CAN tunnel pair (kind `vxcan`).
+///
+/// The CAN counterpart of a veth pair: frames sent on one end arrive
+/// on the other, typically to bridge CAN traffic into a namespace.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::link::VxcanLink;
+///
+/// conn.add_link(VxcanLink::new("vxcan0", "vxcan1")).await?;
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct VxcanLink {
+    name: String,
+    peer_name: String,
+    mtu: Option<u32>,
+}
+
+impl VxcanLink {
+    /// Create a new vxcan pair configuration.
+    pub fn new(name: impl Into<String>, peer_name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            peer_name: peer_name.into(),
+            mtu: None,
+        }
+    }
+
+    /// Set the MTU for both ends (72 for CAN FD).
+    pub fn mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+}
+
+impl LinkConfig for VxcanLink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> &str {
+        "vxcan"
+    }
+
+    fn peer_name(&self) -> Option<&str> {
+        Some(&self.peer_name)
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, _parent_index: Option<u32>) {
+        write_ifname(builder, &self.name);
+
+        if let Some(mtu) = self.mtu {
+            builder.append_attr_u32(IflaAttr::Mtu as u16, mtu);
+        }
+
+        let linkinfo = builder.nest_start(IflaAttr::Linkinfo as u16);
+        builder.append_attr_str(IflaInfo::Kind as u16, "vxcan");
+
+        // IFLA_INFO_DATA -> VXCAN_INFO_PEER -> ifinfomsg + peer attrs
+        let data = builder.nest_start(IflaInfo::Data as u16);
+        let peer = builder.nest_start(vxcan::VXCAN_INFO_PEER);
+        builder.append(&IfInfoMsg::new());
+        builder.append_attr_str(IflaAttr::Ifname as u16, &self.peer_name);
+        if let Some(mtu) = self.mtu {
+            builder.append_attr_u32(IflaAttr::Mtu as u16, mtu);
+        }
+        builder.nest_end(peer);
+        builder.nest_end(data);
+
+        builder.nest_end(linkinfo);
+    }
+}
+
+// ============================================================================
+// MACsec Link
+// ============================================================================
+
+/// IFLA_MACSEC_* attribute IDs (kernel `include/uapi/linux/if_link.h`).
+/// We only emit the subset the builder exposes; `ICV_LEN`,
+/// `CIPHER_SUITE`, and `VALIDATION` are managed via the GENL
+/// `Connection::<Macsec>` API after creation.
+mod macsec {
+    pub const IFLA_MACSEC_SCI: u16 = 1;
+    pub const IFLA_MACSEC_PORT: u16 = 2;
+    pub const IFLA_MACSEC_WINDOW: u16 = 5;
+    pub const IFLA_MACSEC_ENCODING_SA: u16 = 6;
+    pub const IFLA_MACSEC_ENCRYPT: u16 = 7;
+    pub const IFLA_MACSEC_PROTECT: u16 = 8;
+    pub const IFLA_MACSEC_INC_SCI: u16 = 9;
+    pub const IFLA_MACSEC_ES: u16 = 10;
+    pub const IFLA_MACSEC_SCB: u16 = 11;
+    pub const IFLA_MACSEC_REPLAY_PROTECT: u16 = 12;
+}
+
+/// Configuration for a MACsec (IEEE 802.1AE) interface.
+///
+/// MACsec provides Layer-2 authenticated encryption for point-to-point
+/// links. The interface sits on top of a parent (typically a physical
+/// NIC or a veth in a lab). Key and SA management happens via the
+/// separate `Connection::<Macsec>` Generic-Netlink API — creating the
+/// interface is an rtnetlink operation, configuring its TX/RX SAs is
+/// GENL.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> nlink::Result<()> {
+/// use nlink::netlink::{Connection, Route};
+/// use nlink::netlink::link::{DummyLink, MacsecLink};
+///
+/// let conn = Connection::<Route>::new()?;
+/// conn.add_link(DummyLink::new("dummy0")).await?;
+/// conn.set_link_up("dummy0").await?;
+///
+/// // Plain macsec interface — encrypt + protect enabled by default.
+/// conn.add_link(MacsecLink::new("macsec0", "dummy0")).await?;
+///
+/// // With an explicit SCI + replay protection disabled (e.g. during
+/// // key rollover when you temporarily accept duplicates).
+/// conn.add_link(
+///     MacsecLink::new("macsec1", "dummy0")
+///         .sci(0x0011_2233_4455_0001)
+///         .replay_protect(false)
+/// ).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct MacsecLink {
+    name: String,
+    parent: InterfaceRef,
+    mtu: Option<u32>,
+    sci: Option<u64>,
+    port: Option<u16>,
+    encrypt: Option<bool>,
+    protect: Option<bool>,
+    inc_sci: Option<bool>,
+    end_station: Option<bool>,
+    scb: Option<bool>,
+    replay_protect: Option<bool>,
+    replay_window: Option<u32>,
+    encoding_sa: Option<u8>,
+}
+
+impl MacsecLink {
+    /// Create a new MACsec interface configuration sitting on top of
+    /// `parent` (a physical NIC or other Ethernet-kind interface).
+    pub fn new(name: impl Into<String>, parent: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            parent: InterfaceRef::Name(parent.into()),
+            mtu: None,
+            sci: None,
+            port: None,
+            encrypt: None,
+            protect: None,
+            inc_sci: None,
+            end_station: None,
+            scb: None,
+            replay_protect: None,
+            replay_window: None,
+            encoding_sa: None,
+        }
+    }
+
+    /// Create a new MACsec interface with parent specified by index.
+    /// Use this when working across namespaces to avoid a `/sys` lookup.
+    pub fn with_parent_index(name: impl Into<String>, parent_index: u32) -> Self {
+        Self {
+            name: name.into(),
+            parent: InterfaceRef::Index(parent_index),
+            mtu: None,
+            sci: None,
+            port: None,
+            encrypt: None,
+            protect: None,
+            inc_sci: None,
+            end_station: None,
+            scb: None,
+            replay_protect: None,
+            replay_window: None,
+            encoding_sa: None,
+        }
+    }
+
+    /// Set the MTU.
+    pub fn mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+
+    /// Explicit Secure Channel Identifier. If omitted, the kernel
+    /// derives the SCI from the parent's MAC address + [`Self::port`]
+    /// (default port = 1).
+    pub fn sci(mut self, sci: u64) -> Self {
+        self.sci = Some(sci);
+        self
+    }
+
+    /// Port component used when the kernel auto-derives the SCI
+    /// (default 1).
     pub fn port(mut self, port: u16) -> Self {
         self.port = Some(port);
         self
@@ -5005,6 +5787,109 @@ fn write_simple_link(
     builder.nest_end(linkinfo);
 }
 
+// ============================================================================
+// Link Tuning
+// ============================================================================
+
+/// Runtime settings applied to an existing interface in one
+/// `RTM_SETLINK`, see [`Connection::tune_link`].
+///
+/// Only the settings that were set are sent.
+#[derive(Debug, Clone, Default)]
+#[must_use = "builders do nothing unless used"]
+pub struct LinkTuning {
+    mtu: Option<u32>,
+    txqueuelen: Option<u32>,
+    group: Option<u32>,
+    gso_max_size: Option<u32>,
+    gso_max_segs: Option<u32>,
+    gro_max_size: Option<u32>,
+    gso_ipv4_max_size: Option<u32>,
+    gro_ipv4_max_size: Option<u32>,
+}
+
+impl LinkTuning {
+    /// Create an empty tuning.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the MTU (`IFLA_MTU`).
+    pub fn mtu(mut self, mtu: u32) -> Self {
+        self.mtu = Some(mtu);
+        self
+    }
+
+    /// Set the transmit queue length (`IFLA_TXQLEN`).
+    pub fn txqueuelen(mut self, txqueuelen: u32) -> Self {
+        self.txqueuelen = Some(txqueuelen);
+        self
+    }
+
+    /// Set the device group (`IFLA_GROUP`).
+    pub fn group(mut self, group: u32) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    /// Set the largest GSO packet the stack builds for this device
+    /// (`IFLA_GSO_MAX_SIZE`). Above 65536 enables BIG TCP for IPv6.
+    pub fn gso_max_size(mut self, size: u32) -> Self {
+        self.gso_max_size = Some(size);
+        self
+    }
+
+    /// Set the most segments in one GSO packet (`IFLA_GSO_MAX_SEGS`).
+    pub fn gso_max_segs(mut self, segs: u32) -> Self {
+        self.gso_max_segs = Some(segs);
+        self
+    }
+
+    /// Set the largest packet GRO aggregates (`IFLA_GRO_MAX_SIZE`).
+    pub fn gro_max_size(mut self, size: u32) -> Self {
+        self.gro_max_size = Some(size);
+        self
+    }
+
+    /// Set the IPv4 GSO limit (`IFLA_GSO_IPV4_MAX_SIZE`, Linux 6.3+).
+    pub fn gso_ipv4_max_size(mut self, size: u32) -> Self {
+        self.gso_ipv4_max_size = Some(size);
+        self
+    }
+
+    /// Set the IPv4 GRO limit (`IFLA_GRO_IPV4_MAX_SIZE`, Linux 6.3+).
+    pub fn gro_ipv4_max_size(mut self, size: u32) -> Self {
+        self.gro_ipv4_max_size = Some(size);
+        self
+    }
+
+    /// Whether nothing is set.
+    pub fn is_empty(&self) -> bool {
+        self.attrs().next().is_none()
+    }
+
+    fn attrs(&self) -> impl Iterator<Item = (IflaAttr, u32)> {
+        [
+            (IflaAttr::Mtu, self.mtu),
+            (IflaAttr::TxqLen, self.txqueuelen),
+            (IflaAttr::Group, self.group),
+            (IflaAttr::GsoMaxSize, self.gso_max_size),
+            (IflaAttr::GsoMaxSegs, self.gso_max_segs),
+            (IflaAttr::GroMaxSize, self.gro_max_size),
+            (IflaAttr::GsoIpv4MaxSize, self.gso_ipv4_max_size),
+            (IflaAttr::GroIpv4MaxSize, self.gro_ipv4_max_size),
+        ]
+        .into_iter()
+        .filter_map(|(attr, value)| value.map(|v| (attr, v)))
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder) {
+        for (attr, value) in self.attrs() {
+            builder.append_attr_u32(attr as u16, value);
+        }
+    }
+}
+
 // ============================================================================
 // Connection Methods
 // ============================================================================
@@ -5059,11 +5944,100 @@ impl Connection<Route> {
         let link_kind = config.kind().to_string();
         config.write_to(&mut builder, parent_index);
 
+        // The names belong to the new interface from now on.
+        self.forget_ifname(&link_name);
+        if let Some(peer) = config.peer_name() {
+            self.forget_ifname(peer);
+        }
         self.send_ack(builder)
             .await
             .map_err(|e| e.with_context(format!("add_link({link_name}, kind={link_kind})")))
     }
 
+    /// Create an interface, add addresses to it and bring it up.
+    ///
+    /// The new interface's index is taken from the kernel's
+    /// `RTM_NEWLINK` notification, and every later step works by that
+    /// index. A udev rename or a name reused after a delete can't send
+    /// the addresses to the wrong device, and there is no polling for
+    /// the name to appear in a dump. MTU and MAC come from `config`
+    /// itself, as with [`add_link`](Self::add_link).
+    ///
+    /// The wait for the notification is bounded by the connection's
+    /// [timeout](Self::get_timeout). Returns the link as it is after
+    /// being brought up.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::link::DummyLink;
+    ///
+    /// let link = conn
+    ///     .create_link_and_up(
+    ///         DummyLink::new("svc0").mtu(9000),
+    ///         &[("10.0.0.1".parse()?, 24), ("fd00::1".parse()?, 64)],
+    ///     )
+    ///     .await?;
+    /// println!("svc0 is ifindex {}", link.ifindex());
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "create_link_and_up"))]
+    pub async fn create_link_and_up<L: LinkConfig>(
+        &self,
+        config: L,
+        addresses: &[(IpAddr, u8)],
+    ) -> Result<LinkMessage> {
+        use tokio_stream::StreamExt;
+
+        use super::{connection::RtnetlinkGroup, events::NetworkEvent};
+
+        let name = config.name().to_string();
+
+        // Listen before creating, in this connection's namespace, so the
+        // notification can't be missed.
+        let watcher = self.event_connection()?;
+        watcher.subscribe(&[RtnetlinkGroup::Link])?;
+        let mut events = watcher.events().await;
+
+        self.add_link(config).await?;
+
+        let created = async {
+            while let Some(event) = events.try_next().await? {
+                if let NetworkEvent::NewLink(link) = event
+                    && link.name() == Some(name.as_str())
+                {
+                    return Ok(link.ifindex());
+                }
+            }
+            Err(Error::InvalidMessage(format!(
+                "create_link_and_up({name}): link event stream ended"
+            )))
+        };
+        let ifindex = match self.get_timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, created)
+                .await
+                .map_err(|_| Error::Timeout)??,
+            None => created.await?,
+        };
+        drop(events);
+
+        for &(address, prefix_len) in addresses {
+            self.add_address_by_index(ifindex, address, prefix_len)
+                .await
+                .map_err(|e| {
+                    e.with_context(format!(
+                        "create_link_and_up({name}): add {address}/{prefix_len}"
+                    ))
+                })?;
+        }
+        self.set_link_up_by_index(ifindex)
+            .await
+            .map_err(|e| e.with_context(format!("create_link_and_up({name}): set up")))?;
+
+        self.get_link_by_index(ifindex)
+            .await?
+            .ok_or(Error::InterfaceNotFound { name })
+    }
+
     /// Set the master (controller) device for an interface.
     ///
     /// This is used to add an interface to a bridge or bond.
@@ -5132,6 +6106,162 @@ impl Connection<Route> {
         self.set_link_up_by_index(member_idx).await
     }
 
+    /// Create a bond and enslave `members` to it, in order.
+    ///
+    /// Each member goes through the same down/master/up sequence as
+    /// [`enslave`](Self::enslave). The bond itself is left down. If a
+    /// member can't be enslaved the bond and the members already added
+    /// are kept; the error names the failing member.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::link::{BondLink, BondMode};
+    ///
+    /// conn.add_bond(BondLink::new("bond0").mode(BondMode::Lacp).miimon(100), ["eth0", "eth1"])
+    ///     .await?;
+    /// conn.set_link_up("bond0").await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "add_bond"))]
+    pub async fn add_bond<I>(&self, bond: BondLink, members: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Into<InterfaceRef>,
+    {
+        let name = bond.name.clone();
+        self.add_link(bond).await?;
+        let master_idx = self
+            .resolve_interface(&InterfaceRef::Name(name.clone()))
+            .await?;
+        for member in members {
+            let member = member.into();
+            let member_idx = self.resolve_interface(&member).await?;
+            self.enslave_by_index(member_idx, master_idx)
+                .await
+                .map_err(|e| e.with_context(format!("add_bond({name}): enslave {member}")))?;
+        }
+        Ok(())
+    }
+
+    /// Set the primary slave of a bond (active-backup, balance-tlb and
+    /// balance-alb modes).
+    ///
+    /// `member` must already be enslaved to `bond`.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_bond_primary"))]
+    pub async fn set_bond_primary(
+        &self,
+        bond: impl Into<InterfaceRef>,
+        member: impl Into<InterfaceRef>,
+    ) -> Result<()> {
+        let bond_idx = self.resolve_interface(&bond.into()).await?;
+        let member_idx = self.resolve_interface(&member.into()).await?;
+        self.set_bond_slave_option(bond_idx, bond_attr::IFLA_BOND_PRIMARY, member_idx)
+            .await
+    }
+
+    /// Make `member` the active slave of an active-backup, balance-tlb or
+    /// balance-alb bond.
+    ///
+    /// The member must be enslaved and have link; the kernel rejects
+    /// the change otherwise.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_bond_active_slave"))]
+    pub async fn set_bond_active_slave(
+        &self,
+        bond: impl Into<InterfaceRef>,
+        member: impl Into<InterfaceRef>,
+    ) -> Result<()> {
+        let bond_idx = self.resolve_interface(&bond.into()).await?;
+        let member_idx = self.resolve_interface(&member.into()).await?;
+        self.set_bond_slave_option(bond_idx, bond_attr::IFLA_BOND_ACTIVE_SLAVE, member_idx)
+            .await
+    }
+
+    /// Change one ifindex-valued IFLA_BOND_* option on an existing bond.
+    async fn set_bond_slave_option(&self, bond_idx: u32, attr: u16, member_idx: u32) -> Result<()> {
+        use super::connection::ack_request;
+
+        let ifinfo = IfInfoMsg::new().with_index(bond_idx as i32);
+
+        let mut builder = ack_request(NlMsgType::RTM_NEWLINK);
+        builder.append(&ifinfo);
+        let linkinfo = builder.nest_start(IflaAttr::Linkinfo as u16);
+        builder.append_attr_str(IflaInfo::Kind as u16, "bond");
+        let data = builder.nest_start(IflaInfo::Data as u16);
+        builder.append_attr_u32(attr, member_idx);
+        builder.nest_end(data);
+        builder.nest_end(linkinfo);
+
+        self.send_ack(builder).await
+    }
+
+    /// Create a WireGuard interface and configure it in one call.
+    ///
+    /// Adds the link, then opens a WireGuard GENL connection in this
+    /// connection's network namespace and applies the device built by
+    /// `configure` (keys, listen port, fwmark, peers) with
+    /// [`WireguardConfig::apply`](super::genl::wireguard::WireguardConfig::apply).
+    /// If the configuration can't be applied the link is deleted again,
+    /// so a failed call leaves no half-configured interface behind. The
+    /// interface is left down.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::genl::wireguard::AllowedIp;
+    /// use nlink::netlink::link::WireguardLink;
+    ///
+    /// conn.create_wireguard(WireguardLink::new("wg0").mtu(1420), |d| {
+    ///     d.private_key(private_key).listen_port(51820).peer(peer_key, |p| {
+    ///         p.endpoint("203.0.113.1:51820".parse().unwrap())
+    ///             .allowed_ip(AllowedIp::v4(Ipv4Addr::new(10, 0, 0, 0), 24))
+    ///     })
+    /// })
+    /// .await?;
+    /// conn.set_link_up("wg0").await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "create_wireguard"))]
+    pub async fn create_wireguard(
+        &self,
+        link: WireguardLink,
+        configure: impl FnOnce(
+            super::genl::wireguard::DeclaredWgDeviceBuilder,
+        ) -> super::genl::wireguard::DeclaredWgDeviceBuilder,
+    ) -> Result<()> {
+        let name = link.name.clone();
+        let config = super::genl::wireguard::WireguardConfig::new().device(&name, configure);
+        self.add_link(link).await?;
+
+        let applied = match self.wireguard_alongside().await {
+            Ok(wg) => config.apply(&wg).await.map(drop),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = applied {
+            if let Err(del) = self.del_link(name.as_str()).await {
+                tracing::warn!(error = %del, "create_wireguard({name}): rollback failed");
+            }
+            return Err(e.with_context(format!("create_wireguard({name})")));
+        }
+        Ok(())
+    }
+
+    /// Open a WireGuard GENL connection in the network namespace this
+    /// connection's socket lives in.
+    async fn wireguard_alongside(&self) -> Result<Connection<super::protocol::Wireguard>> {
+        use std::os::fd::AsRawFd;
+
+        // Same EPERM fallback as `event_connection`.
+        let ns = match self.namespace_fd() {
+            Ok(ns) => ns,
+            Err(Error::Io(e)) if e.raw_os_error() == Some(libc::EPERM) => {
+                return Connection::new_async().await;
+            }
+            Err(e) => return Err(e),
+        };
+        // `ns` stays open until the socket has been created.
+        let path = format!("/proc/self/fd/{}", ns.as_raw_fd());
+        super::namespace::connection_for_path_async(path).await
+    }
+
     /// Enslave an interface to a bond or bridge by index.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "enslave_by_index"))]
     pub async fn enslave_by_index(&self, member_index: u32, master_index: u32) -> Result<()> {
@@ -5153,8 +6283,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_nomaster"))]
     pub async fn set_link_nomaster(&self, iface: impl Into<InterfaceRef>) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_nomaster_by_index(ifindex).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_nomaster_by_index(ifindex)
+        })
+        .await
     }
 
     /// Remove an interface from its master by index.
@@ -5194,8 +6326,10 @@ impl Connection<Route> {
         iface: impl Into<InterfaceRef>,
         config: BridgePortConfig,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_bridge_port_by_index(ifindex, config).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_bridge_port_by_index(ifindex, config.clone())
+        })
+        .await
     }
 
     /// Configure bridge-port options on an enslaved interface, by index.
@@ -5231,6 +6365,57 @@ impl Connection<Route> {
             .map_err(|e| e.with_context("set_bridge_port"))
     }
 
+    /// Apply several runtime settings to an interface in one request.
+    ///
+    /// Accepts either an interface name or index via [`InterfaceRef`].
+    /// Replaces a chain of `set_link_mtu`/`set_link_txqlen` calls (one
+    /// round trip each) with a single `RTM_SETLINK`. An empty tuning
+    /// sends nothing.
+    ///
+    /// The kernel applies the settings in turn and stops at the first
+    /// it rejects, so settings it reached before then stay applied.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// conn.tune_link("eth0", |t| {
+    ///     t.mtu(9000).txqueuelen(10000).gso_max_size(65536)
+    /// })
+    /// .await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "tune_link"))]
+    pub async fn tune_link(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        tune: impl FnOnce(LinkTuning) -> LinkTuning,
+    ) -> Result<()> {
+        let ifindex = self.resolve_interface(&iface.into()).await?;
+        self.tune_link_by_index(ifindex, tune).await
+    }
+
+    /// Apply several runtime settings to an interface by index.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "tune_link_by_index"))]
+    pub async fn tune_link_by_index(
+        &self,
+        ifindex: u32,
+        tune: impl FnOnce(LinkTuning) -> LinkTuning,
+    ) -> Result<()> {
+        use super::connection::ack_request;
+
+        let tuning = tune(LinkTuning::new());
+        if tuning.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder = ack_request(NlMsgType::RTM_SETLINK);
+        builder.append(&IfInfoMsg::new().with_index(ifindex as i32));
+        tuning.write_to(&mut builder);
+
+        self.send_ack(builder)
+            .await
+            .map_err(|e| e.with_context("tune_link"))
+    }
+
     /// Rename a network interface.
     ///
     /// Accepts either an interface name or index via [`InterfaceRef`].
@@ -5247,8 +6432,10 @@ impl Connection<Route> {
         iface: impl Into<InterfaceRef>,
         new_name: &str,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_name_by_index(ifindex, new_name).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_name_by_index(ifindex, new_name)
+        })
+        .await
     }
 
     /// Rename a network interface by index.
@@ -5265,6 +6452,7 @@ impl Connection<Route> {
         builder.append(&ifinfo);
         builder.append_attr_str(IflaAttr::Ifname as u16, new_name);
 
+        self.forget_ifindex(ifindex);
         self.send_ack(builder)
             .await
             .map_err(|e| e.with_context("set_link_name"))
@@ -5285,8 +6473,10 @@ impl Connection<Route> {
         iface: impl Into<InterfaceRef>,
         address: [u8; 6],
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_address_by_index(ifindex, address).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_address_by_index(ifindex, address)
+        })
+        .await
     }
 
     /// Set the MAC address by interface index.
@@ -5321,8 +6511,10 @@ impl Connection<Route> {
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_netns_pid"))]
     pub async fn set_link_netns_pid(&self, iface: impl Into<InterfaceRef>, pid: u32) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_netns_pid_by_index(ifindex, pid).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_netns_pid_by_index(ifindex, pid)
+        })
+        .await
     }
 
     /// Move a network interface to a namespace by PID (by index).
@@ -5340,6 +6532,7 @@ impl Connection<Route> {
         builder.append(&ifinfo);
         builder.append_attr_u32(IflaAttr::NetNsPid as u16, pid);
 
+        self.forget_ifindex(ifindex);
         self.send_ack(builder)
             .await
             .map_err(|e| e.with_context("set_link_netns"))
@@ -5350,8 +6543,10 @@ impl Connection<Route> {
     /// Accepts either an interface name or index via [`InterfaceRef`].
     #[tracing::instrument(level = "debug", skip_all, fields(method = "set_link_netns_fd"))]
     pub async fn set_link_netns_fd(&self, iface: impl Into<InterfaceRef>, fd: i32) -> Result<()> {
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_netns_fd_by_index(ifindex, fd).await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_netns_fd_by_index(ifindex, fd)
+        })
+        .await
     }
 
     /// Move a network interface to a namespace by fd (by index).
@@ -5369,6 +6564,7 @@ impl Connection<Route> {
         builder.append(&ifinfo);
         builder.append_attr_u32(IflaAttr::NetNsFd as u16, fd as u32);
 
+        self.forget_ifindex(ifindex);
         self.send_ack(builder)
             .await
             .map_err(|e| e.with_context("set_link_netns"))
@@ -5391,9 +6587,10 @@ impl Connection<Route> {
         ns_name: &str,
     ) -> Result<()> {
         let ns_fd = super::namespace::open(ns_name)?;
-        let ifindex = self.resolve_interface(&iface.into()).await?;
-        self.set_link_netns_fd_by_index(ifindex, ns_fd.as_raw_fd())
-            .await
+        self.with_interface(&iface.into(), |ifindex| {
+            self.set_link_netns_fd_by_index(ifindex, ns_fd.as_raw_fd())
+        })
+        .await
     }
 
     /// Move a network interface to a named network namespace (by index).
@@ -5510,4 +6707,278 @@ mod tests {
             IFLA_BRPORT_ISOLATED,
         );
     }
+
+    #[test]
+    fn bridge_port_config_writes_evpn_flags() {
+        use super::super::{attr::AttrIter, types::link::brport::*};
+
+        let cfg = BridgePortConfig::new()
+            .vlan_tunnel(true)
+            .neigh_suppress(true)
+            .neigh_vlan_suppress(false);
+        assert!(!cfg.is_empty());
+
+        let mut builder = MessageBuilder::new(0, 0);
+        cfg.write_protinfo(&mut builder);
+        let bytes = builder.as_bytes();
+
+        // Skip the nlmsghdr (16) and the IFLA_PROTINFO nest header (4).
+        let attrs: Vec<_> = AttrIter::new(&bytes[16 + 4..])
+            .map(|(kind, payload)| (kind, payload.to_vec()))
+            .collect();
+        assert_eq!(
+            attrs,
+            vec![
+                (IFLA_BRPORT_NEIGH_SUPPRESS, vec![1]),
+                (IFLA_BRPORT_NEIGH_VLAN_SUPPRESS, vec![0]),
+                (IFLA_BRPORT_VLAN_TUNNEL, vec![1]),
+            ]
+        );
+    }
+
+    #[test]
+    fn vlan_link_writes_qos_maps() {
+        use super::super::attr::AttrIter;
+
+        /// Find a nested attribute by type.
+        fn find(data: &[u8], kind: u16) -> &[u8] {
+            AttrIter::new(data)
+                .find(|(k, _)| k & 0x3fff == kind)
+                .map(|(_, payload)| payload)
+                .unwrap()
+        }
+
+        fn mappings(nest: &[u8]) -> Vec<(u32, u32)> {
+            AttrIter::new(nest)
+                .map(|(kind, payload)| {
+                    assert_eq!(kind, vlan::IFLA_VLAN_QOS_MAPPING);
+                    (
+                        u32::from_ne_bytes(payload[..4].try_into().unwrap()),
+                        u32::from_ne_bytes(payload[4..8].try_into().unwrap()),
+                    )
+                })
+                .collect()
+        }
+
+        let link = VlanLink::new("eth0.100", "eth0", 100)
+            .ingress_qos_map(5, 2)
+            .ingress_qos_map(6, 3)
+            .egress_qos_map(4, 7);
+
+        let mut builder = MessageBuilder::new(0, 0);
+        link.write_to(&mut builder, Some(2));
+        let bytes = builder.as_bytes();
+
+        let linkinfo = find(&bytes[16..], IflaAttr::Linkinfo as u16);
+        let data = find(linkinfo, IflaInfo::Data as u16);
+        assert_eq!(
+            mappings(find(data, vlan::IFLA_VLAN_INGRESS_QOS)),
+            vec![(5, 2), (6, 3)]
+        );
+        assert_eq!(
+            mappings(find(data, vlan::IFLA_VLAN_EGRESS_QOS)),
+            vec![(4, 7)]
+        );
+
+        // No maps configured: neither nest is emitted.
+        let mut builder = MessageBuilder::new(0, 0);
+        VlanLink::new("eth0.100", "eth0", 100).write_to(&mut builder, Some(2));
+        let bytes = builder.as_bytes();
+        let linkinfo = find(&bytes[16..], IflaAttr::Linkinfo as u16);
+        let data = find(linkinfo, IflaInfo::Data as u16);
+        assert!(AttrIter::new(data).all(|(k, _)| {
+            k != vlan::IFLA_VLAN_INGRESS_QOS && k != vlan::IFLA_VLAN_EGRESS_QOS
+        }));
+    }
+
+    #[test]
+    fn team_link_writes_kind_without_data() {
+        use super::super::attr::AttrIter;
+
+        let mut builder = MessageBuilder::new(0, 0);
+        TeamLink::new("team0")
+            .mtu(9000)
+            .write_to(&mut builder, None);
+        let bytes = builder.as_bytes();
+
+        let attrs: Vec<_> = AttrIter::new(&bytes[16..]).collect();
+        let mtu = attrs
+            .iter()
+            .find(|(k, _)| *k == IflaAttr::Mtu as u16)
+            .unwrap();
+        assert_eq!(mtu.1, 9000u32.to_ne_bytes());
+        let linkinfo = attrs
+            .iter()
+            .find(|(k, _)| k & 0x3fff == IflaAttr::Linkinfo as u16)
+            .unwrap()
+            .1;
+        let info: Vec<_> = AttrIter::new(linkinfo).collect();
+        assert_eq!(info.len(), 1);
+        assert_eq!(info[0].0, IflaInfo::Kind as u16);
+        assert_eq!(info[0].1, b"team\0");
+    }
+
+    #[test]
+    fn bond_link_writes_monitor_targets() {
+        use super::super::attr::AttrIter;
+
+        fn find(data: &[u8], kind: u16) -> &[u8] {
+            AttrIter::new(data)
+                .find(|(k, _)| k & 0x3fff == kind)
+                .map(|(_, payload)| payload)
+                .unwrap()
+        }
+
+        let link = BondLink::new("bond0")
+            .mode(BondMode::ActiveBackup)
+            .arp_interval(100)
+            .arp_ip_target(Ipv4Addr::new(192, 0, 2, 1))
+            .arp_all_targets(ArpAllTargets::All)
+            .ns_ip6_target("2001:db8::1".parse().unwrap())
+            .ns_ip6_target("2001:db8::2".parse().unwrap());
+
+        let mut builder = MessageBuilder::new(0, 0);
+        link.write_to(&mut builder, None);
+        let bytes = builder.as_bytes();
+
+        let linkinfo = find(&bytes[16..], IflaAttr::Linkinfo as u16);
+        let data = find(linkinfo, IflaInfo::Data as u16);
+        assert_eq!(find(data, bond_attr::IFLA_BOND_MODE), [1]);
+        assert_eq!(
+            find(data, bond_attr::IFLA_BOND_ARP_ALL_TARGETS),
+            1u32.to_ne_bytes()
+        );
+        let ns: Vec<Ipv6Addr> = AttrIter::new(find(data, bond_attr::IFLA_BOND_NS_IP6_TARGET))
+            .map(|(_, payload)| <[u8; 16]>::try_from(payload).unwrap().into())
+            .collect();
+        assert_eq!(
+            ns,
+            vec![
+                "2001:db8::1".parse::<Ipv6Addr>().unwrap(),
+                "2001:db8::2".parse().unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn xfrmi_gtp_and_vxcan_links_write_info_data() {
+        use super::super::attr::AttrIter;
+
+        fn find(data: &[u8], kind: u16) -> &[u8] {
+            AttrIter::new(data)
+                .find(|(k, _)| k & 0x3fff == kind)
+                .map(|(_, payload)| payload)
+                .unwrap()
+        }
+        fn info_data(link: &dyn LinkConfig, parent: Option<u32>) -> Vec<u8> {
+            let mut builder = MessageBuilder::new(0, 0);
+            link.write_to(&mut builder, parent);
+            let bytes = builder.as_bytes();
+            let linkinfo = find(&bytes[16..], IflaAttr::Linkinfo as u16);
+            find(linkinfo, IflaInfo::Data as u16).to_vec()
+        }
+
+        let data = info_data(&XfrmiLink::new("ipsec0", 42).link("eth0"), Some(7));
+        assert_eq!(
+            find(&data, xfrmi_attr::IFLA_XFRM_IF_ID),
+            42u32.to_ne_bytes()
+        );
+        assert_eq!(find(&data, xfrmi_attr::IFLA_XFRM_LINK), 7u32.to_ne_bytes());
+
+        let gtp = GtpLink::new("gtp0")
+            .role(GtpRole::Sgsn)
+            .hashsize(2048)
+            .create_sockets();
+        let data = info_data(&gtp, None);
+        assert_eq!(find(&data, gtp_attr::IFLA_GTP_ROLE), 1u32.to_ne_bytes());
+        assert_eq!(
+            find(&data, gtp_attr::IFLA_GTP_PDP_HASHSIZE),
+            2048u32.to_ne_bytes()
+        );
+        assert_eq!(find(&data, gtp_attr::IFLA_GTP_CREATE_SOCKETS), [1]);
+
+        let data = info_data(&GtpLink::new("gtp0").fd0(5).fd1(6), None);
+        assert_eq!(find(&data, gtp_attr::IFLA_GTP_FD0), 5u32.to_ne_bytes());
+        assert_eq!(find(&data, gtp_attr::IFLA_GTP_FD1), 6u32.to_ne_bytes());
+
+        let data = info_data(&VxcanLink::new("vxcan0", "vxcan1"), None);
+        let peer = find(&data, vxcan::VXCAN_INFO_PEER);
+        let peer_attrs = &peer[std::mem::size_of::<IfInfoMsg>()..];
+        assert_eq!(find(peer_attrs, IflaAttr::Ifname as u16), b"vxcan1\0");
+    }
+
+    #[test]
+    fn link_tuning_writes_only_set_attributes() {
+        use super::super::attr::AttrIter;
+
+        assert!(LinkTuning::new().is_empty());
+
+        let tuning = LinkTuning::new()
+            .mtu(9000)
+            .txqueuelen(10000)
+            .gso_max_size(65536);
+        let mut builder = MessageBuilder::new(0, 0);
+        tuning.write_to(&mut builder);
+        let attrs: Vec<_> = AttrIter::new(&builder.as_bytes()[16..])
+            .map(|(kind, payload)| (kind, u32::from_ne_bytes(payload.try_into().unwrap())))
+            .collect();
+        assert_eq!(
+            attrs,
+            vec![
+                (IflaAttr::Mtu as u16, 9000),
+                (IflaAttr::TxqLen as u16, 10000),
+                (IflaAttr::GsoMaxSize as u16, 65536),
+            ]
+        );
+    }
+
+    #[test]
+    fn bridge_link_writes_multicast_and_vlan_protocol() {
+        use super::super::attr::AttrIter;
+
+        fn find(data: &[u8], kind: u16) -> Option<&[u8]> {
+            AttrIter::new(data)
+                .find(|(k, _)| k & 0x3fff == kind)
+                .map(|(_, payload)| payload)
+        }
+
+        let link = BridgeLink::new("br0")
+            .vlan_filtering(true)
+            .vlan_protocol(VlanProtocol::Dot1ad)
+            .group_fwd_mask(0x4000)
+            .mcast_snooping(true)
+            .mcast_querier(true)
+            .mcast_igmp_version(3)
+            .mcast_mld_version(2)
+            .nf_call_iptables(false);
+
+        let mut builder = MessageBuilder::new(0, 0);
+        link.write_to(&mut builder, None);
+        let bytes = builder.as_bytes();
+
+        let linkinfo = find(&bytes[16..], IflaAttr::Linkinfo as u16).unwrap();
+        let data = find(linkinfo, IflaInfo::Data as u16).unwrap();
+        assert_eq!(
+            find(data, bridge::IFLA_BR_VLAN_PROTOCOL),
+            Some(&0x88a8u16.to_be_bytes()[..])
+        );
+        assert_eq!(
+            find(data, bridge::IFLA_BR_GROUP_FWD_MASK),
+            Some(&0x4000u16.to_ne_bytes()[..])
+        );
+        assert_eq!(find(data, bridge::IFLA_BR_MCAST_SNOOPING), Some(&[1][..]));
+        assert_eq!(find(data, bridge::IFLA_BR_MCAST_QUERIER), Some(&[1][..]));
+        assert_eq!(
+            find(data, bridge::IFLA_BR_MCAST_IGMP_VERSION),
+            Some(&[3][..])
+        );
+        assert_eq!(
+            find(data, bridge::IFLA_BR_MCAST_MLD_VERSION),
+            Some(&[2][..])
+        );
+        assert_eq!(find(data, bridge::IFLA_BR_NF_CALL_IPTABLES), Some(&[0][..]));
+        // Unset options are left to the kernel defaults.
+        assert_eq!(find(data, bridge::IFLA_BR_NF_CALL_IP6TABLES), None);
+        assert_eq!(find(data, bridge::IFLA_BR_STP_STATE), None);
+    }
 }
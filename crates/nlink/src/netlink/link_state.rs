@@ -0,0 +1,259 @@
+//! Link operational state transitions.
+//!
+//! `RTM_NEWLINK` notifications arrive for any change to a link — MTU,
+//! statistics, master, address — and carry the full state each time.
+//! [`LinkStateTracker`] reduces them to the changes that matter for
+//! reachability: admin up/down, carrier and `IFLA_OPERSTATE`. Each
+//! [`LinkStateChange`] says why the link is down, if it is, so
+//! provisioning code can tell an interface someone set down from a
+//! cable that was pulled.
+//!
+//! [`LinkStateMonitor`] wraps the tracker around an event stream; to
+//! wait for one interface use
+//! [`Connection::wait_for_carrier`](super::Connection::wait_for_carrier).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//! use nlink::netlink::link_state::LinkStateMonitor;
+//! use tokio_stream::StreamExt;
+//!
+//! let mut monitor = LinkStateMonitor::new(Connection::<Route>::new()?).await?;
+//! while let Some(change) = monitor.next().await {
+//!     let change = change?;
+//!     match change.reason {
+//!         None => println!("{} is up", change.name),
+//!         Some(reason) => println!("{} is down: {}", change.name, reason),
+//!     }
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tokio_stream::Stream;
+
+use super::{
+    connection::{Connection, RtnetlinkGroup},
+    error::Result,
+    events::NetworkEvent,
+    messages::{LinkDownReason, LinkMessage},
+    protocol::Route,
+    stream::OwnedEventStream,
+    types::link::OperState,
+};
+
+/// The reachability-relevant part of a link's state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LinkState {
+    /// `IFF_UP` is set.
+    pub admin_up: bool,
+    /// `IFLA_CARRIER`, if reported.
+    pub carrier: Option<bool>,
+    /// `IFLA_OPERSTATE`, if reported.
+    pub operstate: Option<OperState>,
+    /// Why the link isn't operational; `None` if it is.
+    pub reason: Option<LinkDownReason>,
+}
+
+impl LinkState {
+    /// Extract the state from a link message.
+    pub fn of(link: &LinkMessage) -> Self {
+        Self {
+            admin_up: link.is_up(),
+            carrier: link.carrier(),
+            operstate: link.operstate(),
+            reason: link.down_reason(),
+        }
+    }
+}
+
+/// A change of a link's admin, carrier or operational state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LinkStateChange {
+    /// Interface index.
+    pub ifindex: u32,
+    /// Interface name, or `if<index>` if the message carried none.
+    pub name: String,
+    /// State before the change; `None` the first time the link is seen.
+    pub previous: Option<LinkState>,
+    /// State after the change; `None` when the link was deleted.
+    pub current: Option<LinkState>,
+    /// Why the link is down now, `None` if it is operational or was
+    /// deleted.
+    pub reason: Option<LinkDownReason>,
+    /// `IFLA_CARRIER_UP_COUNT` from the message, if reported.
+    pub carrier_up_count: Option<u32>,
+    /// `IFLA_CARRIER_DOWN_COUNT` from the message, if reported.
+    pub carrier_down_count: Option<u32>,
+}
+
+impl LinkStateChange {
+    /// Whether the link became operational with this change.
+    pub fn went_up(&self) -> bool {
+        self.current.is_some_and(|c| c.reason.is_none())
+            && self.previous.is_none_or(|p| p.reason.is_some())
+    }
+
+    /// Whether the link stopped being operational with this change.
+    pub fn went_down(&self) -> bool {
+        self.previous.is_some_and(|p| p.reason.is_none())
+            && self.current.is_none_or(|c| c.reason.is_some())
+    }
+}
+
+/// Turns link events into admin/carrier/operstate changes.
+///
+/// Feed it every link message, in order; it has no I/O of its own.
+#[derive(Debug, Default)]
+pub struct LinkStateTracker {
+    links: HashMap<u32, LinkState>,
+}
+
+impl LinkStateTracker {
+    /// Create an empty tracker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an `RTM_NEWLINK` (`deleted == false`) or `RTM_DELLINK`
+    /// message. Returns the change, or `None` if the message left the
+    /// admin, carrier and operational state as they were.
+    pub fn observe(&mut self, link: &LinkMessage, deleted: bool) -> Option<LinkStateChange> {
+        let ifindex = link.ifindex();
+        let current = (!deleted).then(|| LinkState::of(link));
+        let previous = match current {
+            Some(state) => self.links.insert(ifindex, state),
+            None => self.links.remove(&ifindex),
+        };
+        if previous == current {
+            return None;
+        }
+        Some(LinkStateChange {
+            ifindex,
+            name: link
+                .name()
+                .map_or_else(|| format!("if{ifindex}"), str::to_string),
+            previous,
+            current,
+            reason: current.and_then(|c| c.reason),
+            carrier_up_count: link.carrier_up_count(),
+            carrier_down_count: link.carrier_down_count(),
+        })
+    }
+
+    /// Last known state of a link.
+    pub fn get(&self, ifindex: u32) -> Option<&LinkState> {
+        self.links.get(&ifindex)
+    }
+}
+
+/// Stream of link state changes.
+#[must_use = "streams do nothing unless polled"]
+pub struct LinkStateMonitor {
+    events: OwnedEventStream<Route>,
+    tracker: LinkStateTracker,
+}
+
+impl LinkStateMonitor {
+    /// Subscribe `conn` to link events and start tracking.
+    ///
+    /// Links are learnt from their first event, so the first change of
+    /// each link has `previous: None`.
+    pub async fn new(conn: Connection<Route>) -> Result<Self> {
+        conn.subscribe(&[RtnetlinkGroup::Link])?;
+        Ok(Self {
+            events: conn.into_events().await,
+            tracker: LinkStateTracker::new(),
+        })
+    }
+
+    /// Borrow the tracker, e.g. to look up a link's last state.
+    pub fn tracker(&self) -> &LinkStateTracker {
+        &self.tracker
+    }
+}
+
+impl Stream for LinkStateMonitor {
+    type Item = Result<LinkStateChange>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let event = match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            let (link, deleted) = match &event {
+                NetworkEvent::NewLink(link) => (link, false),
+                NetworkEvent::DelLink(link) => (link, true),
+                _ => continue,
+            };
+            if let Some(change) = self.tracker.observe(link, deleted) {
+                return Poll::Ready(Some(Ok(change)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::messages::LinkMessageBuilder;
+
+    const IFF_UP: u32 = 0x1;
+
+    fn link(flags: u32, operstate: OperState, carrier: bool) -> LinkMessage {
+        let mut msg = LinkMessageBuilder::new()
+            .ifindex(3)
+            .name("eth0")
+            .flags(flags)
+            .build();
+        msg.operstate = Some(operstate);
+        msg.carrier = Some(carrier);
+        msg
+    }
+
+    #[test]
+    fn tracker_reports_state_changes_with_reason() {
+        let mut tracker = LinkStateTracker::new();
+
+        let change = tracker
+            .observe(&link(0, OperState::Down, false), false)
+            .unwrap();
+        assert_eq!(change.reason, Some(LinkDownReason::AdminDown));
+        assert!(!change.went_up());
+
+        // Set up, still no cable.
+        let change = tracker
+            .observe(&link(IFF_UP, OperState::Down, false), false)
+            .unwrap();
+        assert_eq!(change.reason, Some(LinkDownReason::NoCarrier));
+        // Same state again (e.g. a stats or MTU update): no change.
+        assert!(
+            tracker
+                .observe(&link(IFF_UP, OperState::Down, false), false)
+                .is_none()
+        );
+
+        let change = tracker
+            .observe(&link(IFF_UP, OperState::Up, true), false)
+            .unwrap();
+        assert!(change.went_up());
+        assert_eq!(change.reason, None);
+
+        let change = tracker
+            .observe(&link(IFF_UP, OperState::Up, true), true)
+            .unwrap();
+        assert!(change.went_down());
+        assert_eq!(change.current, None);
+        assert!(tracker.get(3).is_none());
+    }
+}
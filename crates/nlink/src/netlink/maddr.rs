@@ -0,0 +1,165 @@
+//! Static link-layer multicast addresses (`ip maddress add/del`).
+//!
+//! The kernel has no netlink request for joining an L2 multicast group:
+//! `RTM_NEWMULTICAST` / `RTM_DELMULTICAST` are notifications only, and
+//! rtnetlink refuses them as requests with `EOPNOTSUPP`. Like iproute2,
+//! these helpers therefore use the `SIOCADDMULTI` / `SIOCDELMULTI`
+//! ioctls. They are issued on the connection's own netlink socket, so the
+//! change lands in the connection's network namespace even when it was
+//! opened with [`Connection::new_in_namespace`].
+//!
+//! Entries added this way show up in `/proc/net/dev_mcast` with a
+//! non-zero global use count. Adding an address that is already a static
+//! entry is a no-op.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//!
+//! let conn = Connection::<Route>::new()?;
+//! let group = [0x01, 0x00, 0x5e, 0x01, 0x02, 0x03];
+//!
+//! conn.add_link_multicast("eth0", group).await?;
+//! conn.del_link_multicast("eth0", group).await?;
+//! ```
+
+use std::{io, mem::MaybeUninit, os::unix::io::AsRawFd};
+
+use super::{
+    connection::Connection,
+    error::{Error, Result},
+    interface_ref::InterfaceRef,
+    protocol::Route,
+};
+use crate::util::{addr::format_mac, ifname};
+
+impl Connection<Route> {
+    /// Join a link-layer multicast group on an interface.
+    ///
+    /// Accepts either an interface name or index via [`InterfaceRef`].
+    /// `addr` must have the group bit set (e.g. `01:00:5e:..` or
+    /// `33:33:..`).
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "add_link_multicast"))]
+    pub async fn add_link_multicast(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        addr: [u8; 6],
+    ) -> Result<()> {
+        self.link_multicast(
+            iface.into(),
+            addr,
+            libc::SIOCADDMULTI as _,
+            "add_link_multicast",
+        )
+        .await
+    }
+
+    /// Leave a link-layer multicast group previously joined with
+    /// [`add_link_multicast`](Self::add_link_multicast).
+    ///
+    /// Fails with `ENOENT` if the address is not a static entry.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "del_link_multicast"))]
+    pub async fn del_link_multicast(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        addr: [u8; 6],
+    ) -> Result<()> {
+        self.link_multicast(
+            iface.into(),
+            addr,
+            libc::SIOCDELMULTI as _,
+            "del_link_multicast",
+        )
+        .await
+    }
+
+    async fn link_multicast(
+        &self,
+        iface: InterfaceRef,
+        addr: [u8; 6],
+        request: libc::Ioctl,
+        operation: &str,
+    ) -> Result<()> {
+        if addr[0] & 0x01 == 0 {
+            return Err(Error::InvalidMessage(format!(
+                "{} is not a multicast address",
+                format_mac(&addr)
+            )));
+        }
+
+        // The ioctl addresses the device by name; resolve an index over
+        // netlink so the name is the one in the connection's namespace.
+        let name = match iface {
+            InterfaceRef::Name(name) => name,
+            InterfaceRef::Index(ifindex) => self
+                .get_link_by_index(ifindex)
+                .await?
+                .and_then(|link| link.name().map(str::to_string))
+                .ok_or_else(|| Error::InterfaceNotFound {
+                    name: format!("ifindex {ifindex}"),
+                })?,
+        };
+        let ifr = multicast_ifreq(&name, addr)?;
+
+        // SAFETY: a plain ioctl on a socket fd we hold a borrow of; `ifr` is
+        // a fully initialised `ifreq` that outlives the call.
+        let ret = unsafe { libc::ioctl(self.socket().as_raw_fd(), request, &ifr) };
+        if ret < 0 {
+            let errno = io::Error::last_os_error().raw_os_error().unwrap_or(0);
+            if errno == libc::ENODEV {
+                return Err(Error::InterfaceNotFound { name });
+            }
+            return Err(Error::from_errno_with_context(
+                errno,
+                format!("{operation}({name}, {})", format_mac(&addr)),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Build the `ifreq` for `SIOC{ADD,DEL}MULTI`: the device name plus an
+/// `AF_UNSPEC` hardware address holding the group.
+fn multicast_ifreq(name: &str, addr: [u8; 6]) -> Result<libc::ifreq> {
+    ifname::validate(name)?;
+
+    // SAFETY: ifreq is plain old data; all-zero is a valid value.
+    let mut ifr = unsafe { MaybeUninit::<libc::ifreq>::zeroed().assume_init() };
+    for (dst, src) in ifr.ifr_name.iter_mut().zip(name.bytes()) {
+        *dst = src as libc::c_char;
+    }
+    // SAFETY: writing the `ifru_hwaddr` member of a zeroed union.
+    let hwaddr = unsafe { &mut ifr.ifr_ifru.ifru_hwaddr };
+    hwaddr.sa_family = libc::AF_UNSPEC as libc::sa_family_t;
+    for (dst, src) in hwaddr.sa_data.iter_mut().zip(addr) {
+        *dst = src as libc::c_char;
+    }
+    Ok(ifr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ifreq_carries_name_and_group() {
+        let group = [0x01, 0x00, 0x5e, 0x01, 0x02, 0x03];
+        let ifr = multicast_ifreq("eth0", group).unwrap();
+
+        let name: Vec<u8> = ifr.ifr_name.iter().map(|&c| c as u8).collect();
+        assert_eq!(&name[..5], b"eth0\0");
+        // SAFETY: multicast_ifreq initialised the hwaddr member.
+        let hwaddr = unsafe { ifr.ifr_ifru.ifru_hwaddr };
+        assert_eq!(hwaddr.sa_family, libc::AF_UNSPEC as libc::sa_family_t);
+        let data: Vec<u8> = hwaddr.sa_data[..6].iter().map(|&c| c as u8).collect();
+        assert_eq!(data, group);
+    }
+
+    #[test]
+    fn ifreq_rejects_bad_names() {
+        let group = [0x01, 0x00, 0x5e, 0x01, 0x02, 0x03];
+        assert!(multicast_ifreq("a-name-that-is-too-long", group).is_err());
+        assert!(multicast_ifreq("eth0:1", group).is_err());
+    }
+}
@@ -164,6 +164,16 @@ impl NlMsgType {
     pub const RTM_DELNEIGH: u16 = 29;
     pub const RTM_GETNEIGH: u16 = 30;
 
+    // Neighbor table messages
+    pub const RTM_NEWNEIGHTBL: u16 = 64;
+    pub const RTM_GETNEIGHTBL: u16 = 66;
+    pub const RTM_SETNEIGHTBL: u16 = 67;
+
+    // IPv6 address label messages
+    pub const RTM_NEWADDRLABEL: u16 = 72;
+    pub const RTM_DELADDRLABEL: u16 = 73;
+    pub const RTM_GETADDRLABEL: u16 = 74;
+
     // Rule messages
     pub const RTM_NEWRULE: u16 = 32;
     pub const RTM_DELRULE: u16 = 33;
@@ -189,6 +199,11 @@ impl NlMsgType {
     pub const RTM_DELACTION: u16 = 49;
     pub const RTM_GETACTION: u16 = 50;
 
+    // Netconf messages
+    pub const RTM_NEWNETCONF: u16 = 80;
+    pub const RTM_DELNETCONF: u16 = 81;
+    pub const RTM_GETNETCONF: u16 = 82;
+
     // Bridge multicast database (MDB) messages
     pub const RTM_NEWMDB: u16 = 84;
     pub const RTM_DELMDB: u16 = 85;
@@ -209,6 +224,11 @@ impl NlMsgType {
     pub const RTM_DELNEXTHOP: u16 = 105;
     pub const RTM_GETNEXTHOP: u16 = 106;
 
+    // Resilient nexthop group buckets (Linux 5.13+)
+    pub const RTM_NEWNEXTHOPBUCKET: u16 = 116;
+    pub const RTM_DELNEXTHOPBUCKET: u16 = 117;
+    pub const RTM_GETNEXTHOPBUCKET: u16 = 118;
+
     // Bridge VLAN-DB messages (Linux 5.10+) — per-VLAN entries and
     // bridge-global VLAN options over `struct br_vlan_msg`.
     pub const RTM_NEWVLAN: u16 = 112;
@@ -0,0 +1,141 @@
+//! Strongly-typed IPv6 address label message (`RTM_NEWADDRLABEL`).
+//!
+//! Address labels are the RFC 6724 policy table used for source and
+//! destination address selection: each entry maps a prefix (optionally
+//! bound to one device) to a label.
+
+use std::net::Ipv6Addr;
+
+use winnow::{prelude::*, token::take};
+
+use crate::netlink::{
+    attr::AttrIter,
+    parse::{FromNetlink, PResult},
+    types::addr::{IfAddrLblMsg, ifal},
+};
+
+/// AF_INET6, the only family address labels exist for.
+const AF_INET6: u8 = 10;
+
+/// Strongly-typed address label message with all attributes parsed.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct AddrLabelMessage {
+    /// Fixed-size header.
+    pub(crate) header: IfAddrLblMsg,
+    /// Prefix address (IFAL_ADDRESS).
+    pub(crate) address: Option<Ipv6Addr>,
+    /// Label (IFAL_LABEL).
+    pub(crate) label: Option<u32>,
+}
+
+impl AddrLabelMessage {
+    /// Create a new empty address label message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the address family (always AF_INET6 from the kernel).
+    pub fn family(&self) -> u8 {
+        self.header.ifal_family
+    }
+
+    /// Get the prefix address.
+    pub fn address(&self) -> Option<Ipv6Addr> {
+        self.address
+    }
+
+    /// Get the prefix length.
+    pub fn prefix_len(&self) -> u8 {
+        self.header.ifal_prefixlen
+    }
+
+    /// Get the device the entry is bound to; `None` if it applies to all.
+    pub fn ifindex(&self) -> Option<u32> {
+        (self.header.ifal_index != 0).then_some(self.header.ifal_index)
+    }
+
+    /// Get the label.
+    pub fn label(&self) -> Option<u32> {
+        self.label
+    }
+
+    /// Get the label table sequence number the entry was dumped at.
+    pub fn seq(&self) -> u32 {
+        self.header.ifal_seq
+    }
+}
+
+impl FromNetlink for AddrLabelMessage {
+    fn write_dump_header(buf: &mut Vec<u8>) {
+        // Address labels are only registered for PF_INET6
+        buf.extend_from_slice(IfAddrLblMsg::new().with_family(AF_INET6).as_bytes());
+    }
+
+    fn parse(input: &mut &[u8]) -> PResult<Self> {
+        if input.len() < IfAddrLblMsg::SIZE {
+            return Err(winnow::error::ErrMode::Cut(
+                winnow::error::ContextError::new(),
+            ));
+        }
+
+        let header_bytes: &[u8] = take(IfAddrLblMsg::SIZE).parse_next(input)?;
+        let header = *IfAddrLblMsg::from_bytes(header_bytes)
+            .map_err(|_| winnow::error::ErrMode::Cut(winnow::error::ContextError::new()))?;
+
+        let mut msg = AddrLabelMessage {
+            header,
+            ..Default::default()
+        };
+
+        let attrs: &[u8] = take(input.len()).parse_next(input)?;
+        for (attr_type, payload) in AttrIter::new(attrs) {
+            match attr_type {
+                ifal::ADDRESS => {
+                    msg.address = <[u8; 16]>::try_from(payload).ok().map(Ipv6Addr::from);
+                }
+                ifal::LABEL if payload.len() >= 4 => {
+                    msg.label = Some(u32::from_ne_bytes(payload[..4].try_into().unwrap()));
+                }
+                _ => {} // Ignore unknown attributes
+            }
+        }
+
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::builder::MessageBuilder;
+
+    #[test]
+    fn parses_address_label() {
+        let mut b = MessageBuilder::new(0, 0);
+        b.append(
+            &IfAddrLblMsg::new()
+                .with_family(AF_INET6)
+                .with_prefixlen(32)
+                .with_index(3),
+        );
+        let prefix: Ipv6Addr = "2001:db8::".parse().unwrap();
+        b.append_attr(ifal::ADDRESS, &prefix.octets());
+        b.append_attr_u32(ifal::LABEL, 100);
+
+        let bytes = b.finish();
+        let msg = AddrLabelMessage::from_bytes(&bytes[16..]).unwrap();
+        assert_eq!(msg.family(), AF_INET6);
+        assert_eq!(msg.address(), Some(prefix));
+        assert_eq!(msg.prefix_len(), 32);
+        assert_eq!(msg.ifindex(), Some(3));
+        assert_eq!(msg.label(), Some(100));
+
+        let mut b = MessageBuilder::new(0, 0);
+        b.append(&IfAddrLblMsg::new().with_family(AF_INET6));
+        let bytes = b.finish();
+        let msg = AddrLabelMessage::from_bytes(&bytes[16..]).unwrap();
+        assert_eq!(msg.ifindex(), None, "ifindex 0 applies to every device");
+        assert_eq!(msg.address(), None);
+    }
+}
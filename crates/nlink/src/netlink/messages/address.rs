@@ -19,6 +19,7 @@ mod attr_ids {
     pub const IFA_ANYCAST: u16 = 5;
     pub const IFA_CACHEINFO: u16 = 6;
     pub const IFA_FLAGS: u16 = 8;
+    pub const IFA_PROTO: u16 = 11;
 }
 
 /// Strongly-typed address message with all attributes parsed.
@@ -41,6 +42,59 @@ pub struct AddressMessage {
     pub(crate) flags: Option<u32>,
     /// Cache info (IFA_CACHEINFO) - preferred/valid lifetimes.
     pub(crate) cache_info: Option<AddressCacheInfo>,
+    /// Originator (IFA_PROTO, kernel 6.3+).
+    pub(crate) protocol: Option<u8>,
+}
+
+/// Where an address most likely came from.
+///
+/// Returned by [`AddressMessage::origin`]. Newer kernels tag the
+/// addresses they create (`IFA_PROTO`); otherwise the origin is
+/// inferred from flags, lifetimes and the address itself, which tells
+/// configured addresses from leased ones but can't tell who leased them
+/// with certainty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AddressOrigin {
+    /// Loopback address the kernel adds to `lo`.
+    KernelLo,
+    /// IPv6 link-local address the kernel derives for every link.
+    KernelLl,
+    /// IPv6 address autoconfigured from a router advertisement,
+    /// including temporary (privacy) addresses.
+    Slaac,
+    /// Address with a finite lifetime, most likely leased by a DHCP or
+    /// DHCPv6 client.
+    Dhcp,
+    /// Address configured without a lifetime, by an administrator or a
+    /// network manager.
+    Static,
+}
+
+impl AddressOrigin {
+    /// Short name, as shown by `ip -d addr`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::KernelLo => "kernel_lo",
+            Self::KernelLl => "kernel_ll",
+            Self::Slaac => "slaac",
+            Self::Dhcp => "dhcp",
+            Self::Static => "static",
+        }
+    }
+
+    /// Whether the address is maintained by the kernel or a client
+    /// daemon rather than configured, and so doesn't belong in a
+    /// captured configuration.
+    pub fn is_dynamic(&self) -> bool {
+        !matches!(self, Self::Static)
+    }
+}
+
+impl std::fmt::Display for AddressOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 /// Address cache information.
@@ -121,6 +175,60 @@ impl AddressMessage {
         self.cache_info.as_ref()
     }
 
+    /// Get the address protocol (`IFA_PROTO`): who created the address.
+    ///
+    /// The kernel sets 1 (`kernel_lo`), 2 (`kernel_ra`) or 3
+    /// (`kernel_ll`) on the addresses it adds; userspace may set its own
+    /// values. `None` on kernels before 6.3 and for untagged addresses.
+    pub fn protocol(&self) -> Option<u8> {
+        self.protocol
+    }
+
+    /// Classify where the address most likely came from.
+    ///
+    /// Uses `IFA_PROTO` when the kernel reports it, then falls back to:
+    /// loopback addresses are [`KernelLo`](AddressOrigin::KernelLo);
+    /// permanent IPv6 link-local ones are
+    /// [`KernelLl`](AddressOrigin::KernelLl); anything with an infinite
+    /// valid lifetime is [`Static`](AddressOrigin::Static); of the rest,
+    /// IPv6 `/128`s are DHCPv6 leases, other IPv6 addresses are SLAAC and
+    /// IPv4 addresses are DHCP leases.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Keep only what an administrator configured
+    /// let configured: Vec<_> = conn
+    ///     .get_addresses()
+    ///     .await?
+    ///     .into_iter()
+    ///     .filter(|a| !a.origin().is_dynamic())
+    ///     .collect();
+    /// ```
+    pub fn origin(&self) -> AddressOrigin {
+        match self.protocol {
+            Some(1) => return AddressOrigin::KernelLo,
+            Some(2) => return AddressOrigin::Slaac,
+            Some(3) => return AddressOrigin::KernelLl,
+            _ => {}
+        }
+
+        let addr = self.primary_address();
+        if addr.is_some_and(IpAddr::is_loopback) {
+            return AddressOrigin::KernelLo;
+        }
+        let permanent = self.is_permanent() || self.cache_info.is_some_and(|c| c.valid == u32::MAX);
+        match addr {
+            Some(IpAddr::V6(a)) if a.is_unicast_link_local() && permanent => {
+                AddressOrigin::KernelLl
+            }
+            _ if permanent => AddressOrigin::Static,
+            Some(IpAddr::V6(_)) if self.prefix_len() == 128 => AddressOrigin::Dhcp,
+            Some(IpAddr::V6(_)) => AddressOrigin::Slaac,
+            _ => AddressOrigin::Dhcp,
+        }
+    }
+
     /// Get the primary address (local or address).
     ///
     /// For point-to-point links, `local` is the local address and `address` is
@@ -162,6 +270,11 @@ impl AddressMessage {
     pub fn is_tentative(&self) -> bool {
         self.flags() & 0x40 != 0 // IFA_F_TENTATIVE
     }
+
+    /// Check if IPv6 duplicate address detection failed for this address.
+    pub fn is_dad_failed(&self) -> bool {
+        self.flags() & 0x08 != 0 // IFA_F_DADFAILED
+    }
 }
 
 impl FromNetlink for AddressMessage {
@@ -243,6 +356,9 @@ impl FromNetlink for AddressMessage {
                     let bytes: [u8; 4] = attr_data[..4].try_into().unwrap();
                     msg.flags = Some(u32::from_ne_bytes(bytes));
                 }
+                attr_ids::IFA_PROTO => {
+                    msg.protocol = attr_data.first().copied();
+                }
                 attr_ids::IFA_CACHEINFO => {
                     if let Some(info) = IfaCacheinfo::from_bytes(attr_data) {
                         msg.cache_info = Some(AddressCacheInfo {
@@ -436,6 +552,18 @@ impl AddressMessageBuilder {
         self
     }
 
+    /// Set the cache info (lifetimes).
+    pub fn cache_info(mut self, info: AddressCacheInfo) -> Self {
+        self.msg.cache_info = Some(info);
+        self
+    }
+
+    /// Set the address protocol (`IFA_PROTO`).
+    pub fn protocol(mut self, protocol: u8) -> Self {
+        self.msg.protocol = Some(protocol);
+        self
+    }
+
     /// Build the message.
     pub fn build(self) -> AddressMessage {
         self.msg
@@ -480,4 +608,82 @@ mod tests {
         assert_eq!(parsed.prefix_len(), original.prefix_len());
         assert_eq!(parsed.address, original.address);
     }
+
+    #[test]
+    fn test_origin_classification() {
+        let v6 = |a: &str, len| {
+            AddressMessageBuilder::new()
+                .ipv6()
+                .prefix_len(len)
+                .address(a.parse().unwrap())
+        };
+        let v4 = |a: &str| {
+            AddressMessageBuilder::new()
+                .ipv4()
+                .prefix_len(24)
+                .address(a.parse().unwrap())
+        };
+        let leased = AddressCacheInfo {
+            preferred: 1800,
+            valid: 3600,
+            ..Default::default()
+        };
+        // IFA_F_PERMANENT
+        let permanent = 0x80;
+
+        // IFA_PROTO wins over the heuristics.
+        let ra = v6("2001:db8::1", 64).flags(permanent).protocol(2).build();
+        assert_eq!(ra.origin(), AddressOrigin::Slaac);
+
+        assert_eq!(
+            v4("127.0.0.1").flags(permanent).build().origin(),
+            AddressOrigin::KernelLo
+        );
+        assert_eq!(
+            v6("fe80::1", 64).flags(permanent).build().origin(),
+            AddressOrigin::KernelLl
+        );
+        assert_eq!(
+            v4("192.0.2.10").flags(permanent).build().origin(),
+            AddressOrigin::Static
+        );
+        assert_eq!(
+            v4("192.0.2.10").cache_info(leased).build().origin(),
+            AddressOrigin::Dhcp
+        );
+        assert_eq!(
+            v6("2001:db8::1", 64).cache_info(leased).build().origin(),
+            AddressOrigin::Slaac
+        );
+        assert_eq!(
+            v6("2001:db8::1", 128).cache_info(leased).build().origin(),
+            AddressOrigin::Dhcp
+        );
+
+        // An infinite valid lifetime without the flag is still static.
+        let forever = AddressCacheInfo {
+            valid: u32::MAX,
+            ..leased
+        };
+        let addr = v4("192.0.2.10").cache_info(forever).build();
+        assert_eq!(addr.origin(), AddressOrigin::Static);
+        assert!(!addr.origin().is_dynamic());
+    }
+
+    #[test]
+    fn test_parse_ifa_proto() {
+        let mut bytes = AddressMessageBuilder::new()
+            .ipv6()
+            .ifindex(2)
+            .address("fe80::1".parse().unwrap())
+            .build()
+            .to_bytes()
+            .unwrap();
+        // IFA_PROTO = IFAPROT_KERNEL_LL
+        bytes.extend_from_slice(&[0x05, 0x00, 0x0b, 0x00, 0x03, 0x00, 0x00, 0x00]);
+
+        let parsed = AddressMessage::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.protocol(), Some(3));
+        assert_eq!(parsed.origin(), AddressOrigin::KernelLl);
+    }
 }
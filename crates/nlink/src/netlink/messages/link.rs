@@ -3,9 +3,10 @@
 use winnow::{prelude::*, token::take};
 
 use crate::netlink::{
+    attr::AttrIter,
     error::Result,
     parse::{FromNetlink, PResult, ToNetlink, parse_string_from_bytes},
-    types::link::{IfInfoMsg, LinkStats64, OperState},
+    types::link::{IfInfoMsg, LinkFlags, LinkStats64, OperState, br_state, brport},
 };
 
 /// Attribute IDs for IFLA_* constants.
@@ -26,9 +27,13 @@ mod attr_ids {
     pub const IFLA_NUM_TX_QUEUES: u16 = 31;
     pub const IFLA_NUM_RX_QUEUES: u16 = 32;
     pub const IFLA_CARRIER: u16 = 33;
+    pub const IFLA_CARRIER_CHANGES: u16 = 35;
+    pub const IFLA_CARRIER_UP_COUNT: u16 = 47;
+    pub const IFLA_CARRIER_DOWN_COUNT: u16 = 48;
     pub const IFLA_MIN_MTU: u16 = 50;
     pub const IFLA_MAX_MTU: u16 = 51;
     pub const IFLA_PERM_ADDRESS: u16 = 54;
+    pub const IFLA_ALLMULTI: u16 = 61;
     // Plan 190 §2.3c — GSO/GRO/TSO caps.
     pub const IFLA_GSO_MAX_SEGS: u16 = 40;
     pub const IFLA_GSO_MAX_SIZE: u16 = 41;
@@ -81,12 +86,20 @@ pub struct LinkMessage {
     pub(crate) group: Option<u32>,
     /// Promiscuity count (IFLA_PROMISCUITY).
     pub(crate) promiscuity: Option<u32>,
+    /// All-multicast count (IFLA_ALLMULTI).
+    pub(crate) allmulti: Option<u32>,
     /// Number of TX queues (IFLA_NUM_TX_QUEUES).
     pub(crate) num_tx_queues: Option<u32>,
     /// Number of RX queues (IFLA_NUM_RX_QUEUES).
     pub(crate) num_rx_queues: Option<u32>,
     /// Carrier state (IFLA_CARRIER).
     pub(crate) carrier: Option<bool>,
+    /// Carrier transitions since creation (IFLA_CARRIER_CHANGES).
+    pub(crate) carrier_changes: Option<u32>,
+    /// Carrier up transitions (IFLA_CARRIER_UP_COUNT).
+    pub(crate) carrier_up_count: Option<u32>,
+    /// Carrier down transitions (IFLA_CARRIER_DOWN_COUNT).
+    pub(crate) carrier_down_count: Option<u32>,
     /// Link info (IFLA_LINKINFO).
     pub(crate) link_info: Option<LinkInfo>,
     /// Statistics (IFLA_STATS64).
@@ -109,6 +122,47 @@ pub struct LinkMessage {
     pub(crate) gro_ipv4_max_size: Option<u32>,
 }
 
+/// Why an interface is not operational, from
+/// [`LinkMessage::down_reason`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LinkDownReason {
+    /// Administratively down (`IFF_UP` not set).
+    AdminDown,
+    /// Up, but no carrier: cable unplugged, peer down, or no signal.
+    NoCarrier,
+    /// Up, but the device it is stacked on (VLAN parent, bond, ...)
+    /// is down.
+    LowerLayerDown,
+    /// Up, but waiting for an external event such as 802.1X
+    /// authentication.
+    Dormant,
+    /// A component is missing, typically hardware that was removed.
+    NotPresent,
+    /// In a test mode.
+    Testing,
+}
+
+impl LinkDownReason {
+    /// Short lowercase description, e.g. `"no carrier"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AdminDown => "admin down",
+            Self::NoCarrier => "no carrier",
+            Self::LowerLayerDown => "lower layer down",
+            Self::Dormant => "dormant",
+            Self::NotPresent => "not present",
+            Self::Testing => "testing",
+        }
+    }
+}
+
+impl std::fmt::Display for LinkDownReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Link type information from IFLA_LINKINFO.
 #[derive(Debug, Clone, Default)]
 pub struct LinkInfo {
@@ -372,10 +426,22 @@ impl LinkMessage {
     }
 
     /// Get the promiscuity count.
+    ///
+    /// This is the number of holders keeping the device promiscuous
+    /// (packet sockets, bridge ports, `IFF_PROMISC` set via netlink, ...),
+    /// whereas [`LinkFlags::PROMISC`] only reports the netlink-set flag.
     pub fn promiscuity(&self) -> Option<u32> {
         self.promiscuity
     }
 
+    /// Get the all-multicast count (IFLA_ALLMULTI; not reported by older kernels).
+    ///
+    /// The [`promiscuity`](Self::promiscuity) counterpart for
+    /// [`LinkFlags::ALLMULTI`].
+    pub fn allmulti(&self) -> Option<u32> {
+        self.allmulti
+    }
+
     /// Get the number of TX queues.
     pub fn num_tx_queues(&self) -> Option<u32> {
         self.num_tx_queues
@@ -391,6 +457,43 @@ impl LinkMessage {
         self.carrier
     }
 
+    /// Get the number of carrier transitions, up and down, since the
+    /// device was created.
+    pub fn carrier_changes(&self) -> Option<u32> {
+        self.carrier_changes
+    }
+
+    /// Get the number of times the carrier came up (kernel 4.16+).
+    pub fn carrier_up_count(&self) -> Option<u32> {
+        self.carrier_up_count
+    }
+
+    /// Get the number of times the carrier went down (kernel 4.16+).
+    pub fn carrier_down_count(&self) -> Option<u32> {
+        self.carrier_down_count
+    }
+
+    /// Why the interface isn't passing traffic, or `None` if it is
+    /// operational.
+    ///
+    /// Tells an interface that was set down ([`LinkDownReason::AdminDown`])
+    /// from one that is up but has no link ([`LinkDownReason::NoCarrier`])
+    /// or whose lower device is down.
+    pub fn down_reason(&self) -> Option<LinkDownReason> {
+        if !self.is_up() {
+            return Some(LinkDownReason::AdminDown);
+        }
+        match self.operstate {
+            Some(OperState::LowerLayerDown) => Some(LinkDownReason::LowerLayerDown),
+            Some(OperState::NotPresent) => Some(LinkDownReason::NotPresent),
+            Some(OperState::Dormant) => Some(LinkDownReason::Dormant),
+            Some(OperState::Testing) => Some(LinkDownReason::Testing),
+            Some(OperState::Down) => Some(LinkDownReason::NoCarrier),
+            _ if self.carrier == Some(false) => Some(LinkDownReason::NoCarrier),
+            _ => None,
+        }
+    }
+
     /// Get the link info.
     pub fn link_info(&self) -> Option<&LinkInfo> {
         self.link_info.as_ref()
@@ -406,6 +509,11 @@ impl LinkMessage {
         self.header.ifi_flags
     }
 
+    /// Get the interface flags as [`LinkFlags`].
+    pub fn link_flags(&self) -> LinkFlags {
+        LinkFlags::from_bits_retain(self.header.ifi_flags)
+    }
+
     /// Get the link type kind (e.g., "vlan", "bridge", "veth").
     pub fn kind(&self) -> Option<&str> {
         self.link_info.as_ref()?.kind.as_deref()
@@ -430,27 +538,27 @@ impl LinkMessage {
 
     /// Check if the interface is up.
     pub fn is_up(&self) -> bool {
-        self.header.ifi_flags & 0x1 != 0 // IFF_UP
+        self.link_flags().is_up()
     }
 
     /// Check if the interface is running (lower layer up).
     pub fn is_running(&self) -> bool {
-        self.header.ifi_flags & 0x40 != 0 // IFF_RUNNING
+        self.link_flags().is_running()
     }
 
     /// Check if the interface is a loopback.
     pub fn is_loopback(&self) -> bool {
-        self.header.ifi_flags & 0x8 != 0 // IFF_LOOPBACK
+        self.link_flags().is_loopback()
     }
 
     /// Check if the interface supports broadcast.
     pub fn is_broadcast(&self) -> bool {
-        self.header.ifi_flags & 0x2 != 0 // IFF_BROADCAST
+        self.link_flags().is_broadcast()
     }
 
     /// Check if the interface is point-to-point.
     pub fn is_pointopoint(&self) -> bool {
-        self.header.ifi_flags & 0x10 != 0 // IFF_POINTOPOINT
+        self.link_flags().is_pointopoint()
     }
 
     /// Check if the carrier is present.
@@ -625,6 +733,9 @@ impl FromNetlink for LinkMessage {
                 attr_ids::IFLA_PROMISCUITY if attr_data.len() >= 4 => {
                     msg.promiscuity = Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
                 }
+                attr_ids::IFLA_ALLMULTI if attr_data.len() >= 4 => {
+                    msg.allmulti = Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
+                }
                 attr_ids::IFLA_NUM_TX_QUEUES if attr_data.len() >= 4 => {
                     msg.num_tx_queues =
                         Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
@@ -636,6 +747,18 @@ impl FromNetlink for LinkMessage {
                 attr_ids::IFLA_CARRIER if !attr_data.is_empty() => {
                     msg.carrier = Some(attr_data[0] != 0);
                 }
+                attr_ids::IFLA_CARRIER_CHANGES if attr_data.len() >= 4 => {
+                    msg.carrier_changes =
+                        Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
+                }
+                attr_ids::IFLA_CARRIER_UP_COUNT if attr_data.len() >= 4 => {
+                    msg.carrier_up_count =
+                        Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
+                }
+                attr_ids::IFLA_CARRIER_DOWN_COUNT if attr_data.len() >= 4 => {
+                    msg.carrier_down_count =
+                        Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
+                }
                 attr_ids::IFLA_LINKINFO => {
                     msg.link_info = Some(parse_link_info(attr_data));
                 }
@@ -1058,6 +1181,136 @@ pub enum MiiStatus {
     Down,
 }
 
+/// Bridge port state as reported by the kernel.
+///
+/// Parsed from `IFLA_INFO_SLAVE_DATA` when `slave_kind == "bridge"` —
+/// the `bridge_slave` block of `ip -d link show`.
+///
+/// # Example
+///
+/// ```ignore
+/// let links = conn.get_links().await?;
+/// for link in &links {
+///     if let Some(port) = link.bridge_port_info() {
+///         println!("{}: state={:?} cost={} port_id={:#x}",
+///             link.name_or("?"), port.state, port.cost, port.port_id);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BridgePortInfo {
+    /// STP port state.
+    pub state: BridgePortState,
+    /// STP port priority.
+    pub priority: u16,
+    /// STP path cost.
+    pub cost: u32,
+    /// STP port id (priority and port number).
+    pub port_id: u16,
+    /// Port number on the bridge.
+    pub port_no: u16,
+    /// Designated port.
+    pub designated_port: u16,
+    /// Designated cost.
+    pub designated_cost: u16,
+    /// Designated root bridge.
+    pub designated_root: Option<BridgeId>,
+    /// Designated bridge.
+    pub designated_bridge: Option<BridgeId>,
+    /// Hairpin mode (reflect frames back out this port).
+    pub hairpin: bool,
+    /// BPDU guard.
+    pub guard: bool,
+    /// Root block (reject superior BPDUs).
+    pub root_block: bool,
+    /// IGMP/MLD fast leave.
+    pub fast_leave: bool,
+    /// MAC learning.
+    pub learning: bool,
+    /// Unknown-unicast flooding.
+    pub unicast_flood: bool,
+}
+
+/// STP port state (`BR_STATE_*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BridgePortState {
+    /// Port disabled.
+    Disabled,
+    /// Listening (STP).
+    Listening,
+    /// Learning (STP).
+    Learning,
+    /// Forwarding.
+    Forwarding,
+    /// Blocking (STP).
+    Blocking,
+    /// A state value this crate does not know.
+    Unknown(u8),
+}
+
+impl BridgePortState {
+    /// Decode a raw `BR_STATE_*` value.
+    pub fn from_raw(raw: u8) -> Self {
+        match raw {
+            br_state::DISABLED => Self::Disabled,
+            br_state::LISTENING => Self::Listening,
+            br_state::LEARNING => Self::Learning,
+            br_state::FORWARDING => Self::Forwarding,
+            br_state::BLOCKING => Self::Blocking,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// iproute2-style lowercase name (`"forwarding"`, ...).
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::Listening => "listening",
+            Self::Learning => "learning",
+            Self::Forwarding => "forwarding",
+            Self::Blocking => "blocking",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+}
+
+/// STP bridge identifier (`struct ifla_bridge_id`): 16-bit priority
+/// followed by the bridge MAC address.
+///
+/// Displays in the iproute2 form `8000.aabbccddeeff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BridgeId {
+    /// Bridge priority.
+    pub priority: u16,
+    /// Bridge MAC address.
+    pub addr: [u8; 6],
+}
+
+impl BridgeId {
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        let data = data.get(..8)?;
+        let mut addr = [0u8; 6];
+        addr.copy_from_slice(&data[2..8]);
+        Some(Self {
+            // Stored as two bytes in network order.
+            priority: u16::from_be_bytes([data[0], data[1]]),
+            addr,
+        })
+    }
+}
+
+impl std::fmt::Display for BridgeId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04x}.", self.priority)?;
+        for b in self.addr {
+            write!(f, "{b:02x}")?;
+        }
+        Ok(())
+    }
+}
+
 impl LinkMessage {
     /// Get bond configuration if this is a bond interface.
     ///
@@ -1090,6 +1343,26 @@ impl LinkMessage {
             .and_then(|i| i.slave_kind.as_deref())
             == Some("bond")
     }
+
+    /// Get bridge port state if this interface is a bridge port.
+    ///
+    /// Returns `None` if the interface is not enslaved to a bridge.
+    pub fn bridge_port_info(&self) -> Option<BridgePortInfo> {
+        let link_info = self.link_info.as_ref()?;
+        if link_info.slave_kind.as_deref() != Some("bridge") {
+            return None;
+        }
+        let data = link_info.slave_data.as_deref()?;
+        Some(parse_bridge_port_info(data))
+    }
+
+    /// Check if this interface is a bridge port.
+    pub fn is_bridge_port(&self) -> bool {
+        self.link_info
+            .as_ref()
+            .and_then(|i| i.slave_kind.as_deref())
+            == Some("bridge")
+    }
 }
 
 /// Parse a u32 from a byte slice (native endian).
@@ -1324,6 +1597,69 @@ fn parse_bond_slave_info(data: &[u8]) -> BondSlaveInfo {
     info
 }
 
+/// Parse bridge port info from raw IFLA_INFO_SLAVE_DATA bytes.
+fn parse_bridge_port_info(data: &[u8]) -> BridgePortInfo {
+    let mut info = BridgePortInfo {
+        state: BridgePortState::Disabled,
+        priority: 0,
+        cost: 0,
+        port_id: 0,
+        port_no: 0,
+        designated_port: 0,
+        designated_cost: 0,
+        designated_root: None,
+        designated_bridge: None,
+        hairpin: false,
+        guard: false,
+        root_block: false,
+        fast_leave: false,
+        learning: false,
+        unicast_flood: false,
+    };
+
+    for (attr_type, payload) in AttrIter::new(data) {
+        let flag = payload.first().is_some_and(|b| *b != 0);
+        match attr_type {
+            brport::IFLA_BRPORT_STATE if !payload.is_empty() => {
+                info.state = BridgePortState::from_raw(payload[0]);
+            }
+            brport::IFLA_BRPORT_PRIORITY => {
+                info.priority = parse_u16_ne(payload).unwrap_or_default();
+            }
+            brport::IFLA_BRPORT_COST => {
+                info.cost = parse_u32_ne(payload).unwrap_or_default();
+            }
+            brport::IFLA_BRPORT_ID => {
+                info.port_id = parse_u16_ne(payload).unwrap_or_default();
+            }
+            brport::IFLA_BRPORT_NO => {
+                info.port_no = parse_u16_ne(payload).unwrap_or_default();
+            }
+            brport::IFLA_BRPORT_DESIGNATED_PORT => {
+                info.designated_port = parse_u16_ne(payload).unwrap_or_default();
+            }
+            brport::IFLA_BRPORT_DESIGNATED_COST => {
+                info.designated_cost = parse_u16_ne(payload).unwrap_or_default();
+            }
+            brport::IFLA_BRPORT_ROOT_ID => {
+                info.designated_root = BridgeId::from_bytes(payload);
+            }
+            brport::IFLA_BRPORT_BRIDGE_ID => {
+                info.designated_bridge = BridgeId::from_bytes(payload);
+            }
+            brport::IFLA_BRPORT_MODE => info.hairpin = flag,
+            brport::IFLA_BRPORT_GUARD => info.guard = flag,
+            brport::IFLA_BRPORT_PROTECT => info.root_block = flag,
+            brport::IFLA_BRPORT_FAST_LEAVE => info.fast_leave = flag,
+            brport::IFLA_BRPORT_LEARNING => info.learning = flag,
+            brport::IFLA_BRPORT_UNICAST_FLOOD => info.unicast_flood = flag,
+            _ => {}
+        }
+    }
+
+    info
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1374,6 +1710,143 @@ mod tests {
         assert_eq!(msg.gro_ipv4_max_size(), Some(16384));
     }
 
+    #[test]
+    fn parser_extracts_carrier_counters() {
+        let mut buf = vec![0u8; IfInfoMsg::SIZE];
+        append_u32_attr(&mut buf, attr_ids::IFLA_CARRIER_CHANGES, 7);
+        append_u32_attr(&mut buf, attr_ids::IFLA_CARRIER_UP_COUNT, 4);
+        append_u32_attr(&mut buf, attr_ids::IFLA_CARRIER_DOWN_COUNT, 3);
+
+        let mut input = buf.as_slice();
+        let msg = LinkMessage::parse(&mut input).expect("parse should succeed");
+
+        assert_eq!(msg.carrier_changes(), Some(7));
+        assert_eq!(msg.carrier_up_count(), Some(4));
+        assert_eq!(msg.carrier_down_count(), Some(3));
+    }
+
+    #[test]
+    fn down_reason_separates_admin_down_from_no_carrier() {
+        const IFF_UP: u32 = 0x1;
+        let link = |flags, operstate, carrier| {
+            let mut msg = LinkMessageBuilder::new().flags(flags).build();
+            msg.operstate = operstate;
+            msg.carrier = carrier;
+            msg
+        };
+
+        assert_eq!(
+            link(0, Some(OperState::Down), Some(true)).down_reason(),
+            Some(LinkDownReason::AdminDown)
+        );
+        assert_eq!(
+            link(IFF_UP, Some(OperState::Down), Some(false)).down_reason(),
+            Some(LinkDownReason::NoCarrier)
+        );
+        assert_eq!(
+            link(IFF_UP, Some(OperState::LowerLayerDown), Some(false)).down_reason(),
+            Some(LinkDownReason::LowerLayerDown)
+        );
+        assert_eq!(
+            link(IFF_UP, Some(OperState::Unknown), Some(false)).down_reason(),
+            Some(LinkDownReason::NoCarrier)
+        );
+        // Loopback and dummies report UNKNOWN with carrier.
+        assert_eq!(
+            link(IFF_UP, Some(OperState::Unknown), Some(true)).down_reason(),
+            None
+        );
+        assert_eq!(
+            link(IFF_UP, Some(OperState::Up), Some(true)).down_reason(),
+            None
+        );
+    }
+
+    /// Append an attribute with an arbitrary payload, padded to 4 bytes.
+    fn append_attr(buf: &mut Vec<u8>, attr_type: u16, payload: &[u8]) {
+        buf.extend_from_slice(&((4 + payload.len()) as u16).to_ne_bytes());
+        buf.extend_from_slice(&attr_type.to_ne_bytes());
+        buf.extend_from_slice(payload);
+        while !buf.len().is_multiple_of(4) {
+            buf.push(0);
+        }
+    }
+
+    fn bridge_port(slave_data: Vec<u8>) -> LinkMessage {
+        LinkMessage {
+            link_info: Some(LinkInfo {
+                slave_kind: Some("bridge".into()),
+                slave_data: Some(slave_data),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn bridge_port_info_decodes_slave_data() {
+        let mut data = Vec::new();
+        append_attr(&mut data, brport::IFLA_BRPORT_STATE, &[br_state::FORWARDING]);
+        append_attr(&mut data, brport::IFLA_BRPORT_PRIORITY, &32u16.to_ne_bytes());
+        append_attr(&mut data, brport::IFLA_BRPORT_COST, &100u32.to_ne_bytes());
+        append_attr(&mut data, brport::IFLA_BRPORT_ID, &0x8001u16.to_ne_bytes());
+        append_attr(&mut data, brport::IFLA_BRPORT_NO, &1u16.to_ne_bytes());
+        append_attr(&mut data, brport::IFLA_BRPORT_LEARNING, &[1]);
+        append_attr(&mut data, brport::IFLA_BRPORT_GUARD, &[0]);
+        append_attr(
+            &mut data,
+            brport::IFLA_BRPORT_ROOT_ID,
+            &[0x80, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x01],
+        );
+
+        let msg = bridge_port(data);
+        assert!(msg.is_bridge_port());
+        assert!(!msg.is_bond_slave());
+        assert!(msg.bond_slave_info().is_none());
+
+        let port = msg.bridge_port_info().unwrap();
+        assert_eq!(port.state, BridgePortState::Forwarding);
+        assert_eq!(port.state.name(), "forwarding");
+        assert_eq!(port.priority, 32);
+        assert_eq!(port.cost, 100);
+        assert_eq!(port.port_id, 0x8001);
+        assert_eq!(port.port_no, 1);
+        assert!(port.learning);
+        assert!(!port.guard);
+        assert_eq!(
+            port.designated_root.unwrap().to_string(),
+            "8000.020000000001"
+        );
+        assert!(port.designated_bridge.is_none());
+    }
+
+    #[test]
+    fn bridge_port_state_keeps_unknown_values() {
+        assert_eq!(BridgePortState::from_raw(4), BridgePortState::Blocking);
+        assert_eq!(BridgePortState::from_raw(9), BridgePortState::Unknown(9));
+    }
+
+    #[test]
+    fn bridge_port_info_needs_bridge_slave_kind() {
+        let mut msg = bridge_port(Vec::new());
+        msg.link_info.as_mut().unwrap().slave_kind = Some("bond".into());
+        assert!(msg.bridge_port_info().is_none());
+        assert!(LinkMessage::default().bridge_port_info().is_none());
+    }
+
+    #[test]
+    fn parser_extracts_promiscuity_and_allmulti_counts() {
+        let mut buf = vec![0u8; IfInfoMsg::SIZE];
+        append_u32_attr(&mut buf, attr_ids::IFLA_PROMISCUITY, 2);
+        append_u32_attr(&mut buf, attr_ids::IFLA_ALLMULTI, 1);
+
+        let mut input = buf.as_slice();
+        let msg = LinkMessage::parse(&mut input).expect("parse should succeed");
+
+        assert_eq!(msg.promiscuity(), Some(2));
+        assert_eq!(msg.allmulti(), Some(1));
+    }
+
     #[test]
     fn parser_skips_gso_caps_when_absent() {
         let buf = vec![0u8; IfInfoMsg::SIZE];
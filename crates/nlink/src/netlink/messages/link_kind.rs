@@ -0,0 +1,648 @@
+//! Typed `IFLA_INFO_DATA` for common link kinds.
+//!
+//! [`LinkInfo::data`](super::LinkInfo::data) carries the kind-specific
+//! attributes as raw bytes. [`LinkMessage::kind_info`] decodes them into a
+//! [`LinkKindInfo`] for the kinds below; the per-kind accessors
+//! ([`LinkMessage::vlan_info`], ...) return just one of them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::messages::LinkKindInfo;
+//!
+//! for link in conn.get_links().await? {
+//!     match link.kind_info() {
+//!         Some(LinkKindInfo::Vlan(vlan)) => println!("{}: vlan {}", link.name_or("?"), vlan.id),
+//!         Some(LinkKindInfo::Tun(tun)) if tun.is_tap() => println!("{}: tap", link.name_or("?")),
+//!         _ => {}
+//!     }
+//! }
+//! ```
+
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
+
+use super::{BondInfo, LinkMessage};
+use crate::netlink::{
+    attr::{AttrIter, get},
+    link::{MacvlanMode, VlanProtocol},
+};
+
+/// IFLA_BR_* attribute IDs.
+mod br_ids {
+    pub const IFLA_BR_FORWARD_DELAY: u16 = 1;
+    pub const IFLA_BR_HELLO_TIME: u16 = 2;
+    pub const IFLA_BR_MAX_AGE: u16 = 3;
+    pub const IFLA_BR_AGEING_TIME: u16 = 4;
+    pub const IFLA_BR_STP_STATE: u16 = 5;
+    pub const IFLA_BR_PRIORITY: u16 = 6;
+    pub const IFLA_BR_VLAN_FILTERING: u16 = 7;
+    pub const IFLA_BR_VLAN_PROTOCOL: u16 = 8;
+    pub const IFLA_BR_GROUP_FWD_MASK: u16 = 9;
+    pub const IFLA_BR_MCAST_SNOOPING: u16 = 23;
+    pub const IFLA_BR_MCAST_QUERIER: u16 = 25;
+    pub const IFLA_BR_NF_CALL_IPTABLES: u16 = 36;
+    pub const IFLA_BR_NF_CALL_IP6TABLES: u16 = 37;
+    pub const IFLA_BR_NF_CALL_ARPTABLES: u16 = 38;
+    pub const IFLA_BR_VLAN_DEFAULT_PVID: u16 = 39;
+    pub const IFLA_BR_MCAST_IGMP_VERSION: u16 = 43;
+    pub const IFLA_BR_MCAST_MLD_VERSION: u16 = 44;
+}
+
+/// IFLA_VLAN_* attribute IDs.
+mod vlan_ids {
+    pub const IFLA_VLAN_ID: u16 = 1;
+    pub const IFLA_VLAN_FLAGS: u16 = 2;
+    pub const IFLA_VLAN_PROTOCOL: u16 = 5;
+}
+
+/// IFLA_VXLAN_* attribute IDs.
+mod vxlan_ids {
+    pub const IFLA_VXLAN_ID: u16 = 1;
+    pub const IFLA_VXLAN_GROUP: u16 = 2;
+    pub const IFLA_VXLAN_LINK: u16 = 3;
+    pub const IFLA_VXLAN_LOCAL: u16 = 4;
+    pub const IFLA_VXLAN_TTL: u16 = 5;
+    pub const IFLA_VXLAN_TOS: u16 = 6;
+    pub const IFLA_VXLAN_LEARNING: u16 = 7;
+    pub const IFLA_VXLAN_AGEING: u16 = 8;
+    pub const IFLA_VXLAN_PORT: u16 = 15;
+    pub const IFLA_VXLAN_GROUP6: u16 = 16;
+    pub const IFLA_VXLAN_LOCAL6: u16 = 17;
+}
+
+/// IFLA_MACVLAN_* attribute IDs.
+mod macvlan_ids {
+    pub const IFLA_MACVLAN_MODE: u16 = 1;
+}
+
+/// IFLA_VRF_* attribute IDs.
+mod vrf_ids {
+    pub const IFLA_VRF_TABLE: u16 = 1;
+}
+
+/// IFLA_TUN_* attribute IDs and `IFF_TUN`/`IFF_TAP`.
+mod tun_ids {
+    pub const IFLA_TUN_OWNER: u16 = 1;
+    pub const IFLA_TUN_GROUP: u16 = 2;
+    pub const IFLA_TUN_TYPE: u16 = 3;
+    pub const IFLA_TUN_PI: u16 = 4;
+    pub const IFLA_TUN_VNET_HDR: u16 = 5;
+    pub const IFLA_TUN_PERSIST: u16 = 6;
+    pub const IFLA_TUN_MULTI_QUEUE: u16 = 7;
+    pub const IFLA_TUN_NUM_QUEUES: u16 = 8;
+    pub const IFLA_TUN_NUM_DISABLED_QUEUES: u16 = 9;
+
+    pub const IFF_TAP: u8 = 0x2;
+}
+
+/// IFLA_GTP_* attribute IDs.
+mod gtp_ids {
+    pub const IFLA_GTP_PDP_HASHSIZE: u16 = 3;
+    pub const IFLA_GTP_ROLE: u16 = 4;
+    pub const IFLA_GTP_RESTART_COUNT: u16 = 6;
+}
+
+/// Decoded `IFLA_INFO_DATA`, by link kind.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum LinkKindInfo {
+    /// `bond`.
+    Bond(BondInfo),
+    /// `bridge`.
+    Bridge(BridgeInfo),
+    /// `vlan`.
+    Vlan(VlanInfo),
+    /// `vxlan`.
+    Vxlan(VxlanInfo),
+    /// `macvlan` or `macvtap`.
+    Macvlan(MacvlanInfo),
+    /// `vrf`.
+    Vrf(VrfInfo),
+    /// `tun`, for both tun and tap devices.
+    Tun(TunInfo),
+    /// `gtp`.
+    Gtp(GtpInfo),
+    /// Any other kind; the data is left in
+    /// [`LinkInfo::data`](super::LinkInfo::data).
+    Other(String),
+}
+
+/// Bridge settings as reported by the kernel.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct BridgeInfo {
+    /// STP forward delay.
+    pub forward_delay: Option<Duration>,
+    /// STP hello time.
+    pub hello_time: Option<Duration>,
+    /// STP max message age.
+    pub max_age: Option<Duration>,
+    /// FDB entry ageing time.
+    pub ageing_time: Option<Duration>,
+    /// STP state (0 off, 1 kernel STP, 2 user-space STP).
+    pub stp_state: Option<u32>,
+    /// Bridge priority.
+    pub priority: Option<u16>,
+    /// VLAN filtering is enabled.
+    pub vlan_filtering: bool,
+    /// VLAN protocol (`0x8100` or `0x88a8`).
+    pub vlan_protocol: Option<u16>,
+    /// Default PVID for new ports.
+    pub vlan_default_pvid: Option<u16>,
+    /// Link-local group address forwarding mask.
+    pub group_fwd_mask: Option<u16>,
+    /// Multicast snooping is enabled.
+    pub mcast_snooping: bool,
+    /// The bridge sends IGMP/MLD queries.
+    pub mcast_querier: bool,
+    /// IGMP version used by the querier.
+    pub mcast_igmp_version: Option<u8>,
+    /// MLD version used by the querier.
+    pub mcast_mld_version: Option<u8>,
+    /// Bridged IPv4 traffic passes through iptables.
+    pub nf_call_iptables: bool,
+    /// Bridged IPv6 traffic passes through ip6tables.
+    pub nf_call_ip6tables: bool,
+    /// Bridged ARP traffic passes through arptables.
+    pub nf_call_arptables: bool,
+}
+
+impl BridgeInfo {
+    /// The VLAN protocol as a typed enum.
+    pub fn vlan_protocol_type(&self) -> Option<VlanProtocol> {
+        vlan_protocol(self.vlan_protocol?)
+    }
+}
+
+/// VLAN settings as reported by the kernel.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct VlanInfo {
+    /// VLAN ID.
+    pub id: u16,
+    /// VLAN protocol (`0x8100` or `0x88a8`).
+    pub protocol: Option<u16>,
+    /// `VLAN_FLAG_*` bits.
+    pub flags: u32,
+}
+
+impl VlanInfo {
+    /// The VLAN protocol as a typed enum.
+    pub fn vlan_protocol(&self) -> Option<VlanProtocol> {
+        vlan_protocol(self.protocol?)
+    }
+}
+
+/// VXLAN settings as reported by the kernel.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct VxlanInfo {
+    /// VXLAN network identifier.
+    pub vni: u32,
+    /// Multicast group or unicast remote.
+    pub group: Option<IpAddr>,
+    /// Local source address.
+    pub local: Option<IpAddr>,
+    /// Underlay interface index.
+    pub link: Option<u32>,
+    /// Destination UDP port.
+    pub port: Option<u16>,
+    /// Outer TTL; `Some(0)` inherits.
+    pub ttl: Option<u8>,
+    /// Outer TOS; `Some(1)` inherits.
+    pub tos: Option<u8>,
+    /// FDB learning is enabled.
+    pub learning: bool,
+    /// FDB entry ageing time.
+    pub ageing: Option<Duration>,
+}
+
+/// Macvlan/macvtap settings as reported by the kernel.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct MacvlanInfo {
+    /// Raw `MACVLAN_MODE_*` value.
+    pub mode: u32,
+}
+
+impl MacvlanInfo {
+    /// The mode as a typed enum.
+    pub fn macvlan_mode(&self) -> Option<MacvlanMode> {
+        match self.mode {
+            1 => Some(MacvlanMode::Private),
+            2 => Some(MacvlanMode::Vepa),
+            4 => Some(MacvlanMode::Bridge),
+            8 => Some(MacvlanMode::Passthru),
+            16 => Some(MacvlanMode::Source),
+            _ => None,
+        }
+    }
+}
+
+/// VRF settings as reported by the kernel.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct VrfInfo {
+    /// Routing table bound to the VRF.
+    pub table: Option<u32>,
+}
+
+/// Tun/tap device settings as reported by the kernel.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct TunInfo {
+    /// Raw `IFF_TUN`/`IFF_TAP` type.
+    pub dev_type: u8,
+    /// Owning user, if restricted.
+    pub owner: Option<u32>,
+    /// Owning group, if restricted.
+    pub group: Option<u32>,
+    /// Packet information header is prepended.
+    pub pi: bool,
+    /// Virtio-net header is prepended.
+    pub vnet_hdr: bool,
+    /// Device outlives its file descriptor.
+    pub persist: bool,
+    /// Multi-queue device.
+    pub multi_queue: bool,
+    /// Attached queues.
+    pub num_queues: Option<u32>,
+    /// Detached queues.
+    pub num_disabled_queues: Option<u32>,
+}
+
+impl TunInfo {
+    /// Whether this is a tap (layer 2) device rather than tun (layer 3).
+    pub fn is_tap(&self) -> bool {
+        self.dev_type == tun_ids::IFF_TAP
+    }
+}
+
+/// GTP tunnel role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GtpRole {
+    /// Gateway GPRS support node (network side).
+    Ggsn,
+    /// Serving GPRS support node (access side).
+    Sgsn,
+    /// A role value this crate does not know.
+    Unknown(u32),
+}
+
+/// GTP device settings as reported by the kernel.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct GtpInfo {
+    /// Tunnel role.
+    pub role: Option<GtpRole>,
+    /// PDP context hash table size.
+    pub pdp_hashsize: Option<u32>,
+    /// GTP restart counter.
+    pub restart_count: Option<u8>,
+}
+
+fn vlan_protocol(raw: u16) -> Option<VlanProtocol> {
+    match raw {
+        0x8100 => Some(VlanProtocol::Dot1q),
+        0x88a8 => Some(VlanProtocol::Dot1ad),
+        _ => None,
+    }
+}
+
+/// Kernel clock_t (USER_HZ, 100 per second) to a duration.
+fn centisecs(payload: &[u8]) -> Option<Duration> {
+    get::u32_ne(payload)
+        .ok()
+        .map(|cs| Duration::from_millis(u64::from(cs) * 10))
+}
+
+fn flag(payload: &[u8]) -> bool {
+    payload.first().is_some_and(|b| *b != 0)
+}
+
+fn ipv4(payload: &[u8]) -> Option<IpAddr> {
+    let octets: [u8; 4] = payload.get(..4)?.try_into().ok()?;
+    Some(IpAddr::V4(Ipv4Addr::from(octets)))
+}
+
+fn ipv6(payload: &[u8]) -> Option<IpAddr> {
+    let octets: [u8; 16] = payload.get(..16)?.try_into().ok()?;
+    Some(IpAddr::V6(Ipv6Addr::from(octets)))
+}
+
+fn parse_bridge_info(data: &[u8]) -> BridgeInfo {
+    let mut info = BridgeInfo::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            br_ids::IFLA_BR_FORWARD_DELAY => info.forward_delay = centisecs(payload),
+            br_ids::IFLA_BR_HELLO_TIME => info.hello_time = centisecs(payload),
+            br_ids::IFLA_BR_MAX_AGE => info.max_age = centisecs(payload),
+            br_ids::IFLA_BR_AGEING_TIME => info.ageing_time = centisecs(payload),
+            br_ids::IFLA_BR_STP_STATE => info.stp_state = get::u32_ne(payload).ok(),
+            br_ids::IFLA_BR_PRIORITY => info.priority = get::u16_ne(payload).ok(),
+            br_ids::IFLA_BR_VLAN_FILTERING => info.vlan_filtering = flag(payload),
+            br_ids::IFLA_BR_VLAN_PROTOCOL => info.vlan_protocol = get::u16_be(payload).ok(),
+            br_ids::IFLA_BR_GROUP_FWD_MASK => info.group_fwd_mask = get::u16_ne(payload).ok(),
+            br_ids::IFLA_BR_MCAST_SNOOPING => info.mcast_snooping = flag(payload),
+            br_ids::IFLA_BR_MCAST_QUERIER => info.mcast_querier = flag(payload),
+            br_ids::IFLA_BR_NF_CALL_IPTABLES => info.nf_call_iptables = flag(payload),
+            br_ids::IFLA_BR_NF_CALL_IP6TABLES => info.nf_call_ip6tables = flag(payload),
+            br_ids::IFLA_BR_NF_CALL_ARPTABLES => info.nf_call_arptables = flag(payload),
+            br_ids::IFLA_BR_VLAN_DEFAULT_PVID => {
+                info.vlan_default_pvid = get::u16_ne(payload).ok();
+            }
+            br_ids::IFLA_BR_MCAST_IGMP_VERSION => {
+                info.mcast_igmp_version = get::u8(payload).ok();
+            }
+            br_ids::IFLA_BR_MCAST_MLD_VERSION => {
+                info.mcast_mld_version = get::u8(payload).ok();
+            }
+            _ => {}
+        }
+    }
+    info
+}
+
+fn parse_vlan_info(data: &[u8]) -> VlanInfo {
+    let mut info = VlanInfo::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            vlan_ids::IFLA_VLAN_ID => info.id = get::u16_ne(payload).unwrap_or_default(),
+            // struct ifla_vlan_flags { u32 flags; u32 mask; }
+            vlan_ids::IFLA_VLAN_FLAGS => info.flags = get::u32_ne(payload).unwrap_or_default(),
+            vlan_ids::IFLA_VLAN_PROTOCOL => info.protocol = get::u16_be(payload).ok(),
+            _ => {}
+        }
+    }
+    info
+}
+
+fn parse_vxlan_info(data: &[u8]) -> VxlanInfo {
+    let mut info = VxlanInfo::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            vxlan_ids::IFLA_VXLAN_ID => info.vni = get::u32_ne(payload).unwrap_or_default(),
+            vxlan_ids::IFLA_VXLAN_GROUP => info.group = ipv4(payload),
+            vxlan_ids::IFLA_VXLAN_GROUP6 => info.group = ipv6(payload),
+            vxlan_ids::IFLA_VXLAN_LOCAL => info.local = ipv4(payload),
+            vxlan_ids::IFLA_VXLAN_LOCAL6 => info.local = ipv6(payload),
+            vxlan_ids::IFLA_VXLAN_LINK => info.link = get::u32_ne(payload).ok(),
+            vxlan_ids::IFLA_VXLAN_PORT => info.port = get::u16_be(payload).ok(),
+            vxlan_ids::IFLA_VXLAN_TTL => info.ttl = get::u8(payload).ok(),
+            vxlan_ids::IFLA_VXLAN_TOS => info.tos = get::u8(payload).ok(),
+            vxlan_ids::IFLA_VXLAN_LEARNING => info.learning = flag(payload),
+            vxlan_ids::IFLA_VXLAN_AGEING => {
+                info.ageing = get::u32_ne(payload)
+                    .ok()
+                    .map(|s| Duration::from_secs(u64::from(s)));
+            }
+            _ => {}
+        }
+    }
+    info
+}
+
+fn parse_macvlan_info(data: &[u8]) -> MacvlanInfo {
+    let mut info = MacvlanInfo::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        if attr_type == macvlan_ids::IFLA_MACVLAN_MODE {
+            info.mode = get::u32_ne(payload).unwrap_or_default();
+        }
+    }
+    info
+}
+
+fn parse_vrf_info(data: &[u8]) -> VrfInfo {
+    let mut info = VrfInfo::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        if attr_type == vrf_ids::IFLA_VRF_TABLE {
+            info.table = get::u32_ne(payload).ok();
+        }
+    }
+    info
+}
+
+fn parse_tun_info(data: &[u8]) -> TunInfo {
+    let mut info = TunInfo::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            tun_ids::IFLA_TUN_TYPE => info.dev_type = get::u8(payload).unwrap_or_default(),
+            tun_ids::IFLA_TUN_OWNER => info.owner = get::u32_ne(payload).ok(),
+            tun_ids::IFLA_TUN_GROUP => info.group = get::u32_ne(payload).ok(),
+            tun_ids::IFLA_TUN_PI => info.pi = flag(payload),
+            tun_ids::IFLA_TUN_VNET_HDR => info.vnet_hdr = flag(payload),
+            tun_ids::IFLA_TUN_PERSIST => info.persist = flag(payload),
+            tun_ids::IFLA_TUN_MULTI_QUEUE => info.multi_queue = flag(payload),
+            tun_ids::IFLA_TUN_NUM_QUEUES => info.num_queues = get::u32_ne(payload).ok(),
+            tun_ids::IFLA_TUN_NUM_DISABLED_QUEUES => {
+                info.num_disabled_queues = get::u32_ne(payload).ok();
+            }
+            _ => {}
+        }
+    }
+    info
+}
+
+fn parse_gtp_info(data: &[u8]) -> GtpInfo {
+    let mut info = GtpInfo::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            gtp_ids::IFLA_GTP_ROLE => {
+                info.role = get::u32_ne(payload).ok().map(|role| match role {
+                    0 => GtpRole::Ggsn,
+                    1 => GtpRole::Sgsn,
+                    other => GtpRole::Unknown(other),
+                });
+            }
+            gtp_ids::IFLA_GTP_PDP_HASHSIZE => info.pdp_hashsize = get::u32_ne(payload).ok(),
+            gtp_ids::IFLA_GTP_RESTART_COUNT => info.restart_count = get::u8(payload).ok(),
+            _ => {}
+        }
+    }
+    info
+}
+
+impl LinkMessage {
+    /// Decode `IFLA_INFO_DATA` according to the link kind.
+    ///
+    /// Returns `None` if the message carries no `IFLA_LINKINFO` kind.
+    /// Kinds without a typed decoder come back as
+    /// [`LinkKindInfo::Other`].
+    pub fn kind_info(&self) -> Option<LinkKindInfo> {
+        let link_info = self.link_info.as_ref()?;
+        let kind = link_info.kind.as_deref()?;
+        let data = link_info.data.as_deref().unwrap_or_default();
+        Some(match kind {
+            "bond" => match self.bond_info() {
+                Some(bond) => LinkKindInfo::Bond(bond),
+                None => LinkKindInfo::Other(kind.to_string()),
+            },
+            "bridge" => LinkKindInfo::Bridge(parse_bridge_info(data)),
+            "vlan" => LinkKindInfo::Vlan(parse_vlan_info(data)),
+            "vxlan" => LinkKindInfo::Vxlan(parse_vxlan_info(data)),
+            "macvlan" | "macvtap" => LinkKindInfo::Macvlan(parse_macvlan_info(data)),
+            "vrf" => LinkKindInfo::Vrf(parse_vrf_info(data)),
+            "tun" => LinkKindInfo::Tun(parse_tun_info(data)),
+            "gtp" => LinkKindInfo::Gtp(parse_gtp_info(data)),
+            other => LinkKindInfo::Other(other.to_string()),
+        })
+    }
+
+    /// Get bridge settings if this is a bridge.
+    pub fn bridge_info(&self) -> Option<BridgeInfo> {
+        match self.kind_info()? {
+            LinkKindInfo::Bridge(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// Get VLAN settings if this is a VLAN interface.
+    pub fn vlan_info(&self) -> Option<VlanInfo> {
+        match self.kind_info()? {
+            LinkKindInfo::Vlan(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// Get VXLAN settings if this is a VXLAN interface.
+    pub fn vxlan_info(&self) -> Option<VxlanInfo> {
+        match self.kind_info()? {
+            LinkKindInfo::Vxlan(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// Get tun/tap settings if this is a tun or tap device.
+    pub fn tun_info(&self) -> Option<TunInfo> {
+        match self.kind_info()? {
+            LinkKindInfo::Tun(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// Get GTP settings if this is a GTP device.
+    pub fn gtp_info(&self) -> Option<GtpInfo> {
+        match self.kind_info()? {
+            LinkKindInfo::Gtp(info) => Some(info),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::messages::LinkInfo;
+
+    fn append_attr(buf: &mut Vec<u8>, attr_type: u16, payload: &[u8]) {
+        buf.extend_from_slice(&((4 + payload.len()) as u16).to_ne_bytes());
+        buf.extend_from_slice(&attr_type.to_ne_bytes());
+        buf.extend_from_slice(payload);
+        while !buf.len().is_multiple_of(4) {
+            buf.push(0);
+        }
+    }
+
+    fn link(kind: &str, data: Vec<u8>) -> LinkMessage {
+        LinkMessage {
+            link_info: Some(LinkInfo {
+                kind: Some(kind.into()),
+                data: Some(data),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn vlan_info_decodes_id_and_big_endian_protocol() {
+        let mut data = Vec::new();
+        append_attr(&mut data, vlan_ids::IFLA_VLAN_ID, &100u16.to_ne_bytes());
+        append_attr(
+            &mut data,
+            vlan_ids::IFLA_VLAN_PROTOCOL,
+            &0x88a8u16.to_be_bytes(),
+        );
+
+        let vlan = link("vlan", data).vlan_info().unwrap();
+        assert_eq!(vlan.id, 100);
+        assert_eq!(vlan.vlan_protocol(), Some(VlanProtocol::Dot1ad));
+    }
+
+    #[test]
+    fn tun_info_tells_tap_from_tun() {
+        let mut data = Vec::new();
+        append_attr(&mut data, tun_ids::IFLA_TUN_TYPE, &[tun_ids::IFF_TAP]);
+        append_attr(&mut data, tun_ids::IFLA_TUN_PERSIST, &[1]);
+        append_attr(&mut data, tun_ids::IFLA_TUN_NUM_QUEUES, &1u32.to_ne_bytes());
+
+        let tap = link("tun", data).tun_info().unwrap();
+        assert!(tap.is_tap());
+        assert!(tap.persist);
+        assert!(!tap.multi_queue);
+        assert_eq!(tap.num_queues, Some(1));
+
+        let mut data = Vec::new();
+        append_attr(&mut data, tun_ids::IFLA_TUN_TYPE, &[0x1]);
+        assert!(!link("tun", data).tun_info().unwrap().is_tap());
+    }
+
+    #[test]
+    fn vxlan_info_decodes_v6_group_and_port() {
+        let group: Ipv6Addr = "ff05::100".parse().unwrap();
+        let mut data = Vec::new();
+        append_attr(&mut data, vxlan_ids::IFLA_VXLAN_ID, &42u32.to_ne_bytes());
+        append_attr(&mut data, vxlan_ids::IFLA_VXLAN_GROUP6, &group.octets());
+        append_attr(
+            &mut data,
+            vxlan_ids::IFLA_VXLAN_PORT,
+            &4789u16.to_be_bytes(),
+        );
+        append_attr(
+            &mut data,
+            vxlan_ids::IFLA_VXLAN_AGEING,
+            &300u32.to_ne_bytes(),
+        );
+
+        let vx = link("vxlan", data).vxlan_info().unwrap();
+        assert_eq!(vx.vni, 42);
+        assert_eq!(vx.group, Some(IpAddr::V6(group)));
+        assert_eq!(vx.port, Some(4789));
+        assert_eq!(vx.ageing, Some(Duration::from_secs(300)));
+        assert!(!vx.learning);
+    }
+
+    #[test]
+    fn kind_info_covers_gtp_bridge_and_unknown_kinds() {
+        let mut data = Vec::new();
+        append_attr(&mut data, gtp_ids::IFLA_GTP_ROLE, &1u32.to_ne_bytes());
+        append_attr(
+            &mut data,
+            gtp_ids::IFLA_GTP_PDP_HASHSIZE,
+            &1024u32.to_ne_bytes(),
+        );
+        let gtp = link("gtp", data).gtp_info().unwrap();
+        assert_eq!(gtp.role, Some(GtpRole::Sgsn));
+        assert_eq!(gtp.pdp_hashsize, Some(1024));
+
+        let mut data = Vec::new();
+        append_attr(
+            &mut data,
+            br_ids::IFLA_BR_FORWARD_DELAY,
+            &1500u32.to_ne_bytes(),
+        );
+        append_attr(&mut data, br_ids::IFLA_BR_VLAN_FILTERING, &[1]);
+        let br = link("bridge", data).bridge_info().unwrap();
+        assert_eq!(br.forward_delay, Some(Duration::from_secs(15)));
+        assert!(br.vlan_filtering);
+
+        let veth = link("veth", Vec::new());
+        assert!(matches!(veth.kind_info(), Some(LinkKindInfo::Other(k)) if k == "veth"));
+        assert!(veth.vlan_info().is_none());
+        assert!(LinkMessage::default().kind_info().is_none());
+    }
+}
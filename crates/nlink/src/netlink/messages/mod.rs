@@ -15,17 +15,23 @@
 //! println!("Interface: {}", msg.header.ifa_index);
 //! ```
 
+mod addr_label;
 mod address;
 mod link;
+mod link_kind;
 mod neighbor;
+mod neighbor_table;
 mod nsid;
 mod route;
 mod rule;
 mod tc;
 
+pub use addr_label::*;
 pub use address::*;
 pub use link::*;
+pub use link_kind::*;
 pub use neighbor::*;
+pub use neighbor_table::*;
 pub use nsid::*;
 pub use route::*;
 pub use rule::*;
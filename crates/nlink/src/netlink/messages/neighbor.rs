@@ -193,6 +193,12 @@ impl NeighborMessage {
     pub fn is_proxy(&self) -> bool {
         self.header.ndm_flags & 0x08 != 0 // NTF_PROXY
     }
+
+    /// Check if this entry was learned by an external control plane
+    /// (EVPN, a switch driver) rather than by the kernel.
+    pub fn is_extern_learn(&self) -> bool {
+        self.header.ndm_flags & 0x10 != 0 // NTF_EXT_LEARNED
+    }
 }
 
 impl FromNetlink for NeighborMessage {
@@ -478,7 +484,7 @@ impl NeighborMessageBuilder {
 
     /// Set the neighbor state.
     pub fn state(mut self, state: NeighborState) -> Self {
-        self.msg.header.ndm_state = state as u16;
+        self.msg.header.ndm_state = state.number();
         self
     }
 
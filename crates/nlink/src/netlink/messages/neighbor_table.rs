@@ -0,0 +1,389 @@
+//! Strongly-typed neighbor table message (`RTM_NEWNEIGHTBL`).
+//!
+//! A `RTM_GETNEIGHTBL` dump returns, for each table (`arp_cache`,
+//! `ndisc_cache`, ...), one message carrying the table-wide settings and
+//! default parameters, followed by one message per device carrying only
+//! that device's parameters.
+
+use std::time::Duration;
+
+use winnow::{prelude::*, token::take};
+
+use crate::netlink::{
+    attr::AttrIter,
+    parse::{FromNetlink, PResult, parse_string_from_bytes},
+    types::neigh::{NdtMsg, ndta, ndtpa},
+};
+
+/// Strongly-typed neighbor table message with all attributes parsed.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct NeighborTableMessage {
+    /// Fixed-size header.
+    pub(crate) header: NdtMsg,
+    /// Table name (NDTA_NAME).
+    pub(crate) name: Option<String>,
+    /// gc_thresh1 (NDTA_THRESH1).
+    pub(crate) gc_thresh1: Option<u32>,
+    /// gc_thresh2 (NDTA_THRESH2).
+    pub(crate) gc_thresh2: Option<u32>,
+    /// gc_thresh3 (NDTA_THRESH3).
+    pub(crate) gc_thresh3: Option<u32>,
+    /// Garbage collection interval (NDTA_GC_INTERVAL).
+    pub(crate) gc_interval: Option<Duration>,
+    /// Table configuration (NDTA_CONFIG).
+    pub(crate) config: Option<NeighborTableConfig>,
+    /// Table statistics (NDTA_STATS).
+    pub(crate) stats: Option<NeighborTableStats>,
+    /// Default or per-device parameters (NDTA_PARMS).
+    pub(crate) parms: Option<NeighborTableParms>,
+}
+
+/// Neighbor table configuration (struct ndt_config).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NeighborTableConfig {
+    /// Length of the lookup key (address) in bytes.
+    pub key_len: u16,
+    /// Size of one entry in bytes.
+    pub entry_size: u16,
+    /// Number of entries in the table.
+    pub entries: u32,
+    /// Time since the last flush.
+    pub last_flush: Duration,
+    /// Time since the last reachable-time randomisation.
+    pub last_rand: Duration,
+    /// Hash seed.
+    pub hash_rnd: u32,
+    /// Hash mask.
+    pub hash_mask: u32,
+    /// Hash chain position of the garbage collector.
+    pub hash_chain_gc: u32,
+    /// Packets queued for proxying.
+    pub proxy_qlen: u32,
+}
+
+/// Neighbor table statistics (struct ndt_stats), summed over CPUs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NeighborTableStats {
+    /// Entries allocated.
+    pub allocs: u64,
+    /// Entries destroyed.
+    pub destroys: u64,
+    /// Times the hash table grew.
+    pub hash_grows: u64,
+    /// Failed resolutions.
+    pub res_failed: u64,
+    /// Lookups.
+    pub lookups: u64,
+    /// Lookups that found an entry.
+    pub hits: u64,
+    /// Multicast probes received.
+    pub rcv_probes_mcast: u64,
+    /// Unicast probes received.
+    pub rcv_probes_ucast: u64,
+    /// Periodic garbage collection runs.
+    pub periodic_gc_runs: u64,
+    /// Forced garbage collection runs (table above gc_thresh2/3).
+    pub forced_gc_runs: u64,
+    /// Times the table was full and an entry could not be created.
+    pub table_fulls: u64,
+}
+
+/// Neighbor table parameters (nested NDTPA_* attributes).
+///
+/// Each field is `None` when the kernel did not report it.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct NeighborTableParms {
+    /// Device these parameters belong to; `None` for the table defaults.
+    pub ifindex: Option<u32>,
+    /// Reference count.
+    pub refcnt: Option<u32>,
+    /// Current (randomised) reachable time.
+    pub reachable_time: Option<Duration>,
+    /// Base reachable time.
+    pub base_reachable_time: Option<Duration>,
+    /// Retransmit time between probes.
+    pub retrans_time: Option<Duration>,
+    /// Time after which a stale entry may be collected.
+    pub gc_stale_time: Option<Duration>,
+    /// Delay before the first probe of a stale entry.
+    pub delay_probe_time: Option<Duration>,
+    /// Unresolved queue length in packets.
+    pub queue_len: Option<u32>,
+    /// Unresolved queue length in bytes.
+    pub queue_len_bytes: Option<u32>,
+    /// Probes sent to the userspace resolver.
+    pub app_probes: Option<u32>,
+    /// Unicast probes.
+    pub ucast_probes: Option<u32>,
+    /// Multicast probes.
+    pub mcast_probes: Option<u32>,
+    /// Multicast re-probes.
+    pub mcast_reprobes: Option<u32>,
+    /// Random delay before answering anycast solicitations.
+    pub anycast_delay: Option<Duration>,
+    /// Random delay before answering proxied requests.
+    pub proxy_delay: Option<Duration>,
+    /// Proxy queue length in packets.
+    pub proxy_qlen: Option<u32>,
+    /// Minimum time between link-layer address updates.
+    pub locktime: Option<Duration>,
+    /// Probe interval for entries with `NTF_MANAGED`.
+    pub interval_probe_time: Option<Duration>,
+}
+
+impl NeighborTableMessage {
+    /// Create a new empty neighbor table message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the address family.
+    pub fn family(&self) -> u8 {
+        self.header.ndtm_family
+    }
+
+    /// Get the table name (`arp_cache`, `ndisc_cache`, ...).
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Get gc_thresh1: below this many entries the collector leaves the
+    /// table alone.
+    pub fn gc_thresh1(&self) -> Option<u32> {
+        self.gc_thresh1
+    }
+
+    /// Get gc_thresh2: the soft limit on entries.
+    pub fn gc_thresh2(&self) -> Option<u32> {
+        self.gc_thresh2
+    }
+
+    /// Get gc_thresh3: the hard limit on entries.
+    pub fn gc_thresh3(&self) -> Option<u32> {
+        self.gc_thresh3
+    }
+
+    /// Get the garbage collection interval.
+    pub fn gc_interval(&self) -> Option<Duration> {
+        self.gc_interval
+    }
+
+    /// Get the table configuration.
+    pub fn config(&self) -> Option<&NeighborTableConfig> {
+        self.config.as_ref()
+    }
+
+    /// Get the table statistics.
+    pub fn stats(&self) -> Option<&NeighborTableStats> {
+        self.stats.as_ref()
+    }
+
+    /// Get the parameters carried by this message.
+    pub fn parms(&self) -> Option<&NeighborTableParms> {
+        self.parms.as_ref()
+    }
+
+    /// Get the device this message's parameters belong to, or `None`
+    /// for the table-wide message (and for parameter sets not bound to
+    /// a device).
+    pub fn ifindex(&self) -> Option<u32> {
+        self.parms.as_ref()?.ifindex
+    }
+}
+
+fn u16_at(data: &[u8], off: usize) -> u16 {
+    u16::from_ne_bytes(data[off..off + 2].try_into().unwrap())
+}
+
+fn u32_at(data: &[u8], off: usize) -> u32 {
+    u32::from_ne_bytes(data[off..off + 4].try_into().unwrap())
+}
+
+fn u64_at(data: &[u8], off: usize) -> u64 {
+    u64::from_ne_bytes(data[off..off + 8].try_into().unwrap())
+}
+
+fn u32_attr(data: &[u8]) -> Option<u32> {
+    (data.len() >= 4).then(|| u32_at(data, 0))
+}
+
+fn msecs_attr(data: &[u8]) -> Option<Duration> {
+    (data.len() >= 8).then(|| Duration::from_millis(u64_at(data, 0)))
+}
+
+impl NeighborTableConfig {
+    /// Size of struct ndt_config.
+    const SIZE: usize = 32;
+
+    fn parse(data: &[u8]) -> Option<Self> {
+        (data.len() >= Self::SIZE).then(|| Self {
+            key_len: u16_at(data, 0),
+            entry_size: u16_at(data, 2),
+            entries: u32_at(data, 4),
+            last_flush: Duration::from_millis(u32_at(data, 8).into()),
+            last_rand: Duration::from_millis(u32_at(data, 12).into()),
+            hash_rnd: u32_at(data, 16),
+            hash_mask: u32_at(data, 20),
+            hash_chain_gc: u32_at(data, 24),
+            proxy_qlen: u32_at(data, 28),
+        })
+    }
+}
+
+impl NeighborTableStats {
+    /// Size of struct ndt_stats.
+    const SIZE: usize = 88;
+
+    fn parse(data: &[u8]) -> Option<Self> {
+        (data.len() >= Self::SIZE).then(|| Self {
+            allocs: u64_at(data, 0),
+            destroys: u64_at(data, 8),
+            hash_grows: u64_at(data, 16),
+            res_failed: u64_at(data, 24),
+            lookups: u64_at(data, 32),
+            hits: u64_at(data, 40),
+            rcv_probes_mcast: u64_at(data, 48),
+            rcv_probes_ucast: u64_at(data, 56),
+            periodic_gc_runs: u64_at(data, 64),
+            forced_gc_runs: u64_at(data, 72),
+            table_fulls: u64_at(data, 80),
+        })
+    }
+}
+
+impl NeighborTableParms {
+    fn parse(data: &[u8]) -> Self {
+        let mut parms = Self::default();
+        for (attr_type, payload) in AttrIter::new(data) {
+            match attr_type {
+                // The kernel sends 0 for the table defaults.
+                ndtpa::IFINDEX => parms.ifindex = u32_attr(payload).filter(|&i| i != 0),
+                ndtpa::REFCNT => parms.refcnt = u32_attr(payload),
+                ndtpa::REACHABLE_TIME => parms.reachable_time = msecs_attr(payload),
+                ndtpa::BASE_REACHABLE_TIME => parms.base_reachable_time = msecs_attr(payload),
+                ndtpa::RETRANS_TIME => parms.retrans_time = msecs_attr(payload),
+                ndtpa::GC_STALETIME => parms.gc_stale_time = msecs_attr(payload),
+                ndtpa::DELAY_PROBE_TIME => parms.delay_probe_time = msecs_attr(payload),
+                ndtpa::QUEUE_LEN => parms.queue_len = u32_attr(payload),
+                ndtpa::QUEUE_LENBYTES => parms.queue_len_bytes = u32_attr(payload),
+                ndtpa::APP_PROBES => parms.app_probes = u32_attr(payload),
+                ndtpa::UCAST_PROBES => parms.ucast_probes = u32_attr(payload),
+                ndtpa::MCAST_PROBES => parms.mcast_probes = u32_attr(payload),
+                ndtpa::MCAST_REPROBES => parms.mcast_reprobes = u32_attr(payload),
+                ndtpa::ANYCAST_DELAY => parms.anycast_delay = msecs_attr(payload),
+                ndtpa::PROXY_DELAY => parms.proxy_delay = msecs_attr(payload),
+                ndtpa::PROXY_QLEN => parms.proxy_qlen = u32_attr(payload),
+                ndtpa::LOCKTIME => parms.locktime = msecs_attr(payload),
+                ndtpa::INTERVAL_PROBE_TIME_MS => parms.interval_probe_time = msecs_attr(payload),
+                _ => {}
+            }
+        }
+        parms
+    }
+}
+
+impl FromNetlink for NeighborTableMessage {
+    fn write_dump_header(buf: &mut Vec<u8>) {
+        // RTM_GETNEIGHTBL requires an NdtMsg header
+        buf.extend_from_slice(NdtMsg::new().as_bytes());
+    }
+
+    fn parse(input: &mut &[u8]) -> PResult<Self> {
+        if input.len() < NdtMsg::SIZE {
+            return Err(winnow::error::ErrMode::Cut(
+                winnow::error::ContextError::new(),
+            ));
+        }
+
+        let header_bytes: &[u8] = take(NdtMsg::SIZE).parse_next(input)?;
+        let header = *NdtMsg::from_bytes(header_bytes)
+            .map_err(|_| winnow::error::ErrMode::Cut(winnow::error::ContextError::new()))?;
+
+        let mut msg = NeighborTableMessage {
+            header,
+            ..Default::default()
+        };
+
+        let attrs: &[u8] = take(input.len()).parse_next(input)?;
+        for (attr_type, payload) in AttrIter::new(attrs) {
+            match attr_type {
+                ndta::NAME => msg.name = Some(parse_string_from_bytes(payload)),
+                ndta::THRESH1 => msg.gc_thresh1 = u32_attr(payload),
+                ndta::THRESH2 => msg.gc_thresh2 = u32_attr(payload),
+                ndta::THRESH3 => msg.gc_thresh3 = u32_attr(payload),
+                ndta::GC_INTERVAL => msg.gc_interval = msecs_attr(payload),
+                ndta::CONFIG => msg.config = NeighborTableConfig::parse(payload),
+                ndta::STATS => msg.stats = NeighborTableStats::parse(payload),
+                ndta::PARMS => msg.parms = Some(NeighborTableParms::parse(payload)),
+                _ => {} // Ignore unknown attributes
+            }
+        }
+
+        Ok(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::builder::MessageBuilder;
+
+    #[test]
+    fn parses_table_and_device_messages() {
+        let mut b = MessageBuilder::new(0, 0);
+        b.append(&NdtMsg::new().with_family(2));
+        b.append_attr_str(ndta::NAME, "arp_cache");
+        b.append_attr_u32(ndta::THRESH1, 128);
+        b.append_attr_u32(ndta::THRESH3, 1024);
+        b.append_attr_u64(ndta::GC_INTERVAL, 30_000);
+        let mut config = vec![0u8; NeighborTableConfig::SIZE];
+        config[0..2].copy_from_slice(&4u16.to_ne_bytes());
+        config[4..8].copy_from_slice(&7u32.to_ne_bytes());
+        b.append_attr(ndta::CONFIG, &config);
+        let mut stats = vec![0u8; NeighborTableStats::SIZE];
+        stats[80..88].copy_from_slice(&3u64.to_ne_bytes());
+        b.append_attr(ndta::STATS, &stats);
+        let parms = b.nest_start(ndta::PARMS);
+        b.append_attr_u32(ndtpa::IFINDEX, 0);
+        b.append_attr_u64(ndtpa::BASE_REACHABLE_TIME, 30_000);
+        b.append_attr_u32(ndtpa::UCAST_PROBES, 3);
+        b.nest_end(parms);
+
+        let bytes = b.finish();
+        let msg = NeighborTableMessage::from_bytes(&bytes[16..]).unwrap();
+        assert_eq!(msg.family(), 2);
+        assert_eq!(msg.name(), Some("arp_cache"));
+        assert_eq!(msg.gc_thresh1(), Some(128));
+        assert_eq!(msg.gc_thresh2(), None);
+        assert_eq!(msg.gc_thresh3(), Some(1024));
+        assert_eq!(msg.gc_interval(), Some(Duration::from_secs(30)));
+        let config = msg.config().unwrap();
+        assert_eq!((config.key_len, config.entries), (4, 7));
+        assert_eq!(msg.stats().unwrap().table_fulls, 3);
+        let parms = msg.parms().unwrap();
+        assert_eq!(msg.ifindex(), None, "ifindex 0 is the table defaults");
+        assert_eq!(parms.base_reachable_time, Some(Duration::from_secs(30)));
+        assert_eq!(parms.ucast_probes, Some(3));
+        assert_eq!(parms.retrans_time, None);
+
+        let mut b = MessageBuilder::new(0, 0);
+        b.append(&NdtMsg::new().with_family(10));
+        b.append_attr_str(ndta::NAME, "ndisc_cache");
+        let parms = b.nest_start(ndta::PARMS);
+        b.append_attr_u32(ndtpa::IFINDEX, 3);
+        b.append_attr_u64(ndtpa::RETRANS_TIME, 1_000);
+        b.nest_end(parms);
+
+        let bytes = b.finish();
+        let msg = NeighborTableMessage::from_bytes(&bytes[16..]).unwrap();
+        assert_eq!(msg.ifindex(), Some(3));
+        assert_eq!(msg.gc_thresh1(), None);
+        assert!(msg.config().is_none());
+        assert_eq!(
+            msg.parms().unwrap().retrans_time,
+            Some(Duration::from_secs(1))
+        );
+    }
+}
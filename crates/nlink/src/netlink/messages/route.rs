@@ -1,6 +1,6 @@
 //! Strongly-typed route message.
 
-use std::net::IpAddr;
+use std::{net::IpAddr, time::Duration};
 
 use winnow::{prelude::*, token::take};
 
@@ -21,9 +21,13 @@ mod attr_ids {
     pub const RTA_PREFSRC: u16 = 7;
     /// Plan 202 — multipath nexthop chain (`RTA_MULTIPATH`).
     pub const RTA_MULTIPATH: u16 = 9;
+    pub const RTA_CACHEINFO: u16 = 12;
     pub const RTA_TABLE: u16 = 15;
+    pub const RTA_MARK: u16 = 16;
     pub const RTA_PREF: u16 = 20;
     pub const RTA_EXPIRES: u16 = 23;
+    pub const RTA_UID: u16 = 25;
+    pub const RTA_NH_ID: u16 = 30;
 }
 
 /// Header size of `struct rtnexthop`
@@ -61,6 +65,54 @@ pub struct RouteMessage {
     /// `None` if the route is single-path; `Some(vec)` with the
     /// parsed nexthop chain otherwise.
     pub(crate) multipath: Option<Vec<ParsedNextHop>>,
+    /// Nexthop object ID (`RTA_NH_ID`) for routes that point at a
+    /// nexthop or nexthop group instead of carrying their own.
+    pub(crate) nh_id: Option<u32>,
+    /// Firewall mark the lookup used (RTA_MARK). Route-get replies only.
+    pub(crate) mark: Option<u32>,
+    /// UID the lookup used (RTA_UID). Route-get replies only.
+    pub(crate) uid: Option<u32>,
+    /// Cache information (RTA_CACHEINFO). Parsed only; never written.
+    pub(crate) cache_info: Option<RouteCacheInfo>,
+}
+
+/// Route cache information (`struct rta_cacheinfo`), reported on
+/// route-get replies and cached/exception routes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RouteCacheInfo {
+    /// References held on the route.
+    pub users: u32,
+    /// Time since the route was last used.
+    pub last_use: Duration,
+    /// Time until the entry expires, `None` if it doesn't.
+    pub expires: Option<Duration>,
+    /// Error recorded on the route (negative errno), 0 if none.
+    pub error: i32,
+    /// Times the route was used.
+    pub used: u32,
+}
+
+impl RouteCacheInfo {
+    /// Size of `struct rta_cacheinfo`.
+    const SIZE: usize = 32;
+
+    fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::SIZE {
+            return None;
+        }
+        let word = |i: usize| u32::from_ne_bytes(data[i * 4..i * 4 + 4].try_into().unwrap());
+        // Times are clock_t (USER_HZ, 100 per second).
+        let ticks = |t: u32| Duration::from_millis(u64::from(t) * 10);
+        let expires = word(2) as i32;
+        Some(Self {
+            users: word(0),
+            last_use: ticks(word(1)),
+            expires: (expires > 0).then(|| ticks(expires as u32)),
+            error: word(3) as i32,
+            used: word(4),
+        })
+    }
 }
 
 /// One nexthop parsed from an `RTA_MULTIPATH` chain. Plan 202.
@@ -120,11 +172,34 @@ impl RouteMessage {
         RouteProtocol::from(self.header.rtm_protocol)
     }
 
+    /// Get the raw route protocol number; same as
+    /// `self.protocol().number()`.
+    pub fn protocol_id(&self) -> u8 {
+        self.header.rtm_protocol
+    }
+
+    /// Get the TOS selector.
+    pub fn tos(&self) -> u8 {
+        self.header.rtm_tos
+    }
+
     /// Get the route scope.
     pub fn scope(&self) -> RouteScope {
         RouteScope::from(self.header.rtm_scope)
     }
 
+    /// Get the route flags (`RTM_F_*`, see
+    /// [`rtm_flags`](crate::netlink::types::route::rtm_flags)).
+    pub fn flags(&self) -> u32 {
+        self.header.rtm_flags
+    }
+
+    /// Check if this is a cached clone rather than a FIB entry, as in
+    /// most `route_get` replies.
+    pub fn is_cloned(&self) -> bool {
+        self.header.rtm_flags & crate::netlink::types::route::rtm_flags::CLONED != 0
+    }
+
     /// Get the routing table ID.
     pub fn table_id(&self) -> u32 {
         self.table.unwrap_or(self.header.rtm_table as u32)
@@ -175,6 +250,18 @@ impl RouteMessage {
         self.expires
     }
 
+    /// Get the time left until the route expires.
+    ///
+    /// The kernel reports the lifetime of an expiring IPv6 route (one
+    /// learned from a router advertisement, say) in `RTA_CACHEINFO`;
+    /// `RTA_EXPIRES` is only set on messages built locally.
+    pub fn lifetime(&self) -> Option<Duration> {
+        self.cache_info
+            .as_ref()
+            .and_then(|cache| cache.expires)
+            .or_else(|| self.expires.map(|secs| Duration::from_secs(secs.into())))
+    }
+
     /// Get the multipath nexthop list parsed from
     /// `RTA_MULTIPATH`. Plan 202.
     ///
@@ -185,6 +272,27 @@ impl RouteMessage {
         self.multipath.as_deref()
     }
 
+    /// Get the nexthop object ID (`RTA_NH_ID`), for routes installed
+    /// with [`nexthop_group`](crate::netlink::route::Ipv4Route::nexthop_group).
+    pub fn nexthop_id(&self) -> Option<u32> {
+        self.nh_id
+    }
+
+    /// Get the firewall mark a route-get lookup used.
+    pub fn mark(&self) -> Option<u32> {
+        self.mark
+    }
+
+    /// Get the UID a route-get lookup used.
+    pub fn uid(&self) -> Option<u32> {
+        self.uid
+    }
+
+    /// Get the route cache information.
+    pub fn cache_info(&self) -> Option<&RouteCacheInfo> {
+        self.cache_info.as_ref()
+    }
+
     // =========================================================================
     // Boolean checks
     // =========================================================================
@@ -278,6 +386,18 @@ impl RouteMessage {
         self.protocol() == RouteProtocol::Kernel && self.scope() == RouteScope::Link
     }
 
+    /// Check if this route was learned from an IPv6 router advertisement.
+    ///
+    /// True for default and route-information routes (`proto ra`) and
+    /// for on-link prefix routes, which the kernel installs as
+    /// `proto kernel` with a lifetime taken from the advertised prefix.
+    pub fn is_router_advertised(&self) -> bool {
+        self.protocol() == RouteProtocol::Ra
+            || (self.is_ipv6()
+                && self.protocol() == RouteProtocol::Kernel
+                && self.lifetime().is_some())
+    }
+
     /// Get the device name using an interface name map.
     ///
     /// This is a convenience method for display purposes.
@@ -412,6 +532,18 @@ impl FromNetlink for RouteMessage {
                 attr_ids::RTA_EXPIRES if attr_data.len() >= 4 => {
                     msg.expires = Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
                 }
+                attr_ids::RTA_NH_ID if attr_data.len() >= 4 => {
+                    msg.nh_id = Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
+                }
+                attr_ids::RTA_MARK if attr_data.len() >= 4 => {
+                    msg.mark = Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
+                }
+                attr_ids::RTA_UID if attr_data.len() >= 4 => {
+                    msg.uid = Some(u32::from_ne_bytes(attr_data[..4].try_into().unwrap()));
+                }
+                attr_ids::RTA_CACHEINFO => {
+                    msg.cache_info = RouteCacheInfo::parse(attr_data);
+                }
                 attr_ids::RTA_MULTIPATH => {
                     // Plan 202 — parse the nexthop chain.
                     // Defensive guards live inside the helper:
@@ -531,6 +663,9 @@ impl ToNetlink for RouteMessage {
         if self.table.is_some() {
             len += nla_size(4);
         }
+        if self.nh_id.is_some() {
+            len += nla_size(4);
+        }
 
         len
     }
@@ -578,6 +713,9 @@ impl ToNetlink for RouteMessage {
         if let Some(ref nexthops) = self.multipath {
             write_attr_multipath(buf, attr_ids::RTA_MULTIPATH, nexthops);
         }
+        if let Some(id) = self.nh_id {
+            write_attr_u32(buf, attr_ids::RTA_NH_ID, id);
+        }
 
         Ok(buf.len() - start)
     }
@@ -748,19 +886,19 @@ impl RouteMessageBuilder {
 
     /// Set the route type.
     pub fn route_type(mut self, rt_type: RouteType) -> Self {
-        self.msg.header.rtm_type = rt_type as u8;
+        self.msg.header.rtm_type = rt_type.number();
         self
     }
 
     /// Set the route protocol (who installed it).
     pub fn protocol(mut self, protocol: RouteProtocol) -> Self {
-        self.msg.header.rtm_protocol = protocol as u8;
+        self.msg.header.rtm_protocol = protocol.number();
         self
     }
 
     /// Set the route scope.
     pub fn scope(mut self, scope: RouteScope) -> Self {
-        self.msg.header.rtm_scope = scope as u8;
+        self.msg.header.rtm_scope = scope.number();
         self
     }
 
@@ -807,6 +945,12 @@ impl RouteMessageBuilder {
         self
     }
 
+    /// Set the nexthop object ID (`RTA_NH_ID`).
+    pub fn nexthop_id(mut self, id: u32) -> Self {
+        self.msg.nh_id = Some(id);
+        self
+    }
+
     /// Build the message.
     pub fn build(self) -> RouteMessage {
         self.msg
@@ -881,6 +1025,90 @@ mod tests {
         assert_eq!(parsed.priority, Some(100));
     }
 
+    #[test]
+    fn nexthop_id_roundtrip() {
+        let original = RouteMessageBuilder::new()
+            .destination(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8)
+            .nexthop_id(100)
+            .build();
+
+        let mut buf = Vec::new();
+        original.write_to(&mut buf).unwrap();
+        assert_eq!(buf.len(), original.netlink_len());
+
+        let parsed = RouteMessage::parse(&mut buf.as_slice()).unwrap();
+        assert_eq!(parsed.nexthop_id(), Some(100));
+        assert_eq!(parsed.gateway(), None);
+    }
+
+    #[test]
+    fn parses_route_get_attributes() {
+        let mut buf = Vec::new();
+        RouteMessageBuilder::new()
+            .destination(IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)), 32)
+            .build()
+            .write_to(&mut buf)
+            .unwrap();
+        let mut attr = |kind: u16, payload: &[u8]| {
+            buf.extend_from_slice(&(4 + payload.len() as u16).to_ne_bytes());
+            buf.extend_from_slice(&kind.to_ne_bytes());
+            buf.extend_from_slice(payload);
+        };
+        attr(attr_ids::RTA_MARK, &0x10u32.to_ne_bytes());
+        attr(attr_ids::RTA_UID, &1000u32.to_ne_bytes());
+        // clntref, lastuse, expires, error, used, id, ts, tsage
+        let cache: Vec<u8> = [2u32, 150, 30_000, 0, 7, 0, 0, 0]
+            .iter()
+            .flat_map(|w| w.to_ne_bytes())
+            .collect();
+        attr(attr_ids::RTA_CACHEINFO, &cache);
+
+        let parsed = RouteMessage::parse(&mut buf.as_slice()).unwrap();
+        assert_eq!(parsed.mark(), Some(0x10));
+        assert_eq!(parsed.uid(), Some(1000));
+        let cache = parsed.cache_info().unwrap();
+        assert_eq!(cache.users, 2);
+        assert_eq!(cache.last_use, Duration::from_millis(1500));
+        assert_eq!(cache.expires, Some(Duration::from_secs(300)));
+        assert_eq!(cache.used, 7);
+    }
+
+    #[test]
+    fn router_advertised_routes() {
+        let v6 = |proto| {
+            RouteMessageBuilder::new()
+                .ipv6()
+                .destination("2001:db8::".parse().unwrap(), 64)
+                .protocol(proto)
+        };
+        let default_ra = RouteMessageBuilder::new()
+            .ipv6()
+            .protocol(RouteProtocol::Ra)
+            .pref(0)
+            .expires(1800)
+            .build();
+        assert!(default_ra.is_router_advertised());
+        assert!(!v6(RouteProtocol::Kernel).build().is_router_advertised());
+        let static_ = v6(RouteProtocol::Static).expires(60).build();
+        assert!(!static_.is_router_advertised());
+
+        // An on-link prefix route as dumped: the lifetime is in
+        // RTA_CACHEINFO (8640000 ticks = 86400s).
+        let mut buf = Vec::new();
+        let kernel = v6(RouteProtocol::Kernel).build();
+        kernel.write_to(&mut buf).unwrap();
+        let cache: Vec<u8> = [0u32, 0, 8_640_000, 0, 0, 0, 0, 0]
+            .iter()
+            .flat_map(|w| w.to_ne_bytes())
+            .collect();
+        buf.extend_from_slice(&(4 + cache.len() as u16).to_ne_bytes());
+        buf.extend_from_slice(&attr_ids::RTA_CACHEINFO.to_ne_bytes());
+        buf.extend_from_slice(&cache);
+        let prefix = RouteMessage::parse(&mut buf.as_slice()).unwrap();
+        assert_eq!(prefix.lifetime(), Some(Duration::from_secs(86400)));
+        assert!(prefix.is_router_advertised());
+    }
+
     // --------- Plan 202 — parse_multipath ---------
 
     /// Helper: build one IPv4 rtnexthop entry — 8-byte header
@@ -8,7 +8,10 @@ use zerocopy::FromBytes;
 use crate::netlink::{
     attr::NLA_TYPE_MASK,
     parse::{FromNetlink, PResult, parse_ip_addr},
-    types::rule::{FibRuleAction, FibRuleHdr, FibRulePortRange, FibRuleUidRange},
+    types::{
+        route::RouteProtocol,
+        rule::{FibRuleAction, FibRuleHdr, FibRulePortRange, FibRuleUidRange},
+    },
 };
 
 /// Attribute IDs for FRA_* constants.
@@ -127,6 +130,11 @@ impl RuleMessage {
         self.header.dst_len
     }
 
+    /// Get the TOS value matched by this rule.
+    pub fn tos(&self) -> u8 {
+        self.header.tos
+    }
+
     /// Get the rule action.
     pub fn action(&self) -> FibRuleAction {
         FibRuleAction::from(self.header.action)
@@ -248,8 +256,8 @@ impl RuleMessage {
     }
 
     /// Rule protocol (FRA_PROTOCOL), if present.
-    pub fn protocol(&self) -> Option<u8> {
-        self.protocol
+    pub fn protocol(&self) -> Option<RouteProtocol> {
+        self.protocol.map(RouteProtocol::from)
     }
 
     /// IP protocol for port matching (FRA_IP_PROTO), if present.
@@ -518,7 +526,7 @@ mod plan_231_tests {
         assert_eq!(r.suppress_ifgroup(), r.suppress_ifgroup);
         assert_eq!(r.suppress_prefixlen(), r.suppress_prefixlen);
         assert_eq!(r.l3mdev(), r.l3mdev);
-        assert_eq!(r.protocol(), r.protocol);
+        assert_eq!(r.protocol(), r.protocol.map(RouteProtocol::from));
         assert_eq!(r.ip_proto(), r.ip_proto);
     }
 }
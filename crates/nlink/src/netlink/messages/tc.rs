@@ -252,6 +252,12 @@ impl TcMessage {
         self.hw_offload
     }
 
+    /// Whether the qdisc or class is offloaded to hardware
+    /// (`TCA_HW_OFFLOAD` set), as `tc` reports with `offloaded`.
+    pub fn is_offloaded(&self) -> bool {
+        self.hw_offload.is_some_and(|v| v != 0)
+    }
+
     /// Get the ingress block index.
     pub fn ingress_block(&self) -> Option<u32> {
         self.ingress_block
@@ -776,7 +782,7 @@ impl FromNetlink for TcMessage {
 }
 
 /// Parse TCA_STATS2 nested attributes.
-fn parse_stats2(msg: &mut TcMessage, data: &[u8]) {
+pub(crate) fn parse_stats2(msg: &mut TcMessage, data: &[u8]) {
     let mut input = data;
 
     while !input.is_empty() && input.len() >= 4 {
@@ -0,0 +1,299 @@
+//! Port mirroring helper (`tc ... action mirred egress mirror`).
+//!
+//! [`PortMirror`] copies the traffic of one interface to another in a
+//! single call, the way a switch SPAN port does. It attaches a `clsact`
+//! qdisc to the source (reusing one that is already there) and hangs a
+//! filter off the ingress and/or egress hook whose only action is a
+//! `mirred` mirror to the target.
+//!
+//! # Topology
+//!
+//! ```text
+//! source -> clsact ─┬─ ingress hook: matchall|flower -> mirred mirror -> target
+//!                   └─ egress hook:  matchall|flower -> mirred mirror -> target
+//! ```
+//!
+//! Without a match every packet is copied (`cls_matchall`); with
+//! [`PortMirror::matching`] only packets a [`FlowerFilter`] accepts are.
+//! The mirror action ends in `TC_ACT_UNSPEC` ("continue"), so filters
+//! at later priorities on the same hook still see the packet — the
+//! mirror never changes how the source forwards it.
+//!
+//! The filters sit at a single priority per hook
+//! ([`DEFAULT_MIRROR_PRIORITY`] unless overridden), which is also how
+//! [`PortMirror::clear`] finds them again. Run several mirrors off one
+//! source by giving each its own priority.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::filter::FlowerFilter;
+//! use nlink::netlink::mirror::{MirrorDirection, PortMirror};
+//! use nlink::netlink::tc_handle::FilterPriority;
+//!
+//! // Copy everything eth0 sends and receives to the capture port.
+//! let span = PortMirror::new("eth0", "mon0");
+//! span.apply(&conn).await?;
+//!
+//! // Only HTTPS arriving on eth1, at its own priority.
+//! let https = PortMirror::new("eth1", "mon0")
+//!     .direction(MirrorDirection::Ingress)
+//!     .matching(FlowerFilter::new().ip_proto_tcp().dst_port(443))
+//!     .priority(FilterPriority::recipe(1));
+//! https.apply(&conn).await?;
+//!
+//! // Teardown.
+//! https.clear(&conn).await?;
+//! span.clear(&conn).await?;
+//! ```
+
+use super::{
+    Connection,
+    action::{ActionList, MirredAction},
+    error::{Error, Result},
+    filter::{FlowerFilter, MatchallFilter},
+    interface_ref::InterfaceRef,
+    protocol::Route,
+    tc::ClsactConfig,
+    tc_handle::{FilterPriority, TcHandle},
+    types::tc::action::TC_ACT_UNSPEC,
+};
+
+/// Filter parent of the clsact ingress hook
+/// (`TC_H_MAKE(TC_H_CLSACT, TC_H_MIN_INGRESS)`).
+const CLSACT_INGRESS: TcHandle = TcHandle::CLSACT;
+/// Filter parent of the clsact egress hook
+/// (`TC_H_MAKE(TC_H_CLSACT, TC_H_MIN_EGRESS)`).
+const CLSACT_EGRESS: TcHandle = TcHandle::CLSACT_EGRESS;
+
+/// Priority of the mirror filters unless [`PortMirror::priority`]
+/// overrides it: the first slot of the recipe band.
+pub const DEFAULT_MIRROR_PRIORITY: FilterPriority = FilterPriority::recipe(0);
+
+/// Which of the source's directions to copy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MirrorDirection {
+    /// Packets the source receives.
+    Ingress,
+    /// Packets the source transmits.
+    Egress,
+    /// Both directions.
+    #[default]
+    Both,
+}
+
+impl MirrorDirection {
+    /// The clsact hooks this direction attaches to.
+    fn hooks(self) -> &'static [TcHandle] {
+        match self {
+            Self::Ingress => &[CLSACT_INGRESS],
+            Self::Egress => &[CLSACT_EGRESS],
+            Self::Both => &[CLSACT_INGRESS, CLSACT_EGRESS],
+        }
+    }
+}
+
+/// Mirror the traffic of one interface to another.
+///
+/// See the [module docs](self) for the tc tree this builds.
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct PortMirror {
+    source: InterfaceRef,
+    target: InterfaceRef,
+    direction: MirrorDirection,
+    filter: Option<FlowerFilter>,
+    priority: FilterPriority,
+}
+
+impl PortMirror {
+    /// Mirror `source` to `target`, both by name, in both directions.
+    pub fn new(source: impl Into<String>, target: impl Into<String>) -> Self {
+        Self::with_ends(
+            InterfaceRef::Name(source.into()),
+            InterfaceRef::Name(target.into()),
+        )
+    }
+
+    /// Mirror by interface index (namespace-safe).
+    pub fn new_by_index(source: u32, target: u32) -> Self {
+        Self::with_ends(InterfaceRef::Index(source), InterfaceRef::Index(target))
+    }
+
+    fn with_ends(source: InterfaceRef, target: InterfaceRef) -> Self {
+        Self {
+            source,
+            target,
+            direction: MirrorDirection::Both,
+            filter: None,
+            priority: DEFAULT_MIRROR_PRIORITY,
+        }
+    }
+
+    /// Copy only one direction of the source's traffic.
+    pub fn direction(mut self, direction: MirrorDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Copy only packets `filter` matches.
+    ///
+    /// The filter's match keys (and its skip_hw/skip_sw flags) are used
+    /// as-is. Its priority is replaced by the mirror's, and any actions
+    /// on it are replaced by the mirror action.
+    pub fn matching(mut self, filter: FlowerFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Install the filters at `priority` instead of
+    /// [`DEFAULT_MIRROR_PRIORITY`].
+    pub fn priority(mut self, priority: FilterPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// The mirrored interface.
+    pub fn source(&self) -> &InterfaceRef {
+        &self.source
+    }
+
+    /// The interface copies are sent out of.
+    pub fn target(&self) -> &InterfaceRef {
+        &self.target
+    }
+
+    /// Install the mirror.
+    ///
+    /// Adds a `clsact` qdisc to the source unless one exists, then one
+    /// filter per hook. Re-applying replaces the mirror's own filters,
+    /// so changing the target or the match is a second `apply`.
+    #[tracing::instrument(level = "info", skip_all, fields(source = ?self.source, target = ?self.target))]
+    pub async fn apply(&self, conn: &Connection<Route>) -> Result<()> {
+        let source = conn.resolve_interface(&self.source).await?;
+        let target = conn.resolve_interface(&self.target).await?;
+        if source == target {
+            return Err(Error::InvalidMessage(format!(
+                "PortMirror: source and target are the same interface (ifindex {source})"
+            )));
+        }
+
+        // Reuse an existing clsact rather than tripping over EEXIST.
+        let qdiscs = conn.get_qdiscs_by_index(source).await?;
+        if !qdiscs.iter().any(|q| q.is_clsact()) {
+            conn.add_qdisc_by_index_full(source, TcHandle::INGRESS, None, ClsactConfig::new())
+                .await
+                .map_err(|e| e.with_context("PortMirror::apply: add clsact"))?;
+        }
+
+        let actions =
+            ActionList::new().with(MirredAction::mirror_by_index(target).action(TC_ACT_UNSPEC));
+        let priority = self.priority.as_u16();
+        for &hook in self.direction.hooks() {
+            // Replace whatever an earlier apply left at our priority.
+            del_mirror_filter(conn, source, hook, priority).await?;
+
+            let added = match &self.filter {
+                Some(flower) => {
+                    let flower = flower.clone().priority(priority).actions(actions.clone());
+                    conn.add_filter_by_index(source, hook, flower).await
+                }
+                None => {
+                    let all = MatchallFilter::new()
+                        .priority(priority)
+                        .actions(actions.clone());
+                    conn.add_filter_by_index(source, hook, all).await
+                }
+            };
+            // The hook exists by now, so ENOENT means the kernel has no
+            // such classifier.
+            added.map_err(|e| {
+                if e.is_not_found() || e.is_not_supported() {
+                    Error::NotSupported(format!(
+                        "classifier or act_mirred not available; try `modprobe cls_matchall \
+                         cls_flower act_mirred` (underlying: {e})"
+                    ))
+                } else {
+                    e.with_context(format!("PortMirror::apply: add filter at {hook}"))
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Remove the mirror.
+    ///
+    /// Deletes the filters at the mirror's priority on its hooks; a
+    /// missing filter or qdisc is not an error. The `clsact` qdisc is
+    /// removed too once no filters are left on either hook, so other
+    /// users of it (BPF programs, other mirrors) keep it alive.
+    #[tracing::instrument(level = "info", skip_all, fields(source = ?self.source))]
+    pub async fn clear(&self, conn: &Connection<Route>) -> Result<()> {
+        let source = conn.resolve_interface(&self.source).await?;
+        let priority = self.priority.as_u16();
+        for &hook in self.direction.hooks() {
+            del_mirror_filter(conn, source, hook, priority).await?;
+        }
+
+        let filters = conn.get_filters_by_index(source).await?;
+        let in_use = filters
+            .iter()
+            .any(|f| f.parent() == CLSACT_INGRESS || f.parent() == CLSACT_EGRESS);
+        if !in_use {
+            match conn.del_qdisc_by_index(source, TcHandle::INGRESS).await {
+                Ok(()) => {}
+                Err(e) if e.is_not_found() || e.is_invalid_argument() => {}
+                Err(e) => return Err(e.with_context("PortMirror::clear: del clsact")),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Delete the filter at `priority` on a clsact hook, whatever its
+/// protocol. Absent filters and a missing clsact are fine.
+async fn del_mirror_filter(
+    conn: &Connection<Route>,
+    ifindex: u32,
+    hook: TcHandle,
+    priority: u16,
+) -> Result<()> {
+    // Protocol 0 matches the filter at this priority regardless of the
+    // protocol it was added with.
+    match conn.del_filter_by_index(ifindex, hook, 0, priority).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.is_not_found() || e.is_invalid_argument() => Ok(()),
+        Err(e) => Err(e.with_context(format!("PortMirror: del filter at {hook}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn direction_selects_hooks() {
+        assert_eq!(MirrorDirection::Ingress.hooks(), &[CLSACT_INGRESS]);
+        assert_eq!(MirrorDirection::Egress.hooks(), &[CLSACT_EGRESS]);
+        assert_eq!(
+            MirrorDirection::Both.hooks(),
+            &[CLSACT_INGRESS, CLSACT_EGRESS]
+        );
+        assert_eq!(CLSACT_INGRESS.as_raw(), 0xFFFF_FFF2);
+    }
+
+    #[test]
+    fn builder_defaults() {
+        let m = PortMirror::new("eth0", "mon0");
+        assert_eq!(m.direction, MirrorDirection::Both);
+        assert_eq!(m.priority, DEFAULT_MIRROR_PRIORITY);
+        assert!(m.filter.is_none());
+        assert_eq!(m.source(), &InterfaceRef::Name("eth0".into()));
+
+        let m = PortMirror::new_by_index(2, 3)
+            .direction(MirrorDirection::Egress)
+            .priority(FilterPriority::recipe(4));
+        assert_eq!(m.target(), &InterfaceRef::Index(3));
+        assert_eq!(m.priority.as_u16(), 104);
+    }
+}
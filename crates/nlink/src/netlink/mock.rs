@@ -0,0 +1,511 @@
+//! In-memory [`NetlinkOps`] for unit tests (feature `mock`).
+//!
+//! [`MockConnection`] keeps links, addresses and routes in memory, so code
+//! written against [`NetlinkOps`] can be tested without root, namespaces
+//! or a kernel. It starts out like a host's namespace: the loopback
+//! interface, up, with `127.0.0.1/8` and `::1/128`.
+//!
+//! Configs go through the same encoders the real connection uses and
+//! are parsed back with the dump parsers, so what `get_links()` returns
+//! for a `DummyLink::new("d0").mtu(9000)` is what the kernel would
+//! report for the same request — name, kind, MTU, MAC. The mock only
+//! does what is asked: it invents no prefix routes, link-local
+//! addresses or carrier changes. Deleting a link does remove its
+//! addresses and the routes through it, and the other half of a veth
+//! or netkit pair, as the kernel does.
+//!
+//! Errors mirror the kernel's: `EEXIST` for duplicates, `ENODEV` or
+//! [`Error::InterfaceNotFound`] for a missing interface,
+//! `EADDRNOTAVAIL` for a missing address and `ESRCH` for a missing
+//! route.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{InterfaceRef, mock::MockConnection, ops::NetlinkOps};
+//! use nlink::netlink::link::DummyLink;
+//!
+//! #[tokio::test]
+//! async fn creates_the_service_interface() -> nlink::Result<()> {
+//!     let mock = MockConnection::new();
+//!     my_app::setup(&mock).await?; // takes `&dyn NetlinkOps`
+//!
+//!     let link = mock.get_link_by_name("svc0").await?.expect("created");
+//!     assert!(link.is_up());
+//!     assert_eq!(mock.get_addresses().await?.len(), 3); // lo's two + ours
+//!     Ok(())
+//! }
+//! ```
+
+use std::{net::IpAddr, sync::Mutex};
+
+use super::{
+    addr::AddressConfig,
+    builder::MessageBuilder,
+    error::{Error, Result},
+    fixtures,
+    interface_ref::InterfaceRef,
+    link::LinkConfig,
+    message::{NLMSG_HDRLEN, NlMsgType},
+    messages::{AddressMessage, LinkMessage, RouteMessage},
+    ops::{NetlinkOps, OpsFuture},
+    parse::FromNetlink,
+    route::{ResolvedRouteInterfaces, RouteConfig},
+    types::link::IfInfoMsg,
+};
+
+/// Flags the mock sets on a link brought up. It has no carrier to lose,
+/// so lower-up and running follow the admin state.
+const UP_FLAGS: u32 = (libc::IFF_UP | libc::IFF_RUNNING | libc::IFF_LOWER_UP) as u32;
+
+/// An in-memory stand-in for `Connection<Route>`.
+///
+/// See the [module docs](self).
+#[derive(Debug)]
+pub struct MockConnection {
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    links: Vec<LinkMessage>,
+    addresses: Vec<AddressMessage>,
+    routes: Vec<RouteMessage>,
+    next_ifindex: u32,
+}
+
+impl Default for MockConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockConnection {
+    /// A namespace with only `lo`, up, holding `127.0.0.1/8` and `::1/128`.
+    pub fn new() -> Self {
+        let parse = "loopback fixtures parse";
+        let state = State {
+            links: vec![LinkMessage::from_bytes(&fixtures::link_loopback()).expect(parse)],
+            addresses: vec![
+                AddressMessage::from_bytes(&fixtures::addr_loopback_v4()).expect(parse),
+                AddressMessage::from_bytes(&fixtures::addr_loopback_v6()).expect(parse),
+            ],
+            routes: Vec::new(),
+            next_ifindex: 2,
+        };
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Add a link as the kernel would report it, e.g. one parsed from
+    /// a capture. Replaces any link with the same index.
+    pub fn insert_link(&self, link: LinkMessage) {
+        let mut state = self.lock();
+        state.next_ifindex = state.next_ifindex.max(link.ifindex() + 1);
+        state.links.retain(|l| l.ifindex() != link.ifindex());
+        state.links.push(link);
+    }
+
+    /// Add an address as the kernel would report it.
+    pub fn insert_address(&self, address: AddressMessage) {
+        self.lock().addresses.push(address);
+    }
+
+    /// Add a route as the kernel would report it.
+    pub fn insert_route(&self, route: RouteMessage) {
+        self.lock().routes.push(route);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panic in another test thread mid-update leaves nothing
+        // half-written that matters here.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl State {
+    fn resolve(&self, iface: &InterfaceRef, operation: &str) -> Result<u32> {
+        let found = match iface {
+            InterfaceRef::Name(name) => self.links.iter().find(|l| l.name() == Some(name)),
+            InterfaceRef::Index(index) => self.links.iter().find(|l| l.ifindex() == *index),
+        };
+        match (found, iface) {
+            (Some(link), _) => Ok(link.ifindex()),
+            (None, InterfaceRef::Name(name)) => {
+                Err(Error::InterfaceNotFound { name: name.clone() })
+            }
+            (None, InterfaceRef::Index(_)) => {
+                Err(Error::from_errno_with_context(libc::ENODEV, operation))
+            }
+        }
+    }
+
+    fn link_mut(&mut self, iface: &InterfaceRef, operation: &str) -> Result<&mut LinkMessage> {
+        let index = self.resolve(iface, operation)?;
+        Ok(self
+            .links
+            .iter_mut()
+            .find(|l| l.ifindex() == index)
+            .expect("resolved above"))
+    }
+
+    fn has_name(&self, name: &str) -> bool {
+        self.links.iter().any(|l| l.name() == Some(name))
+    }
+
+    fn take_ifindex(&mut self) -> u32 {
+        let index = self.next_ifindex;
+        self.next_ifindex += 1;
+        index
+    }
+
+    fn add_link(&mut self, config: &dyn LinkConfig) -> Result<()> {
+        let operation = format!("add_link({}, kind={})", config.name(), config.kind());
+        crate::util::ifname::validate(config.name()).map_err(Error::Interface)?;
+        let names = [Some(config.name()), config.peer_name()];
+        if names.into_iter().flatten().any(|n| self.has_name(n)) {
+            return Err(Error::from_errno_with_context(libc::EEXIST, operation));
+        }
+        let parent = match config.parent_ref() {
+            Some(iface) => Some(self.resolve(iface, &operation)?),
+            None => None,
+        };
+
+        let mut link: LinkMessage = encode(NlMsgType::RTM_NEWLINK, |b| {
+            b.append(&IfInfoMsg::new());
+            config.write_to(b, parent);
+            Ok(())
+        })?;
+        link.header.ifi_index = self.take_ifindex() as i32;
+
+        let peer = config.peer_name().map(|peer_name| {
+            let mut peer = link.clone();
+            peer.name = Some(peer_name.to_string());
+            peer.header.ifi_index = self.take_ifindex() as i32;
+            peer.link = Some(link.ifindex());
+            link.link = Some(peer.ifindex());
+            peer
+        });
+        self.links.push(link);
+        self.links.extend(peer);
+        Ok(())
+    }
+
+    fn del_link(&mut self, iface: &InterfaceRef) -> Result<()> {
+        let index = self.resolve(iface, "del_link")?;
+        let peer = self
+            .links
+            .iter()
+            .find(|l| l.ifindex() == index)
+            .filter(|l| matches!(l.kind(), Some("veth" | "netkit")))
+            .and_then(LinkMessage::link);
+        let gone = |i: u32| i == index || Some(i) == peer;
+        self.links.retain(|l| !gone(l.ifindex()));
+        self.addresses.retain(|a| !gone(a.ifindex()));
+        self.routes.retain(|r| !r.oif().is_some_and(gone));
+        Ok(())
+    }
+
+    fn add_address(&mut self, config: &dyn AddressConfig) -> Result<()> {
+        let ifindex = self.resolve(config.interface_ref(), "add_address")?;
+        let address: AddressMessage =
+            encode(NlMsgType::RTM_NEWADDR, |b| config.write_add(b, ifindex))?;
+        let exists = self.addresses.iter().any(|a| {
+            a.ifindex() == ifindex
+                && a.prefix_len() == address.prefix_len()
+                && a.primary_address() == address.primary_address()
+        });
+        if exists {
+            return Err(Error::from_errno_with_context(libc::EEXIST, "add_address"));
+        }
+        self.addresses.push(address);
+        Ok(())
+    }
+
+    fn del_address(&mut self, iface: &InterfaceRef, address: IpAddr, prefix_len: u8) -> Result<()> {
+        let ifindex = self.resolve(iface, "del_address")?;
+        let before = self.addresses.len();
+        self.addresses.retain(|a| {
+            !(a.ifindex() == ifindex
+                && a.prefix_len() == prefix_len
+                && a.primary_address() == Some(&address))
+        });
+        if self.addresses.len() == before {
+            return Err(Error::from_errno_with_context(
+                libc::EADDRNOTAVAIL,
+                "del_address",
+            ));
+        }
+        Ok(())
+    }
+
+    fn resolve_route(&self, config: &dyn RouteConfig) -> Result<ResolvedRouteInterfaces> {
+        let oif = match config.device_ref() {
+            Some(iface) => Some(self.resolve(iface, "route")?),
+            None => None,
+        };
+        let multipath = config
+            .multipath_device_refs()
+            .into_iter()
+            .map(|iface| iface.map(|i| self.resolve(i, "route")).transpose())
+            .collect::<Result<_>>()?;
+        Ok(ResolvedRouteInterfaces { oif, multipath })
+    }
+
+    fn add_route(&mut self, config: &dyn RouteConfig) -> Result<()> {
+        let interfaces = self.resolve_route(config)?;
+        let route: RouteMessage = encode(NlMsgType::RTM_NEWROUTE, |b| {
+            config.write_add(b, &interfaces);
+            Ok(())
+        })?;
+        if self.routes.iter().any(|r| same_key(r, &route)) {
+            return Err(Error::from_errno_with_context(libc::EEXIST, "add_route"));
+        }
+        self.routes.push(route);
+        Ok(())
+    }
+
+    fn del_route(&mut self, config: &dyn RouteConfig) -> Result<()> {
+        let interfaces = self.resolve_route(config)?;
+        let wanted: RouteMessage = encode(NlMsgType::RTM_DELROUTE, |b| {
+            config.write_delete_with_interfaces(b, &interfaces);
+            Ok(())
+        })?;
+        // Like the kernel, attributes left out of the delete request
+        // match anything.
+        let position = self.routes.iter().position(|r| {
+            same_key(r, &wanted)
+                && wanted.oif().is_none_or(|oif| r.oif() == Some(oif))
+                && wanted.gateway().is_none_or(|gw| r.gateway() == Some(gw))
+        });
+        match position {
+            Some(i) => {
+                self.routes.remove(i);
+                Ok(())
+            }
+            None => Err(Error::from_errno_with_context(libc::ESRCH, "del_route")),
+        }
+    }
+}
+
+/// Run an encoder and parse its output back the way a dump reply would
+/// be parsed.
+fn encode<T: FromNetlink>(
+    msg_type: u16,
+    write: impl FnOnce(&mut MessageBuilder) -> Result<()>,
+) -> Result<T> {
+    let mut builder = MessageBuilder::new(msg_type, 0);
+    write(&mut builder)?;
+    T::from_bytes(&builder.as_bytes()[NLMSG_HDRLEN..])
+}
+
+/// Whether two routes collide on the kernel's key for `NLM_F_EXCL`.
+fn same_key(a: &RouteMessage, b: &RouteMessage) -> bool {
+    a.family() == b.family()
+        && a.dst_len() == b.dst_len()
+        && a.destination() == b.destination()
+        && a.table_id() == b.table_id()
+        && a.priority().unwrap_or(0) == b.priority().unwrap_or(0)
+}
+
+impl NetlinkOps for MockConnection {
+    fn get_links(&self) -> OpsFuture<'_, Vec<LinkMessage>> {
+        let links = self.lock().links.clone();
+        Box::pin(async move { Ok(links) })
+    }
+
+    fn get_link_by_name<'a>(&'a self, name: &'a str) -> OpsFuture<'a, Option<LinkMessage>> {
+        let link = self
+            .lock()
+            .links
+            .iter()
+            .find(|l| l.name() == Some(name))
+            .cloned();
+        Box::pin(async move { Ok(link) })
+    }
+
+    fn get_link_by_index(&self, index: u32) -> OpsFuture<'_, Option<LinkMessage>> {
+        let link = self
+            .lock()
+            .links
+            .iter()
+            .find(|l| l.ifindex() == index)
+            .cloned();
+        Box::pin(async move { Ok(link) })
+    }
+
+    fn add_link<'a>(&'a self, config: &'a dyn LinkConfig) -> OpsFuture<'a, ()> {
+        let result = self.lock().add_link(config);
+        Box::pin(async move { result })
+    }
+
+    fn del_link(&self, iface: InterfaceRef) -> OpsFuture<'_, ()> {
+        let result = self.lock().del_link(&iface);
+        Box::pin(async move { result })
+    }
+
+    fn set_link_up(&self, iface: InterfaceRef) -> OpsFuture<'_, ()> {
+        let result = self
+            .lock()
+            .link_mut(&iface, "set_link_up")
+            .map(|l| l.header.ifi_flags |= UP_FLAGS);
+        Box::pin(async move { result })
+    }
+
+    fn set_link_down(&self, iface: InterfaceRef) -> OpsFuture<'_, ()> {
+        let result = self
+            .lock()
+            .link_mut(&iface, "set_link_down")
+            .map(|l| l.header.ifi_flags &= !UP_FLAGS);
+        Box::pin(async move { result })
+    }
+
+    fn set_link_mtu(&self, iface: InterfaceRef, mtu: u32) -> OpsFuture<'_, ()> {
+        let result = self
+            .lock()
+            .link_mut(&iface, "set_link_mtu")
+            .map(|l| l.mtu = Some(mtu));
+        Box::pin(async move { result })
+    }
+
+    fn get_addresses(&self) -> OpsFuture<'_, Vec<AddressMessage>> {
+        let addresses = self.lock().addresses.clone();
+        Box::pin(async move { Ok(addresses) })
+    }
+
+    fn add_address<'a>(&'a self, config: &'a dyn AddressConfig) -> OpsFuture<'a, ()> {
+        let result = self.lock().add_address(config);
+        Box::pin(async move { result })
+    }
+
+    fn del_address(
+        &self,
+        iface: InterfaceRef,
+        address: IpAddr,
+        prefix_len: u8,
+    ) -> OpsFuture<'_, ()> {
+        let result = self.lock().del_address(&iface, address, prefix_len);
+        Box::pin(async move { result })
+    }
+
+    fn get_routes(&self) -> OpsFuture<'_, Vec<RouteMessage>> {
+        let routes = self.lock().routes.clone();
+        Box::pin(async move { Ok(routes) })
+    }
+
+    fn add_route<'a>(&'a self, config: &'a dyn RouteConfig) -> OpsFuture<'a, ()> {
+        let result = self.lock().add_route(config);
+        Box::pin(async move { result })
+    }
+
+    fn del_route<'a>(&'a self, config: &'a dyn RouteConfig) -> OpsFuture<'a, ()> {
+        let result = self.lock().del_route(config);
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+    use crate::netlink::{
+        addr::Ipv4Address,
+        link::{DummyLink, VethLink},
+        route::Ipv4Route,
+    };
+
+    /// Application code under test only sees the trait object.
+    async fn setup(ops: &dyn NetlinkOps) -> Result<()> {
+        ops.add_link(&DummyLink::new("svc0").mtu(9000)).await?;
+        ops.set_link_up(InterfaceRef::name("svc0")).await?;
+        ops.add_address(&Ipv4Address::new("svc0", Ipv4Addr::new(10, 0, 0, 1), 24))
+            .await?;
+        ops.add_route(&Ipv4Route::new("10.1.0.0", 16).gateway(Ipv4Addr::new(10, 0, 0, 254)))
+            .await
+    }
+
+    #[tokio::test]
+    async fn starts_with_loopback() {
+        let mock = MockConnection::new();
+        let lo = mock.get_link_by_index(1).await.unwrap().unwrap();
+        assert_eq!(lo.name(), Some("lo"));
+        assert!(lo.is_up());
+        assert_eq!(mock.get_addresses().await.unwrap().len(), 2);
+        assert!(mock.get_routes().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn configs_round_trip_through_the_encoders() {
+        let mock = MockConnection::new();
+        setup(&mock).await.unwrap();
+
+        let svc = mock.get_link_by_name("svc0").await.unwrap().unwrap();
+        assert_eq!(svc.ifindex(), 2);
+        assert_eq!(svc.kind(), Some("dummy"));
+        assert_eq!(svc.mtu(), Some(9000));
+        assert!(svc.is_up());
+
+        let addrs = mock.get_addresses().await.unwrap();
+        let ours = addrs.iter().find(|a| a.ifindex() == 2).unwrap();
+        assert_eq!(ours.primary_address(), Some(&IpAddr::from([10, 0, 0, 1])));
+        assert_eq!(ours.prefix_len(), 24);
+
+        let routes = mock.get_routes().await.unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].dst_len(), 16);
+        assert_eq!(routes[0].gateway(), Some(&IpAddr::from([10, 0, 0, 254])));
+
+        // Running it again fails the way the kernel would.
+        let err = setup(&mock).await.unwrap_err();
+        assert!(err.is_already_exists());
+    }
+
+    #[tokio::test]
+    async fn missing_objects_report_kernel_errors() {
+        let mock = MockConnection::new();
+        let err = mock
+            .set_link_up(InterfaceRef::name("nope"))
+            .await
+            .unwrap_err();
+        assert!(err.is_not_found());
+        let err = mock.del_link(InterfaceRef::index(42)).await.unwrap_err();
+        assert_eq!(err.errno(), Some(libc::ENODEV));
+
+        let lo = InterfaceRef::name("lo");
+        let err = mock
+            .del_address(lo, IpAddr::from([10, 9, 9, 9]), 8)
+            .await
+            .unwrap_err();
+        assert_eq!(err.errno(), Some(libc::EADDRNOTAVAIL));
+
+        let err = mock
+            .del_route(&Ipv4Route::new("10.1.0.0", 16))
+            .await
+            .unwrap_err();
+        assert_eq!(err.errno(), Some(libc::ESRCH));
+    }
+
+    #[tokio::test]
+    async fn deleting_a_veth_end_removes_the_pair_and_its_state() {
+        let mock = MockConnection::new();
+        mock.add_link(&VethLink::new("v0", "v1")).await.unwrap();
+        let v0 = mock.get_link_by_name("v0").await.unwrap().unwrap();
+        let v1 = mock.get_link_by_name("v1").await.unwrap().unwrap();
+        assert_eq!(v0.link(), Some(v1.ifindex()));
+        assert_eq!(v1.link(), Some(v0.ifindex()));
+
+        mock.add_address(&Ipv4Address::new("v1", Ipv4Addr::new(10, 0, 0, 2), 24))
+            .await
+            .unwrap();
+        mock.add_route(&Ipv4Route::new("10.2.0.0", 16).dev("v1"))
+            .await
+            .unwrap();
+
+        mock.del_link(InterfaceRef::name("v0")).await.unwrap();
+        assert_eq!(mock.get_links().await.unwrap().len(), 1, "only lo left");
+        assert_eq!(mock.get_addresses().await.unwrap().len(), 2);
+        assert!(mock.get_routes().await.unwrap().is_empty());
+    }
+}
@@ -74,6 +74,7 @@ pub mod attr;
 pub mod audit;
 pub mod batch;
 pub mod bridge_vlan;
+pub mod capture;
 mod builder;
 pub mod config;
 pub mod connection;
@@ -83,27 +84,37 @@ pub mod dispatcher;
 pub mod dump_stream;
 mod error;
 pub mod events;
+pub mod failover;
 pub mod fdb;
 pub mod fib_lookup;
 pub mod filter;
-#[cfg(test)]
-mod fixtures;
+#[cfg(any(test, feature = "fixtures"))]
+pub mod fixtures;
 pub mod genl;
+pub mod ifindex_resolver;
 pub mod impair;
 mod interface_ref;
 pub mod link;
+pub mod link_state;
+pub mod maddr;
 pub mod mdb;
 pub mod message;
 pub mod messages;
+pub mod mirror;
+#[cfg(feature = "mock")]
+pub mod mock;
 pub mod mpls;
 pub mod namespace;
 pub mod namespace_events;
 #[cfg(feature = "namespace_watcher")]
 pub mod namespace_watcher;
 pub mod neigh;
+pub mod neigh_monitor;
 pub mod netfilter;
+pub mod network_state;
 pub mod nexthop;
 pub mod nftables;
+pub mod ops;
 pub mod parse;
 pub mod parse_params;
 /// Property-based parser-robustness harnesses (Plan 193 phase 2-3).
@@ -132,6 +143,8 @@ pub mod tc;
 pub mod tc_handle;
 pub mod tc_options;
 pub mod tc_recipe;
+pub mod tc_tree;
+pub mod transaction;
 pub(crate) mod tc_recipe_internals;
 /// Byte-level assertions on encoder output, shared by the `tc` and
 /// `action` test modules.
@@ -139,6 +152,7 @@ pub(crate) mod tc_recipe_internals;
 pub(crate) mod test_support;
 pub mod types;
 pub mod uevent;
+pub mod wait;
 pub mod xfrm;
 
 pub use attr::{AttrIter, NlAttr};
@@ -153,6 +167,7 @@ pub use events::NetworkEvent;
 // Surface it at `nlink::netlink::Dpll` for path-symmetry with
 // `Wireguard`, `Macsec`, `Devlink`, etc.
 pub use genl::dpll::Dpll;
+pub use ifindex_resolver::IfIndexResolver;
 pub use interface_ref::InterfaceRef;
 pub use message::{MessageIter, NLMSG_HDRLEN, NlMsgHdr, NlMsgType};
 pub use namespace::NamespaceSpec;
@@ -161,6 +176,7 @@ pub use namespace_events::{NamespaceEventSubscriber, NamespaceNetlinkEvent};
 pub use namespace_watcher::{
     NamespaceEvent, NamespaceEventStream, NamespaceWatcher, NamespaceWatcherConfig,
 };
+pub use network_state::NetworkState;
 pub use parse::{FromNetlink, ToNetlink};
 // Macro-only re-export of the sealed-trait module; downstream
 // code should NOT name these paths directly. See
@@ -182,3 +198,4 @@ pub(crate) use socket::NL_BATCH_SIZE;
 pub use socket::{NetlinkSocket, Protocol, rtnetlink_groups};
 pub use stream::{EventSource, EventSubscription, OwnedEventStream};
 pub use tc_options::NetemParameter;
+pub use transaction::Transaction;
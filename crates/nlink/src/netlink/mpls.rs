@@ -52,12 +52,31 @@
 //! // Query MPLS routes
 //! let routes = conn.get_mpls_routes().await?;
 //! for route in &routes {
-//!     println!("Label {}: {:?}", route.label.0, route.action);
+//!     println!("Label {}: {:?}", route.label().0, route.action());
 //! }
 //!
 //! // Cleanup
 //! conn.del_mpls_route(100).await?;
 //! ```
+//!
+//! # Per-interface input and the label table size
+//!
+//! The kernel drops labelled packets unless the receiving interface has
+//! `net.mpls.conf.<dev>.input` set, and rejects routes for labels at or
+//! above `net.mpls.platform_labels` (which defaults to 0, i.e. no label
+//! table). Both are sysctls; [`Connection::set_mpls_input`] and
+//! [`Connection::set_mpls_platform_labels`] write them in the
+//! connection's namespace, and [`Connection::get_mpls_netconf`] reads the
+//! per-interface state back over `RTM_GETNETCONF`.
+//!
+//! ```ignore
+//! conn.set_mpls_platform_labels(1_048_575).await?;
+//! conn.set_mpls_input("eth0", true).await?;
+//!
+//! for conf in conn.get_mpls_netconf().await? {
+//!     println!("{:?}: input {}", conf.ifindex(), conf.input());
+//! }
+//! ```
 
 use std::net::IpAddr;
 
@@ -68,10 +87,12 @@ use super::{
     error::{Error, Result},
     interface_ref::InterfaceRef,
     message::{NLM_F_ACK, NLM_F_CREATE, NLM_F_DUMP, NLM_F_REQUEST, NLMSG_HDRLEN, NlMsgType},
+    namespace,
     protocol::Route,
+    sysctl,
     types::{
-        mpls::{MplsLabelEntry, lwtunnel_encap, mpls_label, mpls_tunnel},
-        route::{RtMsg, RtaAttr},
+        mpls::{MplsLabelEntry, lwtunnel_encap, mpls_label, mpls_tunnel, netconfa},
+        route::{RouteProtocol, RtMsg, RtaAttr},
     },
 };
 
@@ -312,6 +333,47 @@ impl MplsAction {
 // MplsRoute
 // ============================================================================
 
+/// One nexthop of a multipath MPLS route (`RTA_MULTIPATH`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MplsNexthop {
+    /// Output interface index (`rtnh_ifindex`).
+    pub(crate) oif: u32,
+    /// Kernel `rtnh_flags` byte (`RTNH_F_*`).
+    pub(crate) flags: u8,
+    /// Next hop address.
+    pub(crate) via: Option<IpAddr>,
+    /// Outgoing label stack; empty for pop.
+    pub(crate) labels: Vec<MplsLabel>,
+}
+
+impl MplsNexthop {
+    /// `RTNH_F_DEAD` — the nexthop is unusable.
+    pub const FLAG_DEAD: u8 = 1;
+    /// `RTNH_F_LINKDOWN` — the output link has no carrier.
+    pub const FLAG_LINKDOWN: u8 = 16;
+
+    /// Output interface index.
+    pub fn oif(&self) -> u32 {
+        self.oif
+    }
+
+    /// Raw `RTNH_F_*` flags.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// Next-hop address, if reported.
+    pub fn via(&self) -> Option<IpAddr> {
+        self.via
+    }
+
+    /// Outgoing label stack, outermost first; empty for pop.
+    pub fn labels(&self) -> &[MplsLabel] {
+        &self.labels
+    }
+}
+
 /// A parsed MPLS route.
 ///
 /// Fields are `pub(crate)`; consumers read via the per-field
@@ -330,7 +392,9 @@ pub struct MplsRoute {
     /// Next hop address.
     pub(crate) via: Option<IpAddr>,
     /// Route protocol.
-    pub(crate) protocol: u8,
+    pub(crate) protocol: RouteProtocol,
+    /// Nexthops of a multipath route (`RTA_MULTIPATH`).
+    pub(crate) multipath: Option<Vec<MplsNexthop>>,
 }
 
 impl MplsRoute {
@@ -340,6 +404,9 @@ impl MplsRoute {
     }
 
     /// Forwarding action (pop / swap / …).
+    ///
+    /// For a multipath route each nexthop carries its own label stack;
+    /// see [`multipath`](Self::multipath).
     pub fn action(&self) -> &MplsAction {
         &self.action
     }
@@ -354,11 +421,20 @@ impl MplsRoute {
         self.via
     }
 
-    /// Route protocol byte (`RTPROT_*` from `linux/rtnetlink.h`).
-    pub fn protocol(&self) -> u8 {
+    /// Route protocol (who installed it).
+    pub fn protocol(&self) -> RouteProtocol {
         self.protocol
     }
 
+    /// The nexthops of a multipath route.
+    ///
+    /// `None` for single-path routes, where [`oif`](Self::oif),
+    /// [`via`](Self::via) and [`action`](Self::action) describe the
+    /// egress.
+    pub fn multipath(&self) -> Option<&[MplsNexthop]> {
+        self.multipath.as_deref()
+    }
+
     /// Parse an MPLS route from netlink message payload.
     pub fn parse(data: &[u8]) -> Result<Self> {
         if data.len() < RtMsg::SIZE {
@@ -369,12 +445,21 @@ impl MplsRoute {
         }
 
         let rtmsg = RtMsg::from_bytes(data)?;
+        // Without `mpls_router` loaded the kernel answers an AF_MPLS dump
+        // with every route it has; don't read IP prefixes as labels.
+        if rtmsg.rtm_family != AF_MPLS {
+            return Err(Error::InvalidMessage(format!(
+                "not an MPLS route (family {})",
+                rtmsg.rtm_family
+            )));
+        }
         let attrs_data = &data[RtMsg::SIZE..];
 
         let mut label = MplsLabel(0);
         let mut action = MplsAction::Pop;
         let mut oif = None;
         let mut via = None;
+        let mut multipath = None;
 
         for (attr_type, payload) in AttrIter::new(attrs_data) {
             match RtaAttr::from(attr_type) {
@@ -384,46 +469,18 @@ impl MplsRoute {
                     }
                 }
                 RtaAttr::Newdst => {
-                    // Parse outgoing label stack
-                    let mut out_labels = Vec::new();
-                    let mut offset = 0;
-                    while offset + 4 <= payload.len() {
-                        if let Some(entry) = MplsLabelEntry::from_bytes(&payload[offset..]) {
-                            out_labels.push(MplsLabel(entry.label()));
-                            if entry.is_bos() {
-                                break;
-                            }
-                        }
-                        offset += 4;
-                    }
+                    let out_labels = parse_label_stack(payload);
                     if !out_labels.is_empty() {
                         action = MplsAction::Swap(out_labels);
                     }
                 }
-                RtaAttr::Oif
-                    if payload.len() >= 4 => {
-                        oif = Some(u32::from_ne_bytes(
-                            payload[..4].try_into().unwrap_or([0; 4]),
-                        ));
-                    }
-                RtaAttr::Via
-                    // RTA_VIA: { family(2), addr(4 or 16) }
-                    if payload.len() >= 6 => {
-                        let family = u16::from_ne_bytes(payload[..2].try_into().unwrap_or([0; 2]));
-                        match family as i32 {
-                            libc::AF_INET if payload.len() >= 6 => {
-                                let addr_bytes: [u8; 4] =
-                                    payload[2..6].try_into().unwrap_or([0; 4]);
-                                via = Some(IpAddr::from(addr_bytes));
-                            }
-                            libc::AF_INET6 if payload.len() >= 18 => {
-                                let addr_bytes: [u8; 16] =
-                                    payload[2..18].try_into().unwrap_or([0; 16]);
-                                via = Some(IpAddr::from(addr_bytes));
-                            }
-                            _ => {}
-                        }
-                    }
+                RtaAttr::Oif if payload.len() >= 4 => {
+                    oif = Some(u32::from_ne_bytes(
+                        payload[..4].try_into().unwrap_or([0; 4]),
+                    ));
+                }
+                RtaAttr::Via => via = parse_via(payload),
+                RtaAttr::Multipath => multipath = Some(parse_multipath(payload)),
                 _ => {}
             }
         }
@@ -433,11 +490,149 @@ impl MplsRoute {
             action,
             oif,
             via,
-            protocol: rtmsg.rtm_protocol,
+            protocol: RouteProtocol::from(rtmsg.rtm_protocol),
+            multipath,
         })
     }
 }
 
+/// Parse an `RTA_NEWDST` label stack, outermost first.
+fn parse_label_stack(payload: &[u8]) -> Vec<MplsLabel> {
+    let mut labels = Vec::new();
+    for chunk in payload.chunks_exact(MplsLabelEntry::SIZE) {
+        if let Some(entry) = MplsLabelEntry::from_bytes(chunk) {
+            labels.push(MplsLabel(entry.label()));
+            if entry.is_bos() {
+                break;
+            }
+        }
+    }
+    labels
+}
+
+/// Parse an `RTA_VIA` payload: `{ family: u16, addr: [u8; 4 or 16] }`.
+fn parse_via(payload: &[u8]) -> Option<IpAddr> {
+    let family = u16::from_ne_bytes(payload.get(..2)?.try_into().ok()?);
+    match family as i32 {
+        libc::AF_INET => {
+            let addr: [u8; 4] = payload.get(2..6)?.try_into().ok()?;
+            Some(IpAddr::from(addr))
+        }
+        libc::AF_INET6 => {
+            let addr: [u8; 16] = payload.get(2..18)?.try_into().ok()?;
+            Some(IpAddr::from(addr))
+        }
+        _ => None,
+    }
+}
+
+/// Walk an `RTA_MULTIPATH` payload of `struct rtnexthop` entries, each
+/// followed by its nested `RTA_NEWDST` / `RTA_VIA`.
+fn parse_multipath(data: &[u8]) -> Vec<MplsNexthop> {
+    /// `sizeof(struct rtnexthop)`.
+    const RTNH_HDRLEN: usize = 8;
+
+    let mut out = Vec::new();
+    let mut offset = 0;
+    while offset + RTNH_HDRLEN <= data.len() {
+        let rtnh_len = u16::from_ne_bytes([data[offset], data[offset + 1]]) as usize;
+        // A short or overrunning entry ends the walk (and `rtnh_len == 0`
+        // would never advance).
+        if rtnh_len < RTNH_HDRLEN || offset + rtnh_len > data.len() {
+            break;
+        }
+        let mut nexthop = MplsNexthop {
+            oif: u32::from_ne_bytes(data[offset + 4..offset + 8].try_into().unwrap_or([0; 4])),
+            flags: data[offset + 2],
+            via: None,
+            labels: Vec::new(),
+        };
+        for (attr_type, payload) in AttrIter::new(&data[offset + RTNH_HDRLEN..offset + rtnh_len]) {
+            match RtaAttr::from(attr_type) {
+                RtaAttr::Newdst => nexthop.labels = parse_label_stack(payload),
+                RtaAttr::Via => nexthop.via = parse_via(payload),
+                _ => {}
+            }
+        }
+        out.push(nexthop);
+        offset += (rtnh_len + 3) & !3;
+    }
+    out
+}
+
+// ============================================================================
+// MplsNetconf
+// ============================================================================
+
+/// Per-interface MPLS configuration, as reported by `RTM_GETNETCONF`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct MplsNetconf {
+    /// Interface index, or `NETCONFA_IFINDEX_ALL` / `_DEFAULT`.
+    pub(crate) ifindex: i32,
+    /// `net.mpls.conf.<dev>.input`.
+    pub(crate) input: bool,
+}
+
+impl MplsNetconf {
+    /// Interface index, or `None` for the `all` and `default`
+    /// pseudo-devices.
+    pub fn ifindex(&self) -> Option<u32> {
+        u32::try_from(self.ifindex).ok()
+    }
+
+    /// Whether this entry is the `all` pseudo-device.
+    pub fn is_all(&self) -> bool {
+        self.ifindex == netconfa::IFINDEX_ALL
+    }
+
+    /// Whether this entry is the `default` pseudo-device (the value new
+    /// interfaces start with).
+    pub fn is_default(&self) -> bool {
+        self.ifindex == netconfa::IFINDEX_DEFAULT
+    }
+
+    /// Whether labelled packets received on the interface are accepted.
+    pub fn input(&self) -> bool {
+        self.input
+    }
+
+    /// Parse a netconf message payload (`struct netconfmsg` + attributes).
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        // `struct netconfmsg` is a single family byte, padded to 4.
+        const NETCONFMSG_LEN: usize = 4;
+        if data.len() < NETCONFMSG_LEN {
+            return Err(Error::Truncated {
+                expected: NETCONFMSG_LEN,
+                actual: data.len(),
+            });
+        }
+        if data[0] != AF_MPLS {
+            return Err(Error::InvalidMessage(format!(
+                "not an MPLS netconf message (family {})",
+                data[0]
+            )));
+        }
+
+        let mut conf = Self {
+            ifindex: 0,
+            input: false,
+        };
+        for (attr_type, payload) in AttrIter::new(&data[NETCONFMSG_LEN..]) {
+            let Some(value) = payload.get(..4).and_then(|b| b.try_into().ok()) else {
+                continue;
+            };
+            let value = i32::from_ne_bytes(value);
+            match attr_type {
+                netconfa::IFINDEX => conf.ifindex = value,
+                netconfa::INPUT => conf.input = value != 0,
+                _ => {}
+            }
+        }
+        Ok(conf)
+    }
+}
+
 // ============================================================================
 // MplsRouteBuilder
 // ============================================================================
@@ -606,7 +801,7 @@ impl Connection<Route> {
     /// ```ignore
     /// let routes = conn.get_mpls_routes().await?;
     /// for route in &routes {
-    ///     println!("Label {}: {:?}", route.label.0, route.action);
+    ///     println!("Label {}: {:?}", route.label().0, route.action());
     /// }
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_mpls_routes"))]
@@ -655,10 +850,15 @@ impl Connection<Route> {
             NlMsgType::RTM_NEWROUTE,
             NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE,
         );
+        let label = route_builder.label;
         route_builder.write_to(&mut msg, ifindex);
-        self.send_ack(msg)
-            .await
-            .map_err(|e| e.with_context("add_mpls_route"))
+        match self.send_ack(msg).await {
+            Ok(()) => Ok(()),
+            Err(e) => Err(self
+                .explain_label_error(e, label)
+                .await
+                .with_context("add_mpls_route")),
+        }
     }
 
     /// Replace an MPLS route (add or update).
@@ -669,10 +869,31 @@ impl Connection<Route> {
             NlMsgType::RTM_NEWROUTE,
             NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE,
         );
+        let label = route_builder.label;
         route_builder.write_to(&mut msg, ifindex);
-        self.send_ack(msg)
-            .await
-            .map_err(|e| e.with_context("replace_mpls_route"))
+        match self.send_ack(msg).await {
+            Ok(()) => Ok(()),
+            Err(e) => Err(self
+                .explain_label_error(e, label)
+                .await
+                .with_context("replace_mpls_route")),
+        }
+    }
+
+    /// The kernel answers a route for a label outside the platform label
+    /// table with a bare `EINVAL`; name the cause when that is what
+    /// happened.
+    async fn explain_label_error(&self, err: Error, label: u32) -> Error {
+        if !err.is_invalid_argument() {
+            return err;
+        }
+        match self.mpls_platform_labels().await {
+            Ok(count) if label >= count => Error::InvalidMessage(format!(
+                "MPLS label {label} is outside the platform label table \
+                 (net.mpls.platform_labels = {count})"
+            )),
+            _ => err,
+        }
     }
 
     /// Delete an MPLS route by label.
@@ -697,6 +918,92 @@ impl Connection<Route> {
             .await
             .map_err(|e| e.with_context("del_mpls_route"))
     }
+
+    /// Get the per-interface MPLS configuration (`RTM_GETNETCONF`).
+    ///
+    /// Returns one entry per MPLS-capable interface, plus the `all` and
+    /// `default` pseudo-devices when the kernel reports them. Requires the
+    /// `mpls_router` module.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_mpls_netconf"))]
+    pub async fn get_mpls_netconf(&self) -> Result<Vec<MplsNetconf>> {
+        let mut builder =
+            MessageBuilder::new(NlMsgType::RTM_GETNETCONF, NLM_F_REQUEST | NLM_F_DUMP);
+        // `struct netconfmsg`: the family byte, padded to 4.
+        builder.append_bytes(&[AF_MPLS, 0, 0, 0]);
+
+        let responses = self
+            .send_dump(builder)
+            .await
+            .map_err(|e| e.with_context("get_mpls_netconf"))?;
+
+        let mut confs = Vec::new();
+        for data in responses {
+            if data.len() > NLMSG_HDRLEN
+                && let Ok(conf) = MplsNetconf::parse(&data[NLMSG_HDRLEN..])
+            {
+                confs.push(conf);
+            }
+        }
+        Ok(confs)
+    }
+
+    /// Enable or disable MPLS input on an interface
+    /// (`net.mpls.conf.<dev>.input`).
+    ///
+    /// Labelled packets arriving on an interface without input enabled are
+    /// dropped. Accepts either an interface name or index via
+    /// [`InterfaceRef`]; the sysctl is written in the connection's
+    /// namespace.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_mpls_input"))]
+    pub async fn set_mpls_input(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        enabled: bool,
+    ) -> Result<()> {
+        let dev = match iface.into() {
+            InterfaceRef::Name(name) => name,
+            InterfaceRef::Index(ifindex) => self
+                .get_link_by_index(ifindex)
+                .await?
+                .and_then(|link| link.name().map(str::to_string))
+                .ok_or_else(|| Error::InterfaceNotFound {
+                    name: format!("ifindex {ifindex}"),
+                })?,
+        };
+        let value = if enabled { "1" } else { "0" };
+        namespace::run_in_connection_namespace(self, move || {
+            sysctl::set_conf("mpls", &dev, "input", value)
+        })
+    }
+
+    /// Get the size of the MPLS label table (`net.mpls.platform_labels`)
+    /// in the connection's namespace.
+    ///
+    /// Routes can only be installed for labels below this value; the
+    /// kernel default of 0 disables MPLS forwarding entirely.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "mpls_platform_labels"))]
+    pub async fn mpls_platform_labels(&self) -> Result<u32> {
+        let value = namespace::run_in_connection_namespace(self, || {
+            sysctl::get("net.mpls.platform_labels")
+        })?;
+        value.parse().map_err(|_| {
+            Error::InvalidMessage(format!(
+                "unexpected net.mpls.platform_labels value {value:?}"
+            ))
+        })
+    }
+
+    /// Set the size of the MPLS label table (`net.mpls.platform_labels`)
+    /// in the connection's namespace.
+    ///
+    /// Shrinking the table removes routes for labels that no longer fit.
+    /// Values above [`mpls_label::MAX`] + 1 are rejected by the kernel.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_mpls_platform_labels"))]
+    pub async fn set_mpls_platform_labels(&self, count: u32) -> Result<()> {
+        namespace::run_in_connection_namespace(self, move || {
+            sysctl::set("net.mpls.platform_labels", &count.to_string())
+        })
+    }
 }
 
 #[cfg(test)]
@@ -796,4 +1103,110 @@ mod tests {
         assert!(!MplsAction::Pop.is_swap());
         assert!(MplsAction::Swap(vec![MplsLabel(100)]).is_swap());
     }
+
+    /// Append one netlink attribute (header + payload, padded to 4).
+    fn push_attr(buf: &mut Vec<u8>, kind: u16, payload: &[u8]) {
+        buf.extend_from_slice(&((4 + payload.len()) as u16).to_ne_bytes());
+        buf.extend_from_slice(&kind.to_ne_bytes());
+        buf.extend_from_slice(payload);
+        buf.resize((buf.len() + 3) & !3, 0);
+    }
+
+    fn via_v4(addr: [u8; 4]) -> Vec<u8> {
+        let mut via = (libc::AF_INET as u16).to_ne_bytes().to_vec();
+        via.extend_from_slice(&addr);
+        via
+    }
+
+    fn rtnexthop(flags: u8, ifindex: u32, attrs: &[u8]) -> Vec<u8> {
+        let mut nh = ((8 + attrs.len()) as u16).to_ne_bytes().to_vec();
+        nh.push(flags);
+        nh.push(0); // hops
+        nh.extend_from_slice(&ifindex.to_ne_bytes());
+        nh.extend_from_slice(attrs);
+        nh
+    }
+
+    #[test]
+    fn parses_multipath_route() {
+        let mut hop1 = Vec::new();
+        let mut stack = MplsLabelEntry::new(200).as_bytes().to_vec();
+        stack.extend_from_slice(MplsLabelEntry::bottom(300, 0).as_bytes());
+        push_attr(&mut hop1, RtaAttr::Newdst as u16, &stack);
+        push_attr(&mut hop1, RtaAttr::Via as u16, &via_v4([10, 0, 0, 2]));
+        let mut hop2 = Vec::new();
+        push_attr(&mut hop2, RtaAttr::Via as u16, &via_v4([10, 0, 1, 2]));
+
+        let mut multipath = rtnexthop(0, 3, &hop1);
+        multipath.extend(rtnexthop(MplsNexthop::FLAG_LINKDOWN, 4, &hop2));
+
+        let mut rtmsg = RtMsg::new().with_family(AF_MPLS).with_dst_len(20);
+        rtmsg.rtm_protocol = u8::from(RouteProtocol::Static);
+        let mut data = rtmsg.as_bytes().to_vec();
+        push_attr(
+            &mut data,
+            RtaAttr::Dst as u16,
+            MplsLabelEntry::bottom(100, 0).as_bytes(),
+        );
+        push_attr(&mut data, RtaAttr::Multipath as u16, &multipath);
+
+        let route = MplsRoute::parse(&data).unwrap();
+        assert_eq!(route.label(), MplsLabel(100));
+        assert_eq!(route.protocol(), RouteProtocol::Static);
+        assert!(route.oif().is_none());
+
+        let hops = route.multipath().unwrap();
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].oif(), 3);
+        assert_eq!(hops[0].labels(), &[MplsLabel(200), MplsLabel(300)]);
+        assert_eq!(hops[0].via(), Some(IpAddr::from([10, 0, 0, 2])));
+        assert_eq!(hops[1].oif(), 4);
+        assert!(hops[1].labels().is_empty());
+        assert_eq!(hops[1].flags(), MplsNexthop::FLAG_LINKDOWN);
+    }
+
+    #[test]
+    fn multipath_stops_at_malformed_nexthop() {
+        // A zero rtnh_len must not loop forever or yield an entry.
+        assert!(parse_multipath(&[0; 16]).is_empty());
+        // An entry claiming more than the payload holds is dropped.
+        let mut nh = rtnexthop(0, 1, &[]);
+        nh[0] = 64;
+        assert!(parse_multipath(&nh).is_empty());
+    }
+
+    #[test]
+    fn parses_netconf() {
+        let mut data = vec![AF_MPLS, 0, 0, 0];
+        push_attr(&mut data, netconfa::IFINDEX, &7i32.to_ne_bytes());
+        push_attr(&mut data, netconfa::INPUT, &1i32.to_ne_bytes());
+        let conf = MplsNetconf::parse(&data).unwrap();
+        assert_eq!(conf.ifindex(), Some(7));
+        assert!(conf.input());
+        assert!(!conf.is_all());
+
+        let mut data = vec![AF_MPLS, 0, 0, 0];
+        push_attr(
+            &mut data,
+            netconfa::IFINDEX,
+            &netconfa::IFINDEX_DEFAULT.to_ne_bytes(),
+        );
+        let conf = MplsNetconf::parse(&data).unwrap();
+        assert!(conf.is_default());
+        assert_eq!(conf.ifindex(), None);
+        assert!(!conf.input());
+
+        assert!(MplsNetconf::parse(&[AF_MPLS]).is_err());
+        assert!(MplsNetconf::parse(&[libc::AF_INET as u8, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn rejects_ip_routes() {
+        let mut data = RtMsg::new()
+            .with_family(libc::AF_INET as u8)
+            .as_bytes()
+            .to_vec();
+        push_attr(&mut data, RtaAttr::Dst as u16, &[10, 0, 0, 0]);
+        assert!(MplsRoute::parse(&data).is_err());
+    }
 }
@@ -393,12 +393,90 @@ impl NamespaceFd {
     }
 }
 
+impl NamespaceFd {
+    /// Identify the namespace this handle refers to.
+    pub fn id(&self) -> Result<NamespaceId> {
+        NamespaceId::of_file(&self.file)
+    }
+}
+
 impl AsRawFd for NamespaceFd {
     fn as_raw_fd(&self) -> RawFd {
         self.file.as_raw_fd()
     }
 }
 
+/// Identity of a network namespace: the device and inode of its `nsfs`
+/// file.
+///
+/// Two handles refer to the same namespace exactly when their ids are
+/// equal, whichever path or fd they were opened through. Displays the way
+/// `readlink /proc/self/ns/net` does, e.g. `net:[4026531840]`.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::{Connection, Route, namespace::{self, NamespaceId}};
+///
+/// let conn: Connection<Route> = namespace::connection_for("myns")?;
+/// assert_eq!(conn.current_namespace()?, namespace::open("myns")?.id()?);
+/// assert_ne!(conn.current_namespace()?, NamespaceId::of_thread()?);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NamespaceId {
+    dev: u64,
+    ino: u64,
+}
+
+impl NamespaceId {
+    /// Identify the namespace behind a namespace file, such as
+    /// `/var/run/netns/<name>` or `/proc/<pid>/ns/net`.
+    pub fn of_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let meta = std::fs::metadata(path).map_err(|e| namespace_open_error(path, e))?;
+        Ok(Self::from_metadata(&meta))
+    }
+
+    /// Identify the network namespace the calling thread is in.
+    ///
+    /// This is the namespace lookups such as [`crate::util::get_ifindex`]
+    /// reflect, which is not necessarily the one a [`Connection`] is
+    /// bound to.
+    pub fn of_thread() -> Result<Self> {
+        Self::of_path("/proc/thread-self/ns/net")
+    }
+
+    pub(crate) fn of_file(file: &File) -> Result<Self> {
+        Ok(Self::from_metadata(&file.metadata()?))
+    }
+
+    fn from_metadata(meta: &std::fs::Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        Self {
+            dev: meta.dev(),
+            ino: meta.ino(),
+        }
+    }
+
+    /// The namespace's inode number, as shown by `ls -i` on its file and
+    /// by `lsns`.
+    pub fn ino(&self) -> u64 {
+        self.ino
+    }
+
+    /// The device of the `nsfs` filesystem the inode lives on.
+    pub fn dev(&self) -> u64 {
+        self.dev
+    }
+}
+
+impl std::fmt::Display for NamespaceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "net:[{}]", self.ino)
+    }
+}
+
 /// Enter a network namespace temporarily.
 ///
 /// This function switches the current thread to the specified network namespace.
@@ -1077,6 +1155,21 @@ where
     }
 }
 
+/// Run `f` on a worker thread in the network namespace `conn`'s socket
+/// lives in. See [`run_in_namespace_thread`].
+pub(crate) fn run_in_connection_namespace<P, T, F>(conn: &Connection<P>, f: F) -> Result<T>
+where
+    P: ProtocolState,
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let ns = conn.namespace_fd()?;
+    // The worker opens the namespace through our fd table; `ns` stays
+    // open until it has joined.
+    let path = PathBuf::from(format!("/proc/self/fd/{}", ns.as_raw_fd()));
+    run_in_namespace_thread(path, f)
+}
+
 /// Read a sysctl value inside a named namespace.
 ///
 /// Reads the value from `/proc/sys/` on a dedicated worker thread entered
@@ -1168,6 +1261,40 @@ pub fn set_sysctls_path<P: AsRef<Path>>(path: P, entries: &[(&str, &str)]) -> Re
     })
 }
 
+/// Read a per-interface sysctl (`net.<proto>.conf.<dev>.<name>`) inside a
+/// named namespace.
+///
+/// Like [`get_sysctl`], but goes through [`sysctl::get_conf`](super::sysctl::get_conf)
+/// so device names containing dots (`eth0.100`) resolve correctly.
+pub fn get_sysctl_conf(ns_name: &str, proto: &str, dev: &str, name: &str) -> Result<String> {
+    let (proto, dev, name) = (proto.to_owned(), dev.to_owned(), name.to_owned());
+    run_in_namespace_thread(PathBuf::from(NETNS_RUN_DIR).join(ns_name), move || {
+        super::sysctl::get_conf(&proto, &dev, &name)
+    })
+}
+
+/// Set a per-interface sysctl (`net.<proto>.conf.<dev>.<name>`) inside a
+/// named namespace.
+///
+/// See [`get_sysctl_conf`] for details. Requires root or `CAP_SYS_ADMIN`.
+pub fn set_sysctl_conf(
+    ns_name: &str,
+    proto: &str,
+    dev: &str,
+    name: &str,
+    value: &str,
+) -> Result<()> {
+    let (proto, dev, name, value) = (
+        proto.to_owned(),
+        dev.to_owned(),
+        name.to_owned(),
+        value.to_owned(),
+    );
+    run_in_namespace_thread(PathBuf::from(NETNS_RUN_DIR).join(ns_name), move || {
+        super::sysctl::set_conf(&proto, &dev, &name, &value)
+    })
+}
+
 // ─────────────────────────────────────────────────
 // Process spawning (namespace-aware)
 // ─────────────────────────────────────────────────
@@ -1479,6 +1606,20 @@ mod tests {
         assert_eq!(NETNS_RUN_DIR, "/var/run/netns");
     }
 
+    #[test]
+    fn namespace_id_of_thread_matches_process() {
+        // No test moves its thread, so every thread shares the process netns.
+        let thread = NamespaceId::of_thread().unwrap();
+        assert_eq!(thread, NamespaceId::of_path("/proc/self/ns/net").unwrap());
+        assert_eq!(thread.to_string(), format!("net:[{}]", thread.ino()));
+    }
+
+    #[test]
+    fn namespace_id_of_missing_path_is_not_found() {
+        let err = NamespaceId::of_path("/definitely/not/a/netns").unwrap_err();
+        assert!(err.is_not_found());
+    }
+
     #[test]
     fn test_list_namespaces() {
         // This should not fail even if the directory doesn't exist
@@ -45,7 +45,10 @@
 //! ).await?;
 //! ```
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
 
 use super::{
     builder::MessageBuilder,
@@ -54,7 +57,7 @@ use super::{
     interface_ref::InterfaceRef,
     message::{NLM_F_ACK, NLM_F_REQUEST, NlMsgType},
     protocol::Route,
-    types::neigh::{NdMsg, NdaAttr, NeighborState, nud},
+    types::neigh::{NdMsg, NdaAttr, NdtMsg, NeighborState, ndta, ndtpa, nud},
 };
 
 /// NLM_F_CREATE flag
@@ -255,7 +258,7 @@ impl Neighbor {
 
     /// Set the neighbor state.
     pub fn state(mut self, state: NeighborState) -> Self {
-        self.state = state as u16;
+        self.state = state.number();
         self
     }
 
@@ -301,6 +304,16 @@ impl Neighbor {
         self
     }
 
+    /// Set `NTF_USE`: rather than writing the entry, make the kernel
+    /// resolve it as if a packet were being sent to it (`ip neigh ...
+    /// use`). State and link-layer address are ignored.
+    ///
+    /// See [`Connection::probe_neighbor`] to also wait for the result.
+    pub fn use_entry(mut self) -> Self {
+        self.flags |= ntf::USE;
+        self
+    }
+
     /// Set VLAN ID (for bridge FDB entries).
     pub fn vlan(mut self, vlan_id: u16) -> Self {
         self.vlan = Some(vlan_id);
@@ -402,6 +415,216 @@ impl NeighborConfig for Neighbor {
     }
 }
 
+// ============================================================================
+// Neighbor Table Parameters
+// ============================================================================
+
+/// Changes to a neighbor table (`ip ntable change`).
+///
+/// Tables are named: `arp_cache` for IPv4, `ndisc_cache` for IPv6. Without
+/// [`dev`](Self::dev) the per-table defaults are changed; with it, only that
+/// device's parameters. Unset values are left alone.
+///
+/// The gc thresholds and interval are global: the kernel only accepts them
+/// from the initial network namespace and answers ENOENT elsewhere.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::time::Duration;
+/// use nlink::netlink::neigh::NeighborTableParams;
+///
+/// conn.set_neigh_table_params(
+///     NeighborTableParams::arp()
+///         .dev("eth0")
+///         .base_reachable_time(Duration::from_secs(60))
+///         .ucast_probes(5),
+/// )
+/// .await?;
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "builders do nothing unless used"]
+pub struct NeighborTableParams {
+    name: String,
+    interface: Option<InterfaceRef>,
+    gc_thresh: [Option<u32>; 3],
+    gc_interval: Option<Duration>,
+    parms: Vec<(u16, TableValue)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum TableValue {
+    U32(u32),
+    Msecs(Duration),
+}
+
+impl NeighborTableParams {
+    /// Change the table called `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            interface: None,
+            gc_thresh: [None; 3],
+            gc_interval: None,
+            parms: Vec::new(),
+        }
+    }
+
+    /// Change the IPv4 ARP table (`arp_cache`).
+    pub fn arp() -> Self {
+        Self::new("arp_cache")
+    }
+
+    /// Change the IPv6 neighbor discovery table (`ndisc_cache`).
+    pub fn ndisc() -> Self {
+        Self::new("ndisc_cache")
+    }
+
+    /// Change one device's parameters instead of the table defaults.
+    pub fn dev(mut self, iface: impl Into<InterfaceRef>) -> Self {
+        self.interface = Some(iface.into());
+        self
+    }
+
+    /// Set gc_thresh1: below this many entries nothing is collected.
+    pub fn gc_thresh1(mut self, n: u32) -> Self {
+        self.gc_thresh[0] = Some(n);
+        self
+    }
+
+    /// Set gc_thresh2: the soft limit on entries.
+    pub fn gc_thresh2(mut self, n: u32) -> Self {
+        self.gc_thresh[1] = Some(n);
+        self
+    }
+
+    /// Set gc_thresh3: the hard limit on entries.
+    pub fn gc_thresh3(mut self, n: u32) -> Self {
+        self.gc_thresh[2] = Some(n);
+        self
+    }
+
+    /// Set the garbage collection interval.
+    pub fn gc_interval(mut self, interval: Duration) -> Self {
+        self.gc_interval = Some(interval);
+        self
+    }
+
+    /// Set the base reachable time.
+    pub fn base_reachable_time(self, time: Duration) -> Self {
+        self.msecs(ndtpa::BASE_REACHABLE_TIME, time)
+    }
+
+    /// Set the retransmit time between probes.
+    pub fn retrans_time(self, time: Duration) -> Self {
+        self.msecs(ndtpa::RETRANS_TIME, time)
+    }
+
+    /// Set the time after which a stale entry may be collected.
+    pub fn gc_stale_time(self, time: Duration) -> Self {
+        self.msecs(ndtpa::GC_STALETIME, time)
+    }
+
+    /// Set the delay before the first probe of a stale entry.
+    pub fn delay_probe_time(self, time: Duration) -> Self {
+        self.msecs(ndtpa::DELAY_PROBE_TIME, time)
+    }
+
+    /// Set the unresolved queue length in packets.
+    pub fn queue_len(self, packets: u32) -> Self {
+        self.u32(ndtpa::QUEUE_LEN, packets)
+    }
+
+    /// Set the unresolved queue length in bytes.
+    pub fn queue_len_bytes(self, bytes: u32) -> Self {
+        self.u32(ndtpa::QUEUE_LENBYTES, bytes)
+    }
+
+    /// Set the number of probes sent to the userspace resolver.
+    pub fn app_probes(self, n: u32) -> Self {
+        self.u32(ndtpa::APP_PROBES, n)
+    }
+
+    /// Set the number of unicast probes.
+    pub fn ucast_probes(self, n: u32) -> Self {
+        self.u32(ndtpa::UCAST_PROBES, n)
+    }
+
+    /// Set the number of multicast probes.
+    pub fn mcast_probes(self, n: u32) -> Self {
+        self.u32(ndtpa::MCAST_PROBES, n)
+    }
+
+    /// Set the number of multicast re-probes.
+    pub fn mcast_reprobes(self, n: u32) -> Self {
+        self.u32(ndtpa::MCAST_REPROBES, n)
+    }
+
+    /// Set the random delay before answering anycast solicitations.
+    pub fn anycast_delay(self, time: Duration) -> Self {
+        self.msecs(ndtpa::ANYCAST_DELAY, time)
+    }
+
+    /// Set the random delay before answering proxied requests.
+    pub fn proxy_delay(self, time: Duration) -> Self {
+        self.msecs(ndtpa::PROXY_DELAY, time)
+    }
+
+    /// Set the proxy queue length in packets.
+    pub fn proxy_qlen(self, packets: u32) -> Self {
+        self.u32(ndtpa::PROXY_QLEN, packets)
+    }
+
+    /// Set the minimum time between link-layer address updates.
+    pub fn locktime(self, time: Duration) -> Self {
+        self.msecs(ndtpa::LOCKTIME, time)
+    }
+
+    /// Check whether nothing would be changed.
+    pub fn is_empty(&self) -> bool {
+        self.gc_thresh.iter().all(Option::is_none)
+            && self.gc_interval.is_none()
+            && self.parms.is_empty()
+    }
+
+    fn u32(mut self, attr: u16, value: u32) -> Self {
+        self.parms.push((attr, TableValue::U32(value)));
+        self
+    }
+
+    fn msecs(mut self, attr: u16, value: Duration) -> Self {
+        self.parms.push((attr, TableValue::Msecs(value)));
+        self
+    }
+
+    fn write_to(&self, builder: &mut MessageBuilder, ifindex: Option<u32>) {
+        builder.append(&NdtMsg::new());
+        builder.append_attr_str(ndta::NAME, &self.name);
+        let thresh = [ndta::THRESH1, ndta::THRESH2, ndta::THRESH3];
+        for (attr, value) in thresh.into_iter().zip(self.gc_thresh) {
+            if let Some(value) = value {
+                builder.append_attr_u32(attr, value);
+            }
+        }
+        if let Some(interval) = self.gc_interval {
+            builder.append_attr_u64(ndta::GC_INTERVAL, interval.as_millis() as u64);
+        }
+        if ifindex.is_some() || !self.parms.is_empty() {
+            let nest = builder.nest_start(ndta::PARMS);
+            if let Some(ifindex) = ifindex {
+                builder.append_attr_u32(ndtpa::IFINDEX, ifindex);
+            }
+            for &(attr, value) in &self.parms {
+                match value {
+                    TableValue::U32(v) => builder.append_attr_u32(attr, v),
+                    TableValue::Msecs(d) => builder.append_attr_u64(attr, d.as_millis() as u64),
+                }
+            }
+            builder.nest_end(nest);
+        }
+    }
+}
+
 // ============================================================================
 // Connection Methods
 // ============================================================================
@@ -583,42 +806,66 @@ impl Connection<Route> {
         self.replace_neighbor(neigh).await
     }
 
-    /// Flush all neighbor entries for an interface.
+    /// Flush the neighbor entries of an interface whose state is in
+    /// `state_mask` (`nud::*` bits).
+    ///
+    /// Returns the number of entries removed. [`nud::FLUSHABLE`] matches
+    /// `ip neigh flush`, which keeps permanent and NOARP entries;
+    /// [`nud::ALL`] removes everything. Proxy entries are never flushed.
     ///
     /// # Example
     ///
     /// ```ignore
-    /// conn.flush_neighbors("eth0").await?;
+    /// use nlink::netlink::types::neigh::nud;
+    ///
+    /// let removed = conn.flush_neighbors("eth0", nud::STALE | nud::FAILED).await?;
     /// ```
     #[tracing::instrument(level = "debug", skip_all, fields(method = "flush_neighbors"))]
-    pub async fn flush_neighbors(&self, ifname: impl Into<InterfaceRef>) -> Result<()> {
-        let ifindex = self.resolve_interface(&ifname.into()).await?;
-        self.flush_neighbors_by_index(ifindex).await
+    pub async fn flush_neighbors(
+        &self,
+        ifname: impl Into<InterfaceRef>,
+        state_mask: u16,
+    ) -> Result<usize> {
+        self.with_interface(&ifname.into(), |ifindex| {
+            self.flush_neighbors_by_index(ifindex, state_mask)
+        })
+        .await
     }
 
-    /// Flush all neighbor entries for an interface by index.
+    /// Flush the neighbor entries of an interface by index whose state is
+    /// in `state_mask`.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "flush_neighbors_by_index"))]
-    pub async fn flush_neighbors_by_index(&self, ifindex: u32) -> Result<()> {
+    pub async fn flush_neighbors_by_index(&self, ifindex: u32, state_mask: u16) -> Result<usize> {
         let neighbors = self.get_neighbors_by_index(ifindex).await?;
 
+        let mut removed = 0;
         for neigh in neighbors {
+            if neigh.state().number() & state_mask == 0 {
+                continue;
+            }
             if let Some(dest) = neigh.destination {
-                // Skip permanent entries unless explicitly requested
-                // (matching iproute2 behavior)
-                if neigh.state() == NeighborState::Permanent {
-                    continue;
-                }
-
-                if let Err(e) = self.del_neighbor(Neighbor::with_index(ifindex, dest)).await {
-                    // Ignore "not found" errors (race condition)
-                    if !e.is_not_found() {
-                        return Err(e);
-                    }
+                match self.del_neighbor(Neighbor::with_index(ifindex, dest)).await {
+                    Ok(()) => removed += 1,
+                    // Already gone (expired or removed by someone else).
+                    Err(e) if e.is_not_found() => {}
+                    Err(e) => return Err(e),
                 }
             }
         }
 
-        Ok(())
+        Ok(removed)
+    }
+
+    /// Get the proxy ARP/NDP entries of every interface.
+    ///
+    /// Proxy entries live in a separate table the kernel only dumps when
+    /// asked for `NTF_PROXY`; [`get_neighbors`](Self::get_neighbors)
+    /// never returns them.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_proxy_neighbors"))]
+    pub async fn get_proxy_neighbors(&self) -> Result<Vec<super::messages::NeighborMessage>> {
+        let mut builder = super::connection::dump_request(NlMsgType::RTM_GETNEIGH);
+        builder.append(&NdMsg::new().with_flags(ntf::PROXY));
+        self.dump_typed_request(builder).await
     }
 
     /// Add a proxy ARP entry.
@@ -666,15 +913,134 @@ impl Connection<Route> {
     }
 
     /// Get neighbor entries for an interface by index.
+    ///
+    /// The kernel filters the dump (`NDA_IFINDEX`), so entries on
+    /// other interfaces are never sent.
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_neighbors_by_index"))]
     pub async fn get_neighbors_by_index(
         &self,
         ifindex: u32,
     ) -> Result<Vec<super::messages::NeighborMessage>> {
-        let neighbors = self.get_neighbors().await?;
+        let neighbors = self
+            .dump_neighbors_filtered(NdaAttr::Ifindex, ifindex)
+            .await?;
         Ok(neighbors
             .into_iter()
             .filter(|n| n.ifindex() == ifindex)
             .collect())
     }
+
+    /// Get the neighbor entries of every interface enslaved to `master`,
+    /// such as the ports of a bridge or the members of a VRF.
+    ///
+    /// The kernel filters the dump (`NDA_MASTER`, Linux 4.15+).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let neighbors = conn.get_neighbors_by_master("vrf-blue").await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_neighbors_by_master"))]
+    pub async fn get_neighbors_by_master(
+        &self,
+        master: impl Into<InterfaceRef>,
+    ) -> Result<Vec<super::messages::NeighborMessage>> {
+        let master = self.resolve_interface(&master.into()).await?;
+        self.dump_neighbors_filtered(NdaAttr::Master, master).await
+    }
+
+    /// Get the neighbor tables and their parameters (`ip ntable show`).
+    ///
+    /// Each table yields one message with its thresholds, configuration,
+    /// statistics and default parameters, then one message per device
+    /// with that device's parameters
+    /// ([`ifindex`](super::messages::NeighborTableMessage::ifindex) set).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for table in conn.get_neigh_tables().await? {
+    ///     if table.ifindex().is_none() {
+    ///         println!("{:?} gc_thresh3={:?}", table.name(), table.gc_thresh3());
+    ///     }
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_neigh_tables"))]
+    pub async fn get_neigh_tables(&self) -> Result<Vec<super::messages::NeighborTableMessage>> {
+        self.dump_typed(NlMsgType::RTM_GETNEIGHTBL).await
+    }
+
+    /// Change neighbor table parameters (`ip ntable change`).
+    ///
+    /// Sends nothing when `params` is empty. An unknown table name is
+    /// reported as ENOENT.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "set_neigh_table_params"))]
+    pub async fn set_neigh_table_params(&self, params: NeighborTableParams) -> Result<()> {
+        if params.is_empty() {
+            return Ok(());
+        }
+        let ifindex = match &params.interface {
+            Some(iface) => Some(self.resolve_interface(iface).await?),
+            None => None,
+        };
+
+        let mut builder =
+            MessageBuilder::new(NlMsgType::RTM_SETNEIGHTBL, NLM_F_REQUEST | NLM_F_ACK);
+        params.write_to(&mut builder, ifindex);
+        self.send_ack(builder)
+            .await
+            .map_err(|e| e.with_context("set_neigh_table_params"))
+    }
+
+    /// Dump neighbors with one `u32` filter attribute. Strict-checking
+    /// kernels require `ndm_ifindex` to be zero in dump requests, so the
+    /// interface filter travels as an attribute too.
+    async fn dump_neighbors_filtered(
+        &self,
+        attr: NdaAttr,
+        ifindex: u32,
+    ) -> Result<Vec<super::messages::NeighborMessage>> {
+        let mut builder = super::connection::dump_request(NlMsgType::RTM_GETNEIGH);
+        builder.append(&NdMsg::new());
+        builder.append_attr_u32(attr as u16, ifindex);
+        self.dump_typed_request(builder).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::test_support::parse_attrs;
+
+    #[test]
+    fn neighbor_table_params_nest_device_parameters() {
+        assert!(NeighborTableParams::arp().dev("lo").is_empty());
+
+        let params = NeighborTableParams::ndisc()
+            .gc_thresh3(4096)
+            .retrans_time(Duration::from_millis(1500))
+            .ucast_probes(5);
+        let mut builder = MessageBuilder::new(0, 0);
+        params.write_to(&mut builder, Some(3));
+
+        let attrs = parse_attrs(&builder.as_bytes()[16 + NdtMsg::SIZE..]);
+        assert_eq!(attrs[&ndta::NAME], b"ndisc_cache\0");
+        assert_eq!(attrs[&ndta::THRESH3], 4096u32.to_ne_bytes());
+        assert!(!attrs.contains_key(&ndta::THRESH1));
+        assert!(!attrs.contains_key(&ndta::GC_INTERVAL));
+
+        let parms = parse_attrs(&attrs[&ndta::PARMS]);
+        assert_eq!(parms[&ndtpa::IFINDEX], 3u32.to_ne_bytes());
+        assert_eq!(parms[&ndtpa::RETRANS_TIME], 1500u64.to_ne_bytes());
+        assert_eq!(parms[&ndtpa::UCAST_PROBES], 5u32.to_ne_bytes());
+
+        // Table-level settings alone send no NDTA_PARMS.
+        let mut builder = MessageBuilder::new(0, 0);
+        NeighborTableParams::arp()
+            .gc_interval(Duration::from_secs(30))
+            .write_to(&mut builder, None);
+        let attrs = parse_attrs(&builder.as_bytes()[16 + NdtMsg::SIZE..]);
+        assert_eq!(attrs[&ndta::GC_INTERVAL], 30_000u64.to_ne_bytes());
+        assert!(!attrs.contains_key(&ndta::PARMS));
+    }
 }
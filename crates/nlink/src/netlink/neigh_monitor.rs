@@ -0,0 +1,364 @@
+//! Neighbour state transitions and flap detection.
+//!
+//! The kernel reports every neighbour cache update on the `Neigh`
+//! rtnetlink group, including timer refreshes that don't change the
+//! `NUD_*` state. [`NeighborStateTracker`] reduces that to per-entry
+//! state transitions and counts how often an entry flips between
+//! resolved and `FAILED` within a sliding window. An entry that flips
+//! [`FlapOptions::threshold`] times is reported as flapping — typically a
+//! gateway that drops off the wire and comes back, or two hosts
+//! fighting over an address.
+//!
+//! [`NeighborMonitor`] wraps the tracker around an event stream.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//! use nlink::netlink::neigh_monitor::{FlapOptions, NeighborMonitor};
+//! use tokio_stream::StreamExt;
+//!
+//! let conn = Connection::<Route>::new()?;
+//! let mut monitor = NeighborMonitor::new(conn, FlapOptions::default()).await?;
+//! while let Some(t) = monitor.next().await {
+//!     let t = t?;
+//!     println!("{} on if{}: {:?} -> {:?}", t.destination, t.ifindex, t.previous, t.current);
+//!     if t.started_flapping {
+//!         println!("  flapping ({} flips)", t.flaps);
+//!     }
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use tokio_stream::Stream;
+
+use super::{
+    connection::{Connection, RtnetlinkGroup},
+    diagnostics::{Issue, IssueCategory, Severity},
+    error::Result,
+    events::NetworkEvent,
+    messages::NeighborMessage,
+    protocol::Route,
+    stream::OwnedEventStream,
+    types::neigh::NeighborState,
+};
+
+/// Flap detection settings.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct FlapOptions {
+    /// Sliding window flips are counted over. Default: 60 seconds.
+    pub window: Duration,
+    /// Flips within `window` from which an entry is flapping. Default:
+    /// 4, i.e. two full up/down cycles.
+    pub threshold: usize,
+}
+
+impl Default for FlapOptions {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(60),
+            threshold: 4,
+        }
+    }
+}
+
+impl FlapOptions {
+    /// Set the counting window (builder shape).
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Set the flip count from which an entry is flapping (builder
+    /// shape).
+    pub fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold.max(1);
+        self
+    }
+}
+
+/// A change of a neighbour entry's `NUD_*` state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NeighborTransition {
+    /// Interface the entry is on.
+    pub ifindex: u32,
+    /// The neighbour's protocol address.
+    pub destination: IpAddr,
+    /// State before the change; `None` for a new entry.
+    pub previous: Option<NeighborState>,
+    /// State after the change; `None` when the entry was deleted.
+    pub current: Option<NeighborState>,
+    /// Resolved/`FAILED` flips of this entry within the window,
+    /// including this one.
+    pub flaps: usize,
+    /// Whether the entry is flapping.
+    pub flapping: bool,
+    /// `true` on the transition that made the entry flapping; later
+    /// transitions of the same episode leave it `false`.
+    pub started_flapping: bool,
+}
+
+impl NeighborTransition {
+    /// The diagnostics issue for an entry that just started flapping,
+    /// or `None` for any other transition.
+    pub fn to_issue(&self) -> Option<Issue> {
+        if !self.started_flapping {
+            return None;
+        }
+        let name = crate::util::ifname::index_to_name(self.ifindex)
+            .unwrap_or_else(|_| format!("if{}", self.ifindex));
+        Some(Issue {
+            severity: Severity::Warning,
+            category: IssueCategory::NeighborFlap,
+            message: format!("Neighbour {} on {} is flapping", self.destination, name),
+            details: Some(format!(
+                "{} REACHABLE/FAILED flips; check the peer's link and for duplicate addresses",
+                self.flaps
+            )),
+            interface: Some(name),
+            timestamp: Instant::now(),
+        })
+    }
+}
+
+/// Whether a state counts as resolved or failed for flap counting.
+/// Transient states (`STALE`, `DELAY`, `PROBE`, `INCOMPLETE`) don't
+/// decide either way.
+fn reachability(state: NeighborState) -> Option<bool> {
+    match state {
+        NeighborState::Reachable | NeighborState::Permanent | NeighborState::Noarp => Some(true),
+        NeighborState::Failed => Some(false),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+struct Entry {
+    state: Option<NeighborState>,
+    /// Last decisive reachability, kept across deletes.
+    reachable: Option<bool>,
+    flips: VecDeque<Instant>,
+    flapping: bool,
+}
+
+/// Turns neighbour events into per-entry transitions with flap counts.
+///
+/// Feed it every neighbour message, in order; it has no I/O of its
+/// own, so it can also be driven from an existing event loop.
+#[derive(Debug, Default)]
+pub struct NeighborStateTracker {
+    opts: FlapOptions,
+    entries: HashMap<(u32, IpAddr), Entry>,
+}
+
+impl NeighborStateTracker {
+    /// Create a tracker with the given flap settings.
+    pub fn new(opts: FlapOptions) -> Self {
+        Self {
+            opts,
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Record an `RTM_NEWNEIGH` (`deleted == false`) or `RTM_DELNEIGH`
+    /// message seen at `now`.
+    ///
+    /// Returns the transition, or `None` if the entry's state didn't
+    /// change or the message has no destination (e.g. bridge FDB
+    /// entries).
+    pub fn observe(
+        &mut self,
+        msg: &NeighborMessage,
+        deleted: bool,
+        now: Instant,
+    ) -> Option<NeighborTransition> {
+        let destination = *msg.destination()?;
+        let key = (msg.ifindex(), destination);
+        let current = (!deleted).then(|| msg.state());
+
+        let entry = self.entries.entry(key).or_default();
+        if entry.state == current {
+            return None;
+        }
+        let previous = entry.state;
+        entry.state = current;
+
+        if let Some(reachable) = current.and_then(reachability) {
+            if entry.reachable.is_some_and(|r| r != reachable) {
+                entry.flips.push_back(now);
+            }
+            entry.reachable = Some(reachable);
+        }
+        while entry
+            .flips
+            .front()
+            .is_some_and(|&t| now.duration_since(t) > self.opts.window)
+        {
+            entry.flips.pop_front();
+        }
+
+        let flapping = entry.flips.len() >= self.opts.threshold;
+        let started_flapping = flapping && !entry.flapping;
+        entry.flapping = flapping;
+        let flaps = entry.flips.len();
+
+        // A deleted entry with no recent flips carries no state worth
+        // keeping.
+        if current.is_none() && flaps == 0 {
+            self.entries.remove(&key);
+        }
+
+        Some(NeighborTransition {
+            ifindex: key.0,
+            destination,
+            previous,
+            current,
+            flaps,
+            flapping,
+            started_flapping,
+        })
+    }
+
+    /// Entries currently flapping, as `(ifindex, destination)`.
+    pub fn flapping(&self) -> impl Iterator<Item = (u32, IpAddr)> + '_ {
+        self.entries
+            .iter()
+            .filter(|(_, e)| e.flapping)
+            .map(|(&key, _)| key)
+    }
+}
+
+/// Stream of neighbour state transitions, with flap detection.
+#[must_use = "streams do nothing unless polled"]
+pub struct NeighborMonitor {
+    events: OwnedEventStream<Route>,
+    tracker: NeighborStateTracker,
+}
+
+impl NeighborMonitor {
+    /// Subscribe `conn` to neighbour events and start tracking.
+    ///
+    /// Entries already in the cache are learnt from their first event,
+    /// so the first transition of an entry has `previous: None`.
+    pub async fn new(conn: Connection<Route>, opts: FlapOptions) -> Result<Self> {
+        conn.subscribe(&[RtnetlinkGroup::Neigh])?;
+        Ok(Self {
+            events: conn.into_events().await,
+            tracker: NeighborStateTracker::new(opts),
+        })
+    }
+
+    /// Borrow the tracker, e.g. to list flapping entries.
+    pub fn tracker(&self) -> &NeighborStateTracker {
+        &self.tracker
+    }
+}
+
+impl Stream for NeighborMonitor {
+    type Item = Result<NeighborTransition>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let event = match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(event))) => event,
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+            let (msg, deleted) = match &event {
+                NetworkEvent::NewNeighbor(msg) => (msg, false),
+                NetworkEvent::DelNeighbor(msg) => (msg, true),
+                _ => continue,
+            };
+            if let Some(transition) = self.tracker.observe(msg, deleted, Instant::now()) {
+                return Poll::Ready(Some(Ok(transition)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+    use crate::netlink::messages::NeighborMessageBuilder;
+
+    fn neigh(state: NeighborState) -> NeighborMessage {
+        NeighborMessageBuilder::new()
+            .ifindex(2)
+            .destination(IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)))
+            .state(state)
+            .build()
+    }
+
+    #[test]
+    fn tracker_reports_only_state_changes() {
+        let mut tracker = NeighborStateTracker::new(FlapOptions::default());
+        let now = Instant::now();
+
+        let t = tracker
+            .observe(&neigh(NeighborState::Reachable), false, now)
+            .unwrap();
+        assert_eq!(t.previous, None);
+        assert_eq!(t.current, Some(NeighborState::Reachable));
+        assert!(
+            tracker
+                .observe(&neigh(NeighborState::Reachable), false, now)
+                .is_none()
+        );
+
+        let t = tracker
+            .observe(&neigh(NeighborState::Stale), true, now)
+            .unwrap();
+        assert_eq!(t.previous, Some(NeighborState::Reachable));
+        assert_eq!(t.current, None);
+        assert_eq!(tracker.entries.len(), 0);
+    }
+
+    #[test]
+    fn tracker_flags_reachable_failed_cycling() {
+        use NeighborState::*;
+
+        let opts = FlapOptions::default()
+            .window(Duration::from_secs(10))
+            .threshold(4);
+        let mut tracker = NeighborStateTracker::new(opts);
+        let start = Instant::now();
+        let mut started = Vec::new();
+
+        // Two full cycles through the transient states; the fourth flip
+        // (FAILED -> REACHABLE) makes the entry flapping.
+        let states = [
+            Reachable, Stale, Probe, Failed, Incomplete, Reachable, Delay, Failed, Reachable,
+        ];
+        for (i, state) in states.into_iter().enumerate() {
+            let now = start + Duration::from_secs(i as u64);
+            let t = tracker.observe(&neigh(state), false, now).unwrap();
+            started.push(t.started_flapping);
+            if i == states.len() - 1 {
+                assert_eq!(t.flaps, 4);
+                assert!(t.flapping);
+            }
+        }
+        assert_eq!(started.iter().filter(|&&s| s).count(), 1);
+        assert!(started[states.len() - 1]);
+        assert_eq!(tracker.flapping().count(), 1);
+
+        // Once the flips age out of the window the entry settles.
+        let later = start + Duration::from_secs(30);
+        let t = tracker.observe(&neigh(Stale), false, later).unwrap();
+        assert_eq!(t.flaps, 0);
+        assert!(!t.flapping);
+        assert_eq!(tracker.flapping().count(), 0);
+    }
+}
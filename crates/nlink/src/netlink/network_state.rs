@@ -0,0 +1,687 @@
+//! An event-driven cache of links, addresses, routes and neighbours.
+//!
+//! Daemons that react to network changes usually keep their own copy of
+//! the kernel's tables: dump everything at startup, then apply every
+//! `RTM_NEW*` / `RTM_DEL*` notification. [`NetworkState`] is that copy.
+//! It is loaded from a dump, folds events into the tables as they arrive,
+//! and reports each net change as a [`StateChange`] — both as the return
+//! value of [`observe`](NetworkState::observe) and on a broadcast channel
+//! for any number of [subscribers](NetworkState::subscribe).
+//!
+//! Events are coalesced into state: a notification that re-announces an
+//! object replaces the cached copy (one [`Change::Updated`]), deleting
+//! something already gone reports nothing, and the deletions the kernel
+//! performs silently are applied too. Removing a link drops its
+//! addresses, neighbours and routes; setting a link down drops the IPv4
+//! routes through it (all but the host-scope `local` ones), which the
+//! kernel flushes without notifying.
+//!
+//! Like [`IfIndexResolver`](super::IfIndexResolver), the state is a
+//! cheap-to-clone handle over shared tables, so one task can feed it while
+//! others query it.
+//!
+//! # Ordering
+//!
+//! Subscribe to [`NetworkState::GROUPS`] *before* loading the dump (from a
+//! second connection) so no change falls between the two. Events queued
+//! meanwhile describe changes the dump may already contain; replaying
+//! them in order converges on the kernel's state. If the event socket
+//! overruns (`ENOBUFS`), notifications were lost and
+//! [`refresh`](NetworkState::refresh) is needed.
+//!
+//! The kernel announces an IPv6 address only once duplicate address
+//! detection finishes, so a dump can hold tentative addresses that events
+//! never mention; the same holds in reverse for a tentative address that
+//! is removed.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//! use nlink::netlink::network_state::{NetworkState, StateChange};
+//! use tokio_stream::StreamExt;
+//!
+//! let events = Connection::<Route>::new()?;
+//! events.subscribe(NetworkState::GROUPS)?;
+//! let state = NetworkState::load(&Connection::<Route>::new()?).await?;
+//!
+//! let mut changes = state.subscribe();
+//! tokio::spawn({
+//!     let mut stream = state.track(events.events().await);
+//!     async move { while stream.next().await.is_some() {} }
+//! });
+//!
+//! while let Ok(change) = changes.recv().await {
+//!     if let StateChange::Route(change) = change {
+//!         println!("{:?}", change);
+//!     }
+//!     let via = state.route_lookup("192.0.2.1".parse()?);
+//!     println!("now routed via {:?}", via.and_then(|r| r.oif()));
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::broadcast;
+use tokio_stream::{Stream, StreamExt};
+
+use super::{
+    connection::{Connection, RtnetlinkGroup},
+    error::Result,
+    events::NetworkEvent,
+    messages::{AddressMessage, LinkMessage, NeighborMessage, RouteMessage},
+    protocol::Route,
+    types::route::RouteScope,
+};
+
+/// Capacity of the change channel; slow subscribers see `Lagged`.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Tables consulted by [`NetworkState::route_lookup`], in the order of the
+/// kernel's default policy rules: local, main, default.
+const LOOKUP_TABLES: [u32; 3] = [255, 254, 253];
+
+const AF_INET: u8 = libc::AF_INET as u8;
+
+/// A change to one cached object.
+#[derive(Debug, Clone)]
+pub enum Change<T> {
+    /// The object is new.
+    Added(T),
+    /// The object was re-announced; `new` replaces `old`.
+    Updated {
+        /// The previously cached copy.
+        old: T,
+        /// The copy now cached.
+        new: T,
+    },
+    /// The object is gone.
+    Removed(T),
+}
+
+impl<T> Change<T> {
+    /// The current copy, or the removed one for [`Change::Removed`].
+    pub fn object(&self) -> &T {
+        match self {
+            Change::Added(obj) | Change::Removed(obj) => obj,
+            Change::Updated { new, .. } => new,
+        }
+    }
+
+    /// Whether the object was removed.
+    pub fn is_removed(&self) -> bool {
+        matches!(self, Change::Removed(_))
+    }
+}
+
+/// A net change to a [`NetworkState`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum StateChange {
+    /// A link changed. Boxed, as link messages are large.
+    Link(Box<Change<LinkMessage>>),
+    /// An address changed.
+    Address(Change<AddressMessage>),
+    /// A route changed.
+    Route(Change<RouteMessage>),
+    /// A neighbour entry changed.
+    Neighbor(Change<NeighborMessage>),
+}
+
+type AddrKey = (u32, IpAddr, u8);
+type RouteKey = (
+    u8,
+    u32,
+    Option<IpAddr>,
+    u8,
+    u8,
+    u32,
+    Option<u32>,
+    Option<IpAddr>,
+);
+type NeighKey = (u32, IpAddr);
+
+fn addr_key(addr: &AddressMessage) -> Option<AddrKey> {
+    Some((addr.ifindex(), *addr.primary_address()?, addr.prefix_len()))
+}
+
+/// The kernel's identity of a route: a new route with the same key
+/// replaces the old one.
+///
+/// IPv4 keys a route by prefix, TOS and metric, so a replace that moves it
+/// to another nexthop still matches. IPv6 keeps one route per nexthop for
+/// the same prefix (`fe80::/64` on every interface), so the output
+/// interface and gateway are part of its key.
+fn route_key(route: &RouteMessage) -> RouteKey {
+    let (oif, gateway) = if route.family() == AF_INET {
+        (None, None)
+    } else {
+        (route.oif(), route.gateway().copied())
+    };
+    (
+        route.family(),
+        route.table_id(),
+        route.destination().copied(),
+        route.dst_len(),
+        route.tos(),
+        route.priority().unwrap_or(0),
+        oif,
+        gateway,
+    )
+}
+
+fn neigh_key(neigh: &NeighborMessage) -> Option<NeighKey> {
+    // Bridge FDB entries share RTM_NEWNEIGH but aren't neighbours.
+    if neigh.family() == libc::AF_BRIDGE as u8 {
+        return None;
+    }
+    Some((neigh.ifindex(), *neigh.destination()?))
+}
+
+/// Whether `route` leaves through `ifindex`, directly or via a nexthop.
+fn uses_link(route: &RouteMessage, ifindex: u32) -> bool {
+    route.oif() == Some(ifindex)
+        || route
+            .multipath()
+            .is_some_and(|hops| hops.iter().any(|hop| hop.ifindex == ifindex))
+}
+
+/// Whether `addr` falls within `prefix/len`.
+fn prefix_contains(prefix: Option<&IpAddr>, len: u8, addr: &IpAddr) -> bool {
+    match (prefix, addr) {
+        (None, _) => len == 0,
+        (Some(IpAddr::V4(p)), IpAddr::V4(a)) => {
+            let mask = u32::MAX
+                .checked_shl(32 - u32::from(len.min(32)))
+                .unwrap_or(0);
+            u32::from(*p) & mask == u32::from(*a) & mask
+        }
+        (Some(IpAddr::V6(p)), IpAddr::V6(a)) => {
+            let mask = u128::MAX
+                .checked_shl(128 - u32::from(len.min(128)))
+                .unwrap_or(0);
+            u128::from(*p) & mask == u128::from(*a) & mask
+        }
+        _ => false,
+    }
+}
+
+fn upsert<K: Ord, T: Clone>(map: &mut BTreeMap<K, T>, key: K, obj: &T) -> Change<T> {
+    match map.insert(key, obj.clone()) {
+        Some(old) => Change::Updated {
+            old,
+            new: obj.clone(),
+        },
+        None => Change::Added(obj.clone()),
+    }
+}
+
+#[derive(Debug, Default)]
+struct Tables {
+    links: BTreeMap<u32, LinkMessage>,
+    addresses: BTreeMap<AddrKey, AddressMessage>,
+    routes: BTreeMap<RouteKey, RouteMessage>,
+    neighbors: BTreeMap<NeighKey, NeighborMessage>,
+}
+
+impl Tables {
+    fn apply(&mut self, event: &NetworkEvent, changes: &mut Vec<StateChange>) {
+        match event {
+            NetworkEvent::NewLink(link) => {
+                let ifindex = link.ifindex();
+                let change = upsert(&mut self.links, ifindex, link);
+                changes.push(StateChange::Link(Box::new(change)));
+                if !link.is_up() {
+                    self.purge_routes(changes, |r| {
+                        r.family() == AF_INET
+                            && r.scope() != RouteScope::Host
+                            && uses_link(r, ifindex)
+                    });
+                }
+            }
+            NetworkEvent::DelLink(link) => {
+                let ifindex = link.ifindex();
+                if let Some(old) = self.links.remove(&ifindex) {
+                    changes.push(StateChange::Link(Box::new(Change::Removed(old))));
+                }
+                self.purge_link(ifindex, changes);
+            }
+            NetworkEvent::NewAddress(addr) => {
+                if let Some(key) = addr_key(addr) {
+                    let change = upsert(&mut self.addresses, key, addr);
+                    changes.push(StateChange::Address(change));
+                }
+            }
+            NetworkEvent::DelAddress(addr) => {
+                if let Some(old) = addr_key(addr).and_then(|k| self.addresses.remove(&k)) {
+                    changes.push(StateChange::Address(Change::Removed(old)));
+                }
+            }
+            NetworkEvent::NewRoute(route) if !route.is_cloned() => {
+                let change = upsert(&mut self.routes, route_key(route), route);
+                changes.push(StateChange::Route(change));
+            }
+            NetworkEvent::DelRoute(route) if !route.is_cloned() => {
+                if let Some(old) = self.routes.remove(&route_key(route)) {
+                    changes.push(StateChange::Route(Change::Removed(old)));
+                }
+            }
+            NetworkEvent::NewNeighbor(neigh) => {
+                if let Some(key) = neigh_key(neigh) {
+                    let change = upsert(&mut self.neighbors, key, neigh);
+                    changes.push(StateChange::Neighbor(change));
+                }
+            }
+            NetworkEvent::DelNeighbor(neigh) => {
+                if let Some(old) = neigh_key(neigh).and_then(|k| self.neighbors.remove(&k)) {
+                    changes.push(StateChange::Neighbor(Change::Removed(old)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Drop everything that belonged to a deleted link.
+    fn purge_link(&mut self, ifindex: u32, changes: &mut Vec<StateChange>) {
+        let addrs = self.addresses.extract_if(.., |(i, ..), _| *i == ifindex);
+        changes.extend(addrs.map(|(_, a)| StateChange::Address(Change::Removed(a))));
+        let neighs = self.neighbors.extract_if(.., |(i, _), _| *i == ifindex);
+        changes.extend(neighs.map(|(_, n)| StateChange::Neighbor(Change::Removed(n))));
+        self.purge_routes(changes, |r| uses_link(r, ifindex));
+    }
+
+    fn purge_routes(
+        &mut self,
+        changes: &mut Vec<StateChange>,
+        doomed: impl Fn(&RouteMessage) -> bool,
+    ) {
+        let routes = self.routes.extract_if(.., |_, r| doomed(r));
+        changes.extend(routes.map(|(_, r)| StateChange::Route(Change::Removed(r))));
+    }
+}
+
+/// A shared cache of the kernel's links, addresses, routes and
+/// neighbours, kept current by netlink events.
+///
+/// Cloning shares the tables and the change channel. Queries take a brief
+/// read lock and return copies; they never touch the kernel.
+#[derive(Debug, Clone)]
+pub struct NetworkState {
+    inner: Arc<RwLock<Tables>>,
+    changes: broadcast::Sender<StateChange>,
+}
+
+impl Default for NetworkState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NetworkState {
+    /// The multicast groups whose events [`observe`](Self::observe) folds
+    /// into the state.
+    pub const GROUPS: &'static [RtnetlinkGroup] = &[
+        RtnetlinkGroup::Link,
+        RtnetlinkGroup::Ipv4Addr,
+        RtnetlinkGroup::Ipv6Addr,
+        RtnetlinkGroup::Ipv4Route,
+        RtnetlinkGroup::Ipv6Route,
+        RtnetlinkGroup::Neigh,
+    ];
+
+    /// Create an empty state.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::default(),
+            changes: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// Create a state populated from a dump of every table.
+    pub async fn load(conn: &Connection<Route>) -> Result<Self> {
+        let state = Self::new();
+        state.refresh(conn).await?;
+        Ok(state)
+    }
+
+    /// Replace the tables with a fresh dump.
+    ///
+    /// Subscribers are not told what changed; call this after losing
+    /// events, then re-query.
+    pub async fn refresh(&self, conn: &Connection<Route>) -> Result<()> {
+        let links = conn.get_links().await?;
+        let addresses = conn.get_addresses().await?;
+        let routes = conn.get_routes().await?;
+        let neighbors = conn.get_neighbors().await?;
+
+        let mut tables = Tables::default();
+        let mut ignored = Vec::new();
+        let events = links
+            .into_iter()
+            .map(NetworkEvent::NewLink)
+            .chain(addresses.into_iter().map(NetworkEvent::NewAddress))
+            .chain(routes.into_iter().map(NetworkEvent::NewRoute))
+            .chain(neighbors.into_iter().map(NetworkEvent::NewNeighbor));
+        for event in events {
+            tables.apply(&event, &mut ignored);
+            ignored.clear();
+        }
+        *self.write() = tables;
+        Ok(())
+    }
+
+    /// Fold an event into the state.
+    ///
+    /// Returns the resulting changes, which are also sent to every
+    /// [subscriber](Self::subscribe). Events for other object types,
+    /// and deletions of objects not in the cache, change nothing.
+    pub fn observe(&self, event: &NetworkEvent) -> Vec<StateChange> {
+        let mut changes = Vec::new();
+        self.write().apply(event, &mut changes);
+        for change in &changes {
+            // No receivers is fine; nobody is listening.
+            let _ = self.changes.send(change.clone());
+        }
+        changes
+    }
+
+    /// Wrap an event stream so every event updates the state before it
+    /// is yielded.
+    ///
+    /// The connection should be subscribed to [`GROUPS`](Self::GROUPS).
+    pub fn track<S>(&self, events: S) -> impl Stream<Item = Result<NetworkEvent>>
+    where
+        S: Stream<Item = Result<NetworkEvent>>,
+    {
+        let state = self.clone();
+        events.map(move |event| {
+            if let Ok(event) = &event {
+                state.observe(event);
+            }
+            event
+        })
+    }
+
+    /// Receive every change made by [`observe`](Self::observe) from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<StateChange> {
+        self.changes.subscribe()
+    }
+
+    /// All links, by interface index.
+    pub fn links(&self) -> Vec<LinkMessage> {
+        self.read().links.values().cloned().collect()
+    }
+
+    /// The link with this interface index.
+    pub fn link(&self, ifindex: u32) -> Option<LinkMessage> {
+        self.read().links.get(&ifindex).cloned()
+    }
+
+    /// The link with this name.
+    pub fn link_by_name(&self, name: &str) -> Option<LinkMessage> {
+        let tables = self.read();
+        tables
+            .links
+            .values()
+            .find(|l| l.name() == Some(name))
+            .cloned()
+    }
+
+    /// All addresses.
+    pub fn addresses(&self) -> Vec<AddressMessage> {
+        self.read().addresses.values().cloned().collect()
+    }
+
+    /// The addresses on one interface.
+    pub fn addr_of(&self, ifindex: u32) -> Vec<AddressMessage> {
+        let tables = self.read();
+        tables
+            .addresses
+            .range((ifindex, IpAddr::from([0u8; 4]), 0)..)
+            .take_while(|((i, ..), _)| *i == ifindex)
+            .map(|(_, a)| a.clone())
+            .collect()
+    }
+
+    /// All routes in every table, excluding cached (cloned) routes.
+    pub fn routes(&self) -> Vec<RouteMessage> {
+        self.read().routes.values().cloned().collect()
+    }
+
+    /// All neighbour entries (ARP and NDP; bridge FDB entries excluded).
+    pub fn neighbors(&self) -> Vec<NeighborMessage> {
+        self.read().neighbors.values().cloned().collect()
+    }
+
+    /// The route the kernel would pick for `dst` under the default policy
+    /// rules.
+    ///
+    /// Searches the local, main and default tables in turn for the
+    /// longest matching prefix, preferring the lowest metric among equal
+    /// prefixes. Custom policy rules, source addresses, marks and TOS are
+    /// not considered; for an authoritative answer ask the kernel with
+    /// [`Connection::route_get`](super::Connection::route_get).
+    pub fn route_lookup(&self, dst: IpAddr) -> Option<RouteMessage> {
+        let family = match dst {
+            IpAddr::V4(_) => AF_INET,
+            IpAddr::V6(_) => libc::AF_INET6 as u8,
+        };
+        let tables = self.read();
+        LOOKUP_TABLES.iter().find_map(|&table| {
+            tables
+                .routes
+                .values()
+                .filter(|r| r.family() == family && r.table_id() == table)
+                .filter(|r| prefix_contains(r.destination(), r.dst_len(), &dst))
+                .max_by_key(|r| (r.dst_len(), std::cmp::Reverse(r.priority().unwrap_or(0))))
+                .cloned()
+        })
+    }
+
+    /// Number of cached links.
+    pub fn len(&self) -> usize {
+        self.read().links.len()
+    }
+
+    /// Whether no links are cached.
+    pub fn is_empty(&self) -> bool {
+        self.read().links.is_empty()
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Tables> {
+        // Table updates can't be observed half-applied by a reader after a
+        // panicking writer in a way that matters, so recover from poisoning.
+        self.inner.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Tables> {
+        self.inner.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::messages::{
+        AddressMessageBuilder, LinkMessageBuilder, NeighborMessageBuilder, RouteMessageBuilder,
+    };
+
+    const IFF_UP: u32 = 0x1;
+
+    fn link(ifindex: i32, name: &str, flags: u32) -> NetworkEvent {
+        let msg = LinkMessageBuilder::new()
+            .ifindex(ifindex)
+            .name(name)
+            .flags(flags)
+            .build();
+        NetworkEvent::NewLink(msg)
+    }
+
+    fn addr(ifindex: u32, ip: &str, len: u8) -> AddressMessage {
+        AddressMessageBuilder::new()
+            .ifindex(ifindex)
+            .address(ip.parse().unwrap())
+            .prefix_len(len)
+            .build()
+    }
+
+    fn route(dst: &str, len: u8, oif: u32) -> RouteMessageBuilder {
+        RouteMessageBuilder::new()
+            .destination(dst.parse().unwrap(), len)
+            .oif(oif)
+            .table(254)
+    }
+
+    #[test]
+    fn events_are_folded_into_changes() {
+        let state = NetworkState::new();
+        let mut rx = state.subscribe();
+
+        let link_change = |change: &StateChange| match change {
+            StateChange::Link(change) => Some((**change).clone()),
+            _ => None,
+        };
+
+        let changes = state.observe(&link(2, "eth0", IFF_UP));
+        assert!(matches!(
+            changes.iter().map(link_change).collect::<Vec<_>>()[..],
+            [Some(Change::Added(_))]
+        ));
+        let changes = state.observe(&link(2, "wan0", IFF_UP));
+        assert!(matches!(
+            &changes.iter().map(link_change).collect::<Vec<_>>()[..],
+            [Some(Change::Updated { old, new })]
+                if old.name() == Some("eth0") && new.name() == Some("wan0")
+        ));
+        assert_eq!(state.link_by_name("wan0").unwrap().ifindex(), 2);
+        assert!(state.link_by_name("eth0").is_none());
+
+        // Deleting something unknown is not a change.
+        let gone = addr(2, "10.0.0.1", 24);
+        assert!(state.observe(&NetworkEvent::DelAddress(gone)).is_empty());
+
+        assert!(matches!(
+            link_change(&rx.try_recv().unwrap()),
+            Some(Change::Added(_))
+        ));
+        assert!(matches!(
+            link_change(&rx.try_recv().unwrap()),
+            Some(Change::Updated { .. })
+        ));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn deleting_a_link_purges_what_used_it() {
+        let state = NetworkState::new();
+        state.observe(&link(2, "eth0", IFF_UP));
+        state.observe(&link(3, "eth1", IFF_UP));
+        state.observe(&NetworkEvent::NewAddress(addr(2, "10.0.0.1", 24)));
+        state.observe(&NetworkEvent::NewAddress(addr(3, "10.1.0.1", 24)));
+        state.observe(&NetworkEvent::NewRoute(route("10.0.0.0", 24, 2).build()));
+        state.observe(&NetworkEvent::NewRoute(route("2001:db8::", 64, 2).build()));
+        let neigh = NeighborMessageBuilder::new()
+            .ifindex(2)
+            .destination("10.0.0.2".parse().unwrap())
+            .build();
+        state.observe(&NetworkEvent::NewNeighbor(neigh));
+
+        let del = NetworkEvent::DelLink(state.link(2).unwrap());
+        let changes = state.observe(&del);
+        assert_eq!(changes.len(), 5);
+        assert!(changes.iter().all(|c| match c {
+            StateChange::Link(c) => c.is_removed(),
+            StateChange::Address(c) => c.is_removed(),
+            StateChange::Route(c) => c.is_removed(),
+            StateChange::Neighbor(c) => c.is_removed(),
+        }));
+        assert!(state.addr_of(2).is_empty());
+        assert_eq!(state.addr_of(3).len(), 1);
+        assert!(state.routes().is_empty());
+        assert!(state.neighbors().is_empty());
+    }
+
+    #[test]
+    fn link_down_drops_ipv4_routes_except_local() {
+        let state = NetworkState::new();
+        state.observe(&link(2, "eth0", IFF_UP));
+        state.observe(&NetworkEvent::NewRoute(route("10.0.0.0", 24, 2).build()));
+        state.observe(&NetworkEvent::NewRoute(route("2001:db8::", 64, 2).build()));
+        let local = route("10.0.0.1", 32, 2)
+            .table(255)
+            .scope(RouteScope::Host)
+            .build();
+        state.observe(&NetworkEvent::NewRoute(local));
+
+        let changes = state.observe(&link(2, "eth0", 0));
+        assert_eq!(changes.len(), 2);
+        let routes = state.routes();
+        assert_eq!(routes.len(), 2);
+        assert!(
+            routes
+                .iter()
+                .all(|r| r.family() == libc::AF_INET6 as u8 || r.table_id() == 255)
+        );
+    }
+
+    #[test]
+    fn ipv6_routes_are_kept_per_nexthop() {
+        let state = NetworkState::new();
+        state.observe(&NetworkEvent::NewRoute(route("fe80::", 64, 2).build()));
+        state.observe(&NetworkEvent::NewRoute(route("fe80::", 64, 3).build()));
+        assert_eq!(state.routes().len(), 2);
+
+        // IPv4 replaces by prefix and metric alone.
+        state.observe(&NetworkEvent::NewRoute(route("10.0.0.0", 8, 2).build()));
+        let changes = state.observe(&NetworkEvent::NewRoute(route("10.0.0.0", 8, 3).build()));
+        assert!(matches!(
+            changes[..],
+            [StateChange::Route(Change::Updated { .. })]
+        ));
+        assert_eq!(state.routes().len(), 3);
+
+        let del = route("fe80::", 64, 3).build();
+        state.observe(&NetworkEvent::DelRoute(del));
+        let left: Vec<_> = state.routes().iter().filter_map(|r| r.oif()).collect();
+        assert_eq!(left, [3, 2]);
+    }
+
+    #[test]
+    fn route_lookup_prefers_local_then_longest_prefix_then_metric() {
+        let state = NetworkState::new();
+        let default = RouteMessageBuilder::new().ipv4().oif(2).table(254).build();
+        state.observe(&NetworkEvent::NewRoute(default));
+        for (dst, len, oif, metric) in [("10.0.0.0", 8, 3, 0), ("10.1.0.0", 16, 4, 100)] {
+            let r = route(dst, len, oif).priority(metric).build();
+            state.observe(&NetworkEvent::NewRoute(r));
+        }
+        let backup = route("10.1.0.0", 16, 5).priority(200).build();
+        state.observe(&NetworkEvent::NewRoute(backup));
+        let local = route("10.1.2.3", 32, 1).table(255).build();
+        state.observe(&NetworkEvent::NewRoute(local));
+
+        let via = |dst: &str| {
+            state
+                .route_lookup(dst.parse().unwrap())
+                .and_then(|r| r.oif())
+        };
+        assert_eq!(via("192.0.2.1"), Some(2));
+        assert_eq!(via("10.9.9.9"), Some(3));
+        assert_eq!(via("10.1.9.9"), Some(4));
+        assert_eq!(via("10.1.2.3"), Some(1));
+        assert_eq!(via("2001:db8::1"), None);
+    }
+
+    #[test]
+    fn prefix_contains_handles_edges() {
+        let v4 = |s: &str| s.parse::<IpAddr>().unwrap();
+        assert!(prefix_contains(Some(&v4("0.0.0.0")), 0, &v4("1.2.3.4")));
+        assert!(prefix_contains(Some(&v4("1.2.3.4")), 32, &v4("1.2.3.4")));
+        assert!(!prefix_contains(Some(&v4("1.2.3.4")), 32, &v4("1.2.3.5")));
+        assert!(prefix_contains(Some(&v4("::")), 0, &v4("::1")));
+        assert!(!prefix_contains(Some(&v4("::")), 0, &v4("1.2.3.4")));
+        assert!(prefix_contains(None, 0, &v4("::1")));
+    }
+}
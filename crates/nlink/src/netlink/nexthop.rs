@@ -51,7 +51,7 @@
 //! conn.del_nexthop(2).await?;
 //! ```
 
-use std::net::IpAddr;
+use std::{net::IpAddr, time::Duration};
 
 use super::{
     attr::AttrIter,
@@ -61,7 +61,7 @@ use super::{
     interface_ref::InterfaceRef,
     message::{NLM_F_ACK, NLM_F_DUMP, NLM_F_REQUEST, NLMSG_HDRLEN, NlMsgType},
     protocol::Route,
-    types::nexthop::{NexthopGrp, NhMsg, nha, nha_res_group, nhf, nhg_type},
+    types::nexthop::{NexthopGrp, NhMsg, nha, nha_res_bucket, nha_res_group, nhf, nhg_type},
 };
 
 /// NLM_F_CREATE flag
@@ -69,6 +69,10 @@ const NLM_F_CREATE: u16 = 0x400;
 /// NLM_F_REPLACE flag
 const NLM_F_REPLACE: u16 = 0x100;
 
+/// Kernel clock_t ticks per second (USER_HZ). Resilient-group timers
+/// and bucket idle times are exchanged in these units.
+const USER_HZ: u32 = 100;
+
 /// Nexthop group types.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[non_exhaustive]
@@ -107,6 +111,8 @@ pub struct ResilientParams {
     pub idle_timer: u32,
     /// Unbalanced timer in seconds.
     pub unbalanced_timer: u32,
+    /// How long the group has been unbalanced, in seconds (read-only).
+    pub unbalanced_time: u64,
 }
 
 /// A nexthop group member entry.
@@ -115,14 +121,14 @@ pub struct ResilientParams {
 pub struct NexthopGroupMember {
     /// Nexthop ID.
     pub(crate) id: u32,
-    /// Weight (1-256, where 0 means 1).
-    pub(crate) weight: u8,
+    /// Weight (1-256).
+    pub(crate) weight: u16,
 }
 
 impl NexthopGroupMember {
     /// Construct a new group-member descriptor. Used by builders
     /// (the parser path goes through `Nexthop::parse`).
-    pub fn new(id: u32, weight: u8) -> Self {
+    pub fn new(id: u32, weight: u16) -> Self {
         Self { id, weight }
     }
 
@@ -131,8 +137,9 @@ impl NexthopGroupMember {
         self.id
     }
 
-    /// Weight (`1..=256`, where `0` is treated by the kernel as 1).
-    pub fn weight(&self) -> u8 {
+    /// Weight (`1..=256`). The kernel carries it as weight minus one;
+    /// this is the actual weight, as `ip nexthop` prints it.
+    pub fn weight(&self) -> u16 {
         self.weight
     }
 }
@@ -281,7 +288,7 @@ impl Nexthop {
                         if let Some(grp) = NexthopGrp::from_bytes(&payload[offset..]) {
                             members.push(NexthopGroupMember {
                                 id: grp.id,
-                                weight: grp.weight,
+                                weight: u16::from(grp.weight) + 1,
                             });
                         }
                         offset += NexthopGrp::SIZE;
@@ -308,7 +315,7 @@ impl Nexthop {
                                     res_payload[1],
                                     res_payload[2],
                                     res_payload[3],
-                                ]);
+                                ]) / USER_HZ;
                             }
                             nha_res_group::UNBALANCED_TIMER if res_payload.len() >= 4 => {
                                 params.unbalanced_timer = u32::from_ne_bytes([
@@ -316,7 +323,13 @@ impl Nexthop {
                                     res_payload[1],
                                     res_payload[2],
                                     res_payload[3],
-                                ]);
+                                ]) / USER_HZ;
+                            }
+                            nha_res_group::UNBALANCED_TIME if res_payload.len() >= 8 => {
+                                let mut bytes = [0u8; 8];
+                                bytes.copy_from_slice(&res_payload[..8]);
+                                params.unbalanced_time =
+                                    u64::from_ne_bytes(bytes) / u64::from(USER_HZ);
                             }
                             _ => {}
                         }
@@ -364,6 +377,102 @@ impl Nexthop {
     }
 }
 
+/// One hash bucket of a resilient nexthop group.
+///
+/// Dumped with [`Connection::get_nexthop_buckets`]; shows which member
+/// each bucket currently steers flows to and how long it has been idle.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct NexthopBucket {
+    /// ID of the resilient group.
+    pub(crate) group_id: u32,
+    /// Bucket index within the group.
+    pub(crate) index: u16,
+    /// Time since a packet last hit the bucket.
+    pub(crate) idle_time: Duration,
+    /// Nexthop the bucket points at.
+    pub(crate) nexthop_id: u32,
+    /// Bucket flags (see `nhf` module).
+    pub(crate) flags: u32,
+}
+
+impl NexthopBucket {
+    /// ID of the resilient group the bucket belongs to.
+    pub fn group_id(&self) -> u32 {
+        self.group_id
+    }
+
+    /// Bucket index within the group.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Time since a packet last hit the bucket.
+    pub fn idle_time(&self) -> Duration {
+        self.idle_time
+    }
+
+    /// Nexthop the bucket points at.
+    pub fn nexthop_id(&self) -> u32 {
+        self.nexthop_id
+    }
+
+    /// Bucket flags (see `nhf` module).
+    pub fn flags(&self) -> u32 {
+        self.flags
+    }
+
+    /// Parse a bucket from an `RTM_NEWNEXTHOPBUCKET` message.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let nhmsg = NhMsg::from_bytes(data)?;
+        let attrs_data = &data[NhMsg::SIZE..];
+
+        let mut bucket = Self {
+            group_id: 0,
+            index: 0,
+            idle_time: Duration::ZERO,
+            nexthop_id: 0,
+            flags: nhmsg.nh_flags,
+        };
+
+        for (attr_type, payload) in AttrIter::new(attrs_data) {
+            match attr_type {
+                nha::ID if payload.len() >= 4 => {
+                    bucket.group_id =
+                        u32::from_ne_bytes([payload[0], payload[1], payload[2], payload[3]]);
+                }
+                nha::RES_BUCKET => {
+                    for (res_type, res_payload) in AttrIter::new(payload) {
+                        match res_type {
+                            nha_res_bucket::INDEX if res_payload.len() >= 2 => {
+                                bucket.index = u16::from_ne_bytes([res_payload[0], res_payload[1]]);
+                            }
+                            nha_res_bucket::IDLE_TIME if res_payload.len() >= 8 => {
+                                let mut bytes = [0u8; 8];
+                                bytes.copy_from_slice(&res_payload[..8]);
+                                let ticks = u64::from_ne_bytes(bytes);
+                                bucket.idle_time = Duration::from_millis(ticks.saturating_mul(10));
+                            }
+                            nha_res_bucket::NH_ID if res_payload.len() >= 4 => {
+                                bucket.nexthop_id = u32::from_ne_bytes([
+                                    res_payload[0],
+                                    res_payload[1],
+                                    res_payload[2],
+                                    res_payload[3],
+                                ]);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(bucket)
+    }
+}
+
 /// Builder for individual nexthop objects.
 ///
 /// # Example
@@ -477,11 +586,12 @@ impl NexthopBuilder {
 
     /// Write the netlink message with resolved interface index.
     pub(crate) fn write_to(&self, builder: &mut MessageBuilder, ifindex: Option<u32>) {
-        // Determine address family
+        // Determine address family. The kernel only accepts AF_UNSPEC
+        // for groups, so gateway-less nexthops (device-only, blackhole)
+        // default to AF_INET.
         let family = match &self.gateway {
-            Some(IpAddr::V4(_)) => libc::AF_INET as u8,
             Some(IpAddr::V6(_)) => libc::AF_INET6 as u8,
-            None => libc::AF_UNSPEC as u8,
+            Some(IpAddr::V4(_)) | None => libc::AF_INET as u8,
         };
 
         let mut nh_flags = 0u32;
@@ -552,16 +662,23 @@ impl NexthopBuilder {
 ///     .member(2, 1)
 ///     .buckets(128)
 ///     .idle_timer(120);
+///
+/// // Group of FDB nexthops, for VXLAN remote endpoints
+/// let vtep = NexthopGroupBuilder::new(103)
+///     .fdb()
+///     .member(10, 1)
+///     .member(11, 1);
 /// ```
 #[derive(Debug, Clone)]
 #[must_use = "builders do nothing unless used"]
 pub struct NexthopGroupBuilder {
     id: u32,
     group_type: NexthopGroupType,
-    members: Vec<(u32, u8)>, // (nexthop_id, weight)
+    members: Vec<(u32, u16)>, // (nexthop_id, weight)
     buckets: Option<u16>,
     idle_timer: Option<u32>,
     unbalanced_timer: Option<u32>,
+    fdb: bool,
     protocol: Option<u8>,
 }
 
@@ -577,6 +694,7 @@ impl NexthopGroupBuilder {
             buckets: None,
             idle_timer: None,
             unbalanced_timer: None,
+            fdb: false,
             protocol: None,
         }
     }
@@ -600,7 +718,7 @@ impl NexthopGroupBuilder {
     ///
     /// - `nexthop_id`: ID of an existing nexthop object
     /// - `weight`: Weight for load balancing (1-256, where higher = more traffic)
-    pub fn member(mut self, nexthop_id: u32, weight: u8) -> Self {
+    pub fn member(mut self, nexthop_id: u32, weight: u16) -> Self {
         self.members.push((nexthop_id, weight));
         self
     }
@@ -630,6 +748,15 @@ impl NexthopGroupBuilder {
         self
     }
 
+    /// Mark as an FDB nexthop group.
+    ///
+    /// Every member must itself be an FDB nexthop. Bridge FDB entries
+    /// can then point at the group to spread traffic across VTEPs.
+    pub fn fdb(mut self) -> Self {
+        self.fdb = true;
+        self
+    }
+
     /// Set the routing protocol.
     pub fn protocol(mut self, protocol: u8) -> Self {
         self.protocol = Some(protocol);
@@ -643,6 +770,11 @@ impl NexthopGroupBuilder {
                 "nexthop group must have at least one member".into(),
             ));
         }
+        if let Some((nh_id, weight)) = self.members.iter().find(|(_, w)| !(1..=256).contains(w)) {
+            return Err(Error::InvalidMessage(format!(
+                "nexthop group member {nh_id}: weight {weight} is outside 1-256"
+            )));
+        }
 
         let nhmsg = NhMsg::new()
             .with_family(libc::AF_UNSPEC as u8)
@@ -661,11 +793,16 @@ impl NexthopGroupBuilder {
         // Add group members
         let mut grp_data = Vec::with_capacity(self.members.len() * NexthopGrp::SIZE);
         for (nh_id, weight) in &self.members {
-            let grp = NexthopGrp::new(*nh_id, *weight);
+            // The kernel stores weight minus one.
+            let grp = NexthopGrp::new(*nh_id, (*weight - 1) as u8);
             grp_data.extend_from_slice(grp.as_bytes());
         }
         builder.append_attr(nha::GROUP, &grp_data);
 
+        if self.fdb {
+            builder.append_attr(nha::FDB, &[]);
+        }
+
         // Add resilient group parameters if applicable
         if self.group_type == NexthopGroupType::Resilient
             && (self.buckets.is_some()
@@ -677,10 +814,12 @@ impl NexthopGroupBuilder {
                 builder.append_attr(nha_res_group::BUCKETS, &buckets.to_ne_bytes());
             }
             if let Some(idle) = self.idle_timer {
-                builder.append_attr(nha_res_group::IDLE_TIMER, &idle.to_ne_bytes());
+                let ticks = idle.saturating_mul(USER_HZ);
+                builder.append_attr(nha_res_group::IDLE_TIMER, &ticks.to_ne_bytes());
             }
             if let Some(unbal) = self.unbalanced_timer {
-                builder.append_attr(nha_res_group::UNBALANCED_TIMER, &unbal.to_ne_bytes());
+                let ticks = unbal.saturating_mul(USER_HZ);
+                builder.append_attr(nha_res_group::UNBALANCED_TIMER, &ticks.to_ne_bytes());
             }
             builder.nest_end(res_token);
         }
@@ -884,6 +1023,99 @@ impl Connection<Route> {
         self.del_nexthop(id).await
     }
 
+    /// Move a nexthop group onto new members, make-before-break.
+    ///
+    /// Every nexthop in `nexthops` is installed (or updated) first. The
+    /// group is then replaced in one `NLM_F_REPLACE`, so every route
+    /// using it switches at once, and read back to confirm its members.
+    /// Only then are the previous members that left the group deleted,
+    /// skipping any still used by another group or by a route directly.
+    /// Routes never point at a group without a usable member, which
+    /// avoids transient blackholes during a gateway migration.
+    ///
+    /// Returns the IDs of the nexthops that were deleted. A group whose
+    /// read-back members differ from `group` is reported as
+    /// [`Error::InvalidMessage`] and nothing is deleted.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::nexthop::{NexthopBuilder, NexthopGroupBuilder};
+    ///
+    /// // Group 100 currently uses nexthops 1 and 2; move it to 3 and 4.
+    /// let retired = conn.migrate_nexthop_group(
+    ///     NexthopGroupBuilder::new(100).member(3, 1).member(4, 1),
+    ///     [
+    ///         NexthopBuilder::new(3).gateway("192.168.1.3".parse()?).dev("eth0"),
+    ///         NexthopBuilder::new(4).gateway("192.168.2.4".parse()?).dev("eth1"),
+    ///     ],
+    /// ).await?;
+    /// assert_eq!(retired, [1, 2]);
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "migrate_nexthop_group"))]
+    pub async fn migrate_nexthop_group(
+        &self,
+        group: NexthopGroupBuilder,
+        nexthops: impl IntoIterator<Item = NexthopBuilder>,
+    ) -> Result<Vec<u32>> {
+        let id = group.id;
+        let wanted = group.members.clone();
+        let previous: Vec<u32> = match self.get_nexthop(id).await? {
+            Some(nh) => nh
+                .group()
+                .unwrap_or_default()
+                .iter()
+                .map(|m| m.id())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        for nh in nexthops {
+            self.replace_nexthop(nh).await?;
+        }
+        self.replace_nexthop_group(group).await?;
+
+        let members: Vec<(u32, u16)> = match self.get_nexthop(id).await? {
+            Some(nh) => {
+                let group = nh.group().unwrap_or_default();
+                group.iter().map(|m| (m.id(), m.weight())).collect()
+            }
+            None => Vec::new(),
+        };
+        if members != wanted {
+            return Err(Error::InvalidMessage(format!(
+                "migrate_nexthop_group: group {id} has members {members:?}, expected {wanted:?}"
+            )));
+        }
+
+        let retired: Vec<u32> = previous
+            .into_iter()
+            .filter(|old| !wanted.iter().any(|(nh_id, _)| nh_id == old))
+            .collect();
+        if retired.is_empty() {
+            return Ok(retired);
+        }
+
+        let groups = self.get_nexthop_groups().await?;
+        let routes = self.get_routes().await?;
+        let mut removed = Vec::with_capacity(retired.len());
+        for old in retired {
+            let in_group = groups.iter().any(|g| {
+                g.group()
+                    .is_some_and(|members| members.iter().any(|m| m.id() == old))
+            });
+            if in_group || routes.iter().any(|r| r.nexthop_id() == Some(old)) {
+                continue;
+            }
+            match self.del_nexthop(old).await {
+                Ok(()) => removed.push(old),
+                Err(e) if e.is_not_found() => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(removed)
+    }
+
     /// Get only nexthop groups (not individual nexthops).
     #[tracing::instrument(level = "debug", skip_all, fields(method = "get_nexthop_groups"))]
     pub async fn get_nexthop_groups(&self) -> Result<Vec<Nexthop>> {
@@ -910,6 +1142,80 @@ impl Connection<Route> {
 
         Ok(groups)
     }
+
+    /// Dump the hash buckets of resilient nexthop groups.
+    ///
+    /// With `group_id`, only that group's buckets are returned;
+    /// otherwise the buckets of every resilient group are.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// for bucket in conn.get_nexthop_buckets(Some(102)).await? {
+    ///     println!(
+    ///         "bucket {} -> nexthop {} (idle {:?})",
+    ///         bucket.index(),
+    ///         bucket.nexthop_id(),
+    ///         bucket.idle_time(),
+    ///     );
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_nexthop_buckets"))]
+    pub async fn get_nexthop_buckets(&self, group_id: Option<u32>) -> Result<Vec<NexthopBucket>> {
+        let nhmsg = NhMsg::new().with_family(libc::AF_UNSPEC as u8);
+
+        let mut builder =
+            MessageBuilder::new(NlMsgType::RTM_GETNEXTHOPBUCKET, NLM_F_REQUEST | NLM_F_DUMP);
+        builder.append(&nhmsg);
+        if let Some(id) = group_id {
+            builder.append_attr_u32(nha::ID, id);
+        }
+
+        let responses = self.send_dump(builder).await?;
+
+        let mut buckets = Vec::new();
+        for data in responses {
+            // Skip the netlink header (16 bytes)
+            if data.len() > NLMSG_HDRLEN
+                && let Ok(bucket) = NexthopBucket::parse(&data[NLMSG_HDRLEN..])
+            {
+                buckets.push(bucket);
+            }
+        }
+
+        Ok(buckets)
+    }
+
+    /// Get one bucket of a resilient nexthop group.
+    ///
+    /// Returns `None` if the group or the bucket doesn't exist.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_nexthop_bucket"))]
+    pub async fn get_nexthop_bucket(
+        &self,
+        group_id: u32,
+        index: u16,
+    ) -> Result<Option<NexthopBucket>> {
+        let nhmsg = NhMsg::new().with_family(libc::AF_UNSPEC as u8);
+
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_GETNEXTHOPBUCKET, NLM_F_REQUEST);
+        builder.append(&nhmsg);
+        builder.append_attr_u32(nha::ID, group_id);
+        let bucket = builder.nest_start(nha::RES_BUCKET);
+        builder.append_attr(nha_res_bucket::INDEX, &index.to_ne_bytes());
+        builder.nest_end(bucket);
+
+        match self.send_request(builder).await {
+            Ok(data) => {
+                if data.len() > NLMSG_HDRLEN {
+                    Ok(Some(NexthopBucket::parse(&data[NLMSG_HDRLEN..])?))
+                } else {
+                    Ok(None)
+                }
+            }
+            Err(e) if e.is_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -937,6 +1243,15 @@ mod tests {
         assert!(nh.gateway.is_none());
     }
 
+    #[test]
+    fn test_nexthop_without_gateway_uses_inet_family() {
+        let mut msg = MessageBuilder::new(NlMsgType::RTM_NEWNEXTHOP, 0);
+        NexthopBuilder::new(1).blackhole().write_to(&mut msg, None);
+        let bytes = msg.finish();
+        // nhmsg.nh_family is the first byte after the netlink header.
+        assert_eq!(bytes[16], libc::AF_INET as u8);
+    }
+
     #[test]
     fn test_nexthop_group_builder() {
         let grp = NexthopGroupBuilder::new(100)
@@ -967,4 +1282,76 @@ mod tests {
             NexthopGroupType::Resilient
         );
     }
+
+    #[test]
+    fn test_group_weights_and_timers_round_trip() {
+        let msg = NexthopGroupBuilder::new(100)
+            .resilient()
+            .member(1, 1)
+            .member(2, 256)
+            .buckets(64)
+            .idle_timer(120)
+            .unbalanced_timer(30)
+            .build(NlMsgType::RTM_NEWNEXTHOP, 0)
+            .unwrap();
+        let bytes = msg.finish();
+
+        // nexthop_grp.weight is weight minus one on the wire.
+        let (_, grp) = AttrIter::new(&bytes[NLMSG_HDRLEN + NhMsg::SIZE..])
+            .find(|(t, _)| *t == nha::GROUP)
+            .unwrap();
+        assert_eq!(NexthopGrp::from_bytes(grp).unwrap().weight, 0);
+        assert_eq!(NexthopGrp::from_bytes(&grp[8..]).unwrap().weight, 255);
+
+        let nh = Nexthop::parse(&bytes[NLMSG_HDRLEN..]).unwrap();
+        let weights: Vec<u16> = nh.group().unwrap().iter().map(|m| m.weight()).collect();
+        assert_eq!(weights, [1, 256]);
+        let res = nh.resilient().unwrap();
+        assert_eq!(res.buckets, 64);
+        assert_eq!(res.idle_timer, 120);
+        assert_eq!(res.unbalanced_timer, 30);
+    }
+
+    #[test]
+    fn test_group_rejects_out_of_range_weight() {
+        for weight in [0, 257] {
+            let err = NexthopGroupBuilder::new(100)
+                .member(1, weight)
+                .build(NlMsgType::RTM_NEWNEXTHOP, 0)
+                .unwrap_err();
+            assert!(err.to_string().contains("outside 1-256"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_fdb_group_sets_nha_fdb() {
+        let bytes = NexthopGroupBuilder::new(100)
+            .fdb()
+            .member(1, 1)
+            .build(NlMsgType::RTM_NEWNEXTHOP, 0)
+            .unwrap()
+            .finish();
+        let nh = Nexthop::parse(&bytes[NLMSG_HDRLEN..]).unwrap();
+        assert!(nh.is_fdb());
+        assert!(nh.is_group());
+    }
+
+    #[test]
+    fn test_bucket_parse() {
+        let mut msg = MessageBuilder::new(NlMsgType::RTM_NEWNEXTHOPBUCKET, 0);
+        msg.append(&NhMsg::new());
+        msg.append_attr_u32(nha::ID, 102);
+        let nest = msg.nest_start(nha::RES_BUCKET);
+        msg.append_attr(nha_res_bucket::INDEX, &7u16.to_ne_bytes());
+        msg.append_attr(nha_res_bucket::IDLE_TIME, &250u64.to_ne_bytes());
+        msg.append_attr_u32(nha_res_bucket::NH_ID, 2);
+        msg.nest_end(nest);
+        let bytes = msg.finish();
+
+        let bucket = NexthopBucket::parse(&bytes[NLMSG_HDRLEN..]).unwrap();
+        assert_eq!(bucket.group_id(), 102);
+        assert_eq!(bucket.index(), 7);
+        assert_eq!(bucket.idle_time(), Duration::from_millis(2500));
+        assert_eq!(bucket.nexthop_id(), 2);
+    }
 }
@@ -0,0 +1,162 @@
+//! Object-safe view of the common rtnetlink operations.
+//!
+//! [`Connection<Route>`](Connection) has a large inherent API, most of
+//! it generic (`impl Into<InterfaceRef>`, `L: LinkConfig`), which makes
+//! it impossible to put behind a `dyn` and awkward to swap out in tests.
+//! [`NetlinkOps`] is the small, object-safe subset application code
+//! usually needs — links, addresses and routes — so that logic written
+//! against `&dyn NetlinkOps` (or `impl NetlinkOps`) runs both on a real
+//! connection and, with the `mock` feature, on an in-memory
+//! `MockConnection` that needs neither
+//! root nor a namespace.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{InterfaceRef, ops::NetlinkOps};
+//! use nlink::netlink::link::DummyLink;
+//!
+//! async fn ensure_dummy(ops: &dyn NetlinkOps, name: &str) -> nlink::Result<u32> {
+//!     if let Some(link) = ops.get_link_by_name(name).await? {
+//!         return Ok(link.ifindex());
+//!     }
+//!     ops.add_link(&DummyLink::new(name)).await?;
+//!     ops.set_link_up(InterfaceRef::name(name)).await?;
+//!     let link = ops.get_link_by_name(name).await?.expect("just created");
+//!     Ok(link.ifindex())
+//! }
+//! ```
+
+use std::{future::Future, net::IpAddr, pin::Pin};
+
+use super::{
+    addr::AddressConfig,
+    connection::Connection,
+    error::Result,
+    interface_ref::InterfaceRef,
+    link::LinkConfig,
+    messages::{AddressMessage, LinkMessage, RouteMessage},
+    protocol::Route,
+    route::RouteConfig,
+};
+
+/// Boxed future returned by every [`NetlinkOps`] method.
+pub type OpsFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Link, address and route operations, usable as a trait object.
+///
+/// Each method behaves like the [`Connection`] method of the same name;
+/// see those for the details. Configs are taken by reference so the
+/// trait stays object-safe: pass `&DummyLink::new("d0")` where the
+/// inherent method takes `DummyLink::new("d0")`.
+pub trait NetlinkOps: Send + Sync {
+    /// List all interfaces.
+    fn get_links(&self) -> OpsFuture<'_, Vec<LinkMessage>>;
+
+    /// Look up an interface by name.
+    fn get_link_by_name<'a>(&'a self, name: &'a str) -> OpsFuture<'a, Option<LinkMessage>>;
+
+    /// Look up an interface by index.
+    fn get_link_by_index(&self, index: u32) -> OpsFuture<'_, Option<LinkMessage>>;
+
+    /// Create an interface.
+    fn add_link<'a>(&'a self, config: &'a dyn LinkConfig) -> OpsFuture<'a, ()>;
+
+    /// Delete an interface.
+    fn del_link(&self, iface: InterfaceRef) -> OpsFuture<'_, ()>;
+
+    /// Set an interface administratively up.
+    fn set_link_up(&self, iface: InterfaceRef) -> OpsFuture<'_, ()>;
+
+    /// Set an interface administratively down.
+    fn set_link_down(&self, iface: InterfaceRef) -> OpsFuture<'_, ()>;
+
+    /// Change an interface's MTU.
+    fn set_link_mtu(&self, iface: InterfaceRef, mtu: u32) -> OpsFuture<'_, ()>;
+
+    /// List all addresses.
+    fn get_addresses(&self) -> OpsFuture<'_, Vec<AddressMessage>>;
+
+    /// Add an address.
+    fn add_address<'a>(&'a self, config: &'a dyn AddressConfig) -> OpsFuture<'a, ()>;
+
+    /// Remove an address.
+    fn del_address(
+        &self,
+        iface: InterfaceRef,
+        address: IpAddr,
+        prefix_len: u8,
+    ) -> OpsFuture<'_, ()>;
+
+    /// List all routes, in every table.
+    fn get_routes(&self) -> OpsFuture<'_, Vec<RouteMessage>>;
+
+    /// Add a route.
+    fn add_route<'a>(&'a self, config: &'a dyn RouteConfig) -> OpsFuture<'a, ()>;
+
+    /// Delete a route.
+    fn del_route<'a>(&'a self, config: &'a dyn RouteConfig) -> OpsFuture<'a, ()>;
+}
+
+impl NetlinkOps for Connection<Route> {
+    fn get_links(&self) -> OpsFuture<'_, Vec<LinkMessage>> {
+        Box::pin(Connection::get_links(self))
+    }
+
+    fn get_link_by_name<'a>(&'a self, name: &'a str) -> OpsFuture<'a, Option<LinkMessage>> {
+        Box::pin(Connection::get_link_by_name(self, name))
+    }
+
+    fn get_link_by_index(&self, index: u32) -> OpsFuture<'_, Option<LinkMessage>> {
+        Box::pin(Connection::get_link_by_index(self, index))
+    }
+
+    fn add_link<'a>(&'a self, config: &'a dyn LinkConfig) -> OpsFuture<'a, ()> {
+        Box::pin(Connection::add_link(self, config))
+    }
+
+    fn del_link(&self, iface: InterfaceRef) -> OpsFuture<'_, ()> {
+        Box::pin(Connection::del_link(self, iface))
+    }
+
+    fn set_link_up(&self, iface: InterfaceRef) -> OpsFuture<'_, ()> {
+        Box::pin(Connection::set_link_up(self, iface))
+    }
+
+    fn set_link_down(&self, iface: InterfaceRef) -> OpsFuture<'_, ()> {
+        Box::pin(Connection::set_link_down(self, iface))
+    }
+
+    fn set_link_mtu(&self, iface: InterfaceRef, mtu: u32) -> OpsFuture<'_, ()> {
+        Box::pin(Connection::set_link_mtu(self, iface, mtu))
+    }
+
+    fn get_addresses(&self) -> OpsFuture<'_, Vec<AddressMessage>> {
+        Box::pin(Connection::get_addresses(self))
+    }
+
+    fn add_address<'a>(&'a self, config: &'a dyn AddressConfig) -> OpsFuture<'a, ()> {
+        Box::pin(Connection::add_address(self, config))
+    }
+
+    fn del_address(
+        &self,
+        iface: InterfaceRef,
+        address: IpAddr,
+        prefix_len: u8,
+    ) -> OpsFuture<'_, ()> {
+        Box::pin(Connection::del_address(self, iface, address, prefix_len))
+    }
+
+    fn get_routes(&self) -> OpsFuture<'_, Vec<RouteMessage>> {
+        Box::pin(Connection::get_routes(self))
+    }
+
+    fn add_route<'a>(&'a self, config: &'a dyn RouteConfig) -> OpsFuture<'a, ()> {
+        Box::pin(Connection::add_route(self, config))
+    }
+
+    fn del_route<'a>(&'a self, config: &'a dyn RouteConfig) -> OpsFuture<'a, ()> {
+        Box::pin(Connection::del_route(self, config))
+    }
+}
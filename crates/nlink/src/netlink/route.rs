@@ -48,19 +48,29 @@
 //! conn.del_route_v4("192.168.2.0", 24).await?;
 //! ```
 
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use tokio_stream::{Stream, StreamExt};
 
 use super::{
     builder::MessageBuilder,
     connection::Connection,
-    error::Result,
+    error::{Error, Result},
+    events::NetworkEvent,
+    genl::ioam6::Ioam6Encap,
     interface_ref::InterfaceRef,
-    message::{NLM_F_ACK, NLM_F_REQUEST, NlMsgType},
+    message::{NLM_F_ACK, NLM_F_REQUEST, NLMSG_HDRLEN, NlMsgType},
+    messages::{AddressOrigin, ParsedNextHop, RouteMessage},
     mpls::MplsEncap,
+    parse::FromNetlink,
     protocol::Route,
     srv6::Srv6Encap,
-    types::route::{RouteProtocol, RouteScope, RouteType, RtMsg, RtaAttr, rt_table},
+    types::route::{RouteProtocol, RouteScope, RouteType, RtMsg, RtaAttr, rt_table, rtm_flags},
 };
+use crate::util::addr::{ipv4_in_prefix, ipv6_in_prefix};
 
 /// NLM_F_CREATE flag
 const NLM_F_CREATE: u16 = 0x400;
@@ -159,6 +169,36 @@ pub trait RouteConfig: Send + Sync {
     }
 }
 
+impl<R: RouteConfig + ?Sized> RouteConfig for &R {
+    fn device_ref(&self) -> Option<&InterfaceRef> {
+        (**self).device_ref()
+    }
+
+    fn multipath_device_refs(&self) -> Vec<Option<&InterfaceRef>> {
+        (**self).multipath_device_refs()
+    }
+
+    fn family(&self) -> u8 {
+        (**self).family()
+    }
+
+    fn write_add(&self, builder: &mut MessageBuilder, interfaces: &ResolvedRouteInterfaces) {
+        (**self).write_add(builder, interfaces)
+    }
+
+    fn write_delete(&self, builder: &mut MessageBuilder) {
+        (**self).write_delete(builder)
+    }
+
+    fn write_delete_with_interfaces(
+        &self,
+        builder: &mut MessageBuilder,
+        interfaces: &ResolvedRouteInterfaces,
+    ) {
+        (**self).write_delete_with_interfaces(builder, interfaces)
+    }
+}
+
 /// Route metrics configuration.
 #[derive(Debug, Clone, Default)]
 pub struct RouteMetrics {
@@ -436,7 +476,7 @@ pub struct Ipv4Route {
     dev: Option<InterfaceRef>,
     /// Route type
     route_type: RouteType,
-    /// Route protocol
+    /// Route protocol (`RTPROT_*`, or a custom value)
     protocol: RouteProtocol,
     /// Route scope
     scope: Option<RouteScope>,
@@ -555,6 +595,13 @@ impl Ipv4Route {
         self
     }
 
+    /// Set the route protocol by number, such as a daemon's own
+    /// `rt_protos` entry.
+    pub fn protocol_id(mut self, protocol: u8) -> Self {
+        self.protocol = RouteProtocol::from(protocol);
+        self
+    }
+
     /// Set the route scope.
     pub fn scope(mut self, scope: RouteScope) -> Self {
         self.scope = Some(scope);
@@ -730,9 +777,9 @@ impl RouteConfig for Ipv4Route {
             .with_family(AF_INET)
             .with_dst_len(self.prefix_len)
             .with_table(table_u8)
-            .with_protocol(self.protocol as u8)
-            .with_scope(scope as u8)
-            .with_type(self.route_type as u8);
+            .with_protocol(self.protocol.number())
+            .with_scope(scope.number())
+            .with_type(self.route_type.number());
 
         builder.append(&rtmsg);
 
@@ -863,9 +910,9 @@ impl RouteConfig for Ipv4Route {
             .with_family(AF_INET)
             .with_dst_len(self.prefix_len)
             .with_table(table_u8)
-            .with_protocol(self.protocol as u8)
-            .with_scope(scope as u8)
-            .with_type(self.route_type as u8);
+            .with_protocol(self.protocol.number())
+            .with_scope(scope.number())
+            .with_type(self.route_type.number());
 
         builder.append(&rtmsg);
 
@@ -919,7 +966,7 @@ pub struct Ipv6Route {
     dev: Option<InterfaceRef>,
     /// Route type
     route_type: RouteType,
-    /// Route protocol
+    /// Route protocol (`RTPROT_*`, or a custom value)
     protocol: RouteProtocol,
     /// Route scope
     scope: Option<RouteScope>,
@@ -941,6 +988,8 @@ pub struct Ipv6Route {
     mpls_encap: Option<MplsEncap>,
     /// SRv6 encapsulation
     srv6_encap: Option<Srv6Encap>,
+    /// IOAM6 encapsulation
+    ioam6_encap: Option<Ioam6Encap>,
 }
 
 impl Ipv6Route {
@@ -974,6 +1023,7 @@ impl Ipv6Route {
             nexthop_id: None,
             mpls_encap: None,
             srv6_encap: None,
+            ioam6_encap: None,
         }
     }
 
@@ -997,6 +1047,7 @@ impl Ipv6Route {
             nexthop_id: None,
             mpls_encap: None,
             srv6_encap: None,
+            ioam6_encap: None,
         }
     }
 
@@ -1037,6 +1088,13 @@ impl Ipv6Route {
         self
     }
 
+    /// Set the route protocol by number, such as a daemon's own
+    /// `rt_protos` entry.
+    pub fn protocol_id(mut self, protocol: u8) -> Self {
+        self.protocol = RouteProtocol::from(protocol);
+        self
+    }
+
     /// Set the route scope.
     pub fn scope(mut self, scope: RouteScope) -> Self {
         self.scope = Some(scope);
@@ -1141,6 +1199,26 @@ impl Ipv6Route {
         self
     }
 
+    /// Add IOAM6 encapsulation (in-situ OAM trace insertion).
+    ///
+    /// The trace's namespace must be configured on every node that
+    /// should fill it in — see [`crate::netlink::genl::ioam6`].
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::route::Ipv6Route;
+    /// use nlink::netlink::genl::ioam6::Ioam6Encap;
+    ///
+    /// let route = Ipv6Route::new("2001:db8::", 32)
+    ///     .dev("eth0")
+    ///     .ioam6_encap(Ioam6Encap::trace(123, 0x800000, 12));
+    /// ```
+    pub fn ioam6_encap(mut self, encap: Ioam6Encap) -> Self {
+        self.ioam6_encap = Some(encap);
+        self
+    }
+
     fn determine_scope(&self) -> RouteScope {
         if let Some(scope) = self.scope {
             return scope;
@@ -1189,9 +1267,9 @@ impl RouteConfig for Ipv6Route {
             .with_family(AF_INET6)
             .with_dst_len(self.prefix_len)
             .with_table(table_u8)
-            .with_protocol(self.protocol as u8)
-            .with_scope(scope as u8)
-            .with_type(self.route_type as u8);
+            .with_protocol(self.protocol.number())
+            .with_scope(scope.number())
+            .with_type(self.route_type.number());
 
         builder.append(&rtmsg);
 
@@ -1261,6 +1339,11 @@ impl RouteConfig for Ipv6Route {
         if let Some(ref encap) = self.srv6_encap {
             encap.write_to(builder);
         }
+
+        // RTA_ENCAP_TYPE + RTA_ENCAP (IOAM6 encapsulation)
+        if let Some(ref encap) = self.ioam6_encap {
+            encap.write_to(builder);
+        }
     }
 
     fn write_delete(&self, builder: &mut MessageBuilder) {
@@ -1309,9 +1392,9 @@ impl RouteConfig for Ipv6Route {
             .with_family(AF_INET6)
             .with_dst_len(self.prefix_len)
             .with_table(table_u8)
-            .with_protocol(self.protocol as u8)
-            .with_scope(scope as u8)
-            .with_type(self.route_type as u8);
+            .with_protocol(self.protocol.number())
+            .with_scope(scope.number())
+            .with_type(self.route_type.number());
 
         builder.append(&rtmsg);
 
@@ -1426,7 +1509,7 @@ const NLM_F_REPLACE: u16 = 0x100;
 
 impl Connection<Route> {
     /// Resolve all interface references for a route configuration.
-    async fn resolve_route_interfaces<R: RouteConfig>(
+    pub(crate) async fn resolve_route_interfaces<R: RouteConfig>(
         &self,
         config: &R,
     ) -> Result<ResolvedRouteInterfaces> {
@@ -1562,12 +1645,1102 @@ impl Connection<Route> {
             .await
             .map_err(|e| e.with_context("replace_route"))
     }
+
+    /// Replace a route's nexthops in place and confirm the kernel
+    /// installed them.
+    ///
+    /// The route is sent with `NLM_F_REPLACE`, so the kernel swaps the
+    /// nexthops of the existing entry (same family, table, destination,
+    /// TOS and metric) in one step and the prefix is never missing.
+    /// The route is then read back and its type, gateway, device,
+    /// multipath set or nexthop ID compared with `config`. A mismatch,
+    /// e.g. another daemon replacing the route in between, is returned
+    /// as [`Error::InvalidMessage`].
+    ///
+    /// Returns the route as installed. For routes that use a nexthop
+    /// group, change the group with
+    /// [`migrate_nexthop_group`](Self::migrate_nexthop_group) instead.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::route::{Ipv4Route, NextHop};
+    ///
+    /// // Move the default route from one gateway pair to another.
+    /// conn.replace_route_verified(
+    ///     Ipv4Route::default_route().multipath(vec![
+    ///         NextHop::new().gateway_v4("192.168.1.2".parse()?).dev("eth0"),
+    ///         NextHop::new().gateway_v4("192.168.2.2".parse()?).dev("eth1"),
+    ///     ]),
+    /// ).await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "replace_route_verified"))]
+    pub async fn replace_route_verified<R: RouteConfig>(&self, config: R) -> Result<RouteMessage> {
+        let interfaces = self.resolve_route_interfaces(&config).await?;
+        let payload = add_payload(&config, &interfaces);
+        let expected = RouteMessage::from_bytes(&payload)?;
+
+        let mut builder = MessageBuilder::new(
+            NlMsgType::RTM_NEWROUTE,
+            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE,
+        );
+        builder.append_bytes(&payload);
+        self.send_ack(builder)
+            .await
+            .map_err(|e| e.with_context("replace_route_verified"))?;
+
+        let key = RouteKey::of(&expected);
+        let mut routes = self.stream_routes().await?;
+        while let Some(route) = routes.next().await {
+            let route = route?;
+            if RouteKey::of(&route) != key {
+                continue;
+            }
+            if !same_nexthops(&expected, &route) {
+                return Err(Error::InvalidMessage(format!(
+                    "replace_route_verified: {}/{} installed with different nexthops",
+                    expected.destination_str(),
+                    expected.dst_len()
+                )));
+            }
+            return Ok(route);
+        }
+        Err(Error::InvalidMessage(format!(
+            "replace_route_verified: {}/{} missing after replace",
+            expected.destination_str(),
+            expected.dst_len()
+        )))
+    }
+
+    /// Delete a route as returned by a dump ([`Self::get_routes`]).
+    ///
+    /// Writes the dumped key (header, `RTA_DST`, `RTA_TABLE`,
+    /// `RTA_PRIORITY`, and `RTA_OIF` / `RTA_GATEWAY` for single-path
+    /// routes) so the kernel removes exactly that entry.
+    pub async fn del_route_message(&self, route: &RouteMessage) -> Result<()> {
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_DELROUTE, NLM_F_REQUEST | NLM_F_ACK);
+        builder.append(&route.header);
+        if let Some(dst) = route.destination() {
+            builder.append_attr(RtaAttr::Dst as u16, &ip_octets(dst));
+        }
+        builder.append_attr_u32(RtaAttr::Table as u16, route.table_id());
+        if let Some(priority) = route.priority() {
+            builder.append_attr_u32(RtaAttr::Priority as u16, priority);
+        }
+        if route.multipath.is_none() {
+            if let Some(oif) = route.oif() {
+                builder.append_attr_u32(RtaAttr::Oif as u16, oif);
+            }
+            if let Some(gw) = route.gateway() {
+                builder.append_attr(RtaAttr::Gateway as u16, &ip_octets(gw));
+            }
+        }
+        self.send_ack(builder)
+            .await
+            .map_err(|e| e.with_context("del_route_message"))
+    }
+
+    /// Delete every route installed with `protocol`, in all tables
+    /// and both families (`ip route flush proto X table all`).
+    ///
+    /// Returns the number of routes removed. Routes that vanish
+    /// between the dump and the delete are skipped.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "flush_routes_by_protocol"))]
+    pub async fn flush_routes_by_protocol(
+        &self,
+        protocol: impl Into<RouteProtocol>,
+    ) -> Result<usize> {
+        self.flush_routes_matching(RouteSelector::new().protocol(protocol))
+            .await
+    }
+
+    /// Delete every route `selector` matches.
+    ///
+    /// Returns the number of routes removed. Matching routes are
+    /// collected from a streamed dump first, then deleted; routes that
+    /// vanish in between are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::route::RouteSelector;
+    /// use nlink::netlink::types::route::RouteProtocol;
+    ///
+    /// // Drop kernel routes from the main table, but keep the prefix
+    /// // routes SLAAC depends on.
+    /// let selector = RouteSelector::new()
+    ///     .table(254)
+    ///     .protocol(RouteProtocol::Kernel)
+    ///     .router_advertised(false);
+    /// let removed = conn.flush_routes_matching(selector).await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "flush_routes_matching"))]
+    pub async fn flush_routes_matching(&self, selector: impl Into<RouteSelector>) -> Result<usize> {
+        let mut routes = Vec::new();
+        {
+            let mut stream = std::pin::pin!(self.stream_routes_matching(selector).await?);
+            while let Some(route) = stream.next().await {
+                routes.push(route?);
+            }
+        }
+        let mut removed = 0;
+        for route in &routes {
+            match self.del_route_message(route).await {
+                Ok(()) => removed += 1,
+                Err(e) if e.is_not_found() || e.errno() == Some(libc::ESRCH) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Make `routes` the complete set of routes owned by `protocol`.
+    ///
+    /// This is the routing-daemon pattern (FRR, bird): every route is
+    /// stamped with the daemon's protocol number and installed with
+    /// `NLM_F_REPLACE`, then any other route carrying that protocol
+    /// is deleted. Routes the daemon keeps are never absent in
+    /// between, and routes owned by anyone else are never touched.
+    ///
+    /// A route's identity is its family, table, destination, TOS and
+    /// metric; a replaced route may change gateway or device in place.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::route::Ipv4Route;
+    ///
+    /// const RTPROT_MYD: u8 = 99; // or a `RouteProtocol`
+    /// let result = conn.sync_protocol_routes(RTPROT_MYD, vec![
+    ///     Ipv4Route::new("10.1.0.0", 16).gateway("192.168.1.1".parse()?),
+    ///     Ipv4Route::new("10.2.0.0", 16).gateway("192.168.1.2".parse()?),
+    /// ]).await?;
+    /// println!("{} installed, {} removed", result.installed, result.removed);
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "sync_protocol_routes"))]
+    pub async fn sync_protocol_routes<R: RouteConfig>(
+        &self,
+        protocol: impl Into<RouteProtocol>,
+        routes: impl IntoIterator<Item = R>,
+    ) -> Result<RouteSyncResult> {
+        let protocol = protocol.into();
+        let mut wanted = Vec::new();
+        for config in routes {
+            let interfaces = self.resolve_route_interfaces(&config).await?;
+            let payload = stamped_add_payload(&config, &interfaces, protocol);
+            wanted.push(RouteKey::of(&RouteMessage::from_bytes(&payload)?));
+
+            let mut builder = MessageBuilder::new(
+                NlMsgType::RTM_NEWROUTE,
+                NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_REPLACE,
+            );
+            builder.append_bytes(&payload);
+            self.send_ack(builder)
+                .await
+                .map_err(|e| e.with_context("sync_protocol_routes"))?;
+        }
+
+        let mut removed = 0;
+        for route in self.get_routes().await? {
+            if route.protocol() != protocol || wanted.contains(&RouteKey::of(&route)) {
+                continue;
+            }
+            match self.del_route_message(&route).await {
+                Ok(()) => removed += 1,
+                Err(e) if e.is_not_found() || e.errno() == Some(libc::ESRCH) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(RouteSyncResult {
+            installed: wanted.len(),
+            removed,
+        })
+    }
+
+    /// Look up the route the kernel would use for `destination`, like
+    /// `ip route get`.
+    ///
+    /// `opts` supplies the rest of the flow: source address, input or
+    /// output interface, firewall mark, UID, TOS and table. The reply is
+    /// the resolved route, including the preferred source and, for
+    /// IPv4, its cache information. With [`RouteGetOptions::fib_match`]
+    /// the reply is the matching FIB entry instead.
+    ///
+    /// An unreachable destination is returned as the kernel's error
+    /// (`ENETUNREACH`, `EHOSTUNREACH`, ...).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::route::RouteGetOptions;
+    ///
+    /// let route = conn
+    ///     .route_get("8.8.8.8".parse()?, RouteGetOptions::new().mark(0x10).uid(1000))
+    ///     .await?;
+    /// println!("via {:?} dev {:?} src {:?}", route.gateway(), route.oif(), route.prefsrc());
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "route_get"))]
+    pub async fn route_get(
+        &self,
+        destination: IpAddr,
+        opts: RouteGetOptions,
+    ) -> Result<RouteMessage> {
+        let (family, host_len) = match destination {
+            IpAddr::V4(_) => (AF_INET, 32),
+            IpAddr::V6(_) => (AF_INET6, 128),
+        };
+        if let Some(src) = opts.source
+            && src.is_ipv4() != destination.is_ipv4()
+        {
+            return Err(Error::InvalidMessage(format!(
+                "route_get: source {src} and destination {destination} differ in family"
+            )));
+        }
+        let iif = self.resolve_interface_opt(opts.iif.as_ref()).await?;
+        let oif = self.resolve_interface_opt(opts.oif.as_ref()).await?;
+
+        let mut rtmsg = RtMsg::new().with_family(family).with_dst_len(host_len);
+        rtmsg.rtm_tos = opts.tos;
+        // Report the table the route was found in rather than the
+        // lookup's result table.
+        rtmsg.rtm_flags = rtm_flags::LOOKUP_TABLE;
+        if opts.fib_match {
+            rtmsg.rtm_flags |= rtm_flags::FIB_MATCH;
+        }
+        if opts.source.is_some() {
+            rtmsg.rtm_src_len = host_len;
+        }
+
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_GETROUTE, NLM_F_REQUEST);
+        builder.append(&rtmsg);
+        builder.append_attr(RtaAttr::Dst as u16, &ip_octets(&destination));
+        if let Some(src) = opts.source {
+            builder.append_attr(RtaAttr::Src as u16, &ip_octets(&src));
+        }
+        if let Some(iif) = iif {
+            builder.append_attr_u32(RtaAttr::Iif as u16, iif);
+        }
+        if let Some(oif) = oif {
+            builder.append_attr_u32(RtaAttr::Oif as u16, oif);
+        }
+        if let Some(mark) = opts.mark {
+            builder.append_attr_u32(RtaAttr::Mark as u16, mark);
+        }
+        if let Some(uid) = opts.uid {
+            builder.append_attr_u32(RtaAttr::Uid as u16, uid);
+        }
+        if let Some(table) = opts.table {
+            builder.append_attr_u32(RtaAttr::Table as u16, table);
+        }
+
+        let response = self
+            .send_request(builder)
+            .await
+            .map_err(|e| e.with_context(format!("route_get({destination})")))?;
+        RouteMessage::from_bytes(response.get(NLMSG_HDRLEN..).unwrap_or_default())
+    }
+
+    /// Stream the routes `selector` matches.
+    ///
+    /// The dump is scoped to the selector's address family in the
+    /// kernel; the other criteria are applied as frames arrive. Memory
+    /// is bounded by one socket read, not by the size of the table, so
+    /// this is the way to walk a full BGP table.
+    ///
+    /// With [strict checking](Connection::enable_strict_checking) on,
+    /// the kernel also filters by protocol and output interface, and by
+    /// table for IPv4 or IPv6 selectors, so routes that don't match are
+    /// never sent. A table or interface that doesn't exist yields an
+    /// empty stream either way.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::AddressFamily;
+    /// use nlink::netlink::route::RouteSelector;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// let selector = RouteSelector::new().family(AddressFamily::v4()).table(254);
+    /// let mut routes = conn.stream_routes_matching(selector).await?;
+    /// while let Some(route) = routes.next().await {
+    ///     let route = route?;
+    ///     // process one route at a time
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "stream_routes_matching"))]
+    pub async fn stream_routes_matching(
+        &self,
+        selector: impl Into<RouteSelector>,
+    ) -> Result<impl Stream<Item = Result<RouteMessage>> + '_> {
+        let selector = selector.into();
+        let routes = self
+            .dump_stream_with_body::<RouteMessage>(NlMsgType::RTM_GETROUTE, &selector.dump_body())
+            .await?;
+        Ok(routes.filter(move |route| match route {
+            Ok(route) => selector.matches(route),
+            // Strict kernels reject a filter on a missing table
+            // (ENOENT) or device (ENODEV): nothing matches.
+            Err(e) => !e.is_not_found(),
+        }))
+    }
+
+    /// Count the routes `selector` matches.
+    ///
+    /// Routes are streamed from the kernel and dropped as they are
+    /// counted, so memory stays flat even on a router carrying full
+    /// BGP tables.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::netlink::route::RouteSelector;
+    /// use nlink::netlink::types::route::RouteProtocol;
+    ///
+    /// let bgp = conn
+    ///     .count_routes(RouteSelector::new().protocol(RouteProtocol::Bgp))
+    ///     .await?;
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "count_routes"))]
+    pub async fn count_routes(&self, selector: impl Into<RouteSelector>) -> Result<usize> {
+        let mut routes = std::pin::pin!(self.stream_routes_matching(selector).await?);
+        let mut count = 0;
+        while let Some(route) = routes.next().await {
+            route?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Summarize the routes `selector` matches by table, protocol and
+    /// prefix length, without keeping the routes themselves.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::AddressFamily;
+    ///
+    /// let summary = conn.summarize_routes(AddressFamily::v4()).await?;
+    /// for (len, count) in &summary.ipv4_prefix_lens {
+    ///     println!("/{len}: {count}");
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "summarize_routes"))]
+    pub async fn summarize_routes(
+        &self,
+        selector: impl Into<RouteSelector>,
+    ) -> Result<RouteSummary> {
+        let mut routes = std::pin::pin!(self.stream_routes_matching(selector).await?);
+        let mut summary = RouteSummary::default();
+        while let Some(route) = routes.next().await {
+            summary.add(&route?);
+        }
+        Ok(summary)
+    }
+}
+
+/// Flow parameters for [`Connection::route_get`], matching the selectors
+/// of `ip route get`.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::route::RouteGetOptions;
+///
+/// // Which route would a packet from 10.0.0.5 arriving on eth1 take?
+/// let opts = RouteGetOptions::new()
+///     .source("10.0.0.5".parse()?)
+///     .iif("eth1");
+/// let route = conn.route_get("192.0.2.1".parse()?, opts).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use = "builders do nothing unless used"]
+pub struct RouteGetOptions {
+    source: Option<IpAddr>,
+    iif: Option<InterfaceRef>,
+    oif: Option<InterfaceRef>,
+    mark: Option<u32>,
+    uid: Option<u32>,
+    tos: u8,
+    table: Option<u32>,
+    fib_match: bool,
+}
+
+impl RouteGetOptions {
+    /// Create options for a plain destination lookup.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the source address (`from`). Must match the destination's
+    /// family.
+    pub fn source(mut self, addr: IpAddr) -> Self {
+        self.source = Some(addr);
+        self
+    }
+
+    /// Look up as if the packet arrived on `dev` (`iif`). Needs a
+    /// [`source`](Self::source).
+    pub fn iif(mut self, dev: impl Into<InterfaceRef>) -> Self {
+        self.iif = Some(dev.into());
+        self
+    }
+
+    /// Force the output interface (`oif`).
+    pub fn oif(mut self, dev: impl Into<InterfaceRef>) -> Self {
+        self.oif = Some(dev.into());
+        self
+    }
+
+    /// Set the firewall mark, for policy rules matching `fwmark`.
+    pub fn mark(mut self, mark: u32) -> Self {
+        self.mark = Some(mark);
+        self
+    }
+
+    /// Set the UID, for policy rules matching `uidrange`.
+    pub fn uid(mut self, uid: u32) -> Self {
+        self.uid = Some(uid);
+        self
+    }
+
+    /// Set the TOS byte.
+    pub fn tos(mut self, tos: u8) -> Self {
+        self.tos = tos;
+        self
+    }
+
+    /// Look up in this table only, bypassing policy rules.
+    pub fn table(mut self, table: u32) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    /// Return the matching FIB entry (its prefix and all nexthops)
+    /// instead of the resolved route (`fibmatch`). Linux 4.13+.
+    pub fn fib_match(mut self) -> Self {
+        self.fib_match = true;
+        self
+    }
+}
+
+/// Route counts returned by [`Connection::summarize_routes`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RouteSummary {
+    /// Routes counted.
+    pub total: usize,
+    /// Routes per table ID.
+    pub by_table: BTreeMap<u32, usize>,
+    /// Routes per protocol number (see [`RouteProtocol::from`]).
+    pub by_protocol: BTreeMap<u8, usize>,
+    /// IPv4 routes per destination prefix length.
+    pub ipv4_prefix_lens: BTreeMap<u8, usize>,
+    /// IPv6 routes per destination prefix length.
+    pub ipv6_prefix_lens: BTreeMap<u8, usize>,
+}
+
+impl RouteSummary {
+    /// Count `route`. Useful for summarizing routes already dumped or
+    /// arriving as events.
+    pub fn add(&mut self, route: &RouteMessage) {
+        self.total += 1;
+        *self.by_table.entry(route.table_id()).or_default() += 1;
+        *self.by_protocol.entry(route.protocol_id()).or_default() += 1;
+        let lens = match route.family() {
+            AF_INET => &mut self.ipv4_prefix_lens,
+            AF_INET6 => &mut self.ipv6_prefix_lens,
+            _ => return,
+        };
+        *lens.entry(route.dst_len()).or_default() += 1;
+    }
+
+    /// Routes installed by `protocol`.
+    pub fn protocol_count(&self, protocol: impl Into<RouteProtocol>) -> usize {
+        let number = protocol.into().number();
+        self.by_protocol.get(&number).copied().unwrap_or(0)
+    }
+}
+
+impl<'a> Extend<&'a RouteMessage> for RouteSummary {
+    fn extend<I: IntoIterator<Item = &'a RouteMessage>>(&mut self, routes: I) {
+        for route in routes {
+            self.add(route);
+        }
+    }
+}
+
+/// Outcome of [`Connection::sync_protocol_routes`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RouteSyncResult {
+    /// Routes installed or replaced.
+    pub installed: usize,
+    /// Stale routes of the protocol that were deleted.
+    pub removed: usize,
+}
+
+/// Selects routes by table, protocol and destination prefix.
+///
+/// Used to narrow route dumps and route event streams to the part of
+/// the FIB a caller cares about, e.g. to keep a BGP full table out of a
+/// monitor. Every criterion left unset matches all routes; setting
+/// [`table`](Self::table) or [`protocol`](Self::protocol) more than once
+/// matches any of the given values.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::route::RouteSelector;
+/// use nlink::netlink::types::route::RouteProtocol;
+///
+/// // Watch main-table route churn, ignoring routes learned via BGP.
+/// let selector = RouteSelector::new()
+///     .table(254)
+///     .exclude_protocol(RouteProtocol::Bgp);
+/// let mut events = std::pin::pin!(selector.filter_events(conn.events().await));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use = "selectors do nothing unless used"]
+pub struct RouteSelector {
+    family: Option<u8>,
+    tables: Vec<u32>,
+    protocols: Vec<RouteProtocol>,
+    excluded_protocols: Vec<RouteProtocol>,
+    prefix: Option<(IpAddr, u8)>,
+    oif: Option<u32>,
+    router_advertised: Option<bool>,
+}
+
+impl RouteSelector {
+    /// Create a selector that matches every route.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match routes of this address family. `AddressFamily::unspec()`
+    /// matches every family.
+    pub fn family(mut self, family: crate::AddressFamily) -> Self {
+        self.family = (family.as_u8() != 0).then_some(family.as_u8());
+        self
+    }
+
+    /// Match routes in this table. May be given several times.
+    pub fn table(mut self, table: u32) -> Self {
+        self.tables.push(table);
+        self
+    }
+
+    /// Match routes installed by this protocol. May be given several times.
+    pub fn protocol(mut self, protocol: impl Into<RouteProtocol>) -> Self {
+        self.protocols.push(protocol.into());
+        self
+    }
+
+    /// Never match routes installed by this protocol.
+    pub fn exclude_protocol(mut self, protocol: impl Into<RouteProtocol>) -> Self {
+        self.excluded_protocols.push(protocol.into());
+        self
+    }
+
+    /// Only match routes whose destination lies within `addr/len`,
+    /// including the prefix itself (`ip route show root PREFIX`).
+    /// Implies the prefix's address family.
+    pub fn prefix(mut self, addr: IpAddr, len: u8) -> Self {
+        self.prefix = Some((addr, len));
+        self
+    }
+
+    /// Only match routes leaving through this interface, directly or as
+    /// one of their multipath nexthops.
+    pub fn oif(mut self, ifindex: u32) -> Self {
+        self.oif = Some(ifindex);
+        self
+    }
+
+    /// Only match routes learned from IPv6 router advertisements (`true`)
+    /// or never match them (`false`). See
+    /// [`RouteMessage::is_router_advertised`].
+    ///
+    /// In [`filter_events`](Self::filter_events) this also applies to
+    /// address events, keyed on [`AddressOrigin::Slaac`].
+    pub fn router_advertised(mut self, router_advertised: bool) -> Self {
+        self.router_advertised = Some(router_advertised);
+        self
+    }
+
+    /// The family a dump for this selector can be limited to, or
+    /// `AF_UNSPEC` (0) for all of them.
+    fn dump_family(&self) -> u8 {
+        match (self.family, self.prefix) {
+            (Some(family), _) => family,
+            (None, Some((IpAddr::V4(_), _))) => AF_INET,
+            (None, Some((IpAddr::V6(_), _))) => AF_INET6,
+            (None, None) => 0,
+        }
+    }
+
+    /// The `RTM_GETROUTE` dump body: an `rtmsg` header plus the filter
+    /// attributes a strict-checking kernel accepts. Kernels without
+    /// strict checking only look at the family.
+    fn dump_body(&self) -> Vec<u8> {
+        let family = self.dump_family();
+        let mut rtmsg = RtMsg::new().with_family(family);
+        if let [protocol] = self.protocols[..] {
+            rtmsg = rtmsg.with_protocol(protocol.number());
+        }
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_GETROUTE, 0);
+        builder.append(&rtmsg);
+        // MPLS rejects a table filter, so only send it when the dump
+        // can't reach the MPLS handler.
+        if let [table] = self.tables[..]
+            && (family == AF_INET || family == AF_INET6)
+        {
+            builder.append_attr_u32(RtaAttr::Table as u16, table);
+        }
+        if let Some(oif) = self.oif {
+            builder.append_attr_u32(RtaAttr::Oif as u16, oif);
+        }
+        builder.finish()[NLMSG_HDRLEN..].to_vec()
+    }
+
+    /// Check whether `route` is selected.
+    pub fn matches(&self, route: &RouteMessage) -> bool {
+        self.family.is_none_or(|f| route.family() == f)
+            && (self.tables.is_empty() || self.tables.contains(&route.table_id()))
+            && (self.protocols.is_empty() || self.protocols.contains(&route.protocol()))
+            && !self.excluded_protocols.contains(&route.protocol())
+            && self
+                .prefix
+                .is_none_or(|(addr, len)| route_within(route, addr, len))
+            && self.oif.is_none_or(|oif| route_uses_oif(route, oif))
+            && self
+                .router_advertised
+                .is_none_or(|ra| route.is_router_advertised() == ra)
+    }
+
+    /// Check whether `event` passes the selector. Address events are
+    /// only subject to the [`router_advertised`](Self::router_advertised)
+    /// filter; other events always pass.
+    pub fn matches_event(&self, event: &NetworkEvent) -> bool {
+        if let Some(route) = event.as_route() {
+            return self.matches(route);
+        }
+        match (event.as_address(), self.router_advertised) {
+            (Some(addr), Some(ra)) => (addr.origin() == AddressOrigin::Slaac) == ra,
+            _ => true,
+        }
+    }
+
+    /// Drop route events that don't match from `events`, passing every
+    /// other event and error through unchanged.
+    pub fn filter_events<S>(&self, events: S) -> impl Stream<Item = Result<NetworkEvent>>
+    where
+        S: Stream<Item = Result<NetworkEvent>>,
+    {
+        let selector = self.clone();
+        events.filter(move |event| match event {
+            Ok(event) => selector.matches_event(event),
+            Err(_) => true,
+        })
+    }
+}
+
+impl From<crate::AddressFamily> for RouteSelector {
+    fn from(family: crate::AddressFamily) -> Self {
+        Self::new().family(family)
+    }
+}
+
+/// Whether `route` leaves through `oif`, directly or via a multipath
+/// nexthop.
+fn route_uses_oif(route: &RouteMessage, oif: u32) -> bool {
+    route.oif() == Some(oif)
+        || route
+            .multipath()
+            .is_some_and(|hops| hops.iter().any(|hop| hop.ifindex == oif))
+}
+
+/// Whether `route`'s destination lies within `addr/len`. A route without
+/// `RTA_DST` is the family's default route.
+fn route_within(route: &RouteMessage, addr: IpAddr, len: u8) -> bool {
+    if route.dst_len() < len {
+        return false;
+    }
+    match (addr, route.family()) {
+        (IpAddr::V4(prefix), AF_INET) => {
+            let dst = match route.destination() {
+                Some(IpAddr::V4(dst)) => *dst,
+                _ => Ipv4Addr::UNSPECIFIED,
+            };
+            ipv4_in_prefix(dst, prefix, len)
+        }
+        (IpAddr::V6(prefix), AF_INET6) => {
+            let dst = match route.destination() {
+                Some(IpAddr::V6(dst)) => *dst,
+                _ => Ipv6Addr::UNSPECIFIED,
+            };
+            ipv6_in_prefix(dst, prefix, len)
+        }
+        _ => false,
+    }
+}
+
+/// The fields the kernel treats as a route's identity when replacing.
+#[derive(Debug, PartialEq, Eq)]
+struct RouteKey {
+    family: u8,
+    table: u32,
+    dst: Option<IpAddr>,
+    dst_len: u8,
+    tos: u8,
+    metric: u32,
+}
+
+impl RouteKey {
+    fn of(route: &RouteMessage) -> Self {
+        // An IPv6 route added without a metric gets the kernel's
+        // default (IP6_RT_PRIO_USER); IPv4's default is 0.
+        let default_metric = if route.is_ipv4() { 0 } else { 1024 };
+        Self {
+            family: route.family(),
+            table: route.table_id(),
+            dst: route.destination().copied().filter(|_| route.dst_len() > 0),
+            dst_len: route.dst_len(),
+            tos: route.tos(),
+            metric: route.priority().unwrap_or(default_metric),
+        }
+    }
+}
+
+/// The `RTM_NEWROUTE` payload (without the netlink header) for `config`.
+fn add_payload<R: RouteConfig>(config: &R, interfaces: &ResolvedRouteInterfaces) -> Vec<u8> {
+    let mut add = MessageBuilder::new(NlMsgType::RTM_NEWROUTE, 0);
+    config.write_add(&mut add, interfaces);
+    add.as_bytes()[NLMSG_HDRLEN..].to_vec()
+}
+
+/// The `RTM_NEWROUTE` payload for `config` with `rtm_protocol`
+/// overwritten, so any [`RouteConfig`] can be installed as `protocol`.
+fn stamped_add_payload<R: RouteConfig>(
+    config: &R,
+    interfaces: &ResolvedRouteInterfaces,
+    protocol: RouteProtocol,
+) -> Vec<u8> {
+    let mut payload = add_payload(config, interfaces);
+    payload[std::mem::offset_of!(RtMsg, rtm_protocol)] = protocol.number();
+    payload
+}
+
+/// Whether `installed` forwards the way `expected` asked for.
+///
+/// A device left unset in `expected` matches any device, and weights
+/// only matter with more than one nexthop: the kernel reports a
+/// single-entry `RTA_MULTIPATH` as a plain gateway/device route.
+fn same_nexthops(expected: &RouteMessage, installed: &RouteMessage) -> bool {
+    if expected.route_type() != installed.route_type() {
+        return false;
+    }
+    if let Some(id) = expected.nexthop_id() {
+        return installed.nexthop_id() == Some(id);
+    }
+    let want = nexthop_list(expected);
+    let have = nexthop_list(installed);
+    want.len() == have.len()
+        && want.iter().zip(&have).all(|(w, h)| {
+            w.gateway == h.gateway
+                && (w.ifindex == 0 || w.ifindex == h.ifindex)
+                && (want.len() == 1 || w.weight == h.weight)
+        })
+}
+
+/// A route's nexthops as a list, with a single-path route as one entry.
+fn nexthop_list(route: &RouteMessage) -> Vec<ParsedNextHop> {
+    match route.multipath() {
+        Some(nexthops) => nexthops.to_vec(),
+        None => vec![ParsedNextHop {
+            ifindex: route.oif().unwrap_or(0),
+            weight: 1,
+            flags: 0,
+            gateway: route.gateway().copied(),
+        }],
+    }
+}
+
+fn ip_octets(addr: &IpAddr) -> Vec<u8> {
+    match addr {
+        IpAddr::V4(v4) => v4.octets().to_vec(),
+        IpAddr::V6(v6) => v6.octets().to_vec(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn stamped_payload_carries_protocol_and_key() {
+        let route = Ipv4Route::new("10.1.0.0", 16)
+            .gateway(Ipv4Addr::new(192, 168, 1, 1))
+            .table(100);
+        let payload = stamped_add_payload(
+            &route,
+            &ResolvedRouteInterfaces::default(),
+            RouteProtocol::from(99),
+        );
+        let msg = RouteMessage::from_bytes(&payload).unwrap();
+        assert_eq!(msg.protocol(), RouteProtocol::Unknown(99));
+        assert_eq!(
+            RouteKey::of(&msg),
+            RouteKey {
+                family: AF_INET,
+                table: 100,
+                dst: Some(IpAddr::V4(Ipv4Addr::new(10, 1, 0, 0))),
+                dst_len: 16,
+                tos: 0,
+                metric: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn route_key_applies_ipv6_default_metric() {
+        // Added without a metric, dumped back with the kernel's 1024.
+        let payload = stamped_add_payload(
+            &Ipv6Route::new("2001:db8:1::", 48).dev_index(2),
+            &ResolvedRouteInterfaces {
+                oif: Some(2),
+                multipath: Vec::new(),
+            },
+            RouteProtocol::Unknown(99),
+        );
+        let added = RouteMessage::from_bytes(&payload).unwrap();
+        let mut dumped = added.clone();
+        dumped.priority = Some(1024);
+        assert_eq!(RouteKey::of(&added), RouteKey::of(&dumped));
+
+        dumped.priority = Some(512);
+        assert_ne!(RouteKey::of(&added), RouteKey::of(&dumped));
+    }
+
+    #[test]
+    fn same_nexthops_compares_gateways_devices_and_weights() {
+        let gw = |last| IpAddr::V4(Ipv4Addr::new(192, 168, last, 1));
+        let hop = |ifindex, weight, last| ParsedNextHop {
+            ifindex,
+            weight,
+            flags: 0,
+            gateway: Some(gw(last)),
+        };
+        let expected = RouteMessage::from_bytes(&add_payload(
+            &Ipv4Route::default_route().multipath(vec![
+                NextHop::new().gateway_v4(Ipv4Addr::new(192, 168, 1, 1)),
+                NextHop::new()
+                    .gateway_v4(Ipv4Addr::new(192, 168, 2, 1))
+                    .weight(2),
+            ]),
+            &ResolvedRouteInterfaces::default(),
+        ))
+        .unwrap();
+
+        // Devices unset in the request match whatever the kernel picked.
+        let mut installed = expected.clone();
+        installed.multipath = Some(vec![hop(2, 1, 1), hop(3, 2, 2)]);
+        assert!(same_nexthops(&expected, &installed));
+
+        installed.multipath = Some(vec![hop(2, 1, 1), hop(3, 1, 2)]);
+        assert!(!same_nexthops(&expected, &installed));
+        installed.multipath = Some(vec![hop(2, 1, 1)]);
+        assert!(!same_nexthops(&expected, &installed));
+
+        // A one-entry multipath request comes back as a plain route.
+        let single = RouteMessage::from_bytes(&add_payload(
+            &Ipv4Route::default_route().multipath(vec![
+                NextHop::new().gateway_v4(Ipv4Addr::new(192, 168, 1, 1)),
+            ]),
+            &ResolvedRouteInterfaces {
+                oif: None,
+                multipath: vec![Some(2)],
+            },
+        ))
+        .unwrap();
+        let mut installed = single.clone();
+        installed.multipath = None;
+        installed.gateway = Some(gw(1));
+        installed.oif = Some(2);
+        assert!(same_nexthops(&single, &installed));
+        installed.oif = Some(3);
+        assert!(!same_nexthops(&single, &installed));
+
+        let group = RouteMessage::from_bytes(&add_payload(
+            &Ipv4Route::default_route().nexthop_group(100),
+            &ResolvedRouteInterfaces::default(),
+        ))
+        .unwrap();
+        let mut installed = group.clone();
+        assert!(same_nexthops(&group, &installed));
+        installed.nh_id = Some(101);
+        assert!(!same_nexthops(&group, &installed));
+    }
+
+    fn route_msg<R: RouteConfig>(route: &R, protocol: RouteProtocol) -> RouteMessage {
+        let payload = stamped_add_payload(route, &ResolvedRouteInterfaces::default(), protocol);
+        RouteMessage::from_bytes(&payload).unwrap()
+    }
+
+    #[test]
+    fn route_selector_matches_table_protocol_and_prefix() {
+        let bgp = route_msg(&Ipv4Route::new("10.1.2.0", 24), RouteProtocol::Bgp);
+        let static_ = route_msg(
+            &Ipv4Route::new("10.1.0.0", 16).table(100),
+            RouteProtocol::Static,
+        );
+        let default = route_msg(&Ipv4Route::default_route(), RouteProtocol::Static);
+        let v6 = route_msg(&Ipv6Route::new("2001:db8::", 32), RouteProtocol::Static);
+
+        let all = RouteSelector::new();
+        assert!(
+            [&bgp, &static_, &default, &v6]
+                .iter()
+                .all(|r| all.matches(r))
+        );
+
+        let sel = RouteSelector::new()
+            .table(254)
+            .exclude_protocol(RouteProtocol::Bgp);
+        assert!(!sel.matches(&bgp));
+        assert!(!sel.matches(&static_));
+        assert!(sel.matches(&default));
+        assert!(sel.matches(&v6));
+
+        let sel = RouteSelector::new()
+            .table(100)
+            .table(254)
+            .protocol(RouteProtocol::Static);
+        assert!(!sel.matches(&bgp));
+        assert!(sel.matches(&static_));
+
+        let sel = RouteSelector::new().prefix(IpAddr::V4(Ipv4Addr::new(10, 1, 0, 0)), 16);
+        assert!(sel.matches(&bgp));
+        assert!(sel.matches(&static_));
+        assert!(!sel.matches(&default));
+        assert!(!sel.matches(&v6));
+
+        let sel = RouteSelector::new().prefix(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+        assert!(sel.matches(&default));
+        assert!(!sel.matches(&v6));
+
+        let ra = route_msg(&Ipv6Route::new("::", 0), RouteProtocol::Ra);
+        assert!(RouteSelector::new().router_advertised(true).matches(&ra));
+        assert!(!RouteSelector::new().router_advertised(true).matches(&v6));
+        assert!(!RouteSelector::new().router_advertised(false).matches(&ra));
+        assert!(RouteSelector::new().router_advertised(false).matches(&v6));
+    }
+
+    #[test]
+    fn route_selector_dump_body_carries_kernel_filters() {
+        let body = RouteSelector::new()
+            .family(crate::AddressFamily::v4())
+            .table(100)
+            .protocol(RouteProtocol::Bgp)
+            .oif(3)
+            .dump_body();
+        let header = RtMsg::from_bytes(&body).unwrap();
+        assert_eq!(header.rtm_family, AF_INET);
+        assert_eq!(header.rtm_protocol, RouteProtocol::Bgp.number());
+        assert_eq!(header.rtm_table, 0);
+        let attrs = &body[RtMsg::SIZE..];
+        assert_eq!(attrs.len(), 16);
+        assert_eq!(
+            u16::from_ne_bytes([attrs[2], attrs[3]]),
+            RtaAttr::Table as u16
+        );
+        assert_eq!(u32::from_ne_bytes(attrs[4..8].try_into().unwrap()), 100);
+        assert_eq!(
+            u16::from_ne_bytes([attrs[10], attrs[11]]),
+            RtaAttr::Oif as u16
+        );
+        assert_eq!(u32::from_ne_bytes(attrs[12..16].try_into().unwrap()), 3);
+
+        // No table filter without a family (MPLS would reject it), and
+        // no protocol filter when several are accepted.
+        let body = RouteSelector::new()
+            .table(100)
+            .protocol(RouteProtocol::Bgp)
+            .protocol(RouteProtocol::Static)
+            .dump_body();
+        assert_eq!(body.len(), RtMsg::SIZE);
+        assert_eq!(RtMsg::from_bytes(&body).unwrap().rtm_protocol, 0);
+    }
+
+    #[test]
+    fn route_selector_dump_family() {
+        assert_eq!(RouteSelector::new().table(100).dump_family(), 0);
+        assert_eq!(
+            RouteSelector::new()
+                .prefix(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0)
+                .dump_family(),
+            AF_INET6
+        );
+        assert_eq!(
+            RouteSelector::new()
+                .prefix(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 8)
+                .dump_family(),
+            AF_INET
+        );
+    }
+
+    #[tokio::test]
+    async fn route_selector_filters_only_route_events() {
+        use crate::netlink::messages::LinkMessageBuilder;
+
+        let bgp = route_msg(&Ipv4Route::new("10.1.2.0", 24), RouteProtocol::Bgp);
+        let kernel = route_msg(&Ipv4Route::new("10.9.0.0", 16), RouteProtocol::Kernel);
+        let events = tokio_stream::iter(vec![
+            Ok(NetworkEvent::NewRoute(bgp.clone())),
+            Ok(NetworkEvent::NewLink(
+                LinkMessageBuilder::new().ifindex(3).build(),
+            )),
+            Ok(NetworkEvent::DelRoute(kernel)),
+            Ok(NetworkEvent::DelRoute(bgp)),
+        ]);
+
+        let seen: Vec<_> = RouteSelector::new()
+            .exclude_protocol(RouteProtocol::Bgp)
+            .filter_events(events)
+            .collect()
+            .await;
+        let seen: Vec<_> = seen.into_iter().map(|e| e.unwrap()).collect();
+        assert_eq!(seen.len(), 2);
+        assert!(matches!(seen[0], NetworkEvent::NewLink(_)));
+        assert!(
+            matches!(&seen[1], NetworkEvent::DelRoute(r) if r.protocol() == RouteProtocol::Kernel)
+        );
+    }
+
+    #[test]
+    fn route_summary_counts_table_protocol_and_prefix_len() {
+        let routes = [
+            route_msg(&Ipv4Route::new("10.1.2.0", 24), RouteProtocol::Bgp),
+            route_msg(&Ipv4Route::new("10.1.3.0", 24), RouteProtocol::Bgp),
+            route_msg(
+                &Ipv4Route::new("10.2.0.0", 16).table(100),
+                RouteProtocol::Static,
+            ),
+            route_msg(&Ipv6Route::new("2001:db8::", 32), RouteProtocol::Bgp),
+        ];
+        let mut summary = RouteSummary::default();
+        summary.extend(&routes);
+
+        assert_eq!(summary.total, 4);
+        assert_eq!(summary.by_table, BTreeMap::from([(100, 1), (254, 3)]));
+        assert_eq!(summary.protocol_count(RouteProtocol::Bgp), 3);
+        assert_eq!(summary.protocol_count(RouteProtocol::Static), 1);
+        assert_eq!(summary.protocol_count(RouteProtocol::Kernel), 0);
+        assert_eq!(summary.ipv4_prefix_lens, BTreeMap::from([(16, 1), (24, 2)]));
+        assert_eq!(summary.ipv6_prefix_lens, BTreeMap::from([(32, 1)]));
+    }
+
     // ---- Plan 184 — default_route constructors ----
 
     #[test]
@@ -33,13 +33,20 @@
 //! ).await?;
 //! ```
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    ops::RangeInclusive,
+};
 
 use super::{
     builder::MessageBuilder,
     error::Result,
     message::{NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST, NlMsgType},
-    types::rule::{FibRuleAction, FibRuleHdr, FraAttr},
+    messages::RuleMessage,
+    types::{
+        route::RouteProtocol,
+        rule::{FibRuleAction, FibRuleHdr, FraAttr},
+    },
 };
 
 /// Address family constants.
@@ -60,11 +67,18 @@ pub struct RuleBuilder {
     oif: Option<String>,
     fwmark: Option<u32>,
     fwmask: Option<u32>,
-    table: u32,
-    action: FibRuleAction,
+    table: Option<u32>,
+    action: Option<FibRuleAction>,
     ipproto: Option<u8>,
     sport: Option<(u16, u16)>,
     dport: Option<(u16, u16)>,
+    uid_range: Option<(u32, u32)>,
+    goto: Option<u32>,
+    suppress_prefixlen: Option<u32>,
+    suppress_ifgroup: Option<u32>,
+    tos: u8,
+    l3mdev: bool,
+    protocol: Option<RouteProtocol>,
 }
 
 impl RuleBuilder {
@@ -81,14 +95,53 @@ impl RuleBuilder {
             oif: None,
             fwmark: None,
             fwmask: None,
-            table: 254, // main
-            action: FibRuleAction::ToTbl,
+            table: None,
+            action: None,
             ipproto: None,
             sport: None,
             dport: None,
+            uid_range: None,
+            goto: None,
+            suppress_prefixlen: None,
+            suppress_ifgroup: None,
+            tos: 0,
+            l3mdev: false,
+            protocol: None,
         }
     }
 
+    /// Create a builder that identifies an existing rule, e.g. one returned
+    /// by [`Connection::get_rules`](super::Connection::get_rules).
+    ///
+    /// Every selector the kernel reported is copied, so
+    /// [`build_delete`](Self::build_delete) removes exactly this rule even
+    /// when other rules share its priority.
+    pub fn from_message(rule: &RuleMessage) -> Self {
+        let mut b = Self::new(rule.family()).priority(rule.priority());
+        b.src = rule.source();
+        b.src_len = rule.src_len();
+        b.dst = rule.destination();
+        b.dst_len = rule.dst_len();
+        b.tos = rule.tos();
+        b.iif = rule.iifname().map(str::to_string);
+        b.oif = rule.oifname().map(str::to_string);
+        b.fwmark = rule.fwmark();
+        b.fwmask = rule.fwmask();
+        b.table = Some(rule.table_id());
+        b.action = Some(rule.action());
+        b.ipproto = rule.ip_proto();
+        b.sport = rule.sport_range().map(|r| (r.start, r.end));
+        b.dport = rule.dport_range().map(|r| (r.start, r.end));
+        b.uid_range = rule.uid_range().map(|r| (r.start, r.end));
+        b.goto = rule.goto();
+        // The kernel reports unset suppressors as -1.
+        b.suppress_prefixlen = rule.suppress_prefixlen().filter(|&v| v != u32::MAX);
+        b.suppress_ifgroup = rule.suppress_ifgroup().filter(|&v| v != u32::MAX);
+        b.l3mdev = rule.l3mdev().is_some_and(|v| v != 0);
+        b.protocol = rule.protocol();
+        b
+    }
+
     /// Create a new IPv4 rule builder.
     pub fn v4() -> Self {
         Self::new(AF_INET)
@@ -192,26 +245,26 @@ impl RuleBuilder {
 
     /// Set the routing table to lookup.
     pub fn table(mut self, table: u32) -> Self {
-        self.table = table;
-        self.action = FibRuleAction::ToTbl;
+        self.table = Some(table);
+        self.action = Some(FibRuleAction::ToTbl);
         self
     }
 
     /// Set the action to blackhole (drop packets silently).
     pub fn blackhole(mut self) -> Self {
-        self.action = FibRuleAction::Blackhole;
+        self.action = Some(FibRuleAction::Blackhole);
         self
     }
 
     /// Set the action to unreachable (return ICMP unreachable).
     pub fn unreachable(mut self) -> Self {
-        self.action = FibRuleAction::Unreachable;
+        self.action = Some(FibRuleAction::Unreachable);
         self
     }
 
     /// Set the action to prohibit (return ICMP prohibited).
     pub fn prohibit(mut self) -> Self {
-        self.action = FibRuleAction::Prohibit;
+        self.action = Some(FibRuleAction::Prohibit);
         self
     }
 
@@ -253,6 +306,61 @@ impl RuleBuilder {
         self.dport(port, port)
     }
 
+    /// Set the UID range to match (inclusive).
+    pub fn uid_range(mut self, start: u32, end: u32) -> Self {
+        self.uid_range = Some((start, end));
+        self
+    }
+
+    /// Set the TOS value to match.
+    pub fn tos(mut self, tos: u8) -> Self {
+        self.tos = tos;
+        self
+    }
+
+    /// Jump to the rule with the given priority instead of looking up a table.
+    pub fn goto(mut self, priority: u32) -> Self {
+        self.goto = Some(priority);
+        self.action = Some(FibRuleAction::Goto);
+        self
+    }
+
+    /// Set the action to nop (match and continue with the next rule).
+    pub fn nop(mut self) -> Self {
+        self.action = Some(FibRuleAction::Nop);
+        self
+    }
+
+    /// Reject lookup results whose prefix length is less than or equal to
+    /// `len` (`suppress_prefixlength` in iproute2).
+    pub fn suppress_prefixlength(mut self, len: u32) -> Self {
+        self.suppress_prefixlen = Some(len);
+        self
+    }
+
+    /// Reject lookup results whose output device belongs to interface group
+    /// `group` (`suppress_ifgroup` in iproute2).
+    pub fn suppress_ifgroup(mut self, group: u32) -> Self {
+        self.suppress_ifgroup = Some(group);
+        self
+    }
+
+    /// Look up the table of the L3 master device (VRF) the packet arrived on.
+    ///
+    /// The kernel rejects l3mdev rules that also carry a table, so this
+    /// clears any table set earlier.
+    pub fn l3mdev(mut self) -> Self {
+        self.l3mdev = true;
+        self.table = Some(0);
+        self
+    }
+
+    /// Set the protocol that installed the rule (FRA_PROTOCOL).
+    pub fn protocol(mut self, protocol: RouteProtocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
     /// Build the netlink message for adding this rule.
     pub fn build(&self) -> Result<MessageBuilder> {
         self.build_internal(NlMsgType::RTM_NEWRULE, true)
@@ -271,16 +379,28 @@ impl RuleBuilder {
 
         let mut builder = MessageBuilder::new(msg_type, flags);
 
+        // A new rule defaults to a lookup in the main table. A delete leaves
+        // unset action and table as wildcards, so a rule can be removed by
+        // priority alone whatever table it points at.
+        let (action, table) = if create {
+            (
+                self.action.unwrap_or(FibRuleAction::ToTbl),
+                self.table.unwrap_or(254),
+            )
+        } else {
+            (
+                self.action.unwrap_or(FibRuleAction::Unspec),
+                self.table.unwrap_or(0),
+            )
+        };
+
         // Build header
         let mut hdr = FibRuleHdr::new().with_family(self.family);
         hdr.src_len = self.src_len;
         hdr.dst_len = self.dst_len;
-        hdr.action = self.action as u8;
-        hdr.table = if self.table <= 255 {
-            self.table as u8
-        } else {
-            0
-        };
+        hdr.tos = self.tos;
+        hdr.action = action.number();
+        hdr.table = if table <= 255 { table as u8 } else { 0 };
 
         builder.append(&hdr);
 
@@ -332,8 +452,8 @@ impl RuleBuilder {
         }
 
         // Add table if > 255
-        if self.table > 255 {
-            builder.append_attr_u32(FraAttr::Table as u16, self.table);
+        if table > 255 {
+            builder.append_attr_u32(FraAttr::Table as u16, table);
         }
 
         // Add IP protocol
@@ -363,6 +483,219 @@ impl RuleBuilder {
             builder.append_attr(FraAttr::Dport as u16, &range_bytes);
         }
 
+        // Add UID range
+        if let Some((start, end)) = self.uid_range {
+            let mut range_bytes = [0u8; 8];
+            range_bytes[..4].copy_from_slice(&start.to_ne_bytes());
+            range_bytes[4..].copy_from_slice(&end.to_ne_bytes());
+            builder.append_attr(FraAttr::UidRange as u16, &range_bytes);
+        }
+
+        // Add goto target
+        if let Some(target) = self.goto {
+            builder.append_attr_u32(FraAttr::Goto as u16, target);
+        }
+
+        // Add suppressors
+        if let Some(len) = self.suppress_prefixlen {
+            builder.append_attr_u32(FraAttr::SuppressPrefixlen as u16, len);
+        }
+        if let Some(group) = self.suppress_ifgroup {
+            builder.append_attr_u32(FraAttr::SuppressIfgroup as u16, group);
+        }
+
+        // Add l3mdev
+        if self.l3mdev {
+            builder.append_attr(FraAttr::L3Mdev as u16, &[1]);
+        }
+
+        // Add protocol
+        if let Some(proto) = self.protocol {
+            builder.append_attr(FraAttr::Protocol as u16, &[proto.number()]);
+        }
+
         Ok(builder)
     }
 }
+
+/// Selects routing rules for [`Connection::get_rules_filtered`] and
+/// [`Connection::flush_rules`](super::Connection::flush_rules).
+///
+/// An empty filter matches every rule. Converting an [`AddressFamily`]
+/// gives a filter on that family alone, so `flush_rules(AddressFamily::v4())`
+/// keeps working.
+///
+/// [`Connection::get_rules_filtered`]: super::Connection::get_rules_filtered
+/// [`AddressFamily`]: crate::AddressFamily
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::rule::RuleFilter;
+///
+/// // Remove every IPv4 rule our daemon installed in its priority block.
+/// let removed = conn.flush_rules(
+///     RuleFilter::new()
+///         .family(AddressFamily::v4())
+///         .protocol(RTPROT_MYD)
+///         .priorities(10_000..=10_999),
+/// ).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+#[must_use = "filters do nothing unless used"]
+pub struct RuleFilter {
+    family: Option<crate::AddressFamily>,
+    protocol: Option<RouteProtocol>,
+    priorities: Option<RangeInclusive<u32>>,
+    table: Option<u32>,
+}
+
+impl RuleFilter {
+    /// Create a filter that matches every rule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match rules of this address family. `AddressFamily::unspec()`
+    /// matches every family.
+    pub fn family(mut self, family: crate::AddressFamily) -> Self {
+        self.family = (family.as_u8() != 0).then_some(family);
+        self
+    }
+
+    /// Only match rules installed with this protocol (FRA_PROTOCOL).
+    pub fn protocol(mut self, protocol: impl Into<RouteProtocol>) -> Self {
+        self.protocol = Some(protocol.into());
+        self
+    }
+
+    /// Only match rules whose priority falls in this range.
+    pub fn priorities(mut self, range: RangeInclusive<u32>) -> Self {
+        self.priorities = Some(range);
+        self
+    }
+
+    /// Only match rules pointing at this routing table.
+    pub fn table(mut self, table: u32) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    /// Check whether `rule` passes the filter.
+    pub fn matches(&self, rule: &RuleMessage) -> bool {
+        self.family.is_none_or(|f| rule.family() == f.as_u8())
+            && self.protocol.is_none_or(|p| rule.protocol() == Some(p))
+            && self
+                .priorities
+                .as_ref()
+                .is_none_or(|r| r.contains(&rule.priority()))
+            && self.table.is_none_or(|t| rule.table_id() == t)
+    }
+}
+
+impl From<crate::AddressFamily> for RuleFilter {
+    fn from(family: crate::AddressFamily) -> Self {
+        Self::new().family(family)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::netlink::{messages::RuleMessage, parse::FromNetlink};
+
+    fn roundtrip(rule: RuleBuilder) -> RuleMessage {
+        let bytes = rule.build().unwrap().finish();
+        // Skip the nlmsghdr.
+        RuleMessage::from_bytes(&bytes[16..]).unwrap()
+    }
+
+    #[test]
+    fn test_goto_and_suppressors() {
+        let msg = roundtrip(
+            RuleBuilder::v4()
+                .priority(100)
+                .goto(200)
+                .suppress_prefixlength(0)
+                .suppress_ifgroup(5),
+        );
+        assert_eq!(msg.action(), FibRuleAction::Goto);
+        assert_eq!(msg.goto(), Some(200));
+        assert_eq!(msg.suppress_prefixlen(), Some(0));
+        assert_eq!(msg.suppress_ifgroup(), Some(5));
+    }
+
+    #[test]
+    fn test_uid_range_tos_l3mdev() {
+        let msg = roundtrip(
+            RuleBuilder::v6()
+                .table(100)
+                .uid_range(1000, 1999)
+                .tos(0x10)
+                .l3mdev()
+                .protocol(RouteProtocol::Static),
+        );
+        let uid = msg.uid_range().unwrap();
+        assert_eq!((uid.start, uid.end), (1000, 1999));
+        assert_eq!(msg.tos(), 0x10);
+        assert_eq!(msg.l3mdev(), Some(1));
+        assert_eq!(msg.table_id(), 0);
+        assert_eq!(msg.protocol(), Some(RouteProtocol::Static));
+    }
+
+    #[test]
+    fn test_delete_wildcards_action_and_table() {
+        let bytes = RuleBuilder::v4()
+            .priority(1000)
+            .build_delete()
+            .unwrap()
+            .finish();
+        let msg = RuleMessage::from_bytes(&bytes[16..]).unwrap();
+        assert_eq!(msg.action(), FibRuleAction::Unspec);
+        assert_eq!(msg.table_id(), 0);
+
+        let msg = roundtrip(RuleBuilder::v4().priority(1000));
+        assert_eq!(msg.action(), FibRuleAction::ToTbl);
+        assert_eq!(msg.table_id(), 254);
+    }
+
+    #[test]
+    fn test_from_message_identifies_rule() {
+        let data = crate::netlink::fixtures::rule_from_lookup();
+        let rule = RuleMessage::from_bytes(&data).unwrap();
+        let bytes = RuleBuilder::from_message(&rule)
+            .build_delete()
+            .unwrap()
+            .finish();
+        let msg = RuleMessage::from_bytes(&bytes[16..]).unwrap();
+        assert_eq!(msg.priority(), 1000);
+        assert_eq!(msg.table_id(), 100);
+        assert_eq!(msg.action(), FibRuleAction::ToTbl);
+        assert_eq!(msg.source(), rule.source());
+        assert_eq!(msg.src_len(), 8);
+        assert_eq!(msg.suppress_prefixlen(), None);
+    }
+
+    #[test]
+    fn test_rule_filter() {
+        let rule = roundtrip(
+            RuleBuilder::v4()
+                .priority(10_100)
+                .table(100)
+                .protocol(RouteProtocol::Unknown(99)),
+        );
+        assert!(RuleFilter::new().matches(&rule));
+        assert!(RuleFilter::from(crate::AddressFamily::v4()).matches(&rule));
+        assert!(RuleFilter::from(crate::AddressFamily::unspec()).matches(&rule));
+        assert!(!RuleFilter::from(crate::AddressFamily::v6()).matches(&rule));
+
+        let ours = RuleFilter::new()
+            .protocol(RouteProtocol::Unknown(99))
+            .priorities(10_000..=10_999)
+            .table(100);
+        assert!(ours.matches(&rule));
+        assert!(!ours.clone().priorities(0..=9_999).matches(&rule));
+        assert!(!ours.clone().table(200).matches(&rule));
+        assert!(!ours.protocol(RouteProtocol::Static).matches(&rule));
+    }
+}
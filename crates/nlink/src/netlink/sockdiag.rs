@@ -32,8 +32,9 @@ pub use crate::sockdiag::filter::{
 pub use crate::sockdiag::{
     socket::{InetSocket, SocketInfo, UnixSocket, UnixType},
     types::{
-        AddressFamily, BbrInfo, CcInfo, DctcpInfo, MemInfo, Protocol as InetProtocol, SocketState,
-        SocketSummary, TcpInfo, TcpState, Timer, VegasInfo,
+        AddressFamily, BbrInfo, CcInfo, DctcpInfo, DestroyTarget, InetExtension, MemInfo,
+        Protocol as InetProtocol, SocketMemory, SocketState, SocketSummary, SocketUsage, TcpInfo,
+        TcpState, Timer, VegasInfo,
     },
 };
 
@@ -86,13 +87,22 @@ const UNIX_DIAG_MEMINFO: u16 = 5;
 const UNIX_DIAG_SHUTDOWN: u16 = 6;
 const UNIX_DIAG_UID: u16 = 7;
 
+// SMC diag
+const AF_SMC: u8 = 43;
+const SMC_DIAG_LGRINFO: u16 = 2;
+const SMC_DIAG_SHUTDOWN: u16 = 3;
+const SMC_DIAG_DMBINFO: u16 = 4;
+const SMC_DIAG_FALLBACK: u16 = 5;
+
 // Netlink diag show flags
 const NDIAG_SHOW_MEMINFO: u32 = 0x01;
 const NDIAG_SHOW_GROUPS: u32 = 0x02;
+const NDIAG_SHOW_FLAGS: u32 = 0x08;
 
 // Netlink diag attributes
 const NETLINK_DIAG_MEMINFO: u16 = 0;
 const NETLINK_DIAG_GROUPS: u16 = 1;
+const NETLINK_DIAG_FLAGS: u16 = 4;
 
 impl Connection<SockDiag> {
     /// Query sockets matching the given filter.
@@ -179,6 +189,107 @@ impl Connection<SockDiag> {
             .collect())
     }
 
+    /// Query `AF_SMC` sockets (SMC-R, SMC-D and connections that fell
+    /// back to TCP), with shutdown state, fallback reason and the
+    /// SMC-R link / SMC-D DMB each connection uses.
+    ///
+    /// Needs the `smc_diag` module; without it the kernel rejects the
+    /// dump with `ENOENT`.
+    pub async fn query_smc_sockets(&self) -> Result<Vec<crate::sockdiag::socket::SmcSocket>> {
+        let _guard = self.lock_request().await;
+        self.with_timeout(async move {
+            let seq = self.socket().next_seq();
+            let pid = self.socket().pid();
+
+            // struct smc_diag_req {
+            //   __u8 diag_family; __u8 pad[2]; __u8 diag_ext;
+            //   struct inet_diag_sockid id;
+            // }
+            let mut buf = Vec::with_capacity(68);
+            buf.extend_from_slice(&0u32.to_ne_bytes()); // nlmsg_len placeholder
+            buf.extend_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+            buf.extend_from_slice(&(NLM_F_REQUEST | NLM_F_DUMP).to_ne_bytes());
+            buf.extend_from_slice(&seq.to_ne_bytes());
+            buf.extend_from_slice(&pid.to_ne_bytes());
+
+            buf.push(AF_SMC); // diag_family
+            buf.extend_from_slice(&[0u8; 2]); // pad
+            // Extension bits are `1 << (SMC_DIAG_* - 1)`; the fallback
+            // attribute needs no bit.
+            buf.push(
+                (1 << (SMC_DIAG_LGRINFO - 1))
+                    | (1 << (SMC_DIAG_SHUTDOWN - 1))
+                    | (1 << (SMC_DIAG_DMBINFO - 1)),
+            );
+            buf.extend_from_slice(&[0u8; 48]); // inet_diag_sockid (unused for dumps)
+
+            let len = buf.len() as u32;
+            buf[0..4].copy_from_slice(&len.to_ne_bytes());
+
+            self.socket().send(&buf).await?;
+
+            let mut sockets = Vec::new();
+            loop {
+                let data: Vec<u8> = self.socket().recv_msg().await?;
+
+                let mut offset = 0;
+                while offset + 16 <= data.len() {
+                    let nlmsg_len = u32::from_ne_bytes([
+                        data[offset],
+                        data[offset + 1],
+                        data[offset + 2],
+                        data[offset + 3],
+                    ]) as usize;
+                    let nlmsg_type = u16::from_ne_bytes([data[offset + 4], data[offset + 5]]);
+                    let nlmsg_flags = u16::from_ne_bytes([data[offset + 6], data[offset + 7]]);
+                    let nlmsg_seq = u32::from_ne_bytes([
+                        data[offset + 8],
+                        data[offset + 9],
+                        data[offset + 10],
+                        data[offset + 11],
+                    ]);
+
+                    if nlmsg_len < 16 || offset + nlmsg_len > data.len() {
+                        break;
+                    }
+                    if nlmsg_seq != seq {
+                        offset += (nlmsg_len + 3) & !3;
+                        continue;
+                    }
+                    if nlmsg_flags & 0x10 != 0 {
+                        return Err(crate::netlink::Error::DumpInterrupted);
+                    }
+
+                    match nlmsg_type {
+                        NLMSG_DONE => return Ok(sockets),
+                        NLMSG_ERROR if nlmsg_len >= 20 => {
+                            let errno = i32::from_ne_bytes([
+                                data[offset + 16],
+                                data[offset + 17],
+                                data[offset + 18],
+                                data[offset + 19],
+                            ]);
+                            if errno != 0 {
+                                return Err(super::error::Error::from_errno_with_context(
+                                    errno, "smc_diag",
+                                ));
+                            }
+                        }
+                        SOCK_DIAG_BY_FAMILY => {
+                            if let Some(sock) = parse_smc_msg(&data[offset..offset + nlmsg_len]) {
+                                sockets.push(sock);
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    offset += (nlmsg_len + 3) & !3;
+                }
+            }
+        })
+        .await
+    }
+
     /// Get aggregated socket statistics across all families.
     ///
     /// Queries TCP, UDP, raw, and Unix sockets and aggregates the counts
@@ -199,17 +310,37 @@ impl Connection<SockDiag> {
     /// // UNIX:  175
     /// ```
     pub async fn socket_summary(&self) -> Result<SocketSummary> {
-        use crate::sockdiag::types::{SocketSummary, TcpSummary};
+        Ok(self.summarize(0).await?.summary)
+    }
+
+    /// Get socket counts plus the buffer memory held by TCP and UDP
+    /// sockets.
+    ///
+    /// Like [`socket_summary`](Self::socket_summary), with each inet
+    /// socket's `INET_DIAG_MEMINFO` summed into
+    /// [`SocketUsage::tcp_memory`] and [`SocketUsage::udp_memory`]. See
+    /// [`socket_usage_in`](crate::sockdiag::socket_usage_in) for several
+    /// namespaces at once.
+    pub async fn socket_usage(&self) -> Result<SocketUsage> {
+        self.summarize(InetExtension::MemInfo.mask()).await
+    }
+
+    /// Shared by `socket_summary` and `socket_usage`; memory is only
+    /// filled when `extensions` asks for `INET_DIAG_MEMINFO`.
+    async fn summarize(&self, extensions: u8) -> Result<SocketUsage> {
+        use crate::sockdiag::types::TcpSummary;
 
         let tcp_filter = InetFilter {
             protocol: InetProtocol::Tcp,
             states: TcpState::all_mask(),
+            extensions,
             ..Default::default()
         };
         let tcp_sockets = self.query_inet_typed(&tcp_filter).await?;
 
         let udp_filter = InetFilter {
             protocol: InetProtocol::Udp,
+            extensions,
             ..Default::default()
         };
         let udp_sockets = self.query_inet_typed(&udp_filter).await?;
@@ -244,11 +375,23 @@ impl Connection<SockDiag> {
             }
         }
 
-        Ok(SocketSummary {
-            tcp,
-            udp: udp_sockets.len() as u32,
-            raw: raw_sockets.len() as u32,
-            unix: unix_sockets.len() as u32,
+        let memory = |sockets: &[InetSocket]| {
+            let mut total = SocketMemory::default();
+            for mem in sockets.iter().filter_map(|s| s.mem_info.as_ref()) {
+                total.add(mem);
+            }
+            total
+        };
+
+        Ok(SocketUsage {
+            summary: SocketSummary {
+                tcp,
+                udp: udp_sockets.len() as u32,
+                raw: raw_sockets.len() as u32,
+                unix: unix_sockets.len() as u32,
+            },
+            tcp_memory: memory(&tcp_sockets),
+            udp_memory: memory(&udp_sockets),
         })
     }
 
@@ -345,28 +488,24 @@ impl Connection<SockDiag> {
                         "response too short".into(),
                     ));
                 }
-                let resp_seq =
-                    u32::from_ne_bytes([data[8], data[9], data[10], data[11]]);
+                let resp_seq = u32::from_ne_bytes([data[8], data[9], data[10], data[11]]);
                 if resp_seq != seq {
                     continue;
                 }
                 let msg_type = u16::from_ne_bytes([data[4], data[5]]);
                 if msg_type == NLMSG_ERROR {
                     if data.len() >= 20 {
-                        let errno =
-                            i32::from_ne_bytes([data[16], data[17], data[18], data[19]]);
+                        let errno = i32::from_ne_bytes([data[16], data[17], data[18], data[19]]);
                         if errno != 0 {
                             // Route through the `from_errno_with_context_ext_ack`
                             // factory so the stored errno is the
                             // canonical positive POSIX value (Plan 187).
-                            return Err(
-                                crate::netlink::Error::from_errno_with_context_ext_ack(
-                                    errno,
-                                    "SOCK_DESTROY",
-                                    None,
-                                    None,
-                                ),
-                            );
+                            return Err(crate::netlink::Error::from_errno_with_context_ext_ack(
+                                errno,
+                                "SOCK_DESTROY",
+                                None,
+                                None,
+                            ));
                         }
                     }
                     return Ok(());
@@ -403,11 +542,69 @@ impl Connection<SockDiag> {
         &self,
         filter: &InetFilter,
     ) -> Result<crate::sockdiag::types::DestroyResult> {
-        let sockets = self.query_inet_typed(filter).await?;
+        self.destroy_target(filter, DestroyTarget::Matching).await
+    }
+
+    /// List the sockets [`destroy_target`](Self::destroy_target) would
+    /// destroy, without touching them — a dry run.
+    ///
+    /// `target` narrows the filter to TIME-WAIT or orphaned sockets; the
+    /// filter's ports, addresses and expression still apply.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::sockdiag::{DestroyTarget, InetFilter};
+    ///
+    /// let filter = InetFilter { local_port: Some(8080), ..Default::default() };
+    /// for sock in conn.destroy_candidates(&filter, DestroyTarget::TimeWait).await? {
+    ///     println!("would kill {} -> {}", sock.local, sock.remote);
+    /// }
+    /// ```
+    pub async fn destroy_candidates(
+        &self,
+        filter: &InetFilter,
+        target: DestroyTarget,
+    ) -> Result<Vec<InetSocket>> {
+        let filter = InetFilter {
+            states: target.states(filter.states),
+            ..filter.clone()
+        };
+        let mut sockets = self.query_inet_typed(&filter).await?;
+        sockets.retain(|sock| target.matches(sock));
+        Ok(sockets)
+    }
+
+    /// Destroy the sockets matching the filter and `target`, e.g. only
+    /// the TIME-WAIT sockets on a port. Requires `CAP_NET_ADMIN`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use nlink::sockdiag::{DestroyTarget, InetFilter};
+    ///
+    /// let filter = InetFilter { remote_port: Some(443), ..Default::default() };
+    /// let result = conn.destroy_target(&filter, DestroyTarget::Orphaned).await?;
+    /// ```
+    pub async fn destroy_target(
+        &self,
+        filter: &InetFilter,
+        target: DestroyTarget,
+    ) -> Result<crate::sockdiag::types::DestroyResult> {
+        let sockets = self.destroy_candidates(filter, target).await?;
+        Ok(self.destroy_sockets(&sockets).await)
+    }
+
+    /// Destroy each of `sockets`, collecting per-socket failures instead
+    /// of stopping at the first one.
+    pub async fn destroy_sockets(
+        &self,
+        sockets: &[InetSocket],
+    ) -> crate::sockdiag::types::DestroyResult {
         let mut destroyed = 0u32;
         let mut errors = Vec::new();
 
-        for sock in &sockets {
+        for sock in sockets {
             match self.destroy_tcp_socket(sock).await {
                 Ok(()) => destroyed += 1,
                 Err(e) => errors.push(crate::sockdiag::types::DestroyError {
@@ -417,7 +614,7 @@ impl Connection<SockDiag> {
             }
         }
 
-        Ok(crate::sockdiag::types::DestroyResult { destroyed, errors })
+        crate::sockdiag::types::DestroyResult { destroyed, errors }
     }
 
     async fn query_inet(&self, filter: &InetFilter) -> Result<Vec<SocketInfo>> {
@@ -428,7 +625,7 @@ impl Connection<SockDiag> {
             .collect())
     }
 
-    async fn query_inet_typed(&self, filter: &InetFilter) -> Result<Vec<InetSocket>> {
+    pub(crate) async fn query_inet_typed(&self, filter: &InetFilter) -> Result<Vec<InetSocket>> {
         let mut results = Vec::new();
 
         // Query IPv4 if not filtering to IPv6 only
@@ -626,10 +823,9 @@ impl Connection<SockDiag> {
                             }
                         }
                         SOCK_DIAG_BY_FAMILY | TCPDIAG_GETSOCK => {
-                            if let Some(sock) = parse_inet_msg(
-                                &data[offset..offset + nlmsg_len],
-                                filter.protocol,
-                            ) {
+                            if let Some(sock) =
+                                parse_inet_msg(&data[offset..offset + nlmsg_len], filter.protocol)
+                            {
                                 // Backstop for inexact kernel-side
                                 // lowering (#163), then the scalar filters
                                 // that have no bytecode form (#223).
@@ -739,8 +935,7 @@ impl Connection<SockDiag> {
                             }
                         }
                         SOCK_DIAG_BY_FAMILY => {
-                            if let Some(sock) = parse_unix_msg(&data[offset..offset + nlmsg_len])
-                            {
+                            if let Some(sock) = parse_unix_msg(&data[offset..offset + nlmsg_len]) {
                                 // socket_types / path_pattern were read by
                                 // nothing at all, so `.unix().stream().path(..)`
                                 // returned every unix socket of every type
@@ -801,6 +996,9 @@ impl Connection<SockDiag> {
             if filter.show_groups {
                 show |= NDIAG_SHOW_GROUPS;
             }
+            if filter.show_flags {
+                show |= NDIAG_SHOW_FLAGS;
+            }
             buf.extend_from_slice(&show.to_ne_bytes());
             buf.extend_from_slice(&[0u8; 8]);
 
@@ -810,6 +1008,10 @@ impl Connection<SockDiag> {
             self.socket().send(&buf).await?;
 
             let mut sockets = Vec::new();
+            // The kernel walks the bound-port table and then the
+            // multicast listener list, so a bound socket that joined
+            // groups comes back twice.
+            let mut seen = std::collections::HashSet::new();
 
             loop {
                 let data: Vec<u8> = self.socket().recv_msg().await?;
@@ -862,6 +1064,7 @@ impl Connection<SockDiag> {
                         SOCK_DIAG_BY_FAMILY => {
                             if let Some(sock) =
                                 parse_netlink_msg(&data[offset..offset + nlmsg_len], filter)
+                                && seen.insert(sock.inode)
                             {
                                 sockets.push(sock);
                             }
@@ -974,7 +1177,8 @@ impl Connection<SockDiag> {
                             }
                         }
                         SOCK_DIAG_BY_FAMILY => {
-                            if let Some(sock) = parse_packet_msg(&data[offset..offset + nlmsg_len]) {
+                            if let Some(sock) = parse_packet_msg(&data[offset..offset + nlmsg_len])
+                            {
                                 sockets.push(SocketInfo::Packet(sock));
                             }
                         }
@@ -1069,8 +1273,12 @@ fn parse_packet_msg(data: &[u8]) -> Option<crate::sockdiag::socket::PacketSocket
             // struct sk_meminfo (>= 36 bytes); rmem_alloc / wmem_alloc
             // map to recv_q / send_q like the other socket families.
             PACKET_DIAG_MEMINFO if attr_data.len() >= 36 => {
-                sock.recv_q =
-                    Some(u32::from_ne_bytes([attr_data[0], attr_data[1], attr_data[2], attr_data[3]]));
+                sock.recv_q = Some(u32::from_ne_bytes([
+                    attr_data[0],
+                    attr_data[1],
+                    attr_data[2],
+                    attr_data[3],
+                ]));
                 sock.send_q = Some(u32::from_ne_bytes([
                     attr_data[8],
                     attr_data[9],
@@ -1087,6 +1295,120 @@ fn parse_packet_msg(data: &[u8]) -> Option<crate::sockdiag::socket::PacketSocket
     Some(sock)
 }
 
+/// Parse a single `smc_diag_msg` (+ attributes) into an
+/// [`SmcSocket`](crate::sockdiag::socket::SmcSocket). Returns `None` if
+/// the buffer is too short or the family is not `AF_SMC`.
+fn parse_smc_msg(data: &[u8]) -> Option<crate::sockdiag::socket::SmcSocket> {
+    use crate::sockdiag::socket::{SmcFallback, SmcMode, SmcSocket, SmcdDmbInfo, SmcrLinkInfo};
+
+    // struct smc_diag_msg {
+    //   __u8 diag_family; __u8 diag_state; __u8 diag_mode; __u8 diag_shutdown;
+    //   struct inet_diag_sockid id;   // 48 bytes
+    //   __u32 diag_uid; __aligned_u64 diag_inode;
+    // } = 64 bytes, after the 16-byte nlmsghdr.
+    const MSG_LEN: usize = 64;
+    if data.len() < 16 + MSG_LEN {
+        return None;
+    }
+    let payload = &data[16..];
+    if payload[0] != AF_SMC {
+        return None;
+    }
+
+    // The message carries AF_SMC, not the CLC socket's family. IPv4
+    // sockets only fill the first word of each address.
+    let id = &payload[4..52];
+    let sport = parse_port(&id[0..2]);
+    let dport = parse_port(&id[2..4]);
+    let (src, dst) = (&id[4..20], &id[20..36]);
+    let is_v4 = src[4..].iter().chain(&dst[4..]).all(|&b| b == 0);
+    let (local_ip, remote_ip): (IpAddr, IpAddr) = if is_v4 {
+        (parse_ipv4(src).into(), parse_ipv4(dst).into())
+    } else {
+        (parse_ipv6(src).into(), parse_ipv6(dst).into())
+    };
+    let interface = u32::from_ne_bytes([id[36], id[37], id[38], id[39]]);
+    let cookie = u64::from_ne_bytes(id[40..48].try_into().ok()?);
+    let uid = u32::from_ne_bytes(payload[52..56].try_into().ok()?);
+    let inode = u64::from_ne_bytes(payload[56..64].try_into().ok()?) as u32;
+
+    let mut sock = SmcSocket {
+        state: payload[1],
+        mode: SmcMode::from_u8(payload[2]),
+        local: SocketAddr::new(local_ip, sport),
+        remote: SocketAddr::new(remote_ip, dport),
+        interface,
+        cookie,
+        uid,
+        inode,
+        shutdown: None,
+        fallback: None,
+        smcr_link: None,
+        smcd_dmb: None,
+    };
+
+    let u32_at = |d: &[u8], o: usize| u32::from_ne_bytes([d[o], d[o + 1], d[o + 2], d[o + 3]]);
+    let u64_at = |d: &[u8], o: usize| {
+        let mut b = [0u8; 8];
+        b.copy_from_slice(&d[o..o + 8]);
+        u64::from_ne_bytes(b)
+    };
+    let c_str = |d: &[u8]| {
+        let end = d.iter().position(|&b| b == 0).unwrap_or(d.len());
+        String::from_utf8_lossy(&d[..end]).into_owned()
+    };
+
+    let mut attr_offset = 16 + MSG_LEN;
+    while attr_offset + 4 <= data.len() {
+        let attr_len = u16::from_ne_bytes([data[attr_offset], data[attr_offset + 1]]) as usize;
+        let attr_type = u16::from_ne_bytes([data[attr_offset + 2], data[attr_offset + 3]]);
+        if attr_len < 4 || attr_offset + attr_len > data.len() {
+            break;
+        }
+        let attr_data = &data[attr_offset + 4..attr_offset + attr_len];
+
+        match attr_type {
+            SMC_DIAG_SHUTDOWN if !attr_data.is_empty() => {
+                sock.shutdown = Some(attr_data[0]);
+            }
+            SMC_DIAG_FALLBACK if attr_data.len() >= 8 => {
+                sock.fallback = Some(SmcFallback {
+                    reason: u32_at(attr_data, 0),
+                    peer_diagnosis: u32_at(attr_data, 4),
+                });
+            }
+            // struct smc_diag_lgrinfo { struct smc_diag_linkinfo lnk[1]; __u8 role; }
+            // linkinfo = link_id(1) + ibname[64] + ibport(1) + gid[40] + peer_gid[40].
+            SMC_DIAG_LGRINFO if attr_data.len() >= 147 => {
+                sock.smcr_link = Some(SmcrLinkInfo {
+                    link_id: attr_data[0],
+                    ib_device: c_str(&attr_data[1..65]),
+                    ib_port: attr_data[65],
+                    gid: c_str(&attr_data[66..106]),
+                    peer_gid: c_str(&attr_data[106..146]),
+                    role: attr_data[146],
+                });
+            }
+            // struct smcd_diag_dmbinfo { __u32 linkid; __aligned_u64 peer_gid,
+            // my_gid, token, peer_token; } = 40 bytes.
+            SMC_DIAG_DMBINFO if attr_data.len() >= 40 => {
+                sock.smcd_dmb = Some(SmcdDmbInfo {
+                    link_id: u32_at(attr_data, 0),
+                    peer_gid: u64_at(attr_data, 8),
+                    gid: u64_at(attr_data, 16),
+                    token: u64_at(attr_data, 24),
+                    peer_token: u64_at(attr_data, 32),
+                });
+            }
+            _ => {}
+        }
+
+        attr_offset += (attr_len + 3) & !3;
+    }
+
+    Some(sock)
+}
+
 // Helper functions for parsing
 
 fn parse_ipv4(data: &[u8]) -> std::net::Ipv4Addr {
@@ -1456,7 +1778,7 @@ fn parse_tcp_info(data: &[u8]) -> TcpInfo {
     info.delivery_rate_app_limited = data[7] != 0;
 
     info.rto = u32::from_ne_bytes([data[8], data[9], data[10], data[11]]);
-    // ATO at 12-15 (skip)
+    info.ato = u32::from_ne_bytes([data[12], data[13], data[14], data[15]]);
     info.snd_mss = u32::from_ne_bytes([data[16], data[17], data[18], data[19]]);
     info.rcv_mss = u32::from_ne_bytes([data[20], data[21], data[22], data[23]]);
 
@@ -1547,6 +1869,32 @@ fn parse_tcp_info(data: &[u8]) -> TcpInfo {
         info.snd_wnd = u32::from_ne_bytes([data[228], data[229], data[230], data[231]]);
     }
 
+    // 6.2+ (rcv_wnd, rehash), 6.7+ (RTO totals) and 6.18+ (AccECN
+    // counters), same per-block `>=` guards.
+    let u16_at = |off: usize| u16::from_ne_bytes([data[off], data[off + 1]]);
+    let u32_at =
+        |off: usize| u32::from_ne_bytes([data[off], data[off + 1], data[off + 2], data[off + 3]]);
+    if data.len() >= 240 {
+        info.rcv_wnd = u32_at(232);
+        info.rehash = u32_at(236);
+    }
+    if data.len() >= 248 {
+        info.total_rto = u16_at(240);
+        info.total_rto_recoveries = u16_at(242);
+        info.total_rto_time = u32_at(244);
+    }
+    if data.len() >= 280 {
+        info.received_ce = u32_at(248);
+        info.delivered_e1_bytes = u32_at(252);
+        info.delivered_e0_bytes = u32_at(256);
+        info.delivered_ce_bytes = u32_at(260);
+        info.received_e1_bytes = u32_at(264);
+        info.received_e0_bytes = u32_at(268);
+        info.received_ce_bytes = u32_at(272);
+        info.accecn_fail_mode = u16_at(276);
+        info.accecn_opt_seen = u16_at(278);
+    }
+
     info
 }
 
@@ -1702,13 +2050,30 @@ fn parse_unix_msg(data: &[u8]) -> Option<UnixSocket> {
     Some(sock)
 }
 
+/// Decode a `NETLINK_DIAG_GROUPS` bitmap — an array of native `unsigned
+/// long`s, bit *n* standing for group *n + 1* — into group numbers.
+fn parse_group_bitmap(data: &[u8]) -> Vec<u32> {
+    const WORD: usize = std::mem::size_of::<libc::c_ulong>();
+    let mut groups = Vec::new();
+    for (i, chunk) in data.chunks_exact(WORD).enumerate() {
+        let mut word = libc::c_ulong::from_ne_bytes(chunk.try_into().unwrap());
+        while word != 0 {
+            let bit = word.trailing_zeros();
+            groups.push((i * WORD * 8) as u32 + bit + 1);
+            word &= word - 1;
+        }
+    }
+    groups
+}
+
 fn parse_netlink_msg(
     data: &[u8],
     filter: &NetlinkFilter,
 ) -> Option<crate::sockdiag::socket::NetlinkSocket> {
-    // netlink_diag_msg: family(1) + type(1) + protocol(2) + state(4) +
-    //                   portid(4) + dst_portid(4) + dst_group(4) + ino(4) + cookie(8) = 32 bytes
-    if data.len() < 16 + 32 {
+    // netlink_diag_msg: family(1) + type(1) + protocol(1) + state(1) +
+    //                   portid(4) + dst_portid(4) + dst_group(4) + ino(4) + cookie(8) = 28 bytes
+    const MSG_LEN: usize = 28;
+    if data.len() < 16 + MSG_LEN {
         return None;
     }
 
@@ -1719,8 +2084,8 @@ fn parse_netlink_msg(
         return None;
     }
 
-    // payload[1] is ndiag_type (SOCK_RAW / SOCK_DGRAM)
-    let protocol = u16::from_ne_bytes([payload[2], payload[3]]) as u8;
+    // payload[1] is ndiag_type (SOCK_RAW / SOCK_DGRAM), payload[3] the state
+    let protocol = payload[2];
 
     // Apply protocol filter
     if let Some(filter_proto) = filter.protocol
@@ -1729,21 +2094,11 @@ fn parse_netlink_msg(
         return None;
     }
 
-    // state at offset 4..8 (unused for display)
-    let portid = u32::from_ne_bytes([payload[8], payload[9], payload[10], payload[11]]);
-    let dst_portid = u32::from_ne_bytes([payload[12], payload[13], payload[14], payload[15]]);
-    let dst_group = u32::from_ne_bytes([payload[16], payload[17], payload[18], payload[19]]);
-    let inode = u32::from_ne_bytes([payload[20], payload[21], payload[22], payload[23]]);
-    let cookie = u64::from_ne_bytes([
-        payload[24],
-        payload[25],
-        payload[26],
-        payload[27],
-        payload[28],
-        payload[29],
-        payload[30],
-        payload[31],
-    ]);
+    let portid = u32_at(payload, 4);
+    let dst_portid = u32_at(payload, 8);
+    let dst_group = u32_at(payload, 12);
+    let inode = u32_at(payload, 16);
+    let cookie = u64::from_ne_bytes(payload[20..28].try_into().ok()?);
 
     let mut sock = crate::sockdiag::socket::NetlinkSocket {
         protocol,
@@ -1751,6 +2106,8 @@ fn parse_netlink_msg(
         dst_portid,
         dst_group,
         groups: 0,
+        memberships: Vec::new(),
+        flags: None,
         inode,
         cookie,
         recv_q: None,
@@ -1759,7 +2116,7 @@ fn parse_netlink_msg(
     };
 
     // Parse attributes
-    let mut attr_offset = 16 + 32;
+    let mut attr_offset = 16 + MSG_LEN;
     while attr_offset + 4 <= data.len() {
         let attr_len = u16::from_ne_bytes([data[attr_offset], data[attr_offset + 1]]) as usize;
         let attr_type = u16::from_ne_bytes([data[attr_offset + 2], data[attr_offset + 3]]);
@@ -1771,20 +2128,24 @@ fn parse_netlink_msg(
         let attr_data = &data[attr_offset + 4..attr_offset + attr_len];
 
         match attr_type {
-            NETLINK_DIAG_MEMINFO
-                if attr_data.len() >= 36 => {
-                    sock.mem_info = Some(parse_sk_meminfo(attr_data));
-                }
-            NETLINK_DIAG_GROUPS
-                // Groups bitmask - first 4 bytes give us the basic groups u32
-                if attr_data.len() >= 4 => {
-                    sock.groups = u32::from_ne_bytes([
-                        attr_data[0],
-                        attr_data[1],
-                        attr_data[2],
-                        attr_data[3],
-                    ]);
-                }
+            NETLINK_DIAG_MEMINFO if attr_data.len() >= 36 => {
+                let mem = parse_sk_meminfo(attr_data);
+                // What ss(8) shows as Recv-Q / Send-Q for netlink.
+                sock.recv_q = Some(mem.rmem_alloc);
+                sock.send_q = Some(mem.wmem_alloc);
+                sock.mem_info = Some(mem);
+            }
+            NETLINK_DIAG_GROUPS => {
+                sock.memberships = parse_group_bitmap(attr_data);
+                sock.groups = sock
+                    .memberships
+                    .iter()
+                    .take_while(|&&g| g <= 32)
+                    .fold(0, |mask, g| mask | 1 << (g - 1));
+            }
+            NETLINK_DIAG_FLAGS if attr_data.len() >= 4 => {
+                sock.flags = Some(u32_at(attr_data, 0));
+            }
             _ => {}
         }
 
@@ -1794,6 +2155,19 @@ fn parse_netlink_msg(
     Some(sock)
 }
 
+/// Append a netlink attribute (4-byte header + padded payload) to a
+/// synthetic message in the parser tests below.
+#[cfg(test)]
+fn push_attr(buf: &mut Vec<u8>, attr_type: u16, payload: &[u8]) {
+    let len = (4 + payload.len()) as u16;
+    buf.extend_from_slice(&len.to_ne_bytes());
+    buf.extend_from_slice(&attr_type.to_ne_bytes());
+    buf.extend_from_slice(payload);
+    while !buf.len().is_multiple_of(4) {
+        buf.push(0);
+    }
+}
+
 #[cfg(test)]
 mod packet_tests {
     use super::*;
@@ -1843,16 +2217,143 @@ mod packet_tests {
         msg[16] = libc::AF_INET as u8; // wrong family
         assert!(parse_packet_msg(&msg).is_none());
     }
+}
 
-    /// Append a netlink attribute (4-byte header + padded payload).
-    fn push_attr(buf: &mut Vec<u8>, attr_type: u16, payload: &[u8]) {
-        let len = (4 + payload.len()) as u16;
-        buf.extend_from_slice(&len.to_ne_bytes());
-        buf.extend_from_slice(&attr_type.to_ne_bytes());
-        buf.extend_from_slice(payload);
-        while !buf.len().is_multiple_of(4) {
-            buf.push(0);
+#[cfg(test)]
+mod netlink_tests {
+    use super::*;
+
+    /// `parse_netlink_msg` decodes groups past 32, flags, and takes the
+    /// queue sizes from meminfo.
+    #[test]
+    fn parse_netlink_msg_groups_flags_and_queues() {
+        let mut msg = vec![0u8; 16];
+        // netlink_diag_msg: family, type, protocol, state, portid,
+        // dst_portid, dst_group, ino(u32), cookie[2].
+        msg.push(libc::AF_NETLINK as u8);
+        msg.push(3); // SOCK_RAW
+        msg.push(0); // NETLINK_ROUTE
+        msg.push(0);
+        msg.extend_from_slice(&1234u32.to_ne_bytes());
+        msg.extend_from_slice(&0u32.to_ne_bytes());
+        msg.extend_from_slice(&2u32.to_ne_bytes());
+        msg.extend_from_slice(&99u32.to_ne_bytes());
+        msg.extend_from_slice(&5u64.to_ne_bytes());
+
+        // Groups 1, 3 and 36 — the last one past the legacy u32 mask.
+        let mut bitmap = [0 as libc::c_ulong; 2];
+        for group in [1usize, 3, 36] {
+            let bit = group - 1;
+            let word_bits = libc::c_ulong::BITS as usize;
+            bitmap[bit / word_bits] |= 1 << (bit % word_bits);
         }
+        let bytes: Vec<u8> = bitmap.iter().flat_map(|w| w.to_ne_bytes()).collect();
+        push_attr(&mut msg, NETLINK_DIAG_GROUPS, &bytes);
+
+        let mut mem = Vec::new();
+        for v in [4096u32, 212992, 0, 212992, 0, 0, 0, 0, 17] {
+            mem.extend_from_slice(&v.to_ne_bytes());
+        }
+        push_attr(&mut msg, NETLINK_DIAG_MEMINFO, &mem);
+        let flags = crate::sockdiag::socket::netlink_flags::NO_ENOBUFS;
+        push_attr(&mut msg, NETLINK_DIAG_FLAGS, &flags.to_ne_bytes());
+
+        let sock = parse_netlink_msg(&msg, &NetlinkFilter::default()).expect("should parse");
+        assert_eq!(sock.portid, 1234);
+        assert_eq!(sock.dst_group, 2);
+        assert_eq!(sock.inode, 99);
+        assert_eq!(sock.cookie, 5);
+        assert_eq!(sock.memberships, [1, 3, 36]);
+        assert_eq!(sock.groups, 0b101);
+        assert!(sock.is_member(36));
+        assert!(sock.has_flag(flags));
+        assert_eq!(sock.recv_q, Some(4096));
+        assert_eq!(sock.drops(), Some(17));
+        assert!(sock.rcvbuf_usage().unwrap() < 0.02);
+    }
+}
+
+#[cfg(test)]
+mod smc_tests {
+    use super::*;
+    use crate::sockdiag::socket::SmcMode;
+
+    /// Build a synthetic `smc_diag_msg` for an IPv4 CLC socket.
+    fn smc_msg(mode: u8) -> Vec<u8> {
+        let mut msg = vec![0u8; 16];
+        msg.push(AF_SMC); // diag_family
+        msg.push(1); // diag_state = SMC_ACTIVE
+        msg.push(mode); // diag_mode
+        msg.push(0); // diag_shutdown
+        // inet_diag_sockid
+        msg.extend_from_slice(&12345u16.to_be_bytes()); // sport
+        msg.extend_from_slice(&80u16.to_be_bytes()); // dport
+        let mut src = [0u8; 16];
+        src[..4].copy_from_slice(&[10, 0, 0, 1]);
+        let mut dst = [0u8; 16];
+        dst[..4].copy_from_slice(&[10, 0, 0, 2]);
+        msg.extend_from_slice(&src);
+        msg.extend_from_slice(&dst);
+        msg.extend_from_slice(&3u32.to_ne_bytes()); // if
+        msg.extend_from_slice(&99u64.to_ne_bytes()); // cookie
+        msg.extend_from_slice(&1000u32.to_ne_bytes()); // diag_uid
+        msg.extend_from_slice(&4242u64.to_ne_bytes()); // diag_inode
+        msg
+    }
+
+    #[test]
+    fn parse_smc_msg_fallback() {
+        let mut msg = smc_msg(1);
+        let mut fb = Vec::new();
+        fb.extend_from_slice(&0x0303_0000u32.to_ne_bytes());
+        fb.extend_from_slice(&0x0301_0000u32.to_ne_bytes());
+        push_attr(&mut msg, SMC_DIAG_FALLBACK, &fb);
+        push_attr(&mut msg, SMC_DIAG_SHUTDOWN, &[2]);
+
+        let sock = parse_smc_msg(&msg).expect("should parse");
+        assert_eq!(sock.mode, SmcMode::FallbackTcp);
+        assert!(sock.is_fallback());
+        assert_eq!(sock.state_name(), "ACTIVE");
+        assert_eq!(sock.local, "10.0.0.1:12345".parse().unwrap());
+        assert_eq!(sock.remote, "10.0.0.2:80".parse().unwrap());
+        assert_eq!(sock.netid(), "smc");
+        assert_eq!(sock.interface, 3);
+        assert_eq!(sock.cookie, 99);
+        assert_eq!(sock.uid, 1000);
+        assert_eq!(sock.inode, 4242);
+        assert_eq!(sock.shutdown, Some(2));
+        let fb = sock.fallback.expect("fallback");
+        assert_eq!(fb.reason, 0x0303_0000);
+        assert_eq!(fb.peer_diagnosis, 0x0301_0000);
+    }
+
+    #[test]
+    fn parse_smc_msg_smcr_link() {
+        let mut msg = smc_msg(0);
+        let mut lgr = vec![0u8; 147];
+        lgr[0] = 1; // link_id
+        lgr[1..6].copy_from_slice(b"mlx50");
+        lgr[65] = 1; // ibport
+        lgr[66..70].copy_from_slice(b"fe80");
+        lgr[146] = 1; // role = server
+        push_attr(&mut msg, SMC_DIAG_LGRINFO, &lgr);
+
+        let sock = parse_smc_msg(&msg).expect("should parse");
+        assert_eq!(sock.mode, SmcMode::SmcR);
+        let link = sock.smcr_link.expect("lgr info");
+        assert_eq!(link.ib_device, "mlx50");
+        assert_eq!(link.ib_port, 1);
+        assert_eq!(link.gid, "fe80");
+        assert_eq!(link.role, 1);
+        assert!(sock.fallback.is_none());
+    }
+
+    #[test]
+    fn parse_smc_msg_rejects_short_and_wrong_family() {
+        assert!(parse_smc_msg(&[0u8; 40]).is_none());
+        let mut msg = smc_msg(0);
+        msg[16] = libc::AF_INET as u8;
+        assert!(parse_smc_msg(&msg).is_none());
     }
 }
 
@@ -1908,6 +2409,38 @@ mod tcp_info_tests {
         assert_eq!(info.snd_wnd, 99);
     }
 
+    /// The 6.2+ / 6.7+ / 6.18+ tail: a 280-byte `tcp_info` (6.18) fills
+    /// every field; a 240-byte one (6.2) stops after `rehash`.
+    #[test]
+    fn parse_tcp_info_reads_post_232_fields() {
+        let mut buf = vec![0u8; 280];
+        put_u32(&mut buf, 12, 40_000); // ato
+        put_u32(&mut buf, 232, 65_536); // rcv_wnd
+        put_u32(&mut buf, 236, 2); // rehash
+        buf[240..242].copy_from_slice(&3u16.to_ne_bytes()); // total_rto
+        buf[242..244].copy_from_slice(&1u16.to_ne_bytes()); // total_rto_recoveries
+        put_u32(&mut buf, 244, 600); // total_rto_time
+        put_u32(&mut buf, 248, 5); // received_ce
+        put_u32(&mut buf, 272, 7); // received_ce_bytes
+        buf[278..280].copy_from_slice(&9u16.to_ne_bytes()); // accecn_opt_seen
+
+        let info = parse_tcp_info(&buf);
+        assert_eq!(info.ato, 40_000);
+        assert_eq!(info.rcv_wnd, 65_536);
+        assert_eq!(info.rehash, 2);
+        assert_eq!(info.total_rto, 3);
+        assert_eq!(info.total_rto_recoveries, 1);
+        assert_eq!(info.total_rto_time, 600);
+        assert_eq!(info.received_ce, 5);
+        assert_eq!(info.received_ce_bytes, 7);
+        assert_eq!(info.accecn_opt_seen, 9);
+
+        let info = parse_tcp_info(&buf[..240]);
+        assert_eq!(info.rehash, 2);
+        assert_eq!(info.total_rto, 0, "6.7 block absent from a 240-byte struct");
+        assert_eq!(info.received_ce, 0);
+    }
+
     /// Rule 1 (accept-larger + graceful-shorter): older-kernel sizes
     /// leave the newer fields zeroed; oversized buffers parse the
     /// known prefix and ignore the tail.
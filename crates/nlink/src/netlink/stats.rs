@@ -36,11 +36,32 @@
 //!     }
 //!     tokio::time::sleep(Duration::from_secs(1)).await;
 //! }
+//!
+//! // Option 3: Let a Poller schedule the reads
+//! let mut poller = Poller::new(&conn, Duration::from_secs(1))
+//!     .links(["eth0"])
+//!     .qdiscs(true);
+//! loop {
+//!     let sample = poller.next().await?;
+//!     println!("{:.2} Mbps over {:?}",
+//!         sample.rates.total_bytes_per_sec() * 8.0 / 1_000_000.0,
+//!         sample.elapsed);
+//! }
 //! ```
 
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
+use tokio::time::{Interval, MissedTickBehavior};
 
-use super::messages::{LinkMessage, TcMessage};
+use super::{
+    connection::Connection,
+    error::Result,
+    messages::{LinkMessage, TcMessage},
+    protocol::Route,
+};
 
 /// Statistics for a network interface.
 #[derive(Debug, Clone, Default)]
@@ -163,6 +184,11 @@ impl LinkRates {
     pub fn total_bps(&self) -> f64 {
         self.total_bytes_per_sec() * 8.0
     }
+
+    /// Total drops per second (RX + TX).
+    pub fn total_dropped_per_sec(&self) -> f64 {
+        self.rx_dropped_per_sec + self.tx_dropped_per_sec
+    }
 }
 
 /// Statistics for a TC qdisc/class.
@@ -565,6 +591,162 @@ impl StatsTracker {
     }
 }
 
+/// One sample from a [`Poller`].
+#[derive(Debug, Clone)]
+pub struct StatsSample {
+    /// When this sample's counters were read.
+    pub at: Instant,
+    /// Time since the previous read; the rates are averaged over it.
+    pub elapsed: Duration,
+    /// Rates since the previous sample.
+    pub rates: RatesSnapshot,
+    /// Counters as of this sample.
+    pub snapshot: StatsSnapshot,
+}
+
+/// Samples link (and optionally qdisc/class) counters at a fixed
+/// interval and yields rates.
+///
+/// Ticks are scheduled from a fixed start rather than by sleeping after
+/// each read, so dump latency doesn't accumulate as drift. Rates are
+/// computed over the time actually elapsed between reads, so a late tick
+/// doesn't inflate them, and ticks missed while the caller was busy are
+/// skipped rather than fired in a burst.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::stats::Poller;
+///
+/// let mut poller = Poller::new(&conn, Duration::from_secs(1))
+///     .links(["eth0", "eth1"])
+///     .qdiscs(true);
+///
+/// loop {
+///     let sample = poller.next().await?;
+///     for rate in sample.rates.links.values() {
+///         println!("{}: {:.0} bps RX, {:.0} pps RX, {:.1} drops/s",
+///             rate.name.as_deref().unwrap_or("?"),
+///             rate.rx_bps(),
+///             rate.rx_packets_per_sec,
+///             rate.total_dropped_per_sec());
+///     }
+/// }
+/// ```
+pub struct Poller<'a> {
+    conn: &'a Connection<Route>,
+    interval: Duration,
+    links: Option<Vec<String>>,
+    qdiscs: bool,
+    classes: bool,
+    ticker: Option<Interval>,
+    previous: Option<(Instant, StatsSnapshot)>,
+}
+
+impl<'a> Poller<'a> {
+    /// Poll every link's counters every `interval`.
+    pub fn new(conn: &'a Connection<Route>, interval: Duration) -> Self {
+        Self {
+            conn,
+            interval,
+            links: None,
+            qdiscs: false,
+            classes: false,
+            ticker: None,
+            previous: None,
+        }
+    }
+
+    /// Only sample these interfaces (and their qdiscs/classes).
+    pub fn links<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.links = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Also sample qdisc counters.
+    pub fn qdiscs(mut self, enabled: bool) -> Self {
+        self.qdiscs = enabled;
+        self
+    }
+
+    /// Also sample class counters.
+    pub fn classes(mut self, enabled: bool) -> Self {
+        self.classes = enabled;
+        self
+    }
+
+    /// The sampling interval.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Wait for the next tick and return the rates since the previous one.
+    ///
+    /// The first call reads a baseline and returns one interval later.
+    /// Objects that appear between two reads show up from the following
+    /// sample on.
+    pub async fn next(&mut self) -> Result<StatsSample> {
+        if self.previous.is_none() {
+            let at = Instant::now();
+            self.previous = Some((at, self.read().await?));
+        }
+        let period = self.interval;
+        let ticker = self.ticker.get_or_insert_with(|| {
+            let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            ticker
+        });
+        ticker.tick().await;
+
+        let at = Instant::now();
+        let snapshot = self.read().await?;
+        let (previous_at, previous) = self
+            .previous
+            .replace((at, snapshot.clone()))
+            .expect("baseline read above");
+        let elapsed = at.duration_since(previous_at);
+        Ok(StatsSample {
+            at,
+            elapsed,
+            rates: snapshot.rates(&previous, elapsed),
+            snapshot,
+        })
+    }
+
+    /// Drop the baseline so the next call starts over, e.g. after the
+    /// selected interfaces were recreated and their counters reset.
+    pub fn reset(&mut self) {
+        self.ticker = None;
+        self.previous = None;
+    }
+
+    async fn read(&self) -> Result<StatsSnapshot> {
+        let mut links = self.conn.get_links().await?;
+        if let Some(names) = &self.links {
+            links.retain(|l| l.name().is_some_and(|n| names.iter().any(|s| s == n)));
+        }
+        let selected: HashSet<u32> = links.iter().map(|l| l.ifindex()).collect();
+        let wanted = |msg: &TcMessage| self.links.is_none() || selected.contains(&msg.ifindex());
+
+        let mut snapshot = StatsSnapshot::from_links(&links);
+        if self.qdiscs {
+            let mut qdiscs = self.conn.get_qdiscs().await?;
+            qdiscs.retain(wanted);
+            snapshot.add_qdiscs(&qdiscs);
+        }
+        if self.classes {
+            let mut classes = self.conn.get_classes().await?;
+            classes.retain(wanted);
+            snapshot.add_classes(&classes);
+        }
+        Ok(snapshot)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -618,6 +800,17 @@ mod tests {
         assert_eq!(rates.total_bps(), 24000.0);
     }
 
+    #[test]
+    fn test_link_rates_dropped() {
+        let rates = LinkRates {
+            rx_dropped_per_sec: 1.5,
+            tx_dropped_per_sec: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(rates.total_dropped_per_sec(), 3.5);
+    }
+
     #[test]
     fn test_stats_snapshot_rates() {
         let mut prev = StatsSnapshot::new();
@@ -1002,6 +1002,29 @@ impl EventSource for super::genl::ovpn::Ovpn {
     }
 }
 
+// Taskstats exit notifications. Delivered unicast to sockets
+// registered via `Connection::<Taskstats>::register_exit_listener()`
+// rather than on a multicast group; parsing mirrors DPLL. See
+// `crates/nlink/src/netlink/genl/taskstats/events.rs`.
+impl private::Sealed for super::genl::taskstats::Taskstats {}
+
+impl EventSource for super::genl::taskstats::Taskstats {
+    type Event = super::genl::taskstats::TaskstatsEvent;
+
+    fn parse_events(data: &[u8]) -> Vec<Self::Event> {
+        let mut events = Vec::new();
+        for msg_result in MessageIter::new(data) {
+            let Ok((_header, payload)) = msg_result else {
+                continue;
+            };
+            if let Some(evt) = super::genl::taskstats::events::parse_taskstats_event(payload) {
+                events.push(evt);
+            }
+        }
+        events
+    }
+}
+
 fn parse_ethtool_events(data: &[u8]) -> Vec<super::genl::ethtool::EthtoolEvent> {
     use super::genl::{GENL_HDRLEN, GenlMsgHdr};
 
@@ -21,9 +21,15 @@
 //!     ("net.ipv4.ip_forward", "1"),
 //!     ("net.ipv6.conf.all.forwarding", "1"),
 //! ])?;
+//!
+//! // Typed helpers for the common network parameters
+//! sysctl::set_ip_forward(true)?;
+//! if sysctl::effective_rp_filter("eth1")? == sysctl::RpFilter::Strict {
+//!     sysctl::set_rp_filter("eth1", sysctl::RpFilter::Loose)?;
+//! }
 //! ```
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::error::{Error, Result};
 
@@ -71,15 +77,7 @@ fn validate_key(key: &str) -> Result<()> {
 /// assert!(val == "0" || val == "1");
 /// ```
 pub fn get(key: &str) -> Result<String> {
-    let path = sysctl_path(key)?;
-    let contents = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
-        std::io::ErrorKind::NotFound => {
-            Error::InvalidMessage(format!("sysctl key not found: {}", key))
-        }
-        std::io::ErrorKind::PermissionDenied => Error::Io(e),
-        _ => Error::Io(e),
-    })?;
-    Ok(contents.trim_end().to_string())
+    read_path(key, &sysctl_path(key)?)
 }
 
 /// Set a sysctl value.
@@ -96,15 +94,7 @@ pub fn get(key: &str) -> Result<String> {
 /// sysctl::set("net.ipv4.ip_forward", "1")?;
 /// ```
 pub fn set(key: &str, value: &str) -> Result<()> {
-    let path = sysctl_path(key)?;
-    std::fs::write(&path, value).map_err(|e| match e.kind() {
-        std::io::ErrorKind::NotFound => {
-            Error::InvalidMessage(format!("sysctl key not found: {}", key))
-        }
-        std::io::ErrorKind::PermissionDenied => Error::Io(e),
-        _ => Error::Io(e),
-    })?;
-    Ok(())
+    write_path(key, &sysctl_path(key)?, value)
 }
 
 /// Set multiple sysctl values.
@@ -129,6 +119,206 @@ pub fn set_many(entries: &[(&str, &str)]) -> Result<()> {
     Ok(())
 }
 
+/// Read a per-interface `net.<proto>.conf.<dev>.<name>` value.
+///
+/// Unlike [`get`], `dev` is used verbatim as one path component, so
+/// interface names containing dots (VLANs such as `eth0.100`) work.
+/// `dev` may also be `all` or `default`.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::sysctl;
+///
+/// let rp = sysctl::get_conf("ipv4", "eth0.100", "rp_filter")?;
+/// ```
+pub fn get_conf(proto: &str, dev: &str, name: &str) -> Result<String> {
+    let (key, path) = conf_path(proto, dev, name)?;
+    read_path(&key, &path)
+}
+
+/// Set a per-interface `net.<proto>.conf.<dev>.<name>` value. See
+/// [`get_conf`] for how `dev` is handled.
+pub fn set_conf(proto: &str, dev: &str, name: &str, value: &str) -> Result<()> {
+    let (key, path) = conf_path(proto, dev, name)?;
+    write_path(&key, &path, value)
+}
+
+/// Read a sysctl as an integer.
+pub fn get_u32(key: &str) -> Result<u32> {
+    parse_u32(key, &get(key)?)
+}
+
+/// Read a per-interface `net.<proto>.conf.<dev>.<name>` value as an
+/// integer. See [`get_conf`] for how `dev` is handled.
+pub fn get_conf_u32(proto: &str, dev: &str, name: &str) -> Result<u32> {
+    let value = get_conf(proto, dev, name)?;
+    parse_u32(&format!("net.{proto}.conf.{dev}.{name}"), &value)
+}
+
+/// Set a per-interface value on both `all` and `default`.
+///
+/// `default` is what interfaces created later start with; `all` covers
+/// the existing ones (for keys where the kernel honours it, such as
+/// `forwarding`, or combines it with the per-interface value, such as
+/// `rp_filter`). `default` is written first, so an interface that shows
+/// up in between still ends up with the new value.
+pub fn set_conf_global(proto: &str, name: &str, value: &str) -> Result<()> {
+    set_conf(proto, "default", name, value)?;
+    set_conf(proto, "all", name, value)
+}
+
+/// Whether IPv4 forwarding (`net.ipv4.ip_forward`) is enabled.
+pub fn ip_forward() -> Result<bool> {
+    Ok(get_u32("net.ipv4.ip_forward")? != 0)
+}
+
+/// Enable or disable IPv4 forwarding on all interfaces.
+///
+/// Writing `net.ipv4.ip_forward` also resets every interface's
+/// `net.ipv4.conf.<dev>.forwarding`, so this is a host-wide switch.
+pub fn set_ip_forward(enabled: bool) -> Result<()> {
+    set("net.ipv4.ip_forward", bool_value(enabled))
+}
+
+/// Whether IPv6 forwarding (`net.ipv6.conf.all.forwarding`) is enabled.
+pub fn ipv6_forwarding() -> Result<bool> {
+    Ok(get_conf_u32("ipv6", "all", "forwarding")? != 0)
+}
+
+/// Enable or disable IPv6 forwarding on all interfaces.
+///
+/// Like `sysctl -w net.ipv6.conf.all.forwarding=...`, this also changes
+/// every interface's setting, and with it how `accept_ra` behaves (a
+/// forwarding interface ignores router advertisements unless
+/// `accept_ra` is 2).
+pub fn set_ipv6_forwarding(enabled: bool) -> Result<()> {
+    set_conf("ipv6", "all", "forwarding", bool_value(enabled))
+}
+
+/// Reverse path filtering mode (`net.ipv4.conf.<dev>.rp_filter`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RpFilter {
+    /// No source validation.
+    Off,
+    /// Drop packets whose source isn't routed back out the interface
+    /// they arrived on (RFC 3704 strict mode).
+    Strict,
+    /// Drop packets whose source isn't routable through any interface
+    /// (RFC 3704 loose mode).
+    Loose,
+}
+
+impl RpFilter {
+    /// The value the kernel uses for this mode.
+    pub fn as_u32(self) -> u32 {
+        match self {
+            Self::Off => 0,
+            Self::Strict => 1,
+            Self::Loose => 2,
+        }
+    }
+
+    /// Parse a kernel value; `None` for anything other than 0, 1 or 2.
+    pub fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Off),
+            1 => Some(Self::Strict),
+            2 => Some(Self::Loose),
+            _ => None,
+        }
+    }
+
+    /// The mode the kernel applies given the `all` and per-interface
+    /// values: the numerically larger one wins.
+    pub fn effective(all: Self, dev: Self) -> Self {
+        all.max(dev)
+    }
+}
+
+/// Read the `rp_filter` value configured on `dev` (or `all` /
+/// `default`).
+///
+/// This is only the per-interface setting; use [`effective_rp_filter`]
+/// for what the kernel actually enforces.
+pub fn rp_filter(dev: &str) -> Result<RpFilter> {
+    let value = get_conf_u32("ipv4", dev, "rp_filter")?;
+    RpFilter::from_u32(value).ok_or_else(|| {
+        Error::InvalidMessage(format!(
+            "unexpected value for net.ipv4.conf.{dev}.rp_filter: {value}"
+        ))
+    })
+}
+
+/// Read the `rp_filter` mode the kernel enforces on `dev`, which
+/// combines `conf.all` and `conf.<dev>`
+/// (see [`RpFilter::effective`]).
+pub fn effective_rp_filter(dev: &str) -> Result<RpFilter> {
+    Ok(RpFilter::effective(rp_filter("all")?, rp_filter(dev)?))
+}
+
+/// Set `rp_filter` on `dev` (or `all` / `default`).
+///
+/// Because the kernel uses the larger of `conf.all` and `conf.<dev>`,
+/// lowering one interface below the `all` value has no effect; lower
+/// `all` as well in that case.
+pub fn set_rp_filter(dev: &str, mode: RpFilter) -> Result<()> {
+    set_conf("ipv4", dev, "rp_filter", &mode.as_u32().to_string())
+}
+
+fn bool_value(enabled: bool) -> &'static str {
+    if enabled { "1" } else { "0" }
+}
+
+/// Parse a value read from `key` as an integer.
+pub(crate) fn parse_u32(key: &str, value: &str) -> Result<u32> {
+    value.trim().parse().map_err(|_| {
+        Error::InvalidMessage(format!("sysctl {} is not an integer: {:?}", key, value))
+    })
+}
+
+/// Dotted key (for messages) and `/proc/sys/` path of a per-interface
+/// sysctl.
+fn conf_path(proto: &str, dev: &str, name: &str) -> Result<(String, PathBuf)> {
+    validate_key(proto)?;
+    validate_key(name)?;
+    if dev.is_empty() || dev == "." || dev == ".." || dev.contains(['/', '\0']) {
+        return Err(Error::InvalidMessage(format!(
+            "invalid interface name for sysctl: {}",
+            dev
+        )));
+    }
+    let key = format!("net.{proto}.conf.{dev}.{name}");
+    let path = PathBuf::from("/proc/sys/net")
+        .join(proto.replace('.', "/"))
+        .join("conf")
+        .join(dev)
+        .join(name.replace('.', "/"));
+    Ok((key, path))
+}
+
+fn read_path(key: &str, path: &Path) -> Result<String> {
+    let contents = std::fs::read_to_string(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            Error::InvalidMessage(format!("sysctl key not found: {}", key))
+        }
+        std::io::ErrorKind::PermissionDenied => Error::Io(e),
+        _ => Error::Io(e),
+    })?;
+    Ok(contents.trim_end().to_string())
+}
+
+fn write_path(key: &str, path: &Path, value: &str) -> Result<()> {
+    std::fs::write(path, value).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            Error::InvalidMessage(format!("sysctl key not found: {}", key))
+        }
+        std::io::ErrorKind::PermissionDenied => Error::Io(e),
+        _ => Error::Io(e),
+    })?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,4 +349,41 @@ mod tests {
         assert!(validate_key("net.ipv6.conf.all.forwarding").is_ok());
         assert!(validate_key("kernel.hostname").is_ok());
     }
+
+    #[test]
+    fn test_conf_path_keeps_dotted_interface_names() {
+        let (key, path) = conf_path("ipv4", "eth0.100", "rp_filter").unwrap();
+        assert_eq!(key, "net.ipv4.conf.eth0.100.rp_filter");
+        assert_eq!(
+            path,
+            PathBuf::from("/proc/sys/net/ipv4/conf/eth0.100/rp_filter")
+        );
+    }
+
+    #[test]
+    fn test_conf_path_rejects_bad_interface_names() {
+        assert!(conf_path("ipv4", "..", "forwarding").is_err());
+        assert!(conf_path("ipv4", "a/b", "forwarding").is_err());
+        assert!(conf_path("ipv4", "", "forwarding").is_err());
+    }
+
+    #[test]
+    fn test_rp_filter_effective_takes_the_larger_value() {
+        use RpFilter::*;
+        assert_eq!(RpFilter::effective(Off, Strict), Strict);
+        assert_eq!(RpFilter::effective(Loose, Strict), Loose);
+        assert_eq!(RpFilter::effective(Strict, Off), Strict);
+        assert_eq!(RpFilter::effective(Off, Off), Off);
+        for mode in [Off, Strict, Loose] {
+            assert_eq!(RpFilter::from_u32(mode.as_u32()), Some(mode));
+        }
+        assert_eq!(RpFilter::from_u32(3), None);
+    }
+
+    #[test]
+    fn test_parse_u32_reports_key() {
+        assert_eq!(parse_u32("net.ipv4.ip_forward", "1").unwrap(), 1);
+        let err = parse_u32("net.ipv4.ip_forward", "yes").unwrap_err();
+        assert!(err.to_string().contains("net.ipv4.ip_forward"), "{err}");
+    }
 }
@@ -41,7 +41,7 @@ use super::{
     connection::{ack_request, create_request, replace_request},
     error::{Error, Result},
     interface_ref::InterfaceRef,
-    message::NlMsgType,
+    message::{NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REPLACE, NLM_F_REQUEST, NlMsgType},
     protocol::Route,
     psched,
     tc_handle::TcHandle,
@@ -7440,6 +7440,41 @@ impl ClassConfig for QfqClassConfig {
 // Connection extension methods
 // ============================================================================
 
+/// The `tc qdisc` verbs, each a distinct RTM_NEWQDISC flag set.
+///
+/// The kernel decides what to do with an existing qdisc from the flags
+/// alone: `add` refuses to touch one (EEXIST), `change` only modifies one
+/// in place (ENOENT if absent), `replace` creates or swaps, and `link`
+/// grafts an existing qdisc under a new parent without creating anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QdiscVerb {
+    Add,
+    Change,
+    Replace,
+    Link,
+}
+
+impl QdiscVerb {
+    fn flags(self) -> u16 {
+        let extra = match self {
+            Self::Add => NLM_F_CREATE | NLM_F_EXCL,
+            Self::Change => 0,
+            Self::Replace => NLM_F_CREATE | NLM_F_REPLACE,
+            Self::Link => NLM_F_REPLACE,
+        };
+        NLM_F_REQUEST | NLM_F_ACK | extra
+    }
+
+    fn method(self) -> &'static str {
+        match self {
+            Self::Add => "add_qdisc",
+            Self::Change => "change_qdisc",
+            Self::Replace => "replace_qdisc",
+            Self::Link => "link_qdisc",
+        }
+    }
+}
+
 impl Connection<Route> {
     /// Add a qdisc to an interface.
     ///
@@ -7514,25 +7549,8 @@ impl Connection<Route> {
         handle: Option<TcHandle>,
         config: impl QdiscConfig,
     ) -> Result<()> {
-        let parent_handle = parent.as_raw();
-        let qdisc_handle = handle.map(|h| h.as_raw()).unwrap_or(0);
-
-        let tcmsg = TcMsg::new()
-            .with_ifindex(ifindex as i32)
-            .with_parent(parent_handle)
-            .with_handle(qdisc_handle);
-
-        let mut builder = create_request(NlMsgType::RTM_NEWQDISC);
-        builder.append(&tcmsg);
-        builder.append_attr_str(TcaAttr::Kind as u16, config.kind());
-
-        let options_token = builder.nest_start(TcaAttr::Options as u16);
-        config.write_options(&mut builder)?;
-        builder.nest_end(options_token);
-
-        self.send_ack(builder)
+        self.send_new_qdisc(QdiscVerb::Add, ifindex, parent, handle, Some(&config))
             .await
-            .map_err(|e| e.with_context("add_qdisc"))
     }
 
     /// Delete a qdisc from an interface.
@@ -7576,8 +7594,10 @@ impl Connection<Route> {
         parent: TcHandle,
         handle: Option<TcHandle>,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.del_qdisc_by_index_full(ifindex, parent, handle).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.del_qdisc_by_index_full(ifindex, parent, handle)
+        })
+        .await
     }
 
     /// Delete a qdisc by interface index.
@@ -7682,25 +7702,8 @@ impl Connection<Route> {
         handle: Option<TcHandle>,
         config: impl QdiscConfig,
     ) -> Result<()> {
-        let parent_handle = parent.as_raw();
-        let qdisc_handle = handle.map(|h| h.as_raw()).unwrap_or(0);
-
-        let tcmsg = TcMsg::new()
-            .with_ifindex(ifindex as i32)
-            .with_parent(parent_handle)
-            .with_handle(qdisc_handle);
-
-        let mut builder = replace_request(NlMsgType::RTM_NEWQDISC);
-        builder.append(&tcmsg);
-        builder.append_attr_str(TcaAttr::Kind as u16, config.kind());
-
-        let options_token = builder.nest_start(TcaAttr::Options as u16);
-        config.write_options(&mut builder)?;
-        builder.nest_end(options_token);
-
-        self.send_ack(builder)
+        self.send_new_qdisc(QdiscVerb::Replace, ifindex, parent, handle, Some(&config))
             .await
-            .map_err(|e| e.with_context("replace_qdisc"))
     }
 
     /// Change a qdisc's parameters.
@@ -7766,35 +7769,85 @@ impl Connection<Route> {
         handle: Option<TcHandle>,
         config: impl QdiscConfig,
     ) -> Result<()> {
-        let parent_handle = parent.as_raw();
-        let qdisc_handle = handle.map(|h| h.as_raw()).unwrap_or(0);
+        self.send_new_qdisc(QdiscVerb::Change, ifindex, parent, handle, Some(&config))
+            .await
+    }
 
+    /// Graft an existing qdisc at `parent` (`tc qdisc link`).
+    ///
+    /// Sends `NLM_F_REPLACE` without `NLM_F_CREATE`, so nothing is ever
+    /// created: fails with [`Error::QdiscNotFound`] if no qdisc has
+    /// `handle`. Current kernels refuse to move a qdisc away from the
+    /// parent it already hangs off ("Cannot move an existing qdisc to a
+    /// different parent"), so this mostly serves to re-assert a graft.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "link_qdisc"))]
+    pub async fn link_qdisc(
+        &self,
+        dev: impl Into<InterfaceRef>,
+        parent: TcHandle,
+        handle: TcHandle,
+    ) -> Result<()> {
+        self.with_interface(&dev.into(), |ifindex| {
+            self.link_qdisc_by_index(ifindex, parent, handle)
+        })
+        .await
+    }
+
+    /// Graft an existing qdisc at `parent` by interface index.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "link_qdisc_by_index"))]
+    pub async fn link_qdisc_by_index(
+        &self,
+        ifindex: u32,
+        parent: TcHandle,
+        handle: TcHandle,
+    ) -> Result<()> {
+        self.send_new_qdisc::<IngressConfig>(QdiscVerb::Link, ifindex, parent, Some(handle), None)
+            .await
+    }
+
+    /// Send an RTM_NEWQDISC for `verb` and map the kernel's answer to the
+    /// typed qdisc errors.
+    async fn send_new_qdisc<C: QdiscConfig>(
+        &self,
+        verb: QdiscVerb,
+        ifindex: u32,
+        parent: TcHandle,
+        handle: Option<TcHandle>,
+        config: Option<&C>,
+    ) -> Result<()> {
         let tcmsg = TcMsg::new()
             .with_ifindex(ifindex as i32)
-            .with_parent(parent_handle)
-            .with_handle(qdisc_handle);
+            .with_parent(parent.as_raw())
+            .with_handle(handle.map(|h| h.as_raw()).unwrap_or(0));
 
-        let kind = config.kind().to_string();
-        let mut builder = ack_request(NlMsgType::RTM_NEWQDISC);
+        let mut builder = MessageBuilder::new(NlMsgType::RTM_NEWQDISC, verb.flags());
         builder.append(&tcmsg);
-        builder.append_attr_str(TcaAttr::Kind as u16, &kind);
-
-        let options_token = builder.nest_start(TcaAttr::Options as u16);
-        config.write_options(&mut builder)?;
-        builder.nest_end(options_token);
-
-        self.send_ack(builder).await.map_err(|e| {
-            if e.is_not_found() {
-                Error::QdiscNotFound {
-                    kind,
-                    interface: format!("ifindex {ifindex}"),
-                }
-            } else {
-                e.with_context(format!(
-                    "change_qdisc(ifindex {ifindex}, parent={})",
-                    parent
-                ))
-            }
+        if let Some(config) = config {
+            builder.append_attr_str(TcaAttr::Kind as u16, config.kind());
+            let options_token = builder.nest_start(TcaAttr::Options as u16);
+            config.write_options(&mut builder)?;
+            builder.nest_end(options_token);
+        }
+
+        // Name the qdisc by kind when we have one, else by handle.
+        let what = || match (config, handle) {
+            (Some(c), _) => c.kind().to_string(),
+            (None, Some(h)) => h.to_string(),
+            (None, None) => parent.to_string(),
+        };
+        self.send_ack(builder).await.map_err(|e| match verb {
+            QdiscVerb::Add if e.is_already_exists() => Error::QdiscExists {
+                kind: what(),
+                interface: format!("ifindex {ifindex}"),
+            },
+            QdiscVerb::Change | QdiscVerb::Link if e.is_not_found() => Error::QdiscNotFound {
+                kind: what(),
+                interface: format!("ifindex {ifindex}"),
+            },
+            _ => e.with_context(format!(
+                "{}(ifindex {ifindex}, parent={parent})",
+                verb.method()
+            )),
         })
     }
 
@@ -7883,8 +7936,10 @@ impl Connection<Route> {
         parent: TcHandle,
         classid: TcHandle,
     ) -> Result<()> {
-        let ifindex = self.resolve_interface(&dev.into()).await?;
-        self.del_class_by_index(ifindex, parent, classid).await
+        self.with_interface(&dev.into(), |ifindex| {
+            self.del_class_by_index(ifindex, parent, classid)
+        })
+        .await
     }
 
     /// Delete a TC class by interface index.
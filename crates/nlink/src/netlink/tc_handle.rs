@@ -26,6 +26,11 @@
 //!
 //! assert_eq!(TcHandle::ROOT.to_string(), "root");
 //! assert!(TcHandle::ROOT.is_root());
+//!
+//! // Shorthand and special forms accepted by `tc(8)`.
+//! assert_eq!(":a".parse::<TcHandle>().unwrap(), TcHandle::new(0, 10));
+//! assert_eq!("ffff:fff3".parse::<TcHandle>().unwrap(), TcHandle::CLSACT_EGRESS);
+//! assert_eq!(TcHandle::parse_qdisc_handle("1").unwrap(), TcHandle::major_only(1));
 //! ```
 
 use core::{fmt, str::FromStr};
@@ -33,7 +38,8 @@ use core::{fmt, str::FromStr};
 use super::types::tc::tc_handle as raw;
 
 /// A traffic-control handle: a packed `(major, minor)` `u16` pair plus
-/// the special values `ROOT`, `INGRESS`, `CLSACT`, `UNSPEC`.
+/// the special values `ROOT`, `INGRESS`, `CLSACT`, `CLSACT_EGRESS`,
+/// `UNSPEC`.
 ///
 /// Internally `(major as u32) << 16 | minor as u32`, matching the
 /// kernel's encoding. Round-trips with `tc(8)` notation via
@@ -49,9 +55,14 @@ impl TcHandle {
     /// Ingress qdisc handle (kernel constant `TC_H_INGRESS`).
     pub const INGRESS: Self = Self(raw::INGRESS);
 
-    /// Clsact qdisc handle (kernel constant `TC_H_CLSACT`).
+    /// Clsact qdisc handle (kernel constant `TC_H_CLSACT`), which is also
+    /// the filter parent of the clsact ingress hook (`ffff:fff2`).
     pub const CLSACT: Self = Self(raw::CLSACT);
 
+    /// Filter parent of the clsact egress hook (`ffff:fff3`), displayed
+    /// and parsed as `"egress"`.
+    pub const CLSACT_EGRESS: Self = Self(raw::CLSACT_EGRESS);
+
     /// Unspecified handle (`0`).
     pub const UNSPEC: Self = Self(raw::UNSPEC);
 
@@ -110,10 +121,38 @@ impl TcHandle {
         self.0 == raw::CLSACT
     }
 
+    #[inline]
+    pub const fn is_clsact_egress(self) -> bool {
+        self.0 == raw::CLSACT_EGRESS
+    }
+
     #[inline]
     pub const fn is_unspec(self) -> bool {
         self.0 == raw::UNSPEC
     }
+
+    /// Parse a qdisc `handle` argument.
+    ///
+    /// Like `tc(8)`, accepts a bare major (`"1"` is `1:`) on top of the
+    /// [`FromStr`] forms, and rejects anything with a non-zero minor.
+    pub fn parse_qdisc_handle(s: &str) -> Result<Self, TcHandleParseError> {
+        raw::parse_qdisc(s)
+            .map(Self)
+            .ok_or_else(|| TcHandleParseError::Invalid(s.to_string()))
+    }
+
+    /// Parse a filter `parent` argument.
+    ///
+    /// As in `tc filter`, `"ingress"` names the ingress hook
+    /// (`ffff:fff2`) rather than the ingress qdisc's parent (`ffff:fff1`),
+    /// so it works for filters under both an `ingress` and a `clsact`
+    /// qdisc. Everything else parses as with [`FromStr`].
+    pub fn parse_filter_parent(s: &str) -> Result<Self, TcHandleParseError> {
+        if s.eq_ignore_ascii_case("ingress") {
+            return Ok(Self::CLSACT);
+        }
+        s.parse()
+    }
 }
 
 impl fmt::Display for TcHandle {
@@ -347,6 +386,65 @@ mod tests {
         assert!("1".parse::<TcHandle>().is_err());
         assert!("1:zzzz".parse::<TcHandle>().is_err());
         assert!("zzzz:1".parse::<TcHandle>().is_err());
+        assert!(":".parse::<TcHandle>().is_err());
+        assert!("+1:".parse::<TcHandle>().is_err());
+        assert!("1:2:3".parse::<TcHandle>().is_err());
+        assert!("10000:".parse::<TcHandle>().is_err());
+    }
+
+    #[test]
+    fn tc_handle_fromstr_special_and_shorthand_forms() {
+        assert_eq!(":a".parse::<TcHandle>().unwrap(), TcHandle::new(0, 10));
+        assert_eq!("ffff:fff1".parse::<TcHandle>().unwrap(), TcHandle::INGRESS);
+        assert_eq!("FFFF:FFF2".parse::<TcHandle>().unwrap(), TcHandle::CLSACT);
+        assert_eq!(
+            "ffff:fff3".parse::<TcHandle>().unwrap(),
+            TcHandle::CLSACT_EGRESS
+        );
+        assert_eq!(
+            "egress".parse::<TcHandle>().unwrap(),
+            TcHandle::CLSACT_EGRESS
+        );
+        assert_eq!("ROOT".parse::<TcHandle>().unwrap(), TcHandle::ROOT);
+    }
+
+    #[test]
+    fn tc_handle_parse_qdisc_handle() {
+        assert_eq!(
+            TcHandle::parse_qdisc_handle("1").unwrap(),
+            TcHandle::major_only(1)
+        );
+        assert_eq!(
+            TcHandle::parse_qdisc_handle("ffff:").unwrap(),
+            TcHandle::major_only(0xffff)
+        );
+        assert_eq!(
+            TcHandle::parse_qdisc_handle("none").unwrap(),
+            TcHandle::UNSPEC
+        );
+        assert!(TcHandle::parse_qdisc_handle("1:a").is_err());
+        assert!(TcHandle::parse_qdisc_handle("root").is_err());
+        assert!(TcHandle::parse_qdisc_handle("").is_err());
+    }
+
+    #[test]
+    fn tc_handle_parse_filter_parent_maps_ingress_to_the_hook() {
+        assert_eq!(
+            TcHandle::parse_filter_parent("ingress").unwrap(),
+            TcHandle::CLSACT
+        );
+        assert_eq!(
+            TcHandle::parse_filter_parent("egress").unwrap(),
+            TcHandle::CLSACT_EGRESS
+        );
+        assert_eq!(
+            TcHandle::parse_filter_parent("1:").unwrap(),
+            TcHandle::major_only(1)
+        );
+        assert_eq!(
+            TcHandle::parse_filter_parent("root").unwrap(),
+            TcHandle::ROOT
+        );
     }
 
     #[test]
@@ -355,7 +453,9 @@ mod tests {
             TcHandle::ROOT,
             TcHandle::INGRESS,
             TcHandle::CLSACT,
+            TcHandle::CLSACT_EGRESS,
             TcHandle::UNSPEC,
+            TcHandle::new(0, 10),
             TcHandle::new(1, 10),
             TcHandle::new(0xff, 0xff),
             TcHandle::major_only(1),
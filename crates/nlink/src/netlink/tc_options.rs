@@ -26,7 +26,13 @@
 //! }
 //! ```
 
-use super::{messages::TcMessage, psched};
+use std::{collections::HashMap, net::IpAddr};
+
+use super::{
+    attr::AttrIter,
+    messages::{TcMessage, TcStatsBasic, TcStatsQueue, parse_stats2},
+    psched,
+};
 
 /// Parsed qdisc options, strongly typed by qdisc kind.
 #[derive(Debug, Clone)]
@@ -974,6 +980,278 @@ pub fn parse_htb_class_options(data: &[u8]) -> Option<HtbClassOptions> {
     Some(opts)
 }
 
+/// Parsed class options, strongly typed by class kind.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ClassOptions {
+    /// htb - Hierarchical Token Bucket class
+    Htb(HtbClassOptions),
+    /// Unknown class type (contains raw options)
+    Unknown(Vec<u8>),
+}
+
+/// Parse class options from a TcMessage.
+///
+/// Returns `None` if the message has no kind or no options.
+pub fn parse_class_options(msg: &TcMessage) -> Option<ClassOptions> {
+    let kind = msg.kind()?;
+    let data = msg.options.as_ref()?;
+
+    Some(match kind {
+        "htb" => match parse_htb_class_options(data) {
+            Some(htb) => ClassOptions::Htb(htb),
+            None => ClassOptions::Unknown(data.clone()),
+        },
+        _ => ClassOptions::Unknown(data.clone()),
+    })
+}
+
+// ============================================================================
+// Filter options
+// ============================================================================
+
+/// Parsed filter options, strongly typed by classifier kind.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum FilterOptions {
+    /// u32 - Universal 32-bit key classifier
+    U32(U32Options),
+    /// flower - Flow classifier
+    Flower(FlowerOptions),
+    /// matchall - Match every packet
+    Matchall(MatchallOptions),
+    /// Unknown filter type (contains raw options)
+    Unknown(Vec<u8>),
+}
+
+impl FilterOptions {
+    /// Actions attached to the filter, in execution order.
+    pub fn actions(&self) -> &[FilterAction] {
+        match self {
+            FilterOptions::U32(o) => &o.actions,
+            FilterOptions::Flower(o) => &o.actions,
+            FilterOptions::Matchall(o) => &o.actions,
+            FilterOptions::Unknown(_) => &[],
+        }
+    }
+
+    /// Target class (`flowid`/`classid`), if the filter sets one.
+    pub fn classid(&self) -> Option<u32> {
+        match self {
+            FilterOptions::U32(o) => o.classid,
+            FilterOptions::Flower(o) => o.classid,
+            FilterOptions::Matchall(o) => o.classid,
+            FilterOptions::Unknown(_) => None,
+        }
+    }
+
+    /// Classifier flags (`TCA_CLS_FLAGS_*`): the requested `skip_hw` /
+    /// `skip_sw` plus the kernel's `in_hw` / `not_in_hw` verdict.
+    pub fn flags(&self) -> u32 {
+        match self {
+            FilterOptions::U32(o) => o.flags,
+            FilterOptions::Flower(o) => o.flags,
+            FilterOptions::Matchall(o) => o.flags,
+            FilterOptions::Unknown(_) => 0,
+        }
+    }
+
+    /// Whether the kernel reports the rule as installed in hardware.
+    pub fn is_in_hw(&self) -> bool {
+        self.flags() & super::types::tc::filter::flower::TCA_CLS_FLAGS_IN_HW != 0
+    }
+
+    /// Number of devices the rule is offloaded to, for classifiers
+    /// that report it (flower).
+    pub fn in_hw_count(&self) -> Option<u32> {
+        match self {
+            FilterOptions::Flower(o) => o.in_hw_count,
+            _ => None,
+        }
+    }
+}
+
+/// An action attached to a filter (one slot of the `*_ACT` table).
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct FilterAction {
+    /// Position in the action table (1-based).
+    pub order: u16,
+    /// Action kind string (`"gact"`, `"mirred"`, etc.).
+    pub kind: String,
+    /// Kernel-assigned action index.
+    pub index: u32,
+    /// Raw bytes of the kind-specific `TCA_ACT_OPTIONS` payload.
+    pub options_raw: Vec<u8>,
+    /// Packets and bytes the action has seen.
+    pub stats_basic: Option<TcStatsBasic>,
+    /// Drop/overlimit counters for the action.
+    pub stats_queue: Option<TcStatsQueue>,
+    /// Action flags (`TCA_ACT_FLAGS_*`, e.g. `skip_hw`).
+    pub flags: u32,
+    /// Number of devices the action is offloaded to.
+    pub in_hw_count: Option<u32>,
+}
+
+/// u32 selector key (`match VAL/MASK at OFF`).
+///
+/// `val` and `mask` are in host order, i.e. the 32-bit word as it
+/// appears on the wire read big-endian.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct U32Key {
+    /// Value to match.
+    pub val: u32,
+    /// Mask applied before comparing.
+    pub mask: u32,
+    /// Byte offset from the current header.
+    pub off: i32,
+    /// Offset mask (`nexthdr+` matches).
+    pub offmask: i32,
+}
+
+/// u32 selector (`struct tc_u32_sel`).
+#[derive(Debug, Clone, Default)]
+pub struct U32Selector {
+    /// Selector flags (`TC_U32_TERMINAL`, `TC_U32_OFFSET`, ...).
+    pub flags: u8,
+    /// Shift applied to the variable offset.
+    pub offshift: u8,
+    /// Mask for the variable offset (host order).
+    pub offmask: u16,
+    /// Fixed offset added to the header pointer.
+    pub off: u16,
+    /// Offset of the variable offset field.
+    pub offoff: i16,
+    /// Hash table offset.
+    pub hoff: i16,
+    /// Hash mask (host order).
+    pub hmask: u32,
+    /// Match keys, all of which must match.
+    pub keys: Vec<U32Key>,
+}
+
+impl U32Selector {
+    /// Whether a match on this selector ends classification.
+    pub fn is_terminal(&self) -> bool {
+        self.flags & super::types::tc::filter::u32::TC_U32_TERMINAL != 0
+    }
+}
+
+/// u32 rule hit counters (`struct tc_u32_pcnt`), present when the
+/// kernel is built with `CONFIG_CLS_U32_PERF`.
+#[derive(Debug, Clone, Default)]
+pub struct U32Counters {
+    /// Times the rule was evaluated.
+    pub lookups: u64,
+    /// Times every key matched.
+    pub hits: u64,
+    /// Per-key success counts, parallel to [`U32Selector::keys`].
+    pub key_hits: Vec<u64>,
+}
+
+/// u32 filter options.
+#[derive(Debug, Clone, Default)]
+pub struct U32Options {
+    /// Target class (`flowid`).
+    pub classid: Option<u32>,
+    /// Hash table to continue in (`link`).
+    pub link: Option<u32>,
+    /// Number of buckets, on a hash table node.
+    pub divisor: Option<u32>,
+    /// Hash table the rule lives in (`ht`).
+    pub hash: Option<u32>,
+    /// Classifier flags (`TCA_CLS_FLAGS_*`).
+    pub flags: u32,
+    /// The selector, absent on hash table nodes.
+    pub selector: Option<U32Selector>,
+    /// Hit counters.
+    pub counters: Option<U32Counters>,
+    /// Attached actions.
+    pub actions: Vec<FilterAction>,
+}
+
+/// flower filter options.
+///
+/// Covers the commonly used keys; ports are reported in the same
+/// fields whether the rule matches TCP, UDP or SCTP.
+#[derive(Debug, Clone, Default)]
+pub struct FlowerOptions {
+    /// Target class (`classid`).
+    pub classid: Option<u32>,
+    /// Classifier flags (`TCA_CLS_FLAGS_*`).
+    pub flags: u32,
+    /// Ingress device name (`indev`).
+    pub indev: Option<String>,
+    /// Destination MAC and mask.
+    pub dst_mac: Option<([u8; 6], [u8; 6])>,
+    /// Source MAC and mask.
+    pub src_mac: Option<([u8; 6], [u8; 6])>,
+    /// Ethertype (host order).
+    pub eth_type: Option<u16>,
+    /// IP protocol number.
+    pub ip_proto: Option<u8>,
+    /// VLAN id.
+    pub vlan_id: Option<u16>,
+    /// VLAN priority.
+    pub vlan_prio: Option<u8>,
+    /// Source IP address and prefix length.
+    pub src_ip: Option<(IpAddr, u8)>,
+    /// Destination IP address and prefix length.
+    pub dst_ip: Option<(IpAddr, u8)>,
+    /// Source port.
+    pub src_port: Option<u16>,
+    /// Destination port.
+    pub dst_port: Option<u16>,
+    /// IP TOS value and mask.
+    pub ip_tos: Option<(u8, u8)>,
+    /// IP TTL value and mask.
+    pub ip_ttl: Option<(u8, u8)>,
+    /// TCP flags value and mask (host order).
+    pub tcp_flags: Option<(u16, u16)>,
+    /// Source port range (inclusive).
+    pub src_port_range: Option<(u16, u16)>,
+    /// Destination port range (inclusive).
+    pub dst_port_range: Option<(u16, u16)>,
+    /// Tunnel key ID (`enc_key_id`).
+    pub enc_key_id: Option<u32>,
+    /// Outer source address and prefix length (`enc_src_ip`).
+    pub enc_src_ip: Option<(IpAddr, u8)>,
+    /// Outer destination address and prefix length (`enc_dst_ip`).
+    pub enc_dst_ip: Option<(IpAddr, u8)>,
+    /// Outer UDP destination port (`enc_dst_port`).
+    pub enc_dst_port: Option<u16>,
+    /// Number of devices the rule is offloaded to.
+    pub in_hw_count: Option<u32>,
+    /// Attached actions.
+    pub actions: Vec<FilterAction>,
+}
+
+/// matchall filter options.
+#[derive(Debug, Clone, Default)]
+pub struct MatchallOptions {
+    /// Target class (`classid`).
+    pub classid: Option<u32>,
+    /// Classifier flags (`TCA_CLS_FLAGS_*`).
+    pub flags: u32,
+    /// Attached actions.
+    pub actions: Vec<FilterAction>,
+}
+
+/// Parse filter options from a TcMessage.
+///
+/// Returns `None` if the message has no kind or no options.
+pub fn parse_filter_options(msg: &TcMessage) -> Option<FilterOptions> {
+    let kind = msg.kind()?;
+    let data = msg.options.as_ref()?;
+
+    Some(match kind {
+        "u32" => FilterOptions::U32(parse_u32_options(data)),
+        "flower" => FilterOptions::Flower(parse_flower_options(data)),
+        "matchall" => FilterOptions::Matchall(parse_matchall_options(data)),
+        _ => FilterOptions::Unknown(data.clone()),
+    })
+}
+
 // ============================================================================
 // Internal parsing functions
 // ============================================================================
@@ -1204,8 +1482,7 @@ fn parse_tbf_options(data: &[u8]) -> TbfOptions {
 
     // Byte counts, from the authoritative attribute if the kernel sent one,
     // else converted back from the tick-valued qopt fields.
-    opts.burst =
-        burst_bytes.unwrap_or_else(|| psched::tc_calc_xmitsize(opts.rate, buffer_ticks));
+    opts.burst = burst_bytes.unwrap_or_else(|| psched::tc_calc_xmitsize(opts.rate, buffer_ticks));
     // The peak bucket is sized against the peak rate; with no peakrate the
     // kernel sizes it against the main rate.
     let peak = if opts.peakrate > 0 {
@@ -1716,10 +1993,412 @@ fn parse_sfq_options(data: &[u8]) -> SfqOptions {
     }
 }
 
+fn be_u16(b: &[u8]) -> Option<u16> {
+    Some(u16::from_be_bytes(b.get(..2)?.try_into().ok()?))
+}
+
+fn ne_u32(b: &[u8]) -> Option<u32> {
+    Some(u32::from_ne_bytes(b.get(..4)?.try_into().ok()?))
+}
+
+fn ne_u64(b: &[u8]) -> Option<u64> {
+    Some(u64::from_ne_bytes(b.get(..8)?.try_into().ok()?))
+}
+
+/// Parse a filter's action table (`TCA_*_ACT`): one nested slot per
+/// action, numbered by execution order.
+fn parse_filter_actions(data: &[u8]) -> Vec<FilterAction> {
+    use super::types::tc::action::{
+        TCA_ACT_FLAGS, TCA_ACT_IN_HW_COUNT, TCA_ACT_INDEX, TCA_ACT_KIND, TCA_ACT_OPTIONS,
+        TCA_ACT_STATS,
+    };
+
+    let mut actions = Vec::new();
+    for (order, slot) in AttrIter::new(data) {
+        let mut act = FilterAction {
+            order,
+            ..Default::default()
+        };
+        for (attr_type, payload) in AttrIter::new(slot) {
+            match attr_type {
+                TCA_ACT_KIND => {
+                    let bytes = payload.split(|&b| b == 0).next().unwrap_or(payload);
+                    act.kind = String::from_utf8_lossy(bytes).into_owned();
+                }
+                TCA_ACT_INDEX => act.index = ne_u32(payload).unwrap_or(act.index),
+                TCA_ACT_OPTIONS => {
+                    act.options_raw = payload.to_vec();
+                    // Bound actions carry their index as the first field
+                    // of the kind's PARMS struct rather than as a sibling
+                    // TCA_ACT_INDEX.
+                    if act.index == 0
+                        && let Some((_, parms)) = AttrIter::new(payload).next()
+                    {
+                        act.index = ne_u32(parms).unwrap_or(0);
+                    }
+                }
+                TCA_ACT_STATS => {
+                    let mut stats = TcMessage::new();
+                    parse_stats2(&mut stats, payload);
+                    act.stats_basic = stats.stats_basic().copied();
+                    act.stats_queue = stats.stats_queue().copied();
+                }
+                // nla_bitfield32: the value comes first.
+                TCA_ACT_FLAGS => act.flags = ne_u32(payload).unwrap_or(0),
+                TCA_ACT_IN_HW_COUNT => act.in_hw_count = ne_u32(payload),
+                _ => {}
+            }
+        }
+        actions.push(act);
+    }
+    actions
+}
+
+fn parse_u32_options(data: &[u8]) -> U32Options {
+    use super::types::tc::filter::u32::*;
+
+    let mut opts = U32Options::default();
+    let mut pcnt: Option<&[u8]> = None;
+
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            TCA_U32_CLASSID => opts.classid = ne_u32(payload),
+            TCA_U32_LINK => opts.link = ne_u32(payload),
+            TCA_U32_DIVISOR => opts.divisor = ne_u32(payload),
+            TCA_U32_HASH => opts.hash = ne_u32(payload),
+            TCA_U32_FLAGS => opts.flags = ne_u32(payload).unwrap_or(0),
+            TCA_U32_SEL => opts.selector = parse_u32_selector(payload),
+            TCA_U32_PCNT => pcnt = Some(payload),
+            TCA_U32_ACT => opts.actions = parse_filter_actions(payload),
+            _ => {}
+        }
+    }
+
+    // struct tc_u32_pcnt: u64 rcnt, u64 rhit, u64 kcnts[nkeys]
+    if let Some(p) = pcnt
+        && let (Some(lookups), Some(hits)) = (ne_u64(p), p.get(8..).and_then(ne_u64))
+    {
+        let nkeys = opts.selector.as_ref().map_or(0, |s| s.keys.len());
+        let key_hits = p[16..]
+            .chunks_exact(8)
+            .take(nkeys)
+            .filter_map(ne_u64)
+            .collect();
+        opts.counters = Some(U32Counters {
+            lookups,
+            hits,
+            key_hits,
+        });
+    }
+
+    opts
+}
+
+/// Parse `struct tc_u32_sel`: a 16-byte header followed by `nkeys`
+/// 16-byte `tc_u32_key`s. Masks and values are big-endian on the wire.
+fn parse_u32_selector(data: &[u8]) -> Option<U32Selector> {
+    if data.len() < 16 {
+        return None;
+    }
+    let nkeys = data[2] as usize;
+    let mut sel = U32Selector {
+        flags: data[0],
+        offshift: data[1],
+        offmask: be_u16(&data[4..])?,
+        off: u16::from_ne_bytes(data[6..8].try_into().ok()?),
+        offoff: i16::from_ne_bytes(data[8..10].try_into().ok()?),
+        hoff: i16::from_ne_bytes(data[10..12].try_into().ok()?),
+        hmask: u32::from_be_bytes(data[12..16].try_into().ok()?),
+        keys: Vec::with_capacity(nkeys),
+    };
+    for key in data[16..].chunks_exact(16).take(nkeys) {
+        sel.keys.push(U32Key {
+            mask: u32::from_be_bytes(key[0..4].try_into().ok()?),
+            val: u32::from_be_bytes(key[4..8].try_into().ok()?),
+            off: i32::from_ne_bytes(key[8..12].try_into().ok()?),
+            offmask: i32::from_ne_bytes(key[12..16].try_into().ok()?),
+        });
+    }
+    Some(sel)
+}
+
+fn parse_flower_options(data: &[u8]) -> FlowerOptions {
+    use super::types::tc::filter::flower::*;
+
+    let mut opts = FlowerOptions::default();
+    let mut attrs: HashMap<u16, &[u8]> = HashMap::new();
+
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            TCA_FLOWER_CLASSID => opts.classid = ne_u32(payload),
+            TCA_FLOWER_FLAGS => opts.flags = ne_u32(payload).unwrap_or(0),
+            TCA_FLOWER_IN_HW_COUNT => opts.in_hw_count = ne_u32(payload),
+            TCA_FLOWER_INDEV => {
+                let bytes = payload.split(|&b| b == 0).next().unwrap_or(payload);
+                opts.indev = Some(String::from_utf8_lossy(bytes).into_owned());
+            }
+            TCA_FLOWER_ACT => opts.actions = parse_filter_actions(payload),
+            _ => {
+                attrs.insert(attr_type, payload);
+            }
+        }
+    }
+
+    // Keys come with a sibling *_MASK attribute; a missing mask means an
+    // exact match.
+    let u8_key = |key, mask| -> Option<(u8, u8)> {
+        let v = *attrs.get(&key)?.first()?;
+        let m = attrs.get(&mask).and_then(|m| m.first().copied());
+        Some((v, m.unwrap_or(0xff)))
+    };
+    let mac_key = |key, mask| -> Option<([u8; 6], [u8; 6])> {
+        let v: [u8; 6] = attrs.get(&key)?.get(..6)?.try_into().ok()?;
+        let m = attrs
+            .get(&mask)
+            .and_then(|m| m.get(..6)?.try_into().ok())
+            .unwrap_or([0xff; 6]);
+        Some((v, m))
+    };
+    let ip_key = |key, mask| -> Option<(IpAddr, u8)> {
+        let v = attrs.get(&key)?;
+        let m = attrs.get(&mask).copied();
+        let prefix = |m: Option<&[u8]>, bits: u8| {
+            m.map_or(bits, |m| m.iter().map(|b| b.count_ones() as u8).sum())
+        };
+        match v.len() {
+            4 => {
+                let octets: [u8; 4] = (*v).try_into().ok()?;
+                Some((IpAddr::from(octets), prefix(m, 32)))
+            }
+            16 => {
+                let octets: [u8; 16] = (*v).try_into().ok()?;
+                Some((IpAddr::from(octets), prefix(m, 128)))
+            }
+            _ => None,
+        }
+    };
+    let port = |keys: [u16; 3]| {
+        keys.iter()
+            .find_map(|k| attrs.get(k).and_then(|p| be_u16(p)))
+    };
+
+    opts.dst_mac = mac_key(TCA_FLOWER_KEY_ETH_DST, TCA_FLOWER_KEY_ETH_DST_MASK);
+    opts.src_mac = mac_key(TCA_FLOWER_KEY_ETH_SRC, TCA_FLOWER_KEY_ETH_SRC_MASK);
+    opts.eth_type = attrs.get(&TCA_FLOWER_KEY_ETH_TYPE).and_then(|p| be_u16(p));
+    opts.ip_proto = attrs
+        .get(&TCA_FLOWER_KEY_IP_PROTO)
+        .and_then(|p| p.first().copied());
+    opts.vlan_id = attrs
+        .get(&TCA_FLOWER_KEY_VLAN_ID)
+        .and_then(|p| Some(u16::from_ne_bytes(p.get(..2)?.try_into().ok()?)));
+    opts.vlan_prio = attrs
+        .get(&TCA_FLOWER_KEY_VLAN_PRIO)
+        .and_then(|p| p.first().copied());
+    opts.src_ip = ip_key(TCA_FLOWER_KEY_IPV4_SRC, TCA_FLOWER_KEY_IPV4_SRC_MASK)
+        .or_else(|| ip_key(TCA_FLOWER_KEY_IPV6_SRC, TCA_FLOWER_KEY_IPV6_SRC_MASK));
+    opts.dst_ip = ip_key(TCA_FLOWER_KEY_IPV4_DST, TCA_FLOWER_KEY_IPV4_DST_MASK)
+        .or_else(|| ip_key(TCA_FLOWER_KEY_IPV6_DST, TCA_FLOWER_KEY_IPV6_DST_MASK));
+    opts.src_port = port([
+        TCA_FLOWER_KEY_TCP_SRC,
+        TCA_FLOWER_KEY_UDP_SRC,
+        TCA_FLOWER_KEY_SCTP_SRC,
+    ]);
+    opts.dst_port = port([
+        TCA_FLOWER_KEY_TCP_DST,
+        TCA_FLOWER_KEY_UDP_DST,
+        TCA_FLOWER_KEY_SCTP_DST,
+    ]);
+    opts.ip_tos = u8_key(TCA_FLOWER_KEY_IP_TOS, TCA_FLOWER_KEY_IP_TOS_MASK);
+    opts.ip_ttl = u8_key(TCA_FLOWER_KEY_IP_TTL, TCA_FLOWER_KEY_IP_TTL_MASK);
+    opts.tcp_flags = attrs.get(&TCA_FLOWER_KEY_TCP_FLAGS).and_then(|p| {
+        let mask = attrs
+            .get(&TCA_FLOWER_KEY_TCP_FLAGS_MASK)
+            .and_then(|m| be_u16(m))
+            .unwrap_or(0xffff);
+        Some((be_u16(p)?, mask))
+    });
+    let range = |min, max| {
+        let min = attrs.get(&min).and_then(|p| be_u16(p))?;
+        Some((min, attrs.get(&max).and_then(|p| be_u16(p))?))
+    };
+    opts.src_port_range = range(TCA_FLOWER_KEY_PORT_SRC_MIN, TCA_FLOWER_KEY_PORT_SRC_MAX);
+    opts.dst_port_range = range(TCA_FLOWER_KEY_PORT_DST_MIN, TCA_FLOWER_KEY_PORT_DST_MAX);
+    opts.enc_key_id = attrs
+        .get(&TCA_FLOWER_KEY_ENC_KEY_ID)
+        .and_then(|p| Some(u32::from_be_bytes(p.get(..4)?.try_into().ok()?)));
+    opts.enc_src_ip = ip_key(
+        TCA_FLOWER_KEY_ENC_IPV4_SRC,
+        TCA_FLOWER_KEY_ENC_IPV4_SRC_MASK,
+    )
+    .or_else(|| {
+        ip_key(
+            TCA_FLOWER_KEY_ENC_IPV6_SRC,
+            TCA_FLOWER_KEY_ENC_IPV6_SRC_MASK,
+        )
+    });
+    opts.enc_dst_ip = ip_key(
+        TCA_FLOWER_KEY_ENC_IPV4_DST,
+        TCA_FLOWER_KEY_ENC_IPV4_DST_MASK,
+    )
+    .or_else(|| {
+        ip_key(
+            TCA_FLOWER_KEY_ENC_IPV6_DST,
+            TCA_FLOWER_KEY_ENC_IPV6_DST_MASK,
+        )
+    });
+    opts.enc_dst_port = attrs
+        .get(&TCA_FLOWER_KEY_ENC_UDP_DST_PORT)
+        .and_then(|p| be_u16(p));
+
+    opts
+}
+
+fn parse_matchall_options(data: &[u8]) -> MatchallOptions {
+    use super::types::tc::filter::matchall::*;
+
+    let mut opts = MatchallOptions::default();
+    for (attr_type, payload) in AttrIter::new(data) {
+        match attr_type {
+            TCA_MATCHALL_CLASSID => opts.classid = ne_u32(payload),
+            TCA_MATCHALL_FLAGS => opts.flags = ne_u32(payload).unwrap_or(0),
+            TCA_MATCHALL_ACT => opts.actions = parse_filter_actions(payload),
+            _ => {}
+        }
+    }
+    opts
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn nla(ty: u16, payload: &[u8]) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend_from_slice(&((4 + payload.len()) as u16).to_ne_bytes());
+        v.extend_from_slice(&ty.to_ne_bytes());
+        v.extend_from_slice(payload);
+        v.resize((v.len() + 3) & !3, 0);
+        v
+    }
+
+    #[test]
+    fn test_u32_filter_options() {
+        // tc filter add ... u32 match ip dst 10.0.0.0/8 flowid 1:10 action drop
+        let mut sel = vec![1u8, 0, 1, 0]; // TERMINAL, offshift 0, nkeys 1
+        sel.extend_from_slice(&[0; 12]);
+        sel.extend_from_slice(&[0xff, 0, 0, 0]); // mask, big-endian
+        sel.extend_from_slice(&[10, 0, 0, 0]); // val, big-endian
+        sel.extend_from_slice(&16i32.to_ne_bytes());
+        sel.extend_from_slice(&0i32.to_ne_bytes());
+
+        let mut pcnt = Vec::new();
+        for n in [7u64, 3, 3] {
+            pcnt.extend_from_slice(&n.to_ne_bytes());
+        }
+
+        // gact slot: kind, options { parms { index 5, ... } }, stats { basic }
+        let mut parms = 5u32.to_ne_bytes().to_vec();
+        parms.extend_from_slice(&[0; 16]);
+        let mut basic = 1500u64.to_ne_bytes().to_vec();
+        basic.extend_from_slice(&3u32.to_ne_bytes());
+        let mut slot = nla(1, b"gact\0");
+        slot.extend(nla(2, &nla(2, &parms)));
+        slot.extend(nla(4, &nla(1, &basic)));
+
+        let mut data = nla(1, &0x1_0010u32.to_ne_bytes());
+        data.extend(nla(5, &sel));
+        data.extend(nla(9, &pcnt));
+        data.extend(nla(7, &nla(1, &slot)));
+
+        let opts = parse_u32_options(&data);
+        assert_eq!(opts.classid, Some(0x1_0010));
+        let sel = opts.selector.unwrap();
+        assert!(sel.is_terminal());
+        assert_eq!(
+            sel.keys,
+            [U32Key {
+                val: 0x0a00_0000,
+                mask: 0xff00_0000,
+                off: 16,
+                offmask: 0,
+            }]
+        );
+        let counters = opts.counters.unwrap();
+        assert_eq!((counters.lookups, counters.hits), (7, 3));
+        assert_eq!(counters.key_hits, [3]);
+
+        assert_eq!(opts.actions.len(), 1);
+        let act = &opts.actions[0];
+        assert_eq!((act.order, act.kind.as_str(), act.index), (1, "gact", 5));
+        let stats = act.stats_basic.unwrap();
+        assert_eq!((stats.bytes, stats.packets), (1500, 3));
+    }
+
+    #[test]
+    fn test_flower_filter_options() {
+        // flower skip_hw eth_type ipv4 ip_proto tcp dst_ip 10.1.0.0/16 dst_port 80
+        let mut data = nla(22, &1u32.to_ne_bytes());
+        data.extend(nla(8, &[0x08, 0x00]));
+        data.extend(nla(9, &[6]));
+        data.extend(nla(12, &[10, 1, 0, 0]));
+        data.extend(nla(13, &[255, 255, 0, 0]));
+        data.extend(nla(19, &[0, 80]));
+        data.extend(nla(36, &[0xff, 0xff]));
+
+        let opts = parse_flower_options(&data);
+        assert_eq!(opts.flags, 1);
+        assert_eq!(opts.eth_type, Some(0x0800));
+        assert_eq!(opts.ip_proto, Some(6));
+        assert_eq!(opts.dst_ip, Some(("10.1.0.0".parse().unwrap(), 16)));
+        assert_eq!(opts.src_ip, None);
+        assert_eq!(opts.dst_port, Some(80));
+        assert!(opts.actions.is_empty());
+    }
+
+    #[test]
+    fn test_flower_tunnel_and_port_range_options() {
+        // flower enc_key_id 42 enc_dst_ip 192.0.2.1 enc_dst_port 4789
+        //   ip_proto udp dst_port 1000-2000
+        let mut data = nla(26, &42u32.to_be_bytes());
+        data.extend(nla(29, &[192, 0, 2, 1]));
+        data.extend(nla(30, &[255; 4]));
+        data.extend(nla(45, &4789u16.to_be_bytes()));
+        data.extend(nla(9, &[17]));
+        data.extend(nla(89, &1000u16.to_be_bytes()));
+        data.extend(nla(90, &2000u16.to_be_bytes()));
+
+        let opts = parse_flower_options(&data);
+        assert_eq!(opts.enc_key_id, Some(42));
+        assert_eq!(opts.enc_dst_ip, Some(("192.0.2.1".parse().unwrap(), 32)));
+        assert_eq!(opts.enc_src_ip, None);
+        assert_eq!(opts.enc_dst_port, Some(4789));
+        assert_eq!(opts.dst_port_range, Some((1000, 2000)));
+        assert_eq!(opts.dst_port, None);
+    }
+
+    #[test]
+    fn test_flower_offload_indication() {
+        // flower skip_sw ... action drop skip_sw, as dumped from a NIC
+        // that accepted the rule: in_hw, in_hw_count 1.
+        let mut act = nla(1, b"gact\0");
+        act.extend(nla(7, &[4, 0, 0, 0, 4, 0, 0, 0]));
+        act.extend(nla(10, &1u32.to_ne_bytes()));
+        let mut data = nla(22, &(2u32 | 4).to_ne_bytes());
+        data.extend(nla(86, &1u32.to_ne_bytes()));
+        data.extend(nla(3, &nla(1, &act)));
+
+        let opts = FilterOptions::Flower(parse_flower_options(&data));
+        assert!(opts.is_in_hw());
+        assert_eq!(opts.in_hw_count(), Some(1));
+        let action = &opts.actions()[0];
+        assert_eq!(action.flags, 4);
+        assert_eq!(action.in_hw_count, Some(1));
+
+        let not_in_hw = FilterOptions::Flower(parse_flower_options(&nla(22, &8u32.to_ne_bytes())));
+        assert!(!not_in_hw.is_in_hw());
+        assert_eq!(not_in_hw.in_hw_count(), None);
+    }
+
     #[test]
     fn test_fq_codel_defaults() {
         let opts = FqCodelOptions::default();
@@ -0,0 +1,360 @@
+//! Structured view of a device's TC hierarchy.
+//!
+//! The kernel dumps qdiscs, classes and filters as three flat lists
+//! linked only by `handle`/`parent` numbers. [`QdiscTree`] puts them back
+//! together: the root qdisc owns its classes, each class owns its child
+//! classes and the qdisc attached to it, and every filter hangs off the
+//! qdisc or class whose block it belongs to.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//!
+//! let conn = Connection::<Route>::new()?;
+//! let tree = conn.get_qdisc_tree("eth0").await?;
+//!
+//! if let Some(root) = &tree.root {
+//!     println!("root: {} {}", root.qdisc.kind().unwrap_or("?"), root.qdisc.handle());
+//!     for class in &root.classes {
+//!         println!("  class {} ({} children)", class.class.handle(), class.children.len());
+//!     }
+//! }
+//!
+//! // Existence checks without walking the lists by hand.
+//! let has_htb_class = tree.find_class("1:10".parse()?).is_some();
+//! ```
+
+use super::{
+    Connection, error::Result, interface_ref::InterfaceRef, messages::TcMessage, protocol::Route,
+    tc_handle::TcHandle,
+};
+
+/// The TC hierarchy of one device.
+///
+/// Built by [`Connection::get_qdisc_tree`] or, from lists already in
+/// hand, [`QdiscTree::from_parts`].
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct QdiscTree {
+    /// Interface index the tree belongs to.
+    pub ifindex: u32,
+    /// The egress root qdisc, if the device has one.
+    pub root: Option<QdiscNode>,
+    /// The `ingress` or `clsact` qdisc, if attached. Filters on both
+    /// `clsact` hooks land here; [`TcMessage::parent`] tells them apart.
+    pub ingress: Option<QdiscNode>,
+}
+
+/// A qdisc together with its classes and filters.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct QdiscNode {
+    /// The qdisc itself.
+    pub qdisc: TcMessage,
+    /// Top-level classes (those whose parent is this qdisc).
+    pub classes: Vec<ClassNode>,
+    /// Filters attached directly to this qdisc.
+    pub filters: Vec<TcMessage>,
+}
+
+/// A class together with its children, leaf qdisc and filters.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ClassNode {
+    /// The class itself.
+    pub class: TcMessage,
+    /// Child classes.
+    pub children: Vec<ClassNode>,
+    /// Qdisc attached under this class, if any.
+    pub qdisc: Option<QdiscNode>,
+    /// Filters attached to this class.
+    pub filters: Vec<TcMessage>,
+}
+
+impl QdiscTree {
+    /// Assemble a tree from one device's qdisc, class and filter dumps.
+    ///
+    /// Entries for other devices are ignored, as are entries whose parent
+    /// is missing (e.g. a class deleted between the dumps).
+    pub fn from_parts(
+        ifindex: u32,
+        qdiscs: &[TcMessage],
+        classes: &[TcMessage],
+        filters: &[TcMessage],
+    ) -> Self {
+        let on_dev = |m: &&TcMessage| m.ifindex() == ifindex;
+        let parts = Parts {
+            qdiscs: qdiscs.iter().filter(on_dev).collect(),
+            classes: classes.iter().filter(on_dev).collect(),
+            filters: filters.iter().filter(on_dev).collect(),
+        };
+
+        let root = parts
+            .qdiscs
+            .iter()
+            .find(|q| q.is_root())
+            .map(|q| parts.qdisc_node(q));
+        let ingress = parts
+            .qdiscs
+            .iter()
+            .find(|q| q.is_ingress() || q.is_clsact())
+            .map(|q| parts.qdisc_node(q));
+
+        Self {
+            ifindex,
+            root,
+            ingress,
+        }
+    }
+
+    /// Check whether the device has no qdisc at all.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none() && self.ingress.is_none()
+    }
+
+    /// Find the qdisc with `handle` anywhere in the tree.
+    pub fn find_qdisc(&self, handle: TcHandle) -> Option<&QdiscNode> {
+        self.root
+            .iter()
+            .chain(&self.ingress)
+            .find_map(|q| q.find_qdisc(handle))
+    }
+
+    /// Find the class with `handle` anywhere in the tree.
+    pub fn find_class(&self, handle: TcHandle) -> Option<&ClassNode> {
+        self.root
+            .iter()
+            .chain(&self.ingress)
+            .find_map(|q| q.find_class(handle))
+    }
+}
+
+impl QdiscNode {
+    fn find_qdisc(&self, handle: TcHandle) -> Option<&QdiscNode> {
+        if self.qdisc.handle() == handle {
+            return Some(self);
+        }
+        self.classes.iter().find_map(|c| c.find_qdisc(handle))
+    }
+
+    fn find_class(&self, handle: TcHandle) -> Option<&ClassNode> {
+        self.classes.iter().find_map(|c| c.find_class(handle))
+    }
+}
+
+impl ClassNode {
+    fn find_qdisc(&self, handle: TcHandle) -> Option<&QdiscNode> {
+        self.qdisc
+            .iter()
+            .find_map(|q| q.find_qdisc(handle))
+            .or_else(|| self.children.iter().find_map(|c| c.find_qdisc(handle)))
+    }
+
+    fn find_class(&self, handle: TcHandle) -> Option<&ClassNode> {
+        if self.class.handle() == handle {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .find_map(|c| c.find_class(handle))
+            .or_else(|| self.qdisc.as_ref().and_then(|q| q.find_class(handle)))
+    }
+}
+
+/// One device's dumps, borrowed while the tree is assembled.
+struct Parts<'a> {
+    qdiscs: Vec<&'a TcMessage>,
+    classes: Vec<&'a TcMessage>,
+    filters: Vec<&'a TcMessage>,
+}
+
+impl Parts<'_> {
+    fn qdisc_node(&self, qdisc: &TcMessage) -> QdiscNode {
+        let handle = qdisc.handle_raw();
+        // A default qdisc (noqueue, pfifo_fast) has handle 0 and owns nothing.
+        if handle == 0 {
+            return QdiscNode {
+                qdisc: qdisc.clone(),
+                classes: Vec::new(),
+                filters: Vec::new(),
+            };
+        }
+
+        // Top-level classes usually report the root as parent (htb, mq)
+        // rather than the qdisc handle; the major ties them to the qdisc.
+        let major = qdisc.handle().major();
+        let classes = self
+            .classes
+            .iter()
+            .filter(|c| {
+                c.parent_raw() == handle || (c.parent().is_root() && c.handle().major() == major)
+            })
+            .map(|c| self.class_node(c))
+            .collect();
+
+        // ingress and clsact share major ffff; their filters sit on the
+        // ffff:fff2 / ffff:fff3 hooks rather than on the qdisc handle.
+        let ingress = qdisc.is_ingress() || qdisc.is_clsact();
+        let filters = self
+            .filters
+            .iter()
+            .filter(|f| f.parent_raw() == handle || (ingress && f.parent().major() == major))
+            .map(|f| (*f).clone())
+            .collect();
+
+        QdiscNode {
+            qdisc: qdisc.clone(),
+            classes,
+            filters,
+        }
+    }
+
+    fn class_node(&self, class: &TcMessage) -> ClassNode {
+        let handle = class.handle_raw();
+        let children = self
+            .classes
+            .iter()
+            .filter(|c| c.parent_raw() == handle && c.handle_raw() != handle)
+            .map(|c| self.class_node(c))
+            .collect();
+        let qdisc = self
+            .qdiscs
+            .iter()
+            .find(|q| q.parent_raw() == handle)
+            .map(|q| self.qdisc_node(q));
+        let filters = self
+            .filters
+            .iter()
+            .filter(|f| f.parent_raw() == handle)
+            .map(|f| (*f).clone())
+            .collect();
+
+        ClassNode {
+            class: class.clone(),
+            children,
+            qdisc,
+            filters,
+        }
+    }
+}
+
+impl Connection<Route> {
+    /// Get the TC hierarchy of a device as a tree.
+    ///
+    /// Accepts either an interface name or index via [`InterfaceRef`].
+    /// See [`QdiscTree`] for how qdiscs, classes and filters are linked.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_qdisc_tree"))]
+    pub async fn get_qdisc_tree(&self, iface: impl Into<InterfaceRef>) -> Result<QdiscTree> {
+        self.with_interface(&iface.into(), |ifindex| {
+            self.get_qdisc_tree_by_index(ifindex)
+        })
+        .await
+    }
+
+    /// Get the TC hierarchy of a device by interface index.
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_qdisc_tree_by_index"))]
+    pub async fn get_qdisc_tree_by_index(&self, ifindex: u32) -> Result<QdiscTree> {
+        let qdiscs = self.get_qdiscs_by_index(ifindex).await?;
+        let classes = self.get_classes_by_index(ifindex).await?;
+        let filters = self.get_filters_by_index(ifindex).await?;
+        Ok(QdiscTree::from_parts(ifindex, &qdiscs, &classes, &filters))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tc(ifindex: u32, handle: &str, parent: &str, kind: &str) -> TcMessage {
+        let mut msg = TcMessage::default();
+        msg.header.tcm_ifindex = ifindex as i32;
+        msg.header.tcm_handle = handle.parse::<TcHandle>().unwrap().as_raw();
+        msg.header.tcm_parent = parent.parse::<TcHandle>().unwrap().as_raw();
+        msg.kind = Some(kind.to_string());
+        msg
+    }
+
+    fn h(s: &str) -> TcHandle {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_htb_tree() {
+        let qdiscs = [
+            tc(2, "1:", "root", "htb"),
+            tc(2, "10:", "1:10", "fq_codel"),
+            tc(2, "ffff:", "ffff:fff1", "clsact"),
+            tc(3, "1:", "root", "netem"),
+        ];
+        let classes = [
+            tc(2, "1:1", "root", "htb"),
+            tc(2, "1:10", "1:1", "htb"),
+            tc(2, "1:20", "1:1", "htb"),
+            // Parent vanished between dumps.
+            tc(2, "1:30", "1:99", "htb"),
+        ];
+        let filters = [
+            tc(2, "0:", "1:", "u32"),
+            tc(2, "0:", "1:1", "fw"),
+            tc(2, "0:", "ffff:fff2", "matchall"),
+            tc(2, "0:", "ffff:fff3", "flower"),
+        ];
+
+        let tree = QdiscTree::from_parts(2, &qdiscs, &classes, &filters);
+        let root = tree.root.as_ref().unwrap();
+        assert_eq!(root.qdisc.kind(), Some("htb"));
+        assert_eq!(root.filters.len(), 1);
+        assert_eq!(root.classes.len(), 1);
+
+        let parent = &root.classes[0];
+        assert_eq!(parent.class.handle(), h("1:1"));
+        assert_eq!(parent.filters[0].kind(), Some("fw"));
+        assert_eq!(parent.children.len(), 2);
+
+        let leaf = tree.find_class(h("1:10")).unwrap();
+        assert_eq!(leaf.qdisc.as_ref().unwrap().qdisc.kind(), Some("fq_codel"));
+        assert!(tree.find_class(h("1:30")).is_none());
+        assert_eq!(
+            tree.find_qdisc(h("10:")).unwrap().qdisc.kind(),
+            Some("fq_codel")
+        );
+
+        let ingress = tree.ingress.as_ref().unwrap();
+        assert_eq!(ingress.qdisc.kind(), Some("clsact"));
+        assert_eq!(ingress.filters.len(), 2);
+        assert!(!tree.is_empty());
+    }
+
+    #[test]
+    fn test_default_qdisc_owns_nothing() {
+        let qdiscs = [tc(1, "0:", "root", "noqueue")];
+        let filters = [tc(1, "0:", "0:", "u32")];
+        let tree = QdiscTree::from_parts(1, &qdiscs, &[], &filters);
+        let root = tree.root.unwrap();
+        assert!(root.classes.is_empty());
+        assert!(root.filters.is_empty());
+        assert!(tree.ingress.is_none());
+
+        assert!(QdiscTree::from_parts(5, &qdiscs, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_mq_tree() {
+        let qdiscs = [
+            tc(1, "8001:", "root", "mq"),
+            tc(1, "0:", "8001:1", "pfifo_fast"),
+            tc(1, "0:", "8001:2", "pfifo_fast"),
+        ];
+        let classes = [tc(1, "8001:1", "root", "mq"), tc(1, "8001:2", "root", "mq")];
+        let tree = QdiscTree::from_parts(1, &qdiscs, &classes, &[]);
+        let root = tree.root.unwrap();
+        assert_eq!(root.classes.len(), 2);
+        for class in &root.classes {
+            assert_eq!(
+                class.qdisc.as_ref().unwrap().qdisc.kind(),
+                Some("pfifo_fast")
+            );
+        }
+    }
+}
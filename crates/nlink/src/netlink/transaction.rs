@@ -0,0 +1,214 @@
+//! Transactions: apply changes one by one and undo them on failure.
+//!
+//! Composite setups (a veth pair, its addresses, routes through it) leave
+//! half-configured state behind when a later step fails. A [`Transaction`]
+//! records the inverse of every change it makes; when a step fails it
+//! reverts the earlier ones, newest first, before returning the error.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use nlink::netlink::{Connection, Route};
+//! use nlink::netlink::addr::Ipv4Address;
+//! use nlink::netlink::link::VethLink;
+//! use nlink::netlink::route::Ipv4Route;
+//!
+//! let conn = Connection::<Route>::new()?;
+//! let mut tx = conn.transaction();
+//! tx.add_link(VethLink::new("veth0", "veth1")).await?;
+//! tx.add_address(Ipv4Address::new("veth0", "10.0.0.1".parse()?, 24)).await?;
+//! // If this fails, the address and the veth pair are removed again.
+//! tx.add_route(Ipv4Route::new("10.1.0.0", 16).dev("veth0")).await?;
+//! tx.commit();
+//! ```
+//!
+//! Unlike a [`Batch`](super::batch::Batch), each step is sent and
+//! acknowledged on its own, so a failure is known before the next step
+//! runs.
+
+use super::{
+    addr::AddressConfig,
+    builder::MessageBuilder,
+    connection::Connection,
+    error::{Error, Result},
+    link::LinkConfig,
+    message::{NLM_F_ACK, NLM_F_CREATE, NLM_F_EXCL, NLM_F_REQUEST, NlMsgType},
+    protocol::Route,
+    route::RouteConfig,
+};
+
+/// A sequence of changes that is undone as a whole on failure.
+///
+/// Created with [`Connection::transaction`]. Each `add_*` method applies
+/// its change immediately. If it fails, every change the transaction made
+/// so far is reverted in reverse order and the step's error is returned;
+/// the transaction is then empty and can be reused. [`rollback`](Self::rollback)
+/// reverts on demand and [`commit`](Self::commit) keeps the changes.
+///
+/// Undoing is best effort: a resource already removed by someone else
+/// counts as undone, and undo steps that fail are logged and skipped.
+/// Dropping a transaction without committing or rolling back keeps its
+/// changes and logs a warning.
+#[must_use = "changes stay applied unless the transaction is rolled back"]
+pub struct Transaction<'a> {
+    conn: &'a Connection<Route>,
+    undo: Vec<Undo>,
+}
+
+/// How to revert one applied change.
+enum Undo {
+    /// Delete a link created by the transaction (and its veth peer).
+    DeleteLink(String),
+    /// Send a prepared delete request.
+    Request {
+        operation: String,
+        builder: MessageBuilder,
+    },
+}
+
+impl<'a> Transaction<'a> {
+    pub(crate) fn new(conn: &'a Connection<Route>) -> Self {
+        Self {
+            conn,
+            undo: Vec::new(),
+        }
+    }
+
+    /// Create a link; undone by deleting it.
+    pub async fn add_link<L: LinkConfig>(&mut self, config: L) -> Result<()> {
+        let name = config.name().to_string();
+        let outcome = self.conn.add_link(config).await;
+        self.record(outcome, Undo::DeleteLink(name)).await
+    }
+
+    /// Add an address; undone by deleting it.
+    pub async fn add_address<A: AddressConfig>(&mut self, config: A) -> Result<()> {
+        let ifindex = match self.conn.resolve_interface(config.interface_ref()).await {
+            Ok(ifindex) => ifindex,
+            Err(e) => return self.fail(e).await,
+        };
+        let mut add = MessageBuilder::new(
+            NlMsgType::RTM_NEWADDR,
+            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
+        );
+        let mut del = MessageBuilder::new(NlMsgType::RTM_DELADDR, NLM_F_REQUEST | NLM_F_ACK);
+        if let Err(e) = config
+            .write_add(&mut add, ifindex)
+            .and_then(|()| config.write_delete(&mut del, ifindex))
+        {
+            return self.fail(e).await;
+        }
+        let outcome = self
+            .conn
+            .send_ack(add)
+            .await
+            .map_err(|e| e.with_context("add_address"));
+        let undo = Undo::Request {
+            operation: "del_address".to_string(),
+            builder: del,
+        };
+        self.record(outcome, undo).await
+    }
+
+    /// Add a route; undone by deleting it.
+    pub async fn add_route<R: RouteConfig>(&mut self, config: R) -> Result<()> {
+        let interfaces = match self.conn.resolve_route_interfaces(&config).await {
+            Ok(interfaces) => interfaces,
+            Err(e) => return self.fail(e).await,
+        };
+        let mut add = MessageBuilder::new(
+            NlMsgType::RTM_NEWROUTE,
+            NLM_F_REQUEST | NLM_F_ACK | NLM_F_CREATE | NLM_F_EXCL,
+        );
+        config.write_add(&mut add, &interfaces);
+        let mut del = MessageBuilder::new(NlMsgType::RTM_DELROUTE, NLM_F_REQUEST | NLM_F_ACK);
+        config.write_delete_with_interfaces(&mut del, &interfaces);
+        let outcome = self
+            .conn
+            .send_ack(add)
+            .await
+            .map_err(|e| e.with_context("add_route"));
+        let undo = Undo::Request {
+            operation: "del_route".to_string(),
+            builder: del,
+        };
+        self.record(outcome, undo).await
+    }
+
+    /// Number of changes that would be undone.
+    pub fn len(&self) -> usize {
+        self.undo.len()
+    }
+
+    /// Whether the transaction has no changes to undo.
+    pub fn is_empty(&self) -> bool {
+        self.undo.is_empty()
+    }
+
+    /// Keep every change.
+    pub fn commit(mut self) {
+        self.undo.clear();
+    }
+
+    /// Undo every change, newest first.
+    ///
+    /// All undo steps run even if some fail; the first failure is
+    /// returned.
+    pub async fn rollback(mut self) -> Result<()> {
+        let mut errors = self.undo_all().await;
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.swap_remove(0))
+        }
+    }
+
+    /// Keep `undo` if the step succeeded, otherwise roll back.
+    async fn record(&mut self, outcome: Result<()>, undo: Undo) -> Result<()> {
+        match outcome {
+            Ok(()) => {
+                self.undo.push(undo);
+                Ok(())
+            }
+            Err(e) => self.fail(e).await,
+        }
+    }
+
+    /// Roll back after a failed step and return its error.
+    async fn fail(&mut self, err: Error) -> Result<()> {
+        for undo_err in self.undo_all().await {
+            tracing::warn!(error = %undo_err, "transaction rollback step failed");
+        }
+        Err(err)
+    }
+
+    async fn undo_all(&mut self) -> Vec<Error> {
+        let mut errors = Vec::new();
+        while let Some(undo) = self.undo.pop() {
+            let outcome = match undo {
+                Undo::DeleteLink(name) => self.conn.del_link(name.as_str()).await,
+                Undo::Request { operation, builder } => self
+                    .conn
+                    .send_ack(builder)
+                    .await
+                    .map_err(|e| e.with_context(operation)),
+            };
+            match outcome {
+                Err(e) if !e.is_not_found() => errors.push(e),
+                _ => {}
+            }
+        }
+        errors
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.undo.is_empty() {
+            tracing::warn!(
+                changes = self.undo.len(),
+                "transaction dropped without commit or rollback; its changes stay applied"
+            );
+        }
+    }
+}
@@ -175,6 +175,75 @@ impl Scope {
     }
 }
 
+/// IPv6 address label message (struct ifaddrlblmsg).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, FromBytes, IntoBytes, Immutable, KnownLayout)]
+pub struct IfAddrLblMsg {
+    /// Address family (always AF_INET6).
+    pub ifal_family: u8,
+    /// Reserved.
+    pub ifal_reserved: u8,
+    /// Prefix length.
+    pub ifal_prefixlen: u8,
+    /// Flags (unused).
+    pub ifal_flags: u8,
+    /// Interface index, 0 for any.
+    pub ifal_index: u32,
+    /// Label table sequence number.
+    pub ifal_seq: u32,
+}
+
+impl IfAddrLblMsg {
+    /// Size of this structure.
+    pub const SIZE: usize = std::mem::size_of::<Self>();
+
+    /// Create a new address label message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the address family.
+    pub fn with_family(mut self, family: u8) -> Self {
+        self.ifal_family = family;
+        self
+    }
+
+    /// Set the prefix length.
+    pub fn with_prefixlen(mut self, prefixlen: u8) -> Self {
+        self.ifal_prefixlen = prefixlen;
+        self
+    }
+
+    /// Set the interface index.
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.ifal_index = index;
+        self
+    }
+
+    /// Convert to bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        <Self as IntoBytes>::as_bytes(self)
+    }
+
+    /// Parse from bytes.
+    pub fn from_bytes(data: &[u8]) -> Result<&Self> {
+        Self::ref_from_prefix(data)
+            .map(|(r, _)| r)
+            .map_err(|_| Error::Truncated {
+                expected: Self::SIZE,
+                actual: data.len(),
+            })
+    }
+}
+
+/// Address label attributes (IFAL_*).
+pub mod ifal {
+    /// Prefix address (struct in6_addr).
+    pub const ADDRESS: u16 = 1;
+    /// Label (u32).
+    pub const LABEL: u16 = 2;
+}
+
 /// Address cache info (struct ifa_cacheinfo).
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, FromBytes, Immutable, KnownLayout)]
@@ -256,6 +256,142 @@ pub mod iff {
     pub const ECHO: u32 = 1 << 18;
 }
 
+bitflags::bitflags! {
+    /// Interface flags (`IFF_*`) as carried in [`IfInfoMsg::ifi_flags`].
+    ///
+    /// Only the bits in [`LinkFlags::CHANGEABLE`] can be set from userspace;
+    /// the rest (`RUNNING`, `LOWER_UP`, `LOOPBACK`, ...) are reported by the
+    /// kernel and driver. `Display` prints the `ip link` style list, e.g.
+    /// `UP,BROADCAST,LOWER_UP`, or `NONE`.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct LinkFlags: u32 {
+        /// `IFF_UP` — administratively up.
+        const UP = iff::UP;
+        /// `IFF_BROADCAST` — broadcast address valid.
+        const BROADCAST = iff::BROADCAST;
+        /// `IFF_DEBUG` — driver debugging enabled.
+        const DEBUG = iff::DEBUG;
+        /// `IFF_LOOPBACK` — loopback device.
+        const LOOPBACK = iff::LOOPBACK;
+        /// `IFF_POINTOPOINT` — point-to-point link.
+        const POINTOPOINT = iff::POINTOPOINT;
+        /// `IFF_NOTRAILERS` — unused, kept for compatibility.
+        const NOTRAILERS = iff::NOTRAILERS;
+        /// `IFF_RUNNING` — operationally up.
+        const RUNNING = iff::RUNNING;
+        /// `IFF_NOARP` — no neighbour resolution (`arp off`).
+        const NOARP = iff::NOARP;
+        /// `IFF_PROMISC` — receive all packets.
+        const PROMISC = iff::PROMISC;
+        /// `IFF_ALLMULTI` — receive all multicast packets.
+        const ALLMULTI = iff::ALLMULTI;
+        /// `IFF_MASTER` — bonding master.
+        const MASTER = iff::MASTER;
+        /// `IFF_SLAVE` — bonding slave.
+        const SLAVE = iff::SLAVE;
+        /// `IFF_MULTICAST` — multicast capable.
+        const MULTICAST = iff::MULTICAST;
+        /// `IFF_PORTSEL` — media type selectable.
+        const PORTSEL = iff::PORTSEL;
+        /// `IFF_AUTOMEDIA` — automatic media selection.
+        const AUTOMEDIA = iff::AUTOMEDIA;
+        /// `IFF_DYNAMIC` — address may change on reconnect.
+        const DYNAMIC = iff::DYNAMIC;
+        /// `IFF_LOWER_UP` — carrier present.
+        const LOWER_UP = iff::LOWER_UP;
+        /// `IFF_DORMANT` — waiting for an external event.
+        const DORMANT = iff::DORMANT;
+        /// `IFF_ECHO` — echoes sent packets.
+        const ECHO = iff::ECHO;
+    }
+}
+
+impl LinkFlags {
+    /// Flags the kernel lets `RTM_SETLINK` change (see `dev_change_flags()`).
+    pub const CHANGEABLE: Self = Self::UP
+        .union(Self::DEBUG)
+        .union(Self::NOTRAILERS)
+        .union(Self::NOARP)
+        .union(Self::PROMISC)
+        .union(Self::ALLMULTI)
+        .union(Self::MULTICAST)
+        .union(Self::PORTSEL)
+        .union(Self::AUTOMEDIA)
+        .union(Self::DYNAMIC);
+
+    /// Administratively up.
+    pub fn is_up(&self) -> bool {
+        self.contains(Self::UP)
+    }
+
+    /// Operationally up.
+    pub fn is_running(&self) -> bool {
+        self.contains(Self::RUNNING)
+    }
+
+    /// Carrier present.
+    pub fn is_lower_up(&self) -> bool {
+        self.contains(Self::LOWER_UP)
+    }
+
+    /// Loopback device.
+    pub fn is_loopback(&self) -> bool {
+        self.contains(Self::LOOPBACK)
+    }
+
+    /// Broadcast capable.
+    pub fn is_broadcast(&self) -> bool {
+        self.contains(Self::BROADCAST)
+    }
+
+    /// Point-to-point link.
+    pub fn is_pointopoint(&self) -> bool {
+        self.contains(Self::POINTOPOINT)
+    }
+
+    /// Promiscuous mode.
+    pub fn is_promisc(&self) -> bool {
+        self.contains(Self::PROMISC)
+    }
+
+    /// Receiving all multicast.
+    pub fn is_allmulti(&self) -> bool {
+        self.contains(Self::ALLMULTI)
+    }
+
+    /// Multicast capable.
+    pub fn is_multicast(&self) -> bool {
+        self.contains(Self::MULTICAST)
+    }
+
+    /// Neighbour resolution enabled (`IFF_NOARP` clear).
+    pub fn is_arp(&self) -> bool {
+        !self.contains(Self::NOARP)
+    }
+
+    /// Dormant.
+    pub fn is_dormant(&self) -> bool {
+        self.contains(Self::DORMANT)
+    }
+}
+
+impl std::fmt::Display for LinkFlags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for (name, _) in self.iter_names() {
+            if !first {
+                f.write_str(",")?;
+            }
+            f.write_str(name)?;
+            first = false;
+        }
+        if first {
+            f.write_str("NONE")?;
+        }
+        Ok(())
+    }
+}
+
 /// Operational state (IF_OPER_*).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[non_exhaustive]
@@ -381,12 +517,27 @@ pub mod bridge_af {
     pub const IFLA_BRIDGE_MST: u16 = 6;
 }
 
+/// `IFLA_BRIDGE_FLAGS` values: which device an AF_BRIDGE request targets.
+///
+/// With no flags the kernel forwards the request to the port's master
+/// bridge; `SELF` makes the device handle it itself (needed for the
+/// bridge device and for switchdev drivers).
+pub mod bridge_flags {
+    /// Send to the master bridge (`BRIDGE_FLAGS_MASTER`)
+    pub const MASTER: u16 = 1 << 0;
+    /// Handle on the device itself (`BRIDGE_FLAGS_SELF`)
+    pub const SELF: u16 = 1 << 1;
+}
+
 /// Bridge port attributes (`IFLA_BRPORT_*`), carried inside the
 /// `IFLA_PROTINFO` nest of an `RTM_SETLINK` with `ifi_family =
 /// AF_BRIDGE`. These configure a single port's behaviour on its
-/// bridge (`bridge link set dev <port> ...`). Only the subset
-/// modelled by [`BridgePortConfig`](crate::netlink::link::BridgePortConfig)
-/// is listed.
+/// bridge (`bridge link set dev <port> ...`). The same attributes
+/// come back as `IFLA_INFO_SLAVE_DATA` of a port whose slave kind is
+/// `"bridge"`. Lists the subset modelled by
+/// [`BridgePortConfig`](crate::netlink::link::BridgePortConfig) plus
+/// the read-only STP identifiers decoded into
+/// [`BridgePortInfo`](crate::netlink::messages::BridgePortInfo).
 pub mod brport {
     /// STP port state (u8): 0 disabled, 1 listening, 2 learning,
     /// 3 forwarding, 4 blocking.
@@ -411,17 +562,39 @@ pub mod brport {
     pub const IFLA_BRPORT_UNICAST_FLOOD: u16 = 9;
     /// Proxy ARP (u8 bool).
     pub const IFLA_BRPORT_PROXYARP: u16 = 10;
+    /// Designated root bridge id (read-only, `struct ifla_bridge_id`).
+    pub const IFLA_BRPORT_ROOT_ID: u16 = 13;
+    /// Designated bridge id (read-only, `struct ifla_bridge_id`).
+    pub const IFLA_BRPORT_BRIDGE_ID: u16 = 14;
+    /// Designated port (read-only, u16).
+    pub const IFLA_BRPORT_DESIGNATED_PORT: u16 = 15;
+    /// Designated cost (read-only, u16).
+    pub const IFLA_BRPORT_DESIGNATED_COST: u16 = 16;
+    /// STP port id (read-only, u16): priority in the high bits, port
+    /// number in the low bits.
+    pub const IFLA_BRPORT_ID: u16 = 17;
+    /// STP port number (read-only, u16).
+    pub const IFLA_BRPORT_NO: u16 = 18;
     /// Multicast flooding (u8 bool).
-    pub const IFLA_BRPORT_MCAST_FLOOD: u16 = 23;
+    pub const IFLA_BRPORT_MCAST_FLOOD: u16 = 27;
     /// Multicast-to-unicast (u8 bool).
-    pub const IFLA_BRPORT_MCAST_TO_UCAST: u16 = 24;
+    pub const IFLA_BRPORT_MCAST_TO_UCAST: u16 = 28;
+    /// VLAN-to-tunnel mapping (u8 bool) — use the port's
+    /// `IFLA_BRIDGE_VLAN_TUNNEL_INFO` entries to pick the VNI of
+    /// egress frames and the VLAN of ingress ones.
+    pub const IFLA_BRPORT_VLAN_TUNNEL: u16 = 29;
     /// Broadcast flooding (u8 bool).
     pub const IFLA_BRPORT_BCAST_FLOOD: u16 = 30;
-    /// Neighbour suppression (u8 bool).
+    /// Neighbour suppression (u8 bool) — answer ARP / ND from the
+    /// bridge's neighbour table instead of flooding the request.
     pub const IFLA_BRPORT_NEIGH_SUPPRESS: u16 = 32;
     /// Port isolation (u8 bool) — isolated ports cannot talk to
     /// each other.
     pub const IFLA_BRPORT_ISOLATED: u16 = 33;
+    /// Per-VLAN neighbour suppression (u8 bool) — take suppression
+    /// from each VLAN's `BRIDGE_VLANDB_ENTRY_NEIGH_SUPPRESS` option
+    /// rather than the port-wide flag.
+    pub const IFLA_BRPORT_NEIGH_VLAN_SUPPRESS: u16 = 43;
 }
 
 /// Bridge VLAN tunnel info nested attributes (IFLA_BRIDGE_VLAN_TUNNEL_*).
@@ -599,6 +772,22 @@ pub mod bridge_vlandb_entry {
     pub const NEIGH_SUPPRESS: u16 = 9;
 }
 
+/// Per-VLAN counters (`BRIDGE_VLANDB_ENTRY_STATS` → `BRIDGE_VLANDB_STATS_*`),
+/// all u64. Only reported when the dump sets
+/// [`bridge_vlandb_dump::DUMPF_STATS`].
+pub mod bridge_vlandb_stats {
+    /// Received bytes.
+    pub const RX_BYTES: u16 = 1;
+    /// Received packets.
+    pub const RX_PACKETS: u16 = 2;
+    /// Transmitted bytes.
+    pub const TX_BYTES: u16 = 3;
+    /// Transmitted packets.
+    pub const TX_PACKETS: u16 = 4;
+    /// Padding attribute for 64-bit alignment.
+    pub const PAD: u16 = 5;
+}
+
 /// Spanning-tree port states (`BR_STATE_*`), used by the per-VLAN
 /// [`bridge_vlandb_entry::STATE`] attribute (MSTP per-VLAN state).
 pub mod br_state {
@@ -15,7 +15,7 @@ pub mod srv6;
 pub mod tc;
 
 // Re-export commonly used types
-pub use addr::{IfAddrMsg, IfaAttr};
+pub use addr::{IfAddrLblMsg, IfAddrMsg, IfaAttr};
 pub use link::{IfInfoMsg, IflaAttr};
 pub use neigh::{NdMsg, NdaAttr};
 pub use nsid::{RTM_DELNSID, RTM_GETNSID, RTM_NEWNSID, RTNLGRP_NSID, RtGenMsg, netnsa};
@@ -154,6 +154,19 @@ pub mod lwtunnel_encap {
     pub const XFRM: u16 = 10;
 }
 
+/// Netconf attributes (NETCONFA_*) reported for AF_MPLS devices.
+pub mod netconfa {
+    /// Interface index (`s32`), or one of the pseudo-indexes below.
+    pub const IFINDEX: u16 = 1;
+    /// MPLS input enabled (`s32`, `net.mpls.conf.<dev>.input`).
+    pub const INPUT: u16 = 7;
+
+    /// `NETCONFA_IFINDEX_ALL` — the `all` pseudo-device.
+    pub const IFINDEX_ALL: i32 = -1;
+    /// `NETCONFA_IFINDEX_DEFAULT` — the `default` pseudo-device.
+    pub const IFINDEX_DEFAULT: i32 = -2;
+}
+
 /// Special MPLS label values.
 pub mod mpls_label {
     /// IPv4 Explicit NULL.
@@ -1,5 +1,7 @@
 //! Neighbor (ARP/NDP) message types.
 
+use std::{fmt, str::FromStr};
+
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
 use crate::netlink::error::{Error, Result};
@@ -131,58 +133,79 @@ pub mod nud {
     pub const NOARP: u16 = 0x40;
     pub const PERMANENT: u16 = 0x80;
     pub const NONE: u16 = 0x00;
+    /// Every state bit, for state masks.
+    pub const ALL: u16 = 0xff;
+    /// The states `ip neigh flush` removes by default: all but NOARP
+    /// and PERMANENT.
+    pub const FLUSHABLE: u16 = ALL & !(NOARP | PERMANENT);
 }
 
 /// Get the name of a neighbor state.
 pub fn nud_state_name(state: u16) -> &'static str {
-    match state {
-        nud::INCOMPLETE => "INCOMPLETE",
-        nud::REACHABLE => "REACHABLE",
-        nud::STALE => "STALE",
-        nud::DELAY => "DELAY",
-        nud::PROBE => "PROBE",
-        nud::FAILED => "FAILED",
-        nud::NOARP => "NOARP",
-        nud::PERMANENT => "PERMANENT",
-        nud::NONE => "NONE",
-        _ => "UNKNOWN",
-    }
+    NeighborState::from(state).name()
 }
 
 /// Neighbor state as an enum.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u16)]
+///
+/// Values without a variant (e.g. several `NUD_*` bits at once) are kept
+/// in [`NeighborState::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum NeighborState {
-    None = 0x00,
-    Incomplete = 0x01,
-    Reachable = 0x02,
-    Stale = 0x04,
-    Delay = 0x08,
-    Probe = 0x10,
-    Failed = 0x20,
-    Noarp = 0x40,
-    Permanent = 0x80,
+    None,
+    Incomplete,
+    Reachable,
+    Stale,
+    Delay,
+    Probe,
+    Failed,
+    Noarp,
+    Permanent,
+    /// A state value this crate has no name for.
+    Unknown(u16),
 }
 
 impl From<u16> for NeighborState {
     fn from(val: u16) -> Self {
         match val {
-            0x01 => Self::Incomplete,
-            0x02 => Self::Reachable,
-            0x04 => Self::Stale,
-            0x08 => Self::Delay,
-            0x10 => Self::Probe,
-            0x20 => Self::Failed,
-            0x40 => Self::Noarp,
-            0x80 => Self::Permanent,
-            _ => Self::None,
+            nud::NONE => Self::None,
+            nud::INCOMPLETE => Self::Incomplete,
+            nud::REACHABLE => Self::Reachable,
+            nud::STALE => Self::Stale,
+            nud::DELAY => Self::Delay,
+            nud::PROBE => Self::Probe,
+            nud::FAILED => Self::Failed,
+            nud::NOARP => Self::Noarp,
+            nud::PERMANENT => Self::Permanent,
+            other => Self::Unknown(other),
         }
     }
 }
 
+impl From<NeighborState> for u16 {
+    fn from(val: NeighborState) -> Self {
+        val.number()
+    }
+}
+
 impl NeighborState {
-    /// Get the name of this state.
+    /// Get the `NUD_*` value.
+    pub fn number(&self) -> u16 {
+        match self {
+            Self::None => nud::NONE,
+            Self::Incomplete => nud::INCOMPLETE,
+            Self::Reachable => nud::REACHABLE,
+            Self::Stale => nud::STALE,
+            Self::Delay => nud::DELAY,
+            Self::Probe => nud::PROBE,
+            Self::Failed => nud::FAILED,
+            Self::Noarp => nud::NOARP,
+            Self::Permanent => nud::PERMANENT,
+            Self::Unknown(n) => *n,
+        }
+    }
+
+    /// Get the name of this state (`"UNKNOWN"` for [`NeighborState::Unknown`]).
     pub fn name(&self) -> &'static str {
         match self {
             Self::None => "NONE",
@@ -194,10 +217,51 @@ impl NeighborState {
             Self::Failed => "FAILED",
             Self::Noarp => "NOARP",
             Self::Permanent => "PERMANENT",
+            Self::Unknown(_) => "UNKNOWN",
+        }
+    }
+}
+
+impl fmt::Display for NeighborState {
+    /// The iproute2 name, or the hex value for an unknown state.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(n) => write!(f, "{n:#x}"),
+            known => f.write_str(known.name()),
         }
     }
 }
 
+impl FromStr for NeighborState {
+    type Err = Error;
+
+    /// Parse a state name (case-insensitive) or a `NUD_*` value, decimal
+    /// or `0x`-prefixed hex.
+    fn from_str(s: &str) -> Result<Self> {
+        let state = match s.to_ascii_uppercase().as_str() {
+            "NONE" => Self::None,
+            "INCOMPLETE" => Self::Incomplete,
+            "REACHABLE" => Self::Reachable,
+            "STALE" => Self::Stale,
+            "DELAY" => Self::Delay,
+            "PROBE" => Self::Probe,
+            "FAILED" => Self::Failed,
+            "NOARP" => Self::Noarp,
+            "PERMANENT" => Self::Permanent,
+            _ => {
+                let n = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                    Some(hex) => u16::from_str_radix(hex, 16).ok(),
+                    None => s.parse().ok(),
+                };
+                return n
+                    .map(Self::from)
+                    .ok_or_else(|| Error::InvalidMessage(format!("unknown neighbor state `{s}`")));
+            }
+        };
+        Ok(state)
+    }
+}
+
 /// Neighbor flags (NTF_*).
 pub mod ntf {
     pub const USE: u8 = 0x01;
@@ -210,6 +274,101 @@ pub mod ntf {
     pub const ROUTER: u8 = 0x80;
 }
 
+/// Neighbor table message (struct ndtmsg).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, FromBytes, IntoBytes, Immutable, KnownLayout)]
+pub struct NdtMsg {
+    /// Address family.
+    pub ndtm_family: u8,
+    /// Padding.
+    pub ndtm_pad1: u8,
+    /// Padding.
+    pub ndtm_pad2: u16,
+}
+
+impl NdtMsg {
+    /// Size of this structure.
+    pub const SIZE: usize = std::mem::size_of::<Self>();
+
+    /// Create a new neighbor table message.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the address family.
+    pub fn with_family(mut self, family: u8) -> Self {
+        self.ndtm_family = family;
+        self
+    }
+
+    /// Convert to bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        <Self as IntoBytes>::as_bytes(self)
+    }
+
+    /// Parse from bytes.
+    pub fn from_bytes(data: &[u8]) -> Result<&Self> {
+        Self::ref_from_prefix(data)
+            .map(|(r, _)| r)
+            .map_err(|_| Error::Truncated {
+                expected: Self::SIZE,
+                actual: data.len(),
+            })
+    }
+}
+
+/// Neighbor table attributes (NDTA_*).
+pub mod ndta {
+    /// Table name, e.g. `arp_cache` (string).
+    pub const NAME: u16 = 1;
+    /// gc_thresh1 (u32).
+    pub const THRESH1: u16 = 2;
+    /// gc_thresh2 (u32).
+    pub const THRESH2: u16 = 3;
+    /// gc_thresh3 (u32).
+    pub const THRESH3: u16 = 4;
+    /// struct ndt_config.
+    pub const CONFIG: u16 = 5;
+    /// Nested NDTPA_* parameters.
+    pub const PARMS: u16 = 6;
+    /// struct ndt_stats.
+    pub const STATS: u16 = 7;
+    /// Garbage collection interval in milliseconds (u64).
+    pub const GC_INTERVAL: u16 = 8;
+    pub const PAD: u16 = 9;
+}
+
+/// Neighbor table parameter attributes (NDTPA_*), nested in
+/// [`ndta::PARMS`]. Times are u64 milliseconds.
+pub mod ndtpa {
+    /// Device the parameters belong to; absent or 0 for the table
+    /// defaults (u32).
+    pub const IFINDEX: u16 = 1;
+    /// Reference count (u32, read-only).
+    pub const REFCNT: u16 = 2;
+    /// Current randomised reachable time (read-only).
+    pub const REACHABLE_TIME: u16 = 3;
+    pub const BASE_REACHABLE_TIME: u16 = 4;
+    pub const RETRANS_TIME: u16 = 5;
+    pub const GC_STALETIME: u16 = 6;
+    pub const DELAY_PROBE_TIME: u16 = 7;
+    /// Unresolved queue length in packets (u32).
+    pub const QUEUE_LEN: u16 = 8;
+    pub const APP_PROBES: u16 = 9;
+    pub const UCAST_PROBES: u16 = 10;
+    pub const MCAST_PROBES: u16 = 11;
+    pub const ANYCAST_DELAY: u16 = 12;
+    pub const PROXY_DELAY: u16 = 13;
+    /// Proxy queue length in packets (u32).
+    pub const PROXY_QLEN: u16 = 14;
+    pub const LOCKTIME: u16 = 15;
+    /// Unresolved queue length in bytes (u32).
+    pub const QUEUE_LENBYTES: u16 = 16;
+    pub const MCAST_REPROBES: u16 = 17;
+    pub const PAD: u16 = 18;
+    pub const INTERVAL_PROBE_TIME_MS: u16 = 19;
+}
+
 /// Neighbor cache info (struct nda_cacheinfo).
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, FromBytes, Immutable, KnownLayout)]
@@ -75,7 +75,7 @@ impl NhMsg {
 pub struct NexthopGrp {
     /// Nexthop ID.
     pub id: u32,
-    /// Weight (1-256, 0 means 1).
+    /// Weight minus one (0 means weight 1).
     pub weight: u8,
     /// Reserved.
     pub resvd1: u8,
@@ -162,11 +162,11 @@ pub mod nha_res_group {
     pub const UNSPEC: u16 = 0;
     /// Number of hash buckets (u16).
     pub const BUCKETS: u16 = 1;
-    /// Idle timer in seconds (u32).
+    /// Idle timer in clock_t (u32).
     pub const IDLE_TIMER: u16 = 2;
-    /// Unbalanced timer in seconds (u32).
+    /// Unbalanced timer in clock_t (u32).
     pub const UNBALANCED_TIMER: u16 = 3;
-    /// Unbalanced time remaining in seconds (u32, read-only).
+    /// Time the group has been unbalanced, in clock_t (u64, read-only).
     pub const UNBALANCED_TIME: u16 = 4;
 }
 
@@ -177,7 +177,7 @@ pub mod nha_res_bucket {
     pub const PAD: u16 = 1;
     /// Bucket index (u16).
     pub const INDEX: u16 = 2;
-    /// Idle time in seconds (u32).
+    /// Time since the bucket was last used, in clock_t (u64).
     pub const IDLE_TIME: u16 = 3;
     /// Nexthop ID for this bucket (u32).
     pub const NH_ID: u16 = 4;
@@ -1,5 +1,7 @@
 //! Route message types.
 
+use std::{fmt, str::FromStr};
+
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
 use crate::netlink::error::{Error, Result};
@@ -165,22 +167,26 @@ impl From<u16> for RtaAttr {
 }
 
 /// Route types (RTN_*).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
+///
+/// Values without a variant are kept in [`RouteType::Unknown`], so a
+/// dumped route converts back to the same number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum RouteType {
-    Unspec = 0,
-    Unicast = 1,
-    Local = 2,
-    Broadcast = 3,
-    Anycast = 4,
-    Multicast = 5,
-    Blackhole = 6,
-    Unreachable = 7,
-    Prohibit = 8,
-    Throw = 9,
-    Nat = 10,
-    ExternalResolver = 11,
+    Unspec,
+    Unicast,
+    Local,
+    Broadcast,
+    Anycast,
+    Multicast,
+    Blackhole,
+    Unreachable,
+    Prohibit,
+    Throw,
+    Nat,
+    ExternalResolver,
+    /// A type this crate has no name for.
+    Unknown(u8),
 }
 
 impl From<u8> for RouteType {
@@ -198,13 +204,38 @@ impl From<u8> for RouteType {
             9 => Self::Throw,
             10 => Self::Nat,
             11 => Self::ExternalResolver,
-            _ => Self::Unspec,
+            other => Self::Unknown(other),
         }
     }
 }
 
+impl From<RouteType> for u8 {
+    fn from(val: RouteType) -> Self {
+        val.number()
+    }
+}
+
 impl RouteType {
-    /// Get the name of this route type.
+    /// Get the route type number.
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::Unspec => 0,
+            Self::Unicast => 1,
+            Self::Local => 2,
+            Self::Broadcast => 3,
+            Self::Anycast => 4,
+            Self::Multicast => 5,
+            Self::Blackhole => 6,
+            Self::Unreachable => 7,
+            Self::Prohibit => 8,
+            Self::Throw => 9,
+            Self::Nat => 10,
+            Self::ExternalResolver => 11,
+            Self::Unknown(n) => *n,
+        }
+    }
+
+    /// Get the name of this route type (`"unknown"` for [`RouteType::Unknown`]).
     pub fn name(&self) -> &'static str {
         match self {
             Self::Unspec => "unspec",
@@ -219,38 +250,78 @@ impl RouteType {
             Self::Throw => "throw",
             Self::Nat => "nat",
             Self::ExternalResolver => "xresolve",
+            Self::Unknown(_) => "unknown",
         }
     }
 }
 
+impl fmt::Display for RouteType {
+    /// The iproute2 name, or the number for an unknown type.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(n) => write!(f, "{n}"),
+            known => f.write_str(known.name()),
+        }
+    }
+}
+
+impl FromStr for RouteType {
+    type Err = Error;
+
+    /// Parse an iproute2 route type name (case-insensitive) or a number.
+    fn from_str(s: &str) -> Result<Self> {
+        let ty = match s.to_ascii_lowercase().as_str() {
+            "unspec" => Self::Unspec,
+            "unicast" => Self::Unicast,
+            "local" => Self::Local,
+            "broadcast" => Self::Broadcast,
+            "anycast" => Self::Anycast,
+            "multicast" => Self::Multicast,
+            "blackhole" => Self::Blackhole,
+            "unreachable" => Self::Unreachable,
+            "prohibit" => Self::Prohibit,
+            "throw" => Self::Throw,
+            "nat" => Self::Nat,
+            "xresolve" => Self::ExternalResolver,
+            _ => return parse_number::<u8>(s, "route type").map(Self::from),
+        };
+        Ok(ty)
+    }
+}
+
 /// Route protocols (RTPROT_*).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
+///
+/// Values without a variant are kept in [`RouteProtocol::Unknown`].
+/// [`Display`](fmt::Display) and [`FromStr`] also honour names registered
+/// through [`crate::util::names::register_protocol`] (rt_protos).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum RouteProtocol {
-    Unspec = 0,
-    Redirect = 1,
-    Kernel = 2,
-    Boot = 3,
-    Static = 4,
+    Unspec,
+    Redirect,
+    Kernel,
+    Boot,
+    Static,
     // Routing daemons
-    Gated = 8,
-    Ra = 9,
-    Mrt = 10,
-    Zebra = 11,
-    Bird = 12,
-    Dnrouted = 13,
-    Xorp = 14,
-    Ntk = 15,
-    Dhcp = 16,
-    Mrouted = 17,
-    Keepalived = 18,
-    Babel = 42,
-    Bgp = 186,
-    Isis = 187,
-    Ospf = 188,
-    Rip = 189,
-    Eigrp = 192,
+    Gated,
+    Ra,
+    Mrt,
+    Zebra,
+    Bird,
+    Dnrouted,
+    Xorp,
+    Ntk,
+    Dhcp,
+    Mrouted,
+    Keepalived,
+    Babel,
+    Bgp,
+    Isis,
+    Ospf,
+    Rip,
+    Eigrp,
+    /// A protocol number this crate has no variant for.
+    Unknown(u8),
 }
 
 impl From<u8> for RouteProtocol {
@@ -278,13 +349,52 @@ impl From<u8> for RouteProtocol {
             188 => Self::Ospf,
             189 => Self::Rip,
             192 => Self::Eigrp,
-            _ => Self::Unspec,
+            other => Self::Unknown(other),
         }
     }
 }
 
+impl From<RouteProtocol> for u8 {
+    fn from(val: RouteProtocol) -> Self {
+        val.number()
+    }
+}
+
 impl RouteProtocol {
-    /// Get the name of this protocol.
+    /// Get the protocol number.
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::Unspec => 0,
+            Self::Redirect => 1,
+            Self::Kernel => 2,
+            Self::Boot => 3,
+            Self::Static => 4,
+            Self::Gated => 8,
+            Self::Ra => 9,
+            Self::Mrt => 10,
+            Self::Zebra => 11,
+            Self::Bird => 12,
+            Self::Dnrouted => 13,
+            Self::Xorp => 14,
+            Self::Ntk => 15,
+            Self::Dhcp => 16,
+            Self::Mrouted => 17,
+            Self::Keepalived => 18,
+            Self::Babel => 42,
+            Self::Bgp => 186,
+            Self::Isis => 187,
+            Self::Ospf => 188,
+            Self::Rip => 189,
+            Self::Eigrp => 192,
+            Self::Unknown(n) => *n,
+        }
+    }
+
+    /// Get the built-in name of this protocol (`"unknown"` for
+    /// [`RouteProtocol::Unknown`]).
+    ///
+    /// Ignores registered rt_protos names; use [`Display`](fmt::Display)
+    /// for output.
     pub fn name(&self) -> &'static str {
         match self {
             Self::Unspec => "unspec",
@@ -309,20 +419,74 @@ impl RouteProtocol {
             Self::Ospf => "ospf",
             Self::Rip => "rip",
             Self::Eigrp => "eigrp",
+            Self::Unknown(_) => "unknown",
         }
     }
+
+    /// Parse a built-in protocol name (case-insensitive).
+    pub(crate) fn from_builtin_name(name: &str) -> Option<Self> {
+        let proto = match name.to_ascii_lowercase().as_str() {
+            "unspec" => Self::Unspec,
+            "redirect" => Self::Redirect,
+            "kernel" => Self::Kernel,
+            "boot" => Self::Boot,
+            "static" => Self::Static,
+            "gated" => Self::Gated,
+            "ra" => Self::Ra,
+            "mrt" => Self::Mrt,
+            "zebra" => Self::Zebra,
+            "bird" => Self::Bird,
+            "dnrouted" => Self::Dnrouted,
+            "xorp" => Self::Xorp,
+            "ntk" => Self::Ntk,
+            "dhcp" => Self::Dhcp,
+            "mrouted" => Self::Mrouted,
+            "keepalived" => Self::Keepalived,
+            "babel" => Self::Babel,
+            "bgp" => Self::Bgp,
+            "isis" => Self::Isis,
+            "ospf" => Self::Ospf,
+            "rip" => Self::Rip,
+            "eigrp" => Self::Eigrp,
+            _ => return None,
+        };
+        Some(proto)
+    }
+}
+
+impl fmt::Display for RouteProtocol {
+    /// The rt_protos or built-in name, or the number if there is none
+    /// (as `ip route` prints it).
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&crate::util::names::format_protocol(self.number()))
+    }
+}
+
+impl FromStr for RouteProtocol {
+    type Err = Error;
+
+    /// Parse a registered or built-in protocol name, or a number.
+    fn from_str(s: &str) -> Result<Self> {
+        crate::util::names::protocol_id(s)
+            .map(Self::from)
+            .ok_or_else(|| Error::InvalidMessage(format!("unknown route protocol `{s}`")))
+    }
 }
 
 /// Route scope (RT_SCOPE_*).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
+///
+/// Values without a variant (user-defined scopes) are kept in
+/// [`RouteScope::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum RouteScope {
-    Universe = 0,
-    Site = 200,
-    Link = 253,
-    Host = 254,
-    Nowhere = 255,
+    Universe,
+    Site,
+    Link,
+    Host,
+    Nowhere,
+    /// A scope this crate has no name for.
+    Unknown(u8),
 }
 
 impl From<u8> for RouteScope {
@@ -333,13 +497,31 @@ impl From<u8> for RouteScope {
             253 => Self::Link,
             254 => Self::Host,
             255 => Self::Nowhere,
-            _ => Self::Universe,
+            other => Self::Unknown(other),
         }
     }
 }
 
+impl From<RouteScope> for u8 {
+    fn from(val: RouteScope) -> Self {
+        val.number()
+    }
+}
+
 impl RouteScope {
-    /// Get the name of this scope.
+    /// Get the scope number.
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::Universe => 0,
+            Self::Site => 200,
+            Self::Link => 253,
+            Self::Host => 254,
+            Self::Nowhere => 255,
+            Self::Unknown(n) => *n,
+        }
+    }
+
+    /// Get the name of this scope (`"unknown"` for [`RouteScope::Unknown`]).
     pub fn name(&self) -> &'static str {
         match self {
             Self::Universe => "global",
@@ -347,6 +529,7 @@ impl RouteScope {
             Self::Link => "link",
             Self::Host => "host",
             Self::Nowhere => "nowhere",
+            Self::Unknown(_) => "unknown",
         }
     }
 
@@ -363,6 +546,34 @@ impl RouteScope {
     }
 }
 
+impl fmt::Display for RouteScope {
+    /// The iproute2 name, or the number for an unknown scope.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(n) => write!(f, "{n}"),
+            known => f.write_str(known.name()),
+        }
+    }
+}
+
+impl FromStr for RouteScope {
+    type Err = Error;
+
+    /// Parse a scope name (case-insensitive) or a number.
+    fn from_str(s: &str) -> Result<Self> {
+        match Self::from_name(s) {
+            Some(scope) => Ok(scope),
+            None => parse_number::<u8>(s, "route scope").map(Self::from),
+        }
+    }
+}
+
+/// Parse the numeric form of a named constant.
+pub(crate) fn parse_number<T: FromStr>(s: &str, what: &str) -> Result<T> {
+    s.parse()
+        .map_err(|_| Error::InvalidMessage(format!("unknown {what} `{s}`")))
+}
+
 /// Route table IDs.
 pub mod rt_table {
     pub const UNSPEC: u8 = 0;
@@ -384,3 +595,43 @@ pub mod rtm_flags {
     pub const TRAP: u32 = 0x8000;
     pub const OFFLOAD_FAILED: u32 = 0x20000000;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_values_round_trip() {
+        for n in 0..=u8::MAX {
+            assert_eq!(RouteType::from(n).number(), n);
+            assert_eq!(RouteProtocol::from(n).number(), n);
+            assert_eq!(RouteScope::from(n).number(), n);
+        }
+        assert_eq!(RouteProtocol::from(99), RouteProtocol::Unknown(99));
+    }
+
+    #[test]
+    fn display_and_parse_agree() {
+        assert_eq!(RouteType::Blackhole.to_string(), "blackhole");
+        assert_eq!(
+            "BLACKHOLE".parse::<RouteType>().unwrap(),
+            RouteType::Blackhole
+        );
+        assert_eq!(RouteType::Unknown(77).to_string(), "77");
+        assert_eq!("77".parse::<RouteType>().unwrap(), RouteType::Unknown(77));
+
+        assert_eq!(RouteProtocol::Bird.to_string(), "bird");
+        assert_eq!(
+            "static".parse::<RouteProtocol>().unwrap(),
+            RouteProtocol::Static
+        );
+        assert_eq!("4".parse::<RouteProtocol>().unwrap(), RouteProtocol::Static);
+
+        assert_eq!(RouteScope::Link.to_string(), "link");
+        assert_eq!("host".parse::<RouteScope>().unwrap(), RouteScope::Host);
+        assert_eq!(RouteScope::Unknown(100).to_string(), "100");
+
+        assert!("bogus".parse::<RouteType>().is_err());
+        assert!("300".parse::<RouteScope>().is_err());
+    }
+}
@@ -1,5 +1,7 @@
 //! Routing rule message types.
 
+use std::{fmt, str::FromStr};
+
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
 use crate::netlink::error::{Error, Result};
@@ -121,19 +123,22 @@ impl From<u16> for FraAttr {
 }
 
 /// FIB rule actions (FR_ACT_*).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[repr(u8)]
+///
+/// Values without a variant are kept in [`FibRuleAction::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum FibRuleAction {
-    Unspec = 0,
-    ToTbl = 1,
-    Goto = 2,
-    Nop = 3,
-    Res3 = 4,
-    Res4 = 5,
-    Blackhole = 6,
-    Unreachable = 7,
-    Prohibit = 8,
+    Unspec,
+    ToTbl,
+    Goto,
+    Nop,
+    Res3,
+    Res4,
+    Blackhole,
+    Unreachable,
+    Prohibit,
+    /// An action this crate has no name for.
+    Unknown(u8),
 }
 
 impl From<u8> for FibRuleAction {
@@ -143,16 +148,40 @@ impl From<u8> for FibRuleAction {
             1 => Self::ToTbl,
             2 => Self::Goto,
             3 => Self::Nop,
+            4 => Self::Res3,
+            5 => Self::Res4,
             6 => Self::Blackhole,
             7 => Self::Unreachable,
             8 => Self::Prohibit,
-            _ => Self::Unspec,
+            other => Self::Unknown(other),
         }
     }
 }
 
+impl From<FibRuleAction> for u8 {
+    fn from(val: FibRuleAction) -> Self {
+        val.number()
+    }
+}
+
 impl FibRuleAction {
-    /// Get the name of this action.
+    /// Get the action number.
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::Unspec => 0,
+            Self::ToTbl => 1,
+            Self::Goto => 2,
+            Self::Nop => 3,
+            Self::Res3 => 4,
+            Self::Res4 => 5,
+            Self::Blackhole => 6,
+            Self::Unreachable => 7,
+            Self::Prohibit => 8,
+            Self::Unknown(n) => *n,
+        }
+    }
+
+    /// Get the name of this action (`"unknown"` for [`FibRuleAction::Unknown`]).
     pub fn name(&self) -> &'static str {
         match self {
             Self::Unspec => "unspec",
@@ -164,10 +193,43 @@ impl FibRuleAction {
             Self::Blackhole => "blackhole",
             Self::Unreachable => "unreachable",
             Self::Prohibit => "prohibit",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for FibRuleAction {
+    /// The iproute2 name, or the number for an unknown action.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(n) => write!(f, "{n}"),
+            known => f.write_str(known.name()),
         }
     }
 }
 
+impl FromStr for FibRuleAction {
+    type Err = Error;
+
+    /// Parse an action name (case-insensitive; `table` and `lookup` both
+    /// mean [`FibRuleAction::ToTbl`]) or a number.
+    fn from_str(s: &str) -> Result<Self> {
+        let action = match s.to_ascii_lowercase().as_str() {
+            "unspec" => Self::Unspec,
+            "lookup" | "table" => Self::ToTbl,
+            "goto" => Self::Goto,
+            "nop" => Self::Nop,
+            "res3" => Self::Res3,
+            "res4" => Self::Res4,
+            "blackhole" => Self::Blackhole,
+            "unreachable" => Self::Unreachable,
+            "prohibit" => Self::Prohibit,
+            _ => return super::route::parse_number::<u8>(s, "rule action").map(Self::from),
+        };
+        Ok(action)
+    }
+}
+
 /// UID range for FRA_UID_RANGE.
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, FromBytes, Immutable, KnownLayout)]
@@ -164,11 +164,20 @@ pub mod tc_handle {
     pub const ROOT: u32 = 0xFFFFFFFF;
     /// Ingress qdisc.
     pub const INGRESS: u32 = 0xFFFFFFF1;
-    /// Clsact qdisc.
+    /// Clsact qdisc. This is also the filter parent of the clsact ingress
+    /// hook, `TC_H_MAKE(TC_H_CLSACT, TC_H_MIN_INGRESS)`.
     pub const CLSACT: u32 = 0xFFFFFFF2;
+    /// Filter parent of the clsact egress hook,
+    /// `TC_H_MAKE(TC_H_CLSACT, TC_H_MIN_EGRESS)`.
+    pub const CLSACT_EGRESS: u32 = 0xFFFFFFF3;
     /// Unspecified.
     pub const UNSPEC: u32 = 0;
 
+    /// Minor of the clsact ingress hook (`TC_H_MIN_INGRESS`).
+    pub const MIN_INGRESS: u16 = 0xFFF2;
+    /// Minor of the clsact egress hook (`TC_H_MIN_EGRESS`).
+    pub const MIN_EGRESS: u16 = 0xFFF3;
+
     /// Make a handle from major:minor.
     pub const fn make(major: u16, minor: u16) -> u32 {
         ((major as u32) << 16) | (minor as u32)
@@ -192,6 +201,8 @@ pub mod tc_handle {
             "ingress".to_string()
         } else if handle == CLSACT {
             "clsact".to_string()
+        } else if handle == CLSACT_EGRESS {
+            "egress".to_string()
         } else if handle == UNSPEC {
             "none".to_string()
         } else {
@@ -206,28 +217,57 @@ pub mod tc_handle {
     }
 
     /// Parse a handle from major:minor string.
+    ///
+    /// Besides the names [`format()`] emits, accepts `X:Y`, `X:` and the
+    /// `:Y` class-id shorthand (major 0), all in hex. Names and digits
+    /// are matched case-insensitively, so `FFFF:FFF2` works.
     pub fn parse(s: &str) -> Option<u32> {
-        match s {
+        match s.to_ascii_lowercase().as_str() {
             "root" => Some(ROOT),
             "ingress" => Some(INGRESS),
             "clsact" => Some(CLSACT),
+            "egress" => Some(CLSACT_EGRESS),
             "none" => Some(UNSPEC),
             _ => {
-                let parts: Vec<&str> = s.split(':').collect();
-                if parts.len() == 2 {
-                    let major = u16::from_str_radix(parts[0], 16).ok()?;
-                    let minor = if parts[1].is_empty() {
-                        0
-                    } else {
-                        u16::from_str_radix(parts[1], 16).ok()?
-                    };
-                    Some(make(major, minor))
+                let (major, minor) = s.split_once(':')?;
+                let major = if major.is_empty() {
+                    if minor.is_empty() {
+                        return None;
+                    }
+                    0
                 } else {
-                    None
-                }
+                    parse_hex_u16(major)?
+                };
+                let minor = if minor.is_empty() {
+                    0
+                } else {
+                    parse_hex_u16(minor)?
+                };
+                Some(make(major, minor))
             }
         }
     }
+
+    /// Parse a qdisc handle, which `tc(8)` also accepts without the
+    /// trailing colon (`handle 1` is `1:`). A non-zero minor is rejected:
+    /// qdisc handles only carry a major.
+    pub fn parse_qdisc(s: &str) -> Option<u32> {
+        if !s.contains(':')
+            && let Some(major) = parse_hex_u16(s)
+        {
+            return Some(make(major, 0));
+        }
+        let handle = parse(s)?;
+        (minor(handle) == 0).then_some(handle)
+    }
+
+    /// Hex digits only: `from_str_radix` would also take a leading `+`.
+    fn parse_hex_u16(s: &str) -> Option<u16> {
+        if s.is_empty() || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        u16::from_str_radix(s, 16).ok()
+    }
 }
 
 /// TC statistics (struct tc_stats).
@@ -1847,14 +1887,22 @@ pub mod filter {
         pub const TCA_FLOWER_KEY_ENC_IP_TOS_MASK: u16 = 81;
         pub const TCA_FLOWER_KEY_ENC_IP_TTL: u16 = 82;
         pub const TCA_FLOWER_KEY_ENC_IP_TTL_MASK: u16 = 83;
-        pub const TCA_FLOWER_KEY_CT_STATE: u16 = 84;
-        pub const TCA_FLOWER_KEY_CT_STATE_MASK: u16 = 85;
-        pub const TCA_FLOWER_KEY_CT_ZONE: u16 = 86;
-        pub const TCA_FLOWER_KEY_CT_ZONE_MASK: u16 = 87;
-        pub const TCA_FLOWER_KEY_CT_MARK: u16 = 88;
-        pub const TCA_FLOWER_KEY_CT_MARK_MASK: u16 = 89;
-        pub const TCA_FLOWER_KEY_CT_LABELS: u16 = 90;
-        pub const TCA_FLOWER_KEY_CT_LABELS_MASK: u16 = 91;
+        pub const TCA_FLOWER_KEY_ENC_OPTS: u16 = 84;
+        pub const TCA_FLOWER_KEY_ENC_OPTS_MASK: u16 = 85;
+        /// Number of devices the filter is offloaded to (dump only).
+        pub const TCA_FLOWER_IN_HW_COUNT: u16 = 86;
+        pub const TCA_FLOWER_KEY_PORT_SRC_MIN: u16 = 87;
+        pub const TCA_FLOWER_KEY_PORT_SRC_MAX: u16 = 88;
+        pub const TCA_FLOWER_KEY_PORT_DST_MIN: u16 = 89;
+        pub const TCA_FLOWER_KEY_PORT_DST_MAX: u16 = 90;
+        pub const TCA_FLOWER_KEY_CT_STATE: u16 = 91;
+        pub const TCA_FLOWER_KEY_CT_STATE_MASK: u16 = 92;
+        pub const TCA_FLOWER_KEY_CT_ZONE: u16 = 93;
+        pub const TCA_FLOWER_KEY_CT_ZONE_MASK: u16 = 94;
+        pub const TCA_FLOWER_KEY_CT_MARK: u16 = 95;
+        pub const TCA_FLOWER_KEY_CT_MARK_MASK: u16 = 96;
+        pub const TCA_FLOWER_KEY_CT_LABELS: u16 = 97;
+        pub const TCA_FLOWER_KEY_CT_LABELS_MASK: u16 = 98;
 
         /// Flower filter flags.
         pub const TCA_CLS_FLAGS_SKIP_HW: u32 = 1 << 0;
@@ -2218,6 +2266,11 @@ pub mod action {
     pub const TCA_ACT_USED_HW_STATS: u16 = 9;
     pub const TCA_ACT_IN_HW_COUNT: u16 = 10;
 
+    /// `TCA_ACT_FLAGS` bits (an `nla_bitfield32`).
+    pub const TCA_ACT_FLAGS_NO_PERCPU_STATS: u32 = 1 << 0;
+    pub const TCA_ACT_FLAGS_SKIP_HW: u32 = 1 << 1;
+    pub const TCA_ACT_FLAGS_SKIP_SW: u32 = 1 << 2;
+
     /// Action binding constants.
     pub const TCA_ACT_BIND: i32 = 1;
     pub const TCA_ACT_NOBIND: i32 = 0;
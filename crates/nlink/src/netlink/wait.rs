@@ -0,0 +1,404 @@
+//! Waiting for links, addresses and routes to show up.
+//!
+//! Orchestration and test code often has to wait for the kernel to
+//! reach a state before moving on: a link to be up, an address to be
+//! usable, a route to be installed. These helpers subscribe to the
+//! relevant rtnetlink group first and then look at the current state,
+//! so a change that lands between the two is never missed. After that
+//! they wake on events instead of polling.
+//!
+//! The events are read on a second connection opened in the same
+//! network namespace as the one the method is called on. That
+//! connection only lives for the duration of the call.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::time::Duration;
+//!
+//! let timeout = Duration::from_secs(5);
+//! conn.wait_for_link_up("eth0", timeout).await?;
+//! conn.wait_for_address("eth0", "fd00::1".parse()?, 64, timeout).await?;
+//! conn.wait_for_route("0.0.0.0".parse()?, 0, timeout).await?;
+//!
+//! // Make the kernel ARP for the gateway and wait for the answer.
+//! let gw = conn.probe_neighbor("eth0", "192.0.2.1".parse()?, timeout).await?;
+//! assert!(gw.is_reachable());
+//! ```
+
+use std::{future::Future, net::IpAddr, time::Duration};
+
+use tokio_stream::StreamExt;
+
+use super::{
+    connection::{Connection, RtnetlinkGroup},
+    error::{Error, Result},
+    events::NetworkEvent,
+    interface_ref::InterfaceRef,
+    messages::{AddressMessage, LinkMessage, NeighborMessage, RouteMessage},
+    neigh::Neighbor,
+    protocol::Route,
+    types::neigh::NeighborState,
+};
+
+impl Connection<Route> {
+    /// Wait until an interface exists and is administratively up
+    /// (`IFF_UP`).
+    ///
+    /// Unlike [`wait_link_up`](Self::wait_link_up), the interface need
+    /// not exist yet: waiting for a name that a container runtime or
+    /// udev is about to create is fine. Returns the link as it was when
+    /// it came up, or `Err(Timeout)`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let link = conn.wait_for_link_up("veth-ctr", Duration::from_secs(5)).await?;
+    /// println!("ifindex {}", link.ifindex());
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "wait_for_link_up"))]
+    pub async fn wait_for_link_up(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        timeout: Duration,
+    ) -> Result<LinkMessage> {
+        let iface = iface.into();
+        let is_target = |link: &LinkMessage| match &iface {
+            InterfaceRef::Name(name) => link.name() == Some(name.as_str()),
+            InterfaceRef::Index(index) => link.ifindex() == *index,
+        };
+
+        self.wait_for(
+            &[RtnetlinkGroup::Link],
+            timeout,
+            async {
+                let link = match &iface {
+                    InterfaceRef::Name(_) => self.get_link_by_name(iface.clone()).await?,
+                    InterfaceRef::Index(index) => self.get_link_by_index(*index).await?,
+                };
+                Ok(link.filter(LinkMessage::is_up))
+            },
+            |event| match event {
+                NetworkEvent::NewLink(link) if is_target(&link) && link.is_up() => Ok(Some(link)),
+                _ => Ok(None),
+            },
+        )
+        .await
+    }
+
+    /// Wait until an interface is up and has carrier.
+    ///
+    /// The interface must already exist. On `Err(Timeout)`, fetch the
+    /// link and look at [`LinkMessage::down_reason`] to tell an
+    /// interface nobody set up from one with no cable or peer.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// match conn.wait_for_carrier("eth0", Duration::from_secs(10)).await {
+    ///     Ok(link) => println!("eth0 up, {:?} carrier flaps", link.carrier_down_count()),
+    ///     Err(Error::Timeout) => {
+    ///         let link = conn.get_link_by_name("eth0").await?.unwrap();
+    ///         eprintln!("eth0 not ready: {}", link.down_reason().unwrap());
+    ///     }
+    ///     Err(e) => return Err(e),
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "wait_for_carrier"))]
+    pub async fn wait_for_carrier(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        timeout: Duration,
+    ) -> Result<LinkMessage> {
+        let ifindex = self.resolve_interface(&iface.into()).await?;
+        let ready = |link: &LinkMessage| link.is_up() && link.has_carrier();
+
+        self.wait_for(
+            &[RtnetlinkGroup::Link],
+            timeout,
+            async {
+                let link = self.get_link_by_index(ifindex).await?;
+                Ok(link.filter(ready))
+            },
+            |event| match event {
+                NetworkEvent::NewLink(link) if link.ifindex() == ifindex && ready(&link) => {
+                    Ok(Some(link))
+                }
+                _ => Ok(None),
+            },
+        )
+        .await
+    }
+
+    /// Wait until `address/prefix_len` is assigned to an interface and
+    /// usable.
+    ///
+    /// An IPv6 address only counts once duplicate address detection has
+    /// finished, so it can be bound to as soon as this returns. If DAD
+    /// fails the wait ends early with an error. The interface itself must
+    /// already exist.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// conn.add_address_by_name("eth0", "fd00::1".parse()?, 64).await?;
+    /// conn.wait_for_address("eth0", "fd00::1".parse()?, 64, Duration::from_secs(5)).await?;
+    /// // The address is no longer tentative; bind() will succeed.
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "wait_for_address"))]
+    pub async fn wait_for_address(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        address: IpAddr,
+        prefix_len: u8,
+        timeout: Duration,
+    ) -> Result<AddressMessage> {
+        let ifindex = self.resolve_interface(&iface.into()).await?;
+        let group = match address {
+            IpAddr::V4(_) => RtnetlinkGroup::Ipv4Addr,
+            IpAddr::V6(_) => RtnetlinkGroup::Ipv6Addr,
+        };
+        let is_target = |a: &AddressMessage| {
+            a.ifindex() == ifindex
+                && a.prefix_len() == prefix_len
+                && a.primary_address() == Some(&address)
+        };
+
+        self.wait_for(
+            &[group],
+            timeout,
+            async {
+                let addresses = self.get_addresses_by_index(ifindex).await?;
+                match addresses.into_iter().find(is_target) {
+                    Some(a) => address_ready(a),
+                    None => Ok(None),
+                }
+            },
+            |event| match event {
+                NetworkEvent::NewAddress(a) if is_target(&a) => address_ready(a),
+                _ => Ok(None),
+            },
+        )
+        .await
+    }
+
+    /// Wait until a route to `dst/prefix_len` is installed, in any table.
+    ///
+    /// `0.0.0.0/0` or `::/0` waits for a default route. Returns the first
+    /// matching route seen.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Wait for DHCP to install a default route.
+    /// let route = conn
+    ///     .wait_for_route("0.0.0.0".parse()?, 0, Duration::from_secs(30))
+    ///     .await?;
+    /// println!("default via {:?}", route.gateway());
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "wait_for_route"))]
+    pub async fn wait_for_route(
+        &self,
+        dst: IpAddr,
+        prefix_len: u8,
+        timeout: Duration,
+    ) -> Result<RouteMessage> {
+        let group = match dst {
+            IpAddr::V4(_) => RtnetlinkGroup::Ipv4Route,
+            IpAddr::V6(_) => RtnetlinkGroup::Ipv6Route,
+        };
+
+        self.wait_for(
+            &[group],
+            timeout,
+            async {
+                let routes = self.get_routes().await?;
+                Ok(routes
+                    .into_iter()
+                    .find(|r| route_matches(r, dst, prefix_len)))
+            },
+            |event| match event {
+                NetworkEvent::NewRoute(r) if route_matches(&r, dst, prefix_len) => Ok(Some(r)),
+                _ => Ok(None),
+            },
+        )
+        .await
+    }
+
+    /// Make the kernel resolve `destination` on an interface (ARP for
+    /// IPv4, neighbour solicitation for IPv6) and wait for the outcome.
+    ///
+    /// The request is an `NTF_USE` neighbour update, so the kernel
+    /// sends the probes with its usual retransmit timers and no raw
+    /// socket is needed. An entry that is already `REACHABLE` (or
+    /// `PERMANENT`/`NOARP`) is returned at once; a `STALE` one is
+    /// re-confirmed, which takes the kernel's `delay_first_probe_time`
+    /// (5 s by default) unless traffic confirms it first.
+    ///
+    /// Returns the entry once resolution ends: check
+    /// [`is_reachable`](NeighborMessage::is_reachable) or
+    /// [`is_failed`](NeighborMessage::is_failed). `Err(Timeout)` if it
+    /// is still in progress after `timeout`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let entry = conn
+    ///     .probe_neighbor("eth0", "fe80::1".parse()?, Duration::from_secs(3))
+    ///     .await?;
+    /// if entry.is_failed() {
+    ///     eprintln!("router does not answer");
+    /// }
+    /// ```
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "probe_neighbor"))]
+    pub async fn probe_neighbor(
+        &self,
+        iface: impl Into<InterfaceRef>,
+        destination: IpAddr,
+        timeout: Duration,
+    ) -> Result<NeighborMessage> {
+        let ifindex = self.resolve_interface(&iface.into()).await?;
+        let is_target =
+            |n: &NeighborMessage| n.ifindex() == ifindex && n.destination() == Some(&destination);
+
+        self.wait_for(
+            &[RtnetlinkGroup::Neigh],
+            timeout,
+            async {
+                self.replace_neighbor(
+                    Neighbor::with_index(ifindex, destination)
+                        .state(NeighborState::None)
+                        .use_entry(),
+                )
+                .await?;
+                let neighbors = self.get_neighbors_by_index(ifindex).await?;
+                Ok(neighbors
+                    .into_iter()
+                    .find(|n| is_target(n) && neighbor_resolved(n.state())))
+            },
+            |event| match event {
+                NetworkEvent::NewNeighbor(n)
+                    if is_target(&n)
+                        && (neighbor_resolved(n.state()) || n.state() == NeighborState::Failed) =>
+                {
+                    Ok(Some(n))
+                }
+                _ => Ok(None),
+            },
+        )
+        .await
+    }
+
+    /// Subscribe to `groups`, run `current`, then feed events to
+    /// `on_event` until either yields a value or `timeout` passes.
+    async fn wait_for<T>(
+        &self,
+        groups: &[RtnetlinkGroup],
+        timeout: Duration,
+        current: impl Future<Output = Result<Option<T>>>,
+        mut on_event: impl FnMut(NetworkEvent) -> Result<Option<T>>,
+    ) -> Result<T> {
+        // Subscribe before looking, so a change between the check and
+        // the first event read is still delivered.
+        let watcher = self.event_connection()?;
+        watcher.subscribe(groups)?;
+        let mut events = watcher.events().await;
+
+        let wait = async {
+            if let Some(found) = current.await? {
+                return Ok(found);
+            }
+            while let Some(event) = events.try_next().await? {
+                if let Some(found) = on_event(event)? {
+                    return Ok(found);
+                }
+            }
+            Err(Error::InvalidMessage(
+                "event stream ended while waiting".into(),
+            ))
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| Error::Timeout)?
+    }
+}
+
+/// `Some` once an address is usable, an error if DAD failed on it.
+fn address_ready(a: AddressMessage) -> Result<Option<AddressMessage>> {
+    if a.is_dad_failed() {
+        let address = a.primary_address().map(ToString::to_string);
+        return Err(Error::InvalidMessage(format!(
+            "duplicate address detection failed for {}",
+            address.as_deref().unwrap_or("address")
+        )));
+    }
+    Ok((!a.is_tentative()).then_some(a))
+}
+
+/// Whether a neighbour state means the link-layer address is known
+/// and confirmed.
+fn neighbor_resolved(state: NeighborState) -> bool {
+    matches!(
+        state,
+        NeighborState::Reachable | NeighborState::Permanent | NeighborState::Noarp
+    )
+}
+
+/// Whether `route` is for `dst/prefix_len`. A route without a
+/// destination attribute is a default route of its family.
+fn route_matches(route: &RouteMessage, dst: IpAddr, prefix_len: u8) -> bool {
+    let family = match dst {
+        IpAddr::V4(_) => libc::AF_INET as u8,
+        IpAddr::V6(_) => libc::AF_INET6 as u8,
+    };
+    if route.family() != family || route.dst_len() != prefix_len {
+        return false;
+    }
+    match route.destination() {
+        Some(d) => *d == dst,
+        None => dst.is_unspecified(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+    use crate::netlink::messages::{AddressMessageBuilder, RouteMessageBuilder};
+
+    #[test]
+    fn route_match_by_family_and_prefix() {
+        let net: IpAddr = Ipv4Addr::new(10, 1, 0, 0).into();
+        let r = RouteMessageBuilder::new().destination(net, 24).build();
+        assert!(route_matches(&r, net, 24));
+        assert!(!route_matches(&r, net, 16));
+        assert!(!route_matches(&r, Ipv4Addr::new(10, 2, 0, 0).into(), 24));
+
+        // A default route carries no RTA_DST.
+        let default = RouteMessageBuilder::new().ipv6().build();
+        assert!(route_matches(&default, Ipv6Addr::UNSPECIFIED.into(), 0));
+        assert!(!route_matches(&default, Ipv4Addr::UNSPECIFIED.into(), 0));
+    }
+
+    #[test]
+    fn tentative_address_is_not_ready() {
+        let addr = |flags| AddressMessageBuilder::new().ipv6().flags(flags).build();
+        // IFA_F_TENTATIVE
+        assert!(address_ready(addr(0x40)).unwrap().is_none());
+        // ... with IFA_F_DADFAILED
+        assert!(address_ready(addr(0x40 | 0x08)).is_err());
+        // IFA_F_PERMANENT
+        assert!(address_ready(addr(0x80)).unwrap().is_some());
+    }
+
+    #[test]
+    fn only_confirmed_neighbor_states_are_resolved() {
+        assert!(neighbor_resolved(NeighborState::Reachable));
+        assert!(neighbor_resolved(NeighborState::Permanent));
+        // A stale address is known but not confirmed.
+        assert!(!neighbor_resolved(NeighborState::Stale));
+        assert!(!neighbor_resolved(NeighborState::Incomplete));
+        assert!(!neighbor_resolved(NeighborState::Failed));
+    }
+}
@@ -25,7 +25,11 @@
 //! }
 //! ```
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::{Duration, SystemTime},
+};
 
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
@@ -758,12 +762,78 @@ pub struct SecurityAssociation {
     pub bytes: u64,
     /// Packets processed.
     pub packets: u64,
+    /// When the SA was added, in seconds since the epoch.
+    pub add_time: u64,
+    /// When the SA last processed a packet, in seconds since the epoch
+    /// (0 = never).
+    pub use_time: u64,
+    /// Configured soft/hard byte, packet and time limits.
+    pub lifetime: XfrmLifetimeCfg,
+    /// Replay-window, replay and integrity-check failure counters.
+    pub stats: XfrmStats,
     /// Replay window size.
     pub replay_window: u8,
     /// Flags.
     pub flags: u8,
 }
 
+impl SecurityAssociation {
+    /// When the SA was added.
+    pub fn added_at(&self) -> Option<SystemTime> {
+        epoch_secs(self.add_time)
+    }
+
+    /// When the SA last processed a packet, or `None` if it never has.
+    pub fn last_used(&self) -> Option<SystemTime> {
+        epoch_secs(self.use_time)
+    }
+}
+
+fn epoch_secs(secs: u64) -> Option<SystemTime> {
+    (secs != 0).then(|| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Traffic counters of one XFRM interface, summed over the SAs bound to
+/// its `if_id`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct XfrmInterfaceStats {
+    /// The interface ID (`XFRMA_IF_ID`) shared by the SAs.
+    pub if_id: u32,
+    /// Number of SAs bound to the interface.
+    pub sa_count: usize,
+    /// Bytes processed.
+    pub bytes: u64,
+    /// Packets processed.
+    pub packets: u64,
+    /// Packets dropped as outside the replay window.
+    pub replay_window_errors: u64,
+    /// Packets dropped as replays.
+    pub replay_errors: u64,
+    /// Packets that failed the integrity check.
+    pub integrity_failures: u64,
+}
+
+/// Sum SA counters per XFRM interface, ordered by `if_id`. SAs without
+/// an interface ID are skipped.
+pub fn xfrm_interface_stats(sas: &[SecurityAssociation]) -> Vec<XfrmInterfaceStats> {
+    let mut by_if: BTreeMap<u32, XfrmInterfaceStats> = BTreeMap::new();
+    for sa in sas {
+        let Some(if_id) = sa.if_id else { continue };
+        let entry = by_if.entry(if_id).or_insert_with(|| XfrmInterfaceStats {
+            if_id,
+            ..Default::default()
+        });
+        entry.sa_count += 1;
+        entry.bytes += sa.bytes;
+        entry.packets += sa.packets;
+        entry.replay_window_errors += u64::from(sa.stats.replay_window);
+        entry.replay_errors += u64::from(sa.stats.replay);
+        entry.integrity_failures += u64::from(sa.stats.integrity_failed);
+    }
+    by_if.into_values().collect()
+}
+
 /// A Security Policy (SP).
 #[derive(Debug, Clone)]
 pub struct SecurityPolicy {
@@ -1741,6 +1811,15 @@ impl Connection<Xfrm> {
         .await
     }
 
+    /// Get traffic counters per XFRM interface, summed over the SAs
+    /// bound to each `if_id`. See [`xfrm_interface_stats`].
+    #[tracing::instrument(level = "debug", skip_all, fields(method = "get_xfrm_interface_stats"))]
+    pub async fn get_xfrm_interface_stats(&self) -> Result<Vec<XfrmInterfaceStats>> {
+        Ok(xfrm_interface_stats(
+            &self.get_security_associations().await?,
+        ))
+    }
+
     /// Get all Security Policies.
     ///
     /// # Example
@@ -1906,6 +1985,10 @@ pub(crate) fn parse_sa_payload(msg_data: &[u8]) -> Option<SecurityAssociation> {
         if_id: None,
         bytes: info.curlft.bytes,
         packets: info.curlft.packets,
+        add_time: info.curlft.add_time,
+        use_time: info.curlft.use_time,
+        lifetime: info.lft,
+        stats: info.stats,
         replay_window: info.replay_window,
         flags: info.flags,
     };
@@ -2542,6 +2625,63 @@ mod tests {
         assert_eq!(encr.key.len(), 16);
     }
 
+    #[test]
+    fn xfrm_sa_parse_surfaces_usage_counters() {
+        let sa = XfrmSaBuilder::new(
+            "10.0.0.1".parse().unwrap(),
+            "10.0.0.2".parse().unwrap(),
+            0x100,
+            IpsecProtocol::Esp,
+        )
+        .encr_aes_cbc(&[0u8; 16])
+        .if_id(7);
+        let mut frame = build_add_sa_frame(sa);
+
+        let curlft = XfrmLifetimeCur {
+            bytes: 1500,
+            packets: 3,
+            add_time: 1_700_000_000,
+            use_time: 0,
+        };
+        let stats = XfrmStats {
+            replay_window: 1,
+            replay: 2,
+            integrity_failed: 4,
+        };
+        let at = NLMSG_HDRLEN + std::mem::offset_of!(XfrmUsersaInfo, curlft);
+        frame[at..at + 32].copy_from_slice(curlft.as_bytes());
+        let at = NLMSG_HDRLEN + std::mem::offset_of!(XfrmUsersaInfo, stats);
+        frame[at..at + 12].copy_from_slice(stats.as_bytes());
+
+        let parsed = Connection::<Xfrm>::parse_sa_msg(&frame).expect("parse");
+        assert_eq!((parsed.bytes, parsed.packets), (1500, 3));
+        assert_eq!(
+            parsed.added_at(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+        assert_eq!(parsed.last_used(), None);
+        assert_eq!(parsed.lifetime.hard_byte_limit, u64::MAX);
+        assert_eq!(parsed.stats.integrity_failed, 4);
+
+        let mut other = parsed.clone();
+        other.spi = 0x200;
+        let mut unbound = parsed.clone();
+        unbound.if_id = None;
+        let per_if = xfrm_interface_stats(&[parsed, other, unbound]);
+        assert_eq!(
+            per_if,
+            vec![XfrmInterfaceStats {
+                if_id: 7,
+                sa_count: 2,
+                bytes: 3000,
+                packets: 6,
+                replay_window_errors: 2,
+                replay_errors: 4,
+                integrity_failures: 8,
+            }]
+        );
+    }
+
     #[test]
     fn xfrm_sa_v4_esp_aead_aes_gcm_roundtrips() {
         // AEAD AES-GCM-128: 16-byte key + 4-byte salt = 20 bytes;
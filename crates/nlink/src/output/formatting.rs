@@ -6,15 +6,12 @@
 //! # Example
 //!
 //! ```
-//! use nlink::output::formatting::{format_bytes, format_rate_bps, format_duration};
+//! use nlink::output::formatting::{format_bytes, format_duration};
 //! use std::time::Duration;
 //!
 //! assert_eq!(format_bytes(1024), "1.00 KiB");
 //! assert_eq!(format_bytes(1_048_576), "1.00 MiB");
 //!
-//! assert_eq!(format_rate_bps(1_000_000), "1.0Mbps");
-//! assert_eq!(format_rate_bps(1_000_000_000), "1.0Gbps");
-//!
 //! assert_eq!(format_duration(Duration::from_secs(90)), "1 minute, 30 seconds");
 //! ```
 
@@ -59,9 +56,14 @@ pub fn format_bytes(bytes: u64) -> String {
 
 /// Format a bit rate in human-readable format (Kbps, Mbps, Gbps).
 ///
+/// Superseded by [`crate::util::format_rate`], which prints rates the way
+/// `tc(8)` does and whose output parses back with
+/// [`get_rate`](crate::util::get_rate).
+///
 /// # Example
 ///
 /// ```
+/// # #![allow(deprecated)]
 /// use nlink::output::formatting::format_rate_bps;
 ///
 /// assert_eq!(format_rate_bps(0), "0bps");
@@ -70,6 +72,7 @@ pub fn format_bytes(bytes: u64) -> String {
 /// assert_eq!(format_rate_bps(1_000_000_000), "1.0Gbps");
 /// assert_eq!(format_rate_bps(10_000_000_000), "10.0Gbps");
 /// ```
+#[deprecated(since = "0.26.0", note = "use `nlink::util::format_rate` instead")]
 pub fn format_rate_bps(bits_per_sec: u64) -> String {
     const KBPS: f64 = 1_000.0;
     const MBPS: f64 = 1_000_000.0;
@@ -407,6 +410,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_format_rate_bps() {
         assert_eq!(format_rate_bps(0), "0bps");
         assert_eq!(format_rate_bps(500), "500bps");
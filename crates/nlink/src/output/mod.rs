@@ -8,14 +8,16 @@ mod text;
 
 use std::io::Write;
 
+#[allow(deprecated)] // `format_rate_bps`, kept for existing callers
 pub use formatting::{
     format_bytes, format_duration, format_duration_compact, format_hex, format_ipv4, format_mac,
     format_percent, format_rate_bps, format_rate_bytes, format_tc_handle, format_time_ago,
 };
 pub use json::JsonOutput;
 pub use monitor::{
-    AddressEvent, IpEvent, LinkEvent, MonitorConfig, MonitorEvent, NeighborEvent, RouteEvent,
-    TcEvent, print_event, print_monitor_start, write_timestamp,
+    AddressEvent, IpEvent, LinkEvent, MonitorConfig, MonitorEvent, NeighborEvent, NexthopEvent,
+    NsIdEvent, RouteEvent, RuleEvent, TcEvent, print_event, print_event_at, print_monitor_start,
+    write_timestamp, write_timestamp_at,
 };
 pub use text::TextOutput;
 
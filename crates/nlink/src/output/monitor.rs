@@ -71,11 +71,23 @@ impl MonitorConfig {
 ///
 /// Format: `[seconds.milliseconds] `
 pub fn write_timestamp<W: Write>(w: &mut W, config: &MonitorConfig) -> io::Result<()> {
+    write_timestamp_at(w, config, SystemTime::now())
+}
+
+/// Write a timestamp prefix for `time` to the output if enabled.
+///
+/// Like [`write_timestamp`], for events whose time is known — such as
+/// messages replayed from a [capture](crate::netlink::capture).
+pub fn write_timestamp_at<W: Write>(
+    w: &mut W,
+    config: &MonitorConfig,
+    time: SystemTime,
+) -> io::Result<()> {
     if config.timestamp {
-        let now = SystemTime::now()
+        let since = time
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default();
-        write!(w, "[{}.{:03}] ", now.as_secs(), now.subsec_millis())?;
+        write!(w, "[{}.{:03}] ", since.as_secs(), since.subsec_millis())?;
     }
     Ok(())
 }
@@ -95,7 +107,22 @@ where
     W: Write,
     E: MonitorEvent,
 {
-    write_timestamp(w, config)?;
+    print_event_at(w, event, config, SystemTime::now())
+}
+
+/// Print a monitor event that happened at `time` using the configured
+/// format.
+pub fn print_event_at<W, E>(
+    w: &mut W,
+    event: &E,
+    config: &MonitorConfig,
+    time: SystemTime,
+) -> io::Result<()>
+where
+    W: Write,
+    E: MonitorEvent,
+{
+    write_timestamp_at(w, config, time)?;
 
     match config.format {
         OutputFormat::Text => {
@@ -178,7 +205,7 @@ pub struct AddressEvent {
 
 impl MonitorEvent for AddressEvent {
     fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> io::Result<()> {
-        let ifname = crate::util::get_ifname_or_index(self.ifindex);
+        let ifname = crate::netlink::ifindex_resolver::ifname_or_index(self.ifindex);
         writeln!(
             w,
             "ADDR{}: {}/{} dev {}",
@@ -236,7 +263,7 @@ impl MonitorEvent for RouteEvent {
         }
 
         if let Some(oif) = self.oif {
-            let name = crate::util::get_ifname_or_index(oif);
+            let name = crate::netlink::ifindex_resolver::ifname_or_index(oif);
             write!(w, " dev {}", name)?;
         }
 
@@ -271,7 +298,7 @@ pub struct NeighborEvent {
 
 impl MonitorEvent for NeighborEvent {
     fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> io::Result<()> {
-        let ifname = crate::util::get_ifname_or_index(self.ifindex);
+        let ifname = crate::netlink::ifindex_resolver::ifname_or_index(self.ifindex);
 
         write!(
             w,
@@ -301,6 +328,172 @@ impl MonitorEvent for NeighborEvent {
     }
 }
 
+/// Policy-routing rule event for monitoring.
+pub struct RuleEvent {
+    pub action: &'static str,
+    pub family: u8,
+    pub priority: u32,
+    pub source: Option<String>,
+    pub src_len: u8,
+    pub destination: Option<String>,
+    pub dst_len: u8,
+    pub iif: Option<String>,
+    pub oif: Option<String>,
+    pub fwmark: Option<u32>,
+    pub table: u32,
+    pub rule_action: &'static str,
+}
+
+impl MonitorEvent for RuleEvent {
+    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> io::Result<()> {
+        write!(
+            w,
+            "RULE{}: {}: from ",
+            if self.action == "del" { " DEL" } else { "" },
+            self.priority
+        )?;
+        match &self.source {
+            Some(src) => write!(w, "{}/{}", src, self.src_len)?,
+            None => write!(w, "all")?,
+        }
+        if let Some(ref dst) = self.destination {
+            write!(w, " to {}/{}", dst, self.dst_len)?;
+        }
+        if let Some(ref iif) = self.iif {
+            write!(w, " iif {}", iif)?;
+        }
+        if let Some(ref oif) = self.oif {
+            write!(w, " oif {}", oif)?;
+        }
+        if let Some(mark) = self.fwmark {
+            write!(w, " fwmark {:#x}", mark)?;
+        }
+        if self.rule_action == "lookup" {
+            writeln!(w, " lookup {}", self.table)
+        } else {
+            writeln!(w, " {}", self.rule_action)
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "event": "rule",
+            "action": self.action,
+            "family": self.family,
+            "priority": self.priority,
+            "src": self.source,
+            "src_len": self.src_len,
+            "dst": self.destination,
+            "dst_len": self.dst_len,
+            "iif": self.iif,
+            "oif": self.oif,
+            "fwmark": self.fwmark,
+            "table": self.table,
+            "type": self.rule_action,
+        })
+    }
+}
+
+/// Nexthop-object event for monitoring.
+pub struct NexthopEvent {
+    pub action: &'static str,
+    pub id: u32,
+    pub gateway: Option<String>,
+    pub ifindex: Option<u32>,
+    pub blackhole: bool,
+    /// Group members as `(id, weight)`; empty for a single nexthop.
+    pub group: Vec<(u32, u16)>,
+}
+
+impl MonitorEvent for NexthopEvent {
+    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> io::Result<()> {
+        write!(
+            w,
+            "NEXTHOP{}: id {}",
+            if self.action == "del" { " DEL" } else { "" },
+            self.id
+        )?;
+
+        if !self.group.is_empty() {
+            let members: Vec<String> = self
+                .group
+                .iter()
+                .map(|(id, weight)| {
+                    if *weight > 1 {
+                        format!("{},{}", id, weight)
+                    } else {
+                        id.to_string()
+                    }
+                })
+                .collect();
+            write!(w, " group {}", members.join("/"))?;
+        }
+        if let Some(ref gw) = self.gateway {
+            write!(w, " via {}", gw)?;
+        }
+        if let Some(ifindex) = self.ifindex {
+            let name = crate::netlink::ifindex_resolver::ifname_or_index(ifindex);
+            write!(w, " dev {}", name)?;
+        }
+        if self.blackhole {
+            write!(w, " blackhole")?;
+        }
+
+        writeln!(w)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let group: Vec<_> = self
+            .group
+            .iter()
+            .map(|(id, weight)| serde_json::json!({ "id": id, "weight": weight }))
+            .collect();
+        serde_json::json!({
+            "event": "nexthop",
+            "action": self.action,
+            "id": self.id,
+            "gateway": self.gateway,
+            "ifindex": self.ifindex,
+            "blackhole": self.blackhole,
+            "group": group,
+        })
+    }
+}
+
+/// Network-namespace ID event for monitoring.
+pub struct NsIdEvent {
+    pub action: &'static str,
+    pub nsid: Option<u32>,
+    pub pid: Option<u32>,
+}
+
+impl MonitorEvent for NsIdEvent {
+    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> io::Result<()> {
+        write!(
+            w,
+            "NSID{}: ",
+            if self.action == "del" { " DEL" } else { "" }
+        )?;
+        match self.nsid {
+            Some(nsid) => write!(w, "nsid {}", nsid)?,
+            None => write!(w, "nsid unassigned")?,
+        }
+        if let Some(pid) = self.pid {
+            write!(w, " pid {}", pid)?;
+        }
+        writeln!(w)
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "event": "nsid",
+            "action": self.action,
+            "nsid": self.nsid,
+            "pid": self.pid,
+        })
+    }
+}
+
 // ============================================================================
 // TC Monitor Events
 // ============================================================================
@@ -354,6 +547,9 @@ pub enum IpEvent {
     Address(AddressEvent),
     Route(RouteEvent),
     Neighbor(NeighborEvent),
+    Rule(RuleEvent),
+    Nexthop(NexthopEvent),
+    NsId(NsIdEvent),
 }
 
 impl MonitorEvent for IpEvent {
@@ -363,6 +559,9 @@ impl MonitorEvent for IpEvent {
             IpEvent::Address(e) => e.print_text(w, opts),
             IpEvent::Route(e) => e.print_text(w, opts),
             IpEvent::Neighbor(e) => e.print_text(w, opts),
+            IpEvent::Rule(e) => e.print_text(w, opts),
+            IpEvent::Nexthop(e) => e.print_text(w, opts),
+            IpEvent::NsId(e) => e.print_text(w, opts),
         }
     }
 
@@ -372,6 +571,9 @@ impl MonitorEvent for IpEvent {
             IpEvent::Address(e) => e.to_json(),
             IpEvent::Route(e) => e.to_json(),
             IpEvent::Neighbor(e) => e.to_json(),
+            IpEvent::Rule(e) => e.to_json(),
+            IpEvent::Nexthop(e) => e.to_json(),
+            IpEvent::NsId(e) => e.to_json(),
         }
     }
 }
@@ -0,0 +1,42 @@
+//! Printable implementation for AddrLabelMessage.
+
+use std::io::Write;
+
+use crate::{
+    netlink::{ifindex_resolver::ifname_or_index, messages::AddrLabelMessage},
+    output::{OutputOptions, Printable},
+};
+
+impl Printable for AddrLabelMessage {
+    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> std::io::Result<()> {
+        if let Some(address) = self.address() {
+            write!(w, "prefix {}/{} ", address, self.prefix_len())?;
+        }
+        if let Some(ifindex) = self.ifindex() {
+            write!(w, "dev {} ", ifname_or_index(ifindex))?;
+        }
+        if let Some(label) = self.label() {
+            write!(w, "label {}", label)?;
+        }
+        writeln!(w)?;
+
+        Ok(())
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut obj = serde_json::json!({});
+
+        if let Some(address) = self.address() {
+            obj["address"] = serde_json::json!(address.to_string());
+            obj["prefixlen"] = serde_json::json!(self.prefix_len());
+        }
+        if let Some(ifindex) = self.ifindex() {
+            obj["dev"] = serde_json::json!(ifname_or_index(ifindex));
+        }
+        if let Some(label) = self.label() {
+            obj["label"] = serde_json::json!(label);
+        }
+
+        obj
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
 };
 
 impl Printable for AddressMessage {
-    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> std::io::Result<()> {
+    fn print_text<W: Write>(&self, w: &mut W, opts: &OutputOptions) -> std::io::Result<()> {
         let family = crate::util::names::family_name(self.family());
 
         // Get the primary address to display
@@ -43,6 +43,9 @@ impl Printable for AddressMessage {
         if self.is_tentative() {
             write!(w, " tentative")?;
         }
+        if opts.details {
+            write!(w, " origin {}", self.origin())?;
+        }
 
         if let Some(ref label) = self.label {
             write!(w, " {}", label)?;
@@ -54,7 +57,7 @@ impl Printable for AddressMessage {
     }
 
     fn to_json(&self) -> serde_json::Value {
-        let ifname = crate::util::get_ifname_or_index(self.ifindex());
+        let ifname = crate::netlink::ifindex_resolver::ifname_or_index(self.ifindex());
 
         let mut obj = serde_json::json!({
             "ifindex": self.ifindex(),
@@ -94,6 +97,7 @@ impl Printable for AddressMessage {
         if !flags.is_empty() {
             obj["flags"] = serde_json::json!(flags);
         }
+        obj["origin"] = serde_json::json!(self.origin().name());
 
         obj
     }
@@ -3,12 +3,12 @@
 use std::io::Write;
 
 use crate::{
-    netlink::messages::LinkMessage,
+    netlink::messages::{GtpRole, LinkKindInfo, LinkMessage},
     output::{OutputOptions, Printable},
 };
 
 impl Printable for LinkMessage {
-    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> std::io::Result<()> {
+    fn print_text<W: Write>(&self, w: &mut W, opts: &OutputOptions) -> std::io::Result<()> {
         let name = self.name.as_deref().unwrap_or("?");
 
         // Build flags string, adding NO-CARRIER if carrier is false
@@ -116,9 +116,60 @@ impl Printable for LinkMessage {
             if slave.link_failure_count > 0 {
                 write!(w, " failures {}", slave.link_failure_count)?;
             }
+            if opts.details {
+                if let Some(queue_id) = slave.queue_id {
+                    write!(w, " queue_id {}", queue_id)?;
+                }
+                if let Some(agg) = slave.ad_aggregator_id {
+                    write!(w, " ad_aggregator_id {}", agg)?;
+                }
+            }
             writeln!(w)?;
         }
 
+        // Line 3/4 (details only): bridge port (slave) info
+        if opts.details
+            && let Some(port) = self.bridge_port_info()
+        {
+            write!(
+                w,
+                "    bridge_slave state {} priority {} cost {}",
+                port.state.name(),
+                port.priority,
+                port.cost
+            )?;
+            write!(
+                w,
+                " hairpin {} guard {} root_block {} fastleave {} learning {} flood {}",
+                on_off(port.hairpin),
+                on_off(port.guard),
+                on_off(port.root_block),
+                on_off(port.fast_leave),
+                on_off(port.learning),
+                on_off(port.unicast_flood)
+            )?;
+            write!(w, " port_id {:#x} port_no {:#x}", port.port_id, port.port_no)?;
+            write!(
+                w,
+                " designated_port {} designated_cost {}",
+                port.designated_port, port.designated_cost
+            )?;
+            if let Some(bridge) = port.designated_bridge {
+                write!(w, " designated_bridge {}", bridge)?;
+            }
+            if let Some(root) = port.designated_root {
+                write!(w, " designated_root {}", root)?;
+            }
+            writeln!(w)?;
+        }
+
+        // Details only: kind-specific settings (bonds are printed above)
+        if opts.details
+            && let Some(kind) = self.kind_info()
+        {
+            write_kind_info(w, &kind)?;
+        }
+
         Ok(())
     }
 
@@ -196,10 +247,252 @@ impl Printable for LinkMessage {
             obj["bond_slave"] = serde_json::Value::Object(slave_obj);
         }
 
+        if let Some(port) = self.bridge_port_info() {
+            let mut port_obj = serde_json::json!({
+                "state": port.state.name(),
+                "priority": port.priority,
+                "cost": port.cost,
+                "hairpin": port.hairpin,
+                "guard": port.guard,
+                "root_block": port.root_block,
+                "fastleave": port.fast_leave,
+                "learning": port.learning,
+                "flood": port.unicast_flood,
+                "port_id": port.port_id,
+                "port_no": port.port_no,
+                "designated_port": port.designated_port,
+                "designated_cost": port.designated_cost,
+            });
+            if let Some(bridge) = port.designated_bridge {
+                port_obj["designated_bridge"] = serde_json::json!(bridge.to_string());
+            }
+            if let Some(root) = port.designated_root {
+                port_obj["designated_root"] = serde_json::json!(root.to_string());
+            }
+            obj["bridge_slave"] = port_obj;
+        }
+
+        if let Some(kind) = self.kind_info()
+            && let Some(data) = kind_info_json(&kind)
+        {
+            obj["info_data"] = data;
+        }
+
         obj
     }
 }
 
+/// Write the `    vlan protocol 802.1Q id 100`-style line for a link kind.
+fn write_kind_info<W: Write>(w: &mut W, kind: &LinkKindInfo) -> std::io::Result<()> {
+    match kind {
+        LinkKindInfo::Bridge(br) => {
+            write!(w, "    bridge")?;
+            if let Some(d) = br.forward_delay {
+                write!(w, " forward_delay {}", d.as_millis() / 10)?;
+            }
+            if let Some(d) = br.hello_time {
+                write!(w, " hello_time {}", d.as_millis() / 10)?;
+            }
+            if let Some(d) = br.max_age {
+                write!(w, " max_age {}", d.as_millis() / 10)?;
+            }
+            if let Some(d) = br.ageing_time {
+                write!(w, " ageing_time {}", d.as_millis() / 10)?;
+            }
+            if let Some(stp) = br.stp_state {
+                write!(w, " stp_state {}", stp)?;
+            }
+            if let Some(prio) = br.priority {
+                write!(w, " priority {}", prio)?;
+            }
+            write!(w, " vlan_filtering {}", u8::from(br.vlan_filtering))?;
+            if let Some(proto) = br.vlan_protocol {
+                write!(w, " vlan_protocol {}", vlan_proto_name(proto))?;
+            }
+            if let Some(pvid) = br.vlan_default_pvid {
+                write!(w, " vlan_default_pvid {}", pvid)?;
+            }
+            write!(w, " mcast_snooping {}", u8::from(br.mcast_snooping))?;
+            write!(w, " mcast_querier {}", u8::from(br.mcast_querier))?;
+            if let Some(version) = br.mcast_igmp_version {
+                write!(w, " mcast_igmp_version {}", version)?;
+            }
+            if let Some(version) = br.mcast_mld_version {
+                write!(w, " mcast_mld_version {}", version)?;
+            }
+            write!(w, " nf_call_iptables {}", u8::from(br.nf_call_iptables))?;
+            write!(w, " nf_call_ip6tables {}", u8::from(br.nf_call_ip6tables))?;
+            write!(w, " nf_call_arptables {}", u8::from(br.nf_call_arptables))?;
+        }
+        LinkKindInfo::Vlan(vlan) => {
+            write!(w, "    vlan")?;
+            if let Some(proto) = vlan.protocol {
+                write!(w, " protocol {}", vlan_proto_name(proto))?;
+            }
+            write!(w, " id {}", vlan.id)?;
+        }
+        LinkKindInfo::Vxlan(vx) => {
+            write!(w, "    vxlan id {}", vx.vni)?;
+            if let Some(group) = vx.group {
+                let word = if group.is_multicast() {
+                    "group"
+                } else {
+                    "remote"
+                };
+                write!(w, " {} {}", word, group)?;
+            }
+            if let Some(local) = vx.local {
+                write!(w, " local {}", local)?;
+            }
+            if let Some(link) = vx.link {
+                write!(
+                    w,
+                    " dev {}",
+                    crate::netlink::ifindex_resolver::ifname_or_index(link)
+                )?;
+            }
+            if let Some(port) = vx.port {
+                write!(w, " dstport {}", port)?;
+            }
+            match vx.ttl {
+                Some(0) => write!(w, " ttl auto")?,
+                Some(ttl) => write!(w, " ttl {}", ttl)?,
+                None => {}
+            }
+            if !vx.learning {
+                write!(w, " nolearning")?;
+            }
+            if let Some(ageing) = vx.ageing {
+                write!(w, " ageing {}", ageing.as_secs())?;
+            }
+        }
+        LinkKindInfo::Macvlan(mv) => {
+            let mode = mv
+                .macvlan_mode()
+                .map(|m| format!("{:?}", m).to_lowercase())
+                .unwrap_or_else(|| mv.mode.to_string());
+            write!(w, "    macvlan mode {}", mode)?;
+        }
+        LinkKindInfo::Vrf(vrf) => {
+            write!(w, "    vrf table {}", vrf.table.unwrap_or(0))?;
+        }
+        LinkKindInfo::Tun(tun) => {
+            write!(
+                w,
+                "    tun type {} pi {} vnet_hdr {} persist {} multi_queue {}",
+                if tun.is_tap() { "tap" } else { "tun" },
+                on_off(tun.pi),
+                on_off(tun.vnet_hdr),
+                on_off(tun.persist),
+                on_off(tun.multi_queue)
+            )?;
+            if let Some(owner) = tun.owner {
+                write!(w, " user {}", owner)?;
+            }
+            if let Some(group) = tun.group {
+                write!(w, " group {}", group)?;
+            }
+        }
+        LinkKindInfo::Gtp(gtp) => {
+            write!(w, "    gtp")?;
+            if let Some(role) = gtp.role {
+                write!(w, " role {}", gtp_role_name(role))?;
+            }
+            if let Some(size) = gtp.pdp_hashsize {
+                write!(w, " hashsize {}", size)?;
+            }
+            if let Some(count) = gtp.restart_count {
+                write!(w, " restart_count {}", count)?;
+            }
+        }
+        _ => return Ok(()),
+    }
+    writeln!(w)
+}
+
+/// JSON object for the kind-specific settings, if the kind has any
+/// decoded (bonds have their own `bond` object).
+fn kind_info_json(kind: &LinkKindInfo) -> Option<serde_json::Value> {
+    let centis = |d: Option<std::time::Duration>| d.map(|d| d.as_millis() as u64 / 10);
+    Some(match kind {
+        LinkKindInfo::Bridge(br) => serde_json::json!({
+            "forward_delay": centis(br.forward_delay),
+            "hello_time": centis(br.hello_time),
+            "max_age": centis(br.max_age),
+            "ageing_time": centis(br.ageing_time),
+            "stp_state": br.stp_state,
+            "priority": br.priority,
+            "vlan_filtering": br.vlan_filtering,
+            "vlan_protocol": br.vlan_protocol.map(vlan_proto_name),
+            "vlan_default_pvid": br.vlan_default_pvid,
+            "mcast_snooping": br.mcast_snooping,
+            "mcast_querier": br.mcast_querier,
+            "mcast_igmp_version": br.mcast_igmp_version,
+            "mcast_mld_version": br.mcast_mld_version,
+            "nf_call_iptables": br.nf_call_iptables,
+            "nf_call_ip6tables": br.nf_call_ip6tables,
+            "nf_call_arptables": br.nf_call_arptables,
+        }),
+        LinkKindInfo::Vlan(vlan) => serde_json::json!({
+            "protocol": vlan.protocol.map(vlan_proto_name),
+            "id": vlan.id,
+            "flags": vlan.flags,
+        }),
+        LinkKindInfo::Vxlan(vx) => serde_json::json!({
+            "id": vx.vni,
+            "group": vx.group.map(|a| a.to_string()),
+            "local": vx.local.map(|a| a.to_string()),
+            "link": vx.link,
+            "port": vx.port,
+            "ttl": vx.ttl,
+            "learning": vx.learning,
+            "ageing": vx.ageing.map(|d| d.as_secs()),
+        }),
+        LinkKindInfo::Macvlan(mv) => serde_json::json!({
+            "mode": mv
+                .macvlan_mode()
+                .map(|m| format!("{:?}", m).to_lowercase())
+                .unwrap_or_else(|| mv.mode.to_string()),
+        }),
+        LinkKindInfo::Vrf(vrf) => serde_json::json!({ "table": vrf.table }),
+        LinkKindInfo::Tun(tun) => serde_json::json!({
+            "type": if tun.is_tap() { "tap" } else { "tun" },
+            "pi": tun.pi,
+            "vnet_hdr": tun.vnet_hdr,
+            "persist": tun.persist,
+            "multi_queue": tun.multi_queue,
+            "user": tun.owner,
+            "group": tun.group,
+        }),
+        LinkKindInfo::Gtp(gtp) => serde_json::json!({
+            "role": gtp.role.map(gtp_role_name),
+            "hashsize": gtp.pdp_hashsize,
+            "restart_count": gtp.restart_count,
+        }),
+        _ => return None,
+    })
+}
+
+fn vlan_proto_name(proto: u16) -> String {
+    match proto {
+        0x8100 => "802.1Q".to_string(),
+        0x88a8 => "802.1ad".to_string(),
+        other => format!("{:#06x}", other),
+    }
+}
+
+fn gtp_role_name(role: GtpRole) -> String {
+    match role {
+        GtpRole::Ggsn => "ggsn".to_string(),
+        GtpRole::Sgsn => "sgsn".to_string(),
+        GtpRole::Unknown(raw) => raw.to_string(),
+    }
+}
+
+fn on_off(v: bool) -> &'static str {
+    if v { "on" } else { "off" }
+}
+
 fn group_name(group: u32) -> String {
     if group == 0 {
         "default".to_string()
@@ -6,8 +6,11 @@
 //! The implementations are automatically available when using the
 //! `Printable` trait from this crate.
 
+mod addr_label;
 mod address;
 mod link;
+mod mpls;
 mod neighbor;
+mod neighbor_table;
 mod route;
 mod tc;
@@ -0,0 +1,134 @@
+//! Printable implementation for MplsRoute.
+
+use std::{io::Write, net::IpAddr};
+
+use crate::{
+    netlink::{
+        ifindex_resolver::ifname_or_index,
+        mpls::{MplsAction, MplsLabel, MplsNexthop, MplsRoute},
+        types::route::RouteProtocol,
+    },
+    output::{OutputOptions, Printable},
+};
+
+/// `l1/l2/l3`, as iproute2 prints a label stack.
+fn label_stack(labels: &[MplsLabel]) -> String {
+    labels
+        .iter()
+        .map(|l| l.0.to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn via_family(via: &IpAddr) -> &'static str {
+    match via {
+        IpAddr::V4(_) => "inet",
+        IpAddr::V6(_) => "inet6",
+    }
+}
+
+/// The ` as to .. via .. dev ..` part shared by single-path routes and
+/// each nexthop.
+fn write_egress<W: Write>(
+    w: &mut W,
+    labels: &[MplsLabel],
+    via: Option<IpAddr>,
+    oif: Option<u32>,
+) -> std::io::Result<()> {
+    if !labels.is_empty() {
+        write!(w, " as to {}", label_stack(labels))?;
+    }
+    if let Some(via) = via {
+        write!(w, " via {} {}", via_family(&via), via)?;
+    }
+    if let Some(oif) = oif {
+        write!(w, " dev {}", ifname_or_index(oif))?;
+    }
+    Ok(())
+}
+
+fn nexthop_flags(nh: &MplsNexthop) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+    if nh.flags() & MplsNexthop::FLAG_DEAD != 0 {
+        flags.push("dead");
+    }
+    if nh.flags() & MplsNexthop::FLAG_LINKDOWN != 0 {
+        flags.push("linkdown");
+    }
+    flags
+}
+
+fn egress_json(labels: &[MplsLabel], via: Option<IpAddr>, oif: Option<u32>) -> serde_json::Value {
+    let mut obj = serde_json::json!({});
+    if !labels.is_empty() {
+        obj["newdst"] = serde_json::json!(label_stack(labels));
+    }
+    if let Some(via) = via {
+        obj["via"] = serde_json::json!({
+            "family": via_family(&via),
+            "host": via.to_string(),
+        });
+    }
+    if let Some(oif) = oif {
+        obj["dev"] = serde_json::json!(ifname_or_index(oif));
+    }
+    obj
+}
+
+impl Printable for MplsRoute {
+    fn print_text<W: Write>(&self, w: &mut W, _opts: &OutputOptions) -> std::io::Result<()> {
+        write!(w, "{}", self.label().0)?;
+
+        if self.multipath().is_none() {
+            let labels = match self.action() {
+                MplsAction::Swap(labels) => labels.as_slice(),
+                _ => &[],
+            };
+            write_egress(w, labels, self.via(), self.oif())?;
+        }
+
+        if self.protocol() != RouteProtocol::Unspec {
+            write!(w, " proto {}", self.protocol())?;
+        }
+
+        for nh in self.multipath().unwrap_or_default() {
+            write!(w, "\n\tnexthop")?;
+            write_egress(w, nh.labels(), nh.via(), Some(nh.oif()))?;
+            for flag in nexthop_flags(nh) {
+                write!(w, " {}", flag)?;
+            }
+        }
+
+        writeln!(w)?;
+
+        Ok(())
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut obj = match self.multipath() {
+            Some(nexthops) => {
+                let nexthops: Vec<_> = nexthops
+                    .iter()
+                    .map(|nh| {
+                        let mut hop = egress_json(nh.labels(), nh.via(), Some(nh.oif()));
+                        hop["flags"] = serde_json::json!(nexthop_flags(nh));
+                        hop
+                    })
+                    .collect();
+                serde_json::json!({ "nexthops": nexthops })
+            }
+            None => {
+                let labels = match self.action() {
+                    MplsAction::Swap(labels) => labels.as_slice(),
+                    _ => &[],
+                };
+                egress_json(labels, self.via(), self.oif())
+            }
+        };
+
+        obj["dst"] = serde_json::json!(self.label().0.to_string());
+        obj["protocol"] = serde_json::json!(self.protocol().to_string());
+
+        obj
+    }
+}
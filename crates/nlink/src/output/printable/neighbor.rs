@@ -3,7 +3,7 @@
 use std::io::Write;
 
 use crate::{
-    netlink::{messages::NeighborMessage, types::neigh::nud_state_name},
+    netlink::messages::NeighborMessage,
     output::{OutputOptions, Printable},
 };
 
@@ -17,8 +17,7 @@ impl Printable for NeighborMessage {
         }
 
         // Device
-        let dev = crate::util::ifname::index_to_name(self.ifindex())
-            .unwrap_or_else(|_| format!("if{}", self.ifindex()));
+        let dev = crate::netlink::ifindex_resolver::ifname_or_index(self.ifindex());
         write!(w, " dev {}", dev)?;
 
         // Link-layer address
@@ -30,9 +29,16 @@ impl Printable for NeighborMessage {
         if self.is_router() {
             write!(w, " router")?;
         }
+        if self.is_extern_learn() {
+            write!(w, " extern_learn")?;
+        }
 
-        // State
-        write!(w, " {}", nud_state_name(self.header.ndm_state))?;
+        // Proxy entries have no NUD state
+        if self.is_proxy() {
+            write!(w, " proxy")?;
+        } else {
+            write!(w, " {}", self.state())?;
+        }
 
         writeln!(w)?;
 
@@ -40,13 +46,12 @@ impl Printable for NeighborMessage {
     }
 
     fn to_json(&self) -> serde_json::Value {
-        let dev = crate::util::ifname::index_to_name(self.ifindex())
-            .unwrap_or_else(|_| format!("if{}", self.ifindex()));
+        let dev = crate::netlink::ifindex_resolver::ifname_or_index(self.ifindex());
 
         let mut obj = serde_json::json!({
             "ifindex": self.ifindex(),
             "dev": dev,
-            "state": nud_state_name(self.header.ndm_state),
+            "state": self.state().to_string(),
         });
 
         if let Some(ref dst) = self.destination {
@@ -65,6 +70,10 @@ impl Printable for NeighborMessage {
             obj["proxy"] = serde_json::json!(true);
         }
 
+        if self.is_extern_learn() {
+            obj["extern_learn"] = serde_json::json!(true);
+        }
+
         obj
     }
 }
@@ -0,0 +1,196 @@
+//! Printable implementation for NeighborTableMessage.
+
+use std::{io::Write, time::Duration};
+
+use crate::{
+    netlink::messages::{NeighborTableMessage, NeighborTableParms},
+    output::{OutputOptions, Printable},
+    util::names::family_name,
+};
+
+fn ms(d: Duration) -> u64 {
+    d.as_millis() as u64
+}
+
+/// Write `name value` pairs for the fields that are set, as one indented
+/// line; nothing if none are.
+fn write_line<W: Write>(w: &mut W, fields: &[(&str, Option<u64>)]) -> std::io::Result<()> {
+    let mut first = true;
+    for (name, value) in fields {
+        if let Some(value) = value {
+            write!(w, "{}{name} {value}", if first { "    " } else { " " })?;
+            first = false;
+        }
+    }
+    if !first {
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+fn parms_fields(p: &NeighborTableParms) -> [Vec<(&'static str, Option<u64>)>; 4] {
+    let n = |v: Option<u32>| v.map(u64::from);
+    let t = |v: Option<Duration>| v.map(ms);
+    [
+        vec![
+            ("refcnt", n(p.refcnt)),
+            ("reachable", t(p.reachable_time)),
+            ("base_reachable", t(p.base_reachable_time)),
+            ("retrans", t(p.retrans_time)),
+        ],
+        vec![
+            ("gc_stale", t(p.gc_stale_time)),
+            ("delay_probe", t(p.delay_probe_time)),
+            ("queue", n(p.queue_len)),
+        ],
+        vec![
+            ("app_probes", n(p.app_probes)),
+            ("ucast_probes", n(p.ucast_probes)),
+            ("mcast_probes", n(p.mcast_probes)),
+        ],
+        vec![
+            ("anycast_delay", t(p.anycast_delay)),
+            ("proxy_delay", t(p.proxy_delay)),
+            ("proxy_queue", n(p.proxy_qlen)),
+            ("locktime", t(p.locktime)),
+        ],
+    ]
+}
+
+impl Printable for NeighborTableMessage {
+    fn print_text<W: Write>(&self, w: &mut W, opts: &OutputOptions) -> std::io::Result<()> {
+        writeln!(
+            w,
+            "{} {}",
+            family_name(self.family()),
+            self.name().unwrap_or("?")
+        )?;
+
+        if let Some(ifindex) = self.ifindex() {
+            let dev = crate::netlink::ifindex_resolver::ifname_or_index(ifindex);
+            writeln!(w, "    dev {}", dev)?;
+        } else if self.config().is_none() && self.gc_thresh1().is_none() {
+            // Parameters not bound to a device, outside the table-wide message.
+            writeln!(w, "    dev *")?;
+        }
+
+        write_line(
+            w,
+            &[
+                ("thresh1", self.gc_thresh1().map(u64::from)),
+                ("thresh2", self.gc_thresh2().map(u64::from)),
+                ("thresh3", self.gc_thresh3().map(u64::from)),
+                ("gc_int", self.gc_interval().map(ms)),
+            ],
+        )?;
+
+        if let Some(parms) = self.parms() {
+            for line in parms_fields(parms) {
+                write_line(w, &line)?;
+            }
+        }
+
+        if opts.stats {
+            if let Some(c) = self.config() {
+                writeln!(
+                    w,
+                    "    config key_len {} entry_size {} entries {} last_flush {} last_rand {} \
+                     hash_rnd {} hash_mask {:08x} hash_chain_gc {} proxy_qlen {}",
+                    c.key_len,
+                    c.entry_size,
+                    c.entries,
+                    ms(c.last_flush),
+                    ms(c.last_rand),
+                    c.hash_rnd,
+                    c.hash_mask,
+                    c.hash_chain_gc,
+                    c.proxy_qlen
+                )?;
+            }
+            if let Some(s) = self.stats() {
+                writeln!(
+                    w,
+                    "    stats allocs {} destroys {} hash_grows {} res_failed {} lookups {} \
+                     hits {} rcv_probes_mcast {} rcv_probes_ucast {} periodic_gc_runs {} \
+                     forced_gc_runs {} table_fulls {}",
+                    s.allocs,
+                    s.destroys,
+                    s.hash_grows,
+                    s.res_failed,
+                    s.lookups,
+                    s.hits,
+                    s.rcv_probes_mcast,
+                    s.rcv_probes_ucast,
+                    s.periodic_gc_runs,
+                    s.forced_gc_runs,
+                    s.table_fulls
+                )?;
+            }
+        }
+
+        writeln!(w)?;
+
+        Ok(())
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut obj = serde_json::json!({
+            "family": family_name(self.family()),
+        });
+
+        if let Some(name) = self.name() {
+            obj["name"] = serde_json::json!(name);
+        }
+
+        if let Some(ifindex) = self.ifindex() {
+            obj["ifindex"] = serde_json::json!(ifindex);
+            obj["dev"] =
+                serde_json::json!(crate::netlink::ifindex_resolver::ifname_or_index(ifindex));
+        }
+
+        let fields = [
+            ("thresh1", self.gc_thresh1().map(u64::from)),
+            ("thresh2", self.gc_thresh2().map(u64::from)),
+            ("thresh3", self.gc_thresh3().map(u64::from)),
+            ("gc_interval", self.gc_interval().map(ms)),
+        ];
+        let parms = self.parms().map(parms_fields).unwrap_or_default();
+        for (name, value) in fields.into_iter().chain(parms.into_iter().flatten()) {
+            if let Some(value) = value {
+                obj[name] = serde_json::json!(value);
+            }
+        }
+
+        if let Some(c) = self.config() {
+            obj["config"] = serde_json::json!({
+                "key_len": c.key_len,
+                "entry_size": c.entry_size,
+                "entries": c.entries,
+                "last_flush": ms(c.last_flush),
+                "last_rand": ms(c.last_rand),
+                "hash_rnd": c.hash_rnd,
+                "hash_mask": c.hash_mask,
+                "hash_chain_gc": c.hash_chain_gc,
+                "proxy_qlen": c.proxy_qlen,
+            });
+        }
+
+        if let Some(s) = self.stats() {
+            obj["stats"] = serde_json::json!({
+                "allocs": s.allocs,
+                "destroys": s.destroys,
+                "hash_grows": s.hash_grows,
+                "res_failed": s.res_failed,
+                "lookups": s.lookups,
+                "hits": s.hits,
+                "rcv_probes_mcast": s.rcv_probes_mcast,
+                "rcv_probes_ucast": s.rcv_probes_ucast,
+                "periodic_gc_runs": s.periodic_gc_runs,
+                "forced_gc_runs": s.forced_gc_runs,
+                "table_fulls": s.table_fulls,
+            });
+        }
+
+        obj
+    }
+}
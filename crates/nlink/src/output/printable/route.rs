@@ -22,20 +22,19 @@ impl Printable for RouteMessage {
 
         // Device
         if let Some(oif) = self.oif {
-            let dev = crate::util::get_ifname_or_index(oif);
+            let dev = crate::netlink::ifindex_resolver::ifname_or_index(oif);
             write!(w, " dev {}", dev)?;
         }
 
         // Protocol
-        let protocol = self.protocol();
-        if protocol != RouteProtocol::Unspec {
-            write!(w, " proto {}", protocol.name())?;
+        if self.protocol() != RouteProtocol::Unspec {
+            write!(w, " proto {}", self.protocol())?;
         }
 
         // Scope
         let scope = self.scope();
         if scope != RouteScope::Universe {
-            write!(w, " scope {}", scope.name())?;
+            write!(w, " scope {}", scope)?;
         }
 
         // Preferred source
@@ -48,16 +47,54 @@ impl Printable for RouteMessage {
             write!(w, " metric {}", prio)?;
         }
 
+        // Route-get reply fields
+        if let Some(mark) = self.mark {
+            write!(w, " mark 0x{:x}", mark)?;
+        }
+        if let Some(uid) = self.uid {
+            write!(w, " uid {}", uid)?;
+        }
+
+        // IPv6 lifetime and RFC 4191 preference, as set by router
+        // advertisements
+        if !self.is_cloned()
+            && let Some(lifetime) = self.lifetime()
+        {
+            write!(w, " expires {}sec", lifetime.as_secs())?;
+        }
+        if let Some(pref) = self.pref {
+            write!(w, " pref {}", router_pref_name(pref))?;
+        }
+
         writeln!(w)?;
 
+        if self.is_cloned()
+            && let Some(ref cache) = self.cache_info
+        {
+            write!(w, "    cache")?;
+            if let Some(expires) = cache.expires {
+                write!(w, " expires {}sec", expires.as_secs())?;
+            }
+            if cache.error != 0 {
+                write!(w, " error {}", cache.error)?;
+            }
+            if cache.users != 0 {
+                write!(w, " users {}", cache.users)?;
+            }
+            if cache.used != 0 {
+                write!(w, " used {}", cache.used)?;
+            }
+            writeln!(w)?;
+        }
+
         Ok(())
     }
 
     fn to_json(&self) -> serde_json::Value {
         let mut obj = serde_json::json!({
-            "type": self.route_type().name(),
-            "protocol": self.protocol().name(),
-            "scope": self.scope().name(),
+            "type": self.route_type().to_string(),
+            "protocol": self.protocol().to_string(),
+            "scope": self.scope().to_string(),
             "table": crate::util::names::table_name(self.table_id()),
             "dst": self.destination_str(),
         });
@@ -67,7 +104,7 @@ impl Printable for RouteMessage {
         }
 
         if let Some(oif) = self.oif {
-            let dev = crate::util::get_ifname_or_index(oif);
+            let dev = crate::netlink::ifindex_resolver::ifname_or_index(oif);
             obj["dev"] = serde_json::json!(dev);
         }
 
@@ -79,6 +116,45 @@ impl Printable for RouteMessage {
             obj["metric"] = serde_json::json!(prio);
         }
 
+        if let Some(mark) = self.mark {
+            obj["mark"] = serde_json::json!(mark);
+        }
+
+        if let Some(uid) = self.uid {
+            obj["uid"] = serde_json::json!(uid);
+        }
+
+        if let Some(pref) = self.pref {
+            obj["pref"] = serde_json::json!(router_pref_name(pref));
+        }
+
+        if !self.is_cloned()
+            && let Some(lifetime) = self.lifetime()
+        {
+            obj["expires"] = serde_json::json!(lifetime.as_secs());
+        }
+
+        if self.is_cloned()
+            && let Some(ref cache) = self.cache_info
+        {
+            obj["cache"] = serde_json::json!({
+                "expires": cache.expires.map(|d| d.as_secs()),
+                "error": cache.error,
+                "users": cache.users,
+                "used": cache.used,
+            });
+        }
+
         obj
     }
 }
+
+/// Name of an RFC 4191 router preference (`RTA_PREF`).
+fn router_pref_name(pref: u8) -> &'static str {
+    match pref {
+        0 => "medium",
+        1 => "high",
+        3 => "low",
+        _ => "invalid",
+    }
+}
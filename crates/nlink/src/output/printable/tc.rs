@@ -9,7 +9,7 @@ use crate::{
 
 impl Printable for TcMessage {
     fn print_text<W: Write>(&self, w: &mut W, opts: &OutputOptions) -> std::io::Result<()> {
-        let dev = crate::util::get_ifname_or_index(self.ifindex());
+        let dev = crate::netlink::ifindex_resolver::ifname_or_index(self.ifindex());
 
         write!(
             w,
@@ -30,6 +30,10 @@ impl Printable for TcMessage {
 
         write!(w, "refcnt 2")?; // placeholder
 
+        if self.is_offloaded() {
+            write!(w, " offloaded")?;
+        }
+
         writeln!(w)?;
 
         if opts.stats {
@@ -42,6 +46,15 @@ impl Printable for TcMessage {
                 self.overlimits(),
                 self.requeues()
             )?;
+            if let Some(hw) = self.stats_basic_hw() {
+                writeln!(
+                    w,
+                    " Sent software {} bytes {} pkt",
+                    self.bytes().saturating_sub(hw.bytes),
+                    self.packets().saturating_sub(hw.packets)
+                )?;
+                writeln!(w, " Sent hardware {} bytes {} pkt", hw.bytes, hw.packets)?;
+            }
             writeln!(w, " backlog {}b {}p", self.backlog(), self.qlen())?;
         }
 
@@ -49,9 +62,9 @@ impl Printable for TcMessage {
     }
 
     fn to_json(&self) -> serde_json::Value {
-        let dev = crate::util::get_ifname_or_index(self.ifindex());
+        let dev = crate::netlink::ifindex_resolver::ifname_or_index(self.ifindex());
 
-        serde_json::json!({
+        let mut obj = serde_json::json!({
             "dev": dev,
             "kind": self.kind().unwrap_or(""),
             "handle": self.handle().to_string(),
@@ -63,6 +76,16 @@ impl Printable for TcMessage {
             "requeues": self.requeues(),
             "qlen": self.qlen(),
             "backlog": self.backlog(),
-        })
+        });
+
+        if self.is_offloaded() {
+            obj["offloaded"] = serde_json::json!(true);
+        }
+        if let Some(hw) = self.stats_basic_hw() {
+            obj["hw_bytes"] = serde_json::json!(hw.bytes);
+            obj["hw_packets"] = serde_json::json!(hw.packets);
+        }
+
+        obj
     }
 }
@@ -452,15 +452,6 @@ fn eval_state_mask(expr: &FilterExpr) -> Option<u32> {
     }
 }
 
-fn contains_state(expr: &FilterExpr) -> bool {
-    match expr {
-        FilterExpr::State(_) => true,
-        FilterExpr::And(a, b) | FilterExpr::Or(a, b) => contains_state(a) || contains_state(b),
-        FilterExpr::Not(inner) => contains_state(inner),
-        _ => false,
-    }
-}
-
 /// Flatten the top-level `and` spine into conjuncts.
 fn conjuncts(expr: &FilterExpr) -> Vec<&FilterExpr> {
     match expr {
@@ -497,7 +488,7 @@ pub fn compile_filter(expr: &FilterExpr) -> CompiledFilter {
     for c in conjuncts(expr) {
         if let Some(mask) = eval_state_mask(c) {
             states = Some(states.unwrap_or(u32::MAX) & mask);
-        } else if !contains_state(c) {
+        } else if !c.has_state() {
             bc_parts.push(c);
         } else {
             // Mixed state/non-state below an or/not: client-side only.
@@ -959,6 +950,25 @@ mod tests {
         assert!(c.exact);
     }
 
+    #[test]
+    fn state_groups_and_exclude_hoist_to_mask() {
+        let bucket = TcpState::SynRecv.mask() | TcpState::TimeWait.mask();
+        let c = compile_filter(&FilterExpr::parse("state bucket").unwrap());
+        assert_eq!(c.states, Some(bucket));
+        assert!(c.bytecode.is_none());
+        assert!(c.exact);
+
+        let c = compile_filter(&FilterExpr::parse("exclude bucket").unwrap());
+        assert_eq!(c.states, Some(TcpState::all_mask() & !bucket));
+
+        let c = compile_filter(&FilterExpr::parse("state connected exclude syn-sent").unwrap());
+        assert_eq!(
+            c.states,
+            Some(TcpState::connected_mask() & !TcpState::SynSent.mask())
+        );
+        assert!(c.exact);
+    }
+
     #[test]
     fn state_and_port_split_between_mask_and_bytecode() {
         let c = compile_filter(&FilterExpr::parse("state established and sport = :22").unwrap());
@@ -79,7 +79,10 @@ impl FilterExpr {
     /// - `sport > :1024` — port range comparison
     /// - `src 192.168.0.0/16` — source address/prefix match
     /// - `dst 10.0.0.1` — destination address match
-    /// - `state established` — state match
+    /// - `state established` — state match; `state` also takes the ss(8)
+    ///   groups `all`, `connected`, `synchronized`, `bucket` and `big`
+    /// - `exclude time-wait` — every state but these; consecutive
+    ///   `state`/`exclude` clauses combine as in ss(8)
     /// - `expr and expr` — logical AND
     /// - `expr or expr` — logical OR
     /// - `not expr` — logical NOT
@@ -105,6 +108,18 @@ impl FilterExpr {
         Ok(expr)
     }
 
+    /// Whether any `state` / `exclude` predicate appears in the
+    /// expression. ss(8) lets such a filter replace its default
+    /// connected-only state set.
+    pub fn has_state(&self) -> bool {
+        match self {
+            Self::State(_) => true,
+            Self::And(a, b) | Self::Or(a, b) => a.has_state() || b.has_state(),
+            Self::Not(inner) => inner.has_state(),
+            _ => false,
+        }
+    }
+
     /// Evaluate this expression against a socket.
     ///
     /// Every predicate in the grammar (`sport`/`dport`/`src`/`dst`/
@@ -262,17 +277,92 @@ fn parse_primary(input: &mut &str) -> PResult<FilterExpr> {
         return Ok(FilterExpr::Dst(addr, prefix));
     }
 
-    // state match
-    if input.starts_with("state") {
-        let _ = "state".parse_next(input)?;
-        let _ = multispace1.parse_next(input)?;
-        let state = parse_state(input)?;
-        return Ok(FilterExpr::State(state));
+    // state / exclude clauses
+    if input.starts_with("state") || input.starts_with("exclude") {
+        return parse_state_clauses(input);
     }
 
     Err(ErrMode::Cut(ContextError::new()))
 }
 
+/// Parse a run of `state X` / `exclude X` clauses the way ss(8) does:
+/// the `state` sets accumulate, `exclude` removes from them (or from
+/// every state if no `state` clause was given). A predicate written
+/// straight after the run is ANDed in, so the common
+/// `state established '( dport = :ssh )'` works without an `and`.
+fn parse_state_clauses(input: &mut &str) -> PResult<FilterExpr> {
+    let mut include: Option<FilterExpr> = None;
+    let mut exclude: Option<FilterExpr> = None;
+    loop {
+        let _ = multispace0.parse_next(input)?;
+        let target = if input.starts_with("state ") || input.starts_with("state\t") {
+            let _ = "state".parse_next(input)?;
+            &mut include
+        } else if input.starts_with("exclude ") || input.starts_with("exclude\t") {
+            let _ = "exclude".parse_next(input)?;
+            &mut exclude
+        } else {
+            break;
+        };
+        let _ = multispace1.parse_next(input)?;
+        let set = parse_state_set(input)?;
+        *target = Some(match target.take() {
+            Some(prev) => FilterExpr::Or(Box::new(prev), Box::new(set)),
+            None => set,
+        });
+    }
+
+    let mut expr = match (include, exclude) {
+        (Some(inc), Some(exc)) => {
+            FilterExpr::And(Box::new(inc), Box::new(FilterExpr::Not(Box::new(exc))))
+        }
+        (Some(inc), None) => inc,
+        (None, Some(exc)) => FilterExpr::Not(Box::new(exc)),
+        (None, None) => return Err(ErrMode::Cut(ContextError::new())),
+    };
+
+    let _ = multispace0.parse_next(input)?;
+    let juxtaposed = ["(", "!", "not", "sport", "dport", "src", "dst"]
+        .iter()
+        .any(|kw| input.starts_with(kw));
+    if juxtaposed {
+        let rest = parse_unary_expr(input)?;
+        expr = FilterExpr::And(Box::new(expr), Box::new(rest));
+    }
+    Ok(expr)
+}
+
+/// Parse one state name or ss(8) state group into an `or` of states.
+fn parse_state_set(input: &mut &str) -> PResult<FilterExpr> {
+    let word: &str = take_while(1.., |c: char| {
+        c.is_ascii_alphanumeric() || c == '-' || c == '_'
+    })
+    .parse_next(input)?;
+    let bucket = TcpState::SynRecv.mask() | TcpState::TimeWait.mask();
+    let mask = match word.to_ascii_lowercase().as_str() {
+        "all" => TcpState::all_mask(),
+        "connected" => TcpState::connected_mask(),
+        "synchronized" => TcpState::connected_mask() & !TcpState::SynSent.mask(),
+        "bucket" => bucket,
+        "big" => TcpState::all_mask() & !bucket,
+        name => {
+            let state = state_by_name(name).ok_or(ErrMode::Cut(ContextError::new()))?;
+            return Ok(FilterExpr::State(state));
+        }
+    };
+    Ok(states_expr(mask))
+}
+
+/// An `or` of one `State` leaf per bit in `mask`.
+fn states_expr(mask: u32) -> FilterExpr {
+    (0..=13u8)
+        .map(TcpState::from_u8)
+        .filter(|s| mask & s.mask() != 0)
+        .map(|s| FilterExpr::State(SocketState::Tcp(s)))
+        .reduce(|a, b| FilterExpr::Or(Box::new(a), Box::new(b)))
+        .expect("state group is non-empty")
+}
+
 fn parse_port_expr(input: &mut &str) -> PResult<FilterExpr> {
     let is_sport = input.starts_with("sport");
     if is_sport {
@@ -371,24 +461,20 @@ fn parse_ip_prefix(input: &mut &str) -> PResult<(IpAddr, u8)> {
     Ok((addr, prefix))
 }
 
-fn parse_state(input: &mut &str) -> PResult<SocketState> {
-    let word: &str = take_while(1.., |c: char| {
-        c.is_ascii_alphanumeric() || c == '-' || c == '_'
-    })
-    .parse_next(input)?;
-    match word.to_ascii_lowercase().as_str() {
-        "established" | "estab" => Ok(SocketState::Tcp(TcpState::Established)),
-        "syn-sent" | "syn_sent" => Ok(SocketState::Tcp(TcpState::SynSent)),
-        "syn-recv" | "syn_recv" => Ok(SocketState::Tcp(TcpState::SynRecv)),
-        "fin-wait-1" | "fin_wait_1" | "fin-wait1" => Ok(SocketState::Tcp(TcpState::FinWait1)),
-        "fin-wait-2" | "fin_wait_2" | "fin-wait2" => Ok(SocketState::Tcp(TcpState::FinWait2)),
-        "time-wait" | "time_wait" | "timewait" => Ok(SocketState::Tcp(TcpState::TimeWait)),
-        "close" | "closed" => Ok(SocketState::Tcp(TcpState::Close)),
-        "close-wait" | "close_wait" => Ok(SocketState::Tcp(TcpState::CloseWait)),
-        "last-ack" | "last_ack" => Ok(SocketState::Tcp(TcpState::LastAck)),
-        "listening" | "listen" => Ok(SocketState::Tcp(TcpState::Listen)),
-        "closing" => Ok(SocketState::Tcp(TcpState::Closing)),
-        _ => Err(ErrMode::Cut(ContextError::new())),
+fn state_by_name(name: &str) -> Option<SocketState> {
+    match name {
+        "established" | "estab" => Some(SocketState::Tcp(TcpState::Established)),
+        "syn-sent" | "syn_sent" => Some(SocketState::Tcp(TcpState::SynSent)),
+        "syn-recv" | "syn_recv" => Some(SocketState::Tcp(TcpState::SynRecv)),
+        "fin-wait-1" | "fin_wait_1" | "fin-wait1" => Some(SocketState::Tcp(TcpState::FinWait1)),
+        "fin-wait-2" | "fin_wait_2" | "fin-wait2" => Some(SocketState::Tcp(TcpState::FinWait2)),
+        "time-wait" | "time_wait" | "timewait" => Some(SocketState::Tcp(TcpState::TimeWait)),
+        "close" | "closed" => Some(SocketState::Tcp(TcpState::Close)),
+        "close-wait" | "close_wait" => Some(SocketState::Tcp(TcpState::CloseWait)),
+        "last-ack" | "last_ack" => Some(SocketState::Tcp(TcpState::LastAck)),
+        "listening" | "listen" => Some(SocketState::Tcp(TcpState::Listen)),
+        "closing" => Some(SocketState::Tcp(TcpState::Closing)),
+        _ => None,
     }
 }
 
@@ -488,6 +574,84 @@ mod tests {
         assert!(FilterExpr::parse("state close-wait").is_ok());
     }
 
+    #[test]
+    fn parse_state_groups() {
+        let estab = make_socket(
+            "10.0.0.1:22",
+            "10.0.0.2:5000",
+            tcp_state(TcpState::Established),
+        );
+        let syn_sent = make_socket("10.0.0.1:5000", "10.0.0.2:22", tcp_state(TcpState::SynSent));
+        let time_wait = make_socket(
+            "10.0.0.1:22",
+            "10.0.0.2:5001",
+            tcp_state(TcpState::TimeWait),
+        );
+        let listen = make_socket("0.0.0.0:22", "0.0.0.0:0", tcp_state(TcpState::Listen));
+
+        let bucket = FilterExpr::parse("state bucket").unwrap();
+        assert!(bucket.matches(&time_wait));
+        assert!(!bucket.matches(&estab));
+
+        let big = FilterExpr::parse("state big").unwrap();
+        assert!(big.matches(&estab) && big.matches(&listen));
+        assert!(!big.matches(&time_wait));
+
+        let connected = FilterExpr::parse("state connected").unwrap();
+        assert!(connected.matches(&estab) && connected.matches(&syn_sent));
+        assert!(!connected.matches(&listen) && !connected.matches(&time_wait));
+
+        let synchronized = FilterExpr::parse("state synchronized").unwrap();
+        assert!(synchronized.matches(&estab));
+        assert!(!synchronized.matches(&syn_sent));
+
+        let all = FilterExpr::parse("state all").unwrap();
+        assert!(
+            [&estab, &syn_sent, &time_wait, &listen]
+                .iter()
+                .all(|s| all.matches(s))
+        );
+
+        assert!(FilterExpr::parse("state nonsense").is_err());
+    }
+
+    #[test]
+    fn parse_state_clause_runs() {
+        let estab = make_socket(
+            "10.0.0.1:22",
+            "10.0.0.2:5000",
+            tcp_state(TcpState::Established),
+        );
+        let time_wait = make_socket(
+            "10.0.0.1:22",
+            "10.0.0.2:5001",
+            tcp_state(TcpState::TimeWait),
+        );
+        let listen = make_socket("0.0.0.0:22", "0.0.0.0:0", tcp_state(TcpState::Listen));
+
+        // Repeated `state` clauses accumulate.
+        let expr = FilterExpr::parse("state established state time-wait").unwrap();
+        assert!(expr.matches(&estab) && expr.matches(&time_wait));
+        assert!(!expr.matches(&listen));
+
+        // `exclude` alone means every other state.
+        let expr = FilterExpr::parse("exclude listening").unwrap();
+        assert!(expr.matches(&estab) && expr.matches(&time_wait));
+        assert!(!expr.matches(&listen));
+
+        // ... and narrows a preceding `state`.
+        let expr = FilterExpr::parse("state all exclude bucket").unwrap();
+        assert!(expr.matches(&estab) && expr.matches(&listen));
+        assert!(!expr.matches(&time_wait));
+
+        // A predicate straight after the run is ANDed in.
+        let expr =
+            FilterExpr::parse("state established ( dport = :5000 or dport = :6000 )").unwrap();
+        assert!(expr.matches(&estab));
+        let expr = FilterExpr::parse("state established dport = :6000").unwrap();
+        assert!(!expr.matches(&estab));
+    }
+
     #[test]
     fn parse_dst_prefix() {
         let expr = FilterExpr::parse("dst 192.168.0.0/16").unwrap();
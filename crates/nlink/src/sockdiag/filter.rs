@@ -327,7 +327,12 @@ impl Default for UnixFilter {
         Self {
             socket_types: 0xFFFFFFFF,
             states: TcpState::all_mask(),
-            show: UnixShow::combine(&[UnixShow::Name, UnixShow::Peer, UnixShow::RqLen]),
+            show: UnixShow::combine(&[
+                UnixShow::Name,
+                UnixShow::Vfs,
+                UnixShow::Peer,
+                UnixShow::RqLen,
+            ]),
             inode: None,
             path_pattern: None,
         }
@@ -475,14 +480,18 @@ pub struct NetlinkFilter {
     pub show_meminfo: bool,
     /// Show groups.
     pub show_groups: bool,
+    /// Show socket flags (`NETLINK_DIAG_FLAGS`).
+    pub show_flags: bool,
 }
 
 impl Default for NetlinkFilter {
     fn default() -> Self {
+        // Meminfo carries the queue sizes ss(8) shows, so ask for it too.
         Self {
             protocol: None,
-            show_meminfo: false,
+            show_meminfo: true,
             show_groups: true,
+            show_flags: true,
         }
     }
 }
@@ -519,6 +528,12 @@ impl NetlinkFilterBuilder {
         self
     }
 
+    /// Show socket flags.
+    pub fn show_flags(mut self) -> Self {
+        self.filter.show_flags = true;
+        self
+    }
+
     /// Build the filter.
     pub fn build(self) -> SocketFilter {
         SocketFilter {
@@ -59,6 +59,7 @@
 //! - [`UnixSocket`] - Unix domain sockets
 //! - [`NetlinkSocket`] - Netlink protocol sockets
 //! - [`PacketSocket`] - Raw packet sockets
+//! - [`SmcSocket`] - SMC-R / SMC-D sockets (including TCP fallbacks)
 //!
 //! Each socket type has its own query method and response structure.
 //!
@@ -86,17 +87,25 @@ pub mod expr;
 pub mod filter;
 pub mod procmap;
 pub mod rate;
+pub mod shard;
 pub mod socket;
 pub mod types;
+pub mod usage;
 
 pub use bytecode::{CompiledFilter, compile_filter};
 pub use error::{Error, Result};
 pub use expr::{Comparison, FilterExpr};
 pub use filter::{InetFilter, SocketFilter, UnixFilter};
-pub use procmap::{CgroupPathMap, ProcessRef, SocketOwnerMap};
+pub use procmap::{CgroupPathMap, NetlinkPortMap, ProcessRef, SocketOwnerMap};
 pub use rate::{SocketRate, SocketRateTracker};
-pub use socket::{InetSocket, NetlinkSocket, PacketSocket, SocketInfo, UnixSocket};
+pub use shard::{port_shards, query_inet_sharded};
+pub use socket::{
+    InetSocket, NetlinkSocket, PacketSocket, SmcFallback, SmcMode, SmcSocket, SmcdDmbInfo,
+    SmcrLinkInfo, SocketInfo, UnixPeers, UnixSocket,
+};
 pub use types::{
-    AddressFamily, BbrInfo, CcInfo, DctcpInfo, DestroyError, DestroyResult, InetExtension,
-    Protocol, SocketState, SocketSummary, TcpInfo, TcpState, TcpSummary, UnixShow, VegasInfo,
+    AddressFamily, BbrInfo, CcInfo, DctcpInfo, DestroyError, DestroyResult, DestroyTarget,
+    InetExtension, Protocol, SocketMemory, SocketState, SocketSummary, SocketUsage, TcpInfo,
+    TcpState, TcpSummary, UnixShow, VegasInfo,
 };
+pub use usage::socket_usage_in;
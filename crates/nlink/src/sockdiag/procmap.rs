@@ -8,6 +8,9 @@
 //! [`InetSocket::inode`](super::InetSocket) (or any other socket
 //! type's inode) against it.
 //!
+//! [`NetlinkPortMap`] applies the same join to netlink sockets, keyed
+//! by `(protocol, port ID)`.
+//!
 //! [`CgroupPathMap`] is the companion join for
 //! [`InetSocket::cgroup_id`](super::InetSocket): it maps the kernel's
 //! cgroup v2 ID (the cgroup directory's inode on the unified
@@ -72,6 +75,8 @@ use std::{
     path::{Path, PathBuf},
 };
 
+use super::NetlinkSocket;
+
 /// One process holding a socket open, identified stably.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ProcessRef {
@@ -154,9 +159,8 @@ impl SocketOwnerMap {
                     .and_then(|s| s.parse::<i32>().ok())
                     .unwrap_or(-1);
 
-                let (comm, start_time) = identity.get_or_insert_with(|| {
-                    (read_comm(&pid_dir), read_start_time(&pid_dir))
-                });
+                let (comm, start_time) = identity
+                    .get_or_insert_with(|| (read_comm(&pid_dir), read_start_time(&pid_dir)));
                 map.entry(inode).or_default().push(ProcessRef {
                     pid,
                     start_time: *start_time,
@@ -199,6 +203,54 @@ impl SocketOwnerMap {
     }
 }
 
+/// Netlink `(protocol, port ID)` → owning-processes map.
+///
+/// Netlink messages name their sender by port ID (`nlmsg_pid`), and a
+/// socket dump reports ports, not processes. Joining a
+/// [`NetlinkSocket`] dump with a [`SocketOwnerMap`] by inode answers
+/// "who sent this?" and "who is this listener?". A process's first
+/// socket usually gets its PID as port ID, but later ones and
+/// explicitly bound ports do not, so a port ID alone is no PID. Port 0
+/// is the kernel and resolves to no process.
+#[derive(Debug, Default)]
+pub struct NetlinkPortMap {
+    map: HashMap<(u8, u32), Vec<ProcessRef>>,
+}
+
+impl NetlinkPortMap {
+    /// Build the map from a netlink socket dump and an owner scan.
+    pub fn new(sockets: &[NetlinkSocket], owners: &SocketOwnerMap) -> Self {
+        let map = sockets
+            .iter()
+            .filter(|s| !s.is_kernel())
+            .filter_map(|s| {
+                let procs = owners.resolve(s.inode);
+                (!procs.is_empty()).then(|| ((s.protocol, s.portid), procs.to_vec()))
+            })
+            .collect();
+        Self { map }
+    }
+
+    /// Processes holding the socket bound to `portid` on `protocol`
+    /// (e.g. `NETLINK_ROUTE`). Empty when unknown.
+    pub fn resolve(&self, protocol: u8, portid: u32) -> &[ProcessRef] {
+        self.map
+            .get(&(protocol, portid))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    /// Number of attributed ports.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// `true` if no port was attributed.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
 /// cgroup-v2 ID → cgroup path map (one `/sys/fs/cgroup` walk).
 ///
 /// On the unified (v2) hierarchy the kernel's `cgroup_id` — what
@@ -400,11 +452,51 @@ mod tests {
         let map = SocketOwnerMap::scan();
         let me = std::process::id() as i32;
         let owners = map.resolve(inode);
-        let mine = owners.iter().find(|p| p.pid == me).expect("own socket attributed");
-        assert!(mine.start_time > 0, "start_time populated from /proc/self/stat");
+        let mine = owners
+            .iter()
+            .find(|p| p.pid == me)
+            .expect("own socket attributed");
+        assert!(
+            mine.start_time > 0,
+            "start_time populated from /proc/self/stat"
+        );
         assert_ne!(mine.comm, "?");
     }
 
+    #[test]
+    fn netlink_ports_resolve_through_inodes() {
+        let nl = |portid, inode| NetlinkSocket {
+            protocol: 0,
+            portid,
+            dst_portid: 0,
+            dst_group: 0,
+            groups: 0,
+            memberships: Vec::new(),
+            flags: None,
+            inode,
+            cookie: 0,
+            recv_q: None,
+            send_q: None,
+            mem_info: None,
+        };
+        let mut owners = SocketOwnerMap::new();
+        let proc = |pid| ProcessRef {
+            pid,
+            start_time: 1,
+            comm: "nld".into(),
+            fd: 3,
+        };
+        owners.insert(10, proc(0));
+        owners.insert(11, proc(500));
+
+        // Port 0 is the kernel, whatever the inode says.
+        let ports = NetlinkPortMap::new(&[nl(0, 10), nl(4000, 11), nl(4001, 12)], &owners);
+        assert_eq!(ports.len(), 1);
+        assert_eq!(ports.resolve(0, 4000)[0].pid, 500);
+        assert!(ports.resolve(16, 4000).is_empty());
+        assert!(ports.resolve(0, 0).is_empty());
+    }
+
     #[test]
     fn cgroup_map_inverts_inodes() {
         let root = std::env::temp_dir().join(format!(
@@ -0,0 +1,155 @@
+//! Sharded inet dumps.
+//!
+//! A single `inet_diag` dump walks the kernel's socket hash tables on
+//! one CPU and funnels every match through one netlink socket. On hosts
+//! with millions of sockets that serialises the bytecode filter, the
+//! copy to userspace and the parse. [`query_inet_sharded`] splits the
+//! local port space into ranges, dumps each range on its own connection
+//! and task, and merges the results.
+//!
+//! The request has no way to ask for a range of hash buckets, so shards
+//! are port ranges: each one is a kernel-side `sport >= lo and sport <=
+//! hi` filter, and together they partition the sockets, so nothing is
+//! dumped twice.
+
+use std::ops::RangeInclusive;
+
+use crate::netlink::{Connection, Result, SockDiag};
+
+use super::{
+    expr::{Comparison, FilterExpr},
+    filter::InetFilter,
+    socket::InetSocket,
+};
+
+/// Split the local port space into `shards` contiguous ranges of nearly
+/// equal size. `0` is treated as `1`; more than 65536 is capped.
+///
+/// ```
+/// let ranges = nlink::sockdiag::port_shards(4);
+/// assert_eq!(ranges[0], 0..=16383);
+/// assert_eq!(ranges[3], 49152..=65535);
+/// ```
+pub fn port_shards(shards: usize) -> Vec<RangeInclusive<u16>> {
+    const PORTS: usize = 1 << 16;
+    let shards = shards.clamp(1, PORTS);
+    (0..shards)
+        .map(|i| {
+            let lo = i * PORTS / shards;
+            let hi = (i + 1) * PORTS / shards - 1;
+            lo as u16..=hi as u16
+        })
+        .collect()
+}
+
+/// Run an inet dump as `shards` parallel dumps over local port ranges
+/// (see [`port_shards`]) and merge the results.
+///
+/// Each shard opens its own `SOCK_DIAG` connection in the current
+/// namespace and runs on its own task, so the dumps proceed in parallel
+/// on a multi-threaded runtime. `filter` applies to every shard as
+/// usual. Results come back grouped by shard, in port order; the first
+/// shard to fail fails the whole query. With `shards <= 1` this is a
+/// plain dump.
+///
+/// Sharding pays off only for very large tables: every shard still
+/// visits every socket kernel-side, it just hands most of them to the
+/// port filter instead of to userspace.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::sockdiag::{InetFilter, query_inet_sharded};
+///
+/// let sockets = query_inet_sharded(&InetFilter::default(), 8).await?;
+/// println!("{} TCP sockets", sockets.len());
+/// ```
+pub async fn query_inet_sharded(filter: &InetFilter, shards: usize) -> Result<Vec<InetSocket>> {
+    let ranges = port_shards(shards);
+    if ranges.len() == 1 {
+        return Connection::<SockDiag>::new()?
+            .query_inet_typed(filter)
+            .await;
+    }
+
+    let mut handles = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        let filter = shard_filter(filter, range);
+        let conn = Connection::<SockDiag>::new()?;
+        handles.push(tokio::spawn(
+            async move { conn.query_inet_typed(&filter).await },
+        ));
+    }
+
+    let mut sockets = Vec::new();
+    for handle in handles {
+        match handle.await {
+            Ok(shard) => sockets.extend(shard?),
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+    Ok(sockets)
+}
+
+/// `filter` narrowed to sockets whose local port is in `range`.
+fn shard_filter(filter: &InetFilter, range: RangeInclusive<u16>) -> InetFilter {
+    let ports = FilterExpr::And(
+        Box::new(FilterExpr::Sport(Comparison::Ge, *range.start())),
+        Box::new(FilterExpr::Sport(Comparison::Le, *range.end())),
+    );
+    let expr = match &filter.expr {
+        Some(expr) => FilterExpr::And(Box::new(expr.clone()), Box::new(ports)),
+        None => ports,
+    };
+    InetFilter {
+        expr: Some(expr),
+        ..filter.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sockdiag::types::{AddressFamily, Protocol, TcpState};
+
+    #[test]
+    fn port_shards_partition_the_port_space() {
+        for n in [1, 2, 3, 7, 64, 65536] {
+            let ranges = port_shards(n);
+            assert_eq!(ranges.len(), n);
+            assert_eq!(*ranges[0].start(), 0);
+            assert_eq!(*ranges[n - 1].end(), u16::MAX);
+            for pair in ranges.windows(2) {
+                assert_eq!(*pair[0].end() as u32 + 1, *pair[1].start() as u32);
+            }
+        }
+        assert_eq!(port_shards(0), vec![0..=u16::MAX]);
+        assert_eq!(port_shards(1 << 20).len(), 1 << 16);
+    }
+
+    #[test]
+    fn shard_filter_ands_the_port_range_onto_the_expression() {
+        let mut filter = InetFilter::default();
+        let sock = |port: u16| {
+            InetSocket::new(
+                AddressFamily::Inet,
+                Protocol::Tcp,
+                TcpState::Established,
+                format!("10.0.0.1:{port}").parse().unwrap(),
+                "10.0.0.2:443".parse().unwrap(),
+            )
+        };
+
+        let expr = shard_filter(&filter, 1000..=1999).expr.unwrap();
+        assert!(expr.matches(&sock(1000)));
+        assert!(expr.matches(&sock(1999)));
+        assert!(!expr.matches(&sock(2000)));
+
+        filter.expr = Some(FilterExpr::parse("dport = :80").unwrap());
+        let expr = shard_filter(&filter, 1000..=1999).expr.unwrap();
+        assert!(
+            !expr.matches(&sock(1500)),
+            "the caller's expression still applies"
+        );
+    }
+}
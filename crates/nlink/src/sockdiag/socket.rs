@@ -3,7 +3,10 @@
 //! This module provides strongly-typed representations of socket information
 //! returned by the kernel's SOCK_DIAG interface.
 
-use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
 
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +25,8 @@ pub enum SocketInfo {
     Netlink(NetlinkSocket),
     /// Packet (raw) socket.
     Packet(PacketSocket),
+    /// SMC (Shared Memory Communications) socket.
+    Smc(SmcSocket),
 }
 
 impl SocketInfo {
@@ -32,6 +37,7 @@ impl SocketInfo {
             SocketInfo::Unix(s) => s.state,
             SocketInfo::Netlink(_) => SocketState::Close,
             SocketInfo::Packet(_) => SocketState::Close,
+            SocketInfo::Smc(s) => s.socket_state(),
         }
     }
 
@@ -42,6 +48,7 @@ impl SocketInfo {
             SocketInfo::Unix(s) => s.inode,
             SocketInfo::Netlink(s) => s.inode,
             SocketInfo::Packet(s) => s.inode,
+            SocketInfo::Smc(s) => s.inode,
         }
     }
 
@@ -52,6 +59,7 @@ impl SocketInfo {
             SocketInfo::Unix(s) => s.uid,
             SocketInfo::Netlink(s) => Some(s.portid),
             SocketInfo::Packet(s) => Some(s.uid),
+            SocketInfo::Smc(s) => Some(s.uid),
         }
     }
 
@@ -163,6 +171,17 @@ impl InetSocket {
         matches!(self.state, SocketState::Tcp(TcpState::Established))
     }
 
+    /// Check if this is an orphaned TCP socket: closed by its owner (no
+    /// inode) while still finishing the shutdown handshake.
+    pub fn is_orphaned(&self) -> bool {
+        match self.state {
+            SocketState::Tcp(state) => {
+                self.inode == 0 && state.mask() & TcpState::orphan_mask() != 0
+            }
+            _ => false,
+        }
+    }
+
     /// Get the netid string for output.
     pub fn netid(&self) -> &'static str {
         match (self.protocol, self.family) {
@@ -300,6 +319,55 @@ impl UnixSocket {
     pub fn netid(&self) -> &'static str {
         self.socket_type.netid()
     }
+
+    /// The `(device, inode)` of the filesystem node a path-bound socket
+    /// is bound to (`UNIX_DIAG_VFS`), if requested and bound.
+    pub fn vfs(&self) -> Option<(u32, u32)> {
+        self.vfs_dev.zip(self.vfs_inode)
+    }
+}
+
+/// Unix sockets indexed by inode, for resolving each socket's
+/// [`peer_inode`](UnixSocket::peer_inode) to the socket at the other end.
+///
+/// The kernel reports only the peer's inode; its name, queues and owner
+/// come from the peer's own entry in the same dump. Peers filtered out
+/// of the dump, or living in another network namespace, don't resolve.
+///
+/// # Example
+///
+/// ```ignore
+/// let sockets = conn.query_unix_sockets().await?;
+/// let peers = UnixPeers::new(&sockets);
+/// for sock in &sockets {
+///     if let Some(peer) = peers.peer(sock) {
+///         println!("{} <-> {}", sock.inode, peer.name());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UnixPeers<'a> {
+    by_inode: HashMap<u32, &'a UnixSocket>,
+}
+
+impl<'a> UnixPeers<'a> {
+    /// Index `sockets` by inode.
+    pub fn new(sockets: impl IntoIterator<Item = &'a UnixSocket>) -> Self {
+        Self {
+            by_inode: sockets.into_iter().map(|s| (s.inode, s)).collect(),
+        }
+    }
+
+    /// The socket with this inode, if it was indexed.
+    pub fn get(&self, inode: u32) -> Option<&'a UnixSocket> {
+        self.by_inode.get(&inode).copied()
+    }
+
+    /// The socket connected to `sock`, if `sock` has a peer and the peer
+    /// was indexed.
+    pub fn peer(&self, sock: &UnixSocket) -> Option<&'a UnixSocket> {
+        sock.peer_inode.and_then(|ino| self.get(ino))
+    }
 }
 
 /// Netlink socket information.
@@ -313,21 +381,76 @@ pub struct NetlinkSocket {
     pub dst_portid: u32,
     /// Destination group.
     pub dst_group: u32,
-    /// Subscribed groups bitmask.
+    /// Subscribed groups bitmask (groups 1–32 only; see
+    /// [`memberships`](Self::memberships)).
     pub groups: u32,
+    /// Every multicast group joined, as 1-based group numbers. Unlike
+    /// `groups` this includes groups above 32, such as most rtnetlink
+    /// groups added since `RTNLGRP_NSID`.
+    pub memberships: Vec<u32>,
+    /// `NDIAG_FLAG_*` socket flags (`NETLINK_DIAG_FLAGS`, Linux 4.2+).
+    pub flags: Option<u32>,
     /// Inode number.
     pub inode: u32,
     /// Socket cookie.
     pub cookie: u64,
-    /// Receive queue size.
+    /// Bytes of messages queued for the owner to read (`sk_rmem_alloc`).
+    /// Requires meminfo.
     pub recv_q: Option<u32>,
-    /// Send queue size.
+    /// Bytes queued for sending (`sk_wmem_alloc`). Requires meminfo.
     pub send_q: Option<u32>,
     /// Memory information.
     pub mem_info: Option<MemInfo>,
 }
 
+/// `NDIAG_FLAG_*` bits of [`NetlinkSocket::flags`].
+pub mod netlink_flags {
+    /// A dump is in progress on the socket.
+    pub const CB_RUNNING: u32 = 0x01;
+    /// `NETLINK_PKTINFO` is set.
+    pub const PKTINFO: u32 = 0x02;
+    /// `NETLINK_BROADCAST_ERROR` is set.
+    pub const BROADCAST_ERROR: u32 = 0x04;
+    /// `NETLINK_NO_ENOBUFS` is set: overruns drop messages silently.
+    pub const NO_ENOBUFS: u32 = 0x08;
+    /// `NETLINK_LISTEN_ALL_NSID` is set.
+    pub const LISTEN_ALL_NSID: u32 = 0x10;
+    /// `NETLINK_CAP_ACK` is set.
+    pub const CAP_ACK: u32 = 0x20;
+}
+
 impl NetlinkSocket {
+    /// Whether this is the kernel's own socket for the protocol
+    /// (port ID 0).
+    pub fn is_kernel(&self) -> bool {
+        self.portid == 0
+    }
+
+    /// Whether the socket has joined multicast `group`.
+    pub fn is_member(&self, group: u32) -> bool {
+        self.memberships.contains(&group)
+    }
+
+    /// Whether the [`netlink_flags`] bit `flag` is reported set.
+    pub fn has_flag(&self, flag: u32) -> bool {
+        self.flags.is_some_and(|f| f & flag != 0)
+    }
+
+    /// Messages dropped because the receive queue was full — each one an
+    /// `ENOBUFS` overrun, and lost events for a multicast listener.
+    /// Requires meminfo.
+    pub fn drops(&self) -> Option<u32> {
+        self.mem_info.as_ref().and_then(|m| m.drops)
+    }
+
+    /// How full the receive buffer is, from 0.0 to (briefly past) 1.0.
+    /// Requires meminfo.
+    pub fn rcvbuf_usage(&self) -> Option<f64> {
+        let mem = self.mem_info.as_ref()?;
+        let rcvbuf = mem.rcvbuf.filter(|&b| b > 0)?;
+        Some(f64::from(mem.rmem_alloc) / f64::from(rcvbuf))
+    }
+
     /// Get the protocol name.
     pub fn protocol_name(&self) -> &'static str {
         match self.protocol {
@@ -412,6 +535,159 @@ impl PacketSocket {
     }
 }
 
+/// How an SMC connection carries its data (`SMC_DIAG_MODE_*`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SmcMode {
+    /// SMC-R — RDMA over RoCE.
+    SmcR,
+    /// Negotiation failed; the connection runs over plain TCP.
+    FallbackTcp,
+    /// SMC-D — ISM (internal shared memory) device.
+    SmcD,
+    /// A mode this crate does not know about.
+    Unknown(u8),
+}
+
+impl SmcMode {
+    /// Parse from the raw `diag_mode` byte.
+    pub fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::SmcR,
+            1 => Self::FallbackTcp,
+            2 => Self::SmcD,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// Get the mode name as printed by `smcss`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SmcR => "SMCR",
+            Self::FallbackTcp => "TCP",
+            Self::SmcD => "SMCD",
+            Self::Unknown(_) => "unknown",
+        }
+    }
+}
+
+/// Why a connection fell back to TCP (`struct smc_diag_fallback`).
+///
+/// Both fields are `SMC_CLC_DECL_*` codes; `peer_diagnosis` is the
+/// code the peer sent in its decline message (0 if none).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmcFallback {
+    /// Local fallback reason.
+    pub reason: u32,
+    /// Peer's decline reason.
+    pub peer_diagnosis: u32,
+}
+
+/// SMC-R link details (`struct smc_diag_lgrinfo`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmcrLinkInfo {
+    /// Link id within the link group.
+    pub link_id: u8,
+    /// RDMA device name.
+    pub ib_device: String,
+    /// RDMA device port.
+    pub ib_port: u8,
+    /// Local GID.
+    pub gid: String,
+    /// Peer GID.
+    pub peer_gid: String,
+    /// Link-group role (0 = client, 1 = server).
+    pub role: u8,
+}
+
+/// SMC-D DMB details (`struct smcd_diag_dmbinfo`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SmcdDmbInfo {
+    /// Link id.
+    pub link_id: u32,
+    /// Peer GID.
+    pub peer_gid: u64,
+    /// Local GID.
+    pub gid: u64,
+    /// Token of the local DMB.
+    pub token: u64,
+    /// Token of the remote DMB element.
+    pub peer_token: u64,
+}
+
+/// SMC socket information (`AF_SMC`, via `smc_diag`).
+///
+/// Addresses and ports are those of the internal TCP "CLC" socket
+/// used for the SMC handshake (and for the data when the connection
+/// fell back to TCP).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmcSocket {
+    /// Raw SMC state (`SMC_ACTIVE`, `SMC_LISTEN`, ...). See
+    /// [`state_name`](Self::state_name).
+    pub state: u8,
+    /// Transport mode.
+    pub mode: SmcMode,
+    /// Local address and port.
+    pub local: SocketAddr,
+    /// Remote address and port.
+    pub remote: SocketAddr,
+    /// Interface index the CLC socket is bound to (0 = any).
+    pub interface: u32,
+    /// Socket cookie.
+    pub cookie: u64,
+    /// Socket owner UID.
+    pub uid: u32,
+    /// Inode number.
+    pub inode: u32,
+    /// Shutdown state (read/write).
+    pub shutdown: Option<u8>,
+    /// Fallback reason, set when `mode` is [`SmcMode::FallbackTcp`].
+    pub fallback: Option<SmcFallback>,
+    /// SMC-R link the connection uses.
+    pub smcr_link: Option<SmcrLinkInfo>,
+    /// SMC-D DMB the connection uses.
+    pub smcd_dmb: Option<SmcdDmbInfo>,
+}
+
+impl SmcSocket {
+    /// Get the netid string.
+    pub fn netid(&self) -> &'static str {
+        if self.local.is_ipv6() { "smc6" } else { "smc" }
+    }
+
+    /// Whether the connection fell back to TCP.
+    pub fn is_fallback(&self) -> bool {
+        self.mode == SmcMode::FallbackTcp
+    }
+
+    /// Get the SMC state name (the kernel's `enum smc_state`).
+    pub fn state_name(&self) -> &'static str {
+        match self.state {
+            1 => "ACTIVE",
+            2 => "INIT",
+            7 => "CLOSED",
+            10 => "LISTEN",
+            20 => "PEERCW1",
+            21 => "PEERCW2",
+            22 => "APPCLW1",
+            23 => "APPCLW2",
+            24 => "APPFCL",
+            25 => "PEERFCL",
+            26 => "PEERABORTW",
+            27 => "PROCESSABORT",
+            _ => "UNKNOWN",
+        }
+    }
+
+    /// Map the SMC state onto the generic [`SocketState`].
+    pub fn socket_state(&self) -> SocketState {
+        match self.state {
+            1 => SocketState::Established,
+            10 => SocketState::Listen,
+            _ => SocketState::Close,
+        }
+    }
+}
+
 /// Parse an IPv4 address from 4 bytes (network byte order).
 pub fn parse_ipv4(data: &[u8]) -> Ipv4Addr {
     if data.len() >= 4 {
@@ -440,3 +716,32 @@ pub fn parse_port(data: &[u8]) -> u16 {
         0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unix(inode: u32, peer: Option<u32>, path: Option<&str>) -> UnixSocket {
+        let mut sock = UnixSocket::new(UnixType::Stream, SocketState::Established, inode);
+        sock.peer_inode = peer;
+        sock.path = path.map(str::to_string);
+        sock
+    }
+
+    #[test]
+    fn unix_peers_pairs_connected_sockets() {
+        let sockets = vec![
+            unix(10, Some(11), Some("/run/app.sock")),
+            unix(11, Some(10), None),
+            unix(12, Some(99), None),
+            unix(13, None, None),
+        ];
+        let peers = UnixPeers::new(&sockets);
+
+        assert_eq!(peers.peer(&sockets[1]).unwrap().name(), "/run/app.sock");
+        assert_eq!(peers.peer(&sockets[0]).unwrap().inode, 11);
+        // Peer outside the dump, and no peer at all.
+        assert!(peers.peer(&sockets[2]).is_none());
+        assert!(peers.peer(&sockets[3]).is_none());
+    }
+}
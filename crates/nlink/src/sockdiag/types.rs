@@ -3,7 +3,7 @@
 //! This module provides strongly-typed representations of socket states,
 //! address families, protocols, and diagnostic information.
 
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use serde::{Deserialize, Serialize};
 
@@ -230,6 +230,12 @@ impl TcpState {
                 | Self::SynRecv.mask())
     }
 
+    /// States an orphaned socket (closed by its owner, still finishing
+    /// the shutdown handshake) can be in.
+    pub fn orphan_mask() -> u32 {
+        Self::FinWait1.mask() | Self::FinWait2.mask() | Self::Closing.mask() | Self::LastAck.mask()
+    }
+
     /// All states mask.
     pub fn all_mask() -> u32 {
         (1 << 14) - 1
@@ -413,6 +419,8 @@ pub struct TcpInfo {
 
     /// Retransmit timeout (usec).
     pub rto: u32,
+    /// Delayed ACK timeout (usec).
+    pub ato: u32,
     /// Estimated RTT (usec).
     pub rtt: u32,
     /// RTT variance (usec).
@@ -513,6 +521,36 @@ pub struct TcpInfo {
     pub rcv_ooopack: u32,
     /// Send window.
     pub snd_wnd: u32,
+
+    /// Local advertised receive window (bytes, 6.2+).
+    pub rcv_wnd: u32,
+    /// PLB or timeout triggered rehash attempts (6.2+).
+    pub rehash: u32,
+    /// RTO timeouts, including SYN/SYN-ACK and recurring ones (6.7+).
+    pub total_rto: u16,
+    /// RTO recoveries, excluding recurring timeouts (6.7+).
+    pub total_rto_recoveries: u16,
+    /// Time spent in RTO recoveries (msec, 6.7+).
+    pub total_rto_time: u32,
+
+    /// Packets received with CE marks (AccECN, 6.18+).
+    pub received_ce: u32,
+    /// Bytes delivered with ECT(1) marks (AccECN, 6.18+).
+    pub delivered_e1_bytes: u32,
+    /// Bytes delivered with ECT(0) marks (AccECN, 6.18+).
+    pub delivered_e0_bytes: u32,
+    /// Bytes delivered with CE marks (AccECN, 6.18+).
+    pub delivered_ce_bytes: u32,
+    /// Bytes received with ECT(1) marks (AccECN, 6.18+).
+    pub received_e1_bytes: u32,
+    /// Bytes received with ECT(0) marks (AccECN, 6.18+).
+    pub received_e0_bytes: u32,
+    /// Bytes received with CE marks (AccECN, 6.18+).
+    pub received_ce_bytes: u32,
+    /// AccECN failure mode (6.18+).
+    pub accecn_fail_mode: u16,
+    /// AccECN option seen state (6.18+).
+    pub accecn_opt_seen: u16,
 }
 
 impl TcpInfo {
@@ -580,7 +618,7 @@ impl TcpInfo {
         if self.delivery_rate > 0 {
             parts.push(format!(
                 "delivery_rate:{}{}",
-                format_rate_bps(self.delivery_rate * 8),
+                crate::util::format_rate(self.delivery_rate * 8),
                 if self.delivery_rate_app_limited {
                     "app_limited"
                 } else {
@@ -593,7 +631,7 @@ impl TcpInfo {
         if self.pacing_rate > 0 && self.pacing_rate < u64::MAX {
             parts.push(format!(
                 "pacing_rate:{}",
-                format_rate_bps(self.pacing_rate * 8)
+                crate::util::format_rate(self.pacing_rate * 8)
             ));
         }
 
@@ -635,7 +673,7 @@ impl TcpInfo {
     /// Format delivery rate as human-readable string.
     pub fn delivery_rate_str(&self) -> String {
         if self.delivery_rate > 0 {
-            format_rate_bps(self.delivery_rate * 8)
+            crate::util::format_rate(self.delivery_rate * 8)
         } else {
             String::new()
         }
@@ -644,12 +682,86 @@ impl TcpInfo {
     /// Format pacing rate as human-readable string.
     pub fn pacing_rate_str(&self) -> String {
         if self.pacing_rate > 0 && self.pacing_rate < u64::MAX {
-            format_rate_bps(self.pacing_rate * 8)
+            crate::util::format_rate(self.pacing_rate * 8)
         } else {
             String::new()
         }
     }
 
+    /// Smoothed RTT.
+    pub fn rtt_duration(&self) -> Duration {
+        Duration::from_micros(self.rtt.into())
+    }
+
+    /// Minimum RTT seen on the connection.
+    pub fn min_rtt_duration(&self) -> Duration {
+        Duration::from_micros(self.min_rtt.into())
+    }
+
+    /// Current retransmit timeout.
+    pub fn rto_duration(&self) -> Duration {
+        Duration::from_micros(self.rto.into())
+    }
+
+    /// Time spent with data outstanding.
+    pub fn busy_duration(&self) -> Duration {
+        Duration::from_micros(self.busy_time)
+    }
+
+    /// Time spent limited by the peer's receive window.
+    pub fn rwnd_limited_duration(&self) -> Duration {
+        Duration::from_micros(self.rwnd_limited)
+    }
+
+    /// Time spent limited by the local send buffer.
+    pub fn sndbuf_limited_duration(&self) -> Duration {
+        Duration::from_micros(self.sndbuf_limited)
+    }
+
+    /// Share of the busy time spent receive-window limited, in `0.0..=1.0`.
+    /// `None` before the connection has been busy (or on pre-4.10 kernels).
+    pub fn rwnd_limited_ratio(&self) -> Option<f64> {
+        ratio(self.rwnd_limited, self.busy_time)
+    }
+
+    /// Share of the busy time spent send-buffer limited, in `0.0..=1.0`.
+    /// `None` before the connection has been busy (or on pre-4.10 kernels).
+    pub fn sndbuf_limited_ratio(&self) -> Option<f64> {
+        ratio(self.sndbuf_limited, self.busy_time)
+    }
+
+    /// Retransmitted segments per data segment sent. `None` before any
+    /// data was sent.
+    pub fn retransmit_rate(&self) -> Option<f64> {
+        ratio(self.total_retrans.into(), self.data_segs_out.into())
+    }
+
+    /// Retransmitted bytes per byte sent. `None` before any data was sent
+    /// (or on pre-4.19 kernels).
+    pub fn byte_retransmit_rate(&self) -> Option<f64> {
+        ratio(self.bytes_retrans, self.bytes_sent)
+    }
+
+    /// Estimated bandwidth-delay product in bytes: delivery rate times
+    /// minimum RTT (smoothed RTT if no minimum is known yet). `None` until
+    /// the kernel has a delivery rate sample.
+    pub fn bdp_bytes(&self) -> Option<u64> {
+        let rtt = if self.min_rtt > 0 {
+            self.min_rtt
+        } else {
+            self.rtt
+        };
+        if self.delivery_rate == 0 || rtt == 0 {
+            return None;
+        }
+        Some((u128::from(self.delivery_rate) * u128::from(rtt) / 1_000_000) as u64)
+    }
+
+    /// Congestion window in bytes (`snd_cwnd` segments of `snd_mss`).
+    pub fn cwnd_bytes(&self) -> u64 {
+        u64::from(self.snd_cwnd) * u64::from(self.snd_mss)
+    }
+
     /// Get the send window scale (high nibble of wscale).
     pub fn snd_wscale(&self) -> u8 {
         self.wscale >> 4
@@ -661,17 +773,9 @@ impl TcpInfo {
     }
 }
 
-/// Format bits per second as human-readable string.
-fn format_rate_bps(bps: u64) -> String {
-    if bps >= 1_000_000_000 {
-        format!("{:.1}Gbps", bps as f64 / 1_000_000_000.0)
-    } else if bps >= 1_000_000 {
-        format!("{:.1}Mbps", bps as f64 / 1_000_000.0)
-    } else if bps >= 1_000 {
-        format!("{:.1}Kbps", bps as f64 / 1_000.0)
-    } else {
-        format!("{}bps", bps)
-    }
+/// `num / den`, or `None` when `den` is zero.
+fn ratio(num: u64, den: u64) -> Option<f64> {
+    (den > 0).then(|| num as f64 / den as f64)
 }
 
 /// Socket memory information.
@@ -827,7 +931,7 @@ impl Timer {
     ///
     /// Returns `None` when no timer is active ([`Timer::Off`]), so
     /// callers can skip the field entirely. The expiry is rendered
-    /// in `ss(8)`'s `<hr>hr<min>min<sec>sec` / `<sec>.<ms>sec` style.
+    /// exactly as `ss(8)` does (`119min`, `1min2sec`, `21sec`, `200ms`).
     pub fn describe(&self) -> Option<String> {
         let (name, expires_ms, count) = match self {
             Timer::Off => return None,
@@ -849,21 +953,34 @@ impl Timer {
     }
 }
 
-/// Render a millisecond timer expiry the way `ss(8)` does.
-fn format_timer_expires(msecs: u32) -> String {
-    let total_secs = msecs / 1000;
-    let ms = msecs % 1000;
-    let secs = total_secs % 60;
-    let total_min = total_secs / 60;
-    let minutes = total_min % 60;
-    let hrs = total_min / 60;
-    if hrs > 0 {
-        format!("{hrs}hr{minutes:02}min{secs:02}sec")
-    } else if minutes > 0 {
-        format!("{minutes}min{secs:02}sec")
-    } else {
-        format!("{secs}.{ms:03}sec")
+/// Render a millisecond timer expiry the way `ss(8)`'s
+/// `print_ms_timer()` does: precision drops as the value grows (no
+/// seconds past 9 minutes, no milliseconds past 9 seconds), and a
+/// sub-minute value with milliseconds reads `5.500ms`.
+fn format_timer_expires(timeout: u32) -> String {
+    let mut secs = timeout / 1000;
+    let minutes = secs / 60;
+    secs %= 60;
+    let mut msecs = timeout % 1000;
+
+    let mut out = String::new();
+    if minutes > 0 {
+        msecs = 0;
+        out.push_str(&format!("{minutes}min"));
+        if minutes > 9 {
+            secs = 0;
+        }
+    }
+    if secs > 0 {
+        if secs > 9 {
+            msecs = 0;
+        }
+        out.push_str(&format!("{secs}{}", if msecs > 0 { "." } else { "sec" }));
     }
+    if msecs > 0 {
+        out.push_str(&format!("{msecs:03}ms"));
+    }
+    out
 }
 
 /// Aggregated socket statistics across all families.
@@ -904,6 +1021,54 @@ impl fmt::Display for SocketSummary {
     }
 }
 
+/// Socket buffer memory summed over a set of sockets, in bytes.
+///
+/// Built from each socket's `INET_DIAG_MEMINFO` counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SocketMemory {
+    /// Receive memory allocated (`sk_rmem_alloc`).
+    pub rmem_alloc: u64,
+    /// Write memory allocated (`sk_wmem_alloc`).
+    pub wmem_alloc: u64,
+    /// Forward allocation (`sk_forward_alloc`).
+    pub fwd_alloc: u64,
+    /// Write memory queued (`sk_wmem_queued`).
+    pub wmem_queued: u64,
+}
+
+impl SocketMemory {
+    /// Memory charged to the sockets: received data, queued data and the
+    /// forward allocation reserved for both.
+    pub fn total(&self) -> u64 {
+        self.rmem_alloc + self.wmem_queued + self.fwd_alloc
+    }
+
+    pub(crate) fn add(&mut self, mem: &MemInfo) {
+        self.rmem_alloc += u64::from(mem.rmem_alloc);
+        self.wmem_alloc += u64::from(mem.wmem_alloc);
+        self.fwd_alloc += u64::from(mem.fwd_alloc);
+        self.wmem_queued += u64::from(mem.wmem_queued);
+    }
+}
+
+/// Socket counts plus TCP and UDP buffer memory for one namespace.
+///
+/// # Example
+///
+/// ```ignore
+/// let usage = conn.socket_usage().await?;
+/// println!("{} established, {} bytes", usage.summary.tcp.established, usage.tcp_memory.total());
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocketUsage {
+    /// Socket counts, as from `socket_summary`.
+    pub summary: SocketSummary,
+    /// Memory held by TCP sockets.
+    pub tcp_memory: SocketMemory,
+    /// Memory held by UDP sockets.
+    pub udp_memory: SocketMemory,
+}
+
 /// Result of a batch socket destruction operation.
 ///
 /// Contains the count of successfully destroyed sockets and any errors.
@@ -932,6 +1097,43 @@ impl DestroyResult {
     }
 }
 
+/// Which of the sockets matching an [`InetFilter`] to destroy.
+///
+/// [`InetFilter`]: crate::sockdiag::InetFilter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DestroyTarget {
+    /// Every socket the filter matches, in the filter's states.
+    #[default]
+    Matching,
+    /// Only TIME-WAIT sockets; replaces the filter's state mask.
+    TimeWait,
+    /// Only orphaned sockets (see [`InetSocket::is_orphaned`]); replaces
+    /// the filter's state mask.
+    ///
+    /// [`InetSocket::is_orphaned`]: crate::sockdiag::InetSocket::is_orphaned
+    Orphaned,
+}
+
+impl DestroyTarget {
+    /// The state mask to dump with, given the filter's own.
+    pub fn states(self, filter_states: u32) -> u32 {
+        match self {
+            Self::Matching => filter_states,
+            Self::TimeWait => TcpState::TimeWait.mask(),
+            Self::Orphaned => TcpState::orphan_mask(),
+        }
+    }
+
+    /// Whether a dumped socket is a target.
+    pub fn matches(self, socket: &crate::sockdiag::InetSocket) -> bool {
+        match self {
+            Self::Matching => true,
+            Self::TimeWait => matches!(socket.state, SocketState::Tcp(TcpState::TimeWait)),
+            Self::Orphaned => socket.is_orphaned(),
+        }
+    }
+}
+
 /// Error from destroying a specific socket.
 #[derive(Debug)]
 pub struct DestroyError {
@@ -984,7 +1186,7 @@ mod tests {
             }
             .describe()
             .as_deref(),
-            Some("timer:(keepalive,29.000sec,0)")
+            Some("timer:(keepalive,29sec,0)")
         );
         assert_eq!(
             Timer::On {
@@ -993,7 +1195,7 @@ mod tests {
             }
             .describe()
             .as_deref(),
-            Some("timer:(on,1min02sec,3)")
+            Some("timer:(on,1min2sec,3)")
         );
         assert_eq!(
             Timer::TimeWait {
@@ -1001,7 +1203,7 @@ mod tests {
             }
             .describe()
             .as_deref(),
-            Some("timer:(timewait,1hr01min01sec,0)")
+            Some("timer:(timewait,61min,0)")
         );
         assert_eq!(
             Timer::Probe {
@@ -1010,8 +1212,101 @@ mod tests {
             }
             .describe()
             .as_deref(),
-            Some("timer:(persist,0.200sec,1)")
+            Some("timer:(persist,200ms,1)")
+        );
+        assert_eq!(
+            Timer::On {
+                expires_ms: 5_500,
+                retrans: 0
+            }
+            .describe()
+            .as_deref(),
+            Some("timer:(on,5.500ms,0)")
+        );
+    }
+
+    #[test]
+    fn socket_memory_sums_meminfo_counters() {
+        let mem = MemInfo {
+            rmem_alloc: 100,
+            wmem_alloc: 20,
+            fwd_alloc: 7,
+            wmem_queued: 30,
+            ..Default::default()
+        };
+        let mut total = SocketMemory::default();
+        total.add(&mem);
+        total.add(&mem);
+        assert_eq!(total.rmem_alloc, 200);
+        assert_eq!(total.wmem_alloc, 40);
+        assert_eq!(total.total(), 200 + 60 + 14);
+    }
+
+    #[test]
+    fn tcp_info_derived_metrics() {
+        let info = TcpInfo {
+            rtt: 20_000,
+            min_rtt: 10_000,
+            snd_cwnd: 10,
+            snd_mss: 1448,
+            delivery_rate: 12_500_000, // 100 Mbit/s
+            busy_time: 2_000_000,
+            rwnd_limited: 500_000,
+            sndbuf_limited: 0,
+            data_segs_out: 1000,
+            total_retrans: 25,
+            bytes_sent: 1_000_000,
+            bytes_retrans: 10_000,
+            ..Default::default()
+        };
+
+        assert_eq!(info.rtt_duration(), Duration::from_millis(20));
+        assert_eq!(info.busy_duration(), Duration::from_secs(2));
+        assert_eq!(info.rwnd_limited_ratio(), Some(0.25));
+        assert_eq!(info.sndbuf_limited_ratio(), Some(0.0));
+        assert_eq!(info.retransmit_rate(), Some(0.025));
+        assert_eq!(info.byte_retransmit_rate(), Some(0.01));
+        // 12.5 MB/s over the 10 ms minimum RTT.
+        assert_eq!(info.bdp_bytes(), Some(125_000));
+        assert_eq!(info.cwnd_bytes(), 14_480);
+
+        let idle = TcpInfo::default();
+        assert_eq!(idle.rwnd_limited_ratio(), None);
+        assert_eq!(idle.retransmit_rate(), None);
+        assert_eq!(idle.bdp_bytes(), None);
+    }
+
+    #[test]
+    fn destroy_target_narrows_states_and_sockets() {
+        use crate::sockdiag::InetSocket;
+
+        let sock = |state, inode| InetSocket {
+            inode,
+            ..InetSocket::new(
+                AddressFamily::Inet,
+                Protocol::Tcp,
+                state,
+                "10.0.0.1:80".parse().unwrap(),
+                "10.0.0.2:5000".parse().unwrap(),
+            )
+        };
+
+        let all = TcpState::all_mask();
+        assert_eq!(DestroyTarget::Matching.states(all), all);
+        assert_eq!(
+            DestroyTarget::TimeWait.states(all),
+            TcpState::TimeWait.mask()
         );
+        assert_eq!(DestroyTarget::Orphaned.states(all), TcpState::orphan_mask());
+
+        assert!(DestroyTarget::TimeWait.matches(&sock(TcpState::TimeWait, 0)));
+        assert!(!DestroyTarget::TimeWait.matches(&sock(TcpState::FinWait2, 0)));
+
+        // An owned FIN-WAIT socket isn't orphaned; an owner-less one is.
+        assert!(DestroyTarget::Orphaned.matches(&sock(TcpState::FinWait1, 0)));
+        assert!(!DestroyTarget::Orphaned.matches(&sock(TcpState::FinWait1, 4242)));
+        assert!(!DestroyTarget::Orphaned.matches(&sock(TcpState::TimeWait, 0)));
+        assert!(!DestroyTarget::Orphaned.matches(&sock(TcpState::Established, 0)));
     }
 
     #[test]
@@ -0,0 +1,54 @@
+//! Socket usage across network namespaces.
+//!
+//! [`socket_usage_in`] runs [`Connection::socket_usage`] in each of a
+//! set of namespaces — one `ss -s` plus buffer memory per container or
+//! pod — without the caller managing a connection per namespace.
+//!
+//! [`Connection::socket_usage`]: crate::netlink::Connection::socket_usage
+
+use crate::netlink::{Result, SockDiag, namespace::NamespaceSpec};
+
+use super::types::SocketUsage;
+
+/// Get [`Connection::socket_usage`] for each of several namespaces.
+///
+/// Each namespace gets its own short-lived `SOCK_DIAG` socket, queried
+/// one after another. A failure (a namespace that has gone away, say) is
+/// reported in that namespace's slot and does not stop the others.
+/// Results come back in input order.
+///
+/// # Example
+///
+/// ```ignore
+/// use nlink::netlink::namespace::NamespaceSpec;
+/// use nlink::sockdiag::socket_usage_in;
+///
+/// let pods = [NamespaceSpec::Named("pod-a"), NamespaceSpec::Pid(4242)];
+/// for (ns, usage) in socket_usage_in(&pods).await {
+///     match usage {
+///         Ok(u) => println!(
+///             "{ns:?}: {} estab, {} listen, {} udp, {} bytes",
+///             u.summary.tcp.established,
+///             u.summary.tcp.listen,
+///             u.summary.udp,
+///             u.tcp_memory.total() + u.udp_memory.total(),
+///         ),
+///         Err(e) => eprintln!("{ns:?}: {e}"),
+///     }
+/// }
+/// ```
+///
+/// [`Connection::socket_usage`]: crate::netlink::Connection::socket_usage
+pub async fn socket_usage_in<'a>(
+    namespaces: &[NamespaceSpec<'a>],
+) -> Vec<(NamespaceSpec<'a>, Result<SocketUsage>)> {
+    let mut out = Vec::with_capacity(namespaces.len());
+    for ns in namespaces {
+        let usage = match ns.connection::<SockDiag>() {
+            Ok(conn) => conn.socket_usage().await,
+            Err(e) => Err(e),
+        };
+        out.push((ns.clone(), usage));
+    }
+    out
+}
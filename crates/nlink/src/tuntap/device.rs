@@ -3,7 +3,7 @@
 use std::{
     ffi::CString,
     fs::{File, OpenOptions},
-    io::{self, Read, Write},
+    io::{self, IoSlice, IoSliceMut, Read, Write},
     mem::MaybeUninit,
     os::unix::io::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
 };
@@ -11,6 +11,7 @@ use std::{
 use super::{
     TUN_DEVICE_PATH,
     error::{Error, Result},
+    vnet::TunOffload,
 };
 
 // TUN/TAP ioctl constants
@@ -20,6 +21,10 @@ const TUNSETGROUP: libc::c_ulong = 0x400454ce;
 const TUNSETPERSIST: libc::c_ulong = 0x400454cb;
 const TUNSETOFFLOAD: libc::c_ulong = 0x400454d0;
 const TUNSETVNETHDRSZ: libc::c_ulong = 0x400454d8;
+const TUNSETQUEUE: libc::c_ulong = 0x400454d9;
+const TUNGETVNETHDRSZ: libc::c_ulong = 0x800454d7;
+const TUNSETSTEERINGEBPF: libc::c_ulong = 0x800454e0;
+const TUNSETFILTEREBPF: libc::c_ulong = 0x800454e1;
 
 // TUN/TAP flags (from linux/if_tun.h)
 /// TUN device (Layer 3).
@@ -34,6 +39,13 @@ const IFF_ONE_QUEUE: libc::c_short = 0x2000;
 const IFF_VNET_HDR: libc::c_short = 0x4000;
 /// Multi-queue support.
 const IFF_MULTI_QUEUE: libc::c_short = 0x0100;
+/// Re-enable a queue (TUNSETQUEUE).
+const IFF_ATTACH_QUEUE: libc::c_short = 0x0200;
+/// Disable a queue without closing it (TUNSETQUEUE).
+const IFF_DETACH_QUEUE: libc::c_short = 0x0400;
+
+/// Most queues a multi-queue device can have (`MAX_TAP_QUEUES`).
+pub const MAX_QUEUES: usize = 256;
 /// Exclusive open (prevent re-open).
 #[allow(dead_code)]
 const IFF_TUN_EXCL: libc::c_short = -0x8000; // 0x8000 as signed
@@ -136,6 +148,8 @@ pub struct TunTapBuilder {
     group: Option<u32>,
     persistent: bool,
     flags: TunTapFlags,
+    queues: usize,
+    offload: Option<TunOffload>,
 }
 
 impl TunTapBuilder {
@@ -147,6 +161,8 @@ impl TunTapBuilder {
             owner: None,
             group: None,
             persistent: false,
+            queues: 1,
+            offload: None,
             flags: TunTapFlags {
                 no_pi: true, // Default to no protocol info
                 ..Default::default()
@@ -224,6 +240,33 @@ impl TunTapBuilder {
         self
     }
 
+    /// Open the device with `n` queues (up to [`MAX_QUEUES`]).
+    ///
+    /// Each queue is its own file descriptor and the kernel spreads flows
+    /// across them, so packets can be processed on several cores. More
+    /// than one queue implies [`multi_queue`](Self::multi_queue). Use
+    /// [`TunTap::into_queues`] to hand each queue to its own thread or
+    /// task.
+    pub fn queues(mut self, n: usize) -> Self {
+        self.queues = n;
+        if n > 1 {
+            self.flags.multi_queue = true;
+        }
+        self
+    }
+
+    /// Enable checksum and segmentation offloads once the device is
+    /// created.
+    ///
+    /// With TSO/USO the kernel passes packets of up to 64 KiB, described
+    /// by a [`VirtioNetHdr`](super::VirtioNetHdr) prefix, so offloads
+    /// imply [`vnet_hdr`](Self::vnet_hdr).
+    pub fn offload(mut self, offloads: TunOffload) -> Self {
+        self.offload = Some(offloads);
+        self.flags.vnet_hdr = true;
+        self
+    }
+
     /// Set additional flags.
     pub fn flags(mut self, flags: TunTapFlags) -> Self {
         self.flags = flags;
@@ -234,53 +277,38 @@ impl TunTapBuilder {
     pub fn create(self) -> Result<TunTap> {
         let mode = self.mode.ok_or(Error::NoModeSpecified)?;
 
-        // Validate name length
-        if let Some(ref name) = self.name
-            && name.len() > libc::IFNAMSIZ - 1
-        {
-            return Err(Error::NameTooLong {
-                name: name.clone(),
-                len: name.len(),
-            });
+        // Validate the name with the same rules as the netlink builders
+        if let Some(ref name) = self.name {
+            if name.len() > libc::IFNAMSIZ - 1 {
+                return Err(Error::NameTooLong {
+                    name: name.clone(),
+                    len: name.len(),
+                });
+            }
+            if let Err(crate::util::ifname::IfError::InvalidName(reason)) =
+                crate::util::ifname::validate(name)
+            {
+                return Err(Error::InvalidName(format!("{name}: {reason}")));
+            }
         }
 
-        // Open the TUN device
-        let file = OpenOptions::new()
-            .read(true)
-            .write(true)
-            .open(TUN_DEVICE_PATH)?;
+        if self.queues == 0 || self.queues > MAX_QUEUES {
+            return Err(Error::InvalidQueues(self.queues));
+        }
+        if self.queues > 1 && !self.flags.multi_queue {
+            return Err(Error::InvalidQueues(self.queues));
+        }
+        if let Some(offload) = self.offload
+            && !offload.is_empty()
+            && !self.flags.vnet_hdr
+        {
+            return Err(Error::InvalidOffload(offload.bits()));
+        }
 
+        let flags = mode.flag() | self.flags.as_flags();
+        let (file, name) = open_queue(self.name.as_deref(), flags)?;
         let fd = file.as_raw_fd();
 
-        // Build ifreq
-        // SAFETY: ifreq is a C struct that is safe to zero-initialize.
-        // All fields are primitive integers, arrays, or unions of such types.
-        let mut ifr = {
-            let uninit = MaybeUninit::<libc::ifreq>::zeroed();
-            // SAFETY: zeroed is valid for ifreq
-            unsafe { uninit.assume_init() }
-        };
-        ifr.ifr_ifru.ifru_flags = mode.flag() | self.flags.as_flags();
-
-        // Set name if provided
-        if let Some(ref name) = self.name {
-            let name_bytes = name.as_bytes();
-            // SAFETY: ifr_name is a [c_char; IFNAMSIZ] array. Casting to [u8] is safe
-            // because c_char and u8 have the same size and alignment on Linux.
-            let name_slice =
-                unsafe { &mut *(&mut ifr.ifr_name as *mut [libc::c_char] as *mut [u8]) };
-            name_slice[..name_bytes.len()].copy_from_slice(name_bytes);
-        }
-
-        // Create the interface
-        // SAFETY: libc::ioctl with TUNSETIFF is the standard way to create TUN/TAP
-        // devices. fd is a valid file descriptor from opening /dev/net/tun, and
-        // ifr is a properly initialized ifreq struct.
-        let ret = unsafe { libc::ioctl(fd, TUNSETIFF, &ifr) };
-        if ret < 0 {
-            return Err(Error::ioctl("TUNSETIFF", io::Error::last_os_error()));
-        }
-
         // Set owner if specified
         if let Some(uid) = self.owner {
             // SAFETY: TUNSETOWNER ioctl sets the owner UID of the TUN/TAP device.
@@ -311,24 +339,23 @@ impl TunTapBuilder {
             }
         }
 
-        // Get the actual interface name
-        // SAFETY: After TUNSETIFF, the kernel writes the actual interface name
-        // back to ifr_name. Casting to [u8] is safe (same size/alignment).
-        let name = unsafe {
-            let name_slice = &*(&ifr.ifr_name as *const [libc::c_char] as *const [u8]);
-            let len = name_slice
-                .iter()
-                .position(|&c| c == 0)
-                .unwrap_or(name_slice.len());
-            String::from_utf8_lossy(&name_slice[..len]).to_string()
-        };
+        // The remaining queues attach to the device the first one created.
+        let extra_queues = (1..self.queues)
+            .map(|_| open_queue(Some(&name), flags).map(|(file, _)| file))
+            .collect::<Result<Vec<_>>>()?;
 
-        Ok(TunTap {
+        let device = TunTap {
             file,
+            extra_queues,
             name,
             mode,
+            flags,
             persistent: self.persistent,
-        })
+        };
+        if let Some(offload) = self.offload {
+            device.set_offloads(offload)?;
+        }
+        Ok(device)
     }
 
     /// Create the TUN/TAP device without keeping it open.
@@ -349,11 +376,67 @@ impl Default for TunTapBuilder {
     }
 }
 
+/// Open `/dev/net/tun` and attach it to the device `name` (created if
+/// missing, named by the kernel if `None`). Returns the queue's file and
+/// the device name.
+fn open_queue(name: Option<&str>, flags: libc::c_short) -> Result<(File, String)> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(TUN_DEVICE_PATH)?;
+
+    // SAFETY: ifreq is a C struct that is safe to zero-initialize.
+    // All fields are primitive integers, arrays, or unions of such types.
+    let mut ifr = {
+        let uninit = MaybeUninit::<libc::ifreq>::zeroed();
+        // SAFETY: zeroed is valid for ifreq
+        unsafe { uninit.assume_init() }
+    };
+    ifr.ifr_ifru.ifru_flags = flags;
+
+    // Set name if provided
+    if let Some(name) = name {
+        let name_bytes = name.as_bytes();
+        // SAFETY: ifr_name is a [c_char; IFNAMSIZ] array. Casting to [u8] is safe
+        // because c_char and u8 have the same size and alignment on Linux.
+        let name_slice = unsafe { &mut *(&mut ifr.ifr_name as *mut [libc::c_char] as *mut [u8]) };
+        name_slice[..name_bytes.len()].copy_from_slice(name_bytes);
+    }
+
+    // Create the interface, or attach another queue to it
+    // SAFETY: libc::ioctl with TUNSETIFF is the standard way to create TUN/TAP
+    // devices. The fd is valid from opening /dev/net/tun, and ifr is a
+    // properly initialized ifreq struct.
+    let ret = unsafe { libc::ioctl(file.as_raw_fd(), TUNSETIFF, &ifr) };
+    if ret < 0 {
+        return Err(Error::ioctl("TUNSETIFF", io::Error::last_os_error()));
+    }
+
+    // Get the actual interface name
+    // SAFETY: After TUNSETIFF, the kernel writes the actual interface name
+    // back to ifr_name. Casting to [u8] is safe (same size/alignment).
+    let name = unsafe {
+        let name_slice = &*(&ifr.ifr_name as *const [libc::c_char] as *const [u8]);
+        let len = name_slice
+            .iter()
+            .position(|&c| c == 0)
+            .unwrap_or(name_slice.len());
+        String::from_utf8_lossy(&name_slice[..len]).to_string()
+    };
+
+    Ok((file, name))
+}
+
 /// A TUN/TAP device.
+///
+/// Reads and writes through the device itself use its first queue; a
+/// device opened with [`TunTapBuilder::queues`] holds the others too.
 pub struct TunTap {
     file: File,
+    extra_queues: Vec<File>,
     name: String,
     mode: Mode,
+    flags: libc::c_short,
     persistent: bool,
 }
 
@@ -383,6 +466,34 @@ impl TunTap {
         self.file.as_raw_fd()
     }
 
+    /// Number of queues this handle holds open.
+    pub fn num_queues(&self) -> usize {
+        1 + self.extra_queues.len()
+    }
+
+    /// Open one more queue on this multi-queue device.
+    ///
+    /// Fails unless the device was created with
+    /// [`multi_queue`](TunTapBuilder::multi_queue).
+    pub fn attach_queue(&self) -> Result<TunTapQueue> {
+        if self.flags & IFF_MULTI_QUEUE == 0 {
+            return Err(Error::InvalidQueues(self.num_queues() + 1));
+        }
+        let (file, _) = open_queue(Some(&self.name), self.flags)?;
+        Ok(TunTapQueue { file })
+    }
+
+    /// Split the device into one handle per queue, first queue first.
+    ///
+    /// The device stays up while any queue is open (or, if persistent,
+    /// until deleted).
+    pub fn into_queues(self) -> Vec<TunTapQueue> {
+        std::iter::once(self.file)
+            .chain(self.extra_queues)
+            .map(|file| TunTapQueue { file })
+            .collect()
+    }
+
     /// Set the VNET header size (for VNET_HDR mode).
     pub fn set_vnet_hdr_size(&self, size: i32) -> Result<()> {
         // SAFETY: TUNSETVNETHDRSZ ioctl sets the VNET header size.
@@ -394,6 +505,37 @@ impl TunTap {
         Ok(())
     }
 
+    /// Get the VNET header size the device prepends to each packet.
+    pub fn vnet_hdr_size(&self) -> Result<i32> {
+        let mut size: libc::c_int = 0;
+        // SAFETY: TUNGETVNETHDRSZ writes the header size into size. The fd
+        // is valid (owned by self.file).
+        let ret = unsafe { libc::ioctl(self.file.as_raw_fd(), TUNGETVNETHDRSZ, &mut size) };
+        if ret < 0 {
+            return Err(Error::ioctl("TUNGETVNETHDRSZ", io::Error::last_os_error()));
+        }
+        Ok(size)
+    }
+
+    /// Check if packets carry a [`VirtioNetHdr`](super::VirtioNetHdr)
+    /// prefix.
+    pub fn has_vnet_hdr(&self) -> bool {
+        self.flags & IFF_VNET_HDR != 0
+    }
+
+    /// Set the offloads userspace handles.
+    ///
+    /// Needs a device created with [`vnet_hdr`](TunTapBuilder::vnet_hdr);
+    /// without the header the kernel has no way to describe a GSO packet,
+    /// so this fails with [`Error::InvalidOffload`]. An empty set turns
+    /// offloads off again.
+    pub fn set_offloads(&self, offloads: TunOffload) -> Result<()> {
+        if !offloads.is_empty() && !self.has_vnet_hdr() {
+            return Err(Error::InvalidOffload(offloads.bits()));
+        }
+        self.set_offload(offloads.bits())
+    }
+
     /// Set offload flags.
     pub fn set_offload(&self, flags: u32) -> Result<()> {
         // SAFETY: TUNSETOFFLOAD ioctl sets offload flags on the device.
@@ -406,6 +548,37 @@ impl TunTap {
         Ok(())
     }
 
+    /// Attach a `BPF_PROG_TYPE_SOCKET_FILTER` program that filters
+    /// packets before they reach userspace; packets for which it returns
+    /// 0 are dropped. `prog_fd` is the loaded program's fd and may be
+    /// closed afterwards. The program is shared by all queues.
+    pub fn set_filter_ebpf(&self, prog_fd: RawFd) -> Result<()> {
+        set_ebpf(&self.file, TUNSETFILTEREBPF, "TUNSETFILTEREBPF", prog_fd)
+    }
+
+    /// Detach the filter program set with [`set_filter_ebpf`](Self::set_filter_ebpf).
+    pub fn clear_filter_ebpf(&self) -> Result<()> {
+        set_ebpf(&self.file, TUNSETFILTEREBPF, "TUNSETFILTEREBPF", -1)
+    }
+
+    /// Attach a `BPF_PROG_TYPE_SOCKET_FILTER` program that picks the
+    /// queue of a multi-queue device for each packet: its return value
+    /// modulo the number of queues is the queue index. Replaces the
+    /// kernel's flow-hash steering.
+    pub fn set_steering_ebpf(&self, prog_fd: RawFd) -> Result<()> {
+        set_ebpf(
+            &self.file,
+            TUNSETSTEERINGEBPF,
+            "TUNSETSTEERINGEBPF",
+            prog_fd,
+        )
+    }
+
+    /// Detach the steering program and go back to flow-hash steering.
+    pub fn clear_steering_ebpf(&self) -> Result<()> {
+        set_ebpf(&self.file, TUNSETSTEERINGEBPF, "TUNSETSTEERINGEBPF", -1)
+    }
+
     /// Make the device persistent.
     pub fn set_persistent(&mut self, persistent: bool) -> Result<()> {
         let value = if persistent { 1 } else { 0 };
@@ -451,6 +624,25 @@ impl TunTap {
         self.file.write(buf)
     }
 
+    /// Read up to `bufs.len()` packets, one per buffer.
+    ///
+    /// Blocks for the first packet only, then takes whatever else is
+    /// already queued. Each packet's length is stored in `lens`; returns
+    /// the number of packets read.
+    pub fn recv_many(
+        &mut self,
+        bufs: &mut [IoSliceMut<'_>],
+        lens: &mut [usize],
+    ) -> io::Result<usize> {
+        recv_batch(&self.file, bufs, lens, false)
+    }
+
+    /// Write each buffer as one packet. Returns the number of packets
+    /// written.
+    pub fn send_many(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        send_batch(&self.file, bufs)
+    }
+
     /// Take ownership of the underlying file.
     pub fn into_file(self) -> File {
         self.file
@@ -471,6 +663,12 @@ impl Read for TunTap {
     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
         self.file.read(buf)
     }
+
+    /// Read one packet scattered across `bufs` (`readv(2)`), e.g. a
+    /// [`VirtioNetHdr`](super::VirtioNetHdr) and its payload.
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.file.read_vectored(bufs)
+    }
 }
 
 impl Write for TunTap {
@@ -478,6 +676,11 @@ impl Write for TunTap {
         self.file.write(buf)
     }
 
+    /// Write `bufs` gathered into one packet (`writev(2)`).
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.file.write_vectored(bufs)
+    }
+
     fn flush(&mut self) -> io::Result<()> {
         self.file.flush()
     }
@@ -505,14 +708,331 @@ impl FromRawFd for TunTap {
         TunTap {
             // SAFETY: Caller guarantees fd is a valid TUN/TAP device fd.
             file: unsafe { File::from_raw_fd(fd) },
+            extra_queues: Vec::new(),
             name: String::new(), // Unknown
             mode: Mode::Tun,     // Unknown
+            flags: 0,            // Unknown
             persistent: false,
         }
     }
 }
 
+/// One queue of a multi-queue TUN/TAP device.
+///
+/// Returned by [`TunTap::into_queues`] and [`TunTap::attach_queue`].
+/// Each queue is an independent file descriptor that can be moved to
+/// its own thread; the kernel steers each flow to one queue.
+pub struct TunTapQueue {
+    file: File,
+}
+
+impl TunTapQueue {
+    /// Read a packet from this queue.
+    pub fn read_packet(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+
+    /// Write a packet to this queue.
+    pub fn write_packet(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    /// Read up to `bufs.len()` packets, one per buffer.
+    ///
+    /// See [`TunTap::recv_many`].
+    pub fn recv_many(
+        &mut self,
+        bufs: &mut [IoSliceMut<'_>],
+        lens: &mut [usize],
+    ) -> io::Result<usize> {
+        recv_batch(&self.file, bufs, lens, false)
+    }
+
+    /// Write each buffer as one packet. Returns the number of packets
+    /// written.
+    pub fn send_many(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        send_batch(&self.file, bufs)
+    }
+
+    /// Enable or disable this queue without closing it.
+    ///
+    /// A disabled queue receives no packets; the kernel rebalances flows
+    /// over the remaining ones.
+    pub fn set_enabled(&self, enabled: bool) -> Result<()> {
+        // SAFETY: ifreq is a C struct that is safe to zero-initialize.
+        let mut ifr = unsafe { MaybeUninit::<libc::ifreq>::zeroed().assume_init() };
+        ifr.ifr_ifru.ifru_flags = if enabled {
+            IFF_ATTACH_QUEUE
+        } else {
+            IFF_DETACH_QUEUE
+        };
+        // SAFETY: TUNSETQUEUE reads the attach/detach flag from ifr. The fd
+        // is valid (owned by self.file).
+        let ret = unsafe { libc::ioctl(self.file.as_raw_fd(), TUNSETQUEUE, &ifr) };
+        if ret < 0 {
+            return Err(Error::ioctl("TUNSETQUEUE", io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+
+    /// Attach a packet filter program to the device; see
+    /// [`TunTap::set_filter_ebpf`].
+    pub fn set_filter_ebpf(&self, prog_fd: RawFd) -> Result<()> {
+        set_ebpf(&self.file, TUNSETFILTEREBPF, "TUNSETFILTEREBPF", prog_fd)
+    }
+
+    /// Attach a queue steering program to the device; see
+    /// [`TunTap::set_steering_ebpf`].
+    pub fn set_steering_ebpf(&self, prog_fd: RawFd) -> Result<()> {
+        set_ebpf(
+            &self.file,
+            TUNSETSTEERINGEBPF,
+            "TUNSETSTEERINGEBPF",
+            prog_fd,
+        )
+    }
+
+    /// Convert into an async queue driven by the tokio reactor.
+    #[cfg(feature = "tuntap-async")]
+    pub fn into_async(self) -> io::Result<AsyncTunTapQueue> {
+        AsyncTunTapQueue::new(self.file)
+    }
+
+    /// Take ownership of the underlying file.
+    pub fn into_file(self) -> File {
+        self.file
+    }
+
+    /// Get a reference to the underlying file.
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+}
+
+impl Read for TunTapQueue {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+
+    /// Read one packet scattered across `bufs` (`readv(2)`), e.g. a
+    /// [`VirtioNetHdr`](super::VirtioNetHdr) and its payload.
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.file.read_vectored(bufs)
+    }
+}
+
+impl Write for TunTapQueue {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    /// Write `bufs` gathered into one packet (`writev(2)`).
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.file.write_vectored(bufs)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+impl AsRawFd for TunTapQueue {
+    fn as_raw_fd(&self) -> RawFd {
+        self.file.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for TunTapQueue {
+    fn into_raw_fd(self) -> RawFd {
+        self.file.into_raw_fd()
+    }
+}
+
+/// A TUN/TAP queue with async reads and writes.
+///
+/// The file descriptor is switched to non-blocking mode and registered
+/// with the tokio reactor, so one task per queue can process packets
+/// without blocking a runtime thread.
+#[cfg(feature = "tuntap-async")]
+pub struct AsyncTunTapQueue {
+    fd: tokio::io::unix::AsyncFd<File>,
+}
+
+#[cfg(feature = "tuntap-async")]
+impl AsyncTunTapQueue {
+    fn new(file: File) -> io::Result<Self> {
+        // SAFETY: F_GETFL/F_SETFL on a valid fd (owned by file).
+        let ret = unsafe {
+            let flags = libc::fcntl(file.as_raw_fd(), libc::F_GETFL);
+            if flags < 0 {
+                flags
+            } else {
+                libc::fcntl(file.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK)
+            }
+        };
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self {
+            fd: tokio::io::unix::AsyncFd::new(file)?,
+        })
+    }
+
+    /// Read a packet from this queue.
+    pub async fn read_packet(&self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let mut guard = self.fd.readable().await?;
+            match guard.try_io(|fd| (&mut fd.get_ref()).read(buf)) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Write a packet to this queue.
+    pub async fn write_packet(&self, buf: &[u8]) -> io::Result<usize> {
+        loop {
+            let mut guard = self.fd.writable().await?;
+            match guard.try_io(|fd| (&mut fd.get_ref()).write(buf)) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Read one packet scattered across `bufs`.
+    pub async fn read_vectored(&self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        loop {
+            let mut guard = self.fd.readable().await?;
+            match guard.try_io(|fd| (&mut fd.get_ref()).read_vectored(bufs)) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Write `bufs` gathered into one packet.
+    pub async fn write_vectored(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        loop {
+            let mut guard = self.fd.writable().await?;
+            match guard.try_io(|fd| (&mut fd.get_ref()).write_vectored(bufs)) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Wait for a packet, then read up to `bufs.len()` packets, one per
+    /// buffer, without waiting again. Each packet's length is stored in
+    /// `lens`; returns the number of packets read.
+    pub async fn recv_many(
+        &self,
+        bufs: &mut [IoSliceMut<'_>],
+        lens: &mut [usize],
+    ) -> io::Result<usize> {
+        loop {
+            let mut guard = self.fd.readable().await?;
+            match guard.try_io(|fd| recv_batch(fd.get_ref(), bufs, lens, true)) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Write each buffer as one packet, stopping early if the queue
+    /// fills up. Returns the number of packets written.
+    pub async fn send_many(&self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        loop {
+            let mut guard = self.fd.writable().await?;
+            match guard.try_io(|fd| send_batch(fd.get_ref(), bufs)) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Get a reference to the underlying file.
+    pub fn file(&self) -> &File {
+        self.fd.get_ref()
+    }
+}
+
+#[cfg(feature = "tuntap-async")]
+impl AsRawFd for AsyncTunTapQueue {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
 /// Look up a user by name and return the UID.
+/// Attach (`prog_fd >= 0`) or detach (`-1`) an eBPF program with
+/// `TUNSETFILTEREBPF` or `TUNSETSTEERINGEBPF`.
+fn set_ebpf(file: &File, request: libc::c_ulong, name: &'static str, prog_fd: RawFd) -> Result<()> {
+    // SAFETY: both ioctls read a program fd (or -1) from the pointed-to
+    // int. The device fd is valid (owned by file).
+    let ret = unsafe { libc::ioctl(file.as_raw_fd(), request, &prog_fd) };
+    if ret < 0 {
+        return Err(Error::ioctl(name, io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+/// Read packets into `bufs`, one per buffer, until they are used up or
+/// nothing more is queued. Only the first read may wait: on a blocking
+/// file the rest are preceded by a zero-timeout `poll`, on a non-blocking
+/// one they stop at `WouldBlock`. An error after the first packet ends
+/// the batch early instead of losing the packets already read.
+fn recv_batch(
+    mut file: &File,
+    bufs: &mut [IoSliceMut<'_>],
+    lens: &mut [usize],
+    nonblocking: bool,
+) -> io::Result<usize> {
+    let max = bufs.len().min(lens.len());
+    let mut n = 0;
+    while n < max {
+        if n > 0 && !nonblocking && !poll_readable(file)? {
+            break;
+        }
+        match file.read(&mut bufs[n]) {
+            Ok(len) => lens[n] = len,
+            Err(_) if n > 0 => break,
+            Err(e) => return Err(e),
+        }
+        n += 1;
+    }
+    Ok(n)
+}
+
+/// Write each buffer as one packet. An error after the first packet ends
+/// the batch early.
+fn send_batch(mut file: &File, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+    for (n, buf) in bufs.iter().enumerate() {
+        match file.write(buf) {
+            Ok(_) => {}
+            Err(_) if n > 0 => return Ok(n),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(bufs.len())
+}
+
+/// Whether a read on `file` would return without blocking.
+fn poll_readable(file: &File) -> io::Result<bool> {
+    let mut pfd = libc::pollfd {
+        fd: file.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    // SAFETY: pfd is a valid pollfd for a valid fd (owned by file), and
+    // nfds is 1. A zero timeout makes poll return immediately.
+    let ret = unsafe { libc::poll(&mut pfd, 1, 0) };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(ret > 0 && pfd.revents & libc::POLLIN != 0)
+}
+
 fn lookup_user(name: &str) -> Result<u32> {
     let name_cstr = CString::new(name).map_err(|_| Error::InvalidName(name.to_string()))?;
 
@@ -630,3 +1150,89 @@ impl TunTapInfo {
         self.flags & (IFF_MULTI_QUEUE as u32) != 0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_count_is_validated_before_opening() {
+        let build = |n| TunTap::builder().mode(Mode::Tap).queues(n).create();
+        assert!(matches!(build(0), Err(Error::InvalidQueues(0))));
+        assert!(matches!(
+            build(MAX_QUEUES + 1),
+            Err(Error::InvalidQueues(257))
+        ));
+
+        // Replacing the flags after `queues` drops multi_queue again.
+        let single = TunTap::builder()
+            .mode(Mode::Tun)
+            .queues(2)
+            .flags(TunTapFlags::new().no_pi(true))
+            .create();
+        assert!(matches!(single, Err(Error::InvalidQueues(2))));
+    }
+
+    #[test]
+    fn ebpf_errors_name_the_ioctl() {
+        let null = File::open("/dev/null").unwrap();
+        // SAFETY: the fd comes from an open file we give up ownership of.
+        let not_tun = unsafe { TunTap::from_raw_fd(null.into_raw_fd()) };
+        assert!(matches!(
+            not_tun.set_filter_ebpf(-1),
+            Err(Error::Ioctl {
+                name: "TUNSETFILTEREBPF",
+                ..
+            })
+        ));
+        assert!(matches!(
+            not_tun.clear_steering_ebpf(),
+            Err(Error::Ioctl {
+                name: "TUNSETSTEERINGEBPF",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn offload_implies_vnet_hdr() {
+        let builder = TunTapBuilder::new().offload(TunOffload::CSUM);
+        assert!(builder.flags.vnet_hdr);
+
+        let without_hdr = TunTap::builder()
+            .mode(Mode::Tun)
+            .offload(TunOffload::CSUM | TunOffload::TSO4)
+            .flags(TunTapFlags::new().no_pi(true))
+            .create();
+        assert!(matches!(without_hdr, Err(Error::InvalidOffload(0x03))));
+    }
+
+    #[test]
+    fn batches_keep_packet_boundaries() {
+        use std::os::{fd::OwnedFd, unix::net::UnixDatagram};
+
+        let (a, b) = UnixDatagram::pair().unwrap();
+        let (a, b) = (File::from(OwnedFd::from(a)), File::from(OwnedFd::from(b)));
+
+        let sent = send_batch(&a, &[IoSlice::new(b"one"), IoSlice::new(b"three")]).unwrap();
+        assert_eq!(sent, 2);
+
+        let mut bufs = [[0u8; 8]; 3];
+        let mut lens = [0usize; 3];
+        let mut slices: Vec<_> = bufs.iter_mut().map(|b| IoSliceMut::new(b)).collect();
+        // Only two packets are queued, so the third slot stays unused
+        // rather than blocking.
+        assert_eq!(recv_batch(&b, &mut slices, &mut lens, false).unwrap(), 2);
+        assert_eq!(&lens[..2], &[3, 5]);
+        assert_eq!(&bufs[0][..3], b"one");
+        assert_eq!(&bufs[1][..5], b"three");
+    }
+
+    #[test]
+    fn queues_imply_multi_queue() {
+        let builder = TunTapBuilder::new().queues(4);
+        assert!(builder.flags.multi_queue);
+        assert_ne!(builder.flags.as_flags() & IFF_MULTI_QUEUE, 0);
+        assert!(!TunTapBuilder::new().queues(1).flags.multi_queue);
+    }
+}
@@ -45,6 +45,16 @@ pub enum Error {
     #[error("group not found: {0}")]
     GroupNotFound(String),
 
+    /// Queue count out of range, or more than one queue without
+    /// multi-queue support.
+    #[error("invalid queue count {0} (must be 1..=256, more than one needs multi_queue)")]
+    InvalidQueues(usize),
+
+    /// Offload flags set without the virtio-net header that carries
+    /// offload state.
+    #[error("offload flags {0:#x} need vnet_hdr")]
+    InvalidOffload(u32),
+
     /// No mode specified (must be TUN or TAP).
     #[error("no mode specified (must be tun or tap)")]
     NoModeSpecified,
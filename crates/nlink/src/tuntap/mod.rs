@@ -37,6 +37,61 @@
 //!     .create()?;
 //! ```
 //!
+//! # Multi-queue
+//!
+//! A device opened with several queues gets one file descriptor per
+//! queue, and the kernel spreads flows across them:
+//!
+//! ```ignore
+//! use nlink::tuntap::{TunTap, Mode};
+//!
+//! let queues = TunTap::builder()
+//!     .name("mytun0")
+//!     .mode(Mode::Tun)
+//!     .queues(4)
+//!     .create()?
+//!     .into_queues();
+//!
+//! for mut queue in queues {
+//!     std::thread::spawn(move || {
+//!         let mut buf = [0u8; 1500];
+//!         while let Ok(n) = queue.read_packet(&mut buf) {
+//!             // process &buf[..n]
+//!         }
+//!     });
+//! }
+//! ```
+//!
+//! With the `tuntap-async` feature, [`TunTapQueue::into_async`] turns a
+//! queue into an [`AsyncTunTapQueue`] for use from a tokio task.
+//!
+//! # Batched I/O and offloads
+//!
+//! `recv_many` reads every packet already queued into a slice of
+//! buffers with one wait, and `send_many` writes a batch; `Read` and
+//! `Write` forward `read_vectored`/`write_vectored` to `readv`/`writev`
+//! so a packet can be split across buffers. With
+//! [`TunTapBuilder::offload`] the device exchanges TSO/USO super-packets
+//! prefixed by a [`VirtioNetHdr`]:
+//!
+//! ```ignore
+//! use std::io::{IoSliceMut, Read};
+//! use nlink::tuntap::{Mode, TunOffload, TunTap, VirtioNetHdr, VIRTIO_NET_HDR_LEN};
+//!
+//! let mut tun = TunTap::builder()
+//!     .mode(Mode::Tun)
+//!     .offload(TunOffload::TSO)
+//!     .create()?;
+//!
+//! let mut hdr = [0u8; VIRTIO_NET_HDR_LEN];
+//! let mut packet = vec![0u8; 65535];
+//! let n = tun.read_vectored(&mut [IoSliceMut::new(&mut hdr), IoSliceMut::new(&mut packet)])?;
+//! let hdr = VirtioNetHdr::parse(&hdr).unwrap();
+//! if hdr.is_gso() {
+//!     // split &packet[..n - VIRTIO_NET_HDR_LEN] into hdr.gso_size segments
+//! }
+//! ```
+//!
 //! # Async Support
 //!
 //! Enable the `async` feature for async read/write operations:
@@ -60,9 +115,17 @@
 
 mod device;
 mod error;
+mod vnet;
 
-pub use device::{Mode, TunTap, TunTapBuilder, TunTapFlags};
+#[cfg(feature = "tuntap-async")]
+pub use device::AsyncTunTapQueue;
+pub use device::{MAX_QUEUES, Mode, TunTap, TunTapBuilder, TunTapFlags, TunTapQueue};
 pub use error::{Error, Result};
+pub use vnet::{
+    TunOffload, VIRTIO_NET_HDR_F_DATA_VALID, VIRTIO_NET_HDR_F_NEEDS_CSUM, VIRTIO_NET_HDR_GSO_ECN,
+    VIRTIO_NET_HDR_GSO_NONE, VIRTIO_NET_HDR_GSO_TCPV4, VIRTIO_NET_HDR_GSO_TCPV6,
+    VIRTIO_NET_HDR_GSO_UDP, VIRTIO_NET_HDR_GSO_UDP_L4, VIRTIO_NET_HDR_LEN, VirtioNetHdr, offload,
+};
 
 /// The path to the TUN device.
 pub const TUN_DEVICE_PATH: &str = "/dev/net/tun";
@@ -0,0 +1,174 @@
+//! virtio-net packet header for devices opened with `IFF_VNET_HDR`.
+//!
+//! With the header enabled every packet read from or written to the
+//! device is prefixed by a [`VirtioNetHdr`]. It carries checksum and
+//! segmentation offload state, so once TSO/USO is switched on with
+//! [`TunTapBuilder::offload`](super::TunTapBuilder::offload) the kernel
+//! can hand over (and accept) packets of up to 64 KiB instead of one
+//! MTU-sized packet per syscall.
+
+/// Size of [`VirtioNetHdr`] on the wire, the kernel's default vnet
+/// header size.
+pub const VIRTIO_NET_HDR_LEN: usize = 10;
+
+/// `flags`: the checksum at `csum_start + csum_offset` still has to be
+/// computed.
+pub const VIRTIO_NET_HDR_F_NEEDS_CSUM: u8 = 1;
+/// `flags`: the checksum has already been validated.
+pub const VIRTIO_NET_HDR_F_DATA_VALID: u8 = 2;
+
+/// `gso_type`: not a GSO packet.
+pub const VIRTIO_NET_HDR_GSO_NONE: u8 = 0;
+/// `gso_type`: TCP over IPv4 segmentation.
+pub const VIRTIO_NET_HDR_GSO_TCPV4: u8 = 1;
+/// `gso_type`: UDP fragmentation offload.
+pub const VIRTIO_NET_HDR_GSO_UDP: u8 = 3;
+/// `gso_type`: TCP over IPv6 segmentation.
+pub const VIRTIO_NET_HDR_GSO_TCPV6: u8 = 4;
+/// `gso_type`: UDP segmentation (USO).
+pub const VIRTIO_NET_HDR_GSO_UDP_L4: u8 = 5;
+/// `gso_type` bit: the TCP segments carry ECN.
+pub const VIRTIO_NET_HDR_GSO_ECN: u8 = 0x80;
+
+/// Offload flags for [`TunTap::set_offload`](super::TunTap::set_offload)
+/// and [`TunTapBuilder::offload`](super::TunTapBuilder::offload).
+pub mod offload {
+    /// Userspace handles packets with partial checksums.
+    pub const TUN_F_CSUM: u32 = 0x01;
+    /// Userspace handles TSO for IPv4 packets.
+    pub const TUN_F_TSO4: u32 = 0x02;
+    /// Userspace handles TSO for IPv6 packets.
+    pub const TUN_F_TSO6: u32 = 0x04;
+    /// Userspace handles TSO with ECN bits.
+    pub const TUN_F_TSO_ECN: u32 = 0x08;
+    /// Userspace handles UFO packets.
+    pub const TUN_F_UFO: u32 = 0x10;
+    /// Userspace handles USO for IPv4 packets.
+    pub const TUN_F_USO4: u32 = 0x20;
+    /// Userspace handles USO for IPv6 packets.
+    pub const TUN_F_USO6: u32 = 0x40;
+}
+
+bitflags::bitflags! {
+    /// Offloads userspace takes on for a device (`TUN_F_*`), set with
+    /// [`TunTap::set_offloads`](super::TunTap::set_offloads) or
+    /// [`TunTapBuilder::offload`](super::TunTapBuilder::offload).
+    ///
+    /// The kernel only honours TSO/USO/UFO together with `CSUM` and
+    /// rejects them otherwise.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct TunOffload: u32 {
+        /// `TUN_F_CSUM` — packets may carry partial checksums.
+        const CSUM = offload::TUN_F_CSUM;
+        /// `TUN_F_TSO4` — TCP segmentation for IPv4.
+        const TSO4 = offload::TUN_F_TSO4;
+        /// `TUN_F_TSO6` — TCP segmentation for IPv6.
+        const TSO6 = offload::TUN_F_TSO6;
+        /// `TUN_F_TSO_ECN` — TSO with ECN bits.
+        const TSO_ECN = offload::TUN_F_TSO_ECN;
+        /// `TUN_F_UFO` — UDP fragmentation.
+        const UFO = offload::TUN_F_UFO;
+        /// `TUN_F_USO4` — UDP segmentation for IPv4.
+        const USO4 = offload::TUN_F_USO4;
+        /// `TUN_F_USO6` — UDP segmentation for IPv6.
+        const USO6 = offload::TUN_F_USO6;
+    }
+}
+
+impl TunOffload {
+    /// Checksum offload plus TSO for both families, the usual set for a
+    /// userspace VPN.
+    pub const TSO: Self = Self::CSUM.union(Self::TSO4).union(Self::TSO6);
+}
+
+/// The `struct virtio_net_hdr` prefix of a packet.
+///
+/// Multi-byte fields are in host byte order, which is what the kernel
+/// uses unless the device was switched with `TUNSETVNETLE`/`TUNSETVNETBE`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VirtioNetHdr {
+    /// `VIRTIO_NET_HDR_F_*` flags.
+    pub flags: u8,
+    /// `VIRTIO_NET_HDR_GSO_*` type, optionally with
+    /// [`VIRTIO_NET_HDR_GSO_ECN`].
+    pub gso_type: u8,
+    /// Length of the headers to copy into each segment.
+    pub hdr_len: u16,
+    /// Payload size of each segment.
+    pub gso_size: u16,
+    /// Offset at which checksumming starts.
+    pub csum_start: u16,
+    /// Offset of the checksum field from `csum_start`.
+    pub csum_offset: u16,
+}
+
+impl VirtioNetHdr {
+    /// Parse the header at the start of `buf`, or `None` if `buf` is
+    /// shorter than [`VIRTIO_NET_HDR_LEN`].
+    pub fn parse(buf: &[u8]) -> Option<Self> {
+        let buf: &[u8; VIRTIO_NET_HDR_LEN] = buf.get(..VIRTIO_NET_HDR_LEN)?.try_into().ok()?;
+        let u16_at = |i: usize| u16::from_ne_bytes([buf[i], buf[i + 1]]);
+        Some(Self {
+            flags: buf[0],
+            gso_type: buf[1],
+            hdr_len: u16_at(2),
+            gso_size: u16_at(4),
+            csum_start: u16_at(6),
+            csum_offset: u16_at(8),
+        })
+    }
+
+    /// Encode the header for the front of a written packet.
+    pub fn to_bytes(&self) -> [u8; VIRTIO_NET_HDR_LEN] {
+        let mut buf = [0u8; VIRTIO_NET_HDR_LEN];
+        buf[0] = self.flags;
+        buf[1] = self.gso_type;
+        buf[2..4].copy_from_slice(&self.hdr_len.to_ne_bytes());
+        buf[4..6].copy_from_slice(&self.gso_size.to_ne_bytes());
+        buf[6..8].copy_from_slice(&self.csum_start.to_ne_bytes());
+        buf[8..10].copy_from_slice(&self.csum_offset.to_ne_bytes());
+        buf
+    }
+
+    /// Whether the packet is a GSO super-packet that needs segmenting.
+    pub fn is_gso(&self) -> bool {
+        self.gso_type & !VIRTIO_NET_HDR_GSO_ECN != VIRTIO_NET_HDR_GSO_NONE
+    }
+
+    /// Whether the packet's checksum still has to be filled in.
+    pub fn needs_csum(&self) -> bool {
+        self.flags & VIRTIO_NET_HDR_F_NEEDS_CSUM != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn virtio_net_hdr_round_trips() {
+        let hdr = VirtioNetHdr {
+            flags: VIRTIO_NET_HDR_F_NEEDS_CSUM,
+            gso_type: VIRTIO_NET_HDR_GSO_TCPV4 | VIRTIO_NET_HDR_GSO_ECN,
+            hdr_len: 54,
+            gso_size: 1448,
+            csum_start: 34,
+            csum_offset: 16,
+        };
+        let mut packet = hdr.to_bytes().to_vec();
+        packet.extend_from_slice(b"payload");
+        assert_eq!(VirtioNetHdr::parse(&packet), Some(hdr));
+        assert!(hdr.is_gso());
+        assert!(hdr.needs_csum());
+
+        assert_eq!(VirtioNetHdr::parse(&packet[..VIRTIO_NET_HDR_LEN - 1]), None);
+        assert!(!VirtioNetHdr::default().is_gso());
+    }
+
+    #[test]
+    fn tun_offload_matches_kernel_bits() {
+        assert_eq!(TunOffload::TSO.bits(), 0x07);
+        assert_eq!(TunOffload::USO4.bits(), offload::TUN_F_USO4);
+        assert_eq!(TunOffload::all().bits(), 0x7f);
+    }
+}
@@ -88,16 +88,62 @@ pub fn format_addr_bytes(bytes: &[u8], family: u8) -> Option<String> {
     }
 }
 
+/// Notation used when formatting a MAC address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MacNotation {
+    /// `aa:bb:cc:dd:ee:ff` (iproute2, Linux).
+    #[default]
+    Colon,
+    /// `aa-bb-cc-dd-ee-ff` (IEEE, Windows).
+    Hyphen,
+    /// `aabb.ccdd.eeff` (Cisco).
+    Dotted,
+    /// `aabbccddeeff`.
+    Bare,
+}
+
 /// Parse a MAC address from string.
+///
+/// Accepts colon- or hyphen-separated octets (one or two hex digits each,
+/// as `ip link` does), Cisco dotted triples (`aabb.ccdd.eeff`) and twelve
+/// bare hex digits. Separators may not be mixed.
 pub fn parse_mac(s: &str) -> Result<[u8; 6]> {
-    let parts: Vec<&str> = s.split(':').collect();
-    if parts.len() != 6 {
-        return Err(AddrError::InvalidMac(s.to_string()));
-    }
+    let invalid = || AddrError::InvalidMac(s.to_string());
+    let hex = |t: &str| -> Result<u32> {
+        if t.is_empty() || !t.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        u32::from_str_radix(t, 16).map_err(|_| invalid())
+    };
 
     let mut mac = [0u8; 6];
-    for (i, part) in parts.iter().enumerate() {
-        mac[i] = u8::from_str_radix(part, 16).map_err(|_| AddrError::InvalidMac(s.to_string()))?;
+    if let Some(sep) = [':', '-'].into_iter().find(|&c| s.contains(c)) {
+        let parts: Vec<&str> = s.split(sep).collect();
+        if parts.len() != 6 {
+            return Err(invalid());
+        }
+        for (i, part) in parts.iter().enumerate() {
+            if part.len() > 2 {
+                return Err(invalid());
+            }
+            mac[i] = hex(part)? as u8;
+        }
+    } else if s.contains('.') {
+        let parts: Vec<&str> = s.split('.').collect();
+        if parts.len() != 3 || parts.iter().any(|p| p.len() != 4) {
+            return Err(invalid());
+        }
+        for (i, part) in parts.iter().enumerate() {
+            let word = hex(part)? as u16;
+            mac[i * 2..i * 2 + 2].copy_from_slice(&word.to_be_bytes());
+        }
+    } else {
+        if s.len() != 12 {
+            return Err(invalid());
+        }
+        for (i, byte) in mac.iter_mut().enumerate() {
+            *byte = hex(&s[i * 2..i * 2 + 2])? as u8;
+        }
     }
 
     Ok(mac)
@@ -119,6 +165,50 @@ pub fn format_mac(bytes: &[u8]) -> String {
     }
 }
 
+/// Format a MAC address in the given notation.
+pub fn format_mac_as(mac: &[u8; 6], notation: MacNotation) -> String {
+    match notation {
+        MacNotation::Colon => format_mac(mac),
+        MacNotation::Hyphen => format_mac(mac).replace(':', "-"),
+        MacNotation::Dotted => format!(
+            "{:02x}{:02x}.{:02x}{:02x}.{:02x}{:02x}",
+            mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]
+        ),
+        MacNotation::Bare => mac.iter().map(|b| format!("{:02x}", b)).collect(),
+    }
+}
+
+/// Derive the modified EUI-64 interface identifier of a MAC address
+/// (RFC 4291 appendix A): `ff:fe` is inserted in the middle and the
+/// universal/local bit is inverted.
+pub fn mac_to_eui64(mac: &[u8; 6]) -> [u8; 8] {
+    [
+        mac[0] ^ 0x02,
+        mac[1],
+        mac[2],
+        0xff,
+        0xfe,
+        mac[3],
+        mac[4],
+        mac[5],
+    ]
+}
+
+/// Build the SLAAC address for `mac` within a /64 `prefix`.
+///
+/// Only the upper 64 bits of `prefix` are used.
+pub fn eui64_addr(prefix: Ipv6Addr, mac: &[u8; 6]) -> Ipv6Addr {
+    let mut octets = prefix.octets();
+    octets[8..].copy_from_slice(&mac_to_eui64(mac));
+    Ipv6Addr::from(octets)
+}
+
+/// The `fe80::/64` link-local address the kernel derives from `mac`
+/// (`addrgenmode eui64`).
+pub fn eui64_link_local(mac: &[u8; 6]) -> Ipv6Addr {
+    eui64_addr(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0), mac)
+}
+
 /// Check if an IPv4 address is in a given prefix.
 pub fn ipv4_in_prefix(addr: Ipv4Addr, prefix_addr: Ipv4Addr, prefix_len: u8) -> bool {
     if prefix_len == 0 {
@@ -184,4 +274,72 @@ mod tests {
             "aa:bb:cc:dd:ee:ff"
         );
     }
+
+    #[test]
+    fn test_parse_mac_notations() {
+        let want = [0x00, 0x1b, 0x21, 0x0a, 0xbc, 0xde];
+        for s in [
+            "00:1b:21:0a:bc:de",
+            "00:1B:21:0A:BC:DE",
+            "0:1b:21:a:bc:de",
+            "00-1b-21-0a-bc-de",
+            "001b.210a.bcde",
+            "001b210abcde",
+        ] {
+            assert_eq!(parse_mac(s).unwrap(), want, "{s}");
+        }
+
+        for s in [
+            "",
+            "00:1b:21:0a:bc",
+            "00:1b:21:0a:bc:de:ff",
+            "00:1b-21:0a:bc:de",
+            "000:1b:21:0a:bc:de",
+            "00:1b:21:0a:bc:+e",
+            "001b.210a.bcd",
+            "001b210abcd",
+            "001b210abcdg",
+        ] {
+            assert!(parse_mac(s).is_err(), "{s:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_format_mac_as() {
+        let mac = [0x00, 0x1b, 0x21, 0x0a, 0xbc, 0xde];
+        assert_eq!(format_mac_as(&mac, MacNotation::Colon), "00:1b:21:0a:bc:de");
+        assert_eq!(
+            format_mac_as(&mac, MacNotation::Hyphen),
+            "00-1b-21-0a-bc-de"
+        );
+        assert_eq!(format_mac_as(&mac, MacNotation::Dotted), "001b.210a.bcde");
+        assert_eq!(format_mac_as(&mac, MacNotation::Bare), "001b210abcde");
+        for n in [
+            MacNotation::Colon,
+            MacNotation::Hyphen,
+            MacNotation::Dotted,
+            MacNotation::Bare,
+        ] {
+            assert_eq!(parse_mac(&format_mac_as(&mac, n)).unwrap(), mac);
+        }
+    }
+
+    #[test]
+    fn test_eui64() {
+        let mac = [0x52, 0x54, 0x00, 0x12, 0x34, 0x56];
+        assert_eq!(
+            mac_to_eui64(&mac),
+            [0x50, 0x54, 0x00, 0xff, 0xfe, 0x12, 0x34, 0x56]
+        );
+        assert_eq!(
+            eui64_link_local(&mac),
+            "fe80::5054:ff:fe12:3456".parse::<Ipv6Addr>().unwrap()
+        );
+        assert_eq!(
+            eui64_addr("2001:db8:1:2:ffff::".parse().unwrap(), &mac),
+            "2001:db8:1:2:5054:ff:fe12:3456"
+                .parse::<Ipv6Addr>()
+                .unwrap()
+        );
+    }
 }
@@ -82,9 +82,9 @@ impl Bytes {
     /// - `m`, `mb` — 1024² bytes
     /// - `g`, `gb` — 1024³ bytes
     /// - `t`, `tb` — 1024⁴ bytes
-    /// - `kbit` — 1000 bits / 8 = 125 bytes
-    /// - `mbit` — 1_000_000 bits / 8
-    /// - `gbit` — 1_000_000_000 bits / 8
+    /// - `kbit` — 1024 bits / 8 = 128 bytes (binary, as in `tc(8)`)
+    /// - `mbit` — 1024² bits / 8
+    /// - `gbit` — 1024³ bits / 8
     pub fn parse(s: &str) -> Result<Self, BytesParseError> {
         let n = crate::util::parse::get_size(s.trim())
             .map_err(|_| BytesParseError::Invalid(s.to_string()))?;
@@ -19,12 +19,20 @@ pub enum IfError {
 
 pub type Result<T> = std::result::Result<T, IfError>;
 
-/// Validate an interface name.
+/// Validate an interface name against the kernel's `dev_valid_name()` rules.
+///
+/// A name must be 1..=15 bytes, must not be `.` or `..`, and must not contain
+/// `/`, `:`, NUL or whitespace. Checking up front turns what would otherwise
+/// be a bare `EINVAL` from `RTM_NEWLINK` into an error naming the problem.
 pub fn validate(name: &str) -> Result<()> {
     if name.is_empty() {
         return Err(IfError::InvalidName("empty name".to_string()));
     }
 
+    if name == "." || name == ".." {
+        return Err(IfError::InvalidName(format!("`{name}` is reserved")));
+    }
+
     if name.len() >= IFNAMSIZ {
         return Err(IfError::InvalidName(format!(
             "name too long (max {} chars)",
@@ -32,10 +40,10 @@ pub fn validate(name: &str) -> Result<()> {
         )));
     }
 
-    if name.contains('/') || name.contains('\0') {
-        return Err(IfError::InvalidName(
-            "name contains invalid characters".to_string(),
-        ));
+    if let Some(c) = name.chars().find(|c| matches!(c, '/' | ':' | '\0')) {
+        return Err(IfError::InvalidName(format!(
+            "name contains invalid character {c:?}"
+        )));
     }
 
     // Check for whitespace
@@ -47,48 +55,66 @@ pub fn validate(name: &str) -> Result<()> {
 }
 
 /// Convert an interface index to name.
+///
+/// Looked up through the kernel (`if_indextoname(3)`) in the calling
+/// thread's network namespace. `/sys/class/net` is not used: it shows the
+/// namespace sysfs was mounted in, which a thread that has `setns()`-ed
+/// elsewhere doesn't share.
 pub fn index_to_name(index: u32) -> Result<String> {
     if index == 0 {
         return Err(IfError::NotFound("index 0".to_string()));
     }
 
-    // Read from /sys/class/net
-    let entries = std::fs::read_dir("/sys/class/net")?;
-
-    for entry in entries.flatten() {
-        let path = entry.path().join("ifindex");
-        if let Ok(content) = std::fs::read_to_string(&path)
-            && let Ok(idx) = content.trim().parse::<u32>()
-            && idx == index
-        {
-            return Ok(entry.file_name().to_string_lossy().to_string());
-        }
+    let mut buf = [0 as libc::c_char; IFNAMSIZ];
+    // SAFETY: `buf` holds IFNAMSIZ bytes, the most if_indextoname writes.
+    let ret = unsafe { libc::if_indextoname(index, buf.as_mut_ptr()) };
+    if ret.is_null() {
+        return Err(IfError::NotFound(format!("index {}", index)));
     }
-
-    Err(IfError::NotFound(format!("index {}", index)))
+    // SAFETY: on success the buffer holds a NUL-terminated name.
+    let name = unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) };
+    Ok(name.to_string_lossy().into_owned())
 }
 
 /// Convert an interface name to index.
+///
+/// Looked up through the kernel (`if_nametoindex(3)`) in the calling
+/// thread's network namespace; see [`index_to_name`].
 pub fn name_to_index(name: &str) -> Result<u32> {
     validate(name)?;
 
-    let path = format!("/sys/class/net/{}/ifindex", name);
-    let content =
-        std::fs::read_to_string(&path).map_err(|_| IfError::NotFound(name.to_string()))?;
-
-    content
-        .trim()
-        .parse()
-        .map_err(|_| IfError::NotFound(name.to_string()))
+    let c_name =
+        std::ffi::CString::new(name).map_err(|_| IfError::InvalidName(name.to_string()))?;
+    // SAFETY: `c_name` is a valid NUL-terminated string.
+    match unsafe { libc::if_nametoindex(c_name.as_ptr()) } {
+        0 => Err(IfError::NotFound(name.to_string())),
+        index => Ok(index),
+    }
 }
 
-/// Get all interface names.
+/// Get all interface names in the calling thread's network namespace.
 pub fn list_interfaces() -> Result<Vec<String>> {
-    let entries = std::fs::read_dir("/sys/class/net")?;
+    // SAFETY: if_nameindex returns NULL or an array terminated by a
+    // zero entry, released below with if_freenameindex.
+    let list = unsafe { libc::if_nameindex() };
+    if list.is_null() {
+        return Err(std::io::Error::last_os_error().into());
+    }
 
     let mut names = Vec::new();
-    for entry in entries.flatten() {
-        names.push(entry.file_name().to_string_lossy().to_string());
+    let mut entry = list;
+    // SAFETY: every entry up to the terminator is valid, with a
+    // NUL-terminated name.
+    unsafe {
+        while (*entry).if_index != 0 {
+            names.push(
+                std::ffi::CStr::from_ptr((*entry).if_name)
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+            entry = entry.add(1);
+        }
+        libc::if_freenameindex(list);
     }
 
     names.sort();
@@ -108,6 +134,32 @@ pub fn resolve(s: &str) -> Result<(String, u32)> {
     }
 }
 
+/// Shell-style wildcard match: `*` matches any run of characters, `?`
+/// any single one, the way `ip` matches interface names and labels.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let (p, n): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+    let (mut pi, mut ni) = (0, 0);
+    // Position after the last `*`, and the name position it matched up to.
+    let mut star = None;
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi + 1, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            // Let the last `*` swallow one more character.
+            pi = sp;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,10 +176,43 @@ mod tests {
         assert!(validate("eth 0").is_err());
     }
 
+    #[test]
+    fn test_validate_kernel_rules() {
+        // 15 bytes is the longest name the kernel accepts.
+        assert!(validate("abcdefghijklmno").is_ok());
+        assert!(validate("abcdefghijklmnop").is_err());
+        assert!(validate("br-lan.100").is_ok());
+        assert!(validate("eth0@peer").is_ok());
+
+        assert!(validate(".").is_err());
+        assert!(validate("..").is_err());
+        assert!(validate("eth0:1").is_err());
+        assert!(validate("eth\0").is_err());
+        assert!(validate("eth\t0").is_err());
+
+        let err = validate("eth0:1").unwrap_err().to_string();
+        assert!(err.contains("':'"), "{err}");
+    }
+
     #[test]
     fn test_list_interfaces() {
         // This should at least find "lo"
         let interfaces = list_interfaces().unwrap();
         assert!(interfaces.contains(&"lo".to_string()));
     }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("eth0", "eth0"));
+        assert!(!glob_match("eth0", "eth01"));
+        assert!(glob_match("eth*", "eth0"));
+        assert!(glob_match("eth*", "eth"));
+        assert!(glob_match("veth-*-a", "veth-web-a"));
+        assert!(!glob_match("veth-*-a", "veth-web-b"));
+        assert!(glob_match("*.100", "eth0.100"));
+        assert!(glob_match("br?", "br0"));
+        assert!(!glob_match("br?", "br10"));
+        assert!(glob_match("*a*b", "xaab"));
+        assert!(!glob_match("*a*b", "xaba"));
+    }
 }
@@ -14,6 +14,10 @@ pub use addr::{format_addr, format_prefix, parse_addr, parse_prefix};
 pub use address_family::AddressFamily;
 pub use bytes::{Bytes, BytesParseError};
 pub use device::{get_ifindex, get_ifindex_opt, get_ifname, get_ifname_or_index};
-pub use parse::{get_rate, get_size, get_time, get_u8, get_u16, get_u32, get_u64};
+pub use ifname::glob_match;
+pub use parse::{
+    format_rate, format_size, format_time, get_rate, get_size, get_time, get_u8, get_u16, get_u32,
+    get_u64,
+};
 pub use percent::{Percent, PercentParseError};
 pub use rate::{Rate, RateParseError};
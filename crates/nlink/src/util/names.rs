@@ -1,85 +1,131 @@
 //! Name resolution utilities for protocols, scopes, tables, etc.
 
+use std::{collections::BTreeMap, path::Path, sync::RwLock};
+
+use crate::netlink::types::route::{RouteProtocol, RouteScope, RouteType};
+
+/// Routing protocol names registered at runtime, checked before the
+/// built-in table.
+static PROTOCOL_NAMES: RwLock<BTreeMap<u8, &'static str>> = RwLock::new(BTreeMap::new());
+
+/// Register a name for a routing protocol number, the way an entry in
+/// `/etc/iproute2/rt_protos` does. Later registrations for the same
+/// number win, and registered names shadow the built-in ones.
+///
+/// Meant for start-up configuration: each call keeps its name for the
+/// life of the process.
+pub fn register_protocol(id: u8, name: &str) {
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+    PROTOCOL_NAMES
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(id, name);
+}
+
+/// Register every `number name` line of an rt_protos(5) file.
+///
+/// Returns how many names were registered. Blank lines, `#` comments
+/// and malformed lines are skipped.
+pub fn load_protocol_names(path: impl AsRef<Path>) -> std::io::Result<usize> {
+    let content = std::fs::read_to_string(path)?;
+    let entries = parse_rt_protos(&content);
+    for (id, name) in &entries {
+        register_protocol(*id, name);
+    }
+    Ok(entries.len())
+}
+
+/// Register the system's protocol names the way iproute2 finds them:
+/// `/etc/iproute2/rt_protos` (or `/usr/share/iproute2/rt_protos`),
+/// then every `rt_protos.d/*.conf`. Missing files are ignored.
+///
+/// Returns how many names were registered.
+pub fn load_system_protocol_names() -> usize {
+    let mut count = 0;
+    for dir in ["/usr/share/iproute2", "/etc/iproute2"] {
+        count += load_protocol_names(Path::new(dir).join("rt_protos")).unwrap_or(0);
+        let Ok(entries) = std::fs::read_dir(Path::new(dir).join("rt_protos.d")) else {
+            continue;
+        };
+        let mut confs: Vec<_> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|ext| ext == "conf"))
+            .collect();
+        confs.sort();
+        for conf in confs {
+            count += load_protocol_names(conf).unwrap_or(0);
+        }
+    }
+    count
+}
+
+fn parse_rt_protos(content: &str) -> Vec<(u8, String)> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next()?.trim();
+            let mut fields = line.split_whitespace();
+            let id = fields.next()?;
+            let name = fields.next()?;
+            let id = match id.strip_prefix("0x") {
+                Some(hex) => u8::from_str_radix(hex, 16).ok()?,
+                None => id.parse().ok()?,
+            };
+            Some((id, name.to_string()))
+        })
+        .collect()
+}
+
+fn registered_protocol(id: u8) -> Option<&'static str> {
+    PROTOCOL_NAMES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&id)
+        .copied()
+}
+
+/// Get the name of a routing protocol, or its number if it has none
+/// (as `ip route` prints it).
+pub fn format_protocol(id: u8) -> String {
+    match protocol_name(id) {
+        "unknown" => id.to_string(),
+        name => name.to_string(),
+    }
+}
+
 /// Get the name of a routing protocol.
 pub fn protocol_name(id: u8) -> &'static str {
-    match id {
-        0 => "unspec",
-        1 => "redirect",
-        2 => "kernel",
-        3 => "boot",
-        4 => "static",
-        8 => "gated",
-        9 => "ra",
-        10 => "mrt",
-        11 => "zebra",
-        12 => "bird",
-        13 => "dnrouted",
-        14 => "xorp",
-        15 => "ntk",
-        16 => "dhcp",
-        17 => "mrouted",
-        18 => "keepalived",
-        42 => "babel",
-        186 => "bgp",
-        187 => "isis",
-        188 => "ospf",
-        189 => "rip",
-        192 => "eigrp",
-        _ => "unknown",
+    if let Some(name) = registered_protocol(id) {
+        return name;
     }
+    RouteProtocol::from(id).name()
 }
 
 /// Get protocol ID from name.
 pub fn protocol_id(name: &str) -> Option<u8> {
-    match name.to_lowercase().as_str() {
-        "unspec" => Some(0),
-        "redirect" => Some(1),
-        "kernel" => Some(2),
-        "boot" => Some(3),
-        "static" => Some(4),
-        "gated" => Some(8),
-        "ra" => Some(9),
-        "mrt" => Some(10),
-        "zebra" => Some(11),
-        "bird" => Some(12),
-        "dnrouted" => Some(13),
-        "xorp" => Some(14),
-        "ntk" => Some(15),
-        "dhcp" => Some(16),
-        "mrouted" => Some(17),
-        "keepalived" => Some(18),
-        "babel" => Some(42),
-        "bgp" => Some(186),
-        "isis" => Some(187),
-        "ospf" => Some(188),
-        "rip" => Some(189),
-        "eigrp" => Some(192),
-        _ => name.parse().ok(),
+    let registered = PROTOCOL_NAMES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(_, n)| n.eq_ignore_ascii_case(name))
+        .map(|(id, _)| *id);
+    if registered.is_some() {
+        return registered;
+    }
+    match RouteProtocol::from_builtin_name(name) {
+        Some(proto) => Some(proto.number()),
+        None => name.parse().ok(),
     }
 }
 
 /// Get the name of a route scope.
 pub fn scope_name(id: u8) -> &'static str {
-    match id {
-        0 => "global",
-        200 => "site",
-        253 => "link",
-        254 => "host",
-        255 => "nowhere",
-        _ => "unknown",
-    }
+    RouteScope::from(id).name()
 }
 
 /// Get scope ID from name.
 pub fn scope_id(name: &str) -> Option<u8> {
-    match name.to_lowercase().as_str() {
-        "global" | "universe" => Some(0),
-        "site" => Some(200),
-        "link" => Some(253),
-        "host" => Some(254),
-        "nowhere" => Some(255),
-        _ => name.parse().ok(),
-    }
+    name.parse::<RouteScope>().ok().map(u8::from)
 }
 
 /// Get the name of a routing table.
@@ -108,40 +154,12 @@ pub fn table_id(name: &str) -> Option<u32> {
 
 /// Get the name of a route type.
 pub fn route_type_name(id: u8) -> &'static str {
-    match id {
-        0 => "unspec",
-        1 => "unicast",
-        2 => "local",
-        3 => "broadcast",
-        4 => "anycast",
-        5 => "multicast",
-        6 => "blackhole",
-        7 => "unreachable",
-        8 => "prohibit",
-        9 => "throw",
-        10 => "nat",
-        11 => "xresolve",
-        _ => "unknown",
-    }
+    RouteType::from(id).name()
 }
 
 /// Get route type ID from name.
 pub fn route_type_id(name: &str) -> Option<u8> {
-    match name.to_lowercase().as_str() {
-        "unspec" => Some(0),
-        "unicast" => Some(1),
-        "local" => Some(2),
-        "broadcast" => Some(3),
-        "anycast" => Some(4),
-        "multicast" => Some(5),
-        "blackhole" => Some(6),
-        "unreachable" => Some(7),
-        "prohibit" => Some(8),
-        "throw" => Some(9),
-        "nat" => Some(10),
-        "xresolve" => Some(11),
-        _ => name.parse().ok(),
-    }
+    name.parse::<RouteType>().ok().map(u8::from)
 }
 
 /// Get the name of an address family.
@@ -189,52 +207,49 @@ pub fn operstate_name(id: u8) -> &'static str {
 }
 
 /// Format interface flags as a string.
+///
+/// Same as the [`LinkFlags`](crate::netlink::types::link::LinkFlags)
+/// `Display` impl.
 pub fn format_link_flags(flags: u32) -> String {
-    let mut parts = Vec::new();
+    crate::netlink::types::link::LinkFlags::from_bits_retain(flags).to_string()
+}
 
-    if flags & 0x1 != 0 {
-        parts.push("UP");
-    }
-    if flags & 0x2 != 0 {
-        parts.push("BROADCAST");
-    }
-    if flags & 0x8 != 0 {
-        parts.push("LOOPBACK");
-    }
-    if flags & 0x10 != 0 {
-        parts.push("POINTOPOINT");
-    }
-    if flags & 0x40 != 0 {
-        parts.push("RUNNING");
-    }
-    if flags & 0x80 != 0 {
-        parts.push("NOARP");
-    }
-    if flags & 0x100 != 0 {
-        parts.push("PROMISC");
-    }
-    if flags & 0x200 != 0 {
-        parts.push("ALLMULTI");
-    }
-    if flags & 0x400 != 0 {
-        parts.push("MASTER");
-    }
-    if flags & 0x800 != 0 {
-        parts.push("SLAVE");
-    }
-    if flags & 0x1000 != 0 {
-        parts.push("MULTICAST");
-    }
-    if flags & 0x10000 != 0 {
-        parts.push("LOWER_UP");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn link_flags_format() {
+        assert_eq!(format_link_flags(0), "NONE");
+        assert_eq!(
+            format_link_flags(0x1_1043),
+            "UP,BROADCAST,RUNNING,MULTICAST,LOWER_UP"
+        );
+        // Unknown bits are dropped rather than printed.
+        assert_eq!(format_link_flags(0x8000_0009), "UP,LOOPBACK");
     }
-    if flags & 0x20000 != 0 {
-        parts.push("DORMANT");
+
+    #[test]
+    fn rt_protos_lines() {
+        let entries = parse_rt_protos(
+            "# comment\n\n99\tmyd\n0x64 other # trailing\nbogus line\n300 toolarge\n",
+        );
+        assert_eq!(
+            entries,
+            vec![(99, "myd".to_string()), (100, "other".to_string())]
+        );
     }
 
-    if parts.is_empty() {
-        "NONE".to_string()
-    } else {
-        parts.join(",")
+    #[test]
+    fn registered_protocols_resolve_both_ways() {
+        assert_eq!(format_protocol(251), "251");
+        register_protocol(251, "testd");
+        assert_eq!(protocol_name(251), "testd");
+        assert_eq!(format_protocol(251), "testd");
+        assert_eq!(protocol_id("testd"), Some(251));
+        // Built-ins and numbers still resolve.
+        assert_eq!(protocol_id("bird"), Some(12));
+        assert_eq!(protocol_id("77"), Some(77));
+        assert_eq!(format_protocol(4), "static");
     }
 }
@@ -1,4 +1,9 @@
 //! Argument parsing utilities.
+//!
+//! The `get_*` parsers follow the `tc(8)` grammar for rates, sizes and
+//! times; [`format_rate`], [`format_size`] and [`format_time`] print values
+//! the way `tc` does. Whatever they emit parses, but like `tc`'s own output
+//! it is rounded, so only values that fit the printed unit come back exactly.
 
 use std::time::Duration;
 
@@ -147,7 +152,8 @@ pub fn get_rate(s: &str) -> Result<u64> {
 ///
 /// Supports tc-style suffixes (binary base for `k`/`m`/`g`/`t`): `b`,
 /// `k`, `kb`, `m`, `mb`, `g`, `gb`, `t`, `tb`. Bit-suffixes (`kbit`/
-/// `mbit`/`gbit`) are accepted and divided by 8 to yield bytes.
+/// `mbit`/`gbit`) are accepted and divided by 8 to yield bytes. Like
+/// `tc(8)`, they are binary too: `1kbit` is 1024 bits, i.e. 128 bytes.
 ///
 /// # New code: prefer [`crate::util::Bytes`]
 ///
@@ -170,10 +176,10 @@ pub fn get_size(s: &str) -> Result<u64> {
         "m" | "mb" | "mib" => 1024 * 1024,
         "g" | "gb" | "gib" => 1024 * 1024 * 1024,
         "t" | "tb" | "tib" => 1024u64 * 1024 * 1024 * 1024,
-        "kbit" => 1000 / 8,
-        "mbit" => 1_000_000 / 8,
-        "gbit" => 1_000_000_000 / 8,
-        "tbit" => 1_000_000_000_000 / 8,
+        "kbit" => 1024 / 8,
+        "mbit" => 1024 * 1024 / 8,
+        "gbit" => 1024 * 1024 * 1024 / 8,
+        "tbit" => 1024u64 * 1024 * 1024 * 1024 / 8,
         _ => return Err(ParseError::UnknownUnit(unit.to_string())),
     };
 
@@ -214,6 +220,87 @@ pub fn get_time(s: &str) -> Result<Duration> {
     Ok(duration)
 }
 
+/// Format a rate in **bits per second** the way `tc(8)` prints it.
+///
+/// Picks the largest decimal unit that keeps the value exact, or that
+/// leaves at least four digits: `100Mbit`, `1500Kbit`, `8bit`. The result
+/// approximately round-trips through [`get_rate`]: rates below 1 Mbit and
+/// whole multiples of the chosen unit come back exactly, anything else is
+/// truncated to that unit (`1234567` prints as `1234Kbit`).
+pub fn format_rate(bits_per_sec: u64) -> String {
+    const UNITS: [&str; 5] = ["", "K", "M", "G", "T"];
+
+    let mut rate = bits_per_sec;
+    let mut unit = 0;
+    while unit < UNITS.len() - 1 {
+        if rate < 1000 || (!rate.is_multiple_of(1000) && rate < 1_000_000) {
+            break;
+        }
+        rate /= 1000;
+        unit += 1;
+    }
+    format!("{rate}{}bit", UNITS[unit])
+}
+
+/// Format a size in **bytes** the way `tc(8)` prints it.
+///
+/// Like `tc`, a value within 1 KiB of a whole MiB prints as `Mb`, and one
+/// within 16 bytes of a whole KiB prints as `Kb`; anything else prints as
+/// bytes. The slack absorbs the rounding of bursts the kernel reports in
+/// scheduler ticks. The result approximately round-trips through
+/// [`get_size`]: whole KiB and MiB come back exactly, values inside the
+/// slack come back rounded to the unit.
+pub fn format_size(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * 1024;
+
+    let nearest = |unit: u64| (bytes + unit / 2) / unit;
+    if bytes >= MIB && (nearest(MIB) * MIB).abs_diff(bytes) < KIB {
+        format!("{}Mb", nearest(MIB))
+    } else if bytes >= KIB && (nearest(KIB) * KIB).abs_diff(bytes) < 16 {
+        format!("{}Kb", nearest(KIB))
+    } else {
+        format!("{bytes}b")
+    }
+}
+
+/// Format a duration the way `tc(8)` prints it.
+///
+/// Uses the largest of `s`, `ms` and `us` that the value reaches, with
+/// three significant digits, and whole nanoseconds below a microsecond:
+/// `1.5s`, `100ms`, `250us`, `800ns`. The result approximately
+/// round-trips through [`get_time`]: durations with more than three
+/// significant digits come back rounded.
+pub fn format_time(d: Duration) -> String {
+    let ns = d.as_nanos();
+    if ns >= 1_000_000_000 {
+        format!("{}s", significant3(ns as f64 / 1e9))
+    } else if ns >= 1_000_000 {
+        format!("{}ms", significant3(ns as f64 / 1e6))
+    } else if ns >= 1_000 {
+        format!("{}us", significant3(ns as f64 / 1e3))
+    } else {
+        format!("{ns}ns")
+    }
+}
+
+/// `printf("%.3g")` without the exponent form, which no tc parser accepts.
+fn significant3(v: f64) -> String {
+    let decimals = if v >= 100.0 {
+        0
+    } else if v >= 10.0 {
+        1
+    } else {
+        2
+    };
+    let s = format!("{v:.decimals$}");
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        s
+    }
+}
+
 /// Parse the numeric span of a value, rejecting negatives.
 ///
 /// `f64 as u64` **saturates to 0** for negative values, so `get_rate("-5mbit")`
@@ -375,4 +462,60 @@ mod tests {
         assert_eq!(get_size("1mib").unwrap(), 1024 * 1024);
         assert_eq!(get_size("1gib").unwrap(), 1024 * 1024 * 1024);
     }
+
+    /// tc's size table is binary throughout, bit suffixes included.
+    #[test]
+    fn size_bit_suffixes_are_binary_like_tc() {
+        assert_eq!(get_size("1kbit").unwrap(), 128);
+        assert_eq!(get_size("8mbit").unwrap(), 1024 * 1024);
+    }
+
+    /// Expected strings are what `tc` prints for the same values.
+    #[test]
+    fn formatters_match_tc_output() {
+        assert_eq!(format_rate(0), "0bit");
+        assert_eq!(format_rate(8), "8bit");
+        assert_eq!(format_rate(100_000_000), "100Mbit");
+        assert_eq!(format_rate(1_500_000), "1500Kbit");
+        assert_eq!(format_rate(1_234_567), "1234Kbit");
+
+        assert_eq!(format_size(1600), "1600b");
+        assert_eq!(format_size(32 * 1024), "32Kb");
+        assert_eq!(format_size(32 * 1024 + 3), "32Kb");
+        assert_eq!(format_size(2 * 1024 * 1024), "2Mb");
+
+        assert_eq!(format_time(Duration::from_millis(1500)), "1.5s");
+        assert_eq!(format_time(Duration::from_millis(100)), "100ms");
+        assert_eq!(format_time(Duration::from_micros(1234)), "1.23ms");
+        assert_eq!(format_time(Duration::from_micros(250)), "250us");
+        assert_eq!(format_time(Duration::from_nanos(800)), "800ns");
+    }
+
+    #[test]
+    fn formatted_values_parse_back() {
+        for bits in [8, 1_000, 1_500_000, 100_000_000, 10_000_000_000] {
+            assert_eq!(get_rate(&format_rate(bits)).unwrap(), bits);
+        }
+        for bytes in [1, 1600, 32 * 1024, 5 * 1024 * 1024] {
+            assert_eq!(get_size(&format_size(bytes)).unwrap(), bytes);
+        }
+        for d in [
+            Duration::from_secs(2),
+            Duration::from_millis(5),
+            Duration::from_micros(250),
+            Duration::from_nanos(800),
+        ] {
+            assert_eq!(get_time(&format_time(d)).unwrap(), d);
+        }
+    }
+
+    #[test]
+    fn formatted_values_round_to_the_printed_unit() {
+        assert_eq!(get_rate(&format_rate(1_234_567)).unwrap(), 1_234_000);
+        assert_eq!(get_size(&format_size(32 * 1024 + 3)).unwrap(), 32 * 1024);
+        assert_eq!(
+            get_time(&format_time(Duration::from_micros(1234))).unwrap(),
+            Duration::from_micros(1230)
+        );
+    }
 }
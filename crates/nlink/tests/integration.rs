@@ -64,9 +64,15 @@ mod ratelimit;
 #[path = "integration/impair.rs"]
 mod impair;
 
+#[path = "integration/mirror.rs"]
+mod mirror;
+
 #[path = "integration/diagnostics.rs"]
 mod diagnostics;
 
+#[path = "integration/stats.rs"]
+mod stats;
+
 #[path = "integration/timeout.rs"]
 mod timeout;
 
@@ -102,6 +108,31 @@ mod sockdiag_bytecode;
 #[path = "integration/sockdiag_correctness.rs"]
 mod sockdiag_correctness;
 
+// Unprivileged, like sockdiag_correctness.
+#[cfg(feature = "sockdiag")]
+#[path = "integration/sockdiag_usage.rs"]
+mod sockdiag_usage;
+
+// Candidate listing is unprivileged; the destroy half is root-gated.
+#[cfg(feature = "sockdiag")]
+#[path = "integration/sockdiag_destroy.rs"]
+mod sockdiag_destroy;
+
+// Unprivileged, like sockdiag_correctness.
+#[cfg(feature = "sockdiag")]
+#[path = "integration/sockdiag_shard.rs"]
+mod sockdiag_shard;
+
+// Unprivileged, like sockdiag_correctness.
+#[cfg(feature = "sockdiag")]
+#[path = "integration/sockdiag_unix.rs"]
+mod sockdiag_unix;
+
+// Unprivileged, like sockdiag_correctness.
+#[cfg(feature = "sockdiag")]
+#[path = "integration/sockdiag_netlink.rs"]
+mod sockdiag_netlink;
+
 #[path = "integration/neigh.rs"]
 mod neigh;
 
@@ -174,3 +205,8 @@ mod psched_ticks;
 // concurrent-request coexistence with dispatcher subscribers.
 #[path = "integration/dispatcher.rs"]
 mod dispatcher;
+
+// Transactions: a failed step or an explicit rollback undoes the
+// links, addresses and routes created so far.
+#[path = "integration/transaction.rs"]
+mod transaction;
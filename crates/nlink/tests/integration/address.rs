@@ -7,7 +7,7 @@ use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use nlink::{
     Result,
     netlink::{
-        addr::{Ipv4Address, Ipv6Address},
+        addr::{AddrLabel, Ipv4Address, Ipv6Address},
         link::DummyLink,
     },
 };
@@ -441,3 +441,59 @@ async fn test_replace_address_by_name() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_addr_label_add_replace_del() -> Result<()> {
+    // lo exists in every namespace, so no module is needed.
+    require_root!();
+
+    let ns = TestNamespace::new("addrlbl")?;
+    let conn = ns.connection()?;
+    let lo = conn
+        .get_link_by_name("lo")
+        .await?
+        .expect("lo should exist")
+        .ifindex();
+    let prefix: Ipv6Addr = "2001:db8::".parse().unwrap();
+    let find = |labels: &[nlink::netlink::messages::AddrLabelMessage]| {
+        labels
+            .iter()
+            .find(|l| l.address() == Some(prefix) && l.prefix_len() == 32)
+            .map(|l| (l.ifindex(), l.label()))
+    };
+
+    // Each namespace starts with the RFC 6724 default table.
+    let labels = conn.get_addr_labels().await?;
+    assert!(
+        labels
+            .iter()
+            .any(|l| l.address() == Some(Ipv6Addr::LOCALHOST))
+    );
+    assert_eq!(find(&labels), None);
+
+    conn.add_addr_label(AddrLabel::new(prefix, 32, 100).dev("lo"))
+        .await?;
+    assert_eq!(
+        find(&conn.get_addr_labels().await?),
+        Some((Some(lo), Some(100)))
+    );
+
+    let err = conn
+        .add_addr_label(AddrLabel::new(prefix, 32, 101).dev("lo"))
+        .await
+        .unwrap_err();
+    assert!(err.is_already_exists(), "{err}");
+
+    conn.replace_addr_label(AddrLabel::new(prefix, 32, 101).dev("lo"))
+        .await?;
+    assert_eq!(
+        find(&conn.get_addr_labels().await?),
+        Some((Some(lo), Some(101)))
+    );
+
+    conn.del_addr_label(AddrLabel::new(prefix, 32, 101).dev("lo"))
+        .await?;
+    assert_eq!(find(&conn.get_addr_labels().await?), None);
+
+    Ok(())
+}
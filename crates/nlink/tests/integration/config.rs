@@ -1,10 +1,14 @@
 //! Integration tests for declarative network configuration.
 
+use std::time::Duration;
+
 use nlink::{
-    Result,
+    Rate, Result, TcHandle,
     netlink::{
+        Route,
         config::{ApplyOptions, DeclaredLinkType, LinkState, NetworkConfig},
         link::DummyLink,
+        namespace,
     },
 };
 
@@ -124,7 +128,7 @@ fn test_veth_link_builder() {
     assert_eq!(link.name(), "veth0");
     assert!(matches!(
         link.link_type(),
-        DeclaredLinkType::Veth { peer } if peer == "veth1"
+        DeclaredLinkType::Veth { peer, .. } if peer == "veth1"
     ));
     assert_eq!(link.master(), Some("br0"));
     assert_eq!(link.state(), LinkState::Up);
@@ -394,3 +398,234 @@ async fn test_config_apply_qdisc() -> Result<()> {
     assert!(netem.is_some());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_config_apply_htb_tree_with_filters() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+    nlink::require_module!("sch_htb");
+    nlink::require_module!("sch_fq_codel");
+    nlink::require_module!("cls_flower");
+
+    let ns = TestNamespace::new("config-htb-tree")?;
+    let conn = ns.connection()?;
+
+    let root = TcHandle::major_only(1);
+    let tree = |ssh_rate: Rate| {
+        NetworkConfig::new()
+            .link("dummy0", |l| l.dummy().up())
+            .qdisc("dummy0", |q| q.htb().default_class(0x20))
+            .class("dummy0", TcHandle::new(1, 1), |c| c.htb(Rate::mbit(100)))
+            .class("dummy0", TcHandle::new(1, 0x10), |c| {
+                c.parent(TcHandle::new(1, 1))
+                    .htb(ssh_rate)
+                    .ceil(Rate::mbit(100))
+                    .prio(0)
+            })
+            .class("dummy0", TcHandle::new(1, 0x20), |c| {
+                c.parent(TcHandle::new(1, 1))
+                    .htb(Rate::mbit(40))
+                    .ceil(Rate::mbit(100))
+            })
+            .qdisc("dummy0", |q| {
+                q.fq_codel()
+                    .parent_class(TcHandle::new(1, 0x10))
+                    .handle(TcHandle::major_only(0x10))
+            })
+            .filter("dummy0", root, 10, |f| {
+                f.flower()
+                    .tcp()
+                    .dst_port(22)
+                    .classid(TcHandle::new(1, 0x10))
+            })
+            .unwrap()
+            .filter("dummy0", root, 20, |f| {
+                f.flower().dst("10.0.0.0/8").classid(TcHandle::new(1, 0x20))
+            })
+            .unwrap()
+    };
+
+    let config = tree(Rate::mbit(60));
+    let result = config.apply(&conn).await?;
+    assert!(result.is_success(), "{}", result.summary_text());
+
+    let classes = conn.get_classes_by_name("dummy0").await?;
+    assert_eq!(classes.len(), 3);
+    let filters = conn.get_filters_by_name("dummy0").await?;
+    assert!(
+        filters
+            .iter()
+            .any(|f| f.priority() == 10 && f.handle_raw() != 0)
+    );
+
+    // The kernel's view matches the declaration, so re-applying is a no-op.
+    let diff = config.diff(&conn).await?;
+    assert!(diff.classes_to_add.is_empty(), "unexpected drift:\n{diff}");
+    assert!(
+        diff.classes_to_change.is_empty(),
+        "unexpected drift:\n{diff}"
+    );
+    assert!(diff.filters_to_add.is_empty(), "unexpected drift:\n{diff}");
+    assert!(
+        diff.filters_to_replace.is_empty(),
+        "unexpected drift:\n{diff}"
+    );
+
+    // Changing one class's rate changes just that class.
+    let changed = tree(Rate::mbit(50));
+    let diff = changed.diff(&conn).await?;
+    assert_eq!(diff.classes_to_change.len(), 1, "{diff}");
+    assert_eq!(diff.classes_to_change[0].classid(), TcHandle::new(1, 0x10));
+    let result = diff.apply(&conn, ApplyOptions::default()).await?;
+    assert!(result.is_success(), "{}", result.summary_text());
+    assert!(changed.diff(&conn).await?.classes_to_change.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_config_apply_rolls_back_on_failure() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let ns = TestNamespace::new("config-rollback")?;
+    let conn = ns.connection()?;
+
+    // The link and address apply; the route's gateway isn't on-link,
+    // so the route fails and the first two steps must be undone.
+    let config = NetworkConfig::new()
+        .link("dummy0", |l| l.dummy().up())
+        .address("dummy0", "10.0.0.1/24")
+        .unwrap()
+        .route("10.9.0.0/16", |r| r.via("192.0.2.1"))
+        .unwrap();
+
+    let result = config
+        .apply_with_options(&conn, ApplyOptions::default().with_rollback(true))
+        .await?;
+    assert!(!result.is_success());
+    assert_eq!(result.errors.len(), 1);
+
+    let rollback = result.rollback.expect("rollback report");
+    assert!(rollback.is_complete(), "{}", rollback.summary_text());
+    assert_eq!(rollback.reverted.len(), 2);
+    assert!(conn.get_link_by_name("dummy0").await?.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_config_apply_rules_and_neighbors() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let ns = TestNamespace::new("config-rules")?;
+    let conn = ns.connection()?;
+
+    let config = NetworkConfig::new()
+        .link("dummy0", |l| l.dummy().up())
+        .address("dummy0", "10.0.0.1/24")
+        .unwrap()
+        .rule(1000, |r| r.from("10.0.0.0/24").table(100))
+        .unwrap()
+        .rule(1001, |r| r.fwmark(0x10).iif("dummy0").blackhole())
+        .unwrap()
+        .neighbor("dummy0", "10.0.0.2".parse().unwrap(), [2, 0, 0, 0, 0, 2]);
+
+    let result = config.apply(&conn).await?;
+    assert!(result.is_success(), "{}", result.summary_text());
+
+    let rules = conn.get_rules_v4().await?;
+    assert!(rules.iter().any(|r| r.priority() == 1000 && r.table() == 100));
+    assert!(rules.iter().any(|r| r.priority() == 1001 && r.is_blackhole()));
+    let neighbors = conn.get_neighbors_by_name("dummy0").await?;
+    assert!(neighbors.iter().any(|n| n.is_permanent()));
+
+    // The kernel's view matches the declaration, so re-applying is a no-op.
+    let diff = config.diff(&conn).await?;
+    assert!(diff.is_empty(), "unexpected drift:\n{diff}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_config_apply_namespaces() -> Result<()> {
+    require_root!();
+    nlink::require_module!("veth");
+
+    let ns = TestNamespace::new("config-netns")?;
+    let conn = ns.connection()?;
+    // Declared namespaces live under /var/run/netns, outside the
+    // test namespace's own lifetime; clean it up explicitly.
+    let host = format!("{}-host", ns.name());
+
+    let config = NetworkConfig::new()
+        .link("veth0", |l| l.veth("veth1").veth_peer_netns(&host).up())
+        .address("veth0", "10.0.0.1/24")
+        .unwrap()
+        .namespace(
+            &host,
+            NetworkConfig::new()
+                .link("veth1", |l| l.up())
+                .address("veth1", "10.0.0.2/24")
+                .unwrap()
+                .route("0.0.0.0/0", |r| r.via("10.0.0.1"))
+                .unwrap(),
+        );
+
+    let diff = config.diff(&conn).await?;
+    assert_eq!(diff.namespaces_to_create, vec![host.clone()]);
+    let result = diff.apply(&conn, ApplyOptions::default()).await;
+
+    let check = async {
+        let result = result?;
+        assert!(result.is_success(), "{}", result.summary_text());
+
+        let host_conn = namespace::connection_for::<Route>(&host)?;
+        let peer = host_conn
+            .get_link_by_name("veth1")
+            .await?
+            .expect("veth1 moved");
+        assert!(peer.is_up());
+        let addrs = host_conn.get_addresses_by_name("veth1").await?;
+        assert!(addrs.iter().any(|a| a.prefix_len() == 24));
+
+        let diff = config.diff(&conn).await?;
+        assert!(diff.is_empty(), "unexpected drift:\n{diff}");
+        Ok::<_, nlink::Error>(())
+    }
+    .await;
+
+    let _ = namespace::delete(&host);
+    check
+}
+
+#[tokio::test]
+async fn test_config_apply_confirmed_reverts_unless_confirmed() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let ns = TestNamespace::new("config-confirm")?;
+    let conn = ns.connection()?;
+
+    let config = NetworkConfig::new()
+        .link("dummy0", |l| l.dummy().up())
+        .address("dummy0", "10.0.0.1/24")
+        .unwrap();
+
+    // Left unconfirmed: reverted at the deadline, in the namespace
+    // the connection belongs to.
+    let pending = config
+        .apply_confirmed(&conn, Duration::from_millis(200))
+        .await?;
+    assert!(pending.result().is_success());
+    assert!(conn.get_link_by_name("dummy0").await?.is_some());
+    let report = pending.wait().await?;
+    assert!(report.is_complete(), "{}", report.summary_text());
+    assert!(conn.get_link_by_name("dummy0").await?.is_none());
+
+    // Confirmed: kept.
+    let pending = config
+        .apply_confirmed(&conn, Duration::from_secs(30))
+        .await?;
+    pending.confirm().await?;
+    assert!(conn.get_link_by_name("dummy0").await?.is_some());
+    Ok(())
+}
@@ -1,12 +1,14 @@
-//! Plan 148 ergonomics — `wait_link_up` + `get_link_stats`.
+//! Plan 148 ergonomics — `wait_link_up` + `get_link_stats`, plus the
+//! event-driven `wait_for_*` helpers.
 //!
 //! Mirrors §5.1 of `plans/166-0.17-integration-test-backfill-plan.md`.
-//! Each test gates on root + the `dummy` module; on a regular-user
-//! invocation `require_root!()` early-returns so the suite is no-op.
+//! Each test gates on root + the module it needs (`dummy` or
+//! `bridge`); on a regular-user invocation `require_root!()`
+//! early-returns so the suite is no-op.
 
 use std::time::Duration;
 
-use nlink::netlink::link::DummyLink;
+use nlink::netlink::link::{BridgeLink, DummyLink};
 
 use crate::common::TestNamespace;
 
@@ -144,3 +146,64 @@ async fn wireguard_diff_reports_missing_device_and_ensure_creates_it() -> nlink:
 
     Ok(())
 }
+
+#[tokio::test]
+async fn wait_for_link_up_sees_a_link_created_later() -> nlink::Result<()> {
+    require_root!();
+    nlink::require_modules!("bridge");
+
+    let ns = TestNamespace::new("wait-for-link")?;
+    let conn = ns.connection()?;
+
+    // br0 doesn't exist yet when the wait starts.
+    let ns_name = ns.name().to_string();
+    let wait_task = tokio::spawn(async move {
+        let conn = nlink::netlink::namespace::connection_for::<nlink::Route>(&ns_name)?;
+        conn.wait_for_link_up("br0", Duration::from_secs(5)).await
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    conn.add_link(BridgeLink::new("br0")).await?;
+    conn.set_link_up("br0").await?;
+
+    let link = wait_task
+        .await
+        .expect("wait task must complete")
+        .expect("wait_for_link_up must return the link once it is up");
+    assert_eq!(link.name(), Some("br0"));
+    assert!(link.is_up());
+    Ok(())
+}
+
+#[tokio::test]
+async fn wait_for_address_and_route_return_existing_state() -> nlink::Result<()> {
+    require_root!();
+    nlink::require_modules!("bridge");
+
+    let ns = TestNamespace::new("wait-for-addr")?;
+    let conn = ns.connection()?;
+    let link = conn
+        .create_link_and_up(
+            BridgeLink::new("br0"),
+            &[("10.98.0.1".parse().unwrap(), 24)],
+        )
+        .await?;
+
+    let timeout = Duration::from_secs(5);
+    let addr = conn
+        .wait_for_address("br0", "10.98.0.1".parse().unwrap(), 24, timeout)
+        .await?;
+    assert_eq!(addr.ifindex(), link.ifindex());
+
+    // The prefix route the kernel adds with the address.
+    let route = conn
+        .wait_for_route("10.98.0.0".parse().unwrap(), 24, timeout)
+        .await?;
+    assert_eq!(route.dst_len(), 24);
+
+    let err = conn
+        .wait_for_route("10.97.0.0".parse().unwrap(), 24, Duration::from_millis(200))
+        .await
+        .expect_err("no such route");
+    assert!(err.is_timeout(), "expected timeout, got {err:?}");
+    Ok(())
+}
@@ -382,3 +382,130 @@ async fn test_ipv6_address_events() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_rule_and_nexthop_events() -> Result<()> {
+    require_root!();
+
+    use nlink::{
+        AddressFamily,
+        netlink::{nexthop::NexthopBuilder, rule::RuleBuilder},
+    };
+
+    let ns = TestNamespace::new("rulenhev")?;
+    let conn = ns.connection()?;
+    // Blackhole nexthops hang off the loopback device.
+    conn.set_link_up("lo").await?;
+
+    conn.subscribe(&[RtnetlinkGroup::Ipv4Rule, RtnetlinkGroup::Nexthop])?;
+    let mut events = conn.events().await;
+
+    {
+        let conn2 = ns.connection()?;
+        conn2
+            .add_rule(
+                RuleBuilder::new(AddressFamily::v4().as_u8())
+                    .priority(300)
+                    .fwmark(7)
+                    .table(100),
+            )
+            .await?;
+        conn2
+            .add_nexthop(NexthopBuilder::new(6).blackhole())
+            .await?;
+        conn2.del_rule_by_priority(AddressFamily::v4(), 300).await?;
+    }
+
+    let mut seen = Vec::new();
+    while seen.len() < 3 {
+        match tokio::time::timeout(Duration::from_secs(2), events.next()).await {
+            Ok(Some(event)) => seen.push(event?),
+            _ => break,
+        }
+    }
+
+    assert!(matches!(&seen[0], NetworkEvent::NewRule(r)
+        if r.priority() == 300 && r.fwmark() == Some(7) && r.table() == 100));
+    assert!(matches!(&seen[1], NetworkEvent::NewNexthop(nh)
+        if nh.id() == 6 && nh.is_blackhole()));
+    assert!(matches!(&seen[2], NetworkEvent::DelRule(r) if r.priority() == 300));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_network_state_follows_events() -> Result<()> {
+    require_root!();
+    nlink::require_module!("veth");
+
+    use nlink::netlink::{NetworkState, link::VethLink, route::Ipv4Route};
+
+    let ns = TestNamespace::new("netstate")?;
+    let conn = ns.connection()?;
+    conn.subscribe(NetworkState::GROUPS)?;
+    let state = NetworkState::load(&ns.connection()?).await?;
+    let mut events = state.track(conn.events().await);
+
+    // Apply everything queued until the socket goes quiet.
+    async fn settle(events: &mut (impl tokio_stream::Stream<Item = Result<NetworkEvent>> + Unpin)) {
+        while let Ok(Some(_)) =
+            tokio::time::timeout(Duration::from_millis(500), events.next()).await
+        {}
+    }
+
+    let conn2 = ns.connection()?;
+    conn2.add_link(VethLink::new("veth0", "veth1")).await?;
+    conn2.set_link_up("veth0").await?;
+    conn2.set_link_up("veth1").await?;
+    conn2
+        .add_address(Ipv4Address::new("veth0", Ipv4Addr::new(10, 20, 0, 1), 24))
+        .await?;
+    conn2
+        .add_route(Ipv4Route::new("10.30.0.0", 16).gateway(Ipv4Addr::new(10, 20, 0, 2)))
+        .await?;
+    settle(&mut events).await;
+
+    let veth0 = state.link_by_name("veth0").expect("veth0 cached");
+    let addr = IpAddr::V4(Ipv4Addr::new(10, 20, 0, 1));
+    assert!(
+        state
+            .addr_of(veth0.ifindex())
+            .iter()
+            .any(|a| a.primary_address() == Some(&addr))
+    );
+    let route = state
+        .route_lookup("10.30.1.1".parse().unwrap())
+        .expect("route cached");
+    assert_eq!(
+        route.gateway(),
+        Some(&IpAddr::V4(Ipv4Addr::new(10, 20, 0, 2)))
+    );
+    assert_eq!(route.oif(), Some(veth0.ifindex()));
+
+    // The kernel drops IPv4 routes through a downed link without telling us.
+    conn2.set_link_down("veth0").await?;
+    settle(&mut events).await;
+    assert!(state.route_lookup("10.30.1.1".parse().unwrap()).is_none());
+
+    let key = |r: &nlink::netlink::messages::RouteMessage| {
+        (
+            r.family(),
+            r.table_id(),
+            r.destination().copied(),
+            r.dst_len(),
+            r.oif(),
+        )
+    };
+    let fresh = NetworkState::load(&conn2).await?;
+    let mut cached: Vec<_> = state.routes().iter().map(key).collect();
+    let mut dumped: Vec<_> = fresh.routes().iter().map(key).collect();
+    cached.sort();
+    dumped.sort();
+    assert_eq!(cached, dumped);
+    // Tentative IPv6 addresses are dumped but not announced until DAD
+    // completes, so only IPv4 addresses are certain to match.
+    let v4 = |s: &NetworkState| s.addresses().iter().filter(|a| a.family() == 2).count();
+    assert_eq!(v4(&state), v4(&fresh));
+
+    Ok(())
+}
@@ -7,7 +7,7 @@ use nlink::{
     netlink::link::{
         BondLink, BondMode, BridgeLink, DummyLink, GreLink, GretapLink, IfbLink, IpipLink,
         IpvlanLink, LacpRate, MacvlanLink, MacvlanMode, NetkitLink, NetkitMode, NetkitPolicy,
-        SitLink, VethLink, VlanLink, VrfLink, XmitHashPolicy,
+        SitLink, VethLink, VlanLink, VrfLink, WireguardLink, XmitHashPolicy,
     },
 };
 
@@ -54,6 +54,72 @@ async fn test_create_dummy_interface() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_set_links_up_batched() -> Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy");
+
+    let ns = TestNamespace::new("linksup")?;
+    let conn = ns.connection()?;
+
+    let names: Vec<String> = (0..8).map(|i| format!("dummy{i}")).collect();
+    for name in &names {
+        conn.add_link(DummyLink::new(name)).await?;
+    }
+
+    let results = conn.set_links_up(&names).await?;
+    assert!(results.all_ok());
+    let links = conn.get_links().await?;
+    for name in &names {
+        let link = links.iter().find(|l| l.name() == Some(name)).unwrap();
+        assert!(link.is_up(), "{name} should be up");
+    }
+
+    // An unknown name fails on its own; the others still go down.
+    let results = conn
+        .set_links_down(&["dummy0", "nonexistent", "dummy1"])
+        .await?;
+    let errors: Vec<_> = results.errors().collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 1);
+    assert!(errors[0].1.is_not_found());
+    let links = conn.get_links().await?;
+    for name in ["dummy0", "dummy1"] {
+        let link = links.iter().find(|l| l.name() == Some(name)).unwrap();
+        assert!(!link.is_up(), "{name} should be down");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_tune_link() -> Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy");
+
+    let ns = TestNamespace::new("tune")?;
+    let conn = ns.connection()?;
+    conn.add_link(DummyLink::new("dummy0")).await?;
+
+    conn.tune_link("dummy0", |t| {
+        t.mtu(9000).txqueuelen(10000).gso_max_size(32768)
+    })
+    .await?;
+
+    let link = conn.get_link_by_name("dummy0").await?.unwrap();
+    assert_eq!(link.mtu(), Some(9000));
+    assert_eq!(link.txqlen(), Some(10000));
+    assert_eq!(link.gso_max_size(), Some(32768));
+
+    let err = conn
+        .tune_link("dummy0", |t| t.gso_max_size(u32::MAX))
+        .await
+        .unwrap_err();
+    assert!(err.is_invalid_argument(), "{err}");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_create_veth_pair() -> Result<()> {
     require_root!();
@@ -320,6 +386,104 @@ async fn test_rename_interface() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_cached_names_follow_rename_and_recreate() -> Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy");
+
+    let ns = TestNamespace::new("ifcache")?;
+    let conn = ns.connection()?;
+
+    conn.add_link(DummyLink::new("cached0")).await?;
+    let first = conn.resolve_interface(&"cached0".into()).await?;
+
+    // A rename through the connection drops the cached name...
+    conn.set_link_name("cached0", "moved0").await?;
+    conn.add_link(DummyLink::new("cached0")).await?;
+    let second = conn.resolve_interface(&"cached0".into()).await?;
+    assert_ne!(first, second, "cached0 now names the new interface");
+
+    // ...and del_link recovers from an entry made stale behind its back.
+    ns.exec("ip", &["link", "del", "cached0"])?;
+    ns.exec("ip", &["link", "add", "cached0", "type", "dummy"])?;
+    conn.del_link("cached0").await?;
+    assert!(conn.get_link_by_name("cached0").await?.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cached_names_survive_recreate_through_another_connection() -> Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy");
+
+    let ns = TestNamespace::new("ifcache2")?;
+    let conn = ns.connection()?;
+    let other = ns.connection()?;
+
+    conn.add_link(DummyLink::new("cached0")).await?;
+    conn.set_link_up("cached0").await?;
+    let first = conn.resolve_interface(&"cached0".into()).await?;
+
+    // Recreated behind `conn`'s back: the next name-based op looks the
+    // name up again instead of failing on the stale index.
+    other.del_link("cached0").await?;
+    other.add_link(DummyLink::new("cached0")).await?;
+    conn.set_link_up("cached0").await?;
+    conn.add_address_by_name("cached0", "10.0.0.1".parse().unwrap(), 24)
+        .await?;
+    let second = conn.resolve_interface(&"cached0".into()).await?;
+    assert_ne!(first, second, "cached0 now names the new interface");
+    let v4 = conn.get_addresses_by_index(second).await?;
+    assert_eq!(v4.iter().filter(|a| a.is_ipv4()).count(), 1);
+
+    // Deleted elsewhere and recreated through `conn`: add_link drops
+    // the stale entry, so addresses land on the new interface.
+    other.del_link("cached0").await?;
+    conn.add_link(DummyLink::new("cached0")).await?;
+    conn.add_address_by_name("cached0", "10.0.0.2".parse().unwrap(), 24)
+        .await?;
+    let third = other.resolve_interface(&"cached0".into()).await?;
+    assert_eq!(conn.resolve_interface(&"cached0".into()).await?, third);
+    let v4 = conn.get_addresses_by_index(third).await?;
+    assert_eq!(v4.iter().filter(|a| a.is_ipv4()).count(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cached_names_follow_external_rename_in_dumps() -> Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy");
+
+    let ns = TestNamespace::new("ifcache3")?;
+    let conn = ns.connection()?;
+    let other = ns.connection()?;
+
+    conn.add_link(DummyLink::new("cached0")).await?;
+    conn.add_address_by_name("cached0", "10.0.0.1".parse().unwrap(), 24)
+        .await?;
+    assert_eq!(conn.get_addresses_by_name("cached0").await?.len(), 1);
+
+    // Renamed elsewhere, and the name reused: dumps by name must read
+    // the new interface, not the one the cached index still points at.
+    other.set_link_name("cached0", "moved0").await?;
+    other.add_link(DummyLink::new("cached0")).await?;
+    assert!(conn.get_addresses_by_name("cached0").await?.is_empty());
+    assert_eq!(
+        conn.resolve_interface(&"cached0".into()).await?,
+        other.resolve_interface(&"cached0".into()).await?
+    );
+
+    // Deleted elsewhere: a dump by name reports the missing interface
+    // instead of an empty result for the dead index.
+    other.del_link("cached0").await?;
+    let err = conn.get_addresses_by_name("cached0").await.unwrap_err();
+    assert!(err.is_not_found());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_set_mac_address() -> Result<()> {
     require_root!();
@@ -455,6 +619,37 @@ async fn test_interface_flags() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_promiscuous_and_allmulticast() -> Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy");
+
+    let ns = TestNamespace::new("promisc")?;
+    let conn = ns.connection()?;
+
+    conn.add_link(DummyLink::new("dummy0")).await?;
+
+    conn.set_promiscuous("dummy0", true).await?;
+    // Setting it twice holds a single reference.
+    conn.set_promiscuous("dummy0", true).await?;
+    conn.set_allmulticast("dummy0", true).await?;
+
+    let dummy = conn.get_link_by_name("dummy0").await?.unwrap();
+    assert!(dummy.link_flags().is_promisc());
+    assert!(dummy.link_flags().is_allmulti());
+    assert_eq!(dummy.promiscuity(), Some(1));
+
+    conn.set_promiscuous("dummy0", false).await?;
+    conn.set_allmulticast("dummy0", false).await?;
+
+    let dummy = conn.get_link_by_name("dummy0").await?.unwrap();
+    assert!(!dummy.link_flags().is_promisc());
+    assert!(!dummy.link_flags().is_allmulti());
+    assert_eq!(dummy.promiscuity(), Some(0));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_loopback_exists() -> Result<()> {
     require_root!();
@@ -734,3 +929,75 @@ async fn test_create_netkit_pair() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_create_link_and_up() -> Result<()> {
+    require_root!();
+    nlink::require_modules!("bridge");
+
+    let ns = TestNamespace::new("create-up")?;
+    let conn = ns.connection()?;
+
+    let link = conn
+        .create_link_and_up(
+            BridgeLink::new("br0").mtu(1400),
+            &[
+                ("10.99.0.1".parse().unwrap(), 24),
+                ("fd99::1".parse().unwrap(), 64),
+            ],
+        )
+        .await?;
+    assert_eq!(link.name(), Some("br0"));
+    assert!(link.is_up(), "br0 should be up");
+    assert_eq!(link.mtu(), Some(1400));
+
+    let addrs = conn.get_addresses_by_index(link.ifindex()).await?;
+    let addrs: Vec<_> = addrs.iter().filter_map(|a| a.address().copied()).collect();
+    assert!(addrs.contains(&"10.99.0.1".parse().unwrap()));
+    assert!(addrs.contains(&"fd99::1".parse().unwrap()));
+
+    // A second create fails like add_link does, before touching br0.
+    let err = conn
+        .create_link_and_up(BridgeLink::new("br0"), &[])
+        .await
+        .unwrap_err();
+    assert!(err.is_already_exists(), "{err}");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_wireguard() -> Result<()> {
+    require_root!();
+    nlink::require_module!("wireguard");
+    use nlink::netlink::{Wireguard, genl::wireguard::AllowedIp, namespace};
+    use std::net::Ipv4Addr;
+
+    let ns = TestNamespace::new("create-wg")?;
+    let conn = ns.connection()?;
+
+    let peer_pk = [0xbbu8; 32];
+    conn.create_wireguard(WireguardLink::new("wg0").mtu(1420), |d| {
+        d.private_key([0xaa; 32])
+            .listen_port(51820)
+            .peer(peer_pk, |p| {
+                p.allowed_ip(AllowedIp::v4(Ipv4Addr::new(10, 0, 0, 0), 24))
+            })
+    })
+    .await?;
+
+    let link = conn
+        .get_link_by_name("wg0")
+        .await?
+        .expect("wg0 should exist");
+    assert_eq!(link.kind(), Some("wireguard"));
+    assert_eq!(link.mtu(), Some(1420));
+
+    let wg = namespace::connection_for_async::<Wireguard>(ns.name()).await?;
+    let device = wg.get_device_by_name("wg0").await?;
+    assert_eq!(device.listen_port, Some(51820));
+    assert_eq!(device.peers.len(), 1);
+    assert_eq!(device.peers[0].public_key, peer_pk);
+
+    Ok(())
+}
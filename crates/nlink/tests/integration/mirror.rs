@@ -0,0 +1,111 @@
+//! Integration tests for the port mirroring helper.
+//!
+//! These require root + a kernel with `cls_matchall`, `cls_flower` and
+//! `act_mirred` loaded.
+
+use nlink::netlink::{
+    filter::FlowerFilter,
+    link::DummyLink,
+    mirror::{MirrorDirection, PortMirror},
+    tc_handle::FilterPriority,
+};
+
+use crate::common::TestNamespace;
+
+async fn two_dummies(ns: &TestNamespace) -> nlink::Result<(u32, u32)> {
+    let conn = ns.connection()?;
+    conn.add_link(DummyLink::new("src0")).await?;
+    conn.add_link(DummyLink::new("mon0")).await?;
+    conn.set_link_up("src0").await?;
+    conn.set_link_up("mon0").await?;
+    let src = conn.get_link_by_name("src0").await?.expect("src0 exists");
+    let mon = conn.get_link_by_name("mon0").await?.expect("mon0 exists");
+    Ok((src.ifindex(), mon.ifindex()))
+}
+
+#[tokio::test]
+async fn test_apply_mirrors_both_hooks_and_clear_removes_clsact() -> nlink::Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy", "cls_matchall", "act_mirred");
+
+    let ns = TestNamespace::new("mirror_both")?;
+    let conn = ns.connection()?;
+    let (src, _) = two_dummies(&ns).await?;
+
+    let mirror = PortMirror::new("src0", "mon0");
+    mirror.apply(&conn).await?;
+    // Re-applying replaces rather than duplicates.
+    mirror.apply(&conn).await?;
+
+    let qdiscs = conn.get_qdiscs_by_index(src).await?;
+    assert!(qdiscs.iter().any(|q| q.is_clsact()), "clsact should exist");
+
+    let filters = conn.get_filters_by_index(src).await?;
+    let mirrors: Vec<_> = filters
+        .iter()
+        .filter(|f| f.kind() == Some("matchall"))
+        .collect();
+    assert_eq!(mirrors.len(), 2, "one matchall per hook");
+    assert!(mirrors.iter().all(|f| f.priority() == 100));
+
+    mirror.clear(&conn).await?;
+    let qdiscs = conn.get_qdiscs_by_index(src).await?;
+    assert!(
+        !qdiscs.iter().any(|q| q.is_clsact()),
+        "clsact should be gone once its last filter is"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clear_keeps_clsact_in_use() -> nlink::Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy", "cls_matchall", "cls_flower", "act_mirred");
+
+    let ns = TestNamespace::new("mirror_shared")?;
+    let conn = ns.connection()?;
+    let (src, mon) = two_dummies(&ns).await?;
+
+    let all = PortMirror::new_by_index(src, mon).direction(MirrorDirection::Ingress);
+    let https = PortMirror::new_by_index(src, mon)
+        .direction(MirrorDirection::Egress)
+        .matching(FlowerFilter::new().ip_proto_tcp().dst_port(443))
+        .priority(FilterPriority::recipe(1));
+    all.apply(&conn).await?;
+    https.apply(&conn).await?;
+
+    all.clear(&conn).await?;
+    let qdiscs = conn.get_qdiscs_by_index(src).await?;
+    assert!(
+        qdiscs.iter().any(|q| q.is_clsact()),
+        "clsact still carries the flower mirror"
+    );
+    let filters = conn.get_filters_by_index(src).await?;
+    assert!(filters.iter().all(|f| f.kind() != Some("matchall")));
+    assert!(filters.iter().any(|f| f.kind() == Some("flower")));
+
+    https.clear(&conn).await?;
+    let qdiscs = conn.get_qdiscs_by_index(src).await?;
+    assert!(!qdiscs.iter().any(|q| q.is_clsact()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_rejects_mirror_to_self() -> nlink::Result<()> {
+    require_root!();
+    nlink::require_modules!("dummy");
+
+    let ns = TestNamespace::new("mirror_self")?;
+    let conn = ns.connection()?;
+    two_dummies(&ns).await?;
+
+    let err = PortMirror::new("src0", "src0")
+        .apply(&conn)
+        .await
+        .expect_err("mirroring a port to itself must fail");
+    assert!(err.to_string().contains("same interface"), "{err}");
+
+    Ok(())
+}
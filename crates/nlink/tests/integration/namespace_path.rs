@@ -89,3 +89,68 @@ async fn test_is_namespace_path_tracks_lifecycle() -> Result<()> {
 // Root-free cases (already-exists rejection, missing-path not-found,
 // stale-marker → false) live as unit tests in `namespace.rs`; this file
 // covers only the privileged round-trips above.
+
+#[tokio::test]
+async fn test_current_namespace_identifies_the_bound_netns() -> Result<()> {
+    require_root!();
+
+    let scratch = ScratchDir::new("curns");
+    let ns_path = scratch.join("myns");
+    namespace::create_path(&ns_path)?;
+
+    let host = Connection::<Route>::new()?;
+    assert_eq!(host.current_namespace()?, namespace::NamespaceId::of_thread()?);
+    host.ensure_thread_namespace()?;
+
+    let conn: Connection<Route> = namespace::connection_for_path(&ns_path)?;
+    let bound = conn.current_namespace()?;
+    assert_eq!(bound, namespace::NamespaceId::of_path(&ns_path)?);
+    assert_ne!(bound, host.current_namespace()?);
+
+    let err = conn
+        .ensure_thread_namespace()
+        .expect_err("a foreign-netns connection must not match the thread");
+    assert!(err.is_namespace_mismatch());
+    drop(conn);
+
+    namespace::delete_path(&ns_path)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_mirred_dev_resolved_on_host_is_rejected_in_foreign_netns() -> Result<()> {
+    use nlink::netlink::{
+        action::{ActionList, MirredAction},
+        filter::MatchallFilter,
+        tc_handle::TcHandle,
+    };
+
+    require_root!();
+
+    let scratch = ScratchDir::new("mirred");
+    let ns_path = scratch.join("myns");
+    namespace::create_path(&ns_path)?;
+    let conn: Connection<Route> = namespace::connection_for_path(&ns_path)?;
+
+    // `dev lo` is looked up in the test thread's (host) namespace.
+    let mirred = MirredAction::parse_params(&["redirect", "dev", "lo"])?;
+
+    let err = conn
+        .add_action(mirred.clone())
+        .await
+        .expect_err("a host ifindex must not be sent into the foreign netns");
+    assert!(err.is_namespace_mismatch());
+
+    let filter = MatchallFilter::new()
+        .actions(ActionList::new().with(mirred))
+        .build();
+    let err = conn
+        .add_filter("lo", TcHandle::INGRESS, filter)
+        .await
+        .expect_err("filter actions carry the same check");
+    assert!(err.is_namespace_mismatch());
+    drop(conn);
+
+    namespace::delete_path(&ns_path)?;
+    Ok(())
+}
@@ -6,13 +6,14 @@
 //! the ENOENT failure without the fix; the others are guards
 //! against future drift in the unicast and ext_learned paths.
 
-use std::net::Ipv6Addr;
+use std::{net::Ipv6Addr, time::Duration};
 
 use nlink::{
     Connection, Result, Route,
     netlink::{
         link::DummyLink,
-        neigh::{Neighbor, ntf},
+        neigh::{Neighbor, NeighborTableParams, ntf},
+        types::neigh::nud,
     },
 };
 
@@ -41,8 +42,8 @@ async fn test_delete_proxy_ndp_entry_round_trip() -> Result<()> {
     let target: Ipv6Addr = "fd00:abcd::1".parse().unwrap();
 
     // Proxy NDP entries live in the kernel's pneigh table, which the
-    // bare RTM_GETNEIGH dump used by get_neighbors() doesn't surface
-    // (the dump request would need NTF_PROXY set in its ndm_flags).
+    // bare RTM_GETNEIGH dump used by get_neighbors() doesn't surface;
+    // get_proxy_neighbors() sets NTF_PROXY in the dump request.
     // The bug being fixed is in the *delete* path, so the assertion
     // we care about is: a second delete after a successful add must
     // not ENOENT due to the kernel failing to match on ndm_flags.
@@ -53,6 +54,22 @@ async fn test_delete_proxy_ndp_entry_round_trip() -> Result<()> {
     )
     .await?;
 
+    let proxies = conn.get_proxy_neighbors().await?;
+    assert!(
+        proxies.iter().any(|n| n.ifindex() == ifindex
+            && n.destination() == Some(&target.into())
+            && n.is_proxy()),
+        "proxy entry should be in the proxy dump"
+    );
+    assert!(
+        !conn
+            .get_neighbors()
+            .await?
+            .iter()
+            .any(|n| n.destination() == Some(&target.into())),
+        "proxy entry should not be in the neighbor cache dump"
+    );
+
     // Without the write_delete fix this returns ENOENT — ndm_flags
     // wasn't being propagated, so the kernel pneigh lookup missed.
     conn.del_neighbor(Neighbor::with_index_v6(ifindex, target).proxy())
@@ -157,3 +174,97 @@ async fn test_delete_ext_learned_entry_round_trip() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_flush_neighbors_by_state() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let (_ns, conn, ifindex) = setup("neigh-flush").await?;
+    let stale: Ipv6Addr = "fd00:abcd::10".parse().unwrap();
+    let permanent: Ipv6Addr = "fd00:abcd::11".parse().unwrap();
+    let lladdr = [0x02, 0x00, 0x00, 0x00, 0x00, 0x03];
+
+    conn.add_neighbor(
+        Neighbor::with_index_v6(ifindex, stale)
+            .lladdr(lladdr)
+            .stale(),
+    )
+    .await?;
+    conn.add_neighbor(
+        Neighbor::with_index_v6(ifindex, permanent)
+            .lladdr(lladdr)
+            .permanent(),
+    )
+    .await?;
+
+    let present = |neighbors: &[nlink::netlink::messages::NeighborMessage], addr: Ipv6Addr| {
+        neighbors
+            .iter()
+            .any(|n| n.destination() == Some(&addr.into()))
+    };
+
+    // Only REACHABLE requested: nothing matches.
+    assert_eq!(conn.flush_neighbors("dummy0", nud::REACHABLE).await?, 0);
+
+    // The default mask keeps permanent entries.
+    assert_eq!(conn.flush_neighbors("dummy0", nud::FLUSHABLE).await?, 1);
+    let neighbors = conn.get_neighbors_by_index(ifindex).await?;
+    assert!(!present(&neighbors, stale));
+    assert!(present(&neighbors, permanent));
+
+    assert_eq!(conn.flush_neighbors_by_index(ifindex, nud::ALL).await?, 1);
+    let neighbors = conn.get_neighbors_by_index(ifindex).await?;
+    assert!(!present(&neighbors, permanent));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_neigh_table_params_round_trip() -> Result<()> {
+    // lo exists in every namespace, so no module is needed.
+    require_root!();
+
+    let ns = TestNamespace::new("neigh-tbl")?;
+    let conn = ns.connection()?;
+    let lo = conn
+        .get_link_by_name("lo")
+        .await?
+        .expect("lo should exist")
+        .ifindex();
+
+    let tables = conn.get_neigh_tables().await?;
+    assert!(
+        tables
+            .iter()
+            .any(|t| t.name() == Some("arp_cache") && t.gc_thresh3().is_some()),
+        "arp_cache table-wide message should carry the gc thresholds"
+    );
+
+    conn.set_neigh_table_params(
+        NeighborTableParams::arp()
+            .dev("lo")
+            .base_reachable_time(Duration::from_secs(60))
+            .ucast_probes(7),
+    )
+    .await?;
+
+    let tables = conn.get_neigh_tables().await?;
+    let parms = tables
+        .iter()
+        .find(|t| t.name() == Some("arp_cache") && t.ifindex() == Some(lo))
+        .and_then(|t| t.parms())
+        .expect("lo should have arp_cache parameters");
+    assert_eq!(parms.base_reachable_time, Some(Duration::from_secs(60)));
+    assert_eq!(parms.ucast_probes, Some(7));
+
+    // The gc thresholds are global; the kernel refuses them outside the
+    // initial namespace.
+    let err = conn
+        .set_neigh_table_params(NeighborTableParams::arp().gc_thresh1(64))
+        .await
+        .unwrap_err();
+    assert!(err.is_not_found(), "got {err}");
+
+    Ok(())
+}
@@ -502,3 +502,114 @@ async fn purge_removes_static_route_keeps_connected_route() -> Result<()> {
     })
     .await
 }
+
+/// With an owner tag, apply stamps it on the routes and addresses it
+/// creates, and purge removes only undeclared resources carrying it:
+/// another owner's route, an untagged `boot` route and an untagged
+/// address all survive.
+#[tokio::test]
+async fn purge_with_owner_removes_only_tagged_resources() -> Result<()> {
+    nlink::require_root!();
+
+    with_timeout(async {
+        use std::net::{IpAddr, Ipv4Addr};
+
+        use nlink::netlink::addr::Ipv4Address;
+        use nlink::netlink::link::DummyLink;
+        use nlink::netlink::route::Ipv4Route;
+        use nlink::netlink::types::route::RouteProtocol;
+
+        let ns = TestNamespace::new("purge-owner")?;
+        let conn = conn_in_ns(&ns)?;
+
+        conn.add_link(DummyLink::new("eth0")).await?;
+        conn.set_link_up("eth0").await?;
+        conn.add_address(Ipv4Address::new("eth0", Ipv4Addr::new(10, 0, 0, 9), 24))
+            .await?;
+        conn.add_route(Ipv4Route::new("10.8.0.0", 16).dev("eth0"))
+            .await?;
+        conn.add_route(Ipv4Route::new("10.9.0.0", 16).dev("eth0").protocol_id(201))
+            .await?;
+
+        let declare = |routes: &[&str], addrs: &[&str]| {
+            let mut cfg = NetworkConfig::new().owner(200).link("eth0", |b| b.dummy());
+            for a in addrs {
+                cfg = cfg.address("eth0", a).expect("valid CIDR");
+            }
+            for r in routes {
+                cfg = cfg.route(r, |r| r.dev("eth0")).expect("valid CIDR");
+            }
+            cfg
+        };
+
+        let _ = declare(
+            &["10.1.0.0/16", "10.2.0.0/16"],
+            &["10.0.0.1/24", "10.0.0.2/24"],
+        )
+        .apply(&conn)
+        .await?;
+        let routes = conn.get_routes().await?;
+        let tagged: Vec<_> = routes
+            .iter()
+            .filter(|r| r.protocol() == RouteProtocol::Unknown(200))
+            .filter_map(|r| r.destination().copied())
+            .collect();
+        assert_eq!(
+            tagged.len(),
+            2,
+            "declared routes carry the tag; got {tagged:?}"
+        );
+        // IFA_PROTO is kernel 6.3+; older kernels drop the tag.
+        let addrs_tagged = conn
+            .get_addresses()
+            .await?
+            .iter()
+            .any(|a| a.protocol() == Some(200));
+
+        let cfg = declare(&["10.1.0.0/16"], &["10.0.0.1/24"]);
+        let diff = cfg
+            .diff_with_options(&conn, DiffOptions::default().purge(true))
+            .await?;
+        let removed: Vec<(IpAddr, u8)> = diff
+            .routes_to_remove
+            .iter()
+            .map(|r| (r.destination(), r.prefix_len()))
+            .collect();
+        assert_eq!(removed, vec![(IpAddr::V4(Ipv4Addr::new(10, 2, 0, 0)), 16)]);
+        let removed: Vec<IpAddr> = diff
+            .addresses_to_remove
+            .iter()
+            .map(|a| a.address())
+            .collect();
+        if addrs_tagged {
+            assert_eq!(removed, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2))]);
+        } else {
+            assert!(removed.is_empty(), "untagged addresses are never purged");
+        }
+
+        let _ = diff.apply(&conn, Default::default()).await?;
+        let routes = conn.get_routes().await?;
+        for kept in [
+            Ipv4Addr::new(10, 1, 0, 0),
+            Ipv4Addr::new(10, 8, 0, 0),
+            Ipv4Addr::new(10, 9, 0, 0),
+        ] {
+            assert!(
+                routes
+                    .iter()
+                    .any(|r| r.destination() == Some(&IpAddr::V4(kept))),
+                "{kept} must survive purge"
+            );
+        }
+        let addrs = conn.get_addresses().await?;
+        assert!(
+            addrs
+                .iter()
+                .any(|a| a.address() == Some(&IpAddr::V4(Ipv4Addr::new(10, 0, 0, 9)))),
+            "untagged address must survive purge"
+        );
+
+        Ok(())
+    })
+    .await
+}
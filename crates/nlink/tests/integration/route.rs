@@ -9,8 +9,9 @@ use nlink::{
     netlink::{
         addr::Ipv4Address,
         link::DummyLink,
-        route::{Ipv4Route, Ipv6Route, NextHop, RouteMetrics},
-        types::route::RouteType,
+        nexthop::{NexthopBuilder, NexthopGroupBuilder},
+        route::{Ipv4Route, Ipv6Route, NextHop, RouteMetrics, RouteSelector},
+        types::route::{RouteProtocol, RouteType},
     },
 };
 
@@ -58,6 +59,42 @@ async fn test_add_route_via_interface() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_batch_final_ack_reports_only_failures() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let (_ns, conn) = setup_routed_ns("rtbatch").await?;
+    let ifindex = conn.get_link_by_name("dummy0").await?.unwrap().ifindex();
+
+    // 500 routes, with the tenth added twice.
+    let mut batch = conn.batch().final_ack();
+    for i in 0..500u32 {
+        let dst = Ipv4Addr::from(0x0a00_0000 | (i << 8));
+        batch = batch.add_route(Ipv4Route::from_addr(dst, 24).dev_index(ifindex));
+    }
+    let dup = Ipv4Route::new("10.0.9.0", 24).dev_index(ifindex);
+    let results = batch
+        .add_route(dup.clone())
+        .replace_route(dup)
+        .execute()
+        .await?;
+
+    assert_eq!(results.len(), 502);
+    let errors: Vec<_> = results.errors().collect();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].0, 500);
+    assert!(errors[0].1.is_already_exists(), "{}", errors[0].1);
+
+    let routes = conn.get_routes().await?;
+    let added = routes
+        .iter()
+        .filter(|r| r.dst_len() == 24 && r.destination_str().starts_with("10."))
+        .count();
+    assert_eq!(added, 500);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_add_route_via_gateway() -> Result<()> {
     require_root!();
@@ -241,6 +278,79 @@ async fn test_ipv6_route() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_flush_router_advertised_routes() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let ns = TestNamespace::new("rtra")?;
+    let conn = ns.connection()?;
+    conn.add_link(DummyLink::new("dummy0")).await?;
+    conn.set_link_up("dummy0").await?;
+
+    conn.add_route(
+        Ipv6Route::new("2001:db8:1::", 48)
+            .dev("dummy0")
+            .protocol(RouteProtocol::Ra),
+    )
+    .await?;
+    conn.add_route(Ipv6Route::new("2001:db8:2::", 48).dev("dummy0"))
+        .await?;
+
+    let ra = RouteSelector::new()
+        .family(nlink::AddressFamily::v6())
+        .router_advertised(true);
+    assert_eq!(conn.count_routes(ra.clone()).await?, 1);
+    assert_eq!(conn.flush_routes_matching(ra).await?, 1);
+
+    let routes = conn.get_routes().await?;
+    let learned = IpAddr::V6("2001:db8:1::".parse::<Ipv6Addr>().unwrap());
+    let configured = IpAddr::V6("2001:db8:2::".parse::<Ipv6Addr>().unwrap());
+    assert!(!routes.iter().any(|r| r.destination() == Some(&learned)));
+    assert!(routes.iter().any(|r| r.destination() == Some(&configured)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_strict_dump_filters() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let (_ns, conn) = setup_routed_ns("rtstrict").await?;
+    conn.add_link(DummyLink::new("dummy1")).await?;
+    conn.set_link_up("dummy1").await?;
+    conn.add_address(Ipv4Address::new(
+        "dummy1",
+        Ipv4Addr::new(192, 168, 2, 1),
+        24,
+    ))
+    .await?;
+    conn.add_route(Ipv4Route::new("10.1.0.0", 16).dev("dummy0").table(100))
+        .await?;
+    conn.add_route(Ipv4Route::new("10.2.0.0", 16).dev("dummy1").table(100))
+        .await?;
+    conn.enable_strict_checking(true)?;
+
+    let dummy0 = conn.get_link_by_name("dummy0").await?.unwrap().ifindex();
+    let v4 = nlink::AddressFamily::v4();
+    let table = RouteSelector::new().family(v4).table(100);
+    assert_eq!(conn.count_routes(table.clone()).await?, 2);
+    assert_eq!(conn.count_routes(table.oif(dummy0)).await?, 1);
+    assert_eq!(conn.get_routes_for_table(100).await?.len(), 2);
+    // A table that was never created is empty, not an error.
+    let missing = RouteSelector::new().family(v4).table(4242);
+    assert_eq!(conn.count_routes(missing).await?, 0);
+
+    let addrs = conn.get_addresses_by_index(dummy0).await?;
+    assert!(!addrs.is_empty());
+    assert!(addrs.iter().all(|a| a.ifindex() == dummy0));
+    let neighbors = conn.get_neighbors_by_index(dummy0).await?;
+    assert!(neighbors.iter().all(|n| n.ifindex() == dummy0));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_route_with_source() -> Result<()> {
     require_root!();
@@ -464,3 +574,213 @@ async fn test_host_route() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_sync_protocol_routes() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let (_ns, conn) = setup_routed_ns("rtsync").await?;
+    const PROTO: u8 = 99;
+    let gw = Ipv4Addr::new(192, 168, 1, 254);
+
+    // A route owned by someone else must survive every sync.
+    conn.add_route(Ipv4Route::new("10.9.0.0", 16).gateway(gw))
+        .await?;
+
+    let result = conn
+        .sync_protocol_routes(
+            PROTO,
+            vec![
+                Ipv4Route::new("10.1.0.0", 16).gateway(gw),
+                Ipv4Route::new("10.2.0.0", 16).gateway(gw),
+            ],
+        )
+        .await?;
+    assert_eq!((result.installed, result.removed), (2, 0));
+
+    // Second generation: keep 10.2/16, drop 10.1/16, add 10.3/16.
+    let result = conn
+        .sync_protocol_routes(
+            PROTO,
+            vec![
+                Ipv4Route::new("10.2.0.0", 16).gateway(gw),
+                Ipv4Route::new("10.3.0.0", 16).gateway(gw),
+            ],
+        )
+        .await?;
+    assert_eq!((result.installed, result.removed), (2, 1));
+
+    let mut owned: Vec<_> = conn
+        .get_routes()
+        .await?
+        .iter()
+        .filter(|r| r.protocol_id() == PROTO)
+        .filter_map(|r| r.destination().copied())
+        .collect();
+    owned.sort();
+    assert_eq!(
+        owned,
+        vec![
+            IpAddr::V4(Ipv4Addr::new(10, 2, 0, 0)),
+            IpAddr::V4(Ipv4Addr::new(10, 3, 0, 0)),
+        ]
+    );
+
+    assert_eq!(conn.flush_routes_by_protocol(PROTO).await?, 2);
+    let routes = conn.get_routes().await?;
+    assert!(!routes.iter().any(|r| r.protocol_id() == PROTO));
+    let other = IpAddr::V4(Ipv4Addr::new(10, 9, 0, 0));
+    assert!(routes.iter().any(|r| r.destination() == Some(&other)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_replace_route_verified() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let (_ns, conn) = setup_routed_ns("rtverify").await?;
+
+    conn.add_route(
+        Ipv4Route::new("10.0.0.0", 8)
+            .gateway(Ipv4Addr::new(192, 168, 1, 100))
+            .dev("dummy0"),
+    )
+    .await?;
+
+    let installed = conn
+        .replace_route_verified(Ipv4Route::new("10.0.0.0", 8).multipath(vec![
+                NextHop::new().gateway_v4(Ipv4Addr::new(192, 168, 1, 101)),
+                NextHop::new()
+                    .gateway_v4(Ipv4Addr::new(192, 168, 1, 102))
+                    .weight(3),
+            ]))
+        .await?;
+    let gateways: Vec<_> = installed
+        .multipath()
+        .unwrap()
+        .iter()
+        .map(|nh| (nh.gateway, nh.weight))
+        .collect();
+    assert_eq!(
+        gateways,
+        vec![
+            (Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 101))), 1),
+            (Some(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 102))), 3),
+        ]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_migrate_nexthop_group() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let (_ns, conn) = setup_routed_ns("rtnhmig").await?;
+    let nh = |id, last| {
+        NexthopBuilder::new(id)
+            .gateway(IpAddr::V4(Ipv4Addr::new(192, 168, 1, last)))
+            .dev("dummy0")
+    };
+
+    for (id, last) in [(1, 11), (2, 12)] {
+        if let Err(e) = conn.add_nexthop(nh(id, last)).await {
+            if e.is_not_supported() {
+                eprintln!("skipping: nexthop objects unsupported");
+                return Ok(());
+            }
+            return Err(e);
+        }
+    }
+    conn.add_nexthop_group(NexthopGroupBuilder::new(100).member(1, 1).member(2, 1))
+        .await?;
+    conn.add_route(Ipv4Route::new("10.0.0.0", 8).nexthop_group(100))
+        .await?;
+
+    let retired = conn
+        .migrate_nexthop_group(
+            NexthopGroupBuilder::new(100).member(2, 1).member(3, 1),
+            [nh(3, 13)],
+        )
+        .await?;
+    assert_eq!(retired, [1]);
+    assert!(conn.get_nexthop(1).await?.is_none());
+
+    let route = conn
+        .get_routes()
+        .await?
+        .into_iter()
+        .find(|r| r.dst_len() == 8 && r.nexthop_id().is_some())
+        .expect("route still uses the group");
+    assert_eq!(route.nexthop_id(), Some(100));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resilient_nexthop_group_buckets() -> Result<()> {
+    require_root!();
+    nlink::require_module!("dummy");
+
+    let (_ns, conn) = setup_routed_ns("rtnhres").await?;
+    for (id, last) in [(1, 11), (2, 12)] {
+        let nh = NexthopBuilder::new(id)
+            .gateway(IpAddr::V4(Ipv4Addr::new(192, 168, 1, last)))
+            .dev("dummy0");
+        if let Err(e) = conn.add_nexthop(nh).await {
+            if e.is_not_supported() {
+                eprintln!("skipping: nexthop objects unsupported");
+                return Ok(());
+            }
+            return Err(e);
+        }
+    }
+    let group = NexthopGroupBuilder::new(100)
+        .resilient()
+        .member(1, 1)
+        .member(2, 3)
+        .buckets(8)
+        .idle_timer(120);
+    if let Err(e) = conn.add_nexthop_group(group).await {
+        if e.is_invalid_argument() {
+            eprintln!("skipping: resilient nexthop groups unsupported");
+            return Ok(());
+        }
+        return Err(e);
+    }
+
+    let nh = conn.get_nexthop(100).await?.expect("group exists");
+    let weights: Vec<_> = nh.group().unwrap().iter().map(|m| m.weight()).collect();
+    assert_eq!(weights, [1, 3]);
+    let res = nh.resilient().expect("resilient parameters");
+    assert_eq!(res.buckets, 8);
+    assert_eq!(res.idle_timer, 120);
+
+    let buckets = conn.get_nexthop_buckets(Some(100)).await?;
+    assert_eq!(buckets.len(), 8);
+    assert!(buckets.iter().all(|b| b.group_id() == 100));
+    // Buckets are split by weight, 1:3.
+    let on_two = buckets.iter().filter(|b| b.nexthop_id() == 2).count();
+    assert_eq!(on_two, 6);
+
+    let bucket = conn.get_nexthop_bucket(100, 3).await?.expect("bucket 3");
+    assert_eq!(bucket.index(), 3);
+
+    // Replace keeps the group ID and rewrites the members.
+    conn.replace_nexthop_group(
+        NexthopGroupBuilder::new(100)
+            .resilient()
+            .member(1, 1)
+            .member(2, 1),
+    )
+    .await?;
+    let nh = conn.get_nexthop(100).await?.expect("group exists");
+    let weights: Vec<_> = nh.group().unwrap().iter().map(|m| m.weight()).collect();
+    assert_eq!(weights, [1, 1]);
+
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Live-kernel checks for state-targeted socket destruction.
+//!
+//! Listing candidates is unprivileged; destroying needs `CAP_NET_ADMIN`
+//! and `CONFIG_INET_DIAG_DESTROY`, and is skipped without them.
+
+use std::{
+    net::{TcpListener, TcpStream},
+    time::Duration,
+};
+
+use nlink::netlink::{Connection, SockDiag};
+use nlink::sockdiag::{DestroyTarget, InetFilter, SocketState, TcpState};
+
+/// Closing the client first leaves it in TIME-WAIT; the TIME-WAIT target
+/// finds exactly that socket and nothing else on the port.
+#[tokio::test]
+async fn time_wait_target_lists_and_destroys_only_time_wait() -> nlink::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let port = listener.local_addr().unwrap().port();
+    let client = TcpStream::connect(("127.0.0.1", port)).expect("connect");
+    let (server, _) = listener.accept().expect("accept");
+    drop(client);
+    drop(server);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let conn = Connection::<SockDiag>::new()?;
+    let filter = InetFilter {
+        remote_port: Some(port),
+        ..Default::default()
+    };
+
+    let candidates = conn
+        .destroy_candidates(&filter, DestroyTarget::TimeWait)
+        .await?;
+    assert_eq!(candidates.len(), 1, "{candidates:?}");
+    assert_eq!(candidates[0].state, SocketState::Tcp(TcpState::TimeWait));
+
+    // Nothing on the port is orphaned: both ends finished closing.
+    let orphans = conn
+        .destroy_candidates(&filter, DestroyTarget::Orphaned)
+        .await?;
+    assert!(orphans.is_empty(), "{orphans:?}");
+
+    nlink::require_root!();
+    let result = conn.destroy_sockets(&candidates).await;
+    if let Some(err) = result.errors.first() {
+        if err.error.is_not_supported() {
+            eprintln!("skipping: kernel built without CONFIG_INET_DIAG_DESTROY");
+            return Ok(());
+        }
+        panic!("destroy failed: {}", err.error);
+    }
+    assert_eq!(result.destroyed, 1);
+
+    let left = conn
+        .destroy_candidates(&filter, DestroyTarget::TimeWait)
+        .await?;
+    assert!(left.is_empty(), "{left:?}");
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Live-kernel check for netlink socket group, flag and owner reporting.
+//! Not root-gated: joining rtnetlink groups needs no privileges.
+
+use std::os::unix::{fs::MetadataExt, io::AsRawFd};
+
+use nlink::{
+    netlink::{Connection, Route, RtnetlinkGroup, SockDiag},
+    sockdiag::{NetlinkPortMap, SocketOwnerMap},
+};
+
+#[tokio::test]
+async fn netlink_listener_reports_groups_and_owner() -> nlink::Result<()> {
+    let listener = Connection::<Route>::new()?;
+    // RTNLGRP_LINK is group 1, RTNLGRP_NEXTHOP group 32.
+    listener.subscribe(&[RtnetlinkGroup::Link, RtnetlinkGroup::Nexthop])?;
+    let fd = listener.socket().as_raw_fd();
+    let inode = std::fs::metadata(format!("/proc/self/fd/{fd}"))
+        .expect("stat socket fd")
+        .ino() as u32;
+
+    let conn = Connection::<SockDiag>::new()?;
+    let sockets = conn.query_netlink_sockets().await?;
+    // Bound and subscribed: the kernel lists it twice, nlink once.
+    let mut matches = sockets.iter().filter(|s| s.inode == inode);
+    let sock = matches.next().expect("listener dumped");
+    assert!(matches.next().is_none());
+
+    assert_eq!(sock.protocol, libc::NETLINK_ROUTE as u8);
+    assert_eq!(sock.portid, listener.socket().pid());
+    assert_eq!(sock.memberships, [1, 32]);
+    assert_eq!(sock.groups, 1 | 1 << 31);
+    assert!(sock.flags.is_some());
+    assert_eq!(sock.recv_q, Some(0));
+    assert!(sock.rcvbuf_usage().is_some());
+
+    let ports = NetlinkPortMap::new(&sockets, &SocketOwnerMap::scan());
+    let owners = ports.resolve(sock.protocol, sock.portid);
+    assert!(owners.iter().any(|p| p.pid == std::process::id() as i32));
+
+    Ok(())
+}
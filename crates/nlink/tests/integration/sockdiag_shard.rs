@@ -0,0 +1,31 @@
+//! Live-kernel check for sharded inet dumps. Not root-gated: any user
+//! can dump their own sockets.
+
+use std::net::TcpListener;
+
+use nlink::sockdiag::{InetFilter, TcpState, query_inet_sharded};
+
+/// Every listener turns up exactly once, however the port space is cut.
+#[tokio::test(flavor = "multi_thread")]
+async fn sharded_dump_returns_each_socket_once() -> nlink::Result<()> {
+    let listeners: Vec<_> = (0..8)
+        .map(|_| TcpListener::bind("127.0.0.1:0").expect("bind"))
+        .collect();
+    let ports: Vec<u16> = listeners
+        .iter()
+        .map(|l| l.local_addr().unwrap().port())
+        .collect();
+    let filter = InetFilter {
+        states: TcpState::Listen.mask(),
+        ..Default::default()
+    };
+
+    for shards in [1, 3, 16] {
+        let sockets = query_inet_sharded(&filter, shards).await?;
+        for port in &ports {
+            let n = sockets.iter().filter(|s| s.local.port() == *port).count();
+            assert_eq!(n, 1, "port {port} seen {n} times with {shards} shards");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Live-kernel check for unix socket peer pairing and VFS info. Not
+//! root-gated: any user can dump their own sockets.
+
+use std::os::unix::{
+    fs::MetadataExt,
+    io::AsRawFd,
+    net::{UnixListener, UnixStream},
+};
+
+use nlink::{
+    netlink::{Connection, SockDiag},
+    sockdiag::UnixPeers,
+};
+
+/// The inode of the socket behind `fd`.
+fn socket_inode(fd: &impl AsRawFd) -> u32 {
+    std::fs::metadata(format!("/proc/self/fd/{}", fd.as_raw_fd()))
+        .expect("stat socket fd")
+        .ino() as u32
+}
+
+#[tokio::test]
+async fn connected_unix_sockets_pair_up() -> nlink::Result<()> {
+    let path = std::env::temp_dir().join(format!("nlink-unix-peer-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let listener = UnixListener::bind(&path).expect("bind");
+    let client = UnixStream::connect(&path).expect("connect");
+    let (server, _) = listener.accept().expect("accept");
+
+    let conn = Connection::<SockDiag>::new()?;
+    let sockets = conn.query_unix_sockets().await?;
+    let peers = UnixPeers::new(&sockets);
+
+    let client_ino = socket_inode(&client);
+    let server_ino = socket_inode(&server);
+    let client_sock = peers.get(client_ino).expect("client socket dumped");
+    let server_sock = peers.get(server_ino).expect("server socket dumped");
+
+    assert_eq!(client_sock.peer_inode, Some(server_ino));
+    assert_eq!(
+        peers.peer(client_sock).map(|p| p.name()),
+        Some(path.display().to_string())
+    );
+    assert_eq!(peers.peer(server_sock).map(|p| p.inode), Some(client_ino));
+
+    // The bound listener reports the filesystem node it is bound to.
+    let listener_sock = peers.get(socket_inode(&listener)).expect("listener dumped");
+    let node = std::fs::metadata(&path).expect("stat socket path");
+    assert_eq!(
+        listener_sock.vfs().map(|(_, ino)| ino),
+        Some(node.ino() as u32)
+    );
+
+    std::fs::remove_file(&path).ok();
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Live-kernel checks for per-namespace socket usage.
+//!
+//! Not root-gated: the default namespace needs no privilege, and the
+//! missing-namespace case fails before any `setns`.
+
+use std::{
+    io::Write,
+    net::{TcpListener, TcpStream},
+};
+
+use nlink::netlink::{Connection, SockDiag, namespace::NamespaceSpec};
+use nlink::sockdiag::socket_usage_in;
+
+/// Unread data on an established socket shows up as receive memory.
+#[tokio::test]
+async fn socket_usage_counts_buffered_tcp_memory() -> nlink::Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let mut client = TcpStream::connect(listener.local_addr().unwrap()).expect("connect");
+    let (_server, _) = listener.accept().expect("accept");
+    client.write_all(&[0u8; 4096]).expect("write");
+
+    let usage = Connection::<SockDiag>::new()?.socket_usage().await?;
+    assert!(usage.summary.tcp.listen >= 1);
+    assert!(usage.summary.tcp.established >= 2);
+    assert!(
+        usage.tcp_memory.rmem_alloc >= 4096,
+        "4 KiB sits unread in the server's receive queue, got {:?}",
+        usage.tcp_memory
+    );
+    assert!(usage.tcp_memory.total() >= usage.tcp_memory.rmem_alloc);
+    Ok(())
+}
+
+/// A namespace that doesn't exist fails its own slot only.
+#[tokio::test]
+async fn socket_usage_in_reports_each_namespace_separately() {
+    let namespaces = [
+        NamespaceSpec::Named("nlink-test-no-such-namespace"),
+        NamespaceSpec::Default,
+    ];
+    let results = socket_usage_in(&namespaces).await;
+
+    assert_eq!(results.len(), 2);
+    assert!(matches!(results[0].0, NamespaceSpec::Named(_)));
+    assert!(results[0].1.is_err());
+    assert!(matches!(results[1].0, NamespaceSpec::Default));
+    assert!(results[1].1.is_ok());
+}
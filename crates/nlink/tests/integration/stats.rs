@@ -0,0 +1,33 @@
+//! Integration tests for the stats poller.
+
+use std::time::Duration;
+
+use nlink::netlink::stats::Poller;
+
+use crate::common::TestNamespace;
+
+#[tokio::test]
+async fn test_poller_samples_selected_links() -> nlink::Result<()> {
+    nlink::require_root!();
+    let ns = TestNamespace::new("stats_poller")?;
+    ns.link_up("lo")?;
+
+    let conn = ns.connection()?;
+    let interval = Duration::from_millis(200);
+    let mut poller = Poller::new(&conn, interval).links(["lo"]).qdiscs(true);
+
+    for _ in 0..3 {
+        let sample = poller.next().await?;
+
+        // Only the selected link, and rates over roughly one interval.
+        assert_eq!(sample.rates.links.len(), 1);
+        assert_eq!(
+            sample.rates.links.values().next().unwrap().name.as_deref(),
+            Some("lo")
+        );
+        assert_eq!(sample.snapshot.links.len(), 1);
+        assert!(sample.elapsed >= interval / 2, "{:?}", sample.elapsed);
+        assert!(sample.elapsed < interval * 3, "{:?}", sample.elapsed);
+    }
+    Ok(())
+}
@@ -0,0 +1,74 @@
+//! Transaction integration tests: a failed step, or an explicit
+//! rollback, removes everything the transaction created.
+
+use std::net::Ipv4Addr;
+
+use nlink::{
+    Result,
+    netlink::{addr::Ipv4Address, link::VethLink, route::Ipv4Route},
+};
+
+use crate::common::TestNamespace;
+
+#[tokio::test]
+async fn test_failed_step_rolls_back_earlier_steps() -> Result<()> {
+    require_root!();
+    nlink::require_module!("veth");
+
+    let ns = TestNamespace::new("tx-fail")?;
+    let conn = ns.connection()?;
+
+    let mut tx = conn.transaction();
+    tx.add_link(VethLink::new("veth0", "veth1")).await?;
+    tx.add_address(Ipv4Address::new("veth0", Ipv4Addr::new(10, 0, 0, 1), 24))
+        .await?;
+    assert_eq!(tx.len(), 2);
+
+    // The gateway isn't on a connected network.
+    let err = tx
+        .add_route(Ipv4Route::new("10.1.0.0", 16).gateway(Ipv4Addr::new(192, 0, 2, 1)))
+        .await
+        .unwrap_err();
+    assert!(err.errno().is_some(), "{err}");
+    assert!(tx.is_empty());
+    tx.commit();
+
+    assert!(conn.get_link_by_name("veth0").await?.is_none());
+    assert!(conn.get_link_by_name("veth1").await?.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rollback_and_commit() -> Result<()> {
+    require_root!();
+    nlink::require_module!("veth");
+
+    let ns = TestNamespace::new("tx-rollback")?;
+    let conn = ns.connection()?;
+    conn.add_link(VethLink::new("veth0", "veth1")).await?;
+    conn.set_link_up("veth0").await?;
+
+    let mut tx = conn.transaction();
+    tx.add_address(Ipv4Address::new("veth0", Ipv4Addr::new(10, 0, 0, 1), 24))
+        .await?;
+    tx.add_route(Ipv4Route::new("10.1.0.0", 16).dev("veth0"))
+        .await?;
+    tx.rollback().await?;
+
+    let has_route = |routes: &[nlink::RouteMessage]| {
+        routes
+            .iter()
+            .any(|r| r.destination_str().starts_with("10.1.0.0"))
+    };
+    assert!(conn.get_addresses_by_name("veth0").await?.is_empty());
+    assert!(!has_route(&conn.get_routes().await?));
+    // The link predates the transaction.
+    assert!(conn.get_link_by_name("veth0").await?.is_some());
+
+    let mut tx = conn.transaction();
+    tx.add_route(Ipv4Route::new("10.1.0.0", 16).dev("veth0"))
+        .await?;
+    tx.commit();
+    assert!(has_route(&conn.get_routes().await?));
+    Ok(())
+}